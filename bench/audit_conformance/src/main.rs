@@ -142,6 +142,12 @@ fn fixed_record(
         quarantined: false,
         quarantine_reason: None,
         decay_function: None,
+        pinned: false,
+        valid_from: None,
+        valid_until: None,
+        curation: None,
+        attestation: None,
+        channel: None,
     }
 }
 
@@ -318,6 +324,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             agent_id: None,
             strategy: Some(ForgetStrategy::SoftDelete),
             criteria: None,
+            force: None,
+            dry_run: None,
         })
         .await?;
     let events_after = event_count(&engine).await;
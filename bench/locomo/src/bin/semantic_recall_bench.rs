@@ -352,6 +352,10 @@ async fn seed(engine: &MnemoEngine, dataset: &[LongMemRecord]) {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         };
         engine.remember(req).await.expect("seed remember failed");
     }
@@ -379,6 +383,7 @@ fn build_recall(
         temporal_range: None,
         recency_half_life_hours: None,
         hybrid_weights: weights,
+        hybrid: None,
         rrf_k,
         as_of: None,
         explain: None,
@@ -389,6 +394,22 @@ fn build_recall(
         evidence_budget: None,
         retained_token_budget: None,
         domain_scope: None,
+        text_query_mode: None,
+        text_field_boosts: None,
+        with_snippets: None,
+        profile: None,
+        include: None,
+        thread_id: None,
+        thread_isolation: None,
+        exclude_query: None,
+        exclude_tags: None,
+        queries: None,
+        expand_query: None,
+        channel: None,
+        fields: None,
+        deadline_ms: None,
+        agent_ids: None,
+        min_relevance: None,
     }
 }
 
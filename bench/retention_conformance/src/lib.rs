@@ -206,6 +206,8 @@ pub async fn run_report(cfg: &Config) -> Result<RetentionReport, BoxErr> {
                         agent_id: Some(AGENT.to_string()),
                         strategy: Some(ForgetStrategy::SoftDelete),
                         criteria: None,
+                        force: None,
+                        dry_run: None,
                     })
                     .await?;
                 Ok(engine)
@@ -226,6 +228,8 @@ pub async fn run_report(cfg: &Config) -> Result<RetentionReport, BoxErr> {
                         agent_id: Some(AGENT.to_string()),
                         strategy: Some(ForgetStrategy::HardDelete),
                         criteria: None,
+                        force: None,
+                        dry_run: None,
                     })
                     .await?;
                 Ok(engine)
@@ -246,6 +250,8 @@ pub async fn run_report(cfg: &Config) -> Result<RetentionReport, BoxErr> {
                         agent_id: Some(AGENT.to_string()),
                         strategy: Some(ForgetStrategy::Redact),
                         criteria: None,
+                        force: None,
+                        dry_run: None,
                     })
                     .await?;
                 Ok(engine)
@@ -266,6 +272,8 @@ pub async fn run_report(cfg: &Config) -> Result<RetentionReport, BoxErr> {
                         agent_id: Some(AGENT.to_string()),
                         strategy: Some(ForgetStrategy::Archive),
                         criteria: None,
+                        force: None,
+                        dry_run: None,
                     })
                     .await?;
                 Ok(engine)
@@ -7,8 +7,20 @@ use axum::response::{Html, IntoResponse, Response};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use mnemo_core::cache::CacheStats;
 use mnemo_core::error::Error as CoreError;
+use mnemo_core::model::conflict::{ConflictRecord, ConflictStatus};
+use mnemo_core::model::content_stats::ContentStorageStats;
 use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::clustering::TopicMap;
+use mnemo_core::query::conflict::ResolutionStrategy;
+use mnemo_core::query::conflict_queue::{
+    ConflictDecision, ConflictListRequest, ResolveQueuedConflictRequest,
+};
+use mnemo_core::query::drift::{DriftReport, DriftRequest};
+use mnemo_core::query::growth::{GrowthReport, GrowthRequest};
+use mnemo_core::query::transfer::{TransferRequest, TransferResponse};
+use mnemo_core::query::vacuum::{VacuumRequest, VacuumResponse};
 use mnemo_core::storage::MemoryFilter;
 
 type AppState = Arc<MnemoEngine>;
@@ -23,8 +35,15 @@ impl IntoResponse for AdminError {
     fn into_response(self) -> Response {
         let (status, msg) = match &self.0 {
             CoreError::Validation(m) => (StatusCode::BAD_REQUEST, m.clone()),
+            e @ CoreError::InvalidFields(_) => (StatusCode::BAD_REQUEST, e.to_string()),
             CoreError::PermissionDenied(m) => (StatusCode::FORBIDDEN, m.clone()),
             CoreError::NotFound(m) => (StatusCode::NOT_FOUND, m.clone()),
+            CoreError::ReadOnly(m) => (StatusCode::SERVICE_UNAVAILABLE, m.clone()),
+            CoreError::Conflict(m) => (StatusCode::CONFLICT, m.clone()),
+            CoreError::QuotaExceeded(m) => (StatusCode::TOO_MANY_REQUESTS, m.clone()),
+            e @ CoreError::RateLimited { .. } => (StatusCode::TOO_MANY_REQUESTS, e.to_string()),
+            CoreError::Unavailable(m) => (StatusCode::SERVICE_UNAVAILABLE, m.clone()),
+            CoreError::Timeout(m) => (StatusCode::GATEWAY_TIMEOUT, m.clone()),
             other => (StatusCode::INTERNAL_SERVER_ERROR, other.to_string()),
         };
         (status, Json(serde_json::json!({"error": msg}))).into_response()
@@ -46,6 +65,9 @@ pub struct StatsResponse {
     pub memory_count: usize,
     pub event_count: usize,
     pub agent_ids: Vec<String>,
+    /// Hit, miss and eviction counters of the engine's memory cache; absent
+    /// when the cache is disabled.
+    pub cache: Option<CacheStats>,
 }
 
 #[derive(Debug, Serialize)]
@@ -58,6 +80,7 @@ pub struct MemorySummary {
     pub importance: f32,
     pub quarantined: bool,
     pub quarantine_reason: Option<String>,
+    pub pinned: bool,
     pub tags: Vec<String>,
     pub created_at: String,
     pub updated_at: String,
@@ -98,10 +121,29 @@ pub struct QuarantineResponse {
     pub message: String,
 }
 
+#[derive(Debug, Serialize)]
+pub struct RevealedPseudonym {
+    pub alias: String,
+    pub agent_id: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConflictList {
+    pub conflicts: Vec<ConflictRecord>,
+    pub limit: usize,
+    pub offset: usize,
+}
+
 // ---------------------------------------------------------------------------
 // Query parameters
 // ---------------------------------------------------------------------------
 
+/// Body and response of the read-only endpoints.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadOnlyState {
+    pub read_only: bool,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct MemoryQueryParams {
     pub limit: Option<usize>,
@@ -115,6 +157,54 @@ pub struct EventQueryParams {
     pub offset: Option<usize>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct TopicQueryParams {
+    pub agent_id: String,
+    /// Number of clusters; defaults to 8.
+    pub k: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StorageQueryParams {
+    /// Report only this agent; omitted reports every agent.
+    pub agent_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConflictQueryParams {
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+    pub agent_id: Option<String>,
+    /// `open`, `resolved` or `dismissed`; omitted lists every status.
+    pub status: Option<String>,
+}
+
+/// Body of `POST /admin/api/pseudonyms/reveal`. Not `Debug`: it carries
+/// the pseudonym key.
+#[derive(Deserialize)]
+pub struct RevealPseudonymBody {
+    pub alias: String,
+    /// Org whose alias table holds `alias`; omitted for memories without
+    /// an org.
+    pub org_id: Option<String>,
+    /// The pseudonym key (64 hex chars).
+    pub key: String,
+}
+
+/// Body of `POST /admin/api/conflicts/:id/resolve`. Exactly one of
+/// `strategy`, `winner_id` or `dismiss` must be set.
+#[derive(Debug, Deserialize)]
+pub struct ResolveConflictBody {
+    /// An automatic strategy such as `keep_newest` or `evidence_weighted`.
+    pub strategy: Option<String>,
+    /// Keep this memory of the pair and soft-delete the other.
+    pub winner_id: Option<Uuid>,
+    /// Close the entry without touching either memory.
+    #[serde(default)]
+    pub dismiss: bool,
+    pub resolved_by: Option<String>,
+}
+
 // ---------------------------------------------------------------------------
 // Handlers
 // ---------------------------------------------------------------------------
@@ -154,6 +244,7 @@ pub async fn stats_handler(
         memory_count,
         event_count,
         agent_ids,
+        cache: engine.cache.as_ref().map(|cache| cache.stats()),
     }))
 }
 
@@ -221,6 +312,7 @@ pub async fn memories_handler(
                 importance: m.importance,
                 quarantined: m.quarantined,
                 quarantine_reason: m.quarantine_reason.clone(),
+                pinned: m.pinned,
                 tags: m.tags.clone(),
                 created_at: m.created_at.clone(),
                 updated_at: m.updated_at.clone(),
@@ -343,6 +435,152 @@ pub async fn unquarantine_handler(
     }))
 }
 
+/// GET /admin/api/topics -- cluster an agent's memories into a topic map.
+pub async fn topics_handler(
+    State(engine): State<AppState>,
+    Query(params): Query<TopicQueryParams>,
+) -> Result<Json<TopicMap>, AdminError> {
+    let k = params.k.unwrap_or(8);
+    Ok(Json(
+        engine.cluster_memories(Some(params.agent_id), k).await?,
+    ))
+}
+
+/// GET /admin/api/storage -- content size and compression savings per
+/// agent.
+pub async fn storage_handler(
+    State(engine): State<AppState>,
+    Query(params): Query<StorageQueryParams>,
+) -> Result<Json<Vec<ContentStorageStats>>, AdminError> {
+    Ok(Json(engine.content_storage_stats(params.agent_id).await?))
+}
+
+/// GET /admin/api/growth -- memories and events stored per day, with a
+/// linear forecast. Takes `days`, `memory_quota` and `disk_quota_bytes` as
+/// query parameters.
+pub async fn growth_handler(
+    State(engine): State<AppState>,
+    Query(request): Query<GrowthRequest>,
+) -> Result<Json<GrowthReport>, AdminError> {
+    Ok(Json(engine.storage_growth(request).await?))
+}
+
+/// POST /admin/api/vacuum -- reclaim storage space now. Takes `force` and
+/// `quiet_seconds` as query parameters; a skipped pass is still a 200.
+pub async fn vacuum_handler(
+    State(engine): State<AppState>,
+    Query(request): Query<VacuumRequest>,
+) -> Result<Json<VacuumResponse>, AdminError> {
+    Ok(Json(engine.vacuum(request).await?))
+}
+
+/// POST /admin/api/drift -- compare an agent's memories with a corpus of
+/// ground-truth facts.
+pub async fn drift_handler(
+    State(engine): State<AppState>,
+    Json(body): Json<DriftRequest>,
+) -> Result<Json<DriftReport>, AdminError> {
+    Ok(Json(engine.detect_drift(body).await?))
+}
+
+/// POST /admin/api/transfer -- hand one agent's memories to another.
+/// With `dry_run` set, only lists the memories that would move.
+pub async fn transfer_handler(
+    State(engine): State<AppState>,
+    Json(body): Json<TransferRequest>,
+) -> Result<Json<TransferResponse>, AdminError> {
+    Ok(Json(engine.transfer_ownership(body).await?))
+}
+
+/// POST /admin/api/pseudonyms/reveal -- resolve an agent alias seen in a
+/// shared-scope read or export. Needs the pseudonym key.
+pub async fn reveal_pseudonym_handler(
+    State(engine): State<AppState>,
+    Json(body): Json<RevealPseudonymBody>,
+) -> Result<Json<RevealedPseudonym>, AdminError> {
+    let agent_id = engine
+        .reveal_pseudonym(body.org_id.as_deref(), &body.alias, &body.key)
+        .await?;
+    Ok(Json(RevealedPseudonym {
+        alias: body.alias,
+        agent_id,
+    }))
+}
+
+/// GET /admin/api/conflicts -- list queued conflicts, newest first.
+pub async fn conflicts_handler(
+    State(engine): State<AppState>,
+    Query(params): Query<ConflictQueryParams>,
+) -> Result<Json<ConflictList>, AdminError> {
+    let limit = params.limit.unwrap_or(50).min(500);
+    let offset = params.offset.unwrap_or(0);
+    let status = params
+        .status
+        .as_deref()
+        .map(str::parse::<ConflictStatus>)
+        .transpose()?;
+
+    let mut request = ConflictListRequest::new();
+    request.agent_id = params.agent_id;
+    request.status = status;
+    request.limit = Some(limit);
+    request.offset = Some(offset);
+    let conflicts = engine.list_conflicts(request).await?;
+
+    Ok(Json(ConflictList {
+        conflicts,
+        limit,
+        offset,
+    }))
+}
+
+/// POST /admin/api/conflicts/:id/resolve -- close a queued conflict.
+pub async fn resolve_conflict_handler(
+    State(engine): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<ResolveConflictBody>,
+) -> Result<Json<ConflictRecord>, AdminError> {
+    let decision = match (body.strategy, body.winner_id, body.dismiss) {
+        (Some(strategy), None, false) => {
+            let strategy: ResolutionStrategy =
+                serde_json::from_value(serde_json::Value::String(strategy.clone())).map_err(
+                    |_| CoreError::Validation(format!("unknown resolution strategy: {strategy}")),
+                )?;
+            ConflictDecision::Strategy(strategy)
+        }
+        (None, Some(winner), false) => ConflictDecision::Winner(winner),
+        (None, None, true) => ConflictDecision::Dismiss,
+        _ => {
+            return Err(CoreError::Validation(
+                "set exactly one of strategy, winner_id or dismiss".to_string(),
+            )
+            .into());
+        }
+    };
+
+    let mut request = ResolveQueuedConflictRequest::new(id, decision);
+    request.resolved_by = body.resolved_by;
+    Ok(Json(engine.resolve_queued_conflict(request).await?))
+}
+
+/// GET /admin/api/read-only -- whether writes are frozen.
+pub async fn read_only_handler(State(engine): State<AppState>) -> Json<ReadOnlyState> {
+    Json(ReadOnlyState {
+        read_only: engine.is_read_only(),
+    })
+}
+
+/// PUT /admin/api/read-only -- freeze or unfreeze writes.
+pub async fn set_read_only_handler(
+    State(engine): State<AppState>,
+    Json(body): Json<ReadOnlyState>,
+) -> Json<ReadOnlyState> {
+    engine.set_read_only(body.read_only);
+    Json(ReadOnlyState {
+        read_only: engine.is_read_only(),
+    })
+}
+
 /// GET /admin/api/health -- simple health check.
 pub async fn health_handler() -> Json<serde_json::Value> {
     Json(serde_json::json!({"status": "ok", "service": "mnemo-admin"}))
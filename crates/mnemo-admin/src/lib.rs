@@ -13,16 +13,27 @@ use mnemo_core::query::MnemoEngine;
 ///
 /// # Routes
 ///
-/// | Method | Path                            | Description                    |
-/// |--------|---------------------------------|--------------------------------|
-/// | GET    | `/admin/`                       | HTML dashboard                 |
-/// | GET    | `/admin/api/health`             | Health check                   |
-/// | GET    | `/admin/api/stats`              | Aggregate statistics           |
-/// | GET    | `/admin/api/agents`             | List known agent IDs           |
-/// | GET    | `/admin/api/memories`           | Paginated memory browser       |
-/// | GET    | `/admin/api/events`             | Paginated event timeline       |
-/// | POST   | `/admin/api/quarantine/:id`     | Quarantine a memory            |
-/// | POST   | `/admin/api/unquarantine/:id`   | Release memory from quarantine |
+/// | Method | Path                               | Description                    |
+/// |--------|------------------------------------|--------------------------------|
+/// | GET    | `/admin/`                          | HTML dashboard                 |
+/// | GET    | `/admin/api/health`                | Health check                   |
+/// | GET    | `/admin/api/stats`                 | Aggregate statistics           |
+/// | GET    | `/admin/api/agents`                | List known agent IDs           |
+/// | GET    | `/admin/api/memories`              | Paginated memory browser       |
+/// | GET    | `/admin/api/events`                | Paginated event timeline       |
+/// | GET    | `/admin/api/topics`                | Topic map of an agent          |
+/// | GET    | `/admin/api/storage`               | Content compression savings    |
+/// | GET    | `/admin/api/growth`                | Storage growth and forecast    |
+/// | POST   | `/admin/api/vacuum`                | Reclaim storage space          |
+/// | POST   | `/admin/api/drift`                 | Drift against a fact corpus    |
+/// | POST   | `/admin/api/transfer`              | Transfer memory ownership      |
+/// | POST   | `/admin/api/pseudonyms/reveal`     | Resolve an agent alias         |
+/// | POST   | `/admin/api/quarantine/:id`        | Quarantine a memory            |
+/// | POST   | `/admin/api/unquarantine/:id`      | Release memory from quarantine |
+/// | GET    | `/admin/api/conflicts`             | Conflict review queue          |
+/// | POST   | `/admin/api/conflicts/:id/resolve` | Resolve a queued conflict      |
+/// | GET    | `/admin/api/read-only`             | Whether writes are frozen      |
+/// | PUT    | `/admin/api/read-only`             | Freeze or unfreeze writes      |
 pub fn router(engine: Arc<MnemoEngine>) -> Router {
     Router::new()
         // Dashboard
@@ -33,6 +44,16 @@ pub fn router(engine: Arc<MnemoEngine>) -> Router {
         .route("/admin/api/agents", get(handlers::agents_handler))
         .route("/admin/api/memories", get(handlers::memories_handler))
         .route("/admin/api/events", get(handlers::events_handler))
+        .route("/admin/api/topics", get(handlers::topics_handler))
+        .route("/admin/api/storage", get(handlers::storage_handler))
+        .route("/admin/api/growth", get(handlers::growth_handler))
+        .route("/admin/api/vacuum", post(handlers::vacuum_handler))
+        .route("/admin/api/drift", post(handlers::drift_handler))
+        .route("/admin/api/transfer", post(handlers::transfer_handler))
+        .route(
+            "/admin/api/pseudonyms/reveal",
+            post(handlers::reveal_pseudonym_handler),
+        )
         .route(
             "/admin/api/quarantine/{id}",
             post(handlers::quarantine_handler),
@@ -41,6 +62,15 @@ pub fn router(engine: Arc<MnemoEngine>) -> Router {
             "/admin/api/unquarantine/{id}",
             post(handlers::unquarantine_handler),
         )
+        .route("/admin/api/conflicts", get(handlers::conflicts_handler))
+        .route(
+            "/admin/api/conflicts/{id}/resolve",
+            post(handlers::resolve_conflict_handler),
+        )
+        .route(
+            "/admin/api/read-only",
+            get(handlers::read_only_handler).put(handlers::set_read_only_handler),
+        )
         .layer(tower_http::cors::CorsLayer::permissive())
         .layer(tower_http::trace::TraceLayer::new_for_http())
         .with_state(engine)
@@ -68,6 +68,10 @@ async fn test_admin_stats_endpoint() {
             ttl_seconds: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -147,6 +151,10 @@ async fn test_admin_agents_endpoint() {
             ttl_seconds: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -168,6 +176,10 @@ async fn test_admin_agents_endpoint() {
             ttl_seconds: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
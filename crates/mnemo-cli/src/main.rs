@@ -1,27 +1,32 @@
 use std::io::IsTerminal;
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, Ordering};
 
 use clap::{Parser, Subcommand};
 use rmcp::{ServiceExt, transport::stdio};
-use tokio::sync::Notify;
 
 mod attest;
 mod commands;
 mod lease;
 mod manifest;
 mod safe_spawn;
+mod shutdown;
 
+use mnemo_core::activity::ActivityTracker;
 use mnemo_core::anomaly::outlier::train_baseline;
-use mnemo_core::embedding::openai::OpenAiEmbedding;
+use mnemo_core::cache::EvictionPolicy;
+use mnemo_core::config::{
+    CacheConfig, EmbeddingConfig, FeatureConfig, FullTextConfig, MnemoEngineConfig,
+    PseudonymizationConfig, ShareLinkConfig, StorageConfig,
+};
 use mnemo_core::embedding::{EmbeddingProvider, NoopEmbedding};
-use mnemo_core::encryption::ContentEncryption;
-use mnemo_core::index::VectorIndex;
 use mnemo_core::index::usearch::UsearchIndex;
 use mnemo_core::query::MnemoEngine;
-use mnemo_core::search::FullTextIndex;
-use mnemo_core::search::tantivy_index::TantivyFullTextIndex;
+use mnemo_core::query::access_log::{self, AccessLogPolicy};
+use mnemo_core::query::curation::CurationPolicy;
+use mnemo_core::query::entity_summary::EntitySummaryRequest;
+use mnemo_core::query::event_retention;
+use mnemo_core::query::write_anomaly::WriteAnomalyPolicy;
 use mnemo_core::storage::StorageBackend;
 use mnemo_core::storage::duckdb::DuckDbStorage;
 use mnemo_mcp::server::MnemoServer;
@@ -33,6 +38,11 @@ struct Cli {
     #[arg(long, default_value = "mnemo.db", env = "MNEMO_DB_PATH")]
     db_path: PathBuf,
 
+    /// Storage backend: `duckdb` (the `--db-path` file) or `memory`
+    /// (process-local, nothing persisted; needs the `memory-storage` feature)
+    #[arg(long, value_parser = ["duckdb", "memory"], default_value = "duckdb", env = "MNEMO_STORAGE")]
+    storage: String,
+
     /// OpenAI API key for embeddings
     #[arg(long, env = "OPENAI_API_KEY")]
     openai_api_key: Option<String>,
@@ -65,14 +75,76 @@ struct Cli {
     #[arg(long, env = "MNEMO_POSTGRES_URL")]
     postgres_url: Option<String>,
 
+    /// pgvector HNSW graph neighbours per node (PostgreSQL backend)
+    #[arg(long, default_value = "16", env = "MNEMO_PG_HNSW_M")]
+    pg_hnsw_m: u32,
+
+    /// pgvector HNSW build candidate list size (PostgreSQL backend)
+    #[arg(long, default_value = "64", env = "MNEMO_PG_HNSW_EF_CONSTRUCTION")]
+    pg_hnsw_ef_construction: u32,
+
+    /// pgvector `hnsw.ef_search` set for each recall (unset = server default)
+    #[arg(long, env = "MNEMO_PG_HNSW_EF_SEARCH")]
+    pg_hnsw_ef_search: Option<u32>,
+
+    /// Interval in seconds between vector index maintenance runs on the
+    /// PostgreSQL backend: `ANALYZE memories`, plus a concurrent HNSW rebuild
+    /// after the build options change (0 = disabled)
+    #[arg(
+        long,
+        default_value = "0",
+        env = "MNEMO_PG_VECTOR_MAINTENANCE_INTERVAL"
+    )]
+    pg_vector_maintenance_interval_seconds: u64,
+
+    /// Enforce per-agent row-level security policies on the PostgreSQL
+    /// backend, in addition to the engine's permission checks
+    #[arg(long, env = "MNEMO_PG_ROW_LEVEL_SECURITY")]
+    pg_row_level_security: bool,
+
+    /// Engine configuration file (TOML, see `MnemoEngineConfig`). When set it
+    /// replaces the engine flags (db path, embeddings, encryption, ...);
+    /// server flags still apply.
+    #[arg(long, env = "MNEMO_CONFIG")]
+    config: Option<PathBuf>,
+
     /// REST API port (starts an HTTP server alongside MCP stdio)
     #[arg(long, env = "MNEMO_REST_PORT")]
     rest_port: Option<u16>,
 
+    /// gRPC API port (starts a tonic server alongside MCP stdio)
+    #[cfg(feature = "grpc")]
+    #[arg(long, env = "MNEMO_GRPC_PORT")]
+    grpc_port: Option<u16>,
+
+    /// PostgreSQL wire-protocol port (binds localhost only; trust auth)
+    #[cfg(feature = "pgwire")]
+    #[arg(long, env = "MNEMO_PGWIRE_PORT")]
+    pgwire_port: Option<u16>,
+
+    /// Seconds to let in-flight requests drain on shutdown before aborting
+    /// them and flushing the indexes
+    #[arg(long, default_value = "30", env = "MNEMO_SHUTDOWN_DRAIN_SECONDS")]
+    shutdown_drain_seconds: u64,
+
     /// Idle timeout in seconds — auto-shutdown after no requests (0 = disabled)
     #[arg(long, default_value = "0", env = "MNEMO_IDLE_TIMEOUT")]
     idle_timeout_seconds: u64,
 
+    /// Deliver a notification for every new memory to this URL through the
+    /// transactional outbox (POST, JSON, `Idempotency-Key` header)
+    #[arg(long, env = "MNEMO_OUTBOX_WEBHOOK_URL")]
+    outbox_webhook_url: Option<String>,
+
+    /// Sign outbox webhook bodies with this HMAC-SHA256 key
+    /// (`X-Mnemo-Signature` header)
+    #[arg(long, env = "MNEMO_OUTBOX_WEBHOOK_SECRET")]
+    outbox_webhook_secret: Option<String>,
+
+    /// Interval in seconds between outbox dispatch passes
+    #[arg(long, default_value = "5", env = "MNEMO_OUTBOX_INTERVAL")]
+    outbox_interval_seconds: u64,
+
     /// AES-256-GCM encryption key (64-char hex string) for at-rest content encryption
     #[arg(long, env = "MNEMO_ENCRYPTION_KEY")]
     encryption_key: Option<String>,
@@ -83,6 +155,93 @@ struct Cli {
     #[arg(long, default_value = "0", env = "MNEMO_TTL_SWEEP_INTERVAL")]
     ttl_sweep_interval_seconds: u64,
 
+    /// Interval in seconds between passes that checkpoint threads idle past
+    /// their agent's `auto_checkpoint` inactivity timeout. Only runs with a
+    /// `--config` file, which can set `inactivity_seconds` policies.
+    #[arg(long, default_value = "30", env = "MNEMO_AUTO_CHECKPOINT_INTERVAL")]
+    auto_checkpoint_interval_seconds: u64,
+
+    /// Interval in seconds between passes that refresh the default agent's
+    /// entity summaries (0 = disabled)
+    #[arg(long, default_value = "0", env = "MNEMO_ENTITY_SUMMARY_INTERVAL")]
+    entity_summary_interval_seconds: u64,
+
+    /// Days a soft-deleted memory stays restorable before the maintenance
+    /// sweep hard-deletes it (unset = keep forever). Runs on the TTL sweep
+    /// cadence, so it needs `--ttl-sweep-interval-seconds` > 0.
+    #[arg(long, env = "MNEMO_TRASH_RETENTION_DAYS")]
+    trash_retention_days: Option<u32>,
+
+    /// Hold global-scope writes for curator review. Comma-separated agents
+    /// that may approve them besides the server's own agent
+    #[arg(long, env = "MNEMO_CURATORS", value_delimiter = ',')]
+    curators: Option<Vec<String>>,
+
+    /// Key (64-char hex string) for aliasing agent ids in shared-scope reads
+    /// and exports. Aliases can only be revealed with this key
+    #[arg(long, env = "MNEMO_PSEUDONYM_KEY")]
+    pseudonym_key: Option<String>,
+
+    /// Comma-separated orgs whose agents are aliased (default: every org)
+    #[arg(long, env = "MNEMO_PSEUDONYM_ORGS", value_delimiter = ',')]
+    pseudonym_orgs: Option<Vec<String>>,
+
+    /// Key (64-char hex string) signing share links, read-only capability
+    /// tokens for one memory. Unset disables share links
+    #[arg(long, env = "MNEMO_SHARE_LINK_KEY")]
+    share_link_key: Option<String>,
+
+    /// Start with writes frozen: remember, forget, share, checkpoint and
+    /// other writes fail with a read-only error while recall and replay work
+    #[arg(long, env = "MNEMO_READ_ONLY")]
+    read_only: bool,
+
+    /// Hours before a memory's `expires_at` at which the TTL sweep emits a
+    /// MemoryExpiring event so agents can renew it (unset = no warnings).
+    #[arg(long, env = "MNEMO_EXPIRY_WARNING_HOURS")]
+    expiry_warning_hours: Option<u32>,
+
+    /// Record who read each memory (principal, operation, time, query) and
+    /// keep the entries for this many days (unset = no access log). Pruned
+    /// on the TTL sweep cadence.
+    #[arg(long, env = "MNEMO_ACCESS_LOG_RETENTION_DAYS")]
+    access_log_retention_days: Option<u32>,
+
+    /// Cache up to this many memory records in process (0 = no cache).
+    /// Statistics are served at `/metrics` and the admin stats endpoint.
+    #[arg(long, default_value = "0", env = "MNEMO_CACHE_MAX_ENTRIES")]
+    cache_max_entries: usize,
+
+    /// Seconds a cached memory record stays fresh
+    #[arg(long, default_value = "300", env = "MNEMO_CACHE_TTL_SECONDS")]
+    cache_ttl_seconds: u64,
+
+    /// Evict by LRU-K with this K instead of plain LRU (unset = LRU)
+    #[arg(long, env = "MNEMO_CACHE_LRU_K")]
+    cache_lru_k: Option<usize>,
+
+    /// Interval in seconds between vacuum passes that compact the database
+    /// and reclaim space left by deletes and updates (0 = disabled). A pass
+    /// is skipped while writes are coming in.
+    #[arg(long, default_value = "0", env = "MNEMO_VACUUM_INTERVAL")]
+    vacuum_interval_seconds: u64,
+
+    /// Seconds without writes a vacuum pass waits for
+    #[arg(long, default_value = "30", env = "MNEMO_VACUUM_QUIET_SECONDS")]
+    vacuum_quiet_seconds: u64,
+
+    /// Interval in seconds between passes that compare each agent's recent
+    /// writes with its baseline and alert on bursts, importance inflation,
+    /// scope changes and off-hours activity (0 = disabled). Thresholds come
+    /// from `[features.write_anomaly]` in `--config`.
+    #[arg(long, default_value = "0", env = "MNEMO_WRITE_ANOMALY_INTERVAL")]
+    write_anomaly_interval_seconds: u64,
+
+    /// Refuse writes from an agent flagged by a write anomaly pass for an
+    /// hour
+    #[arg(long, env = "MNEMO_WRITE_ANOMALY_THROTTLE")]
+    write_anomaly_throttle: bool,
+
     #[command(subcommand)]
     command: Option<Command>,
 }
@@ -129,6 +288,123 @@ enum Command {
     /// error if it cannot.
     #[command(subcommand)]
     Compliance(ComplianceCommand),
+    /// Compare an agent's memories with a JSONL corpus of ground-truth
+    /// facts (`{id, content, updated_at, source}` per line) and report the
+    /// stale and contradicting ones.
+    ///
+    /// Uses the same engine settings as the server (`--config` or the
+    /// engine flags), so memories are matched with the embeddings they
+    /// were stored with.
+    AuditDrift(AuditDriftArgs),
+    /// Recompute content hashes and relink the `prev_hash` chains of an
+    /// agent's memories in creation order, e.g. after an import left them
+    /// unchained. Prints the rebuilt chains with their roots before and
+    /// after as JSON.
+    ///
+    /// Uses the same engine settings as the server (`--config` or the
+    /// engine flags).
+    Rechain(RechainArgs),
+    /// Write a deterministic synthetic dataset — agents, threads,
+    /// memories, events and checkpoints, optionally relations — for load
+    /// and correctness testing. The same `--seed` and sizes always produce
+    /// the same data. Prints a summary as JSON.
+    ///
+    /// Uses the same engine settings as the server (`--config` or the
+    /// engine flags).
+    Seed(SeedArgs),
+    /// Chunk, embed and remember documents: a file, every supported file
+    /// under a directory, or a URL. Markdown, HTML, PDF (with the `pdf`
+    /// feature), source code and plain text are recognised by extension.
+    /// Prints the written memory ids per document as JSON.
+    ///
+    /// Uses the same engine settings as the server (`--config` or the
+    /// engine flags).
+    Ingest(IngestArgs),
+}
+
+#[derive(clap::Args)]
+struct IngestArgs {
+    /// File, directory or http(s) URL to ingest.
+    source: String,
+    /// Agent that owns the memories. Defaults to the engine's agent.
+    #[arg(long)]
+    agent_id: Option<String>,
+    /// Tag added to every memory; repeat for several.
+    #[arg(long = "tag")]
+    tags: Vec<String>,
+    /// Loader to use instead of the one picked by extension.
+    #[arg(long)]
+    format: Option<String>,
+    /// Upper bound on the characters of one chunk.
+    #[arg(long)]
+    max_chunk_chars: Option<usize>,
+}
+
+#[derive(clap::Args)]
+struct SeedArgs {
+    /// Seed of the generator.
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+    #[arg(long, default_value_t = 10)]
+    agents: usize,
+    /// Memories to write; accepts a `k` or `m` suffix, e.g. `100k`.
+    #[arg(long, default_value = "10k", value_parser = parse_count)]
+    memories: usize,
+    /// Threads, spread over the agents.
+    #[arg(long, default_value_t = 50)]
+    threads: usize,
+    /// Also link memories with `related_to` and `derived_from` relations.
+    #[arg(long)]
+    with_relations: bool,
+    /// Days the timeline spans, ending at 2026-01-01.
+    #[arg(long, default_value_t = 90)]
+    days: u32,
+}
+
+fn parse_count(value: &str) -> Result<usize, String> {
+    let lower = value.trim().to_ascii_lowercase();
+    let (digits, scale) = match lower.as_bytes().last() {
+        Some(b'k') => (&lower[..lower.len() - 1], 1_000),
+        Some(b'm') => (&lower[..lower.len() - 1], 1_000_000),
+        _ => (lower.as_str(), 1),
+    };
+    digits
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_mul(scale))
+        .ok_or_else(|| format!("invalid count: {value}"))
+}
+
+#[derive(clap::Args)]
+struct RechainArgs {
+    /// Agent whose chains are rebuilt. Defaults to the engine's agent.
+    #[arg(long)]
+    agent_id: Option<String>,
+    /// Rebuild only this thread's chain.
+    #[arg(long)]
+    thread_id: Option<String>,
+}
+
+#[derive(clap::Args)]
+struct AuditDriftArgs {
+    /// Path to the JSONL corpus of current facts.
+    #[arg(long)]
+    corpus: PathBuf,
+    /// Agent whose memories are audited. Defaults to the engine's agent.
+    #[arg(long)]
+    agent_id: Option<String>,
+    /// Similarity at which a memory is taken to be about a fact.
+    #[arg(long)]
+    match_threshold: Option<f32>,
+    /// Similarity at which a matched memory says the same as its fact.
+    #[arg(long)]
+    consistent_threshold: Option<f32>,
+    /// Where to write the JSON report. Defaults to stdout.
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// Exit with an error when any memory drifted.
+    #[arg(long)]
+    fail_on_drift: bool,
 }
 
 #[derive(Subcommand)]
@@ -253,124 +529,119 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Some(Command::Eval(args)) => return run_eval(&cli, args).await,
         Some(Command::Bench(sub)) => return run_bench(sub).await,
         Some(Command::Compliance(sub)) => return run_compliance(sub).await,
+        Some(Command::AuditDrift(args)) => return run_audit_drift(&cli, args).await,
+        Some(Command::Rechain(args)) => return run_rechain(&cli, args).await,
+        Some(Command::Seed(args)) => return run_seed(&cli, args).await,
+        Some(Command::Ingest(args)) => return run_ingest(&cli, args).await,
         None => {}
     }
 
-    // Initialize embedding provider (ONNX > OpenAI > Noop)
-    let embedding: Arc<dyn EmbeddingProvider> = if let Some(ref onnx_path) = cli.onnx_model_path {
-        tracing::info!("Using ONNX local embeddings from {}", onnx_path);
-        Arc::new(mnemo_core::embedding::onnx::OnnxEmbedding::new(
-            onnx_path,
-            cli.dimensions,
-        )?)
-    } else if let Some(api_key) = cli.openai_api_key {
-        tracing::info!("Using OpenAI embeddings ({})", cli.embedding_model);
-        Arc::new(OpenAiEmbedding::new(
-            api_key,
-            cli.embedding_model,
-            cli.dimensions,
-        ))
-    } else {
-        tracing::warn!(
-            "No OPENAI_API_KEY set, using noop embeddings (semantic search will not work)"
-        );
-        Arc::new(NoopEmbedding::new(cli.dimensions))
-    };
+    let config = load_engine_config(&cli)?;
+
+    // Shared activity tracker for idle timeout. Attached to the engine so
+    // every front-end (MCP, REST, gRPC, pgwire) touches the same clock.
+    let activity_tracker = (cli.idle_timeout_seconds > 0).then(ActivityTracker::new);
 
     // Build engine based on backend selection
-    // Keep a reference to the DuckDB vector index for shutdown save
-    #[allow(unused_assignments)]
-    let mut duckdb_index: Option<Arc<UsearchIndex>> = None;
+    #[cfg(feature = "postgres")]
+    let mut pg_storage_handle: Option<Arc<mnemo_postgres::PgStorage>> = None;
     let engine = if let Some(_pg_url) = &cli.postgres_url {
         #[cfg(feature = "postgres")]
         {
-            let pg_storage =
-                Arc::new(mnemo_postgres::PgStorage::connect(_pg_url, cli.dimensions).await?);
+            let hnsw = mnemo_postgres::HnswConfig {
+                m: cli.pg_hnsw_m,
+                ef_construction: cli.pg_hnsw_ef_construction,
+                ef_search: cli.pg_hnsw_ef_search,
+            };
+            let mut pg_storage =
+                mnemo_postgres::PgStorage::connect_with_hnsw(_pg_url, config.dimensions, &hnsw)
+                    .await?;
+            if cli.pg_row_level_security {
+                pg_storage = pg_storage.with_row_level_security().await?;
+            }
+            let pg_storage = Arc::new(pg_storage);
             // Share the storage pool so pgvector ANN search (semantic / auto /
             // graph / domain_scoped recall) runs against the HNSW index (#99).
-            let pg_index = Arc::new(mnemo_postgres::PgVectorIndex::with_pool(
-                pg_storage.pool(),
-                cli.dimensions,
-            ));
+            let mut pg_index =
+                mnemo_postgres::PgVectorIndex::with_pool(pg_storage.pool(), config.dimensions);
+            if let Some(ef_search) = hnsw.ef_search {
+                pg_index = pg_index.with_ef_search(ef_search);
+            }
+            if cli.pg_row_level_security {
+                pg_index = pg_index.with_row_level_security();
+            }
+            pg_storage_handle = Some(pg_storage.clone());
+            let pg_index = Arc::new(pg_index);
             tracing::info!("Using PostgreSQL backend");
-            let mut eng = MnemoEngine::new(
+            let eng = MnemoEngine::new(
                 pg_storage,
                 pg_index,
-                embedding,
-                cli.agent_id.clone(),
-                cli.org_id.clone(),
+                config.embedding.build(config.dimensions)?,
+                config.agent_id.clone(),
+                config.org_id.clone(),
             );
-            if let Some(ref key_hex) = cli.encryption_key {
-                let enc = ContentEncryption::from_hex(key_hex)?;
-                eng = eng.with_encryption(Arc::new(enc));
-                tracing::info!("At-rest encryption enabled");
-            }
-            if experience_memory_enabled() {
-                eng = eng.with_experience_memory();
-                tracing::info!("Experience-memory tier (DocTrace) enabled");
-            }
-            Arc::new(eng)
+            config.apply(eng).await?
         }
         #[cfg(not(feature = "postgres"))]
         {
             return Err("PostgreSQL support not enabled. Rebuild with --features postgres".into());
         }
     } else {
-        // DuckDB backend (default)
-        let storage = Arc::new(DuckDbStorage::open(&cli.db_path)?);
-        tracing::info!("Database opened at {:?}", cli.db_path);
-
-        let index = Arc::new(UsearchIndex::new(cli.dimensions)?);
-
-        // Load existing index if available
-        let index_path = cli.db_path.with_extension("usearch");
-        if index_path.exists() {
-            index.load(&index_path)?;
-            tracing::info!("Loaded vector index ({} vectors)", index.len());
-        }
+        let eng = MnemoEngine::from_config(&config).await?;
+        tracing::info!("Database opened ({:?})", config.storage);
+        eng
+    };
+    if engine.encrypts_content() {
+        tracing::info!("At-rest encryption enabled");
+    }
+    if config.features.experience_memory {
+        tracing::info!("Experience-memory tier (DocTrace) enabled");
+    }
+    let engine = if cli.outbox_webhook_url.is_some() {
+        engine.with_outbox()
+    } else {
+        engine
+    };
+    let engine = Arc::new(match activity_tracker {
+        Some(ref tracker) => engine.with_activity_tracker(tracker.clone()),
+        None => engine,
+    });
 
-        // Initialize full-text index
-        let ft_path = cli.db_path.with_extension("tantivy");
-        let full_text = Arc::new(TantivyFullTextIndex::new(&ft_path)?);
-        tracing::info!(
-            "Full-text index ready at {:?} ({} docs)",
-            ft_path,
-            full_text.len()
-        );
+    // Sampled recalls rerun under the shadow arms once their callers have
+    // their responses.
+    if mnemo_core::query::shadow_recall::spawn_worker(engine.clone()).is_some() {
+        tracing::info!("Shadow recall enabled");
+    }
 
-        // Keep a clone of the actual index for shutdown save
-        duckdb_index = Some(index.clone());
+    // Other processes sharing the PostgreSQL database write memories too;
+    // follow their NOTIFY feed to keep this process's cache and indexes fresh.
+    #[cfg(feature = "postgres")]
+    if let Some(ref pg_storage) = pg_storage_handle {
+        let changes = pg_storage.subscribe_changes().await?;
+        mnemo_core::query::change_feed::spawn_change_listener(engine.clone(), changes);
+        tracing::info!("Listening for memory changes from other processes");
+    }
 
-        let mut eng = MnemoEngine::new(
-            storage,
-            index.clone(),
-            embedding,
-            cli.agent_id.clone(),
-            cli.org_id.clone(),
-        )
-        .with_full_text(full_text.clone());
-        if let Some(ref key_hex) = cli.encryption_key {
-            let enc = ContentEncryption::from_hex(key_hex)?;
-            eng = eng.with_encryption(Arc::new(enc));
-            tracing::info!("At-rest encryption enabled");
-        }
-        if experience_memory_enabled() {
-            eng = eng.with_experience_memory();
-            tracing::info!("Experience-memory tier (DocTrace) enabled");
-        }
-        Arc::new(eng)
-    };
+    // Every server task is registered with the controller so shutdown can
+    // stop accepting, drain in-flight requests and then flush state once.
+    let mut servers = shutdown::ShutdownController::new(std::time::Duration::from_secs(
+        cli.shutdown_drain_seconds,
+    ));
+    let shutdown_signal = servers.signal();
 
     // Optionally start REST API server
     #[cfg(feature = "rest")]
     if let Some(port) = cli.rest_port {
-        let rest_engine = engine.clone();
-        tokio::spawn(async move {
-            let app = mnemo_rest::router(rest_engine);
+        let app = mnemo_rest::router(engine.clone());
+        let stop = shutdown_signal.wait();
+        servers.spawn("REST server", async move {
             match tokio::net::TcpListener::bind(format!("0.0.0.0:{port}")).await {
                 Ok(listener) => {
                     tracing::info!("REST API listening on 0.0.0.0:{port}");
-                    if let Err(e) = axum::serve(listener, app).await {
+                    if let Err(e) = axum::serve(listener, app)
+                        .with_graceful_shutdown(stop)
+                        .await
+                    {
                         tracing::error!("REST server failed: {e}");
                     }
                 }
@@ -381,55 +652,57 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         });
     }
 
-    // Shared activity tracker for idle timeout
-    let activity_tracker = if cli.idle_timeout_seconds > 0 {
-        Some(Arc::new(AtomicU64::new(
-            std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
-        )))
-    } else {
-        None
-    };
+    // Optionally start gRPC API server
+    #[cfg(feature = "grpc")]
+    if let Some(port) = cli.grpc_port {
+        let grpc_router = mnemo_grpc::router(engine.clone());
+        let stop = shutdown_signal.wait();
+        servers.spawn("gRPC server", async move {
+            let addr = std::net::SocketAddr::from(([0, 0, 0, 0], port));
+            tracing::info!("gRPC API listening on {addr}");
+            if let Err(e) = grpc_router.serve_with_shutdown(addr, stop).await {
+                tracing::error!("gRPC server failed: {e}");
+            }
+        });
+    }
 
-    // Shared shutdown signal
-    let shutdown_notify = Arc::new(Notify::new());
+    // Optionally start pgwire server
+    #[cfg(feature = "pgwire")]
+    if let Some(port) = cli.pgwire_port {
+        let pg_config = mnemo_pgwire::PgWireConfig {
+            bind_addr: format!("127.0.0.1:{port}"),
+            default_agent_id: config.agent_id.clone(),
+            ..Default::default()
+        };
+        let pg_engine = engine.clone();
+        let stop = shutdown_signal.wait();
+        servers.spawn("pgwire server", async move {
+            if let Err(e) =
+                mnemo_pgwire::start_server_with_shutdown(pg_engine, pg_config, stop).await
+            {
+                tracing::error!("pgwire server failed: {e}");
+            }
+        });
+    }
 
     // Start idle timeout watchdog (for scale-to-zero)
     if let Some(ref tracker) = activity_tracker {
         let timeout = cli.idle_timeout_seconds;
         let watchdog_tracker = tracker.clone();
-        let watchdog_engine = engine.clone();
-        let watchdog_shutdown = shutdown_notify.clone();
+        let watchdog_signal = shutdown_signal.clone();
         tokio::spawn(async move {
+            let stop = watchdog_signal.wait();
+            tokio::pin!(stop);
             loop {
-                tokio::time::sleep(std::time::Duration::from_secs(10)).await;
-                let last = watchdog_tracker.load(Ordering::Relaxed);
-                let now = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs();
-                if now - last > timeout {
+                tokio::select! {
+                    _ = tokio::time::sleep(std::time::Duration::from_secs(10)) => {}
+                    () = &mut stop => return,
+                }
+                if watchdog_tracker.is_idle(std::time::Duration::from_secs(timeout)) {
                     tracing::info!(
                         "Idle timeout reached ({timeout}s), shutting down for scale-to-zero"
                     );
-                    // Checkpoint before exit so state can be restored on next start
-                    match watchdog_engine
-                        .checkpoint(mnemo_core::query::checkpoint::CheckpointRequest {
-                            thread_id: "__shutdown__".to_string(),
-                            agent_id: None,
-                            branch_name: Some("main".to_string()),
-                            state_snapshot: serde_json::json!({"reason": "idle_timeout"}),
-                            label: Some("auto-shutdown".to_string()),
-                            metadata: None,
-                        })
-                        .await
-                    {
-                        Ok(resp) => tracing::info!("Shutdown checkpoint created: {}", resp.id),
-                        Err(e) => tracing::warn!("Failed to create shutdown checkpoint: {e}"),
-                    }
-                    watchdog_shutdown.notify_one();
+                    watchdog_signal.trigger("idle_timeout");
                     return;
                 }
             }
@@ -439,33 +712,79 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Signal handler for graceful shutdown (Ctrl+C / SIGTERM)
-    let signal_shutdown = shutdown_notify.clone();
-    tokio::spawn(async move {
-        if let Err(e) = tokio::signal::ctrl_c().await {
-            tracing::error!("Failed to listen for Ctrl+C: {e}");
-            return;
-        }
-        tracing::info!("Received shutdown signal");
-        signal_shutdown.notify_one();
-    });
+    shutdown::listen_for_os_signals(shutdown_signal.clone());
+
+    // Re-read `--config` on SIGHUP and apply the settings that can change
+    // while serving.
+    #[cfg(unix)]
+    if let Some(ref path) = cli.config {
+        let reload_engine = engine.clone();
+        let path = path.clone();
+        let mut running = config.clone();
+        let stop = shutdown_signal.wait();
+        tracing::info!("Reloading {:?} on SIGHUP", path);
+        servers.spawn("config reloader", async move {
+            use tokio::signal::unix::{SignalKind, signal};
+            let mut hangup = match signal(SignalKind::hangup()) {
+                Ok(hangup) => hangup,
+                Err(e) => {
+                    tracing::error!("Failed to listen for SIGHUP: {e}");
+                    return;
+                }
+            };
+            tokio::pin!(stop);
+            loop {
+                tokio::select! {
+                    Some(()) = hangup.recv() => {
+                        let reloaded = read_engine_config(&path).and_then(|next| {
+                            Ok(reload_engine.reload_config(&mut running, &next)?)
+                        });
+                        match reloaded {
+                            Ok(reload) => tracing::info!(
+                                applied = reload.applied.len(),
+                                restart_required = reload.restart_required.len(),
+                                "Engine configuration reloaded from {:?}",
+                                path
+                            ),
+                            Err(e) => tracing::error!(
+                                "Engine configuration reload failed, keeping current settings: {e}"
+                            ),
+                        }
+                    }
+                    () = &mut stop => return,
+                }
+            }
+        });
+    }
 
-    // Start TTL sweeper that hard-deletes expired memories on a fixed cadence.
+    // Start TTL sweeper that hard-deletes expired memories (and, when a trash
+    // retention is configured, long-soft-deleted ones) on a fixed cadence.
     // Disabled when ttl_sweep_interval_seconds == 0.
     if cli.ttl_sweep_interval_seconds > 0 {
         let ttl_interval = cli.ttl_sweep_interval_seconds;
         let ttl_engine = engine.clone();
-        let ttl_shutdown = shutdown_notify.clone();
-        tokio::spawn(async move {
+        let ttl_shutdown = shutdown_signal.wait();
+        servers.spawn("TTL sweeper", async move {
+            tokio::pin!(ttl_shutdown);
             let mut interval = tokio::time::interval(std::time::Duration::from_secs(ttl_interval));
             // Skip the immediate first tick so startup isn't surprised by a sweep.
             interval.tick().await;
             loop {
                 tokio::select! {
                     _ = interval.tick() => {
+                        // Every step below deletes or archives.
+                        if ttl_engine.is_read_only() {
+                            continue;
+                        }
                         match ttl_engine.run_ttl_sweep().await {
-                            Ok(report) if report.swept_count > 0 || !report.errors.is_empty() => {
+                            Ok(report)
+                                if report.swept_count > 0
+                                    || report.warned_count > 0
+                                    || !report.errors.is_empty() =>
+                            {
                                 tracing::info!(
                                     swept = report.swept_count,
+                                    warned = report.warned_count,
                                     errors = report.errors.len(),
                                     "TTL sweep complete"
                                 );
@@ -473,19 +792,309 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             Ok(_) => {}
                             Err(e) => tracing::warn!("TTL sweep failed: {e}"),
                         }
+                        match ttl_engine.run_trash_purge().await {
+                            Ok(report) if report.purged_count > 0 || !report.errors.is_empty() => {
+                                tracing::info!(
+                                    purged = report.purged_count,
+                                    errors = report.errors.len(),
+                                    "Trash purge complete"
+                                );
+                            }
+                            Ok(_) => {}
+                            Err(e) => tracing::warn!("Trash purge failed: {e}"),
+                        }
+                        match access_log::prune(&ttl_engine).await {
+                            Ok(0) => {}
+                            Ok(pruned) => tracing::info!(pruned, "Access log pruned"),
+                            Err(e) => tracing::warn!("Access log prune failed: {e}"),
+                        }
+                        match event_retention::archive(&ttl_engine).await {
+                            Ok(report) if report.events_archived > 0 => tracing::info!(
+                                archived = report.events_archived,
+                                segments = report.segments.len(),
+                                "Events archived"
+                            ),
+                            Ok(_) => {}
+                            Err(e) => tracing::warn!("Event archival failed: {e}"),
+                        }
                     }
-                    _ = ttl_shutdown.notified() => return,
+                    () = &mut ttl_shutdown => return,
                 }
             }
         });
         tracing::info!("TTL sweeper enabled (every {ttl_interval}s)");
     }
 
-    // Create and start MCP server
-    let mut server = MnemoServer::new(engine);
-    if let Some(ref tracker) = activity_tracker {
-        server = server.with_activity_tracker(tracker.clone());
+    // Checkpoint threads that went quiet with unsaved remembers.
+    if cli.config.is_some() {
+        let every = cli.auto_checkpoint_interval_seconds.max(1);
+        let auto_engine = engine.clone();
+        let stop = shutdown_signal.wait();
+        servers.spawn("auto checkpointer", async move {
+            tokio::pin!(stop);
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(every));
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if auto_engine.is_read_only() {
+                            continue;
+                        }
+                        match auto_engine.run_auto_checkpoints().await {
+                            Ok(report) => {
+                                if report.created > 0 || !report.errors.is_empty() {
+                                    tracing::info!(
+                                        created = report.created,
+                                        errors = report.errors.len(),
+                                        "auto checkpoint pass complete"
+                                    );
+                                }
+                            }
+                            Err(e) => tracing::warn!("auto checkpoint pass failed: {e}"),
+                        }
+                    }
+                    () = &mut stop => return,
+                }
+            }
+        });
+        tracing::info!("Inactivity checkpoints enabled (checked every {every}s)");
+    }
+
+    // Run export schedules as they come due. Cron has minute resolution, so
+    // checking once a minute is enough. Schedules can arrive with a config
+    // reload, so the loop runs whenever there is a config file.
+    if cli.config.is_some() {
+        let export_engine = engine.clone();
+        let stop = shutdown_signal.wait();
+        servers.spawn("export scheduler", async move {
+            tokio::pin!(stop);
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if export_engine.is_read_only() {
+                            continue;
+                        }
+                        match export_engine.run_scheduled_exports().await {
+                            Ok(runs) => {
+                                for run in runs {
+                                    tracing::info!(
+                                        schedule = %run.schedule,
+                                        status = ?run.status,
+                                        memories = run.memories,
+                                        pruned = run.pruned.len(),
+                                        "export run complete"
+                                    );
+                                }
+                            }
+                            Err(e) => tracing::warn!("export scheduling failed: {e}"),
+                        }
+                    }
+                    () = &mut stop => return,
+                }
+            }
+        });
+        tracing::info!("{} export schedule(s) enabled", engine.exports().len());
+    }
+
+    // Re-fetch ingested web pages as their refresh intervals pass. The
+    // shortest interval is a minute, so checking once a minute is enough.
+    {
+        let refresh_engine = engine.clone();
+        let stop = shutdown_signal.wait();
+        servers.spawn("url refresher", async move {
+            tokio::pin!(stop);
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if refresh_engine.is_read_only() {
+                            continue;
+                        }
+                        match refresh_engine.run_url_refresh().await {
+                            Ok(report) => {
+                                for page in report.refreshed {
+                                    tracing::info!(
+                                        url = %page.url,
+                                        status = ?page.status,
+                                        added = page.added.len(),
+                                        invalidated = page.invalidated.len(),
+                                        "page refreshed"
+                                    );
+                                }
+                                for error in report.errors {
+                                    tracing::warn!("page refresh failed: {error}");
+                                }
+                            }
+                            Err(e) => tracing::warn!("page refresh pass failed: {e}"),
+                        }
+                    }
+                    () = &mut stop => return,
+                }
+            }
+        });
+    }
+
+    // Keep one summary memory per frequently mentioned entity up to date.
+    if cli.entity_summary_interval_seconds > 0 {
+        let every = cli.entity_summary_interval_seconds;
+        let summary_engine = engine.clone();
+        let stop = shutdown_signal.wait();
+        servers.spawn("entity summarizer", async move {
+            tokio::pin!(stop);
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(every));
+            interval.tick().await;
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => match summary_engine
+                        .run_entity_summaries(EntitySummaryRequest::default())
+                        .await
+                    {
+                        Ok(report) => {
+                            if report.summaries_created > 0 || report.summaries_updated > 0 {
+                                tracing::info!(
+                                    created = report.summaries_created,
+                                    updated = report.summaries_updated,
+                                    "entity summary pass complete"
+                                );
+                            }
+                        }
+                        Err(e) => tracing::warn!("entity summary pass failed: {e}"),
+                    },
+                    () = &mut stop => return,
+                }
+            }
+        });
+        tracing::info!("Entity summaries enabled (every {every}s)");
+    }
+
+    // Compact the database during quiet periods.
+    if cli.vacuum_interval_seconds > 0 {
+        let every = cli.vacuum_interval_seconds;
+        let vacuum_engine = engine.clone();
+        let mut request = mnemo_core::query::vacuum::VacuumRequest::new();
+        request.quiet_seconds = Some(cli.vacuum_quiet_seconds);
+        let stop = shutdown_signal.wait();
+        servers.spawn("vacuum", async move {
+            tokio::pin!(stop);
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(every));
+            interval.tick().await;
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => match vacuum_engine.vacuum(request.clone()).await {
+                        Ok(report) if report.ran => tracing::info!(
+                            reclaimed_bytes = report.reclaimed_bytes,
+                            "vacuum pass complete"
+                        ),
+                        Ok(report) => tracing::debug!(
+                            reason = report.skipped_reason.as_deref().unwrap_or_default(),
+                            "vacuum pass skipped"
+                        ),
+                        Err(e) => tracing::warn!("vacuum pass failed: {e}"),
+                    },
+                    () = &mut stop => return,
+                }
+            }
+        });
+        tracing::info!("Vacuum enabled (every {every}s)");
     }
+
+    // Watch for agents whose writes no longer look like their own.
+    if cli.write_anomaly_interval_seconds > 0 && engine.write_anomaly.is_some() {
+        let every = cli.write_anomaly_interval_seconds;
+        let anomaly_engine = engine.clone();
+        let stop = shutdown_signal.wait();
+        servers.spawn("write anomaly detector", async move {
+            tokio::pin!(stop);
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(every));
+            interval.tick().await;
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if anomaly_engine.is_read_only() {
+                            continue;
+                        }
+                        match anomaly_engine.run_write_anomaly_pass().await {
+                            Ok(report) => tracing::debug!(
+                                checked = report.agents_checked,
+                                flagged = report.flagged.len(),
+                                "write anomaly pass complete"
+                            ),
+                            Err(e) => tracing::warn!("write anomaly pass failed: {e}"),
+                        }
+                    }
+                    () = &mut stop => return,
+                }
+            }
+        });
+        tracing::info!("Write anomaly detection enabled (every {every}s)");
+    } else if cli.write_anomaly_interval_seconds > 0 {
+        tracing::warn!(
+            "--write-anomaly-interval-seconds is set but the config has no [features.write_anomaly]; not running write anomaly passes"
+        );
+    }
+
+    // Keep the pgvector planner statistics fresh and apply changed HNSW
+    // build options with a concurrent rebuild.
+    #[cfg(feature = "postgres")]
+    if let Some(pg_storage) = pg_storage_handle
+        .clone()
+        .filter(|_| cli.pg_vector_maintenance_interval_seconds > 0)
+    {
+        let every = cli.pg_vector_maintenance_interval_seconds;
+        let stop = shutdown_signal.wait();
+        servers.spawn("pgvector maintenance", async move {
+            tokio::pin!(stop);
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(every));
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => match pg_storage.maintain_vector_index().await {
+                        Ok(true) => tracing::info!("pgvector maintenance rebuilt the HNSW index"),
+                        Ok(false) => {}
+                        Err(e) => tracing::warn!("pgvector maintenance failed: {e}"),
+                    },
+                    () = &mut stop => return,
+                }
+            }
+        });
+        tracing::info!("pgvector maintenance enabled (every {every}s)");
+    }
+
+    // Deliver outbox messages to the webhook. Each tick drains the pending
+    // messages, then drops those delivered more than a day ago.
+    if let Some(ref url) = cli.outbox_webhook_url {
+        let mut sink = mnemo_core::query::outbox::WebhookSink::new(url.clone());
+        if let Some(ref secret) = cli.outbox_webhook_secret {
+            sink = sink.with_secret(secret.as_bytes());
+        }
+        let storage = engine.storage.clone();
+        let dispatcher =
+            mnemo_core::query::outbox::OutboxDispatcher::new(storage.clone(), Arc::new(sink));
+        let every = cli.outbox_interval_seconds.max(1);
+        let stop = shutdown_signal.wait();
+        servers.spawn("outbox dispatcher", async move {
+            tokio::pin!(stop);
+            let mut interval = tokio::time::interval(std::time::Duration::from_secs(every));
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        drain_outbox(&dispatcher).await;
+                        let cutoff = (chrono::Utc::now() - chrono::Duration::days(1)).to_rfc3339();
+                        if let Err(e) = storage.prune_outbox(&cutoff).await {
+                            tracing::warn!("outbox prune failed: {e}");
+                        }
+                    }
+                    () = &mut stop => return,
+                }
+            }
+        });
+        tracing::info!("Outbox webhook delivery enabled (every {every}s)");
+    }
+
+    // Create and start MCP server
+    // Tool calls touch the engine's activity tracker, shared with the REST,
+    // gRPC and pgwire front-ends, so the idle watchdog only fires when every
+    // transport is quiet.
+    let server = MnemoServer::new(engine.clone());
     tracing::info!("Starting Mnemo MCP server on stdio");
 
     let service = server.serve(stdio()).await?;
@@ -496,24 +1105,155 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             if let Err(e) = result {
                 tracing::error!("MCP service error: {e}");
             }
+            shutdown_signal.trigger("stdio_closed");
         }
-        _ = shutdown_notify.notified() => {
-            tracing::info!("Shutdown initiated, saving state...");
-        }
+        () = shutdown_signal.wait() => {}
     }
+    let reason = shutdown_signal.reason().unwrap_or("stdio_closed");
+    tracing::info!("Shutdown initiated ({reason}), draining servers...");
 
-    // Save DuckDB vector index on shutdown (using the actual populated index)
-    if let Some(ref index) = duckdb_index {
-        let index_path = cli.db_path.with_extension("usearch");
-        tracing::info!("Saving vector index ({} vectors)...", index.len());
-        if let Err(e) = index.save(&index_path) {
-            tracing::error!("Failed to save vector index: {}", e);
-        }
+    let aborted = servers.drain().await;
+    if aborted > 0 {
+        tracing::warn!("{aborted} server task(s) aborted after the drain deadline");
     }
 
+    // Save the usearch snapshot on shutdown (pgvector lives in Postgres)
+    let vector_index = match config.index.path {
+        Some(ref path) if cli.postgres_url.is_none() => Some((&*engine.index, path.as_path())),
+        _ => None,
+    };
+    shutdown::flush_state(&engine, vector_index, reason).await;
+
     Ok(())
 }
 
+/// Dispatch outbox batches until one comes back empty or a delivery fails
+/// (the rest wait for the next tick).
+async fn drain_outbox(dispatcher: &mnemo_core::query::outbox::OutboxDispatcher) {
+    loop {
+        match dispatcher.dispatch_once().await {
+            Ok(report) if report.delivered > 0 && report.failed == 0 => {
+                tracing::debug!(delivered = report.delivered, "outbox dispatched");
+            }
+            Ok(report) => {
+                if report.failed > 0 {
+                    tracing::debug!(
+                        delivered = report.delivered,
+                        failed = report.failed,
+                        "outbox dispatched with failures"
+                    );
+                }
+                return;
+            }
+            Err(e) => {
+                tracing::warn!("outbox dispatch failed: {e}");
+                return;
+            }
+        }
+    }
+}
+
+/// Engine configuration from command-line flags / environment: the DuckDB
+/// file layout (or PostgreSQL, which brings its own index and skips the
+/// full-text index), embedding provider (ONNX > OpenAI > Noop), encryption
+/// and feature toggles.
+/// Engine settings from `--config` when given, otherwise from flags.
+fn load_engine_config(cli: &Cli) -> Result<MnemoEngineConfig, Box<dyn std::error::Error>> {
+    Ok(match cli.config {
+        Some(ref path) => {
+            let config = read_engine_config(path).map_err(|e| e as Box<dyn std::error::Error>)?;
+            tracing::info!("Engine configuration loaded from {:?}", path);
+            config
+        }
+        None => cli_engine_config(cli),
+    })
+}
+
+fn read_engine_config(
+    path: &std::path::Path,
+) -> Result<MnemoEngineConfig, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(toml::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+fn cli_engine_config(cli: &Cli) -> MnemoEngineConfig {
+    let mut config = if cli.postgres_url.is_some() {
+        MnemoEngineConfig {
+            full_text: FullTextConfig {
+                enabled: false,
+                path: None,
+            },
+            ..Default::default()
+        }
+    } else if cli.storage == "memory" {
+        MnemoEngineConfig {
+            storage: StorageConfig::InMemory,
+            ..Default::default()
+        }
+    } else {
+        MnemoEngineConfig::duckdb(&cli.db_path)
+    };
+    config.agent_id = cli.agent_id.clone();
+    config.org_id = cli.org_id.clone();
+    config.dimensions = cli.dimensions;
+    config.embedding = if let Some(ref onnx_path) = cli.onnx_model_path {
+        tracing::info!("Using ONNX local embeddings from {}", onnx_path);
+        EmbeddingConfig::Onnx {
+            model_path: onnx_path.clone(),
+        }
+    } else if let Some(ref api_key) = cli.openai_api_key {
+        tracing::info!("Using OpenAI embeddings ({})", cli.embedding_model);
+        EmbeddingConfig::OpenAi {
+            api_key: Some(api_key.clone()),
+            model: cli.embedding_model.clone(),
+        }
+    } else {
+        tracing::warn!(
+            "No OPENAI_API_KEY set, using noop embeddings (semantic search will not work)"
+        );
+        EmbeddingConfig::Noop
+    };
+    config.encryption_key = cli.encryption_key.clone();
+    config.features.experience_memory = experience_memory_enabled();
+    config.features.trash_retention_days = cli.trash_retention_days;
+    config.features.expiry_warning_hours = cli.expiry_warning_hours;
+    config.features.curation = cli.curators.as_ref().map(|curators| CurationPolicy {
+        curators: curators
+            .iter()
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty())
+            .collect(),
+    });
+    config.features.pseudonymization =
+        cli.pseudonym_key
+            .as_ref()
+            .map(|key| PseudonymizationConfig {
+                key: key.clone(),
+                orgs: cli.pseudonym_orgs.clone().unwrap_or_default(),
+            });
+    config.features.share_links = cli
+        .share_link_key
+        .as_ref()
+        .map(|key| ShareLinkConfig { key: key.clone() });
+    config.features.write_anomaly =
+        (cli.write_anomaly_interval_seconds > 0).then(|| WriteAnomalyPolicy {
+            throttle: cli.write_anomaly_throttle,
+            ..Default::default()
+        });
+    config.read_only = cli.read_only;
+    config.cache = (cli.cache_max_entries > 0).then(|| CacheConfig {
+        ttl_seconds: cli.cache_ttl_seconds,
+        max_entries: cli.cache_max_entries,
+        policy: cli
+            .cache_lru_k
+            .map_or(EvictionPolicy::Lru, |k| EvictionPolicy::LruK { k }),
+    });
+    config.features.access_log = cli.access_log_retention_days.map(|days| AccessLogPolicy {
+        retention_days: Some(days),
+        ..Default::default()
+    });
+    config
+}
+
 /// Handle `mnemo baseline --train --agent-id <id>` (v0.3.3 Task A).
 ///
 /// Loads every non-deleted memory for the agent from DuckDB, computes
@@ -733,73 +1473,26 @@ async fn run_mcp_server(cli: &Cli, args: &McpServerArgs) -> Result<(), Box<dyn s
         }
     });
 
-    // Embedding provider: mirror the default startup path. ONNX > OpenAI > Noop.
-    let embedding: Arc<dyn EmbeddingProvider> = if let Some(ref onnx_path) = cli.onnx_model_path {
-        tracing::info!("Using ONNX local embeddings from {}", onnx_path);
-        Arc::new(mnemo_core::embedding::onnx::OnnxEmbedding::new(
-            onnx_path,
-            cli.dimensions,
-        )?)
-    } else if let Some(ref api_key) = cli.openai_api_key {
-        tracing::info!("Using OpenAI embeddings ({})", cli.embedding_model);
-        Arc::new(OpenAiEmbedding::new(
-            api_key.clone(),
-            cli.embedding_model.clone(),
-            cli.dimensions,
-        ))
-    } else {
-        tracing::warn!(
-            "No OPENAI_API_KEY set, using noop embeddings (semantic search will not work)"
-        );
-        Arc::new(NoopEmbedding::new(cli.dimensions))
-    };
-
     // Storage: hardened mode is DuckDB-only (PostgreSQL connection
     // strings are exactly the kind of capability the manifest is meant
-    // to keep out of env). `cli.db_path` still applies — it is the
-    // path-only knob in the CLI.
-    let storage = Arc::new(DuckDbStorage::open(&cli.db_path)?);
-    let index = Arc::new(UsearchIndex::new(cli.dimensions)?);
-    let index_path = cli.db_path.with_extension("usearch");
-    if index_path.exists() {
-        index.load(&index_path)?;
-        tracing::info!("Loaded vector index ({} vectors)", index.len());
-    }
-    let ft_path = cli.db_path.with_extension("tantivy");
-    let full_text = Arc::new(TantivyFullTextIndex::new(&ft_path)?);
-    tracing::info!(
-        "Full-text index ready at {:?} ({} docs)",
-        ft_path,
-        full_text.len()
+    // to keep out of env) and ignores `--config`. `cli.db_path` still
+    // applies — it is the path-only knob in the CLI. Feature toggles stay
+    // at their defaults; privileged knobs come from the manifest.
+    let mut config = cli_engine_config(cli);
+    config.features = FeatureConfig::default();
+    let engine = Arc::new(
+        MnemoEngine::from_config(&config)
+            .await?
+            .with_provenance_signer(Arc::new(signer)),
     );
-    let mut eng = MnemoEngine::new(
-        storage,
-        index.clone(),
-        embedding,
-        cli.agent_id.clone(),
-        cli.org_id.clone(),
-    )
-    .with_full_text(full_text)
-    .with_provenance_signer(Arc::new(signer));
-    if let Some(ref key_hex) = cli.encryption_key {
-        let enc = ContentEncryption::from_hex(key_hex)?;
-        eng = eng.with_encryption(Arc::new(enc));
+    if engine.encrypts_content() {
         tracing::info!("At-rest encryption enabled");
     }
-    let engine = Arc::new(eng);
 
-    let shutdown_notify = Arc::new(Notify::new());
-    let signal_shutdown = shutdown_notify.clone();
-    tokio::spawn(async move {
-        if let Err(e) = tokio::signal::ctrl_c().await {
-            tracing::error!("Failed to listen for Ctrl+C: {e}");
-            return;
-        }
-        tracing::info!("Received shutdown signal");
-        signal_shutdown.notify_one();
-    });
+    let shutdown_signal = shutdown::ShutdownSignal::new();
+    shutdown::listen_for_os_signals(shutdown_signal.clone());
 
-    let server = MnemoServer::new(engine);
+    let server = MnemoServer::new(engine.clone());
     tracing::info!("Starting Mnemo MCP server on stdio (hardened mode)");
     let service = server.serve(stdio()).await?;
     tokio::select! {
@@ -807,16 +1500,18 @@ async fn run_mcp_server(cli: &Cli, args: &McpServerArgs) -> Result<(), Box<dyn s
             if let Err(e) = result {
                 tracing::error!("MCP service error: {e}");
             }
+            shutdown_signal.trigger("stdio_closed");
         }
-        _ = shutdown_notify.notified() => {
-            tracing::info!("Shutdown initiated, saving state...");
-        }
-    }
-    let index_path = cli.db_path.with_extension("usearch");
-    tracing::info!("Saving vector index ({} vectors)...", index.len());
-    if let Err(e) = index.save(&index_path) {
-        tracing::error!("Failed to save vector index: {}", e);
+        () = shutdown_signal.wait() => {}
     }
+    let reason = shutdown_signal.reason().unwrap_or("stdio_closed");
+    tracing::info!("Shutdown initiated ({reason}), saving state...");
+    let vector_index = config
+        .index
+        .path
+        .as_deref()
+        .map(|path| (&*engine.index, path));
+    shutdown::flush_state(&engine, vector_index, reason).await;
     Ok(())
 }
 
@@ -927,6 +1622,7 @@ async fn run_eval(cli: &Cli, args: &EvalArgs) -> Result<(), Box<dyn std::error::
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: None,
             explain: None,
@@ -941,6 +1637,22 @@ async fn run_eval(cli: &Cli, args: &EvalArgs) -> Result<(), Box<dyn std::error::
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         };
         let t0 = Instant::now();
         let resp = engine.recall(recall).await?;
@@ -985,6 +1697,188 @@ async fn run_eval(cli: &Cli, args: &EvalArgs) -> Result<(), Box<dyn std::error::
     Ok(())
 }
 
+/// Handle `mnemo audit-drift --corpus <facts.jsonl>`.
+///
+/// Opens the configured DuckDB engine, compares the agent's memories with
+/// the corpus and writes the [`DriftReport`](mnemo_core::query::drift::DriftReport)
+/// as JSON.
+async fn run_audit_drift(
+    cli: &Cli,
+    args: &AuditDriftArgs,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::Write;
+
+    use mnemo_core::query::drift::{self, DriftRequest};
+
+    let text = std::fs::read_to_string(&args.corpus)
+        .map_err(|e| format!("audit-drift: failed to read corpus {:?}: {e}", args.corpus))?;
+    let facts = drift::parse_corpus(&text)?;
+    if facts.is_empty() {
+        return Err(format!("audit-drift: corpus {:?} is empty", args.corpus).into());
+    }
+
+    let config = load_engine_config(cli)?;
+    let engine = MnemoEngine::from_config(&config).await?;
+    let mut request = DriftRequest::new(facts);
+    request.agent_id = args.agent_id.clone();
+    request.match_threshold = args.match_threshold;
+    request.consistent_threshold = args.consistent_threshold;
+    let report = engine.detect_drift(request).await?;
+    tracing::info!(
+        agent = %report.agent_id,
+        checked = report.memories_checked,
+        stale = report.stale(),
+        contradicting = report.contradicting(),
+        "drift audit finished"
+    );
+
+    let json = serde_json::to_string_pretty(&report)?;
+    match &args.output {
+        Some(path) => std::fs::write(path, json + "\n")?,
+        None => writeln!(std::io::stdout().lock(), "{json}")?,
+    }
+    if args.fail_on_drift && !report.findings.is_empty() {
+        return Err(format!(
+            "audit-drift: {} stale and {} contradicting memories",
+            report.stale(),
+            report.contradicting()
+        )
+        .into());
+    }
+    Ok(())
+}
+
+async fn run_rechain(cli: &Cli, args: &RechainArgs) -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::Write;
+
+    use mnemo_core::query::rechain::RechainRequest;
+
+    let config = load_engine_config(cli)?;
+    let engine = MnemoEngine::from_config(&config).await?;
+    let mut request = RechainRequest::new();
+    request.agent_id = args.agent_id.clone();
+    request.thread_id = args.thread_id.clone();
+    let response = engine.rechain(request).await?;
+    tracing::info!(
+        agent = %response.agent_id,
+        chains = response.chains.len(),
+        relinked = response.chains.iter().map(|c| c.relinked).sum::<usize>(),
+        "rechain finished"
+    );
+    let json = serde_json::to_string_pretty(&response)?;
+    writeln!(std::io::stdout().lock(), "{json}")?;
+    Ok(())
+}
+
+async fn run_ingest(cli: &Cli, args: &IngestArgs) -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::Write;
+
+    use mnemo_core::ingest::{self, DocumentInput, IngestRequest, IngestResponse};
+
+    /// Documents sent to the engine per request.
+    const DOCUMENTS_PER_BATCH: usize = 64;
+
+    let config = load_engine_config(cli)?;
+    let documents = if args.source.starts_with("http://") || args.source.starts_with("https://") {
+        let policy = config.features.url_fetch.clone().unwrap_or_default();
+        vec![ingest::fetch(&args.source, &policy).await?]
+    } else {
+        let mut paths = Vec::new();
+        collect_ingest_paths(std::path::Path::new(&args.source), &mut paths)?;
+        paths.sort();
+        paths
+            .into_iter()
+            .map(|path| {
+                let bytes = std::fs::read(&path)?;
+                Ok(DocumentInput::from_bytes(path.display().to_string(), bytes))
+            })
+            .collect::<std::io::Result<Vec<_>>>()?
+    };
+    if documents.is_empty() {
+        return Err(format!("no supported documents found at {}", args.source).into());
+    }
+
+    let engine = MnemoEngine::from_config(&config).await?;
+    let mut response = IngestResponse {
+        documents: Vec::new(),
+        memories: 0,
+    };
+    for batch in documents.chunks(DOCUMENTS_PER_BATCH) {
+        let mut request = IngestRequest::new(batch.to_vec());
+        for document in &mut request.documents {
+            document.format = args.format.clone().or(document.format.take());
+        }
+        request.agent_id = args.agent_id.clone();
+        request.tags = (!args.tags.is_empty()).then(|| args.tags.clone());
+        request.max_chunk_chars = args.max_chunk_chars;
+        let ingested = engine.ingest_documents(request).await?;
+        response.memories += ingested.memories;
+        response.documents.extend(ingested.documents);
+    }
+    tracing::info!(
+        documents = response.documents.len(),
+        memories = response.memories,
+        "ingest finished"
+    );
+    let json = serde_json::to_string_pretty(&response)?;
+    writeln!(std::io::stdout().lock(), "{json}")?;
+    Ok(())
+}
+
+/// `path` itself if it is a file, else the files under it with a
+/// recognised format, skipping hidden entries.
+fn collect_ingest_paths(path: &std::path::Path, out: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if !path.is_dir() {
+        out.push(path.to_path_buf());
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if name.to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let child = entry.path();
+        if child.is_dir() {
+            collect_ingest_paths(&child, out)?;
+        } else {
+            let source = child.to_string_lossy();
+            if mnemo_core::ingest::format_of(&source) != "text" || source.ends_with(".txt") {
+                out.push(child);
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn run_seed(cli: &Cli, args: &SeedArgs) -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::Write;
+
+    use mnemo_core::eval::seed::{SeedConfig, seed};
+
+    let config = load_engine_config(cli)?;
+    let engine = MnemoEngine::from_config(&config).await?;
+    let seed_config = SeedConfig {
+        seed: args.seed,
+        agents: args.agents,
+        memories: args.memories,
+        threads: args.threads,
+        with_relations: args.with_relations,
+        days: args.days,
+        ..Default::default()
+    };
+    let started = std::time::Instant::now();
+    let report = seed(&engine, seed_config).await?;
+    tracing::info!(
+        memories = report.memories,
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        "seed finished"
+    );
+    let json = serde_json::to_string_pretty(&report)?;
+    writeln!(std::io::stdout().lock(), "{json}")?;
+    Ok(())
+}
+
 /// v0.4.9 — `mnemo bench <subcommand>` dispatch.
 async fn run_bench(sub: &BenchCommand) -> Result<(), Box<dyn std::error::Error>> {
     match sub {
@@ -0,0 +1,214 @@
+//! Coordinated shutdown for every front-end the binary runs.
+//!
+//! A [`ShutdownController`] owns the spawned server tasks (REST, gRPC,
+//! pgwire, the TTL sweeper). Once any source — Ctrl+C, SIGTERM, the idle
+//! watchdog or the MCP stdio stream closing — triggers the shared
+//! [`ShutdownSignal`], each server stops accepting new connections and
+//! drains its in-flight requests. [`ShutdownController::drain`] waits for
+//! them up to a deadline and aborts stragglers, after which
+//! [`flush_state`] persists the indexes and writes a shutdown checkpoint.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use mnemo_core::index::VectorIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::checkpoint::CheckpointRequest;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+/// Cloneable trigger / waiter pair. The carried value is the shutdown
+/// reason, recorded in the shutdown checkpoint.
+#[derive(Clone)]
+pub struct ShutdownSignal {
+    tx: Arc<watch::Sender<Option<&'static str>>>,
+}
+
+impl ShutdownSignal {
+    pub fn new() -> Self {
+        Self {
+            tx: Arc::new(watch::channel(None).0),
+        }
+    }
+
+    /// Start the shutdown. Only the first reason is kept.
+    pub fn trigger(&self, reason: &'static str) {
+        self.tx.send_if_modified(|current| {
+            if current.is_some() {
+                return false;
+            }
+            *current = Some(reason);
+            true
+        });
+    }
+
+    /// Why shutdown was triggered, if it has been.
+    pub fn reason(&self) -> Option<&'static str> {
+        *self.tx.borrow()
+    }
+
+    /// Future that resolves once shutdown has been triggered. Suitable for
+    /// `with_graceful_shutdown` / `serve_with_shutdown`.
+    pub fn wait(&self) -> impl Future<Output = ()> + Send + 'static {
+        let mut rx = self.tx.subscribe();
+        async move {
+            while rx.borrow_and_update().is_none() {
+                if rx.changed().await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Tracks the server tasks that must drain before state is flushed.
+pub struct ShutdownController {
+    signal: ShutdownSignal,
+    tasks: Vec<(&'static str, JoinHandle<()>)>,
+    drain_timeout: Duration,
+}
+
+impl ShutdownController {
+    pub fn new(drain_timeout: Duration) -> Self {
+        Self {
+            signal: ShutdownSignal::new(),
+            tasks: Vec::new(),
+            drain_timeout,
+        }
+    }
+
+    pub fn signal(&self) -> ShutdownSignal {
+        self.signal.clone()
+    }
+
+    /// Spawn a task that must finish (or hit the drain deadline) before
+    /// state is flushed. The task should watch [`ShutdownSignal::wait`].
+    pub fn spawn<F>(&mut self, name: &'static str, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.tasks.push((name, tokio::spawn(task)));
+    }
+
+    /// Trigger shutdown (if nothing has yet) and wait for every tracked
+    /// task, aborting any still running when the drain deadline passes.
+    /// Returns the number of tasks that had to be aborted.
+    pub async fn drain(self) -> usize {
+        self.signal.trigger("drain");
+        let deadline = tokio::time::Instant::now() + self.drain_timeout;
+        let mut aborted = 0;
+        for (name, mut handle) in self.tasks {
+            match tokio::time::timeout_at(deadline, &mut handle).await {
+                Ok(Ok(())) => tracing::debug!("{name} drained"),
+                Ok(Err(e)) => tracing::warn!("{name} task failed during shutdown: {e}"),
+                Err(_) => {
+                    tracing::warn!(
+                        "{name} did not drain within {}s, aborting",
+                        self.drain_timeout.as_secs()
+                    );
+                    handle.abort();
+                    aborted += 1;
+                }
+            }
+        }
+        aborted
+    }
+}
+
+/// Trigger `signal` on Ctrl+C and, on Unix, SIGTERM.
+pub fn listen_for_os_signals(signal: ShutdownSignal) {
+    tokio::spawn(async move {
+        let ctrl_c = tokio::signal::ctrl_c();
+        #[cfg(unix)]
+        let terminate = async {
+            match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+                Ok(mut sigterm) => {
+                    sigterm.recv().await;
+                }
+                Err(e) => {
+                    tracing::error!("Failed to listen for SIGTERM: {e}");
+                    std::future::pending::<()>().await;
+                }
+            }
+        };
+        #[cfg(not(unix))]
+        let terminate = std::future::pending::<()>();
+
+        tokio::select! {
+            result = ctrl_c => {
+                if let Err(e) = result {
+                    tracing::error!("Failed to listen for Ctrl+C: {e}");
+                    return;
+                }
+            }
+            () = terminate => {}
+        }
+        tracing::info!("Received shutdown signal");
+        signal.trigger("signal");
+    });
+}
+
+/// Persist engine state after the servers have drained: commit the
+/// full-text index, save the usearch snapshot when one is configured
+/// (pgvector lives in Postgres) and record a shutdown checkpoint carrying `reason`.
+pub async fn flush_state(
+    engine: &MnemoEngine,
+    vector_index: Option<(&dyn VectorIndex, &Path)>,
+    reason: &str,
+) {
+    if let Some(ref ft) = engine.full_text
+        && let Err(e) = ft.commit()
+    {
+        tracing::error!("Failed to commit full-text index: {e}");
+    }
+
+    if let Some((index, path)) = vector_index {
+        tracing::info!("Saving vector index ({} vectors)...", index.len());
+        if let Err(e) = index.save(path) {
+            tracing::error!("Failed to save vector index: {e}");
+        }
+    }
+
+    match engine
+        .checkpoint(CheckpointRequest {
+            thread_id: "__shutdown__".to_string(),
+            agent_id: None,
+            branch_name: Some("main".to_string()),
+            state_snapshot: serde_json::json!({ "reason": reason }),
+            label: Some("auto-shutdown".to_string()),
+            metadata: None,
+        })
+        .await
+    {
+        Ok(resp) => tracing::info!("Shutdown checkpoint created: {}", resp.id),
+        Err(e) => tracing::warn!("Failed to create shutdown checkpoint: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn first_reason_wins_and_wakes_waiters() {
+        let controller = ShutdownController::new(Duration::from_secs(1));
+        let signal = controller.signal();
+        let waiter = tokio::spawn(signal.wait());
+        signal.trigger("idle_timeout");
+        signal.trigger("signal");
+        waiter.await.unwrap();
+        assert_eq!(signal.reason(), Some("idle_timeout"));
+        // Late subscribers resolve immediately.
+        signal.wait().await;
+    }
+
+    #[tokio::test]
+    async fn drain_waits_for_cooperative_tasks_and_aborts_stuck_ones() {
+        let mut controller = ShutdownController::new(Duration::from_millis(100));
+        let wait = controller.signal().wait();
+        controller.spawn("cooperative", wait);
+        controller.spawn("stuck", std::future::pending());
+        assert_eq!(controller.drain().await, 1);
+    }
+}
@@ -0,0 +1,9 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // The server crate owns the service definition; generate only the client.
+    let proto = "../mnemo-grpc/proto/mnemo.proto";
+    println!("cargo:rerun-if-changed={proto}");
+    tonic_prost_build::configure()
+        .build_server(false)
+        .compile_protos(&[proto], &["../mnemo-grpc/proto"])?;
+    Ok(())
+}
@@ -0,0 +1,495 @@
+//! Connection management, retries and the non-builder RPCs.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use tonic::Code;
+use tonic::metadata::{Ascii, MetadataValue};
+use tonic::transport::{Channel, Endpoint};
+
+use crate::error::{ClientError, Result};
+use crate::proto;
+use crate::proto::mnemo_service_client::MnemoServiceClient;
+use crate::requests::{ForgetBuilder, RecallBuilder, RememberBuilder};
+
+type Rpc = MnemoServiceClient<Channel>;
+
+/// Default per-call deadline.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default time allowed to establish a connection.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How failed calls are retried.
+///
+/// Only transient statuses (`UNAVAILABLE`, `DEADLINE_EXCEEDED`,
+/// `RESOURCE_EXHAUSTED`, `ABORTED`) are retried. Reads are always eligible;
+/// writes (remember, forget, share, ...) only when `retry_writes` is set,
+/// because a write whose response was lost may already have been applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts per call, including the first. `1` disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled after every attempt.
+    pub initial_backoff: Duration,
+    /// Upper bound on the delay between attempts.
+    pub max_backoff: Duration,
+    /// Also retry non-idempotent calls.
+    pub retry_writes: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+            retry_writes: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    fn should_retry(&self, attempt: u32, write: bool, code: Code) -> bool {
+        attempt < self.max_attempts
+            && (!write || self.retry_writes)
+            && matches!(
+                code,
+                Code::Unavailable
+                    | Code::DeadlineExceeded
+                    | Code::ResourceExhausted
+                    | Code::Aborted
+            )
+    }
+}
+
+/// Builder for [`MnemoClient`].
+#[derive(Clone)]
+pub struct ClientBuilder {
+    endpoint: String,
+    agent_id: Option<String>,
+    auth_token: Option<String>,
+    timeout: Option<Duration>,
+    connect_timeout: Duration,
+    pool_size: usize,
+    retry: RetryPolicy,
+}
+
+impl std::fmt::Debug for ClientBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientBuilder")
+            .field("endpoint", &self.endpoint)
+            .field("agent_id", &self.agent_id)
+            .field(
+                "auth_token",
+                &self.auth_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field("timeout", &self.timeout)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("pool_size", &self.pool_size)
+            .field("retry", &self.retry)
+            .finish()
+    }
+}
+
+impl ClientBuilder {
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            agent_id: None,
+            auth_token: None,
+            timeout: Some(DEFAULT_TIMEOUT),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            pool_size: 1,
+            retry: RetryPolicy::default(),
+        }
+    }
+
+    /// Agent id sent with every request that does not set its own.
+    pub fn agent_id(mut self, agent_id: impl Into<String>) -> Self {
+        self.agent_id = Some(agent_id.into());
+        self
+    }
+
+    /// API key sent as `authorization: Bearer <token>`.
+    pub fn auth_token(mut self, token: impl Into<String>) -> Self {
+        self.auth_token = Some(token.into());
+        self
+    }
+
+    /// Per-call deadline. `None` waits indefinitely.
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Number of HTTP/2 connections to open. Calls are spread round-robin
+    /// across them, which helps once a single connection's stream limit
+    /// becomes the bottleneck.
+    pub fn pool_size(mut self, size: usize) -> Self {
+        self.pool_size = size;
+        self
+    }
+
+    pub fn retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    fn endpoint(&self) -> Result<Endpoint> {
+        if self.pool_size == 0 {
+            return Err(ClientError::Config("pool_size must be at least 1".into()));
+        }
+        if self.retry.max_attempts == 0 {
+            return Err(ClientError::Config(
+                "retry max_attempts must be at least 1".into(),
+            ));
+        }
+        let mut endpoint = Endpoint::from_shared(self.endpoint.clone())
+            .map_err(|e| ClientError::InvalidEndpoint(format!("{}: {e}", self.endpoint)))?
+            .connect_timeout(self.connect_timeout)
+            .tcp_nodelay(true)
+            .http2_keep_alive_interval(Duration::from_secs(30))
+            .keep_alive_while_idle(true);
+        if let Some(timeout) = self.timeout {
+            endpoint = endpoint.timeout(timeout);
+        }
+        Ok(endpoint)
+    }
+
+    fn finish(self, channels: Vec<Channel>) -> Result<MnemoClient> {
+        let auth = self
+            .auth_token
+            .map(|token| {
+                format!("Bearer {token}")
+                    .parse::<MetadataValue<Ascii>>()
+                    .map_err(|_| ClientError::Config("auth token is not valid ASCII".into()))
+            })
+            .transpose()?;
+        Ok(MnemoClient {
+            pool: channels.into_iter().map(MnemoServiceClient::new).collect(),
+            next: Arc::new(AtomicUsize::new(0)),
+            agent_id: self.agent_id,
+            auth,
+            retry: self.retry,
+        })
+    }
+
+    /// Open every pooled connection and return the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClientError::Transport`] if a connection cannot be
+    /// established within the connect timeout.
+    pub async fn connect(self) -> Result<MnemoClient> {
+        let endpoint = self.endpoint()?;
+        let mut channels = Vec::with_capacity(self.pool_size);
+        for _ in 0..self.pool_size {
+            channels.push(endpoint.connect().await?);
+        }
+        self.finish(channels)
+    }
+
+    /// Return a client whose connections are opened on first use. Connection
+    /// failures then surface as `UNAVAILABLE` statuses and are retried.
+    pub fn connect_lazy(self) -> Result<MnemoClient> {
+        let endpoint = self.endpoint()?;
+        let channels = (0..self.pool_size)
+            .map(|_| endpoint.connect_lazy())
+            .collect();
+        self.finish(channels)
+    }
+}
+
+/// Typed client for the Mnemo gRPC API.
+///
+/// Cloning is cheap: clones share the connection pool.
+#[derive(Clone)]
+pub struct MnemoClient {
+    pool: Arc<[Rpc]>,
+    next: Arc<AtomicUsize>,
+    agent_id: Option<String>,
+    auth: Option<MetadataValue<Ascii>>,
+    retry: RetryPolicy,
+}
+
+impl std::fmt::Debug for MnemoClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MnemoClient")
+            .field("pool_size", &self.pool.len())
+            .field("agent_id", &self.agent_id)
+            .field("authenticated", &self.auth.is_some())
+            .field("retry", &self.retry)
+            .finish()
+    }
+}
+
+impl MnemoClient {
+    pub fn builder(endpoint: impl Into<String>) -> ClientBuilder {
+        ClientBuilder::new(endpoint)
+    }
+
+    /// Connect with default options.
+    pub async fn connect(endpoint: impl Into<String>) -> Result<Self> {
+        ClientBuilder::new(endpoint).connect().await
+    }
+
+    /// Default agent id, if one was configured.
+    pub fn agent_id(&self) -> Option<&str> {
+        self.agent_id.as_deref()
+    }
+
+    // -- Builder-style operations --------------------------------------------
+
+    /// Store a memory. Configure it on the returned builder, then `.send()`.
+    pub fn remember(&self, content: impl Into<String>) -> RememberBuilder<'_> {
+        RememberBuilder::new(self, content.into())
+    }
+
+    /// Search memories. Finish with `.send()` for a single response or
+    /// `.stream()` to receive hits one at a time.
+    pub fn recall(&self, query: impl Into<String>) -> RecallBuilder<'_> {
+        RecallBuilder::new(self, query.into())
+    }
+
+    /// Delete, decay or archive memories by id.
+    pub fn forget<I, S>(&self, memory_ids: I) -> ForgetBuilder<'_>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        ForgetBuilder::new(self, memory_ids.into_iter().map(Into::into).collect())
+    }
+
+    // -- Raw-request operations ----------------------------------------------
+
+    /// Liveness (`deep = false`) or readiness (`deep = true`) probe.
+    pub async fn health(&self, deep: bool) -> Result<proto::HealthResponse> {
+        self.call(
+            false,
+            proto::HealthRequest { deep },
+            |mut c, r| async move { c.health(r).await },
+        )
+        .await
+    }
+
+    /// Store a scratchpad memory: Working tier, a short TTL, no embedding
+    /// and never consolidated.
+    pub async fn remember_ephemeral(
+        &self,
+        mut request: proto::RememberEphemeralRequest,
+    ) -> Result<proto::RememberResponse> {
+        self.fill_agent(&mut request.agent_id);
+        self.call(true, request, |mut c, r| async move {
+            c.remember_ephemeral(r).await
+        })
+        .await
+    }
+
+    /// Fetch several memories by id, e.g. the content of hits recalled with
+    /// [`RecallBuilder::fields`](crate::RecallBuilder::fields).
+    pub async fn get_batch(
+        &self,
+        mut request: proto::GetBatchRequest,
+    ) -> Result<proto::GetBatchResponse> {
+        self.fill_agent(&mut request.agent_id);
+        self.call(
+            false,
+            request,
+            |mut c, r| async move { c.get_batch(r).await },
+        )
+        .await
+    }
+
+    pub async fn share(&self, mut request: proto::ShareRequest) -> Result<proto::ShareResponse> {
+        self.fill_agent(&mut request.agent_id);
+        self.call(true, request, |mut c, r| async move { c.share(r).await })
+            .await
+    }
+
+    pub async fn checkpoint(
+        &self,
+        mut request: proto::CheckpointRequest,
+    ) -> Result<proto::CheckpointResponse> {
+        self.fill_agent(&mut request.agent_id);
+        self.call(
+            true,
+            request,
+            |mut c, r| async move { c.checkpoint(r).await },
+        )
+        .await
+    }
+
+    pub async fn consolidate(
+        &self,
+        mut request: proto::ConsolidateRequest,
+    ) -> Result<proto::ConsolidateResponse> {
+        self.fill_agent(&mut request.agent_id);
+        self.call(
+            true,
+            request,
+            |mut c, r| async move { c.consolidate(r).await },
+        )
+        .await
+    }
+
+    pub async fn branch(&self, mut request: proto::BranchRequest) -> Result<proto::BranchResponse> {
+        self.fill_agent(&mut request.agent_id);
+        self.call(true, request, |mut c, r| async move { c.branch(r).await })
+            .await
+    }
+
+    pub async fn merge(&self, mut request: proto::MergeRequest) -> Result<proto::MergeResponse> {
+        self.fill_agent(&mut request.agent_id);
+        self.call(true, request, |mut c, r| async move { c.merge(r).await })
+            .await
+    }
+
+    pub async fn replay(&self, mut request: proto::ReplayRequest) -> Result<proto::ReplayResponse> {
+        self.fill_agent(&mut request.agent_id);
+        self.call(false, request, |mut c, r| async move { c.replay(r).await })
+            .await
+    }
+
+    pub async fn delegate(
+        &self,
+        request: proto::DelegateRequest,
+    ) -> Result<proto::DelegateResponse> {
+        self.call(true, request, |mut c, r| async move { c.delegate(r).await })
+            .await
+    }
+
+    pub async fn verify(&self, mut request: proto::VerifyRequest) -> Result<proto::VerifyResponse> {
+        self.fill_agent(&mut request.agent_id);
+        self.call(false, request, |mut c, r| async move { c.verify(r).await })
+            .await
+    }
+
+    pub async fn trajectory_audit(
+        &self,
+        mut request: proto::TrajectoryAuditRequest,
+    ) -> Result<proto::TrajectoryAuditResponse> {
+        self.fill_agent(&mut request.agent_id);
+        self.call(false, request, |mut c, r| async move {
+            c.trajectory_audit(r).await
+        })
+        .await
+    }
+
+    pub async fn forget_subject(
+        &self,
+        mut request: proto::ForgetSubjectRequest,
+    ) -> Result<proto::ForgetSubjectResponse> {
+        self.fill_agent(&mut request.agent_id);
+        self.call(true, request, |mut c, r| async move {
+            c.forget_subject(r).await
+        })
+        .await
+    }
+
+    pub async fn run_decay_pass(
+        &self,
+        mut request: proto::DecayPassRequest,
+    ) -> Result<proto::DecayPassResponse> {
+        self.fill_agent(&mut request.agent_id);
+        self.call(true, request, |mut c, r| async move {
+            c.run_decay_pass(r).await
+        })
+        .await
+    }
+
+    pub async fn run_consolidation(
+        &self,
+        mut request: proto::ConsolidationRequest,
+    ) -> Result<proto::ConsolidationResponse> {
+        self.fill_agent(&mut request.agent_id);
+        self.call(true, request, |mut c, r| async move {
+            c.run_consolidation(r).await
+        })
+        .await
+    }
+
+    pub async fn run_cleanup(&self) -> Result<proto::CleanupResponse> {
+        self.run_cleanup_with(proto::CleanupRequest::default())
+            .await
+    }
+
+    /// Run cleanup with options, e.g. `dry_run` to see what it would delete.
+    pub async fn run_cleanup_with(
+        &self,
+        request: proto::CleanupRequest,
+    ) -> Result<proto::CleanupResponse> {
+        self.call(
+            true,
+            request,
+            |mut c, r| async move { c.run_cleanup(r).await },
+        )
+        .await
+    }
+
+    // -- Plumbing ------------------------------------------------------------
+
+    pub(crate) fn fill_agent(&self, agent_id: &mut Option<String>) {
+        if agent_id.is_none() {
+            agent_id.clone_from(&self.agent_id);
+        }
+    }
+
+    fn next_rpc(&self) -> Rpc {
+        let slot = self.next.fetch_add(1, Ordering::Relaxed) % self.pool.len();
+        self.pool[slot].clone()
+    }
+
+    /// Run one RPC on the next pooled connection, retrying transient
+    /// failures according to the [`RetryPolicy`].
+    pub(crate) async fn call<Req, Resp, F, Fut>(
+        &self,
+        write: bool,
+        message: Req,
+        rpc: F,
+    ) -> Result<Resp>
+    where
+        Req: Clone,
+        F: Fn(Rpc, tonic::Request<Req>) -> Fut,
+        Fut: Future<Output = std::result::Result<tonic::Response<Resp>, tonic::Status>>,
+    {
+        let mut backoff = self.retry.initial_backoff;
+        let mut attempt = 1;
+        loop {
+            let mut request = tonic::Request::new(message.clone());
+            if let Some(auth) = &self.auth {
+                request.metadata_mut().insert("authorization", auth.clone());
+            }
+            match rpc(self.next_rpc(), request).await {
+                Ok(response) => return Ok(response.into_inner()),
+                Err(status) if self.retry.should_retry(attempt, write, status.code()) => {
+                    tracing::debug!(
+                        attempt,
+                        code = ?status.code(),
+                        backoff_ms = backoff.as_millis() as u64,
+                        "retrying mnemo call"
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(self.retry.max_backoff);
+                    attempt += 1;
+                }
+                Err(status) => return Err(status.into()),
+            }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! Client error type.
+
+use thiserror::Error;
+
+/// Errors returned by [`MnemoClient`](crate::MnemoClient).
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// The endpoint URI could not be parsed.
+    #[error("invalid endpoint: {0}")]
+    InvalidEndpoint(String),
+
+    /// The client options are inconsistent (e.g. a zero pool size).
+    #[error("invalid client configuration: {0}")]
+    Config(String),
+
+    /// The connection could not be established.
+    #[error("transport error: {0}")]
+    Transport(#[from] tonic::transport::Error),
+
+    /// The server answered with a non-OK status.
+    #[error("{}: {}", .0.code(), .0.message())]
+    Status(Box<tonic::Status>),
+}
+
+impl ClientError {
+    /// gRPC status code, if the server returned one.
+    pub fn code(&self) -> Option<tonic::Code> {
+        match self {
+            ClientError::Status(status) => Some(status.code()),
+            _ => None,
+        }
+    }
+}
+
+impl From<tonic::Status> for ClientError {
+    fn from(status: tonic::Status) -> Self {
+        ClientError::Status(Box::new(status))
+    }
+}
+
+/// Convenience result type for client calls.
+pub type Result<T> = std::result::Result<T, ClientError>;
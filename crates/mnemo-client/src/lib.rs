@@ -0,0 +1,50 @@
+//! Typed Rust client for the Mnemo gRPC API.
+//!
+//! [`MnemoClient`] wraps the generated tonic stub with builder-style
+//! requests, a default agent id, bearer-token auth, per-call deadlines,
+//! retries with exponential backoff and a small round-robin connection
+//! pool. Its surface mirrors the Python client: `remember`, `recall` and
+//! `forget` return builders, everything else takes the protobuf request
+//! from [`proto`] directly.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! # async fn run() -> mnemo_client::Result<()> {
+//! use mnemo_client::MnemoClient;
+//!
+//! let client = MnemoClient::builder("http://127.0.0.1:50051")
+//!     .agent_id("planner")
+//!     .auth_token("secret")
+//!     .connect()
+//!     .await?;
+//!
+//! client
+//!     .remember("the deploy window is tuesday 14:00 UTC")
+//!     .tags(["ops", "deploy"])
+//!     .importance(0.8)
+//!     .send()
+//!     .await?;
+//!
+//! let hits = client.recall("when do we deploy").limit(5).send().await?;
+//! for hit in hits.memories {
+//!     println!("{:.3} {}", hit.score, hit.content);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod client;
+mod error;
+mod requests;
+
+/// Generated protobuf messages and the raw tonic client.
+pub mod proto {
+    tonic::include_proto!("mnemo.v1");
+}
+
+pub use client::{
+    ClientBuilder, DEFAULT_CONNECT_TIMEOUT, DEFAULT_TIMEOUT, MnemoClient, RetryPolicy,
+};
+pub use error::{ClientError, Result};
+pub use requests::{ForgetBuilder, RecallBuilder, RememberBuilder};
@@ -0,0 +1,377 @@
+//! Builder-style requests for the core remember / recall / forget calls.
+//!
+//! Each builder wraps the generated protobuf request. Setters mirror the
+//! keyword arguments of the Python client; anything not exposed as a setter
+//! can be reached through [`into_request`](RecallBuilder::into_request) and
+//! the raw stub in [`crate::proto`].
+
+use std::time::Duration;
+
+use crate::client::MnemoClient;
+use crate::error::Result;
+use crate::proto;
+
+/// Pending `Remember` call, created by [`MnemoClient::remember`].
+#[must_use = "requests do nothing until `.send()` is awaited"]
+#[derive(Debug, Clone)]
+pub struct RememberBuilder<'a> {
+    client: &'a MnemoClient,
+    request: proto::RememberRequest,
+}
+
+impl<'a> RememberBuilder<'a> {
+    pub(crate) fn new(client: &'a MnemoClient, content: String) -> Self {
+        Self {
+            client,
+            request: proto::RememberRequest {
+                content,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// `episodic`, `semantic`, `procedural` or `working`.
+    pub fn memory_type(mut self, memory_type: impl Into<String>) -> Self {
+        self.request.memory_type = Some(memory_type.into());
+        self
+    }
+
+    /// `private`, `shared`, `public` or `global`.
+    pub fn scope(mut self, scope: impl Into<String>) -> Self {
+        self.request.scope = Some(scope.into());
+        self
+    }
+
+    pub fn importance(mut self, importance: f32) -> Self {
+        self.request.importance = Some(importance);
+        self
+    }
+
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.request.tags.push(tag.into());
+        self
+    }
+
+    pub fn tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.request.tags.extend(tags.into_iter().map(Into::into));
+        self
+    }
+
+    /// JSON-encoded metadata object.
+    pub fn metadata_json(mut self, metadata: impl Into<String>) -> Self {
+        self.request.metadata = Some(metadata.into());
+        self
+    }
+
+    pub fn thread_id(mut self, thread_id: impl Into<String>) -> Self {
+        self.request.thread_id = Some(thread_id.into());
+        self
+    }
+
+    /// Expire the memory after `ttl` (whole seconds).
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.request.ttl_seconds = Some(ttl.as_secs());
+        self
+    }
+
+    /// Override the client's default agent id.
+    pub fn agent_id(mut self, agent_id: impl Into<String>) -> Self {
+        self.request.agent_id = Some(agent_id.into());
+        self
+    }
+
+    pub fn source(mut self, source_type: impl Into<String>, source_id: Option<String>) -> Self {
+        self.request.source_type = Some(source_type.into());
+        self.request.source_id = source_id;
+        self
+    }
+
+    pub fn org_id(mut self, org_id: impl Into<String>) -> Self {
+        self.request.org_id = Some(org_id.into());
+        self
+    }
+
+    pub fn decay_rate(mut self, decay_rate: f32) -> Self {
+        self.request.decay_rate = Some(decay_rate);
+        self
+    }
+
+    pub fn created_by(mut self, created_by: impl Into<String>) -> Self {
+        self.request.created_by = Some(created_by.into());
+        self
+    }
+
+    pub fn related_to(mut self, memory_id: impl Into<String>) -> Self {
+        self.request.related_to.push(memory_id.into());
+        self
+    }
+
+    /// Protect the memory from decay, consolidation and auto-forget.
+    pub fn pinned(mut self, pinned: bool) -> Self {
+        self.request.pinned = Some(pinned);
+        self
+    }
+
+    /// The protobuf request as it would be sent, default agent id applied.
+    pub fn into_request(mut self) -> proto::RememberRequest {
+        self.client.fill_agent(&mut self.request.agent_id);
+        self.request
+    }
+
+    pub async fn send(self) -> Result<proto::RememberResponse> {
+        let client = self.client;
+        client
+            .call(true, self.into_request(), |mut c, r| async move {
+                c.remember(r).await
+            })
+            .await
+    }
+}
+
+/// Pending `Recall` call, created by [`MnemoClient::recall`].
+#[must_use = "requests do nothing until `.send()` or `.stream()` is awaited"]
+#[derive(Debug, Clone)]
+pub struct RecallBuilder<'a> {
+    client: &'a MnemoClient,
+    request: proto::RecallRequest,
+}
+
+impl<'a> RecallBuilder<'a> {
+    pub(crate) fn new(client: &'a MnemoClient, query: String) -> Self {
+        Self {
+            client,
+            request: proto::RecallRequest {
+                query,
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.request.limit = Some(limit);
+        self
+    }
+
+    /// `semantic`, `lexical`, `hybrid`, `graph`, `exact`, `auto`, ...
+    pub fn strategy(mut self, strategy: impl Into<String>) -> Self {
+        self.request.strategy = Some(strategy.into());
+        self
+    }
+
+    pub fn min_importance(mut self, min_importance: f32) -> Self {
+        self.request.min_importance = Some(min_importance);
+        self
+    }
+
+    /// Drop hits whose calibrated relevance is below this, in `[0, 1]`.
+    pub fn min_relevance(mut self, min_relevance: f32) -> Self {
+        self.request.min_relevance = Some(min_relevance);
+        self
+    }
+
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.request.tags.push(tag.into());
+        self
+    }
+
+    pub fn tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.request.tags.extend(tags.into_iter().map(Into::into));
+        self
+    }
+
+    /// Override the client's default agent id.
+    pub fn agent_id(mut self, agent_id: impl Into<String>) -> Self {
+        self.request.agent_id = Some(agent_id.into());
+        self
+    }
+
+    pub fn memory_type(mut self, memory_type: impl Into<String>) -> Self {
+        self.request.memory_type = Some(memory_type.into());
+        self
+    }
+
+    pub fn scope(mut self, scope: impl Into<String>) -> Self {
+        self.request.scope = Some(scope.into());
+        self
+    }
+
+    pub fn org_id(mut self, org_id: impl Into<String>) -> Self {
+        self.request.org_id = Some(org_id.into());
+        self
+    }
+
+    /// Per-signal weights for hybrid fusion.
+    pub fn hybrid_weights(mut self, weights: impl Into<Vec<f32>>) -> Self {
+        self.request.hybrid_weights = weights.into();
+        self
+    }
+
+    pub fn rrf_k(mut self, rrf_k: f32) -> Self {
+        self.request.rrf_k = Some(rrf_k);
+        self
+    }
+
+    /// Recall the memory state as of an RFC 3339 timestamp.
+    pub fn as_of(mut self, as_of: impl Into<String>) -> Self {
+        self.request.as_of = Some(as_of.into());
+        self
+    }
+
+    /// Attach a per-signal score breakdown to every hit.
+    pub fn explain(mut self, explain: bool) -> Self {
+        self.request.explain = Some(explain);
+        self
+    }
+
+    pub fn with_snippets(mut self, with_snippets: bool) -> Self {
+        self.request.with_snippets = Some(with_snippets);
+        self
+    }
+
+    pub fn thread_id(mut self, thread_id: impl Into<String>) -> Self {
+        self.request.thread_id = Some(thread_id.into());
+        self
+    }
+
+    /// Only return memories from the builder's thread plus global ones.
+    pub fn thread_isolation(mut self, thread_isolation: bool) -> Self {
+        self.request.thread_isolation = Some(thread_isolation);
+        self
+    }
+
+    /// Rank memories similar to this text lower.
+    pub fn exclude_query(mut self, exclude_query: impl Into<String>) -> Self {
+        self.request.exclude_query = Some(exclude_query.into());
+        self
+    }
+
+    /// Drop memories carrying this tag.
+    pub fn exclude_tag(mut self, tag: impl Into<String>) -> Self {
+        self.request.exclude_tags.push(tag.into());
+        self
+    }
+
+    /// Also search with this phrasing of the query and fuse the rankings.
+    pub fn query_variant(mut self, query: impl Into<String>) -> Self {
+        self.request.queries.push(query.into());
+        self
+    }
+
+    /// Add the server's paraphrases of the query.
+    pub fn expand_query(mut self, expand_query: bool) -> Self {
+        self.request.expand_query = Some(expand_query);
+        self
+    }
+
+    /// Return only these response fields, e.g. `["id", "score"]`. Content
+    /// and metadata come back empty unless listed.
+    pub fn fields<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let fields: Vec<String> = fields.into_iter().map(Into::into).collect();
+        self.request.fields = Some(fields.join(","));
+        self
+    }
+
+    /// Expand a saved recall profile; fields set on the builder override it.
+    pub fn profile(mut self, profile: impl Into<String>) -> Self {
+        self.request.profile = Some(profile.into());
+        self
+    }
+
+    /// The protobuf request as it would be sent, default agent id applied.
+    pub fn into_request(mut self) -> proto::RecallRequest {
+        self.client.fill_agent(&mut self.request.agent_id);
+        self.request
+    }
+
+    pub async fn send(self) -> Result<proto::RecallResponse> {
+        let client = self.client;
+        client
+            .call(false, self.into_request(), |mut c, r| async move {
+                c.recall(r).await
+            })
+            .await
+    }
+
+    /// Receive hits one message at a time, in rank order. Retries apply to
+    /// opening the stream, not to a stream that fails midway.
+    pub async fn stream(self) -> Result<tonic::Streaming<proto::ScoredMemory>> {
+        let client = self.client;
+        client
+            .call(false, self.into_request(), |mut c, r| async move {
+                c.recall_stream(r).await
+            })
+            .await
+    }
+}
+
+/// Pending `Forget` call, created by [`MnemoClient::forget`].
+#[must_use = "requests do nothing until `.send()` is awaited"]
+#[derive(Debug, Clone)]
+pub struct ForgetBuilder<'a> {
+    client: &'a MnemoClient,
+    request: proto::ForgetRequest,
+}
+
+impl<'a> ForgetBuilder<'a> {
+    pub(crate) fn new(client: &'a MnemoClient, memory_ids: Vec<String>) -> Self {
+        Self {
+            client,
+            request: proto::ForgetRequest {
+                memory_ids,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// `soft_delete` (default), `hard_delete`, `decay`, `consolidate` or
+    /// `archive`.
+    pub fn strategy(mut self, strategy: impl Into<String>) -> Self {
+        self.request.strategy = Some(strategy.into());
+        self
+    }
+
+    /// Override the client's default agent id.
+    pub fn agent_id(mut self, agent_id: impl Into<String>) -> Self {
+        self.request.agent_id = Some(agent_id.into());
+        self
+    }
+
+    /// Required to remove pinned memories.
+    pub fn force(mut self, force: bool) -> Self {
+        self.request.force = Some(force);
+        self
+    }
+
+    /// Report what would be forgotten without changing anything.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.request.dry_run = Some(dry_run);
+        self
+    }
+
+    /// The protobuf request as it would be sent, default agent id applied.
+    pub fn into_request(mut self) -> proto::ForgetRequest {
+        self.client.fill_agent(&mut self.request.agent_id);
+        self.request
+    }
+
+    pub async fn send(self) -> Result<proto::ForgetResponse> {
+        let client = self.client;
+        client
+            .call(true, self.into_request(), |mut c, r| async move {
+                c.forget(r).await
+            })
+            .await
+    }
+}
@@ -0,0 +1,170 @@
+//! Client integration tests against an in-process gRPC server.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::Code;
+
+use mnemo_client::{ClientError, MnemoClient, RetryPolicy};
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+const TOKEN: &str = "client-test-token";
+
+async fn spawn_server() -> String {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    let engine = Arc::new(MnemoEngine::new(
+        storage,
+        index,
+        embedding,
+        "server-agent".to_string(),
+        None,
+    ));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let router = mnemo_grpc::router_with_auth(engine, Some(TOKEN.to_string()));
+    tokio::spawn(router.serve_with_incoming(TcpListenerStream::new(listener)));
+    format!("http://{addr}")
+}
+
+async fn connect(endpoint: &str) -> MnemoClient {
+    MnemoClient::builder(endpoint)
+        .agent_id("client-agent")
+        .auth_token(TOKEN)
+        .pool_size(2)
+        .connect()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn remember_recall_and_forget_round_trip() {
+    let endpoint = spawn_server().await;
+    let client = connect(&endpoint).await;
+
+    let stored = client
+        .remember("the deploy window is tuesday afternoon")
+        .tags(["ops", "deploy"])
+        .importance(0.8)
+        .send()
+        .await
+        .unwrap();
+    client.remember("lunch is at noon").send().await.unwrap();
+
+    let recalled = client
+        .recall("deploy window")
+        .limit(5)
+        .tag("deploy")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(recalled.memories.len(), 1);
+    let hit = &recalled.memories[0];
+    assert_eq!(hit.id, stored.id);
+    assert_eq!(hit.agent_id, "client-agent");
+    assert_eq!(hit.tags, ["ops", "deploy"]);
+
+    let forgotten = client.forget([stored.id.clone()]).send().await.unwrap();
+    assert_eq!(forgotten.forgotten, [stored.id]);
+    let recalled = client
+        .recall("deploy window")
+        .tag("deploy")
+        .send()
+        .await
+        .unwrap();
+    assert!(recalled.memories.is_empty());
+
+    assert_eq!(client.health(false).await.unwrap().status, "ok");
+}
+
+#[tokio::test]
+async fn streaming_recall_yields_hits_in_rank_order() {
+    let endpoint = spawn_server().await;
+    let client = connect(&endpoint).await;
+    for i in 0..5 {
+        client
+            .remember(format!("incident report number {i}"))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    let unary = client
+        .recall("incident report")
+        .limit(3)
+        .send()
+        .await
+        .unwrap();
+    let mut stream = client
+        .recall("incident report")
+        .limit(3)
+        .stream()
+        .await
+        .unwrap();
+    let mut streamed = Vec::new();
+    while let Some(hit) = stream.next().await {
+        streamed.push(hit.unwrap().id);
+    }
+    let expected: Vec<_> = unary.memories.into_iter().map(|m| m.id).collect();
+    assert_eq!(streamed, expected);
+    assert_eq!(streamed.len(), 3);
+}
+
+#[tokio::test]
+async fn missing_token_is_rejected_without_retry() {
+    let endpoint = spawn_server().await;
+    let client = MnemoClient::connect(endpoint).await.unwrap();
+    let err = client.recall("anything").send().await.unwrap_err();
+    assert_eq!(err.code(), Some(Code::Unauthenticated));
+}
+
+#[tokio::test]
+async fn unavailable_reads_are_retried_with_backoff() {
+    // Bind and drop a listener to get a port nothing is serving on.
+    let port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let policy = RetryPolicy {
+        max_attempts: 3,
+        initial_backoff: Duration::from_millis(50),
+        max_backoff: Duration::from_millis(50),
+        retry_writes: false,
+    };
+    let client = MnemoClient::builder(format!("http://127.0.0.1:{port}"))
+        .retry_policy(policy)
+        .connect_lazy()
+        .unwrap();
+
+    let started = Instant::now();
+    let err = client.recall("anything").send().await.unwrap_err();
+    assert_eq!(err.code(), Some(Code::Unavailable));
+    assert!(started.elapsed() >= Duration::from_millis(100));
+
+    // Writes are not retried by default.
+    let started = Instant::now();
+    let err = client.remember("anything").send().await.unwrap_err();
+    assert_eq!(err.code(), Some(Code::Unavailable));
+    assert!(started.elapsed() < Duration::from_millis(100));
+}
+
+#[tokio::test]
+async fn invalid_options_are_rejected() {
+    assert!(matches!(
+        MnemoClient::builder("not a uri").connect_lazy(),
+        Err(ClientError::InvalidEndpoint(_))
+    ));
+    assert!(matches!(
+        MnemoClient::builder("http://127.0.0.1:1")
+            .pool_size(0)
+            .connect_lazy(),
+        Err(ClientError::Config(_))
+    ));
+}
@@ -292,6 +292,7 @@ mod tests {
             content_hash: vec![idx as u8; 32],
             prev_hash: None,
             embedding: None,
+            request_id: None,
         }
     }
 
@@ -348,6 +348,7 @@ mod tests {
             content_hash: vec![id_seed as u8; 32],
             prev_hash: None,
             embedding: None,
+            request_id: None,
         }
     }
 
@@ -512,6 +512,7 @@ mod tests {
             content_hash: vec![],
             prev_hash: None,
             embedding: None,
+            request_id: None,
         }
     }
 
@@ -548,6 +549,7 @@ mod tests {
             content_hash: vec![],
             prev_hash: None,
             embedding: None,
+            request_id: None,
         }
     }
 
@@ -572,6 +574,7 @@ mod tests {
             content_hash: vec![],
             prev_hash: None,
             embedding: None,
+            request_id: None,
         }
     }
 
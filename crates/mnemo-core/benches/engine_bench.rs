@@ -39,6 +39,10 @@ fn remember_throughput(c: &mut Criterion) {
                     related_to: None,
                     decay_rate: None,
                     created_by: None,
+                    pinned: None,
+                    valid_from: None,
+                    valid_until: None,
+                    channel: None,
                 };
                 engine.remember(request).await.unwrap();
             });
@@ -72,6 +76,10 @@ fn recall_latency(c: &mut Criterion) {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             };
             engine.remember(request).await.unwrap();
         }
@@ -94,6 +102,7 @@ fn recall_latency(c: &mut Criterion) {
                     temporal_range: None,
                     recency_half_life_hours: None,
                     hybrid_weights: None,
+                    hybrid: None,
                     rrf_k: None,
                     as_of: None,
                     explain: None,
@@ -104,6 +113,22 @@ fn recall_latency(c: &mut Criterion) {
                     evidence_budget: None,
                     retained_token_budget: None,
                     domain_scope: None,
+                    text_query_mode: None,
+                    text_field_boosts: None,
+                    with_snippets: None,
+                    profile: None,
+                    include: None,
+                    thread_id: None,
+                    thread_isolation: None,
+                    exclude_query: None,
+                    exclude_tags: None,
+                    queries: None,
+                    expand_query: None,
+                    channel: None,
+                    fields: None,
+                    deadline_ms: None,
+                    agent_ids: None,
+                    min_relevance: None,
                 };
                 engine.recall(request).await.unwrap();
             });
@@ -134,6 +159,10 @@ fn verify_chain_performance(c: &mut Criterion) {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             };
             engine.remember(request).await.unwrap();
         }
@@ -181,6 +210,10 @@ fn hybrid_recall_latency(c: &mut Criterion) {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             };
             engine.remember(request).await.unwrap();
         }
@@ -203,6 +236,7 @@ fn hybrid_recall_latency(c: &mut Criterion) {
                     temporal_range: None,
                     recency_half_life_hours: None,
                     hybrid_weights: None,
+                    hybrid: None,
                     rrf_k: None,
                     as_of: None,
                     explain: None,
@@ -213,6 +247,22 @@ fn hybrid_recall_latency(c: &mut Criterion) {
                     evidence_budget: None,
                     retained_token_budget: None,
                     domain_scope: None,
+                    text_query_mode: None,
+                    text_field_boosts: None,
+                    with_snippets: None,
+                    profile: None,
+                    include: None,
+                    thread_id: None,
+                    thread_isolation: None,
+                    exclude_query: None,
+                    exclude_tags: None,
+                    queries: None,
+                    expand_query: None,
+                    channel: None,
+                    fields: None,
+                    deadline_ms: None,
+                    agent_ids: None,
+                    min_relevance: None,
                 };
                 engine.recall(request).await.unwrap();
             });
@@ -248,6 +298,10 @@ fn graph_traversal_latency(c: &mut Criterion) {
                 related_to: related,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             };
             let resp = engine.remember(request).await.unwrap();
             ids.push(resp.id);
@@ -271,6 +325,7 @@ fn graph_traversal_latency(c: &mut Criterion) {
                     temporal_range: None,
                     recency_half_life_hours: None,
                     hybrid_weights: None,
+                    hybrid: None,
                     rrf_k: None,
                     as_of: None,
                     explain: None,
@@ -281,6 +336,22 @@ fn graph_traversal_latency(c: &mut Criterion) {
                     evidence_budget: None,
                     retained_token_budget: None,
                     domain_scope: None,
+                    text_query_mode: None,
+                    text_field_boosts: None,
+                    with_snippets: None,
+                    profile: None,
+                    include: None,
+                    thread_id: None,
+                    thread_isolation: None,
+                    exclude_query: None,
+                    exclude_tags: None,
+                    queries: None,
+                    expand_query: None,
+                    channel: None,
+                    fields: None,
+                    deadline_ms: None,
+                    agent_ids: None,
+                    min_relevance: None,
                 };
                 engine.recall(request).await.unwrap();
             });
@@ -313,6 +384,10 @@ fn checkpoint_restore_latency(c: &mut Criterion) {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             };
             engine.remember(request).await.unwrap();
         }
@@ -345,6 +420,49 @@ fn checkpoint_restore_latency(c: &mut Criterion) {
     });
 }
 
+fn first_recall_latency(c: &mut Criterion) {
+    use criterion::BatchSize;
+    use mnemo_core::cache::MemoryCache;
+    use mnemo_core::query::prefetch::PrefetchRequest;
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let cache = Arc::new(MemoryCache::new(3600, 1000));
+    let engine = make_engine().with_cache(cache.clone());
+
+    rt.block_on(async {
+        for i in 0..200 {
+            let mut request =
+                RememberRequest::new(format!("Thread memory {i} for first recall benchmark"));
+            request.importance = Some((i % 10) as f32 / 10.0);
+            request.thread_id = Some("bench-prefetch-thread".to_string());
+            engine.remember(request).await.unwrap();
+        }
+    });
+    let recall = || {
+        rt.block_on(async {
+            let mut request = RecallRequest::new("first recall benchmark".to_string());
+            request.limit = Some(10);
+            engine.recall(request).await.unwrap();
+        })
+    };
+
+    // The first recall after a cold start, with and without a prefetch.
+    c.bench_function("first_recall_cold", |b| {
+        b.iter_batched(|| cache.clear(), |()| recall(), BatchSize::SmallInput);
+    });
+    c.bench_function("first_recall_prefetched", |b| {
+        b.iter_batched(
+            || {
+                cache.clear();
+                let request = PrefetchRequest::new("bench-prefetch-thread".to_string());
+                rt.block_on(engine.prefetch(request)).unwrap();
+            },
+            |()| recall(),
+            BatchSize::SmallInput,
+        );
+    });
+}
+
 fn concurrent_agents_throughput(c: &mut Criterion) {
     let rt = tokio::runtime::Runtime::new().unwrap();
     let engine = make_engine();
@@ -371,6 +489,10 @@ fn concurrent_agents_throughput(c: &mut Criterion) {
                             related_to: None,
                             decay_rate: None,
                             created_by: None,
+                            pinned: None,
+                            valid_from: None,
+                            valid_until: None,
+                            channel: None,
                         };
                         engine.remember(request).await.unwrap();
                     }
@@ -406,6 +528,10 @@ fn forget_throughput(c: &mut Criterion) {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             };
             let resp = engine.remember(request).await.unwrap();
             ids.push(resp.id);
@@ -424,6 +550,8 @@ fn forget_throughput(c: &mut Criterion) {
                         agent_id: None,
                         strategy: None,
                         criteria: None,
+                        force: None,
+                        dry_run: None,
                     };
                     let _ = engine.forget(request).await;
                     batch_start += 10;
@@ -441,6 +569,7 @@ criterion_group!(
     hybrid_recall_latency,
     graph_traversal_latency,
     checkpoint_restore_latency,
+    first_recall_latency,
     concurrent_agents_throughput,
     forget_throughput
 );
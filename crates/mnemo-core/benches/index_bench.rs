@@ -0,0 +1,132 @@
+//! usearch vs the disk-resident IVF-PQ index.
+//!
+//! Both indexes are filled with the same clustered random vectors. Before
+//! the criterion runs (which measure per-query latency, i.e. 1/QPS) the
+//! bench prints recall@10 against exact brute force and the resident
+//! memory of each index. Set `MNEMO_INDEX_BENCH_N` to change the corpus
+//! size (default 20 000).
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use uuid::Uuid;
+
+use mnemo_core::index::VectorIndex;
+use mnemo_core::index::ivf_pq::{IvfPqConfig, IvfPqIndex};
+use mnemo_core::index::usearch::UsearchIndex;
+
+const DIMS: usize = 128;
+const K: usize = 10;
+const QUERIES: usize = 100;
+
+/// xorshift, so the corpus is identical across runs.
+struct Rng(u64);
+
+impl Rng {
+    fn next_f32(&mut self) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 40) as f32 / (1u64 << 24) as f32 - 0.5
+    }
+
+    fn vector(&mut self) -> Vec<f32> {
+        (0..DIMS).map(|_| self.next_f32()).collect()
+    }
+}
+
+/// `n` vectors scattered around 64 random centers.
+fn corpus(rng: &mut Rng, n: usize) -> Vec<Vec<f32>> {
+    let centers: Vec<Vec<f32>> = (0..64).map(|_| rng.vector()).collect();
+    (0..n)
+        .map(|i| {
+            centers[i % centers.len()]
+                .iter()
+                .map(|c| c + 0.3 * rng.next_f32())
+                .collect()
+        })
+        .collect()
+}
+
+fn cosine_distance(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let na = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let nb = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    1.0 - dot / (na * nb)
+}
+
+fn exact_top_k(ids: &[Uuid], vectors: &[Vec<f32>], query: &[f32]) -> Vec<Uuid> {
+    let mut scored: Vec<(f32, Uuid)> = vectors
+        .iter()
+        .zip(ids)
+        .map(|(v, id)| (cosine_distance(v, query), *id))
+        .collect();
+    scored.sort_by(|a, b| a.0.total_cmp(&b.0));
+    scored.into_iter().take(K).map(|(_, id)| id).collect()
+}
+
+fn recall_at_k(index: &dyn VectorIndex, queries: &[Vec<f32>], truth: &[Vec<Uuid>]) -> f64 {
+    let mut hits = 0;
+    for (query, expected) in queries.iter().zip(truth) {
+        let found = index.search(query, K).unwrap();
+        hits += found.iter().filter(|(id, _)| expected.contains(id)).count();
+    }
+    hits as f64 / (queries.len() * K) as f64
+}
+
+fn index_comparison(c: &mut Criterion) {
+    let n: usize = std::env::var("MNEMO_INDEX_BENCH_N")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(20_000);
+    let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+    let vectors = corpus(&mut rng, n);
+    let ids: Vec<Uuid> = (0..n).map(|_| Uuid::now_v7()).collect();
+    let queries = corpus(&mut rng, QUERIES);
+    let truth: Vec<Vec<Uuid>> = queries
+        .iter()
+        .map(|q| exact_top_k(&ids, &vectors, q))
+        .collect();
+
+    let usearch = UsearchIndex::with_capacity(DIMS, n).unwrap();
+    for (id, v) in ids.iter().zip(&vectors) {
+        usearch.add(*id, v).unwrap();
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let config = IvfPqConfig {
+        nlist: ((n as f64).sqrt() as usize).clamp(16, 4096),
+        nprobe: 16,
+        subvectors: 32,
+        train_size: n.min(20_000),
+        ..Default::default()
+    };
+    let ivf_pq = IvfPqIndex::open(dir.path(), DIMS, config).unwrap();
+    for (id, v) in ids.iter().zip(&vectors) {
+        ivf_pq.add(*id, v).unwrap();
+    }
+    ivf_pq.save(dir.path()).unwrap();
+
+    println!("index comparison, {n} vectors x {DIMS} dims, recall@{K} over {QUERIES} queries");
+    println!(
+        "  usearch: recall {:.3}, resident {} KiB",
+        recall_at_k(&usearch, &queries, &truth),
+        usearch.memory_usage() / 1024
+    );
+    println!(
+        "  ivf_pq:  recall {:.3}, resident {} KiB",
+        recall_at_k(&ivf_pq, &queries, &truth),
+        ivf_pq.memory_bytes() / 1024
+    );
+
+    let mut group = c.benchmark_group("vector_index_search");
+    let indexes: [(&str, &dyn VectorIndex); 2] = [("usearch", &usearch), ("ivf_pq", &ivf_pq)];
+    for (name, index) in indexes {
+        let mut q_iter = queries.iter().cycle();
+        group.bench_function(BenchmarkId::new(name, n), |b| {
+            b.iter(|| index.search(q_iter.next().unwrap(), K).unwrap());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, index_comparison);
+criterion_main!(benches);
@@ -114,6 +114,10 @@ fn seed(engine: &MnemoEngine, dataset: &[LongMemRecord], rt: &tokio::runtime::Ru
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             };
             engine.remember(req).await.unwrap();
         }
@@ -135,6 +139,7 @@ fn build_recall(query: &str, with_provenance: Option<bool>) -> RecallRequest {
         temporal_range: None,
         recency_half_life_hours: None,
         hybrid_weights: None,
+        hybrid: None,
         rrf_k: None,
         as_of: None,
         explain: None,
@@ -145,6 +150,22 @@ fn build_recall(query: &str, with_provenance: Option<bool>) -> RecallRequest {
         evidence_budget: None,
         retained_token_budget: None,
         domain_scope: None,
+        text_query_mode: None,
+        text_field_boosts: None,
+        with_snippets: None,
+        profile: None,
+        include: None,
+        thread_id: None,
+        thread_isolation: None,
+        exclude_query: None,
+        exclude_tags: None,
+        queries: None,
+        expand_query: None,
+        channel: None,
+        fields: None,
+        deadline_ms: None,
+        agent_ids: None,
+        min_relevance: None,
     }
 }
 
@@ -103,6 +103,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         };
         engine.remember(req).await?;
     }
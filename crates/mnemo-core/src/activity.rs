@@ -0,0 +1,122 @@
+//! Shared activity tracker for the idle-timeout watchdog.
+//!
+//! Every transport (MCP, REST, gRPC, pgwire) touches the same
+//! [`ActivityTracker`] so scale-to-zero only fires when *no* protocol has
+//! seen traffic. Long-running requests hold an [`ActivityGuard`] for their
+//! whole duration: the tracker never reports idle while a request is in
+//! flight, even if it started before the timeout window.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Cheaply cloneable handle recording the last time any transport served a
+/// request, plus the number of requests currently in flight.
+#[derive(Debug, Clone)]
+pub struct ActivityTracker {
+    last_activity: Arc<AtomicU64>,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl ActivityTracker {
+    /// Create a tracker whose last activity is "now".
+    pub fn new() -> Self {
+        Self {
+            last_activity: Arc::new(AtomicU64::new(now_secs())),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Record activity at the current time.
+    pub fn touch(&self) {
+        self.last_activity.store(now_secs(), Ordering::Relaxed);
+    }
+
+    /// Unix seconds of the most recent [`touch`](Self::touch).
+    pub fn last_activity_secs(&self) -> u64 {
+        self.last_activity.load(Ordering::Relaxed)
+    }
+
+    /// Number of requests currently holding an [`ActivityGuard`].
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Mark the start of a request. The returned guard keeps the tracker
+    /// busy until it is dropped, and touches it again on completion.
+    pub fn begin(&self) -> ActivityGuard {
+        self.touch();
+        self.in_flight.fetch_add(1, Ordering::Relaxed);
+        ActivityGuard {
+            tracker: self.clone(),
+        }
+    }
+
+    /// `true` when no request is in flight and nothing has touched the
+    /// tracker for at least `timeout`.
+    pub fn is_idle(&self, timeout: Duration) -> bool {
+        if self.in_flight() > 0 {
+            return false;
+        }
+        now_secs().saturating_sub(self.last_activity_secs()) >= timeout.as_secs()
+    }
+}
+
+impl Default for ActivityTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// RAII guard returned by [`ActivityTracker::begin`].
+#[derive(Debug)]
+pub struct ActivityGuard {
+    tracker: ActivityTracker,
+}
+
+impl Drop for ActivityGuard {
+    fn drop(&mut self) {
+        self.tracker.in_flight.fetch_sub(1, Ordering::Relaxed);
+        self.tracker.touch();
+    }
+}
+
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fresh_tracker_is_idle_only_for_zero_timeout() {
+        let tracker = ActivityTracker::new();
+        assert!(tracker.is_idle(Duration::ZERO));
+        assert!(!tracker.is_idle(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn stale_tracker_is_idle() {
+        let tracker = ActivityTracker::new();
+        tracker.last_activity.store(0, Ordering::Relaxed);
+        assert!(tracker.is_idle(Duration::from_secs(60)));
+        tracker.touch();
+        assert!(!tracker.is_idle(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn in_flight_request_blocks_idle() {
+        let tracker = ActivityTracker::new();
+        let guard = tracker.clone().begin();
+        tracker.last_activity.store(0, Ordering::Relaxed);
+        assert_eq!(tracker.in_flight(), 1);
+        assert!(!tracker.is_idle(Duration::ZERO));
+        drop(guard);
+        assert_eq!(tracker.in_flight(), 0);
+        assert!(!tracker.is_idle(Duration::from_secs(60)));
+    }
+}
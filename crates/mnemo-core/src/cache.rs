@@ -1,112 +1,274 @@
-//! In-memory LRU cache for memory records.
+//! In-memory cache for memory records.
 //!
 //! Wraps StorageBackend lookups with an in-process cache. Does not require
-//! Redis — uses a simple `HashMap` with TTL-based eviction.
+//! Redis — uses a simple `HashMap` with TTL-based expiry. When full, an
+//! [`EvictionPolicy`] picks the entry to drop. Hits, misses, evictions and
+//! the approximate size of the working set are counted in [`CacheStats`].
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::model::memory::MemoryRecord;
 
-/// A simple in-process cache for memory records with TTL-based eviction.
+/// How a full [`MemoryCache`] picks the entry to drop.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EvictionPolicy {
+    /// The least recently used entry.
+    #[default]
+    Lru,
+    /// LRU-K: the entry whose `k`-th most recent use is oldest, and before
+    /// any of those, entries used fewer than `k` times. One-off reads, such
+    /// as a replay walking a thread's history, then cannot flush the
+    /// memories an agent keeps coming back to.
+    LruK { k: usize },
+}
+
+impl EvictionPolicy {
+    /// Uses remembered per entry.
+    fn history(self) -> usize {
+        match self {
+            EvictionPolicy::Lru => 1,
+            EvictionPolicy::LruK { k } => k.max(1),
+        }
+    }
+}
+
+/// Counters since the cache was created, and its current size.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries dropped to make room.
+    pub evictions: u64,
+    /// Entries dropped because their TTL ran out.
+    pub expirations: u64,
+    pub entries: usize,
+    pub capacity: usize,
+    /// Approximate memory held by the cached records.
+    pub bytes: usize,
+}
+
+impl CacheStats {
+    /// Hits over lookups; 0 before the first lookup.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// A simple in-process cache for memory records with TTL-based expiry.
 pub struct MemoryCache {
-    entries: Mutex<HashMap<Uuid, CacheEntry>>,
+    state: Mutex<CacheState>,
     ttl: Duration,
     max_entries: usize,
+    policy: EvictionPolicy,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<Uuid, CacheEntry>,
+    bytes: usize,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+    expirations: u64,
 }
 
 struct CacheEntry {
     record: MemoryRecord,
     inserted_at: Instant,
+    /// The most recent uses, oldest first; insertion counts as one.
+    uses: VecDeque<Instant>,
+    bytes: usize,
+}
+
+impl CacheState {
+    fn remove(&mut self, id: Uuid) -> Option<CacheEntry> {
+        let entry = self.entries.remove(&id)?;
+        self.bytes -= entry.bytes;
+        Some(entry)
+    }
 }
 
 impl MemoryCache {
-    /// Create a new cache with the given TTL and max entry count.
+    /// Create a new LRU cache with the given TTL and max entry count.
     pub fn new(ttl_seconds: u64, max_entries: usize) -> Self {
         Self {
-            entries: Mutex::new(HashMap::new()),
+            state: Mutex::new(CacheState::default()),
             ttl: Duration::from_secs(ttl_seconds),
             max_entries,
+            policy: EvictionPolicy::default(),
         }
     }
 
+    /// Evict with `policy` instead of plain LRU.
+    pub fn with_policy(mut self, policy: EvictionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     /// Get a cached record by ID. Returns None if not cached or expired.
     pub fn get(&self, id: Uuid) -> Option<MemoryRecord> {
-        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
-        if let Some(entry) = entries.get(&id) {
-            if entry.inserted_at.elapsed() < self.ttl {
-                return Some(entry.record.clone());
+        let mut guard = self.lock();
+        let state = &mut *guard;
+        let now = Instant::now();
+        let expired = match state.entries.get_mut(&id) {
+            Some(entry) if now.duration_since(entry.inserted_at) < self.ttl => {
+                if entry.uses.len() == self.policy.history() {
+                    entry.uses.pop_front();
+                }
+                entry.uses.push_back(now);
+                let record = entry.record.clone();
+                state.hits += 1;
+                return Some(record);
             }
-            // Expired — remove it
-            entries.remove(&id);
+            Some(_) => true,
+            None => false,
+        };
+        if expired {
+            state.remove(id);
+            state.expirations += 1;
         }
+        state.misses += 1;
         None
     }
 
     /// Insert or update a record in the cache.
     pub fn put(&self, record: MemoryRecord) {
-        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let mut guard = self.lock();
+        let state = &mut *guard;
+        let now = Instant::now();
+        let bytes = approximate_size(&record);
 
-        // Evict expired entries if we're at capacity
-        if entries.len() >= self.max_entries {
-            let now = Instant::now();
-            entries.retain(|_, e| now.duration_since(e.inserted_at) < self.ttl);
+        if let Some(entry) = state.entries.get_mut(&record.id) {
+            let previous = std::mem::replace(&mut entry.bytes, bytes);
+            entry.record = record;
+            entry.inserted_at = now;
+            state.bytes = state.bytes - previous + bytes;
+            return;
+        }
+        if self.max_entries == 0 {
+            return;
         }
 
-        // If still at capacity, evict oldest
-        if entries.len() >= self.max_entries
-            && let Some(&oldest_id) = entries
+        // Drop expired entries before evicting live ones.
+        if state.entries.len() >= self.max_entries {
+            let expired: Vec<Uuid> = state
+                .entries
                 .iter()
-                .min_by_key(|(_, e)| e.inserted_at)
-                .map(|(id, _)| id)
-        {
-            entries.remove(&oldest_id);
+                .filter(|(_, e)| now.duration_since(e.inserted_at) >= self.ttl)
+                .map(|(id, _)| *id)
+                .collect();
+            state.expirations += expired.len() as u64;
+            for id in expired {
+                state.remove(id);
+            }
         }
-
-        // If still at capacity after eviction attempts, skip insert to prevent unbounded growth
-        if entries.len() >= self.max_entries && !entries.contains_key(&record.id) {
-            return;
+        while state.entries.len() >= self.max_entries {
+            let Some(victim) = self.victim(state) else {
+                break;
+            };
+            state.remove(victim);
+            state.evictions += 1;
         }
 
-        entries.insert(
+        state.bytes += bytes;
+        state.entries.insert(
             record.id,
             CacheEntry {
                 record,
-                inserted_at: Instant::now(),
+                inserted_at: now,
+                uses: VecDeque::from([now]),
+                bytes,
             },
         );
     }
 
+    /// The entry the policy drops next.
+    fn victim(&self, state: &CacheState) -> Option<Uuid> {
+        let k = self.policy.history();
+        state
+            .entries
+            .iter()
+            .min_by_key(|(_, e)| {
+                // Too few uses sorts first; then by the k-th most recent
+                // use, which for plain LRU is the last one.
+                let full = e.uses.len() >= k;
+                let kth = if full { e.uses.front() } else { e.uses.back() };
+                (full, kth.copied())
+            })
+            .map(|(id, _)| *id)
+    }
+
     /// Invalidate (remove) a cached record.
     pub fn invalidate(&self, id: Uuid) {
-        self.entries
-            .lock()
-            .unwrap_or_else(|e| e.into_inner())
-            .remove(&id);
+        self.lock().remove(id);
     }
 
-    /// Clear all cached entries.
+    /// Clear all cached entries. The counters are kept.
     pub fn clear(&self) {
-        self.entries
-            .lock()
-            .unwrap_or_else(|e| e.into_inner())
-            .clear();
+        let mut state = self.lock();
+        state.entries.clear();
+        state.bytes = 0;
     }
 
     /// Number of entries currently in cache.
     pub fn len(&self) -> usize {
-        self.entries.lock().unwrap_or_else(|e| e.into_inner()).len()
+        self.lock().entries.len()
     }
 
     /// Whether the cache is empty.
     pub fn is_empty(&self) -> bool {
-        self.entries
-            .lock()
-            .unwrap_or_else(|e| e.into_inner())
-            .is_empty()
+        self.lock().entries.is_empty()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let state = self.lock();
+        CacheStats {
+            hits: state.hits,
+            misses: state.misses,
+            evictions: state.evictions,
+            expirations: state.expirations,
+            entries: state.entries.len(),
+            capacity: self.max_entries,
+            bytes: state.bytes,
+        }
+    }
+}
+
+/// Heap and inline bytes of a record, give or take allocator overhead.
+fn approximate_size(record: &MemoryRecord) -> usize {
+    std::mem::size_of::<MemoryRecord>()
+        + record.agent_id.len()
+        + record.content.len()
+        + record.tags.iter().map(String::len).sum::<usize>()
+        + json_size(&record.metadata)
+        + record.embedding.as_ref().map_or(0, |e| e.len() * 4)
+        + record.content_hash.len()
+        + record.prev_hash.as_ref().map_or(0, Vec::len)
+        + record.created_at.len()
+        + record.updated_at.len()
+}
+
+fn json_size(value: &serde_json::Value) -> usize {
+    match value {
+        serde_json::Value::String(s) => s.len(),
+        serde_json::Value::Array(items) => items.iter().map(json_size).sum(),
+        serde_json::Value::Object(map) => map.iter().map(|(k, v)| k.len() + json_size(v)).sum(),
+        _ => 8,
     }
 }
 
@@ -145,6 +307,12 @@ mod tests {
             quarantined: false,
             quarantine_reason: None,
             decay_function: None,
+            pinned: false,
+            valid_from: None,
+            valid_until: None,
+            curation: None,
+            attestation: None,
+            channel: None,
         }
     }
 
@@ -193,4 +361,52 @@ mod tests {
         // One should have been evicted
         assert_eq!(cache.len(), 2);
     }
+
+    #[test]
+    fn test_cache_stats() {
+        let cache = MemoryCache::new(60, 1);
+        let id1 = Uuid::now_v7();
+        cache.put(make_record(id1));
+        cache.get(id1);
+        cache.get(Uuid::now_v7());
+        cache.put(make_record(Uuid::now_v7()));
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.evictions), (1, 1, 1));
+        assert_eq!(stats.entries, 1);
+        assert_eq!(stats.capacity, 1);
+        assert!(stats.bytes > 0);
+        assert_eq!(stats.hit_rate(), 0.5);
+
+        cache.clear();
+        assert_eq!(cache.stats().bytes, 0);
+    }
+
+    #[test]
+    fn test_lru_evicts_least_recently_used() {
+        let cache = MemoryCache::new(60, 2);
+        let (id1, id2, id3) = (Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7());
+        cache.put(make_record(id1));
+        cache.put(make_record(id2));
+        cache.get(id1);
+
+        cache.put(make_record(id3));
+        assert!(cache.get(id1).is_some());
+        assert!(cache.get(id2).is_none());
+    }
+
+    #[test]
+    fn test_lru_k_keeps_the_working_set() {
+        let cache = MemoryCache::new(60, 2).with_policy(EvictionPolicy::LruK { k: 2 });
+        let hot = Uuid::now_v7();
+        cache.put(make_record(hot));
+        cache.get(hot);
+
+        // A scan of one-off reads never displaces the entry used twice;
+        // plain LRU would have dropped it on the second read.
+        for _ in 0..5 {
+            cache.put(make_record(Uuid::now_v7()));
+        }
+        assert!(cache.get(hot).is_some());
+    }
 }
@@ -0,0 +1,944 @@
+//! Declarative engine configuration.
+//!
+//! [`MnemoEngineConfig`] captures everything the front-ends (CLI, Python
+//! bindings, server crates) used to wire up with a chain of `with_*` calls:
+//! storage, vector index, embedding provider, full-text index, encryption,
+//! cache, cold storage, feature toggles, request limits, automatic checkpoints, scheduled
+//! exports and policy scripts.
+//! It deserializes with serde (the CLI reads it from TOML) so deployments
+//! can ship one config file, and [`MnemoEngine::from_config`] turns it into
+//! an engine.
+//!
+//! Backends that live outside `mnemo-core` (PostgreSQL, custom storage)
+//! build their storage and index themselves and call
+//! [`MnemoEngineConfig::apply`] to layer the rest of the configuration on.
+//! Runtime-only attachments — provenance signers, scorers, checkers,
+//! activity trackers, compiled hooks — stay on the builder.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cache::{EvictionPolicy, MemoryCache};
+use crate::embedding::openai::OpenAiEmbedding;
+use crate::embedding::pipeline::{EmbeddingPipelineConfig, PipelinedEmbedding};
+use crate::embedding::{DeterministicEmbedding, EmbeddingProvider, HashEmbedding, NoopEmbedding};
+use crate::encryption::ContentEncryption;
+use crate::encryption::keys::{
+    DEFAULT_KEY_ENV_PREFIX, EnvKeyProvider, FileKeyProvider, KeyProvider, KeyRegistry,
+    validate_key_id,
+};
+use crate::error::{Error, Result};
+use crate::hooks::script::ScriptHook;
+use crate::hooks::{HookErrorPolicy, HookOptions};
+use crate::index::VectorIndex;
+use crate::index::ivf_pq::{IvfPqConfig, IvfPqIndex};
+use crate::index::sharded::{ShardKey, ShardedIndex};
+use crate::index::usearch::UsearchIndex;
+use crate::ingest::UrlFetchPolicy;
+use crate::query::access_log::AccessLogPolicy;
+use crate::query::attestation::AttestationPolicy;
+use crate::query::auto_checkpoint::AutoCheckpointConfig;
+use crate::query::calibration::CalibrationConfig;
+use crate::query::checkpoint::DEFAULT_CHECKPOINT_SNAPSHOT_INTERVAL;
+use crate::query::curation::CurationPolicy;
+use crate::query::dedup::DedupPolicy;
+use crate::query::event_retention::EventRetentionPolicy;
+use crate::query::export::ExportSchedule;
+use crate::query::ingestion_filter::{IngestionFilter, IngestionFilterConfig};
+use crate::query::latency_slo::LatencySloConfig;
+use crate::query::limits::LimitsConfig;
+use crate::query::privacy::SharedRecallPrivacy;
+use crate::query::pseudonym::Pseudonymizer;
+use crate::query::retention_lock::RetentionLockPolicy;
+use crate::query::shadow_recall::ShadowRecallConfig;
+use crate::query::share_link::ShareLinkSigner;
+use crate::query::write_anomaly::WriteAnomalyPolicy;
+use crate::query::{DEFAULT_PROCEDURAL_IMPORTANCE_FLOOR, DEFAULT_TTL_WORKING_SECONDS, MnemoEngine};
+use crate::search::tantivy_index::TantivyFullTextIndex;
+use crate::storage::StorageBackend;
+use crate::storage::cold::{ColdStorageConfig, InMemoryColdStorage};
+use crate::storage::duckdb::DuckDbStorage;
+
+/// Default embedding width (OpenAI `text-embedding-3-small`).
+pub const DEFAULT_DIMENSIONS: usize = 1536;
+
+/// Default OpenAI embedding model.
+pub const DEFAULT_OPENAI_MODEL: &str = "text-embedding-3-small";
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MnemoEngineConfig {
+    pub agent_id: String,
+    pub org_id: Option<String>,
+    /// Width of every vector: embedding output and index dimensions.
+    pub dimensions: usize,
+    pub storage: StorageConfig,
+    pub index: IndexConfig,
+    pub embedding: EmbeddingConfig,
+    /// Chunked, parallel `embed_batch` (`[embedding_pipeline]`); off by
+    /// default.
+    pub embedding_pipeline: Option<EmbeddingPipelineConfig>,
+    pub full_text: FullTextConfig,
+    /// AES-256-GCM key (64 hex chars) for at-rest content encryption.
+    pub encryption_key: Option<String>,
+    /// Per-org encryption keys (`[org_keys]`), preferred over
+    /// `encryption_key` for the orgs they list.
+    pub org_keys: Option<OrgKeysConfig>,
+    pub cache: Option<CacheConfig>,
+    pub cold_storage: Option<ColdStorageSettings>,
+    pub features: FeatureConfig,
+    pub hooks: HooksConfig,
+    /// Request size limits; each can be overridden on its own.
+    pub limits: LimitsConfig,
+    /// Per-agent automatic checkpoint policies; none by default.
+    pub auto_checkpoint: AutoCheckpointConfig,
+    /// Scheduled exports (`[[exports]]` tables); none by default.
+    pub exports: Vec<ExportSchedule>,
+    /// Start with writes frozen (see [`MnemoEngine::set_read_only`]).
+    pub read_only: bool,
+}
+
+impl Default for MnemoEngineConfig {
+    fn default() -> Self {
+        Self {
+            agent_id: "default".to_string(),
+            org_id: None,
+            dimensions: DEFAULT_DIMENSIONS,
+            storage: StorageConfig::default(),
+            index: IndexConfig::default(),
+            embedding: EmbeddingConfig::default(),
+            embedding_pipeline: None,
+            full_text: FullTextConfig::default(),
+            encryption_key: None,
+            org_keys: None,
+            cache: None,
+            cold_storage: None,
+            features: FeatureConfig::default(),
+            hooks: HooksConfig::default(),
+            limits: LimitsConfig::default(),
+            auto_checkpoint: AutoCheckpointConfig::default(),
+            exports: Vec::new(),
+            read_only: false,
+        }
+    }
+}
+
+/// Where memories, events and metadata live.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum StorageConfig {
+    /// Ephemeral in-process DuckDB.
+    #[default]
+    Memory,
+    /// File-backed DuckDB.
+    #[serde(rename = "duckdb")]
+    DuckDb { path: PathBuf },
+    /// Pure-Rust maps with no persistence and no native libraries (needs
+    /// the `memory-storage` feature). For tests and throwaway agents.
+    InMemory,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IndexConfig {
+    /// `.usearch` snapshot loaded on startup if it exists. `None` keeps the
+    /// index purely in memory.
+    pub path: Option<PathBuf>,
+    /// Log mutations to `<path>.wal` and replay them on startup so vectors
+    /// added after the last save survive a crash. Ignored without `path`.
+    pub wal: bool,
+    /// Split the index into per-agent or per-org shards. `path` is then a
+    /// directory of shard files, and `wal` is ignored.
+    pub shards: Option<ShardingConfig>,
+    /// Use the disk-resident IVF-PQ index instead of usearch, for corpora
+    /// too large for RAM. Needs `path` (a directory); `wal` is ignored.
+    pub ivf_pq: Option<IvfPqConfig>,
+}
+
+impl Default for IndexConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            wal: true,
+            shards: None,
+            ivf_pq: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShardingConfig {
+    pub key: ShardKey,
+    /// Shards kept in memory before the least recently used is saved and
+    /// dropped. `None` keeps every shard loaded. Needs `index.path`.
+    pub max_loaded_shards: Option<usize>,
+}
+
+impl IndexConfig {
+    /// `<path>.wal`, when a snapshot path is configured.
+    pub fn wal_path(&self) -> Option<PathBuf> {
+        self.path.as_ref().map(|p| {
+            let mut wal = p.clone().into_os_string();
+            wal.push(".wal");
+            PathBuf::from(wal)
+        })
+    }
+
+    /// Build the usearch index, loading the snapshot and replaying the WAL
+    /// when configured.
+    pub fn build(&self, dimensions: usize) -> Result<UsearchIndex> {
+        let mut index = UsearchIndex::new(dimensions)?;
+        if let Some(ref path) = self.path
+            && path.exists()
+        {
+            index.load(path)?;
+            tracing::info!("Loaded vector index ({} vectors)", index.len());
+        }
+        if self.wal
+            && let Some(wal_path) = self.wal_path()
+        {
+            let replayed = index.replay_wal(&wal_path)?;
+            if replayed > 0 {
+                tracing::info!(
+                    "Replayed {replayed} vector WAL entries ({} vectors)",
+                    index.len()
+                );
+            }
+            index = index.with_wal(&wal_path)?;
+        }
+        Ok(index)
+    }
+
+    /// Build the configured index: an [`IvfPqIndex`] when `ivf_pq` is set, a
+    /// [`ShardedIndex`] when `shards` is set, otherwise the usearch index
+    /// from [`build`](Self::build).
+    pub fn build_shared(&self, dimensions: usize) -> Result<Arc<dyn VectorIndex>> {
+        if let Some(ref ivf_pq) = self.ivf_pq {
+            let Some(ref dir) = self.path else {
+                return Err(Error::Validation(
+                    "index.ivf_pq needs index.path".to_string(),
+                ));
+            };
+            let index = IvfPqIndex::open(dir, dimensions, ivf_pq.clone())?;
+            tracing::info!("Opened IVF-PQ vector index ({} vectors)", index.len());
+            return Ok(Arc::new(index));
+        }
+        let Some(ref sharding) = self.shards else {
+            return Ok(Arc::new(self.build(dimensions)?));
+        };
+        let mut index = ShardedIndex::new(dimensions, sharding.key);
+        if let Some(ref dir) = self.path {
+            index = index.with_dir(dir)?;
+            tracing::info!(
+                "Opened sharded vector index ({} vectors in {} shards)",
+                index.len(),
+                index.shard_names().len()
+            );
+        }
+        if let Some(max) = sharding.max_loaded_shards {
+            index = index.with_max_loaded_shards(max);
+        }
+        Ok(Arc::new(index))
+    }
+}
+
+/// Which embedding provider to use.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "provider", rename_all = "snake_case")]
+pub enum EmbeddingConfig {
+    /// All-zero vectors: lexical recall only.
+    #[default]
+    Noop,
+    /// Bag-of-words hashed vectors for tests and offline demos.
+    Deterministic,
+    /// Random unit vectors seeded by each text's hash, for ranking tests.
+    Hash,
+    #[serde(rename = "openai")]
+    OpenAi {
+        /// Falls back to `OPENAI_API_KEY` when unset.
+        #[serde(default)]
+        api_key: Option<String>,
+        #[serde(default = "default_openai_model")]
+        model: String,
+    },
+    /// Local ONNX inference (needs the `onnx` feature).
+    Onnx { model_path: String },
+}
+
+fn default_openai_model() -> String {
+    DEFAULT_OPENAI_MODEL.to_string()
+}
+
+impl std::fmt::Debug for EmbeddingConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmbeddingConfig::Noop => write!(f, "Noop"),
+            EmbeddingConfig::Deterministic => write!(f, "Deterministic"),
+            EmbeddingConfig::Hash => write!(f, "Hash"),
+            EmbeddingConfig::OpenAi { model, .. } => f
+                .debug_struct("OpenAi")
+                .field("api_key", &"<redacted>")
+                .field("model", model)
+                .finish(),
+            EmbeddingConfig::Onnx { model_path } => f
+                .debug_struct("Onnx")
+                .field("model_path", model_path)
+                .finish(),
+        }
+    }
+}
+
+impl EmbeddingConfig {
+    pub fn build(&self, dimensions: usize) -> Result<Arc<dyn EmbeddingProvider>> {
+        let provider: Arc<dyn EmbeddingProvider> = match self {
+            EmbeddingConfig::Noop => Arc::new(NoopEmbedding::new(dimensions)),
+            EmbeddingConfig::Deterministic => Arc::new(DeterministicEmbedding::new(dimensions)),
+            EmbeddingConfig::Hash => Arc::new(HashEmbedding::new(dimensions)),
+            EmbeddingConfig::OpenAi { api_key, model } => {
+                let api_key = match api_key {
+                    Some(key) => key.clone(),
+                    None => std::env::var("OPENAI_API_KEY").map_err(|_| {
+                        Error::Validation(
+                            "embedding.provider = \"openai\" needs api_key or OPENAI_API_KEY"
+                                .to_string(),
+                        )
+                    })?,
+                };
+                Arc::new(OpenAiEmbedding::new(api_key, model.clone(), dimensions))
+            }
+            EmbeddingConfig::Onnx { model_path } => Arc::new(
+                crate::embedding::onnx::OnnxEmbedding::new(model_path, dimensions)?,
+            ),
+        };
+        Ok(provider)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FullTextConfig {
+    pub enabled: bool,
+    /// Tantivy directory. `None` keeps the index in memory.
+    pub path: Option<PathBuf>,
+}
+
+impl Default for FullTextConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CacheConfig {
+    pub ttl_seconds: u64,
+    pub max_entries: usize,
+    /// `{ kind = "lru" }` (the default) or `{ kind = "lru_k", k = 2 }`.
+    #[serde(default)]
+    pub policy: EvictionPolicy,
+}
+
+/// Per-org encryption keys. See [`crate::encryption::keys`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OrgKeysConfig {
+    pub provider: KeyProviderConfig,
+    /// Org id → the key id its new memories are sealed under.
+    pub orgs: BTreeMap<String, String>,
+    /// Key ids no org writes with any more, still loaded so memories
+    /// sealed under them stay readable.
+    #[serde(default)]
+    pub retired: Vec<String>,
+}
+
+/// Where per-org data keys come from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum KeyProviderConfig {
+    /// Hex keys in `<prefix><KEY_ID>` environment variables.
+    Env {
+        #[serde(default = "default_key_env_prefix")]
+        prefix: String,
+    },
+    /// Hex keys in `<dir>/<key_id>.key` files.
+    File { dir: PathBuf },
+    /// Data keys in `<dir>/<key_id>.wrapped`, unwrapped by AWS KMS (needs
+    /// the `aws-kms` feature).
+    AwsKms {
+        dir: PathBuf,
+        #[serde(default)]
+        region: Option<String>,
+    },
+    /// Data keys in `<dir>/<key_id>.wrapped`, unwrapped by a Vault transit
+    /// key (needs the `vault` feature). The token is read from the
+    /// `token_env` variable.
+    Vault {
+        addr: String,
+        #[serde(default = "default_vault_mount")]
+        mount: String,
+        transit_key: String,
+        #[serde(default = "default_vault_token_env")]
+        token_env: String,
+        dir: PathBuf,
+    },
+}
+
+fn default_key_env_prefix() -> String {
+    DEFAULT_KEY_ENV_PREFIX.to_string()
+}
+
+fn default_vault_mount() -> String {
+    "transit".to_string()
+}
+
+fn default_vault_token_env() -> String {
+    "VAULT_TOKEN".to_string()
+}
+
+impl KeyProviderConfig {
+    pub async fn build(&self) -> Result<Box<dyn KeyProvider>> {
+        match self {
+            KeyProviderConfig::Env { prefix } => Ok(Box::new(EnvKeyProvider::new(prefix.clone()))),
+            KeyProviderConfig::File { dir } => Ok(Box::new(FileKeyProvider::new(dir.clone()))),
+            #[cfg(feature = "aws-kms")]
+            KeyProviderConfig::AwsKms { dir, region } => Ok(Box::new(
+                crate::encryption::kms::AwsKmsKeyProvider::new(dir.clone(), region.clone()).await,
+            )),
+            #[cfg(not(feature = "aws-kms"))]
+            KeyProviderConfig::AwsKms { .. } => Err(Error::Validation(
+                "org_keys.provider.backend = \"aws_kms\" needs mnemo-core built with the `aws-kms` feature"
+                    .to_string(),
+            )),
+            #[cfg(feature = "vault")]
+            KeyProviderConfig::Vault {
+                addr,
+                mount,
+                transit_key,
+                token_env,
+                dir,
+            } => {
+                let token = std::env::var(token_env).map_err(|_| {
+                    Error::Validation(format!("org_keys.provider: {token_env} not set"))
+                })?;
+                Ok(Box::new(crate::encryption::kms::VaultKeyProvider::new(
+                    addr.clone(),
+                    mount.clone(),
+                    transit_key.clone(),
+                    token,
+                    dir.clone(),
+                )))
+            }
+            #[cfg(not(feature = "vault"))]
+            KeyProviderConfig::Vault { .. } => Err(Error::Validation(
+                "org_keys.provider.backend = \"vault\" needs mnemo-core built with the `vault` feature"
+                    .to_string(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum ColdStorageSettings {
+    /// Process-local archive, for tests.
+    Memory,
+    /// S3-compatible object storage (needs the `s3` feature).
+    S3 {
+        bucket: String,
+        prefix: String,
+        #[serde(default)]
+        endpoint: Option<String>,
+        region: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeatureConfig {
+    /// Enable the experience-memory (plan cache) tier.
+    pub experience_memory: bool,
+    /// Embed audit events for semantic event search.
+    pub embed_events: bool,
+    pub trash_retention_days: Option<u32>,
+    pub expiry_warning_hours: Option<u32>,
+    pub ttl_working_seconds: u64,
+    pub procedural_importance_floor: f32,
+    /// Record per-memory reads. `None` disables the access log.
+    pub access_log: Option<AccessLogPolicy>,
+    /// Archive old events to cold storage. `None` keeps every event hot.
+    pub event_retention: Option<EventRetentionPolicy>,
+    /// k-anonymity / attribution redaction for shared-scope recall.
+    pub shared_recall_privacy: Option<SharedRecallPrivacy>,
+    /// Agent aliases in shared-scope reads and exports. `None` shows real
+    /// agent ids.
+    pub pseudonymization: Option<PseudonymizationConfig>,
+    /// Capability tokens granting read access to one memory. `None`
+    /// disables share links.
+    pub share_links: Option<ShareLinkConfig>,
+    /// Background checks of agents' write patterns. `None` disables them.
+    pub write_anomaly: Option<WriteAnomalyPolicy>,
+    /// Alternate recall configurations to compare on live traffic. `None`
+    /// shadows nothing.
+    pub shadow_recall: Option<ShadowRecallConfig>,
+    /// Tuning for the 0-1 relevance of recall hits. `None` uses the
+    /// defaults.
+    pub score_calibration: Option<CalibrationConfig>,
+    /// Recall deadline and stage budgets past which recall degrades.
+    /// `None` never degrades it.
+    pub latency_slo: Option<LatencySloConfig>,
+    /// Near-duplicate handling at remember time. `None` always inserts.
+    pub dedup: Option<DedupPolicy>,
+    /// Review of global-scope writes. `None` publishes them immediately.
+    pub curation: Option<CurationPolicy>,
+    /// Tags whose memories need attestation before recall.
+    pub attestation: Option<AttestationPolicy>,
+    /// Periods during which memories cannot be hard deleted or erased.
+    pub retention_lock: Option<RetentionLockPolicy>,
+    /// Content rules every write must pass. `None` accepts everything.
+    pub ingestion_filter: Option<IngestionFilterConfig>,
+    /// Hosts URL ingestion may fetch from. `None` allows public addresses
+    /// only.
+    pub url_fetch: Option<UrlFetchPolicy>,
+    /// Store every n-th checkpoint state in full and deltas in between.
+    pub checkpoint_snapshot_interval: u32,
+}
+
+impl Default for FeatureConfig {
+    fn default() -> Self {
+        Self {
+            experience_memory: false,
+            embed_events: false,
+            trash_retention_days: None,
+            expiry_warning_hours: None,
+            ttl_working_seconds: DEFAULT_TTL_WORKING_SECONDS,
+            procedural_importance_floor: DEFAULT_PROCEDURAL_IMPORTANCE_FLOOR,
+            access_log: None,
+            event_retention: None,
+            shared_recall_privacy: None,
+            pseudonymization: None,
+            share_links: None,
+            write_anomaly: None,
+            shadow_recall: None,
+            score_calibration: None,
+            latency_slo: None,
+            dedup: None,
+            curation: None,
+            attestation: None,
+            retention_lock: None,
+            ingestion_filter: None,
+            url_fetch: None,
+            checkpoint_snapshot_interval: DEFAULT_CHECKPOINT_SNAPSHOT_INTERVAL,
+        }
+    }
+}
+
+/// Agent aliasing for shared-scope reads and exports. See
+/// [`crate::query::pseudonym`].
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PseudonymizationConfig {
+    /// 32-byte key (64 hex chars) deriving aliases and sealing the alias
+    /// tables; revealing an alias needs it.
+    pub key: String,
+    /// Orgs whose agents are aliased. Empty covers every org.
+    #[serde(default)]
+    pub orgs: Vec<String>,
+}
+
+impl std::fmt::Debug for PseudonymizationConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PseudonymizationConfig")
+            .field("key", &"<redacted>")
+            .field("orgs", &self.orgs)
+            .finish()
+    }
+}
+
+/// Share link signing. See [`crate::query::share_link`].
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ShareLinkConfig {
+    /// 32-byte key (64 hex chars) signing the tokens. Changing it voids
+    /// every link minted before.
+    pub key: String,
+}
+
+impl std::fmt::Debug for ShareLinkConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShareLinkConfig")
+            .field("key", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Pipeline hooks loaded from files.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HooksConfig {
+    /// Rhai policy scripts run at remember / forget time (needs the
+    /// `scripting` feature). See [`crate::hooks::script`].
+    pub scripts: Vec<ScriptHookConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScriptHookConfig {
+    pub path: PathBuf,
+    /// Name used in logs and errors. Defaults to the file stem.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Lower runs first.
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default)]
+    pub on_error: HookErrorPolicy,
+}
+
+impl MnemoEngineConfig {
+    /// File-backed DuckDB layout used by the CLI and Python bindings:
+    /// `<db>`, `<db stem>.usearch` (+ `.usearch.wal`) and `<db stem>.tantivy`.
+    pub fn duckdb(db_path: &Path) -> Self {
+        Self {
+            storage: StorageConfig::DuckDb {
+                path: db_path.to_path_buf(),
+            },
+            index: IndexConfig {
+                path: Some(db_path.with_extension("usearch")),
+                wal: true,
+                shards: None,
+                ivf_pq: None,
+            },
+            full_text: FullTextConfig {
+                enabled: true,
+                path: Some(db_path.with_extension("tantivy")),
+            },
+            ..Self::default()
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        crate::query::validate_agent_id(&self.agent_id)?;
+        if self.dimensions == 0 {
+            return Err(Error::Validation("dimensions must be > 0".to_string()));
+        }
+        let floor = self.features.procedural_importance_floor;
+        if !(0.0..=1.0).contains(&floor) {
+            return Err(Error::Validation(format!(
+                "features.procedural_importance_floor must be in [0, 1], got {floor}"
+            )));
+        }
+        if let Some(ref pipeline) = self.embedding_pipeline {
+            pipeline.validate()?;
+        }
+        if let Some(ref privacy) = self.features.shared_recall_privacy {
+            privacy.validate()?;
+        }
+        if let Some(ref pseudonyms) = self.features.pseudonymization {
+            Pseudonymizer::from_hex(&pseudonyms.key)?;
+        }
+        if let Some(ref org_keys) = self.org_keys {
+            if org_keys.orgs.is_empty() {
+                return Err(Error::invalid_field(
+                    "org_keys.orgs",
+                    "must list at least one org",
+                ));
+            }
+            for key_id in org_keys.orgs.values().chain(&org_keys.retired) {
+                validate_key_id(key_id)?;
+            }
+        }
+        if let Some(ref links) = self.features.share_links {
+            ShareLinkSigner::from_hex(&links.key)?;
+        }
+        if let Some(ref policy) = self.features.write_anomaly {
+            policy.validate()?;
+        }
+        if let Some(ref shadow) = self.features.shadow_recall {
+            shadow.validate()?;
+        }
+        if let Some(ref calibration) = self.features.score_calibration {
+            calibration.validate()?;
+        }
+        if let Some(ref slo) = self.features.latency_slo {
+            slo.validate()?;
+        }
+        if let Some(ShardingConfig {
+            max_loaded_shards: Some(0),
+            ..
+        }) = self.index.shards
+        {
+            return Err(Error::Validation(
+                "index.shards.max_loaded_shards must be > 0".to_string(),
+            ));
+        }
+        if let Some(ref ivf_pq) = self.index.ivf_pq {
+            if self.index.shards.is_some() {
+                return Err(Error::Validation(
+                    "index.ivf_pq and index.shards cannot be combined".to_string(),
+                ));
+            }
+            if self.index.path.is_none() {
+                return Err(Error::Validation(
+                    "index.ivf_pq needs index.path".to_string(),
+                ));
+            }
+            ivf_pq.validate(self.dimensions)?;
+        }
+        if let Some(ref retention) = self.features.event_retention {
+            retention.validate()?;
+            if self.cold_storage.is_none() {
+                return Err(Error::Validation(
+                    "features.event_retention needs cold_storage".to_string(),
+                ));
+            }
+        }
+        if let Some(ref dedup) = self.features.dedup {
+            dedup.validate()?;
+        }
+        if let Some(ref lock) = self.features.retention_lock {
+            lock.validate()?;
+        }
+        if let Some(ref filter) = self.features.ingestion_filter {
+            IngestionFilter::new(filter)?;
+        }
+        self.limits.validate()?;
+        self.auto_checkpoint.validate()?;
+        let mut export_names = std::collections::HashSet::new();
+        for schedule in &self.exports {
+            schedule.validate()?;
+            if !export_names.insert(schedule.name.as_str()) {
+                return Err(Error::Validation(format!(
+                    "export name {} is used twice",
+                    schedule.name
+                )));
+            }
+        }
+        if self.features.checkpoint_snapshot_interval == 0 {
+            return Err(Error::Validation(
+                "features.checkpoint_snapshot_interval must be > 0".to_string(),
+            ));
+        }
+        if let Some(ref cache) = self.cache
+            && cache.max_entries == 0
+        {
+            return Err(Error::Validation(
+                "cache.max_entries must be > 0".to_string(),
+            ));
+        }
+        if let Some(CacheConfig {
+            policy: EvictionPolicy::LruK { k: 0 },
+            ..
+        }) = self.cache
+        {
+            return Err(Error::Validation("cache.policy.k must be > 0".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Layer everything except storage, index and embedding onto an
+    /// engine built elsewhere (e.g. with PostgreSQL storage).
+    pub async fn apply(&self, mut engine: MnemoEngine) -> Result<MnemoEngine> {
+        self.validate()?;
+
+        if self.full_text.enabled {
+            let ft = match self.full_text.path {
+                Some(ref path) => TantivyFullTextIndex::new(path)?,
+                None => TantivyFullTextIndex::open_in_memory()?,
+            };
+            engine = engine.with_full_text(Arc::new(ft));
+        }
+        if let Some(ref key_hex) = self.encryption_key {
+            engine = engine.with_encryption(Arc::new(ContentEncryption::from_hex(key_hex)?));
+        }
+        if let Some(ref org_keys) = self.org_keys {
+            let provider = org_keys.provider.build().await?;
+            let registry =
+                KeyRegistry::load(provider.as_ref(), org_keys.orgs.clone(), &org_keys.retired)
+                    .await?;
+            engine = engine.with_org_keys(Arc::new(registry));
+        }
+        if let Some(ref cache) = self.cache {
+            engine = engine.with_cache(Arc::new(
+                MemoryCache::new(cache.ttl_seconds, cache.max_entries).with_policy(cache.policy),
+            ));
+        }
+        match self.cold_storage {
+            None => {}
+            Some(ColdStorageSettings::Memory) => {
+                engine = engine.with_cold_storage(Arc::new(InMemoryColdStorage::new(
+                    ColdStorageConfig {
+                        bucket: "memory".to_string(),
+                        prefix: "memories".to_string(),
+                        endpoint: None,
+                        region: "local".to_string(),
+                    },
+                )));
+            }
+            #[cfg(feature = "s3")]
+            Some(ColdStorageSettings::S3 {
+                ref bucket,
+                ref prefix,
+                ref endpoint,
+                ref region,
+            }) => {
+                let s3 = crate::storage::cold::S3ColdStorage::new(ColdStorageConfig {
+                    bucket: bucket.clone(),
+                    prefix: prefix.clone(),
+                    endpoint: endpoint.clone(),
+                    region: region.clone(),
+                })
+                .await;
+                engine = engine.with_cold_storage(Arc::new(s3));
+            }
+            #[cfg(not(feature = "s3"))]
+            Some(ColdStorageSettings::S3 { .. }) => {
+                return Err(Error::Validation(
+                    "cold_storage.backend = \"s3\" needs mnemo-core built with the `s3` feature"
+                        .to_string(),
+                ));
+            }
+        }
+
+        let features = &self.features;
+        if features.experience_memory {
+            engine = engine.with_experience_memory();
+        }
+        if features.embed_events {
+            engine = engine.with_event_embeddings();
+        }
+        if let Some(days) = features.trash_retention_days {
+            engine = engine.with_trash_retention_days(days);
+        }
+        if let Some(hours) = features.expiry_warning_hours {
+            engine = engine.with_expiry_warning_hours(hours);
+        }
+        if let Some(ref policy) = features.access_log {
+            engine = engine.with_access_log(policy.clone());
+        }
+        if let Some(ref retention) = features.event_retention {
+            engine = engine.with_event_retention(retention.clone());
+        }
+        if let Some(ref privacy) = features.shared_recall_privacy {
+            engine = engine.with_shared_recall_privacy(privacy.clone());
+        }
+        if let Some(ref pseudonyms) = features.pseudonymization {
+            engine = engine.with_pseudonymizer(
+                Pseudonymizer::from_hex(&pseudonyms.key)?.with_orgs(pseudonyms.orgs.clone()),
+            );
+        }
+        if let Some(ref links) = features.share_links {
+            engine = engine.with_share_links(ShareLinkSigner::from_hex(&links.key)?);
+        }
+        if let Some(ref policy) = features.write_anomaly {
+            engine = engine.with_write_anomaly_detection(policy.clone());
+        }
+        if let Some(ref shadow) = features.shadow_recall {
+            engine = engine.with_shadow_recall(shadow.clone());
+        }
+        if let Some(ref calibration) = features.score_calibration {
+            engine = engine.with_score_calibration(calibration.clone());
+        }
+        if let Some(ref slo) = features.latency_slo {
+            engine = engine.with_latency_slo(slo.clone());
+        }
+        if let Some(ref dedup) = features.dedup {
+            engine = engine.with_dedup_policy(dedup.clone());
+        }
+        if let Some(ref curation) = features.curation {
+            engine = engine.with_curation(curation.clone());
+        }
+        if let Some(ref attestation) = features.attestation {
+            engine = engine.with_attestation(attestation.clone());
+        }
+        if let Some(ref lock) = features.retention_lock {
+            engine = engine.with_retention_lock(lock.clone());
+        }
+        if let Some(ref filter) = features.ingestion_filter {
+            engine = engine.with_ingestion_filter(IngestionFilter::new(filter)?);
+        }
+        if let Some(ref policy) = features.url_fetch {
+            engine = engine.with_url_fetch_policy(policy.clone());
+        }
+        for script in &self.hooks.scripts {
+            let mut hook = ScriptHook::from_file(&script.path)?;
+            if let Some(ref name) = script.name {
+                hook = hook.with_name(name.clone());
+            }
+            engine = engine.with_hook(
+                Arc::new(hook),
+                HookOptions::new(script.priority, script.on_error),
+            );
+        }
+        engine = engine
+            .with_limits(self.limits.clone())
+            .with_auto_checkpoint(self.auto_checkpoint.clone())
+            .with_exports(self.exports.clone());
+        if self.read_only {
+            engine = engine.with_read_only(true);
+        }
+        Ok(engine
+            .with_ttl_working_seconds(features.ttl_working_seconds)
+            .with_procedural_importance_floor(features.procedural_importance_floor)
+            .with_checkpoint_snapshot_interval(features.checkpoint_snapshot_interval))
+    }
+}
+
+impl MnemoEngine {
+    /// Build an engine from a [`MnemoEngineConfig`]: open storage, load the
+    /// vector index (replaying its WAL), construct the embedding provider and
+    /// apply the remaining settings.
+    pub async fn from_config(config: &MnemoEngineConfig) -> Result<Self> {
+        config.validate()?;
+        let embedding = config.embedding.build(config.dimensions)?;
+        Self::from_config_with_embedding(config, embedding).await
+    }
+
+    /// Like [`from_config`](Self::from_config), with a caller-supplied
+    /// embedding provider in place of `config.embedding`.
+    pub async fn from_config_with_embedding(
+        config: &MnemoEngineConfig,
+        embedding: Arc<dyn EmbeddingProvider>,
+    ) -> Result<Self> {
+        config.validate()?;
+        if embedding.dimensions() != config.dimensions {
+            return Err(Error::Validation(format!(
+                "embedding provider produces {} dimensions but the config expects {}",
+                embedding.dimensions(),
+                config.dimensions
+            )));
+        }
+        let embedding: Arc<dyn EmbeddingProvider> = match config.embedding_pipeline {
+            Some(ref pipeline) => Arc::new(PipelinedEmbedding::new(embedding, pipeline.clone())),
+            None => embedding,
+        };
+        let storage: Arc<dyn StorageBackend> = match config.storage {
+            StorageConfig::Memory => Arc::new(DuckDbStorage::open_in_memory()?),
+            StorageConfig::DuckDb { ref path } => Arc::new(DuckDbStorage::open(path)?),
+            #[cfg(feature = "memory-storage")]
+            StorageConfig::InMemory => Arc::new(crate::storage::memory::InMemoryStorage::new()),
+            #[cfg(not(feature = "memory-storage"))]
+            StorageConfig::InMemory => {
+                return Err(Error::Validation(
+                    "storage.backend = \"in_memory\" needs mnemo-core built with the `memory-storage` feature"
+                        .to_string(),
+                ));
+            }
+        };
+        let index = config.index.build_shared(config.dimensions)?;
+        let engine = MnemoEngine::new(
+            storage,
+            index,
+            embedding,
+            config.agent_id.clone(),
+            config.org_id.clone(),
+        );
+        config.apply(engine).await
+    }
+}
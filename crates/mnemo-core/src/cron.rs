@@ -0,0 +1,212 @@
+//! Five-field cron expressions, evaluated in UTC.
+//!
+//! `minute hour day-of-month month day-of-week`, each field `*`, a value,
+//! a range `a-b`, a step `*/n` / `a-b/n` / `a/n`, or a comma-separated
+//! list of those. Day-of-week runs 0–7 with both 0 and 7 meaning Sunday.
+//! As in classic cron, when both day fields are restricted a day matches
+//! if either does. The shorthands `@hourly`, `@daily`, `@weekly`,
+//! `@monthly` and `@yearly` are accepted too.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Timelike, Utc};
+
+use crate::error::{Error, Result};
+
+/// Days searched for the next fire time before giving up, enough for any
+/// expression that can fire at all (29 February recurs within 8 years).
+const SEARCH_DAYS: i64 = 366 * 8 + 2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: Vec<bool>,
+    hours: Vec<bool>,
+    days_of_month: Vec<bool>,
+    months: Vec<bool>,
+    days_of_week: Vec<bool>,
+    /// The day-of-month field was not `*`.
+    dom_restricted: bool,
+    /// The day-of-week field was not `*`.
+    dow_restricted: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<Self> {
+        let expanded = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, dom, month, dow] = fields[..] else {
+            return Err(Error::Validation(format!(
+                "cron expression {expression:?} must have 5 fields"
+            )));
+        };
+        let mut days_of_week = parse_field(dow, 0, 7, "day-of-week")?;
+        if days_of_week[7] {
+            days_of_week[0] = true;
+        }
+        days_of_week.truncate(7);
+        Ok(Self {
+            minutes: parse_field(minute, 0, 59, "minute")?,
+            hours: parse_field(hour, 0, 23, "hour")?,
+            days_of_month: parse_field(dom, 1, 31, "day-of-month")?,
+            months: parse_field(month, 1, 12, "month")?,
+            days_of_week,
+            dom_restricted: dom != "*",
+            dow_restricted: dow != "*",
+        })
+    }
+
+    /// First fire time strictly after `after`, or `None` if the expression
+    /// never fires (e.g. `0 0 31 2 *`).
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after
+            .with_second(0)
+            .and_then(|t| t.with_nanosecond(0))
+            .unwrap_or(after)
+            + Duration::minutes(1);
+        let first_day = start.date_naive();
+        for offset in 0..SEARCH_DAYS {
+            let day = first_day + Duration::days(offset);
+            if !self.matches_day(day) {
+                continue;
+            }
+            let (from_hour, from_minute) = if offset == 0 {
+                (start.hour(), start.minute())
+            } else {
+                (0, 0)
+            };
+            for hour in from_hour..24 {
+                if !self.hours[hour as usize] {
+                    continue;
+                }
+                let first_minute = if hour == from_hour { from_minute } else { 0 };
+                if let Some(minute) = (first_minute..60).find(|m| self.minutes[*m as usize]) {
+                    let time = day.and_hms_opt(hour, minute, 0)?;
+                    return Some(Utc.from_utc_datetime(&time));
+                }
+            }
+        }
+        None
+    }
+
+    fn matches_day(&self, day: NaiveDate) -> bool {
+        if !self.months[day.month() as usize] {
+            return false;
+        }
+        let dom = self.days_of_month[day.day() as usize];
+        let dow = self.days_of_week[day.weekday().num_days_from_sunday() as usize];
+        if self.dom_restricted && self.dow_restricted {
+            dom || dow
+        } else {
+            dom && dow
+        }
+    }
+}
+
+/// Values of one field as a table indexed by value, `0..=max`.
+fn parse_field(field: &str, min: u32, max: u32, name: &str) -> Result<Vec<bool>> {
+    let invalid = || Error::Validation(format!("invalid cron {name} field {field:?}"));
+    let mut allowed = vec![false; max as usize + 1];
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().map_err(|_| invalid())?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            return Err(invalid());
+        }
+        let (low, high) = if range == "*" {
+            (min, max)
+        } else if let Some((low, high)) = range.split_once('-') {
+            (
+                low.parse().map_err(|_| invalid())?,
+                high.parse().map_err(|_| invalid())?,
+            )
+        } else {
+            let value: u32 = range.parse().map_err(|_| invalid())?;
+            // `a/n` runs from `a` to the end of the field.
+            (value, if part.contains('/') { max } else { value })
+        };
+        if low < min || high > max || low > high {
+            return Err(invalid());
+        }
+        for value in (low..=high).step_by(step as usize) {
+            allowed[value as usize] = true;
+        }
+    }
+    Ok(allowed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn next_fire_times() {
+        let daily = CronSchedule::parse("30 2 * * *").unwrap();
+        assert_eq!(
+            daily.next_after(at("2026-03-01T02:29:59Z")),
+            Some(at("2026-03-01T02:30:00Z"))
+        );
+        assert_eq!(
+            daily.next_after(at("2026-03-01T02:30:00Z")),
+            Some(at("2026-03-02T02:30:00Z"))
+        );
+
+        let every_15 = CronSchedule::parse("*/15 9-17 * * 1-5").unwrap();
+        // Saturday evening rolls over to Monday morning.
+        assert_eq!(
+            every_15.next_after(at("2026-03-07T18:00:00Z")),
+            Some(at("2026-03-09T09:00:00Z"))
+        );
+
+        let monthly = CronSchedule::parse("@monthly").unwrap();
+        assert_eq!(
+            monthly.next_after(at("2026-12-15T00:00:00Z")),
+            Some(at("2027-01-01T00:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn restricted_day_fields_match_either() {
+        // The 13th, or any Friday.
+        let cron = CronSchedule::parse("0 0 13 * 5").unwrap();
+        assert_eq!(
+            cron.next_after(at("2026-03-01T00:00:00Z")),
+            Some(at("2026-03-06T00:00:00Z"))
+        );
+        assert_eq!(
+            cron.next_after(at("2026-03-12T00:00:00Z")),
+            Some(at("2026-03-13T00:00:00Z"))
+        );
+        let sunday = CronSchedule::parse("0 0 * * 7").unwrap();
+        assert_eq!(
+            sunday.next_after(at("2026-03-02T00:00:00Z")),
+            Some(at("2026-03-08T00:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for bad in [
+            "* * * *",
+            "60 * * * *",
+            "* * 0 * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "a * * * *",
+        ] {
+            assert!(CronSchedule::parse(bad).is_err(), "{bad}");
+        }
+        let never = CronSchedule::parse("0 0 31 2 *").unwrap();
+        assert_eq!(never.next_after(at("2026-01-01T00:00:00Z")), None);
+    }
+}
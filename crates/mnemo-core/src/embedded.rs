@@ -0,0 +1,273 @@
+//! Stable in-process API for embedding Mnemo in a Rust application.
+//!
+//! [`Mnemo`] is the supported entry point for library users: open a store
+//! with [`Mnemo::open`], [`Mnemo::in_memory`] or [`Mnemo::builder`], then
+//! call `remember` / `recall` / `forget`. The simple methods pick sensible
+//! defaults (the engine's agent, soft deletes, lexical recall when no
+//! semantic embedder is configured); the `*_with` variants take the full
+//! request types for everything else.
+//!
+//! ```rust,no_run
+//! # async fn run() -> mnemo_core::error::Result<()> {
+//! use mnemo_core::Mnemo;
+//!
+//! let mnemo = Mnemo::builder()
+//!     .path("agent.db")
+//!     .agent_id("planner")
+//!     .build()
+//!     .await?;
+//!
+//! let id = mnemo.remember("the deploy window is tuesday 14:00 UTC").await?;
+//! let hits = mnemo.recall("deploy window", 5).await?;
+//! assert_eq!(hits[0].id, id);
+//! mnemo.forget(id).await?;
+//! mnemo.flush().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Stability
+//!
+//! `Mnemo`, [`MnemoBuilder`] and their methods follow semantic versioning:
+//! they are only changed incompatibly in a major release, and new builder
+//! options are added as methods. The types they exchange (requests,
+//! responses, [`MnemoEngineConfig`]) are stable in the same way. The
+//! underlying [`MnemoEngine`], reachable through [`Mnemo::engine`], is not
+//! covered by this guarantee: its constructor and fields change as the
+//! engine grows.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::config::{EmbeddingConfig, MnemoEngineConfig, OrgKeysConfig};
+use crate::embedding::EmbeddingProvider;
+use crate::error::Result;
+use crate::hooks::{Hook, HookOptions};
+use crate::query::MnemoEngine;
+use crate::query::forget::{ForgetRequest, ForgetResponse};
+use crate::query::recall::{RecallRequest, RecallResponse, ScoredMemory};
+use crate::query::remember::{RememberRequest, RememberResponse};
+
+/// An embedded Mnemo store.
+///
+/// Cloning is cheap: clones share the same engine.
+#[derive(Clone)]
+pub struct Mnemo {
+    engine: Arc<MnemoEngine>,
+    index_path: Option<PathBuf>,
+}
+
+impl std::fmt::Debug for Mnemo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Mnemo")
+            .field("agent_id", &self.engine.default_agent_id)
+            .field("storage", &self.engine.storage.backend_name())
+            .field("index_path", &self.index_path)
+            .finish()
+    }
+}
+
+impl Mnemo {
+    pub fn builder() -> MnemoBuilder {
+        MnemoBuilder::default()
+    }
+
+    /// Open (or create) a file-backed store at `db_path` with default
+    /// options. The vector and full-text indexes live next to it.
+    pub async fn open(db_path: impl AsRef<Path>) -> Result<Self> {
+        Self::builder().path(db_path).build().await
+    }
+
+    /// An ephemeral store that lives only as long as the process.
+    pub async fn in_memory() -> Result<Self> {
+        Self::builder().build().await
+    }
+
+    /// Wrap an engine assembled by hand. [`flush`](Self::flush) then only
+    /// commits the full-text index.
+    pub fn from_engine(engine: Arc<MnemoEngine>) -> Self {
+        Self {
+            engine,
+            index_path: None,
+        }
+    }
+
+    /// Agent that operations run as unless a request names another.
+    pub fn agent_id(&self) -> &str {
+        &self.engine.default_agent_id
+    }
+
+    /// Store `content` as an episodic memory and return its id.
+    pub async fn remember(&self, content: impl Into<String>) -> Result<Uuid> {
+        Ok(self
+            .remember_with(RememberRequest::new(content.into()))
+            .await?
+            .id)
+    }
+
+    pub async fn remember_with(&self, request: RememberRequest) -> Result<RememberResponse> {
+        self.engine.remember(request).await
+    }
+
+    /// The `limit` best matches for `query`. Uses the engine's `auto`
+    /// strategy when a semantic embedder is configured and BM25 otherwise.
+    pub async fn recall(
+        &self,
+        query: impl Into<String>,
+        limit: usize,
+    ) -> Result<Vec<ScoredMemory>> {
+        let mut request = RecallRequest::new(query.into());
+        request.limit = Some(limit);
+        if !self.engine.embedding.is_semantic_capable() {
+            request.strategy = Some("lexical".to_string());
+        }
+        Ok(self.recall_with(request).await?.memories)
+    }
+
+    pub async fn recall_with(&self, request: RecallRequest) -> Result<RecallResponse> {
+        self.engine.recall(request).await
+    }
+
+    /// Soft-delete one memory. Returns `false` if it was not forgotten
+    /// (unknown, pinned or not owned); use [`forget_with`](Self::forget_with)
+    /// for the reason.
+    pub async fn forget(&self, id: Uuid) -> Result<bool> {
+        let response = self.forget_with(ForgetRequest::new(vec![id])).await?;
+        Ok(response.forgotten.contains(&id))
+    }
+
+    pub async fn forget_with(&self, request: ForgetRequest) -> Result<ForgetResponse> {
+        self.engine.forget(request).await
+    }
+
+    /// Persist in-memory index state: commit the full-text index and save
+    /// the vector index snapshot. Writes are durable without this (the
+    /// vector WAL covers them); flushing keeps the next open fast.
+    pub async fn flush(&self) -> Result<()> {
+        if let Some(ref full_text) = self.engine.full_text {
+            full_text.commit()?;
+        }
+        if let Some(ref path) = self.index_path {
+            self.engine.index.save(path)?;
+        }
+        Ok(())
+    }
+
+    /// The underlying engine, for operations without a facade method.
+    /// Not covered by the stability guarantee.
+    pub fn engine(&self) -> &Arc<MnemoEngine> {
+        &self.engine
+    }
+}
+
+/// Builder for [`Mnemo`]. Without [`path`](Self::path) the store is
+/// in-memory.
+#[derive(Default)]
+pub struct MnemoBuilder {
+    config: MnemoEngineConfig,
+    embedding: Option<Arc<dyn EmbeddingProvider>>,
+    hooks: Vec<(Arc<dyn Hook>, HookOptions)>,
+}
+
+impl MnemoBuilder {
+    /// Start from a full engine configuration, e.g. one read from TOML.
+    pub fn config(mut self, config: MnemoEngineConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Store data in a DuckDB file at `db_path`, with `<stem>.usearch` and
+    /// `<stem>.tantivy` alongside it.
+    pub fn path(mut self, db_path: impl AsRef<Path>) -> Self {
+        let layout = MnemoEngineConfig::duckdb(db_path.as_ref());
+        self.config.storage = layout.storage;
+        self.config.index.path = layout.index.path;
+        self.config.full_text.path = layout.full_text.path;
+        self
+    }
+
+    pub fn agent_id(mut self, agent_id: impl Into<String>) -> Self {
+        self.config.agent_id = agent_id.into();
+        self
+    }
+
+    pub fn org_id(mut self, org_id: impl Into<String>) -> Self {
+        self.config.org_id = Some(org_id.into());
+        self
+    }
+
+    /// Vector width for a configured provider. Ignored when a provider
+    /// instance is passed to [`embedding_provider`](Self::embedding_provider).
+    pub fn dimensions(mut self, dimensions: usize) -> Self {
+        self.config.dimensions = dimensions;
+        self
+    }
+
+    /// Which built-in embedding provider to construct.
+    pub fn embedding(mut self, embedding: EmbeddingConfig) -> Self {
+        self.config.embedding = embedding;
+        self.embedding = None;
+        self
+    }
+
+    /// Use a provider instance, e.g. a custom [`EmbeddingProvider`].
+    pub fn embedding_provider(mut self, provider: Arc<dyn EmbeddingProvider>) -> Self {
+        self.embedding = Some(provider);
+        self
+    }
+
+    /// AES-256-GCM key (64 hex chars) for at-rest content encryption.
+    pub fn encryption_key(mut self, key: impl Into<String>) -> Self {
+        self.config.encryption_key = Some(key.into());
+        self
+    }
+
+    /// Per-org encryption keys, preferred over [`encryption_key`](Self::encryption_key)
+    /// for the orgs they list.
+    pub fn org_keys(mut self, org_keys: OrgKeysConfig) -> Self {
+        self.config.org_keys = Some(org_keys);
+        self
+    }
+
+    /// Enable or disable the BM25 full-text index (on by default).
+    pub fn full_text(mut self, enabled: bool) -> Self {
+        self.config.full_text.enabled = enabled;
+        self
+    }
+
+    pub fn hook(mut self, hook: Arc<dyn Hook>, options: HookOptions) -> Self {
+        self.hooks.push((hook, options));
+        self
+    }
+
+    /// Open storage, load the indexes and build the store.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Validation`](crate::error::Error::Validation) for an
+    /// invalid configuration and a storage or index error if the files
+    /// cannot be opened.
+    pub async fn build(self) -> Result<Mnemo> {
+        let MnemoBuilder {
+            mut config,
+            embedding,
+            hooks,
+        } = self;
+        let mut engine = match embedding {
+            Some(provider) => {
+                config.dimensions = provider.dimensions();
+                MnemoEngine::from_config_with_embedding(&config, provider).await?
+            }
+            None => MnemoEngine::from_config(&config).await?,
+        };
+        for (hook, options) in hooks {
+            engine = engine.with_hook(hook, options);
+        }
+        Ok(Mnemo {
+            engine: Arc::new(engine),
+            index_path: config.index.path,
+        })
+    }
+}
@@ -1,5 +1,10 @@
 pub mod onnx;
 pub mod openai;
+pub mod pipeline;
+
+use std::collections::HashMap;
+
+use sha2::{Digest, Sha256};
 
 use crate::error::Result;
 
@@ -113,6 +118,98 @@ impl EmbeddingProvider for DeterministicEmbedding {
     // Inherits `is_semantic_capable() == true` — it emits real, non-zero vectors.
 }
 
+/// A deterministic **content-hash** embedder for tests that assert ranking.
+///
+/// Each text maps to a pseudo-random unit vector seeded by its SHA-256, so
+/// the same text always embeds identically while any two different texts
+/// are nearly orthogonal: unlike [`DeterministicEmbedding`], sharing words
+/// does not make texts similar. A test lays out the geometry it needs with
+/// [`HashEmbedding::with_vector`], e.g. a query vector that is closer to
+/// one memory than another, and can then assert the exact recall order.
+pub struct HashEmbedding {
+    dimensions: usize,
+    pinned: HashMap<String, Vec<f32>>,
+}
+
+impl HashEmbedding {
+    pub fn new(dimensions: usize) -> Self {
+        Self {
+            dimensions,
+            pinned: HashMap::new(),
+        }
+    }
+
+    /// Embed `text` as `vector`, L2-normalized, instead of its hash vector.
+    ///
+    /// # Panics
+    ///
+    /// If `vector` does not have [`EmbeddingProvider::dimensions`] entries.
+    pub fn with_vector(mut self, text: impl Into<String>, mut vector: Vec<f32>) -> Self {
+        assert_eq!(
+            vector.len(),
+            self.dimensions,
+            "pinned vector has the wrong dimensions"
+        );
+        normalize(&mut vector);
+        self.pinned.insert(text.into(), vector);
+        self
+    }
+
+    /// The vector `text` embeds to. Tests blend these to build a query
+    /// near given memories.
+    pub fn vector(&self, text: &str) -> Vec<f32> {
+        if let Some(vector) = self.pinned.get(text) {
+            return vector.clone();
+        }
+        let digest = Sha256::digest(text.as_bytes());
+        let mut state = u64::from_le_bytes(digest[..8].try_into().expect("8 bytes"));
+        let mut uniform = move || {
+            // SplitMix64, mapped to (0, 1] so the logarithm below is finite.
+            state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^= z >> 31;
+            ((z >> 11) as f64 + 1.0) / (1u64 << 53) as f64
+        };
+        // Box-Muller normals point in a uniformly random direction.
+        let mut v = Vec::with_capacity(self.dimensions + 1);
+        while v.len() < self.dimensions {
+            let radius = (-2.0 * uniform().ln()).sqrt();
+            let angle = std::f64::consts::TAU * uniform();
+            v.push((radius * angle.cos()) as f32);
+            v.push((radius * angle.sin()) as f32);
+        }
+        v.truncate(self.dimensions);
+        normalize(&mut v);
+        v
+    }
+}
+
+fn normalize(v: &mut [f32]) {
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for x in v {
+            *x /= norm;
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EmbeddingProvider for HashEmbedding {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        Ok(self.vector(text))
+    }
+
+    async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        Ok(texts.iter().map(|t| self.vector(t)).collect())
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,4 +229,31 @@ mod tests {
         assert_eq!(a.len(), 16);
         assert!(a.iter().any(|x| *x != 0.0), "produces non-zero vectors");
     }
+
+    fn cosine(a: &[f32], b: &[f32]) -> f32 {
+        a.iter().zip(b).map(|(x, y)| x * y).sum()
+    }
+
+    #[tokio::test]
+    async fn hash_embedding_is_stable_unit_and_spread_out() {
+        let e = HashEmbedding::new(257);
+        let a = e.embed("clinician adjusted the dosage").await.unwrap();
+        assert_eq!(a, e.embed("clinician adjusted the dosage").await.unwrap());
+        assert_eq!(a.len(), 257);
+        assert!((cosine(&a, &a) - 1.0).abs() < 1e-5);
+
+        // Shared words do not make texts similar.
+        let b = e.embed("clinician adjusted the dose").await.unwrap();
+        assert!(cosine(&a, &b).abs() < 0.3);
+        let batch = e.embed_batch(&["x", "y", "x"]).await.unwrap();
+        assert_eq!(batch[0], batch[2]);
+        assert_ne!(batch[0], batch[1]);
+    }
+
+    #[tokio::test]
+    async fn hash_embedding_pins_vectors() {
+        let e = HashEmbedding::new(4).with_vector("north", vec![0.0, 2.0, 0.0, 0.0]);
+        assert_eq!(e.embed("north").await.unwrap(), vec![0.0, 1.0, 0.0, 0.0]);
+        assert_ne!(e.vector("south"), e.vector("north"));
+    }
 }
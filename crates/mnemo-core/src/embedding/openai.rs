@@ -71,10 +71,23 @@ impl EmbeddingProvider for OpenAiEmbedding {
 
         if !response.status().is_success() {
             let status = response.status();
+            let retry_after_secs = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok());
             let body = response.text().await.unwrap_or_default();
-            return Err(Error::Embedding(format!(
-                "OpenAI API error {status}: {body}"
-            )));
+            let message = format!("OpenAI API error {status}: {body}");
+            return Err(if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                Error::RateLimited {
+                    message,
+                    retry_after_secs,
+                }
+            } else if status.is_server_error() {
+                Error::Unavailable(message)
+            } else {
+                Error::Embedding(message)
+            });
         }
 
         let resp: EmbeddingResponse = response.json().await?;
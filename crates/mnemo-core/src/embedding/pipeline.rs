@@ -0,0 +1,396 @@
+//! Chunked, parallel `embed_batch` with a request and token budget.
+//!
+//! [`PipelinedEmbedding`] wraps any [`EmbeddingProvider`]. A batch is split
+//! into chunks of `chunk_size` texts, and up to `concurrency` chunks are in
+//! flight at once. Each chunk first takes its share of the per-minute
+//! request (`requests_per_minute`) and token (`tokens_per_minute`) budgets,
+//! waiting for the next minute when either is spent. A chunk the provider
+//! throttles or cannot reach is retried up to `max_retries` times, after
+//! the provider's `Retry-After` when it sent one. Vectors come back in the
+//! order of the input texts.
+//!
+//! Tokens are estimated as one per four bytes of text, which is close
+//! enough for OpenAI's tokenizers to stay under a TPM limit.
+//!
+//! ```toml
+//! [embedding_pipeline]
+//! concurrency = 4
+//! chunk_size = 256
+//! requests_per_minute = 3000
+//! tokens_per_minute = 1000000
+//! ```
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, Semaphore};
+use tokio::task::JoinSet;
+use tokio::time::Instant;
+
+use crate::embedding::EmbeddingProvider;
+use crate::error::{Error, Result};
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EmbeddingPipelineConfig {
+    /// Chunks embedded at once.
+    pub concurrency: usize,
+    /// Texts per provider request.
+    pub chunk_size: usize,
+    /// Provider requests allowed per minute; unlimited when unset.
+    pub requests_per_minute: Option<u32>,
+    /// Estimated tokens allowed per minute; unlimited when unset.
+    pub tokens_per_minute: Option<u64>,
+    /// Retries of a throttled or failed chunk before the batch fails.
+    pub max_retries: u32,
+}
+
+impl Default for EmbeddingPipelineConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            chunk_size: 256,
+            requests_per_minute: None,
+            tokens_per_minute: None,
+            max_retries: 3,
+        }
+    }
+}
+
+impl EmbeddingPipelineConfig {
+    pub fn validate(&self) -> Result<()> {
+        for (name, value) in [
+            ("concurrency", Some(self.concurrency as u64)),
+            ("chunk_size", Some(self.chunk_size as u64)),
+            (
+                "requests_per_minute",
+                self.requests_per_minute.map(u64::from),
+            ),
+            ("tokens_per_minute", self.tokens_per_minute),
+        ] {
+            if value == Some(0) {
+                return Err(Error::invalid_field(
+                    format!("embedding_pipeline.{name}"),
+                    "must be > 0",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Estimated tokens in `text`.
+fn estimate_tokens(text: &str) -> u64 {
+    text.len().div_ceil(4) as u64
+}
+
+/// Requests and tokens spent in the current minute.
+struct Window {
+    started: Instant,
+    requests: u32,
+    tokens: u64,
+}
+
+struct RateBudget {
+    requests_per_minute: Option<u32>,
+    tokens_per_minute: Option<u64>,
+    window: Mutex<Window>,
+}
+
+impl RateBudget {
+    /// Wait until one request of `tokens` fits in the minute, then spend it.
+    /// A request larger than the whole token budget goes out alone at the
+    /// start of a minute.
+    async fn acquire(&self, tokens: u64) {
+        loop {
+            let wait = {
+                let mut window = self.window.lock().await;
+                let elapsed = window.started.elapsed();
+                if elapsed >= WINDOW {
+                    *window = Window {
+                        started: Instant::now(),
+                        requests: 0,
+                        tokens: 0,
+                    };
+                }
+                let requests_fit = self
+                    .requests_per_minute
+                    .is_none_or(|limit| window.requests < limit);
+                let tokens_fit = self
+                    .tokens_per_minute
+                    .is_none_or(|limit| window.tokens + tokens <= limit || window.requests == 0);
+                if requests_fit && tokens_fit {
+                    window.requests += 1;
+                    window.tokens += tokens;
+                    return;
+                }
+                WINDOW.saturating_sub(window.started.elapsed())
+            };
+            tracing::debug!(
+                target: "mnemo::embedding",
+                wait_ms = wait.as_millis() as u64,
+                "embedding rate budget spent; waiting for the next minute"
+            );
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// An [`EmbeddingProvider`] that runs `embed_batch` through the pipeline.
+/// `embed` is a one-text batch, so it shares the budget and retries.
+pub struct PipelinedEmbedding {
+    inner: Arc<dyn EmbeddingProvider>,
+    config: EmbeddingPipelineConfig,
+    budget: Arc<RateBudget>,
+}
+
+impl PipelinedEmbedding {
+    pub fn new(inner: Arc<dyn EmbeddingProvider>, config: EmbeddingPipelineConfig) -> Self {
+        let budget = Arc::new(RateBudget {
+            requests_per_minute: config.requests_per_minute,
+            tokens_per_minute: config.tokens_per_minute,
+            window: Mutex::new(Window {
+                started: Instant::now(),
+                requests: 0,
+                tokens: 0,
+            }),
+        });
+        Self {
+            inner,
+            config,
+            budget,
+        }
+    }
+
+    pub fn config(&self) -> &EmbeddingPipelineConfig {
+        &self.config
+    }
+}
+
+/// Embed one chunk within the budget, retrying retryable failures.
+async fn embed_chunk(
+    inner: &dyn EmbeddingProvider,
+    budget: &RateBudget,
+    texts: &[String],
+    max_retries: u32,
+) -> Result<Vec<Vec<f32>>> {
+    let tokens = texts.iter().map(|t| estimate_tokens(t)).sum();
+    let refs: Vec<&str> = texts.iter().map(String::as_str).collect();
+    let mut attempt = 0;
+    loop {
+        budget.acquire(tokens).await;
+        match inner.embed_batch(&refs).await {
+            Ok(vectors) if vectors.len() == texts.len() => return Ok(vectors),
+            Ok(vectors) => {
+                return Err(Error::Embedding(format!(
+                    "provider returned {} vectors for {} texts",
+                    vectors.len(),
+                    texts.len()
+                )));
+            }
+            Err(e) if e.is_retryable() && attempt < max_retries => {
+                let backoff = match e {
+                    Error::RateLimited {
+                        retry_after_secs: Some(secs),
+                        ..
+                    } => Duration::from_secs(secs),
+                    _ => Duration::from_millis(500 << attempt),
+                };
+                attempt += 1;
+                tracing::warn!(
+                    target: "mnemo::embedding",
+                    error = %e,
+                    attempt,
+                    backoff_ms = backoff.as_millis() as u64,
+                    "embedding chunk failed; retrying"
+                );
+                tokio::time::sleep(backoff).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EmbeddingProvider for PipelinedEmbedding {
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        let mut vectors = embed_chunk(
+            self.inner.as_ref(),
+            &self.budget,
+            &[text.to_string()],
+            self.config.max_retries,
+        )
+        .await?;
+        Ok(vectors.remove(0))
+    }
+
+    async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        if texts.len() <= self.config.chunk_size {
+            let texts: Vec<String> = texts.iter().map(|t| t.to_string()).collect();
+            return embed_chunk(
+                self.inner.as_ref(),
+                &self.budget,
+                &texts,
+                self.config.max_retries,
+            )
+            .await;
+        }
+
+        let permits = Arc::new(Semaphore::new(self.config.concurrency));
+        let mut tasks = JoinSet::new();
+        for (i, chunk) in texts.chunks(self.config.chunk_size).enumerate() {
+            let chunk: Vec<String> = chunk.iter().map(|t| t.to_string()).collect();
+            let inner = self.inner.clone();
+            let budget = self.budget.clone();
+            let permits = permits.clone();
+            let max_retries = self.config.max_retries;
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                let vectors = embed_chunk(inner.as_ref(), &budget, &chunk, max_retries).await;
+                (i, vectors)
+            });
+        }
+
+        let mut chunks: Vec<Option<Vec<Vec<f32>>>> = vec![None; tasks.len()];
+        while let Some(joined) = tasks.join_next().await {
+            let (i, vectors) =
+                joined.map_err(|e| Error::Internal(format!("embedding task failed: {e}")))?;
+            // Dropping `tasks` on error aborts the chunks still running.
+            chunks[i] = Some(vectors?);
+        }
+        Ok(chunks.into_iter().flatten().flatten().collect())
+    }
+
+    fn dimensions(&self) -> usize {
+        self.inner.dimensions()
+    }
+
+    fn is_semantic_capable(&self) -> bool {
+        self.inner.is_semantic_capable()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::embedding::DeterministicEmbedding;
+
+    /// Counts calls and tracks the most chunks in flight at once.
+    struct Counting {
+        inner: DeterministicEmbedding,
+        calls: AtomicUsize,
+        in_flight: AtomicUsize,
+        peak: AtomicUsize,
+        throttle_first: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl EmbeddingProvider for Counting {
+        async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+            self.inner.embed(text).await
+        }
+
+        async fn embed_batch(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self
+                .throttle_first
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                return Err(Error::RateLimited {
+                    message: "slow down".to_string(),
+                    retry_after_secs: Some(1),
+                });
+            }
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            self.inner.embed_batch(texts).await
+        }
+
+        fn dimensions(&self) -> usize {
+            self.inner.dimensions()
+        }
+    }
+
+    fn counting(throttle_first: usize) -> Arc<Counting> {
+        Arc::new(Counting {
+            inner: DeterministicEmbedding::new(16),
+            calls: AtomicUsize::new(0),
+            in_flight: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+            throttle_first: AtomicUsize::new(throttle_first),
+        })
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn chunks_run_in_parallel_and_keep_their_order() {
+        let provider = counting(0);
+        let pipeline = PipelinedEmbedding::new(
+            provider.clone(),
+            EmbeddingPipelineConfig {
+                concurrency: 3,
+                chunk_size: 4,
+                ..Default::default()
+            },
+        );
+        let texts: Vec<String> = (0..30).map(|i| format!("text {i}")).collect();
+        let refs: Vec<&str> = texts.iter().map(String::as_str).collect();
+
+        let vectors = pipeline.embed_batch(&refs).await.unwrap();
+        let expected = DeterministicEmbedding::new(16)
+            .embed_batch(&refs)
+            .await
+            .unwrap();
+        assert_eq!(vectors, expected);
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 8);
+        assert_eq!(provider.peak.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn request_budget_waits_for_the_next_minute() {
+        let provider = counting(0);
+        let pipeline = PipelinedEmbedding::new(
+            provider.clone(),
+            EmbeddingPipelineConfig {
+                chunk_size: 1,
+                requests_per_minute: Some(2),
+                ..Default::default()
+            },
+        );
+        let started = Instant::now();
+        pipeline.embed_batch(&["a", "b", "c"]).await.unwrap();
+        assert!(started.elapsed() >= WINDOW);
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn throttled_chunks_are_retried() {
+        let provider = counting(2);
+        let pipeline = PipelinedEmbedding::new(provider.clone(), Default::default());
+        let vectors = pipeline.embed_batch(&["a", "b"]).await.unwrap();
+        assert_eq!(vectors.len(), 2);
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 3);
+
+        let provider = counting(5);
+        let pipeline = PipelinedEmbedding::new(provider, Default::default());
+        let err = pipeline.embed_batch(&["a"]).await.unwrap_err();
+        assert!(matches!(err, Error::RateLimited { .. }));
+    }
+
+    #[test]
+    fn zero_limits_are_rejected() {
+        let config = EmbeddingPipelineConfig {
+            requests_per_minute: Some(0),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+        assert!(EmbeddingPipelineConfig::default().validate().is_ok());
+    }
+}
@@ -0,0 +1,292 @@
+//! Per-org encryption keys.
+//!
+//! With one engine-wide key, a leaked key exposes every tenant. A
+//! [`KeyRegistry`] instead maps each org to a key id and seals the org's
+//! records under that key. The key id is stored with the ciphertext, so
+//! moving an org to a new key leaves its older records readable as long as
+//! the old key stays loaded. Keys come from a [`KeyProvider`]: environment
+//! variables, key files, or a KMS that unwraps them (the `kms` module,
+//! behind the `aws-kms` and `vault` features).
+//!
+//! Content sealed under an org key is stored as `<key_id>:<payload>`, where
+//! the payload is the base64 of [`ContentEncryption::encrypt`]'s output.
+//! Content sealed under the engine-wide key is the bare payload; base64 has
+//! no `:`, so the two never collide.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+
+use super::ContentEncryption;
+use crate::error::{Error, Result};
+
+/// Source of the data keys a [`KeyRegistry`] seals with.
+#[async_trait]
+pub trait KeyProvider: Send + Sync {
+    /// The 32-byte data key named `key_id`.
+    async fn data_key(&self, key_id: &str) -> Result<ContentEncryption>;
+}
+
+/// Reject key ids that could not round-trip through sealed content or a
+/// file name: they must be non-empty ASCII alphanumerics, `-`, `_` or `.`.
+pub fn validate_key_id(key_id: &str) -> Result<()> {
+    if key_id.is_empty()
+        || key_id.len() > 128
+        || !key_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+    {
+        return Err(Error::Validation(format!(
+            "invalid key id {key_id:?}: use 1-128 ASCII alphanumerics, '-', '_' or '.'"
+        )));
+    }
+    Ok(())
+}
+
+/// Reads hex keys from environment variables: key `acme-2026` under the
+/// default prefix comes from `MNEMO_KEY_ACME_2026`.
+pub struct EnvKeyProvider {
+    prefix: String,
+}
+
+/// Default prefix of [`EnvKeyProvider`] variables.
+pub const DEFAULT_KEY_ENV_PREFIX: &str = "MNEMO_KEY_";
+
+impl EnvKeyProvider {
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+        }
+    }
+
+    /// The variable holding `key_id`.
+    pub fn var_name(&self, key_id: &str) -> String {
+        let suffix: String = key_id
+            .chars()
+            .map(|c| match c {
+                '-' | '.' => '_',
+                c => c.to_ascii_uppercase(),
+            })
+            .collect();
+        format!("{}{suffix}", self.prefix)
+    }
+}
+
+impl Default for EnvKeyProvider {
+    fn default() -> Self {
+        Self::new(DEFAULT_KEY_ENV_PREFIX)
+    }
+}
+
+#[async_trait]
+impl KeyProvider for EnvKeyProvider {
+    async fn data_key(&self, key_id: &str) -> Result<ContentEncryption> {
+        let var = self.var_name(key_id);
+        let hex_key = std::env::var(&var)
+            .map_err(|_| Error::Validation(format!("key {key_id}: {var} not set")))?;
+        ContentEncryption::from_hex(hex_key.trim())
+    }
+}
+
+/// Reads hex keys from `<dir>/<key_id>.key`.
+pub struct FileKeyProvider {
+    dir: PathBuf,
+}
+
+impl FileKeyProvider {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl KeyProvider for FileKeyProvider {
+    async fn data_key(&self, key_id: &str) -> Result<ContentEncryption> {
+        let path = self.dir.join(format!("{key_id}.key"));
+        let hex_key = tokio::fs::read_to_string(&path).await.map_err(|e| {
+            Error::Validation(format!("key {key_id}: cannot read {}: {e}", path.display()))
+        })?;
+        ContentEncryption::from_hex(hex_key.trim())
+    }
+}
+
+/// Keys held in memory, for tests and embedders that fetch keys themselves.
+#[derive(Default)]
+pub struct StaticKeyProvider {
+    keys: HashMap<String, [u8; 32]>,
+}
+
+impl StaticKeyProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_key(mut self, key_id: impl Into<String>, key: [u8; 32]) -> Self {
+        self.keys.insert(key_id.into(), key);
+        self
+    }
+}
+
+#[async_trait]
+impl KeyProvider for StaticKeyProvider {
+    async fn data_key(&self, key_id: &str) -> Result<ContentEncryption> {
+        self.keys
+            .get(key_id)
+            .map(|key| ContentEncryption::new(*key))
+            .ok_or_else(|| Error::Validation(format!("unknown key id {key_id}")))
+    }
+}
+
+/// The data keys of every org, resolved up front so sealing and opening
+/// stay synchronous on the read and write paths.
+pub struct KeyRegistry {
+    /// Org id → the key id its new records are sealed under.
+    org_keys: BTreeMap<String, String>,
+    keys: HashMap<String, ContentEncryption>,
+}
+
+impl KeyRegistry {
+    /// Fetch the key of every org in `orgs` (org id → key id) plus the
+    /// `retired` key ids, which no org writes with any more but older
+    /// records were sealed under.
+    pub async fn load(
+        provider: &dyn KeyProvider,
+        orgs: BTreeMap<String, String>,
+        retired: &[String],
+    ) -> Result<Self> {
+        let mut keys = HashMap::new();
+        for key_id in orgs.values().chain(retired) {
+            validate_key_id(key_id)?;
+            if !keys.contains_key(key_id) {
+                keys.insert(key_id.clone(), provider.data_key(key_id).await?);
+            }
+        }
+        tracing::info!(
+            orgs = orgs.len(),
+            keys = keys.len(),
+            "per-org encryption keys loaded"
+        );
+        Ok(Self {
+            org_keys: orgs,
+            keys,
+        })
+    }
+
+    /// The key id `org_id`'s new records are sealed under, if it has one.
+    pub fn key_id_for(&self, org_id: Option<&str>) -> Option<&str> {
+        org_id
+            .and_then(|org| self.org_keys.get(org))
+            .map(String::as_str)
+    }
+
+    /// Seal `plaintext` under `org_id`'s key; `None` when the org has none.
+    pub fn seal(&self, org_id: Option<&str>, plaintext: &[u8]) -> Option<Result<String>> {
+        let key_id = self.key_id_for(org_id)?;
+        let key = &self.keys[key_id];
+        Some(
+            key.encrypt(plaintext)
+                .map(|sealed| format!("{key_id}:{}", STANDARD.encode(sealed))),
+        )
+    }
+
+    /// Open content sealed by [`seal`](Self::seal); `None` when `stored`
+    /// carries no key id, i.e. was sealed under the engine-wide key.
+    pub fn open(&self, stored: &str) -> Option<Result<Vec<u8>>> {
+        let (key_id, payload) = stored.split_once(':')?;
+        Some(match self.keys.get(key_id) {
+            Some(key) => decode(payload).and_then(|bytes| key.decrypt(&bytes)),
+            None => Err(Error::Encryption(format!(
+                "content sealed under key {key_id}, which is not loaded"
+            ))),
+        })
+    }
+}
+
+/// Decode the base64 payload of sealed content.
+pub(crate) fn decode(payload: &str) -> Result<Vec<u8>> {
+    STANDARD
+        .decode(payload)
+        .map_err(|e| Error::Encryption(format!("invalid sealed content: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn registry() -> KeyRegistry {
+        let provider = StaticKeyProvider::new()
+            .with_key("acme-1", [1; 32])
+            .with_key("acme-2", [2; 32])
+            .with_key("globex-1", [3; 32]);
+        let orgs = BTreeMap::from([
+            ("acme".to_string(), "acme-2".to_string()),
+            ("globex".to_string(), "globex-1".to_string()),
+        ]);
+        KeyRegistry::load(&provider, orgs, &["acme-1".to_string()])
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_orgs_seal_under_their_own_keys() {
+        let registry = registry().await;
+        let acme = registry.seal(Some("acme"), b"acme plans").unwrap().unwrap();
+        let globex = registry
+            .seal(Some("globex"), b"globex plans")
+            .unwrap()
+            .unwrap();
+        assert!(acme.starts_with("acme-2:"));
+        assert!(globex.starts_with("globex-1:"));
+        assert_eq!(registry.open(&acme).unwrap().unwrap(), b"acme plans");
+
+        // Another org's key does not open the payload.
+        let (_, payload) = acme.split_once(':').unwrap();
+        assert!(
+            registry
+                .open(&format!("globex-1:{payload}"))
+                .unwrap()
+                .is_err()
+        );
+        assert!(registry.seal(Some("initech"), b"x").is_none());
+        assert!(registry.seal(None, b"x").is_none());
+        assert!(registry.open(payload).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_retired_keys_still_open() {
+        let registry = registry().await;
+        let old = ContentEncryption::new([1; 32])
+            .encrypt(b"old note")
+            .unwrap();
+        let stored = format!("acme-1:{}", STANDARD.encode(old));
+        assert_eq!(registry.open(&stored).unwrap().unwrap(), b"old note");
+        assert!(registry.open("acme-0:AAAA").unwrap().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_load_fails_on_a_missing_key() {
+        let orgs = BTreeMap::from([("acme".to_string(), "acme-9".to_string())]);
+        assert!(
+            KeyRegistry::load(&StaticKeyProvider::new(), orgs, &[])
+                .await
+                .is_err()
+        );
+        let orgs = BTreeMap::from([("acme".to_string(), "acme:9".to_string())]);
+        assert!(
+            KeyRegistry::load(&StaticKeyProvider::new(), orgs, &[])
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_env_var_names() {
+        assert_eq!(
+            EnvKeyProvider::default().var_name("acme-2026.q1"),
+            "MNEMO_KEY_ACME_2026_Q1"
+        );
+    }
+}
@@ -0,0 +1,159 @@
+//! KMS-backed [`KeyProvider`]s.
+//!
+//! Both providers use envelope encryption: every data key is stored wrapped
+//! (encrypted under a KMS master key) in `<dir>/<key_id>.wrapped`, and the
+//! KMS unwraps it once when the [`KeyRegistry`](super::keys::KeyRegistry)
+//! loads. The master key never leaves the KMS, and the plaintext data key
+//! lives only in the engine's memory.
+//!
+//! - [`AwsKmsKeyProvider`] (feature `aws-kms`): the file holds the base64
+//!   `CiphertextBlob` of `aws kms generate-data-key --key-spec AES_256`.
+//! - [`VaultKeyProvider`] (feature `vault`): the file holds the
+//!   `vault:v1:…` ciphertext of a Vault transit `datakey/wrapped` call.
+
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+
+use super::ContentEncryption;
+use super::keys::KeyProvider;
+use crate::error::{Error, Result};
+
+/// The wrapped form of `key_id`, trimmed.
+async fn read_wrapped(dir: &Path, key_id: &str) -> Result<String> {
+    let path = dir.join(format!("{key_id}.wrapped"));
+    let wrapped = tokio::fs::read_to_string(&path).await.map_err(|e| {
+        Error::Validation(format!("key {key_id}: cannot read {}: {e}", path.display()))
+    })?;
+    Ok(wrapped.trim().to_string())
+}
+
+fn data_key(key_id: &str, plaintext: &[u8]) -> Result<ContentEncryption> {
+    let key: [u8; 32] = plaintext.try_into().map_err(|_| {
+        Error::Encryption(format!(
+            "key {key_id}: unwrapped data key is {} bytes, expected 32",
+            plaintext.len()
+        ))
+    })?;
+    Ok(ContentEncryption::new(key))
+}
+
+/// Unwraps data keys with AWS KMS `Decrypt`.
+#[cfg(feature = "aws-kms")]
+pub struct AwsKmsKeyProvider {
+    client: aws_sdk_kms::Client,
+    dir: PathBuf,
+}
+
+#[cfg(feature = "aws-kms")]
+impl AwsKmsKeyProvider {
+    /// Client from the default AWS credential chain, optionally pinned to
+    /// `region`.
+    pub async fn new(dir: impl Into<PathBuf>, region: Option<String>) -> Self {
+        let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest());
+        if let Some(region) = region {
+            loader = loader.region(aws_config::Region::new(region));
+        }
+        Self {
+            client: aws_sdk_kms::Client::new(&loader.load().await),
+            dir: dir.into(),
+        }
+    }
+}
+
+#[cfg(feature = "aws-kms")]
+#[async_trait]
+impl KeyProvider for AwsKmsKeyProvider {
+    async fn data_key(&self, key_id: &str) -> Result<ContentEncryption> {
+        use base64::Engine as _;
+
+        let wrapped = read_wrapped(&self.dir, key_id).await?;
+        let blob = base64::engine::general_purpose::STANDARD
+            .decode(&wrapped)
+            .map_err(|e| Error::Validation(format!("key {key_id}: invalid wrapped key: {e}")))?;
+        let output = self
+            .client
+            .decrypt()
+            .ciphertext_blob(aws_sdk_kms::primitives::Blob::new(blob))
+            .send()
+            .await
+            .map_err(|e| Error::Unavailable(format!("key {key_id}: KMS decrypt failed: {e}")))?;
+        let plaintext = output
+            .plaintext()
+            .ok_or_else(|| Error::Encryption(format!("key {key_id}: KMS returned no plaintext")))?;
+        data_key(key_id, plaintext.as_ref())
+    }
+}
+
+/// Unwraps data keys with a Vault transit engine's `decrypt` endpoint.
+#[cfg(feature = "vault")]
+pub struct VaultKeyProvider {
+    client: reqwest::Client,
+    /// e.g. `https://vault.internal:8200`
+    addr: String,
+    /// Mount path of the transit engine, e.g. `transit`.
+    mount: String,
+    /// Name of the transit key that wrapped the data keys.
+    transit_key: String,
+    token: String,
+    dir: PathBuf,
+}
+
+#[cfg(feature = "vault")]
+impl VaultKeyProvider {
+    pub fn new(
+        addr: impl Into<String>,
+        mount: impl Into<String>,
+        transit_key: impl Into<String>,
+        token: impl Into<String>,
+        dir: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            addr: addr.into().trim_end_matches('/').to_string(),
+            mount: mount.into(),
+            transit_key: transit_key.into(),
+            token: token.into(),
+            dir: dir.into(),
+        }
+    }
+}
+
+#[cfg(feature = "vault")]
+#[async_trait]
+impl KeyProvider for VaultKeyProvider {
+    async fn data_key(&self, key_id: &str) -> Result<ContentEncryption> {
+        use base64::Engine as _;
+
+        let wrapped = read_wrapped(&self.dir, key_id).await?;
+        let url = format!(
+            "{}/v1/{}/decrypt/{}",
+            self.addr, self.mount, self.transit_key
+        );
+        let response = self
+            .client
+            .post(&url)
+            .header("X-Vault-Token", &self.token)
+            .json(&serde_json::json!({ "ciphertext": wrapped }))
+            .send()
+            .await
+            .map_err(|e| Error::Unavailable(format!("key {key_id}: Vault unreachable: {e}")))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::Encryption(format!(
+                "key {key_id}: Vault decrypt returned {status}"
+            )));
+        }
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| Error::Encryption(format!("key {key_id}: bad Vault response: {e}")))?;
+        let plaintext = body["data"]["plaintext"]
+            .as_str()
+            .and_then(|b64| base64::engine::general_purpose::STANDARD.decode(b64).ok())
+            .ok_or_else(|| {
+                Error::Encryption(format!("key {key_id}: Vault returned no plaintext"))
+            })?;
+        data_key(key_id, &plaintext)
+    }
+}
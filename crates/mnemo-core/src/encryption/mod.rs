@@ -2,6 +2,11 @@
 //!
 //! Provides encrypt/decrypt operations for memory content before storage.
 //! The encryption key is loaded from an environment variable or passed directly.
+//! [`keys`] adds per-org keys on top of the engine-wide one.
+
+pub mod keys;
+#[cfg(any(feature = "aws-kms", feature = "vault"))]
+pub mod kms;
 
 use crate::error::{Error, Result};
 
@@ -48,7 +53,7 @@ impl ContentEncryption {
     /// Uses AES-256-GCM with a random 12-byte nonce.
     pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
         let cipher = Aes256Gcm::new_from_slice(&self.key)
-            .map_err(|e| Error::Internal(format!("invalid AES-256 key: {e}")))?;
+            .map_err(|e| Error::Encryption(format!("invalid AES-256 key: {e}")))?;
 
         // Random 96-bit nonce straight from the OS CSPRNG. Using `getrandom`
         // directly (rather than aes-gcm's re-exported RNG) keeps this stable
@@ -60,7 +65,7 @@ impl ContentEncryption {
 
         let ciphertext = cipher
             .encrypt(&nonce, plaintext)
-            .map_err(|e| Error::Internal(format!("encryption failed: {e}")))?;
+            .map_err(|e| Error::Encryption(format!("encryption failed: {e}")))?;
 
         let mut output = Vec::with_capacity(12 + ciphertext.len());
         output.extend_from_slice(&nonce_bytes);
@@ -72,19 +77,19 @@ impl ContentEncryption {
     pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
         if data.len() < 28 {
             // 12 nonce + 16 tag minimum
-            return Err(Error::Validation("encrypted data too short".to_string()));
+            return Err(Error::Encryption("encrypted data too short".to_string()));
         }
 
         let cipher = Aes256Gcm::new_from_slice(&self.key)
-            .map_err(|e| Error::Internal(format!("invalid AES-256 key: {e}")))?;
+            .map_err(|e| Error::Encryption(format!("invalid AES-256 key: {e}")))?;
 
         let nonce = <Nonce<Aes256Gcm>>::try_from(&data[..12])
-            .map_err(|_| Error::Validation("invalid nonce".to_string()))?;
+            .map_err(|_| Error::Encryption("invalid nonce".to_string()))?;
         let ciphertext = &data[12..];
 
         cipher
             .decrypt(&nonce, ciphertext)
-            .map_err(|_| Error::Validation("decryption tag mismatch".to_string()))
+            .map_err(|_| Error::Encryption("decryption tag mismatch".to_string()))
     }
 }
 
@@ -1,4 +1,8 @@
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use uuid::Uuid;
+
+use crate::model::memory::MemoryRecord;
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -8,9 +12,70 @@ pub enum Error {
     #[error("validation error: {0}")]
     Validation(String),
 
+    /// Request validation failed on specific fields. Transports pass the
+    /// violations through (REST in the error body, gRPC as `BadRequest`
+    /// details) so clients can tell which field to fix.
+    #[error("validation error: {}", format_violations(.0))]
+    InvalidFields(Vec<FieldViolation>),
+
     #[error("permission denied: {0}")]
     PermissionDenied(String),
 
+    /// A write was attempted while the engine is read-only (see
+    /// [`MnemoEngine::set_read_only`](crate::query::MnemoEngine::set_read_only)).
+    /// Reads keep working; retry the write once writes are re-enabled.
+    #[error("read-only: {0}")]
+    ReadOnly(String),
+
+    /// An update named an `expected_version` the memory is no longer at:
+    /// someone else updated it first. `current` is the memory as stored
+    /// now, so the caller can merge and retry against `actual`.
+    #[error("version conflict on memory {memory_id}: expected version {expected}, found {actual}")]
+    VersionConflict {
+        memory_id: Uuid,
+        expected: u32,
+        actual: u32,
+        current: Box<MemoryRecord>,
+    },
+
+    /// The request clashes with existing state, e.g. a name that is
+    /// already taken. Retrying it unchanged fails the same way.
+    #[error("conflict: {0}")]
+    Conflict(String),
+
+    /// A configured limit, such as a channel's `max_memories`, is used up.
+    /// Retry once room has been made.
+    #[error("quota exceeded: {0}")]
+    QuotaExceeded(String),
+
+    /// An upstream service (e.g. the embedding API) throttled the call, or
+    /// the engine throttled an agent whose writes looked anomalous.
+    /// `retry_after_secs` is the hint, when there is one.
+    #[error("rate limited: {message}")]
+    RateLimited {
+        message: String,
+        retry_after_secs: Option<u64>,
+    },
+
+    /// A backend or upstream service could not be reached, or answered
+    /// with a server error. Usually transient.
+    #[error("unavailable: {0}")]
+    Unavailable(String),
+
+    /// A backend or upstream call ran past its deadline.
+    #[error("timeout: {0}")]
+    Timeout(String),
+
+    /// Encrypting or decrypting memory content failed: the wrong key, or
+    /// ciphertext that was truncated or tampered with.
+    #[error("encryption error: {0}")]
+    Encryption(String),
+
+    /// A persisted vector index or its side files could not be read back.
+    /// Rebuild the index from storage.
+    #[error("index corruption: {0}")]
+    IndexCorruption(String),
+
     #[error("storage error: {0}")]
     Storage(String),
 
@@ -83,8 +148,100 @@ impl From<serde_json::Error> for Error {
 
 impl From<reqwest::Error> for Error {
     fn from(e: reqwest::Error) -> Self {
-        Error::Embedding(e.to_string())
+        if e.is_timeout() {
+            Error::Timeout(e.to_string())
+        } else if e.is_connect() {
+            Error::Unavailable(e.to_string())
+        } else {
+            Error::Embedding(e.to_string())
+        }
+    }
+}
+
+/// One invalid field of a request. `path` names the field as it appears in
+/// the request body, e.g. `importance` or `tags`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldViolation {
+    pub path: String,
+    pub message: String,
+}
+
+impl FieldViolation {
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+fn format_violations(violations: &[FieldViolation]) -> String {
+    violations
+        .iter()
+        .map(|v| format!("{}: {}", v.path, v.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+impl Error {
+    /// An [`Error::InvalidFields`] for a single field.
+    pub fn invalid_field(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Error::InvalidFields(vec![FieldViolation::new(path, message)])
+    }
+
+    /// Whether this is a validation failure, with or without field paths.
+    pub fn is_validation(&self) -> bool {
+        matches!(self, Error::Validation(_) | Error::InvalidFields(_))
+    }
+
+    /// Whether the same request may succeed if retried later, with backoff:
+    /// throttling, unreachable or slow backends, and writes refused while
+    /// the engine is read-only. Everything else fails again until the
+    /// request or the stored state changes — a [`Error::VersionConflict`]
+    /// needs a merge first.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Error::RateLimited { .. }
+                | Error::Unavailable(_)
+                | Error::Timeout(_)
+                | Error::ReadOnly(_)
+        )
+    }
+
+    /// The field violations of an [`Error::InvalidFields`]; empty otherwise.
+    pub fn field_violations(&self) -> &[FieldViolation] {
+        match self {
+            Error::InvalidFields(violations) => violations,
+            _ => &[],
+        }
     }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_transient_failures_are_retryable() {
+        assert!(
+            Error::RateLimited {
+                message: "slow down".into(),
+                retry_after_secs: Some(2),
+            }
+            .is_retryable()
+        );
+        assert!(Error::Unavailable("connection refused".into()).is_retryable());
+        assert!(Error::Timeout("pool timed out".into()).is_retryable());
+        assert!(Error::ReadOnly("frozen".into()).is_retryable());
+
+        assert!(!Error::Conflict("exists".into()).is_retryable());
+        assert!(!Error::QuotaExceeded("full".into()).is_retryable());
+        assert!(!Error::Encryption("tag mismatch".into()).is_retryable());
+        assert!(!Error::IndexCorruption("bad mappings".into()).is_retryable());
+        assert!(!Error::Storage("disk".into()).is_retryable());
+        assert!(!Error::invalid_field("importance", "out of range").is_retryable());
+    }
+}
@@ -0,0 +1,820 @@
+//! Deterministic synthetic datasets for load and correctness testing.
+//!
+//! [`SeedGenerator`] turns a [`SeedConfig`] into a plausible memory bank:
+//! agents with uneven activity, threads owned by one agent each, memories
+//! written from topic templates with tags, types and importance to match,
+//! optional `related_to` / `derived_from` relations, a `MemoryWrite` event
+//! per memory and a checkpoint every `checkpoint_every` writes to a
+//! thread. Writes follow a working-hours, weekday-heavy rhythm that grows
+//! towards `until`, and every record is hash-chained the way
+//! [`remember`](crate::query::remember) chains it, so the bank verifies.
+//!
+//! Everything — ids included — is a function of the config alone: the
+//! generator draws from its own SplitMix64 stream rather than `rand`, so
+//! a seed names the same dataset across dependency upgrades, and
+//! timestamps count back from `until`, never from the wall clock.
+//!
+//! [`seed`] writes a dataset into an engine: it adds embeddings, encrypts
+//! content when the engine does, assigns logical clocks and updates agent
+//! profiles and indexes, as a live write would. `mnemo seed` wraps it.
+
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::hash::{compute_chain_hash, compute_content_hash};
+use crate::index::ShardRoute;
+use crate::model::checkpoint::Checkpoint;
+use crate::model::event::{AgentEvent, EventType};
+use crate::model::memory::{ConsolidationState, MemoryRecord, MemoryType, Scope, SourceType};
+use crate::model::relation::{DERIVED_FROM, Relation};
+use crate::query::MnemoEngine;
+use crate::storage::WriteBatch;
+
+/// Default end of a seeded timeline, so the same seed yields the same
+/// timestamps whenever it runs.
+pub const SEED_EPOCH: &str = "2026-01-01T00:00:00Z";
+
+/// Memories embedded per `embed_batch` call while seeding.
+const EMBED_CHUNK: usize = 256;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SeedConfig {
+    pub seed: u64,
+    pub agents: usize,
+    pub memories: usize,
+    /// Threads, spread over the agents; `0` writes thread-less memories.
+    pub threads: usize,
+    pub with_relations: bool,
+    /// Writes to a thread between its checkpoints; `0` writes none.
+    pub checkpoint_every: usize,
+    /// Days the timeline spans, ending at `until`.
+    pub days: u32,
+    pub until: DateTime<Utc>,
+    /// Prefix of the generated agent ids and thread ids.
+    pub prefix: String,
+}
+
+impl Default for SeedConfig {
+    fn default() -> Self {
+        Self {
+            seed: 42,
+            agents: 10,
+            memories: 10_000,
+            threads: 50,
+            with_relations: false,
+            checkpoint_every: 25,
+            days: 90,
+            until: DateTime::parse_from_rfc3339(SEED_EPOCH)
+                .expect("SEED_EPOCH is RFC 3339")
+                .with_timezone(&Utc),
+            prefix: "seed".to_string(),
+        }
+    }
+}
+
+impl SeedConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.agents == 0 {
+            return Err(Error::invalid_field("agents", "must be > 0"));
+        }
+        if self.days == 0 {
+            return Err(Error::invalid_field("days", "must be > 0"));
+        }
+        crate::query::validate_agent_id(&agent_id(&self.prefix, self.agents - 1))
+    }
+}
+
+fn agent_id(prefix: &str, index: usize) -> String {
+    format!("{prefix}-agent-{index:03}")
+}
+
+fn thread_id(prefix: &str, index: usize) -> String {
+    format!("{prefix}-thread-{index:03}")
+}
+
+/// SplitMix64: tiny, fast and fixed forever, unlike `rand`'s generators.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`.
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    fn chance(&mut self, p: f64) -> bool {
+        self.unit() < p
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    /// Index into `cumulative` (running totals of weights).
+    fn weighted(&mut self, cumulative: &[f64]) -> usize {
+        let target = self.unit() * cumulative[cumulative.len() - 1];
+        cumulative
+            .iter()
+            .position(|&total| target < total)
+            .unwrap_or(cumulative.len() - 1)
+    }
+
+    /// A v7 id carrying `at` as its timestamp, so ids sort like times.
+    fn uuid_at(&mut self, at: DateTime<Utc>) -> Uuid {
+        let mut bytes = [0u8; 10];
+        bytes[..8].copy_from_slice(&self.next_u64().to_le_bytes());
+        bytes[8..].copy_from_slice(&self.next_u64().to_le_bytes()[..2]);
+        uuid::Builder::from_unix_timestamp_millis(at.timestamp_millis() as u64, &bytes).into_uuid()
+    }
+}
+
+fn cumulative(weights: impl Iterator<Item = f64>) -> Vec<f64> {
+    weights
+        .scan(0.0, |total, w| {
+            *total += w;
+            Some(*total)
+        })
+        .collect()
+}
+
+/// A topic: the words its memories are written from.
+struct Topic {
+    tag: &'static str,
+    subjects: &'static [&'static str],
+    facts: &'static [&'static str],
+}
+
+const TOPICS: &[Topic] = &[
+    Topic {
+        tag: "deploys",
+        subjects: &[
+            "the api gateway",
+            "the billing service",
+            "the search cluster",
+            "the web app",
+        ],
+        facts: &[
+            "ships behind a feature flag",
+            "rolls out blue-green",
+            "needs a migration first",
+            "deploys from the release branch",
+        ],
+    },
+    Topic {
+        tag: "incidents",
+        subjects: &[
+            "the queue workers",
+            "the primary database",
+            "the cdn edge",
+            "the auth service",
+        ],
+        facts: &[
+            "paged on-call after a latency spike",
+            "ran out of connections",
+            "returned stale results",
+            "failed health checks for ten minutes",
+        ],
+    },
+    Topic {
+        tag: "customers",
+        subjects: &[
+            "Acme Corp",
+            "Globex",
+            "Initech",
+            "Umbrella Health",
+            "Stark Logistics",
+        ],
+        facts: &[
+            "asked for SSO on the starter plan",
+            "renewed for two years",
+            "reported slow exports",
+            "wants invoices in euros",
+        ],
+    },
+    Topic {
+        tag: "preferences",
+        subjects: &[
+            "the user",
+            "Priya",
+            "Marco",
+            "the design lead",
+            "the finance team",
+        ],
+        facts: &[
+            "prefers short summaries",
+            "wants dark mode everywhere",
+            "reads reports on Monday mornings",
+            "dislikes unscheduled calls",
+        ],
+    },
+    Topic {
+        tag: "planning",
+        subjects: &[
+            "the roadmap",
+            "the Q3 launch",
+            "the hiring plan",
+            "the pricing review",
+        ],
+        facts: &[
+            "slipped by two weeks",
+            "needs sign-off from legal",
+            "moved the beta to September",
+            "drops the mobile scope",
+        ],
+    },
+    Topic {
+        tag: "research",
+        subjects: &[
+            "the ranking experiment",
+            "the churn model",
+            "the embedding benchmark",
+            "the cache study",
+        ],
+        facts: &[
+            "improved recall by four points",
+            "overfits on last year's data",
+            "favours the smaller model",
+            "halved p95 latency",
+        ],
+    },
+];
+
+const EXTRA_TAGS: &[&str] = &[
+    "urgent",
+    "follow-up",
+    "decision",
+    "blocked",
+    "fyi",
+    "review",
+];
+const WEEKDAYS: &[&str] = &["Monday", "Tuesday", "Wednesday", "Thursday", "Friday"];
+const STEPS: &[&str] = &[
+    "drain traffic",
+    "snapshot the database",
+    "pause the workers",
+    "notify support",
+    "check the dashboards",
+];
+
+/// One memory with the relations and event written alongside it.
+#[derive(Debug, Clone)]
+pub struct SeededMemory {
+    pub record: MemoryRecord,
+    pub relations: Vec<Relation>,
+    pub event: AgentEvent,
+}
+
+#[derive(Debug, Clone)]
+pub struct SeededCheckpoint {
+    pub checkpoint: Checkpoint,
+    pub event: AgentEvent,
+}
+
+#[derive(Debug, Clone)]
+pub enum SeedItem {
+    Memory(Box<SeededMemory>),
+    Checkpoint(Box<SeededCheckpoint>),
+}
+
+#[derive(Default)]
+struct ThreadState {
+    since_checkpoint: Vec<Uuid>,
+    last_checkpoint: Option<Uuid>,
+    step: usize,
+}
+
+/// Yields one day of a seeded timeline at a time, oldest first, in write
+/// order.
+pub struct SeedGenerator {
+    config: SeedConfig,
+    rng: SplitMix64,
+    agent_weights: Vec<f64>,
+    threads_by_agent: Vec<Vec<usize>>,
+    day_counts: Vec<usize>,
+    day: usize,
+    /// Last content hash per (agent, thread) memory chain.
+    memory_chains: HashMap<(usize, Option<usize>), Vec<u8>>,
+    /// Last content hash per (agent, thread) event chain; an agent's
+    /// thread-less events chain to its latest event in any thread.
+    event_chains: HashMap<(usize, Option<usize>), Vec<u8>>,
+    /// Recent memories per (agent, topic), newest last.
+    recent: HashMap<(usize, usize), VecDeque<(Uuid, MemoryType)>>,
+    threads: HashMap<usize, ThreadState>,
+}
+
+impl SeedGenerator {
+    pub fn new(config: SeedConfig) -> Result<Self> {
+        config.validate()?;
+        let mut rng = SplitMix64(config.seed);
+        // A few busy agents and a long tail, like real deployments.
+        let agent_weights = cumulative((0..config.agents).map(|i| 1.0 / (i as f64 + 1.0)));
+        let mut threads_by_agent = vec![Vec::new(); config.agents];
+        for thread in 0..config.threads {
+            threads_by_agent[thread % config.agents].push(thread);
+        }
+
+        // Weekdays carry more writes than weekends, and activity grows
+        // towards the end of the timeline.
+        let start = config.until - Duration::days(config.days as i64);
+        let weights: Vec<f64> = (0..config.days)
+            .map(|d| {
+                let date = start + Duration::days(d as i64);
+                let weekday = !matches!(date.weekday(), Weekday::Sat | Weekday::Sun);
+                let growth = 1.0 + d as f64 / config.days as f64;
+                growth * if weekday { 1.0 } else { 0.3 } * (0.8 + 0.4 * rng.unit())
+            })
+            .collect();
+        let total: f64 = weights.iter().sum();
+        let mut day_counts = Vec::with_capacity(weights.len());
+        let (mut carried, mut assigned) = (0.0, 0);
+        for w in &weights {
+            carried += w / total * config.memories as f64;
+            let upto = carried.round() as usize;
+            day_counts.push(upto - assigned);
+            assigned = upto;
+        }
+        if let Some(last) = day_counts.last_mut() {
+            *last += config.memories - assigned;
+        }
+
+        Ok(Self {
+            config,
+            rng,
+            agent_weights,
+            threads_by_agent,
+            day_counts,
+            day: 0,
+            memory_chains: HashMap::new(),
+            event_chains: HashMap::new(),
+            recent: HashMap::new(),
+            threads: HashMap::new(),
+        })
+    }
+
+    pub fn config(&self) -> &SeedConfig {
+        &self.config
+    }
+
+    /// Write times for `count` memories on day `day`, in order. Most fall
+    /// in working hours, UTC.
+    fn times(&mut self, day: usize, count: usize) -> Vec<DateTime<Utc>> {
+        let start = self.config.until - Duration::days((self.config.days as usize - day) as i64);
+        let midnight = Utc.from_utc_datetime(&start.date_naive().and_time(NaiveTime::MIN));
+        let mut times: Vec<DateTime<Utc>> = (0..count)
+            .map(|_| {
+                let hour = if self.rng.chance(0.8) {
+                    8 + self.rng.below(11)
+                } else {
+                    self.rng.below(24)
+                };
+                let second = hour * 3600 + self.rng.below(3600);
+                midnight + Duration::seconds(second as i64)
+            })
+            .collect();
+        times.sort();
+        times
+    }
+
+    fn content(&mut self, topic: &Topic, memory_type: MemoryType) -> String {
+        let subject = *self.rng.pick(topic.subjects);
+        let fact = *self.rng.pick(topic.facts);
+        match memory_type {
+            MemoryType::Semantic => format!("Known: {subject} {fact}."),
+            MemoryType::Procedural => format!(
+                "Runbook for {subject}: {} first, then {}.",
+                self.rng.pick(STEPS),
+                self.rng.pick(STEPS)
+            ),
+            MemoryType::Working => format!("Currently looking into how {subject} {fact}."),
+            MemoryType::Episodic => format!(
+                "On {} we learned that {subject} {fact}.",
+                self.rng.pick(WEEKDAYS)
+            ),
+        }
+    }
+
+    fn memory(&mut self, at: DateTime<Utc>, items: &mut Vec<SeedItem>) {
+        let prefix = self.config.prefix.clone();
+        let agent = self.rng.weighted(&self.agent_weights);
+        let agent_id = agent_id(&prefix, agent);
+        let thread = match self.threads_by_agent[agent].as_slice() {
+            [] => None,
+            threads if self.rng.chance(0.7) => Some(*self.rng.pick(threads)),
+            _ => None,
+        };
+        let topic_index = self.rng.below(TOPICS.len());
+        let topic = &TOPICS[topic_index];
+        let roll = self.rng.unit();
+        let memory_type = if roll < 0.6 {
+            MemoryType::Episodic
+        } else if roll < 0.85 {
+            MemoryType::Semantic
+        } else if roll < 0.95 {
+            MemoryType::Procedural
+        } else {
+            MemoryType::Working
+        };
+        let content = self.content(topic, memory_type);
+        let mut tags = vec![topic.tag.to_string()];
+        if self.rng.chance(0.4) {
+            let extra = self.rng.pick(EXTRA_TAGS).to_string();
+            tags.push(extra);
+        }
+        // Mostly routine; facts and runbooks matter more.
+        let base: f64 = match memory_type {
+            MemoryType::Semantic | MemoryType::Procedural => 0.5,
+            _ => 0.2,
+        };
+        let importance = (base + 0.5 * self.rng.unit().powi(2)).min(1.0) as f32;
+        let scope = if self.rng.chance(0.15) {
+            Scope::Shared
+        } else {
+            Scope::Private
+        };
+
+        let id = self.rng.uuid_at(at);
+        let timestamp = at.to_rfc3339();
+        let content_hash = compute_content_hash(&content, &agent_id, &timestamp);
+        let chain = (agent, thread);
+        let prev_hash = Some(compute_chain_hash(
+            &content_hash,
+            self.memory_chains.get(&chain).map(Vec::as_slice),
+        ));
+        self.memory_chains.insert(chain, content_hash.clone());
+
+        let mut record = MemoryRecord::new(agent_id.clone(), content);
+        record.id = id;
+        record.memory_type = memory_type;
+        record.scope = scope;
+        record.importance = importance;
+        record.tags = tags;
+        record.metadata = serde_json::json!({ "seed": self.config.seed });
+        record.content_hash = content_hash.clone();
+        record.prev_hash = prev_hash;
+        record.source_type = SourceType::Agent;
+        record.consolidation_state = ConsolidationState::Raw;
+        record.thread_id = thread.map(|t| thread_id(&prefix, t));
+        record.created_at = timestamp.clone();
+        record.updated_at = timestamp.clone();
+
+        let mut relations = Vec::new();
+        let recent = self.recent.entry((agent, topic_index)).or_default();
+        if self.config.with_relations && !recent.is_empty() {
+            let recent: Vec<(Uuid, MemoryType)> = recent.iter().copied().collect();
+            let (target, target_type) = *self.rng.pick(&recent);
+            let relation_type =
+                if memory_type == MemoryType::Semantic && target_type == MemoryType::Episodic {
+                    DERIVED_FROM
+                } else {
+                    "related_to"
+                };
+            if relation_type == DERIVED_FROM || self.rng.chance(0.3) {
+                relations.push(Relation {
+                    id: self.rng.uuid_at(at),
+                    source_id: id,
+                    target_id: target,
+                    relation_type: relation_type.to_string(),
+                    weight: (0.5 + 0.5 * self.rng.unit()) as f32,
+                    metadata: serde_json::json!({}),
+                    created_at: timestamp.clone(),
+                });
+            }
+        }
+        let recent = self.recent.entry((agent, topic_index)).or_default();
+        recent.push_back((id, memory_type));
+        if recent.len() > 20 {
+            recent.pop_front();
+        }
+
+        let event = self.event(
+            agent,
+            thread,
+            EventType::MemoryWrite,
+            serde_json::json!({ "memory_id": id.to_string() }),
+            content_hash,
+            at,
+        );
+        items.push(SeedItem::Memory(Box::new(SeededMemory {
+            record,
+            relations,
+            event,
+        })));
+
+        if let Some(thread) = thread
+            && self.config.checkpoint_every > 0
+        {
+            let state = self.threads.entry(thread).or_default();
+            state.since_checkpoint.push(id);
+            if state.since_checkpoint.len() >= self.config.checkpoint_every {
+                self.checkpoint(agent, thread, at + Duration::seconds(1), items);
+            }
+        }
+    }
+
+    fn checkpoint(
+        &mut self,
+        agent: usize,
+        thread: usize,
+        at: DateTime<Utc>,
+        items: &mut Vec<SeedItem>,
+    ) {
+        let id = self.rng.uuid_at(at);
+        let state = self.threads.entry(thread).or_default();
+        state.step += 1;
+        let memory_refs = std::mem::take(&mut state.since_checkpoint);
+        let parent_id = state.last_checkpoint.replace(id);
+        let step = state.step;
+        let agent_id = agent_id(&self.config.prefix, agent);
+        let timestamp = at.to_rfc3339();
+        let checkpoint = Checkpoint {
+            id,
+            thread_id: thread_id(&self.config.prefix, thread),
+            agent_id: agent_id.clone(),
+            parent_id,
+            branch_name: "main".to_string(),
+            state_snapshot: serde_json::json!({
+                "step": step,
+                "memories": memory_refs.len(),
+            }),
+            state_diff: None,
+            memory_refs,
+            event_cursor: None,
+            label: Some(format!("step-{step}")),
+            created_at: timestamp.clone(),
+            metadata: serde_json::json!({ "seed": self.config.seed }),
+        };
+        let content_hash = compute_content_hash(&id.to_string(), &agent_id, &timestamp);
+        let event = self.event(
+            agent,
+            Some(thread),
+            EventType::Checkpoint,
+            serde_json::json!({ "checkpoint_id": id.to_string(), "branch": "main" }),
+            content_hash,
+            at,
+        );
+        items.push(SeedItem::Checkpoint(Box::new(SeededCheckpoint {
+            checkpoint,
+            event,
+        })));
+    }
+
+    fn event(
+        &mut self,
+        agent: usize,
+        thread: Option<usize>,
+        event_type: EventType,
+        payload: serde_json::Value,
+        content_hash: Vec<u8>,
+        at: DateTime<Utc>,
+    ) -> AgentEvent {
+        let chain = (agent, thread);
+        let prev_hash = Some(compute_chain_hash(
+            &content_hash,
+            self.event_chains.get(&chain).map(Vec::as_slice),
+        ));
+        self.event_chains.insert(chain, content_hash.clone());
+        self.event_chains
+            .insert((agent, None), content_hash.clone());
+        AgentEvent {
+            id: self.rng.uuid_at(at),
+            agent_id: agent_id(&self.config.prefix, agent),
+            thread_id: thread.map(|t| thread_id(&self.config.prefix, t)),
+            run_id: None,
+            parent_event_id: None,
+            event_type,
+            payload,
+            trace_id: None,
+            span_id: None,
+            model: None,
+            tokens_input: None,
+            tokens_output: None,
+            latency_ms: None,
+            cost_usd: None,
+            timestamp: at.to_rfc3339(),
+            logical_clock: 0,
+            content_hash,
+            prev_hash,
+            embedding: None,
+            request_id: None,
+        }
+    }
+}
+
+impl Iterator for SeedGenerator {
+    /// The next day with any writes.
+    type Item = Vec<SeedItem>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.day < self.day_counts.len() {
+            let (day, count) = (self.day, self.day_counts[self.day]);
+            self.day += 1;
+            if count == 0 {
+                continue;
+            }
+            let mut items = Vec::with_capacity(count);
+            for at in self.times(day, count) {
+                self.memory(at, &mut items);
+            }
+            return Some(items);
+        }
+        None
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SeedReport {
+    pub agents: usize,
+    pub threads: usize,
+    pub memories: usize,
+    pub relations: usize,
+    pub checkpoints: usize,
+    pub events: usize,
+    pub first_at: Option<String>,
+    pub last_at: Option<String>,
+}
+
+/// Generate the dataset `config` describes and write it into `engine`.
+/// Chains start fresh, so seed an empty engine.
+pub async fn seed(engine: &MnemoEngine, config: SeedConfig) -> Result<SeedReport> {
+    let mut report = SeedReport {
+        agents: config.agents,
+        threads: config.threads,
+        ..Default::default()
+    };
+    for day in SeedGenerator::new(config)? {
+        for chunk in day.chunks(EMBED_CHUNK) {
+            write_chunk(engine, chunk, &mut report).await?;
+        }
+        if let Some(ref ft) = engine.full_text {
+            ft.commit()?;
+        }
+        tracing::debug!(
+            memories = report.memories,
+            "seeded up to {:?}",
+            report.last_at
+        );
+    }
+    Ok(report)
+}
+
+async fn write_chunk(
+    engine: &MnemoEngine,
+    chunk: &[SeedItem],
+    report: &mut SeedReport,
+) -> Result<()> {
+    let contents: Vec<&str> = chunk
+        .iter()
+        .filter_map(|item| match item {
+            SeedItem::Memory(memory) => Some(memory.record.content.as_str()),
+            SeedItem::Checkpoint(_) => None,
+        })
+        .collect();
+    let mut embeddings = engine.embedding.embed_batch(&contents).await?.into_iter();
+
+    for item in chunk {
+        match item {
+            SeedItem::Memory(memory) => {
+                let SeededMemory {
+                    mut record,
+                    relations,
+                    mut event,
+                } = (**memory).clone();
+                let embedding = embeddings.next();
+                record.embedding = embedding.clone();
+                let plaintext = record.content.clone();
+                record.content = engine.encrypt_content(record.org_id.as_deref(), &plaintext)?;
+                event.logical_clock = crate::query::clock::tick(engine, &record.agent_id).await;
+                engine
+                    .storage
+                    .commit_write(&WriteBatch {
+                        memory: Some(record.clone()),
+                        relations: relations.clone(),
+                        events: vec![event],
+                        ..Default::default()
+                    })
+                    .await?;
+                if let Some(ref embedding) = embedding {
+                    engine
+                        .index
+                        .add_routed(ShardRoute::of(&record), record.id, embedding)?;
+                }
+                if let Some(ref ft) = engine.full_text {
+                    ft.add_with_language(record.id, &record.content, None)?;
+                }
+                crate::query::poisoning::update_agent_profile(engine, &record).await?;
+
+                report.memories += 1;
+                report.relations += relations.len();
+                report.events += 1;
+                if report.first_at.is_none() {
+                    report.first_at = Some(record.created_at.clone());
+                }
+                report.last_at = Some(record.created_at);
+            }
+            SeedItem::Checkpoint(checkpoint) => {
+                let mut event = checkpoint.event.clone();
+                event.logical_clock =
+                    crate::query::clock::tick(engine, &checkpoint.checkpoint.agent_id).await;
+                engine
+                    .storage
+                    .insert_checkpoint(&checkpoint.checkpoint)
+                    .await?;
+                engine.storage.insert_event(&event).await?;
+                report.checkpoints += 1;
+                report.events += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Timelike;
+
+    use super::*;
+
+    fn config() -> SeedConfig {
+        SeedConfig {
+            agents: 3,
+            memories: 500,
+            threads: 4,
+            with_relations: true,
+            checkpoint_every: 10,
+            days: 14,
+            ..Default::default()
+        }
+    }
+
+    fn memories(config: SeedConfig) -> Vec<MemoryRecord> {
+        SeedGenerator::new(config)
+            .unwrap()
+            .flatten()
+            .filter_map(|item| match item {
+                SeedItem::Memory(memory) => Some(memory.record),
+                SeedItem::Checkpoint(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_same_seed_same_dataset() {
+        let a = memories(config());
+        let b = memories(config());
+        assert_eq!(a.len(), 500);
+        assert_eq!(a, b);
+        let c = memories(SeedConfig {
+            seed: 7,
+            ..config()
+        });
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_timeline_is_ordered_and_bounded() {
+        let config = config();
+        let until = config.until;
+        let start = until - Duration::days(config.days as i64);
+        let records = memories(config);
+        let times: Vec<DateTime<Utc>> = records
+            .iter()
+            .map(|r| DateTime::parse_from_rfc3339(&r.created_at).unwrap().into())
+            .collect();
+        assert!(times.windows(2).all(|w| w[0] <= w[1]));
+        assert!(times.iter().all(|t| *t >= start && *t < until));
+        let working = times.iter().filter(|t| (8..19).contains(&t.hour())).count();
+        assert!(working * 2 > times.len());
+    }
+
+    #[test]
+    fn test_checkpoints_cover_thread_writes() {
+        let items: Vec<SeedItem> = SeedGenerator::new(config()).unwrap().flatten().collect();
+        let checkpoints: Vec<&Checkpoint> = items
+            .iter()
+            .filter_map(|item| match item {
+                SeedItem::Checkpoint(c) => Some(&c.checkpoint),
+                SeedItem::Memory(_) => None,
+            })
+            .collect();
+        assert!(!checkpoints.is_empty());
+        assert!(checkpoints.iter().all(|c| c.memory_refs.len() == 10));
+        assert!(checkpoints.iter().any(|c| c.parent_id.is_some()));
+    }
+}
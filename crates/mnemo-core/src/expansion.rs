@@ -0,0 +1,26 @@
+//! Query expansion for multi-query recall.
+//!
+//! A recall with several phrasings of one question
+//! ([`RecallRequest::queries`](crate::query::recall::RecallRequest::queries))
+//! searches with each and fuses the rankings, so a memory worded unlike the
+//! original query can still surface. With
+//! [`expand_query`](crate::query::recall::RecallRequest::expand_query) the
+//! extra phrasings come from the attached [`QueryExpander`]: an LLM-backed
+//! implementation prompts a model for paraphrases, and the engine core
+//! never embeds a model client. Attach one via
+//! [`MnemoEngine::with_query_expander`](crate::query::MnemoEngine::with_query_expander).
+//!
+//! There is no model-free fallback; asking for expansion without an
+//! expander is an error rather than a silent single-query recall.
+
+use crate::error::Result;
+
+/// Pluggable paraphrasing used by multi-query recall.
+#[async_trait::async_trait]
+pub trait QueryExpander: Send + Sync {
+    /// Alternative phrasings of `query`, best first. The original query
+    /// need not be repeated; blank and duplicate entries are ignored.
+    async fn expand(&self, query: &str) -> Result<Vec<String>>;
+    /// Stable identifier used in logs.
+    fn name(&self) -> &str;
+}
@@ -39,6 +39,16 @@ pub struct ChainVerificationResult {
 }
 
 pub fn verify_chain(records: &[MemoryRecord]) -> ChainVerificationResult {
+    verify_chain_from(None, records)
+}
+
+/// Verify `records` as the continuation of a chain whose last verified
+/// record had content hash `prev_content_hash`, so the first record's
+/// `prev_hash` is checked too. `None` verifies them as a chain of their own.
+pub fn verify_chain_from(
+    prev_content_hash: Option<&[u8]>,
+    records: &[MemoryRecord],
+) -> ChainVerificationResult {
     if records.is_empty() {
         return ChainVerificationResult {
             valid: true,
@@ -66,10 +76,12 @@ pub fn verify_chain(records: &[MemoryRecord]) -> ChainVerificationResult {
         }
 
         // Verify chain linking (prev_hash)
-        if i > 0 {
-            let prev_record = &records[i - 1];
-            let expected_chain =
-                compute_chain_hash(&record.content_hash, Some(&prev_record.content_hash));
+        let prev_content = match i {
+            0 => prev_content_hash,
+            _ => Some(records[i - 1].content_hash.as_slice()),
+        };
+        if let Some(prev_content) = prev_content {
+            let expected_chain = compute_chain_hash(&record.content_hash, Some(prev_content));
             if let Some(ref prev_hash) = record.prev_hash
                 && !hashes_equal(prev_hash, &expected_chain)
             {
@@ -156,6 +168,50 @@ pub fn verify_event_chain(events: &[AgentEvent]) -> ChainVerificationResult {
     }
 }
 
+/// Fold one more content hash into a rolling chain root:
+/// `sha256(root || content_hash)`. The root before any record is empty.
+pub fn roll_chain_root(root: &[u8], content_hash: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(root);
+    hasher.update(content_hash);
+    hasher.finalize().to_vec()
+}
+
+/// Merkle leaf of one event: its id, content hash and chain link.
+fn event_merkle_leaf(event: &AgentEvent) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update([0u8]);
+    hasher.update(event.id.as_bytes());
+    hasher.update(&event.content_hash);
+    if let Some(ref prev) = event.prev_hash {
+        hasher.update(prev);
+    }
+    hasher.finalize().to_vec()
+}
+
+/// SHA-256 Merkle root over `events` in the given order. Leaves and inner
+/// nodes are domain-separated; an odd node is paired with itself. The root
+/// of no events is the hash of the empty string.
+pub fn compute_event_merkle_root(events: &[AgentEvent]) -> Vec<u8> {
+    if events.is_empty() {
+        return Sha256::digest([]).to_vec();
+    }
+    let mut level: Vec<Vec<u8>> = events.iter().map(event_merkle_leaf).collect();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                let mut hasher = Sha256::new();
+                hasher.update([1u8]);
+                hasher.update(&pair[0]);
+                hasher.update(pair.get(1).unwrap_or(&pair[0]));
+                hasher.finalize().to_vec()
+            })
+            .collect();
+    }
+    level.remove(0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -246,6 +302,12 @@ mod tests {
                 quarantined: false,
                 quarantine_reason: None,
                 decay_function: None,
+                pinned: false,
+                valid_from: None,
+                valid_until: None,
+                curation: None,
+                attestation: None,
+                channel: None,
             });
         }
 
@@ -307,6 +369,12 @@ mod tests {
                 quarantined: false,
                 quarantine_reason: None,
                 decay_function: None,
+                pinned: false,
+                valid_from: None,
+                valid_until: None,
+                curation: None,
+                attestation: None,
+                channel: None,
             });
         }
 
@@ -323,4 +391,29 @@ mod tests {
                 .contains("content hash mismatch")
         );
     }
+
+    #[test]
+    fn test_event_merkle_root_covers_every_event() {
+        use crate::model::event::EventType;
+
+        let events: Vec<AgentEvent> = (0..5)
+            .map(|i| {
+                AgentEvent::new(
+                    "a".to_string(),
+                    EventType::MemoryWrite,
+                    serde_json::Value::Null,
+                    format!("2025-01-01T00:00:0{i}Z"),
+                    compute_content_hash(&i.to_string(), "a", "t"),
+                )
+            })
+            .collect();
+        let root = compute_event_merkle_root(&events);
+        assert_eq!(root.len(), 32);
+        assert_eq!(root, compute_event_merkle_root(&events));
+
+        let mut tampered = events.clone();
+        tampered[4].content_hash = compute_content_hash("x", "a", "t");
+        assert_ne!(compute_event_merkle_root(&tampered), root);
+        assert_ne!(compute_event_merkle_root(&events[..4]), root);
+    }
 }
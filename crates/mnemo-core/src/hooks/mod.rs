@@ -0,0 +1,235 @@
+//! Plugin hooks for the remember / recall / forget pipelines.
+//!
+//! A [`Hook`] runs custom logic around the engine's core operations without
+//! forking the crate: validate or rewrite a request before it executes,
+//! observe a write after it lands, or re-score recall results. Hooks are
+//! attached with [`MnemoEngine::with_hook`](crate::query::MnemoEngine::with_hook)
+//! and run by the public `remember`, `recall` and `forget` methods.
+//!
+//! Hooks run in ascending [`HookOptions::priority`], ties in registration
+//! order. Every method has a no-op default, so a hook only implements the
+//! stages it cares about. A hook that returns an error is handled according
+//! to its [`HookErrorPolicy`]: `Abort` fails the operation with that error,
+//! `Warn` logs it and carries on with the next hook. A failing post-stage
+//! hook never undoes the operation it observed.
+//!
+//! [`wasm::WasmHook`] loads a hook from a WebAssembly module at runtime
+//! behind the `wasm-hooks` feature, and [`script::ScriptHook`] runs a Rhai
+//! policy script from the config file behind the `scripting` feature.
+
+pub mod script;
+pub mod wasm;
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::query::forget::ForgetRequest;
+use crate::query::recall::{RecallRequest, RecallResponse};
+use crate::query::remember::{RememberRequest, RememberResponse};
+
+/// Pipeline point a hook runs at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookStage {
+    PreRemember,
+    PostRemember,
+    PreRecall,
+    PostRecall,
+    PreForget,
+}
+
+impl std::fmt::Display for HookStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HookStage::PreRemember => write!(f, "pre_remember"),
+            HookStage::PostRemember => write!(f, "post_remember"),
+            HookStage::PreRecall => write!(f, "pre_recall"),
+            HookStage::PostRecall => write!(f, "post_recall"),
+            HookStage::PreForget => write!(f, "pre_forget"),
+        }
+    }
+}
+
+/// Custom logic around `remember`, `recall` and `forget`.
+#[async_trait::async_trait]
+pub trait Hook: Send + Sync {
+    /// Stable identifier used in logs.
+    fn name(&self) -> &str;
+
+    /// Validate or rewrite a write before it is stored.
+    async fn pre_remember(&self, _request: &mut RememberRequest) -> Result<()> {
+        Ok(())
+    }
+
+    /// Observe a completed write.
+    async fn post_remember(
+        &self,
+        _request: &RememberRequest,
+        _response: &RememberResponse,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Validate or rewrite a recall before it runs.
+    async fn pre_recall(&self, _request: &mut RecallRequest) -> Result<()> {
+        Ok(())
+    }
+
+    /// Filter, re-score or annotate recall results.
+    async fn post_recall(
+        &self,
+        _request: &RecallRequest,
+        _response: &mut RecallResponse,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Validate or rewrite a deletion before it runs.
+    async fn pre_forget(&self, _request: &mut ForgetRequest) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// What happens when a hook returns an error.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookErrorPolicy {
+    /// Fail the operation with the hook's error.
+    #[default]
+    Abort,
+    /// Log the error and continue with the next hook.
+    Warn,
+}
+
+/// Registration options for one hook.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookOptions {
+    /// Lower runs first.
+    pub priority: i32,
+    pub on_error: HookErrorPolicy,
+}
+
+impl HookOptions {
+    pub fn new(priority: i32, on_error: HookErrorPolicy) -> Self {
+        Self { priority, on_error }
+    }
+}
+
+#[derive(Clone)]
+struct RegisteredHook {
+    hook: Arc<dyn Hook>,
+    options: HookOptions,
+}
+
+/// Ordered set of hooks attached to an engine.
+#[derive(Clone, Default)]
+pub struct HookRegistry {
+    hooks: Vec<RegisteredHook>,
+}
+
+impl std::fmt::Debug for HookRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.hooks.iter().map(|h| h.hook.name()))
+            .finish()
+    }
+}
+
+impl HookRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a hook, keeping the registry sorted by priority. Hooks with equal
+    /// priority run in the order they were registered.
+    pub fn register(&mut self, hook: Arc<dyn Hook>, options: HookOptions) {
+        let at = self
+            .hooks
+            .partition_point(|h| h.options.priority <= options.priority);
+        self.hooks.insert(at, RegisteredHook { hook, options });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.hooks.len()
+    }
+
+    /// Hook names in execution order.
+    pub fn names(&self) -> Vec<String> {
+        self.hooks
+            .iter()
+            .map(|h| h.hook.name().to_string())
+            .collect()
+    }
+
+    pub async fn pre_remember(&self, request: &mut RememberRequest) -> Result<()> {
+        for h in &self.hooks {
+            settle(
+                h,
+                HookStage::PreRemember,
+                h.hook.pre_remember(request).await,
+            )?;
+        }
+        Ok(())
+    }
+
+    pub async fn post_remember(
+        &self,
+        request: &RememberRequest,
+        response: &RememberResponse,
+    ) -> Result<()> {
+        for h in &self.hooks {
+            settle(
+                h,
+                HookStage::PostRemember,
+                h.hook.post_remember(request, response).await,
+            )?;
+        }
+        Ok(())
+    }
+
+    pub async fn pre_recall(&self, request: &mut RecallRequest) -> Result<()> {
+        for h in &self.hooks {
+            settle(h, HookStage::PreRecall, h.hook.pre_recall(request).await)?;
+        }
+        Ok(())
+    }
+
+    pub async fn post_recall(
+        &self,
+        request: &RecallRequest,
+        response: &mut RecallResponse,
+    ) -> Result<()> {
+        for h in &self.hooks {
+            settle(
+                h,
+                HookStage::PostRecall,
+                h.hook.post_recall(request, response).await,
+            )?;
+        }
+        Ok(())
+    }
+
+    pub async fn pre_forget(&self, request: &mut ForgetRequest) -> Result<()> {
+        for h in &self.hooks {
+            settle(h, HookStage::PreForget, h.hook.pre_forget(request).await)?;
+        }
+        Ok(())
+    }
+}
+
+fn settle(hook: &RegisteredHook, stage: HookStage, result: Result<()>) -> Result<()> {
+    match (result, hook.options.on_error) {
+        (Ok(()), _) => Ok(()),
+        (Err(e), HookErrorPolicy::Abort) => Err(e),
+        (Err(e), HookErrorPolicy::Warn) => {
+            tracing::warn!(hook = hook.hook.name(), %stage, error = %e, "hook failed, continuing");
+            Ok(())
+        }
+    }
+}
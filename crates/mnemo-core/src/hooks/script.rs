@@ -0,0 +1,230 @@
+//! Rhai policy scripts.
+//!
+//! [`ScriptHook`] lets operators express write policies — "reject memories
+//! that name a competitor", "force importance 1.0 for `incident` tags" — as
+//! a small [Rhai](https://rhai.rs) script instead of a compiled [`Hook`].
+//! Scripts are listed under `[[hooks.scripts]]` in the engine config file
+//! and loaded by [`MnemoEngineConfig::apply`](crate::config::MnemoEngineConfig::apply).
+//!
+//! A script defines any of these functions:
+//!
+//! - `pre_remember(memory)` — `memory` is the remember request as an object
+//!   map (`content`, `tags`, `importance`, `memory_type`, `scope`,
+//!   `metadata`, ...);
+//! - `pre_forget(request)` — the forget request (`memory_ids`, `strategy`,
+//!   `agent_id`, ...).
+//!
+//! Returning the (modified) map replaces the request; returning nothing
+//! leaves it unchanged; `throw "reason"` rejects the operation with a
+//! validation error. Unset optional fields are `()`.
+//!
+//! ```rhai
+//! fn pre_remember(memory) {
+//!     if memory.content.to_lower().contains("acme corp") {
+//!         throw "competitor names are not stored";
+//!     }
+//!     if memory.tags != () && "incident" in memory.tags {
+//!         memory.importance = 1.0;
+//!         return memory;
+//!     }
+//! }
+//! ```
+//!
+//! Scripts run sandboxed: no module imports, no `eval`, and bounded
+//! operations, call depth and string / array / map sizes. `print` goes to
+//! the log. Requires the `scripting` feature; without it
+//! [`ScriptHook::compile`] returns [`Error::Validation`].
+
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use crate::hooks::Hook;
+
+/// Operations one script call may execute before it is aborted.
+pub const MAX_SCRIPT_OPERATIONS: u64 = 100_000;
+
+fn script_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "policy".to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Real implementation (feature = "scripting")
+// ---------------------------------------------------------------------------
+#[cfg(feature = "scripting")]
+mod inner {
+    use rhai::{AST, CallFnOptions, Dynamic, Engine, EvalAltResult, Scope};
+    use serde::Serialize;
+    use serde::de::DeserializeOwned;
+
+    use super::*;
+    use crate::query::forget::ForgetRequest;
+    use crate::query::remember::RememberRequest;
+
+    fn sandboxed_engine(name: &str) -> Engine {
+        let mut engine = Engine::new();
+        engine.set_module_resolver(rhai::module_resolvers::DummyModuleResolver::new());
+        engine.disable_symbol("eval");
+        engine.set_max_operations(MAX_SCRIPT_OPERATIONS);
+        engine.set_max_call_levels(32);
+        engine.set_max_expr_depths(64, 32);
+        engine.set_max_string_size(1 << 20);
+        engine.set_max_array_size(10_000);
+        engine.set_max_map_size(10_000);
+        let script = name.to_string();
+        engine.on_print(move |s| tracing::info!(script = %script, "{s}"));
+        engine
+    }
+
+    /// A [`Hook`] that runs a Rhai policy script at remember / forget time.
+    pub struct ScriptHook {
+        name: String,
+        engine: Engine,
+        ast: AST,
+        pre_remember: bool,
+        pre_forget: bool,
+    }
+
+    impl ScriptHook {
+        /// Compile `source` as a policy script.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`Error::Validation`] if the script does not parse.
+        pub fn compile(name: impl Into<String>, source: &str) -> Result<Self> {
+            let name = name.into();
+            let engine = sandboxed_engine(&name);
+            let ast = engine
+                .compile(source)
+                .map_err(|e| Error::Validation(format!("policy script {name}: {e}")))?;
+            let defines = |function: &str| ast.iter_functions().any(|f| f.name == function);
+            let (pre_remember, pre_forget) = (defines("pre_remember"), defines("pre_forget"));
+            Ok(Self {
+                name,
+                engine,
+                ast,
+                pre_remember,
+                pre_forget,
+            })
+        }
+
+        /// Read and compile the script at `path`, named after the file stem.
+        pub fn from_file(path: &Path) -> Result<Self> {
+            let source = std::fs::read_to_string(path).map_err(|e| {
+                Error::Validation(format!(
+                    "failed to read policy script {}: {e}",
+                    path.display()
+                ))
+            })?;
+            Self::compile(script_name(path), &source)
+        }
+
+        pub fn with_name(mut self, name: impl Into<String>) -> Self {
+            self.name = name.into();
+            self
+        }
+
+        fn run<T: Serialize + DeserializeOwned>(
+            &self,
+            function: &str,
+            value: &mut T,
+        ) -> Result<()> {
+            let input = rhai::serde::to_dynamic(&*value)
+                .map_err(|e| Error::Internal(format!("policy {}: {e}", self.name)))?;
+            let output: Dynamic = self
+                .engine
+                .call_fn_with_options(
+                    CallFnOptions::new().eval_ast(false),
+                    &mut Scope::new(),
+                    &self.ast,
+                    function,
+                    (input,),
+                )
+                .map_err(|e| match *e {
+                    EvalAltResult::ErrorRuntime(reason, _) => {
+                        Error::Validation(format!("policy {}: {reason}", self.name))
+                    }
+                    other => Error::Internal(format!("policy {}: {other}", self.name)),
+                })?;
+            if output.is_unit() {
+                return Ok(());
+            }
+            *value = rhai::serde::from_dynamic(&output).map_err(|e| {
+                Error::Validation(format!(
+                    "policy {}: {function} returned an invalid request: {e}",
+                    self.name
+                ))
+            })?;
+            Ok(())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Hook for ScriptHook {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        async fn pre_remember(&self, request: &mut RememberRequest) -> Result<()> {
+            if !self.pre_remember {
+                return Ok(());
+            }
+            self.run("pre_remember", request)
+        }
+
+        async fn pre_forget(&self, request: &mut ForgetRequest) -> Result<()> {
+            if !self.pre_forget {
+                return Ok(());
+            }
+            self.run("pre_forget", request)
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Stub implementation (no scripting feature)
+// ---------------------------------------------------------------------------
+#[cfg(not(feature = "scripting"))]
+mod inner {
+    use super::*;
+
+    /// A [`Hook`] that runs a Rhai policy script. Without the `scripting`
+    /// feature it cannot be constructed.
+    #[derive(Debug)]
+    pub struct ScriptHook {
+        name: String,
+    }
+
+    impl ScriptHook {
+        /// # Errors
+        ///
+        /// Always returns [`Error::Validation`]: the script engine is not
+        /// compiled in.
+        pub fn compile(name: impl Into<String>, _source: &str) -> Result<Self> {
+            Err(Error::Validation(format!(
+                "policy script {}: compile mnemo-core with the `scripting` feature \
+                 to run policy scripts",
+                name.into()
+            )))
+        }
+
+        pub fn from_file(path: &Path) -> Result<Self> {
+            Self::compile(script_name(path), "")
+        }
+
+        pub fn with_name(mut self, name: impl Into<String>) -> Self {
+            self.name = name.into();
+            self
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Hook for ScriptHook {
+        fn name(&self) -> &str {
+            &self.name
+        }
+    }
+}
+
+pub use inner::ScriptHook;
@@ -0,0 +1,336 @@
+//! WebAssembly hook loader.
+//!
+//! [`WasmHook`] runs a [`Hook`] compiled to a WebAssembly module, so custom
+//! pipeline logic can be deployed without rebuilding the server. The module
+//! talks JSON over linear memory:
+//!
+//! - it exports `memory` and `alloc(len: i32) -> i32`, which returns a
+//!   buffer the host writes the input into;
+//! - it exports a function per stage it handles, named after the stage
+//!   (`pre_remember`, `post_remember`, `pre_recall`, `post_recall`,
+//!   `pre_forget`), with signature `(ptr: i32, len: i32) -> i64`. Stages
+//!   without an export are skipped;
+//! - pre-stage functions receive the request, post-stage functions receive
+//!   `{"request": ..., "response": ...}`;
+//! - the return value is `0` for "no change", or a pointer in the high 32
+//!   bits and a length in the low 32 bits of a JSON reply
+//!   `{"value": ...}` (replaces the request, or the recall response for
+//!   `post_recall`) or `{"error": "..."}` (rejects the operation).
+//!
+//! Each call runs in a fresh instance with a fuel budget, so a module keeps
+//! no state between calls and cannot spin forever.
+//!
+//! Without the `wasm-hooks` feature the module provides a stub that
+//! validates the module path but fails every stage with
+//! [`Error::Internal`].
+
+use crate::error::{Error, Result};
+use crate::hooks::{Hook, HookStage};
+use crate::query::forget::ForgetRequest;
+use crate::query::recall::{RecallRequest, RecallResponse};
+use crate::query::remember::{RememberRequest, RememberResponse};
+
+/// Fuel granted to one hook call (roughly one unit per instruction).
+pub const DEFAULT_WASM_FUEL: u64 = 10_000_000;
+
+// ---------------------------------------------------------------------------
+// Real implementation (feature = "wasm-hooks")
+// ---------------------------------------------------------------------------
+#[cfg(feature = "wasm-hooks")]
+mod inner {
+    use std::collections::HashSet;
+    use std::path::Path;
+
+    use serde::de::DeserializeOwned;
+    use serde::{Deserialize, Serialize};
+    use wasmtime::{Config, Engine, Linker, Module, Store};
+
+    use super::*;
+
+    /// Reply a module writes back to linear memory.
+    #[derive(Debug, Default, Deserialize)]
+    #[serde(default)]
+    struct WasmReply {
+        value: Option<serde_json::Value>,
+        error: Option<String>,
+    }
+
+    fn wasm_err(e: impl std::fmt::Display) -> Error {
+        Error::Internal(format!("wasm hook: {e}"))
+    }
+
+    /// A [`Hook`] backed by a WebAssembly module.
+    pub struct WasmHook {
+        name: String,
+        engine: Engine,
+        module: Module,
+        linker: Linker<()>,
+        exports: HashSet<String>,
+        fuel: u64,
+    }
+
+    impl WasmHook {
+        /// Compile the module at `path`. The hook is named after the file
+        /// stem.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`Error::Validation`] if the file does not exist or the
+        /// module lacks the `memory` / `alloc` exports, and
+        /// [`Error::Internal`] if it fails to compile.
+        pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+            let path = path.as_ref();
+            if !path.exists() {
+                return Err(Error::Validation(format!(
+                    "wasm hook not found at: {}",
+                    path.display()
+                )));
+            }
+            let mut config = Config::new();
+            config.consume_fuel(true);
+            let engine = Engine::new(&config).map_err(wasm_err)?;
+            let module = Module::from_file(&engine, path).map_err(wasm_err)?;
+            let exports: HashSet<String> = module.exports().map(|e| e.name().to_string()).collect();
+            for required in ["memory", "alloc"] {
+                if !exports.contains(required) {
+                    return Err(Error::Validation(format!(
+                        "wasm hook {} does not export `{required}`",
+                        path.display()
+                    )));
+                }
+            }
+            let name = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "wasm-hook".to_string());
+            Ok(Self {
+                name,
+                linker: Linker::new(&engine),
+                engine,
+                module,
+                exports,
+                fuel: DEFAULT_WASM_FUEL,
+            })
+        }
+
+        pub fn with_name(mut self, name: impl Into<String>) -> Self {
+            self.name = name.into();
+            self
+        }
+
+        /// Override the per-call fuel budget.
+        pub fn with_fuel(mut self, fuel: u64) -> Self {
+            self.fuel = fuel;
+            self
+        }
+
+        /// Stages the module exports, in pipeline order.
+        pub fn stages(&self) -> Vec<HookStage> {
+            [
+                HookStage::PreRemember,
+                HookStage::PostRemember,
+                HookStage::PreRecall,
+                HookStage::PostRecall,
+                HookStage::PreForget,
+            ]
+            .into_iter()
+            .filter(|stage| self.exports.contains(&stage.to_string()))
+            .collect()
+        }
+
+        fn call(&self, stage: HookStage, input: &[u8]) -> Result<Option<serde_json::Value>> {
+            let export = stage.to_string();
+            if !self.exports.contains(&export) {
+                return Ok(None);
+            }
+            let mut store = Store::new(&self.engine, ());
+            store.set_fuel(self.fuel).map_err(wasm_err)?;
+            let instance = self
+                .linker
+                .instantiate(&mut store, &self.module)
+                .map_err(wasm_err)?;
+            let memory = instance
+                .get_memory(&mut store, "memory")
+                .ok_or_else(|| wasm_err("`memory` export is not a memory"))?;
+            let alloc = instance
+                .get_typed_func::<i32, i32>(&mut store, "alloc")
+                .map_err(wasm_err)?;
+            let func = instance
+                .get_typed_func::<(i32, i32), i64>(&mut store, &export)
+                .map_err(wasm_err)?;
+
+            let len = i32::try_from(input.len()).map_err(wasm_err)?;
+            let ptr = alloc.call(&mut store, len).map_err(wasm_err)?;
+            memory
+                .write(&mut store, ptr as u32 as usize, input)
+                .map_err(wasm_err)?;
+            let packed = func.call(&mut store, (ptr, len)).map_err(wasm_err)? as u64;
+            if packed == 0 {
+                return Ok(None);
+            }
+            let mut out = vec![0u8; (packed & 0xffff_ffff) as usize];
+            memory
+                .read(&store, (packed >> 32) as usize, &mut out)
+                .map_err(wasm_err)?;
+            let reply: WasmReply = serde_json::from_slice(&out)?;
+            if let Some(message) = reply.error {
+                return Err(Error::Validation(format!("hook {}: {message}", self.name)));
+            }
+            Ok(reply.value)
+        }
+
+        fn rewrite<T: Serialize + DeserializeOwned>(
+            &self,
+            stage: HookStage,
+            value: &mut T,
+        ) -> Result<()> {
+            if let Some(replacement) = self.call(stage, &serde_json::to_vec(value)?)? {
+                *value = serde_json::from_value(replacement)?;
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Hook for WasmHook {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        async fn pre_remember(&self, request: &mut RememberRequest) -> Result<()> {
+            self.rewrite(HookStage::PreRemember, request)
+        }
+
+        async fn post_remember(
+            &self,
+            request: &RememberRequest,
+            response: &RememberResponse,
+        ) -> Result<()> {
+            let input = serde_json::to_vec(&serde_json::json!({
+                "request": request,
+                "response": response,
+            }))?;
+            self.call(HookStage::PostRemember, &input).map(|_| ())
+        }
+
+        async fn pre_recall(&self, request: &mut RecallRequest) -> Result<()> {
+            self.rewrite(HookStage::PreRecall, request)
+        }
+
+        async fn post_recall(
+            &self,
+            request: &RecallRequest,
+            response: &mut RecallResponse,
+        ) -> Result<()> {
+            let input = serde_json::to_vec(&serde_json::json!({
+                "request": request,
+                "response": &*response,
+            }))?;
+            if let Some(replacement) = self.call(HookStage::PostRecall, &input)? {
+                *response = serde_json::from_value(replacement)?;
+            }
+            Ok(())
+        }
+
+        async fn pre_forget(&self, request: &mut ForgetRequest) -> Result<()> {
+            self.rewrite(HookStage::PreForget, request)
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Stub implementation (no wasm-hooks feature)
+// ---------------------------------------------------------------------------
+#[cfg(not(feature = "wasm-hooks"))]
+mod inner {
+    use std::path::Path;
+
+    use super::*;
+
+    fn unavailable() -> Error {
+        Error::Internal(
+            "WASM runtime not available: compile with the `wasm-hooks` feature \
+             to run WebAssembly hooks"
+                .to_string(),
+        )
+    }
+
+    /// A [`Hook`] backed by a WebAssembly module. Without the `wasm-hooks`
+    /// feature every stage returns an [`Error::Internal`] explaining how to
+    /// enable the runtime.
+    #[derive(Debug)]
+    pub struct WasmHook {
+        name: String,
+    }
+
+    impl WasmHook {
+        /// # Errors
+        ///
+        /// Returns [`Error::Validation`] if the file at `path` does not
+        /// exist on disk.
+        pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+            let path = path.as_ref();
+            if !path.exists() {
+                return Err(Error::Validation(format!(
+                    "wasm hook not found at: {}",
+                    path.display()
+                )));
+            }
+            let name = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "wasm-hook".to_string());
+            Ok(Self { name })
+        }
+
+        pub fn with_name(mut self, name: impl Into<String>) -> Self {
+            self.name = name.into();
+            self
+        }
+
+        pub fn with_fuel(self, _fuel: u64) -> Self {
+            self
+        }
+
+        pub fn stages(&self) -> Vec<HookStage> {
+            Vec::new()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Hook for WasmHook {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        async fn pre_remember(&self, _request: &mut RememberRequest) -> Result<()> {
+            Err(unavailable())
+        }
+
+        async fn post_remember(
+            &self,
+            _request: &RememberRequest,
+            _response: &RememberResponse,
+        ) -> Result<()> {
+            Err(unavailable())
+        }
+
+        async fn pre_recall(&self, _request: &mut RecallRequest) -> Result<()> {
+            Err(unavailable())
+        }
+
+        async fn post_recall(
+            &self,
+            _request: &RecallRequest,
+            _response: &mut RecallResponse,
+        ) -> Result<()> {
+            Err(unavailable())
+        }
+
+        async fn pre_forget(&self, _request: &mut ForgetRequest) -> Result<()> {
+            Err(unavailable())
+        }
+    }
+}
+
+pub use inner::WasmHook;
@@ -0,0 +1,862 @@
+//! Disk-resident IVF-PQ vector index.
+//!
+//! [`UsearchIndex`](super::usearch::UsearchIndex) keeps every full vector
+//! and its HNSW graph in RAM, which stops scaling long before hundreds of
+//! millions of vectors. [`IvfPqIndex`] trades some accuracy for a footprint
+//! that lives mostly on disk:
+//!
+//! - vectors are assigned to the nearest of `nlist` coarse centroids (the
+//!   inverted file) and the residual is product-quantized into
+//!   `subvectors` one-byte codes;
+//! - each inverted list is an append-only file of `(id, sequence, code)`
+//!   records under `<dir>/lists/`, read only when a query probes it;
+//! - RAM holds the centroids, the PQ codebooks and an id directory
+//!   (id → current sequence number) used for removals and overwrites.
+//!
+//! Until `train_size` vectors have been added the index buffers them in
+//! memory and searches them exactly; the add that fills the buffer trains
+//! both quantizers with k-means and moves the buffer into the lists. Queries
+//! probe the `nprobe` nearest lists and rank candidates by asymmetric
+//! distance, so filtered searches are exact over the probed lists and need
+//! no oversampling. Distances are cosine distances, as with usearch.
+//!
+//! Like the usearch snapshot, the id directory is only written by
+//! [`save`](VectorIndex::save): after a crash the index reopens at its last
+//! save (list files are truncated back to the saved lengths), and later
+//! writes must be re-indexed from storage.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::index::VectorIndex;
+
+/// Centroids per PQ codebook; codes are one byte.
+const KSUB: usize = 256;
+/// Buffered list records written out at once.
+const FLUSH_THRESHOLD: usize = 4096;
+
+const META_FILE: &str = "meta.json";
+const CENTROIDS_FILE: &str = "centroids.f32";
+const CODEBOOKS_FILE: &str = "codebooks.f32";
+const STAGING_FILE: &str = "staging.bin";
+const DIRECTORY_FILE: &str = "directory.bin";
+const LISTS_DIR: &str = "lists";
+
+/// Id and sequence number prefix of every on-disk record.
+const RECORD_HEADER: usize = 16 + 8;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IvfPqConfig {
+    /// Coarse clusters. Around `sqrt(N)` suits a corpus of N vectors.
+    pub nlist: usize,
+    /// Lists scanned per query: higher is slower and more accurate.
+    pub nprobe: usize,
+    /// Bytes per stored vector. Must divide the vector dimensions.
+    pub subvectors: usize,
+    /// Vectors buffered, and searched exactly, before training.
+    pub train_size: usize,
+    pub kmeans_iterations: usize,
+}
+
+impl Default for IvfPqConfig {
+    fn default() -> Self {
+        Self {
+            nlist: 256,
+            nprobe: 16,
+            subvectors: 16,
+            train_size: 20_000,
+            kmeans_iterations: 8,
+        }
+    }
+}
+
+impl IvfPqConfig {
+    pub fn validate(&self, dimensions: usize) -> Result<()> {
+        if self.nlist == 0 || self.nprobe == 0 || self.kmeans_iterations == 0 {
+            return Err(Error::Validation(
+                "ivf_pq nlist, nprobe and kmeans_iterations must be > 0".to_string(),
+            ));
+        }
+        if self.subvectors == 0 || !dimensions.is_multiple_of(self.subvectors) {
+            return Err(Error::Validation(format!(
+                "ivf_pq subvectors ({}) must divide the dimensions ({dimensions})",
+                self.subvectors
+            )));
+        }
+        if self.train_size < self.nlist {
+            return Err(Error::Validation(format!(
+                "ivf_pq train_size ({}) must be >= nlist ({})",
+                self.train_size, self.nlist
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Meta {
+    dimensions: usize,
+    subvectors: usize,
+    next_seq: u64,
+    /// `None` until trained.
+    trained: Option<TrainedMeta>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TrainedMeta {
+    nlist: usize,
+    ksub: usize,
+    /// Byte length of every list file at the save.
+    list_lengths: Vec<u64>,
+}
+
+/// Trained coarse and product quantizers.
+struct PqModel {
+    nlist: usize,
+    ksub: usize,
+    /// `nlist * dimensions`.
+    centroids: Vec<f32>,
+    /// `subvectors * ksub * (dimensions / subvectors)`, subspace-major.
+    codebooks: Vec<f32>,
+}
+
+struct IvfState {
+    dir: PathBuf,
+    next_seq: u64,
+    /// Current sequence number of every live id. Records carrying any
+    /// other sequence are stale.
+    live: HashMap<Uuid, u64>,
+    model: Option<PqModel>,
+    /// Untrained vectors (normalized), searched by brute force.
+    staging: Vec<(Uuid, u64, Vec<f32>)>,
+    /// Records not yet appended to their list file.
+    pending: HashMap<usize, Vec<u8>>,
+    pending_records: usize,
+    list_lengths: Vec<u64>,
+    /// Stale records on disk, reclaimed by compaction on save.
+    stale: usize,
+}
+
+pub struct IvfPqIndex {
+    dimensions: usize,
+    config: IvfPqConfig,
+    state: RwLock<IvfState>,
+}
+
+fn io_error(action: &str, path: &Path) -> impl FnOnce(std::io::Error) -> Error {
+    let context = format!("{action} {}", path.display());
+    move |e| Error::Index(format!("{context}: {e}"))
+}
+
+fn l2sq(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+fn normalized(vector: &[f32]) -> Vec<f32> {
+    let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter().map(|x| x / norm).collect()
+    } else {
+        vector.to_vec()
+    }
+}
+
+/// Index of the row of `centroids` (row width `dim`) closest to `v`.
+fn nearest(centroids: &[f32], dim: usize, v: &[f32]) -> usize {
+    centroids
+        .chunks_exact(dim)
+        .map(|c| l2sq(c, v))
+        .enumerate()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or(0, |(i, _)| i)
+}
+
+/// Lloyd's k-means over the rows of `data`, seeded with evenly spaced
+/// rows so training is deterministic. Returns `min(k, rows)` centroids.
+fn kmeans(data: &[f32], dim: usize, k: usize, iterations: usize) -> Vec<f32> {
+    let n = data.len() / dim;
+    let k = k.min(n).max(1);
+    let mut centroids: Vec<f32> = (0..k)
+        .flat_map(|i| {
+            let row = i * n / k;
+            data[row * dim..(row + 1) * dim].iter().copied()
+        })
+        .collect();
+    for _ in 0..iterations {
+        let mut sums = vec![0f32; k * dim];
+        let mut counts = vec![0usize; k];
+        for row in data.chunks_exact(dim) {
+            let c = nearest(&centroids, dim, row);
+            counts[c] += 1;
+            for (sum, x) in sums[c * dim..(c + 1) * dim].iter_mut().zip(row) {
+                *sum += x;
+            }
+        }
+        // Empty clusters keep their previous centroid.
+        for (c, &count) in counts.iter().enumerate().filter(|(_, n)| **n > 0) {
+            for (dst, sum) in centroids[c * dim..(c + 1) * dim]
+                .iter_mut()
+                .zip(&sums[c * dim..(c + 1) * dim])
+            {
+                *dst = sum / count as f32;
+            }
+        }
+    }
+    centroids
+}
+
+fn write_f32s(path: &Path, values: &[f32]) -> Result<()> {
+    let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+    fs::write(path, bytes).map_err(io_error("write", path))
+}
+
+fn read_f32s(path: &Path) -> Result<Vec<f32>> {
+    let bytes = fs::read(path).map_err(io_error("read", path))?;
+    Ok(bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect())
+}
+
+fn encode_header(out: &mut Vec<u8>, id: Uuid, seq: u64) {
+    out.extend_from_slice(id.as_bytes());
+    out.extend_from_slice(&seq.to_le_bytes());
+}
+
+fn decode_header(record: &[u8]) -> Option<(Uuid, u64)> {
+    let id = Uuid::from_slice(&record[..16]).ok()?;
+    let seq = u64::from_le_bytes(record[16..RECORD_HEADER].try_into().ok()?);
+    Some((id, seq))
+}
+
+/// Search candidate, ordered by distance so the heap top is the worst hit.
+struct Candidate(f32, Uuid);
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+    }
+}
+
+/// Keeps the `limit` closest candidates.
+struct TopK {
+    limit: usize,
+    heap: BinaryHeap<Candidate>,
+}
+
+impl TopK {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            heap: BinaryHeap::with_capacity(limit + 1),
+        }
+    }
+
+    fn push(&mut self, distance: f32, id: Uuid) {
+        if self.heap.len() < self.limit {
+            self.heap.push(Candidate(distance, id));
+        } else if self.heap.peek().is_some_and(|worst| distance < worst.0) {
+            self.heap.pop();
+            self.heap.push(Candidate(distance, id));
+        }
+    }
+
+    fn into_sorted(self) -> Vec<(Uuid, f32)> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Candidate(distance, id)| (id, distance))
+            .collect()
+    }
+}
+
+impl IvfPqIndex {
+    /// Open the index stored in `dir`, creating an empty one if there is
+    /// none. `config.nprobe` always applies; the other settings only matter
+    /// until the index is trained.
+    pub fn open(dir: impl AsRef<Path>, dimensions: usize, config: IvfPqConfig) -> Result<Self> {
+        config.validate(dimensions)?;
+        let state = Self::read_state(dir.as_ref(), dimensions, &config)?;
+        Ok(Self {
+            dimensions,
+            config,
+            state: RwLock::new(state),
+        })
+    }
+
+    pub fn config(&self) -> &IvfPqConfig {
+        &self.config
+    }
+
+    pub fn is_trained(&self) -> bool {
+        self.read().model.is_some()
+    }
+
+    /// Train now on whatever has been buffered instead of waiting for
+    /// `train_size` vectors. A no-op once trained or while empty.
+    pub fn train(&self) -> Result<()> {
+        let mut state = self.write();
+        if state.model.is_some() || state.staging.is_empty() {
+            return Ok(());
+        }
+        self.train_locked(&mut state)
+    }
+
+    /// Approximate resident memory in bytes: quantizers, id directory and
+    /// buffered vectors. List files are not counted.
+    pub fn memory_bytes(&self) -> usize {
+        let state = self.read();
+        let model = state
+            .model
+            .as_ref()
+            .map_or(0, |m| (m.centroids.len() + m.codebooks.len()) * 4);
+        let directory = state.live.capacity() * (RECORD_HEADER + 8);
+        let staging = state.staging.len() * (RECORD_HEADER + self.dimensions * 4);
+        let pending: usize = state.pending.values().map(Vec::len).sum();
+        model + directory + staging + pending
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, IvfState> {
+        self.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, IvfState> {
+        self.state.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn dsub(&self) -> usize {
+        self.dimensions / self.config.subvectors
+    }
+
+    fn list_record_len(&self) -> usize {
+        RECORD_HEADER + self.config.subvectors
+    }
+
+    fn list_path(dir: &Path, list: usize) -> PathBuf {
+        dir.join(LISTS_DIR).join(format!("{list}.ivf"))
+    }
+
+    fn read_state(dir: &Path, dimensions: usize, config: &IvfPqConfig) -> Result<IvfState> {
+        let lists_dir = dir.join(LISTS_DIR);
+        fs::create_dir_all(&lists_dir).map_err(io_error("create", &lists_dir))?;
+        let mut state = IvfState {
+            dir: dir.to_path_buf(),
+            next_seq: 0,
+            live: HashMap::new(),
+            model: None,
+            staging: Vec::new(),
+            pending: HashMap::new(),
+            pending_records: 0,
+            list_lengths: Vec::new(),
+            stale: 0,
+        };
+        let meta_path = dir.join(META_FILE);
+        if !meta_path.exists() {
+            return Ok(state);
+        }
+        let meta: Meta =
+            serde_json::from_slice(&fs::read(&meta_path).map_err(io_error("read", &meta_path))?)
+                .map_err(|e| {
+                    Error::IndexCorruption(format!("parse {}: {e}", meta_path.display()))
+                })?;
+        if meta.dimensions != dimensions || meta.subvectors != config.subvectors {
+            return Err(Error::Validation(format!(
+                "IVF-PQ index in {} has {} dimensions and {} subvectors, configured {dimensions} and {}",
+                dir.display(),
+                meta.dimensions,
+                meta.subvectors,
+                config.subvectors
+            )));
+        }
+        state.next_seq = meta.next_seq;
+
+        let directory_path = dir.join(DIRECTORY_FILE);
+        if directory_path.exists() {
+            let bytes = fs::read(&directory_path).map_err(io_error("read", &directory_path))?;
+            state.live = bytes
+                .chunks_exact(RECORD_HEADER)
+                .filter_map(decode_header)
+                .collect();
+        }
+
+        match meta.trained {
+            Some(trained) => {
+                // Drop anything appended after the save.
+                for (list, &length) in trained.list_lengths.iter().enumerate() {
+                    let path = Self::list_path(dir, list);
+                    let on_disk = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                    if on_disk > length {
+                        OpenOptions::new()
+                            .write(true)
+                            .open(&path)
+                            .and_then(|file| file.set_len(length))
+                            .map_err(io_error("truncate", &path))?;
+                    }
+                }
+                state.list_lengths = trained.list_lengths;
+                state.model = Some(PqModel {
+                    nlist: trained.nlist,
+                    ksub: trained.ksub,
+                    centroids: read_f32s(&dir.join(CENTROIDS_FILE))?,
+                    codebooks: read_f32s(&dir.join(CODEBOOKS_FILE))?,
+                });
+            }
+            None => {
+                let staging_path = dir.join(STAGING_FILE);
+                if staging_path.exists() {
+                    let bytes = fs::read(&staging_path).map_err(io_error("read", &staging_path))?;
+                    for record in bytes.chunks_exact(RECORD_HEADER + dimensions * 4) {
+                        let Some((id, seq)) = decode_header(record) else {
+                            continue;
+                        };
+                        let vector = record[RECORD_HEADER..]
+                            .chunks_exact(4)
+                            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                            .collect();
+                        state.staging.push((id, seq, vector));
+                    }
+                }
+            }
+        }
+        Ok(state)
+    }
+
+    /// Build both quantizers from the buffered vectors and move them into
+    /// the inverted lists.
+    fn train_locked(&self, state: &mut IvfState) -> Result<()> {
+        let d = self.dimensions;
+        let m = self.config.subvectors;
+        let dsub = self.dsub();
+        let iterations = self.config.kmeans_iterations;
+        let staged: Vec<(Uuid, u64, Vec<f32>)> = std::mem::take(&mut state.staging)
+            .into_iter()
+            .filter(|(id, seq, _)| state.live.get(id) == Some(seq))
+            .collect();
+        let data: Vec<f32> = staged
+            .iter()
+            .flat_map(|(_, _, v)| v.iter().copied())
+            .collect();
+
+        let centroids = kmeans(&data, d, self.config.nlist, iterations);
+        let nlist = centroids.len() / d;
+        let mut lists = Vec::with_capacity(staged.len());
+        let mut residuals = Vec::with_capacity(data.len());
+        for row in data.chunks_exact(d) {
+            let list = nearest(&centroids, d, row);
+            lists.push(list);
+            let centroid = &centroids[list * d..(list + 1) * d];
+            residuals.extend(row.iter().zip(centroid).map(|(x, c)| x - c));
+        }
+        let mut codebooks = Vec::with_capacity(m * KSUB * dsub);
+        let mut ksub = KSUB;
+        for j in 0..m {
+            let sub: Vec<f32> = residuals
+                .chunks_exact(d)
+                .flat_map(|r| r[j * dsub..(j + 1) * dsub].iter().copied())
+                .collect();
+            let codebook = kmeans(&sub, dsub, KSUB, iterations);
+            ksub = codebook.len() / dsub;
+            codebooks.extend(codebook);
+        }
+        let model = PqModel {
+            nlist,
+            ksub,
+            centroids,
+            codebooks,
+        };
+
+        state.list_lengths = vec![0; nlist];
+        for (((id, seq, _), list), residual) in
+            staged.iter().zip(&lists).zip(residuals.chunks_exact(d))
+        {
+            let code = self.encode(&model, residual);
+            Self::push_record(state, *list, *id, *seq, &code);
+        }
+        state.model = Some(model);
+        state.stale = 0;
+        tracing::info!(vectors = staged.len(), nlist, "trained IVF-PQ vector index");
+        self.flush(state)
+    }
+
+    fn encode(&self, model: &PqModel, residual: &[f32]) -> Vec<u8> {
+        let dsub = self.dsub();
+        residual
+            .chunks_exact(dsub)
+            .enumerate()
+            .map(|(j, sub)| {
+                let codebook = &model.codebooks[j * model.ksub * dsub..(j + 1) * model.ksub * dsub];
+                nearest(codebook, dsub, sub) as u8
+            })
+            .collect()
+    }
+
+    fn push_record(state: &mut IvfState, list: usize, id: Uuid, seq: u64, code: &[u8]) {
+        let buffer = state.pending.entry(list).or_default();
+        encode_header(buffer, id, seq);
+        buffer.extend_from_slice(code);
+        state.pending_records += 1;
+    }
+
+    fn flush(&self, state: &mut IvfState) -> Result<()> {
+        for (list, bytes) in std::mem::take(&mut state.pending) {
+            let path = Self::list_path(&state.dir, list);
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .and_then(|mut file| file.write_all(&bytes))
+                .map_err(io_error("append to", &path))?;
+            state.list_lengths[list] += bytes.len() as u64;
+        }
+        state.pending_records = 0;
+        Ok(())
+    }
+
+    /// Rewrite every list without its stale records.
+    fn compact(&self, state: &mut IvfState) -> Result<()> {
+        let record_len = self.list_record_len();
+        for list in 0..state.list_lengths.len() {
+            let path = Self::list_path(&state.dir, list);
+            if !path.exists() {
+                continue;
+            }
+            let bytes = fs::read(&path).map_err(io_error("read", &path))?;
+            let kept: Vec<u8> = bytes
+                .chunks_exact(record_len)
+                .filter(|record| {
+                    decode_header(record).is_some_and(|(id, seq)| state.live.get(&id) == Some(&seq))
+                })
+                .flatten()
+                .copied()
+                .collect();
+            let tmp = path.with_extension("ivf.tmp");
+            fs::write(&tmp, &kept).map_err(io_error("write", &tmp))?;
+            fs::rename(&tmp, &path).map_err(io_error("replace", &path))?;
+            state.list_lengths[list] = kept.len() as u64;
+        }
+        state.stale = 0;
+        Ok(())
+    }
+
+    fn search_locked(
+        &self,
+        state: &IvfState,
+        query: &[f32],
+        limit: usize,
+        filter: &dyn Fn(Uuid) -> bool,
+    ) -> Result<Vec<(Uuid, f32)>> {
+        if query.len() != self.dimensions {
+            return Err(Error::Validation(format!(
+                "expected {} dimensions, got {}",
+                self.dimensions,
+                query.len()
+            )));
+        }
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+        let mut top = TopK::new(limit);
+        let q = normalized(query);
+        let is_live = |id: &Uuid, seq: &u64| state.live.get(id) == Some(seq) && filter(*id);
+
+        let Some(ref model) = state.model else {
+            for (id, seq, vector) in &state.staging {
+                if is_live(id, seq) {
+                    let dot: f32 = q.iter().zip(vector).map(|(a, b)| a * b).sum();
+                    top.push(1.0 - dot, *id);
+                }
+            }
+            return Ok(top.into_sorted());
+        };
+
+        let d = self.dimensions;
+        let dsub = self.dsub();
+        let record_len = self.list_record_len();
+        let mut probes: Vec<(usize, f32)> = model
+            .centroids
+            .chunks_exact(d)
+            .map(|c| l2sq(c, &q))
+            .enumerate()
+            .collect();
+        probes.sort_by(|a, b| a.1.total_cmp(&b.1));
+        probes.truncate(self.config.nprobe);
+
+        let mut lut = vec![0f32; self.config.subvectors * model.ksub];
+        for (list, _) in probes {
+            // Distance table from the query residual to every codeword.
+            let centroid = &model.centroids[list * d..(list + 1) * d];
+            let residual: Vec<f32> = q.iter().zip(centroid).map(|(x, c)| x - c).collect();
+            for (j, sub) in residual.chunks_exact(dsub).enumerate() {
+                let codebook = &model.codebooks[j * model.ksub * dsub..(j + 1) * model.ksub * dsub];
+                for (k, codeword) in codebook.chunks_exact(dsub).enumerate() {
+                    lut[j * model.ksub + k] = l2sq(sub, codeword);
+                }
+            }
+
+            let path = Self::list_path(&state.dir, list);
+            let on_disk = match fs::read(&path) {
+                Ok(bytes) => bytes,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+                Err(e) => return Err(io_error("read", &path)(e)),
+            };
+            let pending = state.pending.get(&list).map_or(&[][..], Vec::as_slice);
+            for record in on_disk
+                .chunks_exact(record_len)
+                .chain(pending.chunks_exact(record_len))
+            {
+                let Some((id, seq)) = decode_header(record) else {
+                    continue;
+                };
+                if !is_live(&id, &seq) {
+                    continue;
+                }
+                let distance: f32 = record[RECORD_HEADER..]
+                    .iter()
+                    .enumerate()
+                    .map(|(j, &code)| lut[j * model.ksub + code as usize])
+                    .sum();
+                // Unit vectors: squared L2 is twice the cosine distance.
+                top.push(distance / 2.0, id);
+            }
+        }
+        Ok(top.into_sorted())
+    }
+}
+
+impl VectorIndex for IvfPqIndex {
+    fn add(&self, id: Uuid, vector: &[f32]) -> Result<()> {
+        if vector.len() != self.dimensions {
+            return Err(Error::Validation(format!(
+                "expected {} dimensions, got {}",
+                self.dimensions,
+                vector.len()
+            )));
+        }
+        let v = normalized(vector);
+        let mut state = self.write();
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        if state.live.insert(id, seq).is_some() {
+            state.stale += 1;
+        }
+        let Some(ref model) = state.model else {
+            state.staging.push((id, seq, v));
+            if state.live.len() >= self.config.train_size {
+                self.train_locked(&mut state)?;
+            }
+            return Ok(());
+        };
+        let list = nearest(&model.centroids, self.dimensions, &v);
+        let centroid = &model.centroids[list * self.dimensions..(list + 1) * self.dimensions];
+        let residual: Vec<f32> = v.iter().zip(centroid).map(|(x, c)| x - c).collect();
+        let code = self.encode(model, &residual);
+        Self::push_record(&mut state, list, id, seq, &code);
+        if state.pending_records >= FLUSH_THRESHOLD {
+            self.flush(&mut state)?;
+        }
+        Ok(())
+    }
+
+    fn remove(&self, id: Uuid) -> Result<()> {
+        let mut state = self.write();
+        if state.live.remove(&id).is_some() {
+            state.stale += 1;
+        }
+        Ok(())
+    }
+
+    fn search(&self, query: &[f32], limit: usize) -> Result<Vec<(Uuid, f32)>> {
+        self.filtered_search(query, limit, &|_| true)
+    }
+
+    fn filtered_search(
+        &self,
+        query: &[f32],
+        limit: usize,
+        filter: &dyn Fn(Uuid) -> bool,
+    ) -> Result<Vec<(Uuid, f32)>> {
+        let state = self.read();
+        self.search_locked(&state, query, limit, filter)
+    }
+
+    /// Persist to the index directory. The index is disk-resident, so
+    /// `path` must be the directory it was opened from.
+    fn save(&self, path: &Path) -> Result<()> {
+        let mut state = self.write();
+        if path != state.dir {
+            return Err(Error::Index(format!(
+                "IVF-PQ index lives in {}; it cannot be saved to {}",
+                state.dir.display(),
+                path.display()
+            )));
+        }
+        let dir = state.dir.clone();
+        if state.model.is_some() {
+            self.flush(&mut state)?;
+            if state.stale > state.live.len() {
+                self.compact(&mut state)?;
+            }
+        } else {
+            let IvfState { live, staging, .. } = &mut *state;
+            staging.retain(|(id, seq, _)| live.get(id) == Some(seq));
+        }
+        let trained = match state.model {
+            Some(ref model) => {
+                write_f32s(&dir.join(CENTROIDS_FILE), &model.centroids)?;
+                write_f32s(&dir.join(CODEBOOKS_FILE), &model.codebooks)?;
+                Some(TrainedMeta {
+                    nlist: model.nlist,
+                    ksub: model.ksub,
+                    list_lengths: state.list_lengths.clone(),
+                })
+            }
+            None => {
+                let mut bytes = Vec::new();
+                for (id, seq, vector) in &state.staging {
+                    encode_header(&mut bytes, *id, *seq);
+                    bytes.extend(vector.iter().flat_map(|v| v.to_le_bytes()));
+                }
+                let staging_path = dir.join(STAGING_FILE);
+                fs::write(&staging_path, bytes).map_err(io_error("write", &staging_path))?;
+                None
+            }
+        };
+
+        let mut directory = Vec::with_capacity(state.live.len() * RECORD_HEADER);
+        for (id, seq) in &state.live {
+            encode_header(&mut directory, *id, *seq);
+        }
+        let directory_path = dir.join(DIRECTORY_FILE);
+        fs::write(&directory_path, directory).map_err(io_error("write", &directory_path))?;
+
+        let meta = Meta {
+            dimensions: self.dimensions,
+            subvectors: self.config.subvectors,
+            next_seq: state.next_seq,
+            trained,
+        };
+        let meta_path = dir.join(META_FILE);
+        let tmp = meta_path.with_extension("json.tmp");
+        let json = serde_json::to_vec(&meta).map_err(|e| Error::Index(e.to_string()))?;
+        fs::write(&tmp, json).map_err(io_error("write", &tmp))?;
+        fs::rename(&tmp, &meta_path).map_err(io_error("replace", &meta_path))
+    }
+
+    /// Reopen from the directory `path`, discarding unsaved changes.
+    fn load(&self, path: &Path) -> Result<()> {
+        let state = Self::read_state(path, self.dimensions, &self.config)?;
+        *self.write() = state;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.read().live.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIMS: usize = 16;
+
+    fn vector(seed: u64) -> Vec<f32> {
+        let mut x = seed.wrapping_add(1);
+        (0..DIMS)
+            .map(|_| {
+                x = x
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                ((x >> 33) as f32 / (1u64 << 31) as f32) - 0.5
+            })
+            .collect()
+    }
+
+    fn small_config() -> IvfPqConfig {
+        IvfPqConfig {
+            nlist: 4,
+            nprobe: 4,
+            subvectors: 4,
+            train_size: 64,
+            kmeans_iterations: 4,
+        }
+    }
+
+    #[test]
+    fn test_trains_after_train_size_and_finds_vectors() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = IvfPqIndex::open(dir.path(), DIMS, small_config()).unwrap();
+        let ids: Vec<Uuid> = (0..100).map(|_| Uuid::now_v7()).collect();
+        for (i, id) in ids.iter().take(10).enumerate() {
+            index.add(*id, &vector(i as u64)).unwrap();
+        }
+        assert!(!index.is_trained());
+        // Untrained search is exact.
+        assert_eq!(index.search(&vector(3), 1).unwrap()[0].0, ids[3]);
+
+        for (i, id) in ids.iter().enumerate().skip(10) {
+            index.add(*id, &vector(i as u64)).unwrap();
+        }
+        assert!(index.is_trained());
+        assert_eq!(index.len(), 100);
+        let hits = index.search(&vector(42), 5).unwrap();
+        assert!(hits.iter().any(|(id, _)| *id == ids[42]));
+        assert!(hits.windows(2).all(|w| w[0].1 <= w[1].1));
+
+        index.remove(ids[42]).unwrap();
+        let hits = index
+            .filtered_search(&vector(42), 5, &|id| id != ids[7])
+            .unwrap();
+        assert!(hits.iter().all(|(id, _)| *id != ids[42] && *id != ids[7]));
+    }
+
+    #[test]
+    fn test_reopen_restores_the_saved_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let ids: Vec<Uuid> = (0..80).map(|_| Uuid::now_v7()).collect();
+        {
+            let index = IvfPqIndex::open(dir.path(), DIMS, small_config()).unwrap();
+            for (i, id) in ids.iter().enumerate() {
+                index.add(*id, &vector(i as u64)).unwrap();
+            }
+            index.remove(ids[0]).unwrap();
+            index.save(dir.path()).unwrap();
+            // Unsaved writes are dropped on reopen.
+            index.add(Uuid::now_v7(), &vector(1000)).unwrap();
+            index.flush(&mut index.write()).unwrap();
+        }
+        let index = IvfPqIndex::open(dir.path(), DIMS, small_config()).unwrap();
+        assert!(index.is_trained());
+        assert_eq!(index.len(), 79);
+        let hits = index.search(&vector(10), 3).unwrap();
+        assert!(hits.iter().any(|(id, _)| *id == ids[10]));
+
+        let other = tempfile::tempdir().unwrap();
+        assert!(index.save(other.path()).is_err());
+        let mismatched = IvfPqIndex::open(dir.path(), DIMS * 2, small_config());
+        assert!(matches!(mismatched, Err(Error::Validation(_))));
+    }
+}
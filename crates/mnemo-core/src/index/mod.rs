@@ -1,8 +1,32 @@
+pub mod ivf_pq;
+pub mod sharded;
+pub mod snapshot;
 pub mod usearch;
 
-use crate::error::Result;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use crate::model::memory::MemoryRecord;
 use uuid::Uuid;
 
+/// The tenant a vector belongs to, used by [`sharded::ShardedIndex`] to pick
+/// a shard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShardRoute<'a> {
+    pub agent_id: &'a str,
+    pub org_id: Option<&'a str>,
+}
+
+impl<'a> ShardRoute<'a> {
+    pub fn of(record: &'a MemoryRecord) -> Self {
+        Self {
+            agent_id: &record.agent_id,
+            org_id: record.org_id.as_deref(),
+        }
+    }
+}
+
 pub trait VectorIndex: Send + Sync {
     fn add(&self, id: Uuid, vector: &[f32]) -> Result<()>;
     fn remove(&self, id: Uuid) -> Result<()>;
@@ -19,4 +43,49 @@ pub trait VectorIndex: Send + Sync {
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Add `vector` for the tenant in `route`. Unsharded indexes ignore the
+    /// route.
+    fn add_routed(&self, route: ShardRoute<'_>, id: Uuid, vector: &[f32]) -> Result<()> {
+        let _ = route;
+        self.add(id, vector)
+    }
+
+    /// Bring the vectors of the tenant in `route` into memory ahead of a
+    /// search. Returns `true` when that loaded something. Indexes that keep
+    /// everything in memory have nothing to do.
+    fn prefetch(&self, route: ShardRoute<'_>) -> Result<bool> {
+        let _ = route;
+        Ok(false)
+    }
+
+    /// Nearest neighbours among `candidates` only. A sharded index searches
+    /// just the shards that hold a candidate.
+    fn search_within(
+        &self,
+        query: &[f32],
+        limit: usize,
+        candidates: &HashSet<Uuid>,
+    ) -> Result<Vec<(Uuid, f32)>> {
+        self.filtered_search(query, limit, &|id| candidates.contains(&id))
+    }
+
+    /// Write a point-in-time copy to the empty directory `dir` for
+    /// [`snapshot`] shipping. Unlike [`save`](Self::save) this leaves the
+    /// index's own files and write-ahead log alone.
+    fn save_snapshot(&self, dir: &Path) -> Result<()> {
+        let _ = dir;
+        Err(Error::Index(
+            "this vector index does not support snapshots".to_string(),
+        ))
+    }
+
+    /// Replace the contents with a copy written by
+    /// [`save_snapshot`](Self::save_snapshot).
+    fn load_snapshot(&self, dir: &Path) -> Result<()> {
+        let _ = dir;
+        Err(Error::Index(
+            "this vector index does not support snapshots".to_string(),
+        ))
+    }
 }
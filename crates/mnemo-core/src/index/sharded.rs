@@ -0,0 +1,428 @@
+//! Per-tenant vector index shards.
+//!
+//! One [`UsearchIndex`] holding every tenant's vectors makes a filtered
+//! search wade through neighbours the caller cannot see. [`ShardedIndex`]
+//! keeps one usearch index per agent (or per org, see [`ShardKey`]):
+//!
+//! - writes that carry a [`ShardRoute`] land in their tenant's shard;
+//!   plain [`VectorIndex::add`] calls go to [`UNROUTED_SHARD`];
+//! - [`VectorIndex::search_within`], which recall uses with the caller's
+//!   accessible ids, only searches the shards that hold one of them;
+//!   unrestricted searches fan out over every shard;
+//! - with a directory attached, shards are loaded on first use and, past
+//!   `max_loaded_shards`, the least recently used one is saved and dropped.
+//!
+//! On disk a shard is `<dir>/<hex shard name>.usearch` plus its mappings
+//! file, and `<dir>/routes.json` records which shard every vector is in.
+//! Sharded indexes have no WAL: vectors added since the last save or
+//! eviction are lost on a crash and must be re-indexed from storage.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::index::usearch::UsearchIndex;
+use crate::index::{ShardRoute, VectorIndex};
+
+/// Shard for vectors added without a route, and for memories without an
+/// org under [`ShardKey::Org`].
+pub const UNROUTED_SHARD: &str = "_default";
+
+/// Initial capacity of a new shard. Shards grow on demand, so this stays
+/// small to keep many-tenant deployments cheap.
+const SHARD_INITIAL_CAPACITY: usize = 1_000;
+
+const ROUTES_FILE: &str = "routes.json";
+
+/// What a shard is keyed on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShardKey {
+    #[default]
+    Agent,
+    Org,
+}
+
+impl ShardKey {
+    fn shard_for<'a>(self, route: ShardRoute<'a>) -> &'a str {
+        match self {
+            ShardKey::Agent => route.agent_id,
+            ShardKey::Org => route.org_id.unwrap_or(UNROUTED_SHARD),
+        }
+    }
+}
+
+struct Shard {
+    index: Arc<UsearchIndex>,
+    last_used: u64,
+    /// Changed since it was last written to the shard directory.
+    dirty: bool,
+}
+
+#[derive(Default)]
+struct ShardState {
+    dir: Option<PathBuf>,
+    loaded: HashMap<String, Shard>,
+    /// Shard of every indexed vector, loaded or not.
+    routes: HashMap<Uuid, String>,
+    clock: u64,
+}
+
+pub struct ShardedIndex {
+    dimensions: usize,
+    key: ShardKey,
+    max_loaded_shards: Option<usize>,
+    state: Mutex<ShardState>,
+}
+
+impl ShardedIndex {
+    /// An in-memory sharded index. Attach a directory with
+    /// [`with_dir`](Self::with_dir) for persistence and eviction.
+    pub fn new(dimensions: usize, key: ShardKey) -> Self {
+        Self {
+            dimensions,
+            key,
+            max_loaded_shards: None,
+            state: Mutex::new(ShardState::default()),
+        }
+    }
+
+    /// Keep shards in `dir`, creating it if needed, and pick up the routes of
+    /// a previous save. Shards themselves load lazily.
+    pub fn with_dir(self, dir: impl AsRef<Path>) -> Result<Self> {
+        self.load(dir.as_ref())?;
+        Ok(self)
+    }
+
+    /// Keep at most `max` shards in memory, evicting the least recently
+    /// used. Needs a directory to evict to; without one the cap is ignored.
+    pub fn with_max_loaded_shards(mut self, max: usize) -> Self {
+        self.max_loaded_shards = Some(max.max(1));
+        self
+    }
+
+    pub fn key(&self) -> ShardKey {
+        self.key
+    }
+
+    /// Number of shards currently in memory.
+    pub fn loaded_shards(&self) -> usize {
+        self.lock().loaded.len()
+    }
+
+    /// Every shard holding at least one vector, sorted.
+    pub fn shard_names(&self) -> Vec<String> {
+        let state = self.lock();
+        let mut names: Vec<String> = state
+            .routes
+            .values()
+            .cloned()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ShardState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn shard_path(dir: &Path, name: &str) -> PathBuf {
+        dir.join(format!("{}.usearch", hex::encode(name)))
+    }
+
+    /// The shard called `name`, loading or creating it as needed and
+    /// evicting others to stay under the cap.
+    fn shard(&self, state: &mut ShardState, name: &str) -> Result<Arc<UsearchIndex>> {
+        state.clock += 1;
+        let now = state.clock;
+        if let Some(shard) = state.loaded.get_mut(name) {
+            shard.last_used = now;
+            return Ok(shard.index.clone());
+        }
+        let index = UsearchIndex::with_capacity(self.dimensions, SHARD_INITIAL_CAPACITY)?;
+        if let Some(ref dir) = state.dir {
+            let path = Self::shard_path(dir, name);
+            if path.exists() {
+                index.load(&path)?;
+                tracing::debug!(shard = name, vectors = index.len(), "loaded vector shard");
+            }
+        }
+        let index = Arc::new(index);
+        state.loaded.insert(
+            name.to_string(),
+            Shard {
+                index: index.clone(),
+                last_used: now,
+                dirty: false,
+            },
+        );
+        self.evict(state, name)?;
+        Ok(index)
+    }
+
+    fn evict(&self, state: &mut ShardState, keep: &str) -> Result<()> {
+        let (Some(max), Some(dir)) = (self.max_loaded_shards, state.dir.clone()) else {
+            return Ok(());
+        };
+        while state.loaded.len() > max {
+            let Some(victim) = state
+                .loaded
+                .iter()
+                .filter(|(name, _)| name.as_str() != keep)
+                .min_by_key(|(_, shard)| shard.last_used)
+                .map(|(name, _)| name.clone())
+            else {
+                break;
+            };
+            if let Some(shard) = state.loaded.remove(&victim)
+                && shard.dirty
+            {
+                shard.index.save(&Self::shard_path(&dir, &victim))?;
+            }
+            tracing::debug!(shard = victim, "evicted vector shard");
+        }
+        Ok(())
+    }
+
+    fn add_to(&self, name: &str, id: Uuid, vector: &[f32]) -> Result<()> {
+        let mut state = self.lock();
+        if let Some(previous) = state.routes.get(&id).cloned()
+            && previous != name
+        {
+            self.remove_from(&mut state, &previous, id)?;
+        }
+        self.shard(&mut state, name)?.add(id, vector)?;
+        if let Some(shard) = state.loaded.get_mut(name) {
+            shard.dirty = true;
+        }
+        state.routes.insert(id, name.to_string());
+        Ok(())
+    }
+
+    fn remove_from(&self, state: &mut ShardState, name: &str, id: Uuid) -> Result<()> {
+        self.shard(state, name)?.remove(id)?;
+        if let Some(shard) = state.loaded.get_mut(name) {
+            shard.dirty = true;
+        }
+        state.routes.remove(&id);
+        Ok(())
+    }
+
+    /// Run `search` over the named shards and keep the `limit` closest hits.
+    fn fan_out(
+        &self,
+        names: impl IntoIterator<Item = String>,
+        limit: usize,
+        search: impl Fn(&UsearchIndex) -> Result<Vec<(Uuid, f32)>>,
+    ) -> Result<Vec<(Uuid, f32)>> {
+        let mut hits = Vec::new();
+        for name in names {
+            // Search outside the lock so shards are searched concurrently
+            // with writes to other shards.
+            let shard = {
+                let mut state = self.lock();
+                self.shard(&mut state, &name)?
+            };
+            hits.extend(search(&shard)?);
+        }
+        hits.sort_by(|a, b| a.1.total_cmp(&b.1));
+        hits.truncate(limit);
+        Ok(hits)
+    }
+}
+
+impl VectorIndex for ShardedIndex {
+    fn add(&self, id: Uuid, vector: &[f32]) -> Result<()> {
+        self.add_to(UNROUTED_SHARD, id, vector)
+    }
+
+    fn add_routed(&self, route: ShardRoute<'_>, id: Uuid, vector: &[f32]) -> Result<()> {
+        self.add_to(self.key.shard_for(route), id, vector)
+    }
+
+    /// Load the route's shard if it holds vectors and is not in memory.
+    fn prefetch(&self, route: ShardRoute<'_>) -> Result<bool> {
+        let name = self.key.shard_for(route);
+        let mut state = self.lock();
+        if state.loaded.contains_key(name) || !state.routes.values().any(|n| n == name) {
+            return Ok(false);
+        }
+        self.shard(&mut state, name)?;
+        Ok(true)
+    }
+
+    fn remove(&self, id: Uuid) -> Result<()> {
+        let mut state = self.lock();
+        match state.routes.get(&id).cloned() {
+            Some(name) => self.remove_from(&mut state, &name, id),
+            None => Ok(()),
+        }
+    }
+
+    fn search(&self, query: &[f32], limit: usize) -> Result<Vec<(Uuid, f32)>> {
+        self.fan_out(self.shard_names(), limit, |shard| {
+            shard.search(query, limit)
+        })
+    }
+
+    fn filtered_search(
+        &self,
+        query: &[f32],
+        limit: usize,
+        filter: &dyn Fn(Uuid) -> bool,
+    ) -> Result<Vec<(Uuid, f32)>> {
+        self.fan_out(self.shard_names(), limit, |shard| {
+            shard.filtered_search(query, limit, filter)
+        })
+    }
+
+    fn search_within(
+        &self,
+        query: &[f32],
+        limit: usize,
+        candidates: &HashSet<Uuid>,
+    ) -> Result<Vec<(Uuid, f32)>> {
+        let names: HashSet<String> = {
+            let state = self.lock();
+            candidates
+                .iter()
+                .filter_map(|id| state.routes.get(id).cloned())
+                .collect()
+        };
+        self.fan_out(names, limit, |shard| {
+            shard.filtered_search(query, limit, &|id| candidates.contains(&id))
+        })
+    }
+
+    /// Write every shard and the routes to the directory `path`, which then
+    /// becomes the index's shard directory.
+    fn save(&self, path: &Path) -> Result<()> {
+        std::fs::create_dir_all(path)
+            .map_err(|e| Error::Index(format!("create shard dir {}: {e}", path.display())))?;
+        let mut state = self.lock();
+        let same_dir = state.dir.as_deref() == Some(path);
+        let names: HashSet<String> = state.routes.values().cloned().collect();
+        for name in names {
+            let clean_on_disk = same_dir && state.loaded.get(&name).is_none_or(|s| !s.dirty);
+            if clean_on_disk {
+                continue;
+            }
+            let shard = self.shard(&mut state, &name)?;
+            shard.save(&Self::shard_path(path, &name))?;
+            if let Some(shard) = state.loaded.get_mut(&name) {
+                shard.dirty = false;
+            }
+        }
+        let routes = serde_json::to_vec(&state.routes).map_err(|e| Error::Index(e.to_string()))?;
+        std::fs::write(path.join(ROUTES_FILE), routes).map_err(|e| Error::Index(e.to_string()))?;
+        state.dir = Some(path.to_path_buf());
+        Ok(())
+    }
+
+    /// Use the directory `path` as the shard directory, reading its routes
+    /// and dropping every loaded shard. Shards load again on first use.
+    fn load(&self, path: &Path) -> Result<()> {
+        std::fs::create_dir_all(path)
+            .map_err(|e| Error::Index(format!("create shard dir {}: {e}", path.display())))?;
+        let routes_path = path.join(ROUTES_FILE);
+        let routes = if routes_path.exists() {
+            let data = std::fs::read(&routes_path).map_err(|e| Error::Index(e.to_string()))?;
+            serde_json::from_slice(&data).map_err(|e| {
+                Error::IndexCorruption(format!("parse {}: {e}", routes_path.display()))
+            })?
+        } else {
+            HashMap::new()
+        };
+        let mut state = self.lock();
+        state.routes = routes;
+        state.loaded.clear();
+        state.dir = Some(path.to_path_buf());
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.lock().routes.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vector(seed: u64) -> Vec<f32> {
+        let mut x = seed;
+        let v: Vec<f32> = (0..16)
+            .map(|_| {
+                x = x.wrapping_mul(6364136223846793005).wrapping_add(1);
+                (x as f32) / (u64::MAX as f32)
+            })
+            .collect();
+        let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+        v.into_iter().map(|x| x / norm).collect()
+    }
+
+    fn route(agent_id: &str) -> ShardRoute<'_> {
+        ShardRoute {
+            agent_id,
+            org_id: None,
+        }
+    }
+
+    #[test]
+    fn test_search_within_only_touches_candidate_shards() {
+        let index = ShardedIndex::new(16, ShardKey::Agent);
+        let a = Uuid::now_v7();
+        let b = Uuid::now_v7();
+        index.add_routed(route("alice"), a, &vector(1)).unwrap();
+        index.add_routed(route("bob"), b, &vector(2)).unwrap();
+        assert_eq!(index.shard_names(), ["alice", "bob"]);
+        assert_eq!(index.len(), 2);
+
+        let hits = index
+            .search_within(&vector(2), 5, &HashSet::from([a]))
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].0, a);
+        assert_eq!(index.search(&vector(2), 5).unwrap()[0].0, b);
+
+        index.remove(b).unwrap();
+        assert_eq!(index.shard_names(), ["alice"]);
+    }
+
+    #[test]
+    fn test_evicted_shards_reload_from_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = ShardedIndex::new(16, ShardKey::Agent)
+            .with_dir(dir.path())
+            .unwrap()
+            .with_max_loaded_shards(2);
+        let ids: Vec<Uuid> = (0..4).map(|_| Uuid::now_v7()).collect();
+        for (i, id) in ids.iter().enumerate() {
+            index
+                .add_routed(route(&format!("agent-{i}")), *id, &vector(i as u64))
+                .unwrap();
+        }
+        assert_eq!(index.loaded_shards(), 2);
+
+        let hits = index.search_within(&vector(0), 1, &HashSet::from([ids[0]]));
+        assert_eq!(hits.unwrap()[0].0, ids[0]);
+
+        index.save(dir.path()).unwrap();
+        let reopened = ShardedIndex::new(16, ShardKey::Agent)
+            .with_dir(dir.path())
+            .unwrap();
+        assert_eq!(reopened.len(), 4);
+        assert_eq!(reopened.loaded_shards(), 0);
+        assert!(reopened.prefetch(route("agent-1")).unwrap());
+        assert!(!reopened.prefetch(route("agent-1")).unwrap());
+        assert!(!reopened.prefetch(route("nobody")).unwrap());
+        assert_eq!(reopened.loaded_shards(), 1);
+        assert_eq!(reopened.search(&vector(3), 1).unwrap()[0].0, ids[3]);
+    }
+}
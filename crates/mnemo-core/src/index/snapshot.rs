@@ -0,0 +1,286 @@
+//! Vector index snapshots for bootstrapping replicas.
+//!
+//! A new replica would otherwise re-embed or re-insert every memory to
+//! build its index. Instead it restores an [`IndexSnapshot`] of a running
+//! node and then applies [`IndexDelta`]s for the memories updated after
+//! the snapshot's `watermark`, until a delta comes back empty.
+//!
+//! A snapshot is the files the index writes with
+//! [`save_snapshot`](super::VectorIndex::save_snapshot), packed into one
+//! payload. Its [`SnapshotManifest`] records the format version and the
+//! payload's SHA-256, and [`IndexSnapshot::restore`] refuses a snapshot
+//! whose version or checksum does not match. Only the usearch index
+//! supports snapshots.
+//!
+//! Deltas are read from storage, so they carry soft deletes but not hard
+//! deletes made after the snapshot.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::index::VectorIndex;
+use crate::model::memory::ConsolidationState;
+use crate::storage::StorageBackend;
+
+/// Version of the payload layout written by [`IndexSnapshot::capture`].
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// Default number of memories read per [`IndexDelta`].
+pub const DEFAULT_DELTA_LIMIT: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub format_version: u32,
+    pub dimensions: usize,
+    /// Vectors in the snapshot.
+    pub vectors: usize,
+    /// Memories updated after this time (RFC 3339) may be missing from the
+    /// snapshot; fetch them with a delta from here.
+    pub watermark: String,
+    /// Hex SHA-256 of the payload.
+    pub checksum: String,
+    /// Payload length in bytes.
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexSnapshot {
+    pub manifest: SnapshotManifest,
+    pub payload: Vec<u8>,
+}
+
+impl IndexSnapshot {
+    /// Snapshot `index`. The watermark is taken before the index is read,
+    /// so a write racing the capture shows up in the next delta.
+    pub fn capture(index: &dyn VectorIndex, dimensions: usize) -> Result<Self> {
+        let watermark = chrono::Utc::now().to_rfc3339();
+        let vectors = index.len();
+        let dir = ScratchDir::new()?;
+        index.save_snapshot(&dir.0)?;
+        let payload = pack(&dir.0)?;
+        Ok(Self {
+            manifest: SnapshotManifest {
+                format_version: SNAPSHOT_FORMAT_VERSION,
+                dimensions,
+                vectors,
+                watermark,
+                checksum: checksum(&payload),
+                bytes: payload.len() as u64,
+            },
+            payload,
+        })
+    }
+
+    /// Check the format version, length and checksum.
+    pub fn verify(&self) -> Result<()> {
+        let manifest = &self.manifest;
+        if manifest.format_version != SNAPSHOT_FORMAT_VERSION {
+            return Err(Error::Validation(format!(
+                "index snapshot format {} is not supported (expected {SNAPSHOT_FORMAT_VERSION})",
+                manifest.format_version
+            )));
+        }
+        if manifest.bytes != self.payload.len() as u64
+            || manifest.checksum != checksum(&self.payload)
+        {
+            return Err(Error::IndexCorruption(
+                "index snapshot payload does not match its checksum".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Verify the snapshot and replace the contents of `index` with it.
+    pub fn restore(&self, index: &dyn VectorIndex, dimensions: usize) -> Result<()> {
+        self.verify()?;
+        if self.manifest.dimensions != dimensions {
+            return Err(Error::Validation(format!(
+                "index snapshot has {} dimensions but this index has {dimensions}",
+                self.manifest.dimensions
+            )));
+        }
+        let dir = ScratchDir::new()?;
+        unpack(&self.payload, &dir.0)?;
+        index.load_snapshot(&dir.0)
+    }
+}
+
+/// Vector changes of the memories updated after `since`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IndexDelta {
+    /// Embeddings to add or replace.
+    pub vectors: Vec<(Uuid, Vec<f32>)>,
+    /// Memories deleted, archived or quarantined since.
+    pub removed: Vec<Uuid>,
+    /// `updated_at` of the last memory read; pass it as `since` for the
+    /// next delta. `None` when no memory was updated after `since`.
+    pub next: Option<String>,
+}
+
+impl IndexDelta {
+    /// Read up to `limit` memories updated after `since`, oldest first.
+    pub async fn since(storage: &dyn StorageBackend, since: &str, limit: usize) -> Result<Self> {
+        let records = storage.list_memories_since(since, limit).await?;
+        let mut delta = Self {
+            next: records.last().map(|r| r.updated_at.clone()),
+            ..Default::default()
+        };
+        for record in records {
+            let searchable = record.deleted_at.is_none()
+                && !record.quarantined
+                && !matches!(
+                    record.consolidation_state,
+                    ConsolidationState::Archived | ConsolidationState::Forgotten
+                );
+            match record.embedding {
+                Some(embedding) if searchable => delta.vectors.push((record.id, embedding)),
+                _ => delta.removed.push(record.id),
+            }
+        }
+        Ok(delta)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.next.is_none()
+    }
+
+    pub fn apply(&self, index: &dyn VectorIndex) -> Result<()> {
+        for (id, vector) in &self.vectors {
+            index.add(*id, vector)?;
+        }
+        for id in &self.removed {
+            index.remove(*id)?;
+        }
+        Ok(())
+    }
+}
+
+fn checksum(payload: &[u8]) -> String {
+    hex::encode(Sha256::digest(payload))
+}
+
+/// Pack every file in `dir` as `name length, name, data length, data`,
+/// lengths little-endian (u32 and u64), in name order.
+fn pack(dir: &Path) -> Result<Vec<u8>> {
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map_err(|e| Error::Index(e.to_string()))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<_>>()
+        .map_err(|e| Error::Index(e.to_string()))?;
+    entries.sort();
+    let mut payload = Vec::new();
+    for path in entries {
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| Error::Index(format!("bad snapshot file {}", path.display())))?;
+        let data = std::fs::read(&path).map_err(|e| Error::Index(e.to_string()))?;
+        payload.extend_from_slice(&(name.len() as u32).to_le_bytes());
+        payload.extend_from_slice(name.as_bytes());
+        payload.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        payload.extend_from_slice(&data);
+    }
+    Ok(payload)
+}
+
+fn unpack(mut payload: &[u8], dir: &Path) -> Result<()> {
+    fn take<'a>(payload: &mut &'a [u8], n: usize) -> Result<&'a [u8]> {
+        if payload.len() < n {
+            return Err(Error::IndexCorruption(
+                "index snapshot payload is truncated".to_string(),
+            ));
+        }
+        let (head, tail) = payload.split_at(n);
+        *payload = tail;
+        Ok(head)
+    }
+    while !payload.is_empty() {
+        let name_len = u32::from_le_bytes(take(&mut payload, 4)?.try_into().unwrap()) as usize;
+        let name = std::str::from_utf8(take(&mut payload, name_len)?)
+            .map_err(|e| Error::IndexCorruption(e.to_string()))?;
+        if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+            return Err(Error::IndexCorruption(format!(
+                "bad snapshot file name {name:?}"
+            )));
+        }
+        let data_len = u64::from_le_bytes(take(&mut payload, 8)?.try_into().unwrap()) as usize;
+        let data = take(&mut payload, data_len)?;
+        std::fs::write(dir.join(name), data).map_err(|e| Error::Index(e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// A temporary directory removed on drop.
+struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    fn new() -> Result<Self> {
+        let path = std::env::temp_dir().join(format!("mnemo-index-snapshot-{}", Uuid::now_v7()));
+        std::fs::create_dir_all(&path).map_err(|e| Error::Index(e.to_string()))?;
+        Ok(Self(path))
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_round_trips_and_rejects_escaping_names() {
+        let src = ScratchDir::new().unwrap();
+        std::fs::write(src.0.join("index.usearch"), b"vectors").unwrap();
+        std::fs::write(src.0.join("index.mappings.json"), b"{}").unwrap();
+        let payload = pack(&src.0).unwrap();
+
+        let dst = ScratchDir::new().unwrap();
+        unpack(&payload, &dst.0).unwrap();
+        assert_eq!(
+            std::fs::read(dst.0.join("index.usearch")).unwrap(),
+            b"vectors"
+        );
+        assert_eq!(
+            std::fs::read(dst.0.join("index.mappings.json")).unwrap(),
+            b"{}"
+        );
+
+        assert!(unpack(&payload[..payload.len() - 1], &dst.0).is_err());
+        let mut escaping = Vec::new();
+        escaping.extend_from_slice(&5u32.to_le_bytes());
+        escaping.extend_from_slice(b"../up");
+        escaping.extend_from_slice(&0u64.to_le_bytes());
+        assert!(unpack(&escaping, &dst.0).is_err());
+    }
+
+    #[test]
+    fn verify_checks_version_and_checksum() {
+        let payload = b"payload".to_vec();
+        let mut snapshot = IndexSnapshot {
+            manifest: SnapshotManifest {
+                format_version: SNAPSHOT_FORMAT_VERSION,
+                dimensions: 4,
+                vectors: 0,
+                watermark: "2026-01-01T00:00:00+00:00".to_string(),
+                checksum: checksum(&payload),
+                bytes: payload.len() as u64,
+            },
+            payload,
+        };
+        snapshot.verify().unwrap();
+
+        snapshot.payload[0] ^= 1;
+        assert!(matches!(snapshot.verify(), Err(Error::IndexCorruption(_))));
+        snapshot.payload[0] ^= 1;
+        snapshot.manifest.format_version += 1;
+        assert!(matches!(snapshot.verify(), Err(Error::Validation(_))));
+    }
+}
@@ -1,6 +1,10 @@
 use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom, Write};
 use std::path::Path;
-use std::sync::RwLock;
+use std::sync::{Mutex, RwLock};
+
+use serde::{Deserialize, Serialize};
 
 use crate::error::{Error, Result};
 use crate::index::VectorIndex;
@@ -12,10 +16,29 @@ pub struct UsearchIndex {
     key_to_uuid: RwLock<HashMap<u64, Uuid>>,
     next_key: RwLock<u64>,
     dimensions: usize,
+    /// Append-only log of mutations since the last [`save`](VectorIndex::save).
+    /// `None` unless attached with [`UsearchIndex::with_wal`].
+    wal: Mutex<Option<File>>,
+}
+
+/// File name of the index inside a snapshot directory.
+const SNAPSHOT_FILE: &str = "index.usearch";
+
+/// One line of the write-ahead log (JSON lines).
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+enum WalEntry {
+    Add { id: Uuid, vector: Vec<f32> },
+    Remove { id: Uuid },
 }
 
 impl UsearchIndex {
     pub fn new(dimensions: usize) -> Result<Self> {
+        Self::with_capacity(dimensions, 10_000)
+    }
+
+    /// An empty index with room for `capacity` vectors before it grows.
+    pub fn with_capacity(dimensions: usize, capacity: usize) -> Result<Self> {
         let opts = usearch::IndexOptions {
             dimensions,
             metric: usearch::MetricKind::Cos,
@@ -24,7 +47,7 @@ impl UsearchIndex {
         };
         let index = usearch::Index::new(&opts).map_err(|e| Error::Index(e.to_string()))?;
         index
-            .reserve(10_000)
+            .reserve(capacity)
             .map_err(|e| Error::Index(e.to_string()))?;
 
         Ok(Self {
@@ -33,9 +56,110 @@ impl UsearchIndex {
             key_to_uuid: RwLock::new(HashMap::new()),
             next_key: RwLock::new(0),
             dimensions,
+            wal: Mutex::new(None),
         })
     }
 
+    /// Bytes held by the usearch graph and vectors.
+    pub fn memory_usage(&self) -> usize {
+        let index = self.index.read().unwrap_or_else(|e| e.into_inner());
+        index.memory_usage()
+    }
+
+    /// Log every subsequent `add` / `remove` to `path` so vectors added after
+    /// the last `save` survive a crash. Call [`replay_wal`](Self::replay_wal)
+    /// with the same path (after `load`) on startup; a successful `save`
+    /// truncates the log. Entries are written per mutation without fsync, so
+    /// they survive a process crash but not necessarily a power loss.
+    pub fn with_wal(self, path: &Path) -> Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| Error::Index(format!("open vector WAL {}: {e}", path.display())))?;
+        *self.wal.lock().unwrap_or_else(|e| e.into_inner()) = Some(file);
+        Ok(self)
+    }
+
+    /// Re-apply the mutations recorded in the WAL at `path`. Replay is
+    /// idempotent, and a torn final line (crash mid-append) ends the replay
+    /// instead of failing it. The torn tail is cut off the file so entries
+    /// appended after this restart aren't stranded behind it. Returns the
+    /// number of entries applied; a missing file replays nothing.
+    pub fn replay_wal(&self, path: &Path) -> Result<usize> {
+        let mut file = match std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)
+        {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(Error::Index(e.to_string())),
+        };
+        let mut reader = BufReader::new(&file);
+        let mut applied = 0;
+        // End of the last intact entry, and whether it lacks its newline.
+        let mut valid_len = 0u64;
+        let mut unterminated = false;
+        let mut torn = false;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = reader
+                .read_line(&mut line)
+                .map_err(|e| Error::Index(e.to_string()))?;
+            if read == 0 {
+                break;
+            }
+            let entry = line.trim_end_matches('\n');
+            if !entry.is_empty() {
+                let Ok(entry) = serde_json::from_str::<WalEntry>(entry) else {
+                    tracing::warn!(
+                        path = %path.display(),
+                        "vector WAL ends in a torn entry; stopping replay"
+                    );
+                    torn = true;
+                    break;
+                };
+                match entry {
+                    WalEntry::Add { id, vector } => self.add_unlogged(id, &vector)?,
+                    WalEntry::Remove { id } => self.remove_unlogged(id)?,
+                }
+                applied += 1;
+            }
+            valid_len += read as u64;
+            unterminated = !line.ends_with('\n');
+        }
+        drop(reader);
+        if torn {
+            file.set_len(valid_len)
+                .map_err(|e| Error::Index(format!("truncate vector WAL: {e}")))?;
+        }
+        if unterminated {
+            file.seek(SeekFrom::End(0))
+                .and_then(|_| file.write_all(b"\n"))
+                .map_err(|e| Error::Index(format!("repair vector WAL: {e}")))?;
+        }
+        Ok(applied)
+    }
+
+    /// Best effort: the in-memory mutation already succeeded, so a failed
+    /// append only narrows crash recovery and is logged rather than returned.
+    fn append_wal(wal: &mut Option<File>, entry: &WalEntry) {
+        let Some(file) = wal.as_mut() else {
+            return;
+        };
+        let result = serde_json::to_vec(entry)
+            .map_err(|e| e.to_string())
+            .and_then(|mut line| {
+                line.push(b'\n');
+                file.write_all(&line).map_err(|e| e.to_string())
+            });
+        if let Err(e) = result {
+            tracing::warn!(error = %e, "failed to append to vector WAL");
+        }
+    }
+
     fn allocate_key(&self, id: Uuid) -> u64 {
         let mut next = self.next_key.write().unwrap_or_else(|e| e.into_inner());
         let key = *next;
@@ -61,10 +185,8 @@ impl UsearchIndex {
             .unwrap_or_else(|e| e.into_inner())
             .remove(&key);
     }
-}
 
-impl VectorIndex for UsearchIndex {
-    fn add(&self, id: Uuid, vector: &[f32]) -> Result<()> {
+    fn add_unlogged(&self, id: Uuid, vector: &[f32]) -> Result<()> {
         if vector.len() != self.dimensions {
             return Err(Error::Validation(format!(
                 "expected {} dimensions, got {}",
@@ -80,7 +202,7 @@ impl VectorIndex for UsearchIndex {
             .unwrap_or_else(|e| e.into_inner())
             .contains_key(&id)
         {
-            self.remove(id)?;
+            self.remove_unlogged(id)?;
         }
 
         let key = self.allocate_key(id);
@@ -102,7 +224,30 @@ impl VectorIndex for UsearchIndex {
         Ok(())
     }
 
-    fn remove(&self, id: Uuid) -> Result<()> {
+    /// Write the index to `path` and its key mappings next to it.
+    fn write_files(&self, path: &Path) -> Result<()> {
+        let path_str = path
+            .to_str()
+            .ok_or_else(|| Error::Index("non-UTF-8 index path".to_string()))?;
+        let index = self.index.read().unwrap_or_else(|e| e.into_inner());
+        index
+            .save(path_str)
+            .map_err(|e| Error::Index(e.to_string()))?;
+
+        // Save mappings alongside
+        let mappings_path = path.with_extension("mappings.json");
+        let uuid_to_key = self.uuid_to_key.read().unwrap_or_else(|e| e.into_inner());
+        let next_key = *self.next_key.read().unwrap_or_else(|e| e.into_inner());
+        let data = serde_json::json!({
+            "uuid_to_key": uuid_to_key.iter().map(|(k, v)| (k.to_string(), v)).collect::<HashMap<String, &u64>>(),
+            "next_key": next_key,
+        });
+        let json_str = serde_json::to_string(&data).map_err(|e| Error::Index(e.to_string()))?;
+        std::fs::write(&mappings_path, json_str).map_err(|e| Error::Index(e.to_string()))?;
+        Ok(())
+    }
+
+    fn remove_unlogged(&self, id: Uuid) -> Result<()> {
         let key = {
             let map = self.uuid_to_key.read().unwrap_or_else(|e| e.into_inner());
             match map.get(&id) {
@@ -124,6 +269,30 @@ impl VectorIndex for UsearchIndex {
             .remove(&key);
         Ok(())
     }
+}
+
+impl VectorIndex for UsearchIndex {
+    fn add(&self, id: Uuid, vector: &[f32]) -> Result<()> {
+        // Hold the WAL lock across the mutation so log order matches apply
+        // order under concurrent writers.
+        let mut wal = self.wal.lock().unwrap_or_else(|e| e.into_inner());
+        self.add_unlogged(id, vector)?;
+        Self::append_wal(
+            &mut wal,
+            &WalEntry::Add {
+                id,
+                vector: vector.to_vec(),
+            },
+        );
+        Ok(())
+    }
+
+    fn remove(&self, id: Uuid) -> Result<()> {
+        let mut wal = self.wal.lock().unwrap_or_else(|e| e.into_inner());
+        self.remove_unlogged(id)?;
+        Self::append_wal(&mut wal, &WalEntry::Remove { id });
+        Ok(())
+    }
 
     fn search(&self, query: &[f32], limit: usize) -> Result<Vec<(Uuid, f32)>> {
         let index = self.index.read().unwrap_or_else(|e| e.into_inner());
@@ -168,24 +337,16 @@ impl VectorIndex for UsearchIndex {
     }
 
     fn save(&self, path: &Path) -> Result<()> {
-        let path_str = path
-            .to_str()
-            .ok_or_else(|| Error::Index("non-UTF-8 index path".to_string()))?;
-        let index = self.index.read().unwrap_or_else(|e| e.into_inner());
-        index
-            .save(path_str)
-            .map_err(|e| Error::Index(e.to_string()))?;
-
-        // Save mappings alongside
-        let mappings_path = path.with_extension("mappings.json");
-        let uuid_to_key = self.uuid_to_key.read().unwrap_or_else(|e| e.into_inner());
-        let next_key = *self.next_key.read().unwrap_or_else(|e| e.into_inner());
-        let data = serde_json::json!({
-            "uuid_to_key": uuid_to_key.iter().map(|(k, v)| (k.to_string(), v)).collect::<HashMap<String, &u64>>(),
-            "next_key": next_key,
-        });
-        let json_str = serde_json::to_string(&data).map_err(|e| Error::Index(e.to_string()))?;
-        std::fs::write(&mappings_path, json_str).map_err(|e| Error::Index(e.to_string()))?;
+        // Hold the WAL lock from the snapshot through the truncate, so an add
+        // can't land in the log after the snapshot and then be cut from it.
+        let wal = self.wal.lock().unwrap_or_else(|e| e.into_inner());
+        self.write_files(path)?;
+
+        // Everything logged so far is in the snapshot now.
+        if let Some(ref file) = *wal {
+            file.set_len(0)
+                .map_err(|e| Error::Index(format!("truncate vector WAL: {e}")))?;
+        }
         Ok(())
     }
 
@@ -196,15 +357,16 @@ impl VectorIndex for UsearchIndex {
         let index = self.index.read().unwrap_or_else(|e| e.into_inner());
         index
             .load(path_str)
-            .map_err(|e| Error::Index(e.to_string()))?;
+            .map_err(|e| Error::IndexCorruption(format!("load {}: {e}", path.display())))?;
 
         // Load mappings
         let mappings_path = path.with_extension("mappings.json");
         if mappings_path.exists() {
             let data =
                 std::fs::read_to_string(&mappings_path).map_err(|e| Error::Index(e.to_string()))?;
-            let parsed: serde_json::Value =
-                serde_json::from_str(&data).map_err(|e| Error::Index(e.to_string()))?;
+            let parsed: serde_json::Value = serde_json::from_str(&data).map_err(|e| {
+                Error::IndexCorruption(format!("parse {}: {e}", mappings_path.display()))
+            })?;
 
             let mut uuid_to_key = self.uuid_to_key.write().unwrap_or_else(|e| e.into_inner());
             let mut key_to_uuid = self.key_to_uuid.write().unwrap_or_else(|e| e.into_inner());
@@ -215,10 +377,10 @@ impl VectorIndex for UsearchIndex {
 
             if let Some(map) = parsed["uuid_to_key"].as_object() {
                 for (uuid_str, key_val) in map {
-                    let uuid =
-                        Uuid::parse_str(uuid_str).map_err(|e| Error::Index(e.to_string()))?;
+                    let uuid = Uuid::parse_str(uuid_str)
+                        .map_err(|e| Error::IndexCorruption(e.to_string()))?;
                     let key = key_val.as_u64().ok_or_else(|| {
-                        Error::Index(format!("invalid key value for UUID {uuid_str}"))
+                        Error::IndexCorruption(format!("invalid key value for UUID {uuid_str}"))
                     })?;
                     uuid_to_key.insert(uuid, key);
                     key_to_uuid.insert(key, uuid);
@@ -236,6 +398,14 @@ impl VectorIndex for UsearchIndex {
         let index = self.index.read().unwrap_or_else(|e| e.into_inner());
         index.size()
     }
+
+    fn save_snapshot(&self, dir: &Path) -> Result<()> {
+        self.write_files(&dir.join(SNAPSHOT_FILE))
+    }
+
+    fn load_snapshot(&self, dir: &Path) -> Result<()> {
+        self.load(&dir.join(SNAPSHOT_FILE))
+    }
 }
 
 #[cfg(test)]
@@ -347,6 +517,84 @@ mod tests {
         std::fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_wal_replays_unsaved_mutations() {
+        let dir = std::env::temp_dir().join(format!("usearch_wal_test_{}", Uuid::now_v7()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let index_path = dir.join("test.usearch");
+        let wal_path = dir.join("test.usearch.wal");
+
+        let index = UsearchIndex::new(128).unwrap().with_wal(&wal_path).unwrap();
+        let saved = Uuid::now_v7();
+        index.add(saved, &random_vector(128, 1)).unwrap();
+        index.save(&index_path).unwrap();
+        assert_eq!(std::fs::metadata(&wal_path).unwrap().len(), 0);
+
+        // Mutations after the save only live in the WAL.
+        let unsaved = Uuid::now_v7();
+        index.add(unsaved, &random_vector(128, 2)).unwrap();
+        index.remove(saved).unwrap();
+        drop(index);
+
+        // Simulate a crash mid-append.
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&wal_path)
+            .unwrap();
+        file.write_all(b"{\"op\":\"add\",\"id\"").unwrap();
+
+        let recovered = UsearchIndex::new(128).unwrap();
+        recovered.load(&index_path).unwrap();
+        assert_eq!(recovered.replay_wal(&wal_path).unwrap(), 2);
+        assert_eq!(recovered.len(), 1);
+        let results = recovered.search(&random_vector(128, 2), 1).unwrap();
+        assert_eq!(results[0].0, unsaved);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_wal_survives_a_second_crash_after_a_torn_entry() {
+        let dir = std::env::temp_dir().join(format!("usearch_wal_test_{}", Uuid::now_v7()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let wal_path = dir.join("test.usearch.wal");
+
+        let index = UsearchIndex::new(128).unwrap().with_wal(&wal_path).unwrap();
+        let first = Uuid::now_v7();
+        index.add(first, &random_vector(128, 1)).unwrap();
+        drop(index);
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&wal_path)
+            .unwrap();
+        file.write_all(b"{\"op\":\"add\",\"id\"").unwrap();
+        drop(file);
+
+        // First restart: replay, then keep logging to the same file.
+        let restarted = UsearchIndex::new(128).unwrap();
+        assert_eq!(restarted.replay_wal(&wal_path).unwrap(), 1);
+        let restarted = restarted.with_wal(&wal_path).unwrap();
+        let second = Uuid::now_v7();
+        restarted.add(second, &random_vector(128, 2)).unwrap();
+        drop(restarted);
+
+        // Second crash: both adds are still recovered.
+        let recovered = UsearchIndex::new(128).unwrap();
+        assert_eq!(recovered.replay_wal(&wal_path).unwrap(), 2);
+        assert_eq!(recovered.len(), 2);
+        let results = recovered.search(&random_vector(128, 2), 1).unwrap();
+        assert_eq!(results[0].0, second);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_replay_missing_wal_is_noop() {
+        let index = UsearchIndex::new(128).unwrap();
+        let path = std::env::temp_dir().join(format!("missing_{}.wal", Uuid::now_v7()));
+        assert_eq!(index.replay_wal(&path).unwrap(), 0);
+    }
+
     #[test]
     fn test_dimension_mismatch() {
         let index = UsearchIndex::new(128).unwrap();
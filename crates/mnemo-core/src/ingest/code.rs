@@ -0,0 +1,239 @@
+//! Source code loader with symbol-aware chunking.
+//!
+//! Each top-level definition (function, type, class, impl block, ...)
+//! becomes its own chunk together with the comments, attributes and
+//! decorators directly above it, labelled with the symbol and its line
+//! range. Definitions are found with per-language patterns on unindented
+//! lines, not a parser, so nested items stay with their parent. Code before
+//! the first definition (imports, module docs) is chunked on its own, and
+//! a definition longer than `max_chars` is split at line boundaries.
+//! Unknown languages fall back to plain line windows.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::{Chunk, DocumentLoader, LoadedDocument};
+use crate::error::Result;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CodeLoader;
+
+/// Language of a file extension, among those with symbol patterns or
+/// otherwise recognised as code.
+pub fn language_of(extension: &str) -> Option<&'static str> {
+    Some(match extension {
+        "rs" => "rust",
+        "py" | "pyi" => "python",
+        "js" | "jsx" | "mjs" | "cjs" => "javascript",
+        "ts" | "tsx" | "mts" | "cts" => "typescript",
+        "go" => "go",
+        "java" => "java",
+        "kt" | "kts" => "kotlin",
+        "cs" => "csharp",
+        "rb" => "ruby",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" => "cpp",
+        "swift" => "swift",
+        "php" => "php",
+        "scala" => "scala",
+        "sh" | "bash" | "zsh" => "shell",
+        "sql" => "sql",
+        "proto" => "protobuf",
+        _ => return None,
+    })
+}
+
+/// Pattern of an unindented line starting a definition; group `name`
+/// captures the symbol.
+fn symbol_pattern(language: &str) -> Option<&'static Regex> {
+    static RUST: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(
+            r"^(?:pub(?:\([^)]*\))?\s+)?(?:(?:async|const|unsafe|extern(?:\s+\x22[^\x22]*\x22)?)\s+)*(?P<kind>fn|struct|enum|union|trait|impl|mod|type|const|static|macro_rules!)\s*(?:<[^>]*>\s*)?(?P<name>[A-Za-z_][A-Za-z0-9_:<>, ]*)",
+        )
+        .unwrap()
+    });
+    static PYTHON: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"^(?:async\s+)?(?P<kind>def|class)\s+(?P<name>[A-Za-z_][A-Za-z0-9_]*)").unwrap()
+    });
+    static JAVASCRIPT: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(
+            r"^(?:export\s+)?(?:default\s+)?(?:declare\s+)?(?:abstract\s+)?(?:async\s+)?(?P<kind>function\*?|class|interface|type|enum|const|let|namespace)\s+(?P<name>[A-Za-z_$][A-Za-z0-9_$]*)",
+        )
+        .unwrap()
+    });
+    static GO: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"^(?P<kind>func|type)\s+(?:\([^)]*\)\s*)?(?P<name>[A-Za-z_][A-Za-z0-9_]*)")
+            .unwrap()
+    });
+    static JVM: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(
+            r"^(?:(?:public|private|protected|internal|abstract|final|sealed|static|data|open|partial)\s+)*(?P<kind>class|interface|enum|record|object|struct|fun)\s+(?P<name>[A-Za-z_][A-Za-z0-9_]*)",
+        )
+        .unwrap()
+    });
+    static RUBY: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(r"^(?P<kind>def|class|module)\s+(?P<name>[A-Za-z_][A-Za-z0-9_.:?!]*)").unwrap()
+    });
+    static C: LazyLock<Regex> = LazyLock::new(|| {
+        Regex::new(
+            r"^(?:(?P<kind>struct|enum|union|class|namespace)\s+(?P<name>[A-Za-z_][A-Za-z0-9_]*)|[A-Za-z_][A-Za-z0-9_\s\*&:<>,]*?\b(?P<func>[A-Za-z_][A-Za-z0-9_:~]*)\s*\([^;]*$)",
+        )
+        .unwrap()
+    });
+    Some(match language {
+        "rust" => &RUST,
+        "python" => &PYTHON,
+        "javascript" | "typescript" => &JAVASCRIPT,
+        "go" => &GO,
+        "java" | "kotlin" | "csharp" | "scala" | "swift" => &JVM,
+        "ruby" => &RUBY,
+        "c" | "cpp" => &C,
+        _ => return None,
+    })
+}
+
+impl DocumentLoader for CodeLoader {
+    fn load(&self, source: &str, bytes: &[u8], max_chars: usize) -> Result<LoadedDocument> {
+        let text = String::from_utf8_lossy(bytes);
+        let lines: Vec<&str> = text.lines().collect();
+        let extension = source
+            .rsplit_once('.')
+            .map(|(_, ext)| ext.to_ascii_lowercase())
+            .unwrap_or_default();
+        let starts = match language_of(&extension).and_then(symbol_pattern) {
+            Some(pattern) => symbol_starts(&lines, pattern),
+            None => Vec::new(),
+        };
+
+        // Segments: the prelude, then each definition up to the next one.
+        let mut segments: Vec<(usize, usize, Option<String>)> = Vec::new();
+        let first = starts.first().map_or(lines.len(), |(start, _)| *start);
+        if first > 0 {
+            segments.push((0, first, None));
+        }
+        for (i, (start, symbol)) in starts.iter().enumerate() {
+            let end = starts.get(i + 1).map_or(lines.len(), |(next, _)| *next);
+            segments.push((*start, end, Some(symbol.clone())));
+        }
+
+        let mut document = LoadedDocument::default();
+        for (start, end, symbol) in segments {
+            for (first, last) in split_lines(&lines[start..end], max_chars) {
+                let body = &lines[start + first..start + last];
+                let text = body.join("\n");
+                if text.trim().is_empty() {
+                    continue;
+                }
+                document.chunks.push(Chunk {
+                    text: text.trim_end().to_string(),
+                    symbol: symbol.clone(),
+                    lines: Some((start + first + 1, start + last)),
+                    ..Default::default()
+                });
+            }
+        }
+        Ok(document)
+    }
+}
+
+/// Line index and `kind name` label of each definition, moved up over the
+/// comments, attributes and decorators directly above it.
+fn symbol_starts(lines: &[&str], pattern: &Regex) -> Vec<(usize, String)> {
+    let mut starts: Vec<(usize, String)> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if line.starts_with(char::is_whitespace) {
+            continue;
+        }
+        let Some(caps) = pattern.captures(line) else {
+            continue;
+        };
+        let label = match (caps.name("kind"), caps.name("name"), caps.name("func")) {
+            (Some(kind), Some(name), _) => format!("{} {}", kind.as_str(), name.as_str().trim()),
+            (_, _, Some(func)) => func.as_str().to_string(),
+            _ => continue,
+        };
+        let floor = starts.last().map_or(0, |(start, _)| *start + 1);
+        let mut start = i;
+        while start > floor && is_preamble(lines[start - 1]) {
+            start -= 1;
+        }
+        starts.push((start, label));
+    }
+    starts
+}
+
+fn is_preamble(line: &str) -> bool {
+    let line = line.trim_start();
+    ["//", "/*", "*", "#[", "#!", "@", "#", "--", "\"\"\""]
+        .iter()
+        .any(|prefix| line.starts_with(prefix))
+}
+
+/// Split a run of lines into consecutive `[first, last)` ranges of at most
+/// `max_chars` characters, counting newlines. A single longer line is a
+/// range of its own.
+fn split_lines(lines: &[&str], max_chars: usize) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut first = 0;
+    let mut chars = 0;
+    for (i, line) in lines.iter().enumerate() {
+        let len = line.chars().count() + 1;
+        if i > first && chars + len > max_chars + 1 {
+            ranges.push((first, i));
+            first = i;
+            chars = 0;
+        }
+        chars += len;
+    }
+    if first < lines.len() {
+        ranges.push((first, lines.len()));
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_rust_by_symbol_with_its_docs() {
+        let source = "use std::fmt;\n\n/// Parses things.\n#[inline]\npub fn parse(x: &str) -> u32 {\n    x.len() as u32\n}\n\nimpl fmt::Display for Thing {\n    fn fmt(&self) {}\n}\n";
+        let document = CodeLoader
+            .load("src/lib.rs", source.as_bytes(), 1000)
+            .unwrap();
+        let labels: Vec<_> = document
+            .chunks
+            .iter()
+            .map(|c| (c.symbol.as_deref(), c.lines.unwrap()))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                (None, (1, 2)),
+                (Some("fn parse"), (3, 8)),
+                (Some("impl fmt::Display for Thing"), (9, 11)),
+            ]
+        );
+        assert!(document.chunks[1].text.starts_with("/// Parses things."));
+    }
+
+    #[test]
+    fn chunks_python_and_splits_long_definitions() {
+        let body: String = (0..20).map(|i| format!("    x{i} = {i}\n")).collect();
+        let source = format!("import os\n\n@cache\ndef load():\n{body}\nclass Store:\n    pass\n");
+        let document = CodeLoader.load("store.py", source.as_bytes(), 80).unwrap();
+        let load: Vec<_> = document
+            .chunks
+            .iter()
+            .filter(|c| c.symbol.as_deref() == Some("def load"))
+            .collect();
+        assert!(load.len() > 1);
+        assert!(load[0].text.starts_with("@cache"));
+        assert!(document.chunks.iter().all(|c| c.text.chars().count() <= 80));
+        assert_eq!(
+            document.chunks.last().unwrap().symbol.as_deref(),
+            Some("class Store")
+        );
+    }
+}
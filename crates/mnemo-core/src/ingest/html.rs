@@ -0,0 +1,326 @@
+//! HTML loader: the visible text of a page, sectioned by its headings.
+//!
+//! Tags are stripped with a small tokenizer rather than a full HTML
+//! parser. Only the main content is kept: the first `<main>` element, or
+//! failing that the first `<article>`, when the page has one; and `nav`,
+//! `aside` and `footer` elements are dropped along with `script`, `style`
+//! and the like. Block elements become paragraph breaks, `h1`–`h6` become
+//! Markdown headings, and the result is chunked like Markdown. The
+//! `<title>` element, or failing that the first `h1`, is the document
+//! title.
+
+use super::markdown::split_sections;
+use super::{DocumentLoader, LoadedDocument};
+use crate::error::Result;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HtmlLoader;
+
+impl DocumentLoader for HtmlLoader {
+    fn load(&self, _source: &str, bytes: &[u8], max_chars: usize) -> Result<LoadedDocument> {
+        let (title, text) = to_text(&String::from_utf8_lossy(bytes));
+        let mut document = split_sections(&text, max_chars);
+        if title.is_some() {
+            document.title = title;
+        }
+        Ok(document)
+    }
+}
+
+/// Elements whose content is never visible text.
+const SKIPPED: &[&str] = &[
+    "script", "style", "noscript", "template", "svg", "head", "iframe", "object", "nav", "aside",
+    "footer",
+];
+
+/// Elements that start a new paragraph.
+const BLOCKS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "header",
+    "hr",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "td",
+    "th",
+    "tr",
+    "ul",
+];
+
+/// The page's `<title>` and the text of its main content as Markdown-like
+/// paragraphs.
+pub(crate) fn to_text(html: &str) -> (Option<String>, String) {
+    let (title, text) = render(html);
+    match main_content(html) {
+        Some(main) => (title, render(main).1),
+        None => (title, text),
+    }
+}
+
+/// The inside of the page's first `<main>` element, or else of its first
+/// `<article>`.
+fn main_content(html: &str) -> Option<&str> {
+    // ASCII lowercasing keeps byte offsets.
+    let lower = html.to_ascii_lowercase();
+    ["main", "article"].iter().find_map(|name| {
+        let open = format!("<{name}");
+        let mut from = 0;
+        let start = loop {
+            let at = from + lower[from..].find(&open)?;
+            let after = at + open.len();
+            if lower[after..].starts_with(['>', ' ', '\t', '\n', '\r', '/']) {
+                break after + lower[after..].find('>')? + 1;
+            }
+            from = after;
+        };
+        let end = start + lower[start..].find(&format!("</{name}>"))?;
+        Some(&html[start..end])
+    })
+}
+
+fn render(html: &str) -> (Option<String>, String) {
+    let mut out = String::new();
+    let mut title: Option<String> = None;
+    let mut in_title = false;
+    let mut skip_until: Option<String> = None;
+    let mut pre_depth = 0usize;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(lt) = rest.find('<') else {
+            push_text(&mut out, rest, pre_depth > 0, skip_until.is_some());
+            break;
+        };
+        let (text, tail) = rest.split_at(lt);
+        if in_title {
+            title
+                .get_or_insert_with(String::new)
+                .push_str(&decode_entities(text));
+        } else {
+            push_text(&mut out, text, pre_depth > 0, skip_until.is_some());
+        }
+        if let Some(after) = tail.strip_prefix("<!--") {
+            rest = after.find("-->").map_or("", |end| &after[end + 3..]);
+            continue;
+        }
+        let Some(gt) = tail.find('>') else {
+            break;
+        };
+        let tag = &tail[1..gt];
+        rest = &tail[gt + 1..];
+
+        let closing = tag.starts_with('/');
+        let name: String = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        if name.is_empty() {
+            continue;
+        }
+
+        if name == "title" {
+            in_title = !closing;
+            continue;
+        }
+        if let Some(ref skipped) = skip_until {
+            if closing && name == *skipped {
+                skip_until = None;
+            }
+            continue;
+        }
+        if !closing && SKIPPED.contains(&name.as_str()) && !tag.ends_with('/') {
+            skip_until = Some(name);
+            continue;
+        }
+
+        match name.as_str() {
+            "br" => out.push('\n'),
+            "pre" if closing => {
+                pre_depth = pre_depth.saturating_sub(1);
+                out.push_str("\n\n");
+            }
+            "pre" => {
+                pre_depth += 1;
+                out.push_str("\n\n");
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                out.push_str("\n\n");
+                if !closing {
+                    let level = (name.as_bytes()[1] - b'0') as usize;
+                    out.push_str(&"#".repeat(level));
+                    out.push(' ');
+                }
+            }
+            _ if BLOCKS.contains(&name.as_str()) => out.push_str("\n\n"),
+            _ => {}
+        }
+    }
+
+    let title = title
+        .map(|t| t.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|t| !t.is_empty());
+    (title, tidy(&out))
+}
+
+fn push_text(out: &mut String, text: &str, preformatted: bool, skipped: bool) {
+    if skipped || text.is_empty() {
+        return;
+    }
+    let text = decode_entities(text);
+    if preformatted {
+        out.push_str(&text);
+        return;
+    }
+    let starts_with_space = text.starts_with(char::is_whitespace);
+    let ends_with_space = text.ends_with(char::is_whitespace);
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.is_empty() {
+        if starts_with_space && !out.ends_with(char::is_whitespace) {
+            out.push(' ');
+        }
+        return;
+    }
+    if starts_with_space && !out.ends_with(char::is_whitespace) {
+        out.push(' ');
+    }
+    out.push_str(&words.join(" "));
+    if ends_with_space {
+        out.push(' ');
+    }
+}
+
+/// Trim trailing whitespace and collapse runs of blank lines.
+fn tidy(text: &str) -> String {
+    let mut out = String::new();
+    let mut blank = true;
+    for line in text.lines() {
+        let line = line.trim_end();
+        if line.trim().is_empty() {
+            if !blank {
+                out.push('\n');
+                blank = true;
+            }
+            continue;
+        }
+        out.push_str(line);
+        out.push('\n');
+        blank = false;
+    }
+    out.trim_end().to_string()
+}
+
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let tail = &rest[amp..];
+        let decoded = tail.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let entity = &tail[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity.strip_prefix('#').and_then(|code| {
+                    match code.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => code.parse().ok(),
+                    }
+                    .and_then(char::from_u32)
+                }),
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &tail[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_visible_text_by_section() {
+        let html = r#"<html><head><title>Runbook &amp; FAQ</title>
+            <style>p { color: red }</style></head>
+            <body><script>var x = "<p>hidden</p>";</script>
+            <h1>Deploys</h1><p>Run <b>make deploy</b>&nbsp;from main.</p>
+            <!-- a comment -->
+            <h2>Rollback</h2><ul><li>Revert</li><li>Redeploy</li></ul>
+            <pre>make   rollback</pre></body></html>"#;
+        let document = HtmlLoader
+            .load("runbook.html", html.as_bytes(), 1000)
+            .unwrap();
+        assert_eq!(document.title.as_deref(), Some("Runbook & FAQ"));
+        assert_eq!(document.chunks.len(), 2);
+        assert_eq!(document.chunks[0].section.as_deref(), Some("Deploys"));
+        assert!(
+            document.chunks[0]
+                .text
+                .contains("Run make deploy from main.")
+        );
+        assert_eq!(
+            document.chunks[1].section.as_deref(),
+            Some("Deploys > Rollback")
+        );
+        assert!(document.chunks[1].text.contains("Revert\n\nRedeploy"));
+        assert!(document.chunks[1].text.contains("make   rollback"));
+        let all: String = document.chunks.iter().map(|c| c.text.as_str()).collect();
+        assert!(!all.contains("hidden") && !all.contains("color") && !all.contains("comment"));
+    }
+
+    #[test]
+    fn keeps_only_the_main_content() {
+        let html = r#"<html><head><title>Pricing</title></head><body>
+            <header><a href="/">Home</a></header>
+            <nav><a href="/docs">Docs</a></nav>
+            <main class="content"><h1>Plans</h1><p>Pro costs $20.</p>
+            <aside>Related: Enterprise</aside></main>
+            <footer>Copyright</footer></body></html>"#;
+        let (title, text) = to_text(html);
+        assert_eq!(title.as_deref(), Some("Pricing"));
+        assert_eq!(text, "# Plans\n\nPro costs $20.");
+
+        let (_, text) =
+            to_text("<body><nav>Menu</nav><p>Body text.</p><footer>Legal</footer></body>");
+        assert_eq!(text, "Body text.");
+        let (_, text) = to_text("<mainframe>x</mainframe><article><p>Story.</p></article>");
+        assert_eq!(text, "Story.");
+    }
+}
@@ -0,0 +1,126 @@
+//! Markdown loader: one or more chunks per section, labelled with the
+//! section's heading path.
+
+use super::{Chunk, DocumentLoader, LoadedDocument, pack_paragraphs};
+use crate::error::Result;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MarkdownLoader;
+
+impl DocumentLoader for MarkdownLoader {
+    fn load(&self, _source: &str, bytes: &[u8], max_chars: usize) -> Result<LoadedDocument> {
+        Ok(split_sections(&String::from_utf8_lossy(bytes), max_chars))
+    }
+}
+
+/// Split Markdown at ATX headings (`#` to `######`) outside fenced code
+/// blocks. Each section keeps its heading line, and its chunks carry the
+/// path of headings above it; sections with nothing but a heading are
+/// dropped. The first level-1 heading is the title.
+pub(crate) fn split_sections(text: &str, max_chars: usize) -> LoadedDocument {
+    let text = strip_front_matter(text);
+    let mut document = LoadedDocument::default();
+    let mut headings: Vec<(usize, String)> = Vec::new();
+    let mut body = String::new();
+    let mut has_content = false;
+    let mut fence: Option<&str> = None;
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        let mut is_heading = false;
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+        } else if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence = Some(&trimmed[..3]);
+        } else if let Some((level, title)) = heading(line) {
+            if has_content {
+                flush(&mut document, &headings, &body, max_chars);
+            }
+            body.clear();
+            has_content = false;
+            is_heading = true;
+            if level == 1 && document.title.is_none() {
+                document.title = Some(title.clone());
+            }
+            headings.retain(|(l, _)| *l < level);
+            headings.push((level, title));
+        }
+        has_content |= !is_heading && !trimmed.is_empty();
+        body.push_str(line);
+        body.push('\n');
+    }
+    if has_content {
+        flush(&mut document, &headings, &body, max_chars);
+    }
+    document
+}
+
+fn flush(
+    document: &mut LoadedDocument,
+    headings: &[(usize, String)],
+    body: &str,
+    max_chars: usize,
+) {
+    let section = (!headings.is_empty()).then(|| {
+        headings
+            .iter()
+            .map(|(_, title)| title.as_str())
+            .collect::<Vec<_>>()
+            .join(" > ")
+    });
+    for text in pack_paragraphs(body, max_chars) {
+        document.chunks.push(Chunk {
+            text,
+            section: section.clone(),
+            ..Default::default()
+        });
+    }
+}
+
+fn heading(line: &str) -> Option<(usize, String)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &line[level..];
+    if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    let title = rest.trim().trim_end_matches('#').trim();
+    (!title.is_empty()).then(|| (level, title.to_string()))
+}
+
+fn strip_front_matter(text: &str) -> &str {
+    let Some(rest) = text.strip_prefix("---\n") else {
+        return text;
+    };
+    match rest.find("\n---\n") {
+        Some(end) => &rest[end + 5..],
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_carry_their_heading_path() {
+        let text = "---\ntitle: ignored\n---\n# Guide\nIntro.\n\n## Install\n### Linux\nRun apt.\n```sh\n# not a heading\n```\n## Usage\nCall it.\n";
+        let document = split_sections(text, 1000);
+        assert_eq!(document.title.as_deref(), Some("Guide"));
+        let sections: Vec<_> = document
+            .chunks
+            .iter()
+            .map(|c| c.section.as_deref().unwrap())
+            .collect();
+        assert_eq!(
+            sections,
+            vec!["Guide", "Guide > Install > Linux", "Guide > Usage"]
+        );
+        assert!(document.chunks[1].text.contains("# not a heading"));
+        assert!(!document.chunks[0].text.contains("ignored"));
+    }
+}
@@ -0,0 +1,754 @@
+//! Document ingestion: preload agent memory from files.
+//!
+//! A [`DocumentLoader`] turns one document into [`Chunk`]s of at most
+//! `max_chars` characters, keeping whatever structure its format has: the
+//! heading path of a Markdown or HTML section, the page of a PDF, the
+//! symbol and line range of source code. [`execute`] loads every document
+//! of an [`IngestRequest`] first, so a document that fails to load leaves
+//! nothing behind, then remembers each document's chunks through
+//! [`remember_batch`](crate::query::MnemoEngine::remember_batch) with
+//! `source_type = import`, `source_id` set to the document's source, the
+//! [`DOCUMENT_TAG`] tag and the chunk's provenance under
+//! `metadata.document`.
+//!
+//! Built-in loaders cover `markdown`, `html`, `pdf` (text extraction needs
+//! the `pdf` feature), `code` and `text`. Register more, or replace one,
+//! with [`MnemoEngine::with_document_loader`](crate::query::MnemoEngine::with_document_loader).
+//! Web pages that change over time are ingested, and kept fresh, through
+//! [`url`]; what they may point at is limited by [`UrlFetchPolicy`].
+
+pub mod code;
+pub mod html;
+pub mod markdown;
+pub mod pdf;
+pub mod url;
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::memory::{MemoryType, Scope, SourceType};
+use crate::query::MnemoEngine;
+use crate::query::remember::RememberRequest;
+
+/// Tag applied to every memory written by ingestion.
+pub const DOCUMENT_TAG: &str = "document";
+
+/// Default upper bound on the characters of one chunk.
+pub const DEFAULT_MAX_CHUNK_CHARS: usize = 1500;
+
+/// Largest `max_chunk_chars` a request may ask for.
+pub const MAX_CHUNK_CHARS: usize = 20_000;
+
+/// Largest response [`fetch`] accepts.
+pub const MAX_FETCH_BYTES: usize = 20 * 1024 * 1024;
+
+/// A piece of a document, remembered as one memory.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Chunk {
+    pub text: String,
+    /// Heading path, e.g. `Install > Linux`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    /// Code symbol the chunk belongs to, e.g. `fn parse`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    /// First and last line, 1-based and inclusive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lines: Option<(usize, usize)>,
+    /// 1-based page number.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<usize>,
+}
+
+impl Chunk {
+    pub fn new(text: String) -> Self {
+        Self {
+            text,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoadedDocument {
+    pub title: Option<String>,
+    pub chunks: Vec<Chunk>,
+}
+
+/// Pluggable reader for one document format.
+pub trait DocumentLoader: Send + Sync {
+    /// Split `bytes`, the document read from `source`, into chunks of at
+    /// most `max_chars` characters.
+    fn load(&self, source: &str, bytes: &[u8], max_chars: usize) -> Result<LoadedDocument>;
+}
+
+/// Plain text, split at blank lines.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextLoader;
+
+impl DocumentLoader for TextLoader {
+    fn load(&self, _source: &str, bytes: &[u8], max_chars: usize) -> Result<LoadedDocument> {
+        let text = String::from_utf8_lossy(bytes);
+        Ok(LoadedDocument {
+            title: None,
+            chunks: pack_paragraphs(&text, max_chars)
+                .into_iter()
+                .map(Chunk::new)
+                .collect(),
+        })
+    }
+}
+
+/// Loaders by format name.
+#[derive(Clone)]
+pub struct LoaderRegistry {
+    loaders: HashMap<String, Arc<dyn DocumentLoader>>,
+}
+
+impl Default for LoaderRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            loaders: HashMap::new(),
+        };
+        registry.register("markdown", Arc::new(markdown::MarkdownLoader));
+        registry.register("html", Arc::new(html::HtmlLoader));
+        registry.register("pdf", Arc::new(pdf::PdfLoader));
+        registry.register("code", Arc::new(code::CodeLoader));
+        registry.register("text", Arc::new(TextLoader));
+        registry
+    }
+}
+
+impl LoaderRegistry {
+    /// Use `loader` for documents of `format`, replacing any loader
+    /// registered for it.
+    pub fn register(&mut self, format: impl Into<String>, loader: Arc<dyn DocumentLoader>) {
+        self.loaders.insert(format.into(), loader);
+    }
+
+    pub fn get(&self, format: &str) -> Option<&Arc<dyn DocumentLoader>> {
+        self.loaders.get(format)
+    }
+
+    /// Registered format names, sorted.
+    pub fn formats(&self) -> Vec<&str> {
+        let mut formats: Vec<&str> = self.loaders.keys().map(String::as_str).collect();
+        formats.sort_unstable();
+        formats
+    }
+}
+
+/// Built-in format of a path or URL, by its extension: `markdown`, `html`,
+/// `pdf`, `code`, or `text` for anything else.
+pub fn format_of(source: &str) -> &'static str {
+    let path = source.split(['?', '#']).next().unwrap_or(source);
+    let name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+    let extension = match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => ext.to_ascii_lowercase(),
+        _ => return "text",
+    };
+    match extension.as_str() {
+        "md" | "markdown" | "mdx" => "markdown",
+        "html" | "htm" | "xhtml" => "html",
+        "pdf" => "pdf",
+        _ if code::language_of(&extension).is_some() => "code",
+        _ => "text",
+    }
+}
+
+/// One document of an [`IngestRequest`]. Set exactly one of `content` and
+/// `content_base64`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentInput {
+    /// Path or URL the document came from; stored as the memories'
+    /// `source_id`.
+    pub source: String,
+    /// Loader to use. Defaults to [`format_of`] the source.
+    #[serde(default)]
+    pub format: Option<String>,
+    #[serde(default)]
+    pub content: Option<String>,
+    /// Base64 content, for binary formats such as PDF.
+    #[serde(default)]
+    pub content_base64: Option<String>,
+}
+
+impl DocumentInput {
+    /// A document read from `source`, as text when it is valid UTF-8 and
+    /// as base64 otherwise.
+    pub fn from_bytes(source: impl Into<String>, bytes: Vec<u8>) -> Self {
+        let (content, content_base64) = match String::from_utf8(bytes) {
+            Ok(text) => (Some(text), None),
+            Err(e) => (
+                None,
+                Some(base64::engine::general_purpose::STANDARD.encode(e.into_bytes())),
+            ),
+        };
+        Self {
+            source: source.into(),
+            format: None,
+            content,
+            content_base64,
+        }
+    }
+
+    fn bytes(&self, path: &str) -> Result<Vec<u8>> {
+        match (&self.content, &self.content_base64) {
+            (Some(content), None) => Ok(content.clone().into_bytes()),
+            (None, Some(encoded)) => base64::engine::general_purpose::STANDARD
+                .decode(encoded)
+                .map_err(|e| Error::invalid_field(format!("{path}.content_base64"), e.to_string())),
+            _ => Err(Error::invalid_field(
+                format!("{path}.content"),
+                "set exactly one of content and content_base64",
+            )),
+        }
+    }
+}
+
+/// Redirects [`fetch`] follows before giving up.
+const MAX_FETCH_REDIRECTS: usize = 5;
+
+/// Which hosts [`fetch`] may reach.
+///
+/// Ingestion stores what it downloads where the caller can read it back,
+/// so by default a fetch refuses any host that resolves to a loopback,
+/// private, link-local or otherwise non-public address (cloud metadata
+/// endpoints, the server's own admin ports, the internal network). Hosts
+/// listed in `allowed_hosts` skip that check.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UrlFetchPolicy {
+    /// Host names or IP literals, as written in the URL, that may resolve
+    /// to non-public addresses.
+    pub allowed_hosts: Vec<String>,
+}
+
+impl UrlFetchPolicy {
+    pub fn allow_host(mut self, host: impl Into<String>) -> Self {
+        self.allowed_hosts.push(host.into());
+        self
+    }
+
+    fn allows(&self, host: &str) -> bool {
+        self.allowed_hosts
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(host))
+    }
+
+    /// The addresses to connect to for `url`, refusing non-public ones
+    /// unless the host is allowed. `None` leaves resolution to the client.
+    async fn resolve(&self, url: &reqwest::Url) -> Result<Option<Vec<SocketAddr>>> {
+        let refuse = |reason: String| Err(Error::invalid_field("url", reason));
+        if !matches!(url.scheme(), "http" | "https") {
+            return refuse(format!("{url} is not an http(s) URL"));
+        }
+        let Some(host) = url.host_str() else {
+            return refuse(format!("{url} has no host"));
+        };
+        if self.allows(host) {
+            return Ok(None);
+        }
+        let port = url.port_or_known_default().unwrap_or(80);
+        let literal = host.trim_start_matches('[').trim_end_matches(']');
+        let addrs: Vec<SocketAddr> = match literal.parse::<IpAddr>() {
+            Ok(ip) => vec![SocketAddr::new(ip, port)],
+            Err(_) => tokio::net::lookup_host((host, port))
+                .await
+                .map_err(|e| Error::Unavailable(format!("resolve {host}: {e}")))?
+                .collect(),
+        };
+        if addrs.is_empty() {
+            return Err(Error::Unavailable(format!("resolve {host}: no addresses")));
+        }
+        if let Some(addr) = addrs.iter().find(|addr| !is_public(addr.ip())) {
+            return refuse(format!(
+                "{host} resolves to non-public address {}",
+                addr.ip()
+            ));
+        }
+        Ok(Some(addrs))
+    }
+}
+
+/// Whether `ip` is a globally routable unicast address.
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                // 0.0.0.0/8, carrier-grade NAT 100.64.0.0/10 and
+                // 240.0.0.0/4 (reserved).
+                || a == 0
+                || (a == 100 && (64..128).contains(&b))
+                || a >= 240)
+        }
+        IpAddr::V6(ip) => {
+            if let Some(v4) = ip.to_ipv4_mapped() {
+                return is_public(IpAddr::V4(v4));
+            }
+            let first = ip.segments()[0];
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_multicast()
+                // Unique local fc00::/7 and link-local fe80::/10.
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+/// Download `url` as a document. Its format comes from the response's
+/// `Content-Type`, or the URL's extension when that is not specific.
+///
+/// Every hop, redirects included, is checked against `policy` and then
+/// connected to at the addresses that passed the check. Responses larger
+/// than [`MAX_FETCH_BYTES`] are refused without reading past the limit.
+pub async fn fetch(url: &str, policy: &UrlFetchPolicy) -> Result<DocumentInput> {
+    let mut current =
+        reqwest::Url::parse(url).map_err(|e| Error::invalid_field("url", e.to_string()))?;
+    let mut redirects = 0;
+    let mut response = loop {
+        let mut builder = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .redirect(reqwest::redirect::Policy::none());
+        if let Some(addrs) = policy.resolve(&current).await?
+            && let Some(host) = current.host_str()
+        {
+            builder = builder.resolve_to_addrs(host, &addrs);
+        }
+        let client = builder
+            .build()
+            .map_err(|e| Error::Internal(e.to_string()))?;
+        let response = client
+            .get(current.clone())
+            .send()
+            .await
+            .map_err(|e| Error::Unavailable(format!("GET {current}: {e}")))?;
+        if !response.status().is_redirection() {
+            break response;
+        }
+        redirects += 1;
+        if redirects > MAX_FETCH_REDIRECTS {
+            return Err(Error::Unavailable(format!(
+                "GET {url}: more than {MAX_FETCH_REDIRECTS} redirects"
+            )));
+        }
+        let location = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| {
+                Error::Unavailable(format!(
+                    "GET {current} returned {} without a Location",
+                    response.status()
+                ))
+            })?;
+        current = current
+            .join(location)
+            .map_err(|e| Error::Unavailable(format!("GET {current}: bad redirect: {e}")))?;
+    };
+    if !response.status().is_success() {
+        return Err(Error::Unavailable(format!(
+            "GET {current} returned {}",
+            response.status()
+        )));
+    }
+    let too_large = || Error::Validation(format!("{url} is larger than {MAX_FETCH_BYTES} bytes"));
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_FETCH_BYTES as u64)
+    {
+        return Err(too_large());
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.split(';').next().unwrap_or(v).trim().to_ascii_lowercase());
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| Error::Unavailable(format!("GET {current}: {e}")))?
+    {
+        if bytes.len() + chunk.len() > MAX_FETCH_BYTES {
+            return Err(too_large());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    let mut document = DocumentInput::from_bytes(url, bytes);
+    document.format = match content_type.as_deref() {
+        Some("text/html" | "application/xhtml+xml") => Some("html".to_string()),
+        Some("text/markdown" | "text/x-markdown") => Some("markdown".to_string()),
+        Some("application/pdf") => Some("pdf".to_string()),
+        _ => None,
+    };
+    Ok(document)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestRequest {
+    pub documents: Vec<DocumentInput>,
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    /// Added to every memory, after [`DOCUMENT_TAG`].
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    /// Defaults to `semantic`.
+    #[serde(default)]
+    pub memory_type: Option<MemoryType>,
+    #[serde(default)]
+    pub scope: Option<Scope>,
+    #[serde(default)]
+    pub org_id: Option<String>,
+    #[serde(default)]
+    pub thread_id: Option<String>,
+    /// Defaults to [`DEFAULT_MAX_CHUNK_CHARS`].
+    #[serde(default)]
+    pub max_chunk_chars: Option<usize>,
+}
+
+impl IngestRequest {
+    pub fn new(documents: Vec<DocumentInput>) -> Self {
+        Self {
+            documents,
+            agent_id: None,
+            tags: None,
+            memory_type: None,
+            scope: None,
+            org_id: None,
+            thread_id: None,
+            max_chunk_chars: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IngestedDocument {
+    pub source: String,
+    pub format: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// One memory per chunk, in document order.
+    pub memory_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IngestResponse {
+    pub documents: Vec<IngestedDocument>,
+    /// Memories written across all documents.
+    pub memories: usize,
+}
+
+pub async fn execute(engine: &MnemoEngine, request: IngestRequest) -> Result<IngestResponse> {
+    if request.documents.is_empty() {
+        return Err(Error::invalid_field("documents", "cannot be empty"));
+    }
+    engine
+        .limits()
+        .check_batch("documents", request.documents.len())?;
+    let max_chars = max_chunk_chars(request.max_chunk_chars)?;
+
+    let mut loaded = Vec::with_capacity(request.documents.len());
+    for (i, document) in request.documents.iter().enumerate() {
+        let (format, doc) = load(engine, document, &format!("documents[{i}]"), max_chars)?;
+        loaded.push((document, format, doc));
+    }
+
+    let mut response = IngestResponse {
+        documents: Vec::with_capacity(loaded.len()),
+        memories: 0,
+    };
+    for (document, format, doc) in loaded {
+        let title = doc.title.clone();
+        let requests = chunk_requests(&request, &document.source, &format, doc);
+        let memory_ids = remember_all(engine, requests).await?;
+        response.memories += memory_ids.len();
+        response.documents.push(IngestedDocument {
+            source: document.source.clone(),
+            format,
+            title,
+            memory_ids,
+        });
+    }
+    Ok(response)
+}
+
+/// `max_chunk_chars` of a request, defaulted and checked.
+pub(crate) fn max_chunk_chars(requested: Option<usize>) -> Result<usize> {
+    let max_chars = requested.unwrap_or(DEFAULT_MAX_CHUNK_CHARS);
+    if max_chars == 0 || max_chars > MAX_CHUNK_CHARS {
+        return Err(Error::invalid_field(
+            "max_chunk_chars",
+            format!("must be between 1 and {MAX_CHUNK_CHARS}"),
+        ));
+    }
+    Ok(max_chars)
+}
+
+/// Check `document`, at request path `path`, and run its loader. Returns
+/// the format used and the loaded document.
+pub(crate) fn load(
+    engine: &MnemoEngine,
+    document: &DocumentInput,
+    path: &str,
+    max_chars: usize,
+) -> Result<(String, LoadedDocument)> {
+    if document.source.trim().is_empty() {
+        return Err(Error::invalid_field(
+            format!("{path}.source"),
+            "cannot be empty",
+        ));
+    }
+    let format = document
+        .format
+        .clone()
+        .unwrap_or_else(|| format_of(&document.source).to_string());
+    let Some(loader) = engine.document_loaders.get(&format) else {
+        return Err(Error::invalid_field(
+            format!("{path}.format"),
+            format!(
+                "unknown format {format:?}; expected one of: {}",
+                engine.document_loaders.formats().join(", ")
+            ),
+        ));
+    };
+    let bytes = document.bytes(path)?;
+    let doc = loader
+        .load(&document.source, &bytes, max_chars)
+        .map_err(|e| Error::invalid_field(format!("{path}.content"), e.to_string()))?;
+    Ok((format, doc))
+}
+
+/// One remember request per chunk of `doc`, in document order, with the
+/// request's settings and the chunk's provenance.
+pub(crate) fn chunk_requests(
+    request: &IngestRequest,
+    source: &str,
+    format: &str,
+    doc: LoadedDocument,
+) -> Vec<RememberRequest> {
+    let mut tags = vec![DOCUMENT_TAG.to_string()];
+    tags.extend(request.tags.iter().flatten().cloned());
+    let count = doc.chunks.len();
+    doc.chunks
+        .into_iter()
+        .enumerate()
+        .map(|(i, chunk)| {
+            let mut provenance = serde_json::json!({
+                "source": source,
+                "format": format,
+                "chunk": i,
+                "chunks": count,
+            });
+            if let Some(ref title) = doc.title {
+                provenance["title"] = title.clone().into();
+            }
+            if let Some(section) = chunk.section {
+                provenance["section"] = section.into();
+            }
+            if let Some(symbol) = chunk.symbol {
+                provenance["symbol"] = symbol.into();
+            }
+            if let Some((first, last)) = chunk.lines {
+                provenance["lines"] = serde_json::json!([first, last]);
+            }
+            if let Some(page) = chunk.page {
+                provenance["page"] = page.into();
+            }
+            let mut remember = RememberRequest::new(chunk.text);
+            remember.agent_id = request.agent_id.clone();
+            remember.memory_type = Some(request.memory_type.unwrap_or(MemoryType::Semantic));
+            remember.scope = request.scope;
+            remember.tags = Some(tags.clone());
+            remember.metadata = Some(serde_json::json!({ "document": provenance }));
+            remember.source_type = Some(SourceType::Import);
+            remember.source_id = Some(source.to_string());
+            remember.org_id = request.org_id.clone();
+            remember.thread_id = request.thread_id.clone();
+            remember
+        })
+        .collect()
+}
+
+/// Remember `requests` in batches of the engine's `max_batch_size`.
+pub(crate) async fn remember_all(
+    engine: &MnemoEngine,
+    requests: Vec<RememberRequest>,
+) -> Result<Vec<Uuid>> {
+    let batch_size = engine.limits().max_batch_size;
+    let mut memory_ids = Vec::with_capacity(requests.len());
+    let mut requests = requests.into_iter().peekable();
+    while requests.peek().is_some() {
+        let batch: Vec<_> = requests.by_ref().take(batch_size).collect();
+        let remembered = engine.remember_batch(batch).await?;
+        memory_ids.extend(remembered.into_iter().map(|r| r.id));
+    }
+    Ok(memory_ids)
+}
+
+/// Pack the blank-line separated paragraphs of `text` into chunks of at
+/// most `max_chars` characters. Longer paragraphs are split at whitespace,
+/// and words longer than `max_chars` are cut.
+pub fn pack_paragraphs(text: &str, max_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut current_chars = 0;
+    for paragraph in paragraphs(text) {
+        for piece in split_long(&paragraph, max_chars) {
+            let chars = piece.chars().count();
+            if current_chars > 0 && current_chars + 2 + chars > max_chars {
+                chunks.push(std::mem::take(&mut current));
+                current_chars = 0;
+            }
+            if current_chars > 0 {
+                current.push_str("\n\n");
+                current_chars += 2;
+            }
+            current.push_str(&piece);
+            current_chars += chars;
+        }
+    }
+    if current_chars > 0 {
+        chunks.push(current);
+    }
+    chunks
+}
+
+fn paragraphs(text: &str) -> Vec<String> {
+    let mut paragraphs = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(current.join("\n"));
+                current.clear();
+            }
+        } else {
+            current.push(line.trim_end());
+        }
+    }
+    if !current.is_empty() {
+        paragraphs.push(current.join("\n"));
+    }
+    paragraphs
+}
+
+fn split_long(paragraph: &str, max_chars: usize) -> Vec<String> {
+    if paragraph.chars().count() <= max_chars {
+        return vec![paragraph.to_string()];
+    }
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    let mut current_chars = 0;
+    for word in paragraph.split_whitespace() {
+        let mut word = word;
+        let mut chars = word.chars().count();
+        if current_chars > 0 && current_chars + 1 + chars > max_chars {
+            pieces.push(std::mem::take(&mut current));
+            current_chars = 0;
+        }
+        while chars > max_chars {
+            let cut = word
+                .char_indices()
+                .nth(max_chars)
+                .map_or(word.len(), |(i, _)| i);
+            pieces.push(word[..cut].to_string());
+            word = &word[cut..];
+            chars -= max_chars;
+        }
+        if current_chars > 0 {
+            current.push(' ');
+            current_chars += 1;
+        }
+        current.push_str(word);
+        current_chars += chars;
+    }
+    if current_chars > 0 {
+        pieces.push(current);
+    }
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packs_paragraphs_up_to_the_limit() {
+        let text = "first paragraph\n\nsecond one\n\n\nthird paragraph here";
+        assert_eq!(
+            pack_paragraphs(text, 30),
+            vec!["first paragraph\n\nsecond one", "third paragraph here"]
+        );
+        let long = "word ".repeat(20);
+        let chunks = pack_paragraphs(&long, 12);
+        assert!(chunks.iter().all(|c| c.chars().count() <= 12));
+        assert_eq!(chunks.join(" ").split_whitespace().count(), 20);
+        assert_eq!(pack_paragraphs(&"x".repeat(25), 10).len(), 3);
+    }
+
+    #[test]
+    fn only_public_addresses_pass() {
+        for ip in ["93.184.216.34", "2606:2800:220:1::1"] {
+            assert!(is_public(ip.parse().unwrap()), "{ip}");
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public(ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[tokio::test]
+    async fn fetch_refuses_private_hosts_unless_allowed() {
+        let policy = UrlFetchPolicy::default();
+        for url in [
+            "http://127.0.0.1:1/",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://[::1]:1/",
+            "http://localhost:1/",
+            "file:///etc/passwd",
+        ] {
+            let err = fetch(url, &policy).await.unwrap_err();
+            assert!(matches!(err, Error::InvalidFields(_)), "{url}: {err}");
+        }
+        // Allowed hosts get as far as connecting.
+        let policy = policy.allow_host("127.0.0.1");
+        let err = fetch("http://127.0.0.1:1/", &policy).await.unwrap_err();
+        assert!(matches!(err, Error::Unavailable(_)), "{err}");
+    }
+
+    #[test]
+    fn infers_formats_from_extensions() {
+        assert_eq!(format_of("docs/README.md"), "markdown");
+        assert_eq!(format_of("https://example.com/guide.html?x=1#top"), "html");
+        assert_eq!(format_of("paper.PDF"), "pdf");
+        assert_eq!(format_of("src/lib.rs"), "code");
+        assert_eq!(format_of("notes.txt"), "text");
+        assert_eq!(format_of(".env"), "text");
+    }
+}
@@ -0,0 +1,40 @@
+//! PDF loader: the extracted text of each page, chunked per page.
+//!
+//! Text extraction needs the `pdf` feature; without it the loader is still
+//! registered and rejects every document.
+
+use super::{DocumentLoader, LoadedDocument};
+use crate::error::{Error, Result};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PdfLoader;
+
+impl DocumentLoader for PdfLoader {
+    #[cfg(feature = "pdf")]
+    fn load(&self, _source: &str, bytes: &[u8], max_chars: usize) -> Result<LoadedDocument> {
+        use super::{Chunk, pack_paragraphs};
+
+        // pdf-extract panics on some malformed files.
+        let pages = std::panic::catch_unwind(|| pdf_extract::extract_text_from_mem_by_pages(bytes))
+            .map_err(|_| Error::Validation("cannot extract text from this PDF".to_string()))?
+            .map_err(|e| Error::Validation(format!("cannot extract text from this PDF: {e}")))?;
+        let mut document = LoadedDocument::default();
+        for (i, page) in pages.iter().enumerate() {
+            for text in pack_paragraphs(page, max_chars) {
+                document.chunks.push(Chunk {
+                    text,
+                    page: Some(i + 1),
+                    ..Default::default()
+                });
+            }
+        }
+        Ok(document)
+    }
+
+    #[cfg(not(feature = "pdf"))]
+    fn load(&self, _source: &str, _bytes: &[u8], _max_chars: usize) -> Result<LoadedDocument> {
+        Err(Error::Validation(
+            "PDF ingestion needs a build with the `pdf` feature".to_string(),
+        ))
+    }
+}
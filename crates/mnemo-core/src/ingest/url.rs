@@ -0,0 +1,400 @@
+//! Web page ingestion with scheduled refresh.
+//!
+//! [`execute`] fetches a URL, loads it like any other document (for HTML,
+//! only the page's main content) and remembers its chunks with
+//! `source_id` set to the URL. Every ingested page is recorded, with the
+//! hash and memory of each chunk, in a registry kept as a sync watermark.
+//! Ingesting a URL again diffs against that record instead of duplicating
+//! it: chunks whose text is unchanged keep their memories, chunks that
+//! disappeared or changed are invalidated by closing their validity
+//! window at the fetch time, and new text is remembered with a window
+//! starting then. Recall with `as_of` therefore still sees the page as it
+//! was.
+//!
+//! A page ingested with `refresh_interval_seconds` is re-fetched by
+//! [`run_url_refresh`], called by the server's maintenance loop, once that
+//! interval has passed since its last fetch. The latest ingest of a URL
+//! sets its options and schedule.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use super::{DocumentInput, IngestRequest};
+use crate::error::{Error, Result};
+use crate::model::memory::{MemoryType, Scope};
+use crate::query::MnemoEngine;
+use crate::query::validity::SetValidityRequest;
+
+/// Shortest refresh interval a page may ask for.
+pub const MIN_REFRESH_INTERVAL_SECONDS: u64 = 60;
+
+/// Sync watermark holding the page registry.
+const REGISTRY_KEY: &str = "ingest:urls";
+
+/// Serializes registry updates within the process.
+static REGISTRY_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UrlIngestRequest {
+    pub url: String,
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    /// Added to every memory, after [`DOCUMENT_TAG`](super::DOCUMENT_TAG).
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    /// Defaults to `semantic`.
+    #[serde(default)]
+    pub memory_type: Option<MemoryType>,
+    #[serde(default)]
+    pub scope: Option<Scope>,
+    #[serde(default)]
+    pub org_id: Option<String>,
+    #[serde(default)]
+    pub thread_id: Option<String>,
+    /// Loader to use. Defaults to the response's `Content-Type`, then the
+    /// URL's extension.
+    #[serde(default)]
+    pub format: Option<String>,
+    #[serde(default)]
+    pub max_chunk_chars: Option<usize>,
+    /// Re-fetch the page this often; `None` fetches it once.
+    #[serde(default)]
+    pub refresh_interval_seconds: Option<u64>,
+}
+
+impl UrlIngestRequest {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            agent_id: None,
+            tags: None,
+            memory_type: None,
+            scope: None,
+            org_id: None,
+            thread_id: None,
+            format: None,
+            max_chunk_chars: None,
+            refresh_interval_seconds: None,
+        }
+    }
+
+    fn ingest_request(&self) -> IngestRequest {
+        IngestRequest {
+            documents: Vec::new(),
+            agent_id: self.agent_id.clone(),
+            tags: self.tags.clone(),
+            memory_type: self.memory_type,
+            scope: self.scope,
+            org_id: self.org_id.clone(),
+            thread_id: self.thread_id.clone(),
+            max_chunk_chars: self.max_chunk_chars,
+        }
+    }
+}
+
+/// A chunk of a page as last fetched.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageChunk {
+    /// Hex SHA-256 of the chunk's text.
+    pub hash: String,
+    pub memory_id: Uuid,
+}
+
+/// Registry entry of an ingested page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchedPage {
+    /// The latest ingest request for the page.
+    pub request: UrlIngestRequest,
+    pub fetched_at: String,
+    /// Chunks of the last fetch, in page order.
+    pub chunks: Vec<PageChunk>,
+}
+
+impl WatchedPage {
+    /// When the page is next due for a refresh, if it has a schedule.
+    pub fn next_refresh_at(&self) -> Option<DateTime<Utc>> {
+        let interval = self.request.refresh_interval_seconds?;
+        let fetched_at = DateTime::parse_from_rfc3339(&self.fetched_at).ok()?;
+        Some(fetched_at.with_timezone(&Utc) + chrono::Duration::seconds(interval as i64))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PageStatus {
+    /// First ingest of the URL.
+    Created,
+    /// Same chunks as the last fetch.
+    Unchanged,
+    /// Some chunks were added or invalidated.
+    Updated,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UrlIngestResponse {
+    pub url: String,
+    pub status: PageStatus,
+    pub format: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Current memory of each chunk, in page order.
+    pub memory_ids: Vec<Uuid>,
+    /// Memories written by this fetch.
+    pub added: Vec<Uuid>,
+    /// Memories of the previous fetch whose text is gone.
+    pub invalidated: Vec<Uuid>,
+    pub fetched_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_refresh_at: Option<String>,
+}
+
+/// Outcome of one [`run_url_refresh`] pass.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UrlRefreshReport {
+    pub refreshed: Vec<UrlIngestResponse>,
+    /// `url: error` of each due page that could not be refreshed; it is
+    /// retried on the next pass.
+    pub errors: Vec<String>,
+}
+
+pub async fn execute(engine: &MnemoEngine, request: UrlIngestRequest) -> Result<UrlIngestResponse> {
+    validate(&request)?;
+    let mut document = super::fetch(&request.url, &engine.url_fetch).await?;
+    if request.format.is_some() {
+        document.format = request.format.clone();
+    }
+    ingest_page(engine, request, document, Utc::now()).await
+}
+
+/// Re-fetch every page whose refresh interval has passed at `now`.
+pub async fn run_url_refresh(engine: &MnemoEngine, now: DateTime<Utc>) -> Result<UrlRefreshReport> {
+    let mut report = UrlRefreshReport::default();
+    for page in load_registry(engine).await?.into_values() {
+        if page.next_refresh_at().is_none_or(|due| due > now) {
+            continue;
+        }
+        let url = page.request.url.clone();
+        match execute(engine, page.request).await {
+            Ok(response) => report.refreshed.push(response),
+            Err(e) => report.errors.push(format!("{url}: {e}")),
+        }
+    }
+    Ok(report)
+}
+
+/// Every ingested page, by URL.
+pub async fn watched_pages(engine: &MnemoEngine) -> Result<Vec<WatchedPage>> {
+    Ok(load_registry(engine).await?.into_values().collect())
+}
+
+fn validate(request: &UrlIngestRequest) -> Result<()> {
+    let parsed = reqwest::Url::parse(&request.url)
+        .map_err(|e| Error::invalid_field("url", e.to_string()))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(Error::invalid_field("url", "must be an http or https URL"));
+    }
+    if let Some(interval) = request.refresh_interval_seconds
+        && interval < MIN_REFRESH_INTERVAL_SECONDS
+    {
+        return Err(Error::invalid_field(
+            "refresh_interval_seconds",
+            format!("must be at least {MIN_REFRESH_INTERVAL_SECONDS}"),
+        ));
+    }
+    super::max_chunk_chars(request.max_chunk_chars)?;
+    Ok(())
+}
+
+/// Load `document`, fetched from the request's URL at `fetched_at`, and
+/// bring the page's memories in line with it.
+async fn ingest_page(
+    engine: &MnemoEngine,
+    request: UrlIngestRequest,
+    document: DocumentInput,
+    fetched_at: DateTime<Utc>,
+) -> Result<UrlIngestResponse> {
+    let max_chars = super::max_chunk_chars(request.max_chunk_chars)?;
+    let (format, doc) = super::load(engine, &document, "url", max_chars)?;
+    let title = doc.title.clone();
+    let hashes: Vec<String> = doc.chunks.iter().map(|c| chunk_hash(&c.text)).collect();
+    let fetched = fetched_at.to_rfc3339();
+
+    let _guard = REGISTRY_LOCK.lock().await;
+    let mut registry = load_registry(engine).await?;
+    let previous = registry.remove(&request.url);
+    let status = match previous {
+        None => PageStatus::Created,
+        Some(ref page) if page.chunks.iter().map(|c| &c.hash).eq(hashes.iter()) => {
+            PageStatus::Unchanged
+        }
+        Some(_) => PageStatus::Updated,
+    };
+
+    let (mut memory_ids, invalidated) = diff(
+        previous
+            .as_ref()
+            .map(|p| p.chunks.as_slice())
+            .unwrap_or_default(),
+        &hashes,
+    );
+
+    let ingest = request.ingest_request();
+    let new_requests: Vec<_> = super::chunk_requests(&ingest, &request.url, &format, doc)
+        .into_iter()
+        .zip(&memory_ids)
+        .filter(|(_, id)| id.is_none())
+        .map(|(mut remember, _)| {
+            if let Some(provenance) = remember
+                .metadata
+                .as_mut()
+                .and_then(|m| m.get_mut("document"))
+            {
+                provenance["fetched_at"] = fetched.clone().into();
+            }
+            if previous.is_some() {
+                remember.valid_from = Some(fetched.clone());
+            }
+            remember
+        })
+        .collect();
+    let added = super::remember_all(engine, new_requests).await?;
+    let mut new_ids = added.iter().copied();
+    for slot in memory_ids.iter_mut().filter(|id| id.is_none()) {
+        *slot = new_ids.next();
+    }
+    let memory_ids: Vec<Uuid> = memory_ids.into_iter().flatten().collect();
+
+    for &memory_id in &invalidated {
+        invalidate(engine, &request, memory_id, &fetched).await?;
+    }
+
+    let page = WatchedPage {
+        request,
+        fetched_at: fetched,
+        chunks: hashes
+            .into_iter()
+            .zip(&memory_ids)
+            .map(|(hash, &memory_id)| PageChunk { hash, memory_id })
+            .collect(),
+    };
+    let response = UrlIngestResponse {
+        url: page.request.url.clone(),
+        status,
+        format,
+        title,
+        memory_ids,
+        added,
+        invalidated,
+        fetched_at: page.fetched_at.clone(),
+        next_refresh_at: page.next_refresh_at().map(|at| at.to_rfc3339()),
+    };
+    registry.insert(page.request.url.clone(), page);
+    save_registry(engine, &registry).await?;
+    Ok(response)
+}
+
+/// Match the chunk hashes of a new fetch against the previous chunks. Each
+/// new chunk whose text was already on the page reuses that memory, in
+/// page order; the memories left over are returned second.
+fn diff(previous: &[PageChunk], hashes: &[String]) -> (Vec<Option<Uuid>>, Vec<Uuid>) {
+    let mut reusable: BTreeMap<&str, Vec<Uuid>> = BTreeMap::new();
+    for chunk in previous.iter().rev() {
+        reusable
+            .entry(chunk.hash.as_str())
+            .or_default()
+            .push(chunk.memory_id);
+    }
+    let memory_ids = hashes
+        .iter()
+        .map(|hash| reusable.get_mut(hash.as_str()).and_then(Vec::pop))
+        .collect();
+    let left_over = previous
+        .iter()
+        .filter(|c| {
+            reusable
+                .get(c.hash.as_str())
+                .is_some_and(|ids| ids.contains(&c.memory_id))
+        })
+        .map(|c| c.memory_id)
+        .collect();
+    (memory_ids, left_over)
+}
+
+/// Close the validity window of a chunk memory that left the page. A
+/// memory deleted in the meantime needs nothing.
+async fn invalidate(
+    engine: &MnemoEngine,
+    request: &UrlIngestRequest,
+    memory_id: Uuid,
+    at: &str,
+) -> Result<()> {
+    let Some(record) = engine.storage.get_memory(memory_id).await? else {
+        return Ok(());
+    };
+    if record.is_deleted() {
+        return Ok(());
+    }
+    let mut validity = SetValidityRequest::new(memory_id);
+    validity.agent_id = request.agent_id.clone();
+    validity.valid_from = record.valid_from;
+    validity.valid_until = Some(at.to_string());
+    engine.set_validity(validity).await?;
+    Ok(())
+}
+
+fn chunk_hash(text: &str) -> String {
+    hex::encode(Sha256::digest(text.as_bytes()))
+}
+
+async fn load_registry(engine: &MnemoEngine) -> Result<BTreeMap<String, WatchedPage>> {
+    match engine.storage.get_sync_watermark(REGISTRY_KEY).await? {
+        Some(json) => serde_json::from_str(&json)
+            .map_err(|e| Error::Internal(format!("corrupt page registry: {e}"))),
+        None => Ok(BTreeMap::new()),
+    }
+}
+
+async fn save_registry(
+    engine: &MnemoEngine,
+    registry: &BTreeMap<String, WatchedPage>,
+) -> Result<()> {
+    let json = serde_json::to_string(registry)?;
+    engine.storage.set_sync_watermark(REGISTRY_KEY, &json).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_bad_urls_and_intervals() {
+        assert!(validate(&UrlIngestRequest::new("https://example.com/docs")).is_ok());
+        let err = validate(&UrlIngestRequest::new("file:///etc/passwd")).unwrap_err();
+        assert_eq!(err.field_violations()[0].path, "url");
+        let mut request = UrlIngestRequest::new("https://example.com/docs");
+        request.refresh_interval_seconds = Some(5);
+        let err = validate(&request).unwrap_err();
+        assert_eq!(err.field_violations()[0].path, "refresh_interval_seconds");
+    }
+
+    #[test]
+    fn diff_reuses_unchanged_chunks_in_order() {
+        let ids: Vec<Uuid> = (0..4).map(|_| Uuid::now_v7()).collect();
+        let previous: Vec<PageChunk> = ["a", "b", "a", "c"]
+            .iter()
+            .zip(&ids)
+            .map(|(text, &memory_id)| PageChunk {
+                hash: chunk_hash(text),
+                memory_id,
+            })
+            .collect();
+        let hashes: Vec<String> = ["a", "d", "c"].iter().map(|t| chunk_hash(t)).collect();
+        let (memory_ids, left_over) = diff(&previous, &hashes);
+        assert_eq!(memory_ids, vec![Some(ids[0]), None, Some(ids[3])]);
+        assert_eq!(left_over, vec![ids[1], ids[2]]);
+    }
+}
@@ -1,18 +1,31 @@
+pub mod activity;
 pub mod anomaly;
 pub mod auth;
 pub mod budget;
 pub mod cache;
+pub mod config;
+pub mod cron;
+pub mod embedded;
 pub mod embedding;
 pub mod encryption;
 pub mod error;
 pub mod eval;
+pub mod expansion;
 pub mod hash;
+pub mod hooks;
 pub mod index;
+pub mod ingest;
 pub mod model;
+pub mod nli;
+pub mod parquet;
 pub mod provenance;
 pub mod query;
+pub mod request_id;
 pub mod retrieval;
 pub mod score;
 pub mod search;
 pub mod storage;
+pub mod summarize;
 pub mod sync;
+
+pub use embedded::{Mnemo, MnemoBuilder};
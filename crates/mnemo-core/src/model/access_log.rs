@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Which read path touched a memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessOperation {
+    /// Returned by `recall`.
+    Recall,
+    /// Fetched directly by id.
+    Get,
+}
+
+impl std::fmt::Display for AccessOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccessOperation::Recall => write!(f, "recall"),
+            AccessOperation::Get => write!(f, "get"),
+        }
+    }
+}
+
+impl std::str::FromStr for AccessOperation {
+    type Err = crate::error::Error;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "recall" => Ok(AccessOperation::Recall),
+            "get" => Ok(AccessOperation::Get),
+            _ => Err(crate::error::Error::Validation(format!(
+                "invalid access operation: {s}"
+            ))),
+        }
+    }
+}
+
+/// One read of one memory: who read it, how, when, and with what query.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MemoryAccess {
+    pub id: Uuid,
+    pub memory_id: Uuid,
+    /// Agent that performed the read.
+    pub principal: String,
+    pub operation: AccessOperation,
+    /// Recall query text, truncated. `None` for direct reads or when the
+    /// access-log policy does not record queries.
+    pub query: Option<String>,
+    pub accessed_at: String,
+}
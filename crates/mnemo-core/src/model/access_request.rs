@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::model::acl::Permission;
+
+/// Lifecycle of a memory access request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessRequestStatus {
+    Pending,
+    Approved,
+    Denied,
+}
+
+impl std::fmt::Display for AccessRequestStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccessRequestStatus::Pending => write!(f, "pending"),
+            AccessRequestStatus::Approved => write!(f, "approved"),
+            AccessRequestStatus::Denied => write!(f, "denied"),
+        }
+    }
+}
+
+impl std::str::FromStr for AccessRequestStatus {
+    type Err = crate::error::Error;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(AccessRequestStatus::Pending),
+            "approved" => Ok(AccessRequestStatus::Approved),
+            "denied" => Ok(AccessRequestStatus::Denied),
+            _ => Err(crate::error::Error::Validation(format!(
+                "invalid access request status: {s}"
+            ))),
+        }
+    }
+}
+
+/// One agent asking another for access to memories it has not shared.
+///
+/// The memories are named either by id (`memory_ids`) or by tags (`tags`:
+/// every memory of the owner carrying all of them, resolved at approval).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AccessRequest {
+    pub id: Uuid,
+    /// The agent asking for access.
+    pub requester_id: String,
+    /// The agent whose memories are asked for.
+    pub owner_id: String,
+    pub memory_ids: Vec<Uuid>,
+    pub tags: Vec<String>,
+    /// Why the requester needs the memories, shown to the owner.
+    pub justification: String,
+    pub permission: Permission,
+    /// Lifetime of the grants an approval creates; `None` never expires.
+    pub expires_in_hours: Option<f64>,
+    pub status: AccessRequestStatus,
+    /// Who made the call (a user id, an operator name, ...).
+    pub decided_by: Option<String>,
+    pub decision_note: Option<String>,
+    /// The grants an approval created.
+    pub acl_ids: Vec<Uuid>,
+    pub created_at: String,
+    pub decided_at: Option<String>,
+}
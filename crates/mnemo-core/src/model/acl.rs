@@ -22,6 +22,10 @@ pub enum Permission {
     Share,
     Delegate,
     Admin,
+    /// Copy memories out of the engine, e.g. in a scheduled export.
+    Export,
+    /// Replay a whole thread or trace an agent's causal event chains.
+    Replay,
 }
 
 impl std::fmt::Display for Permission {
@@ -33,6 +37,8 @@ impl std::fmt::Display for Permission {
             Permission::Share => write!(f, "share"),
             Permission::Delegate => write!(f, "delegate"),
             Permission::Admin => write!(f, "admin"),
+            Permission::Export => write!(f, "export"),
+            Permission::Replay => write!(f, "replay"),
         }
     }
 }
@@ -47,6 +53,8 @@ impl std::str::FromStr for Permission {
             "share" => Ok(Permission::Share),
             "delegate" => Ok(Permission::Delegate),
             "admin" => Ok(Permission::Admin),
+            "export" => Ok(Permission::Export),
+            "replay" => Ok(Permission::Replay),
             _ => Err(crate::error::Error::Validation(format!(
                 "invalid permission: {s}"
             ))),
@@ -55,19 +63,32 @@ impl std::str::FromStr for Permission {
 }
 
 impl Permission {
+    /// Whether holding `self` grants `required`.
+    ///
+    /// Write, Delete, Share and Delegate form a chain, each granting the
+    /// ones before it. Export and Replay stand apart: they grant Read but
+    /// nothing else, and only themselves and Admin grant them. Every
+    /// permission grants Read; Admin grants everything.
     pub fn satisfies(&self, required: Permission) -> bool {
         // Hierarchy: Admin > Delegate > Share > Delete > Write > Read
-        let level = |p: &Permission| -> u8 {
+        let level = |p: &Permission| -> Option<u8> {
             match p {
-                Permission::Read => 0,
-                Permission::Write => 1,
-                Permission::Delete => 2,
-                Permission::Share => 3,
-                Permission::Delegate => 4,
-                Permission::Admin => 5,
+                Permission::Read => Some(0),
+                Permission::Write => Some(1),
+                Permission::Delete => Some(2),
+                Permission::Share => Some(3),
+                Permission::Delegate => Some(4),
+                Permission::Admin => Some(5),
+                Permission::Export | Permission::Replay => None,
             }
         };
-        level(self) >= level(&required)
+        if *self == required || *self == Permission::Admin || required == Permission::Read {
+            return true;
+        }
+        match (level(self), level(&required)) {
+            (Some(held), Some(needed)) => held >= needed,
+            _ => false,
+        }
     }
 }
 
@@ -151,5 +172,24 @@ mod tests {
         assert!(Permission::Delegate.satisfies(Permission::Share));
         assert!(Permission::Delegate.satisfies(Permission::Delete));
         assert!(!Permission::Delegate.satisfies(Permission::Admin));
+        // Export and Replay grant Read only, and only Admin grants them
+        assert!(Permission::Admin.satisfies(Permission::Export));
+        assert!(Permission::Admin.satisfies(Permission::Replay));
+        assert!(Permission::Export.satisfies(Permission::Read));
+        assert!(Permission::Replay.satisfies(Permission::Read));
+        assert!(!Permission::Export.satisfies(Permission::Write));
+        assert!(!Permission::Export.satisfies(Permission::Replay));
+        assert!(!Permission::Replay.satisfies(Permission::Export));
+        assert!(!Permission::Read.satisfies(Permission::Replay));
+        assert!(!Permission::Delegate.satisfies(Permission::Export));
+        assert!(!Permission::Delegate.satisfies(Permission::Replay));
+    }
+
+    #[test]
+    fn test_permission_string_roundtrip() {
+        for permission in [Permission::Export, Permission::Replay] {
+            let parsed: Permission = permission.to_string().parse().unwrap();
+            assert_eq!(parsed, permission);
+        }
     }
 }
@@ -7,4 +7,34 @@ pub struct AgentProfile {
     pub avg_content_length: f64,
     pub total_memories: u64,
     pub last_updated: String,
+    /// Per-agent recall defaults, applied when a request leaves the
+    /// matching field unset.
+    #[serde(default)]
+    pub retrieval: RetrievalDefaults,
+}
+
+/// Default retrieval settings for one agent.
+///
+/// Recall resolves each knob as: explicit request field, then the named
+/// recall profile (if any), then these defaults, then the engine built-ins.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct RetrievalDefaults {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recency_half_life_hours: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hybrid_weights: Option<Vec<f32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strategy: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// Restrict recall to the request's thread plus global memories.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_isolation: Option<bool>,
+}
+
+impl RetrievalDefaults {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
 }
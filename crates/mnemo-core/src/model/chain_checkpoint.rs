@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// How far a memory hash chain has been verified.
+///
+/// One marker per (agent, thread) chain; `thread_id` is `None` for the
+/// agent's whole chain. Incremental verification resumes after
+/// `last_record_id` and checks the next record against
+/// `last_content_hash`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChainCheckpoint {
+    pub agent_id: String,
+    pub thread_id: Option<String>,
+    /// Records verified so far, across all runs.
+    pub verified_count: u64,
+    pub last_record_id: Uuid,
+    /// `created_at` of the last verified record; the pagination cursor
+    /// together with `last_record_id`.
+    pub last_created_at: String,
+    /// Hex content hash of the last verified record.
+    pub last_content_hash: String,
+    /// Hex rolling hash over every verified record's content hash. See
+    /// [`crate::hash::roll_chain_root`].
+    pub rolling_root: String,
+    pub verified_at: String,
+}
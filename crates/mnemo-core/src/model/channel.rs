@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// A named memory space. Memories remembered into a channel are readable
+/// by every member without per-memory ACLs.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Channel {
+    pub name: String,
+    pub created_by: String,
+    /// Most live (not deleted) memories the channel may hold. `None` is
+    /// unlimited.
+    pub max_memories: Option<u64>,
+    pub created_at: String,
+}
+
+/// An agent's membership in a [`Channel`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChannelMember {
+    pub channel: String,
+    pub agent_id: String,
+    pub joined_at: String,
+}
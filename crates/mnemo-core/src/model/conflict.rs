@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Lifecycle of a queued conflict.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictStatus {
+    Open,
+    Resolved,
+    Dismissed,
+}
+
+impl std::fmt::Display for ConflictStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConflictStatus::Open => write!(f, "open"),
+            ConflictStatus::Resolved => write!(f, "resolved"),
+            ConflictStatus::Dismissed => write!(f, "dismissed"),
+        }
+    }
+}
+
+impl std::str::FromStr for ConflictStatus {
+    type Err = crate::error::Error;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "open" => Ok(ConflictStatus::Open),
+            "resolved" => Ok(ConflictStatus::Resolved),
+            "dismissed" => Ok(ConflictStatus::Dismissed),
+            _ => Err(crate::error::Error::Validation(format!(
+                "invalid conflict status: {s}"
+            ))),
+        }
+    }
+}
+
+/// A detected conflict awaiting (or having received) a human decision.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConflictRecord {
+    pub id: Uuid,
+    pub agent_id: String,
+    pub memory_a: Uuid,
+    pub memory_b: Uuid,
+    pub similarity: f32,
+    pub reason: String,
+    pub status: ConflictStatus,
+    /// How the conflict was closed: a `ResolutionStrategy` name,
+    /// `"manual_winner"` or `"dismissed"`.
+    pub resolution: Option<String>,
+    /// The memory kept when a manual winner was chosen.
+    pub winner_id: Option<Uuid>,
+    pub resolved_by: Option<String>,
+    pub created_at: String,
+    pub resolved_at: Option<String>,
+}
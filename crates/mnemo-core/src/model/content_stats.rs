@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+/// How much space one agent's memory content takes in storage, and how
+/// much compression saves. Counts every stored memory, soft-deleted ones
+/// included, since they occupy space until they are purged.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContentStorageStats {
+    pub agent_id: String,
+    pub memories: u64,
+    /// Memories whose content is stored compressed.
+    pub compressed: u64,
+    /// Size of the content as written, in bytes.
+    pub content_bytes: u64,
+    /// Size of the content as stored, in bytes.
+    pub stored_bytes: u64,
+    /// `content_bytes - stored_bytes`.
+    pub saved_bytes: u64,
+}
+
+/// What one UTC day added to storage, by the date of each memory's
+/// `created_at` and each event's `timestamp`. Soft-deleted memories are
+/// counted; purged memories and archived events are not.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DailyGrowth {
+    /// `YYYY-MM-DD`.
+    pub day: String,
+    pub memories: u64,
+    /// Size of the memories' content as stored, in bytes.
+    pub memory_bytes: u64,
+    pub events: u64,
+    /// Size of the events' JSON payloads, in bytes.
+    pub event_bytes: u64,
+}
@@ -26,10 +26,15 @@ pub struct AgentEvent {
     pub prev_hash: Option<Vec<u8>>,
     // Optional embedding of the event payload
     pub embedding: Option<Vec<f32>>,
+    /// Correlation ID of the request that recorded the event (see
+    /// [`crate::request_id`]).
+    #[serde(default)]
+    pub request_id: Option<String>,
 }
 
 impl AgentEvent {
-    /// Create a new `AgentEvent` with required fields; all optional fields default to `None`.
+    /// Create a new `AgentEvent` with required fields; all optional fields default to `None`,
+    /// except `request_id`, which is taken from the current request.
     pub fn new(
         agent_id: String,
         event_type: EventType,
@@ -57,6 +62,7 @@ impl AgentEvent {
             content_hash,
             prev_hash: None,
             embedding: None,
+            request_id: crate::request_id::current(),
         }
     }
 
@@ -83,6 +89,7 @@ impl AgentEvent {
         content_hash: Vec<u8>,
         prev_hash: Option<Vec<u8>>,
         embedding: Option<Vec<f32>>,
+        request_id: Option<String>,
     ) -> Self {
         Self {
             id,
@@ -104,6 +111,7 @@ impl AgentEvent {
             content_hash,
             prev_hash,
             embedding,
+            request_id,
         }
     }
 }
@@ -154,6 +162,60 @@ pub enum EventType {
     /// view), and the topic name. Provenance/hash-chain history is never
     /// dropped.
     MemoryRevised,
+    /// A memory's `expires_at` falls inside the engine's expiry-warning
+    /// window. Emitted once per `expires_at` value by the TTL sweep so
+    /// agents / webhooks can renew it via `extend_ttl`. Payload carries
+    /// `memory_id` and `expires_at`.
+    MemoryExpiring,
+    /// A reviewer approved or rejected a memory held for attestation.
+    /// Payload carries `memory_id`, `status`, `reviewed_by`, the matching
+    /// `tags` and the reviewer's `note`.
+    MemoryReviewed,
+    /// The ingestion filter refused a write. Payload carries the `rule`
+    /// that fired, its `detail`, `content_bytes` and, without at-rest
+    /// encryption, a `content_preview`.
+    IngestionRejected,
+    /// A scheduled export ran. Payload is the
+    /// [`ExportRun`](crate::query::export::ExportRun): schedule, status,
+    /// location, memory and byte counts, pruned objects and any error.
+    ExportRun,
+    /// A hard delete or erasure was refused because the memory is under a
+    /// retention lock. Payload carries `memory_id`, `operation` and
+    /// `locked_until`.
+    RetentionLockViolation,
+    /// Another agent asked for access to this agent's memories. Recorded
+    /// on the owner. Payload carries `request_id`, `requester_id`, the
+    /// requested `memory_ids` / `tags`, `permission` and `justification`.
+    AccessRequested,
+    /// The owner approved or denied this agent's access request. Recorded
+    /// on the requester. Payload carries `request_id`, `owner_id`,
+    /// `status`, `decided_by`, `note` and the `acl_ids` an approval created.
+    AccessRequestDecided,
+    /// A `system` message posted to a thread's message store. Payload
+    /// carries `role`, `content` and `metadata`, like the other message
+    /// events written there.
+    SystemMessage,
+    /// A thread message was deleted. The event log is append-only, so the
+    /// message's event stays; payload carries its `message_id`.
+    MessageDeleted,
+    /// A rechain pass recomputed the content hashes and `prev_hash` links
+    /// of a memory chain. Payload carries `thread_id`, `records`,
+    /// `relinked` and the chain's hex `before_root` and `after_root`.
+    ChainRebuilt,
+    /// An agent minted a share link. Payload carries `link_id`,
+    /// `memory_id` and `expires_at`, never the token.
+    ShareLinkCreated,
+    /// A share link was put on the denylist. Payload carries `link_id`
+    /// and `memory_id`.
+    ShareLinkRevoked,
+    /// An agent's recent writes departed from its baseline. Payload
+    /// carries `window_writes`, the `anomalies` found and, when the agent
+    /// was throttled, `throttled_until`.
+    WriteAnomaly,
+    /// Memories of one agent were handed to another. Payload carries
+    /// `from_agent`, `to_agent`, `mode`, `filter`, `memory_ids` and
+    /// `transferred_by`.
+    OwnershipTransferred,
 }
 
 impl std::fmt::Display for EventType {
@@ -181,6 +243,20 @@ impl std::fmt::Display for EventType {
             EventType::McpToolCatalogDrift => write!(f, "mcp_tool_catalog_drift"),
             EventType::MemoryConsolidated => write!(f, "memory_consolidated"),
             EventType::MemoryRevised => write!(f, "memory_revised"),
+            EventType::MemoryExpiring => write!(f, "memory_expiring"),
+            EventType::MemoryReviewed => write!(f, "memory_reviewed"),
+            EventType::IngestionRejected => write!(f, "ingestion_rejected"),
+            EventType::ExportRun => write!(f, "export_run"),
+            EventType::RetentionLockViolation => write!(f, "retention_lock_violation"),
+            EventType::AccessRequested => write!(f, "access_requested"),
+            EventType::AccessRequestDecided => write!(f, "access_request_decided"),
+            EventType::SystemMessage => write!(f, "system_message"),
+            EventType::MessageDeleted => write!(f, "message_deleted"),
+            EventType::ChainRebuilt => write!(f, "chain_rebuilt"),
+            EventType::ShareLinkCreated => write!(f, "share_link_created"),
+            EventType::ShareLinkRevoked => write!(f, "share_link_revoked"),
+            EventType::WriteAnomaly => write!(f, "write_anomaly"),
+            EventType::OwnershipTransferred => write!(f, "ownership_transferred"),
         }
     }
 }
@@ -211,6 +287,20 @@ impl std::str::FromStr for EventType {
             "mcp_tool_catalog_drift" => Ok(EventType::McpToolCatalogDrift),
             "memory_consolidated" => Ok(EventType::MemoryConsolidated),
             "memory_revised" => Ok(EventType::MemoryRevised),
+            "memory_expiring" => Ok(EventType::MemoryExpiring),
+            "memory_reviewed" => Ok(EventType::MemoryReviewed),
+            "ingestion_rejected" => Ok(EventType::IngestionRejected),
+            "export_run" => Ok(EventType::ExportRun),
+            "retention_lock_violation" => Ok(EventType::RetentionLockViolation),
+            "access_requested" => Ok(EventType::AccessRequested),
+            "access_request_decided" => Ok(EventType::AccessRequestDecided),
+            "system_message" => Ok(EventType::SystemMessage),
+            "message_deleted" => Ok(EventType::MessageDeleted),
+            "chain_rebuilt" => Ok(EventType::ChainRebuilt),
+            "share_link_created" => Ok(EventType::ShareLinkCreated),
+            "share_link_revoked" => Ok(EventType::ShareLinkRevoked),
+            "write_anomaly" => Ok(EventType::WriteAnomaly),
+            "ownership_transferred" => Ok(EventType::OwnershipTransferred),
             _ => Err(crate::error::Error::Validation(format!(
                 "invalid event type: {s}"
             ))),
@@ -244,6 +334,7 @@ mod tests {
             content_hash: vec![1, 2, 3],
             prev_hash: None,
             embedding: None,
+            request_id: Some("req-1".to_string()),
         };
         let json = serde_json::to_string(&event).unwrap();
         let deserialized: AgentEvent = serde_json::from_str(&json).unwrap();
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A run of one agent's events moved from `agent_events` to cold storage.
+///
+/// The manifest row stays in the hot database, so the archived events can
+/// be found, restored and checked against `merkle_root` after their rows
+/// are gone.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EventArchiveSegment {
+    pub id: Uuid,
+    pub agent_id: String,
+    pub first_event_id: Uuid,
+    pub last_event_id: Uuid,
+    /// Timestamp of the oldest archived event.
+    pub from_timestamp: String,
+    /// Timestamp of the newest archived event.
+    pub to_timestamp: String,
+    pub first_logical_clock: i64,
+    pub last_logical_clock: i64,
+    pub event_count: usize,
+    /// Hex SHA-256 Merkle root over the events in logical-clock order. See
+    /// [`crate::hash::compute_event_merkle_root`].
+    pub merkle_root: String,
+    /// Hex content hash of the last archived event, which the next hot
+    /// event's `prev_hash` chains from.
+    pub last_content_hash: String,
+    /// Cold-storage key of the Parquet object holding the events.
+    pub object_key: String,
+    pub archived_at: String,
+}
@@ -32,6 +32,31 @@ pub struct MemoryRecord {
     pub quarantined: bool,
     pub quarantine_reason: Option<String>,
     pub decay_function: Option<String>,
+    /// Protected memory. Pinned records are skipped by the decay pass,
+    /// TTL sweep, consolidation, conflict resolution and criteria-based
+    /// forgetting; removing one requires an explicit `force`.
+    #[serde(default)]
+    pub pinned: bool,
+    /// Start of the period in which the fact holds (RFC 3339). `None`
+    /// means it has held since before it was recorded.
+    #[serde(default)]
+    pub valid_from: Option<String>,
+    /// End of the period in which the fact holds, exclusive (RFC 3339).
+    /// `None` means it still holds.
+    #[serde(default)]
+    pub valid_until: Option<String>,
+    /// Review state of a global-scope write under a curation policy.
+    /// `None` when the memory was never subject to curation.
+    #[serde(default)]
+    pub curation: Option<ReviewStatus>,
+    /// Human attestation state of a memory carrying a tag the
+    /// attestation policy lists. Anything but approved blocks recall.
+    #[serde(default)]
+    pub attestation: Option<ReviewStatus>,
+    /// [Channel](crate::query::channel) the memory was remembered into.
+    /// Every member of the channel can read it.
+    #[serde(default)]
+    pub channel: Option<String>,
 }
 
 impl MemoryRecord {
@@ -70,6 +95,12 @@ impl MemoryRecord {
             quarantined: false,
             quarantine_reason: None,
             decay_function: None,
+            pinned: false,
+            valid_from: None,
+            valid_until: None,
+            curation: None,
+            attestation: None,
+            channel: None,
         }
     }
 
@@ -106,6 +137,12 @@ impl MemoryRecord {
         quarantined: bool,
         quarantine_reason: Option<String>,
         decay_function: Option<String>,
+        pinned: bool,
+        valid_from: Option<String>,
+        valid_until: Option<String>,
+        curation: Option<ReviewStatus>,
+        attestation: Option<ReviewStatus>,
+        channel: Option<String>,
     ) -> Self {
         Self {
             id,
@@ -137,12 +174,38 @@ impl MemoryRecord {
             quarantined,
             quarantine_reason,
             decay_function,
+            pinned,
+            valid_from,
+            valid_until,
+            curation,
+            attestation,
+            channel,
         }
     }
 
     pub fn is_deleted(&self) -> bool {
         self.deleted_at.is_some()
     }
+
+    /// Whether the fact holds at `at`: `valid_from <= at < valid_until`.
+    /// A missing or unparseable bound leaves that side open.
+    pub fn is_valid_at(&self, at: chrono::DateTime<chrono::Utc>) -> bool {
+        let bound = |ts: &Option<String>| {
+            ts.as_deref()
+                .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        };
+        if let Some(from) = bound(&self.valid_from)
+            && at < from
+        {
+            return false;
+        }
+        if let Some(until) = bound(&self.valid_until)
+            && at >= until
+        {
+            return false;
+        }
+        true
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -274,6 +337,39 @@ impl std::str::FromStr for ConsolidationState {
     }
 }
 
+/// Review state of a memory held for curation or attestation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl std::fmt::Display for ReviewStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReviewStatus::Pending => write!(f, "pending"),
+            ReviewStatus::Approved => write!(f, "approved"),
+            ReviewStatus::Rejected => write!(f, "rejected"),
+        }
+    }
+}
+
+impl std::str::FromStr for ReviewStatus {
+    type Err = crate::error::Error;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(ReviewStatus::Pending),
+            "approved" => Ok(ReviewStatus::Approved),
+            "rejected" => Ok(ReviewStatus::Rejected),
+            _ => Err(crate::error::Error::Validation(format!(
+                "invalid curation status: {s}"
+            ))),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SourceType {
@@ -360,6 +456,12 @@ mod tests {
             quarantined: false,
             quarantine_reason: None,
             decay_function: None,
+            pinned: false,
+            valid_from: None,
+            valid_until: None,
+            curation: None,
+            attestation: None,
+            channel: None,
         }
     }
 
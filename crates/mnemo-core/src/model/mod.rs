@@ -1,8 +1,19 @@
+pub mod access_log;
+pub mod access_request;
 pub mod acl;
 pub mod agent_profile;
+pub mod chain_checkpoint;
+pub mod channel;
 pub mod checkpoint;
+pub mod conflict;
+pub mod content_stats;
 pub mod delegation;
 pub mod embedding_baseline;
 pub mod event;
+pub mod event_archive;
 pub mod memory;
+pub mod outbox;
+pub mod pseudonym;
+pub mod recall_profile;
 pub mod relation;
+pub mod share_link;
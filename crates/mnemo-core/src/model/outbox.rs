@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A notification committed in the same transaction as the write it
+/// describes, waiting for the outbox dispatcher to deliver it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OutboxMessage {
+    /// Stable across redeliveries, so receivers can drop duplicates.
+    pub id: Uuid,
+    /// What happened, e.g. `"memory_write"`.
+    pub topic: String,
+    pub payload: serde_json::Value,
+    pub created_at: String,
+    /// Delivery attempts so far, including the current one.
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    /// Set once a sink accepted the message.
+    pub delivered_at: Option<String>,
+}
+
+impl OutboxMessage {
+    pub fn new(topic: impl Into<String>, payload: serde_json::Value) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            topic: topic.into(),
+            payload,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            attempts: 0,
+            last_error: None,
+            delivered_at: None,
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// One entry of an org's pseudonym alias table. See
+/// [`crate::query::pseudonym`].
+///
+/// `org_id` is `""` for memories without an org. `sealed_agent_id` is the
+/// aliased agent id sealed under the pseudonym key, hex-encoded, so only a
+/// holder of that key can reveal it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PseudonymEntry {
+    pub org_id: String,
+    pub alias: String,
+    pub sealed_agent_id: String,
+    pub created_at: String,
+}
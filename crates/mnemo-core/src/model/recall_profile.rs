@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::memory::{MemoryType, Scope};
+use crate::search::TextQueryMode;
+
+/// A named, reusable recall configuration ("saved search").
+///
+/// Profiles are scoped to the agent that created them. A recall that names a
+/// profile is expanded server-side: every setting the request leaves unset is
+/// taken from the profile, while fields the caller set explicitly win.
+/// `use_count` / `last_used_at` record how often the profile is expanded.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecallProfile {
+    pub agent_id: String,
+    pub name: String,
+    pub settings: RecallProfileSettings,
+    pub use_count: u64,
+    pub last_used_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// The recall knobs a profile can pin. Mirrors the matching
+/// [`RecallRequest`](crate::query::recall::RecallRequest) fields; stored as
+/// JSON so new knobs do not need a schema change.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct RecallProfileSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strategy: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_types: Option<Vec<MemoryType>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<Scope>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_importance: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hybrid_weights: Option<Vec<f32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hybrid: Option<crate::query::hybrid::HybridConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rrf_k: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recency_half_life_hours: Option<f64>,
+    /// Retained-evidence token budget (see `RecallRequest::retained_token_budget`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retained_token_budget: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_query_mode: Option<TextQueryMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_field_boosts: Option<std::collections::BTreeMap<String, f32>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explain: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub with_snippets: Option<bool>,
+}
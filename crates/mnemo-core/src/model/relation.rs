@@ -1,6 +1,18 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// Relation from a memory to a memory it was extracted or derived from.
+pub const DERIVED_FROM: &str = "derived_from";
+
+/// Relation from a consolidated memory to one of its members.
+pub const CONSOLIDATED_FROM: &str = "consolidated_from";
+
+/// Relation from a memory to an earlier near-duplicate of it.
+pub const DUPLICATE_OF: &str = "duplicate_of";
+
+/// Relation types that record where a memory came from.
+pub const DERIVATION_RELATION_TYPES: &[&str] = &[CONSOLIDATED_FROM, DERIVED_FROM];
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Relation {
     pub id: Uuid,
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A revoked share link on the denylist. See [`crate::query::share_link`].
+///
+/// An entry only matches links to `memory_id`: revoking needs Share on
+/// that memory, so it must not reach links to any other. `expires_at` is
+/// when the entry may be dropped: by then every link with this id has
+/// expired on its own.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RevokedShareLink {
+    pub link_id: Uuid,
+    pub memory_id: Uuid,
+    pub revoked_by: String,
+    pub revoked_at: String,
+    pub expires_at: String,
+}
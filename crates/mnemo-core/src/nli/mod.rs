@@ -0,0 +1,188 @@
+//! Natural-language-inference contradiction checking.
+//!
+//! Vector near-duplicate detection only catches conflicts that *look*
+//! alike. "The user prefers dark mode" and "The user hates dark mode" can
+//! sit far apart in embedding space yet contradict each other outright. A
+//! [`ContradictionChecker`] scores a (premise, hypothesis) pair with
+//! entailment / neutral / contradiction probabilities; when one is attached
+//! via [`MnemoEngine::with_contradiction_checker`](crate::query::MnemoEngine::with_contradiction_checker),
+//! `detect_conflicts` also runs it over nearby pairs that fall below the
+//! similarity threshold and flags the ones it judges contradictory.
+//!
+//! Two checkers ship with the crate:
+//!
+//! - [`onnx::OnnxNliChecker`] — a local NLI cross-encoder (e.g.
+//!   `cross-encoder/nli-MiniLM2-L6-H768`) behind the `onnx` feature.
+//! - [`NegationChecker`] — a model-free polarity heuristic for tests and
+//!   offline runs.
+//!
+//! An LLM-backed checker is a plain trait impl that prompts for the three
+//! probabilities; the engine core never embeds a model client.
+
+pub mod onnx;
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+
+/// Class probabilities for one NLI judgement. The three values sum to ~1.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NliScores {
+    pub entailment: f32,
+    pub neutral: f32,
+    pub contradiction: f32,
+}
+
+impl NliScores {
+    pub fn new(entailment: f32, neutral: f32, contradiction: f32) -> Self {
+        Self {
+            entailment,
+            neutral,
+            contradiction,
+        }
+    }
+
+    /// Softmax raw model logits into probabilities.
+    pub fn from_logits(entailment: f32, neutral: f32, contradiction: f32) -> Self {
+        let max = entailment.max(neutral).max(contradiction);
+        let e = (entailment - max).exp();
+        let n = (neutral - max).exp();
+        let c = (contradiction - max).exp();
+        let sum = e + n + c;
+        Self::new(e / sum, n / sum, c / sum)
+    }
+}
+
+/// Pluggable NLI judgement used by conflict detection.
+#[async_trait::async_trait]
+pub trait ContradictionChecker: Send + Sync {
+    /// Score whether `hypothesis` is entailed by, neutral to, or
+    /// contradicted by `premise`.
+    async fn check(&self, premise: &str, hypothesis: &str) -> Result<NliScores>;
+    /// Stable identifier recorded in the conflict reason.
+    fn name(&self) -> &str;
+}
+
+/// When and how `detect_conflicts` consults the attached checker.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ContradictionPolicy {
+    /// Only pairs at least this similar are sent to the checker, so a scan
+    /// stays bounded by the vector neighbourhood rather than all pairs.
+    pub candidate_similarity: f32,
+    /// Minimum contradiction probability for a pair to be flagged.
+    pub min_contradiction: f32,
+}
+
+impl Default for ContradictionPolicy {
+    fn default() -> Self {
+        Self {
+            candidate_similarity: 0.5,
+            min_contradiction: 0.7,
+        }
+    }
+}
+
+const NEGATIONS: &[&str] = &[
+    "not", "no", "never", "don't", "doesn't", "didn't", "isn't", "aren't", "wasn't", "won't",
+    "cannot", "can't", "nor",
+];
+
+const POSITIVE: &[&str] = &[
+    "likes", "like", "loves", "love", "prefers", "prefer", "enjoys", "wants",
+];
+
+const NEGATIVE: &[&str] = &[
+    "dislikes", "dislike", "hates", "hate", "avoids", "avoid", "refuses", "detests",
+];
+
+/// Model-free polarity heuristic.
+///
+/// Splits each sentence into polarity cues (negations, like/dislike verbs)
+/// and content words. Two sentences about the same thing — high content
+/// overlap — contradict when their polarity differs and entail when it
+/// matches. It knows nothing about antonyms beyond its cue lists, so use
+/// it for tests and offline runs, not production.
+#[derive(Debug, Clone, Default)]
+pub struct NegationChecker;
+
+impl NegationChecker {
+    fn analyse(text: &str) -> (bool, HashSet<String>) {
+        let mut negative = false;
+        let mut content = HashSet::new();
+        for raw in text.split_whitespace() {
+            let word: String = raw
+                .trim_matches(|c: char| !c.is_alphanumeric() && c != '\'')
+                .to_lowercase();
+            if word.is_empty() {
+                continue;
+            }
+            if NEGATIONS.contains(&word.as_str()) || NEGATIVE.contains(&word.as_str()) {
+                negative = !negative;
+            } else if !POSITIVE.contains(&word.as_str()) {
+                content.insert(word);
+            }
+        }
+        (negative, content)
+    }
+}
+
+#[async_trait::async_trait]
+impl ContradictionChecker for NegationChecker {
+    async fn check(&self, premise: &str, hypothesis: &str) -> Result<NliScores> {
+        let (neg_a, words_a) = Self::analyse(premise);
+        let (neg_b, words_b) = Self::analyse(hypothesis);
+        let union = words_a.union(&words_b).count();
+        let overlap = if union == 0 {
+            0.0
+        } else {
+            words_a.intersection(&words_b).count() as f32 / union as f32
+        };
+        Ok(if neg_a == neg_b {
+            NliScores::new(overlap, 1.0 - overlap, 0.0)
+        } else {
+            NliScores::new(0.0, 1.0 - overlap, overlap)
+        })
+    }
+
+    fn name(&self) -> &str {
+        "negation"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_logits_is_a_distribution() {
+        let scores = NliScores::from_logits(0.1, -1.0, 3.0);
+        let sum = scores.entailment + scores.neutral + scores.contradiction;
+        assert!((sum - 1.0).abs() < 1e-5);
+        assert!(scores.contradiction > scores.entailment);
+    }
+
+    #[tokio::test]
+    async fn test_negation_checker_flags_opposite_polarity() {
+        let checker = NegationChecker;
+        let scores = checker
+            .check("The user prefers dark mode", "The user hates dark mode")
+            .await
+            .unwrap();
+        assert!(scores.contradiction > 0.9);
+
+        let scores = checker
+            .check("The user prefers dark mode", "The user likes dark mode")
+            .await
+            .unwrap();
+        assert_eq!(scores.contradiction, 0.0);
+        assert!(scores.entailment > 0.9);
+
+        let scores = checker
+            .check("The user prefers dark mode", "The office is in Berlin")
+            .await
+            .unwrap();
+        assert!(scores.contradiction < 0.2);
+    }
+}
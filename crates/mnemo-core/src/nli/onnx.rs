@@ -0,0 +1,272 @@
+//! ONNX Runtime NLI cross-encoder.
+//!
+//! Scores a sentence pair with a local NLI cross-encoder such as
+//! `cross-encoder/nli-MiniLM2-L6-H768` exported to ONNX. The model must take
+//! `input_ids`, `attention_mask` and `token_type_ids` (BERT-family exports)
+//! and emit one row of three logits per pair. A `tokenizer.json` must sit
+//! next to the model file, as for [`OnnxEmbedding`](crate::embedding::onnx::OnnxEmbedding).
+//!
+//! Without the `onnx` feature the module provides a stub that validates the
+//! model path but returns [`Error::Embedding`] from `check()`.
+
+use crate::error::{Error, Result};
+use crate::nli::{ContradictionChecker, NliScores};
+
+/// Position of each class in the model's logit row.
+///
+/// The `cross-encoder/nli-*` family emits `[contradiction, entailment,
+/// neutral]`, which is the default; check `id2label` in the model's
+/// `config.json` for others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NliLabelOrder {
+    pub contradiction: usize,
+    pub entailment: usize,
+    pub neutral: usize,
+}
+
+impl Default for NliLabelOrder {
+    fn default() -> Self {
+        Self {
+            contradiction: 0,
+            entailment: 1,
+            neutral: 2,
+        }
+    }
+}
+
+impl NliLabelOrder {
+    /// Map one row of raw logits to class probabilities.
+    pub fn scores(&self, logits: &[f32]) -> Result<NliScores> {
+        let get = |i: usize| {
+            logits.get(i).copied().ok_or_else(|| {
+                Error::Embedding(format!(
+                    "NLI model returned {} logits, expected 3",
+                    logits.len()
+                ))
+            })
+        };
+        Ok(NliScores::from_logits(
+            get(self.entailment)?,
+            get(self.neutral)?,
+            get(self.contradiction)?,
+        ))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Real implementation (feature = "onnx")
+// ---------------------------------------------------------------------------
+#[cfg(feature = "onnx")]
+mod inner {
+    use super::*;
+    use ndarray::Array2;
+    use ort::session::Session;
+    use ort::value::Tensor;
+    use std::path::Path;
+    use std::sync::{Arc, Mutex};
+    use tokenizers::Tokenizer;
+
+    /// Local NLI cross-encoder.
+    pub struct OnnxNliChecker {
+        model_path: String,
+        labels: NliLabelOrder,
+        // `Session::run` takes `&mut self`; see `OnnxEmbedding`.
+        session: Arc<Mutex<Session>>,
+        tokenizer: Arc<Tokenizer>,
+    }
+
+    impl std::fmt::Debug for OnnxNliChecker {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("OnnxNliChecker")
+                .field("model_path", &self.model_path)
+                .field("labels", &self.labels)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl OnnxNliChecker {
+        /// Load an NLI cross-encoder and the `tokenizer.json` next to it.
+        ///
+        /// # Errors
+        ///
+        /// Returns [`Error::Validation`] if the model file does not exist.
+        /// Returns [`Error::Embedding`] if the ONNX session or tokenizer
+        /// fails to load.
+        pub fn new(model_path: &str) -> Result<Self> {
+            let model = Path::new(model_path);
+            if !model.exists() {
+                return Err(Error::Validation(format!(
+                    "ONNX model not found at: {model_path}"
+                )));
+            }
+            let tokenizer_path = model
+                .parent()
+                .map(|p| p.join("tokenizer.json"))
+                .unwrap_or_else(|| Path::new("tokenizer.json").to_path_buf());
+            if !tokenizer_path.exists() {
+                return Err(Error::Embedding(format!(
+                    "tokenizer.json not found next to ONNX model (expected at {})",
+                    tokenizer_path.display()
+                )));
+            }
+
+            let session = Session::builder()
+                .map_err(|e| {
+                    Error::Embedding(format!("failed to create ONNX session builder: {e}"))
+                })?
+                .commit_from_file(model_path)
+                .map_err(|e| Error::Embedding(format!("failed to load ONNX model: {e}")))?;
+            let tokenizer = Tokenizer::from_file(&tokenizer_path)
+                .map_err(|e| Error::Embedding(format!("failed to load tokenizer: {e}")))?;
+
+            Ok(Self {
+                model_path: model_path.to_string(),
+                labels: NliLabelOrder::default(),
+                session: Arc::new(Mutex::new(session)),
+                tokenizer: Arc::new(tokenizer),
+            })
+        }
+
+        /// Override the logit order for models that differ from the
+        /// `cross-encoder/nli-*` default.
+        pub fn with_label_order(mut self, labels: NliLabelOrder) -> Self {
+            self.labels = labels;
+            self
+        }
+
+        /// Get the model path.
+        #[must_use]
+        pub fn model_path(&self) -> &str {
+            &self.model_path
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ContradictionChecker for OnnxNliChecker {
+        async fn check(&self, premise: &str, hypothesis: &str) -> Result<NliScores> {
+            let session = Arc::clone(&self.session);
+            let tokenizer = Arc::clone(&self.tokenizer);
+            let labels = self.labels;
+            let pair = (premise.to_string(), hypothesis.to_string());
+
+            tokio::task::spawn_blocking(move || -> Result<NliScores> {
+                let enc = tokenizer
+                    .encode((pair.0.as_str(), pair.1.as_str()), true)
+                    .map_err(|e| Error::Embedding(format!("tokenization failed: {e}")))?;
+                let len = enc.get_ids().len();
+                let to_row = |values: &[u32]| {
+                    Array2::from_shape_vec((1, len), values.iter().map(|&v| i64::from(v)).collect())
+                        .map_err(|e| Error::Embedding(format!("input shape: {e}")))
+                };
+                let ids_t = Tensor::from_array(to_row(enc.get_ids())?)
+                    .map_err(|e| Error::Embedding(format!("input_ids tensor: {e}")))?;
+                let mask_t = Tensor::from_array(to_row(enc.get_attention_mask())?)
+                    .map_err(|e| Error::Embedding(format!("attention_mask tensor: {e}")))?;
+                let tt_t = Tensor::from_array(to_row(enc.get_type_ids())?)
+                    .map_err(|e| Error::Embedding(format!("token_type_ids tensor: {e}")))?;
+
+                let mut sess = session
+                    .lock()
+                    .map_err(|e| Error::Embedding(format!("onnx session lock poisoned: {e}")))?;
+                let outputs = sess
+                    .run(ort::inputs![
+                        "input_ids" => ids_t,
+                        "attention_mask" => mask_t,
+                        "token_type_ids" => tt_t,
+                    ])
+                    .map_err(|e| Error::Embedding(format!("ONNX inference failed: {e}")))?;
+                let (_, logits) = outputs.iter().next().ok_or_else(|| {
+                    Error::Embedding("no output tensor from ONNX model".to_string())
+                })?;
+                let logits = logits
+                    .try_extract_array::<f32>()
+                    .map_err(|e| Error::Embedding(format!("failed to extract logits: {e}")))?;
+                let row: Vec<f32> = logits.iter().copied().collect();
+                labels.scores(&row)
+            })
+            .await
+            .map_err(|e| Error::Embedding(format!("inference task panicked: {e}")))?
+        }
+
+        fn name(&self) -> &str {
+            "onnx-nli"
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Stub implementation (no onnx feature)
+// ---------------------------------------------------------------------------
+#[cfg(not(feature = "onnx"))]
+mod inner {
+    use super::*;
+
+    /// Local NLI cross-encoder. Without the `onnx` feature, `check` returns
+    /// an [`Error::Embedding`] explaining how to enable inference.
+    #[derive(Debug)]
+    pub struct OnnxNliChecker {
+        model_path: String,
+    }
+
+    impl OnnxNliChecker {
+        /// # Errors
+        ///
+        /// Returns [`Error::Validation`] if the file at `model_path` does not
+        /// exist on disk.
+        pub fn new(model_path: &str) -> Result<Self> {
+            if !std::path::Path::new(model_path).exists() {
+                return Err(Error::Validation(format!(
+                    "ONNX model not found at: {model_path}"
+                )));
+            }
+            Ok(Self {
+                model_path: model_path.to_string(),
+            })
+        }
+
+        pub fn with_label_order(self, _labels: NliLabelOrder) -> Self {
+            self
+        }
+
+        /// Get the model path.
+        #[must_use]
+        pub fn model_path(&self) -> &str {
+            &self.model_path
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ContradictionChecker for OnnxNliChecker {
+        async fn check(&self, _premise: &str, _hypothesis: &str) -> Result<NliScores> {
+            Err(Error::Embedding(
+                "ONNX Runtime not available: compile with the `onnx` feature \
+                 to enable local NLI inference"
+                    .to_string(),
+            ))
+        }
+
+        fn name(&self) -> &str {
+            "onnx-nli"
+        }
+    }
+}
+
+pub use inner::OnnxNliChecker;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label_order_maps_logits() {
+        let scores = NliLabelOrder::default().scores(&[4.0, 0.0, 0.0]).unwrap();
+        assert!(scores.contradiction > 0.9);
+        assert!(NliLabelOrder::default().scores(&[1.0]).is_err());
+    }
+
+    #[test]
+    fn test_onnx_nli_missing_model() {
+        let err = OnnxNliChecker::new("/nonexistent/nli.onnx").unwrap_err();
+        assert!(err.to_string().contains("ONNX model not found"));
+    }
+}
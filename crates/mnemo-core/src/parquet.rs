@@ -0,0 +1,317 @@
+//! A minimal in-memory Parquet writer.
+//!
+//! Enough of the format for flat tables of nullable UTF-8 and 32-bit float
+//! columns: one row group, one uncompressed, PLAIN-encoded v1 data page per
+//! column, no statistics. The file is built in a buffer, so nothing it
+//! holds ever touches the disk before the caller decides where it goes.
+
+use crate::error::{Error, Result};
+
+const MAGIC: &[u8] = b"PAR1";
+
+// Parquet enum values, from parquet.thrift.
+const TYPE_FLOAT: i32 = 4;
+const TYPE_BYTE_ARRAY: i32 = 6;
+const REPETITION_OPTIONAL: i32 = 1;
+const CONVERTED_UTF8: i32 = 0;
+const ENCODING_PLAIN: i32 = 0;
+const ENCODING_RLE: i32 = 3;
+const CODEC_UNCOMPRESSED: i32 = 0;
+const PAGE_DATA: i32 = 0;
+
+/// The values of one column, `None` for null.
+pub enum ColumnValues<'a> {
+    Utf8(Vec<Option<&'a str>>),
+    Float(Vec<Option<f32>>),
+}
+
+impl ColumnValues<'_> {
+    fn len(&self) -> usize {
+        match self {
+            ColumnValues::Utf8(values) => values.len(),
+            ColumnValues::Float(values) => values.len(),
+        }
+    }
+
+    fn physical_type(&self) -> i32 {
+        match self {
+            ColumnValues::Utf8(_) => TYPE_BYTE_ARRAY,
+            ColumnValues::Float(_) => TYPE_FLOAT,
+        }
+    }
+
+    /// Definition levels: 1 for a value, 0 for a null.
+    fn defined(&self) -> Vec<bool> {
+        match self {
+            ColumnValues::Utf8(values) => values.iter().map(Option::is_some).collect(),
+            ColumnValues::Float(values) => values.iter().map(Option::is_some).collect(),
+        }
+    }
+
+    /// The non-null values, PLAIN-encoded.
+    fn plain(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        match self {
+            ColumnValues::Utf8(values) => {
+                for value in values.iter().flatten() {
+                    out.extend_from_slice(&(value.len() as u32).to_le_bytes());
+                    out.extend_from_slice(value.as_bytes());
+                }
+            }
+            ColumnValues::Float(values) => {
+                for value in values.iter().flatten() {
+                    out.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Encode `columns`, named and in order, as a Parquet file. Every column
+/// must hold the same number of rows.
+pub fn write_table(columns: &[(&str, ColumnValues<'_>)]) -> Result<Vec<u8>> {
+    let num_rows = columns.first().map_or(0, |(_, values)| values.len());
+    if let Some((name, _)) = columns.iter().find(|(_, v)| v.len() != num_rows) {
+        return Err(Error::Internal(format!(
+            "parquet column {name} does not have {num_rows} rows"
+        )));
+    }
+
+    let mut file = MAGIC.to_vec();
+    let mut chunks = Vec::with_capacity(columns.len());
+    for (name, values) in columns {
+        let mut page = rle_levels(&values.defined());
+        page.extend(values.plain());
+        let page_size = to_i32(page.len())?;
+
+        let mut header = Thrift::default();
+        header.i32(1, PAGE_DATA);
+        header.i32(2, page_size);
+        header.i32(3, page_size);
+        header.begin_struct(5);
+        header.i32(1, to_i32(values.len())?);
+        header.i32(2, ENCODING_PLAIN);
+        header.i32(3, ENCODING_RLE);
+        header.i32(4, ENCODING_RLE);
+        header.end_struct();
+        header.stop();
+
+        let offset = file.len() as i64;
+        let chunk_size = (header.out.len() + page.len()) as i64;
+        file.extend(header.out);
+        file.extend(page);
+        chunks.push(ChunkInfo {
+            name,
+            physical_type: values.physical_type(),
+            num_values: values.len() as i64,
+            offset,
+            size: chunk_size,
+        });
+    }
+
+    let mut meta = Thrift::default();
+    meta.i32(1, 1);
+    meta.begin_list(2, 12, columns.len() + 1);
+    // The root group, then one leaf per column.
+    meta.begin_element();
+    meta.binary(4, b"schema");
+    meta.i32(5, to_i32(columns.len())?);
+    meta.end_element();
+    for chunk in &chunks {
+        meta.begin_element();
+        meta.i32(1, chunk.physical_type);
+        meta.i32(3, REPETITION_OPTIONAL);
+        meta.binary(4, chunk.name.as_bytes());
+        if chunk.physical_type == TYPE_BYTE_ARRAY {
+            meta.i32(6, CONVERTED_UTF8);
+        }
+        meta.end_element();
+    }
+    meta.i64(3, num_rows as i64);
+    meta.begin_list(4, 12, 1);
+    meta.begin_element();
+    meta.begin_list(1, 12, chunks.len());
+    for chunk in &chunks {
+        meta.begin_element();
+        meta.i64(2, chunk.offset);
+        meta.begin_struct(3);
+        meta.i32(1, chunk.physical_type);
+        meta.begin_list(2, 5, 2);
+        meta.list_i32(ENCODING_PLAIN);
+        meta.list_i32(ENCODING_RLE);
+        meta.begin_list(3, 8, 1);
+        meta.list_binary(chunk.name.as_bytes());
+        meta.i32(4, CODEC_UNCOMPRESSED);
+        meta.i64(5, chunk.num_values);
+        meta.i64(6, chunk.size);
+        meta.i64(7, chunk.size);
+        meta.i64(9, chunk.offset);
+        meta.end_struct();
+        meta.end_element();
+    }
+    meta.i64(2, chunks.iter().map(|c| c.size).sum());
+    meta.i64(3, num_rows as i64);
+    meta.end_element();
+    meta.binary(6, b"mnemo");
+    meta.stop();
+
+    let meta_len = to_i32(meta.out.len())?;
+    file.extend(meta.out);
+    file.extend_from_slice(&meta_len.to_le_bytes());
+    file.extend_from_slice(MAGIC);
+    Ok(file)
+}
+
+struct ChunkInfo<'a> {
+    name: &'a str,
+    physical_type: i32,
+    num_values: i64,
+    offset: i64,
+    size: i64,
+}
+
+fn to_i32(n: usize) -> Result<i32> {
+    i32::try_from(n).map_err(|_| Error::Internal("parquet page exceeds 2 GiB".to_string()))
+}
+
+/// Definition levels of bit width 1 as length-prefixed RLE runs.
+fn rle_levels(defined: &[bool]) -> Vec<u8> {
+    let mut runs = Vec::new();
+    let mut rest = defined;
+    while let Some(&value) = rest.first() {
+        let len = rest.iter().take_while(|&&v| v == value).count();
+        varint(&mut runs, (len as u64) << 1);
+        runs.push(u8::from(value));
+        rest = &rest[len..];
+    }
+    let mut out = (runs.len() as u32).to_le_bytes().to_vec();
+    out.extend(runs);
+    out
+}
+
+fn varint(out: &mut Vec<u8>, mut n: u64) {
+    while n >= 0x80 {
+        out.push((n as u8) | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+/// Thrift compact protocol encoder, as much of it as the metadata needs.
+#[derive(Default)]
+struct Thrift {
+    out: Vec<u8>,
+    last_field: i16,
+    /// Last field ids of the enclosing structs.
+    stack: Vec<i16>,
+}
+
+impl Thrift {
+    fn field(&mut self, id: i16, kind: u8) {
+        let delta = id - self.last_field;
+        if (1..=15).contains(&delta) {
+            self.out.push(((delta as u8) << 4) | kind);
+        } else {
+            self.out.push(kind);
+            varint(&mut self.out, zigzag(i64::from(id)));
+        }
+        self.last_field = id;
+    }
+
+    fn i32(&mut self, id: i16, value: i32) {
+        self.field(id, 5);
+        varint(&mut self.out, zigzag(i64::from(value)));
+    }
+
+    fn i64(&mut self, id: i16, value: i64) {
+        self.field(id, 6);
+        varint(&mut self.out, zigzag(value));
+    }
+
+    fn binary(&mut self, id: i16, value: &[u8]) {
+        self.field(id, 8);
+        self.list_binary(value);
+    }
+
+    fn begin_struct(&mut self, id: i16) {
+        self.field(id, 12);
+        self.begin_element();
+    }
+
+    fn end_struct(&mut self) {
+        self.end_element();
+    }
+
+    fn begin_list(&mut self, id: i16, element: u8, len: usize) {
+        self.field(id, 9);
+        if len < 15 {
+            self.out.push(((len as u8) << 4) | element);
+        } else {
+            self.out.push(0xf0 | element);
+            varint(&mut self.out, len as u64);
+        }
+    }
+
+    /// Start a struct that is a list element.
+    fn begin_element(&mut self) {
+        self.stack.push(self.last_field);
+        self.last_field = 0;
+    }
+
+    fn end_element(&mut self) {
+        self.stop();
+        self.last_field = self.stack.pop().unwrap_or_default();
+    }
+
+    fn list_i32(&mut self, value: i32) {
+        varint(&mut self.out, zigzag(i64::from(value)));
+    }
+
+    fn list_binary(&mut self, value: &[u8]) {
+        varint(&mut self.out, value.len() as u64);
+        self.out.extend_from_slice(value);
+    }
+
+    fn stop(&mut self) {
+        self.out.push(0);
+    }
+}
+
+fn zigzag(n: i64) -> u64 {
+    ((n << 1) ^ (n >> 63)) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn definition_levels_are_run_length_encoded() {
+        // Three values, two nulls, one value.
+        let levels = rle_levels(&[true, true, true, false, false, true]);
+        assert_eq!(levels, [6, 0, 0, 0, 6, 1, 4, 0, 2, 1]);
+    }
+
+    #[test]
+    fn files_are_framed_by_magic_and_footer_length() {
+        let file = write_table(&[
+            ("name", ColumnValues::Utf8(vec![Some("a"), None])),
+            ("score", ColumnValues::Float(vec![Some(0.5), Some(1.0)])),
+        ])
+        .unwrap();
+        assert!(file.starts_with(MAGIC) && file.ends_with(MAGIC));
+        let footer = &file[file.len() - 8..file.len() - 4];
+        let meta_len = u32::from_le_bytes(footer.try_into().unwrap()) as usize;
+        assert!(meta_len < file.len() - 12);
+    }
+
+    #[test]
+    fn ragged_columns_are_rejected() {
+        let ragged = write_table(&[
+            ("a", ColumnValues::Utf8(vec![Some("x")])),
+            ("b", ColumnValues::Float(Vec::new())),
+        ]);
+        assert!(ragged.is_err());
+    }
+}
@@ -0,0 +1,193 @@
+//! Per-memory access log.
+//!
+//! `access_count` / `last_accessed_at` say how often a memory was read, not
+//! who read it or why. With an [`AccessLogPolicy`] attached
+//! ([`MnemoEngine::with_access_log`]) every recall hit and every direct
+//! fetch writes one [`MemoryAccess`] row — principal, operation, timestamp
+//! and (truncated) query — for sensitive-data audits. Rows are pruned by
+//! age and by a per-memory cap in [`prune`], which the cleanup pass runs.
+//! Logging is best-effort: a failed write is warned about and never fails
+//! the read that triggered it.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::access_log::{AccessOperation, MemoryAccess};
+use crate::model::acl::Permission;
+use crate::query::MnemoEngine;
+
+/// Default page size for [`list`].
+pub const DEFAULT_ACCESS_LOG_LIMIT: usize = 50;
+/// Largest page [`list`] returns.
+pub const MAX_ACCESS_LOG_LIMIT: usize = 1000;
+/// Recall queries longer than this many characters are truncated before
+/// they are logged.
+pub const MAX_LOGGED_QUERY_CHARS: usize = 256;
+
+/// What the access log records and how long it keeps it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AccessLogPolicy {
+    /// Drop entries older than this many days. `None` keeps them forever.
+    pub retention_days: Option<u32>,
+    /// Keep at most this many entries per memory (newest win).
+    pub max_entries_per_memory: Option<usize>,
+    /// Store the recall query text. Turn off when queries themselves may
+    /// carry sensitive data.
+    pub record_query: bool,
+}
+
+impl Default for AccessLogPolicy {
+    fn default() -> Self {
+        Self {
+            retention_days: Some(90),
+            max_entries_per_memory: Some(1000),
+            record_query: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessLogRequest {
+    pub memory_id: Uuid,
+    /// Caller; needs `Admin` permission on the memory.
+    pub agent_id: Option<String>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+impl AccessLogRequest {
+    pub fn new(memory_id: Uuid) -> Self {
+        Self {
+            memory_id,
+            agent_id: None,
+            limit: None,
+            offset: None,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessLogResponse {
+    pub memory_id: Uuid,
+    /// Newest first.
+    pub accesses: Vec<MemoryAccess>,
+    /// Total logged accesses for the memory, before pagination.
+    pub total: usize,
+    /// Whether the engine is currently recording accesses.
+    pub enabled: bool,
+}
+
+impl AccessLogResponse {
+    pub fn new(memory_id: Uuid, accesses: Vec<MemoryAccess>, total: usize, enabled: bool) -> Self {
+        Self {
+            memory_id,
+            accesses,
+            total,
+            enabled,
+        }
+    }
+}
+
+/// Log one read of each of `memory_ids` by `principal`. A no-op without an
+/// access-log policy.
+pub async fn record(
+    engine: &MnemoEngine,
+    principal: &str,
+    operation: AccessOperation,
+    query: Option<&str>,
+    memory_ids: &[Uuid],
+) {
+    let Some(ref policy) = engine.access_log else {
+        return;
+    };
+    if memory_ids.is_empty() {
+        return;
+    }
+    let accessed_at = chrono::Utc::now().to_rfc3339();
+    let query = query
+        .filter(|_| policy.record_query)
+        .map(|q| q.chars().take(MAX_LOGGED_QUERY_CHARS).collect::<String>());
+    let accesses: Vec<MemoryAccess> = memory_ids
+        .iter()
+        .map(|&memory_id| MemoryAccess {
+            id: Uuid::now_v7(),
+            memory_id,
+            principal: principal.to_string(),
+            operation,
+            query: query.clone(),
+            accessed_at: accessed_at.clone(),
+        })
+        .collect();
+    if let Err(e) = engine.storage.insert_memory_accesses(&accesses).await {
+        tracing::warn!(error = %e, count = accesses.len(), "failed to write access log");
+    }
+}
+
+/// List who read a memory, newest first.
+pub async fn list(engine: &MnemoEngine, request: AccessLogRequest) -> Result<AccessLogResponse> {
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    let limit = request.limit.unwrap_or(DEFAULT_ACCESS_LOG_LIMIT);
+    if limit == 0 || limit > MAX_ACCESS_LOG_LIMIT {
+        return Err(Error::Validation(format!(
+            "limit must be between 1 and {MAX_ACCESS_LOG_LIMIT}"
+        )));
+    }
+
+    if engine
+        .storage
+        .get_memory(request.memory_id)
+        .await?
+        .is_none()
+    {
+        return Err(Error::NotFound(format!(
+            "memory {} not found",
+            request.memory_id
+        )));
+    }
+    if !engine
+        .storage
+        .check_permission(request.memory_id, &agent_id, Permission::Admin)
+        .await?
+    {
+        return Err(Error::PermissionDenied(format!(
+            "agent {agent_id} cannot view the access log of memory {}",
+            request.memory_id
+        )));
+    }
+
+    let accesses = engine
+        .storage
+        .list_memory_accesses(request.memory_id, limit, request.offset.unwrap_or(0))
+        .await?;
+    let total = engine
+        .storage
+        .count_memory_accesses(request.memory_id)
+        .await?;
+    Ok(AccessLogResponse::new(
+        request.memory_id,
+        accesses,
+        total,
+        engine.access_log.is_some(),
+    ))
+}
+
+/// Apply the policy's age and per-memory limits. Returns the number of
+/// entries deleted; zero without a policy.
+pub async fn prune(engine: &MnemoEngine) -> Result<usize> {
+    let Some(ref policy) = engine.access_log else {
+        return Ok(0);
+    };
+    let cutoff = policy
+        .retention_days
+        .map(|days| (chrono::Utc::now() - chrono::Duration::days(i64::from(days))).to_rfc3339());
+    engine
+        .storage
+        .prune_memory_accesses(cutoff.as_deref(), policy.max_entries_per_memory)
+        .await
+}
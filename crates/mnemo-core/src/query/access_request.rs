@@ -0,0 +1,394 @@
+//! Agent-to-agent memory access requests.
+//!
+//! An agent that needs memories another agent has not shared files an
+//! [`AccessRequest`] naming the memories (by id, or by tags) and saying
+//! why. The owner hears about it through an `access_requested` event on
+//! its own stream and, with the outbox on, an [`ACCESS_REQUEST_TOPIC`]
+//! message for webhooks. The owner (or whoever answers for it) then
+//! approves or denies with [`decide`]. Approval shares the memories with
+//! the requester through [`share`](super::share), so the usual ownership
+//! checks apply and the grants are written in one transaction; the
+//! requester hears back the same way, on [`ACCESS_DECISION_TOPIC`].
+//!
+//! A request is decided once. If two decisions race, the first wins and
+//! the other fails without granting anything.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::access_request::{AccessRequest, AccessRequestStatus};
+use crate::model::acl::Permission;
+use crate::model::event::EventType;
+use crate::model::outbox::OutboxMessage;
+use crate::query::MnemoEngine;
+use crate::query::share::{ShareFilter, ShareRequest};
+use crate::storage::WriteBatch;
+
+/// Topic of the message sent to the owner when a request is filed.
+pub const ACCESS_REQUEST_TOPIC: &str = "access_request";
+
+/// Topic of the message sent to the requester when a request is decided.
+pub const ACCESS_DECISION_TOPIC: &str = "access_request_decided";
+
+/// Default page size for [`list`].
+const DEFAULT_ACCESS_REQUEST_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileAccessRequest {
+    /// The agent asking; defaults to the engine's agent.
+    pub requester_id: Option<String>,
+    pub owner_id: String,
+    /// Ask for these memories. Exactly one of `memory_ids` and `tags` is
+    /// required.
+    pub memory_ids: Option<Vec<Uuid>>,
+    /// Ask for every memory of the owner carrying all of these tags.
+    pub tags: Option<Vec<String>>,
+    pub justification: String,
+    /// Defaults to read access.
+    pub permission: Option<Permission>,
+    pub expires_in_hours: Option<f64>,
+}
+
+impl FileAccessRequest {
+    pub fn new(owner_id: String, justification: String) -> Self {
+        Self {
+            requester_id: None,
+            owner_id,
+            memory_ids: None,
+            tags: None,
+            justification,
+            permission: None,
+            expires_in_hours: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessRequestListRequest {
+    /// `None` lists requests to every owner.
+    pub owner_id: Option<String>,
+    /// `None` lists requests from every requester.
+    pub requester_id: Option<String>,
+    /// `None` lists every status.
+    pub status: Option<AccessRequestStatus>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+impl AccessRequestListRequest {
+    pub fn new() -> Self {
+        Self {
+            owner_id: None,
+            requester_id: None,
+            status: None,
+            limit: None,
+            offset: None,
+        }
+    }
+}
+
+impl Default for AccessRequestListRequest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The owner's answer to a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccessDecision {
+    Approve,
+    Deny,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecideAccessRequest {
+    pub request_id: Uuid,
+    /// The deciding agent; must be the owner. Defaults to the engine's
+    /// agent.
+    pub agent_id: Option<String>,
+    pub decision: AccessDecision,
+    /// Who made the call (a user id, an operator name, ...).
+    pub decided_by: Option<String>,
+    /// Shown to the requester.
+    pub note: Option<String>,
+}
+
+impl DecideAccessRequest {
+    pub fn new(request_id: Uuid, decision: AccessDecision) -> Self {
+        Self {
+            request_id,
+            agent_id: None,
+            decision,
+            decided_by: None,
+            note: None,
+        }
+    }
+}
+
+pub async fn file(engine: &MnemoEngine, request: FileAccessRequest) -> Result<AccessRequest> {
+    let requester_id = request
+        .requester_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&requester_id)?;
+    super::validate_agent_id(&request.owner_id)?;
+    if request.owner_id == requester_id {
+        return Err(Error::invalid_field(
+            "owner_id",
+            "cannot be the requesting agent",
+        ));
+    }
+    if request.justification.trim().is_empty() {
+        return Err(Error::invalid_field("justification", "cannot be empty"));
+    }
+    if let Some(hours) = request.expires_in_hours
+        && (hours.is_nan() || hours <= 0.0)
+    {
+        return Err(Error::invalid_field(
+            "expires_in_hours",
+            "must be greater than 0",
+        ));
+    }
+    let memory_ids = request.memory_ids.unwrap_or_default();
+    let tags = request.tags.unwrap_or_default();
+    if memory_ids.is_empty() == tags.is_empty() {
+        return Err(Error::invalid_field(
+            "memory_ids",
+            "exactly one of memory_ids and tags is required",
+        ));
+    }
+    engine
+        .limits()
+        .check_batch("memory_ids", memory_ids.len())?;
+    engine.limits().check_tags(&tags)?;
+    let mut seen = HashSet::new();
+    let memory_ids: Vec<Uuid> = memory_ids
+        .into_iter()
+        .filter(|id| seen.insert(*id))
+        .collect();
+
+    let record = AccessRequest {
+        id: Uuid::now_v7(),
+        requester_id,
+        owner_id: request.owner_id,
+        memory_ids,
+        tags,
+        justification: request.justification,
+        permission: request.permission.unwrap_or(Permission::Read),
+        expires_in_hours: request.expires_in_hours,
+        status: AccessRequestStatus::Pending,
+        decided_by: None,
+        decision_note: None,
+        acl_ids: Vec::new(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        decided_at: None,
+    };
+    engine.storage.insert_access_request(&record).await?;
+
+    notify(
+        engine,
+        &record.owner_id,
+        EventType::AccessRequested,
+        ACCESS_REQUEST_TOPIC,
+        record.id,
+        serde_json::json!({
+            "request_id": record.id.to_string(),
+            "requester_id": record.requester_id,
+            "owner_id": record.owner_id,
+            "memory_ids": record.memory_ids,
+            "tags": record.tags,
+            "permission": record.permission.to_string(),
+            "justification": record.justification,
+        }),
+    )
+    .await;
+    Ok(record)
+}
+
+pub async fn list(
+    engine: &MnemoEngine,
+    request: AccessRequestListRequest,
+) -> Result<Vec<AccessRequest>> {
+    for agent_id in [&request.owner_id, &request.requester_id]
+        .into_iter()
+        .flatten()
+    {
+        super::validate_agent_id(agent_id)?;
+    }
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_ACCESS_REQUEST_LIMIT)
+        .min(super::MAX_BATCH_QUERY_LIMIT);
+    engine
+        .storage
+        .list_access_requests(
+            request.owner_id.as_deref(),
+            request.requester_id.as_deref(),
+            request.status,
+            limit,
+            request.offset.unwrap_or(0),
+        )
+        .await
+}
+
+pub async fn decide(engine: &MnemoEngine, request: DecideAccessRequest) -> Result<AccessRequest> {
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    let mut record = engine
+        .storage
+        .get_access_request(request.request_id)
+        .await?
+        .ok_or_else(|| {
+            Error::NotFound(format!("access request {} not found", request.request_id))
+        })?;
+    if record.owner_id != agent_id {
+        return Err(Error::PermissionDenied(format!(
+            "agent {agent_id} cannot decide access request {}",
+            record.id
+        )));
+    }
+    if record.status != AccessRequestStatus::Pending {
+        return Err(already_decided(&record));
+    }
+
+    record.status = match request.decision {
+        AccessDecision::Approve => AccessRequestStatus::Approved,
+        AccessDecision::Deny => AccessRequestStatus::Denied,
+    };
+    record.decided_by = request.decided_by;
+    record.decision_note = request.note;
+    record.decided_at = Some(chrono::Utc::now().to_rfc3339());
+    // Claim the request before granting anything, so a racing decision
+    // cannot also act on it.
+    if !engine
+        .storage
+        .update_access_request(&record, AccessRequestStatus::Pending)
+        .await?
+    {
+        return Err(reload_decided(engine, record.id).await);
+    }
+
+    let mut granted = Vec::new();
+    if record.status == AccessRequestStatus::Approved {
+        match grant(engine, &record).await {
+            Ok((memory_ids, acl_ids)) => {
+                granted = memory_ids;
+                record.acl_ids = acl_ids;
+                engine
+                    .storage
+                    .update_access_request(&record, AccessRequestStatus::Approved)
+                    .await?;
+            }
+            Err(e) => {
+                // Nothing was granted: hand the request back to the owner.
+                let mut pending = record.clone();
+                pending.status = AccessRequestStatus::Pending;
+                pending.decided_by = None;
+                pending.decision_note = None;
+                pending.decided_at = None;
+                if let Err(reset) = engine
+                    .storage
+                    .update_access_request(&pending, AccessRequestStatus::Approved)
+                    .await
+                {
+                    tracing::error!(request_id = %record.id, error = %reset, "failed to reopen access request");
+                }
+                return Err(e);
+            }
+        }
+    }
+
+    notify(
+        engine,
+        &record.requester_id,
+        EventType::AccessRequestDecided,
+        ACCESS_DECISION_TOPIC,
+        record.id,
+        serde_json::json!({
+            "request_id": record.id.to_string(),
+            "requester_id": record.requester_id,
+            "owner_id": record.owner_id,
+            "status": record.status.to_string(),
+            "decided_by": record.decided_by,
+            "note": record.decision_note,
+            "memory_ids": granted,
+            "acl_ids": record.acl_ids,
+        }),
+    )
+    .await;
+    Ok(record)
+}
+
+/// Share the requested memories with the requester on the owner's behalf.
+/// Returns the memories shared and the grants created.
+async fn grant(engine: &MnemoEngine, record: &AccessRequest) -> Result<(Vec<Uuid>, Vec<Uuid>)> {
+    let mut share = ShareRequest::new(Uuid::nil(), record.requester_id.clone());
+    share.agent_id = Some(record.owner_id.clone());
+    share.permission = Some(record.permission);
+    share.expires_in_hours = record.expires_in_hours;
+    if record.memory_ids.is_empty() {
+        share.filter = Some(ShareFilter {
+            tags: record.tags.clone(),
+            ..Default::default()
+        });
+    } else {
+        share.memory_ids = Some(record.memory_ids.clone());
+    }
+    let response = super::share::execute(engine, share).await?;
+    Ok((response.memory_ids, response.acl_ids))
+}
+
+async fn reload_decided(engine: &MnemoEngine, id: Uuid) -> Error {
+    match engine.storage.get_access_request(id).await {
+        Ok(Some(record)) => already_decided(&record),
+        Ok(None) => Error::NotFound(format!("access request {id} not found")),
+        Err(e) => e,
+    }
+}
+
+fn already_decided(record: &AccessRequest) -> Error {
+    Error::Validation(format!(
+        "access request {} is already {}",
+        record.id, record.status
+    ))
+}
+
+/// Record `payload` as an event on `agent_id`'s stream and, with the outbox
+/// on, queue it for webhooks. Failures are logged: the request itself is
+/// stored and can still be listed.
+async fn notify(
+    engine: &MnemoEngine,
+    agent_id: &str,
+    event_type: EventType,
+    topic: &str,
+    request_id: Uuid,
+    payload: serde_json::Value,
+) {
+    // The event belongs to the agent being notified, not the caller.
+    crate::storage::with_agent_context(agent_id.to_string(), async {
+        let event = super::event_builder::build_event(
+            engine,
+            agent_id,
+            event_type,
+            payload.clone(),
+            &request_id.to_string(),
+            None,
+        )
+        .await;
+        let mut batch = WriteBatch {
+            events: vec![event],
+            ..Default::default()
+        };
+        if engine.outbox_enabled {
+            batch.outbox.push(OutboxMessage::new(topic, payload));
+        }
+        if let Err(e) = engine.storage.commit_write(&batch).await {
+            tracing::error!(request_id = %request_id, error = %e, "failed to record access request event");
+        }
+    })
+    .await
+}
@@ -0,0 +1,114 @@
+//! Per-agent default retrieval settings.
+//!
+//! Most clients never set `recency_half_life_hours`, `hybrid_weights`,
+//! `strategy`, `limit` or `thread_isolation` on a recall. These defaults
+//! live on the agent's
+//! [`AgentProfile`](crate::model::agent_profile::AgentProfile) and fill
+//! those fields when both the request and its recall profile leave them
+//! unset.
+
+use crate::error::{Error, Result};
+use crate::model::agent_profile::RetrievalDefaults;
+use crate::query::MnemoEngine;
+use crate::query::recall::RecallRequest;
+
+/// Strategies a default may name. `reconstruct` and `domain_scoped` need
+/// per-request inputs, so they are not offered as agent defaults.
+const DEFAULTABLE_STRATEGIES: &[&str] =
+    &["auto", "semantic", "lexical", "hybrid", "graph", "exact"];
+
+fn validate(defaults: &RetrievalDefaults) -> Result<()> {
+    if let Some(hours) = defaults.recency_half_life_hours
+        && !(hours.is_finite() && hours > 0.0)
+    {
+        return Err(Error::Validation(
+            "recency_half_life_hours must be a positive number".to_string(),
+        ));
+    }
+    if let Some(ref weights) = defaults.hybrid_weights
+        && (weights.is_empty() || weights.iter().any(|w| !w.is_finite() || *w < 0.0))
+    {
+        return Err(Error::Validation(
+            "hybrid_weights must be a non-empty list of non-negative numbers".to_string(),
+        ));
+    }
+    if let Some(ref strategy) = defaults.strategy
+        && !DEFAULTABLE_STRATEGIES.contains(&strategy.as_str())
+    {
+        return Err(Error::Validation(format!(
+            "invalid default strategy '{strategy}': expected one of: {}",
+            DEFAULTABLE_STRATEGIES.join(", ")
+        )));
+    }
+    if let Some(limit) = defaults.limit
+        && !(1..=100).contains(&limit)
+    {
+        return Err(Error::Validation(
+            "default limit must be between 1 and 100".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Current retrieval defaults for `agent_id` (empty when never set).
+pub async fn get(engine: &MnemoEngine, agent_id: &str) -> Result<RetrievalDefaults> {
+    super::validate_agent_id(agent_id)?;
+    Ok(engine
+        .storage
+        .get_agent_profile(agent_id)
+        .await?
+        .map(|p| p.retrieval)
+        .unwrap_or_default())
+}
+
+/// Replace the retrieval defaults for `agent_id`.
+pub async fn update(
+    engine: &MnemoEngine,
+    agent_id: &str,
+    defaults: RetrievalDefaults,
+) -> Result<RetrievalDefaults> {
+    super::validate_agent_id(agent_id)?;
+    validate(&defaults)?;
+    let now = chrono::Utc::now().to_rfc3339();
+    engine
+        .storage
+        .set_agent_retrieval_defaults(agent_id, &defaults, &now)
+        .await?;
+    Ok(defaults)
+}
+
+/// Fill every unset field of `request` from `defaults`.
+pub fn apply(defaults: &RetrievalDefaults, request: &mut RecallRequest) {
+    let strategy_chosen = request.strategy.is_some()
+        || request.mode.is_some()
+        || request
+            .domain_scope
+            .as_ref()
+            .map(|s| !s.is_empty())
+            .unwrap_or(false);
+    if !strategy_chosen {
+        request.strategy = defaults.strategy.clone();
+    }
+    request.limit = request.limit.or(defaults.limit);
+    request.recency_half_life_hours = request
+        .recency_half_life_hours
+        .or(defaults.recency_half_life_hours);
+    if request.hybrid_weights.is_none() {
+        request.hybrid_weights = defaults.hybrid_weights.clone();
+    }
+    request.thread_isolation = request.thread_isolation.or(defaults.thread_isolation);
+}
+
+/// Apply the agent's stored defaults to `request`, if it has any.
+pub(crate) async fn expand(
+    engine: &MnemoEngine,
+    request: &mut RecallRequest,
+    agent_id: &str,
+) -> Result<()> {
+    if let Some(profile) = engine.storage.get_agent_profile(agent_id).await?
+        && !profile.retrieval.is_empty()
+    {
+        apply(&profile.retrieval, request);
+    }
+    Ok(())
+}
@@ -0,0 +1,328 @@
+//! Human attestation of high-stakes memories.
+//!
+//! Regulated deployments cannot let an agent act on a "medical" or
+//! "financial" fact nobody has checked. An [`AttestationPolicy`] maps tags
+//! to review requirements; a memory written with (or merged into) one of
+//! those tags is stored as [`ReviewStatus::Pending`] and no recall returns
+//! it, not even its author's, until it is approved. A rejected memory stays
+//! out of recall for good.
+//!
+//! Each requirement names the reviewers allowed to attest memories with
+//! that tag and how many distinct approvals they need. The engine's
+//! default agent (the identity the admin API acts as) may review anything.
+//! An author never attests their own memory. When a memory carries several
+//! policy tags, every one of them must be satisfied; a single rejection by
+//! an allowed reviewer rejects it.
+//!
+//! Approvals are kept under `metadata.attestation`, and every decision is
+//! written as a `MemoryReviewed` audit event.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::event::EventType;
+use crate::model::memory::{MemoryRecord, ReviewStatus};
+use crate::query::MnemoEngine;
+use crate::storage::MemoryFilter;
+
+/// Tags whose memories need attestation, with what each requires.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AttestationPolicy {
+    pub tags: BTreeMap<String, TagReview>,
+}
+
+impl AttestationPolicy {
+    pub fn with_tag(mut self, tag: impl Into<String>, review: TagReview) -> Self {
+        self.tags.insert(tag.into(), review);
+        self
+    }
+
+    fn requirements<'a>(
+        &'a self,
+        record: &'a MemoryRecord,
+    ) -> impl Iterator<Item = (&'a str, &'a TagReview)> + 'a {
+        record
+            .tags
+            .iter()
+            .filter_map(|t| self.tags.get(t).map(|r| (t.as_str(), r)))
+    }
+}
+
+/// Review requirement for one tag.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TagReview {
+    /// Agents allowed to attest, besides the engine's default agent.
+    pub reviewers: BTreeSet<String>,
+    /// Distinct approvals needed.
+    pub approvals: usize,
+}
+
+impl Default for TagReview {
+    fn default() -> Self {
+        Self {
+            reviewers: BTreeSet::new(),
+            approvals: 1,
+        }
+    }
+}
+
+impl TagReview {
+    pub fn with_reviewer(mut self, agent_id: impl Into<String>) -> Self {
+        self.reviewers.insert(agent_id.into());
+        self
+    }
+
+    pub fn with_approvals(mut self, approvals: usize) -> Self {
+        self.approvals = approvals;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttestRequest {
+    pub memory_id: Uuid,
+    /// Reviewing agent. Defaults to the engine's agent.
+    pub agent_id: Option<String>,
+    pub approve: bool,
+    pub note: Option<String>,
+}
+
+impl AttestRequest {
+    pub fn new(memory_id: Uuid, approve: bool) -> Self {
+        Self {
+            memory_id,
+            agent_id: None,
+            approve,
+            note: None,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttestResponse {
+    pub memory_id: Uuid,
+    /// `pending` while approvals are still missing.
+    pub status: ReviewStatus,
+    pub approved_by: Vec<String>,
+}
+
+impl AttestResponse {
+    pub fn new(memory_id: Uuid, status: ReviewStatus, approved_by: Vec<String>) -> Self {
+        Self {
+            memory_id,
+            status,
+            approved_by,
+        }
+    }
+}
+
+/// A memory awaiting attestation.
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingAttestation {
+    pub memory_id: Uuid,
+    pub agent_id: String,
+    pub content: String,
+    /// The tags that put the memory under review.
+    pub review_tags: Vec<String>,
+    pub approved_by: Vec<String>,
+    pub created_at: String,
+}
+
+/// Attestation state of a new memory with `tags`.
+pub(crate) fn initial_status(engine: &MnemoEngine, tags: &[String]) -> Option<ReviewStatus> {
+    let policy = engine.attestation.as_ref()?;
+    tags.iter()
+        .any(|t| policy.tags.contains_key(t))
+        .then_some(ReviewStatus::Pending)
+}
+
+/// Whether any recall may return `record`.
+pub(crate) fn is_recallable(record: &MemoryRecord) -> bool {
+    matches!(record.attestation, None | Some(ReviewStatus::Approved))
+}
+
+/// Memories awaiting attestation that `agent_id` may review, oldest first.
+pub async fn list_pending(
+    engine: &MnemoEngine,
+    agent_id: Option<String>,
+    limit: usize,
+) -> Result<Vec<PendingAttestation>> {
+    let agent_id = agent_id.unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    let Some(ref policy) = engine.attestation else {
+        return Ok(Vec::new());
+    };
+    let filter = MemoryFilter {
+        include_deleted: false,
+        ..Default::default()
+    };
+    let mut records = engine
+        .storage
+        .list_memories(&filter, super::MAX_BATCH_QUERY_LIMIT, 0)
+        .await?;
+    records.retain(|r| {
+        r.attestation == Some(ReviewStatus::Pending)
+            && !r.is_deleted()
+            && may_review(engine, policy, &agent_id, r)
+    });
+    records.sort_by(|a, b| (&a.created_at, a.id).cmp(&(&b.created_at, b.id)));
+    Ok(records
+        .into_iter()
+        .take(limit)
+        .map(|mut record| {
+            super::consolidate::decrypt_in_place(engine, &mut record);
+            PendingAttestation {
+                memory_id: record.id,
+                review_tags: policy
+                    .requirements(&record)
+                    .map(|(t, _)| t.to_string())
+                    .collect(),
+                approved_by: approvals(&record),
+                agent_id: record.agent_id,
+                content: record.content,
+                created_at: record.created_at,
+            }
+        })
+        .collect())
+}
+
+pub async fn attest(engine: &MnemoEngine, request: AttestRequest) -> Result<AttestResponse> {
+    let Some(ref policy) = engine.attestation else {
+        return Err(Error::Validation(
+            "attestation is not enabled on this engine".to_string(),
+        ));
+    };
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+
+    let mut record = engine
+        .storage
+        .get_memory(request.memory_id)
+        .await?
+        .filter(|r| !r.is_deleted() && r.attestation.is_some())
+        .ok_or_else(|| {
+            Error::NotFound(format!(
+                "memory {} is not under attestation",
+                request.memory_id
+            ))
+        })?;
+    if !may_review(engine, policy, &agent_id, &record) {
+        return Err(Error::PermissionDenied(format!(
+            "agent {agent_id} may not attest memory {}",
+            request.memory_id
+        )));
+    }
+    if record.attestation != Some(ReviewStatus::Pending) {
+        return Err(Error::Validation(format!(
+            "memory {} was already {}",
+            request.memory_id,
+            record.attestation.unwrap_or(ReviewStatus::Pending)
+        )));
+    }
+
+    let mut approved_by = approvals(&record);
+    let status = if request.approve {
+        if !approved_by.contains(&agent_id) {
+            approved_by.push(agent_id.clone());
+        }
+        let satisfied = policy.requirements(&record).all(|(_, review)| {
+            approved_by
+                .iter()
+                .filter(|a| **a == engine.default_agent_id || review.reviewers.contains(*a))
+                .count()
+                >= review.approvals.max(1)
+        });
+        if satisfied {
+            ReviewStatus::Approved
+        } else {
+            ReviewStatus::Pending
+        }
+    } else {
+        ReviewStatus::Rejected
+    };
+
+    let now = chrono::Utc::now().to_rfc3339();
+    record.attestation = Some(status);
+    if !record.metadata.is_object() {
+        record.metadata = serde_json::Value::Object(serde_json::Map::new());
+    }
+    let mut decisions = record.metadata["attestation"]["decisions"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    decisions.push(serde_json::json!({
+        "reviewed_by": agent_id,
+        "approve": request.approve,
+        "reviewed_at": now,
+        "note": request.note,
+    }));
+    record.metadata["attestation"] = serde_json::json!({
+        "status": status,
+        "approved_by": approved_by,
+        "decisions": decisions,
+    });
+    record.updated_at = now;
+    engine.storage.update_memory(&record).await?;
+    if let Some(ref cache) = engine.cache {
+        cache.invalidate(record.id);
+    }
+
+    let review_tags: Vec<&str> = policy.requirements(&record).map(|(t, _)| t).collect();
+    let event = super::event_builder::build_event(
+        engine,
+        &agent_id,
+        EventType::MemoryReviewed,
+        serde_json::json!({
+            "memory_id": record.id.to_string(),
+            "status": status,
+            "reviewed_by": agent_id,
+            "tags": review_tags,
+            "note": request.note,
+        }),
+        &record.id.to_string(),
+        record.thread_id.clone(),
+    )
+    .await;
+    if let Err(e) = engine.storage.insert_event(&event).await {
+        tracing::error!(event_id = %event.id, error = %e, "failed to insert audit event");
+    }
+
+    Ok(AttestResponse::new(record.id, status, approved_by))
+}
+
+/// The default agent, or a listed reviewer of one of the record's policy
+/// tags — never the author.
+fn may_review(
+    engine: &MnemoEngine,
+    policy: &AttestationPolicy,
+    agent_id: &str,
+    record: &MemoryRecord,
+) -> bool {
+    if agent_id == record.agent_id {
+        return false;
+    }
+    agent_id == engine.default_agent_id
+        || policy
+            .requirements(record)
+            .any(|(_, review)| review.reviewers.contains(agent_id))
+}
+
+fn approvals(record: &MemoryRecord) -> Vec<String> {
+    record.metadata["attestation"]["approved_by"]
+        .as_array()
+        .map(|a| {
+            a.iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
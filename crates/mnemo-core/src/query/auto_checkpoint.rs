@@ -0,0 +1,202 @@
+//! Automatic checkpoints.
+//!
+//! Replay is only as recent as a thread's last checkpoint, and callers
+//! forget to take them. With an [`AutoCheckpointConfig`] attached
+//! ([`MnemoEngine::with_auto_checkpoint`] or the `[auto_checkpoint]` table
+//! of the engine config file) the engine checkpoints a thread
+//! by itself, per the acting agent's [`AutoCheckpointPolicy`]:
+//!
+//! - after every `every_n_memories` remembers in the thread since its last
+//!   checkpoint, right after the write that reaches the count;
+//! - once the thread has had unsaved remembers and no further writes for
+//!   `inactivity_seconds`, on the next [`run_auto_checkpoints`] pass.
+//!
+//! An automatic checkpoint carries the thread's last checkpointed state
+//! forward (an empty object for a new thread), is labelled
+//! [`AUTO_CHECKPOINT_LABEL`] and records its trigger in its metadata. Any
+//! checkpoint of the thread, manual or automatic, restarts the count.
+//! Pending counts live in memory and are lost on restart.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::query::MnemoEngine;
+use crate::query::checkpoint::{self, CheckpointRequest};
+
+/// Label of every automatic checkpoint.
+pub const AUTO_CHECKPOINT_LABEL: &str = "auto";
+
+/// When to checkpoint one agent's threads automatically. A policy with
+/// neither trigger set does nothing.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AutoCheckpointPolicy {
+    /// Checkpoint after this many remembers since the last checkpoint.
+    pub every_n_memories: Option<u32>,
+    /// Checkpoint a thread with unsaved remembers after this long without
+    /// writes.
+    pub inactivity_seconds: Option<u64>,
+}
+
+/// Automatic checkpoint policies by agent.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AutoCheckpointConfig {
+    /// Policy of agents without an entry in `agents`. `None` leaves them
+    /// alone.
+    pub default: Option<AutoCheckpointPolicy>,
+    /// Per-agent policies, keyed by agent id.
+    pub agents: HashMap<String, AutoCheckpointPolicy>,
+}
+
+impl AutoCheckpointConfig {
+    pub fn policy_for(&self, agent_id: &str) -> Option<&AutoCheckpointPolicy> {
+        self.agents.get(agent_id).or(self.default.as_ref())
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        for policy in self.default.iter().chain(self.agents.values()) {
+            if policy.every_n_memories == Some(0) || policy.inactivity_seconds == Some(0) {
+                return Err(Error::Validation(
+                    "auto_checkpoint triggers must be > 0".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether any policy needs periodic [`run_auto_checkpoints`] passes.
+    pub fn has_inactivity_trigger(&self) -> bool {
+        self.default
+            .iter()
+            .chain(self.agents.values())
+            .any(|p| p.inactivity_seconds.is_some())
+    }
+}
+
+/// Remembers per (agent, thread) since the thread's last checkpoint.
+#[derive(Debug, Default)]
+pub(crate) struct PendingWrites {
+    threads: Mutex<HashMap<(String, String), Pending>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Pending {
+    writes: u32,
+    last_write: Instant,
+}
+
+impl PendingWrites {
+    /// Count a write; returns the writes pending for the thread.
+    fn record(&self, agent_id: &str, thread_id: &str) -> u32 {
+        let mut threads = self.threads.lock().unwrap_or_else(|e| e.into_inner());
+        let pending = threads
+            .entry((agent_id.to_string(), thread_id.to_string()))
+            .or_insert(Pending {
+                writes: 0,
+                last_write: Instant::now(),
+            });
+        pending.writes += 1;
+        pending.last_write = Instant::now();
+        pending.writes
+    }
+
+    pub(crate) fn clear(&self, agent_id: &str, thread_id: &str) {
+        self.threads
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&(agent_id.to_string(), thread_id.to_string()));
+    }
+
+    /// Threads whose last write is older than `idle_for(agent_id)`.
+    fn idle(&self, idle_for: impl Fn(&str) -> Option<Duration>) -> Vec<(String, String)> {
+        let threads = self.threads.lock().unwrap_or_else(|e| e.into_inner());
+        threads
+            .iter()
+            .filter(|((agent_id, _), pending)| {
+                idle_for(agent_id).is_some_and(|idle| pending.last_write.elapsed() >= idle)
+            })
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+}
+
+/// Outcome of one [`run_auto_checkpoints`] pass.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutoCheckpointReport {
+    pub created: usize,
+    pub errors: Vec<String>,
+}
+
+/// Count a remember in `thread_id` and checkpoint the thread when the
+/// agent's `every_n_memories` is reached. Failures are logged: the write
+/// itself has already succeeded.
+pub(crate) async fn record_write(engine: &MnemoEngine, agent_id: &str, thread_id: &str) {
+    let config = engine.auto_checkpoint();
+    let Some(policy) = config.policy_for(agent_id) else {
+        return;
+    };
+    if policy.every_n_memories.is_none() && policy.inactivity_seconds.is_none() {
+        return;
+    }
+    let pending = engine.pending_writes.record(agent_id, thread_id);
+    if let Some(n) = policy.every_n_memories
+        && pending >= n
+        && let Err(e) = create(engine, agent_id, thread_id, "memory_count").await
+    {
+        tracing::warn!(agent_id, thread_id, error = %e, "automatic checkpoint failed");
+    }
+}
+
+/// Checkpoint every thread idle for its agent's `inactivity_seconds`. A
+/// read-only engine keeps the threads pending until writes are allowed.
+pub async fn run_auto_checkpoints(engine: &MnemoEngine) -> Result<AutoCheckpointReport> {
+    if engine.is_read_only() {
+        return Ok(AutoCheckpointReport::default());
+    }
+    let config = engine.auto_checkpoint();
+    let idle = engine.pending_writes.idle(|agent_id| {
+        config
+            .policy_for(agent_id)
+            .and_then(|p| p.inactivity_seconds)
+            .map(Duration::from_secs)
+    });
+    let mut report = AutoCheckpointReport::default();
+    for (agent_id, thread_id) in idle {
+        match create(engine, &agent_id, &thread_id, "inactivity").await {
+            Ok(()) => report.created += 1,
+            Err(e) => report.errors.push(format!("{agent_id}/{thread_id}: {e}")),
+        }
+    }
+    Ok(report)
+}
+
+async fn create(
+    engine: &MnemoEngine,
+    agent_id: &str,
+    thread_id: &str,
+    trigger: &str,
+) -> Result<()> {
+    let state = match engine
+        .storage
+        .get_latest_checkpoint(thread_id, "main")
+        .await?
+    {
+        Some(latest) => {
+            checkpoint::materialize(engine, latest)
+                .await?
+                .state_snapshot
+        }
+        None => serde_json::Value::Object(serde_json::Map::new()),
+    };
+    let mut request = CheckpointRequest::new(thread_id.to_string(), state);
+    request.agent_id = Some(agent_id.to_string());
+    request.label = Some(AUTO_CHECKPOINT_LABEL.to_string());
+    request.metadata = Some(serde_json::json!({ "auto": true, "trigger": trigger }));
+    checkpoint::execute(engine, request).await?;
+    Ok(())
+}
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::error::{Error, Result};
+use crate::model::acl::Permission;
 use crate::model::checkpoint::Checkpoint;
 use crate::model::event::EventType;
 use crate::query::MnemoEngine;
@@ -71,6 +72,14 @@ pub async fn execute(engine: &MnemoEngine, request: BranchRequest) -> Result<Bra
                 ))
             })?
     };
+    super::checkpoint::ensure_thread_access(
+        engine,
+        &source_cp.thread_id,
+        &agent_id,
+        Permission::Write,
+    )
+    .await?;
+    let source_cp = super::checkpoint::materialize(engine, source_cp).await?;
 
     // Create new checkpoint on the new branch with parent = source
     let id = Uuid::now_v7();
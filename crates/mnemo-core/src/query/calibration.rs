@@ -0,0 +1,426 @@
+//! Normalized 0-1 relevance for recall hits.
+//!
+//! Raw recall scores live on different scales: cosine similarity for the
+//! vector strategies, unbounded BM25 for `lexical`, small reciprocal rank
+//! fusion sums for `hybrid`, `graph` and multi-query recall. A threshold
+//! that suits one is meaningless for another. Every recall hit therefore
+//! also carries a `relevance` in `[0, 1]` from the engine's
+//! [`ScoreCalibrator`], and [`RecallRequest::min_relevance`] drops hits
+//! below a threshold on that scale.
+//!
+//! Each strategy is calibrated on its own:
+//!
+//! - Until it has feedback, by min-max over the last
+//!   [`CalibrationConfig::range_window`] raw scores it produced, so an
+//!   outlier stops stretching the range once it ages out. The vector
+//!   strategies use the cosine range `[0, 1]` instead.
+//! - Agents report the hits that proved useful with
+//!   [`MnemoEngine::recall_feedback`]. Every hit of the agent's recent
+//!   recalls becomes a labelled sample, useful or not. Once a strategy has
+//!   [`CalibrationConfig::min_samples`] of them, relevance is the useful
+//!   rate at that raw score, fitted by isotonic regression so it never
+//!   falls as the score rises.
+//!
+//! The calibration lives in memory and starts over when the engine does.
+//!
+//! [`RecallRequest::min_relevance`]: crate::query::recall::RecallRequest::min_relevance
+//! [`MnemoEngine::recall_feedback`]: crate::query::MnemoEngine::recall_feedback
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+
+/// Strategies that score by cosine similarity.
+const COSINE_STRATEGIES: &[&str] = &["semantic", "domain_scoped"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CalibrationConfig {
+    /// Recent raw scores per strategy that the min-max range spans.
+    pub range_window: usize,
+    /// Labelled hits a strategy needs before relevance is fitted to them.
+    pub min_samples: usize,
+    /// Labelled hits kept per strategy; the oldest are dropped first.
+    pub max_samples: usize,
+    /// Recent recalls kept for feedback to label.
+    pub max_recalls: usize,
+    /// How long after a recall feedback still labels it.
+    pub feedback_window_secs: u64,
+}
+
+impl Default for CalibrationConfig {
+    fn default() -> Self {
+        Self {
+            range_window: 1000,
+            min_samples: 50,
+            max_samples: 5000,
+            max_recalls: 1000,
+            feedback_window_secs: 600,
+        }
+    }
+}
+
+impl CalibrationConfig {
+    pub fn validate(&self) -> Result<()> {
+        let field = |name: &str| format!("features.score_calibration.{name}");
+        if self.range_window == 0 {
+            return Err(Error::invalid_field(field("range_window"), "must be > 0"));
+        }
+        if self.min_samples == 0 {
+            return Err(Error::invalid_field(field("min_samples"), "must be > 0"));
+        }
+        if self.max_samples < self.min_samples {
+            return Err(Error::invalid_field(
+                field("max_samples"),
+                "must be at least min_samples",
+            ));
+        }
+        if self.max_recalls == 0 {
+            return Err(Error::invalid_field(field("max_recalls"), "must be > 0"));
+        }
+        Ok(())
+    }
+}
+
+/// How one strategy's raw scores map to relevance.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Calibration {
+    /// Linear from `min` (0) to `max` (1).
+    MinMax { min: f32, max: f32 },
+    /// `(score, useful rate)` knots in score order, interpolated linearly
+    /// between them.
+    Isotonic(Vec<(f32, f32)>),
+}
+
+impl Calibration {
+    pub fn relevance(&self, score: f32) -> f32 {
+        let relevance = match self {
+            Calibration::MinMax { min, max } if max > min => (score - min) / (max - min),
+            Calibration::MinMax { max, .. } => {
+                if score >= *max {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Calibration::Isotonic(knots) => interpolate(knots, score),
+        };
+        if relevance.is_nan() {
+            0.0
+        } else {
+            relevance.clamp(0.0, 1.0)
+        }
+    }
+}
+
+fn interpolate(knots: &[(f32, f32)], score: f32) -> f32 {
+    let (Some(first), Some(last)) = (knots.first(), knots.last()) else {
+        return 0.0;
+    };
+    if score <= first.0 {
+        return first.1;
+    }
+    if score >= last.0 {
+        return last.1;
+    }
+    let upper = knots.partition_point(|(x, _)| *x <= score);
+    let (x0, y0) = knots[upper - 1];
+    let (x1, y1) = knots[upper];
+    y0 + (y1 - y0) * (score - x0) / (x1 - x0)
+}
+
+/// Isotonic (non-decreasing) fit of the useful rate against the score, by
+/// pool adjacent violators.
+fn fit_isotonic(samples: &VecDeque<(f32, bool)>) -> Vec<(f32, f32)> {
+    let mut sorted: Vec<(f32, bool)> = samples.iter().copied().collect();
+    sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+    // (score sum, useful count, samples) per block, starting from one
+    // block per distinct score.
+    let mut ties: Vec<(f32, f64, f64)> = Vec::new();
+    for (score, useful) in sorted {
+        let useful = if useful { 1.0 } else { 0.0 };
+        match ties.last_mut() {
+            Some((last, sy, n)) if *last == score => {
+                *sy += useful;
+                *n += 1.0;
+            }
+            _ => ties.push((score, useful, 1.0)),
+        }
+    }
+    let mut blocks: Vec<(f64, f64, f64)> = Vec::new();
+    for (score, sy, n) in ties {
+        let mut block = (f64::from(score) * n, sy, n);
+        while let Some(&(px, py, pn)) = blocks.last() {
+            if py / pn <= block.1 / block.2 {
+                break;
+            }
+            blocks.pop();
+            block = (block.0 + px, block.1 + py, block.2 + pn);
+        }
+        blocks.push(block);
+    }
+    blocks
+        .into_iter()
+        .map(|(sx, sy, n)| ((sx / n) as f32, (sy / n) as f32))
+        .collect()
+}
+
+/// One strategy's calibration, for reporting.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyCalibration {
+    pub strategy: String,
+    /// `min_max` or `isotonic`.
+    pub method: String,
+    /// Lowest and highest raw score in the range window.
+    pub min_score: Option<f32>,
+    pub max_score: Option<f32>,
+    /// Labelled hits kept.
+    pub samples: usize,
+    /// Share of `samples` reported useful.
+    pub useful_rate: Option<f64>,
+}
+
+#[derive(Default)]
+struct StrategyState {
+    /// The last `range_window` raw scores, oldest first.
+    recent: VecDeque<f32>,
+    samples: VecDeque<(f32, bool)>,
+    fitted: Option<Vec<(f32, f32)>>,
+}
+
+struct RecentRecall {
+    agent_id: String,
+    strategy: String,
+    at: DateTime<Utc>,
+    hits: Vec<(Uuid, f32)>,
+}
+
+#[derive(Default)]
+struct State {
+    strategies: HashMap<String, StrategyState>,
+    recalls: VecDeque<RecentRecall>,
+}
+
+/// The per-strategy score calibration of an engine.
+#[derive(Default)]
+pub struct ScoreCalibrator {
+    config: CalibrationConfig,
+    state: Mutex<State>,
+}
+
+impl StrategyState {
+    fn range(&self) -> Option<(f32, f32)> {
+        self.recent.iter().fold(None, |range, &score| {
+            Some(match range {
+                Some((min, max)) => (f32::min(min, score), f32::max(max, score)),
+                None => (score, score),
+            })
+        })
+    }
+}
+
+impl ScoreCalibrator {
+    pub fn new(config: CalibrationConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::default(),
+        }
+    }
+
+    pub fn config(&self) -> &CalibrationConfig {
+        &self.config
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Add `scores` to `strategy`'s range window and return its
+    /// calibration.
+    pub fn observe(&self, strategy: &str, scores: impl IntoIterator<Item = f32>) -> Calibration {
+        let mut state = self.lock();
+        let entry = state.strategies.entry(strategy.to_string()).or_default();
+        for score in scores.into_iter().filter(|s| s.is_finite()) {
+            if entry.recent.len() >= self.config.range_window {
+                entry.recent.pop_front();
+            }
+            entry.recent.push_back(score);
+        }
+        if let Some(ref knots) = entry.fitted {
+            return Calibration::Isotonic(knots.clone());
+        }
+        if COSINE_STRATEGIES.contains(&strategy) {
+            return Calibration::MinMax { min: 0.0, max: 1.0 };
+        }
+        let (min, max) = entry.range().unwrap_or((0.0, 1.0));
+        Calibration::MinMax { min, max }
+    }
+
+    /// Keep a recall's hits for [`feedback`](Self::feedback) to label.
+    pub fn record(&self, agent_id: &str, strategy: &str, hits: Vec<(Uuid, f32)>) {
+        if hits.is_empty() {
+            return;
+        }
+        let mut state = self.lock();
+        if state.recalls.len() >= self.config.max_recalls {
+            state.recalls.pop_front();
+        }
+        state.recalls.push_back(RecentRecall {
+            agent_id: agent_id.to_string(),
+            strategy: strategy.to_string(),
+            at: Utc::now(),
+            hits,
+        });
+    }
+
+    /// Label the hits of `agent_id`'s recalls within the feedback window:
+    /// those in `useful` as useful, the rest as not. Each recall is
+    /// labelled once, by the first feedback after it. Returns how many
+    /// recalls were labelled.
+    pub fn feedback(&self, agent_id: &str, useful: &[Uuid]) -> usize {
+        let cutoff = Utc::now() - Duration::seconds(self.config.feedback_window_secs as i64);
+        let useful: HashSet<&Uuid> = useful.iter().collect();
+        let mut state = self.lock();
+        let (labelled, kept): (Vec<RecentRecall>, Vec<RecentRecall>) = state
+            .recalls
+            .drain(..)
+            .filter(|recall| recall.at >= cutoff)
+            .partition(|recall| recall.agent_id == agent_id);
+        state.recalls = kept.into();
+
+        let mut refit = HashSet::new();
+        for recall in &labelled {
+            let entry = state.strategies.entry(recall.strategy.clone()).or_default();
+            for (id, score) in &recall.hits {
+                if entry.samples.len() >= self.config.max_samples {
+                    entry.samples.pop_front();
+                }
+                entry.samples.push_back((*score, useful.contains(id)));
+            }
+            refit.insert(recall.strategy.clone());
+        }
+        for strategy in refit {
+            if let Some(entry) = state.strategies.get_mut(&strategy)
+                && entry.samples.len() >= self.config.min_samples
+            {
+                entry.fitted = Some(fit_isotonic(&entry.samples));
+            }
+        }
+        labelled.len()
+    }
+
+    /// Every strategy seen so far, by name.
+    pub fn report(&self) -> Vec<StrategyCalibration> {
+        let state = self.lock();
+        let mut report: Vec<StrategyCalibration> = state
+            .strategies
+            .iter()
+            .map(|(strategy, entry)| {
+                let useful = entry.samples.iter().filter(|(_, useful)| *useful).count();
+                let range = entry.range();
+                StrategyCalibration {
+                    strategy: strategy.clone(),
+                    method: if entry.fitted.is_some() {
+                        "isotonic"
+                    } else {
+                        "min_max"
+                    }
+                    .to_string(),
+                    min_score: range.map(|(min, _)| min),
+                    max_score: range.map(|(_, max)| max),
+                    samples: entry.samples.len(),
+                    useful_rate: (!entry.samples.is_empty())
+                        .then(|| useful as f64 / entry.samples.len() as f64),
+                }
+            })
+            .collect();
+        report.sort_by(|a, b| a.strategy.cmp(&b.strategy));
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_max_follows_the_scores_seen() {
+        let calibrator = ScoreCalibrator::default();
+        let calibration = calibrator.observe("lexical", [2.0, 12.0]);
+        assert_eq!(calibration.relevance(7.0), 0.5);
+        assert_eq!(calibration.relevance(20.0), 1.0);
+        let calibration = calibrator.observe("lexical", [22.0]);
+        assert_eq!(calibration.relevance(12.0), 0.5);
+
+        let cosine = calibrator.observe("semantic", [0.2, 0.4]);
+        assert_eq!(cosine.relevance(0.4), 0.4);
+        assert_eq!(cosine.relevance(-0.3), 0.0);
+    }
+
+    #[test]
+    fn an_outlier_ages_out_of_the_range() {
+        let calibrator = ScoreCalibrator::new(CalibrationConfig {
+            range_window: 3,
+            ..Default::default()
+        });
+        let calibration = calibrator.observe("lexical", [2.0, 12.0, 500.0]);
+        assert!(calibration.relevance(12.0) < 0.05);
+        let calibration = calibrator.observe("lexical", [2.0, 12.0, 7.0]);
+        assert_eq!(calibration.relevance(12.0), 1.0);
+        assert_eq!(calibration.relevance(7.0), 0.5);
+        assert_eq!(calibrator.report()[0].max_score, Some(12.0));
+    }
+
+    #[test]
+    fn feedback_fits_a_non_decreasing_useful_rate() {
+        let calibrator = ScoreCalibrator::new(CalibrationConfig {
+            min_samples: 8,
+            ..Default::default()
+        });
+        let ids: Vec<Uuid> = (0..4).map(|_| Uuid::now_v7()).collect();
+        let hits = |scores: [f32; 4]| ids.iter().copied().zip(scores).collect::<Vec<_>>();
+
+        calibrator.record("agent", "hybrid", hits([0.04, 0.03, 0.02, 0.01]));
+        calibrator.record("other", "hybrid", hits([0.04, 0.03, 0.02, 0.01]));
+        assert_eq!(calibrator.feedback("agent", &ids[..1]), 1);
+        // Labelled once; the other agent's recall is still waiting.
+        assert_eq!(calibrator.feedback("agent", &ids[..1]), 0);
+        assert!(matches!(
+            calibrator.observe("hybrid", []),
+            Calibration::MinMax { .. }
+        ));
+
+        calibrator.record("agent", "hybrid", hits([0.04, 0.03, 0.02, 0.01]));
+        assert_eq!(calibrator.feedback("agent", &ids[..2]), 1);
+        let calibration = calibrator.observe("hybrid", []);
+        assert!(matches!(calibration, Calibration::Isotonic(_)));
+        assert_eq!(calibration.relevance(0.04), 1.0);
+        assert_eq!(calibration.relevance(0.03), 0.5);
+        assert_eq!(calibration.relevance(0.01), 0.0);
+        let rising: Vec<f32> = [0.0, 0.015, 0.025, 0.035, 0.05]
+            .into_iter()
+            .map(|s| calibration.relevance(s))
+            .collect();
+        assert!(rising.windows(2).all(|w| w[0] <= w[1]), "{rising:?}");
+
+        let report = calibrator.report();
+        assert_eq!(report[0].method, "isotonic");
+        assert_eq!(report[0].samples, 8);
+        assert_eq!(report[0].useful_rate, Some(3.0 / 8.0));
+    }
+
+    #[test]
+    fn pooling_merges_violators() {
+        let samples: VecDeque<(f32, bool)> =
+            [(1.0, true), (2.0, false), (3.0, true), (4.0, true)].into();
+        assert_eq!(
+            fit_isotonic(&samples),
+            vec![(1.5, 0.5), (3.0, 1.0), (4.0, 1.0)]
+        );
+    }
+}
@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use uuid::Uuid;
 
-use crate::error::Result;
+use crate::error::{Error, Result};
+use crate::model::acl::Permission;
 use crate::model::event::{AgentEvent, EventType};
 use crate::query::MnemoEngine;
 
@@ -38,6 +39,11 @@ pub struct CausalNode {
 /// - `event_type_filter`: when `Some`, only events matching the given `EventType` are included
 ///   in the returned nodes. However, traversal still proceeds through non-matching events to
 ///   preserve connectivity (i.e., filtering is applied to output, not to graph exploration).
+///
+/// The trace runs as the engine's default agent. Events of other agents
+/// need [`Permission::Replay`] through an all-memories delegation: the call
+/// fails if the root event is off limits, and other off-limits events are
+/// left out of the returned nodes.
 pub async fn trace_causality(
     engine: &MnemoEngine,
     event_id: Uuid,
@@ -50,7 +56,17 @@ pub async fn trace_causality(
         .storage
         .get_event(event_id)
         .await?
-        .ok_or_else(|| crate::error::Error::NotFound(format!("event {event_id} not found")))?;
+        .ok_or_else(|| Error::NotFound(format!("event {event_id} not found")))?;
+
+    let caller = engine.default_agent_id.as_str();
+    let mut replayable: HashMap<String, bool> = HashMap::new();
+    replayable.insert(caller.to_string(), true);
+    if !may_replay(engine, &mut replayable, caller, &root_event.agent_id).await? {
+        return Err(Error::PermissionDenied(format!(
+            "agent {caller} lacks replay access to events of agent {}",
+            root_event.agent_id
+        )));
+    }
 
     let mut seen = HashSet::new();
     let mut nodes: Vec<CausalNode> = Vec::new();
@@ -97,7 +113,9 @@ pub async fn trace_causality(
             depth += 1;
             actual_depth = actual_depth.max(depth);
 
-            if passes_filter(&parent_event) {
+            if passes_filter(&parent_event)
+                && may_replay(engine, &mut replayable, caller, &parent_event.agent_id).await?
+            {
                 nodes.push(CausalNode {
                     event: parent_event.clone(),
                     children: vec![current_event.id],
@@ -135,7 +153,9 @@ pub async fn trace_causality(
                 let child_depth = current_depth + 1;
                 actual_depth = actual_depth.max(child_depth);
 
-                if passes_filter(&child_event) {
+                if passes_filter(&child_event)
+                    && may_replay(engine, &mut replayable, caller, &child_event.agent_id).await?
+                {
                     nodes.push(CausalNode {
                         event: child_event.clone(),
                         children: Vec::new(),
@@ -156,6 +176,22 @@ pub async fn trace_causality(
     })
 }
 
+/// Whether `caller` may see `owner`'s events, memoized per owner.
+async fn may_replay(
+    engine: &MnemoEngine,
+    replayable: &mut HashMap<String, bool>,
+    caller: &str,
+    owner: &str,
+) -> Result<bool> {
+    if let Some(&allowed) = replayable.get(owner) {
+        return Ok(allowed);
+    }
+    let allowed =
+        super::checkpoint::delegated_all(engine, owner, caller, Permission::Replay).await?;
+    replayable.insert(owner.to_string(), allowed);
+    Ok(allowed)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -237,6 +273,7 @@ mod tests {
                 content_hash: vec![],
                 prev_hash: None,
                 embedding: None,
+                request_id: None,
             }
         };
 
@@ -0,0 +1,103 @@
+//! Applying memory changes made by other processes.
+//!
+//! When several processes share one storage backend, each keeps its own
+//! record cache, vector index and full-text index, and those go stale as
+//! soon as another process writes. A backend that can broadcast its writes
+//! (PostgreSQL does with `LISTEN`/`NOTIFY`) turns them into [`MemoryChange`]s;
+//! [`spawn_change_listener`] applies each one to this engine:
+//!
+//! - the cached record is always dropped;
+//! - a deleted memory leaves the vector and full-text indexes;
+//! - an inserted or updated memory is re-read from storage and re-indexed,
+//!   or removed from the indexes when it is now deleted, archived or
+//!   quarantined.
+//!
+//! Backends skip their own process's writes, which are already applied.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::index::ShardRoute;
+use crate::model::memory::ConsolidationState;
+use crate::query::MnemoEngine;
+use crate::search::language::LANGUAGE_METADATA_KEY;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", content = "memory_id", rename_all = "snake_case")]
+pub enum MemoryChange {
+    Inserted(Uuid),
+    Updated(Uuid),
+    Deleted(Uuid),
+    /// Changes may have been missed, e.g. while the feed reconnected. Drops
+    /// every cached record.
+    Resync,
+}
+
+pub async fn apply_change(engine: &MnemoEngine, change: MemoryChange) -> Result<()> {
+    let id = match change {
+        MemoryChange::Inserted(id) | MemoryChange::Updated(id) | MemoryChange::Deleted(id) => id,
+        MemoryChange::Resync => {
+            if let Some(ref cache) = engine.cache {
+                cache.clear();
+            }
+            tracing::warn!(
+                "memory change feed resynced; indexes may miss remote writes until re-indexed"
+            );
+            return Ok(());
+        }
+    };
+    if let Some(ref cache) = engine.cache {
+        cache.invalidate(id);
+    }
+
+    let live = match change {
+        MemoryChange::Deleted(_) => None,
+        _ => engine.storage.get_memory(id).await?.filter(|record| {
+            !record.is_deleted()
+                && !record.quarantined
+                && record.consolidation_state != ConsolidationState::Archived
+        }),
+    };
+
+    engine.index.remove(id)?;
+    if let Some(ref ft) = engine.full_text {
+        ft.remove(id)?;
+    }
+    if let Some(ref record) = live {
+        if let Some(ref embedding) = record.embedding {
+            engine
+                .index
+                .add_routed(ShardRoute::of(record), id, embedding)?;
+        }
+        if let Some(ref ft) = engine.full_text {
+            let language = record
+                .metadata
+                .get(LANGUAGE_METADATA_KEY)
+                .and_then(|v| v.as_str());
+            ft.add_with_language(id, &record.content, language)?;
+        }
+    }
+    if let Some(ref ft) = engine.full_text {
+        ft.commit()?;
+    }
+    Ok(())
+}
+
+/// Apply every change received on `changes` until the sender is dropped.
+/// Failures are logged and do not stop the listener.
+pub fn spawn_change_listener(
+    engine: Arc<MnemoEngine>,
+    mut changes: mpsc::Receiver<MemoryChange>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        while let Some(change) = changes.recv().await {
+            if let Err(e) = apply_change(&engine, change).await {
+                tracing::warn!(?change, error = %e, "failed to apply remote memory change");
+            }
+        }
+    })
+}
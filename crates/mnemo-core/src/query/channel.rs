@@ -0,0 +1,274 @@
+//! Channels: named memory spaces agents subscribe to.
+//!
+//! An agent creates a channel and becomes its first member; any agent can
+//! then [`join`] it. Remembering with `RememberRequest::channel` stores a
+//! shared memory in the channel, and every member can read it without
+//! per-memory ACLs: membership is checked when the memory is read, so an
+//! agent that joins later sees the channel's history and one that leaves
+//! loses access at once. `RecallRequest::channel` recalls from one channel
+//! only.
+//!
+//! Only members write into a channel. Its creator can cap it with
+//! `max_memories`; remembering into a full channel fails until memories in
+//! it are forgotten. The cap is checked before each write, so concurrent
+//! writers can overshoot it by a few memories.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::model::channel::{Channel, ChannelMember};
+use crate::query::MnemoEngine;
+
+/// Longest accepted channel name.
+const MAX_CHANNEL_NAME_LEN: usize = 64;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateChannelRequest {
+    pub name: String,
+    /// The creator; defaults to the engine's agent.
+    pub agent_id: Option<String>,
+    /// Most live memories the channel may hold. `None` is unlimited.
+    pub max_memories: Option<u64>,
+}
+
+impl CreateChannelRequest {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            agent_id: None,
+            max_memories: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelMembershipRequest {
+    pub channel: String,
+    /// The acting agent; defaults to the engine's agent.
+    pub agent_id: Option<String>,
+    /// When leaving: the member to remove, defaulting to the acting agent.
+    /// Only the channel's creator may remove someone else.
+    pub member_id: Option<String>,
+}
+
+impl ChannelMembershipRequest {
+    pub fn new(channel: String) -> Self {
+        Self {
+            channel,
+            agent_id: None,
+            member_id: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetChannelQuotaRequest {
+    pub channel: String,
+    /// Must be the channel's creator; defaults to the engine's agent.
+    pub agent_id: Option<String>,
+    /// `None` removes the cap.
+    pub max_memories: Option<u64>,
+}
+
+impl SetChannelQuotaRequest {
+    pub fn new(channel: String, max_memories: Option<u64>) -> Self {
+        Self {
+            channel,
+            agent_id: None,
+            max_memories,
+        }
+    }
+}
+
+fn validate_channel_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.len() > MAX_CHANNEL_NAME_LEN {
+        return Err(Error::invalid_field(
+            "channel",
+            format!("must be 1-{MAX_CHANNEL_NAME_LEN} characters"),
+        ));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(Error::invalid_field(
+            "channel",
+            format!("'{name}' may only contain letters, digits, '-', '_' and '.'"),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_quota(max_memories: Option<u64>) -> Result<()> {
+    if max_memories == Some(0) {
+        return Err(Error::invalid_field(
+            "max_memories",
+            "must be greater than 0",
+        ));
+    }
+    Ok(())
+}
+
+async fn load(engine: &MnemoEngine, name: &str) -> Result<Channel> {
+    validate_channel_name(name)?;
+    engine
+        .storage
+        .get_channel(name)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("channel {name} not found")))
+}
+
+/// Create a channel. The creator joins it.
+pub async fn create(engine: &MnemoEngine, request: CreateChannelRequest) -> Result<Channel> {
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    validate_channel_name(&request.name)?;
+    validate_quota(request.max_memories)?;
+    if engine.storage.get_channel(&request.name).await?.is_some() {
+        return Err(Error::Conflict(format!(
+            "channel {} already exists",
+            request.name
+        )));
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let channel = Channel {
+        name: request.name,
+        created_by: agent_id.clone(),
+        max_memories: request.max_memories,
+        created_at: now.clone(),
+    };
+    engine.storage.insert_channel(&channel).await?;
+    engine
+        .storage
+        .add_channel_member(&ChannelMember {
+            channel: channel.name.clone(),
+            agent_id,
+            joined_at: now,
+        })
+        .await?;
+    Ok(channel)
+}
+
+/// Subscribe the acting agent to a channel. Joining twice is a no-op.
+pub async fn join(
+    engine: &MnemoEngine,
+    request: ChannelMembershipRequest,
+) -> Result<ChannelMember> {
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    let channel = load(engine, &request.channel).await?;
+    let member = ChannelMember {
+        channel: channel.name,
+        agent_id,
+        joined_at: chrono::Utc::now().to_rfc3339(),
+    };
+    engine.storage.add_channel_member(&member).await?;
+    Ok(member)
+}
+
+/// Remove a member from a channel. Its memories stay in the channel.
+pub async fn leave(engine: &MnemoEngine, request: ChannelMembershipRequest) -> Result<()> {
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    let member_id = request.member_id.unwrap_or_else(|| agent_id.clone());
+    super::validate_agent_id(&member_id)?;
+    let channel = load(engine, &request.channel).await?;
+    if member_id != agent_id && channel.created_by != agent_id {
+        return Err(Error::PermissionDenied(format!(
+            "agent {agent_id} cannot remove members of channel {}",
+            channel.name
+        )));
+    }
+    if !engine
+        .storage
+        .remove_channel_member(&channel.name, &member_id)
+        .await?
+    {
+        return Err(Error::NotFound(format!(
+            "agent {member_id} is not a member of channel {}",
+            channel.name
+        )));
+    }
+    Ok(())
+}
+
+/// The members of a channel, in join order. Only members may list them.
+pub async fn members(
+    engine: &MnemoEngine,
+    channel: &str,
+    agent_id: Option<String>,
+) -> Result<Vec<ChannelMember>> {
+    let agent_id = agent_id.unwrap_or_else(|| engine.default_agent_id.clone());
+    check_read(engine, channel, &agent_id).await?;
+    engine.storage.list_channel_members(channel).await
+}
+
+/// Channels by name; with `member`, only the channels that agent belongs to.
+pub async fn list(engine: &MnemoEngine, member: Option<String>) -> Result<Vec<Channel>> {
+    if let Some(ref agent_id) = member {
+        super::validate_agent_id(agent_id)?;
+    }
+    engine.storage.list_channels(member.as_deref()).await
+}
+
+/// Change a channel's memory cap. Only its creator may. Lowering the cap
+/// below the channel's current size blocks new writes; nothing is removed.
+pub async fn set_quota(engine: &MnemoEngine, request: SetChannelQuotaRequest) -> Result<Channel> {
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    validate_quota(request.max_memories)?;
+    let mut channel = load(engine, &request.channel).await?;
+    if channel.created_by != agent_id {
+        return Err(Error::PermissionDenied(format!(
+            "agent {agent_id} cannot change the quota of channel {}",
+            channel.name
+        )));
+    }
+    channel.max_memories = request.max_memories;
+    engine.storage.update_channel(&channel).await?;
+    Ok(channel)
+}
+
+/// Fail unless `agent_id` is a member of the channel.
+pub(crate) async fn check_read(engine: &MnemoEngine, channel: &str, agent_id: &str) -> Result<()> {
+    load_as_member(engine, channel, agent_id).await.map(|_| ())
+}
+
+/// Fail unless `agent_id` is a member of the channel and the channel has
+/// room for one more memory.
+pub(crate) async fn check_write(engine: &MnemoEngine, channel: &str, agent_id: &str) -> Result<()> {
+    let channel = load_as_member(engine, channel, agent_id).await?;
+    if let Some(max) = channel.max_memories
+        && engine.storage.count_channel_memories(&channel.name).await? >= max
+    {
+        return Err(Error::QuotaExceeded(format!(
+            "channel {} is full ({max} memories)",
+            channel.name
+        )));
+    }
+    Ok(())
+}
+
+async fn load_as_member(engine: &MnemoEngine, name: &str, agent_id: &str) -> Result<Channel> {
+    let channel = load(engine, name).await?;
+    if !engine
+        .storage
+        .is_channel_member(&channel.name, agent_id)
+        .await?
+    {
+        return Err(Error::PermissionDenied(format!(
+            "agent {agent_id} is not a member of channel {}",
+            channel.name
+        )));
+    }
+    Ok(channel)
+}
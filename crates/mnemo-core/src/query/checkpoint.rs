@@ -1,8 +1,25 @@
+//! Thread checkpoints.
+//!
+//! Agent states can run to megabytes and change little between checkpoints,
+//! so most checkpoints store only a delta: a JSON Merge Patch (RFC 7396)
+//! from the parent's state, kept in `state_diff` under
+//! [`MERGE_PATCH_KEY`] with a null `state_snapshot`. Every
+//! [`MnemoEngine::checkpoint_snapshot_interval`]-th checkpoint of a chain
+//! stores the full state again, which bounds how many patches a read
+//! applies. A change a merge patch cannot express (a value set to `null`
+//! inside an object) or a patch no smaller than the state is also stored in
+//! full. Replay, branch and merge see full states through [`materialize`].
+//! Snapshots larger than
+//! [`LimitsConfig::max_snapshot_bytes`](super::limits::LimitsConfig::max_snapshot_bytes)
+//! are rejected.
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::error::Result;
+use crate::error::{Error, Result};
+use crate::model::acl::Permission;
 use crate::model::checkpoint::Checkpoint;
+use crate::model::delegation::DelegationScope;
 use crate::model::event::EventType;
 use crate::query::MnemoEngine;
 use crate::storage::MemoryFilter;
@@ -48,13 +65,25 @@ impl CheckpointResponse {
     }
 }
 
+/// Full snapshot every this many checkpoints of a chain, unless
+/// overridden with [`MnemoEngine::with_checkpoint_snapshot_interval`].
+pub const DEFAULT_CHECKPOINT_SNAPSHOT_INTERVAL: u32 = 10;
+
+/// Key of the merge patch in a delta checkpoint's `state_diff`.
+pub const MERGE_PATCH_KEY: &str = "merge_patch";
+
+/// Longest parent chain [`materialize`] walks before giving up.
+const MAX_DELTA_CHAIN: u32 = 10_000;
+
 pub async fn execute(
     engine: &MnemoEngine,
     request: CheckpointRequest,
 ) -> Result<CheckpointResponse> {
+    engine.limits().check_snapshot(&request.state_snapshot)?;
     let agent_id = request
         .agent_id
         .unwrap_or_else(|| engine.default_agent_id.clone());
+    ensure_thread_access(engine, &request.thread_id, &agent_id, Permission::Write).await?;
     let branch_name = request.branch_name.unwrap_or_else(|| "main".to_string());
     let now = chrono::Utc::now().to_rfc3339();
 
@@ -66,13 +95,22 @@ pub async fn execute(
 
     let parent_id = parent.as_ref().map(|p| p.id);
 
-    // Compute state_diff from parent
-    let state_diff = parent.as_ref().map(|p| {
-        serde_json::json!({
-            "from": p.state_snapshot,
-            "to": request.state_snapshot,
-        })
-    });
+    // Store a delta against the parent unless this checkpoint is due for a
+    // full snapshot or the change is not worth (or able to be) a patch.
+    let mut state_snapshot = request.state_snapshot;
+    let mut state_diff = None;
+    if let Some(ref parent) = parent
+        && engine.checkpoint_snapshot_interval > 1
+    {
+        let (parent_state, depth) = resolve(engine, parent).await?;
+        if depth + 1 < engine.checkpoint_snapshot_interval
+            && let Some(patch) = merge_patch_diff(&parent_state, &state_snapshot)
+            && serde_json::to_vec(&patch)?.len() < serde_json::to_vec(&state_snapshot)?.len()
+        {
+            state_diff = Some(serde_json::json!({ MERGE_PATCH_KEY: patch }));
+            state_snapshot = serde_json::Value::Null;
+        }
+    }
 
     // Collect memory_refs — active memories for this agent
     let filter = MemoryFilter {
@@ -93,7 +131,7 @@ pub async fn execute(
         agent_id: agent_id.clone(),
         parent_id,
         branch_name: branch_name.clone(),
-        state_snapshot: request.state_snapshot,
+        state_snapshot,
         state_diff,
         memory_refs,
         event_cursor,
@@ -105,6 +143,9 @@ pub async fn execute(
     };
 
     engine.storage.insert_checkpoint(&cp).await?;
+    if branch_name == "main" {
+        engine.pending_writes.clear(&agent_id, &request.thread_id);
+    }
 
     // Emit Checkpoint event
     let event = super::event_builder::build_event(
@@ -140,3 +181,163 @@ pub async fn execute(
         branch_name,
     })
 }
+
+/// `checkpoint` with its full state in `state_snapshot`, reconstructed from
+/// its parents if it is stored as a delta.
+pub async fn materialize(engine: &MnemoEngine, mut checkpoint: Checkpoint) -> Result<Checkpoint> {
+    if delta_of(&checkpoint).is_some() {
+        checkpoint.state_snapshot = resolve(engine, &checkpoint).await?.0;
+        checkpoint.state_diff = None;
+    }
+    Ok(checkpoint)
+}
+
+fn delta_of(checkpoint: &Checkpoint) -> Option<&serde_json::Value> {
+    checkpoint.state_diff.as_ref()?.get(MERGE_PATCH_KEY)
+}
+
+/// The full state of `checkpoint` and how many deltas separate it from the
+/// nearest full snapshot.
+async fn resolve(
+    engine: &MnemoEngine,
+    checkpoint: &Checkpoint,
+) -> Result<(serde_json::Value, u32)> {
+    let mut patches = Vec::new();
+    let mut current = checkpoint.clone();
+    while let Some(patch) = delta_of(&current) {
+        if patches.len() as u32 >= MAX_DELTA_CHAIN {
+            return Err(Error::Internal(format!(
+                "checkpoint {} has more than {MAX_DELTA_CHAIN} deltas",
+                checkpoint.id
+            )));
+        }
+        patches.push(patch.clone());
+        let parent_id = current.parent_id.ok_or_else(|| {
+            Error::Internal(format!("delta checkpoint {} has no parent", current.id))
+        })?;
+        current = engine
+            .storage
+            .get_checkpoint(parent_id)
+            .await?
+            .ok_or_else(|| {
+                Error::Internal(format!(
+                    "checkpoint {parent_id}, base of delta checkpoint {}, is missing",
+                    current.id
+                ))
+            })?;
+    }
+    let depth = patches.len() as u32;
+    let mut state = current.state_snapshot;
+    for patch in patches.iter().rev() {
+        apply_merge_patch(&mut state, patch);
+    }
+    Ok((state, depth))
+}
+
+/// The merge patch turning `from` into `to`, or `None` when one cannot
+/// express the change.
+fn merge_patch_diff(from: &serde_json::Value, to: &serde_json::Value) -> Option<serde_json::Value> {
+    match (from, to) {
+        (serde_json::Value::Object(from), serde_json::Value::Object(to)) => {
+            let mut patch = serde_json::Map::new();
+            for (key, old) in from {
+                match to.get(key) {
+                    None => {
+                        patch.insert(key.clone(), serde_json::Value::Null);
+                    }
+                    Some(new) if new != old => {
+                        patch.insert(key.clone(), merge_patch_diff(old, new)?);
+                    }
+                    Some(_) => {}
+                }
+            }
+            for (key, new) in to {
+                if !from.contains_key(key) {
+                    if !patchable(new) {
+                        return None;
+                    }
+                    patch.insert(key.clone(), new.clone());
+                }
+            }
+            Some(serde_json::Value::Object(patch))
+        }
+        _ => patchable(to).then(|| to.clone()),
+    }
+}
+
+/// Whether a merge patch can set a member to `value`: null means "remove",
+/// so neither `value` nor any object member below it may be null. Arrays
+/// are replaced whole, so nulls inside them are fine.
+fn patchable(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Null => false,
+        serde_json::Value::Object(map) => map.values().all(patchable),
+        _ => true,
+    }
+}
+
+fn apply_merge_patch(target: &mut serde_json::Value, patch: &serde_json::Value) {
+    let serde_json::Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = serde_json::Value::Object(serde_json::Map::new());
+    }
+    if let serde_json::Value::Object(map) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                map.remove(key);
+            } else {
+                apply_merge_patch(
+                    map.entry(key.clone()).or_insert(serde_json::Value::Null),
+                    value,
+                );
+            }
+        }
+    }
+}
+
+/// Whether `owner` has given `agent_id` an unrevoked, unexpired
+/// all-memories delegation granting `required`.
+pub(crate) async fn delegated_all(
+    engine: &MnemoEngine,
+    owner: &str,
+    agent_id: &str,
+    required: Permission,
+) -> Result<bool> {
+    Ok(engine
+        .storage
+        .list_delegations_for(agent_id)
+        .await?
+        .iter()
+        .any(|d| {
+            d.delegator_id == owner
+                && d.scope == DelegationScope::AllMemories
+                && d.permission.satisfies(required)
+        }))
+}
+
+/// Fail unless `agent_id` may act on `thread_id` with `required`
+/// permission. A thread belongs to the agent that wrote its first memory or
+/// event (or, for a thread of checkpoints only, its first checkpoint); see
+/// [`StorageBackend::thread_owner`](crate::storage::StorageBackend::thread_owner).
+/// Others need an unrevoked, unexpired all-memories delegation from that
+/// agent. A thread nothing has been written to yet has no owner.
+pub(crate) async fn ensure_thread_access(
+    engine: &MnemoEngine,
+    thread_id: &str,
+    agent_id: &str,
+    required: Permission,
+) -> Result<()> {
+    let Some(owner) = engine.storage.thread_owner(thread_id).await? else {
+        return Ok(());
+    };
+    let owner = owner.as_str();
+    if owner == agent_id || delegated_all(engine, owner, agent_id, required).await? {
+        return Ok(());
+    }
+    Err(Error::PermissionDenied(format!(
+        "agent {agent_id} lacks {required} access to thread '{thread_id}' of agent {owner}"
+    )))
+}
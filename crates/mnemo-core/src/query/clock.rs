@@ -0,0 +1,154 @@
+//! Per-agent logical clocks for audit events.
+//!
+//! Wall-clock timestamps can go backwards (NTP steps, hosts with skewed
+//! clocks writing to shared storage), so they are a poor basis for ordering
+//! an agent's history. Every event the engine writes gets the next value of
+//! its agent's logical clock: strictly increasing within the process and
+//! seeded from the highest stored clock the first time an agent writes, so
+//! it keeps increasing across restarts. Events written before clocks were
+//! assigned keep `logical_clock = 0` and order by timestamp before the rest.
+//!
+//! Processes sharing one storage backend each keep their own counter, so two
+//! of them writing for the same agent can hand out the same value.
+//! [`detect_skew`] reports such repeats, together with events whose
+//! timestamp runs behind an earlier clock value.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::model::event::AgentEvent;
+use crate::query::MnemoEngine;
+
+/// The last clock value handed out per agent.
+#[derive(Debug, Default)]
+pub(crate) struct LogicalClocks {
+    agents: Mutex<HashMap<String, i64>>,
+}
+
+impl LogicalClocks {
+    fn advance(&self, agent_id: &str, floor: i64) -> i64 {
+        let mut agents = self.agents.lock().unwrap_or_else(|e| e.into_inner());
+        let clock = agents.entry(agent_id.to_string()).or_insert(0);
+        *clock = (*clock).max(floor) + 1;
+        *clock
+    }
+
+    fn unseed(&self, agent_id: &str) {
+        self.agents
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(agent_id);
+    }
+
+    fn is_seeded(&self, agent_id: &str) -> bool {
+        self.agents
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains_key(agent_id)
+    }
+}
+
+/// The next logical clock value for `agent_id`. The first call per agent
+/// reads the highest stored value; if that fails the value is only
+/// process-local and the read is retried on the next call.
+pub(crate) async fn tick(engine: &MnemoEngine, agent_id: &str) -> i64 {
+    if engine.clocks.is_seeded(agent_id) {
+        return engine.clocks.advance(agent_id, 0);
+    }
+    match engine.storage.max_logical_clock(agent_id).await {
+        Ok(stored) => engine.clocks.advance(agent_id, stored),
+        Err(e) => {
+            tracing::warn!(agent_id, error = %e, "failed to load logical clock, using process-local value");
+            let clock = engine.clocks.advance(agent_id, 0);
+            engine.clocks.unseed(agent_id);
+            clock
+        }
+    }
+}
+
+/// Order events by agent, logical clock, timestamp and id.
+pub fn compare_events(a: &AgentEvent, b: &AgentEvent) -> Ordering {
+    (&a.agent_id, a.logical_clock, &a.timestamp, a.id).cmp(&(
+        &b.agent_id,
+        b.logical_clock,
+        &b.timestamp,
+        b.id,
+    ))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkewKind {
+    /// The event's timestamp is earlier than that of the agent's event with
+    /// the previous clock value.
+    TimestampBehind,
+    /// Two events of the agent carry the same clock value.
+    DuplicateClock,
+}
+
+impl SkewKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SkewKind::TimestampBehind => "timestamp_behind",
+            SkewKind::DuplicateClock => "duplicate_clock",
+        }
+    }
+}
+
+/// One clock inconsistency between two consecutive events of an agent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClockSkew {
+    pub kind: SkewKind,
+    pub agent_id: String,
+    pub event_id: Uuid,
+    pub previous_event_id: Uuid,
+    pub logical_clock: i64,
+    pub timestamp: String,
+    pub previous_timestamp: String,
+}
+
+/// Compare each clocked event with the same agent's event right before it
+/// in clock order. Events without a clock are skipped.
+pub fn detect_skew(events: &[AgentEvent]) -> Vec<ClockSkew> {
+    let mut clocked: Vec<&AgentEvent> = events.iter().filter(|e| e.logical_clock > 0).collect();
+    clocked.sort_by(|a, b| compare_events(a, b));
+
+    let mut skews = Vec::new();
+    for pair in clocked.windows(2) {
+        let (previous, event) = (pair[0], pair[1]);
+        if previous.agent_id != event.agent_id {
+            continue;
+        }
+        let kind = if previous.logical_clock == event.logical_clock {
+            SkewKind::DuplicateClock
+        } else if timestamp_before(&event.timestamp, &previous.timestamp) {
+            SkewKind::TimestampBehind
+        } else {
+            continue;
+        };
+        skews.push(ClockSkew {
+            kind,
+            agent_id: event.agent_id.clone(),
+            event_id: event.id,
+            previous_event_id: previous.id,
+            logical_clock: event.logical_clock,
+            timestamp: event.timestamp.clone(),
+            previous_timestamp: previous.timestamp.clone(),
+        });
+    }
+    skews
+}
+
+fn timestamp_before(a: &str, b: &str) -> bool {
+    match (
+        chrono::DateTime::parse_from_rfc3339(a),
+        chrono::DateTime::parse_from_rfc3339(b),
+    ) {
+        (Ok(a), Ok(b)) => a < b,
+        _ => a < b,
+    }
+}
@@ -0,0 +1,283 @@
+//! Topic map of an agent's memories.
+//!
+//! [`cluster_memories`] runs spherical k-means over the stored embeddings
+//! of one agent's live memories and describes every cluster by its size,
+//! centroid, most distinctive terms and the memories closest to its
+//! centroid. It gives operators a bird's-eye view of what an agent knows
+//! without reading memories one by one; the admin dashboard's topic panel
+//! is built on it.
+//!
+//! Seeding is farthest-first from the oldest memory, so the same corpus
+//! always yields the same map. Terms are ranked by class-based TF-IDF: how
+//! often a word occurs in the cluster, weighted down when it is common in
+//! every cluster.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::memory::MemoryRecord;
+use crate::query::MnemoEngine;
+use crate::storage::MemoryFilter;
+
+/// Most clusters one call may ask for.
+pub const MAX_CLUSTERS: usize = 64;
+/// Terms reported per cluster.
+const TOP_TERMS: usize = 5;
+/// Representative memories reported per cluster.
+const REPRESENTATIVES: usize = 3;
+/// k-means stops after this many rounds even if assignments still move.
+const MAX_ITERATIONS: usize = 50;
+
+/// Words too common to describe a topic.
+const STOPWORDS: &[&str] = &[
+    "about", "after", "again", "also", "and", "are", "because", "been", "before", "but", "can",
+    "could", "did", "does", "for", "from", "had", "has", "have", "her", "his", "how", "into",
+    "its", "just", "more", "most", "not", "now", "only", "other", "our", "over", "said", "she",
+    "should", "some", "than", "that", "the", "their", "them", "then", "there", "these", "they",
+    "this", "those", "through", "too", "under", "very", "was", "were", "what", "when", "where",
+    "which", "while", "who", "why", "will", "with", "would", "you", "your",
+];
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicMap {
+    pub agent_id: String,
+    /// Memories with an embedding that were clustered.
+    pub memory_count: usize,
+    /// Largest first.
+    pub clusters: Vec<TopicCluster>,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopicCluster {
+    /// Position in `clusters`.
+    pub id: usize,
+    pub size: usize,
+    /// Unit-length mean of the members' embeddings.
+    pub centroid: Vec<f32>,
+    /// Words that best set this cluster apart, most distinctive first.
+    pub top_terms: Vec<String>,
+    /// Members closest to the centroid, closest first.
+    pub representatives: Vec<ClusterMember>,
+    /// Mean cosine similarity of the members to the centroid.
+    pub cohesion: f32,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterMember {
+    pub memory_id: Uuid,
+    pub content: String,
+    /// Cosine similarity to the cluster centroid.
+    pub similarity: f32,
+}
+
+/// Group `agent_id`'s memories into at most `k` topics.
+pub async fn cluster_memories(
+    engine: &MnemoEngine,
+    agent_id: Option<String>,
+    k: usize,
+) -> Result<TopicMap> {
+    let agent_id = agent_id.unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    if !(1..=MAX_CLUSTERS).contains(&k) {
+        return Err(Error::invalid_field(
+            "k",
+            format!("must be between 1 and {MAX_CLUSTERS}"),
+        ));
+    }
+
+    let filter = MemoryFilter {
+        agent_id: Some(agent_id.clone()),
+        include_deleted: false,
+        ..Default::default()
+    };
+    let mut records: Vec<MemoryRecord> = engine
+        .storage
+        .list_memories(&filter, super::MAX_BATCH_QUERY_LIMIT, 0)
+        .await?
+        .into_iter()
+        .filter(|r| {
+            !r.is_deleted() && !r.quarantined && r.embedding.as_ref().is_some_and(|e| !e.is_empty())
+        })
+        .collect();
+    records.sort_by(|a, b| (&a.created_at, a.id).cmp(&(&b.created_at, b.id)));
+    if records.is_empty() {
+        return Ok(TopicMap {
+            agent_id,
+            memory_count: 0,
+            clusters: Vec::new(),
+        });
+    }
+
+    let vectors: Vec<Vec<f32>> = records
+        .iter()
+        .map(|r| normalized(r.embedding.as_deref().unwrap_or_default()))
+        .collect();
+    let (centroids, assignment) = kmeans(&vectors, k.min(records.len()));
+
+    for record in &mut records {
+        super::consolidate::decrypt_in_place(engine, record);
+    }
+    let terms_per_cluster = cluster_terms(&records, &assignment, centroids.len());
+
+    let mut clusters: Vec<TopicCluster> = centroids
+        .into_iter()
+        .enumerate()
+        .filter_map(|(c, centroid)| {
+            let mut members: Vec<(usize, f32)> = assignment
+                .iter()
+                .enumerate()
+                .filter(|(_, a)| **a == c)
+                .map(|(i, _)| (i, dot(&vectors[i], &centroid)))
+                .collect();
+            if members.is_empty() {
+                return None;
+            }
+            members.sort_by(|a, b| b.1.total_cmp(&a.1));
+            let cohesion = members.iter().map(|(_, s)| s).sum::<f32>() / members.len() as f32;
+            Some(TopicCluster {
+                id: 0,
+                size: members.len(),
+                top_terms: terms_per_cluster[c].clone(),
+                representatives: members
+                    .iter()
+                    .take(REPRESENTATIVES)
+                    .map(|&(i, similarity)| ClusterMember {
+                        memory_id: records[i].id,
+                        content: records[i].content.clone(),
+                        similarity,
+                    })
+                    .collect(),
+                centroid,
+                cohesion,
+            })
+        })
+        .collect();
+    clusters.sort_by_key(|c| std::cmp::Reverse(c.size));
+    for (id, cluster) in clusters.iter_mut().enumerate() {
+        cluster.id = id;
+    }
+
+    Ok(TopicMap {
+        agent_id,
+        memory_count: records.len(),
+        clusters,
+    })
+}
+
+/// Spherical k-means over unit vectors: centroids and each vector's
+/// cluster.
+fn kmeans(vectors: &[Vec<f32>], k: usize) -> (Vec<Vec<f32>>, Vec<usize>) {
+    // Farthest-first seeding: start from the first vector, then repeatedly
+    // take the vector least similar to every seed chosen so far.
+    let mut centroids = vec![vectors[0].clone()];
+    let mut best_similarity: Vec<f32> = vectors.iter().map(|v| dot(v, &vectors[0])).collect();
+    while centroids.len() < k {
+        let (next, _) = best_similarity
+            .iter()
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap_or((0, &1.0));
+        let seed = vectors[next].clone();
+        for (i, v) in vectors.iter().enumerate() {
+            best_similarity[i] = best_similarity[i].max(dot(v, &seed));
+        }
+        centroids.push(seed);
+    }
+
+    let mut assignment = vec![usize::MAX; vectors.len()];
+    for _ in 0..MAX_ITERATIONS {
+        let mut moved = false;
+        for (i, v) in vectors.iter().enumerate() {
+            let nearest = centroids
+                .iter()
+                .enumerate()
+                .max_by(|a, b| dot(v, a.1).total_cmp(&dot(v, b.1)))
+                .map(|(c, _)| c)
+                .unwrap_or(0);
+            if assignment[i] != nearest {
+                assignment[i] = nearest;
+                moved = true;
+            }
+        }
+        if !moved {
+            break;
+        }
+        for (c, centroid) in centroids.iter_mut().enumerate() {
+            let mut sum = vec![0.0f32; centroid.len()];
+            for (v, _) in vectors.iter().zip(&assignment).filter(|(_, a)| **a == c) {
+                for (s, x) in sum.iter_mut().zip(v) {
+                    *s += x;
+                }
+            }
+            // An emptied cluster keeps its previous centroid.
+            if sum.iter().any(|x| *x != 0.0) {
+                *centroid = normalized(&sum);
+            }
+        }
+    }
+    (centroids, assignment)
+}
+
+/// The [`TOP_TERMS`] most distinctive words of each cluster.
+fn cluster_terms(records: &[MemoryRecord], assignment: &[usize], k: usize) -> Vec<Vec<String>> {
+    let mut counts: Vec<HashMap<String, usize>> = vec![HashMap::new(); k];
+    for (record, &c) in records.iter().zip(assignment) {
+        for word in words(&record.content) {
+            *counts[c].entry(word).or_default() += 1;
+        }
+    }
+    let mut clusters_with: HashMap<&str, usize> = HashMap::new();
+    for cluster in &counts {
+        for word in cluster.keys() {
+            *clusters_with.entry(word.as_str()).or_default() += 1;
+        }
+    }
+
+    counts
+        .iter()
+        .map(|cluster| {
+            let total = cluster.values().sum::<usize>().max(1) as f32;
+            let mut scored: Vec<(&String, f32)> = cluster
+                .iter()
+                .map(|(word, &n)| {
+                    let idf = (1.0 + k as f32 / clusters_with[word.as_str()] as f32).ln();
+                    (word, n as f32 / total * idf)
+                })
+                .collect();
+            scored.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+            scored
+                .into_iter()
+                .take(TOP_TERMS)
+                .map(|(w, _)| w.clone())
+                .collect()
+        })
+        .collect()
+}
+
+/// Lowercase words of three or more letters, without stopwords, each
+/// counted once per memory.
+fn words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 3 && !w.chars().all(|c| c.is_numeric()))
+        .map(str::to_lowercase)
+        .filter(|w| !STOPWORDS.contains(&w.as_str()))
+        .collect()
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn normalized(v: &[f32]) -> Vec<f32> {
+    let norm = dot(v, v).sqrt();
+    if norm == 0.0 {
+        return v.to_vec();
+    }
+    v.iter().map(|x| x / norm).collect()
+}
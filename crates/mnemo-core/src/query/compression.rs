@@ -0,0 +1,244 @@
+//! Thread compression: summarize-and-replace for old episodic memories.
+//!
+//! Long-running agents pile up thousands of episodic memories per thread,
+//! most of which are only ever useful in aggregate. [`run_compression`]
+//! takes a thread's episodic memories older than `older_than_days`, folds
+//! them oldest first into one semantic summary per `batch_size` memories
+//! using the engine's [`Summarizer`](crate::summarize::Summarizer), and
+//! replaces the originals with the summaries:
+//!
+//! - each summary is stored through the normal remember path (hash chain,
+//!   encryption, indexing) in the same thread, with a `consolidated_from`
+//!   relation to every original it covers;
+//! - each original is copied to cold storage when one is attached, marked
+//!   `Archived` and dropped from the vector and full-text indexes, so recall
+//!   returns the summary instead. The row itself stays for lineage.
+//!
+//! Pinned, quarantined and already consolidated memories are never touched,
+//! and threads with fewer than `min_memories` eligible memories are skipped.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::memory::{ConsolidationState, MemoryRecord, MemoryType, SourceType};
+use crate::model::relation::{CONSOLIDATED_FROM, Relation};
+use crate::query::MnemoEngine;
+use crate::query::remember::RememberRequest;
+use crate::storage::MemoryFilter;
+use crate::summarize::{ExtractiveSummarizer, Summarizer};
+
+/// Age in days after which episodic memories are compressed, when the
+/// caller does not choose one.
+pub const DEFAULT_COMPRESSION_AGE_DAYS: u32 = 30;
+/// Fewest eligible memories a thread needs before it is compressed.
+pub const DEFAULT_COMPRESSION_MIN_MEMORIES: usize = 10;
+/// Originals folded into each summary.
+pub const DEFAULT_COMPRESSION_BATCH_SIZE: usize = 50;
+
+/// `created_by` of the summaries the pass writes.
+pub const COMPRESSION_CREATED_BY: &str = "compression_pass";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompressionRequest {
+    /// Agent whose threads are compressed. Defaults to the engine's agent.
+    pub agent_id: Option<String>,
+    /// Only compress this thread. `None` compresses every thread.
+    pub thread_id: Option<String>,
+    pub older_than_days: u32,
+    pub min_memories: usize,
+    pub batch_size: usize,
+}
+
+impl Default for CompressionRequest {
+    fn default() -> Self {
+        Self {
+            agent_id: None,
+            thread_id: None,
+            older_than_days: DEFAULT_COMPRESSION_AGE_DAYS,
+            min_memories: DEFAULT_COMPRESSION_MIN_MEMORIES,
+            batch_size: DEFAULT_COMPRESSION_BATCH_SIZE,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompressionReport {
+    pub threads_compressed: usize,
+    pub summaries_created: usize,
+    pub originals_archived: usize,
+    /// Originals also copied to cold storage. Zero without cold storage.
+    pub archived_to_cold_storage: usize,
+    pub summary_ids: Vec<Uuid>,
+}
+
+pub async fn run_compression(
+    engine: &MnemoEngine,
+    request: CompressionRequest,
+) -> Result<CompressionReport> {
+    if request.batch_size == 0 {
+        return Err(Error::Validation("batch_size must be > 0".to_string()));
+    }
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(i64::from(request.older_than_days));
+
+    let filter = MemoryFilter {
+        agent_id: Some(agent_id.clone()),
+        memory_type: Some(MemoryType::Episodic),
+        thread_id: request.thread_id.clone(),
+        include_deleted: false,
+        ..Default::default()
+    };
+    let memories = engine
+        .storage
+        .list_memories(&filter, super::MAX_BATCH_QUERY_LIMIT, 0)
+        .await?;
+
+    let mut threads: BTreeMap<String, Vec<MemoryRecord>> = BTreeMap::new();
+    for record in memories {
+        let eligible = !record.pinned
+            && !record.quarantined
+            && !record.is_deleted()
+            && matches!(
+                record.consolidation_state,
+                ConsolidationState::Raw | ConsolidationState::Active
+            )
+            && chrono::DateTime::parse_from_rfc3339(&record.created_at)
+                .is_ok_and(|created| created < cutoff);
+        if eligible && let Some(thread_id) = record.thread_id.clone() {
+            threads.entry(thread_id).or_default().push(record);
+        }
+    }
+
+    let fallback = ExtractiveSummarizer::default();
+    let summarizer: &dyn Summarizer = match engine.summarizer {
+        Some(ref summarizer) => summarizer.as_ref(),
+        None => &fallback,
+    };
+
+    let mut report = CompressionReport::default();
+    for (thread_id, mut records) in threads {
+        if records.len() < request.min_memories.max(1) {
+            continue;
+        }
+        records.sort_by(|a, b| (&a.created_at, a.id).cmp(&(&b.created_at, b.id)));
+        let mut compressed = false;
+        for batch in records.chunks(request.batch_size) {
+            let Some(summary_id) =
+                summarize_batch(engine, summarizer, &agent_id, &thread_id, batch).await?
+            else {
+                continue;
+            };
+            compressed = true;
+            report.summaries_created += 1;
+            report.summary_ids.push(summary_id);
+            for original in batch {
+                if archive_original(engine, original).await? {
+                    report.archived_to_cold_storage += 1;
+                }
+                report.originals_archived += 1;
+            }
+        }
+        if compressed {
+            report.threads_compressed += 1;
+        }
+    }
+    if report.originals_archived > 0
+        && let Some(ref ft) = engine.full_text
+    {
+        ft.commit()?;
+    }
+    Ok(report)
+}
+
+/// Write the summary of `batch` and link it to its originals. Returns
+/// `None` when the summarizer had nothing to say.
+async fn summarize_batch(
+    engine: &MnemoEngine,
+    summarizer: &dyn Summarizer,
+    agent_id: &str,
+    thread_id: &str,
+    batch: &[MemoryRecord],
+) -> Result<Option<Uuid>> {
+    let texts: Vec<String> = batch
+        .iter()
+        .map(|record| {
+            let mut plain = record.clone();
+            super::consolidate::decrypt_in_place(engine, &mut plain);
+            plain.content
+        })
+        .collect();
+    let summary = summarizer.summarize(&texts).await?;
+    if summary.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let ids: Vec<String> = batch.iter().map(|m| m.id.to_string()).collect();
+    let tags: BTreeSet<String> = batch.iter().flat_map(|m| m.tags.iter().cloned()).collect();
+    let mut request = RememberRequest::new(summary);
+    request.agent_id = Some(agent_id.to_string());
+    request.memory_type = Some(MemoryType::Semantic);
+    request.scope = Some(batch[0].scope);
+    request.importance = batch.iter().map(|m| m.importance).reduce(f32::max);
+    request.tags = Some(tags.into_iter().collect());
+    request.metadata = Some(serde_json::json!({
+        "compressed_from": ids,
+        "summarizer": summarizer.name(),
+        "period_start": batch[0].created_at,
+        "period_end": batch[batch.len() - 1].created_at,
+    }));
+    request.source_type = Some(SourceType::Consolidation);
+    request.org_id = batch[0].org_id.clone();
+    request.thread_id = Some(thread_id.to_string());
+    request.created_by = Some(COMPRESSION_CREATED_BY.to_string());
+    let summary_id = super::remember::execute(engine, request).await?.id;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    for original in batch {
+        let relation = Relation {
+            id: Uuid::now_v7(),
+            source_id: summary_id,
+            target_id: original.id,
+            relation_type: CONSOLIDATED_FROM.to_string(),
+            weight: 1.0,
+            metadata: serde_json::Value::Object(serde_json::Map::new()),
+            created_at: now.clone(),
+        };
+        engine.storage.insert_relation(&relation).await?;
+    }
+    Ok(Some(summary_id))
+}
+
+/// Move one original out of recall. Returns whether it reached cold
+/// storage.
+async fn archive_original(engine: &MnemoEngine, original: &MemoryRecord) -> Result<bool> {
+    let mut in_cold = false;
+    if let Some(ref cs) = engine.cold_storage {
+        match cs.archive(original).await {
+            Ok(_) => in_cold = true,
+            Err(e) => {
+                tracing::warn!(memory_id = %original.id, error = %e, "cold storage archive failed during compression");
+            }
+        }
+    }
+
+    let mut archived = original.clone();
+    archived.consolidation_state = ConsolidationState::Archived;
+    archived.updated_at = chrono::Utc::now().to_rfc3339();
+    engine.storage.update_memory(&archived).await?;
+    engine.index.remove(original.id)?;
+    if let Some(ref ft) = engine.full_text {
+        ft.remove(original.id)?;
+    }
+    if let Some(ref cache) = engine.cache {
+        cache.invalidate(original.id);
+    }
+    Ok(in_cold)
+}
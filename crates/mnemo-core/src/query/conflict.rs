@@ -2,7 +2,9 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::error::Result;
+use crate::index::ShardRoute;
 use crate::model::memory::{ConsolidationState, MemoryRecord, MemoryType, SourceType};
+use crate::nli::NliScores;
 use crate::query::MnemoEngine;
 use crate::storage::MemoryFilter;
 
@@ -17,6 +19,9 @@ pub struct ConflictPair {
     pub memory_b: Uuid,
     pub similarity: f32,
     pub reason: String,
+    /// Set when the pair was judged by the engine's contradiction checker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nli: Option<NliScores>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
@@ -81,6 +86,10 @@ fn compute_evidence(record: &MemoryRecord, max_access: u64, similarity: f32) ->
 
 /// Detect potential conflicts (near-duplicate memories) for an agent.
 /// Uses the vector index to find memories with cosine similarity above threshold.
+///
+/// When the engine has a contradiction checker, neighbours below the
+/// threshold but above the policy's candidate similarity are also judged by
+/// NLI and flagged when the contradiction probability is high enough.
 pub async fn detect_conflicts(
     engine: &MnemoEngine,
     agent_id: &str,
@@ -113,7 +122,9 @@ pub async fn detect_conflicts(
                 continue;
             }
             let similarity = 1.0 - distance;
-            if similarity < threshold {
+            let nli_candidate = engine.contradiction_checker.is_some()
+                && similarity >= engine.contradiction_policy.candidate_similarity;
+            if similarity < threshold && !nli_candidate {
                 continue;
             }
 
@@ -134,15 +145,22 @@ pub async fn detect_conflicts(
                     || candidate.quarantined)
                 && candidate.content != record.content
             {
-                conflicts.push(ConflictPair {
-                    memory_a: record.id,
-                    memory_b: candidate_id,
-                    similarity,
-                    reason: format!(
-                        "High semantic similarity ({:.3}) between different content",
-                        similarity
-                    ),
-                });
+                if similarity >= threshold {
+                    conflicts.push(ConflictPair {
+                        memory_a: record.id,
+                        memory_b: candidate_id,
+                        similarity,
+                        reason: format!(
+                            "High semantic similarity ({:.3}) between different content",
+                            similarity
+                        ),
+                        nli: None,
+                    });
+                } else if let Some(pair) =
+                    check_contradiction(engine, record, candidate, similarity).await
+                {
+                    conflicts.push(pair);
+                }
             }
         }
     }
@@ -150,6 +168,55 @@ pub async fn detect_conflicts(
     Ok(ConflictDetectionResult { conflicts })
 }
 
+/// Run the engine's contradiction checker over a candidate pair in both
+/// directions (NLI is not symmetric) and keep the stronger judgement.
+/// Checker failures are logged and treat the pair as not contradictory.
+async fn check_contradiction(
+    engine: &MnemoEngine,
+    record: &MemoryRecord,
+    mut candidate: MemoryRecord,
+    similarity: f32,
+) -> Option<ConflictPair> {
+    let checker = engine.contradiction_checker.as_ref()?;
+    let mut premise = record.clone();
+    super::consolidate::decrypt_in_place(engine, &mut premise);
+    super::consolidate::decrypt_in_place(engine, &mut candidate);
+
+    let mut best: Option<NliScores> = None;
+    for (a, b) in [
+        (&premise.content, &candidate.content),
+        (&candidate.content, &premise.content),
+    ] {
+        match checker.check(a, b).await {
+            Ok(scores) => {
+                if best.is_none_or(|s| scores.contradiction > s.contradiction) {
+                    best = Some(scores);
+                }
+            }
+            Err(e) => {
+                tracing::warn!(checker = checker.name(), error = %e, "contradiction check failed");
+                return None;
+            }
+        }
+    }
+    let scores = best?;
+    if scores.contradiction < engine.contradiction_policy.min_contradiction {
+        return None;
+    }
+    Some(ConflictPair {
+        memory_a: record.id,
+        memory_b: candidate.id,
+        similarity,
+        reason: format!(
+            "NLI contradiction ({:.3}, entailment {:.3}) from {}",
+            scores.contradiction,
+            scores.entailment,
+            checker.name()
+        ),
+        nli: Some(scores),
+    })
+}
+
 /// Resolve a detected conflict using the specified strategy.
 pub async fn resolve_conflict(
     engine: &MnemoEngine,
@@ -171,6 +238,29 @@ pub async fn resolve_conflict(
             crate::error::Error::NotFound(format!("memory {} not found", conflict.memory_b))
         })?;
 
+    // Pinned memories are never the losing side of a resolution: a pinned
+    // memory beats an unpinned one outright, and two pinned memories are
+    // left for a human to sort out.
+    if !matches!(strategy, ResolutionStrategy::Manual) {
+        match (mem_a.pinned, mem_b.pinned) {
+            (true, true) => {
+                return Err(crate::error::Error::Validation(format!(
+                    "memories {} and {} are both pinned; resolve manually",
+                    mem_a.id, mem_b.id
+                )));
+            }
+            (true, false) => {
+                engine.storage.soft_delete_memory(mem_b.id).await?;
+                return Ok(());
+            }
+            (false, true) => {
+                engine.storage.soft_delete_memory(mem_a.id).await?;
+                return Ok(());
+            }
+            (false, false) => {}
+        }
+    }
+
     match strategy {
         ResolutionStrategy::KeepNewest => {
             // Soft-delete the older memory
@@ -246,10 +336,18 @@ pub async fn resolve_conflict(
                 quarantined: false,
                 quarantine_reason: None,
                 decay_function: None,
+                pinned: false,
+                valid_from: None,
+                valid_until: None,
+                curation: None,
+                attestation: None,
+                channel: None,
             };
 
             engine.storage.insert_memory(&new_record).await?;
-            engine.index.add(new_record.id, &embedding)?;
+            engine
+                .index
+                .add_routed(ShardRoute::of(&new_record), new_record.id, &embedding)?;
             if let Some(ref ft) = engine.full_text {
                 ft.add(new_record.id, &new_record.content)?;
                 ft.commit()?;
@@ -0,0 +1,250 @@
+//! Conflict review queue.
+//!
+//! Auto-resolving a detected conflict is fine for low-stakes duplicates but
+//! risky for important facts. Pairs that are not auto-resolved are stored
+//! here as `open` [`ConflictRecord`]s until a human (or an agent relaying a
+//! human's answer) closes them with [`resolve`]: by applying a
+//! [`ResolutionStrategy`], naming the memory to keep, or dismissing the
+//! pair as not actually conflicting.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::conflict::{ConflictRecord, ConflictStatus};
+use crate::model::event::EventType;
+use crate::query::MnemoEngine;
+use crate::query::conflict::{self, ConflictPair, ResolutionStrategy};
+
+/// Default page size for [`list`].
+const DEFAULT_CONFLICT_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictListRequest {
+    /// `None` lists conflicts for every agent.
+    pub agent_id: Option<String>,
+    /// `None` lists every status.
+    pub status: Option<ConflictStatus>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+impl ConflictListRequest {
+    pub fn new() -> Self {
+        Self {
+            agent_id: None,
+            status: None,
+            limit: None,
+            offset: None,
+        }
+    }
+}
+
+impl Default for ConflictListRequest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How a queued conflict is closed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictDecision {
+    /// Run an automatic strategy now that a human approved it.
+    Strategy(ResolutionStrategy),
+    /// Keep this memory (one of the pair); the other is soft-deleted.
+    Winner(Uuid),
+    /// The pair is not a real conflict; both memories stay.
+    Dismiss,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolveQueuedConflictRequest {
+    pub conflict_id: Uuid,
+    pub decision: ConflictDecision,
+    /// Who made the call (a user id, an operator name, ...).
+    pub resolved_by: Option<String>,
+}
+
+impl ResolveQueuedConflictRequest {
+    pub fn new(conflict_id: Uuid, decision: ConflictDecision) -> Self {
+        Self {
+            conflict_id,
+            decision,
+            resolved_by: None,
+        }
+    }
+}
+
+fn pair_key(a: Uuid, b: Uuid) -> (Uuid, Uuid) {
+    if a < b { (a, b) } else { (b, a) }
+}
+
+/// Queue `pairs` for review, skipping any pair that already has an open
+/// entry. Returns the newly queued records.
+pub async fn enqueue(
+    engine: &MnemoEngine,
+    agent_id: &str,
+    pairs: &[ConflictPair],
+) -> Result<Vec<ConflictRecord>> {
+    let mut open: HashSet<(Uuid, Uuid)> = engine
+        .storage
+        .list_conflicts(
+            Some(agent_id),
+            Some(ConflictStatus::Open),
+            super::MAX_BATCH_QUERY_LIMIT,
+            0,
+        )
+        .await?
+        .into_iter()
+        .map(|c| pair_key(c.memory_a, c.memory_b))
+        .collect();
+
+    let mut queued = Vec::new();
+    for pair in pairs {
+        if !open.insert(pair_key(pair.memory_a, pair.memory_b)) {
+            continue;
+        }
+        let record = ConflictRecord {
+            id: Uuid::now_v7(),
+            agent_id: agent_id.to_string(),
+            memory_a: pair.memory_a,
+            memory_b: pair.memory_b,
+            similarity: pair.similarity,
+            reason: pair.reason.clone(),
+            status: ConflictStatus::Open,
+            resolution: None,
+            winner_id: None,
+            resolved_by: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            resolved_at: None,
+        };
+        engine.storage.insert_conflict(&record).await?;
+        queued.push(record);
+    }
+    Ok(queued)
+}
+
+/// Run conflict detection for `agent_id` and queue every pair found.
+pub async fn detect_and_enqueue(
+    engine: &MnemoEngine,
+    agent_id: &str,
+    threshold: f32,
+) -> Result<Vec<ConflictRecord>> {
+    super::validate_agent_id(agent_id)?;
+    let detected = conflict::detect_conflicts(engine, agent_id, threshold).await?;
+    enqueue(engine, agent_id, &detected.conflicts).await
+}
+
+pub async fn list(
+    engine: &MnemoEngine,
+    request: ConflictListRequest,
+) -> Result<Vec<ConflictRecord>> {
+    if let Some(ref agent_id) = request.agent_id {
+        super::validate_agent_id(agent_id)?;
+    }
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_CONFLICT_LIMIT)
+        .min(super::MAX_BATCH_QUERY_LIMIT);
+    engine
+        .storage
+        .list_conflicts(
+            request.agent_id.as_deref(),
+            request.status,
+            limit,
+            request.offset.unwrap_or(0),
+        )
+        .await
+}
+
+pub async fn resolve(
+    engine: &MnemoEngine,
+    request: ResolveQueuedConflictRequest,
+) -> Result<ConflictRecord> {
+    let mut record = engine
+        .storage
+        .get_conflict(request.conflict_id)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("conflict {} not found", request.conflict_id)))?;
+    if record.status != ConflictStatus::Open {
+        return Err(Error::Validation(format!(
+            "conflict {} is already {}",
+            record.id, record.status
+        )));
+    }
+
+    let (status, resolution) = match request.decision {
+        ConflictDecision::Strategy(ResolutionStrategy::Manual) => {
+            return Err(Error::Validation(
+                "the manual strategy only flags a conflict; pick a winner or dismiss it"
+                    .to_string(),
+            ));
+        }
+        ConflictDecision::Strategy(strategy) => {
+            let pair = ConflictPair {
+                memory_a: record.memory_a,
+                memory_b: record.memory_b,
+                similarity: record.similarity,
+                reason: record.reason.clone(),
+                nli: None,
+            };
+            conflict::resolve_conflict(engine, &pair, strategy).await?;
+            let name = serde_json::to_value(strategy)?
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            (ConflictStatus::Resolved, name)
+        }
+        ConflictDecision::Winner(winner) => {
+            let loser = if winner == record.memory_a {
+                record.memory_b
+            } else if winner == record.memory_b {
+                record.memory_a
+            } else {
+                return Err(Error::Validation(format!(
+                    "winner {winner} is not part of conflict {}",
+                    record.id
+                )));
+            };
+            engine.storage.soft_delete_memory(loser).await?;
+            if let Some(ref cache) = engine.cache {
+                cache.invalidate(loser);
+            }
+            record.winner_id = Some(winner);
+            (ConflictStatus::Resolved, "manual_winner".to_string())
+        }
+        ConflictDecision::Dismiss => (ConflictStatus::Dismissed, "dismissed".to_string()),
+    };
+
+    record.status = status;
+    record.resolution = Some(resolution);
+    record.resolved_by = request.resolved_by;
+    record.resolved_at = Some(chrono::Utc::now().to_rfc3339());
+    engine.storage.update_conflict(&record).await?;
+
+    let event = super::event_builder::build_event(
+        engine,
+        &record.agent_id,
+        EventType::Decision,
+        serde_json::json!({
+            "conflict_id": record.id.to_string(),
+            "memory_a": record.memory_a.to_string(),
+            "memory_b": record.memory_b.to_string(),
+            "status": record.status.to_string(),
+            "resolution": record.resolution,
+            "winner_id": record.winner_id.map(|id| id.to_string()),
+            "resolved_by": record.resolved_by,
+        }),
+        &record.id.to_string(),
+        None,
+    )
+    .await;
+    if let Err(e) = engine.storage.insert_event(&event).await {
+        tracing::error!(event_id = %event.id, error = %e, "failed to insert audit event");
+    }
+
+    Ok(record)
+}
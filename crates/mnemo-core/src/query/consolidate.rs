@@ -23,13 +23,12 @@ use uuid::Uuid;
 
 use crate::error::{Error, Result};
 use crate::hash::{compute_chain_hash, compute_content_hash};
+use crate::index::ShardRoute;
 use crate::model::acl::Permission;
 use crate::model::event::EventType;
 use crate::model::memory::{ConsolidationState, MemoryRecord, MemoryType, Scope, SourceType};
-use crate::model::relation::Relation;
+use crate::model::relation::{CONSOLIDATED_FROM, Relation};
 use crate::query::MnemoEngine;
-#[allow(unused_imports)]
-use base64::Engine as _;
 
 /// Request to consolidate a set of member memories into one topic document.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -102,24 +101,12 @@ pub struct ConsolidateResponse {
 
 /// Decrypt a record's content in place if engine-level encryption is configured.
 /// Mirrors the read-path decryption used by `recall`.
-fn decrypt_in_place(engine: &MnemoEngine, record: &mut MemoryRecord) {
-    if let Some(ref enc) = engine.encryption {
-        match base64::engine::general_purpose::STANDARD.decode(&record.content) {
-            Ok(bytes) => match enc.decrypt(&bytes) {
-                Ok(plain) => match String::from_utf8(plain) {
-                    Ok(text) => record.content = text,
-                    Err(e) => {
-                        tracing::error!(memory_id = %record.id, error = %e, "decrypted content is not valid UTF-8");
-                        record.content = "[content unavailable: decryption error]".to_string();
-                    }
-                },
-                Err(e) => {
-                    tracing::error!(memory_id = %record.id, error = %e, "failed to decrypt member content");
-                    record.content = "[content unavailable: decryption error]".to_string();
-                }
-            },
+pub(crate) fn decrypt_in_place(engine: &MnemoEngine, record: &mut MemoryRecord) {
+    if engine.encrypts_content() {
+        match engine.decrypt_content(&record.content) {
+            Ok(text) => record.content = text,
             Err(e) => {
-                tracing::error!(memory_id = %record.id, error = %e, "failed to decode encrypted member content");
+                tracing::error!(memory_id = %record.id, error = %e, "failed to decrypt member content");
                 record.content = "[content unavailable: decryption error]".to_string();
             }
         }
@@ -134,6 +121,9 @@ pub async fn execute(
     if request.memory_ids.is_empty() {
         return Err(Error::Validation("memory_ids cannot be empty".to_string()));
     }
+    engine
+        .limits()
+        .check_batch("memory_ids", request.memory_ids.len())?;
     let topic = request.topic_name.trim().to_string();
     if topic.is_empty() {
         return Err(Error::Validation("topic_name cannot be empty".to_string()));
@@ -163,6 +153,16 @@ pub async fn execute(
                 "memory {id} is deleted and cannot be consolidated"
             )));
         }
+        if record.pinned {
+            return Err(Error::Validation(format!(
+                "memory {id} is pinned and cannot be consolidated"
+            )));
+        }
+        if super::ephemeral::is_ephemeral(&record) {
+            return Err(Error::Validation(format!(
+                "memory {id} is ephemeral and cannot be consolidated"
+            )));
+        }
         if !engine
             .storage
             .check_permission(*id, &agent_id, Permission::Read)
@@ -288,17 +288,22 @@ pub async fn execute(
         quarantined: false,
         quarantine_reason: None,
         decay_function: None,
+        pinned: false,
+        valid_from: None,
+        valid_until: None,
+        curation: None,
+        attestation: super::attestation::initial_status(engine, std::slice::from_ref(&topic)),
+        channel: None,
     };
 
     // Encrypt at rest after hashing/embedding, exactly like `remember`.
-    if let Some(ref enc) = engine.encryption {
-        let encrypted = enc.encrypt(record.content.as_bytes())?;
-        record.content = base64::engine::general_purpose::STANDARD.encode(&encrypted);
-    }
+    record.content = engine.encrypt_content(record.org_id.as_deref(), &record.content)?;
 
     // --- Persist + index --------------------------------------------------
     engine.storage.insert_memory(&record).await?;
-    engine.index.add(id, &embedding)?;
+    engine
+        .index
+        .add_routed(ShardRoute::of(&record), id, &embedding)?;
     if let Some(ref ft) = engine.full_text {
         ft.add(id, &record.content)?;
         ft.commit()?;
@@ -310,7 +315,7 @@ pub async fn execute(
             id: Uuid::now_v7(),
             source_id: id,
             target_id: m.id,
-            relation_type: "consolidated_from".to_string(),
+            relation_type: CONSOLIDATED_FROM.to_string(),
             weight: 1.0,
             metadata: serde_json::Value::Object(serde_json::Map::new()),
             created_at: now.clone(),
@@ -0,0 +1,269 @@
+//! Curation of the global knowledge base.
+//!
+//! `Scope::Global` memories are visible to every agent, so without review
+//! any agent can pollute them. With a [`CurationPolicy`] attached, a global
+//! write by an agent that is not a curator is stored as
+//! [`ReviewStatus::Pending`]: its author still recalls it, but other
+//! agents do not until a curator approves it. Rejected memories stay
+//! private to their author.
+//!
+//! Curators are the engine's default agent (the identity the admin API
+//! acts as), the agents listed in the policy, and agents holding an
+//! `admin` delegation from one of those. A delegated curator may review
+//! only the memories the delegation's scope covers. Writes by a listed
+//! curator are approved on arrival.
+//!
+//! A review keeps the original author and source untouched and records the
+//! reviewer, time and note under `metadata.curation`, plus an audit event.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::acl::Permission;
+use crate::model::delegation::DelegationScope;
+use crate::model::event::EventType;
+use crate::model::memory::{MemoryRecord, ReviewStatus, Scope, SourceType};
+use crate::query::MnemoEngine;
+use crate::storage::MemoryFilter;
+
+/// Who may approve global writes. See the [module docs](self).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CurationPolicy {
+    pub curators: BTreeSet<String>,
+}
+
+impl CurationPolicy {
+    pub fn with_curator(mut self, agent_id: impl Into<String>) -> Self {
+        self.curators.insert(agent_id.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurationReviewRequest {
+    pub memory_id: Uuid,
+    /// Reviewing agent. Defaults to the engine's agent.
+    pub agent_id: Option<String>,
+    pub approve: bool,
+    pub note: Option<String>,
+}
+
+impl CurationReviewRequest {
+    pub fn new(memory_id: Uuid, approve: bool) -> Self {
+        Self {
+            memory_id,
+            agent_id: None,
+            approve,
+            note: None,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurationReviewResponse {
+    pub memory_id: Uuid,
+    pub status: ReviewStatus,
+    pub reviewed_by: String,
+    pub reviewed_at: String,
+}
+
+impl CurationReviewResponse {
+    pub fn new(
+        memory_id: Uuid,
+        status: ReviewStatus,
+        reviewed_by: String,
+        reviewed_at: String,
+    ) -> Self {
+        Self {
+            memory_id,
+            status,
+            reviewed_by,
+            reviewed_at,
+        }
+    }
+}
+
+/// A global memory awaiting review.
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingMemory {
+    pub memory_id: Uuid,
+    pub agent_id: String,
+    pub content: String,
+    pub tags: Vec<String>,
+    pub source_type: SourceType,
+    pub created_by: Option<String>,
+    pub created_at: String,
+}
+
+impl PendingMemory {
+    fn from_record(record: MemoryRecord) -> Self {
+        Self {
+            memory_id: record.id,
+            agent_id: record.agent_id,
+            content: record.content,
+            tags: record.tags,
+            source_type: record.source_type,
+            created_by: record.created_by,
+            created_at: record.created_at,
+        }
+    }
+}
+
+/// Curation state of a new memory written by `agent_id` in `scope`.
+pub(crate) fn initial_status(
+    engine: &MnemoEngine,
+    agent_id: &str,
+    scope: Scope,
+) -> Option<ReviewStatus> {
+    if scope != Scope::Global {
+        return None;
+    }
+    let policy = engine.curation.as_ref()?;
+    if is_curator(engine, policy, agent_id) {
+        Some(ReviewStatus::Approved)
+    } else {
+        Some(ReviewStatus::Pending)
+    }
+}
+
+/// Whether agents other than the author may recall `record`.
+pub(crate) fn visible_to_others(record: &MemoryRecord) -> bool {
+    matches!(record.curation, None | Some(ReviewStatus::Approved))
+}
+
+/// Pending global memories `agent_id` may review, oldest first.
+pub async fn list_pending(
+    engine: &MnemoEngine,
+    agent_id: Option<String>,
+    limit: usize,
+) -> Result<Vec<PendingMemory>> {
+    let agent_id = agent_id.unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    let filter = MemoryFilter {
+        scope: Some(Scope::Global),
+        include_deleted: false,
+        ..Default::default()
+    };
+    let mut pending = Vec::new();
+    let mut records = engine
+        .storage
+        .list_memories(&filter, super::MAX_BATCH_QUERY_LIMIT, 0)
+        .await?;
+    records.retain(|r| r.curation == Some(ReviewStatus::Pending) && !r.is_deleted());
+    records.sort_by(|a, b| (&a.created_at, a.id).cmp(&(&b.created_at, b.id)));
+    for mut record in records {
+        if pending.len() >= limit {
+            break;
+        }
+        if can_review(engine, &agent_id, &record).await? {
+            super::consolidate::decrypt_in_place(engine, &mut record);
+            pending.push(PendingMemory::from_record(record));
+        }
+    }
+    Ok(pending)
+}
+
+pub async fn review(
+    engine: &MnemoEngine,
+    request: CurationReviewRequest,
+) -> Result<CurationReviewResponse> {
+    if engine.curation.is_none() {
+        return Err(Error::Validation(
+            "curation is not enabled on this engine".to_string(),
+        ));
+    }
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+
+    let mut record = engine
+        .storage
+        .get_memory(request.memory_id)
+        .await?
+        .filter(|r| !r.is_deleted() && r.curation.is_some())
+        .ok_or_else(|| {
+            Error::NotFound(format!("curated memory {} not found", request.memory_id))
+        })?;
+    if !can_review(engine, &agent_id, &record).await? {
+        return Err(Error::PermissionDenied(format!(
+            "agent {agent_id} is not a curator for memory {}",
+            request.memory_id
+        )));
+    }
+
+    let status = if request.approve {
+        ReviewStatus::Approved
+    } else {
+        ReviewStatus::Rejected
+    };
+    let now = chrono::Utc::now().to_rfc3339();
+    record.curation = Some(status);
+    if !record.metadata.is_object() {
+        record.metadata = serde_json::Value::Object(serde_json::Map::new());
+    }
+    record.metadata["curation"] = serde_json::json!({
+        "status": status,
+        "reviewed_by": agent_id,
+        "reviewed_at": now,
+        "note": request.note,
+    });
+    record.updated_at = now.clone();
+    engine.storage.update_memory(&record).await?;
+    if let Some(ref cache) = engine.cache {
+        cache.invalidate(record.id);
+    }
+
+    let event = super::event_builder::build_event(
+        engine,
+        &agent_id,
+        EventType::MemoryWrite,
+        serde_json::json!({
+            "memory_id": record.id.to_string(),
+            "author": record.agent_id,
+            "curation": status,
+            "note": request.note,
+        }),
+        &record.id.to_string(),
+        record.thread_id.clone(),
+    )
+    .await;
+    if let Err(e) = engine.storage.insert_event(&event).await {
+        tracing::error!(event_id = %event.id, error = %e, "failed to insert audit event");
+    }
+
+    Ok(CurationReviewResponse::new(
+        record.id, status, agent_id, now,
+    ))
+}
+
+fn is_curator(engine: &MnemoEngine, policy: &CurationPolicy, agent_id: &str) -> bool {
+    agent_id == engine.default_agent_id || policy.curators.contains(agent_id)
+}
+
+/// A curator, or the holder of an `admin` delegation from one whose scope
+/// covers `record`.
+async fn can_review(engine: &MnemoEngine, agent_id: &str, record: &MemoryRecord) -> Result<bool> {
+    let Some(ref policy) = engine.curation else {
+        return Ok(false);
+    };
+    if is_curator(engine, policy, agent_id) {
+        return Ok(true);
+    }
+    let delegations = engine.storage.list_delegations_for(agent_id).await?;
+    Ok(delegations.iter().any(|d| {
+        d.permission.satisfies(Permission::Admin)
+            && is_curator(engine, policy, &d.delegator_id)
+            && match &d.scope {
+                DelegationScope::AllMemories => true,
+                DelegationScope::ByMemoryId(ids) => ids.contains(&record.id),
+                DelegationScope::ByTag(tags) => tags.iter().any(|t| record.tags.contains(t)),
+            }
+    }))
+}
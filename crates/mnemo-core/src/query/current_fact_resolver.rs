@@ -213,10 +213,17 @@ mod tests {
                 None => json!({}),
             },
             score,
+            relevance: None,
             access_count: 0,
             created_at: updated_at.to_string(),
             updated_at: updated_at.to_string(),
             score_breakdown: None,
+            snippets: None,
+            provenance_chain: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
+            recalled_for: None,
         }
     }
 
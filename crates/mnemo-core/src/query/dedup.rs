@@ -0,0 +1,162 @@
+//! Near-duplicate detection at remember time.
+//!
+//! Content-hash chaining only catches byte-identical writes; chatty agents
+//! mostly repeat themselves in paraphrase. With a [`DedupPolicy`] attached
+//! ([`MnemoEngine::with_dedup_policy`]) `remember` looks up the nearest
+//! existing memory before inserting and, when it is at least `threshold`
+//! cosine-similar, applies the policy's [`DedupAction`]:
+//!
+//! - **skip** — store nothing and return the existing memory's id;
+//! - **merge** (the default) — store nothing, raise the existing memory's
+//!   importance to the incoming value if higher, add any new tags and count
+//!   the write as an access;
+//! - **link** — store the new memory as usual plus a
+//!   [`DUPLICATE_OF`](crate::model::relation::DUPLICATE_OF) relation to the
+//!   existing one.
+//!
+//! Only live, unquarantined memories of the same agent, thread and memory
+//! type are candidates. The match is reported in
+//! [`RememberResponse::duplicate`](super::remember::RememberResponse::duplicate).
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::memory::{MemoryRecord, MemoryType};
+use crate::query::MnemoEngine;
+
+/// Nearest neighbours inspected for a duplicate. Several may belong to
+/// other agents or threads, so look past the single closest hit.
+const DUPLICATE_CANDIDATES: usize = 8;
+
+/// What `remember` does with a near-duplicate of an existing memory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DedupAction {
+    /// Drop the write and return the existing memory.
+    Skip,
+    /// Fold the write's importance and tags into the existing memory.
+    #[default]
+    Merge,
+    /// Store the write and link it to the existing memory.
+    Link,
+}
+
+/// Similarity threshold and action for remember-time deduplication.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DedupPolicy {
+    /// Cosine similarity at which a write counts as a duplicate.
+    pub threshold: f32,
+    pub action: DedupAction,
+}
+
+impl Default for DedupPolicy {
+    fn default() -> Self {
+        Self {
+            threshold: 0.95,
+            action: DedupAction::Merge,
+        }
+    }
+}
+
+impl DedupPolicy {
+    pub fn new(threshold: f32, action: DedupAction) -> Self {
+        Self { threshold, action }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if !(self.threshold > 0.0 && self.threshold <= 1.0) {
+            return Err(Error::Validation(format!(
+                "dedup threshold must be in (0, 1], got {}",
+                self.threshold
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// The existing memory a write was matched against.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicateMatch {
+    pub memory_id: Uuid,
+    pub similarity: f32,
+    pub action: DedupAction,
+}
+
+/// The most similar live memory of `agent_id` / `thread_id` /
+/// `memory_type` / `channel` at or above the policy threshold, with its
+/// similarity.
+pub(crate) async fn find_duplicate(
+    engine: &MnemoEngine,
+    policy: &DedupPolicy,
+    agent_id: &str,
+    thread_id: Option<&str>,
+    memory_type: MemoryType,
+    channel: Option<&str>,
+    embedding: &[f32],
+) -> Result<Option<(MemoryRecord, f32)>> {
+    let neighbours = engine.index.search(embedding, DUPLICATE_CANDIDATES)?;
+    let now = chrono::Utc::now();
+    for (id, distance) in neighbours {
+        let similarity = 1.0 - distance;
+        if similarity < policy.threshold {
+            // Neighbours come back closest first.
+            break;
+        }
+        let Some(record) = engine.storage.get_memory(id).await? else {
+            continue;
+        };
+        let expired = record
+            .expires_at
+            .as_deref()
+            .and_then(|e| chrono::DateTime::parse_from_rfc3339(e).ok())
+            .is_some_and(|exp| exp < now);
+        if record.is_deleted()
+            || record.quarantined
+            || expired
+            || record.agent_id != agent_id
+            || record.thread_id.as_deref() != thread_id
+            || record.memory_type != memory_type
+            || record.channel.as_deref() != channel
+        {
+            continue;
+        }
+        return Ok(Some((record, similarity)));
+    }
+    Ok(None)
+}
+
+/// Fold an incoming write into `existing`: keep the higher importance, add
+/// new tags and record the write as an access.
+pub(crate) async fn merge_into(
+    engine: &MnemoEngine,
+    mut existing: MemoryRecord,
+    importance: f32,
+    tags: &[String],
+) -> Result<()> {
+    let mut changed = false;
+    if importance > existing.importance {
+        existing.importance = importance;
+        changed = true;
+    }
+    for tag in tags {
+        if !existing.tags.contains(tag) {
+            existing.tags.push(tag.clone());
+            changed = true;
+        }
+    }
+    if changed {
+        // A merged-in tag can put the memory under attestation.
+        if existing.attestation.is_none() {
+            existing.attestation = super::attestation::initial_status(engine, &existing.tags);
+        }
+        existing.updated_at = chrono::Utc::now().to_rfc3339();
+        engine.storage.update_memory(&existing).await?;
+    }
+    engine.storage.touch_memory(existing.id).await?;
+    if let Some(ref cache) = engine.cache {
+        cache.invalidate(existing.id);
+    }
+    Ok(())
+}
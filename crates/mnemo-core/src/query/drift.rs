@@ -0,0 +1,335 @@
+//! Drift between an agent's memories and a ground-truth corpus.
+//!
+//! Documentation changes; what an agent remembered about it does not.
+//! [`detect_drift`] takes a corpus of current facts (usually parsed from
+//! JSONL with [`parse_corpus`]), embeds them, and pairs every live memory
+//! of the agent with its most similar fact. A memory that matches a fact
+//! is then judged:
+//!
+//! - **contradicting** — the engine's contradiction checker (or the
+//!   model-free [`NegationChecker`] when none is attached) says the memory
+//!   and the fact disagree;
+//! - **stale** — the memory is about the fact but says something else, and
+//!   the fact is newer than the memory (facts without `updated_at` count as
+//!   current);
+//! - consistent otherwise.
+//!
+//! Memories no fact matches are outside the corpus and only counted. The
+//! report lists contradicting and stale memories, most similar first, so a
+//! reviewer can correct or forget them.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::memory::MemoryRecord;
+use crate::nli::{ContradictionChecker, NegationChecker};
+use crate::query::MnemoEngine;
+use crate::storage::MemoryFilter;
+
+/// Similarity at which a memory is taken to be about a fact.
+pub const DEFAULT_MATCH_THRESHOLD: f32 = 0.75;
+/// Similarity at which a matched memory says the same as its fact.
+pub const DEFAULT_CONSISTENT_THRESHOLD: f32 = 0.95;
+
+/// One fact of the ground-truth corpus.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroundTruthFact {
+    /// Stable identifier. When absent, [`parse_corpus`] uses `line-<n>` and
+    /// [`detect_drift`] the fact's position, `facts[<i>]`.
+    #[serde(default)]
+    pub id: String,
+    pub content: String,
+    /// When the fact was last changed (RFC 3339).
+    #[serde(default)]
+    pub updated_at: Option<String>,
+    /// Where the fact comes from, e.g. a document path or URL.
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+impl GroundTruthFact {
+    pub fn new(id: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            content: content.into(),
+            updated_at: None,
+            source: None,
+        }
+    }
+}
+
+/// Parse a JSONL corpus, one [`GroundTruthFact`] per non-blank line.
+pub fn parse_corpus(text: &str) -> Result<Vec<GroundTruthFact>> {
+    let mut facts = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut fact: GroundTruthFact = serde_json::from_str(line)
+            .map_err(|e| Error::Validation(format!("corpus line {}: {e}", i + 1)))?;
+        if fact.id.is_empty() {
+            fact.id = format!("line-{}", i + 1);
+        }
+        facts.push(fact);
+    }
+    Ok(facts)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftRequest {
+    pub agent_id: Option<String>,
+    pub facts: Vec<GroundTruthFact>,
+    pub match_threshold: Option<f32>,
+    pub consistent_threshold: Option<f32>,
+}
+
+impl DriftRequest {
+    pub fn new(facts: Vec<GroundTruthFact>) -> Self {
+        Self {
+            agent_id: None,
+            facts,
+            match_threshold: None,
+            consistent_threshold: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftKind {
+    Stale,
+    Contradicting,
+}
+
+/// A memory that disagrees with the corpus.
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftFinding {
+    pub memory_id: Uuid,
+    pub content: String,
+    pub kind: DriftKind,
+    pub fact_id: String,
+    pub fact_content: String,
+    pub fact_source: Option<String>,
+    /// Cosine similarity between the memory and the fact.
+    pub similarity: f32,
+    pub reason: String,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftReport {
+    pub agent_id: String,
+    pub facts: usize,
+    pub memories_checked: usize,
+    /// Memories matched to a fact and in agreement with it.
+    pub consistent: usize,
+    /// Memories no fact matched.
+    pub unmatched: usize,
+    /// Facts no memory matched.
+    pub uncovered_facts: Vec<String>,
+    /// Most similar first.
+    pub findings: Vec<DriftFinding>,
+}
+
+impl DriftReport {
+    pub fn stale(&self) -> usize {
+        self.count(DriftKind::Stale)
+    }
+
+    pub fn contradicting(&self) -> usize {
+        self.count(DriftKind::Contradicting)
+    }
+
+    fn count(&self, kind: DriftKind) -> usize {
+        self.findings.iter().filter(|f| f.kind == kind).count()
+    }
+}
+
+pub async fn detect_drift(engine: &MnemoEngine, mut request: DriftRequest) -> Result<DriftReport> {
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    let match_threshold = request.match_threshold.unwrap_or(DEFAULT_MATCH_THRESHOLD);
+    let consistent_threshold = request
+        .consistent_threshold
+        .unwrap_or(DEFAULT_CONSISTENT_THRESHOLD);
+    if !(0.0..=1.0).contains(&match_threshold) {
+        return Err(Error::invalid_field(
+            "match_threshold",
+            "must be between 0 and 1",
+        ));
+    }
+    if !(match_threshold..=1.0).contains(&consistent_threshold) {
+        return Err(Error::invalid_field(
+            "consistent_threshold",
+            "must be between match_threshold and 1",
+        ));
+    }
+    let mut fact_times = Vec::with_capacity(request.facts.len());
+    for (i, fact) in request.facts.iter_mut().enumerate() {
+        if fact.id.is_empty() {
+            fact.id = format!("facts[{i}]");
+        }
+        if fact.content.trim().is_empty() {
+            return Err(Error::invalid_field(
+                format!("facts[{i}].content"),
+                "must not be empty",
+            ));
+        }
+        fact_times.push(match fact.updated_at {
+            Some(ref ts) => Some(parse_time(ts).ok_or_else(|| {
+                Error::invalid_field(format!("facts[{i}].updated_at"), "must be RFC 3339")
+            })?),
+            None => None,
+        });
+    }
+
+    let texts: Vec<&str> = request.facts.iter().map(|f| f.content.as_str()).collect();
+    let fact_vectors: Vec<Vec<f32>> = engine
+        .embedding
+        .embed_batch(&texts)
+        .await?
+        .iter()
+        .map(|v| normalized(v))
+        .collect();
+
+    let filter = MemoryFilter {
+        agent_id: Some(agent_id.clone()),
+        include_deleted: false,
+        ..Default::default()
+    };
+    let records: Vec<MemoryRecord> = engine
+        .storage
+        .list_memories(&filter, super::MAX_BATCH_QUERY_LIMIT, 0)
+        .await?
+        .into_iter()
+        .filter(|r| !r.is_deleted() && !r.quarantined)
+        .collect();
+
+    let fallback = NegationChecker;
+    let checker: &dyn ContradictionChecker = match engine.contradiction_checker {
+        Some(ref checker) => checker.as_ref(),
+        None => &fallback,
+    };
+    let min_contradiction = engine.contradiction_policy.min_contradiction;
+
+    let mut covered = HashSet::new();
+    let mut findings = Vec::new();
+    let mut consistent = 0;
+    let mut unmatched = 0;
+    for mut record in records.iter().cloned() {
+        super::consolidate::decrypt_in_place(engine, &mut record);
+        let vector = match record.embedding.take().filter(|e| !e.is_empty()) {
+            Some(embedding) => normalized(&embedding),
+            None => normalized(&engine.embedding.embed(&record.content).await?),
+        };
+        let Some((f, similarity)) = fact_vectors
+            .iter()
+            .map(|fv| dot(fv, &vector))
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .filter(|(_, s)| *s >= match_threshold)
+        else {
+            unmatched += 1;
+            continue;
+        };
+        covered.insert(f);
+        let fact = &request.facts[f];
+
+        let contradiction = contradiction(checker, &record.content, &fact.content).await;
+        let finding = if contradiction >= min_contradiction {
+            Some((
+                DriftKind::Contradicting,
+                format!(
+                    "contradicts fact {} ({contradiction:.3} from {})",
+                    fact.id,
+                    checker.name()
+                ),
+            ))
+        } else if similarity < consistent_threshold
+            && fact_times[f].is_none_or(|fact_time| {
+                parse_time(&record.updated_at).is_none_or(|memory_time| fact_time > memory_time)
+            })
+        {
+            Some((
+                DriftKind::Stale,
+                match fact.updated_at {
+                    Some(ref ts) => format!("fact {} was updated at {ts}", fact.id),
+                    None => format!("differs from fact {}", fact.id),
+                },
+            ))
+        } else {
+            None
+        };
+        match finding {
+            Some((kind, reason)) => findings.push(DriftFinding {
+                memory_id: record.id,
+                content: record.content,
+                kind,
+                fact_id: fact.id.clone(),
+                fact_content: fact.content.clone(),
+                fact_source: fact.source.clone(),
+                similarity,
+                reason,
+            }),
+            None => consistent += 1,
+        }
+    }
+    findings.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+
+    Ok(DriftReport {
+        agent_id,
+        facts: request.facts.len(),
+        memories_checked: records.len(),
+        consistent,
+        unmatched,
+        uncovered_facts: request
+            .facts
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !covered.contains(i))
+            .map(|(_, f)| f.id.clone())
+            .collect(),
+        findings,
+    })
+}
+
+/// Contradiction probability of the pair, taking the stronger direction.
+/// Checker failures count as no contradiction.
+async fn contradiction(checker: &dyn ContradictionChecker, memory: &str, fact: &str) -> f32 {
+    let mut best = 0.0f32;
+    for (premise, hypothesis) in [(fact, memory), (memory, fact)] {
+        match checker.check(premise, hypothesis).await {
+            Ok(scores) => best = best.max(scores.contradiction),
+            Err(e) => {
+                tracing::warn!(checker = checker.name(), error = %e, "contradiction check failed");
+                return 0.0;
+            }
+        }
+    }
+    best
+}
+
+fn parse_time(ts: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(ts)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn normalized(v: &[f32]) -> Vec<f32> {
+    let norm = dot(v, v).sqrt();
+    if norm == 0.0 {
+        return v.to_vec();
+    }
+    v.iter().map(|x| x / norm).collect()
+}
@@ -0,0 +1,462 @@
+//! Entity summaries: one maintained profile memory per person or project.
+//!
+//! Facts about the same entity ("Alice owns the billing service", "Alice
+//! prefers async reviews") land in many separate memories, and recall has
+//! to rediscover them every time. [`run_entity_summaries`] finds the
+//! entities an agent's memories mention and keeps one semantic summary
+//! memory per entity mentioned by at least `min_mentions` of them:
+//!
+//! - a memory's entities are the strings in its `metadata.entities` array
+//!   when it has one, otherwise the capitalized phrases of its content (the
+//!   same heuristic the orientation cache uses), minus leading function
+//!   words such as "The" or "We";
+//! - the summary is tagged [`ENTITY_SUMMARY_TAG`] and its metadata records
+//!   the entity and the memories it covers. A later pass folds only the
+//!   mentions written since into the previous summary, stores the result
+//!   as a new memory derived from both, and soft-deletes the old one;
+//! - recall multiplies the score of a summary whose entity the query names
+//!   by [`ENTITY_SUMMARY_BOOST`], so the profile outranks scattered facts.
+//!
+//! [`get_summary`] returns the current summary of one entity.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::memory::{ConsolidationState, MemoryRecord, MemoryType, SourceType};
+use crate::model::relation::{DERIVED_FROM, Relation};
+use crate::query::MnemoEngine;
+use crate::query::forget::{ForgetRequest, ForgetStrategy};
+use crate::query::remember::RememberRequest;
+use crate::storage::{MemoryColumns, MemoryFilter};
+use crate::summarize::{ExtractiveSummarizer, Summarizer};
+
+/// Tag carried by every entity summary memory.
+pub const ENTITY_SUMMARY_TAG: &str = "entity-summary";
+/// `created_by` of the summaries the pass writes.
+pub const ENTITY_SUMMARY_CREATED_BY: &str = "entity_summary_pass";
+/// Fewest mentioning memories an entity needs before it gets a summary.
+pub const DEFAULT_ENTITY_MIN_MENTIONS: usize = 3;
+/// Score factor for recall hits that summarize an entity named in the query.
+pub const ENTITY_SUMMARY_BOOST: f32 = 1.5;
+
+/// Capitalized words that start sentences rather than name things.
+const LEADING_STOPWORDS: &[&str] = &[
+    "a",
+    "after",
+    "also",
+    "an",
+    "and",
+    "at",
+    "before",
+    "but",
+    "for",
+    "he",
+    "her",
+    "his",
+    "i",
+    "if",
+    "in",
+    "it",
+    "my",
+    "on",
+    "or",
+    "our",
+    "she",
+    "that",
+    "the",
+    "their",
+    "then",
+    "these",
+    "they",
+    "this",
+    "those",
+    "today",
+    "tomorrow",
+    "we",
+    "when",
+    "yesterday",
+    "you",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EntitySummaryRequest {
+    /// Agent whose memories are summarized. Defaults to the engine's agent.
+    pub agent_id: Option<String>,
+    pub min_mentions: usize,
+}
+
+impl Default for EntitySummaryRequest {
+    fn default() -> Self {
+        Self {
+            agent_id: None,
+            min_mentions: DEFAULT_ENTITY_MIN_MENTIONS,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EntitySummaryReport {
+    /// Entities that got their first summary.
+    pub summaries_created: usize,
+    /// Entities whose summary was rewritten to cover new mentions.
+    pub summaries_updated: usize,
+    /// Entities whose summary already covered every mention.
+    pub unchanged: usize,
+    pub summary_ids: Vec<Uuid>,
+}
+
+/// The current summary of one entity.
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntitySummary {
+    pub entity: String,
+    pub memory_id: Uuid,
+    pub content: String,
+    /// Memories the summary covers, oldest first.
+    pub source_ids: Vec<Uuid>,
+    pub updated_at: String,
+}
+
+impl EntitySummary {
+    pub fn new(
+        entity: String,
+        memory_id: Uuid,
+        content: String,
+        source_ids: Vec<Uuid>,
+        updated_at: String,
+    ) -> Self {
+        Self {
+            entity,
+            memory_id,
+            content,
+            source_ids,
+            updated_at,
+        }
+    }
+}
+
+pub async fn run_entity_summaries(
+    engine: &MnemoEngine,
+    request: EntitySummaryRequest,
+) -> Result<EntitySummaryReport> {
+    if request.min_mentions == 0 {
+        return Err(Error::invalid_field("min_mentions", "must be > 0"));
+    }
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+
+    let mut memories = list_live(engine, &agent_id, None).await?;
+    memories.sort_by(|a, b| (&a.created_at, a.id).cmp(&(&b.created_at, b.id)));
+    // Entity key (lowercase) -> display name (as first written) and
+    // mentioning memories, oldest first.
+    let mut mentions: BTreeMap<String, (String, Vec<MemoryRecord>)> = BTreeMap::new();
+    let mut summaries: BTreeMap<String, MemoryRecord> = BTreeMap::new();
+    for mut record in memories {
+        if record.quarantined || record.consolidation_state == ConsolidationState::Archived {
+            continue;
+        }
+        if is_entity_summary(&record) {
+            if let Some(entity) = summary_entity(&record) {
+                summaries.insert(entity.to_lowercase(), record);
+            }
+            continue;
+        }
+        super::consolidate::decrypt_in_place(engine, &mut record);
+        for entity in entities_of(&record) {
+            let entry = mentions
+                .entry(entity.to_lowercase())
+                .or_insert_with(|| (entity.clone(), Vec::new()));
+            entry.1.push(record.clone());
+        }
+    }
+
+    let fallback = ExtractiveSummarizer::default();
+    let summarizer: &dyn Summarizer = match engine.summarizer {
+        Some(ref summarizer) => summarizer.as_ref(),
+        None => &fallback,
+    };
+
+    let mut report = EntitySummaryReport::default();
+    for (key, (entity, records)) in mentions {
+        if records.len() < request.min_mentions {
+            continue;
+        }
+        let previous = summaries.remove(&key);
+        let covered: BTreeSet<Uuid> = previous
+            .as_ref()
+            .map(|p| summary_sources(p).into_iter().collect())
+            .unwrap_or_default();
+        let fresh: Vec<MemoryRecord> = records
+            .into_iter()
+            .filter(|r| !covered.contains(&r.id))
+            .collect();
+        if fresh.is_empty() {
+            report.unchanged += 1;
+            continue;
+        }
+        let is_update = previous.is_some();
+        let Some(summary_id) = write_summary(
+            engine, summarizer, &agent_id, &entity, previous, covered, &fresh,
+        )
+        .await?
+        else {
+            continue;
+        };
+        if is_update {
+            report.summaries_updated += 1;
+        } else {
+            report.summaries_created += 1;
+        }
+        report.summary_ids.push(summary_id);
+    }
+    Ok(report)
+}
+
+/// The current summary of `entity` (case-insensitive) among `agent_id`'s
+/// memories.
+pub async fn get_summary(
+    engine: &MnemoEngine,
+    agent_id: Option<String>,
+    entity: &str,
+) -> Result<EntitySummary> {
+    let agent_id = agent_id.unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    let key = entity.trim().to_lowercase();
+    let summaries = list_live(engine, &agent_id, Some(ENTITY_SUMMARY_TAG)).await?;
+    let mut record = summaries
+        .into_iter()
+        .filter(|r| summary_entity(r).is_some_and(|e| e.to_lowercase() == key))
+        .max_by(|a, b| a.created_at.cmp(&b.created_at))
+        .ok_or_else(|| Error::NotFound(format!("no summary for entity '{}'", entity.trim())))?;
+    super::consolidate::decrypt_in_place(engine, &mut record);
+    Ok(EntitySummary::new(
+        summary_entity(&record).unwrap_or_default(),
+        record.id,
+        record.content.clone(),
+        summary_sources(&record),
+        record.created_at.clone(),
+    ))
+}
+
+/// Whether `record` summarizes an entity the query names.
+pub(crate) fn summarizes_entity_in(record: &MemoryRecord, query: &str) -> bool {
+    if !is_entity_summary(record) {
+        return false;
+    }
+    let query = query.to_lowercase();
+    summary_entity(record).is_some_and(|e| query.contains(&e.to_lowercase()))
+}
+
+/// Read the metadata of the summaries among `hits` that a projected
+/// recall hydrated without it, so [`summarizes_entity_in`] can see their
+/// entity. Other hits are left alone.
+pub(crate) async fn hydrate_summary_metadata(
+    engine: &MnemoEngine,
+    hits: &mut [(MemoryRecord, f32)],
+) -> Result<()> {
+    let ids: Vec<Uuid> = hits
+        .iter()
+        .filter(|(r, _)| is_entity_summary(r))
+        .map(|(r, _)| r.id)
+        .collect();
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let columns = MemoryColumns {
+        content: false,
+        metadata: true,
+    };
+    let mut metadata: HashMap<Uuid, serde_json::Value> = engine
+        .storage
+        .get_memories(&ids, columns)
+        .await?
+        .into_iter()
+        .map(|r| (r.id, r.metadata))
+        .collect();
+    for (record, _) in hits.iter_mut() {
+        if let Some(value) = metadata.remove(&record.id) {
+            record.metadata = value;
+        }
+    }
+    Ok(())
+}
+
+async fn list_live(
+    engine: &MnemoEngine,
+    agent_id: &str,
+    tag: Option<&str>,
+) -> Result<Vec<MemoryRecord>> {
+    let filter = MemoryFilter {
+        agent_id: Some(agent_id.to_string()),
+        tags: tag.map(|t| vec![t.to_string()]),
+        include_deleted: false,
+        ..Default::default()
+    };
+    let memories = engine
+        .storage
+        .list_memories(&filter, super::MAX_BATCH_QUERY_LIMIT, 0)
+        .await?;
+    Ok(memories.into_iter().filter(|r| !r.is_deleted()).collect())
+}
+
+/// Fold `fresh` into `previous` (when there is one) and store the result.
+/// Returns `None` when the summarizer had nothing to say.
+async fn write_summary(
+    engine: &MnemoEngine,
+    summarizer: &dyn Summarizer,
+    agent_id: &str,
+    entity: &str,
+    previous: Option<MemoryRecord>,
+    mut covered: BTreeSet<Uuid>,
+    fresh: &[MemoryRecord],
+) -> Result<Option<Uuid>> {
+    // The previous summary goes in sentence by sentence, so a summarizer
+    // that keeps one sentence per text does not drop what it already said.
+    let mut texts = Vec::with_capacity(fresh.len() + 1);
+    if let Some(ref previous) = previous {
+        let mut plain = previous.clone();
+        super::consolidate::decrypt_in_place(engine, &mut plain);
+        let body = plain
+            .content
+            .strip_prefix(&format!("{entity}: "))
+            .unwrap_or(&plain.content);
+        texts.extend(sentences(body));
+    }
+    texts.extend(fresh.iter().map(|r| r.content.clone()));
+    let summary = summarizer.summarize(&texts).await?;
+    if summary.trim().is_empty() {
+        return Ok(None);
+    }
+
+    covered.extend(fresh.iter().map(|r| r.id));
+    let source_ids: Vec<String> = covered.iter().map(Uuid::to_string).collect();
+    let mut request = RememberRequest::new(format!("{entity}: {summary}"));
+    request.agent_id = Some(agent_id.to_string());
+    request.memory_type = Some(MemoryType::Semantic);
+    request.importance = fresh
+        .iter()
+        .chain(previous.iter())
+        .map(|m| m.importance)
+        .reduce(f32::max);
+    request.tags = Some(vec![ENTITY_SUMMARY_TAG.to_string()]);
+    request.metadata = Some(serde_json::json!({
+        "entity_summary": {
+            "entity": entity,
+            "source_ids": source_ids,
+            "previous_summary": previous.as_ref().map(|p| p.id.to_string()),
+        },
+        "summarizer": summarizer.name(),
+    }));
+    request.source_type = Some(SourceType::Consolidation);
+    request.org_id = fresh[0].org_id.clone();
+    request.created_by = Some(ENTITY_SUMMARY_CREATED_BY.to_string());
+    let summary_id = super::remember::execute(engine, request).await?.id;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    for target_id in fresh
+        .iter()
+        .map(|r| r.id)
+        .chain(previous.iter().map(|p| p.id))
+    {
+        let relation = Relation {
+            id: Uuid::now_v7(),
+            source_id: summary_id,
+            target_id,
+            relation_type: DERIVED_FROM.to_string(),
+            weight: 1.0,
+            metadata: serde_json::Value::Object(serde_json::Map::new()),
+            created_at: now.clone(),
+        };
+        engine.storage.insert_relation(&relation).await?;
+    }
+
+    if let Some(previous) = previous {
+        let mut forget = ForgetRequest::new(vec![previous.id]);
+        forget.agent_id = Some(agent_id.to_string());
+        forget.strategy = Some(ForgetStrategy::SoftDelete);
+        forget.force = Some(true);
+        super::forget::execute(engine, forget).await?;
+    }
+    Ok(Some(summary_id))
+}
+
+fn is_entity_summary(record: &MemoryRecord) -> bool {
+    record.tags.iter().any(|t| t == ENTITY_SUMMARY_TAG)
+}
+
+fn summary_entity(record: &MemoryRecord) -> Option<String> {
+    record
+        .metadata
+        .pointer("/entity_summary/entity")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+}
+
+fn summary_sources(record: &MemoryRecord) -> Vec<Uuid> {
+    record
+        .metadata
+        .pointer("/entity_summary/source_ids")
+        .and_then(|v| v.as_array())
+        .map(|ids| {
+            ids.iter()
+                .filter_map(|id| id.as_str()?.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Entities a (decrypted) memory mentions, de-duplicated case-insensitively.
+fn entities_of(record: &MemoryRecord) -> Vec<String> {
+    let candidates: Vec<String> = match record.metadata.get("entities").and_then(|v| v.as_array()) {
+        Some(listed) => listed
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .collect(),
+        None => super::orientation_cache::distill(&record.content)
+            .entities
+            .into_iter()
+            .filter_map(|(phrase, _)| strip_leading_stopwords(&phrase))
+            .collect(),
+    };
+    let mut seen = BTreeSet::new();
+    candidates
+        .into_iter()
+        .filter(|e| !e.is_empty() && seen.insert(e.to_lowercase()))
+        .collect()
+}
+
+fn sentences(text: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut current = String::new();
+    for c in text.chars() {
+        current.push(c);
+        if matches!(c, '.' | '!' | '?' | '\n') {
+            let sentence = current.trim();
+            if !sentence.is_empty() {
+                out.push(sentence.to_string());
+            }
+            current.clear();
+        }
+    }
+    if !current.trim().is_empty() {
+        out.push(current.trim().to_string());
+    }
+    out
+}
+
+fn strip_leading_stopwords(phrase: &str) -> Option<String> {
+    let words: Vec<&str> = phrase
+        .split_whitespace()
+        .skip_while(|w| LEADING_STOPWORDS.contains(&w.to_lowercase().as_str()))
+        .collect();
+    let entity = words.join(" ");
+    (entity.len() >= 3).then_some(entity)
+}
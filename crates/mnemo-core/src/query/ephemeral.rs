@@ -0,0 +1,98 @@
+//! Ephemeral "scratchpad" memories.
+//!
+//! Agents produce many transient intermediate results: partial plans,
+//! tool outputs and notes to self that matter for a few minutes. An
+//! ephemeral memory is a cheap write for that data. It defaults to the
+//! Working tier with a short TTL, is not embedded, so it adds nothing to
+//! the vector index and is found by exact and BM25 recall only, and is
+//! tagged [`EPHEMERAL_TAG`] so the consolidation passes leave it alone
+//! until it expires.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::model::memory::{MemoryRecord, MemoryType, Scope};
+use crate::query::MnemoEngine;
+use crate::query::remember::{RememberRequest, RememberResponse};
+
+/// Tag applied to every ephemeral memory.
+pub const EPHEMERAL_TAG: &str = "ephemeral";
+
+/// Default lifetime, in seconds, of an ephemeral memory.
+pub const DEFAULT_EPHEMERAL_TTL_SECONDS: u64 = 600;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EphemeralRequest {
+    pub content: String,
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    /// Defaults to [`MemoryType::Working`].
+    #[serde(default)]
+    pub memory_type: Option<MemoryType>,
+    #[serde(default)]
+    pub scope: Option<Scope>,
+    #[serde(default)]
+    pub importance: Option<f32>,
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub metadata: Option<serde_json::Value>,
+    #[serde(default)]
+    pub org_id: Option<String>,
+    #[serde(default)]
+    pub thread_id: Option<String>,
+    /// Defaults to [`DEFAULT_EPHEMERAL_TTL_SECONDS`]. Must be positive.
+    #[serde(default)]
+    pub ttl_seconds: Option<u64>,
+}
+
+impl EphemeralRequest {
+    pub fn new(content: String) -> Self {
+        Self {
+            content,
+            agent_id: None,
+            memory_type: None,
+            scope: None,
+            importance: None,
+            tags: None,
+            metadata: None,
+            org_id: None,
+            thread_id: None,
+            ttl_seconds: None,
+        }
+    }
+
+    /// The equivalent remember request: Working tier, the ephemeral TTL
+    /// and the [`EPHEMERAL_TAG`].
+    pub fn into_remember(self) -> Result<RememberRequest> {
+        let ttl_seconds = self.ttl_seconds.unwrap_or(DEFAULT_EPHEMERAL_TTL_SECONDS);
+        if ttl_seconds == 0 {
+            return Err(Error::invalid_field("ttl_seconds", "must be positive"));
+        }
+        let mut tags = self.tags.unwrap_or_default();
+        if !tags.iter().any(|t| t == EPHEMERAL_TAG) {
+            tags.push(EPHEMERAL_TAG.to_string());
+        }
+        let mut request = RememberRequest::new(self.content);
+        request.agent_id = self.agent_id;
+        request.memory_type = Some(self.memory_type.unwrap_or(MemoryType::Working));
+        request.scope = self.scope;
+        request.importance = self.importance;
+        request.tags = Some(tags);
+        request.metadata = self.metadata;
+        request.org_id = self.org_id;
+        request.thread_id = self.thread_id;
+        request.ttl_seconds = Some(ttl_seconds);
+        Ok(request)
+    }
+}
+
+/// Whether `record` was written as an ephemeral memory.
+pub fn is_ephemeral(record: &MemoryRecord) -> bool {
+    record.tags.iter().any(|t| t == EPHEMERAL_TAG)
+}
+
+/// Store `request` without embedding it.
+pub async fn execute(engine: &MnemoEngine, request: RememberRequest) -> Result<RememberResponse> {
+    super::remember::store(engine, request, super::remember::EmbedWith::Nothing).await
+}
@@ -46,9 +46,22 @@ pub async fn build_event(
         latency_ms: None,
         cost_usd: None,
         timestamp: now.clone(),
-        logical_clock: 0,
+        logical_clock: super::clock::tick(engine, agent_id).await,
         content_hash: event_content_hash,
         prev_hash: event_prev_hash,
         embedding: None,
+        request_id: crate::request_id::current(),
+    }
+}
+
+/// Link `events`, built before any of them was stored, into one hash chain
+/// in order: each event after the first names its predecessor.
+pub(crate) fn chain_in_order(events: &mut [AgentEvent]) {
+    for i in 1..events.len() {
+        let previous = events[i - 1].content_hash.clone();
+        events[i].prev_hash = Some(crate::hash::compute_chain_hash(
+            &events[i].content_hash,
+            Some(previous.as_slice()),
+        ));
     }
 }
@@ -0,0 +1,296 @@
+//! Event retention and archival.
+//!
+//! `agent_events` is append-only, so it grows without bound and chain
+//! verification slows with it. With an [`EventRetentionPolicy`] attached
+//! ([`MnemoEngine::with_event_retention`]) and cold storage configured,
+//! [`archive`] moves events older than `hot_days` out of the hot table:
+//! each agent's run of old events is written to cold storage as one
+//! Parquet object, and an [`EventArchiveSegment`] manifest row keeps the
+//! segment's Merkle root, bounds and object key in the hot database. The
+//! cleanup pass runs it, so the maintenance scheduler enforces the policy.
+//!
+//! Archived events leave replay, causality traces and event verification,
+//! which only see the hot table. For an audit, [`restore`] reads a segment
+//! back from cold storage and checks it against its stored Merkle root and
+//! hash chain. Event embeddings are not archived.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::hash::{ChainVerificationResult, compute_event_merkle_root, verify_event_chain};
+use crate::model::event::AgentEvent;
+use crate::model::event_archive::EventArchiveSegment;
+use crate::query::MnemoEngine;
+
+/// Default age, in days, after which events are archived.
+pub const DEFAULT_EVENT_HOT_DAYS: u32 = 90;
+/// Default number of events one archival pass moves.
+pub const DEFAULT_EVENT_ARCHIVE_BATCH: usize = 10_000;
+
+/// How long events stay in the hot table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EventRetentionPolicy {
+    /// Archive events older than this many days.
+    pub hot_days: u32,
+    /// Most events moved by one pass; the next pass picks up the rest.
+    pub batch_size: usize,
+}
+
+impl Default for EventRetentionPolicy {
+    fn default() -> Self {
+        Self {
+            hot_days: DEFAULT_EVENT_HOT_DAYS,
+            batch_size: DEFAULT_EVENT_ARCHIVE_BATCH,
+        }
+    }
+}
+
+impl EventRetentionPolicy {
+    pub fn validate(&self) -> Result<()> {
+        if self.hot_days == 0 {
+            return Err(Error::Validation(
+                "features.event_retention.hot_days must be > 0".to_string(),
+            ));
+        }
+        if self.batch_size == 0 {
+            return Err(Error::Validation(
+                "features.event_retention.batch_size must be > 0".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventArchiveReport {
+    pub events_archived: usize,
+    /// The segments written by this pass, one per agent.
+    pub segments: Vec<EventArchiveSegment>,
+}
+
+/// An archived segment read back from cold storage.
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestoredEventSegment {
+    pub segment: EventArchiveSegment,
+    /// In logical-clock order.
+    pub events: Vec<AgentEvent>,
+    /// Whether the events hash to the segment's stored Merkle root.
+    pub merkle_root_matches: bool,
+    /// Hash-chain verification of the restored events.
+    pub chain: ChainVerificationResult,
+}
+
+/// How many events the next [`archive`] pass would move.
+pub(crate) async fn archivable(engine: &MnemoEngine) -> Result<usize> {
+    let Some(ref policy) = engine.event_retention else {
+        return Ok(0);
+    };
+    if engine.cold_storage.is_none() {
+        return Err(Error::Validation(
+            "event archival needs cold storage".to_string(),
+        ));
+    }
+    let cutoff =
+        (chrono::Utc::now() - chrono::Duration::days(i64::from(policy.hot_days))).to_rfc3339();
+    Ok(engine
+        .storage
+        .list_events_before(&cutoff, policy.batch_size)
+        .await?
+        .len())
+}
+
+/// Move events older than the policy's hot window to cold storage. A no-op
+/// without an [`EventRetentionPolicy`].
+pub async fn archive(engine: &MnemoEngine) -> Result<EventArchiveReport> {
+    let mut report = EventArchiveReport::default();
+    let Some(ref policy) = engine.event_retention else {
+        return Ok(report);
+    };
+    let Some(ref cold) = engine.cold_storage else {
+        return Err(Error::Validation(
+            "event archival needs cold storage".to_string(),
+        ));
+    };
+    let cutoff =
+        (chrono::Utc::now() - chrono::Duration::days(i64::from(policy.hot_days))).to_rfc3339();
+    let events = engine
+        .storage
+        .list_events_before(&cutoff, policy.batch_size)
+        .await?;
+
+    for agent_events in events.chunk_by(|a, b| a.agent_id == b.agent_id) {
+        let (first, last) = (&agent_events[0], &agent_events[agent_events.len() - 1]);
+        let id = Uuid::now_v7();
+        let object_key = format!("events/{}/{id}.parquet", first.agent_id);
+        cold.put_object(&object_key, events_to_parquet(agent_events)?)
+            .await?;
+        let segment = EventArchiveSegment {
+            id,
+            agent_id: first.agent_id.clone(),
+            first_event_id: first.id,
+            last_event_id: last.id,
+            from_timestamp: first.timestamp.clone(),
+            to_timestamp: last.timestamp.clone(),
+            first_logical_clock: first.logical_clock,
+            last_logical_clock: last.logical_clock,
+            event_count: agent_events.len(),
+            merkle_root: hex::encode(compute_event_merkle_root(agent_events)),
+            last_content_hash: hex::encode(&last.content_hash),
+            object_key,
+            archived_at: chrono::Utc::now().to_rfc3339(),
+        };
+        let ids: Vec<Uuid> = agent_events.iter().map(|e| e.id).collect();
+        engine.storage.archive_event_segment(&segment, &ids).await?;
+        report.events_archived += agent_events.len();
+        report.segments.push(segment);
+    }
+    Ok(report)
+}
+
+/// Archived segments, oldest first.
+pub async fn list(
+    engine: &MnemoEngine,
+    agent_id: Option<&str>,
+) -> Result<Vec<EventArchiveSegment>> {
+    engine.storage.list_event_archives(agent_id).await
+}
+
+/// Read segment `id` back from cold storage and verify it. The events are
+/// returned, not put back into the hot table.
+pub async fn restore(engine: &MnemoEngine, id: Uuid) -> Result<RestoredEventSegment> {
+    let segment = engine
+        .storage
+        .get_event_archive(id)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("event archive {id} not found")))?;
+    let Some(ref cold) = engine.cold_storage else {
+        return Err(Error::Validation(
+            "restoring archived events needs cold storage".to_string(),
+        ));
+    };
+    let events = events_from_parquet(&cold.get_object(&segment.object_key).await?)?;
+    let merkle_root_matches =
+        hex::encode(compute_event_merkle_root(&events)) == segment.merkle_root;
+    let chain = verify_event_chain(&events);
+    Ok(RestoredEventSegment {
+        segment,
+        events,
+        merkle_root_matches,
+        chain,
+    })
+}
+
+const ARCHIVE_TABLE: &str = "CREATE TABLE events (
+    id VARCHAR, agent_id VARCHAR, thread_id VARCHAR, run_id VARCHAR,
+    parent_event_id VARCHAR, event_type VARCHAR, payload VARCHAR,
+    trace_id VARCHAR, span_id VARCHAR, model VARCHAR, tokens_input BIGINT,
+    tokens_output BIGINT, latency_ms BIGINT, cost_usd DOUBLE,
+    timestamp VARCHAR, logical_clock BIGINT, content_hash BLOB,
+    prev_hash BLOB, request_id VARCHAR
+)";
+
+/// Events as Parquet, written through an in-memory DuckDB.
+fn events_to_parquet(events: &[AgentEvent]) -> Result<Vec<u8>> {
+    let conn = duckdb::Connection::open_in_memory()?;
+    conn.execute_batch(ARCHIVE_TABLE)?;
+    {
+        let mut appender = conn.appender("events")?;
+        for e in events {
+            appender.append_row(duckdb::params![
+                e.id.to_string(),
+                e.agent_id,
+                e.thread_id,
+                e.run_id,
+                e.parent_event_id.map(|id| id.to_string()),
+                e.event_type.to_string(),
+                serde_json::to_string(&e.payload)?,
+                e.trace_id,
+                e.span_id,
+                e.model,
+                e.tokens_input,
+                e.tokens_output,
+                e.latency_ms,
+                e.cost_usd,
+                e.timestamp,
+                e.logical_clock,
+                e.content_hash,
+                e.prev_hash,
+                e.request_id,
+            ])?;
+        }
+    }
+    let path = temp_path();
+    conn.execute_batch(&format!(
+        "COPY events TO '{}' (FORMAT PARQUET)",
+        sql_path(&path)
+    ))?;
+    let bytes = std::fs::read(&path);
+    let _ = std::fs::remove_file(&path);
+    bytes.map_err(|e| Error::Storage(format!("reading event archive: {e}")))
+}
+
+/// Events from a Parquet object written by [`events_to_parquet`], in
+/// logical-clock order.
+fn events_from_parquet(bytes: &[u8]) -> Result<Vec<AgentEvent>> {
+    let path = temp_path();
+    std::fs::write(&path, bytes)
+        .map_err(|e| Error::Storage(format!("writing event archive: {e}")))?;
+    let events = read_parquet(&path);
+    let _ = std::fs::remove_file(&path);
+    events
+}
+
+fn read_parquet(path: &std::path::Path) -> Result<Vec<AgentEvent>> {
+    let conn = duckdb::Connection::open_in_memory()?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, agent_id, thread_id, run_id, parent_event_id, event_type, payload, trace_id, span_id, model, tokens_input, tokens_output, latency_ms, cost_usd, timestamp, logical_clock, content_hash, prev_hash, request_id FROM read_parquet('{}') ORDER BY logical_clock, timestamp, id",
+        sql_path(path)
+    ))?;
+    let mut rows = stmt.query([])?;
+    let corrupt = |e: uuid::Error| Error::Storage(format!("corrupt event archive: {e}"));
+    let mut events = Vec::new();
+    while let Some(row) = rows.next()? {
+        let id: String = row.get(0)?;
+        let parent: Option<String> = row.get(4)?;
+        let event_type: String = row.get(5)?;
+        let payload: String = row.get(6)?;
+        events.push(AgentEvent::from_parts(
+            Uuid::parse_str(&id).map_err(corrupt)?,
+            row.get(1)?,
+            row.get(2)?,
+            row.get(3)?,
+            parent
+                .map(|p| Uuid::parse_str(&p))
+                .transpose()
+                .map_err(corrupt)?,
+            event_type.parse()?,
+            serde_json::from_str(&payload)?,
+            row.get(7)?,
+            row.get(8)?,
+            row.get(9)?,
+            row.get(10)?,
+            row.get(11)?,
+            row.get(12)?,
+            row.get(13)?,
+            row.get(14)?,
+            row.get(15)?,
+            row.get(16)?,
+            row.get(17)?,
+            None,
+            row.get(18)?,
+        ));
+    }
+    Ok(events)
+}
+
+fn temp_path() -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("mnemo-events-{}.parquet", Uuid::now_v7()))
+}
+
+fn sql_path(path: &std::path::Path) -> String {
+    path.display().to_string().replace('\'', "''")
+}
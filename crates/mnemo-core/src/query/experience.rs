@@ -188,7 +188,8 @@ async fn plan_visible_to(
     agent_id: &str,
 ) -> bool {
     match record.scope {
-        Scope::Public | Scope::Global => true,
+        Scope::Public => true,
+        Scope::Global => record.agent_id == agent_id || super::curation::visible_to_others(record),
         Scope::Private => record.agent_id == agent_id,
         Scope::Shared => {
             record.agent_id == agent_id
@@ -295,7 +296,7 @@ pub async fn execute_recall_plan(
     let mut considered = 0usize;
     let mut best: Option<CachedPlan> = None;
     for record in &records {
-        if record.is_deleted() || record.quarantined {
+        if record.is_deleted() || record.quarantined || !super::attestation::is_recallable(record) {
             continue;
         }
         if !plan_visible_to(engine, record, &agent_id).await {
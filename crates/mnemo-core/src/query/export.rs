@@ -0,0 +1,693 @@
+//! Scheduled exports.
+//!
+//! Compliance reviews want periodic dumps of what the engine remembers.
+//! Each [`ExportSchedule`] (an `[[exports]]` table of the engine config
+//! file, or [`MnemoEngine::with_exports`]) names a cron expression, a file
+//! format, a destination and a filter. [`run_scheduled_exports`], called
+//! by the server's maintenance loop, runs every schedule that came due
+//! since its last run: it writes one object per run under
+//! `<schedule>/<schedule>-<timestamp>.<format>` and then applies the
+//! schedule's [`ExportRetention`] to the older objects there.
+//!
+//! Every run, successful or not, is recorded as an
+//! [`EventType::ExportRun`] event on the engine's default agent (see
+//! [`list_export_runs`]). With the outbox enabled, a failed run also
+//! queues an [`EXPORT_FAILED_TOPIC`] message in the same transaction, so
+//! the outbox webhook alerts on it.
+//!
+//! Exported records carry decrypted content and no embeddings. The time of
+//! each schedule's last run is kept as a sync watermark, so a restart
+//! neither skips nor repeats a run; a schedule seen for the first time
+//! counts from then.
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::cron::CronSchedule;
+use crate::error::{Error, Result};
+use crate::model::acl::Permission;
+use crate::model::event::EventType;
+use crate::model::memory::{MemoryRecord, MemoryType, Scope};
+use crate::model::outbox::OutboxMessage;
+use crate::query::MnemoEngine;
+use crate::storage::{MemoryFilter, WriteBatch};
+
+/// Outbox topic of the message a failed export run queues.
+pub const EXPORT_FAILED_TOPIC: &str = "export_failed";
+
+/// Timestamp part of an export object's name.
+const OBJECT_TIME_FORMAT: &str = "%Y%m%dT%H%M%S%3fZ";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// One JSON memory record per line.
+    #[default]
+    Jsonl,
+    /// One row per memory; tags and metadata as JSON text.
+    Parquet,
+}
+
+impl ExportFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Jsonl => "jsonl",
+            ExportFormat::Parquet => "parquet",
+        }
+    }
+}
+
+/// Where an export schedule writes its objects.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum ExportDestination {
+    /// A local or mounted directory.
+    Filesystem { path: PathBuf },
+    /// S3-compatible object storage (needs the `s3` feature).
+    S3 {
+        bucket: String,
+        #[serde(default)]
+        prefix: String,
+        #[serde(default)]
+        endpoint: Option<String>,
+        region: String,
+    },
+}
+
+/// Which memories an export holds. The default exports every live memory
+/// of every agent.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExportFilter {
+    pub agent_id: Option<String>,
+    pub memory_type: Option<MemoryType>,
+    pub scope: Option<Scope>,
+    /// Memories carrying any of these tags.
+    pub tags: Option<Vec<String>>,
+    pub org_id: Option<String>,
+    /// Also export soft-deleted memories.
+    pub include_deleted: bool,
+}
+
+/// Which earlier objects of a schedule to delete after a successful run.
+/// Both limits apply; neither set keeps everything.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExportRetention {
+    /// Keep this many most recent objects, the new one included.
+    pub keep_last: Option<usize>,
+    /// Delete objects older than this.
+    pub max_age_days: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExportSchedule {
+    /// Identifies the schedule in run records and object names: letters,
+    /// digits, `-` and `_`.
+    pub name: String,
+    /// Five-field cron expression in UTC, see [`crate::cron`].
+    pub cron: String,
+    #[serde(default)]
+    pub format: ExportFormat,
+    pub destination: ExportDestination,
+    #[serde(default)]
+    pub filter: ExportFilter,
+    #[serde(default)]
+    pub retention: ExportRetention,
+    /// Export on this agent's behalf: only memories it owns or holds
+    /// [`Permission::Export`] on are written. `None` exports everything
+    /// the filter matches, as the operator.
+    #[serde(default)]
+    pub principal: Option<String>,
+}
+
+impl ExportSchedule {
+    pub fn new(
+        name: impl Into<String>,
+        cron: impl Into<String>,
+        destination: ExportDestination,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            cron: cron.into(),
+            format: ExportFormat::default(),
+            destination,
+            filter: ExportFilter::default(),
+            retention: ExportRetention::default(),
+            principal: None,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.name.is_empty()
+            || !self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(Error::Validation(format!(
+                "export name {:?} must be letters, digits, '-' or '_'",
+                self.name
+            )));
+        }
+        if let Some(ref principal) = self.principal {
+            super::validate_agent_id(principal)?;
+        }
+        CronSchedule::parse(&self.cron)
+            .map_err(|e| Error::Validation(format!("export {}: {e}", self.name)))?;
+        if self.retention.keep_last == Some(0) {
+            return Err(Error::Validation(format!(
+                "export {}: retention.keep_last must be > 0",
+                self.name
+            )));
+        }
+        #[cfg(not(feature = "s3"))]
+        if matches!(self.destination, ExportDestination::S3 { .. }) {
+            return Err(Error::Validation(format!(
+                "export {}: destination.backend = \"s3\" needs mnemo-core built with the `s3` feature",
+                self.name
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportStatus {
+    Succeeded,
+    Failed,
+}
+
+/// Record of one export run.
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportRun {
+    pub id: Uuid,
+    pub schedule: String,
+    pub status: ExportStatus,
+    pub started_at: String,
+    pub finished_at: String,
+    /// Where the export was written; `None` when the run failed before.
+    pub location: Option<String>,
+    pub memories: usize,
+    pub bytes: usize,
+    /// Locations of earlier objects retention deleted.
+    pub pruned: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// Run every schedule whose next fire time after its last run is at or
+/// before `now`. A failing schedule does not stop the others; its run is
+/// returned with [`ExportStatus::Failed`].
+pub async fn run_scheduled_exports(
+    engine: &MnemoEngine,
+    now: DateTime<Utc>,
+) -> Result<Vec<ExportRun>> {
+    let mut runs = Vec::new();
+    for schedule in engine.exports().iter() {
+        let cron = CronSchedule::parse(&schedule.cron)?;
+        let watermark = format!("export:{}", schedule.name);
+        let last_run = engine
+            .storage
+            .get_sync_watermark(&watermark)
+            .await?
+            .and_then(|ts| DateTime::parse_from_rfc3339(&ts).ok())
+            .map(|ts| ts.with_timezone(&Utc));
+        let due = match last_run {
+            Some(last_run) => cron.next_after(last_run).is_some_and(|next| next <= now),
+            None => false,
+        };
+        if due {
+            runs.push(run_schedule(engine, schedule).await);
+        }
+        if due || last_run.is_none() {
+            engine
+                .storage
+                .set_sync_watermark(&watermark, &now.to_rfc3339())
+                .await?;
+        }
+    }
+    Ok(runs)
+}
+
+/// Run the schedule called `name` now, outside its cron times.
+pub async fn run_export(engine: &MnemoEngine, name: &str) -> Result<ExportRun> {
+    let schedules = engine.exports();
+    let schedule = schedules
+        .iter()
+        .find(|s| s.name == name)
+        .ok_or_else(|| Error::NotFound(format!("export schedule {name}")))?;
+    Ok(run_schedule(engine, schedule).await)
+}
+
+/// Recorded runs, newest first, of `schedule` or of every schedule.
+pub async fn list_export_runs(
+    engine: &MnemoEngine,
+    schedule: Option<&str>,
+    limit: usize,
+) -> Result<Vec<ExportRun>> {
+    let mut runs: Vec<ExportRun> = Vec::new();
+    let mut offset = 0;
+    loop {
+        let page = engine
+            .storage
+            .list_events(
+                &engine.default_agent_id,
+                super::MAX_BATCH_QUERY_LIMIT,
+                offset,
+            )
+            .await?;
+        offset += page.len();
+        let done = page.len() < super::MAX_BATCH_QUERY_LIMIT;
+        runs.extend(
+            page.into_iter()
+                .filter(|e| e.event_type == EventType::ExportRun)
+                .filter_map(|e| serde_json::from_value::<ExportRun>(e.payload).ok())
+                .filter(|run| schedule.is_none_or(|s| run.schedule == s)),
+        );
+        if done {
+            break;
+        }
+    }
+    runs.sort_by(|a, b| b.started_at.cmp(&a.started_at).then(b.id.cmp(&a.id)));
+    runs.truncate(limit);
+    Ok(runs)
+}
+
+async fn run_schedule(engine: &MnemoEngine, schedule: &ExportSchedule) -> ExportRun {
+    let started = Utc::now();
+    let mut run = ExportRun {
+        id: Uuid::now_v7(),
+        schedule: schedule.name.clone(),
+        status: ExportStatus::Succeeded,
+        started_at: started.to_rfc3339(),
+        finished_at: String::new(),
+        location: None,
+        memories: 0,
+        bytes: 0,
+        pruned: Vec::new(),
+        error: None,
+    };
+    if let Err(e) = write_export(engine, schedule, started, &mut run).await {
+        tracing::warn!(schedule = %schedule.name, error = %e, "export run failed");
+        run.status = ExportStatus::Failed;
+        run.error = Some(e.to_string());
+    }
+    run.finished_at = Utc::now().to_rfc3339();
+    record_run(engine, &run).await;
+    run
+}
+
+async fn write_export(
+    engine: &MnemoEngine,
+    schedule: &ExportSchedule,
+    started: DateTime<Utc>,
+    run: &mut ExportRun,
+) -> Result<()> {
+    let filter = &schedule.filter;
+    let memory_filter = MemoryFilter {
+        agent_id: filter.agent_id.clone(),
+        memory_type: filter.memory_type,
+        scope: filter.scope,
+        tags: filter.tags.clone(),
+        min_importance: None,
+        org_id: filter.org_id.clone(),
+        thread_id: None,
+        include_deleted: filter.include_deleted,
+    };
+    let mut records = Vec::new();
+    loop {
+        let page = engine
+            .storage
+            .list_memories(&memory_filter, super::MAX_BATCH_QUERY_LIMIT, records.len())
+            .await?;
+        let done = page.len() < super::MAX_BATCH_QUERY_LIMIT;
+        records.extend(page);
+        if done {
+            break;
+        }
+    }
+    if let Some(ref principal) = schedule.principal {
+        let mut permitted = Vec::with_capacity(records.len());
+        for record in records {
+            if engine
+                .storage
+                .check_permission(record.id, principal, Permission::Export)
+                .await?
+            {
+                permitted.push(record);
+            }
+        }
+        records = permitted;
+    }
+    records.sort_by(|a, b| (&a.created_at, a.id).cmp(&(&b.created_at, b.id)));
+    for record in &mut records {
+        super::consolidate::decrypt_in_place(engine, record);
+        super::pseudonym::pseudonymize_record(engine, record).await?;
+        record.embedding = None;
+    }
+    let body = match schedule.format {
+        ExportFormat::Jsonl => {
+            let mut body = Vec::new();
+            for record in &records {
+                serde_json::to_writer(&mut body, record)?;
+                body.push(b'\n');
+            }
+            body
+        }
+        ExportFormat::Parquet => parquet_bytes(&records)?,
+    };
+
+    let sink = open_sink(&schedule.destination).await?;
+    let key = format!(
+        "{name}/{name}-{time}.{ext}",
+        name = schedule.name,
+        time = started.format(OBJECT_TIME_FORMAT),
+        ext = schedule.format.extension()
+    );
+    run.memories = records.len();
+    run.bytes = body.len();
+    sink.put(&key, body).await?;
+    run.location = Some(sink.location(&key));
+
+    // A failed cleanup leaves extra objects behind but the export stands.
+    match prune(sink.as_ref(), schedule, &key, started).await {
+        Ok(pruned) => run.pruned = pruned,
+        Err(e) => {
+            tracing::warn!(schedule = %schedule.name, error = %e, "export retention failed");
+        }
+    }
+    Ok(())
+}
+
+/// Delete the schedule's objects retention no longer keeps, never the one
+/// just written.
+async fn prune(
+    sink: &dyn ExportSink,
+    schedule: &ExportSchedule,
+    current: &str,
+    now: DateTime<Utc>,
+) -> Result<Vec<String>> {
+    let retention = &schedule.retention;
+    if retention.keep_last.is_none() && retention.max_age_days.is_none() {
+        return Ok(Vec::new());
+    }
+    let name_prefix = format!("{}/{}-", schedule.name, schedule.name);
+    let mut objects: Vec<(DateTime<Utc>, String)> = sink
+        .list(&schedule.name)
+        .await?
+        .into_iter()
+        .filter_map(|key| {
+            let stem = key.strip_prefix(&name_prefix)?.split('.').next()?;
+            let time = NaiveDateTime::parse_from_str(stem, OBJECT_TIME_FORMAT).ok()?;
+            Some((time.and_utc(), key))
+        })
+        .collect();
+    objects.sort_by(|a, b| b.cmp(a));
+
+    let cutoff = retention
+        .max_age_days
+        .map(|days| now - chrono::Duration::days(i64::from(days)));
+    let mut pruned = Vec::new();
+    for (position, (time, key)) in objects.iter().enumerate() {
+        if key == current {
+            continue;
+        }
+        let beyond_count = retention.keep_last.is_some_and(|n| position >= n);
+        let too_old = cutoff.is_some_and(|cutoff| *time < cutoff);
+        if beyond_count || too_old {
+            sink.delete(key).await?;
+            pruned.push(sink.location(key));
+        }
+    }
+    Ok(pruned)
+}
+
+/// Store the run's event and, when it failed and the outbox is on, its
+/// alert. Failures are logged: the export itself is done.
+async fn record_run(engine: &MnemoEngine, run: &ExportRun) {
+    let payload = match serde_json::to_value(run) {
+        Ok(payload) => payload,
+        Err(e) => {
+            tracing::error!(error = %e, "failed to serialize export run");
+            return;
+        }
+    };
+    let event = super::event_builder::build_event(
+        engine,
+        &engine.default_agent_id,
+        EventType::ExportRun,
+        payload.clone(),
+        &run.id.to_string(),
+        None,
+    )
+    .await;
+    let mut batch = WriteBatch {
+        events: vec![event],
+        ..Default::default()
+    };
+    if run.status == ExportStatus::Failed && engine.outbox_enabled {
+        batch
+            .outbox
+            .push(OutboxMessage::new(EXPORT_FAILED_TOPIC, payload));
+    }
+    if let Err(e) = engine.storage.commit_write(&batch).await {
+        tracing::error!(run_id = %run.id, error = %e, "failed to record export run");
+    }
+}
+
+/// Records as Parquet, built in memory.
+fn parquet_bytes(records: &[MemoryRecord]) -> Result<Vec<u8>> {
+    use crate::parquet::ColumnValues::{Float, Utf8};
+
+    let text = |field: fn(&MemoryRecord) -> Option<&str>| Utf8(records.iter().map(field).collect());
+    let ids: Vec<String> = records.iter().map(|r| r.id.to_string()).collect();
+    let memory_types: Vec<String> = records.iter().map(|r| r.memory_type.to_string()).collect();
+    let scopes: Vec<String> = records.iter().map(|r| r.scope.to_string()).collect();
+    let tags = records
+        .iter()
+        .map(|r| serde_json::to_string(&r.tags))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let metadata = records
+        .iter()
+        .map(|r| serde_json::to_string(&r.metadata))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let hashes: Vec<String> = records
+        .iter()
+        .map(|r| hex::encode(&r.content_hash))
+        .collect();
+    fn owned(values: &[String]) -> crate::parquet::ColumnValues<'_> {
+        Utf8(values.iter().map(|v| Some(v.as_str())).collect())
+    }
+    crate::parquet::write_table(&[
+        ("id", owned(&ids)),
+        ("agent_id", text(|r| Some(&r.agent_id))),
+        ("content", text(|r| Some(&r.content))),
+        ("memory_type", owned(&memory_types)),
+        ("scope", owned(&scopes)),
+        (
+            "importance",
+            Float(records.iter().map(|r| Some(r.importance)).collect()),
+        ),
+        ("tags", owned(&tags)),
+        ("metadata", owned(&metadata)),
+        ("org_id", text(|r| r.org_id.as_deref())),
+        ("thread_id", text(|r| r.thread_id.as_deref())),
+        ("content_hash", owned(&hashes)),
+        ("created_at", text(|r| Some(&r.created_at))),
+        ("updated_at", text(|r| Some(&r.updated_at))),
+        ("expires_at", text(|r| r.expires_at.as_deref())),
+        ("deleted_at", text(|r| r.deleted_at.as_deref())),
+    ])
+}
+
+/// Object store an export schedule writes to. Keys are `/`-separated.
+#[async_trait::async_trait]
+trait ExportSink: Send + Sync {
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<()>;
+    /// Keys of the objects directly under `dir`.
+    async fn list(&self, dir: &str) -> Result<Vec<String>>;
+    async fn delete(&self, key: &str) -> Result<()>;
+    /// Path or URL of `key`, as recorded in run records.
+    fn location(&self, key: &str) -> String;
+}
+
+async fn open_sink(destination: &ExportDestination) -> Result<Box<dyn ExportSink>> {
+    match destination {
+        ExportDestination::Filesystem { path } => {
+            Ok(Box::new(FilesystemSink { root: path.clone() }))
+        }
+        #[cfg(feature = "s3")]
+        ExportDestination::S3 {
+            bucket,
+            prefix,
+            endpoint,
+            region,
+        } => {
+            let mut loader = aws_config::defaults(aws_config::BehaviorVersion::latest())
+                .region(aws_config::Region::new(region.clone()));
+            if let Some(endpoint) = endpoint {
+                loader = loader.endpoint_url(endpoint);
+            }
+            Ok(Box::new(S3Sink {
+                client: aws_sdk_s3::Client::new(&loader.load().await),
+                bucket: bucket.clone(),
+                prefix: prefix.trim_end_matches('/').to_string(),
+            }))
+        }
+        #[cfg(not(feature = "s3"))]
+        ExportDestination::S3 { .. } => Err(Error::Validation(
+            "S3 export destinations need mnemo-core built with the `s3` feature".to_string(),
+        )),
+    }
+}
+
+struct FilesystemSink {
+    root: PathBuf,
+}
+
+fn io_error(action: &str, path: &std::path::Path, e: std::io::Error) -> Error {
+    Error::Storage(format!("{action} {}: {e}", path.display()))
+}
+
+#[async_trait::async_trait]
+impl ExportSink for FilesystemSink {
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<()> {
+        let path = self.root.join(key);
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir)
+                .await
+                .map_err(|e| io_error("creating", dir, e))?;
+        }
+        // Write aside and rename, so a reader never sees half an export.
+        let partial = path.with_extension("partial");
+        tokio::fs::write(&partial, body)
+            .await
+            .map_err(|e| io_error("writing", &partial, e))?;
+        tokio::fs::rename(&partial, &path)
+            .await
+            .map_err(|e| io_error("renaming", &partial, e))
+    }
+
+    async fn list(&self, dir: &str) -> Result<Vec<String>> {
+        let path = self.root.join(dir);
+        let mut entries = match tokio::fs::read_dir(&path).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(io_error("listing", &path, e)),
+        };
+        let mut keys = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| io_error("listing", &path, e))?
+        {
+            if let Some(name) = entry.file_name().to_str() {
+                keys.push(format!("{dir}/{name}"));
+            }
+        }
+        Ok(keys)
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let path = self.root.join(key);
+        tokio::fs::remove_file(&path)
+            .await
+            .map_err(|e| io_error("deleting", &path, e))
+    }
+
+    fn location(&self, key: &str) -> String {
+        self.root.join(key).display().to_string()
+    }
+}
+
+#[cfg(feature = "s3")]
+struct S3Sink {
+    client: aws_sdk_s3::Client,
+    bucket: String,
+    prefix: String,
+}
+
+#[cfg(feature = "s3")]
+impl S3Sink {
+    fn object_key(&self, key: &str) -> String {
+        if self.prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}/{key}", self.prefix)
+        }
+    }
+}
+
+#[cfg(feature = "s3")]
+#[async_trait::async_trait]
+impl ExportSink for S3Sink {
+    async fn put(&self, key: &str, body: Vec<u8>) -> Result<()> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(self.object_key(key))
+            .body(aws_sdk_s3::primitives::ByteStream::from(body))
+            .send()
+            .await
+            .map_err(|e| Error::Storage(format!("S3 put_object failed: {e}")))?;
+        Ok(())
+    }
+
+    async fn list(&self, dir: &str) -> Result<Vec<String>> {
+        let prefix = self.object_key(&format!("{dir}/"));
+        let strip = self.object_key("");
+        let mut keys = Vec::new();
+        let mut continuation_token: Option<String> = None;
+        loop {
+            let mut request = self
+                .client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .prefix(&prefix)
+                .delimiter("/");
+            if let Some(ref token) = continuation_token {
+                request = request.continuation_token(token);
+            }
+            let response = request
+                .send()
+                .await
+                .map_err(|e| Error::Storage(format!("S3 list_objects_v2 failed: {e}")))?;
+            keys.extend(response.contents().iter().filter_map(|o| {
+                let key = o.key()?;
+                Some(
+                    key.strip_prefix(&strip)
+                        .map(|k| k.trim_start_matches('/'))
+                        .unwrap_or(key)
+                        .to_string(),
+                )
+            }));
+            match response.next_continuation_token() {
+                Some(token) if response.is_truncated() == Some(true) => {
+                    continuation_token = Some(token.to_string());
+                }
+                _ => return Ok(keys),
+            }
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(self.object_key(key))
+            .send()
+            .await
+            .map_err(|e| Error::Storage(format!("S3 delete_object failed: {e}")))?;
+        Ok(())
+    }
+
+    fn location(&self, key: &str) -> String {
+        format!("s3://{}/{}", self.bucket, self.object_key(key))
+    }
+}
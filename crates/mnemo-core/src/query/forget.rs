@@ -40,6 +40,14 @@ pub struct ForgetRequest {
     pub agent_id: Option<String>,
     pub strategy: Option<ForgetStrategy>,
     pub criteria: Option<ForgetCriteria>,
+    /// Required to remove pinned memories. Without it, pinned ids are
+    /// reported in `errors` and criteria matches skip them.
+    #[serde(default)]
+    pub force: Option<bool>,
+    /// Run every check and report what would be forgotten in `forgotten`
+    /// and `errors`, but change nothing and record no events.
+    #[serde(default)]
+    pub dry_run: Option<bool>,
 }
 
 impl ForgetRequest {
@@ -49,6 +57,8 @@ impl ForgetRequest {
             agent_id: None,
             strategy: None,
             criteria: None,
+            force: None,
+            dry_run: None,
         }
     }
 }
@@ -58,11 +68,19 @@ impl ForgetRequest {
 pub struct ForgetResponse {
     pub forgotten: Vec<Uuid>,
     pub errors: Vec<ForgetError>,
+    /// True when nothing was changed: `forgotten` lists the memories the
+    /// request would forget.
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 impl ForgetResponse {
     pub fn new(forgotten: Vec<Uuid>, errors: Vec<ForgetError>) -> Self {
-        Self { forgotten, errors }
+        Self {
+            forgotten,
+            errors,
+            dry_run: false,
+        }
     }
 }
 
@@ -77,6 +95,11 @@ pub async fn execute(engine: &MnemoEngine, request: ForgetRequest) -> Result<For
         .agent_id
         .unwrap_or_else(|| engine.default_agent_id.clone());
     let strategy = request.strategy.unwrap_or(ForgetStrategy::SoftDelete);
+    let force = request.force.unwrap_or(false);
+    let dry_run = request.dry_run.unwrap_or(false);
+    engine
+        .limits()
+        .check_batch("memory_ids", request.memory_ids.len())?;
 
     // If criteria is specified and memory_ids is empty, find matching memories
     let memory_ids = if request.memory_ids.is_empty() {
@@ -94,6 +117,9 @@ pub async fn execute(engine: &MnemoEngine, request: ForgetRequest) -> Result<For
             memories
                 .into_iter()
                 .filter(|m| {
+                    if m.pinned && !force {
+                        return false;
+                    }
                     if let Some(max_age) = criteria.max_age_hours
                         && let Ok(created) = chrono::DateTime::parse_from_rfc3339(&m.created_at)
                     {
@@ -126,6 +152,7 @@ pub async fn execute(engine: &MnemoEngine, request: ForgetRequest) -> Result<For
         return Ok(ForgetResponse {
             forgotten: vec![],
             errors: vec![],
+            dry_run,
         });
     }
 
@@ -156,6 +183,59 @@ pub async fn execute(engine: &MnemoEngine, request: ForgetRequest) -> Result<For
             }
         }
 
+        // Pinned memories are protected unless the caller passes `force`.
+        if !force
+            && let Ok(Some(record)) = engine.storage.get_memory(*id).await
+            && record.pinned
+        {
+            errors.push(ForgetError {
+                id: *id,
+                error: "memory is pinned; pass force to remove it".to_string(),
+            });
+            continue;
+        }
+
+        // Retention locks block erasure even with `force`; soft delete and
+        // archiving stay allowed because the row survives them.
+        if matches!(
+            strategy,
+            ForgetStrategy::HardDelete | ForgetStrategy::Redact
+        ) && engine.retention_lock.is_some()
+            && let Ok(Some(record)) = engine.storage.get_memory(*id).await
+            && let Err(e) = super::retention_lock::ensure_erasable(
+                engine,
+                &record,
+                if strategy == ForgetStrategy::Redact {
+                    "redact"
+                } else {
+                    "hard_delete"
+                },
+                &agent_id,
+            )
+            .await
+        {
+            errors.push(ForgetError {
+                id: *id,
+                error: e.to_string(),
+            });
+            continue;
+        }
+
+        if dry_run {
+            match engine.storage.get_memory(*id).await {
+                Ok(Some(_)) => forgotten.push(*id),
+                Ok(None) => errors.push(ForgetError {
+                    id: *id,
+                    error: "not found".to_string(),
+                }),
+                Err(e) => errors.push(ForgetError {
+                    id: *id,
+                    error: e.to_string(),
+                }),
+            }
+            continue;
+        }
+
         // Execute strategy
         match strategy {
             ForgetStrategy::SoftDelete => match engine.storage.soft_delete_memory(*id).await {
@@ -324,6 +404,14 @@ pub async fn execute(engine: &MnemoEngine, request: ForgetRequest) -> Result<For
         }
     }
 
+    if dry_run {
+        return Ok(ForgetResponse {
+            forgotten,
+            errors,
+            dry_run,
+        });
+    }
+
     // Emit MemoryDelete event for each forgotten memory with hash chaining (fire-and-forget)
     let now = chrono::Utc::now().to_rfc3339();
     for id in &forgotten {
@@ -355,10 +443,11 @@ pub async fn execute(engine: &MnemoEngine, request: ForgetRequest) -> Result<For
             latency_ms: None,
             cost_usd: None,
             timestamp: now.clone(),
-            logical_clock: 0,
+            logical_clock: super::clock::tick(engine, &agent_id).await,
             content_hash: event_content_hash,
             prev_hash: event_prev_hash,
             embedding: None,
+            request_id: crate::request_id::current(),
         };
         if let Err(e) = engine.storage.insert_event(&event).await {
             tracing::error!(event_id = %event.id, error = %e, "failed to insert audit event");
@@ -385,7 +474,11 @@ pub async fn execute(engine: &MnemoEngine, request: ForgetRequest) -> Result<For
         tracing::warn!(error = %e, "post-forget maturity-driven consolidation failed (best-effort)");
     }
 
-    Ok(ForgetResponse { forgotten, errors })
+    Ok(ForgetResponse {
+        forgotten,
+        errors,
+        dry_run,
+    })
 }
 
 /// Tag convention used by :fn:`forget_subject` to locate memories owned by
@@ -403,6 +496,9 @@ pub struct ForgetSubjectRequest {
     /// a subject-scoped operation; other strategies are accepted and passed
     /// through to the standard forget pipeline.
     pub strategy: ForgetStrategy,
+    /// Report the memories that would be erased without erasing them.
+    #[serde(default)]
+    pub dry_run: Option<bool>,
 }
 
 #[non_exhaustive]
@@ -414,6 +510,10 @@ pub struct ForgetSubjectResponse {
     pub forgotten: Vec<Uuid>,
     pub cascaded_events: usize,
     pub errors: Vec<ForgetError>,
+    /// True when nothing was erased: `forgotten` lists the memories that
+    /// would be.
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 /// Erase every memory tagged with `subject:<subject_id>`, using the
@@ -459,6 +559,7 @@ pub async fn forget_subject(
         .collect();
     let matched = matched_records.len();
     let ids: Vec<Uuid> = matched_records.iter().map(|r| r.id).collect();
+    let dry_run = request.dry_run.unwrap_or(false);
 
     if ids.is_empty() {
         return Ok(ForgetSubjectResponse {
@@ -468,6 +569,7 @@ pub async fn forget_subject(
             forgotten: Vec::new(),
             cascaded_events: 0,
             errors: Vec::new(),
+            dry_run,
         });
     }
 
@@ -485,12 +587,15 @@ pub async fn forget_subject(
         agent_id: Some(agent_id.clone()),
         strategy: Some(request.strategy),
         criteria: None,
+        // Subject erasure is an explicit legal request; it overrides pins.
+        force: Some(true),
+        dry_run: Some(dry_run),
     };
     let resp = execute(engine, standard_req).await?;
 
     // For Redact, emit a MemoryRedact event per affected memory so auditors
     // can distinguish redactions from ordinary deletes.
-    if request.strategy == ForgetStrategy::Redact {
+    if request.strategy == ForgetStrategy::Redact && !dry_run {
         let now = chrono::Utc::now().to_rfc3339();
         for id in &resp.forgotten {
             let content_hash = compute_content_hash(
@@ -527,10 +632,11 @@ pub async fn forget_subject(
                 latency_ms: None,
                 cost_usd: None,
                 timestamp: now.clone(),
-                logical_clock: 0,
+                logical_clock: super::clock::tick(engine, &agent_id).await,
                 content_hash,
                 prev_hash: event_prev_hash,
                 embedding: None,
+                request_id: crate::request_id::current(),
             };
             if let Err(e) = engine.storage.insert_event(&event).await {
                 tracing::error!(
@@ -549,5 +655,6 @@ pub async fn forget_subject(
         forgotten: resp.forgotten,
         cascaded_events,
         errors: resp.errors,
+        dry_run,
     })
 }
@@ -0,0 +1,121 @@
+//! Fetch several memories by id in one call.
+//!
+//! The other half of recall's [`fields`](super::recall::RecallRequest::fields)
+//! projection: an agent recalls ids and scores only, then fetches the
+//! content of the hits it actually reads. Storage reads only the large
+//! columns asked for. A memory the caller could not recall (deleted,
+//! expired, quarantined, not visible to it or withheld by the shared-recall
+//! privacy layer) is reported as missing, so its existence does not leak,
+//! and shared memories carry the same redacted or aliased author recall
+//! reports.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::access_log::AccessOperation;
+use crate::model::memory::MemoryRecord;
+use crate::query::MnemoEngine;
+use crate::query::recall::{self, RecallRequest, ScoredMemory};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetBatchRequest {
+    pub ids: Vec<Uuid>,
+    pub agent_id: Option<String>,
+    /// Fields to return, as for [`RecallRequest::fields`]. `None` returns
+    /// them all.
+    pub fields: Option<Vec<String>>,
+}
+
+impl GetBatchRequest {
+    pub fn new(ids: Vec<Uuid>) -> Self {
+        Self {
+            ids,
+            agent_id: None,
+            fields: None,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetBatchResponse {
+    /// In request order, duplicates removed. Shaped like recall hits so
+    /// they merge by id; `score` is 0.
+    pub memories: Vec<ScoredMemory>,
+    /// Requested ids that were not returned.
+    pub missing: Vec<Uuid>,
+}
+
+impl GetBatchResponse {
+    pub fn new(memories: Vec<ScoredMemory>, missing: Vec<Uuid>) -> Self {
+        Self { memories, missing }
+    }
+}
+
+pub async fn execute(engine: &MnemoEngine, request: GetBatchRequest) -> Result<GetBatchResponse> {
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    if request.ids.is_empty() {
+        return Err(Error::invalid_field("ids", "cannot be empty"));
+    }
+    engine.limits().check_batch("ids", request.ids.len())?;
+    let columns = recall::projected_columns(request.fields.as_deref())?;
+    let mut seen = HashSet::new();
+    let ids: Vec<Uuid> = request
+        .ids
+        .into_iter()
+        .filter(|id| seen.insert(*id))
+        .collect();
+
+    let mut found: HashMap<Uuid, MemoryRecord> = engine
+        .storage
+        .get_memories(&ids, columns)
+        .await?
+        .into_iter()
+        .map(|record| (record.id, record))
+        .collect();
+
+    // The same visibility, privacy and attribution rules as an unfiltered
+    // recall.
+    let mut unfiltered = RecallRequest::new(String::new());
+    unfiltered.agent_id = Some(agent_id.clone());
+    let mut visible = Vec::with_capacity(ids.len());
+    for id in &ids {
+        if let Some(record) = found.remove(id)
+            && recall::passes_filters(&record, &unfiltered, &agent_id, engine).await
+        {
+            visible.push((record, 0.0));
+        }
+    }
+    let limit = visible.len();
+    super::privacy::shield(engine, &agent_id, &mut visible, limit).await?;
+    let mut visible: HashMap<Uuid, MemoryRecord> = visible
+        .into_iter()
+        .map(|(record, _)| (record.id, record))
+        .collect();
+
+    let mut memories = Vec::with_capacity(ids.len());
+    let mut missing = Vec::new();
+    for id in ids {
+        match visible.remove(&id) {
+            Some(mut record) => {
+                if columns.content {
+                    super::consolidate::decrypt_in_place(engine, &mut record);
+                }
+                let mut memory = ScoredMemory::from((record, 0.0));
+                memory.project(columns);
+                memories.push(memory);
+            }
+            None => missing.push(id),
+        }
+    }
+
+    let returned: Vec<Uuid> = memories.iter().map(|m| m.id).collect();
+    super::access_log::record(engine, &agent_id, AccessOperation::Get, None, &returned).await;
+    Ok(GetBatchResponse::new(memories, missing))
+}
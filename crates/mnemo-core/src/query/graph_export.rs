@@ -0,0 +1,420 @@
+//! Memory graph export for external visualization.
+//!
+//! [`execute`] collects the memories an agent could recall, optionally
+//! narrowed to one thread, type, tag set or importance, as nodes, and the
+//! relations between them as weighted, typed edges. Relations to memories
+//! outside the export are left out, so the graph is closed.
+//! [`GraphExport::render`] writes it as GraphML (for Gephi, yEd or
+//! networkx), Graphviz DOT, or JSON.
+//!
+//! Exports are capped at `max_nodes` nodes and `max_edges` edges; a capped
+//! export says so in `truncated`. Node labels are the first
+//! [`LABEL_CHARS`] characters of the content.
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::memory::MemoryType;
+use crate::query::MnemoEngine;
+use crate::query::recall::{self, RecallRequest};
+use crate::storage::MemoryFilter;
+
+pub const DEFAULT_GRAPH_NODES: usize = 1000;
+pub const MAX_GRAPH_NODES: usize = super::MAX_BATCH_QUERY_LIMIT;
+pub const DEFAULT_GRAPH_EDGES: usize = 5000;
+pub const MAX_GRAPH_EDGES: usize = 50_000;
+
+/// Characters of content kept as a node's label.
+pub const LABEL_CHARS: usize = 80;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphFormat {
+    #[default]
+    Json,
+    GraphMl,
+    Dot,
+}
+
+impl GraphFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            GraphFormat::Json => "application/json",
+            GraphFormat::GraphMl => "application/graphml+xml",
+            GraphFormat::Dot => "text/vnd.graphviz",
+        }
+    }
+}
+
+impl std::str::FromStr for GraphFormat {
+    type Err = Error;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(GraphFormat::Json),
+            "graphml" => Ok(GraphFormat::GraphMl),
+            "dot" => Ok(GraphFormat::Dot),
+            _ => Err(Error::invalid_field(
+                "format",
+                format!("invalid value '{s}': expected one of: graphml, dot, json"),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphExportRequest {
+    pub agent_id: Option<String>,
+    /// Only memories recorded in this thread.
+    pub thread_id: Option<String>,
+    pub memory_type: Option<MemoryType>,
+    /// Memories carrying any of these tags.
+    pub tags: Option<Vec<String>>,
+    pub min_importance: Option<f32>,
+    /// Only edges of these relation types.
+    pub relation_types: Option<Vec<String>>,
+    /// Only edges at least this heavy.
+    pub min_weight: Option<f32>,
+    /// Defaults to [`DEFAULT_GRAPH_NODES`], at most [`MAX_GRAPH_NODES`].
+    pub max_nodes: Option<usize>,
+    /// Defaults to [`DEFAULT_GRAPH_EDGES`], at most [`MAX_GRAPH_EDGES`].
+    pub max_edges: Option<usize>,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GraphNode {
+    pub id: Uuid,
+    pub label: String,
+    pub memory_type: MemoryType,
+    pub importance: f32,
+    pub tags: Vec<String>,
+    pub created_at: String,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GraphEdge {
+    pub id: Uuid,
+    pub source: Uuid,
+    pub target: Uuid,
+    pub relation_type: String,
+    pub weight: f32,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GraphExport {
+    pub agent_id: String,
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    /// Set when `max_nodes` or `max_edges` left part of the graph out.
+    pub truncated: bool,
+}
+
+pub async fn execute(engine: &MnemoEngine, request: GraphExportRequest) -> Result<GraphExport> {
+    let agent_id = engine.acting_agent(request.agent_id.as_deref());
+    super::validate_agent_id(&agent_id)?;
+    let max_nodes = bounded(
+        "max_nodes",
+        request.max_nodes,
+        DEFAULT_GRAPH_NODES,
+        MAX_GRAPH_NODES,
+    )?;
+    let max_edges = bounded(
+        "max_edges",
+        request.max_edges,
+        DEFAULT_GRAPH_EDGES,
+        MAX_GRAPH_EDGES,
+    )?;
+    if let Some(weight) = request.min_weight
+        && !weight.is_finite()
+    {
+        return Err(Error::invalid_field(
+            "min_weight",
+            "must be a finite number",
+        ));
+    }
+
+    let filter = MemoryFilter {
+        agent_id: Some(agent_id.clone()),
+        memory_type: request.memory_type,
+        tags: request.tags,
+        min_importance: request.min_importance,
+        thread_id: request.thread_id,
+        ..Default::default()
+    };
+    // Nodes follow the visibility rules of an unfiltered recall, so the
+    // graph holds nothing the agent could not recall.
+    let mut unfiltered = RecallRequest::new(String::new());
+    unfiltered.agent_id = Some(agent_id.clone());
+    let page = max_nodes + 1;
+    let mut records = Vec::new();
+    let mut offset = 0;
+    while records.len() <= max_nodes {
+        let batch = engine.storage.list_memories(&filter, page, offset).await?;
+        let exhausted = batch.len() < page;
+        offset += batch.len();
+        for record in batch {
+            if recall::passes_filters(&record, &unfiltered, &agent_id, engine).await {
+                records.push(record);
+            }
+        }
+        if exhausted {
+            break;
+        }
+    }
+    let mut truncated = records.len() > max_nodes;
+    records.truncate(max_nodes);
+
+    let ids: HashSet<Uuid> = records.iter().map(|r| r.id).collect();
+    let mut edges = Vec::new();
+    'nodes: for record in &records {
+        for relation in engine.storage.get_relations_from(record.id).await? {
+            let wanted = ids.contains(&relation.target_id)
+                && request
+                    .relation_types
+                    .as_ref()
+                    .is_none_or(|types| types.contains(&relation.relation_type))
+                && request.min_weight.is_none_or(|min| relation.weight >= min);
+            if !wanted {
+                continue;
+            }
+            if edges.len() == max_edges {
+                truncated = true;
+                break 'nodes;
+            }
+            edges.push(GraphEdge {
+                id: relation.id,
+                source: relation.source_id,
+                target: relation.target_id,
+                relation_type: relation.relation_type,
+                weight: relation.weight,
+            });
+        }
+    }
+
+    let nodes = records
+        .into_iter()
+        .map(|mut record| {
+            super::consolidate::decrypt_in_place(engine, &mut record);
+            GraphNode {
+                id: record.id,
+                label: record.content.chars().take(LABEL_CHARS).collect(),
+                memory_type: record.memory_type,
+                importance: record.importance,
+                tags: record.tags,
+                created_at: record.created_at,
+            }
+        })
+        .collect();
+    Ok(GraphExport {
+        agent_id,
+        nodes,
+        edges,
+        truncated,
+    })
+}
+
+fn bounded(field: &str, value: Option<usize>, default: usize, max: usize) -> Result<usize> {
+    match value.unwrap_or(default) {
+        n if (1..=max).contains(&n) => Ok(n),
+        _ => Err(Error::invalid_field(
+            field,
+            format!("must be between 1 and {max}"),
+        )),
+    }
+}
+
+impl GraphExport {
+    pub fn render(&self, format: GraphFormat) -> Result<String> {
+        Ok(match format {
+            GraphFormat::Json => serde_json::to_string(self)?,
+            GraphFormat::GraphMl => self.to_graphml(),
+            GraphFormat::Dot => self.to_dot(),
+        })
+    }
+
+    fn to_graphml(&self) -> String {
+        let mut out = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+            "  <key id=\"memory_type\" for=\"node\" attr.name=\"memory_type\" attr.type=\"string\"/>\n",
+            "  <key id=\"importance\" for=\"node\" attr.name=\"importance\" attr.type=\"double\"/>\n",
+            "  <key id=\"tags\" for=\"node\" attr.name=\"tags\" attr.type=\"string\"/>\n",
+            "  <key id=\"created_at\" for=\"node\" attr.name=\"created_at\" attr.type=\"string\"/>\n",
+            "  <key id=\"relation_type\" for=\"edge\" attr.name=\"relation_type\" attr.type=\"string\"/>\n",
+            "  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"double\"/>\n",
+        ));
+        let _ = writeln!(
+            out,
+            "  <graph id=\"{}\" edgedefault=\"directed\">",
+            xml_escape(&self.agent_id)
+        );
+        for node in &self.nodes {
+            let _ = writeln!(
+                out,
+                concat!(
+                    "    <node id=\"{}\">",
+                    "<data key=\"label\">{}</data>",
+                    "<data key=\"memory_type\">{}</data>",
+                    "<data key=\"importance\">{}</data>",
+                    "<data key=\"tags\">{}</data>",
+                    "<data key=\"created_at\">{}</data>",
+                    "</node>"
+                ),
+                node.id,
+                xml_escape(&node.label),
+                node.memory_type,
+                node.importance,
+                xml_escape(&node.tags.join(",")),
+                xml_escape(&node.created_at),
+            );
+        }
+        for edge in &self.edges {
+            let _ = writeln!(
+                out,
+                concat!(
+                    "    <edge id=\"{}\" source=\"{}\" target=\"{}\">",
+                    "<data key=\"relation_type\">{}</data>",
+                    "<data key=\"weight\">{}</data>",
+                    "</edge>"
+                ),
+                edge.id,
+                edge.source,
+                edge.target,
+                xml_escape(&edge.relation_type),
+                edge.weight,
+            );
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+
+    fn to_dot(&self) -> String {
+        let mut out = format!("digraph \"{}\" {{\n", dot_escape(&self.agent_id));
+        for node in &self.nodes {
+            let _ = writeln!(
+                out,
+                "  \"{}\" [label=\"{}\", memory_type=\"{}\", importance={}];",
+                node.id,
+                dot_escape(&node.label),
+                node.memory_type,
+                node.importance,
+            );
+        }
+        for edge in &self.edges {
+            let _ = writeln!(
+                out,
+                "  \"{}\" -> \"{}\" [label=\"{}\", weight={}];",
+                edge.source,
+                edge.target,
+                dot_escape(&edge.relation_type),
+                edge.weight,
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // Control characters other than tab and newlines are not
+            // allowed in XML 1.0.
+            c if c.is_control() && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn dot_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> GraphExport {
+        let (a, b) = (Uuid::now_v7(), Uuid::now_v7());
+        let node = |id, label: &str| GraphNode {
+            id,
+            label: label.to_string(),
+            memory_type: MemoryType::Semantic,
+            importance: 0.5,
+            tags: vec!["t".to_string()],
+            created_at: "2026-01-01T00:00:00+00:00".to_string(),
+        };
+        GraphExport {
+            agent_id: "agent".to_string(),
+            nodes: vec![node(a, "uses <Rust> & \"tokio\""), node(b, "line\none")],
+            edges: vec![GraphEdge {
+                id: Uuid::now_v7(),
+                source: a,
+                target: b,
+                relation_type: "derived_from".to_string(),
+                weight: 0.75,
+            }],
+            truncated: false,
+        }
+    }
+
+    #[test]
+    fn graphml_escapes_labels() {
+        let graph = graph();
+        let xml = graph.render(GraphFormat::GraphMl).unwrap();
+        assert!(xml.contains("uses &lt;Rust&gt; &amp; &quot;tokio&quot;"));
+        assert!(xml.contains(&format!(
+            "<edge id=\"{}\" source=\"{}\" target=\"{}\">",
+            graph.edges[0].id, graph.nodes[0].id, graph.nodes[1].id
+        )));
+        assert!(xml.contains("<data key=\"weight\">0.75</data>"));
+        assert_eq!(xml.matches("<node ").count(), 2);
+    }
+
+    #[test]
+    fn dot_escapes_labels() {
+        let graph = graph();
+        let dot = graph.render(GraphFormat::Dot).unwrap();
+        assert!(dot.starts_with("digraph \"agent\" {\n"));
+        assert!(dot.contains("label=\"uses <Rust> & \\\"tokio\\\"\""));
+        assert!(dot.contains("label=\"line\\none\""));
+        assert!(dot.contains(&format!(
+            "\"{}\" -> \"{}\" [label=\"derived_from\", weight=0.75];",
+            graph.nodes[0].id, graph.nodes[1].id
+        )));
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(
+            "graphml".parse::<GraphFormat>().unwrap(),
+            GraphFormat::GraphMl
+        );
+        assert_eq!("dot".parse::<GraphFormat>().unwrap(), GraphFormat::Dot);
+        assert!("gexf".parse::<GraphFormat>().is_err());
+    }
+}
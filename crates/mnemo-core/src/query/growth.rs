@@ -0,0 +1,294 @@
+//! Storage growth trends and quota forecasts.
+//!
+//! [`execute`] turns the storage's [`DailyGrowth`] into a day-by-day series
+//! over the last `days` days, ending today, with the running totals after
+//! each day. Days that stored nothing are filled in with zeros.
+//!
+//! The forecast is a least-squares line through the running totals of the
+//! window. Its slope is the growth per day. Given a quota, the report
+//! gives the day the line crosses it. Memory counts are forecast against
+//! `memory_quota`. The database size is forecast against
+//! `disk_quota_bytes`, growing at the rate of stored memory content and
+//! event payloads. A backend that cannot report its size (in-memory) uses
+//! those stored bytes as the size.
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::model::content_stats::DailyGrowth;
+use crate::query::MnemoEngine;
+
+pub const DEFAULT_GROWTH_DAYS: u32 = 30;
+pub const MAX_GROWTH_DAYS: u32 = 366;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GrowthRequest {
+    /// Days to report and fit the forecast to, ending today. Defaults to
+    /// [`DEFAULT_GROWTH_DAYS`].
+    #[serde(default)]
+    pub days: Option<u32>,
+    /// Forecast when the number of stored memories reaches this.
+    #[serde(default)]
+    pub memory_quota: Option<u64>,
+    /// Forecast when the database reaches this many bytes.
+    #[serde(default)]
+    pub disk_quota_bytes: Option<u64>,
+}
+
+/// One day of the series.
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GrowthPoint {
+    #[serde(flatten)]
+    pub added: DailyGrowth,
+    /// Memories stored by the end of the day.
+    pub total_memories: u64,
+    /// Memory and event bytes stored by the end of the day.
+    pub total_bytes: u64,
+}
+
+/// When a quota will be reached at the current rate.
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QuotaForecast {
+    /// `memories` or `disk`.
+    pub resource: String,
+    pub limit: u64,
+    pub current: u64,
+    /// Days until the limit is reached; 0 when it already is. `None` when
+    /// usage is not growing.
+    pub days_left: Option<f64>,
+    /// The date (`YYYY-MM-DD`) the limit is reached.
+    pub reached_on: Option<String>,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrowthReport {
+    /// Oldest first, ending today.
+    pub days: Vec<GrowthPoint>,
+    pub total_memories: u64,
+    pub total_events: u64,
+    /// Stored memory content and event payloads, in bytes.
+    pub total_bytes: u64,
+    /// Size of the database; 0 when the backend cannot tell.
+    pub database_bytes: u64,
+    /// Slope of the fitted line through `total_memories`.
+    pub memories_per_day: f64,
+    /// Slope of the fitted line through `total_bytes`.
+    pub bytes_per_day: f64,
+    pub forecasts: Vec<QuotaForecast>,
+}
+
+pub async fn execute(engine: &MnemoEngine, request: GrowthRequest) -> Result<GrowthReport> {
+    let history = engine.storage.daily_growth().await?;
+    let database_bytes = engine.storage.database_size().await?;
+    report(
+        &history,
+        database_bytes,
+        &request,
+        chrono::Utc::now().date_naive(),
+    )
+}
+
+fn report(
+    history: &[DailyGrowth],
+    database_bytes: u64,
+    request: &GrowthRequest,
+    today: NaiveDate,
+) -> Result<GrowthReport> {
+    let window = request.days.unwrap_or(DEFAULT_GROWTH_DAYS);
+    if !(1..=MAX_GROWTH_DAYS).contains(&window) {
+        return Err(Error::invalid_field(
+            "days",
+            format!("must be between 1 and {MAX_GROWTH_DAYS}"),
+        ));
+    }
+    let first = today - Duration::days(i64::from(window) - 1);
+
+    let mut days = Vec::with_capacity(window as usize);
+    let (mut total_memories, mut total_events, mut total_bytes) = (0, 0, 0);
+    let mut history = history.iter().peekable();
+    for offset in 0..window {
+        let day = first + Duration::days(i64::from(offset));
+        let key = day.format("%Y-%m-%d").to_string();
+        let mut added = DailyGrowth {
+            day: key.clone(),
+            ..Default::default()
+        };
+        while let Some(entry) = history.next_if(|e| e.day <= key) {
+            total_memories += entry.memories;
+            total_events += entry.events;
+            total_bytes += entry.memory_bytes + entry.event_bytes;
+            if entry.day == key {
+                added = entry.clone();
+            }
+        }
+        days.push(GrowthPoint {
+            added,
+            total_memories,
+            total_bytes,
+        });
+    }
+
+    let memories_per_day = slope(days.iter().map(|d| d.total_memories as f64));
+    let bytes_per_day = slope(days.iter().map(|d| d.total_bytes as f64));
+    let mut forecasts = Vec::new();
+    if let Some(limit) = request.memory_quota {
+        forecasts.push(forecast(
+            "memories",
+            limit,
+            total_memories,
+            memories_per_day,
+            today,
+        ));
+    }
+    if let Some(limit) = request.disk_quota_bytes {
+        let current = if database_bytes > 0 {
+            database_bytes
+        } else {
+            total_bytes
+        };
+        forecasts.push(forecast("disk", limit, current, bytes_per_day, today));
+    }
+    Ok(GrowthReport {
+        days,
+        total_memories,
+        total_events,
+        total_bytes,
+        database_bytes,
+        memories_per_day,
+        bytes_per_day,
+        forecasts,
+    })
+}
+
+/// Least-squares slope of `values` against their index.
+fn slope(values: impl Iterator<Item = f64>) -> f64 {
+    let values: Vec<f64> = values.collect();
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let n = values.len() as f64;
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = values.iter().sum::<f64>() / n;
+    let (mut sum_xy, mut sum_xx) = (0.0, 0.0);
+    for (x, y) in values.iter().enumerate() {
+        let dx = x as f64 - mean_x;
+        sum_xy += dx * (y - mean_y);
+        sum_xx += dx * dx;
+    }
+    sum_xy / sum_xx
+}
+
+fn forecast(
+    resource: &str,
+    limit: u64,
+    current: u64,
+    per_day: f64,
+    today: NaiveDate,
+) -> QuotaForecast {
+    let days_left = if current >= limit {
+        Some(0.0)
+    } else if per_day > 0.0 {
+        Some((limit - current) as f64 / per_day)
+    } else {
+        None
+    };
+    let reached_on = days_left
+        .and_then(|days| i64::try_from(days.ceil() as u64).ok())
+        .and_then(|days| today.checked_add_signed(Duration::try_days(days)?))
+        .map(|day| day.format("%Y-%m-%d").to_string());
+    QuotaForecast {
+        resource: resource.to_string(),
+        limit,
+        current,
+        days_left,
+        reached_on,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(day: &str, memories: u64, memory_bytes: u64) -> DailyGrowth {
+        DailyGrowth {
+            day: day.to_string(),
+            memories,
+            memory_bytes,
+            events: memories,
+            event_bytes: 0,
+        }
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, 10).unwrap()
+    }
+
+    #[test]
+    fn fills_gaps_and_carries_earlier_totals() {
+        let history = [
+            day("2025-12-31", 50, 5000),
+            day("2026-03-08", 10, 100),
+            day("2026-03-10", 10, 100),
+        ];
+        let request = GrowthRequest {
+            days: Some(3),
+            ..Default::default()
+        };
+        let report = report(&history, 0, &request, today()).unwrap();
+        let totals: Vec<(&str, u64, u64)> = report
+            .days
+            .iter()
+            .map(|d| (d.added.day.as_str(), d.added.memories, d.total_memories))
+            .collect();
+        assert_eq!(
+            totals,
+            vec![
+                ("2026-03-08", 10, 60),
+                ("2026-03-09", 0, 60),
+                ("2026-03-10", 10, 70)
+            ]
+        );
+        assert_eq!(report.total_events, 70);
+        assert_eq!(report.total_bytes, 5200);
+        assert!((report.memories_per_day - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn forecasts_when_quotas_are_reached() {
+        let history: Vec<DailyGrowth> = (1..=10)
+            .map(|d| day(&format!("2026-03-{d:02}"), 10, 1000))
+            .collect();
+        let request = GrowthRequest {
+            days: Some(10),
+            memory_quota: Some(125),
+            disk_quota_bytes: Some(50),
+        };
+        let growth = report(&history, 0, &request, today()).unwrap();
+        assert!((growth.memories_per_day - 10.0).abs() < 1e-9);
+        let memories = &growth.forecasts[0];
+        assert_eq!(memories.current, 100);
+        assert!((memories.days_left.unwrap() - 2.5).abs() < 1e-9);
+        assert_eq!(memories.reached_on.as_deref(), Some("2026-03-13"));
+        let disk = &growth.forecasts[1];
+        assert_eq!((disk.current, disk.days_left), (10_000, Some(0.0)));
+
+        let flat = report(&history[..1], 0, &request, today()).unwrap();
+        assert_eq!(flat.forecasts[0].days_left, None);
+        assert_eq!(flat.forecasts[0].reached_on, None);
+    }
+
+    #[test]
+    fn rejects_bad_windows() {
+        for days in [0, MAX_GROWTH_DAYS + 1] {
+            let request = GrowthRequest {
+                days: Some(days),
+                ..Default::default()
+            };
+            assert!(report(&[], 0, &request, today()).is_err());
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! Liveness and readiness probes.
+//!
+//! Liveness only says the process is up. Readiness probes every dependency
+//! the engine was built with — storage, the vector index, the embedding
+//! provider, the full-text index and cold storage — and reports a status per
+//! dependency so orchestrators can stop routing to an instance whose
+//! database or embedder is unreachable. Storage, the vector index and the
+//! embedding provider are required: if any of them is down the instance is
+//! not ready. The full-text index and cold storage are optional tiers and
+//! only degrade the overall status.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::query::MnemoEngine;
+
+/// Per-probe timeout. A dependency that does not answer in time is down.
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Ok,
+    Degraded,
+    Down,
+}
+
+impl std::fmt::Display for HealthStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HealthStatus::Ok => write!(f, "ok"),
+            HealthStatus::Degraded => write!(f, "degraded"),
+            HealthStatus::Down => write!(f, "down"),
+        }
+    }
+}
+
+/// Result of probing one dependency.
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyHealth {
+    /// `storage`, `vector_index`, `embedding`, `full_text` or `cold_storage`.
+    pub name: String,
+    pub status: HealthStatus,
+    /// Whether a `Down` status makes the instance not ready.
+    pub required: bool,
+    pub latency_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    /// `false` when any required dependency is down.
+    pub ready: bool,
+    pub version: String,
+    /// Empty for liveness checks.
+    pub checks: Vec<DependencyHealth>,
+}
+
+impl HealthReport {
+    pub fn new(checks: Vec<DependencyHealth>) -> Self {
+        let ready = !checks
+            .iter()
+            .any(|c| c.required && c.status == HealthStatus::Down);
+        let status = if !ready {
+            HealthStatus::Down
+        } else if checks.iter().any(|c| c.status != HealthStatus::Ok) {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Ok
+        };
+        Self {
+            status,
+            ready,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            checks,
+        }
+    }
+}
+
+/// Liveness: the process is up and serving. Never touches a dependency.
+pub fn liveness() -> HealthReport {
+    HealthReport::new(Vec::new())
+}
+
+/// Readiness: probe every attached dependency.
+pub async fn readiness(engine: &MnemoEngine) -> HealthReport {
+    let mut checks = Vec::with_capacity(5);
+
+    checks.push(
+        probe("storage", true, async {
+            engine.storage.ping().await?;
+            Ok(Some(engine.storage.backend_name().to_string()))
+        })
+        .await,
+    );
+
+    let indexed = engine.index.len();
+    checks.push(DependencyHealth {
+        name: "vector_index".to_string(),
+        status: HealthStatus::Ok,
+        required: true,
+        latency_ms: 0,
+        detail: Some(format!("{indexed} vectors")),
+    });
+
+    let mut embedding = probe("embedding", true, async {
+        let vector = engine.embedding.embed("health check").await?;
+        let expected = engine.embedding.dimensions();
+        if vector.len() != expected {
+            return Err(crate::error::Error::Embedding(format!(
+                "provider returned {} dimensions, expected {expected}",
+                vector.len()
+            )));
+        }
+        Ok(None)
+    })
+    .await;
+    if embedding.status == HealthStatus::Ok && !engine.embedding.is_semantic_capable() {
+        embedding.status = HealthStatus::Degraded;
+        embedding.detail = Some("no-op provider: semantic recall unavailable".to_string());
+    }
+    checks.push(embedding);
+
+    if let Some(ref ft) = engine.full_text {
+        checks.push(DependencyHealth {
+            name: "full_text".to_string(),
+            status: HealthStatus::Ok,
+            required: false,
+            latency_ms: 0,
+            detail: Some(format!("{} docs", ft.len())),
+        });
+    }
+
+    if let Some(ref cold) = engine.cold_storage {
+        checks.push(
+            probe("cold_storage", false, async {
+                cold.list_archived(None, 1).await?;
+                Ok(None)
+            })
+            .await,
+        );
+    }
+
+    HealthReport::new(checks)
+}
+
+async fn probe<F>(name: &str, required: bool, check: F) -> DependencyHealth
+where
+    F: Future<Output = Result<Option<String>>>,
+{
+    let started = Instant::now();
+    let (status, detail) = match tokio::time::timeout(PROBE_TIMEOUT, check).await {
+        Ok(Ok(detail)) => (HealthStatus::Ok, detail),
+        Ok(Err(e)) => (HealthStatus::Down, Some(e.to_string())),
+        Err(_) => (
+            HealthStatus::Down,
+            Some(format!("no response within {}s", PROBE_TIMEOUT.as_secs())),
+        ),
+    };
+    DependencyHealth {
+        name: name.to_string(),
+        status,
+        required,
+        latency_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        detail,
+    }
+}
@@ -0,0 +1,246 @@
+//! Typed signal weights for hybrid recall.
+//!
+//! Hybrid recall ranks candidates once per signal and fuses the rankings
+//! with weighted reciprocal rank fusion: each signal adds
+//! `weight / (rrf_k + rank + 1)` to a memory's score. [`HybridConfig`] names
+//! the weights instead of relying on `hybrid_weights`' list positions, and
+//! adds an importance signal that the positional form cannot express. The
+//! graph strategy fuses only its vector and graph rankings.
+//!
+//! A config is written as a preset name (`balanced`, `precision`,
+//! `recency`), as an object (`{"vector": 1.0, "bm25": 0.5}`), or in query
+//! strings as `signal:weight` pairs (`vector:1,bm25:0.5`). Signals left out
+//! keep their `balanced` weight. `rrf_k` still sets the rank damping: a
+//! larger `rrf_k` shrinks the gap between ranks, leaving more of the order
+//! to the weights, while a small one lets each signal's top hits dominate.
+//! When a request carries both, `hybrid` wins over `hybrid_weights`.
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, FieldViolation, Result};
+
+/// Preset names accepted wherever a [`HybridConfig`] is.
+pub const HYBRID_PRESETS: &[&str] = &["balanced", "precision", "recency"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "HybridRepr")]
+pub struct HybridConfig {
+    /// Embedding similarity.
+    pub vector: f32,
+    /// BM25 full-text score.
+    pub bm25: f32,
+    /// Relation-graph proximity to the top vector hits.
+    pub graph: f32,
+    /// Age of the memory, per `recency_half_life_hours`.
+    pub recency: f32,
+    /// The memory's stored importance. `0` leaves it out of the fusion.
+    pub importance: f32,
+}
+
+impl Default for HybridConfig {
+    /// The `balanced` preset: every ranking counts once, importance is not
+    /// used. Ranks exactly like a recall without weights.
+    fn default() -> Self {
+        Self {
+            vector: 1.0,
+            bm25: 1.0,
+            graph: 1.0,
+            recency: 1.0,
+            importance: 0.0,
+        }
+    }
+}
+
+impl HybridConfig {
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "balanced" => Some(Self::default()),
+            // Favour direct semantic and lexical matches and well-rated
+            // memories over neighbours and fresh noise.
+            "precision" => Some(Self {
+                vector: 1.0,
+                bm25: 1.0,
+                graph: 0.25,
+                recency: 0.1,
+                importance: 0.5,
+            }),
+            // Let the newest relevant memories rise.
+            "recency" => Some(Self {
+                vector: 0.6,
+                bm25: 0.4,
+                graph: 0.25,
+                recency: 2.0,
+                importance: 0.0,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Weights must be finite and non-negative, and at least one positive.
+    pub fn validate(&self) -> Result<()> {
+        let mut violations = Vec::new();
+        for (signal, weight) in self.signals() {
+            if !weight.is_finite() || weight < 0.0 {
+                violations.push(FieldViolation::new(
+                    format!("hybrid.{signal}"),
+                    format!("must be a non-negative number, got {weight}"),
+                ));
+            }
+        }
+        if violations.is_empty() && self.signals().iter().all(|(_, w)| *w == 0.0) {
+            violations.push(FieldViolation::new(
+                "hybrid",
+                "at least one weight must be positive",
+            ));
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::InvalidFields(violations))
+        }
+    }
+
+    fn signals(&self) -> [(&'static str, f32); 5] {
+        [
+            ("vector", self.vector),
+            ("bm25", self.bm25),
+            ("graph", self.graph),
+            ("recency", self.recency),
+            ("importance", self.importance),
+        ]
+    }
+
+    fn set(&mut self, signal: &str, weight: f32) -> Result<()> {
+        let slot = match signal {
+            "vector" => &mut self.vector,
+            "bm25" => &mut self.bm25,
+            "graph" => &mut self.graph,
+            "recency" => &mut self.recency,
+            "importance" => &mut self.importance,
+            other => {
+                return Err(Error::invalid_field(
+                    "hybrid",
+                    format!(
+                        "unknown signal '{other}': expected vector, bm25, graph, recency or importance"
+                    ),
+                ));
+            }
+        };
+        *slot = weight;
+        Ok(())
+    }
+
+    /// Weights of hybrid recall's rankings, in fusion order: vector, bm25,
+    /// recency, graph, importance.
+    pub(crate) fn hybrid_weights(&self) -> Vec<f32> {
+        vec![
+            self.vector,
+            self.bm25,
+            self.recency,
+            self.graph,
+            self.importance,
+        ]
+    }
+
+    /// Weights of the graph strategy's rankings: vector, graph.
+    pub(crate) fn graph_weights(&self) -> Vec<f32> {
+        vec![self.vector, self.graph]
+    }
+}
+
+impl FromStr for HybridConfig {
+    type Err = Error;
+
+    /// Parses a preset name or `signal:weight` pairs separated by commas.
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if let Some(config) = Self::preset(s) {
+            return Ok(config);
+        }
+        if !s.contains(':') {
+            return Err(Error::invalid_field(
+                "hybrid",
+                format!(
+                    "unknown preset '{s}': expected one of {}, or signal:weight pairs",
+                    HYBRID_PRESETS.join(", ")
+                ),
+            ));
+        }
+        let mut config = Self::default();
+        for pair in s.split(',') {
+            let pair = pair.trim();
+            let parsed = pair
+                .split_once(':')
+                .and_then(|(signal, w)| Some((signal.trim(), w.trim().parse::<f32>().ok()?)));
+            let Some((signal, weight)) = parsed else {
+                return Err(Error::invalid_field(
+                    "hybrid",
+                    format!("invalid entry '{pair}': expected signal:weight"),
+                ));
+            };
+            config.set(signal, weight)?;
+        }
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+/// Wire forms of a [`HybridConfig`].
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HybridRepr {
+    Preset(String),
+    Weights(HybridWeights),
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct HybridWeights {
+    vector: f32,
+    bm25: f32,
+    graph: f32,
+    recency: f32,
+    importance: f32,
+}
+
+impl Default for HybridWeights {
+    fn default() -> Self {
+        let balanced = HybridConfig::default();
+        Self {
+            vector: balanced.vector,
+            bm25: balanced.bm25,
+            graph: balanced.graph,
+            recency: balanced.recency,
+            importance: balanced.importance,
+        }
+    }
+}
+
+impl TryFrom<HybridRepr> for HybridConfig {
+    type Error = Error;
+
+    fn try_from(repr: HybridRepr) -> Result<Self> {
+        let config = match repr {
+            HybridRepr::Preset(name) => Self::preset(&name).ok_or_else(|| {
+                Error::invalid_field(
+                    "hybrid",
+                    format!(
+                        "unknown preset '{name}': expected one of {}",
+                        HYBRID_PRESETS.join(", ")
+                    ),
+                )
+            })?,
+            HybridRepr::Weights(w) => Self {
+                vector: w.vector,
+                bm25: w.bm25,
+                graph: w.graph,
+                recency: w.recency,
+                importance: w.importance,
+            },
+        };
+        config.validate()?;
+        Ok(config)
+    }
+}
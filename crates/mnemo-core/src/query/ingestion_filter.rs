@@ -0,0 +1,254 @@
+//! Content filters applied before a memory is stored.
+//!
+//! Agents that write whatever a retrieved web page or tool output tells
+//! them to are the usual route for prompt injection and garbage into the
+//! store. An [`IngestionFilter`] checks every `remember` against
+//! [`FilterRules`]: regex and keyword deny-lists, allow-lists the content
+//! must match, size bounds and permitted languages. The `global` rules
+//! apply to every write; rules under `agents` apply on top of them to that
+//! agent's writes only.
+//!
+//! A rejected write fails with [`Error::InvalidFields`] on `content` and is
+//! recorded as an `IngestionRejected` event on the writing agent, naming
+//! the rule that fired, so injection attempts can be monitored from the
+//! audit log. The event carries a short preview of the content unless
+//! at-rest encryption is on.
+
+use std::collections::{BTreeMap, HashMap};
+
+use regex::{Regex, RegexSet};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::model::event::EventType;
+use crate::query::MnemoEngine;
+use crate::search::language;
+
+/// Characters of rejected content kept in the audit event.
+const PREVIEW_CHARS: usize = 120;
+
+/// One set of ingestion rules. Every list is optional; empty lists and
+/// unset bounds do not filter.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FilterRules {
+    /// Regexes; content matching any of them is rejected.
+    pub deny_patterns: Vec<String>,
+    /// Case-insensitive substrings; content containing any is rejected.
+    pub deny_keywords: Vec<String>,
+    /// Regexes; when set, content must match at least one.
+    pub allow_patterns: Vec<String>,
+    /// Case-insensitive substrings; when set, content must contain one.
+    pub allow_keywords: Vec<String>,
+    /// Smallest accepted content, in bytes.
+    pub min_content_bytes: Option<usize>,
+    /// Largest accepted content, in bytes. The engine-wide
+    /// `limits.max_content_bytes` still applies.
+    pub max_content_bytes: Option<usize>,
+    /// ISO 639-3 codes (`eng`, `deu`, ...). Content reliably detected as
+    /// another language is rejected; short or ambiguous content passes.
+    pub languages: Vec<String>,
+}
+
+/// Global and per-agent [`FilterRules`], as found in the engine config.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IngestionFilterConfig {
+    /// Rules every write must pass.
+    pub global: FilterRules,
+    /// Extra rules for writes by one agent, keyed by `agent_id`.
+    pub agents: BTreeMap<String, FilterRules>,
+}
+
+/// Why a write was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    /// `deny_pattern`, `deny_keyword`, `allow_list`, `min_content_bytes`,
+    /// `max_content_bytes` or `language`.
+    pub rule: &'static str,
+    pub detail: String,
+}
+
+/// Compiled [`IngestionFilterConfig`]. Attach with
+/// [`MnemoEngine::with_ingestion_filter`].
+#[derive(Debug, Clone)]
+pub struct IngestionFilter {
+    global: CompiledRules,
+    agents: HashMap<String, CompiledRules>,
+}
+
+impl IngestionFilter {
+    /// Compile `config`, failing on the first invalid regex.
+    pub fn new(config: &IngestionFilterConfig) -> Result<Self> {
+        let global = CompiledRules::new(&config.global, "global")?;
+        let agents = config
+            .agents
+            .iter()
+            .map(|(agent_id, rules)| {
+                let compiled = CompiledRules::new(rules, &format!("agents.{agent_id}"))?;
+                Ok((agent_id.clone(), compiled))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { global, agents })
+    }
+
+    /// The first rule `content` written by `agent_id` breaks, if any.
+    pub fn check(&self, agent_id: &str, content: &str) -> Option<Rejection> {
+        self.global.check(content).or_else(|| {
+            self.agents
+                .get(agent_id)
+                .and_then(|rules| rules.check(content))
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CompiledRules {
+    deny_patterns: Option<(RegexSet, Vec<String>)>,
+    deny_keywords: Vec<String>,
+    allow_patterns: Option<RegexSet>,
+    allow_keywords: Vec<String>,
+    min_content_bytes: Option<usize>,
+    max_content_bytes: Option<usize>,
+    languages: Vec<String>,
+}
+
+impl CompiledRules {
+    fn new(rules: &FilterRules, path: &str) -> Result<Self> {
+        let compile = |patterns: &[String], field: &str| -> Result<Option<RegexSet>> {
+            if patterns.is_empty() {
+                return Ok(None);
+            }
+            for (i, pattern) in patterns.iter().enumerate() {
+                Regex::new(pattern).map_err(|e| {
+                    Error::Validation(format!(
+                        "features.ingestion_filter.{path}.{field}[{i}] is not a valid regex: {e}"
+                    ))
+                })?;
+            }
+            RegexSet::new(patterns).map(Some).map_err(|e| {
+                Error::Validation(format!("features.ingestion_filter.{path}.{field}: {e}"))
+            })
+        };
+        let lowercase = |words: &[String]| -> Vec<String> {
+            words
+                .iter()
+                .map(|w| w.to_lowercase())
+                .filter(|w| !w.is_empty())
+                .collect()
+        };
+        Ok(Self {
+            deny_patterns: compile(&rules.deny_patterns, "deny_patterns")?
+                .map(|set| (set, rules.deny_patterns.clone())),
+            deny_keywords: lowercase(&rules.deny_keywords),
+            allow_patterns: compile(&rules.allow_patterns, "allow_patterns")?,
+            allow_keywords: lowercase(&rules.allow_keywords),
+            min_content_bytes: rules.min_content_bytes,
+            max_content_bytes: rules.max_content_bytes,
+            languages: rules.languages.iter().map(|l| l.to_lowercase()).collect(),
+        })
+    }
+
+    fn check(&self, content: &str) -> Option<Rejection> {
+        let reject = |rule, detail: String| Some(Rejection { rule, detail });
+        if let Some(min) = self.min_content_bytes
+            && content.len() < min
+        {
+            return reject(
+                "min_content_bytes",
+                format!("content is {} bytes, below {min}", content.len()),
+            );
+        }
+        if let Some(max) = self.max_content_bytes
+            && content.len() > max
+        {
+            return reject(
+                "max_content_bytes",
+                format!("content is {} bytes, above {max}", content.len()),
+            );
+        }
+        if let Some((ref set, ref patterns)) = self.deny_patterns
+            && let Some(i) = set.matches(content).iter().next()
+        {
+            return reject("deny_pattern", format!("matches `{}`", patterns[i]));
+        }
+        let lower = content.to_lowercase();
+        if let Some(keyword) = self.deny_keywords.iter().find(|k| lower.contains(*k)) {
+            return reject("deny_keyword", format!("contains `{keyword}`"));
+        }
+        let has_allow_list = self.allow_patterns.is_some() || !self.allow_keywords.is_empty();
+        if has_allow_list
+            && !self
+                .allow_patterns
+                .as_ref()
+                .is_some_and(|set| set.is_match(content))
+            && !self.allow_keywords.iter().any(|k| lower.contains(k))
+        {
+            return reject(
+                "allow_list",
+                "matches no allowed pattern or keyword".to_string(),
+            );
+        }
+        if !self.languages.is_empty()
+            && let Some(detected) = language::detect(content).filter(|d| d.reliable)
+            && !self.languages.iter().any(|l| l == detected.code)
+        {
+            return reject(
+                "language",
+                format!("detected `{}`, not an allowed language", detected.code),
+            );
+        }
+        None
+    }
+}
+
+/// Reject `content` when the engine's filter says so, recording the attempt
+/// as an audit event.
+pub(crate) async fn enforce(
+    engine: &MnemoEngine,
+    agent_id: &str,
+    content: &str,
+    thread_id: Option<String>,
+) -> Result<()> {
+    let Some(ref filter) = engine.ingestion_filter else {
+        return Ok(());
+    };
+    let Some(rejection) = filter.check(agent_id, content) else {
+        return Ok(());
+    };
+
+    let mut payload = serde_json::json!({
+        "rule": rejection.rule,
+        "detail": rejection.detail,
+        "content_bytes": content.len(),
+    });
+    if !engine.encrypts_content() {
+        payload["content_preview"] =
+            serde_json::Value::String(content.chars().take(PREVIEW_CHARS).collect::<String>());
+    }
+    let event = super::event_builder::build_event(
+        engine,
+        agent_id,
+        EventType::IngestionRejected,
+        payload,
+        content,
+        thread_id,
+    )
+    .await;
+    if let Err(e) = engine.storage.insert_event(&event).await {
+        tracing::error!(event_id = %event.id, error = %e, "failed to insert audit event");
+    }
+    tracing::warn!(
+        agent_id,
+        rule = rejection.rule,
+        "write rejected by ingestion filter"
+    );
+
+    Err(Error::invalid_field(
+        "content",
+        format!(
+            "rejected by ingestion filter ({}): {}",
+            rejection.rule, rejection.detail
+        ),
+    ))
+}
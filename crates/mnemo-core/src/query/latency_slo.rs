@@ -0,0 +1,454 @@
+//! Latency SLO guardrails for recall.
+//!
+//! Under load a recall should come back with fewer signals rather than
+//! time out. With a [`LatencySloConfig`] attached, every recall runs
+//! against a deadline and each of its stages against a budget. A recall
+//! in which any stage ran over its budget moves the recalls after it one
+//! rung down the degradation ladder:
+//!
+//! | Level | Work dropped |
+//! |---|---|
+//! | `skip_rerank` | Rescoring beyond the first ranking: extra phrasings (`queries`, `expand_query`) and `exclude_query` |
+//! | `skip_graph` | Also graph expansion |
+//! | `vector_only` | Also BM25 and recency: hybrid and graph recall search the vector index alone |
+//! | `bm25_only` | Also the query embedding: recall searches the full-text index alone |
+//!
+//! After `recover_after` recalls in a row finish within every budget, the
+//! level moves back up one rung. Without a full-text index the ladder
+//! stops at `vector_only`.
+//!
+//! The deadline also holds within one recall: an optional stage that would
+//! start after it is skipped, and an embedding still running at the
+//! deadline is abandoned for BM25. A request can set its own
+//! [`deadline_ms`](super::recall::RecallRequest::deadline_ms), which
+//! applies even without a configured SLO. The level each recall ran at is
+//! reported in
+//! [`RecallResponse::degradation`](super::recall::RecallResponse::degradation).
+
+use std::fmt;
+use std::sync::atomic::{AtomicU8, AtomicU32, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::query::MnemoEngine;
+
+/// A rung of the degradation ladder, from full quality down.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum DegradationLevel {
+    #[default]
+    None,
+    SkipRerank,
+    SkipGraph,
+    VectorOnly,
+    Bm25Only,
+}
+
+impl DegradationLevel {
+    const LADDER: [Self; 5] = [
+        Self::None,
+        Self::SkipRerank,
+        Self::SkipGraph,
+        Self::VectorOnly,
+        Self::Bm25Only,
+    ];
+
+    fn from_rung(rung: u8) -> Self {
+        Self::LADDER[usize::from(rung).min(Self::LADDER.len() - 1)]
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::SkipRerank => "skip_rerank",
+            Self::SkipGraph => "skip_graph",
+            Self::VectorOnly => "vector_only",
+            Self::Bm25Only => "bm25_only",
+        }
+    }
+}
+
+impl fmt::Display for DegradationLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A timed stage of recall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecallStage {
+    /// Embedding the query.
+    Embed,
+    /// Searching the indexes, graph expansion included.
+    Retrieve,
+    /// Expanding candidates along memory relations.
+    Graph,
+    /// Rescoring beyond the first ranking.
+    Rerank,
+}
+
+impl RecallStage {
+    /// The level at which this stage is dropped.
+    fn dropped_at(self) -> DegradationLevel {
+        match self {
+            Self::Rerank => DegradationLevel::SkipRerank,
+            Self::Graph => DegradationLevel::SkipGraph,
+            Self::Embed => DegradationLevel::Bm25Only,
+            Self::Retrieve => DegradationLevel::Bm25Only,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LatencySloConfig {
+    /// Deadline for a whole recall.
+    pub deadline_ms: u64,
+    /// Budget for embedding the query.
+    pub embed_ms: u64,
+    /// Budget for searching the indexes, graph expansion included.
+    pub retrieve_ms: u64,
+    /// Budget for graph expansion.
+    pub graph_ms: u64,
+    /// Budget for rescoring beyond the first ranking.
+    pub rerank_ms: u64,
+    /// Recalls in a row within every budget before the level moves back up.
+    pub recover_after: u32,
+}
+
+impl Default for LatencySloConfig {
+    fn default() -> Self {
+        Self {
+            deadline_ms: 500,
+            embed_ms: 100,
+            retrieve_ms: 250,
+            graph_ms: 100,
+            rerank_ms: 100,
+            recover_after: 20,
+        }
+    }
+}
+
+impl LatencySloConfig {
+    pub fn validate(&self) -> Result<()> {
+        for (name, value) in [
+            ("deadline_ms", self.deadline_ms),
+            ("embed_ms", self.embed_ms),
+            ("retrieve_ms", self.retrieve_ms),
+            ("graph_ms", self.graph_ms),
+            ("rerank_ms", self.rerank_ms),
+            ("recover_after", u64::from(self.recover_after)),
+        ] {
+            if value == 0 {
+                return Err(Error::invalid_field(
+                    format!("features.latency_slo.{name}"),
+                    "must be > 0",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn budget_ms(&self, stage: RecallStage) -> u64 {
+        match stage {
+            RecallStage::Embed => self.embed_ms,
+            RecallStage::Retrieve => self.retrieve_ms,
+            RecallStage::Graph => self.graph_ms,
+            RecallStage::Rerank => self.rerank_ms,
+        }
+    }
+}
+
+/// The level recalls currently run at, shared by every recall on an
+/// engine.
+#[derive(Debug)]
+pub struct LatencySlo {
+    config: LatencySloConfig,
+    rung: AtomicU8,
+    within_budget: AtomicU32,
+    degraded: AtomicU64,
+}
+
+impl LatencySlo {
+    pub fn new(config: LatencySloConfig) -> Self {
+        Self {
+            config,
+            rung: AtomicU8::new(0),
+            within_budget: AtomicU32::new(0),
+            degraded: AtomicU64::new(0),
+        }
+    }
+
+    pub fn config(&self) -> &LatencySloConfig {
+        &self.config
+    }
+
+    /// The level the next recall starts at.
+    pub fn level(&self) -> DegradationLevel {
+        DegradationLevel::from_rung(self.rung.load(Ordering::Relaxed))
+    }
+
+    /// Recalls that ran below full quality.
+    pub fn degraded_recalls(&self) -> u64 {
+        self.degraded.load(Ordering::Relaxed)
+    }
+
+    /// Move the level after a recall: down a rung (no lower than `floor`)
+    /// if it ran over a budget, up a rung after `recover_after` in a row
+    /// that did not.
+    fn observe(&self, over_budget: bool, floor: DegradationLevel, report: &DegradationReport) {
+        if report.level > DegradationLevel::None {
+            self.degraded.fetch_add(1, Ordering::Relaxed);
+        }
+        let from = self.level();
+        if over_budget {
+            self.within_budget.store(0, Ordering::Relaxed);
+            let to = DegradationLevel::from_rung(from as u8 + 1).min(floor);
+            if to > from
+                && self
+                    .rung
+                    .compare_exchange(from as u8, to as u8, Ordering::Relaxed, Ordering::Relaxed)
+                    .is_ok()
+            {
+                tracing::warn!(
+                    target: "mnemo::latency_slo",
+                    from = %from,
+                    to = %to,
+                    elapsed_ms = report.elapsed_ms,
+                    "recall over its latency budget; degrading"
+                );
+            }
+        } else if from > DegradationLevel::None
+            && self.within_budget.fetch_add(1, Ordering::Relaxed) + 1 >= self.config.recover_after
+        {
+            self.within_budget.store(0, Ordering::Relaxed);
+            let to = DegradationLevel::from_rung(from as u8 - 1);
+            if self
+                .rung
+                .compare_exchange(from as u8, to as u8, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+            {
+                tracing::info!(
+                    target: "mnemo::latency_slo",
+                    from = %from,
+                    to = %to,
+                    "recalls back within their latency budgets; recovering"
+                );
+            }
+        }
+    }
+}
+
+/// Time spent in one recall stage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageTiming {
+    pub stage: RecallStage,
+    pub elapsed_ms: u64,
+    /// The stage's budget, when an SLO is configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget_ms: Option<u64>,
+}
+
+/// How a recall was degraded to meet its deadline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DegradationReport {
+    /// The rung the recall ran at.
+    pub level: DegradationLevel,
+    /// Stages dropped, in the order they were reached.
+    pub skipped: Vec<RecallStage>,
+    /// Stages that ran.
+    pub stages: Vec<StageTiming>,
+    pub elapsed_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline_ms: Option<u64>,
+}
+
+/// The deadline and degradation level of one recall.
+pub(crate) struct RecallBudget<'a> {
+    slo: Option<&'a LatencySlo>,
+    started: Instant,
+    deadline: Option<Duration>,
+    level: DegradationLevel,
+    /// The lowest level this engine can run at.
+    floor: DegradationLevel,
+    stages: Vec<StageTiming>,
+    skipped: Vec<RecallStage>,
+}
+
+impl<'a> RecallBudget<'a> {
+    /// Start timing a recall. Unrecorded recalls run at full quality and
+    /// leave the shared level alone.
+    pub(crate) fn start(engine: &'a MnemoEngine, deadline_ms: Option<u64>, record: bool) -> Self {
+        let slo = engine.latency_slo.as_deref().filter(|_| record);
+        let deadline_ms = if record {
+            deadline_ms.or(slo.map(|slo| slo.config.deadline_ms))
+        } else {
+            None
+        };
+        let floor = if engine.full_text.is_some() {
+            DegradationLevel::Bm25Only
+        } else {
+            DegradationLevel::VectorOnly
+        };
+        Self {
+            slo,
+            started: Instant::now(),
+            deadline: deadline_ms.map(Duration::from_millis),
+            level: slo.map_or(DegradationLevel::None, |slo| slo.level().min(floor)),
+            floor,
+            stages: Vec::new(),
+            skipped: Vec::new(),
+        }
+    }
+
+    pub(crate) fn level(&self) -> DegradationLevel {
+        self.level
+    }
+
+    /// Time left before the deadline, if there is one.
+    pub(crate) fn remaining(&self) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.saturating_sub(self.started.elapsed()))
+    }
+
+    /// The strategy to search with at the current level.
+    pub(crate) fn strategy<'s>(&self, requested: &'s str) -> &'s str {
+        let vector_based = matches!(
+            requested,
+            "semantic" | "hybrid" | "auto" | "graph" | "domain_scoped" | "reconstruct"
+        );
+        match self.level {
+            DegradationLevel::Bm25Only if vector_based => "lexical",
+            DegradationLevel::VectorOnly | DegradationLevel::Bm25Only
+                if matches!(requested, "hybrid" | "auto" | "graph" | "reconstruct") =>
+            {
+                "semantic"
+            }
+            DegradationLevel::SkipGraph if requested == "graph" => "semantic",
+            _ => requested,
+        }
+    }
+
+    /// Whether an optional stage runs. It does not once the recall is at
+    /// the level that drops it or past its deadline; in the latter case
+    /// the recall degrades to that level.
+    pub(crate) fn runs(&mut self, stage: RecallStage) -> bool {
+        let dropped_at = stage.dropped_at();
+        if self.level < dropped_at && self.remaining() != Some(Duration::ZERO) {
+            return true;
+        }
+        self.degrade_to(dropped_at);
+        self.skip(stage);
+        false
+    }
+
+    /// Give up on the query embedding and search BM25 alone.
+    pub(crate) fn fall_back_to_bm25(&mut self) {
+        self.degrade_to(DegradationLevel::Bm25Only);
+        self.skip(RecallStage::Embed);
+    }
+
+    pub(crate) fn record(&mut self, stage: RecallStage, elapsed: Duration) {
+        let elapsed_ms = elapsed.as_millis() as u64;
+        match self.stages.iter_mut().find(|t| t.stage == stage) {
+            Some(timing) => timing.elapsed_ms += elapsed_ms,
+            None => self.stages.push(StageTiming {
+                stage,
+                elapsed_ms,
+                budget_ms: self.slo.map(|slo| slo.config.budget_ms(stage)),
+            }),
+        }
+    }
+
+    /// The report for the response, moving the shared level. `None` when
+    /// the recall had neither an SLO nor a deadline.
+    pub(crate) fn finish(self) -> Option<DegradationReport> {
+        self.deadline?;
+        let elapsed = self.started.elapsed();
+        let report = DegradationReport {
+            level: self.level,
+            skipped: self.skipped,
+            stages: self.stages,
+            elapsed_ms: elapsed.as_millis() as u64,
+            deadline_ms: self.deadline.map(|d| d.as_millis() as u64),
+        };
+        if let Some(slo) = self.slo {
+            let over_budget = elapsed > self.deadline.unwrap_or(Duration::MAX)
+                || report
+                    .stages
+                    .iter()
+                    .any(|t| t.budget_ms.is_some_and(|budget| t.elapsed_ms > budget));
+            slo.observe(over_budget, self.floor, &report);
+        }
+        Some(report)
+    }
+
+    fn degrade_to(&mut self, level: DegradationLevel) {
+        self.level = self.level.max(level.min(self.floor));
+    }
+
+    fn skip(&mut self, stage: RecallStage) {
+        if !self.skipped.contains(&stage) {
+            self.skipped.push(stage);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> DegradationReport {
+        DegradationReport {
+            level: DegradationLevel::None,
+            skipped: Vec::new(),
+            stages: Vec::new(),
+            elapsed_ms: 0,
+            deadline_ms: None,
+        }
+    }
+
+    #[test]
+    fn ladder_steps_down_and_recovers() {
+        let slo = LatencySlo::new(LatencySloConfig {
+            recover_after: 2,
+            ..Default::default()
+        });
+        let floor = DegradationLevel::VectorOnly;
+        for expected in [
+            DegradationLevel::SkipRerank,
+            DegradationLevel::SkipGraph,
+            DegradationLevel::VectorOnly,
+            DegradationLevel::VectorOnly,
+        ] {
+            slo.observe(true, floor, &report());
+            assert_eq!(slo.level(), expected);
+        }
+
+        slo.observe(false, floor, &report());
+        assert_eq!(slo.level(), DegradationLevel::VectorOnly);
+        slo.observe(false, floor, &report());
+        assert_eq!(slo.level(), DegradationLevel::SkipGraph);
+        slo.observe(true, floor, &report());
+        slo.observe(false, floor, &report());
+        assert_eq!(
+            slo.level(),
+            DegradationLevel::VectorOnly,
+            "an over-budget recall resets the recovery streak"
+        );
+    }
+
+    #[test]
+    fn levels_serialize_in_snake_case() {
+        assert_eq!(
+            serde_json::to_value(DegradationLevel::Bm25Only).unwrap(),
+            "bm25_only"
+        );
+        assert_eq!(DegradationLevel::SkipRerank.to_string(), "skip_rerank");
+    }
+}
@@ -1,11 +1,13 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::hash::compute_content_hash;
+use crate::index::ShardRoute;
+use crate::model::acl::Permission;
 use crate::model::event::{AgentEvent, EventType};
 use crate::model::memory::{ConsolidationState, MemoryRecord, MemoryType, SourceType};
-use crate::model::relation::Relation;
+use crate::model::relation::{CONSOLIDATED_FROM, Relation};
 use crate::query::MnemoEngine;
 use crate::storage::MemoryFilter;
 
@@ -84,6 +86,16 @@ fn hours_since_creation(created_at: &str) -> f32 {
     }
 }
 
+/// Effective importance below which a decay pass archives a memory, when
+/// the caller does not choose a threshold.
+pub const DEFAULT_ARCHIVE_THRESHOLD: f32 = 0.1;
+/// Effective importance below which a decay pass forgets a memory, when the
+/// caller does not choose a threshold.
+pub const DEFAULT_FORGET_THRESHOLD: f32 = 0.05;
+/// Smallest tag cluster [`run_consolidation`] folds into a summary, when the
+/// caller does not choose a size.
+pub const DEFAULT_MIN_CLUSTER_SIZE: usize = 3;
+
 #[non_exhaustive]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DecayPassResult {
@@ -105,6 +117,7 @@ impl DecayPassResult {
 /// Run a decay pass over all active memories for the given agent.
 /// Memories below `forget_threshold` are marked Forgotten.
 /// Memories below `archive_threshold` (but above forget) are marked Archived.
+/// Pinned memories never decay.
 pub async fn run_decay_pass(
     engine: &MnemoEngine,
     agent_id: &str,
@@ -126,7 +139,8 @@ pub async fn run_decay_pass(
     let total_processed = memories.len();
 
     for mut record in memories {
-        if record.consolidation_state == ConsolidationState::Forgotten
+        if record.pinned
+            || record.consolidation_state == ConsolidationState::Forgotten
             || record.consolidation_state == ConsolidationState::Archived
         {
             continue;
@@ -212,12 +226,14 @@ pub async fn run_consolidation(
         .list_memories(&filter, super::MAX_BATCH_QUERY_LIMIT, 0)
         .await?;
 
-    // Only consider memories that are Raw or Active
+    // Only consider unpinned, non-ephemeral memories that are Raw or Active
     let active: Vec<MemoryRecord> = memories
         .into_iter()
         .filter(|m| {
-            m.consolidation_state == ConsolidationState::Raw
-                || m.consolidation_state == ConsolidationState::Active
+            !m.pinned
+                && !super::ephemeral::is_ephemeral(m)
+                && (m.consolidation_state == ConsolidationState::Raw
+                    || m.consolidation_state == ConsolidationState::Active)
         })
         .collect();
 
@@ -354,10 +370,18 @@ pub async fn run_consolidation(
             quarantined: false,
             quarantine_reason: None,
             decay_function: None,
+            pinned: false,
+            valid_from: None,
+            valid_until: None,
+            curation: None,
+            attestation: None,
+            channel: None,
         };
 
         engine.storage.insert_memory(&new_record).await?;
-        engine.index.add(new_id, &embedding)?;
+        engine
+            .index
+            .add_routed(ShardRoute::of(&new_record), new_id, &embedding)?;
         if let Some(ref ft) = engine.full_text {
             ft.add(new_id, &new_record.content)?;
             ft.commit()?;
@@ -370,7 +394,7 @@ pub async fn run_consolidation(
                 id: Uuid::now_v7(),
                 source_id: new_id,
                 target_id: original.id,
-                relation_type: "consolidated_from".to_string(),
+                relation_type: CONSOLIDATED_FROM.to_string(),
                 weight: 1.0,
                 metadata: serde_json::Value::Object(serde_json::Map::new()),
                 created_at: new_record.created_at.clone(),
@@ -397,23 +421,82 @@ pub async fn run_consolidation(
     })
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CleanupRequest {
+    /// Report what the pass would delete and archive without doing it.
+    /// Expiry warnings are not sent and access-log pruning is skipped.
+    #[serde(default)]
+    pub dry_run: Option<bool>,
+}
+
+impl CleanupRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Report from a cleanup pass: a TTL sweep followed by a trash purge,
+/// access-log pruning and event archival.
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupResult {
+    pub ttl: TtlReport,
+    pub trash: crate::query::trash::PurgeReport,
+    /// Access-log entries dropped by the retention limits.
+    #[serde(default)]
+    pub access_log_pruned: usize,
+    /// Events moved to cold storage by the event-retention policy.
+    #[serde(default)]
+    pub events_archived: usize,
+    /// True when nothing was changed: the counts and ids are what the pass
+    /// would have deleted and archived.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl CleanupResult {
+    pub fn new(ttl: TtlReport, trash: crate::query::trash::PurgeReport) -> Self {
+        Self {
+            ttl,
+            trash,
+            access_log_pruned: 0,
+            events_archived: 0,
+            dry_run: false,
+        }
+    }
+}
+
 /// Report from a single TTL sweep pass.
 #[non_exhaustive]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TtlReport {
     pub swept_count: usize,
+    /// Memories that entered the expiry-warning window during this pass
+    /// and got a `MemoryExpiring` event. Always zero when no warning
+    /// window is configured.
+    pub warned_count: usize,
     pub errors: Vec<TtlError>,
+    /// The swept memories.
+    #[serde(default)]
+    pub memory_ids: Vec<Uuid>,
 }
 
 impl TtlReport {
-    pub fn new(swept_count: usize, errors: Vec<TtlError>) -> Self {
+    pub fn new(swept_count: usize, warned_count: usize, errors: Vec<TtlError>) -> Self {
         Self {
             swept_count,
+            warned_count,
             errors,
+            memory_ids: Vec::new(),
         }
     }
 }
 
+/// Metadata key recording the `expires_at` value a `MemoryExpiring`
+/// warning was already emitted for, so each expiry is announced once.
+/// Extending the TTL changes `expires_at` and re-arms the warning.
+pub const EXPIRY_WARNED_METADATA_KEY: &str = "expiry_warned_for";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TtlError {
     pub memory_id: Uuid,
@@ -426,7 +509,16 @@ pub struct TtlError {
 /// records which memories were purged and when. The function is idempotent
 /// under concurrent callers (storage deletes of an already-absent row surface
 /// as a storage error and are reported, not retried).
+///
+/// When the engine has an
+/// [`expiry_warning_hours`](MnemoEngine::expiry_warning_hours) window, any
+/// memory expiring within it gets one `EventType::MemoryExpiring` event.
 pub async fn run_ttl_sweep(engine: &MnemoEngine) -> Result<TtlReport> {
+    ttl_sweep(engine, false).await
+}
+
+/// [`run_ttl_sweep`], or with `dry_run` only the memories it would delete.
+pub(crate) async fn ttl_sweep(engine: &MnemoEngine, dry_run: bool) -> Result<TtlReport> {
     let filter = MemoryFilter {
         include_deleted: false,
         ..Default::default()
@@ -438,17 +530,65 @@ pub async fn run_ttl_sweep(engine: &MnemoEngine) -> Result<TtlReport> {
 
     let now = chrono::Utc::now();
     let now_str = now.to_rfc3339();
+    let warn_before = engine
+        .expiry_warning_hours
+        .map(|hours| now + chrono::Duration::hours(i64::from(hours)));
     let mut swept_count = 0;
+    let mut warned_count = 0;
     let mut errors = Vec::new();
+    let mut memory_ids = Vec::new();
 
-    for record in memories {
-        let Some(ref expires_at) = record.expires_at else {
+    for mut record in memories {
+        if record.pinned {
+            continue;
+        }
+        let Some(expires_at) = record.expires_at.clone() else {
             continue;
         };
-        let Ok(exp) = chrono::DateTime::parse_from_rfc3339(expires_at) else {
+        let Ok(exp) = chrono::DateTime::parse_from_rfc3339(&expires_at) else {
             continue;
         };
         if exp > now {
+            let already_warned = record
+                .metadata
+                .get(EXPIRY_WARNED_METADATA_KEY)
+                .and_then(|v| v.as_str())
+                == Some(expires_at.as_str());
+            if let Some(warn_before) = warn_before
+                && exp <= warn_before
+                && !already_warned
+                && !dry_run
+            {
+                if let Some(map) = record.metadata.as_object_mut() {
+                    map.insert(
+                        EXPIRY_WARNED_METADATA_KEY.to_string(),
+                        serde_json::Value::String(expires_at.clone()),
+                    );
+                }
+                match engine.storage.update_memory(&record).await {
+                    Ok(()) => {
+                        if let Some(ref cache) = engine.cache {
+                            cache.invalidate(record.id);
+                        }
+                        emit_expiring_event(engine, &record, &expires_at).await;
+                        warned_count += 1;
+                    }
+                    Err(e) => errors.push(TtlError {
+                        memory_id: record.id,
+                        error: e.to_string(),
+                    }),
+                }
+            }
+            continue;
+        }
+        // Expired but retention-locked: recall already hides it, and the
+        // sweep deletes it once the lock ends.
+        if super::retention_lock::active_lock(engine, &record).is_some() {
+            continue;
+        }
+        if dry_run {
+            swept_count += 1;
+            memory_ids.push(record.id);
             continue;
         }
 
@@ -468,6 +608,7 @@ pub async fn run_ttl_sweep(engine: &MnemoEngine) -> Result<TtlReport> {
                 }
                 emit_expiry_event(engine, &record, &now_str).await;
                 swept_count += 1;
+                memory_ids.push(record.id);
             }
             Err(e) => errors.push(TtlError {
                 memory_id: record.id,
@@ -478,10 +619,145 @@ pub async fn run_ttl_sweep(engine: &MnemoEngine) -> Result<TtlReport> {
 
     Ok(TtlReport {
         swept_count,
+        warned_count,
         errors,
+        memory_ids,
     })
 }
 
+async fn emit_expiring_event(engine: &MnemoEngine, record: &MemoryRecord, expires_at: &str) {
+    let event = super::event_builder::build_event(
+        engine,
+        &record.agent_id,
+        EventType::MemoryExpiring,
+        serde_json::json!({
+            "memory_id": record.id.to_string(),
+            "expires_at": expires_at,
+        }),
+        &record.id.to_string(),
+        record.thread_id.clone(),
+    )
+    .await;
+    if let Err(e) = engine.storage.insert_event(&event).await {
+        tracing::error!(event_id = %event.id, error = %e, "ttl sweep: failed to insert MemoryExpiring event");
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtendTtlRequest {
+    pub memory_id: Uuid,
+    pub agent_id: Option<String>,
+    /// Seconds added to the current `expires_at` (or to now, if the memory
+    /// has already expired but not yet been swept).
+    pub extra_seconds: u64,
+}
+
+impl ExtendTtlRequest {
+    pub fn new(memory_id: Uuid, extra_seconds: u64) -> Self {
+        Self {
+            memory_id,
+            agent_id: None,
+            extra_seconds,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtendTtlResponse {
+    pub memory_id: Uuid,
+    pub previous_expires_at: String,
+    pub expires_at: String,
+}
+
+impl ExtendTtlResponse {
+    pub fn new(memory_id: Uuid, previous_expires_at: String, expires_at: String) -> Self {
+        Self {
+            memory_id,
+            previous_expires_at,
+            expires_at,
+        }
+    }
+}
+
+/// Push a memory's `expires_at` further into the future.
+///
+/// Only memories that already carry a TTL can be extended; pin a memory to
+/// make it permanent instead.
+pub async fn extend_ttl(
+    engine: &MnemoEngine,
+    request: ExtendTtlRequest,
+) -> Result<ExtendTtlResponse> {
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    if request.extra_seconds == 0 {
+        return Err(Error::Validation(
+            "extra_seconds must be greater than 0".to_string(),
+        ));
+    }
+
+    let mut record = engine
+        .storage
+        .get_memory(request.memory_id)
+        .await?
+        .filter(|r| !r.is_deleted())
+        .ok_or_else(|| Error::NotFound(format!("memory {} not found", request.memory_id)))?;
+    if !engine
+        .storage
+        .check_permission(request.memory_id, &agent_id, Permission::Write)
+        .await?
+    {
+        return Err(Error::PermissionDenied(format!(
+            "agent {agent_id} cannot modify memory {}",
+            request.memory_id
+        )));
+    }
+    let Some(previous) = record.expires_at.clone() else {
+        return Err(Error::Validation(format!(
+            "memory {} has no TTL to extend",
+            request.memory_id
+        )));
+    };
+
+    let now = chrono::Utc::now();
+    let base = chrono::DateTime::parse_from_rfc3339(&previous)
+        .map(|dt| dt.with_timezone(&chrono::Utc).max(now))
+        .unwrap_or(now);
+    let extra = i64::try_from(request.extra_seconds)
+        .ok()
+        .and_then(chrono::Duration::try_seconds)
+        .ok_or_else(|| Error::Validation("extra_seconds is too large".to_string()))?;
+    let expires_at = (base + extra).to_rfc3339();
+
+    record.expires_at = Some(expires_at.clone());
+    record.updated_at = now.to_rfc3339();
+    engine.storage.update_memory(&record).await?;
+    if let Some(ref cache) = engine.cache {
+        cache.invalidate(record.id);
+    }
+
+    let event = super::event_builder::build_event(
+        engine,
+        &agent_id,
+        EventType::MemoryWrite,
+        serde_json::json!({
+            "memory_id": record.id.to_string(),
+            "previous_expires_at": previous,
+            "expires_at": expires_at,
+        }),
+        &record.id.to_string(),
+        record.thread_id.clone(),
+    )
+    .await;
+    if let Err(e) = engine.storage.insert_event(&event).await {
+        tracing::error!(event_id = %event.id, error = %e, "failed to insert audit event");
+    }
+
+    Ok(ExtendTtlResponse::new(record.id, previous, expires_at))
+}
+
 async fn emit_expiry_event(engine: &MnemoEngine, record: &MemoryRecord, now_str: &str) {
     let event_content_hash =
         compute_content_hash(&record.id.to_string(), &record.agent_id, now_str);
@@ -520,10 +796,11 @@ async fn emit_expiry_event(engine: &MnemoEngine, record: &MemoryRecord, now_str:
         latency_ms: None,
         cost_usd: None,
         timestamp: now_str.to_string(),
-        logical_clock: 0,
+        logical_clock: super::clock::tick(engine, &record.agent_id).await,
         content_hash: event_content_hash,
         prev_hash: event_prev_hash,
         embedding: None,
+        request_id: crate::request_id::current(),
     };
     if let Err(e) = engine.storage.insert_event(&event).await {
         tracing::error!(event_id = %event.id, error = %e, "ttl sweep: failed to insert MemoryExpired event");
@@ -569,6 +846,12 @@ mod tests {
             quarantined: false,
             quarantine_reason: None,
             decay_function: None,
+            pinned: false,
+            valid_from: None,
+            valid_until: None,
+            curation: None,
+            attestation: None,
+            channel: None,
         };
 
         let eff = effective_importance(&record);
@@ -0,0 +1,145 @@
+//! Engine-wide request size limits.
+//!
+//! Every transport funnels into the same engine operations, so the limits
+//! are enforced there rather than per front-end. [`LimitsConfig`] is set
+//! with [`MnemoEngine::with_limits`](super::MnemoEngine::with_limits) or the
+//! `[limits]` table of the engine config file. Each limit can be overridden
+//! on its own; unset limits keep their defaults.
+//!
+//! The recall limit is a soft quota: a larger `limit` is lowered to
+//! `max_recall_limit` instead of failing the request. Every other limit
+//! rejects the request with [`Error::InvalidFields`] naming the field.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// Most memories one recall returns. Larger limits are lowered to it.
+    pub max_recall_limit: usize,
+    /// Largest memory content, in bytes.
+    pub max_content_bytes: usize,
+    /// Most tags on one memory.
+    pub max_tags: usize,
+    /// Largest metadata object, in bytes of serialized JSON.
+    pub max_metadata_bytes: usize,
+    /// Most ids (memories, target agents, cherry-picks) in one request.
+    pub max_batch_size: usize,
+    /// Largest checkpoint state, in bytes of serialized JSON.
+    pub max_snapshot_bytes: usize,
+}
+
+impl Default for LimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_recall_limit: 100,
+            max_content_bytes: 1024 * 1024,
+            max_tags: 64,
+            max_metadata_bytes: 64 * 1024,
+            max_batch_size: 1000,
+            max_snapshot_bytes: 16 * 1024 * 1024,
+        }
+    }
+}
+
+impl LimitsConfig {
+    pub fn validate(&self) -> Result<()> {
+        for (name, value) in [
+            ("max_recall_limit", self.max_recall_limit),
+            ("max_content_bytes", self.max_content_bytes),
+            ("max_tags", self.max_tags),
+            ("max_metadata_bytes", self.max_metadata_bytes),
+            ("max_batch_size", self.max_batch_size),
+            ("max_snapshot_bytes", self.max_snapshot_bytes),
+        ] {
+            if value == 0 {
+                return Err(Error::Validation(format!("limits.{name} must be > 0")));
+            }
+        }
+        Ok(())
+    }
+
+    /// `requested`, lowered to `max_recall_limit`.
+    pub fn recall_limit(&self, requested: usize) -> usize {
+        if requested > self.max_recall_limit {
+            tracing::debug!(
+                requested,
+                max = self.max_recall_limit,
+                "recall limit lowered to the configured maximum"
+            );
+        }
+        requested.min(self.max_recall_limit)
+    }
+
+    pub fn check_content(&self, content: &str) -> Result<()> {
+        if content.len() > self.max_content_bytes {
+            return Err(Error::invalid_field(
+                "content",
+                format!(
+                    "must be at most {} bytes, got {}",
+                    self.max_content_bytes,
+                    content.len()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn check_tags(&self, tags: &[String]) -> Result<()> {
+        if tags.len() > self.max_tags {
+            return Err(Error::invalid_field(
+                "tags",
+                format!(
+                    "must have at most {} entries, got {}",
+                    self.max_tags,
+                    tags.len()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn check_metadata(&self, metadata: &serde_json::Value) -> Result<()> {
+        let size = serde_json::to_vec(metadata)?.len();
+        if size > self.max_metadata_bytes {
+            return Err(Error::invalid_field(
+                "metadata",
+                format!(
+                    "must be at most {} bytes of JSON, got {size}",
+                    self.max_metadata_bytes
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn check_snapshot(&self, snapshot: &serde_json::Value) -> Result<()> {
+        let size = serde_json::to_vec(snapshot)?.len();
+        if size > self.max_snapshot_bytes {
+            return Err(Error::invalid_field(
+                "state_snapshot",
+                format!(
+                    "must be at most {} bytes of JSON, got {size}",
+                    self.max_snapshot_bytes
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Reject a request listing more than `max_batch_size` items in `field`.
+    pub fn check_batch(&self, field: &str, len: usize) -> Result<()> {
+        if len > self.max_batch_size {
+            return Err(Error::invalid_field(
+                field,
+                format!(
+                    "must have at most {} entries, got {len}",
+                    self.max_batch_size
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
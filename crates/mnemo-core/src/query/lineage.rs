@@ -0,0 +1,164 @@
+//! Derivation chains for recalled memories (`include=provenance`).
+//!
+//! Consolidated topic documents, reflection merges and facts extracted from
+//! turns are derived from other memories. Those links are stored as
+//! relations pointing from the derived memory to its sources
+//! (`consolidated_from`, `derived_from`). When a recall asks for the
+//! `provenance` expansion, each hit carries a [`ProvenanceChain`]: its own
+//! hashes and source event, then the tree of memories it was derived from,
+//! each with the same details, so a reader can check every step against the
+//! audit hash chain.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::model::acl::Permission;
+use crate::model::memory::{MemoryRecord, SourceType};
+use crate::model::relation::DERIVATION_RELATION_TYPES;
+use crate::query::MnemoEngine;
+
+/// `RecallRequest::include` value that attaches a [`ProvenanceChain`] to
+/// every hit.
+pub const INCLUDE_PROVENANCE: &str = "provenance";
+
+/// Every expansion `RecallRequest::include` accepts.
+pub const RECALL_INCLUDES: &[&str] = &[INCLUDE_PROVENANCE];
+
+/// How many derivation hops are followed below a recalled memory.
+pub const MAX_PROVENANCE_DEPTH: usize = 5;
+
+/// The event a memory was recorded from, when its `source_id` names one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceEventRef {
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub content_hash: String,
+    pub timestamp: String,
+}
+
+/// One memory in a derivation tree.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProvenanceNode {
+    pub memory_id: Uuid,
+    /// Relation linking this memory to the one derived from it.
+    pub relation_type: String,
+    /// `None` when the reading agent may not see this source's content.
+    pub content: Option<String>,
+    pub content_hash: String,
+    pub prev_hash: Option<String>,
+    pub source_type: SourceType,
+    pub source_event: Option<SourceEventRef>,
+    pub created_at: String,
+    pub deleted: bool,
+    pub derived_from: Vec<ProvenanceNode>,
+}
+
+/// Provenance of one recalled memory.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProvenanceChain {
+    pub content_hash: String,
+    pub prev_hash: Option<String>,
+    pub source_type: SourceType,
+    pub source_event: Option<SourceEventRef>,
+    pub derived_from: Vec<ProvenanceNode>,
+}
+
+/// Validate `RecallRequest::include` values.
+pub fn validate_includes(includes: &[String]) -> Result<()> {
+    for include in includes {
+        if !RECALL_INCLUDES.contains(&include.as_str()) {
+            return Err(crate::error::Error::Validation(format!(
+                "unknown recall include '{include}' (expected one of: {})",
+                RECALL_INCLUDES.join(", ")
+            )));
+        }
+    }
+    Ok(())
+}
+
+async fn source_event(engine: &MnemoEngine, record: &MemoryRecord) -> Option<SourceEventRef> {
+    let event_id = Uuid::parse_str(record.source_id.as_deref()?).ok()?;
+    match engine.storage.get_event(event_id).await {
+        Ok(Some(event)) => Some(SourceEventRef {
+            event_id,
+            event_type: event.event_type.to_string(),
+            content_hash: hex::encode(&event.content_hash),
+            timestamp: event.timestamp,
+        }),
+        Ok(None) => None,
+        Err(e) => {
+            tracing::warn!(event_id = %event_id, error = %e, "provenance: source event lookup failed");
+            None
+        }
+    }
+}
+
+/// Build the provenance chain for `record`, as read by `agent_id`.
+pub async fn build_chain(
+    engine: &MnemoEngine,
+    agent_id: &str,
+    record: &MemoryRecord,
+) -> Result<ProvenanceChain> {
+    let mut visited = HashSet::from([record.id]);
+    let derived_from = sources_of(engine, agent_id, record.id, 1, &mut visited).await?;
+    Ok(ProvenanceChain {
+        content_hash: hex::encode(&record.content_hash),
+        prev_hash: record.prev_hash.as_ref().map(hex::encode),
+        source_type: record.source_type,
+        source_event: source_event(engine, record).await,
+        derived_from,
+    })
+}
+
+/// Depth-first walk over derivation relations. `visited` guards against
+/// cycles and repeated sources; the walk stops at [`MAX_PROVENANCE_DEPTH`].
+async fn sources_of(
+    engine: &MnemoEngine,
+    agent_id: &str,
+    memory_id: Uuid,
+    depth: usize,
+    visited: &mut HashSet<Uuid>,
+) -> Result<Vec<ProvenanceNode>> {
+    if depth > MAX_PROVENANCE_DEPTH {
+        return Ok(Vec::new());
+    }
+    let mut nodes = Vec::new();
+    for relation in engine.storage.get_relations_from(memory_id).await? {
+        if !DERIVATION_RELATION_TYPES.contains(&relation.relation_type.as_str())
+            || !visited.insert(relation.target_id)
+        {
+            continue;
+        }
+        let Some(mut source) = engine.storage.get_memory(relation.target_id).await? else {
+            continue;
+        };
+        let readable = engine
+            .storage
+            .check_permission(source.id, agent_id, Permission::Read)
+            .await?;
+        let content = if readable {
+            super::consolidate::decrypt_in_place(engine, &mut source);
+            Some(source.content.clone())
+        } else {
+            None
+        };
+        let derived_from =
+            Box::pin(sources_of(engine, agent_id, source.id, depth + 1, visited)).await?;
+        nodes.push(ProvenanceNode {
+            memory_id: source.id,
+            relation_type: relation.relation_type,
+            content,
+            content_hash: hex::encode(&source.content_hash),
+            prev_hash: source.prev_hash.as_ref().map(hex::encode),
+            source_type: source.source_type,
+            source_event: source_event(engine, &source).await,
+            created_at: source.created_at.clone(),
+            deleted: source.is_deleted(),
+            derived_from,
+        });
+    }
+    Ok(nodes)
+}
@@ -370,6 +370,12 @@ mod tests {
             quarantined: false,
             quarantine_reason: None,
             decay_function: None,
+            pinned: false,
+            valid_from: None,
+            valid_until: None,
+            curation: None,
+            attestation: None,
+            channel: None,
         }
     }
 
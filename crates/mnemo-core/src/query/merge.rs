@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::error::{Error, Result};
+use crate::model::acl::Permission;
 use crate::model::checkpoint::Checkpoint;
 use crate::model::event::EventType;
 use crate::query::MnemoEngine;
@@ -62,6 +63,9 @@ pub async fn execute(engine: &MnemoEngine, request: MergeRequest) -> Result<Merg
     let target_branch = request.target_branch.unwrap_or_else(|| "main".to_string());
     let strategy = request.strategy.unwrap_or(MergeStrategy::FullMerge);
     let now = chrono::Utc::now().to_rfc3339();
+    if let Some(ref ids) = request.cherry_pick_ids {
+        engine.limits().check_batch("cherry_pick_ids", ids.len())?;
+    }
 
     // Get latest checkpoint on source branch
     let source_cp = engine
@@ -80,6 +84,18 @@ pub async fn execute(engine: &MnemoEngine, request: MergeRequest) -> Result<Merg
         .storage
         .get_latest_checkpoint(&request.thread_id, &target_branch)
         .await?;
+    super::checkpoint::ensure_thread_access(
+        engine,
+        &request.thread_id,
+        &agent_id,
+        Permission::Write,
+    )
+    .await?;
+    let source_cp = super::checkpoint::materialize(engine, source_cp).await?;
+    let target_cp = match target_cp {
+        Some(cp) => Some(super::checkpoint::materialize(engine, cp).await?),
+        None => None,
+    };
 
     let target_parent_id = target_cp.as_ref().map(|cp| cp.id);
 
@@ -1,33 +1,93 @@
+pub mod access_log;
+pub mod access_request;
+pub mod agent_settings;
+pub mod attestation;
+pub mod auto_checkpoint;
 pub mod branch;
+pub mod calibration;
 pub mod causality;
+pub mod change_feed;
+pub mod channel;
 pub mod checkpoint;
+pub mod clock;
+pub mod clustering;
+pub mod compression;
 pub mod conflict;
+pub mod conflict_queue;
 pub mod consolidate;
+pub mod curation;
 pub mod current_fact_resolver;
+pub mod dedup;
+pub mod drift;
+pub mod entity_summary;
+pub mod ephemeral;
 pub mod event_builder;
+pub mod event_retention;
 pub mod evidence;
 pub mod experience;
+pub mod export;
 pub mod forget;
+pub mod get_batch;
+pub mod graph_export;
+pub mod growth;
+pub mod health;
+pub mod hybrid;
+pub mod ingestion_filter;
+pub mod latency_slo;
 pub mod lifecycle;
+pub mod limits;
+pub mod lineage;
 pub mod maturity;
 pub mod merge;
+pub mod multi_agent;
 pub mod orientation_cache;
+pub mod outbox;
+pub mod pin;
 pub mod poisoning;
+pub mod prefetch;
+pub mod privacy;
+pub mod pseudonym;
 pub mod recall;
+pub mod recall_profile;
+pub mod rechain;
+pub mod reembed;
 pub mod reflection;
+pub mod reload;
 pub mod remember;
 pub mod replay;
 pub mod retained;
+pub mod retention_lock;
 pub mod retrieval;
+pub mod shadow_recall;
 pub mod share;
+pub mod share_link;
+pub mod similar;
+pub mod snapshot;
+pub mod thread_message;
+pub mod transfer;
+pub mod trash;
+pub mod turn;
+pub mod update;
+pub mod vacuum;
+pub mod validation;
+pub mod validity;
+pub mod verify;
+pub mod write_anomaly;
 
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::cache::MemoryCache;
 use crate::embedding::EmbeddingProvider;
 use crate::encryption::ContentEncryption;
+use crate::encryption::keys::KeyRegistry;
 use crate::error::{Error, Result};
 use crate::index::VectorIndex;
+use crate::model::access_request::AccessRequest;
+use crate::model::agent_profile::RetrievalDefaults;
+use crate::model::conflict::ConflictRecord;
+use crate::model::memory::MemoryRecord;
+use crate::model::recall_profile::RecallProfile;
 use crate::search::FullTextIndex;
 use crate::storage::StorageBackend;
 use crate::storage::cold::ColdStorage;
@@ -68,6 +128,9 @@ pub struct MnemoEngine {
     pub default_agent_id: String,
     pub default_org_id: Option<String>,
     pub encryption: Option<Arc<ContentEncryption>>,
+    /// Per-org encryption keys, preferred over `encryption` for the orgs
+    /// they cover. Attach via [`MnemoEngine::with_org_keys`].
+    pub org_keys: Option<Arc<KeyRegistry>>,
     pub cold_storage: Option<Arc<dyn ColdStorage>>,
     pub cache: Option<Arc<MemoryCache>>,
     pub embed_events: bool,
@@ -122,6 +185,128 @@ pub struct MnemoEngine {
     /// tier is backend-agnostic and RBAC/consent-gated like everything
     /// else. See [`experience`].
     pub experience_memory_enabled: bool,
+    /// Days a soft-deleted memory stays in the trash before
+    /// [`MnemoEngine::run_trash_purge`] hard-deletes it. `None` (the
+    /// default) keeps soft-deleted rows forever. Set via
+    /// [`MnemoEngine::with_trash_retention_days`].
+    pub trash_retention_days: Option<u32>,
+    /// Hours before `expires_at` at which the TTL sweep emits a
+    /// `MemoryExpiring` event. `None` (the default) disables warnings.
+    /// Set via [`MnemoEngine::with_expiry_warning_hours`].
+    pub expiry_warning_hours: Option<u32>,
+    /// NLI judge consulted by `detect_conflicts` for pairs that are nearby
+    /// but below the near-duplicate threshold. `None` (the default) keeps
+    /// detection purely similarity-based. Attach via
+    /// [`MnemoEngine::with_contradiction_checker`].
+    pub contradiction_checker: Option<Arc<dyn crate::nli::ContradictionChecker>>,
+    /// Candidate floor and flag threshold for the contradiction checker.
+    pub contradiction_policy: crate::nli::ContradictionPolicy,
+    /// Shared idle-timeout tracker. When set, the REST, gRPC and pgwire
+    /// front-ends touch it per request so the watchdog sees traffic from
+    /// every transport. Attach via [`MnemoEngine::with_activity_tracker`].
+    pub activity_tracker: Option<crate::activity::ActivityTracker>,
+    /// Per-memory read log. `None` (the default) records nothing beyond
+    /// `access_count`. Attach via [`MnemoEngine::with_access_log`].
+    pub access_log: Option<access_log::AccessLogPolicy>,
+    /// Archival of old events to cold storage. `None` (the default) keeps
+    /// every event hot. Attach via [`MnemoEngine::with_event_retention`].
+    pub event_retention: Option<event_retention::EventRetentionPolicy>,
+    /// k-anonymity and attribution redaction for shared-scope recall by
+    /// non-owners. `None` (the default) returns shared hits unfiltered.
+    /// Attach via [`MnemoEngine::with_shared_recall_privacy`].
+    pub shared_recall_privacy: Option<privacy::SharedRecallPrivacy>,
+    /// Aliases for agent ids in shared-scope reads and exports. `None`
+    /// (the default) shows real agent ids. Attach via
+    /// [`MnemoEngine::with_pseudonymizer`].
+    pub pseudonymizer: Option<pseudonym::Pseudonymizer>,
+    /// Signs share link tokens. `None` (the default) disables share links.
+    /// Attach via [`MnemoEngine::with_share_links`].
+    pub share_link_signer: Option<share_link::ShareLinkSigner>,
+    /// Judges agents' recent writes against their baseline. `None` (the
+    /// default) disables the pass. Attach via
+    /// [`MnemoEngine::with_write_anomaly_detection`].
+    pub write_anomaly: Option<Arc<write_anomaly::WriteAnomalyDetector>>,
+    /// Alternate configurations a share of recalls also runs under, in
+    /// the background. `None` (the default) shadows nothing. Attach via
+    /// [`MnemoEngine::with_shadow_recall`].
+    pub shadow_recall: Option<Arc<shadow_recall::ShadowRecall>>,
+    /// Maps each strategy's raw recall scores to a 0-1 relevance. Tune it
+    /// with [`MnemoEngine::with_score_calibration`].
+    pub score_calibration: Arc<calibration::ScoreCalibrator>,
+    /// Recall deadline, stage budgets and the shared degradation level.
+    /// `None` (the default) never degrades recall. Attach via
+    /// [`MnemoEngine::with_latency_slo`].
+    pub latency_slo: Option<Arc<latency_slo::LatencySlo>>,
+    /// Near-duplicate handling at remember time. `None` (the default)
+    /// always inserts. Attach via [`MnemoEngine::with_dedup_policy`].
+    pub dedup_policy: Option<dedup::DedupPolicy>,
+    /// Review of global-scope writes. `None` (the default) publishes them
+    /// immediately. Attach via [`MnemoEngine::with_curation`].
+    pub curation: Option<curation::CurationPolicy>,
+    /// Tags whose memories stay out of recall until attested. `None` (the
+    /// default) requires no attestation. Attach via
+    /// [`MnemoEngine::with_attestation`].
+    pub attestation: Option<attestation::AttestationPolicy>,
+    /// Per-org and per-tag periods during which memories cannot be hard
+    /// deleted or erased. `None` (the default) locks nothing. Attach via
+    /// [`MnemoEngine::with_retention_lock`].
+    pub retention_lock: Option<retention_lock::RetentionLockPolicy>,
+    /// Deny/allow lists, size bounds and languages every `remember` must
+    /// pass. `None` (the default) accepts everything within
+    /// [`limits`](Self::limits). Attach via
+    /// [`MnemoEngine::with_ingestion_filter`].
+    pub ingestion_filter: Option<ingestion_filter::IngestionFilter>,
+    /// Writes the summaries of the compression pass. `None` (the default)
+    /// falls back to [`ExtractiveSummarizer`](crate::summarize::ExtractiveSummarizer).
+    /// Attach via [`MnemoEngine::with_summarizer`].
+    pub summarizer: Option<Arc<dyn crate::summarize::Summarizer>>,
+    /// Paraphrases queries of recalls that set `expand_query`. `None` (the
+    /// default) makes such recalls fail. Attach via
+    /// [`MnemoEngine::with_query_expander`].
+    pub query_expander: Option<Arc<dyn crate::expansion::QueryExpander>>,
+    /// Plugin hooks run around `remember`, `recall` and `forget`, in
+    /// priority order. Empty by default. Attach via
+    /// [`MnemoEngine::with_hook`].
+    pub hooks: crate::hooks::HookRegistry,
+    /// Loaders used by [`MnemoEngine::ingest_documents`], by format name.
+    /// The built-in formats by default. Add one via
+    /// [`MnemoEngine::with_document_loader`].
+    pub document_loaders: crate::ingest::LoaderRegistry,
+    /// Hosts [`MnemoEngine::ingest_url`] may fetch from. Public addresses
+    /// only by default. Set via [`MnemoEngine::with_url_fetch_policy`].
+    pub url_fetch: crate::ingest::UrlFetchPolicy,
+    /// When `true`, `remember` also writes an outbox message in the same
+    /// transaction as the memory, for an
+    /// [`OutboxDispatcher`](outbox::OutboxDispatcher) to deliver. `false` by
+    /// default. Set via [`MnemoEngine::with_outbox`].
+    pub outbox_enabled: bool,
+    /// A checkpoint stores its full state when this many checkpoints have
+    /// passed since the last full one, and a delta otherwise; `1` stores
+    /// every state in full. Defaults to
+    /// [`DEFAULT_CHECKPOINT_SNAPSHOT_INTERVAL`](checkpoint::DEFAULT_CHECKPOINT_SNAPSHOT_INTERVAL).
+    /// Set via [`MnemoEngine::with_checkpoint_snapshot_interval`].
+    pub checkpoint_snapshot_interval: u32,
+    /// Request size limits enforced by every operation. Defaults to
+    /// [`LimitsConfig::default`](limits::LimitsConfig). Set via
+    /// [`MnemoEngine::with_limits`]; read with [`MnemoEngine::limits`].
+    limits: reload::Swappable<limits::LimitsConfig>,
+    /// Per-agent automatic checkpoint policies. Empty (no automatic
+    /// checkpoints) by default. Set via [`MnemoEngine::with_auto_checkpoint`].
+    auto_checkpoint: reload::Swappable<auto_checkpoint::AutoCheckpointConfig>,
+    /// Scheduled exports, run by [`MnemoEngine::run_scheduled_exports`].
+    /// None by default. Set via [`MnemoEngine::with_exports`].
+    exports: reload::Swappable<Vec<export::ExportSchedule>>,
+    /// Remembers per thread since its last checkpoint, for
+    /// [`auto_checkpoint`].
+    pending_writes: auto_checkpoint::PendingWrites,
+    /// Per-agent logical clocks stamped on every event. See [`clock`].
+    clocks: clock::LogicalClocks,
+    /// Maintenance switch: while set, write operations fail with
+    /// [`Error::ReadOnly`] and reads keep working. Toggle at runtime with
+    /// [`MnemoEngine::set_read_only`].
+    read_only: AtomicBool,
+    /// Write start times, so [`vacuum`] can wait for a quiet moment.
+    write_activity: vacuum::WriteActivity,
 }
 
 /// Default TTL (in seconds) applied to Working-tier memories.
@@ -146,6 +331,7 @@ impl MnemoEngine {
             default_agent_id,
             default_org_id,
             encryption: None,
+            org_keys: None,
             cold_storage: None,
             cache: None,
             embed_events: false,
@@ -157,6 +343,39 @@ impl MnemoEngine {
             consolidation_policy: maturity::ConsolidationPolicy::default(),
             evidence_scorer: None,
             experience_memory_enabled: false,
+            trash_retention_days: None,
+            expiry_warning_hours: None,
+            contradiction_checker: None,
+            contradiction_policy: crate::nli::ContradictionPolicy::default(),
+            activity_tracker: None,
+            access_log: None,
+            event_retention: None,
+            shared_recall_privacy: None,
+            pseudonymizer: None,
+            share_link_signer: None,
+            write_anomaly: None,
+            shadow_recall: None,
+            score_calibration: Arc::default(),
+            latency_slo: None,
+            dedup_policy: None,
+            curation: None,
+            attestation: None,
+            retention_lock: None,
+            ingestion_filter: None,
+            summarizer: None,
+            query_expander: None,
+            hooks: crate::hooks::HookRegistry::new(),
+            document_loaders: crate::ingest::LoaderRegistry::default(),
+            url_fetch: crate::ingest::UrlFetchPolicy::default(),
+            outbox_enabled: false,
+            checkpoint_snapshot_interval: checkpoint::DEFAULT_CHECKPOINT_SNAPSHOT_INTERVAL,
+            limits: reload::Swappable::default(),
+            auto_checkpoint: reload::Swappable::default(),
+            exports: reload::Swappable::default(),
+            pending_writes: auto_checkpoint::PendingWrites::default(),
+            clocks: clock::LogicalClocks::default(),
+            read_only: AtomicBool::new(false),
+            write_activity: vacuum::WriteActivity::default(),
         }
     }
 
@@ -203,6 +422,70 @@ impl MnemoEngine {
         self
     }
 
+    /// Seal each org's memories under its own key. Orgs the registry does
+    /// not cover fall back to the engine-wide key of
+    /// [`MnemoEngine::with_encryption`]; without one, their writes fail.
+    pub fn with_org_keys(mut self, registry: Arc<KeyRegistry>) -> Self {
+        self.org_keys = Some(registry);
+        self
+    }
+
+    /// Whether memory content is encrypted at rest.
+    pub fn encrypts_content(&self) -> bool {
+        self.encryption.is_some() || self.org_keys.is_some()
+    }
+
+    /// Seal `plaintext` for a memory of `org_id`: under the org's key if
+    /// it has one, else under the engine-wide key. Returned as is when
+    /// encryption is off.
+    pub(crate) fn encrypt_content(&self, org_id: Option<&str>, plaintext: &str) -> Result<String> {
+        if let Some(sealed) = self
+            .org_keys
+            .as_ref()
+            .and_then(|keys| keys.seal(org_id, plaintext.as_bytes()))
+        {
+            return sealed;
+        }
+        match self.encryption {
+            Some(ref enc) => Ok(base64::Engine::encode(
+                &base64::engine::general_purpose::STANDARD,
+                enc.encrypt(plaintext.as_bytes())?,
+            )),
+            None if self.org_keys.is_some() => Err(Error::Encryption(format!(
+                "no encryption key for org {}",
+                org_id.unwrap_or("<none>")
+            ))),
+            None => Ok(plaintext.to_string()),
+        }
+    }
+
+    /// Open content sealed by [`MnemoEngine::encrypt_content`], under
+    /// whichever key it names. Returned as is when encryption is off.
+    pub(crate) fn decrypt_content(&self, stored: &str) -> Result<String> {
+        if !self.encrypts_content() {
+            return Ok(stored.to_string());
+        }
+        let plain = match self.org_keys.as_ref().and_then(|keys| keys.open(stored)) {
+            Some(opened) => opened?,
+            None => match self.encryption {
+                Some(ref enc) => enc.decrypt(&crate::encryption::keys::decode(stored)?)?,
+                None => {
+                    return Err(Error::Encryption(
+                        "content sealed under the engine-wide key, which is not set".to_string(),
+                    ));
+                }
+            },
+        };
+        String::from_utf8(plain)
+            .map_err(|e| Error::Encryption(format!("decrypted content is not valid UTF-8: {e}")))
+    }
+
+    /// Decrypt the content of a record read straight from storage, as
+    /// recall does. A no-op when encryption is off.
+    pub fn decrypt_record(&self, record: &mut MemoryRecord) {
+        consolidate::decrypt_in_place(self, record);
+    }
+
     pub fn with_cold_storage(mut self, cs: Arc<dyn ColdStorage>) -> Self {
         self.cold_storage = Some(cs);
         self
@@ -254,6 +537,30 @@ impl MnemoEngine {
         self
     }
 
+    /// Attach an NLI [`ContradictionChecker`](crate::nli::ContradictionChecker)
+    /// so conflict detection also flags contradictions with low lexical
+    /// overlap ("prefers dark mode" vs "hates dark mode"). See [`crate::nli`].
+    pub fn with_contradiction_checker(
+        mut self,
+        checker: Arc<dyn crate::nli::ContradictionChecker>,
+    ) -> Self {
+        self.contradiction_checker = Some(checker);
+        self
+    }
+
+    /// Override when the contradiction checker runs and what it flags.
+    pub fn with_contradiction_policy(mut self, policy: crate::nli::ContradictionPolicy) -> Self {
+        self.contradiction_policy = policy;
+        self
+    }
+
+    /// Attach the [`ActivityTracker`](crate::activity::ActivityTracker)
+    /// shared with the idle-timeout watchdog.
+    pub fn with_activity_tracker(mut self, tracker: crate::activity::ActivityTracker) -> Self {
+        self.activity_tracker = Some(tracker);
+        self
+    }
+
     /// DocTrace (arXiv:2606.10921) — enable the experience-memory tier so
     /// [`remember_plan`](Self::remember_plan) caches successful plans and
     /// [`recall_plan`](Self::recall_plan) replays them on
@@ -265,19 +572,518 @@ impl MnemoEngine {
         self
     }
 
+    /// Hard-delete soft-deleted memories once they have been in the trash
+    /// for more than `days` days (applied by [`Self::run_trash_purge`]).
+    pub fn with_trash_retention_days(mut self, days: u32) -> Self {
+        self.trash_retention_days = Some(days);
+        self
+    }
+
+    /// Record who read each memory, when and with what query. See
+    /// [`access_log`].
+    pub fn with_access_log(mut self, policy: access_log::AccessLogPolicy) -> Self {
+        self.access_log = Some(policy);
+        self
+    }
+
+    /// Archive events older than the policy's hot window to cold storage
+    /// during cleanup. Needs [`Self::with_cold_storage`]. See
+    /// [`event_retention`].
+    pub fn with_event_retention(mut self, policy: event_retention::EventRetentionPolicy) -> Self {
+        self.event_retention = Some(policy);
+        self
+    }
+
+    /// Withhold uncorroborated shared-scope hits from non-owners and
+    /// optionally hide their authors. See [`privacy`].
+    pub fn with_shared_recall_privacy(mut self, privacy: privacy::SharedRecallPrivacy) -> Self {
+        self.shared_recall_privacy = Some(privacy);
+        self
+    }
+
+    /// Show aliases instead of agent ids in shared-scope recall hits of
+    /// other agents and in exports. See [`pseudonym`].
+    pub fn with_pseudonymizer(mut self, pseudonymizer: pseudonym::Pseudonymizer) -> Self {
+        self.pseudonymizer = Some(pseudonymizer);
+        self
+    }
+
+    /// Let agents mint share links signed with `signer`. See
+    /// [`share_link`].
+    pub fn with_share_links(mut self, signer: share_link::ShareLinkSigner) -> Self {
+        self.share_link_signer = Some(signer);
+        self
+    }
+
+    /// Run write anomaly passes under `policy`. See [`write_anomaly`].
+    pub fn with_write_anomaly_detection(
+        mut self,
+        policy: write_anomaly::WriteAnomalyPolicy,
+    ) -> Self {
+        self.write_anomaly = Some(Arc::new(write_anomaly::WriteAnomalyDetector::new(policy)));
+        self
+    }
+
+    /// Shadow a share of recalls with the alternate configurations in
+    /// `config`. Start the worker with [`shadow_recall::spawn_worker`].
+    pub fn with_shadow_recall(mut self, config: shadow_recall::ShadowRecallConfig) -> Self {
+        self.shadow_recall = Some(Arc::new(shadow_recall::ShadowRecall::new(config)));
+        self
+    }
+
+    /// Calibrate recall relevance under `config`. See [`calibration`].
+    pub fn with_score_calibration(mut self, config: calibration::CalibrationConfig) -> Self {
+        self.score_calibration = Arc::new(calibration::ScoreCalibrator::new(config));
+        self
+    }
+
+    /// Degrade recall step by step when its stages run over their
+    /// budgets. See [`latency_slo`].
+    pub fn with_latency_slo(mut self, config: latency_slo::LatencySloConfig) -> Self {
+        self.latency_slo = Some(Arc::new(latency_slo::LatencySlo::new(config)));
+        self
+    }
+
+    /// Skip, merge or link writes that are near-duplicates of an existing
+    /// memory. See [`dedup`].
+    pub fn with_dedup_policy(mut self, policy: dedup::DedupPolicy) -> Self {
+        self.dedup_policy = Some(policy);
+        self
+    }
+
+    /// Hold global-scope writes by non-curators for review. See
+    /// [`curation`].
+    pub fn with_curation(mut self, policy: curation::CurationPolicy) -> Self {
+        self.curation = Some(policy);
+        self
+    }
+
+    /// Keep memories with the policy's tags out of recall until reviewers
+    /// attest them. See [`attestation`].
+    pub fn with_attestation(mut self, policy: attestation::AttestationPolicy) -> Self {
+        self.attestation = Some(policy);
+        self
+    }
+
+    /// Refuse hard deletes and erasure of memories still inside the
+    /// policy's retention period. See [`retention_lock`].
+    pub fn with_retention_lock(mut self, policy: retention_lock::RetentionLockPolicy) -> Self {
+        self.retention_lock = Some(policy);
+        self
+    }
+
+    /// Screen every `remember` with `filter`. See [`ingestion_filter`].
+    pub fn with_ingestion_filter(mut self, filter: ingestion_filter::IngestionFilter) -> Self {
+        self.ingestion_filter = Some(filter);
+        self
+    }
+
+    /// Store a full checkpoint state every `interval` checkpoints and deltas
+    /// in between; `1` (or `0`) turns deltas off. See [`checkpoint`].
+    pub fn with_checkpoint_snapshot_interval(mut self, interval: u32) -> Self {
+        self.checkpoint_snapshot_interval = interval.max(1);
+        self
+    }
+
+    /// Replace the default request size limits. See [`limits`].
+    pub fn with_limits(self, limits: limits::LimitsConfig) -> Self {
+        self.limits.store(limits);
+        self
+    }
+
+    /// Checkpoint threads automatically per agent policy. See
+    /// [`auto_checkpoint`].
+    pub fn with_auto_checkpoint(self, config: auto_checkpoint::AutoCheckpointConfig) -> Self {
+        self.auto_checkpoint.store(config);
+        self
+    }
+
+    /// Export memories on the given schedules. See [`export`].
+    pub fn with_exports(self, schedules: Vec<export::ExportSchedule>) -> Self {
+        self.exports.store(schedules);
+        self
+    }
+
+    /// The request size limits in effect.
+    pub fn limits(&self) -> Arc<limits::LimitsConfig> {
+        self.limits.load()
+    }
+
+    /// The automatic checkpoint policies in effect.
+    pub fn auto_checkpoint(&self) -> Arc<auto_checkpoint::AutoCheckpointConfig> {
+        self.auto_checkpoint.load()
+    }
+
+    /// The export schedules in effect.
+    pub fn exports(&self) -> Arc<Vec<export::ExportSchedule>> {
+        self.exports.load()
+    }
+
+    /// Apply `next` over `running`, the config this engine runs with: the
+    /// reloadable settings take effect at once and every change is logged.
+    /// See [`reload`].
+    pub fn reload_config(
+        &self,
+        running: &mut crate::config::MnemoEngineConfig,
+        next: &crate::config::MnemoEngineConfig,
+    ) -> Result<reload::ConfigReload> {
+        reload::execute(self, running, next)
+    }
+
+    /// Summarize with `summarizer` (typically LLM-backed) when compressing
+    /// old threads. See [`compression`].
+    pub fn with_summarizer(mut self, summarizer: Arc<dyn crate::summarize::Summarizer>) -> Self {
+        self.summarizer = Some(summarizer);
+        self
+    }
+
+    /// Paraphrase recall queries with `expander` (typically LLM-backed)
+    /// when a request sets `expand_query`. See [`crate::expansion`].
+    pub fn with_query_expander(
+        mut self,
+        expander: Arc<dyn crate::expansion::QueryExpander>,
+    ) -> Self {
+        self.query_expander = Some(expander);
+        self
+    }
+
+    /// Write a [`MEMORY_WRITE_TOPIC`](outbox::MEMORY_WRITE_TOPIC) outbox
+    /// message with every new memory. Run an
+    /// [`OutboxDispatcher`](outbox::OutboxDispatcher) to deliver them.
+    pub fn with_outbox(mut self) -> Self {
+        self.outbox_enabled = true;
+        self
+    }
+
+    /// Register a pipeline [`Hook`](crate::hooks::Hook). See [`crate::hooks`]
+    /// for ordering and error handling.
+    pub fn with_hook(
+        mut self,
+        hook: Arc<dyn crate::hooks::Hook>,
+        options: crate::hooks::HookOptions,
+    ) -> Self {
+        self.hooks.register(hook, options);
+        self
+    }
+
+    /// Ingest documents of `format` with `loader`, replacing any loader
+    /// registered for it. See [`crate::ingest`].
+    pub fn with_document_loader(
+        mut self,
+        format: impl Into<String>,
+        loader: Arc<dyn crate::ingest::DocumentLoader>,
+    ) -> Self {
+        self.document_loaders.register(format, loader);
+        self
+    }
+
+    /// Let URL ingestion reach the hosts `policy` allows. See
+    /// [`crate::ingest::UrlFetchPolicy`].
+    pub fn with_url_fetch_policy(mut self, policy: crate::ingest::UrlFetchPolicy) -> Self {
+        self.url_fetch = policy;
+        self
+    }
+
+    /// Emit a `MemoryExpiring` event from the TTL sweep once a memory is
+    /// within `hours` of its `expires_at`.
+    pub fn with_expiry_warning_hours(mut self, hours: u32) -> Self {
+        self.expiry_warning_hours = Some(hours);
+        self
+    }
+
+    /// Start in read-only mode. See [`MnemoEngine::set_read_only`].
+    pub fn with_read_only(self, read_only: bool) -> Self {
+        self.set_read_only(read_only);
+        self
+    }
+
+    /// Freeze or unfreeze writes. While frozen, `remember`, `forget`,
+    /// `share`, `checkpoint`, `branch`, `merge` and the other write
+    /// operations return [`Error::ReadOnly`]; recall and replay are
+    /// unaffected. Background passes (TTL sweep, trash purge, decay,
+    /// consolidation) are frozen too. Revoking a share link still works,
+    /// so a leaked link can be shut off during an incident.
+    pub fn set_read_only(&self, read_only: bool) {
+        self.read_only.store(read_only, Ordering::SeqCst);
+        tracing::info!(read_only, "engine write mode changed");
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.load(Ordering::SeqCst)
+    }
+
+    fn ensure_writable(&self, operation: &str) -> Result<()> {
+        if self.is_read_only() {
+            return Err(Error::ReadOnly(format!(
+                "{operation} is disabled while the engine is read-only"
+            )));
+        }
+        self.write_activity.record_write();
+        Ok(())
+    }
+
+    /// The agent a request acts as: its `agent_id`, else the default.
+    fn acting_agent(&self, agent_id: Option<&str>) -> String {
+        agent_id.unwrap_or(&self.default_agent_id).to_string()
+    }
+
     pub async fn remember(
         &self,
-        request: remember::RememberRequest,
+        mut request: remember::RememberRequest,
+    ) -> Result<remember::RememberResponse> {
+        self.ensure_writable("remember")?;
+        let agent = self.acting_agent(request.agent_id.as_deref());
+        if let Some(ref detector) = self.write_anomaly {
+            detector.check_throttle(&agent)?;
+        }
+        crate::storage::with_agent_context(agent, async {
+            if self.hooks.is_empty() {
+                return remember::execute(self, request).await;
+            }
+            self.hooks.pre_remember(&mut request).await?;
+            let response = remember::execute(self, request.clone()).await?;
+            self.hooks.post_remember(&request, &response).await?;
+            Ok(response)
+        })
+        .await
+    }
+
+    /// Remember many memories with one batched embedding call; each is
+    /// otherwise stored as by [`remember`](Self::remember). See
+    /// [`remember::execute_batch`].
+    pub async fn remember_batch(
+        &self,
+        mut requests: Vec<remember::RememberRequest>,
+    ) -> Result<Vec<remember::RememberResponse>> {
+        self.ensure_writable("remember_batch")?;
+        for request in &mut requests {
+            let agent = self.acting_agent(request.agent_id.as_deref());
+            if let Some(ref detector) = self.write_anomaly {
+                detector.check_throttle(&agent)?;
+            }
+            if !self.hooks.is_empty() {
+                crate::storage::with_agent_context(agent, self.hooks.pre_remember(request)).await?;
+            }
+        }
+        if self.hooks.is_empty() {
+            return remember::execute_batch(self, requests).await;
+        }
+        let responses = remember::execute_batch(self, requests.clone()).await?;
+        for (request, response) in requests.iter().zip(&responses) {
+            let agent = self.acting_agent(request.agent_id.as_deref());
+            crate::storage::with_agent_context(agent, self.hooks.post_remember(request, response))
+                .await?;
+        }
+        Ok(responses)
+    }
+
+    /// Chunk documents and remember each chunk with its provenance. See
+    /// [`crate::ingest`].
+    pub async fn ingest_documents(
+        &self,
+        request: crate::ingest::IngestRequest,
+    ) -> Result<crate::ingest::IngestResponse> {
+        self.ensure_writable("ingest_documents")?;
+        crate::ingest::execute(self, request).await
+    }
+
+    /// Fetch a web page and remember its main content, diffing against the
+    /// last fetch of the same URL. See [`crate::ingest::url`].
+    pub async fn ingest_url(
+        &self,
+        request: crate::ingest::url::UrlIngestRequest,
+    ) -> Result<crate::ingest::url::UrlIngestResponse> {
+        self.ensure_writable("ingest_url")?;
+        crate::ingest::url::execute(self, request).await
+    }
+
+    /// Re-fetch the ingested pages whose refresh interval has passed. Run
+    /// periodically; see [`crate::ingest::url`].
+    pub async fn run_url_refresh(&self) -> Result<crate::ingest::url::UrlRefreshReport> {
+        self.ensure_writable("run_url_refresh")?;
+        crate::ingest::url::run_url_refresh(self, chrono::Utc::now()).await
+    }
+
+    /// Every page ingested with [`ingest_url`](Self::ingest_url).
+    pub async fn watched_pages(&self) -> Result<Vec<crate::ingest::url::WatchedPage>> {
+        crate::ingest::url::watched_pages(self).await
+    }
+
+    /// Re-embed an agent's memories with the current provider. See
+    /// [`reembed`].
+    pub async fn reembed(
+        &self,
+        request: reembed::ReembedRequest,
+    ) -> Result<reembed::ReembedResponse> {
+        self.ensure_writable("reembed")?;
+        reembed::execute(self, request).await
+    }
+
+    /// Remember a scratchpad result. See [`ephemeral`].
+    pub async fn remember_ephemeral(
+        &self,
+        request: ephemeral::EphemeralRequest,
     ) -> Result<remember::RememberResponse> {
-        remember::execute(self, request).await
+        self.ensure_writable("remember_ephemeral")?;
+        let mut request = request.into_remember()?;
+        let agent = self.acting_agent(request.agent_id.as_deref());
+        crate::storage::with_agent_context(agent, async {
+            if self.hooks.is_empty() {
+                return ephemeral::execute(self, request).await;
+            }
+            self.hooks.pre_remember(&mut request).await?;
+            let response = ephemeral::execute(self, request.clone()).await?;
+            self.hooks.post_remember(&request, &response).await?;
+            Ok(response)
+        })
+        .await
+    }
+
+    pub async fn recall(
+        &self,
+        mut request: recall::RecallRequest,
+    ) -> Result<recall::RecallResponse> {
+        let agent = self.acting_agent(request.agent_id.as_deref());
+        let shadowed = self
+            .shadow_recall
+            .as_ref()
+            .filter(|shadow| shadow.sample())
+            .map(|shadow| (shadow, request.clone()));
+        let response = crate::storage::with_agent_context(agent.clone(), async {
+            if self.hooks.is_empty() {
+                return recall::execute(self, request).await;
+            }
+            self.hooks.pre_recall(&mut request).await?;
+            let mut response = recall::execute(self, request.clone()).await?;
+            self.hooks.post_recall(&request, &mut response).await?;
+            Ok(response)
+        })
+        .await?;
+        if let Some((shadow, request)) = shadowed {
+            shadow.submit(agent, request, &response);
+        }
+        Ok(response)
+    }
+
+    /// Snapshot the vector index for a replica to restore. See
+    /// [`crate::index::snapshot`].
+    pub fn index_snapshot(&self) -> Result<crate::index::snapshot::IndexSnapshot> {
+        crate::index::snapshot::IndexSnapshot::capture(
+            self.index.as_ref(),
+            self.embedding.dimensions(),
+        )
+    }
+
+    /// Replace the vector index with `snapshot`. Catch up afterwards with
+    /// [`index_delta`](Self::index_delta) from its watermark.
+    pub fn restore_index_snapshot(
+        &self,
+        snapshot: &crate::index::snapshot::IndexSnapshot,
+    ) -> Result<()> {
+        self.ensure_writable("restore_index_snapshot")?;
+        snapshot.restore(self.index.as_ref(), self.embedding.dimensions())
+    }
+
+    /// Vector changes of up to `limit` memories updated after `since`.
+    pub async fn index_delta(
+        &self,
+        since: &str,
+        limit: usize,
+    ) -> Result<crate::index::snapshot::IndexDelta> {
+        crate::index::snapshot::IndexDelta::since(self.storage.as_ref(), since, limit).await
+    }
+
+    /// Fetch several memories by id, e.g. the content of hits recalled
+    /// with a `fields` projection. See [`get_batch`].
+    pub async fn get_batch(
+        &self,
+        request: get_batch::GetBatchRequest,
+    ) -> Result<get_batch::GetBatchResponse> {
+        let agent = self.acting_agent(request.agent_id.as_deref());
+        crate::storage::with_agent_context(agent, get_batch::execute(self, request)).await
+    }
+
+    /// The memories closest to a stored one, by its own embedding. See
+    /// [`similar`].
+    pub async fn similar(
+        &self,
+        request: similar::SimilarRequest,
+    ) -> Result<similar::SimilarResponse> {
+        let agent = self.acting_agent(request.agent_id.as_deref());
+        crate::storage::with_agent_context(agent, similar::similar(self, request)).await
+    }
+
+    /// Group an agent's memories into at most `k` topics; see
+    /// [`clustering`].
+    pub async fn cluster_memories(
+        &self,
+        agent_id: Option<String>,
+        k: usize,
+    ) -> Result<clustering::TopicMap> {
+        clustering::cluster_memories(self, agent_id, k).await
+    }
+
+    /// Compare an agent's memories with a ground-truth corpus and report
+    /// the stale and contradicting ones; see [`drift`].
+    pub async fn detect_drift(&self, request: drift::DriftRequest) -> Result<drift::DriftReport> {
+        drift::detect_drift(self, request).await
+    }
+
+    /// Per-agent content size in storage and the bytes compression saves;
+    /// `Some(agent_id)` reports only that agent. See
+    /// [`content_codec`](crate::storage::content_codec).
+    pub async fn content_storage_stats(
+        &self,
+        agent_id: Option<String>,
+    ) -> Result<Vec<crate::model::content_stats::ContentStorageStats>> {
+        if let Some(ref agent_id) = agent_id {
+            validate_agent_id(agent_id)?;
+        }
+        self.storage
+            .content_storage_stats(agent_id.as_deref())
+            .await
+    }
+
+    /// Daily storage growth and when quotas will be reached at the current
+    /// rate; see [`growth`].
+    pub async fn storage_growth(
+        &self,
+        request: growth::GrowthRequest,
+    ) -> Result<growth::GrowthReport> {
+        growth::execute(self, request).await
+    }
+
+    /// Resolve a pseudonym in `org_id`'s alias table to the agent id it
+    /// stands for. Requires the pseudonym key; see [`pseudonym`].
+    pub async fn reveal_pseudonym(
+        &self,
+        org_id: Option<&str>,
+        alias: &str,
+        key_hex: &str,
+    ) -> Result<String> {
+        pseudonym::reveal(self, org_id, alias, key_hex).await
     }
 
-    pub async fn recall(&self, request: recall::RecallRequest) -> Result<recall::RecallResponse> {
-        recall::execute(self, request).await
+    /// Replace the agent ids of a record `reader` fetched by id with their
+    /// aliases when a recall by `reader` would have shown it aliased. A
+    /// no-op without a pseudonymizer; see [`pseudonym`].
+    pub async fn alias_for_reader(&self, reader: &str, record: &mut MemoryRecord) -> Result<()> {
+        pseudonym::alias_for_reader(self, reader, record).await
     }
 
-    pub async fn forget(&self, request: forget::ForgetRequest) -> Result<forget::ForgetResponse> {
-        forget::execute(self, request).await
+    pub async fn forget(
+        &self,
+        mut request: forget::ForgetRequest,
+    ) -> Result<forget::ForgetResponse> {
+        if !request.dry_run.unwrap_or(false) {
+            self.ensure_writable("forget")?;
+        }
+        let agent = self.acting_agent(request.agent_id.as_deref());
+        crate::storage::with_agent_context(agent, async {
+            self.hooks.pre_forget(&mut request).await?;
+            forget::execute(self, request).await
+        })
+        .await
     }
 
     /// Subject-scoped erasure for GDPR / DPDPA compliance.
@@ -286,15 +1092,169 @@ impl MnemoEngine {
         &self,
         request: forget::ForgetSubjectRequest,
     ) -> Result<forget::ForgetSubjectResponse> {
+        if !request.dry_run.unwrap_or(false) {
+            self.ensure_writable("forget_subject")?;
+        }
         forget::forget_subject(self, request).await
     }
 
     /// Hard-delete every memory whose `expires_at` is in the past and emit
     /// one `MemoryExpired` audit event per deletion.
     pub async fn run_ttl_sweep(&self) -> Result<lifecycle::TtlReport> {
+        self.ensure_writable("run_ttl_sweep")?;
         lifecycle::run_ttl_sweep(self).await
     }
 
+    /// Push a memory's `expires_at` further into the future.
+    pub async fn extend_ttl(
+        &self,
+        request: lifecycle::ExtendTtlRequest,
+    ) -> Result<lifecycle::ExtendTtlResponse> {
+        self.ensure_writable("extend_ttl")?;
+        lifecycle::extend_ttl(self, request).await
+    }
+
+    /// Restore a soft-deleted memory and re-index it.
+    pub async fn undelete(
+        &self,
+        request: trash::UndeleteRequest,
+    ) -> Result<trash::UndeleteResponse> {
+        self.ensure_writable("undelete")?;
+        trash::undelete(self, request).await
+    }
+
+    /// An agent's memories and the relations between them, for export to
+    /// graph tools; see [`graph_export`].
+    pub async fn export_graph(
+        &self,
+        request: graph_export::GraphExportRequest,
+    ) -> Result<graph_export::GraphExport> {
+        graph_export::execute(self, request).await
+    }
+
+    /// List an agent's soft-deleted memories, most recently deleted first.
+    pub async fn list_trash(&self, request: trash::TrashRequest) -> Result<trash::TrashResponse> {
+        trash::list_trash(self, request).await
+    }
+
+    /// List the logged reads of one memory, newest first. Needs `Admin`
+    /// permission on the memory.
+    pub async fn list_memory_accesses(
+        &self,
+        request: access_log::AccessLogRequest,
+    ) -> Result<access_log::AccessLogResponse> {
+        access_log::list(self, request).await
+    }
+
+    /// Log a direct read of `memory_ids` by `principal` (front-ends that
+    /// fetch records from storage themselves call this). A no-op without
+    /// an access-log policy.
+    pub async fn record_memory_access(
+        &self,
+        principal: &str,
+        operation: crate::model::access_log::AccessOperation,
+        memory_ids: &[uuid::Uuid],
+    ) {
+        access_log::record(self, principal, operation, None, memory_ids).await
+    }
+
+    /// Move events past the retention policy's hot window to cold storage.
+    /// A no-op without an event-retention policy.
+    pub async fn archive_events(&self) -> Result<event_retention::EventArchiveReport> {
+        self.ensure_writable("archive_events")?;
+        event_retention::archive(self).await
+    }
+
+    /// Archived event segments, optionally for one agent, oldest first.
+    pub async fn list_event_archives(
+        &self,
+        agent_id: Option<String>,
+    ) -> Result<Vec<crate::model::event_archive::EventArchiveSegment>> {
+        event_retention::list(self, agent_id.as_deref()).await
+    }
+
+    /// Read an archived event segment back from cold storage and verify it
+    /// against its manifest.
+    pub async fn restore_event_archive(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<event_retention::RestoredEventSegment> {
+        self.ensure_writable("restore_event_archive")?;
+        event_retention::restore(self, id).await
+    }
+
+    /// Probe storage, the vector index, the embedding provider and any
+    /// optional tiers. See [`health::readiness`].
+    pub async fn readiness(&self) -> health::HealthReport {
+        health::readiness(self).await
+    }
+
+    /// Per-agent default retrieval settings used when a recall leaves the
+    /// matching fields unset.
+    pub async fn agent_retrieval_defaults(&self, agent_id: &str) -> Result<RetrievalDefaults> {
+        agent_settings::get(self, agent_id).await
+    }
+
+    /// Replace an agent's default retrieval settings.
+    pub async fn set_agent_retrieval_defaults(
+        &self,
+        agent_id: &str,
+        defaults: RetrievalDefaults,
+    ) -> Result<RetrievalDefaults> {
+        self.ensure_writable("set_agent_retrieval_defaults")?;
+        agent_settings::update(self, agent_id, defaults).await
+    }
+
+    /// Create or replace a saved recall profile.
+    pub async fn save_recall_profile(
+        &self,
+        request: recall_profile::SaveRecallProfileRequest,
+    ) -> Result<RecallProfile> {
+        self.ensure_writable("save_recall_profile")?;
+        recall_profile::save(self, request).await
+    }
+
+    /// Fetch a saved recall profile, including its usage stats.
+    pub async fn get_recall_profile(
+        &self,
+        agent_id: Option<String>,
+        name: &str,
+    ) -> Result<RecallProfile> {
+        let agent_id = agent_id.unwrap_or_else(|| self.default_agent_id.clone());
+        recall_profile::get(self, &agent_id, name).await
+    }
+
+    /// List an agent's saved recall profiles, ordered by name.
+    pub async fn list_recall_profiles(
+        &self,
+        agent_id: Option<String>,
+    ) -> Result<Vec<RecallProfile>> {
+        let agent_id = agent_id.unwrap_or_else(|| self.default_agent_id.clone());
+        recall_profile::list(self, &agent_id).await
+    }
+
+    /// Delete a saved recall profile.
+    pub async fn delete_recall_profile(&self, agent_id: Option<String>, name: &str) -> Result<()> {
+        self.ensure_writable("delete_recall_profile")?;
+        let agent_id = agent_id.unwrap_or_else(|| self.default_agent_id.clone());
+        recall_profile::delete(self, &agent_id, name).await
+    }
+
+    /// Hard-delete soft-deleted memories older than the configured
+    /// [`trash_retention_days`](Self::trash_retention_days). A no-op when no
+    /// retention is configured.
+    pub async fn run_trash_purge(&self) -> Result<trash::PurgeReport> {
+        self.ensure_writable("run_trash_purge")?;
+        self.trash_purge(false).await
+    }
+
+    async fn trash_purge(&self, dry_run: bool) -> Result<trash::PurgeReport> {
+        match self.trash_retention_days {
+            Some(days) => trash::purge_pass(self, days, dry_run).await,
+            None => Ok(trash::PurgeReport::new(0, Vec::new())),
+        }
+    }
+
     /// Auto-Dream-compatible reflection pass: date absolutization, external
     /// rewrite acceptance, semantic dedup, low-importance conflict
     /// resolution, and stale archival. See [`reflection::run_reflection_pass`].
@@ -302,6 +1262,7 @@ impl MnemoEngine {
         &self,
         agent_id: Option<String>,
     ) -> Result<reflection::ReflectionReport> {
+        self.ensure_writable("run_reflection_pass")?;
         let agent_id = agent_id.unwrap_or_else(|| self.default_agent_id.clone());
         reflection::run_reflection_pass(self, &agent_id).await
     }
@@ -314,6 +1275,7 @@ impl MnemoEngine {
         mode: reflection::ReflectionMode,
         force: bool,
     ) -> Result<reflection::ReflectionReport> {
+        self.ensure_writable("run_reflection_pass")?;
         let agent_id = agent_id.unwrap_or_else(|| self.default_agent_id.clone());
         reflection::run_reflection_pass_with_mode(self, &agent_id, mode, force).await
     }
@@ -329,22 +1291,146 @@ impl MnemoEngine {
         poisoning::replay_quarantine(self, &agent_id, since).await
     }
 
+    /// Update the importance, tags or metadata of an existing memory,
+    /// optionally only if it is still at an expected version. See
+    /// [`update`].
+    pub async fn update(&self, request: update::UpdateRequest) -> Result<update::UpdateResponse> {
+        self.ensure_writable("update")?;
+        update::execute(self, request).await
+    }
+
+    /// Pin or unpin an existing memory. See [`pin`].
+    pub async fn pin(&self, request: pin::PinRequest) -> Result<pin::PinResponse> {
+        self.ensure_writable("pin")?;
+        pin::execute(self, request).await
+    }
+
+    /// Replace the fact validity window of an existing memory. See
+    /// [`validity`].
+    pub async fn set_validity(
+        &self,
+        request: validity::SetValidityRequest,
+    ) -> Result<validity::SetValidityResponse> {
+        self.ensure_writable("set_validity")?;
+        validity::execute(self, request).await
+    }
+
+    /// Approve or reject a pending global memory. See [`curation`].
+    pub async fn review_curation(
+        &self,
+        request: curation::CurationReviewRequest,
+    ) -> Result<curation::CurationReviewResponse> {
+        self.ensure_writable("review_curation")?;
+        curation::review(self, request).await
+    }
+
+    /// Pending global memories `agent_id` may review, oldest first.
+    pub async fn list_pending_curation(
+        &self,
+        agent_id: Option<String>,
+        limit: usize,
+    ) -> Result<Vec<curation::PendingMemory>> {
+        curation::list_pending(self, agent_id, limit).await
+    }
+
+    /// Approve or reject a memory held for attestation. See [`attestation`].
+    pub async fn attest(
+        &self,
+        request: attestation::AttestRequest,
+    ) -> Result<attestation::AttestResponse> {
+        self.ensure_writable("attest")?;
+        attestation::attest(self, request).await
+    }
+
+    /// Memories awaiting attestation that `agent_id` may review, oldest
+    /// first.
+    pub async fn list_pending_attestations(
+        &self,
+        agent_id: Option<String>,
+        limit: usize,
+    ) -> Result<Vec<attestation::PendingAttestation>> {
+        attestation::list_pending(self, agent_id, limit).await
+    }
+
+    /// Share one memory, a list, or every memory matching a filter. See
+    /// [`share`].
     pub async fn share(&self, request: share::ShareRequest) -> Result<share::ShareResponse> {
-        share::execute(self, request).await
+        if !request.dry_run.unwrap_or(false) {
+            self.ensure_writable("share")?;
+        }
+        let agent = self.acting_agent(request.agent_id.as_deref());
+        crate::storage::with_agent_context(agent, share::execute(self, request)).await
+    }
+
+    /// Mint a link granting read access to one memory until it expires.
+    /// See [`share_link`].
+    pub async fn create_share_link(
+        &self,
+        request: share_link::CreateShareLinkRequest,
+    ) -> Result<share_link::ShareLink> {
+        self.ensure_writable("create_share_link")?;
+        share_link::create(self, request).await
+    }
+
+    /// The claims of a share link token, if it is genuine, unexpired and
+    /// not revoked.
+    pub async fn verify_share_link(&self, token: &str) -> Result<share_link::ShareLinkClaims> {
+        share_link::verify(self, token).await
+    }
+
+    /// Revoke a share link. Allowed while read-only: cutting off a leaked
+    /// link is incident response.
+    pub async fn revoke_share_link(
+        &self,
+        request: share_link::RevokeShareLinkRequest,
+    ) -> Result<share_link::RevokeShareLinkResponse> {
+        share_link::revoke(self, request).await
     }
 
     pub async fn checkpoint(
         &self,
         request: checkpoint::CheckpointRequest,
     ) -> Result<checkpoint::CheckpointResponse> {
+        self.ensure_writable("checkpoint")?;
         checkpoint::execute(self, request).await
     }
 
+    /// Checkpoint threads idle past their agent's `inactivity_seconds`. Run
+    /// periodically; see [`auto_checkpoint`].
+    pub async fn run_auto_checkpoints(&self) -> Result<auto_checkpoint::AutoCheckpointReport> {
+        self.ensure_writable("run_auto_checkpoints")?;
+        auto_checkpoint::run_auto_checkpoints(self).await
+    }
+
+    /// Run the export schedules that came due since their last run. Run
+    /// periodically; see [`export`].
+    pub async fn run_scheduled_exports(&self) -> Result<Vec<export::ExportRun>> {
+        self.ensure_writable("run_scheduled_exports")?;
+        export::run_scheduled_exports(self, chrono::Utc::now()).await
+    }
+
+    /// Run the export schedule called `name` immediately.
+    pub async fn run_export(&self, name: &str) -> Result<export::ExportRun> {
+        self.ensure_writable("run_export")?;
+        export::run_export(self, name).await
+    }
+
+    /// Recorded export runs, newest first, optionally of one schedule.
+    pub async fn list_export_runs(
+        &self,
+        schedule: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<export::ExportRun>> {
+        export::list_export_runs(self, schedule, limit).await
+    }
+
     pub async fn branch(&self, request: branch::BranchRequest) -> Result<branch::BranchResponse> {
+        self.ensure_writable("branch")?;
         branch::execute(self, request).await
     }
 
     pub async fn merge(&self, request: merge::MergeRequest) -> Result<merge::MergeResponse> {
+        self.ensure_writable("merge")?;
         merge::execute(self, request).await
     }
 
@@ -352,6 +1438,16 @@ impl MnemoEngine {
         replay::execute(self, request).await
     }
 
+    /// Load a thread's likely recall hits into the cache and vector index
+    /// ahead of its first recall; see [`prefetch`].
+    pub async fn prefetch(
+        &self,
+        request: prefetch::PrefetchRequest,
+    ) -> Result<prefetch::PrefetchResponse> {
+        let agent = self.acting_agent(request.agent_id.as_deref());
+        crate::storage::with_agent_context(agent, prefetch::execute(self, request)).await
+    }
+
     /// `CONSOLIDATE` (Infini-Memory, arXiv:2606.10677) — group a caller-chosen
     /// set of member memories into one revisable topic document, preserving
     /// provenance and the hash-chained audit history. See [`consolidate`].
@@ -359,6 +1455,7 @@ impl MnemoEngine {
         &self,
         request: consolidate::ConsolidateRequest,
     ) -> Result<consolidate::ConsolidateResponse> {
+        self.ensure_writable("consolidate")?;
         consolidate::execute(self, request).await
     }
 
@@ -371,6 +1468,7 @@ impl MnemoEngine {
         &self,
         request: experience::RememberPlanRequest,
     ) -> Result<experience::RememberPlanResponse> {
+        self.ensure_writable("remember_plan")?;
         experience::execute_remember_plan(self, request).await
     }
 
@@ -385,12 +1483,69 @@ impl MnemoEngine {
         experience::execute_recall_plan(self, request).await
     }
 
+    /// Capture one prompt/response turn: user/tool/assistant events linked
+    /// by `parent_event_id`, an episodic memory of the turn, and optional
+    /// fact extraction. See [`turn`].
+    pub async fn capture_turn(&self, request: turn::TurnRequest) -> Result<turn::TurnResponse> {
+        self.ensure_writable("capture_turn")?;
+        turn::execute(self, request).await
+    }
+
+    /// Append a message to a thread's message store, capturing the turn
+    /// when it answers a user message. See [`thread_message`].
+    pub async fn create_thread_message(
+        &self,
+        request: thread_message::CreateMessageRequest,
+    ) -> Result<thread_message::CreateMessageResponse> {
+        self.ensure_writable("create_thread_message")?;
+        let agent = self.acting_agent(request.agent_id.as_deref());
+        crate::storage::with_agent_context(agent, thread_message::create(self, request)).await
+    }
+
+    /// A page of a thread's messages, oldest first unless asked otherwise.
+    pub async fn list_thread_messages(
+        &self,
+        request: thread_message::ListMessagesRequest,
+    ) -> Result<thread_message::ListMessagesResponse> {
+        let agent = self.acting_agent(request.agent_id.as_deref());
+        crate::storage::with_agent_context(agent, thread_message::list(self, request)).await
+    }
+
+    /// Remove a message from a thread's listings.
+    pub async fn delete_thread_message(
+        &self,
+        request: thread_message::DeleteMessageRequest,
+    ) -> Result<thread_message::DeleteMessageResponse> {
+        self.ensure_writable("delete_thread_message")?;
+        let agent = self.acting_agent(request.agent_id.as_deref());
+        crate::storage::with_agent_context(agent, thread_message::delete(self, request)).await
+    }
+
+    /// Archive or forget memories whose effective importance has decayed
+    /// below the thresholds. Rejects thresholds outside `[0, 1]` or a forget
+    /// threshold above the archive threshold.
     pub async fn run_decay_pass(
         &self,
         agent_id: Option<String>,
         archive_threshold: f32,
         forget_threshold: f32,
     ) -> Result<lifecycle::DecayPassResult> {
+        self.ensure_writable("run_decay_pass")?;
+        for (name, value) in [
+            ("archive_threshold", archive_threshold),
+            ("forget_threshold", forget_threshold),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(Error::Validation(format!(
+                    "{name} must be in [0, 1], got {value}"
+                )));
+            }
+        }
+        if forget_threshold > archive_threshold {
+            return Err(Error::Validation(format!(
+                "forget_threshold ({forget_threshold}) must not exceed archive_threshold ({archive_threshold})"
+            )));
+        }
         let agent_id = agent_id.unwrap_or_else(|| self.default_agent_id.clone());
         lifecycle::run_decay_pass(self, &agent_id, archive_threshold, forget_threshold).await
     }
@@ -400,21 +1555,190 @@ impl MnemoEngine {
         agent_id: Option<String>,
         min_cluster_size: usize,
     ) -> Result<lifecycle::ConsolidationResult> {
+        self.ensure_writable("run_consolidation")?;
         let agent_id = agent_id.unwrap_or_else(|| self.default_agent_id.clone());
         lifecycle::run_consolidation(self, &agent_id, min_cluster_size).await
     }
 
+    /// Replace old episodic memories of each thread with a few semantic
+    /// summaries, archiving the originals. See [`compression`].
+    pub async fn run_compression(
+        &self,
+        request: compression::CompressionRequest,
+    ) -> Result<compression::CompressionReport> {
+        self.ensure_writable("run_compression")?;
+        compression::run_compression(self, request).await
+    }
+
+    /// Create or refresh one summary memory per frequently mentioned
+    /// entity. See [`entity_summary`].
+    pub async fn run_entity_summaries(
+        &self,
+        request: entity_summary::EntitySummaryRequest,
+    ) -> Result<entity_summary::EntitySummaryReport> {
+        self.ensure_writable("run_entity_summaries")?;
+        entity_summary::run_entity_summaries(self, request).await
+    }
+
+    /// The current summary of `entity` among `agent_id`'s memories.
+    pub async fn entity_summary(
+        &self,
+        agent_id: Option<String>,
+        entity: &str,
+    ) -> Result<entity_summary::EntitySummary> {
+        entity_summary::get_summary(self, agent_id, entity).await
+    }
+
+    /// Bring the cache and indexes up to date with a write made by another
+    /// process. See [`change_feed`]. Allowed while read-only: it only
+    /// refreshes local state from storage.
+    pub async fn apply_memory_change(&self, change: change_feed::MemoryChange) -> Result<()> {
+        change_feed::apply_change(self, change).await
+    }
+
+    /// Run a TTL sweep, a trash purge, access-log pruning and event
+    /// archival — the maintenance the CLI's background sweeper performs —
+    /// and report all four.
+    pub async fn run_cleanup(&self) -> Result<lifecycle::CleanupResult> {
+        self.run_cleanup_with(lifecycle::CleanupRequest::new())
+            .await
+    }
+
+    /// [`run_cleanup`](Self::run_cleanup), or with `dry_run` a report of
+    /// what it would delete and archive.
+    pub async fn run_cleanup_with(
+        &self,
+        request: lifecycle::CleanupRequest,
+    ) -> Result<lifecycle::CleanupResult> {
+        let dry_run = request.dry_run.unwrap_or(false);
+        if !dry_run {
+            self.ensure_writable("run_cleanup")?;
+        }
+        let ttl = lifecycle::ttl_sweep(self, dry_run).await?;
+        let trash = self.trash_purge(dry_run).await?;
+        let mut result = lifecycle::CleanupResult::new(ttl, trash);
+        result.dry_run = dry_run;
+        if dry_run {
+            result.events_archived = event_retention::archivable(self).await?;
+        } else {
+            result.access_log_pruned = access_log::prune(self).await?;
+            result.events_archived = event_retention::archive(self).await?.events_archived;
+        }
+        Ok(result)
+    }
+
+    /// Reclaim storage space unless writes are in progress; see [`vacuum`].
+    pub async fn vacuum(&self, request: vacuum::VacuumRequest) -> Result<vacuum::VacuumResponse> {
+        vacuum::execute(self, request).await
+    }
+
+    /// Judge every recently active agent's writes against its baseline;
+    /// see [`write_anomaly`].
+    pub async fn run_write_anomaly_pass(&self) -> Result<write_anomaly::WriteAnomalyReport> {
+        self.ensure_writable("run_write_anomaly_pass")?;
+        write_anomaly::execute(self).await
+    }
+
+    /// Lift a write anomaly throttle before it runs out. Returns whether
+    /// `agent_id` was throttled.
+    pub fn release_write_throttle(&self, agent_id: &str) -> bool {
+        self.write_anomaly
+            .as_ref()
+            .is_some_and(|detector| detector.release(agent_id))
+    }
+
+    /// Mark memories an agent found useful after its recent recalls, to
+    /// score the shadow recall arms. Returns how many shadowed recalls it
+    /// applied to. See [`shadow_recall`].
+    pub fn shadow_recall_feedback(
+        &self,
+        agent_id: Option<String>,
+        memory_ids: &[uuid::Uuid],
+    ) -> Result<usize> {
+        let agent_id = self.acting_agent(agent_id.as_deref());
+        validate_agent_id(&agent_id)?;
+        Ok(self.shadow()?.feedback(&agent_id, memory_ids))
+    }
+
+    /// Mark the hits of an agent's recent recalls that proved useful; the
+    /// rest count as not useful. Returns how many recalls it labelled. See
+    /// [`calibration`].
+    pub fn recall_feedback(
+        &self,
+        agent_id: Option<String>,
+        memory_ids: &[uuid::Uuid],
+    ) -> Result<usize> {
+        let agent_id = self.acting_agent(agent_id.as_deref());
+        validate_agent_id(&agent_id)?;
+        Ok(self.score_calibration.feedback(&agent_id, memory_ids))
+    }
+
+    /// How each strategy's scores are currently calibrated.
+    pub fn score_calibration_report(&self) -> Vec<calibration::StrategyCalibration> {
+        self.score_calibration.report()
+    }
+
+    /// Shadow recall arms ranked against the primary configuration.
+    pub fn shadow_recall_report(&self) -> Result<shadow_recall::ShadowRecallReport> {
+        Ok(self.shadow()?.report())
+    }
+
+    /// The newest `limit` shadow recall comparisons, newest first.
+    pub fn shadow_recall_comparisons(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<shadow_recall::ShadowComparison>> {
+        Ok(self.shadow()?.comparisons(limit))
+    }
+
+    fn shadow(&self) -> Result<&shadow_recall::ShadowRecall> {
+        self.shadow_recall
+            .as_deref()
+            .ok_or_else(|| Error::Validation("shadow recall is not enabled".to_string()))
+    }
+
+    /// Verify an agent's (or one thread's) whole memory chain from the
+    /// start. See [`Self::verify_memory_chain`] for incremental runs.
     pub async fn verify_integrity(
         &self,
         agent_id: Option<String>,
         thread_id: Option<&str>,
     ) -> Result<crate::hash::ChainVerificationResult> {
-        let agent_id = agent_id.unwrap_or_else(|| self.default_agent_id.clone());
-        let records = self
-            .storage
-            .list_memories_by_agent_ordered(&agent_id, thread_id, 10000)
-            .await?;
-        Ok(crate::hash::verify_chain(&records))
+        let mut request = verify::VerifyRequest::new();
+        request.agent_id = agent_id;
+        request.thread_id = thread_id.map(str::to_string);
+        Ok(verify::execute(self, request).await?.result)
+    }
+
+    /// Recompute the content hashes and relink the `prev_hash` chains of an
+    /// agent's memories, e.g. after an import. See [`rechain`].
+    pub async fn rechain(
+        &self,
+        request: rechain::RechainRequest,
+    ) -> Result<rechain::RechainResponse> {
+        self.ensure_writable("rechain")?;
+        rechain::execute(self, request).await
+    }
+
+    /// Hand one agent's memories to another, by reassigning them or by
+    /// granting owner ACLs; see [`transfer`].
+    pub async fn transfer_ownership(
+        &self,
+        request: transfer::TransferRequest,
+    ) -> Result<transfer::TransferResponse> {
+        if !request.dry_run {
+            self.ensure_writable("transfer_ownership")?;
+        }
+        transfer::execute(self, request).await
+    }
+
+    /// Verify a memory chain in pages, optionally resuming from its last
+    /// checkpoint. See [`verify`].
+    pub async fn verify_memory_chain(
+        &self,
+        request: verify::VerifyRequest,
+    ) -> Result<verify::VerifyResponse> {
+        verify::execute(self, request).await
     }
 
     pub async fn trace_causality(
@@ -442,21 +1766,46 @@ impl MnemoEngine {
         causality::trace_causality(self, event_id, max_depth, direction, event_type_filter).await
     }
 
+    /// Next logical clock value for an event `agent_id` writes outside the
+    /// engine's own operations (e.g. ingested spans). See [`clock`].
+    pub async fn next_logical_clock(&self, agent_id: &str) -> i64 {
+        clock::tick(self, agent_id).await
+    }
+
+    /// Verify the event hash chain, walking events in logical-clock order.
     pub async fn verify_event_integrity(
         &self,
         agent_id: Option<String>,
         thread_id: Option<&str>,
     ) -> Result<crate::hash::ChainVerificationResult> {
+        let events = self.events_in_clock_order(agent_id, thread_id).await?;
+        Ok(crate::hash::verify_event_chain(&events))
+    }
+
+    /// Report events whose logical clock disagrees with their timestamp or
+    /// repeats another event's. See [`clock::detect_skew`].
+    pub async fn detect_clock_skew(
+        &self,
+        agent_id: Option<String>,
+        thread_id: Option<&str>,
+    ) -> Result<Vec<clock::ClockSkew>> {
+        let events = self.events_in_clock_order(agent_id, thread_id).await?;
+        Ok(clock::detect_skew(&events))
+    }
+
+    async fn events_in_clock_order(
+        &self,
+        agent_id: Option<String>,
+        thread_id: Option<&str>,
+    ) -> Result<Vec<crate::model::event::AgentEvent>> {
         let agent_id = agent_id.unwrap_or_else(|| self.default_agent_id.clone());
-        let events = if let Some(tid) = thread_id {
+        let mut events = if let Some(tid) = thread_id {
             self.storage.get_events_by_thread(tid, 10000).await?
         } else {
-            // list_events returns DESC order; reverse to chronological for chain verification
-            let mut evts = self.storage.list_events(&agent_id, 10000, 0).await?;
-            evts.reverse();
-            evts
+            self.storage.list_events(&agent_id, 10000, 0).await?
         };
-        Ok(crate::hash::verify_event_chain(&events))
+        events.sort_by(clock::compare_events);
+        Ok(events)
     }
 
     pub async fn detect_conflicts(
@@ -464,6 +1813,7 @@ impl MnemoEngine {
         agent_id: Option<String>,
         threshold: f32,
     ) -> Result<conflict::ConflictDetectionResult> {
+        self.ensure_writable("detect_conflicts")?;
         let agent_id = agent_id.unwrap_or_else(|| self.default_agent_id.clone());
         conflict::detect_conflicts(self, &agent_id, threshold).await
     }
@@ -473,6 +1823,120 @@ impl MnemoEngine {
         conflict_pair: &conflict::ConflictPair,
         strategy: conflict::ResolutionStrategy,
     ) -> Result<()> {
+        self.ensure_writable("resolve_conflict")?;
         conflict::resolve_conflict(self, conflict_pair, strategy).await
     }
+
+    /// Detect conflicts for an agent and queue them for human review
+    /// instead of resolving them. Returns only the newly queued entries.
+    pub async fn queue_conflicts(
+        &self,
+        agent_id: Option<String>,
+        threshold: f32,
+    ) -> Result<Vec<ConflictRecord>> {
+        self.ensure_writable("queue_conflicts")?;
+        let agent_id = agent_id.unwrap_or_else(|| self.default_agent_id.clone());
+        conflict_queue::detect_and_enqueue(self, &agent_id, threshold).await
+    }
+
+    /// List queued conflicts, newest first.
+    pub async fn list_conflicts(
+        &self,
+        request: conflict_queue::ConflictListRequest,
+    ) -> Result<Vec<ConflictRecord>> {
+        conflict_queue::list(self, request).await
+    }
+
+    /// Close an open queued conflict with a human decision.
+    pub async fn resolve_queued_conflict(
+        &self,
+        request: conflict_queue::ResolveQueuedConflictRequest,
+    ) -> Result<ConflictRecord> {
+        self.ensure_writable("resolve_queued_conflict")?;
+        conflict_queue::resolve(self, request).await
+    }
+
+    /// Ask another agent for access to memories it has not shared. See
+    /// [`access_request`].
+    pub async fn request_access(
+        &self,
+        request: access_request::FileAccessRequest,
+    ) -> Result<AccessRequest> {
+        self.ensure_writable("request_access")?;
+        let agent = self.acting_agent(request.requester_id.as_deref());
+        crate::storage::with_agent_context(agent, access_request::file(self, request)).await
+    }
+
+    /// List access requests, newest first.
+    pub async fn list_access_requests(
+        &self,
+        request: access_request::AccessRequestListRequest,
+    ) -> Result<Vec<AccessRequest>> {
+        access_request::list(self, request).await
+    }
+
+    /// Approve (granting the memories) or deny a pending access request.
+    /// Only the owner of the requested memories may decide.
+    pub async fn decide_access_request(
+        &self,
+        request: access_request::DecideAccessRequest,
+    ) -> Result<AccessRequest> {
+        self.ensure_writable("decide_access_request")?;
+        let agent = self.acting_agent(request.agent_id.as_deref());
+        crate::storage::with_agent_context(agent, access_request::decide(self, request)).await
+    }
+
+    /// Create a channel; the creator joins it. See [`channel`].
+    pub async fn create_channel(
+        &self,
+        request: channel::CreateChannelRequest,
+    ) -> Result<crate::model::channel::Channel> {
+        self.ensure_writable("create_channel")?;
+        let agent = self.acting_agent(request.agent_id.as_deref());
+        crate::storage::with_agent_context(agent, channel::create(self, request)).await
+    }
+
+    /// Subscribe an agent to a channel.
+    pub async fn join_channel(
+        &self,
+        request: channel::ChannelMembershipRequest,
+    ) -> Result<crate::model::channel::ChannelMember> {
+        self.ensure_writable("join_channel")?;
+        let agent = self.acting_agent(request.agent_id.as_deref());
+        crate::storage::with_agent_context(agent, channel::join(self, request)).await
+    }
+
+    /// Leave a channel, or (as its creator) remove a member.
+    pub async fn leave_channel(&self, request: channel::ChannelMembershipRequest) -> Result<()> {
+        self.ensure_writable("leave_channel")?;
+        let agent = self.acting_agent(request.agent_id.as_deref());
+        crate::storage::with_agent_context(agent, channel::leave(self, request)).await
+    }
+
+    /// The members of a channel. Only members may list them.
+    pub async fn channel_members(
+        &self,
+        channel: &str,
+        agent_id: Option<String>,
+    ) -> Result<Vec<crate::model::channel::ChannelMember>> {
+        channel::members(self, channel, agent_id).await
+    }
+
+    /// Channels by name; with `member`, only that agent's channels.
+    pub async fn list_channels(
+        &self,
+        member: Option<String>,
+    ) -> Result<Vec<crate::model::channel::Channel>> {
+        channel::list(self, member).await
+    }
+
+    /// Change a channel's memory cap. Only its creator may.
+    pub async fn set_channel_quota(
+        &self,
+        request: channel::SetChannelQuotaRequest,
+    ) -> Result<crate::model::channel::Channel> {
+        self.ensure_writable("set_channel_quota")?;
+        let agent = self.acting_agent(request.agent_id.as_deref());
+        crate::storage::with_agent_context(agent, channel::set_quota(self, request)).await
+    }
 }
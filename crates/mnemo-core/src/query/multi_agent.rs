@@ -0,0 +1,198 @@
+//! Recall across several agents.
+//!
+//! An orchestrator supervising worker agents recalls over all of them at
+//! once by setting [`RecallRequest::agent_ids`]. The request's `agent_id`
+//! is the orchestrator. Every other listed agent must have given it an
+//! unrevoked, unexpired delegation granting `read`; a missing one fails
+//! the whole recall. [`execute`] runs the recall once per listed agent,
+//! with that agent's own filters and retrieval defaults. From a worker's
+//! recall it keeps only the worker's own memories that the delegation's
+//! scope covers. Memories shared with a worker by third parties are left
+//! out, and the orchestrator can list itself to include what it can see.
+//!
+//! The hits are merged by score. A memory returned for several agents
+//! appears once. So does identical content remembered by several agents.
+//! Each hit keeps the highest score and names the agent it was recalled
+//! for in [`ScoredMemory::recalled_for`]. Only `memories` and `total` are
+//! merged. Response extras such as provenance receipts, the orientation
+//! cache or degradation reports are not returned.
+//!
+//! The per-agent recalls run unrecorded, and one whose delegation scope is
+//! narrower than all of the worker's memories asks for more hits until
+//! `limit` of them pass the scope or the worker has no more. Only the hits
+//! returned are recorded, once: access timestamps, access log entries and
+//! a `MemoryRead` event, all as the orchestrator's.
+
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use super::recall::{RecallRequest, RecallResponse, ScoredMemory};
+use crate::error::{Error, Result};
+use crate::model::acl::Permission;
+use crate::model::delegation::DelegationScope;
+use crate::query::MnemoEngine;
+
+/// `strategy` of the `MemoryRead` event of a multi-agent recall.
+const MULTI_AGENT_STRATEGY: &str = "multi_agent";
+
+pub(crate) async fn execute(
+    engine: &MnemoEngine,
+    mut request: RecallRequest,
+    record: bool,
+) -> Result<RecallResponse> {
+    let orchestrator = request
+        .agent_id
+        .clone()
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&orchestrator)?;
+    let mut agent_ids = request.agent_ids.take().unwrap_or_default();
+    if agent_ids.is_empty() {
+        return Err(Error::invalid_field("agent_ids", "cannot be empty"));
+    }
+    engine.limits().check_batch("agent_ids", agent_ids.len())?;
+    for (i, agent_id) in agent_ids.iter().enumerate() {
+        super::validate_agent_id(agent_id)
+            .map_err(|e| Error::invalid_field(format!("agent_ids[{i}]"), e.to_string()))?;
+    }
+    let mut seen = HashSet::new();
+    agent_ids.retain(|a| seen.insert(a.clone()));
+
+    // Check every delegation before recalling anything.
+    let delegations = engine.storage.list_delegations_for(&orchestrator).await?;
+    let mut scopes: Vec<Option<Vec<&DelegationScope>>> = Vec::with_capacity(agent_ids.len());
+    for agent_id in &agent_ids {
+        if *agent_id == orchestrator {
+            scopes.push(None);
+            continue;
+        }
+        let granted: Vec<&DelegationScope> = delegations
+            .iter()
+            .filter(|d| d.delegator_id == *agent_id && d.permission.satisfies(Permission::Read))
+            .map(|d| &d.scope)
+            .collect();
+        if granted.is_empty() {
+            return Err(Error::PermissionDenied(format!(
+                "agent {orchestrator} has no read delegation from agent {agent_id}"
+            )));
+        }
+        scopes.push(Some(granted));
+    }
+
+    let limit = engine.limits().recall_limit(request.limit.unwrap_or(10));
+    let max_limit = engine.limits().recall_limit(usize::MAX);
+    let mut hits: Vec<ScoredMemory> = Vec::new();
+    for (agent_id, scope) in agent_ids.iter().zip(&scopes) {
+        let mut fetch = limit;
+        loop {
+            let mut per_agent = request.clone();
+            per_agent.agent_id = Some(agent_id.clone());
+            per_agent.limit = Some(fetch);
+            // Search as the worker, so database row-level security sees what
+            // it sees; the delegation check above stands in for the
+            // orchestrator.
+            let response = crate::storage::with_agent_context(
+                agent_id.clone(),
+                super::recall::execute_at_read_version(engine, per_agent, false),
+            )
+            .await?;
+            let exhausted = response.memories.len() < fetch || fetch >= max_limit;
+            let kept: Vec<ScoredMemory> = response
+                .memories
+                .into_iter()
+                .filter(|hit| match scope {
+                    Some(granted) => {
+                        hit.agent_id == *agent_id && granted.iter().any(|s| covers(s, hit))
+                    }
+                    None => true,
+                })
+                .collect();
+            if kept.len() >= limit || exhausted {
+                hits.extend(kept.into_iter().map(|mut hit| {
+                    hit.recalled_for = Some(agent_id.clone());
+                    hit
+                }));
+                break;
+            }
+            fetch = fetch.saturating_mul(4).min(max_limit);
+        }
+    }
+
+    let memories = merge(hits);
+    let total = memories.len();
+    let mut response = RecallResponse::new(memories, total);
+    response.memories.truncate(limit);
+    if record {
+        let ids: Vec<Uuid> = response.memories.iter().map(|m| m.id).collect();
+        super::recall::record_access(engine, &orchestrator, &request.query, &ids).await;
+        super::recall::record_read_event(
+            engine,
+            &orchestrator,
+            &request.query,
+            total,
+            MULTI_AGENT_STRATEGY,
+        )
+        .await;
+    }
+    Ok(response)
+}
+
+fn covers(scope: &DelegationScope, hit: &ScoredMemory) -> bool {
+    match scope {
+        DelegationScope::AllMemories => true,
+        DelegationScope::ByMemoryId(ids) => ids.contains(&hit.id),
+        DelegationScope::ByTag(tags) => tags.iter().any(|t| hit.tags.contains(t)),
+    }
+}
+
+/// Sort hits by score and drop repeats of a memory id, or of non-empty
+/// content, keeping the best-scored copy.
+fn merge(mut hits: Vec<ScoredMemory>) -> Vec<ScoredMemory> {
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+    let mut ids: HashSet<Uuid> = HashSet::new();
+    let mut contents: HashSet<String> = HashSet::new();
+    hits.retain(|hit| {
+        let new_id = ids.insert(hit.id);
+        let new_content = hit.content.is_empty() || contents.insert(hit.content.clone());
+        new_id && new_content
+    });
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::memory::MemoryRecord;
+
+    fn hit(agent: &str, content: &str, score: f32) -> ScoredMemory {
+        let mut record = MemoryRecord::new(agent.to_string(), content.to_string());
+        record.tags = vec!["ops".to_string()];
+        ScoredMemory::from((record, score))
+    }
+
+    #[test]
+    fn merge_keeps_the_best_copy_of_each_memory_and_content() {
+        let shared = hit("worker-a", "deploy at noon", 0.4);
+        let mut again = shared.clone();
+        again.score = 0.9;
+        let hits = vec![
+            shared,
+            hit("worker-b", "deploy at noon", 0.7),
+            again,
+            hit("worker-b", "rollback plan", 0.5),
+        ];
+        let merged = merge(hits);
+        let scores: Vec<f32> = merged.iter().map(|h| h.score).collect();
+        assert_eq!(scores, vec![0.9, 0.5]);
+        assert_eq!(merged[0].agent_id, "worker-a");
+    }
+
+    #[test]
+    fn delegation_scopes_cover_by_tag_and_id() {
+        let h = hit("worker-a", "x", 1.0);
+        assert!(covers(&DelegationScope::AllMemories, &h));
+        assert!(covers(&DelegationScope::ByTag(vec!["ops".into()]), &h));
+        assert!(!covers(&DelegationScope::ByTag(vec!["hr".into()]), &h));
+        assert!(covers(&DelegationScope::ByMemoryId(vec![h.id]), &h));
+    }
+}
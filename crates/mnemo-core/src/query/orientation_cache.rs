@@ -599,10 +599,17 @@ mod tests {
             tags: vec![],
             metadata: json!({}),
             score: 1.0,
+            relevance: None,
             access_count: 0,
             created_at: "2026-05-23T00:00:00Z".to_string(),
             updated_at: "2026-05-23T00:00:00Z".to_string(),
             score_breakdown: None,
+            snippets: None,
+            provenance_chain: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
+            recalled_for: None,
         }
     }
 
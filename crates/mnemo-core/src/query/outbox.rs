@@ -0,0 +1,208 @@
+//! Delivering write notifications through the transactional outbox.
+//!
+//! With [`MnemoEngine::with_outbox`](crate::query::MnemoEngine::with_outbox),
+//! `remember` writes an [`OutboxMessage`] in the same transaction as the
+//! memory, its relations and its audit event (see
+//! [`StorageBackend::commit_write`]). A message therefore exists exactly
+//! when the write it announces does. An [`OutboxDispatcher`] hands each
+//! message to an [`OutboxSink`] and marks it delivered once the sink accepts
+//! it.
+//!
+//! A message is leased while it is being delivered, so two dispatchers never
+//! send it at the same time. If a dispatcher dies after the sink accepted a
+//! message but before marking it delivered, the message is sent again when
+//! the lease runs out, with the same [`OutboxMessage::id`]. Receivers that
+//! drop ids they have already seen (the webhook sink sends it as
+//! `Idempotency-Key`) get every message exactly once.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::Sha256;
+
+use crate::error::{Error, Result};
+use crate::model::outbox::OutboxMessage;
+use crate::storage::StorageBackend;
+
+/// Topic of the message `remember` writes for a new memory.
+pub const MEMORY_WRITE_TOPIC: &str = "memory_write";
+
+/// Where the dispatcher sends outbox messages.
+#[async_trait::async_trait]
+pub trait OutboxSink: Send + Sync {
+    /// Deliver one message. `Ok` means the receiver has it; an error leaves
+    /// the message pending for a later retry.
+    async fn deliver(&self, message: &OutboxMessage) -> Result<()>;
+}
+
+/// POSTs each message as JSON to a URL, with the message id in the
+/// `Idempotency-Key` header. With a secret, the body's HMAC-SHA256 is sent
+/// as `X-Mnemo-Signature: sha256=<hex>`.
+pub struct WebhookSink {
+    url: String,
+    secret: Option<Vec<u8>>,
+    client: reqwest::Client,
+}
+
+impl WebhookSink {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            secret: None,
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_else(|e| {
+                    tracing::error!(error = %e, "failed to build HTTP client with timeouts, using default");
+                    reqwest::Client::default()
+                }),
+        }
+    }
+
+    pub fn with_secret(mut self, secret: impl Into<Vec<u8>>) -> Self {
+        self.secret = Some(secret.into());
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl OutboxSink for WebhookSink {
+    async fn deliver(&self, message: &OutboxMessage) -> Result<()> {
+        let body = serde_json::to_vec(message)?;
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header("Content-Type", "application/json")
+            .header("Idempotency-Key", message.id.to_string());
+        if let Some(ref secret) = self.secret {
+            let mut mac = <Hmac<Sha256> as KeyInit>::new_from_slice(secret)
+                .map_err(|e| Error::Internal(format!("webhook signing key: {e}")))?;
+            mac.update(&body);
+            let signature = hex::encode(mac.finalize().into_bytes());
+            request = request.header("X-Mnemo-Signature", format!("sha256={signature}"));
+        }
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| Error::Internal(format!("webhook delivery failed: {e}")))?;
+        if !response.status().is_success() {
+            return Err(Error::Internal(format!(
+                "webhook returned {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of one [`OutboxDispatcher::dispatch_once`] pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DispatchReport {
+    pub delivered: usize,
+    pub failed: usize,
+}
+
+/// Moves pending outbox messages to a sink.
+pub struct OutboxDispatcher {
+    storage: Arc<dyn StorageBackend>,
+    sink: Arc<dyn OutboxSink>,
+    batch_size: usize,
+    lease: Duration,
+    retry_delay: Duration,
+}
+
+/// Longest wait between retries of one message.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(3600);
+
+impl OutboxDispatcher {
+    /// Claims 100 messages per pass with a 60 s lease, and retries a failed
+    /// message after 30 s, doubling per attempt up to an hour.
+    pub fn new(storage: Arc<dyn StorageBackend>, sink: Arc<dyn OutboxSink>) -> Self {
+        Self {
+            storage,
+            sink,
+            batch_size: 100,
+            lease: Duration::from_secs(60),
+            retry_delay: Duration::from_secs(30),
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// How long a claimed message is withheld from other dispatchers. Must
+    /// exceed the time a batch takes to deliver, or messages may be sent
+    /// twice concurrently.
+    pub fn with_lease(mut self, lease: Duration) -> Self {
+        self.lease = lease;
+        self
+    }
+
+    pub fn with_retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Claim one batch and deliver it, message by message in write order.
+    pub async fn dispatch_once(&self) -> Result<DispatchReport> {
+        let now = chrono::Utc::now();
+        let lease_until = now + chrono::Duration::from_std(self.lease).unwrap_or_default();
+        let claimed = self
+            .storage
+            .claim_outbox(self.batch_size, &lease_until.to_rfc3339())
+            .await?;
+
+        let mut report = DispatchReport::default();
+        for message in &claimed {
+            match self.sink.deliver(message).await {
+                Ok(()) => {
+                    self.storage.complete_outbox(message.id).await?;
+                    report.delivered += 1;
+                }
+                Err(e) => {
+                    let retry_at = chrono::Utc::now()
+                        + chrono::Duration::from_std(self.backoff(message.attempts))
+                            .unwrap_or_default();
+                    tracing::warn!(
+                        message_id = %message.id,
+                        topic = %message.topic,
+                        attempts = message.attempts,
+                        error = %e,
+                        "outbox delivery failed"
+                    );
+                    self.storage
+                        .fail_outbox(message.id, &e.to_string(), &retry_at.to_rfc3339())
+                        .await?;
+                    report.failed += 1;
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    fn backoff(&self, attempts: u32) -> Duration {
+        let doublings = attempts.saturating_sub(1).min(16);
+        self.retry_delay
+            .saturating_mul(1 << doublings)
+            .min(MAX_RETRY_DELAY)
+    }
+
+    /// Dispatch in the background: drain full batches back to back, then
+    /// wait `interval` before looking again. Errors are logged.
+    pub fn spawn(self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match self.dispatch_once().await {
+                    Ok(report) if report.delivered + report.failed >= self.batch_size => continue,
+                    Ok(_) => {}
+                    Err(e) => tracing::warn!(error = %e, "outbox dispatch failed"),
+                }
+                tokio::time::sleep(interval).await;
+            }
+        })
+    }
+}
@@ -0,0 +1,111 @@
+//! Pin / unpin an existing memory.
+//!
+//! Pinned memories are protected: the decay pass, TTL sweep, reflection
+//! archival, tag-cluster consolidation, conflict resolution and
+//! criteria-based forgetting all leave them alone, and an explicit forget
+//! needs `force`. Pinning also clears any pending `expires_at`.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::acl::Permission;
+use crate::model::event::EventType;
+use crate::query::MnemoEngine;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinRequest {
+    pub memory_id: Uuid,
+    pub agent_id: Option<String>,
+    pub pinned: bool,
+}
+
+impl PinRequest {
+    pub fn new(memory_id: Uuid, pinned: bool) -> Self {
+        Self {
+            memory_id,
+            agent_id: None,
+            pinned,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinResponse {
+    pub memory_id: Uuid,
+    pub pinned: bool,
+    /// `false` when the memory was already in the requested state.
+    pub changed: bool,
+}
+
+impl PinResponse {
+    pub fn new(memory_id: Uuid, pinned: bool, changed: bool) -> Self {
+        Self {
+            memory_id,
+            pinned,
+            changed,
+        }
+    }
+}
+
+pub async fn execute(engine: &MnemoEngine, request: PinRequest) -> Result<PinResponse> {
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+
+    let mut record = engine
+        .storage
+        .get_memory(request.memory_id)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("memory {} not found", request.memory_id)))?;
+    if record.is_deleted() {
+        return Err(Error::NotFound(format!(
+            "memory {} not found",
+            request.memory_id
+        )));
+    }
+    if !engine
+        .storage
+        .check_permission(request.memory_id, &agent_id, Permission::Write)
+        .await?
+    {
+        return Err(Error::PermissionDenied(format!(
+            "agent {agent_id} cannot modify memory {}",
+            request.memory_id
+        )));
+    }
+
+    if record.pinned == request.pinned {
+        return Ok(PinResponse::new(record.id, record.pinned, false));
+    }
+
+    record.pinned = request.pinned;
+    if record.pinned {
+        record.expires_at = None;
+    }
+    record.updated_at = chrono::Utc::now().to_rfc3339();
+    engine.storage.update_memory(&record).await?;
+    if let Some(ref cache) = engine.cache {
+        cache.invalidate(record.id);
+    }
+
+    let event = super::event_builder::build_event(
+        engine,
+        &agent_id,
+        EventType::MemoryWrite,
+        serde_json::json!({
+            "memory_id": record.id.to_string(),
+            "pinned": record.pinned,
+        }),
+        &record.id.to_string(),
+        record.thread_id.clone(),
+    )
+    .await;
+    if let Err(e) = engine.storage.insert_event(&event).await {
+        tracing::error!(event_id = %event.id, error = %e, "failed to insert audit event");
+    }
+
+    Ok(PinResponse::new(record.id, record.pinned, true))
+}
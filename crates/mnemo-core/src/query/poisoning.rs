@@ -337,6 +337,7 @@ pub async fn update_agent_profile(engine: &MnemoEngine, record: &MemoryRecord) -
             avg_content_length: record.content.len() as f64,
             total_memories: 1,
             last_updated: now,
+            retrieval: Default::default(),
         },
     };
 
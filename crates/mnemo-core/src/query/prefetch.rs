@@ -0,0 +1,160 @@
+//! Warm the recall path for a thread before its first recall.
+//!
+//! After a cold start the first recall in a thread pays for reading every
+//! hit from storage and, with a [`ShardedIndex`](crate::index::sharded::ShardedIndex)
+//! that evicts to disk, for loading the tenant's shard. A prefetch reads
+//! the thread's most likely hits up front: half of the budget goes to its
+//! most important memories and the rest to the most recently accessed (or,
+//! never accessed, written) ones. They go into the [`MemoryCache`](crate::cache::MemoryCache),
+//! and their shards are loaded into the vector index.
+//!
+//! Replaying a checkpoint prefetches its thread when the engine has a
+//! cache; agents resuming a thread without a checkpoint call
+//! [`MnemoEngine::prefetch`] themselves.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::index::ShardRoute;
+use crate::model::acl::Permission;
+use crate::model::memory::MemoryRecord;
+use crate::query::MnemoEngine;
+use crate::storage::MemoryFilter;
+
+/// Memories a prefetch loads when the request sets no limit.
+pub const DEFAULT_PREFETCH_LIMIT: usize = 64;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrefetchRequest {
+    pub thread_id: String,
+    /// Whose memories in the thread to load; defaults to the engine's
+    /// agent, who needs read access to the thread.
+    pub agent_id: Option<String>,
+    /// Defaults to [`DEFAULT_PREFETCH_LIMIT`].
+    pub limit: Option<usize>,
+}
+
+impl PrefetchRequest {
+    pub fn new(thread_id: String) -> Self {
+        Self {
+            thread_id,
+            agent_id: None,
+            limit: None,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrefetchResponse {
+    /// Memories put into the cache. 0 when the engine has no cache.
+    pub cached: usize,
+    /// Vector index shards loaded.
+    pub shards_loaded: usize,
+}
+
+impl PrefetchResponse {
+    pub fn new(cached: usize, shards_loaded: usize) -> Self {
+        Self {
+            cached,
+            shards_loaded,
+        }
+    }
+}
+
+pub async fn execute(engine: &MnemoEngine, request: PrefetchRequest) -> Result<PrefetchResponse> {
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_PREFETCH_LIMIT)
+        .min(super::MAX_BATCH_QUERY_LIMIT);
+    super::checkpoint::ensure_thread_access(
+        engine,
+        &request.thread_id,
+        &agent_id,
+        Permission::Read,
+    )
+    .await?;
+
+    let filter = MemoryFilter {
+        agent_id: Some(agent_id),
+        thread_id: Some(request.thread_id),
+        ..Default::default()
+    };
+    let records = engine
+        .storage
+        .list_memories(&filter, super::MAX_BATCH_QUERY_LIMIT, 0)
+        .await?;
+    let selected = select(records, limit);
+
+    let mut shards_loaded = 0;
+    let mut routes = HashSet::new();
+    for record in &selected {
+        let route = ShardRoute::of(record);
+        if routes.insert((route.agent_id, route.org_id)) && engine.index.prefetch(route)? {
+            shards_loaded += 1;
+        }
+    }
+    let cached = match engine.cache {
+        Some(ref cache) => {
+            let cached = selected.len();
+            for record in selected {
+                cache.put(record);
+            }
+            cached
+        }
+        None => 0,
+    };
+    Ok(PrefetchResponse::new(cached, shards_loaded))
+}
+
+/// Up to `limit` records recall could return: the most important half,
+/// then the most recently used.
+fn select(records: Vec<MemoryRecord>, limit: usize) -> Vec<MemoryRecord> {
+    let now = chrono::Utc::now();
+    let mut records: Vec<MemoryRecord> = records
+        .into_iter()
+        .filter(|r| !r.quarantined && !is_expired(r, now))
+        .collect();
+    if records.len() <= limit {
+        return records;
+    }
+
+    records.sort_by(|a, b| b.importance.total_cmp(&a.importance));
+    let mut picked: HashSet<Uuid> = records
+        .iter()
+        .take(limit.div_ceil(2))
+        .map(|r| r.id)
+        .collect();
+    let mut by_use: Vec<&MemoryRecord> = records.iter().collect();
+    by_use.sort_by(|a, b| last_used(b).cmp(last_used(a)));
+    for record in by_use {
+        if picked.len() >= limit {
+            break;
+        }
+        picked.insert(record.id);
+    }
+    records.retain(|r| picked.contains(&r.id));
+    records
+}
+
+fn is_expired(record: &MemoryRecord, now: chrono::DateTime<chrono::Utc>) -> bool {
+    record
+        .expires_at
+        .as_deref()
+        .and_then(|at| chrono::DateTime::parse_from_rfc3339(at).ok())
+        .is_some_and(|at| at < now)
+}
+
+fn last_used(record: &MemoryRecord) -> &str {
+    record
+        .last_accessed_at
+        .as_deref()
+        .unwrap_or(&record.created_at)
+}
@@ -0,0 +1,239 @@
+//! Privacy layer for shared-scope recall.
+//!
+//! Organisations that pool memories in `Global` / `Public` scope want other
+//! agents to benefit from common knowledge without being able to mine one
+//! agent's private details out of the pool. With a [`SharedRecallPrivacy`]
+//! attached ([`MnemoEngine::with_shared_recall_privacy`]), a recall hit that
+//! is shared-scope and owned by someone other than the caller is:
+//!
+//! - **withheld** unless at least `min_sources` distinct agents wrote a
+//!   corroborating memory (k-anonymity over the shared pool), and
+//! - **de-attributed** when `redact_attribution` is set: the hit's
+//!   `agent_id` is replaced by [`REDACTED_AGENT_ID`] and no provenance chain
+//!   is returned.
+//!
+//! Two memories corroborate each other when their embeddings are at least
+//! `similarity_threshold` cosine-similar and both are live, shared-scope
+//! memories of the same org. Policies are configured per org, with an
+//! optional default for memories of orgs without one. Owners always see
+//! their own memories unfiltered.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::memory::{MemoryRecord, Scope};
+use crate::query::MnemoEngine;
+
+/// `agent_id` reported for hits whose attribution was redacted.
+pub const REDACTED_AGENT_ID: &str = "[redacted]";
+
+/// Nearest neighbours inspected when counting corroborating sources.
+const CORROBORATION_CANDIDATES: usize = 64;
+
+/// k-anonymity and attribution settings for one org's shared memories.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PrivacyPolicy {
+    /// Distinct agents (the author included) that must have written a
+    /// corroborating memory before a hit is shown to a non-owner.
+    pub min_sources: usize,
+    /// Cosine similarity at which two memories count as the same fact.
+    pub similarity_threshold: f32,
+    /// Hide who wrote a hit from non-owners.
+    pub redact_attribution: bool,
+}
+
+impl Default for PrivacyPolicy {
+    fn default() -> Self {
+        Self {
+            min_sources: 3,
+            similarity_threshold: 0.9,
+            redact_attribution: true,
+        }
+    }
+}
+
+/// Per-org [`PrivacyPolicy`] table.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SharedRecallPrivacy {
+    /// Policy for memories whose org has no entry in `orgs` (including
+    /// memories without an org). `None` leaves those unfiltered.
+    pub default: Option<PrivacyPolicy>,
+    /// Policies keyed by `org_id`.
+    pub orgs: HashMap<String, PrivacyPolicy>,
+}
+
+impl SharedRecallPrivacy {
+    /// Apply `policy` to every org.
+    pub fn uniform(policy: PrivacyPolicy) -> Self {
+        Self {
+            default: Some(policy),
+            orgs: HashMap::new(),
+        }
+    }
+
+    /// Set the policy for one org.
+    pub fn with_org(mut self, org_id: impl Into<String>, policy: PrivacyPolicy) -> Self {
+        self.orgs.insert(org_id.into(), policy);
+        self
+    }
+
+    /// The policy governing memories of `org_id`, if any.
+    pub fn policy_for(&self, org_id: Option<&str>) -> Option<&PrivacyPolicy> {
+        org_id
+            .and_then(|org| self.orgs.get(org))
+            .or(self.default.as_ref())
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let policies = self
+            .default
+            .iter()
+            .map(|p| ("default", p))
+            .chain(self.orgs.iter().map(|(org, p)| (org.as_str(), p)));
+        for (name, policy) in policies {
+            if policy.min_sources == 0 {
+                return Err(Error::Validation(format!(
+                    "privacy policy {name}: min_sources must be >= 1"
+                )));
+            }
+            if !(policy.similarity_threshold > 0.0 && policy.similarity_threshold <= 1.0) {
+                return Err(Error::Validation(format!(
+                    "privacy policy {name}: similarity_threshold must be in (0, 1], got {}",
+                    policy.similarity_threshold
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn is_shared(scope: Scope) -> bool {
+    matches!(scope, Scope::Public | Scope::Global)
+}
+
+/// Drop hits that fail their org's k-anonymity threshold, keeping at most
+/// `limit` survivors in their original order. Returns the ids of surviving
+/// hits whose attribution must be redacted.
+pub(crate) async fn enforce(
+    engine: &MnemoEngine,
+    privacy: &SharedRecallPrivacy,
+    agent_id: &str,
+    scored: &mut Vec<(MemoryRecord, f32)>,
+    limit: usize,
+) -> HashSet<Uuid> {
+    let mut redacted = HashSet::new();
+    let mut kept = Vec::with_capacity(limit.min(scored.len()));
+    for (record, score) in scored.drain(..) {
+        if kept.len() >= limit {
+            break;
+        }
+        let policy = if is_shared(record.scope) && record.agent_id != agent_id {
+            privacy.policy_for(record.org_id.as_deref())
+        } else {
+            None
+        };
+        if let Some(policy) = policy {
+            if corroborating_sources(engine, &record, policy).await < policy.min_sources {
+                continue;
+            }
+            if policy.redact_attribution {
+                redacted.insert(record.id);
+            }
+        }
+        kept.push((record, score));
+    }
+    *scored = kept;
+    redacted
+}
+
+/// Apply to ranked hits read outside recall what recall applies to its
+/// own: hits the privacy policy withholds from `agent_id` are dropped, at
+/// most `limit` are kept, and those carry the redacted or
+/// [aliased](super::pseudonym) `agent_id` the caller would see in a recall.
+pub(crate) async fn shield(
+    engine: &MnemoEngine,
+    agent_id: &str,
+    hits: &mut Vec<(MemoryRecord, f32)>,
+    limit: usize,
+) -> Result<()> {
+    let redacted = match engine.shared_recall_privacy {
+        Some(ref privacy) => enforce(engine, privacy, agent_id, hits, limit).await,
+        None => {
+            hits.truncate(limit);
+            HashSet::new()
+        }
+    };
+    let mut aliases =
+        super::pseudonym::recall_aliases(engine, agent_id, hits.iter().map(|(r, _)| r), &redacted)
+            .await?;
+    for (record, _) in hits.iter_mut() {
+        if redacted.contains(&record.id) {
+            record.agent_id = REDACTED_AGENT_ID.to_string();
+        } else if let Some(alias) = aliases.remove(&record.id) {
+            record.agent_id = alias;
+        }
+    }
+    Ok(())
+}
+
+/// Distinct agents, the author included, with a live shared-scope memory
+/// of the same org that is near-identical to `record`. Stops counting once
+/// the policy's threshold is reached.
+async fn corroborating_sources(
+    engine: &MnemoEngine,
+    record: &MemoryRecord,
+    policy: &PrivacyPolicy,
+) -> usize {
+    let mut sources: HashSet<String> = HashSet::from([record.agent_id.clone()]);
+    // A projected read leaves the embedding out.
+    let embedding = match record.embedding {
+        Some(ref embedding) => Some(embedding.clone()),
+        None => match engine.storage.get_memory(record.id).await {
+            Ok(full) => full.and_then(|r| r.embedding),
+            Err(e) => {
+                tracing::warn!(memory_id = %record.id, error = %e, "failed to load embedding, treating memory as uncorroborated");
+                None
+            }
+        },
+    };
+    let Some(embedding) = embedding else {
+        return sources.len();
+    };
+    let neighbours = match engine.index.search(&embedding, CORROBORATION_CANDIDATES) {
+        Ok(neighbours) => neighbours,
+        Err(e) => {
+            tracing::warn!(memory_id = %record.id, error = %e, "corroboration search failed, treating memory as uncorroborated");
+            return sources.len();
+        }
+    };
+    for (id, distance) in neighbours {
+        if sources.len() >= policy.min_sources {
+            break;
+        }
+        if id == record.id || 1.0 - distance < policy.similarity_threshold {
+            continue;
+        }
+        let other = match engine.storage.get_memory(id).await {
+            Ok(Some(other)) => other,
+            Ok(None) => continue,
+            Err(e) => {
+                tracing::warn!(memory_id = %id, error = %e, "failed to load corroboration candidate");
+                continue;
+            }
+        };
+        if other.is_deleted()
+            || other.quarantined
+            || !is_shared(other.scope)
+            || other.org_id != record.org_id
+        {
+            continue;
+        }
+        sources.insert(other.agent_id);
+    }
+    sources.len()
+}
@@ -0,0 +1,217 @@
+//! Pseudonymous agent aliases for shared-scope reads and exports.
+//!
+//! Partner organisations pooling memories in `Global` / `Public` scope
+//! should not learn which of each other's agents wrote them. With a
+//! [`Pseudonymizer`] attached ([`MnemoEngine::with_pseudonymizer`]):
+//!
+//! - a shared-scope recall hit owned by someone other than the caller
+//!   reports an alias as its `agent_id` and comes without a provenance
+//!   chain, and
+//! - every record of a scheduled export carries aliases in `agent_id` and
+//!   `created_by`.
+//!
+//! An alias is a keyed hash of the memory's org and agent, so an agent has
+//! one stable alias per org and unrelated aliases across orgs. Each alias
+//! handed out is recorded in its org's alias table with the agent id
+//! sealed under the same key; [`reveal`] opens an entry only for a caller
+//! presenting that key. An alias that cannot be recorded is never handed
+//! out: the read fails instead. Hits whose attribution the
+//! [shared-recall privacy layer](super::privacy) redacts stay redacted.
+
+use std::collections::{BTreeSet, HashMap};
+
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::encryption::ContentEncryption;
+use crate::error::{Error, Result};
+use crate::model::memory::MemoryRecord;
+use crate::model::pseudonym::PseudonymEntry;
+use crate::query::MnemoEngine;
+
+/// Prefix of every alias.
+pub const ALIAS_PREFIX: &str = "anon-";
+
+/// Keyed agent aliasing, optionally limited to some orgs.
+#[derive(Clone)]
+pub struct Pseudonymizer {
+    key: [u8; 32],
+    orgs: BTreeSet<String>,
+}
+
+impl Pseudonymizer {
+    /// Alias agents of every org under a 32-byte key.
+    pub fn new(key: [u8; 32]) -> Self {
+        Self {
+            key,
+            orgs: BTreeSet::new(),
+        }
+    }
+
+    /// Create from a hex-encoded key (64 hex chars = 32 bytes).
+    pub fn from_hex(hex_key: &str) -> Result<Self> {
+        let bytes = hex::decode(hex_key)
+            .map_err(|e| Error::Validation(format!("invalid pseudonym key: {e}")))?;
+        let key: [u8; 32] = bytes.try_into().map_err(|b: Vec<u8>| {
+            Error::Validation(format!("pseudonym key must be 32 bytes, got {}", b.len()))
+        })?;
+        Ok(Self::new(key))
+    }
+
+    /// Only alias agents in these orgs. An empty set (the default) covers
+    /// every org, including memories without one.
+    pub fn with_orgs<I, S>(mut self, orgs: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.orgs = orgs.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Whether memories of `org_id` are aliased.
+    pub fn covers(&self, org_id: Option<&str>) -> bool {
+        self.orgs.is_empty() || org_id.is_some_and(|org| self.orgs.contains(org))
+    }
+
+    /// The alias of `agent_id` within `org_id`.
+    pub fn alias(&self, org_id: Option<&str>, agent_id: &str) -> String {
+        let mut mac = <Hmac<Sha256> as KeyInit>::new_from_slice(&self.key)
+            .expect("HMAC accepts any key length");
+        mac.update(org_id.unwrap_or_default().as_bytes());
+        mac.update(&[0]);
+        mac.update(agent_id.as_bytes());
+        let digest = mac.finalize().into_bytes();
+        format!("{ALIAS_PREFIX}{}", hex::encode(&digest[..8]))
+    }
+}
+
+/// Alias `agent_id` within `org_id`, recording the alias in the org's
+/// table the first time it is handed out.
+pub(crate) async fn pseudonymize(
+    engine: &MnemoEngine,
+    pseudonymizer: &Pseudonymizer,
+    org_id: Option<&str>,
+    agent_id: &str,
+) -> Result<String> {
+    let alias = pseudonymizer.alias(org_id, agent_id);
+    let org = org_id.unwrap_or_default();
+    if engine.storage.get_pseudonym(org, &alias).await?.is_none() {
+        let sealed = ContentEncryption::new(pseudonymizer.key).encrypt(agent_id.as_bytes())?;
+        engine
+            .storage
+            .record_pseudonym(&PseudonymEntry {
+                org_id: org.to_string(),
+                alias: alias.clone(),
+                sealed_agent_id: hex::encode(sealed),
+                created_at: chrono::Utc::now().to_rfc3339(),
+            })
+            .await?;
+    }
+    Ok(alias)
+}
+
+/// Whether `agent_id` sees `record` pseudonymously: a shared-scope memory
+/// owned by someone else, in a covered org.
+fn aliased_for(pseudonymizer: &Pseudonymizer, agent_id: &str, record: &MemoryRecord) -> bool {
+    super::privacy::is_shared(record.scope)
+        && record.agent_id != agent_id
+        && pseudonymizer.covers(record.org_id.as_deref())
+}
+
+/// Aliases for the recall hits in `records` that `agent_id` sees
+/// pseudonymously and that are not already in `redacted`.
+pub(crate) async fn recall_aliases<'a>(
+    engine: &MnemoEngine,
+    agent_id: &str,
+    records: impl Iterator<Item = &'a MemoryRecord>,
+    redacted: &std::collections::HashSet<Uuid>,
+) -> Result<HashMap<Uuid, String>> {
+    let mut aliases = HashMap::new();
+    let Some(ref pseudonymizer) = engine.pseudonymizer else {
+        return Ok(aliases);
+    };
+    for record in records {
+        if aliased_for(pseudonymizer, agent_id, record) && !redacted.contains(&record.id) {
+            let alias = pseudonymize(
+                engine,
+                pseudonymizer,
+                record.org_id.as_deref(),
+                &record.agent_id,
+            )
+            .await?;
+            aliases.insert(record.id, alias);
+        }
+    }
+    Ok(aliases)
+}
+
+/// Alias the agent ids of a memory `agent_id` fetched by id, when a
+/// recall would have shown it under an alias.
+pub(crate) async fn alias_for_reader(
+    engine: &MnemoEngine,
+    agent_id: &str,
+    record: &mut MemoryRecord,
+) -> Result<()> {
+    let Some(ref pseudonymizer) = engine.pseudonymizer else {
+        return Ok(());
+    };
+    if aliased_for(pseudonymizer, agent_id, record) {
+        pseudonymize_agents(engine, pseudonymizer, record).await?;
+    }
+    Ok(())
+}
+
+/// Replace the agent ids of an exported record with their aliases.
+pub(crate) async fn pseudonymize_record(
+    engine: &MnemoEngine,
+    record: &mut MemoryRecord,
+) -> Result<()> {
+    let Some(ref pseudonymizer) = engine.pseudonymizer else {
+        return Ok(());
+    };
+    if pseudonymizer.covers(record.org_id.as_deref()) {
+        pseudonymize_agents(engine, pseudonymizer, record).await?;
+    }
+    Ok(())
+}
+
+async fn pseudonymize_agents(
+    engine: &MnemoEngine,
+    pseudonymizer: &Pseudonymizer,
+    record: &mut MemoryRecord,
+) -> Result<()> {
+    let org_id = record.org_id.clone();
+    record.agent_id =
+        pseudonymize(engine, pseudonymizer, org_id.as_deref(), &record.agent_id).await?;
+    if let Some(ref created_by) = record.created_by {
+        record.created_by =
+            Some(pseudonymize(engine, pseudonymizer, org_id.as_deref(), created_by).await?);
+    }
+    Ok(())
+}
+
+/// Resolve `alias` in `org_id`'s alias table back to its agent id. Only a
+/// caller holding the pseudonym key (`key_hex`) can open the entry.
+pub async fn reveal(
+    engine: &MnemoEngine,
+    org_id: Option<&str>,
+    alias: &str,
+    key_hex: &str,
+) -> Result<String> {
+    let opener = Pseudonymizer::from_hex(key_hex)?;
+    let entry = engine
+        .storage
+        .get_pseudonym(org_id.unwrap_or_default(), alias)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("pseudonym {alias} not found")))?;
+    let bytes = hex::decode(&entry.sealed_agent_id)
+        .map_err(|e| Error::Internal(format!("corrupt pseudonym entry {alias}: {e}")))?;
+    let agent_id = ContentEncryption::new(opener.key)
+        .decrypt(&bytes)
+        .map_err(|_| Error::PermissionDenied(format!("key does not open pseudonym {alias}")))?;
+    tracing::info!(alias, org_id, "pseudonym revealed");
+    String::from_utf8(agent_id)
+        .map_err(|e| Error::Internal(format!("corrupt pseudonym entry {alias}: {e}")))
+}
@@ -5,12 +5,13 @@ use uuid::Uuid;
 
 use crate::error::Result;
 use crate::hash::compute_content_hash;
+use crate::model::access_log::AccessOperation;
 use crate::model::event::{AgentEvent, EventType};
 use crate::model::memory::{MemoryRecord, MemoryType, Scope};
 use crate::query::MnemoEngine;
-use crate::storage::MemoryFilter;
-#[allow(unused_imports)]
-use base64::Engine as _;
+use crate::query::latency_slo::{DegradationLevel, RecallBudget, RecallStage};
+use crate::search::{TextQueryMode, TextSearchOptions};
+use crate::storage::{MemoryColumns, MemoryFilter};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TemporalRange {
@@ -22,6 +23,30 @@ impl TemporalRange {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Check both bounds and normalize them to UTC RFC 3339; see
+    /// [`validation`](super::validation).
+    pub fn normalize(&mut self) -> Result<()> {
+        let parse = |field: &str, value: &Option<String>| {
+            value
+                .as_deref()
+                .map(|v| super::validation::parse_timestamp(field, v))
+                .transpose()
+        };
+        let after = parse("temporal_range.after", &self.after)?;
+        let before = parse("temporal_range.before", &self.before)?;
+        if let (Some(after), Some(before)) = (after, before)
+            && before < after
+        {
+            return Err(crate::error::Error::invalid_field(
+                "temporal_range.before",
+                "must not be earlier than temporal_range.after",
+            ));
+        }
+        self.after = after.map(|dt| dt.to_rfc3339());
+        self.before = before.map(|dt| dt.to_rfc3339());
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +64,11 @@ pub struct RecallRequest {
     pub temporal_range: Option<TemporalRange>,
     pub recency_half_life_hours: Option<f64>,
     pub hybrid_weights: Option<Vec<f32>>,
+    /// Named fusion weights per signal, given as a preset name or an
+    /// object. Takes precedence over the positional `hybrid_weights`. See
+    /// [`crate::query::hybrid`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hybrid: Option<crate::query::hybrid::HybridConfig>,
     pub rrf_k: Option<f32>,
     pub as_of: Option<String>,
     /// When set, each `ScoredMemory` is augmented with a `score_breakdown`
@@ -109,6 +139,84 @@ pub struct RecallRequest {
     /// dilution at scale. Default `None` keeps the read path unchanged.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub domain_scope: Option<crate::retrieval::DomainScope>,
+    /// How the BM25 leg of the `lexical` / `hybrid` / `auto` strategies
+    /// interprets `query`: plain terms (default), an exact phrase, or
+    /// fuzzy terms. Ignored by purely semantic strategies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_query_mode: Option<TextQueryMode>,
+    /// Per-field BM25 boosts for the same strategies, keyed by full-text
+    /// field name (`content`, `content_eng`, `content_cjk`, ...).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_field_boosts: Option<std::collections::BTreeMap<String, f32>>,
+    /// When `Some(true)`, each `ScoredMemory` carries `snippets`: the
+    /// highlighted BM25 fragment when the full-text index matched, else the
+    /// best-matching chunk for the vector hit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub with_snippets: Option<bool>,
+    /// Name of a saved [recall profile](crate::query::recall_profile) to
+    /// expand before recall runs. Fields set on this request take
+    /// precedence over the profile's settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// Optional response expansions. `"provenance"` attaches each hit's
+    /// derivation chain (source memories and events, with hashes); see
+    /// [`crate::query::lineage`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub include: Option<Vec<String>>,
+    /// Conversation thread the recall is issued from. Only consulted when
+    /// thread isolation is on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<String>,
+    /// When `Some(true)`, candidates are restricted to memories written in
+    /// [`thread_id`](Self::thread_id) (memories without a thread when it is
+    /// unset) plus [`Scope::Global`] memories, so context from one
+    /// conversation does not leak into another. `None` falls back to the
+    /// agent's [retrieval defaults](crate::query::agent_settings).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thread_isolation: Option<bool>,
+    /// What the results should *not* be about. Every candidate's score is
+    /// scaled by `1 - similarity` to this text's embedding, so memories
+    /// close to it sink whatever the strategy's score scale.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclude_query: Option<String>,
+    /// Drop memories carrying any of these tags.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclude_tags: Option<Vec<String>>,
+    /// Further phrasings of [`query`](Self::query). Each is searched with
+    /// the same strategy and filters, and the rankings are fused with
+    /// reciprocal rank fusion (`rrf_k`). At most [`MAX_QUERY_VARIANTS`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queries: Option<Vec<String>>,
+    /// When `Some(true)`, the engine's
+    /// [`QueryExpander`](crate::expansion::QueryExpander) adds paraphrases
+    /// of `query` to [`queries`](Self::queries). Fails if none is attached.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expand_query: Option<bool>,
+    /// Recall only from this [channel](crate::query::channel). The agent
+    /// must be a member.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    /// Response fields to return; `None` returns them all. Names come from
+    /// [`RESPONSE_FIELDS`]. Only the large ones, `content` and `metadata`,
+    /// are dropped when not listed: the rest are small and always returned.
+    /// Fetch the content of the hits that need it with
+    /// [`get_batch`](crate::query::get_batch).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<String>>,
+    /// Deadline for this recall in milliseconds, in place of the
+    /// engine's [latency SLO](crate::query::latency_slo) deadline. Optional
+    /// stages that would start after it are skipped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline_ms: Option<u64>,
+    /// Recall over these agents' memories, for an orchestrator (the
+    /// request's `agent_id`) holding read delegations from them. Hits are
+    /// merged and deduplicated; see [`crate::query::multi_agent`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_ids: Option<Vec<String>>,
+    /// Drop hits whose calibrated `relevance` is below this, in `[0, 1]`.
+    /// See [`crate::query::calibration`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_relevance: Option<f32>,
 }
 
 impl RecallRequest {
@@ -127,6 +235,7 @@ impl RecallRequest {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: None,
             explain: None,
@@ -137,7 +246,34 @@ impl RecallRequest {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
+        }
+    }
+
+    /// Check the request's timestamps and normalize them to UTC RFC 3339;
+    /// see [`validation`](super::validation). Recall calls this first, so
+    /// a bad `as_of` or `temporal_range` fails with the field's path.
+    pub fn normalize(&mut self) -> Result<()> {
+        self.as_of = super::validation::normalize_optional_timestamp("as_of", self.as_of.take())?;
+        if let Some(ref mut range) = self.temporal_range {
+            range.normalize()?;
         }
+        Ok(())
     }
 }
 
@@ -157,6 +293,75 @@ pub struct SupersededRecord {
     pub prior_updated_at: String,
 }
 
+/// Longest snippet fragment returned for a recall hit, in characters.
+pub const SNIPPET_MAX_CHARS: usize = 150;
+
+/// Most phrasings a recall searches besides its `query`.
+pub const MAX_QUERY_VARIANTS: usize = 8;
+
+/// Field names accepted by [`RecallRequest::fields`].
+pub const RESPONSE_FIELDS: &[&str] = &[
+    "id",
+    "content",
+    "agent_id",
+    "memory_type",
+    "scope",
+    "importance",
+    "tags",
+    "metadata",
+    "score",
+    "access_count",
+    "created_at",
+    "updated_at",
+    "score_breakdown",
+    "snippets",
+    "provenance_chain",
+    "valid_from",
+    "valid_until",
+    "channel",
+];
+
+/// Check a `fields` projection and return the large columns it keeps.
+/// `None` keeps them all.
+pub(crate) fn projected_columns(fields: Option<&[String]>) -> Result<MemoryColumns> {
+    let Some(fields) = fields else {
+        return Ok(MemoryColumns::ALL);
+    };
+    if let Some(unknown) = fields
+        .iter()
+        .find(|f| !RESPONSE_FIELDS.contains(&f.as_str()))
+    {
+        return Err(crate::error::Error::invalid_field(
+            "fields",
+            format!("unknown field '{unknown}'"),
+        ));
+    }
+    Ok(MemoryColumns {
+        content: fields.iter().any(|f| f == "content"),
+        metadata: fields.iter().any(|f| f == "metadata"),
+    })
+}
+
+/// Which retrieval signal produced a [`MatchSnippet`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnippetSource {
+    Bm25,
+    Vector,
+}
+
+/// An excerpt of a recalled memory showing why it matched.
+///
+/// Emitted when `RecallRequest.with_snippets = Some(true)`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchSnippet {
+    pub fragment: String,
+    /// Byte ranges `(start, end)` into `fragment` covering matched query
+    /// terms. Empty for vector snippets.
+    pub highlights: Vec<(usize, usize)>,
+    pub source: SnippetSource,
+}
+
 /// Per-signal score contributions for a single recall hit.
 ///
 /// Emitted when `RecallRequest.explain = Some(true)`. Each field is the
@@ -222,6 +427,12 @@ pub struct RecallResponse {
     /// hybrid (`auto`) path returns, so the raw read path is unchanged.
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub reconstruction: Option<ReconstructedBelief>,
+    /// The level of the [degradation ladder](crate::query::latency_slo)
+    /// the recall ran at, with its stage timings. Present iff the engine
+    /// has a latency SLO or the request set
+    /// [`deadline_ms`](RecallRequest::deadline_ms).
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub degradation: Option<crate::query::latency_slo::DegradationReport>,
 }
 
 impl RecallResponse {
@@ -235,6 +446,7 @@ impl RecallResponse {
             evidence_selection: None,
             retained_evidence: None,
             reconstruction: None,
+            degradation: None,
         }
     }
 }
@@ -269,19 +481,43 @@ pub struct ReconstructedBelief {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScoredMemory {
     pub id: Uuid,
+    /// Empty and omitted when left out by [`RecallRequest::fields`].
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub content: String,
     pub agent_id: String,
     pub memory_type: MemoryType,
     pub scope: Scope,
     pub importance: f32,
     pub tags: Vec<String>,
+    /// `null` and omitted when left out by [`RecallRequest::fields`].
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
     pub metadata: serde_json::Value,
     pub score: f32,
+    /// `score` calibrated to `[0, 1]` for the strategy that produced it;
+    /// see [`crate::query::calibration`]. Set on recall hits only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relevance: Option<f32>,
     pub access_count: u64,
     pub created_at: String,
     pub updated_at: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub score_breakdown: Option<ScoreBreakdown>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippets: Option<Vec<MatchSnippet>>,
+    /// Emitted when `RecallRequest.include` contains `"provenance"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance_chain: Option<super::lineage::ProvenanceChain>,
+    /// The memory's fact validity window. See [`crate::query::validity`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<String>,
+    /// The [channel](crate::query::channel) the memory belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    /// In a multi-agent recall, the listed agent the hit was recalled for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recalled_for: Option<String>,
 }
 
 impl From<(MemoryRecord, f32)> for ScoredMemory {
@@ -296,37 +532,163 @@ impl From<(MemoryRecord, f32)> for ScoredMemory {
             tags: record.tags,
             metadata: record.metadata,
             score,
+            relevance: None,
             access_count: record.access_count,
             created_at: record.created_at,
             updated_at: record.updated_at,
             score_breakdown: None,
+            snippets: None,
+            provenance_chain: None,
+            valid_from: record.valid_from,
+            valid_until: record.valid_until,
+            channel: record.channel,
+            recalled_for: None,
         }
     }
 }
 
-/// Get a memory by ID, checking cache first then falling back to storage.
-async fn get_memory_cached(engine: &MnemoEngine, id: Uuid) -> Result<Option<MemoryRecord>> {
-    if let Some(ref cache) = engine.cache
-        && let Some(record) = cache.get(id)
-    {
-        return Ok(Some(record));
+impl ScoredMemory {
+    /// Blank the large fields `columns` leaves out.
+    pub(crate) fn project(&mut self, columns: MemoryColumns) {
+        if !columns.content {
+            self.content = String::new();
+        }
+        if !columns.metadata {
+            self.metadata = serde_json::Value::Null;
+        }
     }
-    let result = engine.storage.get_memory(id).await?;
-    if let Some(ref record) = result
-        && let Some(ref cache) = engine.cache
-    {
-        cache.put(record.clone());
+}
+
+/// Build the snippets for one recall hit from its (decrypted) content.
+///
+/// A BM25 highlight wins when the full-text index has one. Otherwise the hit
+/// came from the vector side and the best-matching chunk is returned; until
+/// memories are chunked that is the leading fragment of the whole memory.
+fn match_snippets(
+    engine: &MnemoEngine,
+    query: &str,
+    content: &str,
+    options: &TextSearchOptions,
+) -> Vec<MatchSnippet> {
+    if let Some(ref ft) = engine.full_text {
+        match ft.highlight(query, content, SNIPPET_MAX_CHARS, options) {
+            Ok(Some(snippet)) => {
+                return vec![MatchSnippet {
+                    fragment: snippet.fragment,
+                    highlights: snippet.highlights,
+                    source: SnippetSource::Bm25,
+                }];
+            }
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!(error = %e, "snippet highlighting failed; falling back to vector snippet");
+            }
+        }
+    }
+    if content.is_empty() {
+        return Vec::new();
     }
-    Ok(result)
+    let fragment = match content.char_indices().nth(SNIPPET_MAX_CHARS) {
+        Some((end, _)) => content[..end].to_string(),
+        None => content.to_string(),
+    };
+    vec![MatchSnippet {
+        fragment,
+        highlights: Vec::new(),
+        source: SnippetSource::Vector,
+    }]
+}
+
+/// Get a memory by ID, checking cache first then falling back to storage,
+/// which reads only the large columns in `columns`. Within a recall, every
+/// call for the same ID returns the copy the first one read (see
+/// [`snapshot`](super::snapshot)), so a recall hydrates with one set of
+/// columns throughout.
+pub(crate) async fn get_memory_cached(
+    engine: &MnemoEngine,
+    id: Uuid,
+    columns: MemoryColumns,
+) -> Result<Option<MemoryRecord>> {
+    super::snapshot::hydrate(id, async {
+        if let Some(ref cache) = engine.cache
+            && let Some(record) = cache.get(id)
+        {
+            return Ok(Some(record));
+        }
+        // The cache holds whole rows only.
+        if columns != MemoryColumns::ALL {
+            return Ok(engine.storage.get_memories(&[id], columns).await?.pop());
+        }
+        let result = engine.storage.get_memory(id).await?;
+        if let Some(ref record) = result
+            && let Some(ref cache) = engine.cache
+        {
+            cache.put(record.clone());
+        }
+        Ok(result)
+    })
+    .await
 }
 
+/// Run a recall at a read version taken when it starts; see
+/// [`snapshot`](super::snapshot).
 pub async fn execute(engine: &MnemoEngine, request: RecallRequest) -> Result<RecallResponse> {
-    let limit = request.limit.unwrap_or(10).min(100);
+    super::snapshot::read(execute_at_read_version(engine, request, true)).await
+}
+
+/// Run a recall without recording it: no access timestamps, access log
+/// entries, profile use or `MemoryRead` event. Used by
+/// [`shadow_recall`](super::shadow_recall), whose results never reach a
+/// caller.
+pub(crate) async fn execute_unrecorded(
+    engine: &MnemoEngine,
+    request: RecallRequest,
+) -> Result<RecallResponse> {
+    super::snapshot::read(execute_at_read_version(engine, request, false)).await
+}
+
+pub(crate) async fn execute_at_read_version(
+    engine: &MnemoEngine,
+    mut request: RecallRequest,
+    record: bool,
+) -> Result<RecallResponse> {
+    request.normalize()?;
+    if request.agent_ids.is_some() {
+        return Box::pin(super::multi_agent::execute(engine, request, record)).await;
+    }
+    let mut budget = RecallBudget::start(engine, request.deadline_ms, record);
     let agent_id = request
         .agent_id
         .clone()
         .unwrap_or_else(|| engine.default_agent_id.clone());
     super::validate_agent_id(&agent_id)?;
+    super::recall_profile::expand(engine, &mut request, &agent_id, record).await?;
+    super::agent_settings::expand(engine, &mut request, &agent_id).await?;
+    if let Some(ref channel) = request.channel {
+        super::channel::check_read(engine, channel, &agent_id).await?;
+    }
+    if let Some(ref hybrid) = request.hybrid {
+        hybrid.validate()?;
+    }
+    if let Some(min_relevance) = request.min_relevance
+        && !(0.0..=1.0).contains(&min_relevance)
+    {
+        return Err(crate::error::Error::invalid_field(
+            "min_relevance",
+            "must be between 0 and 1",
+        ));
+    }
+    let limit = engine.limits().recall_limit(request.limit.unwrap_or(10));
+    let include_provenance = match request.include {
+        Some(ref includes) => {
+            super::lineage::validate_includes(includes)?;
+            includes
+                .iter()
+                .any(|i| i == super::lineage::INCLUDE_PROVENANCE)
+        }
+        None => false,
+    };
+    let columns = projected_columns(request.fields.as_deref())?;
 
     // Determine strategy. v0.4.4: prefer the typed
     // `mode: Option<RetrievalMode>` field when set; fall back to the
@@ -365,190 +727,736 @@ pub async fn execute(engine: &MnemoEngine, request: RecallRequest) -> Result<Rec
         });
     }
 
-    // Compute query embedding (needed for semantic/hybrid/auto)
-    let query_embedding = engine.embedding.embed(&request.query).await?;
-
-    // Pre-compute accessible memory IDs for permission-safe ANN pre-filtering
-    let accessible_ids: HashSet<Uuid> = engine
-        .storage
-        .list_accessible_memory_ids(&agent_id, super::MAX_BATCH_QUERY_LIMIT)
-        .await?
-        .into_iter()
-        .collect();
-    let perm_filter = |id: Uuid| accessible_ids.contains(&id);
-
-    let mut scored_memories: Vec<(MemoryRecord, f32)> = Vec::new();
-    let mut breakdowns: std::collections::HashMap<Uuid, ScoreBreakdown> =
-        std::collections::HashMap::new();
+    let text_options = TextSearchOptions {
+        mode: request.text_query_mode.unwrap_or_default(),
+        field_boosts: request.text_field_boosts.clone().unwrap_or_default(),
+    };
 
-    match strategy {
-        "lexical" => {
-            // BM25-only path
-            if let Some(ref ft) = engine.full_text {
-                let bm25_results = ft.search(&request.query, limit * 3)?;
-                for (id, score) in bm25_results {
-                    if let Some(record) = get_memory_cached(engine, id).await?
-                        && passes_filters(&record, &request, &agent_id, engine).await
-                    {
-                        scored_memories.push((record, score));
+    // Compute query embedding (needed for semantic/hybrid/auto). With a
+    // full-text index to fall back on, a slow embedding is abandoned at
+    // the deadline.
+    let query_embedding = if budget.level() == DegradationLevel::Bm25Only {
+        budget.fall_back_to_bm25();
+        Vec::new()
+    } else {
+        let started = std::time::Instant::now();
+        let embedding = match budget.remaining() {
+            Some(remaining) if engine.full_text.is_some() => {
+                match tokio::time::timeout(remaining, engine.embedding.embed(&request.query)).await
+                {
+                    Ok(embedding) => embedding?,
+                    Err(_) => {
+                        budget.fall_back_to_bm25();
+                        Vec::new()
                     }
                 }
             }
+            _ => engine.embedding.embed(&request.query).await?,
+        };
+        budget.record(RecallStage::Embed, started.elapsed());
+        embedding
+    };
+    let search_strategy = budget.strategy(strategy);
+    let hydration = hydration_columns(&request, strategy, columns);
+
+    // Pre-compute accessible memory IDs for permission-safe ANN pre-filtering.
+    // A point-in-time recall also leaves out memories created after `as_of`,
+    // so the vector search spends its candidates on ones that can match.
+    let accessible_ids: HashSet<Uuid> = match request.as_of {
+        Some(ref as_of) => {
+            engine
+                .storage
+                .list_accessible_memory_ids_as_of(&agent_id, as_of, super::MAX_BATCH_QUERY_LIMIT)
+                .await?
         }
-        "semantic" => {
-            // Vector-only path with permission pre-filtering
-            let search_results =
-                engine
-                    .index
-                    .filtered_search(&query_embedding, limit * 3, &perm_filter)?;
-            for (id, distance) in search_results {
-                if let Some(record) = get_memory_cached(engine, id).await?
-                    && passes_filters(&record, &request, &agent_id, engine).await
-                {
-                    let score = 1.0 - distance;
-                    scored_memories.push((record, score));
-                }
-            }
+        None => {
+            engine
+                .storage
+                .list_accessible_memory_ids(&agent_id, super::MAX_BATCH_QUERY_LIMIT)
+                .await?
         }
-        "domain_scoped" => {
-            // v0.4.15 — domain-scoped recall (MASDR-RAG, arXiv:2606.11350).
-            // Restrict the candidate universe to the metadata-defined
-            // sub-corpus BEFORE the dense similarity step, so off-domain
-            // (but semantically similar) records can never enter the
-            // top-k. Then a single vector pass over the sub-corpus.
-            //
-            // The sub-corpus id-set is resolved from storage by the
-            // `DomainScope` predicate and composed with the permission
-            // filter, so the ANN sees only (accessible ∩ in-domain) ids.
-            let domain_ids: Option<HashSet<Uuid>> = match request.domain_scope.as_ref() {
-                Some(scope) if !scope.is_empty() => {
-                    // Coarse narrowing on org_id at the storage layer, then
-                    // exact predicate matching (namespace / doc_class / tags).
-                    let coarse = MemoryFilter {
-                        agent_id: None,
-                        memory_type: None,
-                        scope: None,
-                        tags: None,
-                        min_importance: None,
-                        org_id: scope.org_id.clone(),
-                        thread_id: None,
-                        include_deleted: false,
-                    };
-                    let records = engine
-                        .storage
-                        .list_memories(&coarse, super::MAX_BATCH_QUERY_LIMIT, 0)
-                        .await?;
-                    Some(
-                        records
-                            .iter()
-                            .filter(|r| scope.matches(r))
-                            .map(|r| r.id)
-                            .collect(),
-                    )
-                }
-                // DomainScoped selected without a predicate degrades to a
-                // plain vector pass (no extra restriction).
-                _ => None,
-            };
-
-            let domain_filter = |id: Uuid| {
-                perm_filter(id) && domain_ids.as_ref().map(|d| d.contains(&id)).unwrap_or(true)
+    }
+    .into_iter()
+    .collect();
+    let mut breakdowns: std::collections::HashMap<Uuid, ScoreBreakdown> =
+        std::collections::HashMap::new();
+    let rephrased = request.queries.as_ref().is_some_and(|q| !q.is_empty())
+        || request.expand_query.unwrap_or(false);
+    let variants = if rephrased && !budget.runs(RecallStage::Rerank) {
+        vec![request.query.clone()]
+    } else {
+        query_variants(engine, &request).await?
+    };
+    let started = std::time::Instant::now();
+    let mut scored_memories = if variants.len() == 1 {
+        search_candidates(
+            engine,
+            &request,
+            search_strategy,
+            &agent_id,
+            limit,
+            &query_embedding,
+            &accessible_ids,
+            &text_options,
+            hydration,
+            &mut breakdowns,
+            &mut budget,
+        )
+        .await?
+    } else {
+        // Every phrasing runs the strategy on its own and the rankings are
+        // fused, so a memory any one of them puts near the top ranks high.
+        let extra: Vec<&str> = variants[1..].iter().map(String::as_str).collect();
+        let extra_embeddings = engine.embedding.embed_batch(&extra).await?;
+        let mut ranked_lists: Vec<Vec<(Uuid, f32)>> = Vec::with_capacity(variants.len());
+        let mut records: std::collections::HashMap<Uuid, MemoryRecord> =
+            std::collections::HashMap::new();
+        for (i, variant) in variants.iter().enumerate() {
+            let mut variant_request = request.clone();
+            variant_request.query = variant.clone();
+            let embedding = if i == 0 {
+                &query_embedding
+            } else {
+                &extra_embeddings[i - 1]
             };
-            let search_results =
-                engine
-                    .index
-                    .filtered_search(&query_embedding, limit * 3, &domain_filter)?;
-            for (id, distance) in search_results {
-                if let Some(record) = get_memory_cached(engine, id).await?
-                    && passes_filters(&record, &request, &agent_id, engine).await
-                {
-                    let score = 1.0 - distance;
-                    scored_memories.push((record, score));
-                }
+            // Score breakdowns describe the primary query only.
+            let mut variant_breakdowns = std::collections::HashMap::new();
+            let mut candidates = search_candidates(
+                engine,
+                &variant_request,
+                search_strategy,
+                &agent_id,
+                limit,
+                embedding,
+                &accessible_ids,
+                &text_options,
+                hydration,
+                &mut variant_breakdowns,
+                &mut budget,
+            )
+            .await?;
+            if i == 0 {
+                breakdowns = variant_breakdowns;
+            }
+            candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+            ranked_lists.push(candidates.iter().map(|(r, s)| (r.id, *s)).collect());
+            for (record, _) in candidates {
+                records.entry(record.id).or_insert(record);
             }
         }
-        "graph" => {
-            // Seed from vector results with permission pre-filtering, then expand via graph relations
-            let search_results =
-                engine
-                    .index
-                    .filtered_search(&query_embedding, limit * 3, &perm_filter)?;
-            let mut seeds: Vec<(Uuid, f32)> = Vec::new();
-            for (id, distance) in &search_results {
-                if let Some(record) = get_memory_cached(engine, *id).await?
-                    && passes_filters(&record, &request, &agent_id, engine).await
-                {
-                    seeds.push((*id, 1.0 - distance));
-                }
+        crate::query::retrieval::reciprocal_rank_fusion(
+            &ranked_lists,
+            request.rrf_k.unwrap_or(60.0),
+        )
+        .into_iter()
+        .filter_map(|(id, score)| records.remove(&id).map(|record| (record, score)))
+        .collect()
+    };
+    budget.record(RecallStage::Retrieve, started.elapsed());
+
+    // Prefer facts that hold now. An `as_of` recall has already dropped
+    // the ones that did not hold at `as_of`.
+    if request.as_of.is_none() {
+        let now = chrono::Utc::now();
+        for (record, score) in scored_memories.iter_mut() {
+            if !record.is_valid_at(now) {
+                *score *= super::validity::OUT_OF_WINDOW_PENALTY;
             }
+        }
+    }
 
-            // Collect graph-expanded results with configurable multi-hop traversal
-            let max_hops = 2;
-            let mut seen: HashSet<Uuid> = seeds.iter().map(|(id, _)| *id).collect();
-            let mut graph_ranked: Vec<(Uuid, f32)> = Vec::new();
-
-            // Seeds get score 1.0
-            for &(id, _) in &seeds {
-                graph_ranked.push((id, 1.0));
-            }
+    // A maintained profile of an entity the query names beats the
+    // scattered facts it was built from.
+    if !hydration.metadata {
+        super::entity_summary::hydrate_summary_metadata(engine, &mut scored_memories).await?;
+    }
+    for (record, score) in scored_memories.iter_mut() {
+        if super::entity_summary::summarizes_entity_in(record, &request.query) {
+            *score *= super::entity_summary::ENTITY_SUMMARY_BOOST;
+        }
+    }
 
-            // Multi-hop expansion with exponential decay
-            let mut frontier: Vec<Uuid> = seeds.iter().map(|(id, _)| *id).collect();
-            let mut decay = 0.5_f32;
-            for _hop in 0..max_hops {
-                let mut next_frontier: Vec<Uuid> = Vec::new();
-                for &id in &frontier {
-                    let from_rels = engine.storage.get_relations_from(id).await?;
-                    let to_rels = engine.storage.get_relations_to(id).await?;
-                    for rel in from_rels.iter().chain(to_rels.iter()) {
-                        let related_id = if rel.source_id == id {
-                            rel.target_id
-                        } else {
-                            rel.source_id
-                        };
-                        if seen.insert(related_id)
-                            && let Some(record) = get_memory_cached(engine, related_id).await?
-                            && passes_filters(&record, &request, &agent_id, engine).await
-                        {
-                            graph_ranked.push((related_id, decay));
-                            next_frontier.push(related_id);
-                        }
-                    }
-                }
-                frontier = next_frontier;
-                decay *= 0.5;
+    // Push down whatever resembles the exclusion query.
+    if let Some(ref exclude_query) = request.exclude_query
+        && !exclude_query.trim().is_empty()
+        && budget.runs(RecallStage::Rerank)
+    {
+        let started = std::time::Instant::now();
+        let exclude_embedding = engine.embedding.embed(exclude_query).await?;
+        for (record, score) in scored_memories.iter_mut() {
+            if let Some(ref embedding) = record.embedding {
+                let similarity = cosine_similarity(embedding, &exclude_embedding).max(0.0);
+                *score *= 1.0 - similarity;
             }
+        }
+        budget.record(RecallStage::Rerank, started.elapsed());
+    }
 
-            // Use RRF fusion with vector + graph lists
-            let mut v_sorted: Vec<(Uuid, f32)> = seeds.clone();
-            v_sorted.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-            graph_ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-
-            let ranked_lists = vec![v_sorted, graph_ranked];
-            let rrf_k = request.rrf_k.unwrap_or(60.0);
-            let fused = if let Some(ref weights) = request.hybrid_weights {
-                crate::query::retrieval::weighted_reciprocal_rank_fusion(
-                    &ranked_lists,
-                    rrf_k,
-                    weights,
-                )
-            } else {
-                crate::query::retrieval::reciprocal_rank_fusion(&ranked_lists, rrf_k)
-            };
+    // Calibrate the final scores to 0-1 relevance, per strategy.
+    let calibration_key = if variants.len() > 1 {
+        "multi_query"
+    } else {
+        match search_strategy {
+            "lexical" | "semantic" | "domain_scoped" | "graph" | "exact" => search_strategy,
+            _ if engine.full_text.is_some() => "hybrid",
+            _ => "semantic",
+        }
+    };
+    let calibration = engine
+        .score_calibration
+        .observe(calibration_key, scored_memories.iter().map(|(_, s)| *s));
+    if let Some(min_relevance) = request.min_relevance {
+        scored_memories.retain(|(_, score)| calibration.relevance(*score) >= min_relevance);
+    }
 
-            for (id, score) in fused {
-                if let Some(record) = get_memory_cached(engine, id).await?
-                    && passes_filters(&record, &request, &agent_id, engine).await
-                {
-                    scored_memories.push((record, score));
-                }
-            }
+    // Sort by score descending
+    scored_memories.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    let redacted = match engine.shared_recall_privacy {
+        Some(ref privacy) => {
+            super::privacy::enforce(engine, privacy, &agent_id, &mut scored_memories, limit).await
         }
-        "exact" => {
-            // Filter-based exact matching, no embedding needed
-            // When as_of is set, include deleted records so the as_of filter can evaluate them
+        None => HashSet::new(),
+    };
+    scored_memories.truncate(limit);
+
+    // v0.4.12 — opt-in cost-aware evidence budget. Runs only when the
+    // caller set `request.evidence_budget`. The selector operates on
+    // the already-ranked list and returns the smallest prefix that
+    // clears the sufficiency bar (capped by `max_evidence`); it never
+    // reorders, so the top-k cosine/RRF ordering is preserved. Applied
+    // BEFORE `touch_memory` so we do not mark-accessed evidence the
+    // budget trimmed away (cost-aware on the write side too). See
+    // [`crate::query::evidence`].
+    let evidence_selection = if let Some(ref budget) = request.evidence_budget {
+        let cosine_default = crate::query::evidence::CosineScorer;
+        let scorer: &dyn crate::query::evidence::EvidenceScorer =
+            match (budget.scorer, engine.evidence_scorer.as_ref()) {
+                (crate::query::evidence::ScorerKind::Delta, Some(s)) => s.as_ref(),
+                _ => &cosine_default,
+            };
+        // Pass the query embedding only when it is non-degenerate
+        // (NoopEmbedding yields all-zero vectors, for which cosine is
+        // undefined and the scorer should fall back to retrieval score).
+        let q_emb: Option<&[f32]> = if query_embedding.iter().any(|v| *v != 0.0) {
+            Some(query_embedding.as_slice())
+        } else {
+            None
+        };
+        let candidates: Vec<crate::query::evidence::EvidenceCandidate<'_>> = scored_memories
+            .iter()
+            .map(|(r, score)| crate::query::evidence::EvidenceCandidate {
+                content: &r.content,
+                embedding: r.embedding.as_deref(),
+                retrieval_score: *score,
+            })
+            .collect();
+        let selection = crate::query::evidence::select_within_budget(
+            &candidates,
+            budget,
+            scorer,
+            &request.query,
+            q_emb,
+        );
+        let keep = selection.keep;
+        drop(candidates);
+        scored_memories.truncate(keep);
+        Some(selection.report)
+    } else {
+        None
+    };
+
+    let _total_pre_resolver = scored_memories.len();
+
+    // Touch accessed memories
+    if record {
+        engine.score_calibration.record(
+            &agent_id,
+            calibration_key,
+            scored_memories.iter().map(|(r, s)| (r.id, *s)).collect(),
+        );
+        let accessed: Vec<Uuid> = scored_memories.iter().map(|(r, _)| r.id).collect();
+        record_access(engine, &agent_id, &request.query, &accessed).await;
+    }
+
+    // Decrypt content if encryption is configured and it was read at all
+    if engine.encrypts_content() && hydration.content {
+        for (record, _) in &mut scored_memories {
+            match engine.decrypt_content(&record.content) {
+                Ok(plaintext) => record.content = plaintext,
+                Err(e) => {
+                    tracing::error!(memory_id = %record.id, error = %e, "failed to decrypt memory content");
+                    record.content = "[content unavailable: decryption error]".to_string();
+                }
+            }
+        }
+    }
+
+    // Keep the underlying records around if the caller asked for a
+    // provenance receipt (Task B1) — the HMAC chain needs the
+    // content_hash + prev_hash off each record before they get
+    // collapsed into ScoredMemory.
+    let provenance_records: Option<Vec<MemoryRecord>> =
+        if request.with_provenance == Some(true) && engine.provenance_signer.is_some() {
+            Some(scored_memories.iter().map(|(r, _)| r.clone()).collect())
+        } else {
+            None
+        };
+
+    let mut aliases = super::pseudonym::recall_aliases(
+        engine,
+        &agent_id,
+        scored_memories.iter().map(|(r, _)| r),
+        &redacted,
+    )
+    .await?;
+
+    let mut provenance_chains: std::collections::HashMap<Uuid, super::lineage::ProvenanceChain> =
+        std::collections::HashMap::new();
+    if include_provenance {
+        for (record, _) in scored_memories
+            .iter()
+            .filter(|(r, _)| !redacted.contains(&r.id) && !aliases.contains_key(&r.id))
+        {
+            let chain = super::lineage::build_chain(engine, &agent_id, record).await?;
+            provenance_chains.insert(record.id, chain);
+        }
+    }
+
+    let memories: Vec<ScoredMemory> = scored_memories
+        .into_iter()
+        .map(|(record, score)| {
+            let id = record.id;
+            let mut scored = ScoredMemory::from((record, score));
+            scored.relevance = Some(calibration.relevance(score));
+            if let Some(breakdown) = breakdowns.remove(&id) {
+                scored.score_breakdown = Some(breakdown);
+            }
+            scored.provenance_chain = provenance_chains.remove(&id);
+            if redacted.contains(&id) {
+                scored.agent_id = super::privacy::REDACTED_AGENT_ID.to_string();
+            } else if let Some(alias) = aliases.remove(&id) {
+                scored.agent_id = alias;
+            }
+            if request.with_snippets == Some(true) {
+                scored.snippets = Some(match_snippets(
+                    engine,
+                    &request.query,
+                    &scored.content,
+                    &text_options,
+                ));
+            }
+            scored
+        })
+        .collect();
+
+    // v0.4.7 — opt-in current-fact resolver post-process. Runs only
+    // when the caller set `request.current_fact_resolver`. The
+    // resolver groups by `cfg.fact_key`, keeps the most-recent
+    // write per group, and (optionally) returns the older versions
+    // as a supersession chain. See
+    // [`crate::query::current_fact_resolver`] for the MINTEval
+    // arXiv:2605.18565 anchor + the contract.
+    let (memories, superseded_chain) = if let Some(ref cfg) = request.current_fact_resolver {
+        let out = crate::query::current_fact_resolver::resolve(cfg, memories);
+        let chain = if cfg.include_supersession_chain && !out.superseded.is_empty() {
+            Some(out.superseded)
+        } else {
+            None
+        };
+        (out.kept, chain)
+    } else {
+        (memories, None)
+    };
+    let total = memories.len();
+
+    // v0.5.1 — active reconstruction (MRAgent, arXiv:2606.06036). When the
+    // caller selected the `reconstruct` strategy, walk the memory graph from
+    // the retrieved hits to gather linked/causal context and synthesise a
+    // deterministic belief-state node returned ALONGSIDE the raw hits. The
+    // `memories` list above is untouched, so this is purely additive.
+    let reconstruction = if strategy == "reconstruct" && budget.runs(RecallStage::Graph) {
+        Some(reconstruct_belief(engine, &request, &agent_id, &memories).await)
+    } else {
+        None
+    };
+
+    // v0.4.8 — opt-in orientation cache. Runs only when the caller
+    // set `request.orientation_cache` AND the engine has an
+    // `OrientationCacheStore` attached. Per-namespace map is
+    // updated from the hits + a bounded rendering is returned. See
+    // [`crate::query::orientation_cache`] for the PEEK
+    // arXiv:2605.19932 anchor + the contract.
+    let orientation_rendered = match (
+        request.orientation_cache.as_ref(),
+        engine.orientation_cache_store.as_ref(),
+    ) {
+        (Some(cfg), Some(store)) => {
+            let ns = crate::query::orientation_cache::resolve_namespace(
+                cfg,
+                &agent_id,
+                request.org_id.as_deref(),
+            );
+            let rendered =
+                crate::query::orientation_cache::update_and_render(store, cfg, &ns, &memories);
+            if cfg.include_in_response {
+                Some(rendered)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    };
+
+    // Emit MemoryRead event with hash chain linking (fire-and-forget).
+    // Unrecorded recalls leave the chain and the logical clock alone.
+    if record {
+        record_read_event(engine, &agent_id, &request.query, total, strategy).await;
+    }
+
+    // v0.4.0-rc3 (B1) — sign a ReadProvenance over the recalled
+    // records when the caller opted in. Failures are non-fatal:
+    // missing signer or HMAC error degrades to "no provenance" so the
+    // recall still returns. The caller can detect by `provenance.is_none()`.
+    let provenance = if let (Some(records), Some(signer)) =
+        (provenance_records, engine.provenance_signer.as_ref())
+    {
+        match signer.sign(&agent_id, &request.query, &records) {
+            Ok(p) => Some(p),
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to sign read provenance; degrading to no-provenance response");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // EMBER (arXiv:2606.05894) — opt-in budgeted evidence retention.
+    // Runs only when the caller set `request.retained_token_budget`.
+    // Builds verbatim evidence capsules (excerpt + retrieval key) packed
+    // under the token cap, ranked by `recency × hit-rate` recoverability.
+    // Computed from the FINAL `memories` (post current-fact resolver,
+    // decrypted) and returned ALONGSIDE them — `memories` is not
+    // modified, so the default read path is unaffected. See
+    // [`crate::query::retained`].
+    let retained_evidence = request.retained_token_budget.map(|budget| {
+        let retain_now = chrono::Utc::now();
+        let candidates: Vec<crate::query::retained::RetentionCandidate<'_>> = memories
+            .iter()
+            .map(|m| {
+                let age_hours = chrono::DateTime::parse_from_rfc3339(&m.updated_at)
+                    .or_else(|_| chrono::DateTime::parse_from_rfc3339(&m.created_at))
+                    .map(|ts| {
+                        (retain_now - ts.with_timezone(&chrono::Utc)).num_seconds() as f64 / 3600.0
+                    })
+                    .unwrap_or(0.0);
+                crate::query::retained::RetentionCandidate {
+                    id: m.id,
+                    content: &m.content,
+                    access_count: m.access_count,
+                    age_hours,
+                    retrieval_score: m.score,
+                }
+            })
+            .collect();
+        crate::query::retained::retain_within_budget(
+            &candidates,
+            budget,
+            crate::query::retained::DEFAULT_EXCERPT_TOKENS,
+        )
+    });
+
+    let degradation = budget.finish();
+
+    // Projection comes last: the steps above may read columns the caller
+    // left out.
+    let mut memories = memories;
+    for memory in &mut memories {
+        memory.project(columns);
+    }
+
+    Ok(RecallResponse {
+        memories,
+        total,
+        provenance,
+        superseded: superseded_chain,
+        orientation_cache: orientation_rendered,
+        evidence_selection,
+        retained_evidence,
+        reconstruction,
+        degradation,
+    })
+}
+
+/// The large columns recall hydrates candidates with: the projected
+/// `columns` plus those a switched-on step reads. Steps that compare
+/// embeddings need the whole row, which only [`MemoryColumns::ALL`]
+/// hydrates.
+fn hydration_columns(
+    request: &RecallRequest,
+    strategy: &str,
+    columns: MemoryColumns,
+) -> MemoryColumns {
+    let compares_embeddings = request.evidence_budget.is_some()
+        || request
+            .exclude_query
+            .as_ref()
+            .is_some_and(|q| !q.trim().is_empty());
+    if compares_embeddings {
+        return MemoryColumns::ALL;
+    }
+    MemoryColumns {
+        content: columns.content
+            || request.with_snippets == Some(true)
+            || request.retained_token_budget.is_some()
+            || request.orientation_cache.is_some()
+            || strategy == "reconstruct",
+        metadata: columns.metadata || request.current_fact_resolver.is_some(),
+    }
+}
+
+/// The request's query followed by its extra phrasings: the caller's
+/// [`queries`](RecallRequest::queries) and, with
+/// [`expand_query`](RecallRequest::expand_query), the engine's
+/// paraphrases. Blank and repeated phrasings are dropped.
+async fn query_variants(engine: &MnemoEngine, request: &RecallRequest) -> Result<Vec<String>> {
+    let mut phrasings = request.queries.clone().unwrap_or_default();
+    if phrasings.len() > MAX_QUERY_VARIANTS {
+        return Err(crate::error::Error::invalid_field(
+            "queries",
+            format!("at most {MAX_QUERY_VARIANTS} queries"),
+        ));
+    }
+    if request.expand_query.unwrap_or(false) {
+        let Some(ref expander) = engine.query_expander else {
+            return Err(crate::error::Error::Validation(
+                "expand_query needs a query expander; attach one with \
+                 MnemoEngine::with_query_expander"
+                    .to_string(),
+            ));
+        };
+        // A failed expansion degrades to the phrasings the caller gave.
+        match expander.expand(&request.query).await {
+            Ok(paraphrases) => phrasings.extend(paraphrases),
+            Err(e) => {
+                tracing::warn!(expander = expander.name(), error = %e, "query expansion failed");
+            }
+        }
+    }
+
+    let mut seen: HashSet<String> = HashSet::from([request.query.trim().to_lowercase()]);
+    let mut variants = vec![request.query.clone()];
+    for phrasing in phrasings {
+        let phrasing = phrasing.trim();
+        if variants.len() > MAX_QUERY_VARIANTS {
+            break;
+        }
+        if !phrasing.is_empty() && seen.insert(phrasing.to_lowercase()) {
+            variants.push(phrasing.to_string());
+        }
+    }
+    Ok(variants)
+}
+
+/// Run `strategy` for one phrasing of the query: candidates that pass the
+/// request's filters with their strategy scores, unsorted.
+#[allow(clippy::too_many_arguments)]
+async fn search_candidates(
+    engine: &MnemoEngine,
+    request: &RecallRequest,
+    strategy: &str,
+    agent_id: &str,
+    limit: usize,
+    query_embedding: &[f32],
+    accessible_ids: &HashSet<Uuid>,
+    text_options: &TextSearchOptions,
+    columns: MemoryColumns,
+    breakdowns: &mut std::collections::HashMap<Uuid, ScoreBreakdown>,
+    budget: &mut RecallBudget<'_>,
+) -> Result<Vec<(MemoryRecord, f32)>> {
+    let perm_filter = |id: Uuid| accessible_ids.contains(&id);
+    let mut scored_memories: Vec<(MemoryRecord, f32)> = Vec::new();
+
+    match strategy {
+        "lexical" => {
+            // BM25-only path
+            if let Some(ref ft) = engine.full_text {
+                let bm25_results =
+                    ft.search_with_options(&request.query, limit * 3, text_options)?;
+                for (id, score) in bm25_results {
+                    if let Some(record) = get_memory_cached(engine, id, columns).await?
+                        && passes_filters(&record, request, agent_id, engine).await
+                    {
+                        scored_memories.push((record, score));
+                    }
+                }
+            }
+        }
+        "semantic" => {
+            // Vector-only path with permission pre-filtering
+            let search_results =
+                engine
+                    .index
+                    .search_within(query_embedding, limit * 3, accessible_ids)?;
+            for (id, distance) in search_results {
+                if let Some(record) = get_memory_cached(engine, id, columns).await?
+                    && passes_filters(&record, request, agent_id, engine).await
+                {
+                    let score = 1.0 - distance;
+                    scored_memories.push((record, score));
+                }
+            }
+        }
+        "domain_scoped" => {
+            // v0.4.15 — domain-scoped recall (MASDR-RAG, arXiv:2606.11350).
+            // Restrict the candidate universe to the metadata-defined
+            // sub-corpus BEFORE the dense similarity step, so off-domain
+            // (but semantically similar) records can never enter the
+            // top-k. Then a single vector pass over the sub-corpus.
+            //
+            // The sub-corpus id-set is resolved from storage by the
+            // `DomainScope` predicate and composed with the permission
+            // filter, so the ANN sees only (accessible ∩ in-domain) ids.
+            let domain_ids: Option<HashSet<Uuid>> = match request.domain_scope.as_ref() {
+                Some(scope) if !scope.is_empty() => {
+                    // Coarse narrowing on org_id at the storage layer, then
+                    // exact predicate matching (namespace / doc_class / tags).
+                    let coarse = MemoryFilter {
+                        agent_id: None,
+                        memory_type: None,
+                        scope: None,
+                        tags: None,
+                        min_importance: None,
+                        org_id: scope.org_id.clone(),
+                        thread_id: None,
+                        include_deleted: false,
+                    };
+                    let records = engine
+                        .storage
+                        .list_memories(&coarse, super::MAX_BATCH_QUERY_LIMIT, 0)
+                        .await?;
+                    Some(
+                        records
+                            .iter()
+                            .filter(|r| scope.matches(r))
+                            .map(|r| r.id)
+                            .collect(),
+                    )
+                }
+                // DomainScoped selected without a predicate degrades to a
+                // plain vector pass (no extra restriction).
+                _ => None,
+            };
+
+            let domain_filter = |id: Uuid| {
+                perm_filter(id) && domain_ids.as_ref().map(|d| d.contains(&id)).unwrap_or(true)
+            };
+            let search_results =
+                engine
+                    .index
+                    .filtered_search(query_embedding, limit * 3, &domain_filter)?;
+            for (id, distance) in search_results {
+                if let Some(record) = get_memory_cached(engine, id, columns).await?
+                    && passes_filters(&record, request, agent_id, engine).await
+                {
+                    let score = 1.0 - distance;
+                    scored_memories.push((record, score));
+                }
+            }
+        }
+        "graph" => {
+            // Seed from vector results with permission pre-filtering, then expand via graph relations
+            let search_results =
+                engine
+                    .index
+                    .search_within(query_embedding, limit * 3, accessible_ids)?;
+            let mut seeds: Vec<(Uuid, f32)> = Vec::new();
+            for (id, distance) in &search_results {
+                if let Some(record) = get_memory_cached(engine, *id, columns).await?
+                    && passes_filters(&record, request, agent_id, engine).await
+                {
+                    seeds.push((*id, 1.0 - distance));
+                }
+            }
+
+            // Collect graph-expanded results with configurable multi-hop
+            // traversal, unless the recall has degraded past it
+            let expand = budget.runs(RecallStage::Graph);
+            let max_hops = if expand { 2 } else { 0 };
+            let started = std::time::Instant::now();
+            let mut seen: HashSet<Uuid> = seeds.iter().map(|(id, _)| *id).collect();
+            let mut graph_ranked: Vec<(Uuid, f32)> = Vec::new();
+
+            // Seeds get score 1.0
+            for &(id, _) in &seeds {
+                graph_ranked.push((id, 1.0));
+            }
+
+            // Multi-hop expansion with exponential decay
+            let mut frontier: Vec<Uuid> = seeds.iter().map(|(id, _)| *id).collect();
+            let mut decay = 0.5_f32;
+            for _hop in 0..max_hops {
+                let mut next_frontier: Vec<Uuid> = Vec::new();
+                for &id in &frontier {
+                    let from_rels = engine.storage.get_relations_from(id).await?;
+                    let to_rels = engine.storage.get_relations_to(id).await?;
+                    for rel in from_rels.iter().chain(to_rels.iter()) {
+                        let related_id = if rel.source_id == id {
+                            rel.target_id
+                        } else {
+                            rel.source_id
+                        };
+                        if seen.insert(related_id)
+                            && let Some(record) =
+                                get_memory_cached(engine, related_id, columns).await?
+                            && passes_filters(&record, request, agent_id, engine).await
+                        {
+                            graph_ranked.push((related_id, decay));
+                            next_frontier.push(related_id);
+                        }
+                    }
+                }
+                frontier = next_frontier;
+                decay *= 0.5;
+            }
+            if expand {
+                budget.record(RecallStage::Graph, started.elapsed());
+            }
+
+            // Use RRF fusion with vector + graph lists
+            let mut v_sorted: Vec<(Uuid, f32)> = seeds.clone();
+            v_sorted.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+            graph_ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+            let ranked_lists = vec![v_sorted, graph_ranked];
+            let rrf_k = request.rrf_k.unwrap_or(60.0);
+            let weights = match request.hybrid {
+                Some(ref hybrid) => Some(hybrid.graph_weights()),
+                None => request.hybrid_weights.clone(),
+            };
+            let fused = if let Some(ref weights) = weights {
+                crate::query::retrieval::weighted_reciprocal_rank_fusion(
+                    &ranked_lists,
+                    rrf_k,
+                    weights,
+                )
+            } else {
+                crate::query::retrieval::reciprocal_rank_fusion(&ranked_lists, rrf_k)
+            };
+
+            for (id, score) in fused {
+                if let Some(record) = get_memory_cached(engine, id, columns).await?
+                    && passes_filters(&record, request, agent_id, engine).await
+                {
+                    scored_memories.push((record, score));
+                }
+            }
+        }
+        "exact" => {
+            // Filter-based exact matching, no embedding needed
+            // When as_of is set, include deleted records so the as_of filter can evaluate them
             let filter = MemoryFilter {
-                agent_id: Some(agent_id.clone()),
+                agent_id: Some(agent_id.to_string()),
                 memory_type: request.memory_type,
                 scope: request.scope,
                 tags: request.tags.clone(),
@@ -559,7 +1467,7 @@ pub async fn execute(engine: &MnemoEngine, request: RecallRequest) -> Result<Rec
             };
             let memories = engine.storage.list_memories(&filter, limit, 0).await?;
             for record in memories {
-                if passes_filters(&record, &request, &agent_id, engine).await {
+                if passes_filters(&record, request, agent_id, engine).await {
                     scored_memories.push((record, 1.0));
                 }
             }
@@ -569,7 +1477,7 @@ pub async fn execute(engine: &MnemoEngine, request: RecallRequest) -> Result<Rec
             let vector_results =
                 engine
                     .index
-                    .filtered_search(&query_embedding, limit * 3, &perm_filter)?;
+                    .search_within(query_embedding, limit * 3, accessible_ids)?;
             let mut vector_ranked: Vec<(Uuid, f32)> = Vec::new();
             for (id, distance) in vector_results {
                 vector_ranked.push((id, 1.0 - distance));
@@ -577,12 +1485,13 @@ pub async fn execute(engine: &MnemoEngine, request: RecallRequest) -> Result<Rec
 
             if let Some(ref ft) = engine.full_text {
                 // Hybrid: RRF fusion of vector + BM25 + recency
-                let bm25_results = ft.search(&request.query, limit * 3)?;
+                let bm25_results =
+                    ft.search_with_options(&request.query, limit * 3, text_options)?;
 
                 // Build recency-scored list from vector candidates
                 let mut recency_ranked: Vec<(Uuid, f32)> = Vec::new();
                 for &(id, _) in &vector_ranked {
-                    if let Some(record) = get_memory_cached(engine, id).await? {
+                    if let Some(record) = get_memory_cached(engine, id, columns).await? {
                         let r_score = crate::query::retrieval::recency_score(
                             &record.created_at,
                             request.recency_half_life_hours.unwrap_or(168.0),
@@ -593,7 +1502,7 @@ pub async fn execute(engine: &MnemoEngine, request: RecallRequest) -> Result<Rec
                 // Also add BM25 candidates to recency
                 for &(id, _) in &bm25_results {
                     if !recency_ranked.iter().any(|(rid, _)| *rid == id)
-                        && let Some(record) = get_memory_cached(engine, id).await?
+                        && let Some(record) = get_memory_cached(engine, id, columns).await?
                     {
                         let r_score = crate::query::retrieval::recency_score(
                             &record.created_at,
@@ -611,11 +1520,17 @@ pub async fn execute(engine: &MnemoEngine, request: RecallRequest) -> Result<Rec
                 recency_ranked
                     .sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
 
-                // Graph expansion signal: from top-10 vector results, multi-hop expansion
+                // Graph expansion signal: from top-10 vector results, multi-hop
+                // expansion. A degraded recall has no graph signal.
+                let expand = budget.runs(RecallStage::Graph);
+                let started = std::time::Instant::now();
                 let max_hops = 2;
                 let mut graph_ranked: Vec<(Uuid, f32)> = Vec::new();
-                let top_seeds: Vec<Uuid> =
-                    vector_ranked.iter().take(10).map(|(id, _)| *id).collect();
+                let top_seeds: Vec<Uuid> = if expand {
+                    vector_ranked.iter().take(10).map(|(id, _)| *id).collect()
+                } else {
+                    Vec::new()
+                };
                 let mut graph_seen: HashSet<Uuid> = top_seeds.iter().copied().collect();
                 for &seed_id in &top_seeds {
                     graph_ranked.push((seed_id, 1.0));
@@ -657,6 +1572,9 @@ pub async fn execute(engine: &MnemoEngine, request: RecallRequest) -> Result<Rec
                 }
                 graph_ranked
                     .sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+                if expand {
+                    budget.record(RecallStage::Graph, started.elapsed());
+                }
 
                 // Capture per-signal score maps before moving the ranked lists
                 // into the fusion call, so `explain=true` can surface each
@@ -669,337 +1587,85 @@ pub async fn execute(engine: &MnemoEngine, request: RecallRequest) -> Result<Rec
                     SignalMap,
                     SignalMap,
                 ) = if explain {
-                    (
-                        v_sorted.iter().copied().collect(),
-                        b_sorted.iter().copied().collect(),
-                        recency_ranked.iter().copied().collect(),
-                        graph_ranked.iter().copied().collect(),
-                    )
-                } else {
-                    Default::default()
-                };
-
-                let ranked_lists = vec![v_sorted, b_sorted, recency_ranked, graph_ranked];
-                let rrf_k = request.rrf_k.unwrap_or(60.0);
-                let fused = if let Some(ref weights) = request.hybrid_weights {
-                    crate::query::retrieval::weighted_reciprocal_rank_fusion(
-                        &ranked_lists,
-                        rrf_k,
-                        weights,
-                    )
-                } else {
-                    crate::query::retrieval::reciprocal_rank_fusion(&ranked_lists, rrf_k)
-                };
-
-                for (rank, (id, score)) in fused.into_iter().enumerate() {
-                    if let Some(record) = get_memory_cached(engine, id).await?
-                        && passes_filters(&record, &request, &agent_id, engine).await
-                    {
-                        scored_memories.push((record, score));
-                        if explain {
-                            breakdowns.insert(
-                                id,
-                                ScoreBreakdown {
-                                    vector: vector_map.get(&id).copied().unwrap_or(0.0),
-                                    bm25: bm25_map.get(&id).copied().unwrap_or(0.0),
-                                    graph: graph_map.get(&id).copied().unwrap_or(0.0),
-                                    recency: recency_map.get(&id).copied().unwrap_or(0.0),
-                                    rrf_rank: rank as u32,
-                                },
-                            );
-                        }
-                    }
-                }
-            } else {
-                // Fallback to semantic-only
-                for (id, score) in vector_ranked {
-                    if let Some(record) = get_memory_cached(engine, id).await?
-                        && passes_filters(&record, &request, &agent_id, engine).await
-                    {
-                        scored_memories.push((record, score));
-                    }
-                }
-            }
-        }
-    }
-
-    // Sort by score descending
-    scored_memories.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-    scored_memories.truncate(limit);
-
-    // v0.4.12 — opt-in cost-aware evidence budget. Runs only when the
-    // caller set `request.evidence_budget`. The selector operates on
-    // the already-ranked list and returns the smallest prefix that
-    // clears the sufficiency bar (capped by `max_evidence`); it never
-    // reorders, so the top-k cosine/RRF ordering is preserved. Applied
-    // BEFORE `touch_memory` so we do not mark-accessed evidence the
-    // budget trimmed away (cost-aware on the write side too). See
-    // [`crate::query::evidence`].
-    let evidence_selection = if let Some(ref budget) = request.evidence_budget {
-        let cosine_default = crate::query::evidence::CosineScorer;
-        let scorer: &dyn crate::query::evidence::EvidenceScorer =
-            match (budget.scorer, engine.evidence_scorer.as_ref()) {
-                (crate::query::evidence::ScorerKind::Delta, Some(s)) => s.as_ref(),
-                _ => &cosine_default,
-            };
-        // Pass the query embedding only when it is non-degenerate
-        // (NoopEmbedding yields all-zero vectors, for which cosine is
-        // undefined and the scorer should fall back to retrieval score).
-        let q_emb: Option<&[f32]> = if query_embedding.iter().any(|v| *v != 0.0) {
-            Some(query_embedding.as_slice())
-        } else {
-            None
-        };
-        let candidates: Vec<crate::query::evidence::EvidenceCandidate<'_>> = scored_memories
-            .iter()
-            .map(|(r, score)| crate::query::evidence::EvidenceCandidate {
-                content: &r.content,
-                embedding: r.embedding.as_deref(),
-                retrieval_score: *score,
-            })
-            .collect();
-        let selection = crate::query::evidence::select_within_budget(
-            &candidates,
-            budget,
-            scorer,
-            &request.query,
-            q_emb,
-        );
-        let keep = selection.keep;
-        drop(candidates);
-        scored_memories.truncate(keep);
-        Some(selection.report)
-    } else {
-        None
-    };
-
-    let _total_pre_resolver = scored_memories.len();
-
-    // Touch accessed memories
-    for (record, _) in &scored_memories {
-        if let Err(e) = engine.storage.touch_memory(record.id).await {
-            tracing::warn!(memory_id = %record.id, error = %e, "failed to update access timestamp");
-        }
-    }
-
-    // Decrypt content if encryption is configured
-    if let Some(ref enc) = engine.encryption {
-        for (record, _) in &mut scored_memories {
-            match base64::engine::general_purpose::STANDARD.decode(&record.content) {
-                Ok(encrypted_bytes) => match enc.decrypt(&encrypted_bytes) {
-                    Ok(decrypted) => match String::from_utf8(decrypted) {
-                        Ok(plaintext) => record.content = plaintext,
-                        Err(e) => {
-                            tracing::error!(memory_id = %record.id, error = %e, "decrypted content is not valid UTF-8");
-                            record.content = "[content unavailable: decryption error]".to_string();
-                        }
-                    },
-                    Err(e) => {
-                        tracing::error!(memory_id = %record.id, error = %e, "failed to decrypt memory content");
-                        record.content = "[content unavailable: decryption error]".to_string();
-                    }
-                },
-                Err(e) => {
-                    tracing::error!(memory_id = %record.id, error = %e, "failed to decode encrypted content");
-                    record.content = "[content unavailable: decryption error]".to_string();
-                }
-            }
-        }
-    }
-
-    // Keep the underlying records around if the caller asked for a
-    // provenance receipt (Task B1) — the HMAC chain needs the
-    // content_hash + prev_hash off each record before they get
-    // collapsed into ScoredMemory.
-    let provenance_records: Option<Vec<MemoryRecord>> =
-        if request.with_provenance == Some(true) && engine.provenance_signer.is_some() {
-            Some(scored_memories.iter().map(|(r, _)| r.clone()).collect())
-        } else {
-            None
-        };
-
-    let memories: Vec<ScoredMemory> = scored_memories
-        .into_iter()
-        .map(|(record, score)| {
-            let id = record.id;
-            let mut scored = ScoredMemory::from((record, score));
-            if let Some(breakdown) = breakdowns.remove(&id) {
-                scored.score_breakdown = Some(breakdown);
-            }
-            scored
-        })
-        .collect();
-
-    // v0.4.7 — opt-in current-fact resolver post-process. Runs only
-    // when the caller set `request.current_fact_resolver`. The
-    // resolver groups by `cfg.fact_key`, keeps the most-recent
-    // write per group, and (optionally) returns the older versions
-    // as a supersession chain. See
-    // [`crate::query::current_fact_resolver`] for the MINTEval
-    // arXiv:2605.18565 anchor + the contract.
-    let (memories, superseded_chain) = if let Some(ref cfg) = request.current_fact_resolver {
-        let out = crate::query::current_fact_resolver::resolve(cfg, memories);
-        let chain = if cfg.include_supersession_chain && !out.superseded.is_empty() {
-            Some(out.superseded)
-        } else {
-            None
-        };
-        (out.kept, chain)
-    } else {
-        (memories, None)
-    };
-    let total = memories.len();
+                    (
+                        v_sorted.iter().copied().collect(),
+                        b_sorted.iter().copied().collect(),
+                        recency_ranked.iter().copied().collect(),
+                        graph_ranked.iter().copied().collect(),
+                    )
+                } else {
+                    Default::default()
+                };
 
-    // v0.5.1 — active reconstruction (MRAgent, arXiv:2606.06036). When the
-    // caller selected the `reconstruct` strategy, walk the memory graph from
-    // the retrieved hits to gather linked/causal context and synthesise a
-    // deterministic belief-state node returned ALONGSIDE the raw hits. The
-    // `memories` list above is untouched, so this is purely additive.
-    let reconstruction = if strategy == "reconstruct" {
-        Some(reconstruct_belief(engine, &request, &agent_id, &memories).await)
-    } else {
-        None
-    };
+                let mut ranked_lists = vec![v_sorted, b_sorted, recency_ranked, graph_ranked];
+                let rrf_k = request.rrf_k.unwrap_or(60.0);
+                let weights = match request.hybrid {
+                    Some(ref hybrid) => {
+                        // Importance only joins the fusion when it is weighted,
+                        // ranking every candidate the other signals found.
+                        if hybrid.importance > 0.0 {
+                            let mut importance_ranked: Vec<(Uuid, f32)> = Vec::new();
+                            let mut seen: HashSet<Uuid> = HashSet::new();
+                            for &(id, _) in ranked_lists.iter().flatten() {
+                                if seen.insert(id)
+                                    && let Some(record) =
+                                        get_memory_cached(engine, id, columns).await?
+                                {
+                                    importance_ranked.push((id, record.importance));
+                                }
+                            }
+                            importance_ranked.sort_by(|a, b| {
+                                b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal)
+                            });
+                            ranked_lists.push(importance_ranked);
+                        }
+                        Some(hybrid.hybrid_weights())
+                    }
+                    None => request.hybrid_weights.clone(),
+                };
+                let fused = if let Some(ref weights) = weights {
+                    crate::query::retrieval::weighted_reciprocal_rank_fusion(
+                        &ranked_lists,
+                        rrf_k,
+                        weights,
+                    )
+                } else {
+                    crate::query::retrieval::reciprocal_rank_fusion(&ranked_lists, rrf_k)
+                };
 
-    // v0.4.8 — opt-in orientation cache. Runs only when the caller
-    // set `request.orientation_cache` AND the engine has an
-    // `OrientationCacheStore` attached. Per-namespace map is
-    // updated from the hits + a bounded rendering is returned. See
-    // [`crate::query::orientation_cache`] for the PEEK
-    // arXiv:2605.19932 anchor + the contract.
-    let orientation_rendered = match (
-        request.orientation_cache.as_ref(),
-        engine.orientation_cache_store.as_ref(),
-    ) {
-        (Some(cfg), Some(store)) => {
-            let ns = crate::query::orientation_cache::resolve_namespace(
-                cfg,
-                &agent_id,
-                request.org_id.as_deref(),
-            );
-            let rendered =
-                crate::query::orientation_cache::update_and_render(store, cfg, &ns, &memories);
-            if cfg.include_in_response {
-                Some(rendered)
+                for (rank, (id, score)) in fused.into_iter().enumerate() {
+                    if let Some(record) = get_memory_cached(engine, id, columns).await?
+                        && passes_filters(&record, request, agent_id, engine).await
+                    {
+                        scored_memories.push((record, score));
+                        if explain {
+                            breakdowns.insert(
+                                id,
+                                ScoreBreakdown {
+                                    vector: vector_map.get(&id).copied().unwrap_or(0.0),
+                                    bm25: bm25_map.get(&id).copied().unwrap_or(0.0),
+                                    graph: graph_map.get(&id).copied().unwrap_or(0.0),
+                                    recency: recency_map.get(&id).copied().unwrap_or(0.0),
+                                    rrf_rank: rank as u32,
+                                },
+                            );
+                        }
+                    }
+                }
             } else {
-                None
+                // Fallback to semantic-only
+                for (id, score) in vector_ranked {
+                    if let Some(record) = get_memory_cached(engine, id, columns).await?
+                        && passes_filters(&record, request, agent_id, engine).await
+                    {
+                        scored_memories.push((record, score));
+                    }
+                }
             }
         }
-        _ => None,
-    };
-
-    // Emit MemoryRead event with hash chain linking (fire-and-forget)
-    let now = chrono::Utc::now().to_rfc3339();
-    let event_content_hash = compute_content_hash(&request.query, &agent_id, &now);
-    let prev_event_hash = match engine.storage.get_latest_event_hash(&agent_id, None).await {
-        Ok(hash) => hash,
-        Err(e) => {
-            tracing::warn!(error = %e, "failed to get latest event hash, starting new chain segment");
-            None
-        }
-    };
-    let event_prev_hash = Some(crate::hash::compute_chain_hash(
-        &event_content_hash,
-        prev_event_hash.as_deref(),
-    ));
-    let mut event = AgentEvent {
-        id: Uuid::now_v7(),
-        agent_id: agent_id.clone(),
-        thread_id: None,
-        run_id: None,
-        parent_event_id: None,
-        event_type: EventType::MemoryRead,
-        payload: serde_json::json!({
-            "query": request.query,
-            "results": total,
-            "strategy": strategy,
-        }),
-        trace_id: None,
-        span_id: None,
-        model: None,
-        tokens_input: None,
-        tokens_output: None,
-        latency_ms: None,
-        cost_usd: None,
-        timestamp: now.clone(),
-        logical_clock: 0,
-        content_hash: event_content_hash,
-        prev_hash: event_prev_hash,
-        embedding: None,
-    };
-    // Optionally embed the event payload
-    if engine.embed_events
-        && let Ok(emb) = engine.embedding.embed(&event.payload.to_string()).await
-    {
-        event.embedding = Some(emb);
     }
-    if let Err(e) = engine.storage.insert_event(&event).await {
-        tracing::error!(event_id = %event.id, error = %e, "failed to insert audit event");
-    }
-
-    // v0.4.0-rc3 (B1) — sign a ReadProvenance over the recalled
-    // records when the caller opted in. Failures are non-fatal:
-    // missing signer or HMAC error degrades to "no provenance" so the
-    // recall still returns. The caller can detect by `provenance.is_none()`.
-    let provenance = if let (Some(records), Some(signer)) =
-        (provenance_records, engine.provenance_signer.as_ref())
-    {
-        match signer.sign(&agent_id, &request.query, &records) {
-            Ok(p) => Some(p),
-            Err(e) => {
-                tracing::warn!(error = %e, "failed to sign read provenance; degrading to no-provenance response");
-                None
-            }
-        }
-    } else {
-        None
-    };
-
-    // EMBER (arXiv:2606.05894) — opt-in budgeted evidence retention.
-    // Runs only when the caller set `request.retained_token_budget`.
-    // Builds verbatim evidence capsules (excerpt + retrieval key) packed
-    // under the token cap, ranked by `recency × hit-rate` recoverability.
-    // Computed from the FINAL `memories` (post current-fact resolver,
-    // decrypted) and returned ALONGSIDE them — `memories` is not
-    // modified, so the default read path is unaffected. See
-    // [`crate::query::retained`].
-    let retained_evidence = request.retained_token_budget.map(|budget| {
-        let retain_now = chrono::Utc::now();
-        let candidates: Vec<crate::query::retained::RetentionCandidate<'_>> = memories
-            .iter()
-            .map(|m| {
-                let age_hours = chrono::DateTime::parse_from_rfc3339(&m.updated_at)
-                    .or_else(|_| chrono::DateTime::parse_from_rfc3339(&m.created_at))
-                    .map(|ts| {
-                        (retain_now - ts.with_timezone(&chrono::Utc)).num_seconds() as f64 / 3600.0
-                    })
-                    .unwrap_or(0.0);
-                crate::query::retained::RetentionCandidate {
-                    id: m.id,
-                    content: &m.content,
-                    access_count: m.access_count,
-                    age_hours,
-                    retrieval_score: m.score,
-                }
-            })
-            .collect();
-        crate::query::retained::retain_within_budget(
-            &candidates,
-            budget,
-            crate::query::retained::DEFAULT_EXCERPT_TOKENS,
-        )
-    });
 
-    Ok(RecallResponse {
-        memories,
-        total,
-        provenance,
-        superseded: superseded_chain,
-        orientation_cache: orientation_rendered,
-        evidence_selection,
-        retained_evidence,
-        reconstruction,
-    })
+    Ok(scored_memories)
 }
 
 /// v0.5.1 — synthesise a [`ReconstructedBelief`] from the retrieved hits
@@ -1096,24 +1762,102 @@ fn excerpt(content: &str, max: usize) -> String {
     }
 }
 
+/// Cosine similarity of two vectors; 0 when either is all zeros.
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+/// Update the access timestamps of recalled memories and log their reads
+/// as `agent_id`'s.
+pub(super) async fn record_access(engine: &MnemoEngine, agent_id: &str, query: &str, ids: &[Uuid]) {
+    for id in ids {
+        if let Err(e) = engine.storage.touch_memory(*id).await {
+            tracing::warn!(memory_id = %id, error = %e, "failed to update access timestamp");
+        }
+    }
+    if engine.access_log.is_some() {
+        super::access_log::record(engine, agent_id, AccessOperation::Recall, Some(query), ids)
+            .await;
+    }
+}
+
+/// Append a `MemoryRead` event for a recall to `agent_id`'s chain.
+pub(super) async fn record_read_event(
+    engine: &MnemoEngine,
+    agent_id: &str,
+    query: &str,
+    results: usize,
+    strategy: &str,
+) {
+    let now = chrono::Utc::now().to_rfc3339();
+    let event_content_hash = compute_content_hash(query, agent_id, &now);
+    let prev_event_hash = match engine.storage.get_latest_event_hash(agent_id, None).await {
+        Ok(hash) => hash,
+        Err(e) => {
+            tracing::warn!(error = %e, "failed to get latest event hash, starting new chain segment");
+            None
+        }
+    };
+    let event_prev_hash = Some(crate::hash::compute_chain_hash(
+        &event_content_hash,
+        prev_event_hash.as_deref(),
+    ));
+    let mut event = AgentEvent {
+        id: Uuid::now_v7(),
+        agent_id: agent_id.to_string(),
+        thread_id: None,
+        run_id: None,
+        parent_event_id: None,
+        event_type: EventType::MemoryRead,
+        payload: serde_json::json!({
+            "query": query,
+            "results": results,
+            "strategy": strategy,
+        }),
+        trace_id: None,
+        span_id: None,
+        model: None,
+        tokens_input: None,
+        tokens_output: None,
+        latency_ms: None,
+        cost_usd: None,
+        timestamp: now.clone(),
+        logical_clock: super::clock::tick(engine, agent_id).await,
+        content_hash: event_content_hash,
+        prev_hash: event_prev_hash,
+        embedding: None,
+        request_id: crate::request_id::current(),
+    };
+    // Optionally embed the event payload
+    if engine.embed_events
+        && let Ok(emb) = engine.embedding.embed(&event.payload.to_string()).await
+    {
+        event.embedding = Some(emb);
+    }
+    if let Err(e) = engine.storage.insert_event(&event).await {
+        tracing::error!(event_id = %event.id, error = %e, "failed to insert audit event");
+    }
+}
+
 /// Decrypt a record's content in place if engine-level encryption is on.
 /// Mirrors the read-path decryption in [`execute`]; used by
 /// [`reconstruct_belief`] for graph-linked records fetched after the main
 /// decrypt loop.
 fn decrypt_record_content(engine: &MnemoEngine, record: &mut MemoryRecord) {
-    if let Some(ref enc) = engine.encryption {
-        if let Ok(bytes) = base64::engine::general_purpose::STANDARD.decode(&record.content)
-            && let Ok(plain) = enc.decrypt(&bytes)
-            && let Ok(text) = String::from_utf8(plain)
-        {
-            record.content = text;
-        } else {
-            record.content = "[content unavailable: decryption error]".to_string();
-        }
+    if engine.encrypts_content() {
+        record.content = engine
+            .decrypt_content(&record.content)
+            .unwrap_or_else(|_| "[content unavailable: decryption error]".to_string());
     }
 }
 
-async fn passes_filters(
+pub(crate) async fn passes_filters(
     record: &MemoryRecord,
     request: &RecallRequest,
     agent_id: &str,
@@ -1139,24 +1883,38 @@ async fn passes_filters(
         return false;
     }
 
-    // Skip deleted (unless as_of is set — the as_of filter handles deleted records)
-    if request.as_of.is_none() && record.is_deleted() {
+    // Skip what did not exist or was deleted at the recall's read version
+    // (unless as_of is set — the as_of filter handles deleted records)
+    if request.as_of.is_none() && !super::snapshot::visible(record) {
         return false;
     }
 
     // Skip expired
     if let Some(ref expires_at) = record.expires_at
         && let Ok(exp) = chrono::DateTime::parse_from_rfc3339(expires_at)
-        && exp < chrono::Utc::now()
+        && exp < super::snapshot::read_version().unwrap_or_else(chrono::Utc::now)
     {
         return false;
     }
 
+    // Unattested high-stakes memories reach nobody
+    if !super::attestation::is_recallable(record) {
+        return false;
+    }
+
     // Skip quarantined
     if record.quarantined {
         return false;
     }
 
+    // Thread isolation: only the request's thread, plus global memories
+    if request.thread_isolation == Some(true)
+        && record.scope != Scope::Global
+        && record.thread_id != request.thread_id
+    {
+        return false;
+    }
+
     // Scope filter (explicit request scope filter, separate from visibility below)
     if let Some(ref s) = request.scope
         && record.scope != *s
@@ -1164,6 +1922,11 @@ async fn passes_filters(
         return false;
     }
 
+    // Channel filter
+    if request.channel.is_some() && record.channel != request.channel {
+        return false;
+    }
+
     // Type filter: memory_types (multi) takes precedence over memory_type (single)
     if let Some(ref mts) = request.memory_types {
         if !mts.contains(&record.memory_type) {
@@ -1189,6 +1952,13 @@ async fn passes_filters(
         return false;
     }
 
+    // Excluded tags
+    if let Some(ref excluded) = request.exclude_tags
+        && excluded.iter().any(|t| record.tags.contains(t))
+    {
+        return false;
+    }
+
     // Temporal range filter (parse to DateTime for correct comparison)
     if let Some(ref tr) = request.temporal_range {
         if let Some(ref after) = tr.after
@@ -1231,11 +2001,21 @@ async fn passes_filters(
         {
             return false;
         }
+        // Exclude facts that did not hold at as_of
+        if let Ok(as_of_dt) = chrono::DateTime::parse_from_rfc3339(as_of)
+            && !record.is_valid_at(as_of_dt.with_timezone(&chrono::Utc))
+        {
+            return false;
+        }
     }
 
     // Scope-based visibility
     match record.scope {
-        Scope::Public | Scope::Global => true,
+        Scope::Public => true,
+        // Unreviewed global writes reach only their author.
+        Scope::Global => {
+            record.agent_id == agent_id || super::curation::visible_to_others(record)
+        }
         Scope::Shared => {
             record.agent_id == agent_id
                 || engine
@@ -0,0 +1,187 @@
+//! Saved recall profiles.
+//!
+//! Agents that repeat the same recall configuration (strategy, fusion
+//! weights, filters, token budget) can save it once under a name and then
+//! pass `RecallRequest::profile`. The profile is expanded server-side before
+//! recall runs: settings the request leaves unset are filled from the
+//! profile, explicit request fields always win. Each expansion bumps the
+//! profile's `use_count` / `last_used_at`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::model::recall_profile::{RecallProfile, RecallProfileSettings};
+use crate::query::MnemoEngine;
+use crate::query::recall::RecallRequest;
+
+/// Longest accepted profile name.
+const MAX_PROFILE_NAME_LEN: usize = 64;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveRecallProfileRequest {
+    pub name: String,
+    pub agent_id: Option<String>,
+    #[serde(default)]
+    pub settings: RecallProfileSettings,
+}
+
+impl SaveRecallProfileRequest {
+    pub fn new(name: String, settings: RecallProfileSettings) -> Self {
+        Self {
+            name,
+            agent_id: None,
+            settings,
+        }
+    }
+}
+
+fn validate_profile_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.len() > MAX_PROFILE_NAME_LEN {
+        return Err(Error::Validation(format!(
+            "recall profile name must be 1-{MAX_PROFILE_NAME_LEN} characters"
+        )));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(Error::Validation(format!(
+            "recall profile name '{name}' may only contain letters, digits, '-', '_' and '.'"
+        )));
+    }
+    Ok(())
+}
+
+/// Create or replace a profile. Replacing keeps the usage stats.
+pub async fn save(
+    engine: &MnemoEngine,
+    request: SaveRecallProfileRequest,
+) -> Result<RecallProfile> {
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    validate_profile_name(&request.name)?;
+    if request.settings.limit == Some(0) {
+        return Err(Error::Validation(
+            "recall profile limit must be greater than 0".to_string(),
+        ));
+    }
+    if let Some(ref hybrid) = request.settings.hybrid {
+        hybrid.validate()?;
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let profile = match engine
+        .storage
+        .get_recall_profile(&agent_id, &request.name)
+        .await?
+    {
+        Some(mut existing) => {
+            existing.settings = request.settings;
+            existing.updated_at = now;
+            existing
+        }
+        None => RecallProfile {
+            agent_id,
+            name: request.name,
+            settings: request.settings,
+            use_count: 0,
+            last_used_at: None,
+            created_at: now.clone(),
+            updated_at: now,
+        },
+    };
+    engine.storage.upsert_recall_profile(&profile).await?;
+    Ok(profile)
+}
+
+pub async fn get(engine: &MnemoEngine, agent_id: &str, name: &str) -> Result<RecallProfile> {
+    super::validate_agent_id(agent_id)?;
+    engine
+        .storage
+        .get_recall_profile(agent_id, name)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("recall profile '{name}' not found")))
+}
+
+pub async fn list(engine: &MnemoEngine, agent_id: &str) -> Result<Vec<RecallProfile>> {
+    super::validate_agent_id(agent_id)?;
+    engine.storage.list_recall_profiles(agent_id).await
+}
+
+pub async fn delete(engine: &MnemoEngine, agent_id: &str, name: &str) -> Result<()> {
+    super::validate_agent_id(agent_id)?;
+    if !engine.storage.delete_recall_profile(agent_id, name).await? {
+        return Err(Error::NotFound(format!(
+            "recall profile '{name}' not found"
+        )));
+    }
+    Ok(())
+}
+
+/// Fill every unset field of `request` from `settings`.
+pub fn apply(settings: &RecallProfileSettings, request: &mut RecallRequest) {
+    // A typed `mode` already decides the strategy; don't let the profile
+    // override it through the legacy field.
+    if request.strategy.is_none() && request.mode.is_none() {
+        request.strategy = settings.strategy.clone();
+    }
+    if request.memory_type.is_none() && request.memory_types.is_none() {
+        request.memory_types = settings.memory_types.clone();
+    }
+    request.limit = request.limit.or(settings.limit);
+    request.scope = request.scope.or(settings.scope);
+    request.min_importance = request.min_importance.or(settings.min_importance);
+    if request.tags.is_none() {
+        request.tags = settings.tags.clone();
+    }
+    // Raw weights on the request outrank the profile's named ones.
+    if request.hybrid.is_none() && request.hybrid_weights.is_none() {
+        request.hybrid = settings.hybrid;
+    }
+    if request.hybrid_weights.is_none() {
+        request.hybrid_weights = settings.hybrid_weights.clone();
+    }
+    request.rrf_k = request.rrf_k.or(settings.rrf_k);
+    request.recency_half_life_hours = request
+        .recency_half_life_hours
+        .or(settings.recency_half_life_hours);
+    request.retained_token_budget = request
+        .retained_token_budget
+        .or(settings.retained_token_budget);
+    request.text_query_mode = request.text_query_mode.or(settings.text_query_mode);
+    if request.text_field_boosts.is_none() {
+        request.text_field_boosts = settings.text_field_boosts.clone();
+    }
+    request.explain = request.explain.or(settings.explain);
+    request.with_snippets = request.with_snippets.or(settings.with_snippets);
+}
+
+/// Expand `request.profile` (if any) in place and, with `record_use`,
+/// record the use.
+pub(crate) async fn expand(
+    engine: &MnemoEngine,
+    request: &mut RecallRequest,
+    agent_id: &str,
+    record_use: bool,
+) -> Result<()> {
+    let Some(name) = request.profile.clone() else {
+        return Ok(());
+    };
+    let profile = get(engine, agent_id, &name).await?;
+    apply(&profile.settings, request);
+    if !record_use {
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    if let Err(e) = engine
+        .storage
+        .record_recall_profile_use(agent_id, &name, &now)
+        .await
+    {
+        tracing::warn!(profile = %name, error = %e, "failed to record recall profile use");
+    }
+    Ok(())
+}
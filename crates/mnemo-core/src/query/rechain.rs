@@ -0,0 +1,188 @@
+//! Hash-chain backfill.
+//!
+//! Memories written by an import or a migration carry no `prev_hash`, or
+//! hashes computed some other way, so verification fails on them. A
+//! rechain pass walks an agent's live memories in `created_at` order,
+//! recomputes each content hash the way verification does and relinks
+//! `prev_hash` within each chain — one per thread plus the thread-less
+//! one, as [`remember`](crate::query::remember) links them. Every chain it
+//! changes gets a [`EventType::ChainRebuilt`] event with the chain's roots
+//! before and after, and the agent's verification checkpoints are cleared
+//! so the next incremental verification starts over. Memories under
+//! [retention lock](super::retention_lock) are left as they are and
+//! counted as `locked`.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::hash::{compute_chain_hash, compute_content_hash, roll_chain_root};
+use crate::model::event::EventType;
+use crate::query::MnemoEngine;
+use crate::query::verify::VERIFY_PAGE_SIZE;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RechainRequest {
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    /// Rebuild only this thread's chain. `None` rebuilds all of the
+    /// agent's chains.
+    #[serde(default)]
+    pub thread_id: Option<String>,
+}
+
+impl RechainRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// One rebuilt chain.
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebuiltChain {
+    pub thread_id: Option<String>,
+    pub records: usize,
+    /// Records whose content hash or `prev_hash` changed.
+    pub relinked: usize,
+    /// Records that needed relinking but are under retention lock, so were
+    /// left unchanged.
+    pub locked: usize,
+    /// Hex rolling hash over every record's content hash and `prev_hash`
+    /// before the pass.
+    pub before_root: String,
+    /// The same over the rebuilt hashes.
+    pub after_root: String,
+    /// The chain-rebuild event, when anything changed.
+    pub event_id: Option<Uuid>,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RechainResponse {
+    pub agent_id: String,
+    /// Ordered by thread, the thread-less chain first.
+    pub chains: Vec<RebuiltChain>,
+}
+
+#[derive(Default)]
+struct ChainState {
+    records: usize,
+    relinked: usize,
+    locked: usize,
+    prev_content_hash: Option<Vec<u8>>,
+    before_root: Vec<u8>,
+    after_root: Vec<u8>,
+}
+
+fn fold_root(root: &[u8], content_hash: &[u8], prev_hash: Option<&[u8]>) -> Vec<u8> {
+    roll_chain_root(
+        &roll_chain_root(root, content_hash),
+        prev_hash.unwrap_or_default(),
+    )
+}
+
+pub async fn execute(engine: &MnemoEngine, request: RechainRequest) -> Result<RechainResponse> {
+    let agent_id = engine.acting_agent(request.agent_id.as_deref());
+    super::validate_agent_id(&agent_id)?;
+
+    let mut chains: HashMap<Option<String>, ChainState> = HashMap::new();
+    let mut cursor: Option<(String, Uuid)> = None;
+    loop {
+        let page = engine
+            .storage
+            .list_memories_chain_page(
+                &agent_id,
+                request.thread_id.as_deref(),
+                cursor.as_ref().map(|(at, id)| (at.as_str(), *id)),
+                VERIFY_PAGE_SIZE,
+            )
+            .await?;
+        for mut record in page.iter().cloned() {
+            let chain = chains.entry(record.thread_id.clone()).or_default();
+            chain.records += 1;
+            chain.before_root = fold_root(
+                &chain.before_root,
+                &record.content_hash,
+                record.prev_hash.as_deref(),
+            );
+
+            let content_hash =
+                compute_content_hash(&record.content, &record.agent_id, &record.created_at);
+            let prev_hash = compute_chain_hash(&content_hash, chain.prev_content_hash.as_deref());
+            chain.after_root = fold_root(&chain.after_root, &content_hash, Some(&prev_hash));
+            if record.content_hash != content_hash || record.prev_hash.as_ref() != Some(&prev_hash)
+            {
+                if super::retention_lock::ensure_erasable(engine, &record, "rechain", &agent_id)
+                    .await
+                    .is_err()
+                {
+                    chain.locked += 1;
+                    chain.prev_content_hash = Some(content_hash);
+                    continue;
+                }
+                record.content_hash = content_hash.clone();
+                record.prev_hash = Some(prev_hash);
+                record.updated_at = chrono::Utc::now().to_rfc3339();
+                engine.storage.update_memory(&record).await?;
+                if let Some(ref cache) = engine.cache {
+                    cache.invalidate(record.id);
+                }
+                chain.relinked += 1;
+            }
+            chain.prev_content_hash = Some(content_hash);
+        }
+        match page.last() {
+            Some(last) if page.len() == VERIFY_PAGE_SIZE => {
+                cursor = Some((last.created_at.clone(), last.id));
+            }
+            _ => break,
+        }
+    }
+
+    let mut rebuilt = Vec::with_capacity(chains.len());
+    for (thread_id, chain) in chains {
+        let before_root = hex::encode(&chain.before_root);
+        let after_root = hex::encode(&chain.after_root);
+        let event_id = if chain.relinked > 0 {
+            let event = super::event_builder::build_event(
+                engine,
+                &agent_id,
+                EventType::ChainRebuilt,
+                serde_json::json!({
+                    "thread_id": thread_id,
+                    "records": chain.records,
+                    "relinked": chain.relinked,
+                    "before_root": before_root,
+                    "after_root": after_root,
+                }),
+                &after_root,
+                thread_id.clone(),
+            )
+            .await;
+            engine.storage.insert_event(&event).await?;
+            Some(event.id)
+        } else {
+            None
+        };
+        rebuilt.push(RebuiltChain {
+            thread_id,
+            records: chain.records,
+            relinked: chain.relinked,
+            locked: chain.locked,
+            before_root,
+            after_root,
+            event_id,
+        });
+    }
+    rebuilt.sort_by(|a, b| a.thread_id.cmp(&b.thread_id));
+    if rebuilt.iter().any(|c| c.relinked > 0) {
+        engine.storage.delete_chain_checkpoints(&agent_id).await?;
+    }
+    Ok(RechainResponse {
+        agent_id,
+        chains: rebuilt,
+    })
+}
@@ -0,0 +1,95 @@
+//! Re-embed an agent's memories with the current embedding provider.
+//!
+//! Run after switching models (or when stored vectors are missing): every
+//! live memory of the agent is embedded again through
+//! [`embed_batch`](crate::embedding::EmbeddingProvider::embed_batch), a page
+//! at a time, and its stored embedding and vector index entry replaced.
+//! With an [`embedding pipeline`](crate::embedding::pipeline) configured the
+//! pages are chunked and embedded in parallel within its rate budget.
+//! Ephemeral memories are never embedded and are skipped.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::index::ShardRoute;
+use crate::query::MnemoEngine;
+use crate::storage::MemoryFilter;
+
+/// Memories embedded per `embed_batch` call.
+pub const REEMBED_PAGE_SIZE: usize = 500;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReembedRequest {
+    /// Defaults to the engine's agent.
+    #[serde(default)]
+    pub agent_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReembedResponse {
+    pub agent_id: String,
+    /// Memories given a new embedding.
+    pub reembedded: usize,
+    /// Ephemeral memories left without one.
+    pub skipped: usize,
+}
+
+pub async fn execute(engine: &MnemoEngine, request: ReembedRequest) -> Result<ReembedResponse> {
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    let filter = MemoryFilter {
+        agent_id: Some(agent_id.clone()),
+        include_deleted: false,
+        ..Default::default()
+    };
+
+    let mut response = ReembedResponse {
+        agent_id,
+        reembedded: 0,
+        skipped: 0,
+    };
+    let mut offset = 0;
+    loop {
+        let page = engine
+            .storage
+            .list_memories(&filter, REEMBED_PAGE_SIZE, offset)
+            .await?;
+        let last_page = page.len() < REEMBED_PAGE_SIZE;
+        offset += page.len();
+        let (mut records, ephemeral): (Vec<_>, Vec<_>) = page
+            .into_iter()
+            .partition(|r| !super::ephemeral::is_ephemeral(r));
+        response.skipped += ephemeral.len();
+
+        if !records.is_empty() {
+            let contents: Vec<String> = records
+                .iter()
+                .map(|record| {
+                    let mut plain = record.clone();
+                    super::consolidate::decrypt_in_place(engine, &mut plain);
+                    plain.content
+                })
+                .collect();
+            let texts: Vec<&str> = contents.iter().map(String::as_str).collect();
+            let embeddings = engine.embedding.embed_batch(&texts).await?;
+            for (record, embedding) in records.iter_mut().zip(embeddings) {
+                engine
+                    .index
+                    .add_routed(ShardRoute::of(record), record.id, &embedding)?;
+                record.embedding = Some(embedding);
+                engine.storage.update_memory(record).await?;
+                if let Some(ref cache) = engine.cache {
+                    cache.invalidate(record.id);
+                }
+                response.reembedded += 1;
+            }
+        }
+
+        if last_page {
+            break;
+        }
+    }
+    Ok(response)
+}
@@ -28,9 +28,10 @@ use uuid::Uuid;
 
 use crate::error::Result;
 use crate::hash::{compute_chain_hash, compute_content_hash};
+use crate::index::ShardRoute;
 use crate::model::event::{AgentEvent, EventType};
 use crate::model::memory::{ConsolidationState, MemoryRecord};
-use crate::model::relation::Relation;
+use crate::model::relation::{CONSOLIDATED_FROM, Relation};
 use crate::query::MnemoEngine;
 use crate::query::conflict::ResolutionStrategy;
 use crate::query::lifecycle::effective_importance;
@@ -94,6 +95,9 @@ pub struct ReflectionReport {
     pub archived: usize,
     /// Number of conflict pairs auto-resolved.
     pub conflicts_resolved: usize,
+    /// Number of conflict pairs too important to auto-resolve that were
+    /// added to the conflict review queue.
+    pub conflicts_queued: usize,
     /// Total records scanned.
     pub total_scanned: usize,
     /// Populated when `Coordinated` skipped the run; `None` means the pass
@@ -153,10 +157,12 @@ async fn run_reflection_pass_inner(
         include_deleted: false,
         ..Default::default()
     };
-    let records = engine
+    let mut records = engine
         .storage
         .list_memories(&filter, super::MAX_BATCH_QUERY_LIMIT, 0)
         .await?;
+    // Scratchpad memories expire on their own; leave them as written.
+    records.retain(|r| !super::ephemeral::is_ephemeral(r));
 
     let total_scanned = records.len();
     let mut report = ReflectionReport {
@@ -178,7 +184,9 @@ async fn run_reflection_pass_inner(
             // the cached embedding still beats a skipped reflection.
             if let Ok(emb) = engine.embedding.embed(&record.content).await {
                 record.embedding = Some(emb.clone());
-                let _ = engine.index.add(record.id, &emb);
+                let _ = engine
+                    .index
+                    .add_routed(ShardRoute::of(&record), record.id, &emb);
             }
             engine.storage.update_memory(&record).await?;
             emit_rewrite_event(
@@ -217,7 +225,9 @@ async fn run_reflection_pass_inner(
                 compute_content_hash(&record.content, &record.agent_id, &record.updated_at);
             if let Ok(emb) = engine.embedding.embed(&record.content).await {
                 record.embedding = Some(emb.clone());
-                let _ = engine.index.add(record.id, &emb);
+                let _ = engine
+                    .index
+                    .add_routed(ShardRoute::of(record), record.id, &emb);
             }
             if let Some(obj) = record.metadata.as_object_mut() {
                 obj.insert(
@@ -247,6 +257,7 @@ async fn run_reflection_pass_inner(
     let conflicts = engine
         .detect_conflicts(Some(agent_id.to_string()), DEFAULT_DEDUP_THRESHOLD)
         .await?;
+    let mut needs_review = Vec::new();
     for pair in &conflicts.conflicts {
         let (a, b) = match (
             after_absolutization.iter().find(|r| r.id == pair.memory_a),
@@ -255,16 +266,27 @@ async fn run_reflection_pass_inner(
             (Some(a), Some(b)) => (a, b),
             _ => continue,
         };
-        if a.importance < DEFAULT_LOW_IMPORTANCE_CUTOFF
+        // NLI-flagged pairs disagree rather than duplicate; keeping the
+        // newest could silently drop the true fact.
+        if pair.nli.is_none()
+            && a.importance < DEFAULT_LOW_IMPORTANCE_CUTOFF
             && b.importance < DEFAULT_LOW_IMPORTANCE_CUTOFF
-            && engine
+        {
+            if engine
                 .resolve_conflict(pair, ResolutionStrategy::KeepNewest)
                 .await
                 .is_ok()
-        {
-            report.conflicts_resolved += 1;
+            {
+                report.conflicts_resolved += 1;
+            }
+        } else {
+            // Important facts and contradictions are a human's call, not ours.
+            needs_review.push(pair.clone());
         }
     }
+    report.conflicts_queued = super::conflict_queue::enqueue(engine, agent_id, &needs_review)
+        .await?
+        .len();
     let _ = &conflicts; // keep the borrow alive for the closure above
 
     // -- 5. Stale archival --------------------------------------------------
@@ -273,7 +295,7 @@ async fn run_reflection_pass_inner(
         if consolidated_ids.contains(&record.id) {
             continue;
         }
-        if record.consolidation_state == ConsolidationState::Archived {
+        if record.pinned || record.consolidation_state == ConsolidationState::Archived {
             continue;
         }
         if record.access_count > 0 {
@@ -410,6 +432,14 @@ async fn consolidate_duplicates(
                 std::cmp::Ordering::Less => (j, i),
                 _ => (i, j),
             };
+            // A pinned record is never the victim; two pinned duplicates
+            // are left alone.
+            let (keeper_idx, victim_idx) =
+                match (records[keeper_idx].pinned, records[victim_idx].pinned) {
+                    (_, false) => (keeper_idx, victim_idx),
+                    (false, true) => (victim_idx, keeper_idx),
+                    (true, true) => continue,
+                };
 
             // Union of tags; sum of access_count.
             let mut keeper = records[keeper_idx].clone();
@@ -432,7 +462,7 @@ async fn consolidate_duplicates(
                 id: Uuid::now_v7(),
                 source_id: keeper.id,
                 target_id: victim.id,
-                relation_type: "consolidated_from".to_string(),
+                relation_type: CONSOLIDATED_FROM.to_string(),
                 weight: 1.0,
                 metadata: serde_json::json!({"reason": "semantic_dedup"}),
                 created_at: keeper.updated_at.clone(),
@@ -491,13 +521,14 @@ async fn emit_rewrite_event(
         latency_ms: None,
         cost_usd: None,
         timestamp: now,
-        logical_clock: 0,
+        logical_clock: super::clock::tick(engine, agent_id).await,
         content_hash: content_hash.clone(),
         prev_hash: Some(compute_chain_hash(
             &content_hash,
             prev_event_hash.as_deref(),
         )),
         embedding: None,
+        request_id: crate::request_id::current(),
     };
     let _ = engine.storage.insert_event(&event).await;
 }
@@ -612,13 +643,14 @@ async fn emit_reflection_completed(
         latency_ms: None,
         cost_usd: None,
         timestamp: now,
-        logical_clock: 0,
+        logical_clock: super::clock::tick(engine, agent_id).await,
         content_hash: content_hash.clone(),
         prev_hash: Some(compute_chain_hash(
             &content_hash,
             prev_event_hash.as_deref(),
         )),
         embedding: None,
+        request_id: crate::request_id::current(),
     };
     let _ = engine.storage.insert_event(&event).await;
 }
@@ -729,13 +761,14 @@ async fn ingest_dream_reports(engine: &MnemoEngine, agent_id: &str) -> Result<us
                 latency_ms: None,
                 cost_usd: None,
                 timestamp: now,
-                logical_clock: 0,
+                logical_clock: super::clock::tick(engine, agent_id).await,
                 content_hash: content_hash.clone(),
                 prev_hash: Some(compute_chain_hash(
                     &content_hash,
                     prev_event_hash.as_deref(),
                 )),
                 embedding: None,
+                request_id: crate::request_id::current(),
             };
             let _ = engine.storage.insert_event(&event).await;
         }
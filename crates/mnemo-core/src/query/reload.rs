@@ -0,0 +1,170 @@
+//! Applying a changed engine config to a running engine.
+//!
+//! Most of [`MnemoEngineConfig`] is wired in when the engine is built:
+//! storage, indexes, the embedder, encryption. A few operational settings
+//! are read per request instead and can be swapped while the engine
+//! serves: the `[limits]` quotas, the `auto_checkpoint` policies, the
+//! `[[exports]]` schedules and `read_only`. [`execute`] diffs two configs
+//! setting by setting, applies the reloadable changes at once and reports
+//! the rest as needing a restart. Each change is logged with its old and
+//! new value; secrets are redacted.
+
+use std::sync::{Arc, PoisonError, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::MnemoEngineConfig;
+use crate::error::Result;
+use crate::query::MnemoEngine;
+
+/// Top-level config keys [`execute`] applies without a restart.
+pub const RELOADABLE_SETTINGS: &[&str] = &["limits", "auto_checkpoint", "exports", "read_only"];
+
+/// A value readers take a snapshot of, swapped whole on reload.
+#[derive(Debug, Default)]
+pub(crate) struct Swappable<T>(RwLock<Arc<T>>);
+
+impl<T> Swappable<T> {
+    pub(crate) fn load(&self) -> Arc<T> {
+        self.0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub(crate) fn store(&self, value: T) {
+        *self.0.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(value);
+    }
+}
+
+/// One setting that differs between two configs. `setting` is its dotted
+/// path, e.g. `limits.max_tags`; a missing value is `null`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SettingChange {
+    pub setting: String,
+    pub before: serde_json::Value,
+    pub after: serde_json::Value,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigReload {
+    /// Changes now in effect.
+    pub applied: Vec<SettingChange>,
+    /// Changes that take effect on the next start.
+    pub restart_required: Vec<SettingChange>,
+}
+
+impl ConfigReload {
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty() && self.restart_required.is_empty()
+    }
+}
+
+/// Every setting that differs between `before` and `after`, in path order.
+pub fn diff(before: &MnemoEngineConfig, after: &MnemoEngineConfig) -> Result<Vec<SettingChange>> {
+    let mut changes = Vec::new();
+    diff_values(
+        "",
+        &serde_json::to_value(before)?,
+        &serde_json::to_value(after)?,
+        &mut changes,
+    );
+    Ok(changes)
+}
+
+fn diff_values(
+    path: &str,
+    before: &serde_json::Value,
+    after: &serde_json::Value,
+    changes: &mut Vec<SettingChange>,
+) {
+    use serde_json::Value;
+
+    if before == after {
+        return;
+    }
+    if let (Value::Object(b), Value::Object(a)) = (before, after) {
+        let mut keys: Vec<&String> = b.keys().chain(a.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        for key in keys {
+            let child = if path.is_empty() {
+                key.clone()
+            } else {
+                format!("{path}.{key}")
+            };
+            diff_values(
+                &child,
+                b.get(key).unwrap_or(&Value::Null),
+                a.get(key).unwrap_or(&Value::Null),
+                changes,
+            );
+        }
+        return;
+    }
+    let secret = path
+        .rsplit('.')
+        .next()
+        .is_some_and(|leaf| leaf.ends_with("key"));
+    let redact = |v: &Value| match v {
+        Value::Null => Value::Null,
+        _ if secret => Value::String("<redacted>".to_string()),
+        other => other.clone(),
+    };
+    changes.push(SettingChange {
+        setting: path.to_string(),
+        before: redact(before),
+        after: redact(after),
+    });
+}
+
+/// Apply `next` over `running`, the config the engine runs with. On
+/// success `running` takes `next`'s reloadable settings and keeps the
+/// rest, so a later reload still reports restart-only changes.
+pub fn execute(
+    engine: &MnemoEngine,
+    running: &mut MnemoEngineConfig,
+    next: &MnemoEngineConfig,
+) -> Result<ConfigReload> {
+    next.validate()?;
+    let mut reload = ConfigReload::default();
+    for change in diff(running, next)? {
+        let top = change.setting.split('.').next().unwrap_or_default();
+        if RELOADABLE_SETTINGS.contains(&top) {
+            tracing::info!(
+                setting = %change.setting,
+                before = %change.before,
+                after = %change.after,
+                "config setting reloaded"
+            );
+            reload.applied.push(change);
+        } else {
+            tracing::warn!(
+                setting = %change.setting,
+                before = %change.before,
+                after = %change.after,
+                "config setting changed; takes effect after a restart"
+            );
+            reload.restart_required.push(change);
+        }
+    }
+
+    if next.limits != running.limits {
+        engine.limits.store(next.limits.clone());
+        running.limits = next.limits.clone();
+    }
+    if next.auto_checkpoint != running.auto_checkpoint {
+        engine.auto_checkpoint.store(next.auto_checkpoint.clone());
+        running.auto_checkpoint = next.auto_checkpoint.clone();
+    }
+    if next.exports != running.exports {
+        engine.exports.store(next.exports.clone());
+        running.exports = next.exports.clone();
+    }
+    if next.read_only != running.read_only {
+        engine.set_read_only(next.read_only);
+        running.read_only = next.read_only;
+    }
+    Ok(reload)
+}
@@ -3,12 +3,16 @@ use uuid::Uuid;
 
 use crate::error::{Error, Result};
 use crate::hash::{compute_chain_hash, compute_content_hash};
+use crate::index::ShardRoute;
 use crate::model::event::{AgentEvent, EventType};
 use crate::model::memory::{ConsolidationState, MemoryRecord, MemoryType, Scope, SourceType};
-use crate::model::relation::Relation;
+use crate::model::outbox::OutboxMessage;
+use crate::model::relation::{DUPLICATE_OF, Relation};
 use crate::query::MnemoEngine;
-#[allow(unused_imports)]
-use base64::Engine as _;
+use crate::query::dedup::{self, DedupAction, DuplicateMatch};
+use crate::query::outbox::MEMORY_WRITE_TOPIC;
+use crate::search::language::{self, LANGUAGE_METADATA_KEY};
+use crate::storage::WriteBatch;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RememberRequest {
@@ -27,6 +31,20 @@ pub struct RememberRequest {
     pub related_to: Option<Vec<String>>,
     pub decay_rate: Option<f32>,
     pub created_by: Option<String>,
+    /// Protect the memory from decay, consolidation, TTL expiry and
+    /// criteria-based forgetting. Pinned memories never get a TTL.
+    pub pinned: Option<bool>,
+    /// When the fact starts to hold (RFC 3339). See
+    /// [`crate::query::validity`].
+    #[serde(default)]
+    pub valid_from: Option<String>,
+    /// When the fact stops holding, exclusive (RFC 3339).
+    #[serde(default)]
+    pub valid_until: Option<String>,
+    /// Remember into this [channel](crate::query::channel) so every member
+    /// can recall the memory. The caller must be a member.
+    #[serde(default)]
+    pub channel: Option<String>,
 }
 
 impl RememberRequest {
@@ -47,6 +65,10 @@ impl RememberRequest {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         }
     }
 }
@@ -56,20 +78,134 @@ impl RememberRequest {
 pub struct RememberResponse {
     pub id: Uuid,
     pub content_hash: String,
+    /// Set when the engine's [`DedupPolicy`](super::dedup::DedupPolicy)
+    /// matched an existing memory. For `skip` and `merge`, `id` is that
+    /// memory's id and nothing new was stored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplicate: Option<DuplicateMatch>,
 }
 
 impl RememberResponse {
     pub fn new(id: Uuid, content_hash: String) -> Self {
-        Self { id, content_hash }
+        Self {
+            id,
+            content_hash,
+            duplicate: None,
+        }
     }
 }
 
 pub async fn execute(engine: &MnemoEngine, request: RememberRequest) -> Result<RememberResponse> {
+    store(engine, request, EmbedWith::Content).await
+}
+
+/// Store every request in order, embedding their contents with one
+/// [`embed_batch`](crate::embedding::EmbeddingProvider::embed_batch) call
+/// up front. The first request that fails stops the batch; those before
+/// it stay stored.
+pub async fn execute_batch(
+    engine: &MnemoEngine,
+    requests: Vec<RememberRequest>,
+) -> Result<Vec<RememberResponse>> {
+    engine.limits().check_batch("memories", requests.len())?;
+    for (i, request) in requests.iter().enumerate() {
+        if request.content.trim().is_empty() {
+            return Err(Error::invalid_field(
+                format!("memories[{i}].content"),
+                "cannot be empty",
+            ));
+        }
+    }
+    let contents: Vec<&str> = requests.iter().map(|r| r.content.as_str()).collect();
+    let embeddings = engine.embedding.embed_batch(&contents).await?;
+    let mut responses = Vec::with_capacity(requests.len());
+    for (request, embedding) in requests.into_iter().zip(embeddings) {
+        let agent = request
+            .agent_id
+            .clone()
+            .unwrap_or_else(|| engine.default_agent_id.clone());
+        let stored = crate::storage::with_agent_context(
+            agent,
+            store(engine, request, EmbedWith::Vector(embedding)),
+        )
+        .await?;
+        responses.push(stored);
+    }
+    Ok(responses)
+}
+
+/// Where [`store`] gets the memory's embedding.
+pub(crate) enum EmbedWith {
+    /// Embed the content.
+    Content,
+    /// A vector already computed for the content.
+    Vector(Vec<f32>),
+    /// No embedding: the memory skips the near-duplicate check and the
+    /// vector index, and is found by exact and BM25 recall only.
+    Nothing,
+}
+
+/// Store `request`, embedded as `embed_with` says.
+pub(crate) async fn store(
+    engine: &MnemoEngine,
+    request: RememberRequest,
+    embed_with: EmbedWith,
+) -> Result<RememberResponse> {
+    match prepare(engine, request, embed_with).await? {
+        Prepared::Existing(response) => Ok(response),
+        Prepared::Pending(pending) => {
+            engine.storage.commit_write(&pending.batch).await?;
+            finish(engine, *pending).await
+        }
+    }
+}
+
+/// What [`prepare`] made of a request.
+pub(crate) enum Prepared {
+    /// The dedup policy matched an existing memory and nothing is to be
+    /// written.
+    Existing(RememberResponse),
+    /// A new memory, ready to commit.
+    Pending(Box<PendingWrite>),
+}
+
+/// A new memory: the rows to write in one transaction, which callers may
+/// extend with rows of their own, and what [`finish`] needs once they are
+/// committed.
+pub(crate) struct PendingWrite {
+    pub batch: WriteBatch,
+    embedding: Option<Vec<f32>>,
+    content_language: Option<String>,
+    content_hash: Vec<u8>,
+    duplicate: Option<DuplicateMatch>,
+}
+
+/// Validate `request` and build the rows [`store`] writes for it, without
+/// writing them.
+pub(crate) async fn prepare(
+    engine: &MnemoEngine,
+    request: RememberRequest,
+    embed_with: EmbedWith,
+) -> Result<Prepared> {
     // Validate
     if request.content.trim().is_empty() {
-        return Err(Error::Validation("content cannot be empty".to_string()));
+        return Err(Error::invalid_field("content", "cannot be empty"));
+    }
+    let limits = engine.limits();
+    limits.check_content(&request.content)?;
+    if let Some(ref tags) = request.tags {
+        limits.check_tags(tags)?;
+    }
+    if let Some(ref metadata) = request.metadata {
+        limits.check_metadata(metadata)?;
+    }
+    if let Some(ref related_to) = request.related_to {
+        limits.check_batch("related_to", related_to.len())?;
     }
 
+    let (valid_from, valid_until) =
+        super::validity::normalize_window(request.valid_from, request.valid_until)?;
+
     let resolved_tier = request.memory_type.unwrap_or(MemoryType::Episodic);
 
     // Tier-specific importance enforcement:
@@ -80,8 +216,9 @@ pub async fn execute(engine: &MnemoEngine, request: RememberRequest) -> Result<R
         importance = engine.procedural_importance_floor;
     }
     if !(0.0..=1.0).contains(&importance) {
-        return Err(Error::Validation(
-            "importance must be between 0.0 and 1.0".to_string(),
+        return Err(Error::invalid_field(
+            "importance",
+            "must be between 0.0 and 1.0",
         ));
     }
 
@@ -89,13 +226,69 @@ pub async fn execute(engine: &MnemoEngine, request: RememberRequest) -> Result<R
         .agent_id
         .unwrap_or_else(|| engine.default_agent_id.clone());
     super::validate_agent_id(&agent_id)?;
+    let channel = match request.channel {
+        Some(name) => {
+            super::channel::check_write(engine, &name, &agent_id).await?;
+            Some(name)
+        }
+        None => None,
+    };
+    super::ingestion_filter::enforce(
+        engine,
+        &agent_id,
+        &request.content,
+        request.thread_id.clone(),
+    )
+    .await?;
     let org_id = request.org_id.or_else(|| engine.default_org_id.clone());
     let now = chrono::Utc::now();
     let now_str = now.to_rfc3339();
     let id = Uuid::now_v7();
 
     // Compute embedding
-    let embedding = engine.embedding.embed(&request.content).await?;
+    let embedding = match embed_with {
+        EmbedWith::Content => Some(engine.embedding.embed(&request.content).await?),
+        EmbedWith::Vector(vector) => Some(vector),
+        EmbedWith::Nothing => None,
+    };
+
+    // Near-duplicate check: skip and merge return the existing memory
+    // without storing anything; link stores and relates the two below.
+    let mut duplicate = None;
+    if let Some(ref policy) = engine.dedup_policy
+        && let Some(ref embedding) = embedding
+        && let Some((existing, similarity)) = dedup::find_duplicate(
+            engine,
+            policy,
+            &agent_id,
+            request.thread_id.as_deref(),
+            resolved_tier,
+            channel.as_deref(),
+            embedding,
+        )
+        .await?
+    {
+        let found = DuplicateMatch {
+            memory_id: existing.id,
+            similarity,
+            action: policy.action,
+        };
+        if policy.action == DedupAction::Link {
+            duplicate = Some(found);
+        } else {
+            let existing_id = existing.id;
+            let existing_hash = hex::encode(&existing.content_hash);
+            if policy.action == DedupAction::Merge {
+                let tags = request.tags.as_deref().unwrap_or_default();
+                dedup::merge_into(engine, existing, importance, tags).await?;
+            }
+            return Ok(Prepared::Existing(RememberResponse {
+                id: existing_id,
+                content_hash: existing_hash,
+                duplicate: Some(found),
+            }));
+        }
+    }
 
     // Compute content hash
     let content_hash = compute_content_hash(&request.content, &agent_id, &now_str);
@@ -112,7 +305,8 @@ pub async fn execute(engine: &MnemoEngine, request: RememberRequest) -> Result<R
 
     // Compute expires_at from ttl_seconds. Working-tier memories get an
     // automatic TTL so they can't outlive their session — caller-supplied
-    // ttl_seconds still wins.
+    // ttl_seconds still wins. Pinned memories never expire.
+    let pinned = request.pinned.unwrap_or(false);
     let effective_ttl = request.ttl_seconds.or_else(|| {
         if resolved_tier == MemoryType::Working {
             Some(engine.ttl_working_seconds)
@@ -120,21 +314,36 @@ pub async fn execute(engine: &MnemoEngine, request: RememberRequest) -> Result<R
             None
         }
     });
-    let expires_at =
-        effective_ttl.map(|ttl| (now + chrono::Duration::seconds(ttl as i64)).to_rfc3339());
+    let expires_at = effective_ttl
+        .filter(|_| !pinned)
+        .map(|ttl| (now + chrono::Duration::seconds(ttl as i64)).to_rfc3339());
+
+    let scope = match (&channel, request.scope) {
+        (Some(_), None | Some(Scope::Shared)) => Scope::Shared,
+        (Some(_), Some(_)) => {
+            return Err(Error::invalid_field(
+                "scope",
+                "channel memories are always shared",
+            ));
+        }
+        (None, scope) => scope.unwrap_or(Scope::Private),
+    };
+    let curation = super::curation::initial_status(engine, &agent_id, scope);
+    let tags = request.tags.unwrap_or_default();
+    let attestation = super::attestation::initial_status(engine, &tags);
 
     let mut record = MemoryRecord {
         id,
         agent_id: agent_id.clone(),
         content: request.content,
         memory_type: resolved_tier,
-        scope: request.scope.unwrap_or(Scope::Private),
+        scope,
         importance,
-        tags: request.tags.unwrap_or_default(),
+        tags,
         metadata: request
             .metadata
             .unwrap_or(serde_json::Value::Object(serde_json::Map::new())),
-        embedding: Some(embedding.clone()),
+        embedding: embedding.clone(),
         content_hash: content_hash.clone(),
         prev_hash,
         source_type: request.source_type.unwrap_or(SourceType::Agent),
@@ -155,45 +364,40 @@ pub async fn execute(engine: &MnemoEngine, request: RememberRequest) -> Result<R
         quarantined: false,
         quarantine_reason: None,
         decay_function: None,
+        pinned,
+        valid_from,
+        valid_until,
+        curation,
+        attestation,
+        channel,
     };
 
-    // Encrypt content if encryption is configured (after embedding, before storage)
-    if let Some(ref enc) = engine.encryption {
-        let encrypted = enc.encrypt(record.content.as_bytes())?;
-        record.content =
-            base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &encrypted);
-    }
-
-    // Store in database
-    engine.storage.insert_memory(&record).await?;
-
-    // Add to vector index
-    engine.index.add(id, &embedding)?;
-
-    // Add to full-text index if available
-    if let Some(ref ft) = engine.full_text {
-        ft.add(id, &record.content)?;
-        ft.commit()?;
-    }
-
-    // Check for anomaly and update agent profile
-    let anomaly_result = super::poisoning::check_for_anomaly(engine, &record).await?;
-    if anomaly_result.is_anomalous {
-        super::poisoning::quarantine_memory(engine, id, &anomaly_result.reasons.join("; ")).await?;
-        tracing::warn!(
-            memory_id = %id,
-            score = anomaly_result.score,
-            reasons = ?anomaly_result.reasons,
-            "Memory quarantined due to anomaly detection"
+    // Detect the content language on the plaintext so the full-text index
+    // can pick a matching analyzer. A caller-supplied code wins.
+    if let Some(map) = record.metadata.as_object_mut()
+        && !map.contains_key(LANGUAGE_METADATA_KEY)
+        && let Some(detected) = language::detect(&record.content).filter(|d| d.reliable)
+    {
+        map.insert(
+            LANGUAGE_METADATA_KEY.to_string(),
+            serde_json::Value::String(detected.code.to_string()),
         );
     }
-    super::poisoning::update_agent_profile(engine, &record).await?;
+    let content_language = record
+        .metadata
+        .get(LANGUAGE_METADATA_KEY)
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+
+    // Encrypt content if encryption is configured (after embedding, before storage)
+    record.content = engine.encrypt_content(record.org_id.as_deref(), &record.content)?;
 
-    // Create relations if specified
+    // Relations requested by the caller, plus the link to a near-duplicate.
+    let mut relations = Vec::new();
     if let Some(ref related_ids) = request.related_to {
         for target_str in related_ids {
             if let Ok(target_id) = Uuid::parse_str(target_str) {
-                let relation = Relation {
+                relations.push(Relation {
                     id: Uuid::now_v7(),
                     source_id: id,
                     target_id,
@@ -201,15 +405,23 @@ pub async fn execute(engine: &MnemoEngine, request: RememberRequest) -> Result<R
                     weight: 1.0,
                     metadata: serde_json::Value::Object(serde_json::Map::new()),
                     created_at: record.created_at.clone(),
-                };
-                if let Err(e) = engine.storage.insert_relation(&relation).await {
-                    tracing::error!(relation_id = %relation.id, error = %e, "failed to insert relation");
-                }
+                });
             }
         }
     }
+    if let Some(ref found) = duplicate {
+        relations.push(Relation {
+            id: Uuid::now_v7(),
+            source_id: id,
+            target_id: found.memory_id,
+            relation_type: DUPLICATE_OF.to_string(),
+            weight: found.similarity,
+            metadata: serde_json::Value::Object(serde_json::Map::new()),
+            created_at: record.created_at.clone(),
+        });
+    }
 
-    // Emit MemoryWrite event with hash chain linking (fire-and-forget)
+    // MemoryWrite event with hash chain linking
     let prev_event_hash = match engine
         .storage
         .get_latest_event_hash(&agent_id, record.thread_id.as_deref())
@@ -241,10 +453,11 @@ pub async fn execute(engine: &MnemoEngine, request: RememberRequest) -> Result<R
         latency_ms: None,
         cost_usd: None,
         timestamp: record.created_at.clone(),
-        logical_clock: 0,
+        logical_clock: super::clock::tick(engine, &record.agent_id).await,
         content_hash: content_hash.clone(),
         prev_hash: event_prev_hash,
         embedding: None,
+        request_id: crate::request_id::current(),
     };
     // Optionally embed the event payload
     if engine.embed_events
@@ -252,19 +465,99 @@ pub async fn execute(engine: &MnemoEngine, request: RememberRequest) -> Result<R
     {
         event.embedding = Some(emb);
     }
-    if let Err(e) = engine.storage.insert_event(&event).await {
-        tracing::error!(event_id = %event.id, error = %e, "failed to insert audit event");
+
+    let mut outbox = Vec::new();
+    if engine.outbox_enabled {
+        outbox.push(OutboxMessage::new(
+            MEMORY_WRITE_TOPIC,
+            serde_json::json!({
+                "memory_id": id.to_string(),
+                "agent_id": record.agent_id,
+                "thread_id": record.thread_id,
+                "scope": record.scope.to_string(),
+                "event_id": event.id.to_string(),
+            }),
+        ));
+    }
+
+    // The memory goes in with its relations, audit event and outbox
+    // message in one transaction, so none of them can exist without the
+    // others.
+    Ok(Prepared::Pending(Box::new(PendingWrite {
+        batch: WriteBatch {
+            memory: Some(record),
+            relations,
+            events: vec![event],
+            outbox,
+            ..Default::default()
+        },
+        embedding,
+        content_language,
+        content_hash,
+        duplicate,
+    })))
+}
+
+/// Index, screen and cache a memory once its [`PendingWrite`] is
+/// committed.
+pub(crate) async fn finish(
+    engine: &MnemoEngine,
+    pending: PendingWrite,
+) -> Result<RememberResponse> {
+    let PendingWrite {
+        batch,
+        embedding,
+        content_language,
+        content_hash,
+        duplicate,
+    } = pending;
+    let record = batch
+        .memory
+        .ok_or_else(|| Error::Internal("pending write without a memory".to_string()))?;
+    let id = record.id;
+    let agent_id = record.agent_id.clone();
+
+    // Add to vector index
+    if let Some(ref embedding) = embedding {
+        engine
+            .index
+            .add_routed(ShardRoute::of(&record), id, embedding)?;
+    }
+
+    // Add to full-text index if available
+    if let Some(ref ft) = engine.full_text {
+        ft.add_with_language(id, &record.content, content_language.as_deref())?;
+        ft.commit()?;
+    }
+
+    // Check for anomaly and update agent profile
+    let anomaly_result = super::poisoning::check_for_anomaly(engine, &record).await?;
+    if anomaly_result.is_anomalous {
+        super::poisoning::quarantine_memory(engine, id, &anomaly_result.reasons.join("; ")).await?;
+        tracing::warn!(
+            memory_id = %id,
+            score = anomaly_result.score,
+            reasons = ?anomaly_result.reasons,
+            "Memory quarantined due to anomaly detection"
+        );
     }
+    super::poisoning::update_agent_profile(engine, &record).await?;
+    let thread_id = record.thread_id.clone();
 
     // Put in cache if configured
     if let Some(ref cache) = engine.cache {
         cache.put(record);
     }
 
+    if let Some(ref thread_id) = thread_id {
+        super::auto_checkpoint::record_write(engine, &agent_id, thread_id).await;
+    }
+
     let hash_hex = hex::encode(&content_hash);
 
     Ok(RememberResponse {
         id,
         content_hash: hash_hex,
+        duplicate,
     })
 }
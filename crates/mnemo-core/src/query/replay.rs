@@ -1,8 +1,11 @@
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::error::{Error, Result};
 use crate::hash::{ChainVerificationResult, verify_chain};
+use crate::model::acl::Permission;
 use crate::model::checkpoint::Checkpoint;
 use crate::model::event::AgentEvent;
 use crate::model::memory::MemoryRecord;
@@ -31,6 +34,13 @@ impl ReplayRequest {
             as_of: None,
         }
     }
+
+    /// Check `as_of` and normalize it to UTC RFC 3339; see
+    /// [`validation`](super::validation).
+    pub fn normalize(&mut self) -> Result<()> {
+        self.as_of = super::validation::normalize_optional_timestamp("as_of", self.as_of.take())?;
+        Ok(())
+    }
 }
 
 #[non_exhaustive]
@@ -58,12 +68,63 @@ impl ReplayResponse {
     }
 }
 
-pub async fn execute(engine: &MnemoEngine, request: ReplayRequest) -> Result<ReplayResponse> {
+/// Longest pause a streamed replay may put between two events.
+pub const MAX_REPLAY_PACE: Duration = Duration::from_secs(10);
+
+/// One message of a streamed replay, for playback UIs that step through a
+/// thread: a single [`ReplayFrame::State`] first, then one
+/// [`ReplayFrame::Event`] per event in logical-clock order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReplayFrame {
+    /// The checkpoint state the events lead up to, with its memories.
+    /// `event_count` is the number of event frames that follow.
+    State {
+        checkpoint: Checkpoint,
+        memories: Vec<MemoryRecord>,
+        chain_verification: Option<ChainVerificationResult>,
+        event_count: usize,
+    },
+    Event {
+        event: AgentEvent,
+    },
+}
+
+impl ReplayResponse {
+    /// Split the replay into stream frames. Events are ordered by logical
+    /// clock, then timestamp, then id, so ties and clock-less legacy events
+    /// keep their write order.
+    pub fn into_frames(self) -> Vec<ReplayFrame> {
+        let mut events = self.events;
+        events.sort_by(|a, b| {
+            (a.logical_clock, &a.timestamp, a.id).cmp(&(b.logical_clock, &b.timestamp, b.id))
+        });
+        let mut frames = Vec::with_capacity(events.len() + 1);
+        frames.push(ReplayFrame::State {
+            checkpoint: self.checkpoint,
+            memories: self.memories,
+            chain_verification: self.chain_verification,
+            event_count: events.len(),
+        });
+        frames.extend(events.into_iter().map(|event| ReplayFrame::Event { event }));
+        frames
+    }
+}
+
+/// Replay a thread's state. The caller (`agent_id`, defaulting to the
+/// engine's agent) must own the thread or hold a read delegation from its
+/// owner.
+pub async fn execute(engine: &MnemoEngine, mut request: ReplayRequest) -> Result<ReplayResponse> {
+    request.normalize()?;
     // Time-travel path: synthesize a virtual checkpoint at `as_of`.
     if let Some(ref as_of) = request.as_of {
         return replay_as_of(engine, &request, as_of).await;
     }
 
+    let agent_id = request
+        .agent_id
+        .clone()
+        .unwrap_or_else(|| engine.default_agent_id.clone());
     let branch = request.branch_name.as_deref().unwrap_or("main");
 
     // Get checkpoint (specified or latest)
@@ -85,6 +146,14 @@ pub async fn execute(engine: &MnemoEngine, request: ReplayRequest) -> Result<Rep
                 ))
             })?
     };
+    super::checkpoint::ensure_thread_access(
+        engine,
+        &checkpoint.thread_id,
+        &agent_id,
+        Permission::Replay,
+    )
+    .await?;
+    let checkpoint = super::checkpoint::materialize(engine, checkpoint).await?;
 
     // Load memories referenced by checkpoint.memory_refs
     let mut memories = Vec::new();
@@ -117,6 +186,15 @@ pub async fn execute(engine: &MnemoEngine, request: ReplayRequest) -> Result<Rep
         events
     };
 
+    // The agent is about to resume the thread: warm its first recall.
+    if engine.cache.is_some() {
+        let mut prefetch = super::prefetch::PrefetchRequest::new(checkpoint.thread_id.clone());
+        prefetch.agent_id = Some(agent_id);
+        if let Err(e) = super::prefetch::execute(engine, prefetch).await {
+            tracing::warn!(thread_id = %checkpoint.thread_id, "prefetch after replay failed: {e}");
+        }
+    }
+
     Ok(ReplayResponse {
         checkpoint,
         memories,
@@ -134,15 +212,22 @@ async fn replay_as_of(
     request: &ReplayRequest,
     as_of_str: &str,
 ) -> Result<ReplayResponse> {
-    let as_of = chrono::DateTime::parse_from_rfc3339(as_of_str)
-        .map_err(|e| Error::Validation(format!("invalid as_of timestamp '{as_of_str}': {e}")))?
-        .with_timezone(&chrono::Utc);
+    let as_of = super::validation::parse_timestamp("as_of", as_of_str)?;
 
     let agent_id = request
         .agent_id
         .clone()
         .unwrap_or_else(|| engine.default_agent_id.clone());
     super::validate_agent_id(&agent_id)?;
+    // The thread's events are returned too, so another agent's thread is
+    // off limits without a delegation.
+    super::checkpoint::ensure_thread_access(
+        engine,
+        &request.thread_id,
+        &agent_id,
+        Permission::Replay,
+    )
+    .await?;
 
     // Pull all memories for the agent (including soft-deleted ones, so we can
     // decide per-record whether they existed at `as_of`).
@@ -0,0 +1,181 @@
+//! Immutable (WORM) retention.
+//!
+//! Some deployments must prove that records survive for a fixed number of
+//! years. A [`RetentionLockPolicy`] maps organisations and tags to a
+//! retention period counted from each memory's `created_at`; a memory is
+//! locked until the longest period that covers it has passed.
+//!
+//! While locked, a memory cannot be erased: `forget` with the
+//! `hard_delete` or `redact` strategy (and `forget_subject`, which uses
+//! them) reports it as an error even with `force`, the trash purge keeps
+//! it, and the TTL sweep leaves it alone once it has expired. Soft delete
+//! and archiving still work, so a locked memory can be hidden from recall.
+//!
+//! Nor can a lock be lifted from under a memory: an `update` whose new tags
+//! would end or shorten the lock is refused, ownership transfer won't
+//! reassign a locked memory, and `rechain` leaves locked memories' hashes
+//! as they are. Every refused operation is written as a
+//! `RetentionLockViolation` audit event on the agent that asked for it.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Months, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::model::event::EventType;
+use crate::model::memory::MemoryRecord;
+use crate::query::MnemoEngine;
+
+/// Retention lock periods by organisation and by tag.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetentionLockPolicy {
+    /// Periods for every memory of an organisation, keyed by `org_id`.
+    pub orgs: BTreeMap<String, RetentionPeriod>,
+    /// Periods for memories carrying a tag.
+    pub tags: BTreeMap<String, RetentionPeriod>,
+}
+
+impl RetentionLockPolicy {
+    pub fn with_org(mut self, org_id: impl Into<String>, period: RetentionPeriod) -> Self {
+        self.orgs.insert(org_id.into(), period);
+        self
+    }
+
+    pub fn with_tag(mut self, tag: impl Into<String>, period: RetentionPeriod) -> Self {
+        self.tags.insert(tag.into(), period);
+        self
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        for (kind, rules) in [("orgs", &self.orgs), ("tags", &self.tags)] {
+            for (key, period) in rules {
+                if period.years == 0 && period.days == 0 {
+                    return Err(Error::Validation(format!(
+                        "retention_lock.{kind}.{key} needs years or days"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// End of the longest lock covering `record`, if any covers it.
+    /// Memories with an unreadable `created_at` count as created now.
+    pub fn locked_until(&self, record: &MemoryRecord) -> Option<DateTime<Utc>> {
+        let created = DateTime::parse_from_rfc3339(&record.created_at)
+            .map(|t| t.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now());
+        record
+            .org_id
+            .as_ref()
+            .and_then(|org| self.orgs.get(org))
+            .into_iter()
+            .chain(record.tags.iter().filter_map(|t| self.tags.get(t)))
+            .filter_map(|period| period.end(created))
+            .max()
+    }
+}
+
+/// How long a lock lasts: `years` calendar years plus `days` days.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetentionPeriod {
+    pub years: u32,
+    pub days: u32,
+}
+
+impl RetentionPeriod {
+    pub fn years(years: u32) -> Self {
+        Self { years, days: 0 }
+    }
+
+    pub fn days(days: u32) -> Self {
+        Self { years: 0, days }
+    }
+
+    fn end(self, start: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        start
+            .checked_add_months(Months::new(self.years.checked_mul(12)?))?
+            .checked_add_signed(chrono::Duration::days(i64::from(self.days)))
+    }
+}
+
+/// End of the engine's lock on `record`, if it is still locked.
+pub(crate) fn active_lock(engine: &MnemoEngine, record: &MemoryRecord) -> Option<DateTime<Utc>> {
+    engine
+        .retention_lock
+        .as_ref()?
+        .locked_until(record)
+        .filter(|until| *until > Utc::now())
+}
+
+/// Refuse `operation` on a locked `record`, logging the attempt as a
+/// `RetentionLockViolation` event on `agent_id`.
+pub(crate) async fn ensure_erasable(
+    engine: &MnemoEngine,
+    record: &MemoryRecord,
+    operation: &str,
+    agent_id: &str,
+) -> Result<()> {
+    let Some(until) = active_lock(engine, record) else {
+        return Ok(());
+    };
+    refuse(engine, record, operation, agent_id, until).await
+}
+
+/// Refuse changing `before` into `after` when that would end or shorten
+/// `before`'s active lock, logging the attempt like [`ensure_erasable`].
+pub(crate) async fn ensure_lock_kept(
+    engine: &MnemoEngine,
+    before: &MemoryRecord,
+    after: &MemoryRecord,
+    operation: &str,
+    agent_id: &str,
+) -> Result<()> {
+    let Some(until) = active_lock(engine, before) else {
+        return Ok(());
+    };
+    if active_lock(engine, after).is_some_and(|kept| kept >= until) {
+        return Ok(());
+    }
+    refuse(engine, before, operation, agent_id, until).await
+}
+
+async fn refuse(
+    engine: &MnemoEngine,
+    record: &MemoryRecord,
+    operation: &str,
+    agent_id: &str,
+    until: DateTime<Utc>,
+) -> Result<()> {
+    let locked_until = until.to_rfc3339();
+    tracing::warn!(
+        memory_id = %record.id,
+        operation,
+        agent_id,
+        %locked_until,
+        "refused by retention lock"
+    );
+    let event = super::event_builder::build_event(
+        engine,
+        agent_id,
+        EventType::RetentionLockViolation,
+        serde_json::json!({
+            "memory_id": record.id.to_string(),
+            "operation": operation,
+            "locked_until": locked_until,
+        }),
+        &record.id.to_string(),
+        None,
+    )
+    .await;
+    if let Err(e) = engine.storage.insert_event(&event).await {
+        tracing::error!(event_id = %event.id, error = %e, "failed to insert audit event");
+    }
+    Err(Error::PermissionDenied(format!(
+        "memory {} is under retention lock until {locked_until}",
+        record.id
+    )))
+}
@@ -0,0 +1,596 @@
+//! Shadow recall: compare alternate retrieval configurations on live
+//! traffic.
+//!
+//! Offline benchmarks rarely predict how a strategy or fusion change does
+//! on production queries. With a [`ShadowRecallConfig`], a `sample_rate`
+//! share of recalls is also run under each configured [`ShadowArm`] —
+//! the caller's request with the arm's settings laid over it. The arms run
+//! on a background worker after the caller has its response, their
+//! results are never returned, and they are unrecorded: no access
+//! timestamps, access log entries or events. Each run is logged as a
+//! [`ShadowComparison`] of the primary and arm result sets with per-memory
+//! rank deltas, under the `mnemo::shadow_recall` tracing target and in a
+//! bounded in-memory log.
+//!
+//! Agents (or an evaluator) can report which memories proved useful with
+//! [`MnemoEngine::shadow_recall_feedback`]; the report
+//! ([`MnemoEngine::shadow_recall_report`]) ranks the arms by how many of
+//! those useful memories they found, then by overlap with the primary.
+//!
+//! The worker must be started with [`spawn_worker`]; until it is, sampled
+//! recalls queue up to `queue_capacity` and later ones are dropped.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Instant;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::recall_profile::RecallProfileSettings;
+use crate::query::MnemoEngine;
+use crate::query::recall::{RecallRequest, RecallResponse};
+
+/// Name the report gives the caller's own configuration.
+pub const PRIMARY_ARM: &str = "primary";
+
+/// An alternate configuration to shadow recalls with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ShadowArm {
+    pub name: String,
+    /// Settings laid over the caller's request; set fields win.
+    pub settings: RecallProfileSettings,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShadowRecallConfig {
+    /// Share of recalls shadowed, in `[0, 1]`.
+    pub sample_rate: f64,
+    pub arms: Vec<ShadowArm>,
+    /// Comparisons kept for the report; the oldest are dropped first.
+    pub max_comparisons: usize,
+    /// Sampled recalls waiting for the worker before more are dropped.
+    pub queue_capacity: usize,
+    /// How long after a recall feedback still counts towards it.
+    pub feedback_window_secs: u64,
+}
+
+impl Default for ShadowRecallConfig {
+    fn default() -> Self {
+        Self {
+            sample_rate: 0.05,
+            arms: Vec::new(),
+            max_comparisons: 1000,
+            queue_capacity: 64,
+            feedback_window_secs: 600,
+        }
+    }
+}
+
+impl ShadowRecallConfig {
+    pub fn validate(&self) -> Result<()> {
+        let field = |name: &str| format!("features.shadow_recall.{name}");
+        if !(0.0..=1.0).contains(&self.sample_rate) {
+            return Err(Error::invalid_field(
+                field("sample_rate"),
+                "must be in [0, 1]",
+            ));
+        }
+        if self.arms.is_empty() {
+            return Err(Error::invalid_field(
+                field("arms"),
+                "must list at least one arm",
+            ));
+        }
+        let mut names = HashSet::new();
+        for (i, arm) in self.arms.iter().enumerate() {
+            if arm.name.is_empty() || arm.name == PRIMARY_ARM || !names.insert(&arm.name) {
+                return Err(Error::invalid_field(
+                    format!("features.shadow_recall.arms[{i}].name"),
+                    format!("must be non-empty, unique and not '{PRIMARY_ARM}'"),
+                ));
+            }
+            if arm.settings.limit == Some(0) {
+                return Err(Error::invalid_field(
+                    format!("features.shadow_recall.arms[{i}].settings.limit"),
+                    "must be > 0",
+                ));
+            }
+            if let Some(ref hybrid) = arm.settings.hybrid {
+                hybrid.validate()?;
+            }
+        }
+        if self.max_comparisons == 0 {
+            return Err(Error::invalid_field(
+                field("max_comparisons"),
+                "must be > 0",
+            ));
+        }
+        if self.queue_capacity == 0 {
+            return Err(Error::invalid_field(field("queue_capacity"), "must be > 0"));
+        }
+        Ok(())
+    }
+}
+
+/// Where one memory ranked in the primary and an arm result set, 1-based.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RankDelta {
+    pub memory_id: Uuid,
+    pub primary_rank: Option<usize>,
+    pub shadow_rank: Option<usize>,
+}
+
+/// One arm's run against a shadowed recall.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArmResult {
+    pub arm: String,
+    pub memories: Vec<Uuid>,
+    /// Shared memories over the larger of the two result sets.
+    pub overlap: f64,
+    /// Every memory in either result set; unchanged ranks are omitted.
+    pub rank_deltas: Vec<RankDelta>,
+    pub latency_ms: u64,
+    /// Why the arm failed; its `memories` are then empty.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShadowComparison {
+    pub id: Uuid,
+    pub agent_id: String,
+    pub query: String,
+    pub at: String,
+    pub primary: Vec<Uuid>,
+    pub arms: Vec<ArmResult>,
+    /// Memories reported useful within the feedback window.
+    pub useful: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArmSummary {
+    pub arm: String,
+    pub comparisons: usize,
+    pub errors: usize,
+    /// Mean [`ArmResult::overlap`]; `1.0` for the primary.
+    pub mean_overlap: f64,
+    /// Mean rank change of memories in both result sets.
+    pub mean_rank_shift: f64,
+    pub mean_latency_ms: f64,
+    /// Useful memories this configuration returned.
+    pub useful_found: usize,
+    /// `useful_found` over every memory reported useful; `None` before
+    /// any feedback.
+    pub feedback_recall: Option<f64>,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ShadowRecallReport {
+    pub sample_rate: f64,
+    pub comparisons: usize,
+    /// Sampled recalls dropped because the queue was full.
+    pub dropped: u64,
+    /// The caller's configuration, as a baseline for the arms.
+    pub primary: Option<ArmSummary>,
+    /// Best first: by `feedback_recall`, then `mean_overlap`.
+    pub arms: Vec<ArmSummary>,
+}
+
+/// A sampled recall waiting for the worker.
+pub(crate) struct ShadowJob {
+    agent_id: String,
+    request: RecallRequest,
+    primary: Vec<Uuid>,
+    at: DateTime<Utc>,
+}
+
+/// The config, queue and comparison log of an engine's shadow recalls.
+pub struct ShadowRecall {
+    config: ShadowRecallConfig,
+    seen: AtomicU64,
+    dropped: AtomicU64,
+    jobs: mpsc::Sender<ShadowJob>,
+    /// Taken by [`spawn_worker`].
+    receiver: Mutex<Option<mpsc::Receiver<ShadowJob>>>,
+    comparisons: Mutex<VecDeque<ShadowComparison>>,
+}
+
+impl ShadowRecall {
+    pub fn new(config: ShadowRecallConfig) -> Self {
+        let (jobs, receiver) = mpsc::channel(config.queue_capacity.max(1));
+        Self {
+            config,
+            seen: AtomicU64::new(0),
+            dropped: AtomicU64::new(0),
+            jobs,
+            receiver: Mutex::new(Some(receiver)),
+            comparisons: Mutex::default(),
+        }
+    }
+
+    pub fn config(&self) -> &ShadowRecallConfig {
+        &self.config
+    }
+
+    /// Whether to shadow the next recall. Counts recalls rather than
+    /// drawing at random, so exactly `sample_rate` of them are picked.
+    pub(crate) fn sample(&self) -> bool {
+        let rate = self.config.sample_rate;
+        let n = self.seen.fetch_add(1, Ordering::Relaxed) as f64;
+        ((n + 1.0) * rate).floor() > (n * rate).floor()
+    }
+
+    /// Queue `request` for the arms. Never waits: a full queue drops it.
+    pub(crate) fn submit(
+        &self,
+        agent_id: String,
+        request: RecallRequest,
+        response: &RecallResponse,
+    ) {
+        let job = ShadowJob {
+            agent_id,
+            request,
+            primary: response.memories.iter().map(|m| m.id).collect(),
+            at: Utc::now(),
+        };
+        if self.jobs.try_send(job).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            tracing::debug!("shadow recall queue full; dropping sampled recall");
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<ShadowComparison>> {
+        self.comparisons
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn push(&self, comparison: ShadowComparison) {
+        let mut comparisons = self.lock();
+        if comparisons.len() >= self.config.max_comparisons {
+            comparisons.pop_front();
+        }
+        comparisons.push_back(comparison);
+    }
+
+    /// The newest `limit` comparisons, newest first.
+    pub fn comparisons(&self, limit: usize) -> Vec<ShadowComparison> {
+        self.lock().iter().rev().take(limit).cloned().collect()
+    }
+
+    /// Mark `memory_ids` useful to `agent_id`'s comparisons within the
+    /// feedback window. Returns how many comparisons it applied to.
+    pub fn feedback(&self, agent_id: &str, memory_ids: &[Uuid]) -> usize {
+        let cutoff = Utc::now() - Duration::seconds(self.config.feedback_window_secs as i64);
+        let mut applied = 0;
+        for comparison in self.lock().iter_mut().rev() {
+            let recent = DateTime::parse_from_rfc3339(&comparison.at)
+                .is_ok_and(|at| at.with_timezone(&Utc) >= cutoff);
+            if !recent {
+                break;
+            }
+            if comparison.agent_id != agent_id {
+                continue;
+            }
+            for id in memory_ids {
+                if !comparison.useful.contains(id) {
+                    comparison.useful.push(*id);
+                }
+            }
+            applied += 1;
+        }
+        applied
+    }
+
+    pub fn report(&self) -> ShadowRecallReport {
+        let comparisons = self.lock();
+        let total_useful: usize = comparisons.iter().map(|c| c.useful.len()).sum();
+        let feedback_recall =
+            |found: usize| (total_useful > 0).then(|| found as f64 / total_useful as f64);
+        let useful_in = |memories: &[Uuid], useful: &[Uuid]| {
+            useful.iter().filter(|id| memories.contains(id)).count()
+        };
+
+        let mut arms: Vec<ArmSummary> = self
+            .config
+            .arms
+            .iter()
+            .map(|arm| {
+                let results: Vec<(&ShadowComparison, &ArmResult)> = comparisons
+                    .iter()
+                    .filter_map(|c| c.arms.iter().find(|r| r.arm == arm.name).map(|r| (c, r)))
+                    .collect();
+                let ok: Vec<_> = results.iter().filter(|(_, r)| r.error.is_none()).collect();
+                // Shared memories left out of `rank_deltas` did not move.
+                let shared: usize = ok
+                    .iter()
+                    .map(|(c, r)| {
+                        r.memories
+                            .iter()
+                            .filter(|id| c.primary.contains(id))
+                            .count()
+                    })
+                    .sum();
+                let shift: usize = ok
+                    .iter()
+                    .flat_map(|(_, r)| &r.rank_deltas)
+                    .filter_map(|d| Some(d.shadow_rank?.abs_diff(d.primary_rank?)))
+                    .sum();
+                let useful_found = ok
+                    .iter()
+                    .map(|(c, r)| useful_in(&r.memories, &c.useful))
+                    .sum();
+                ArmSummary {
+                    arm: arm.name.clone(),
+                    comparisons: results.len(),
+                    errors: results.len() - ok.len(),
+                    mean_overlap: mean(ok.iter().map(|(_, r)| r.overlap)),
+                    mean_rank_shift: if shared == 0 {
+                        0.0
+                    } else {
+                        shift as f64 / shared as f64
+                    },
+                    mean_latency_ms: mean(ok.iter().map(|(_, r)| r.latency_ms as f64)),
+                    useful_found,
+                    feedback_recall: feedback_recall(useful_found),
+                }
+            })
+            .collect();
+        arms.sort_by(|a, b| {
+            b.feedback_recall
+                .unwrap_or(0.0)
+                .total_cmp(&a.feedback_recall.unwrap_or(0.0))
+                .then(b.mean_overlap.total_cmp(&a.mean_overlap))
+        });
+
+        let primary_found = comparisons
+            .iter()
+            .map(|c| useful_in(&c.primary, &c.useful))
+            .sum();
+        let primary = (!comparisons.is_empty()).then(|| ArmSummary {
+            arm: PRIMARY_ARM.to_string(),
+            comparisons: comparisons.len(),
+            errors: 0,
+            mean_overlap: 1.0,
+            mean_rank_shift: 0.0,
+            mean_latency_ms: 0.0,
+            useful_found: primary_found,
+            feedback_recall: feedback_recall(primary_found),
+        });
+        ShadowRecallReport {
+            sample_rate: self.config.sample_rate,
+            comparisons: comparisons.len(),
+            dropped: self.dropped.load(Ordering::Relaxed),
+            primary,
+            arms,
+        }
+    }
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, n) = values.fold((0.0, 0usize), |(sum, n), v| (sum + v, n + 1));
+    if n == 0 { 0.0 } else { sum / n as f64 }
+}
+
+/// `request` with every set field of `settings` laid over it, stripped of
+/// the response extras an arm has no use for.
+fn overlay(settings: &RecallProfileSettings, mut request: RecallRequest) -> RecallRequest {
+    if settings.strategy.is_some() {
+        request.strategy = settings.strategy.clone();
+        request.mode = None;
+    }
+    if settings.memory_types.is_some() {
+        request.memory_types = settings.memory_types.clone();
+        request.memory_type = None;
+    }
+    request.limit = settings.limit.or(request.limit);
+    request.scope = settings.scope.or(request.scope);
+    request.min_importance = settings.min_importance.or(request.min_importance);
+    if settings.tags.is_some() {
+        request.tags = settings.tags.clone();
+    }
+    // Named weights outrank positional ones, so an arm's choice of either
+    // clears the other.
+    if settings.hybrid.is_some() {
+        request.hybrid = settings.hybrid;
+        request.hybrid_weights = None;
+    }
+    if settings.hybrid_weights.is_some() {
+        request.hybrid_weights = settings.hybrid_weights.clone();
+        request.hybrid = None;
+    }
+    request.rrf_k = settings.rrf_k.or(request.rrf_k);
+    request.recency_half_life_hours = settings
+        .recency_half_life_hours
+        .or(request.recency_half_life_hours);
+    request.text_query_mode = settings.text_query_mode.or(request.text_query_mode);
+    if settings.text_field_boosts.is_some() {
+        request.text_field_boosts = settings.text_field_boosts.clone();
+    }
+
+    request.explain = None;
+    request.with_snippets = None;
+    request.with_provenance = None;
+    request.orientation_cache = None;
+    request.retained_token_budget = None;
+    request.include = None;
+    request
+}
+
+/// 1-based rank of each memory.
+fn ranks(memories: &[Uuid]) -> HashMap<Uuid, usize> {
+    memories
+        .iter()
+        .enumerate()
+        .map(|(i, id)| (*id, i + 1))
+        .collect()
+}
+
+fn compare(arm: &str, primary: &[Uuid], shadow: Vec<Uuid>, latency_ms: u64) -> ArmResult {
+    let primary_ranks = ranks(primary);
+    let shadow_ranks = ranks(&shadow);
+    let shared = shadow
+        .iter()
+        .filter(|id| primary_ranks.contains_key(id))
+        .count();
+    let larger = primary.len().max(shadow.len());
+    let mut rank_deltas = Vec::new();
+    for id in primary
+        .iter()
+        .chain(shadow.iter().filter(|id| !primary_ranks.contains_key(id)))
+    {
+        let (primary_rank, shadow_rank) = (
+            primary_ranks.get(id).copied(),
+            shadow_ranks.get(id).copied(),
+        );
+        if primary_rank != shadow_rank {
+            rank_deltas.push(RankDelta {
+                memory_id: *id,
+                primary_rank,
+                shadow_rank,
+            });
+        }
+    }
+    ArmResult {
+        arm: arm.to_string(),
+        memories: shadow,
+        overlap: if larger == 0 {
+            1.0
+        } else {
+            shared as f64 / larger as f64
+        },
+        rank_deltas,
+        latency_ms,
+        error: None,
+    }
+}
+
+async fn run_job(engine: &MnemoEngine, shadow: &ShadowRecall, job: ShadowJob) {
+    let mut arms = Vec::with_capacity(shadow.config.arms.len());
+    for arm in &shadow.config.arms {
+        let mut request = overlay(&arm.settings, job.request.clone());
+        request.agent_id = Some(job.agent_id.clone());
+        let started = Instant::now();
+        let result = crate::storage::with_agent_context(
+            job.agent_id.clone(),
+            super::recall::execute_unrecorded(engine, request),
+        )
+        .await;
+        let latency_ms = started.elapsed().as_millis() as u64;
+        arms.push(match result {
+            Ok(response) => compare(
+                &arm.name,
+                &job.primary,
+                response.memories.iter().map(|m| m.id).collect(),
+                latency_ms,
+            ),
+            Err(e) => ArmResult {
+                arm: arm.name.clone(),
+                memories: Vec::new(),
+                overlap: 0.0,
+                rank_deltas: Vec::new(),
+                latency_ms,
+                error: Some(e.to_string()),
+            },
+        });
+    }
+    let comparison = ShadowComparison {
+        id: Uuid::now_v7(),
+        agent_id: job.agent_id,
+        query: job.request.query,
+        at: job.at.to_rfc3339(),
+        primary: job.primary,
+        arms,
+        useful: Vec::new(),
+    };
+    match serde_json::to_string(&comparison) {
+        Ok(json) => {
+            tracing::info!(target: "mnemo::shadow_recall", comparison = %json, "shadow recall")
+        }
+        Err(e) => tracing::warn!(error = %e, "failed to serialize shadow comparison"),
+    }
+    shadow.push(comparison);
+}
+
+/// Start the worker that runs sampled recalls under every arm. Returns
+/// `None` when the engine has no shadow recall config or the worker is
+/// already running.
+pub fn spawn_worker(engine: Arc<MnemoEngine>) -> Option<tokio::task::JoinHandle<()>> {
+    let shadow = engine.shadow_recall.clone()?;
+    let mut receiver = shadow
+        .receiver
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take()?;
+    Some(tokio::spawn(async move {
+        while let Some(job) = receiver.recv().await {
+            run_job(&engine, &shadow, job).await;
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampling_picks_the_configured_share() {
+        let shadow = ShadowRecall::new(ShadowRecallConfig {
+            sample_rate: 0.25,
+            ..Default::default()
+        });
+        let picked = (0..100).filter(|_| shadow.sample()).count();
+        assert_eq!(picked, 25);
+
+        let never = ShadowRecall::new(ShadowRecallConfig {
+            sample_rate: 0.0,
+            ..Default::default()
+        });
+        assert!(!(0..100).any(|_| never.sample()));
+    }
+
+    #[test]
+    fn test_compare_reports_overlap_and_rank_deltas() {
+        let ids: Vec<Uuid> = (0..4).map(|_| Uuid::now_v7()).collect();
+        let primary = vec![ids[0], ids[1], ids[2]];
+        let result = compare("bm25", &primary, vec![ids[1], ids[0], ids[3]], 3);
+        assert!((result.overlap - 2.0 / 3.0).abs() < 1e-9);
+        // ids[0] and ids[1] swapped, ids[2] dropped, ids[3] added.
+        assert_eq!(result.rank_deltas.len(), 4);
+        assert!(result.rank_deltas.contains(&RankDelta {
+            memory_id: ids[2],
+            primary_rank: Some(3),
+            shadow_rank: None,
+        }));
+
+        let same = compare("same", &primary, primary.clone(), 1);
+        assert_eq!(same.overlap, 1.0);
+        assert!(same.rank_deltas.is_empty());
+    }
+
+    #[test]
+    fn test_overlay_lets_the_arm_win() {
+        let mut request = RecallRequest::new("q".to_string());
+        request.strategy = Some("semantic".to_string());
+        request.hybrid_weights = Some(vec![1.0, 0.0]);
+        request.limit = Some(5);
+        request.explain = Some(true);
+        let settings = RecallProfileSettings {
+            strategy: Some("lexical".to_string()),
+            hybrid: Some(crate::query::hybrid::HybridConfig::default()),
+            ..Default::default()
+        };
+        let request = overlay(&settings, request);
+        assert_eq!(request.strategy.as_deref(), Some("lexical"));
+        assert!(request.hybrid.is_some() && request.hybrid_weights.is_none());
+        assert_eq!(request.limit, Some(5));
+        assert_eq!(request.explain, None);
+    }
+}
@@ -1,11 +1,23 @@
+//! Grant other agents access to memories.
+//!
+//! One request shares a single memory (`memory_id`), a list of them
+//! (`memory_ids`) or every memory of the caller matching a
+//! [`ShareFilter`], with one or more agents. All grants of a request are
+//! written in one transaction: if the caller cannot share any one of the
+//! memories, nothing is shared. Set `dry_run` to learn which memories a
+//! request would share without granting anything.
+
+use std::collections::HashSet;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::error::{Error, Result};
 use crate::model::acl::{Acl, Permission, PrincipalType};
 use crate::model::event::EventType;
-use crate::model::memory::Scope;
+use crate::model::memory::{MemoryType, Scope};
 use crate::query::MnemoEngine;
+use crate::storage::{MemoryFilter, WriteBatch};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShareRequest {
@@ -15,6 +27,16 @@ pub struct ShareRequest {
     pub target_agent_ids: Option<Vec<String>>,
     pub permission: Option<Permission>,
     pub expires_in_hours: Option<f64>,
+    /// Share these memories instead of `memory_id`.
+    #[serde(default)]
+    pub memory_ids: Option<Vec<Uuid>>,
+    /// Share every memory of the caller matching this filter instead of
+    /// `memory_id`.
+    #[serde(default)]
+    pub filter: Option<ShareFilter>,
+    /// Resolve the memories to share and check access, but grant nothing.
+    #[serde(default)]
+    pub dry_run: Option<bool>,
 }
 
 impl ShareRequest {
@@ -26,18 +48,47 @@ impl ShareRequest {
             target_agent_ids: None,
             permission: None,
             expires_in_hours: None,
+            memory_ids: None,
+            filter: None,
+            dry_run: None,
         }
     }
+
+    /// Share every memory of the caller matching `filter`.
+    pub fn by_filter(filter: ShareFilter, target_agent_id: String) -> Self {
+        Self {
+            filter: Some(filter),
+            ..Self::new(Uuid::nil(), target_agent_id)
+        }
+    }
+}
+
+/// Selects the caller's own, non-deleted memories for a filter share. Every
+/// set field must match; `tags` must all be present.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ShareFilter {
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub memory_type: Option<MemoryType>,
+    pub scope: Option<Scope>,
+    pub org_id: Option<String>,
+    pub thread_id: Option<String>,
+    pub min_importance: Option<f32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ShareResponse {
+    /// The first grant; nil for a dry run.
     pub acl_id: Uuid,
     pub acl_ids: Vec<Uuid>,
+    /// The first memory shared; nil when a filter matched nothing.
     pub memory_id: Uuid,
+    /// Every memory shared (or, for a dry run, that would be).
+    pub memory_ids: Vec<Uuid>,
     pub shared_with: String,
     pub shared_with_all: Vec<String>,
     pub permission: Permission,
+    pub dry_run: bool,
 }
 
 pub async fn execute(engine: &MnemoEngine, request: ShareRequest) -> Result<ShareResponse> {
@@ -45,18 +96,54 @@ pub async fn execute(engine: &MnemoEngine, request: ShareRequest) -> Result<Shar
         .agent_id
         .unwrap_or_else(|| engine.default_agent_id.clone());
     let permission = request.permission.unwrap_or(Permission::Read);
+    let dry_run = request.dry_run.unwrap_or(false);
+    let expires_at = super::validation::expiry_after_hours(
+        "expires_in_hours",
+        request.expires_in_hours,
+        chrono::Utc::now(),
+    )?;
+    if let Some(ref ids) = request.target_agent_ids {
+        if ids.is_empty() {
+            return Err(Error::invalid_field("target_agent_ids", "cannot be empty"));
+        }
+        engine.limits().check_batch("target_agent_ids", ids.len())?;
+    }
 
-    // Verify the requester owns or has admin access to the memory
-    let has_access = engine
-        .storage
-        .check_permission(request.memory_id, &agent_id, Permission::Admin)
-        .await?;
-
-    if !has_access {
-        return Err(Error::PermissionDenied(format!(
-            "agent {agent_id} cannot share memory {}",
-            request.memory_id
-        )));
+    let memory_ids = match (request.memory_ids, request.filter) {
+        (Some(_), Some(_)) => {
+            return Err(Error::invalid_field(
+                "filter",
+                "cannot be combined with memory_ids",
+            ));
+        }
+        (Some(mut ids), None) => {
+            if ids.is_empty() {
+                return Err(Error::invalid_field("memory_ids", "cannot be empty"));
+            }
+            engine.limits().check_batch("memory_ids", ids.len())?;
+            let mut seen = HashSet::new();
+            ids.retain(|id| seen.insert(*id));
+            ids
+        }
+        (None, Some(filter)) => {
+            let ids = matching(engine, &agent_id, &filter).await?;
+            engine.limits().check_batch("filter", ids.len())?;
+            ids
+        }
+        (None, None) => vec![request.memory_id],
+    };
+
+    // Verify the requester owns or has admin access to every memory
+    for &memory_id in &memory_ids {
+        let has_access = engine
+            .storage
+            .check_permission(memory_id, &agent_id, Permission::Admin)
+            .await?;
+        if !has_access {
+            return Err(Error::PermissionDenied(format!(
+                "agent {agent_id} cannot share memory {memory_id}"
+            )));
+        }
     }
 
     // Build list of targets: multi-target takes precedence over single target
@@ -65,52 +152,68 @@ pub async fn execute(engine: &MnemoEngine, request: ShareRequest) -> Result<Shar
     } else {
         vec![request.target_agent_id.clone()]
     };
+    let first_memory = memory_ids.first().copied().unwrap_or_else(Uuid::nil);
 
-    // Compute expiration from expires_in_hours
-    let expires_at = request.expires_in_hours.map(|h| {
-        let exp = chrono::Utc::now() + chrono::Duration::seconds((h * 3600.0) as i64);
-        exp.to_rfc3339()
-    });
-
-    let now = chrono::Utc::now().to_rfc3339();
-    let mut acl_ids = Vec::new();
-
-    for target in &targets {
-        let acl_id = Uuid::now_v7();
-        let acl = Acl {
-            id: acl_id,
-            memory_id: request.memory_id,
-            principal_type: PrincipalType::Agent,
-            principal_id: target.clone(),
+    if dry_run {
+        return Ok(ShareResponse {
+            acl_id: Uuid::nil(),
+            acl_ids: Vec::new(),
+            memory_id: first_memory,
+            memory_ids,
+            shared_with: targets[0].clone(),
+            shared_with_all: targets,
             permission,
-            granted_by: agent_id.clone(),
-            created_at: now.clone(),
-            expires_at: expires_at.clone(),
-        };
-        engine.storage.insert_acl(&acl).await?;
-        acl_ids.push(acl_id);
+            dry_run,
+        });
+    }
+    if memory_ids.is_empty() {
+        return Err(Error::NotFound(
+            "no memories match the share filter".to_string(),
+        ));
     }
 
-    // Optionally update scope to Shared if it was Private
-    if let Some(mut record) = engine.storage.get_memory(request.memory_id).await?
-        && record.scope == Scope::Private
-    {
-        record.scope = Scope::Shared;
-        record.updated_at = now.clone();
-        engine.storage.update_memory(&record).await?;
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut batch = WriteBatch::default();
+
+    for &memory_id in &memory_ids {
+        for target in &targets {
+            batch.acls.push(Acl {
+                id: Uuid::now_v7(),
+                memory_id,
+                principal_type: PrincipalType::Agent,
+                principal_id: target.clone(),
+                permission,
+                granted_by: agent_id.clone(),
+                created_at: now.clone(),
+                expires_at: expires_at.clone(),
+            });
+        }
+
+        // Optionally update scope to Shared if it was Private
+        if let Some(mut record) = engine.storage.get_memory(memory_id).await?
+            && record.scope == Scope::Private
+        {
+            record.scope = Scope::Shared;
+            record.updated_at = now.clone();
+            batch.updates.push(record);
+        }
     }
 
-    // Emit MemoryShare event (fire-and-forget)
+    // One MemoryShare event covers the whole request
+    let mut payload = serde_json::json!({
+        "memory_id": first_memory.to_string(),
+        "shared_with": targets,
+        "permission": permission.to_string(),
+    });
+    if memory_ids.len() > 1 {
+        payload["memory_ids"] = serde_json::json!(memory_ids);
+    }
     let mut event = super::event_builder::build_event(
         engine,
         &agent_id,
         EventType::MemoryShare,
-        serde_json::json!({
-            "memory_id": request.memory_id.to_string(),
-            "shared_with": targets,
-            "permission": permission.to_string(),
-        }),
-        &request.memory_id.to_string(),
+        payload,
+        &first_memory.to_string(),
         None,
     )
     .await;
@@ -119,19 +222,58 @@ pub async fn execute(engine: &MnemoEngine, request: ShareRequest) -> Result<Shar
     {
         event.embedding = Some(emb);
     }
-    if let Err(e) = engine.storage.insert_event(&event).await {
-        tracing::error!(event_id = %event.id, error = %e, "failed to insert audit event");
-    }
+    batch.events.push(event);
 
-    let first_acl_id = acl_ids[0];
-    let first_target = targets[0].clone();
+    engine.storage.commit_write(&batch).await?;
+    if let Some(ref cache) = engine.cache {
+        for record in &batch.updates {
+            cache.invalidate(record.id);
+        }
+    }
 
     Ok(ShareResponse {
-        acl_id: first_acl_id,
-        acl_ids,
-        memory_id: request.memory_id,
-        shared_with: first_target,
+        acl_id: batch.acls[0].id,
+        acl_ids: batch.acls.iter().map(|acl| acl.id).collect(),
+        memory_id: first_memory,
+        memory_ids,
+        shared_with: targets[0].clone(),
         shared_with_all: targets,
         permission,
+        dry_run,
     })
 }
+
+/// The caller's own memories matching `filter`, newest first. Pages
+/// through every match, so a filter matching more than the batch limit is
+/// rejected rather than silently cut short.
+async fn matching(engine: &MnemoEngine, agent_id: &str, filter: &ShareFilter) -> Result<Vec<Uuid>> {
+    let storage_filter = MemoryFilter {
+        agent_id: Some(agent_id.to_string()),
+        memory_type: filter.memory_type,
+        scope: filter.scope,
+        min_importance: filter.min_importance,
+        org_id: filter.org_id.clone(),
+        thread_id: filter.thread_id.clone(),
+        ..Default::default()
+    };
+    // Tags are matched here: the storage backends don't push the tag
+    // predicate into SQL.
+    let mut ids = Vec::new();
+    let mut offset = 0;
+    loop {
+        let page = engine
+            .storage
+            .list_memories(&storage_filter, super::MAX_BATCH_QUERY_LIMIT, offset)
+            .await?;
+        offset += page.len();
+        let done = page.len() < super::MAX_BATCH_QUERY_LIMIT;
+        ids.extend(
+            page.into_iter()
+                .filter(|r| filter.tags.iter().all(|t| r.tags.contains(t)))
+                .map(|r| r.id),
+        );
+        if done {
+            return Ok(ids);
+        }
+    }
+}
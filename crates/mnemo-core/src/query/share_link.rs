@@ -0,0 +1,339 @@
+//! Share links: capability tokens for readers that are not agents.
+//!
+//! A human reviewer or an external tool has no agent id to grant an ACL
+//! to. Instead, an agent allowed to share a memory mints a link: a token
+//! that grants read access to that one memory until it expires. The token
+//! carries its own claims — link id, memory, issuer, expiry — signed with
+//! HMAC-SHA256 under the engine's [`ShareLinkSigner`] key, so checking the
+//! signature and expiry needs no storage. Revoking a link puts its id and
+//! memory on a denylist that [`verify`] consults with the token's own
+//! memory, so an agent that may share one memory cannot revoke links to
+//! another; entries are dropped once every link they could match has
+//! expired anyway.
+
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, KeyInit, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::acl::Permission;
+use crate::model::event::EventType;
+use crate::model::share_link::RevokedShareLink;
+use crate::query::MnemoEngine;
+
+/// Prefix of every share link token, telling it apart from bearer secrets.
+pub const TOKEN_PREFIX: &str = "mnemo_link_";
+
+/// Lifetime of a link minted without `expires_in_hours`.
+pub const DEFAULT_LINK_HOURS: f64 = 24.0;
+
+/// Longest lifetime a link may be minted with. Denylist entries are kept
+/// this long, which covers every link they could match.
+pub const MAX_LINK_HOURS: f64 = 720.0;
+
+/// What a share link token grants.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShareLinkClaims {
+    pub link_id: Uuid,
+    pub memory_id: Uuid,
+    /// The agent that minted the link.
+    pub issued_by: String,
+    /// Unix seconds after which the token is refused.
+    pub expires_at: i64,
+}
+
+/// Signs and checks share link tokens under a 32-byte key.
+#[derive(Clone)]
+pub struct ShareLinkSigner {
+    key: [u8; 32],
+}
+
+impl ShareLinkSigner {
+    pub fn new(key: [u8; 32]) -> Self {
+        Self { key }
+    }
+
+    /// Create from a hex-encoded key (64 hex chars = 32 bytes).
+    pub fn from_hex(hex_key: &str) -> Result<Self> {
+        let bytes = hex::decode(hex_key)
+            .map_err(|e| Error::Validation(format!("invalid share link key: {e}")))?;
+        let key: [u8; 32] = bytes.try_into().map_err(|b: Vec<u8>| {
+            Error::Validation(format!("share link key must be 32 bytes, got {}", b.len()))
+        })?;
+        Ok(Self::new(key))
+    }
+
+    fn mac(&self) -> Hmac<Sha256> {
+        <Hmac<Sha256> as KeyInit>::new_from_slice(&self.key).expect("HMAC accepts any key length")
+    }
+
+    /// `mnemo_link_<claims>.<signature>`, both parts unpadded base64url.
+    pub fn sign(&self, claims: &ShareLinkClaims) -> Result<String> {
+        let body = URL_SAFE_NO_PAD.encode(serde_json::to_vec(claims)?);
+        let mut mac = self.mac();
+        mac.update(body.as_bytes());
+        let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+        Ok(format!("{TOKEN_PREFIX}{body}.{signature}"))
+    }
+
+    /// The token's claims, if it carries a valid signature and has not
+    /// expired at `now`. Does not consult the denylist.
+    pub fn verify(&self, token: &str, now: DateTime<Utc>) -> Result<ShareLinkClaims> {
+        let invalid = || Error::PermissionDenied("invalid share link".to_string());
+        let (body, signature) = token
+            .strip_prefix(TOKEN_PREFIX)
+            .and_then(|rest| rest.split_once('.'))
+            .ok_or_else(invalid)?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_| invalid())?;
+        let mut mac = self.mac();
+        mac.update(body.as_bytes());
+        mac.verify_slice(&signature).map_err(|_| invalid())?;
+        let claims: ShareLinkClaims = URL_SAFE_NO_PAD
+            .decode(body)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .ok_or_else(invalid)?;
+        if claims.expires_at <= now.timestamp() {
+            return Err(Error::PermissionDenied(format!(
+                "share link {} has expired",
+                claims.link_id
+            )));
+        }
+        Ok(claims)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateShareLinkRequest {
+    pub memory_id: Uuid,
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    /// Hours until the link expires: at most [`MAX_LINK_HOURS`], default
+    /// [`DEFAULT_LINK_HOURS`].
+    #[serde(default)]
+    pub expires_in_hours: Option<f64>,
+}
+
+impl CreateShareLinkRequest {
+    pub fn new(memory_id: Uuid) -> Self {
+        Self {
+            memory_id,
+            agent_id: None,
+            expires_in_hours: None,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShareLink {
+    pub link_id: Uuid,
+    pub memory_id: Uuid,
+    /// Present it as `Authorization: Bearer <token>` or `?link=<token>`.
+    pub token: String,
+    pub expires_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevokeShareLinkRequest {
+    pub memory_id: Uuid,
+    pub link_id: Uuid,
+    #[serde(default)]
+    pub agent_id: Option<String>,
+}
+
+impl RevokeShareLinkRequest {
+    pub fn new(memory_id: Uuid, link_id: Uuid) -> Self {
+        Self {
+            memory_id,
+            link_id,
+            agent_id: None,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevokeShareLinkResponse {
+    pub link_id: Uuid,
+    pub revoked_at: String,
+}
+
+fn signer(engine: &MnemoEngine) -> Result<&ShareLinkSigner> {
+    engine
+        .share_link_signer
+        .as_ref()
+        .ok_or_else(|| Error::Validation("share links are disabled: no share link key".to_string()))
+}
+
+pub async fn create(engine: &MnemoEngine, request: CreateShareLinkRequest) -> Result<ShareLink> {
+    let signer = signer(engine)?;
+    let agent_id = engine.acting_agent(request.agent_id.as_deref());
+    super::validate_agent_id(&agent_id)?;
+    let hours = request.expires_in_hours.unwrap_or(DEFAULT_LINK_HOURS);
+    if !(hours > 0.0 && hours <= MAX_LINK_HOURS) {
+        return Err(Error::invalid_field(
+            "expires_in_hours",
+            format!("must be in (0, {MAX_LINK_HOURS}]"),
+        ));
+    }
+    let memory = engine
+        .storage
+        .get_memory(request.memory_id)
+        .await?
+        .filter(|m| m.deleted_at.is_none())
+        .ok_or_else(|| Error::NotFound(format!("memory {} not found", request.memory_id)))?;
+    if !engine
+        .storage
+        .check_permission(memory.id, &agent_id, Permission::Share)
+        .await?
+    {
+        return Err(Error::PermissionDenied(format!(
+            "agent {agent_id} cannot share memory {}",
+            memory.id
+        )));
+    }
+
+    let expires_at = Utc::now() + Duration::seconds((hours * 3600.0) as i64);
+    let claims = ShareLinkClaims {
+        link_id: Uuid::now_v7(),
+        memory_id: memory.id,
+        issued_by: agent_id.clone(),
+        expires_at: expires_at.timestamp(),
+    };
+    let token = signer.sign(&claims)?;
+    let expires_at = expires_at.to_rfc3339();
+    let event = super::event_builder::build_event(
+        engine,
+        &agent_id,
+        EventType::ShareLinkCreated,
+        serde_json::json!({
+            "link_id": claims.link_id,
+            "memory_id": memory.id,
+            "expires_at": expires_at,
+        }),
+        &claims.link_id.to_string(),
+        memory.thread_id.clone(),
+    )
+    .await;
+    engine.storage.insert_event(&event).await?;
+    Ok(ShareLink {
+        link_id: claims.link_id,
+        memory_id: memory.id,
+        token,
+        expires_at,
+    })
+}
+
+/// The claims of `token` if it is genuine, unexpired and not revoked.
+pub async fn verify(engine: &MnemoEngine, token: &str) -> Result<ShareLinkClaims> {
+    let claims = signer(engine)?.verify(token, Utc::now())?;
+    if engine
+        .storage
+        .is_share_link_revoked(claims.link_id, claims.memory_id)
+        .await?
+    {
+        return Err(Error::PermissionDenied(format!(
+            "share link {} has been revoked",
+            claims.link_id
+        )));
+    }
+    Ok(claims)
+}
+
+pub async fn revoke(
+    engine: &MnemoEngine,
+    request: RevokeShareLinkRequest,
+) -> Result<RevokeShareLinkResponse> {
+    let agent_id = engine.acting_agent(request.agent_id.as_deref());
+    super::validate_agent_id(&agent_id)?;
+    if !engine
+        .storage
+        .check_permission(request.memory_id, &agent_id, Permission::Share)
+        .await?
+    {
+        return Err(Error::PermissionDenied(format!(
+            "agent {agent_id} cannot revoke links to memory {}",
+            request.memory_id
+        )));
+    }
+    let now = Utc::now();
+    let revoked = RevokedShareLink {
+        link_id: request.link_id,
+        memory_id: request.memory_id,
+        revoked_by: agent_id.clone(),
+        revoked_at: now.to_rfc3339(),
+        expires_at: (now + Duration::seconds((MAX_LINK_HOURS * 3600.0) as i64)).to_rfc3339(),
+    };
+    engine.storage.revoke_share_link(&revoked).await?;
+    let event = super::event_builder::build_event(
+        engine,
+        &agent_id,
+        EventType::ShareLinkRevoked,
+        serde_json::json!({
+            "link_id": request.link_id,
+            "memory_id": request.memory_id,
+        }),
+        &request.link_id.to_string(),
+        None,
+    )
+    .await;
+    engine.storage.insert_event(&event).await?;
+    let purged = engine
+        .storage
+        .purge_revoked_share_links(&revoked.revoked_at)
+        .await?;
+    if purged > 0 {
+        tracing::debug!(purged, "dropped lapsed share link denylist entries");
+    }
+    Ok(RevokeShareLinkResponse {
+        link_id: request.link_id,
+        revoked_at: revoked.revoked_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claims(expires_at: i64) -> ShareLinkClaims {
+        ShareLinkClaims {
+            link_id: Uuid::now_v7(),
+            memory_id: Uuid::now_v7(),
+            issued_by: "agent-1".to_string(),
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn test_token_roundtrip_and_tampering() {
+        let signer = ShareLinkSigner::new([7; 32]);
+        let now = Utc::now();
+        let claims = claims(now.timestamp() + 60);
+        let token = signer.sign(&claims).unwrap();
+        assert!(token.starts_with(TOKEN_PREFIX));
+        assert_eq!(signer.verify(&token, now).unwrap(), claims);
+
+        // Another key, an edited body and a late use are all refused.
+        assert!(ShareLinkSigner::new([8; 32]).verify(&token, now).is_err());
+        let forged = signer
+            .sign(&ShareLinkClaims {
+                memory_id: Uuid::now_v7(),
+                ..claims.clone()
+            })
+            .unwrap();
+        let (forged_body, _) = forged.rsplit_once('.').unwrap();
+        let (_, signature) = token.rsplit_once('.').unwrap();
+        assert!(
+            signer
+                .verify(&format!("{forged_body}.{signature}"), now)
+                .is_err()
+        );
+        assert!(signer.verify(&token, now + Duration::seconds(61)).is_err());
+        assert!(signer.verify("not-a-link", now).is_err());
+    }
+}
@@ -0,0 +1,143 @@
+//! More-like-this: the nearest neighbours of a stored memory.
+//!
+//! [`similar`] searches the vector index with the memory's own embedding
+//! instead of embedding a query, which makes it cheap for dedup review
+//! ("what else looks like this?") and for expanding context around a hit.
+//! Results go through the same visibility, filter and shared-recall privacy
+//! rules as recall, so an agent only sees neighbours it could have
+//! recalled, attributed as recall would attribute them, and the memory
+//! itself is never among them.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::memory::{MemoryType, Scope};
+use crate::query::MnemoEngine;
+use crate::query::recall::{self, RecallRequest, ScoredMemory};
+use crate::storage::MemoryColumns;
+
+/// Neighbours returned when the request sets no limit.
+pub const DEFAULT_SIMILAR_LIMIT: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarRequest {
+    pub memory_id: Uuid,
+    pub agent_id: Option<String>,
+    /// Neighbours to return (`k`). Capped like a recall limit.
+    pub limit: Option<usize>,
+    pub memory_type: Option<MemoryType>,
+    pub scope: Option<Scope>,
+    /// Keep neighbours carrying at least one of these tags.
+    pub tags: Option<Vec<String>>,
+    pub min_importance: Option<f32>,
+}
+
+impl SimilarRequest {
+    pub fn new(memory_id: Uuid, limit: usize) -> Self {
+        Self {
+            memory_id,
+            agent_id: None,
+            limit: Some(limit),
+            memory_type: None,
+            scope: None,
+            tags: None,
+            min_importance: None,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarResponse {
+    pub memory_id: Uuid,
+    /// Closest first; `score` is the cosine similarity to the memory.
+    pub memories: Vec<ScoredMemory>,
+}
+
+impl SimilarResponse {
+    pub fn new(memory_id: Uuid, memories: Vec<ScoredMemory>) -> Self {
+        Self {
+            memory_id,
+            memories,
+        }
+    }
+}
+
+pub async fn similar(engine: &MnemoEngine, request: SimilarRequest) -> Result<SimilarResponse> {
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    let limit = engine
+        .limits()
+        .recall_limit(request.limit.unwrap_or(DEFAULT_SIMILAR_LIMIT));
+    if limit == 0 {
+        return Err(Error::invalid_field("limit", "must be > 0"));
+    }
+
+    // The memory must be one the caller could recall; anything else is
+    // reported as missing so its existence does not leak.
+    let mut unfiltered = RecallRequest::new(String::new());
+    unfiltered.agent_id = Some(agent_id.clone());
+    let mut source = match recall::get_memory_cached(engine, request.memory_id, MemoryColumns::ALL)
+        .await?
+    {
+        Some(record) if recall::passes_filters(&record, &unfiltered, &agent_id, engine).await => {
+            record
+        }
+        _ => {
+            return Err(Error::NotFound(format!(
+                "memory {} not found",
+                request.memory_id
+            )));
+        }
+    };
+    let embedding = match source.embedding.take() {
+        Some(embedding) if !embedding.is_empty() => embedding,
+        _ => {
+            super::consolidate::decrypt_in_place(engine, &mut source);
+            engine.embedding.embed(&source.content).await?
+        }
+    };
+
+    let mut filters = unfiltered;
+    filters.memory_type = request.memory_type;
+    filters.scope = request.scope;
+    filters.tags = request.tags;
+    filters.min_importance = request.min_importance;
+
+    let mut accessible_ids: HashSet<Uuid> = engine
+        .storage
+        .list_accessible_memory_ids(&agent_id, super::MAX_BATCH_QUERY_LIMIT)
+        .await?
+        .into_iter()
+        .collect();
+    accessible_ids.remove(&source.id);
+    let neighbours = engine
+        .index
+        .search_within(&embedding, limit * 3, &accessible_ids)?;
+
+    let mut hits = Vec::with_capacity(neighbours.len());
+    for (id, distance) in neighbours {
+        let Some(record) = recall::get_memory_cached(engine, id, MemoryColumns::ALL).await? else {
+            continue;
+        };
+        if !recall::passes_filters(&record, &filters, &agent_id, engine).await {
+            continue;
+        }
+        hits.push((record, 1.0 - distance));
+    }
+    super::privacy::shield(engine, &agent_id, &mut hits, limit).await?;
+
+    let memories = hits
+        .into_iter()
+        .map(|(mut record, score)| {
+            super::consolidate::decrypt_in_place(engine, &mut record);
+            ScoredMemory::from((record, score))
+        })
+        .collect();
+    Ok(SimilarResponse::new(source.id, memories))
+}
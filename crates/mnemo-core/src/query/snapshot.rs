@@ -0,0 +1,117 @@
+//! Read versions for recall.
+//!
+//! A recall reads storage many times: candidate listings, vector and
+//! full-text hits hydrated one by one, graph neighbours, one leg per query
+//! variant. A write landing between those reads could tear the result: a
+//! memory still in the index but already hard-deleted from storage, a row
+//! that changes between two legs, a memory written mid-recall that only
+//! one leg sees.
+//!
+//! Every recall therefore runs at a read version, fixed when it starts:
+//!
+//! - memories created after the read version are invisible, and soft
+//!   deletes and expiry are judged at it rather than at each sub-query's
+//!   own "now";
+//! - each memory is hydrated at most once, in one storage read, and every
+//!   sub-query of the recall sees that same copy. A memory hard-deleted
+//!   before its first hydration reads as missing for the rest of the
+//!   recall; one hard-deleted after keeps the copy already pinned.
+//!
+//! Nothing here takes a lock: recalls and hard deletes never wait for
+//! each other.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::error::Result;
+use crate::model::memory::MemoryRecord;
+
+tokio::task_local! {
+    static SNAPSHOT: Arc<ReadSnapshot>;
+}
+
+/// State shared by every sub-query of one recall.
+#[derive(Debug)]
+struct ReadSnapshot {
+    version: DateTime<Utc>,
+    records: Mutex<HashMap<Uuid, Option<MemoryRecord>>>,
+}
+
+/// Run `fut` at a read version taken now. Nested calls share the outer
+/// version.
+pub(crate) async fn read<F: Future>(fut: F) -> F::Output {
+    if SNAPSHOT.try_with(|_| ()).is_ok() {
+        return fut.await;
+    }
+    let snapshot = Arc::new(ReadSnapshot {
+        version: Utc::now(),
+        records: Mutex::new(HashMap::new()),
+    });
+    SNAPSHOT.scope(snapshot, fut).await
+}
+
+/// The read version of the recall running on this task, if any.
+pub(crate) fn read_version() -> Option<DateTime<Utc>> {
+    SNAPSHOT.try_with(|s| s.version).ok()
+}
+
+/// The copy of `id` this recall sees: the one it already hydrated, else
+/// whatever `load` reads, pinned for every later read.
+pub(crate) async fn hydrate<F>(id: Uuid, load: F) -> Result<Option<MemoryRecord>>
+where
+    F: Future<Output = Result<Option<MemoryRecord>>>,
+{
+    if let Some(record) = hydrated(id) {
+        return Ok(record);
+    }
+    let record = load.await?;
+    Ok(pin(id, record))
+}
+
+/// The copy of `id` this recall already hydrated. `Some(None)` means it
+/// was found missing.
+fn hydrated(id: Uuid) -> Option<Option<MemoryRecord>> {
+    let snapshot = SNAPSHOT.try_with(Arc::clone).ok()?;
+    let records = snapshot
+        .records
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    records.get(&id).cloned()
+}
+
+/// Pin the copy of `id` every later read in this recall sees and return
+/// it. If two sub-queries race, the copy pinned first wins.
+fn pin(id: Uuid, record: Option<MemoryRecord>) -> Option<MemoryRecord> {
+    let Ok(snapshot) = SNAPSHOT.try_with(Arc::clone) else {
+        return record;
+    };
+    let mut records = snapshot
+        .records
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    records.entry(id).or_insert(record).clone()
+}
+
+/// Whether `record` existed and was not deleted at the current read
+/// version. Outside a recall, whether it is not deleted.
+pub(crate) fn visible(record: &MemoryRecord) -> bool {
+    let Some(version) = read_version() else {
+        return !record.is_deleted();
+    };
+    let parse = |time: &str| {
+        DateTime::parse_from_rfc3339(time)
+            .ok()
+            .map(|t| t.with_timezone(&Utc))
+    };
+    if parse(&record.created_at).is_some_and(|t| t > version) {
+        return false;
+    }
+    match record.deleted_at {
+        Some(ref deleted_at) => parse(deleted_at).is_some_and(|t| t > version),
+        None => true,
+    }
+}
@@ -0,0 +1,458 @@
+//! Thread messages: an ordered, paginated message store per thread.
+//!
+//! Lets Mnemo be the canonical store of a conversation, not just of what
+//! was learned from it. Each message is an event in its thread
+//! (`user_message`, `assistant_message`, `system_message` or `tool_result`)
+//! whose payload carries `role`, `content` and the caller's `metadata`, and
+//! whose `parent_event_id` is the message before it. Messages recorded by
+//! [`capture_turn`](super::turn) in the same thread are listed too.
+//!
+//! An assistant message that answers a user message is captured as a turn:
+//! an episodic memory of the exchange and, with `extract_facts`, semantic
+//! memories of the user's first-person statements, exactly as
+//! [`capture_turn`](super::turn) writes them.
+//!
+//! A thread's messages are those the acting agent wrote into it. The event
+//! log is append-only, so deleting a message records a `message_deleted`
+//! event and listings skip the message from then on; memories captured
+//! from it are kept.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, FieldViolation, Result};
+use crate::model::event::{AgentEvent, EventType};
+use crate::query::MnemoEngine;
+use crate::query::turn::{self, TurnMemory};
+
+/// Messages returned per page when the request does not say.
+pub const DEFAULT_MESSAGE_PAGE_SIZE: usize = 20;
+
+/// Largest accepted page size.
+pub const MAX_MESSAGE_PAGE_SIZE: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageRole {
+    User,
+    Assistant,
+    System,
+    Tool,
+}
+
+impl MessageRole {
+    fn event_type(self) -> EventType {
+        match self {
+            MessageRole::User => EventType::UserMessage,
+            MessageRole::Assistant => EventType::AssistantMessage,
+            MessageRole::System => EventType::SystemMessage,
+            MessageRole::Tool => EventType::ToolResult,
+        }
+    }
+
+    fn from_event_type(event_type: EventType) -> Option<Self> {
+        match event_type {
+            EventType::UserMessage => Some(MessageRole::User),
+            EventType::AssistantMessage => Some(MessageRole::Assistant),
+            EventType::SystemMessage => Some(MessageRole::System),
+            EventType::ToolResult => Some(MessageRole::Tool),
+            _ => None,
+        }
+    }
+}
+
+/// Listing order, by when messages were posted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageOrder {
+    /// Oldest first.
+    #[default]
+    Asc,
+    /// Newest first.
+    Desc,
+}
+
+/// One message of a thread.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThreadMessage {
+    /// The id of the message's event.
+    pub id: Uuid,
+    pub thread_id: String,
+    pub role: MessageRole,
+    pub content: String,
+    /// What the caller attached when posting; `null` when nothing was.
+    pub metadata: serde_json::Value,
+    pub agent_id: String,
+    pub run_id: Option<String>,
+    pub created_at: String,
+}
+
+impl ThreadMessage {
+    /// The message an event records, if it records one.
+    fn from_event(event: &AgentEvent) -> Option<Self> {
+        let role = MessageRole::from_event_type(event.event_type)?;
+        let content = event.payload.get("content")?.as_str()?;
+        Some(Self {
+            id: event.id,
+            thread_id: event.thread_id.clone()?,
+            role,
+            content: content.to_string(),
+            metadata: event
+                .payload
+                .get("metadata")
+                .cloned()
+                .unwrap_or(serde_json::Value::Null),
+            agent_id: event.agent_id.clone(),
+            run_id: event.run_id.clone(),
+            created_at: event.timestamp.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateMessageRequest {
+    pub thread_id: String,
+    pub role: MessageRole,
+    pub content: String,
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    #[serde(default)]
+    pub org_id: Option<String>,
+    #[serde(default)]
+    pub run_id: Option<String>,
+    /// A JSON object stored with the message and returned by listings.
+    #[serde(default)]
+    pub metadata: Option<serde_json::Value>,
+    /// For an assistant message answering a user message: write the turn
+    /// memory. Defaults to `true`.
+    #[serde(default)]
+    pub capture_turn: Option<bool>,
+    /// When the turn is captured, also promote first-person statements in
+    /// the user message to semantic memories. Defaults to `false`.
+    #[serde(default)]
+    pub extract_facts: Option<bool>,
+}
+
+impl CreateMessageRequest {
+    pub fn new(thread_id: String, role: MessageRole, content: String) -> Self {
+        Self {
+            thread_id,
+            role,
+            content,
+            agent_id: None,
+            org_id: None,
+            run_id: None,
+            metadata: None,
+            capture_turn: None,
+            extract_facts: None,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateMessageResponse {
+    pub message: ThreadMessage,
+    /// The episodic memory of the turn this message completed, if any.
+    pub turn_memory_id: Option<Uuid>,
+    pub extracted_memory_ids: Vec<Uuid>,
+}
+
+impl CreateMessageResponse {
+    pub fn new(
+        message: ThreadMessage,
+        turn_memory_id: Option<Uuid>,
+        extracted_memory_ids: Vec<Uuid>,
+    ) -> Self {
+        Self {
+            message,
+            turn_memory_id,
+            extracted_memory_ids,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListMessagesRequest {
+    pub thread_id: String,
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    /// Page size; defaults to [`DEFAULT_MESSAGE_PAGE_SIZE`], at most
+    /// [`MAX_MESSAGE_PAGE_SIZE`].
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub order: Option<MessageOrder>,
+    /// Cursor: only messages that come after this one in `order`.
+    #[serde(default)]
+    pub after: Option<Uuid>,
+    /// Cursor: only messages that come before this one in `order`. Without
+    /// `after`, the page is the `limit` messages right before it.
+    #[serde(default)]
+    pub before: Option<Uuid>,
+}
+
+impl ListMessagesRequest {
+    pub fn new(thread_id: String) -> Self {
+        Self {
+            thread_id,
+            agent_id: None,
+            limit: None,
+            order: None,
+            after: None,
+            before: None,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListMessagesResponse {
+    pub messages: Vec<ThreadMessage>,
+    pub first_id: Option<Uuid>,
+    pub last_id: Option<Uuid>,
+    /// More messages remain in the paging direction: after `last_id`, or
+    /// before `first_id` when paging with `before`.
+    pub has_more: bool,
+}
+
+impl ListMessagesResponse {
+    pub fn new(messages: Vec<ThreadMessage>, has_more: bool) -> Self {
+        Self {
+            first_id: messages.first().map(|m| m.id),
+            last_id: messages.last().map(|m| m.id),
+            messages,
+            has_more,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteMessageRequest {
+    pub thread_id: String,
+    pub message_id: Uuid,
+    #[serde(default)]
+    pub agent_id: Option<String>,
+}
+
+impl DeleteMessageRequest {
+    pub fn new(thread_id: String, message_id: Uuid) -> Self {
+        Self {
+            thread_id,
+            message_id,
+            agent_id: None,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteMessageResponse {
+    pub id: Uuid,
+    pub deleted: bool,
+}
+
+impl DeleteMessageResponse {
+    pub fn new(id: Uuid, deleted: bool) -> Self {
+        Self { id, deleted }
+    }
+}
+
+fn validate_thread_id(thread_id: &str) -> Result<()> {
+    if thread_id.trim().is_empty() {
+        return Err(Error::invalid_field("thread_id", "cannot be empty"));
+    }
+    Ok(())
+}
+
+/// The live messages the agent wrote into the thread, oldest first.
+async fn load(engine: &MnemoEngine, thread_id: &str, agent_id: &str) -> Result<Vec<ThreadMessage>> {
+    let mut events: Vec<AgentEvent> = engine
+        .storage
+        .get_events_by_thread(thread_id, super::MAX_BATCH_QUERY_LIMIT)
+        .await?
+        .into_iter()
+        .filter(|e| e.agent_id == agent_id)
+        .collect();
+    events.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then(a.id.cmp(&b.id)));
+    let deleted: HashSet<Uuid> = events
+        .iter()
+        .filter(|e| e.event_type == EventType::MessageDeleted)
+        .filter_map(|e| e.payload.get("message_id")?.as_str()?.parse().ok())
+        .collect();
+    Ok(events
+        .iter()
+        .filter(|e| !deleted.contains(&e.id))
+        .filter_map(ThreadMessage::from_event)
+        .collect())
+}
+
+/// Append a message to a thread.
+pub async fn create(
+    engine: &MnemoEngine,
+    request: CreateMessageRequest,
+) -> Result<CreateMessageResponse> {
+    let mut violations = Vec::new();
+    if request.thread_id.trim().is_empty() {
+        violations.push(FieldViolation::new("thread_id", "cannot be empty"));
+    }
+    if request.content.trim().is_empty() {
+        violations.push(FieldViolation::new("content", "cannot be empty"));
+    }
+    if request.metadata.as_ref().is_some_and(|m| !m.is_object()) {
+        violations.push(FieldViolation::new("metadata", "must be a JSON object"));
+    }
+    if !violations.is_empty() {
+        return Err(Error::InvalidFields(violations));
+    }
+    let agent_id = request
+        .agent_id
+        .clone()
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+
+    let previous = load(engine, &request.thread_id, &agent_id).await?.pop();
+    let metadata = request.metadata.clone().unwrap_or(serde_json::Value::Null);
+    let mut event = super::event_builder::build_event(
+        engine,
+        &agent_id,
+        request.role.event_type(),
+        serde_json::json!({
+            "role": request.role,
+            "content": request.content,
+            "metadata": metadata,
+        }),
+        &request.content,
+        Some(request.thread_id.clone()),
+    )
+    .await;
+    event.run_id = request.run_id.clone();
+    event.parent_event_id = previous.as_ref().map(|m| m.id);
+
+    // An answer is written together with the memory of the turn it
+    // completes.
+    let mut turn_memory_id = None;
+    let mut extracted_memory_ids = Vec::new();
+    if let Some(ref question) = previous
+        && request.role == MessageRole::Assistant
+        && question.role == MessageRole::User
+        && request.capture_turn.unwrap_or(true)
+    {
+        let (memory_id, extracted) = turn::remember_turn(
+            engine,
+            TurnMemory {
+                agent_id: &agent_id,
+                org_id: request.org_id.clone(),
+                thread_id: Some(request.thread_id.clone()),
+                user_message: &question.content,
+                assistant_message: &request.content,
+                tool_names: Vec::new(),
+                user_event_id: question.id,
+                assistant_event_id: event.id,
+                model: None,
+                tokens_input: None,
+                tokens_output: None,
+                cost_usd: None,
+                importance: None,
+                tags: None,
+                extract_facts: request.extract_facts.unwrap_or(false),
+            },
+            vec![event.clone()],
+        )
+        .await?;
+        turn_memory_id = Some(memory_id);
+        extracted_memory_ids = extracted;
+    } else {
+        engine.storage.insert_event(&event).await?;
+    }
+
+    let message = ThreadMessage::from_event(&event)
+        .ok_or_else(|| Error::Internal("message event has no content".to_string()))?;
+    Ok(CreateMessageResponse::new(
+        message,
+        turn_memory_id,
+        extracted_memory_ids,
+    ))
+}
+
+/// A page of a thread's messages.
+pub async fn list(
+    engine: &MnemoEngine,
+    request: ListMessagesRequest,
+) -> Result<ListMessagesResponse> {
+    validate_thread_id(&request.thread_id)?;
+    let limit = request.limit.unwrap_or(DEFAULT_MESSAGE_PAGE_SIZE);
+    if limit == 0 || limit > MAX_MESSAGE_PAGE_SIZE {
+        return Err(Error::invalid_field(
+            "limit",
+            format!("must be between 1 and {MAX_MESSAGE_PAGE_SIZE}"),
+        ));
+    }
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+
+    let mut messages = load(engine, &request.thread_id, &agent_id).await?;
+    if request.order.unwrap_or_default() == MessageOrder::Desc {
+        messages.reverse();
+    }
+    let position = |field: &str, id: Uuid, messages: &[ThreadMessage]| {
+        messages.iter().position(|m| m.id == id).ok_or_else(|| {
+            Error::invalid_field(field, format!("message {id} is not in the thread"))
+        })
+    };
+    if let Some(before) = request.before {
+        let end = position("before", before, &messages)?;
+        messages.truncate(end);
+    }
+    if let Some(after) = request.after {
+        let start = position("after", after, &messages)? + 1;
+        messages.drain(..start);
+    }
+    // Paging backwards from `before` returns the messages right before it.
+    let has_more = messages.len() > limit;
+    if request.before.is_some() && request.after.is_none() {
+        messages.drain(..messages.len().saturating_sub(limit));
+    } else {
+        messages.truncate(limit);
+    }
+    Ok(ListMessagesResponse::new(messages, has_more))
+}
+
+/// Delete a message from a thread's listings.
+pub async fn delete(
+    engine: &MnemoEngine,
+    request: DeleteMessageRequest,
+) -> Result<DeleteMessageResponse> {
+    validate_thread_id(&request.thread_id)?;
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    if !load(engine, &request.thread_id, &agent_id)
+        .await?
+        .iter()
+        .any(|m| m.id == request.message_id)
+    {
+        return Err(Error::NotFound(format!(
+            "message {} not found in thread {}",
+            request.message_id, request.thread_id
+        )));
+    }
+
+    let mut event = super::event_builder::build_event(
+        engine,
+        &agent_id,
+        EventType::MessageDeleted,
+        serde_json::json!({"message_id": request.message_id.to_string()}),
+        &request.message_id.to_string(),
+        Some(request.thread_id),
+    )
+    .await;
+    event.parent_event_id = Some(request.message_id);
+    engine.storage.insert_event(&event).await?;
+    Ok(DeleteMessageResponse::new(request.message_id, true))
+}
@@ -0,0 +1,313 @@
+//! Memory ownership transfer.
+//!
+//! When an agent is decommissioned its memories are stranded: only it owns
+//! them. [`execute`] hands the memories of `from_agent` that match a
+//! [`TransferFilter`] to `to_agent` in one of two ways:
+//!
+//! - [`TransferMode::Reassign`] rewrites each memory's `agent_id` and
+//!   records the move under the `transfer` metadata key; metadata that is
+//!   not an object is kept under `value`. The content hash
+//!   covers the owner, so both agents' chains are then rebuilt with a
+//!   [`rechain`](super::rechain) pass; its `ChainRebuilt` events keep the
+//!   roots from before the move. Vectors are re-added under the new owner
+//!   so a sharded index routes them to the right shard.
+//! - [`TransferMode::GrantOwner`] leaves the memories where they are and
+//!   grants `to_agent` an `admin` ACL on each one.
+//!
+//! Either way both agents get an [`EventType::OwnershipTransferred`]
+//! event, committed in one [`WriteBatch`] with the moves or grants, so a
+//! failed transfer changes nothing. `transferred_by` needs `admin` on every
+//! matching memory. A dry run only reports the memories that would move. A
+//! reassignment that matches a memory under
+//! [retention lock](super::retention_lock) is refused before anything moves.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::rechain::{RechainRequest, RechainResponse};
+use crate::error::{Error, Result};
+use crate::index::ShardRoute;
+use crate::model::acl::{Acl, Permission, PrincipalType};
+use crate::model::event::EventType;
+use crate::model::memory::{ConsolidationState, MemoryRecord, MemoryType, Scope};
+use crate::query::MnemoEngine;
+use crate::storage::{MemoryFilter, WriteBatch};
+
+/// Metadata key recording where a reassigned memory came from.
+pub const TRANSFER_METADATA_KEY: &str = "transfer";
+
+const PAGE_SIZE: usize = 500;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferMode {
+    /// Make `to_agent` the owner of each memory.
+    #[default]
+    Reassign,
+    /// Keep the owner and grant `to_agent` an `admin` ACL.
+    GrantOwner,
+}
+
+/// Which of `from_agent`'s live memories to transfer. Empty transfers
+/// them all.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransferFilter {
+    #[serde(default)]
+    pub memory_type: Option<MemoryType>,
+    #[serde(default)]
+    pub scope: Option<Scope>,
+    /// Memories carrying any of these tags.
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    #[serde(default)]
+    pub org_id: Option<String>,
+    #[serde(default)]
+    pub thread_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferRequest {
+    pub from_agent: String,
+    pub to_agent: String,
+    #[serde(default)]
+    pub filter: TransferFilter,
+    #[serde(default)]
+    pub mode: TransferMode,
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Who asked for the transfer, for the events and ACL grants.
+    /// Defaults to the engine's agent.
+    #[serde(default)]
+    pub transferred_by: Option<String>,
+}
+
+impl TransferRequest {
+    pub fn new(from_agent: impl Into<String>, to_agent: impl Into<String>) -> Self {
+        Self {
+            from_agent: from_agent.into(),
+            to_agent: to_agent.into(),
+            filter: TransferFilter::default(),
+            mode: TransferMode::default(),
+            dry_run: false,
+            transferred_by: None,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferResponse {
+    pub from_agent: String,
+    pub to_agent: String,
+    pub mode: TransferMode,
+    pub dry_run: bool,
+    /// The matching memories, newest first.
+    pub memory_ids: Vec<Uuid>,
+    /// Memories reassigned or granted; 0 on a dry run.
+    pub transferred: usize,
+    /// The chain rebuilds of a reassignment, `from_agent`'s first.
+    pub rechained: Vec<RechainResponse>,
+    /// The ownership-transfer events, `from_agent`'s first.
+    pub event_ids: Vec<Uuid>,
+}
+
+pub async fn execute(engine: &MnemoEngine, request: TransferRequest) -> Result<TransferResponse> {
+    super::validate_agent_id(&request.from_agent)
+        .map_err(|e| Error::invalid_field("from_agent", e.to_string()))?;
+    super::validate_agent_id(&request.to_agent)
+        .map_err(|e| Error::invalid_field("to_agent", e.to_string()))?;
+    if request.from_agent == request.to_agent {
+        return Err(Error::invalid_field(
+            "to_agent",
+            "must differ from from_agent",
+        ));
+    }
+    let transferred_by = engine.acting_agent(request.transferred_by.as_deref());
+
+    // Collect every match before changing owners, so paging is stable.
+    let filter = MemoryFilter {
+        agent_id: Some(request.from_agent.clone()),
+        memory_type: request.filter.memory_type,
+        scope: request.filter.scope,
+        tags: request.filter.tags.clone(),
+        min_importance: None,
+        org_id: request.filter.org_id.clone(),
+        thread_id: request.filter.thread_id.clone(),
+        include_deleted: false,
+    };
+    let mut records = Vec::new();
+    loop {
+        let page = engine
+            .storage
+            .list_memories(&filter, PAGE_SIZE, records.len())
+            .await?;
+        let done = page.len() < PAGE_SIZE;
+        records.extend(page);
+        if done {
+            break;
+        }
+    }
+    let memory_ids: Vec<Uuid> = records.iter().map(|r| r.id).collect();
+
+    let mut response = TransferResponse {
+        from_agent: request.from_agent.clone(),
+        to_agent: request.to_agent.clone(),
+        mode: request.mode,
+        dry_run: request.dry_run,
+        memory_ids,
+        transferred: 0,
+        rechained: Vec::new(),
+        event_ids: Vec::new(),
+    };
+    for record in &records {
+        if !engine
+            .storage
+            .check_permission(record.id, &transferred_by, Permission::Admin)
+            .await?
+        {
+            return Err(Error::PermissionDenied(format!(
+                "agent {transferred_by} cannot transfer memory {}",
+                record.id
+            )));
+        }
+    }
+    if request.dry_run || records.is_empty() {
+        return Ok(response);
+    }
+    if request.mode == TransferMode::Reassign && engine.retention_lock.is_some() {
+        let mut refused = None;
+        for record in &records {
+            if let Err(e) =
+                super::retention_lock::ensure_erasable(engine, record, "transfer", &transferred_by)
+                    .await
+            {
+                refused.get_or_insert(e);
+            }
+        }
+        if let Some(e) = refused {
+            return Err(e);
+        }
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut batch = WriteBatch::default();
+    for mut record in records {
+        match request.mode {
+            TransferMode::Reassign => {
+                reassign(&mut record, &request, &now);
+                batch.updates.push(record);
+            }
+            TransferMode::GrantOwner => batch.acls.push(Acl {
+                id: Uuid::now_v7(),
+                memory_id: record.id,
+                principal_type: PrincipalType::Agent,
+                principal_id: request.to_agent.clone(),
+                permission: Permission::Admin,
+                granted_by: transferred_by.clone(),
+                created_at: now.clone(),
+                expires_at: None,
+            }),
+        }
+    }
+
+    let payload = serde_json::json!({
+        "from_agent": request.from_agent,
+        "to_agent": request.to_agent,
+        "mode": request.mode,
+        "filter": request.filter,
+        "memory_ids": response.memory_ids,
+        "transferred_by": transferred_by,
+    });
+    // One event per agent, each on its own agent's chain.
+    for agent_id in [&request.from_agent, &request.to_agent] {
+        let event = super::event_builder::build_event(
+            engine,
+            agent_id,
+            EventType::OwnershipTransferred,
+            payload.clone(),
+            &format!("{}->{}", request.from_agent, request.to_agent),
+            None,
+        )
+        .await;
+        response.event_ids.push(event.id);
+        batch.events.push(event);
+    }
+    engine.storage.commit_write(&batch).await?;
+    response.transferred = response.memory_ids.len();
+
+    if request.mode == TransferMode::Reassign {
+        for record in &batch.updates {
+            reindex(engine, record)?;
+        }
+        for agent_id in [&request.from_agent, &request.to_agent] {
+            let rechained = super::rechain::execute(
+                engine,
+                RechainRequest {
+                    agent_id: Some(agent_id.clone()),
+                    thread_id: None,
+                },
+            )
+            .await?;
+            response.rechained.push(rechained);
+        }
+    }
+    Ok(response)
+}
+
+/// Hand `record` to `to_agent` and record where it came from.
+fn reassign(record: &mut MemoryRecord, request: &TransferRequest, now: &str) {
+    record.agent_id = request.to_agent.clone();
+    record.updated_at = now.to_string();
+    if !record.metadata.is_object() {
+        let value = std::mem::take(&mut record.metadata);
+        let mut map = serde_json::Map::new();
+        if !value.is_null() {
+            map.insert("value".to_string(), value);
+        }
+        record.metadata = serde_json::Value::Object(map);
+    }
+    if let Some(map) = record.metadata.as_object_mut() {
+        map.insert(
+            TRANSFER_METADATA_KEY.to_string(),
+            serde_json::json!({ "from_agent": request.from_agent, "transferred_at": now }),
+        );
+    }
+}
+
+/// Drop the cached copy and re-add the vector under the new owner, so a
+/// sharded index routes it to the right shard.
+fn reindex(engine: &MnemoEngine, record: &MemoryRecord) -> Result<()> {
+    if let Some(ref cache) = engine.cache {
+        cache.invalidate(record.id);
+    }
+    if let Some(ref embedding) = record.embedding
+        && !record.quarantined
+        && record.consolidation_state != ConsolidationState::Archived
+    {
+        engine.index.remove(record.id)?;
+        engine
+            .index
+            .add_routed(ShardRoute::of(record), record.id, embedding)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_defaults_to_reassigning_everything() {
+        let request: TransferRequest =
+            serde_json::from_str(r#"{"from_agent":"old","to_agent":"new"}"#).unwrap();
+        assert_eq!(request.mode, TransferMode::Reassign);
+        assert!(!request.dry_run);
+        assert!(request.filter.tags.is_none());
+        let request: TransferRequest = serde_json::from_str(
+            r#"{"from_agent":"old","to_agent":"new","mode":"grant_owner","dry_run":true}"#,
+        )
+        .unwrap();
+        assert_eq!(request.mode, TransferMode::GrantOwner);
+        assert!(request.dry_run);
+    }
+}
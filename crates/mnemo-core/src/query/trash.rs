@@ -0,0 +1,358 @@
+//! Trash: list, restore and purge soft-deleted memories.
+//!
+//! `forget` with the default `SoftDelete` strategy only stamps `deleted_at`
+//! and drops the record from the vector / full-text indexes. Until the
+//! retention window elapses the row can be listed with [`list_trash`] and
+//! brought back with [`undelete`], which re-indexes it. [`purge`]
+//! hard-deletes soft-deleted rows older than the configured retention and
+//! emits one `MemoryDelete` audit event per purged row. Rows still under a
+//! [retention lock](super::retention_lock) stay in the trash until it ends.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::index::ShardRoute;
+use crate::model::acl::Permission;
+use crate::model::event::EventType;
+use crate::model::memory::{MemoryRecord, MemoryType};
+use crate::query::MnemoEngine;
+use crate::search::language::LANGUAGE_METADATA_KEY;
+use crate::storage::MemoryFilter;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndeleteRequest {
+    pub memory_id: Uuid,
+    pub agent_id: Option<String>,
+}
+
+impl UndeleteRequest {
+    pub fn new(memory_id: Uuid) -> Self {
+        Self {
+            memory_id,
+            agent_id: None,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndeleteResponse {
+    pub memory_id: Uuid,
+    /// `deleted_at` the memory carried before it was restored.
+    pub deleted_at: String,
+}
+
+impl UndeleteResponse {
+    pub fn new(memory_id: Uuid, deleted_at: String) -> Self {
+        Self {
+            memory_id,
+            deleted_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashRequest {
+    pub agent_id: Option<String>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+impl TrashRequest {
+    pub fn new() -> Self {
+        Self {
+            agent_id: None,
+            limit: None,
+            offset: None,
+        }
+    }
+}
+
+impl Default for TrashRequest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One soft-deleted memory as shown in the trash listing.
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub id: Uuid,
+    pub content: String,
+    pub memory_type: MemoryType,
+    pub tags: Vec<String>,
+    pub importance: f32,
+    pub created_at: String,
+    pub deleted_at: String,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashResponse {
+    pub memories: Vec<TrashEntry>,
+    /// Total soft-deleted memories for the agent, before pagination.
+    pub total: usize,
+}
+
+impl TrashResponse {
+    pub fn new(memories: Vec<TrashEntry>, total: usize) -> Self {
+        Self { memories, total }
+    }
+}
+
+/// Report from a single trash purge pass.
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PurgeReport {
+    pub purged_count: usize,
+    pub errors: Vec<PurgeError>,
+    /// The purged memories.
+    #[serde(default)]
+    pub memory_ids: Vec<Uuid>,
+}
+
+impl PurgeReport {
+    pub fn new(purged_count: usize, errors: Vec<PurgeError>) -> Self {
+        Self {
+            purged_count,
+            errors,
+            memory_ids: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PurgeError {
+    pub memory_id: Uuid,
+    pub error: String,
+}
+
+/// Default page size for [`list_trash`].
+const DEFAULT_TRASH_LIMIT: usize = 50;
+
+pub async fn undelete(engine: &MnemoEngine, request: UndeleteRequest) -> Result<UndeleteResponse> {
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+
+    let mut record = engine
+        .storage
+        .get_memory(request.memory_id)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("memory {} not found", request.memory_id)))?;
+    let Some(deleted_at) = record.deleted_at.take() else {
+        return Err(Error::Validation(format!(
+            "memory {} is not deleted",
+            request.memory_id
+        )));
+    };
+    if !engine
+        .storage
+        .check_permission(request.memory_id, &agent_id, Permission::Write)
+        .await?
+    {
+        return Err(Error::PermissionDenied(format!(
+            "agent {agent_id} cannot restore memory {}",
+            request.memory_id
+        )));
+    }
+
+    record.updated_at = chrono::Utc::now().to_rfc3339();
+    engine.storage.update_memory(&record).await?;
+
+    // Soft delete dropped the record from both indexes; put it back.
+    match record.embedding {
+        Some(ref embedding) => {
+            engine
+                .index
+                .add_routed(ShardRoute::of(&record), record.id, embedding)?
+        }
+        None => {
+            tracing::warn!(memory_id = %record.id, "restored memory has no stored embedding; not re-indexed for vector recall");
+        }
+    }
+    if let Some(ref ft) = engine.full_text {
+        let language = record
+            .metadata
+            .get(LANGUAGE_METADATA_KEY)
+            .and_then(|v| v.as_str());
+        ft.add_with_language(record.id, &record.content, language)?;
+        ft.commit()?;
+    }
+    if let Some(ref cache) = engine.cache {
+        cache.invalidate(record.id);
+    }
+
+    let event = super::event_builder::build_event(
+        engine,
+        &agent_id,
+        EventType::MemoryWrite,
+        serde_json::json!({
+            "memory_id": record.id.to_string(),
+            "undeleted": true,
+            "deleted_at": deleted_at,
+        }),
+        &record.id.to_string(),
+        record.thread_id.clone(),
+    )
+    .await;
+    if let Err(e) = engine.storage.insert_event(&event).await {
+        tracing::error!(event_id = %event.id, error = %e, "failed to insert audit event");
+    }
+
+    Ok(UndeleteResponse::new(record.id, deleted_at))
+}
+
+pub async fn list_trash(engine: &MnemoEngine, request: TrashRequest) -> Result<TrashResponse> {
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_TRASH_LIMIT)
+        .min(super::MAX_BATCH_QUERY_LIMIT);
+    let offset = request.offset.unwrap_or(0);
+
+    let mut deleted = deleted_memories(engine, Some(agent_id)).await?;
+    // Most recently deleted first.
+    deleted.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    let total = deleted.len();
+
+    let memories = deleted
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .map(|mut record| {
+            super::consolidate::decrypt_in_place(engine, &mut record);
+            TrashEntry {
+                id: record.id,
+                content: record.content,
+                memory_type: record.memory_type,
+                tags: record.tags,
+                importance: record.importance,
+                created_at: record.created_at,
+                deleted_at: record.deleted_at.unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    Ok(TrashResponse::new(memories, total))
+}
+
+/// Every soft-deleted memory, of `agent_id` or of all agents. Pages through
+/// storage to the end, and collects before the caller purges anything so
+/// the offsets stay stable.
+async fn deleted_memories(
+    engine: &MnemoEngine,
+    agent_id: Option<String>,
+) -> Result<Vec<MemoryRecord>> {
+    let filter = MemoryFilter {
+        agent_id,
+        include_deleted: true,
+        ..Default::default()
+    };
+    let mut deleted = Vec::new();
+    let mut offset = 0;
+    loop {
+        let page = engine
+            .storage
+            .list_memories(&filter, super::MAX_BATCH_QUERY_LIMIT, offset)
+            .await?;
+        offset += page.len();
+        let done = page.len() < super::MAX_BATCH_QUERY_LIMIT;
+        deleted.extend(page.into_iter().filter(|m| m.deleted_at.is_some()));
+        if done {
+            return Ok(deleted);
+        }
+    }
+}
+
+/// Hard-delete every soft-deleted memory whose `deleted_at` is more than
+/// `retention_days` days in the past, across all agents.
+pub async fn purge(engine: &MnemoEngine, retention_days: u32) -> Result<PurgeReport> {
+    purge_pass(engine, retention_days, false).await
+}
+
+/// [`purge`], or with `dry_run` only the memories it would delete.
+pub(crate) async fn purge_pass(
+    engine: &MnemoEngine,
+    retention_days: u32,
+    dry_run: bool,
+) -> Result<PurgeReport> {
+    let memories = deleted_memories(engine, None).await?;
+
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(i64::from(retention_days));
+    let mut purged_count = 0;
+    let mut errors = Vec::new();
+    let mut memory_ids = Vec::new();
+
+    for record in memories {
+        let Some(ref deleted_at) = record.deleted_at else {
+            continue;
+        };
+        let Ok(deleted) = chrono::DateTime::parse_from_rfc3339(deleted_at) else {
+            continue;
+        };
+        if deleted > cutoff || super::retention_lock::active_lock(engine, &record).is_some() {
+            continue;
+        }
+        if dry_run {
+            purged_count += 1;
+            memory_ids.push(record.id);
+            continue;
+        }
+
+        match engine.storage.hard_delete_memory(record.id).await {
+            Ok(()) => {
+                // Soft delete already removed the record from the indexes;
+                // repeat it so an index restored from an older snapshot
+                // cannot keep serving a purged id.
+                if let Err(e) = engine.index.remove(record.id) {
+                    tracing::warn!(memory_id = %record.id, error = %e, "trash purge: vector index remove failed");
+                }
+                if let Some(ref ft) = engine.full_text {
+                    if let Err(e) = ft.remove(record.id) {
+                        tracing::warn!(memory_id = %record.id, error = %e, "trash purge: full-text remove failed");
+                    }
+                    let _ = ft.commit();
+                }
+                if let Some(ref cache) = engine.cache {
+                    cache.invalidate(record.id);
+                }
+
+                let event = super::event_builder::build_event(
+                    engine,
+                    &record.agent_id,
+                    EventType::MemoryDelete,
+                    serde_json::json!({
+                        "memory_id": record.id.to_string(),
+                        "purged": true,
+                        "deleted_at": deleted_at,
+                    }),
+                    &record.id.to_string(),
+                    None,
+                )
+                .await;
+                if let Err(e) = engine.storage.insert_event(&event).await {
+                    tracing::error!(event_id = %event.id, error = %e, "trash purge: failed to insert MemoryDelete event");
+                }
+                purged_count += 1;
+                memory_ids.push(record.id);
+            }
+            Err(e) => errors.push(PurgeError {
+                memory_id: record.id,
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    Ok(PurgeReport {
+        purged_count,
+        errors,
+        memory_ids,
+    })
+}
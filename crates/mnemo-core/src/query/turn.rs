@@ -0,0 +1,496 @@
+//! Prompt/response turn capture.
+//!
+//! Agent frameworks emit conversation turns as one unit: the user message,
+//! the assistant reply, and whatever tool calls happened in between. This
+//! module ingests that shape in a single call: it records one event per
+//! message/tool call (carrying the token and cost fields on the assistant
+//! event), links them causally through `parent_event_id`, writes an
+//! episodic memory summarising the turn, and — when `extract_facts` is set —
+//! promotes first-person declarative statements from the user message into
+//! semantic memories linked to the turn memory by a `derived_from` relation.
+//!
+//! The events and the turn memory are written in one transaction, so a
+//! turn the engine refuses — a field or limit violation, a hook, the write
+//! throttle, a failed embedding or storage write — never leaves a partial
+//! event trail behind. Extracted facts are written after the turn, each on
+//! its own; a fact that fails is logged and skipped.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, FieldViolation, Result};
+use crate::model::event::{AgentEvent, EventType};
+use crate::model::memory::{MemoryType, SourceType};
+use crate::model::relation::{DERIVED_FROM, Relation};
+use crate::query::MnemoEngine;
+use crate::query::remember::{self, EmbedWith, Prepared, RememberRequest, RememberResponse};
+use crate::storage::WriteBatch;
+
+/// Tag applied to the episodic memory written for every captured turn.
+pub const TURN_TAG: &str = "turn";
+
+/// Tag applied to semantic memories promoted by fact extraction.
+pub const TURN_FACT_TAG: &str = "turn_fact";
+
+/// One tool invocation made while producing the assistant reply.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurnToolCall {
+    pub name: String,
+    #[serde(default)]
+    pub arguments: Option<serde_json::Value>,
+    /// When present, a `ToolResult` event is recorded as a child of the
+    /// `ToolCall` event.
+    #[serde(default)]
+    pub result: Option<serde_json::Value>,
+    #[serde(default)]
+    pub latency_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurnRequest {
+    pub user_message: String,
+    pub assistant_message: String,
+    #[serde(default)]
+    pub tool_calls: Option<Vec<TurnToolCall>>,
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    #[serde(default)]
+    pub org_id: Option<String>,
+    #[serde(default)]
+    pub thread_id: Option<String>,
+    #[serde(default)]
+    pub run_id: Option<String>,
+    /// Event the user message follows on from (typically the previous
+    /// turn's assistant event).
+    #[serde(default)]
+    pub parent_event_id: Option<Uuid>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub tokens_input: Option<i64>,
+    #[serde(default)]
+    pub tokens_output: Option<i64>,
+    #[serde(default)]
+    pub cost_usd: Option<f64>,
+    #[serde(default)]
+    pub latency_ms: Option<i64>,
+    #[serde(default)]
+    pub importance: Option<f32>,
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    /// Promote first-person statements in the user message to semantic
+    /// memories. Defaults to `false`.
+    #[serde(default)]
+    pub extract_facts: Option<bool>,
+}
+
+impl TurnRequest {
+    pub fn new(user_message: String, assistant_message: String) -> Self {
+        Self {
+            user_message,
+            assistant_message,
+            tool_calls: None,
+            agent_id: None,
+            org_id: None,
+            thread_id: None,
+            run_id: None,
+            parent_event_id: None,
+            model: None,
+            tokens_input: None,
+            tokens_output: None,
+            cost_usd: None,
+            latency_ms: None,
+            importance: None,
+            tags: None,
+            extract_facts: None,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TurnResponse {
+    pub user_event_id: Uuid,
+    pub assistant_event_id: Uuid,
+    pub tool_event_ids: Vec<Uuid>,
+    pub memory_id: Uuid,
+    pub extracted_memory_ids: Vec<Uuid>,
+}
+
+impl TurnResponse {
+    pub fn new(
+        user_event_id: Uuid,
+        assistant_event_id: Uuid,
+        tool_event_ids: Vec<Uuid>,
+        memory_id: Uuid,
+        extracted_memory_ids: Vec<Uuid>,
+    ) -> Self {
+        Self {
+            user_event_id,
+            assistant_event_id,
+            tool_event_ids,
+            memory_id,
+            extracted_memory_ids,
+        }
+    }
+}
+
+pub async fn execute(engine: &MnemoEngine, request: TurnRequest) -> Result<TurnResponse> {
+    let mut violations = Vec::new();
+    if request.user_message.trim().is_empty() {
+        violations.push(FieldViolation::new("user_message", "cannot be empty"));
+    }
+    if request.assistant_message.trim().is_empty() {
+        violations.push(FieldViolation::new("assistant_message", "cannot be empty"));
+    }
+    let tool_calls = request.tool_calls.clone().unwrap_or_default();
+    for (i, call) in tool_calls.iter().enumerate() {
+        if call.name.trim().is_empty() {
+            violations.push(FieldViolation::new(
+                format!("tool_calls[{i}].name"),
+                "cannot be empty",
+            ));
+        }
+    }
+    if let Some(importance) = request.importance
+        && !(0.0..=1.0).contains(&importance)
+    {
+        violations.push(FieldViolation::new(
+            "importance",
+            "must be between 0.0 and 1.0",
+        ));
+    }
+    for (field, value) in [
+        ("tokens_input", request.tokens_input),
+        ("tokens_output", request.tokens_output),
+    ] {
+        if value.is_some_and(|v| v < 0) {
+            violations.push(FieldViolation::new(field, "cannot be negative"));
+        }
+    }
+    if request.cost_usd.is_some_and(|c| !c.is_finite() || c < 0.0) {
+        violations.push(FieldViolation::new(
+            "cost_usd",
+            "must be a non-negative number",
+        ));
+    }
+    if !violations.is_empty() {
+        return Err(Error::InvalidFields(violations));
+    }
+
+    let agent_id = request
+        .agent_id
+        .clone()
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+
+    // User message — roots the turn, optionally chained to a prior event.
+    let mut user_event = super::event_builder::build_event(
+        engine,
+        &agent_id,
+        EventType::UserMessage,
+        serde_json::json!({"content": request.user_message}),
+        &request.user_message,
+        request.thread_id.clone(),
+    )
+    .await;
+    user_event.run_id = request.run_id.clone();
+    user_event.parent_event_id = request.parent_event_id;
+
+    // Tool calls hang off the user message; results hang off their call.
+    let mut tool_event_ids = Vec::with_capacity(tool_calls.len());
+    let mut tool_events = Vec::with_capacity(tool_calls.len());
+    for call in &tool_calls {
+        let payload = serde_json::json!({
+            "name": call.name,
+            "arguments": call.arguments,
+        });
+        let mut call_event = super::event_builder::build_event(
+            engine,
+            &agent_id,
+            EventType::ToolCall,
+            payload.clone(),
+            &payload.to_string(),
+            request.thread_id.clone(),
+        )
+        .await;
+        call_event.run_id = request.run_id.clone();
+        call_event.parent_event_id = Some(user_event.id);
+        call_event.latency_ms = call.latency_ms;
+        tool_event_ids.push(call_event.id);
+
+        if let Some(ref result) = call.result {
+            let payload = serde_json::json!({"name": call.name, "result": result});
+            let mut result_event = super::event_builder::build_event(
+                engine,
+                &agent_id,
+                EventType::ToolResult,
+                payload.clone(),
+                &payload.to_string(),
+                request.thread_id.clone(),
+            )
+            .await;
+            result_event.run_id = request.run_id.clone();
+            result_event.parent_event_id = Some(call_event.id);
+            tool_event_ids.push(result_event.id);
+            tool_events.push(call_event);
+            tool_events.push(result_event);
+        } else {
+            tool_events.push(call_event);
+        }
+    }
+
+    // Assistant reply carries the model/token/cost accounting for the turn.
+    let mut assistant_event = super::event_builder::build_event(
+        engine,
+        &agent_id,
+        EventType::AssistantMessage,
+        serde_json::json!({"content": request.assistant_message}),
+        &request.assistant_message,
+        request.thread_id.clone(),
+    )
+    .await;
+    assistant_event.run_id = request.run_id.clone();
+    assistant_event.parent_event_id = Some(user_event.id);
+    assistant_event.model = request.model.clone();
+    assistant_event.tokens_input = request.tokens_input;
+    assistant_event.tokens_output = request.tokens_output;
+    assistant_event.cost_usd = request.cost_usd;
+    assistant_event.latency_ms = request.latency_ms;
+
+    let turn = TurnMemory {
+        agent_id: &agent_id,
+        org_id: request.org_id.clone(),
+        thread_id: request.thread_id.clone(),
+        user_message: &request.user_message,
+        assistant_message: &request.assistant_message,
+        tool_names: tool_calls.iter().map(|t| t.name.as_str()).collect(),
+        user_event_id: user_event.id,
+        assistant_event_id: assistant_event.id,
+        model: request.model.clone(),
+        tokens_input: request.tokens_input,
+        tokens_output: request.tokens_output,
+        cost_usd: request.cost_usd,
+        importance: request.importance,
+        tags: request.tags.clone(),
+        extract_facts: request.extract_facts.unwrap_or(false),
+    };
+    let (user_event_id, assistant_event_id) = (user_event.id, assistant_event.id);
+    let mut events = vec![user_event];
+    events.append(&mut tool_events);
+    events.push(assistant_event);
+    let (memory_id, extracted_memory_ids) = remember_turn(engine, turn, events).await?;
+
+    Ok(TurnResponse::new(
+        user_event_id,
+        assistant_event_id,
+        tool_event_ids,
+        memory_id,
+        extracted_memory_ids,
+    ))
+}
+
+/// A recorded exchange to write memories for.
+pub(crate) struct TurnMemory<'a> {
+    pub agent_id: &'a str,
+    pub org_id: Option<String>,
+    pub thread_id: Option<String>,
+    pub user_message: &'a str,
+    pub assistant_message: &'a str,
+    pub tool_names: Vec<&'a str>,
+    pub user_event_id: Uuid,
+    pub assistant_event_id: Uuid,
+    pub model: Option<String>,
+    pub tokens_input: Option<i64>,
+    pub tokens_output: Option<i64>,
+    pub cost_usd: Option<f64>,
+    pub importance: Option<f32>,
+    pub tags: Option<Vec<String>>,
+    pub extract_facts: bool,
+}
+
+/// The episodic memory written for `turn`.
+fn turn_request(turn: &TurnMemory<'_>) -> RememberRequest {
+    let mut tags = vec![TURN_TAG.to_string()];
+    if let Some(ref extra) = turn.tags {
+        tags.extend(extra.iter().cloned());
+    }
+    let mut remember = RememberRequest::new(render_turn(
+        turn.user_message,
+        turn.assistant_message,
+        &turn.tool_names,
+    ));
+    remember.agent_id = Some(turn.agent_id.to_string());
+    remember.org_id = turn.org_id.clone();
+    remember.thread_id = turn.thread_id.clone();
+    remember.memory_type = Some(MemoryType::Episodic);
+    remember.importance = turn.importance;
+    remember.tags = Some(tags);
+    remember.source_type = Some(SourceType::Agent);
+    remember.source_id = Some(turn.assistant_event_id.to_string());
+    remember.metadata = Some(serde_json::json!({
+        "turn": {
+            "user_event_id": turn.user_event_id.to_string(),
+            "assistant_event_id": turn.assistant_event_id.to_string(),
+            "tool_calls": turn.tool_names,
+            "model": turn.model,
+            "tokens_input": turn.tokens_input,
+            "tokens_output": turn.tokens_output,
+            "cost_usd": turn.cost_usd,
+        }
+    }));
+    remember
+}
+
+/// Write `events`, built but not yet stored, together with the episodic
+/// memory of their turn in one transaction, then, when asked, the
+/// semantic memories extracted from its user message. Returns the turn
+/// memory's id and the extracted ids.
+pub(crate) async fn remember_turn(
+    engine: &MnemoEngine,
+    turn: TurnMemory<'_>,
+    events: Vec<AgentEvent>,
+) -> Result<(Uuid, Vec<Uuid>)> {
+    let agent_id = turn.agent_id.to_string();
+    if let Some(ref detector) = engine.write_anomaly {
+        detector.check_throttle(&agent_id)?;
+    }
+    let mut request = turn_request(&turn);
+    let memory_id = crate::storage::with_agent_context(agent_id, async {
+        engine.hooks.pre_remember(&mut request).await?;
+        let response = commit_turn(engine, request.clone(), events).await?;
+        engine.hooks.post_remember(&request, &response).await?;
+        Ok::<_, Error>(response.id)
+    })
+    .await?;
+
+    let mut extracted_memory_ids = Vec::new();
+    if turn.extract_facts {
+        for fact in extract_facts(turn.user_message) {
+            let mut req = RememberRequest::new(fact);
+            req.agent_id = Some(turn.agent_id.to_string());
+            req.org_id = turn.org_id.clone();
+            req.thread_id = turn.thread_id.clone();
+            req.memory_type = Some(MemoryType::Semantic);
+            req.tags = Some(vec![TURN_FACT_TAG.to_string()]);
+            req.source_type = Some(SourceType::UserInput);
+            req.source_id = Some(turn.user_event_id.to_string());
+            req.metadata = Some(serde_json::json!({"extracted_from_turn": memory_id.to_string()}));
+            let fact_id = match engine.remember(req).await {
+                Ok(response) => response.id,
+                Err(e) => {
+                    tracing::warn!(turn_memory_id = %memory_id, error = %e, "failed to remember extracted fact");
+                    continue;
+                }
+            };
+            let relation = Relation {
+                id: Uuid::now_v7(),
+                source_id: fact_id,
+                target_id: memory_id,
+                relation_type: DERIVED_FROM.to_string(),
+                weight: 1.0,
+                metadata: serde_json::json!({"extractor": "turn_facts"}),
+                created_at: chrono::Utc::now().to_rfc3339(),
+            };
+            if let Err(e) = engine.storage.insert_relation(&relation).await {
+                tracing::error!(relation_id = %relation.id, error = %e, "failed to insert derived_from relation");
+            }
+            extracted_memory_ids.push(fact_id);
+        }
+    }
+    Ok((memory_id, extracted_memory_ids))
+}
+
+/// Commit `events` with the memory `request` describes. A memory the dedup
+/// policy folds into an existing one leaves the events to commit alone.
+async fn commit_turn(
+    engine: &MnemoEngine,
+    request: RememberRequest,
+    mut events: Vec<AgentEvent>,
+) -> Result<RememberResponse> {
+    match remember::prepare(engine, request, EmbedWith::Content).await? {
+        Prepared::Existing(response) => {
+            super::event_builder::chain_in_order(&mut events);
+            engine
+                .storage
+                .commit_write(&WriteBatch {
+                    events,
+                    ..Default::default()
+                })
+                .await?;
+            Ok(response)
+        }
+        Prepared::Pending(mut pending) => {
+            events.append(&mut pending.batch.events);
+            super::event_builder::chain_in_order(&mut events);
+            pending.batch.events = events;
+            engine.storage.commit_write(&pending.batch).await?;
+            remember::finish(engine, *pending).await
+        }
+    }
+}
+
+fn render_turn(user: &str, assistant: &str, tools: &[&str]) -> String {
+    let mut out = format!("User: {}\n", user.trim());
+    if !tools.is_empty() {
+        out.push_str(&format!("Tools: {}\n", tools.join(", ")));
+    }
+    out.push_str(&format!("Assistant: {}", assistant.trim()));
+    out
+}
+
+/// Pull first-person declarative sentences ("I live in Berlin", "My
+/// manager is Dana") out of a user message. Questions and sentences
+/// shorter than three words are skipped. Deliberately conservative — a
+/// missed fact is cheaper than a hallucinated one.
+pub(crate) fn extract_facts(message: &str) -> Vec<String> {
+    const PREFIXES: &[&str] = &["i ", "i'm ", "i am ", "i've ", "my ", "we ", "our "];
+    let mut facts = Vec::new();
+    let mut start = 0;
+    for (i, c) in message.char_indices() {
+        if matches!(c, '.' | '!' | '?' | '\n') {
+            push_fact(&message[start..=i], PREFIXES, &mut facts);
+            start = i + c.len_utf8();
+        }
+    }
+    if start < message.len() {
+        push_fact(&message[start..], PREFIXES, &mut facts);
+    }
+    facts
+}
+
+fn push_fact(sentence: &str, prefixes: &[&str], facts: &mut Vec<String>) {
+    let s = sentence.trim();
+    if s.is_empty() || s.ends_with('?') || s.split_whitespace().count() < 3 {
+        return;
+    }
+    let lower = s.to_lowercase();
+    if prefixes.iter().any(|p| lower.starts_with(p)) {
+        facts.push(s.trim_end_matches(['.', '!']).to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_facts_keeps_first_person_declaratives() {
+        let facts =
+            extract_facts("Hi there. I live in Berlin. What's the weather? My manager is Dana! ok");
+        assert_eq!(facts, vec!["I live in Berlin", "My manager is Dana"]);
+    }
+
+    #[test]
+    fn extract_facts_skips_questions_and_short_fragments() {
+        assert!(extract_facts("Am I late? I am.").is_empty());
+    }
+
+    #[test]
+    fn render_turn_lists_tools_between_messages() {
+        assert_eq!(
+            render_turn("hello", "hi", &["search", "fetch"]),
+            "User: hello\nTools: search, fetch\nAssistant: hi"
+        );
+    }
+}
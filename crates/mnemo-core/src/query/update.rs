@@ -0,0 +1,180 @@
+//! Update the mutable fields of an existing memory in place.
+//!
+//! Only `importance`, `tags` and `metadata` can change. Content is covered
+//! by the hash chain, so content changes go through a revision instead
+//! ([`consolidate`](super::consolidate) `supersede`). New tags that would
+//! end or shorten a [retention lock](super::retention_lock) are refused.
+//!
+//! Every update bumps the memory's `version`. Pass the version you read as
+//! `expected_version` and the update applies only if nobody updated the
+//! memory since; otherwise it fails with [`Error::VersionConflict`]
+//! carrying the memory as stored now, so two agents updating the same
+//! memory cannot silently clobber each other. Without `expected_version`
+//! the update always applies (last writer wins).
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::acl::Permission;
+use crate::model::event::EventType;
+use crate::model::memory::MemoryRecord;
+use crate::query::MnemoEngine;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateRequest {
+    pub memory_id: Uuid,
+    pub agent_id: Option<String>,
+    pub importance: Option<f32>,
+    /// Replaces the memory's tags.
+    pub tags: Option<Vec<String>>,
+    /// Replaces the memory's metadata.
+    pub metadata: Option<serde_json::Value>,
+    /// Apply only if the memory is still at this version.
+    pub expected_version: Option<u32>,
+}
+
+impl UpdateRequest {
+    pub fn new(memory_id: Uuid) -> Self {
+        Self {
+            memory_id,
+            agent_id: None,
+            importance: None,
+            tags: None,
+            metadata: None,
+            expected_version: None,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateResponse {
+    pub id: Uuid,
+    /// The memory's version after this update; pass it as the next
+    /// `expected_version`.
+    pub version: u32,
+    pub updated_at: String,
+}
+
+impl UpdateResponse {
+    pub fn new(id: Uuid, version: u32, updated_at: String) -> Self {
+        Self {
+            id,
+            version,
+            updated_at,
+        }
+    }
+}
+
+pub async fn execute(engine: &MnemoEngine, request: UpdateRequest) -> Result<UpdateResponse> {
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    if let Some(importance) = request.importance
+        && !(0.0..=1.0).contains(&importance)
+    {
+        return Err(Error::invalid_field(
+            "importance",
+            "must be between 0.0 and 1.0",
+        ));
+    }
+    if let Some(ref tags) = request.tags {
+        engine.limits().check_tags(tags)?;
+    }
+    if let Some(ref metadata) = request.metadata {
+        engine.limits().check_metadata(metadata)?;
+    }
+
+    let mut record = load(engine, request.memory_id).await?;
+    if !engine
+        .storage
+        .check_permission(request.memory_id, &agent_id, Permission::Write)
+        .await?
+    {
+        return Err(Error::PermissionDenied(format!(
+            "agent {agent_id} cannot modify memory {}",
+            request.memory_id
+        )));
+    }
+
+    let expected = request.expected_version.unwrap_or(record.version);
+    if record.version != expected {
+        return Err(conflict(engine, record, expected));
+    }
+    let before =
+        (engine.retention_lock.is_some() && request.tags.is_some()).then(|| record.clone());
+
+    if let Some(importance) = request.importance {
+        record.importance = importance;
+    }
+    if let Some(tags) = request.tags {
+        // Tagging an unreviewed memory into a reviewed tag sends it to review.
+        record.attestation = record
+            .attestation
+            .or_else(|| super::attestation::initial_status(engine, &tags));
+        record.tags = tags;
+    }
+    if let Some(metadata) = request.metadata {
+        record.metadata = metadata;
+    }
+    if let Some(ref before) = before {
+        super::retention_lock::ensure_lock_kept(engine, before, &record, "update", &agent_id)
+            .await?;
+    }
+    record.version = expected + 1;
+    record.updated_at = chrono::Utc::now().to_rfc3339();
+
+    if !engine
+        .storage
+        .update_memory_if_version(&record, expected)
+        .await?
+    {
+        // Lost the race to a writer that landed after our read.
+        let current = load(engine, request.memory_id).await?;
+        return Err(conflict(engine, current, expected));
+    }
+    if let Some(ref cache) = engine.cache {
+        cache.invalidate(record.id);
+    }
+
+    let event = super::event_builder::build_event(
+        engine,
+        &agent_id,
+        EventType::MemoryWrite,
+        serde_json::json!({
+            "memory_id": record.id.to_string(),
+            "version": record.version,
+        }),
+        &record.id.to_string(),
+        record.thread_id.clone(),
+    )
+    .await;
+    if let Err(e) = engine.storage.insert_event(&event).await {
+        tracing::error!(event_id = %event.id, error = %e, "failed to insert audit event");
+    }
+
+    Ok(UpdateResponse::new(
+        record.id,
+        record.version,
+        record.updated_at,
+    ))
+}
+
+async fn load(engine: &MnemoEngine, memory_id: Uuid) -> Result<MemoryRecord> {
+    match engine.storage.get_memory(memory_id).await? {
+        Some(record) if !record.is_deleted() => Ok(record),
+        _ => Err(Error::NotFound(format!("memory {memory_id} not found"))),
+    }
+}
+
+fn conflict(engine: &MnemoEngine, mut current: MemoryRecord, expected: u32) -> Error {
+    super::consolidate::decrypt_in_place(engine, &mut current);
+    Error::VersionConflict {
+        memory_id: current.id,
+        expected,
+        actual: current.version,
+        current: Box::new(current),
+    }
+}
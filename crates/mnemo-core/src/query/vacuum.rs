@@ -0,0 +1,131 @@
+//! Reclaim storage space left by hard deletes and updates.
+//!
+//! A vacuum runs [`StorageBackend::compact`](crate::storage::StorageBackend::compact)
+//! and reports how much the database shrank. Compaction holds up writes
+//! while it runs, so a pass is skipped, not queued, while the engine is
+//! busy: when a write started within the last `quiet_seconds`, or when
+//! another vacuum is still running. `force` skips the first check only.
+//! The server runs passes on a timer; the admin API runs one on demand.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::query::MnemoEngine;
+
+/// Seconds without writes a vacuum waits for by default.
+pub const DEFAULT_VACUUM_QUIET_SECONDS: u64 = 30;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VacuumRequest {
+    /// Skip the pass if a write started within this many seconds.
+    /// Defaults to [`DEFAULT_VACUUM_QUIET_SECONDS`].
+    pub quiet_seconds: Option<u64>,
+    /// Run even if writes are in progress.
+    #[serde(default)]
+    pub force: bool,
+}
+
+impl VacuumRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VacuumResponse {
+    /// `false` when the pass was skipped; see `skipped_reason`.
+    pub ran: bool,
+    pub skipped_reason: Option<String>,
+    /// Database size before and after, in bytes. 0 for an in-memory
+    /// database or a skipped pass.
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub reclaimed_bytes: u64,
+}
+
+impl VacuumResponse {
+    fn skipped(reason: String) -> Self {
+        Self {
+            ran: false,
+            skipped_reason: Some(reason),
+            bytes_before: 0,
+            bytes_after: 0,
+            reclaimed_bytes: 0,
+        }
+    }
+}
+
+/// When the engine last started a write, and whether a vacuum is running.
+#[derive(Debug, Default)]
+pub(crate) struct WriteActivity {
+    /// Unix milliseconds; 0 before the first write.
+    last_write_ms: AtomicU64,
+    vacuuming: AtomicBool,
+}
+
+impl WriteActivity {
+    pub(crate) fn record_write(&self) {
+        self.last_write_ms.store(now_ms(), Ordering::Relaxed);
+    }
+
+    fn millis_since_write(&self) -> Option<u64> {
+        match self.last_write_ms.load(Ordering::Relaxed) {
+            0 => None,
+            at => Some(now_ms().saturating_sub(at)),
+        }
+    }
+}
+
+fn now_ms() -> u64 {
+    chrono::Utc::now().timestamp_millis().max(0) as u64
+}
+
+/// Clears the running flag when the pass ends, even on error.
+struct Running<'a>(&'a AtomicBool);
+
+impl Drop for Running<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+pub async fn execute(engine: &MnemoEngine, request: VacuumRequest) -> Result<VacuumResponse> {
+    let activity = &engine.write_activity;
+    let quiet_seconds = request
+        .quiet_seconds
+        .unwrap_or(DEFAULT_VACUUM_QUIET_SECONDS);
+    if !request.force
+        && let Some(elapsed) = activity.millis_since_write()
+        && elapsed < quiet_seconds * 1000
+    {
+        return Ok(VacuumResponse::skipped(format!(
+            "a write started {}s ago; waiting for {quiet_seconds}s without writes",
+            elapsed / 1000
+        )));
+    }
+    if activity.vacuuming.swap(true, Ordering::SeqCst) {
+        return Ok(VacuumResponse::skipped(
+            "another vacuum is running".to_string(),
+        ));
+    }
+    let _running = Running(&activity.vacuuming);
+
+    let started = std::time::Instant::now();
+    let report = engine.storage.compact().await?;
+    tracing::info!(
+        bytes_before = report.bytes_before,
+        bytes_after = report.bytes_after,
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        "vacuum complete"
+    );
+    Ok(VacuumResponse {
+        ran: true,
+        skipped_reason: None,
+        bytes_before: report.bytes_before,
+        bytes_after: report.bytes_after,
+        reclaimed_bytes: report.reclaimed_bytes(),
+    })
+}
@@ -0,0 +1,143 @@
+//! Parsing and canonical forms for the timestamps and ids in requests.
+//!
+//! Transports hand timestamps and ids to the engine as strings. A bad one
+//! used to reach storage, or be skipped by a filter that could not parse
+//! it. The helpers here parse them when a request arrives and report a
+//! bad value as [`Error::InvalidFields`] naming the field. Timestamps are
+//! normalized to UTC RFC 3339, the form the engine writes, so they compare
+//! correctly with stored times, even as strings. UUIDs may be in any form
+//! [`Uuid::parse_str`] accepts.
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::error::{Error, FieldViolation, Result};
+
+/// Parse an RFC 3339 timestamp, e.g. `2026-01-31T09:00:00Z`, to UTC.
+pub fn parse_timestamp(field: &str, value: &str) -> Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value.trim())
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|_| {
+            Error::invalid_field(
+                field,
+                format!("invalid RFC 3339 timestamp '{value}', expected e.g. 2026-01-31T09:00:00Z"),
+            )
+        })
+}
+
+/// [`parse_timestamp`], back to a string in the engine's form.
+pub fn normalize_timestamp(field: &str, value: &str) -> Result<String> {
+    Ok(parse_timestamp(field, value)?.to_rfc3339())
+}
+
+pub fn normalize_optional_timestamp(field: &str, value: Option<String>) -> Result<Option<String>> {
+    value.map(|v| normalize_timestamp(field, &v)).transpose()
+}
+
+/// The time `hours` after `now`, for an `expires_in_hours` field. `None`
+/// never expires. Hours must be positive and finite.
+pub fn expiry_after_hours(
+    field: &str,
+    hours: Option<f64>,
+    now: DateTime<Utc>,
+) -> Result<Option<String>> {
+    let Some(hours) = hours else {
+        return Ok(None);
+    };
+    let expires_at = (hours.is_finite() && hours > 0.0)
+        .then(|| chrono::Duration::try_milliseconds((hours * 3_600_000.0) as i64))
+        .flatten()
+        .and_then(|after| now.checked_add_signed(after))
+        .ok_or_else(|| Error::invalid_field(field, "must be a positive number of hours"))?;
+    Ok(Some(expires_at.to_rfc3339()))
+}
+
+pub fn parse_uuid(field: &str, value: &str) -> Result<Uuid> {
+    Uuid::parse_str(value.trim())
+        .map_err(|_| Error::invalid_field(field, format!("invalid UUID '{value}'")))
+}
+
+/// Parse every id, reporting each bad one as `field[i]`.
+pub fn parse_uuids(field: &str, values: &[String]) -> Result<Vec<Uuid>> {
+    let mut ids = Vec::with_capacity(values.len());
+    let mut violations = Vec::new();
+    for (i, value) in values.iter().enumerate() {
+        match Uuid::parse_str(value.trim()) {
+            Ok(id) => ids.push(id),
+            Err(_) => violations.push(FieldViolation::new(
+                format!("{field}[{i}]"),
+                format!("invalid UUID '{value}'"),
+            )),
+        }
+    }
+    if violations.is_empty() {
+        Ok(ids)
+    } else {
+        Err(Error::InvalidFields(violations))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(err: Error) -> Vec<String> {
+        match err {
+            Error::InvalidFields(violations) => violations.into_iter().map(|v| v.path).collect(),
+            other => panic!("expected invalid fields, got {other}"),
+        }
+    }
+
+    #[test]
+    fn timestamps_normalize_to_utc() {
+        assert_eq!(
+            normalize_timestamp("as_of", " 2026-01-31T10:30:00+01:00 ").unwrap(),
+            "2026-01-31T09:30:00+00:00"
+        );
+        assert_eq!(
+            normalize_timestamp("as_of", "2026-01-31T09:30:00.250Z").unwrap(),
+            "2026-01-31T09:30:00.250+00:00"
+        );
+        for bad in ["2026-01-31", "yesterday", "2026-13-01T00:00:00Z", ""] {
+            assert_eq!(
+                paths(normalize_timestamp("as_of", bad).unwrap_err()),
+                ["as_of"]
+            );
+        }
+        assert_eq!(normalize_optional_timestamp("as_of", None).unwrap(), None);
+    }
+
+    #[test]
+    fn expiries_need_positive_hours() {
+        let now = parse_timestamp("now", "2026-01-31T09:00:00Z").unwrap();
+        assert_eq!(
+            expiry_after_hours("expires_in_hours", Some(1.5), now).unwrap(),
+            Some("2026-01-31T10:30:00+00:00".to_string())
+        );
+        assert_eq!(
+            expiry_after_hours("expires_in_hours", None, now).unwrap(),
+            None
+        );
+        for bad in [0.0, -1.0, f64::NAN, f64::INFINITY, 1e300] {
+            assert!(expiry_after_hours("expires_in_hours", Some(bad), now).is_err());
+        }
+    }
+
+    #[test]
+    fn uuids_report_each_bad_entry() {
+        let id = Uuid::now_v7();
+        assert_eq!(
+            parse_uuid("memory_id", &id.simple().to_string()).unwrap(),
+            id
+        );
+        assert_eq!(
+            paths(parse_uuid("memory_id", "not-a-uuid").unwrap_err()),
+            ["memory_id"]
+        );
+        let values = vec![id.to_string(), "x".into(), "y".into()];
+        assert_eq!(
+            paths(parse_uuids("memory_ids", &values).unwrap_err()),
+            ["memory_ids[1]", "memory_ids[2]"]
+        );
+    }
+}
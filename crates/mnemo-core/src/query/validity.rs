@@ -0,0 +1,146 @@
+//! Fact validity windows on memories.
+//!
+//! A memory can record when the fact it states holds: "the user lives in
+//! Berlin" from the day they moved there until the day they left. Remember
+//! accepts `valid_from` / `valid_until`, and [`execute`] replaces the window
+//! of an existing memory, typically to close a fact that stopped being true.
+//!
+//! Recall prefers facts that hold now: a hit outside its window keeps its
+//! place in the candidate set but its score is multiplied by
+//! [`OUT_OF_WINDOW_PENALTY`]. A recall with `as_of` instead drops every
+//! memory whose fact did not hold at `as_of`.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::acl::Permission;
+use crate::model::event::EventType;
+use crate::query::MnemoEngine;
+
+/// Score factor for recall hits whose fact does not hold at recall time.
+pub const OUT_OF_WINDOW_PENALTY: f32 = 0.5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetValidityRequest {
+    pub memory_id: Uuid,
+    pub agent_id: Option<String>,
+    /// New start of the window; `None` leaves it open.
+    pub valid_from: Option<String>,
+    /// New exclusive end of the window; `None` leaves it open.
+    pub valid_until: Option<String>,
+}
+
+impl SetValidityRequest {
+    pub fn new(memory_id: Uuid) -> Self {
+        Self {
+            memory_id,
+            agent_id: None,
+            valid_from: None,
+            valid_until: None,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetValidityResponse {
+    pub memory_id: Uuid,
+    pub valid_from: Option<String>,
+    pub valid_until: Option<String>,
+}
+
+impl SetValidityResponse {
+    pub fn new(memory_id: Uuid, valid_from: Option<String>, valid_until: Option<String>) -> Self {
+        Self {
+            memory_id,
+            valid_from,
+            valid_until,
+        }
+    }
+}
+
+/// Check a validity window and normalize both bounds to UTC RFC 3339.
+pub(crate) fn normalize_window(
+    valid_from: Option<String>,
+    valid_until: Option<String>,
+) -> Result<(Option<String>, Option<String>)> {
+    let parse = |field: &str, value: Option<String>| {
+        value
+            .map(|v| super::validation::parse_timestamp(field, &v))
+            .transpose()
+    };
+    let from = parse("valid_from", valid_from)?;
+    let until = parse("valid_until", valid_until)?;
+    if let (Some(from), Some(until)) = (from, until)
+        && until <= from
+    {
+        return Err(Error::invalid_field(
+            "valid_until",
+            "must be after valid_from",
+        ));
+    }
+    Ok((
+        from.map(|dt| dt.to_rfc3339()),
+        until.map(|dt| dt.to_rfc3339()),
+    ))
+}
+
+pub async fn execute(
+    engine: &MnemoEngine,
+    request: SetValidityRequest,
+) -> Result<SetValidityResponse> {
+    let agent_id = request
+        .agent_id
+        .unwrap_or_else(|| engine.default_agent_id.clone());
+    super::validate_agent_id(&agent_id)?;
+    let (valid_from, valid_until) = normalize_window(request.valid_from, request.valid_until)?;
+
+    let mut record = engine
+        .storage
+        .get_memory(request.memory_id)
+        .await?
+        .filter(|r| !r.is_deleted())
+        .ok_or_else(|| Error::NotFound(format!("memory {} not found", request.memory_id)))?;
+    if !engine
+        .storage
+        .check_permission(request.memory_id, &agent_id, Permission::Write)
+        .await?
+    {
+        return Err(Error::PermissionDenied(format!(
+            "agent {agent_id} cannot modify memory {}",
+            request.memory_id
+        )));
+    }
+
+    record.valid_from = valid_from;
+    record.valid_until = valid_until;
+    record.updated_at = chrono::Utc::now().to_rfc3339();
+    engine.storage.update_memory(&record).await?;
+    if let Some(ref cache) = engine.cache {
+        cache.invalidate(record.id);
+    }
+
+    let event = super::event_builder::build_event(
+        engine,
+        &agent_id,
+        EventType::MemoryWrite,
+        serde_json::json!({
+            "memory_id": record.id.to_string(),
+            "valid_from": record.valid_from,
+            "valid_until": record.valid_until,
+        }),
+        &record.id.to_string(),
+        record.thread_id.clone(),
+    )
+    .await;
+    if let Err(e) = engine.storage.insert_event(&event).await {
+        tracing::error!(event_id = %event.id, error = %e, "failed to insert audit event");
+    }
+
+    Ok(SetValidityResponse::new(
+        record.id,
+        record.valid_from,
+        record.valid_until,
+    ))
+}
@@ -0,0 +1,163 @@
+//! Paginated and incremental memory hash-chain verification.
+//!
+//! A chain is one agent's live memories, or one thread's, in `created_at`
+//! order. Verification walks it in pages of [`VERIFY_PAGE_SIZE`], so there
+//! is no cap on its length. Every run that ends on a valid record stores a
+//! [`ChainCheckpoint`]: the last verified record, its content hash and a
+//! rolling hash over every verified record. An incremental run resumes
+//! after that record and checks the first new record's link against the
+//! stored hash, so repeated checks only cost the records added since.
+//!
+//! An incremental run trusts the records behind its checkpoint; a record
+//! edited after it was verified is only caught by a full run, which also
+//! rebuilds the checkpoint.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::hash::{ChainVerificationResult, roll_chain_root, verify_chain_from};
+use crate::model::chain_checkpoint::ChainCheckpoint;
+use crate::query::MnemoEngine;
+
+/// Records fetched per storage round trip.
+pub const VERIFY_PAGE_SIZE: usize = 1000;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VerifyRequest {
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    #[serde(default)]
+    pub thread_id: Option<String>,
+    /// Resume after the chain's checkpoint instead of starting over.
+    #[serde(default)]
+    pub incremental: bool,
+    /// Stop after this many records. The checkpoint is saved, so the next
+    /// incremental run carries on from there.
+    #[serde(default)]
+    pub max_records: Option<usize>,
+}
+
+impl VerifyRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyResponse {
+    /// Counts cover the records examined by this run only.
+    #[serde(flatten)]
+    pub result: ChainVerificationResult,
+    /// False when `max_records` stopped the run before the chain's end.
+    pub complete: bool,
+    /// The chain's checkpoint after this run, if anything has been verified.
+    pub checkpoint: Option<ChainCheckpoint>,
+}
+
+pub async fn execute(engine: &MnemoEngine, request: VerifyRequest) -> Result<VerifyResponse> {
+    if request.max_records == Some(0) {
+        return Err(Error::invalid_field("max_records", "must be positive"));
+    }
+    let agent_id = engine.acting_agent(request.agent_id.as_deref());
+    let thread_id = request.thread_id.as_deref();
+    let previous = if request.incremental {
+        engine
+            .storage
+            .get_chain_checkpoint(&agent_id, thread_id)
+            .await?
+    } else {
+        None
+    };
+
+    let decode = |hex_str: &str| {
+        hex::decode(hex_str).map_err(|e| Error::Storage(format!("corrupt chain checkpoint: {e}")))
+    };
+    let mut cursor = previous
+        .as_ref()
+        .map(|c| (c.last_created_at.clone(), c.last_record_id));
+    let mut last_hash = previous
+        .as_ref()
+        .map(|c| decode(&c.last_content_hash))
+        .transpose()?;
+    let mut root = match previous {
+        Some(ref c) => decode(&c.rolling_root)?,
+        None => Vec::new(),
+    };
+    let mut verified_count = previous.as_ref().map_or(0, |c| c.verified_count);
+
+    let budget = request.max_records.unwrap_or(usize::MAX);
+    let mut result = ChainVerificationResult {
+        valid: true,
+        total_records: 0,
+        verified_records: 0,
+        first_broken_at: None,
+        error_message: None,
+    };
+    let mut complete = false;
+    let mut advanced = false;
+    while result.total_records < budget {
+        let limit = VERIFY_PAGE_SIZE.min(budget - result.total_records);
+        let page = engine
+            .storage
+            .list_memories_chain_page(
+                &agent_id,
+                thread_id,
+                cursor.as_ref().map(|(at, id)| (at.as_str(), *id)),
+                limit,
+            )
+            .await?;
+        let checked = verify_chain_from(last_hash.as_deref(), &page);
+        result.total_records += page.len();
+        result.verified_records += checked.verified_records;
+        if !checked.valid {
+            result.valid = false;
+            result.first_broken_at = checked.first_broken_at;
+            result.error_message = checked.error_message;
+            break;
+        }
+        let Some(last) = page.last() else {
+            complete = true;
+            break;
+        };
+        for record in &page {
+            root = roll_chain_root(&root, &record.content_hash);
+        }
+        verified_count += page.len() as u64;
+        cursor = Some((last.created_at.clone(), last.id));
+        last_hash = Some(last.content_hash.clone());
+        advanced = true;
+        if page.len() < limit {
+            complete = true;
+            break;
+        }
+    }
+
+    let checkpoint = match (cursor, last_hash) {
+        (Some((last_created_at, last_record_id)), Some(last_hash)) if advanced => {
+            Some(ChainCheckpoint {
+                agent_id,
+                thread_id: request.thread_id,
+                verified_count,
+                last_record_id,
+                last_created_at,
+                last_content_hash: hex::encode(last_hash),
+                rolling_root: hex::encode(&root),
+                verified_at: chrono::Utc::now().to_rfc3339(),
+            })
+        }
+        _ => previous,
+    };
+    // A read-only engine still verifies; it just cannot move the marker.
+    if advanced
+        && !engine.is_read_only()
+        && let Some(ref checkpoint) = checkpoint
+    {
+        engine.storage.put_chain_checkpoint(checkpoint).await?;
+    }
+    Ok(VerifyResponse {
+        result,
+        complete,
+        checkpoint,
+    })
+}
@@ -0,0 +1,468 @@
+//! Background detection of compromised agents from their write patterns.
+//!
+//! [`check_for_anomaly`](super::poisoning::check_for_anomaly) judges one
+//! write at a time. A hijacked agent may instead write unremarkable
+//! memories in an unusual way: many at once, all marked important, at a
+//! scope it never used, or at hours it is normally idle. A pass compares
+//! each agent's writes in the last `window_minutes` with its baseline:
+//!
+//! - **write burst** — more writes than `burst_factor` times the agent's
+//!   earlier rate predicts for the window;
+//! - **importance inflation** — mean importance above the
+//!   [`AgentProfile`](crate::model::agent_profile::AgentProfile) average
+//!   of its earlier writes by more than `importance_inflation`;
+//! - **scope change** — more than `rare_scope_share` of the writes at
+//!   scopes the agent used for under 5% of its earlier writes;
+//! - **off hours** — more than `off_hours_share` of the writes in UTC
+//!   hours that held under 1% of its earlier writes.
+//!
+//! The baseline is the agent's `baseline_sample` writes before the window;
+//! agents with fewer than `min_baseline_writes` of them, or fewer than
+//! `min_window_writes` writes in the window, are not judged. A flagged
+//! agent gets a [`EventType::WriteAnomaly`] event and, with the outbox on,
+//! a [`WRITE_ANOMALY_TOPIC`] message for webhooks. An ongoing anomaly
+//! raises one alert per window. With `throttle` set, the agent's
+//! `remember` calls are refused with [`Error::RateLimited`] for
+//! `throttle_minutes`, or until [`MnemoEngine::release_write_throttle`].
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, PoisonError};
+
+use chrono::{DateTime, Duration, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::event::EventType;
+use crate::model::memory::MemoryRecord;
+use crate::model::outbox::OutboxMessage;
+use crate::query::MnemoEngine;
+use crate::storage::{MemoryFilter, WriteBatch};
+
+/// Topic of the outbox message raised for a flagged agent.
+pub const WRITE_ANOMALY_TOPIC: &str = "write_anomaly";
+
+/// A scope under this share of the baseline counts as rare.
+const RARE_SCOPE_SHARE: f64 = 0.05;
+/// An hour under this share of the baseline counts as off hours.
+const RARE_HOUR_SHARE: f64 = 0.01;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WriteAnomalyPolicy {
+    /// Minutes of recent writes each pass judges.
+    pub window_minutes: u32,
+    /// Fewest writes in the window for an agent to be judged.
+    pub min_window_writes: usize,
+    /// Fewest earlier writes for an agent to have a baseline.
+    pub min_baseline_writes: usize,
+    /// Most earlier writes the baseline is drawn from, newest first.
+    pub baseline_sample: usize,
+    pub burst_factor: f64,
+    pub importance_inflation: f64,
+    pub rare_scope_share: f64,
+    pub off_hours_share: f64,
+    /// Refuse a flagged agent's writes for `throttle_minutes`.
+    pub throttle: bool,
+    pub throttle_minutes: u32,
+}
+
+impl Default for WriteAnomalyPolicy {
+    fn default() -> Self {
+        Self {
+            window_minutes: 60,
+            min_window_writes: 10,
+            min_baseline_writes: 50,
+            baseline_sample: 1000,
+            burst_factor: 5.0,
+            importance_inflation: 0.3,
+            rare_scope_share: 0.5,
+            off_hours_share: 0.5,
+            throttle: false,
+            throttle_minutes: 60,
+        }
+    }
+}
+
+impl WriteAnomalyPolicy {
+    pub fn validate(&self) -> Result<()> {
+        let field = |name: &str| format!("features.write_anomaly.{name}");
+        if self.window_minutes == 0 {
+            return Err(Error::invalid_field(field("window_minutes"), "must be > 0"));
+        }
+        if self.throttle && self.throttle_minutes == 0 {
+            return Err(Error::invalid_field(
+                field("throttle_minutes"),
+                "must be > 0 when throttle is on",
+            ));
+        }
+        if self.baseline_sample < self.min_baseline_writes {
+            return Err(Error::invalid_field(
+                field("baseline_sample"),
+                "must be at least min_baseline_writes",
+            ));
+        }
+        if !self.burst_factor.is_finite() || self.burst_factor <= 1.0 {
+            return Err(Error::invalid_field(field("burst_factor"), "must be > 1"));
+        }
+        if !self.importance_inflation.is_finite() || self.importance_inflation <= 0.0 {
+            return Err(Error::invalid_field(
+                field("importance_inflation"),
+                "must be > 0",
+            ));
+        }
+        for (name, share) in [
+            ("rare_scope_share", self.rare_scope_share),
+            ("off_hours_share", self.off_hours_share),
+        ] {
+            if !(share > 0.0 && share <= 1.0) {
+                return Err(Error::invalid_field(field(name), "must be in (0, 1]"));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WriteAnomalyKind {
+    WriteBurst,
+    ImportanceInflation,
+    ScopeChange,
+    OffHours,
+}
+
+/// One way an agent's window departed from its baseline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WriteAnomaly {
+    pub kind: WriteAnomalyKind,
+    /// The window's value: writes, mean importance, or a share of writes.
+    pub observed: f64,
+    /// What the baseline predicts for it.
+    pub baseline: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlaggedAgent {
+    pub agent_id: String,
+    pub window_writes: usize,
+    pub anomalies: Vec<WriteAnomaly>,
+    /// The alert event; `None` when this window was already alerted.
+    pub event_id: Option<Uuid>,
+    pub throttled_until: Option<String>,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WriteAnomalyReport {
+    /// Agents with enough writes in the window and before it to judge.
+    pub agents_checked: usize,
+    pub flagged: Vec<FlaggedAgent>,
+}
+
+/// A policy plus the alerts and throttles it has raised.
+pub struct WriteAnomalyDetector {
+    policy: WriteAnomalyPolicy,
+    /// Agent → when it was last alerted.
+    alerted: Mutex<HashMap<String, DateTime<Utc>>>,
+    /// Agent → when its throttle lifts.
+    throttled: Mutex<HashMap<String, DateTime<Utc>>>,
+}
+
+impl WriteAnomalyDetector {
+    pub fn new(policy: WriteAnomalyPolicy) -> Self {
+        Self {
+            policy,
+            alerted: Mutex::default(),
+            throttled: Mutex::default(),
+        }
+    }
+
+    pub fn policy(&self) -> &WriteAnomalyPolicy {
+        &self.policy
+    }
+
+    /// When `agent_id`'s throttle lifts, if it is throttled now.
+    pub fn throttled_until(&self, agent_id: &str) -> Option<DateTime<Utc>> {
+        let mut throttled = self
+            .throttled
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        match throttled.get(agent_id) {
+            Some(until) if *until > Utc::now() => Some(*until),
+            Some(_) => {
+                throttled.remove(agent_id);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Refuse a write by a throttled agent.
+    pub(crate) fn check_throttle(&self, agent_id: &str) -> Result<()> {
+        match self.throttled_until(agent_id) {
+            Some(until) => Err(Error::RateLimited {
+                message: format!(
+                    "agent {agent_id} is throttled until {} after anomalous writes",
+                    until.to_rfc3339()
+                ),
+                retry_after_secs: Some((until - Utc::now()).num_seconds().max(1) as u64),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn release(&self, agent_id: &str) -> bool {
+        self.throttled
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(agent_id)
+            .is_some()
+    }
+}
+
+fn created(record: &MemoryRecord) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&record.created_at)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Share of `window` whose `key` holds under `rare` of `history`.
+fn rare_share<K: Ord>(
+    window: &[(DateTime<Utc>, &MemoryRecord)],
+    history: &[(DateTime<Utc>, &MemoryRecord)],
+    rare: f64,
+    key: impl Fn(&(DateTime<Utc>, &MemoryRecord)) -> K,
+) -> f64 {
+    let mut counts = BTreeMap::new();
+    for entry in history {
+        *counts.entry(key(entry)).or_insert(0usize) += 1;
+    }
+    let is_rare =
+        |k: &K| (counts.get(k).copied().unwrap_or(0) as f64) < rare * history.len() as f64;
+    let hits = window.iter().filter(|entry| is_rare(&key(entry))).count();
+    hits as f64 / window.len() as f64
+}
+
+/// Judge one agent's window against its baseline.
+fn judge(
+    policy: &WriteAnomalyPolicy,
+    window_start: DateTime<Utc>,
+    window: &[(DateTime<Utc>, &MemoryRecord)],
+    history: &[(DateTime<Utc>, &MemoryRecord)],
+    profile_importance: Option<(f64, u64)>,
+) -> Vec<WriteAnomaly> {
+    let mut anomalies = Vec::new();
+
+    let oldest = history
+        .iter()
+        .map(|(t, _)| *t)
+        .min()
+        .unwrap_or(window_start);
+    let span = (window_start - oldest).num_seconds().max(1) as f64;
+    let expected = history.len() as f64 / span * (policy.window_minutes as f64 * 60.0);
+    let observed = window.len() as f64;
+    if observed > policy.burst_factor * expected.max(1.0) {
+        anomalies.push(WriteAnomaly {
+            kind: WriteAnomalyKind::WriteBurst,
+            observed,
+            baseline: expected,
+        });
+    }
+
+    let window_mean =
+        window.iter().map(|(_, r)| r.importance as f64).sum::<f64>() / window.len() as f64;
+    // The profile's running mean already counts the window; take it out.
+    let baseline_mean = match profile_importance {
+        Some((avg, total)) if total as usize > window.len() => {
+            (avg * total as f64 - window_mean * observed) / (total as f64 - observed)
+        }
+        _ => {
+            history
+                .iter()
+                .map(|(_, r)| r.importance as f64)
+                .sum::<f64>()
+                / history.len() as f64
+        }
+    };
+    if window_mean - baseline_mean > policy.importance_inflation {
+        anomalies.push(WriteAnomaly {
+            kind: WriteAnomalyKind::ImportanceInflation,
+            observed: window_mean,
+            baseline: baseline_mean,
+        });
+    }
+
+    let scope_share = rare_share(window, history, RARE_SCOPE_SHARE, |(_, r)| {
+        r.scope.to_string()
+    });
+    if scope_share > policy.rare_scope_share {
+        anomalies.push(WriteAnomaly {
+            kind: WriteAnomalyKind::ScopeChange,
+            observed: scope_share,
+            baseline: RARE_SCOPE_SHARE,
+        });
+    }
+
+    let hour_share = rare_share(window, history, RARE_HOUR_SHARE, |(t, _)| t.hour());
+    if hour_share > policy.off_hours_share {
+        anomalies.push(WriteAnomaly {
+            kind: WriteAnomalyKind::OffHours,
+            observed: hour_share,
+            baseline: RARE_HOUR_SHARE,
+        });
+    }
+    anomalies
+}
+
+pub async fn execute(engine: &MnemoEngine) -> Result<WriteAnomalyReport> {
+    let detector = engine
+        .write_anomaly
+        .as_ref()
+        .ok_or_else(|| Error::Validation("write anomaly detection is not enabled".to_string()))?;
+    let policy = detector.policy();
+    let now = Utc::now();
+    let window_start = now - Duration::minutes(policy.window_minutes as i64);
+
+    // Agents that wrote in the window, with how many writes.
+    let mut active: BTreeMap<String, usize> = BTreeMap::new();
+    let recent = engine
+        .storage
+        .list_memories_since(&window_start.to_rfc3339(), super::MAX_BATCH_QUERY_LIMIT)
+        .await?;
+    for record in &recent {
+        if created(record).is_some_and(|t| t >= window_start) {
+            *active.entry(record.agent_id.clone()).or_default() += 1;
+        }
+    }
+
+    let mut report = WriteAnomalyReport::default();
+    for (agent_id, writes) in active {
+        if writes < policy.min_window_writes {
+            continue;
+        }
+        let filter = MemoryFilter {
+            agent_id: Some(agent_id.clone()),
+            include_deleted: true,
+            ..Default::default()
+        };
+        let records = engine
+            .storage
+            .list_memories(&filter, writes + policy.baseline_sample, 0)
+            .await?;
+        let (window, history): (Vec<_>, Vec<_>) = records
+            .iter()
+            .filter_map(|r| created(r).map(|t| (t, r)))
+            .partition(|(t, _)| *t >= window_start);
+        if history.len() < policy.min_baseline_writes || window.len() < policy.min_window_writes {
+            continue;
+        }
+        report.agents_checked += 1;
+
+        let profile = engine.storage.get_agent_profile(&agent_id).await?;
+        let anomalies = judge(
+            policy,
+            window_start,
+            &window,
+            &history,
+            profile.map(|p| (p.avg_importance, p.total_memories)),
+        );
+        if anomalies.is_empty() {
+            continue;
+        }
+        report
+            .flagged
+            .push(raise(engine, detector, agent_id, window.len(), anomalies, now).await?);
+    }
+    Ok(report)
+}
+
+/// Alert on and, if the policy says so, throttle a flagged agent. An agent
+/// already alerted within the window is only reported.
+async fn raise(
+    engine: &MnemoEngine,
+    detector: &WriteAnomalyDetector,
+    agent_id: String,
+    window_writes: usize,
+    anomalies: Vec<WriteAnomaly>,
+    now: DateTime<Utc>,
+) -> Result<FlaggedAgent> {
+    let policy = detector.policy();
+    let window = Duration::minutes(policy.window_minutes as i64);
+    let already_alerted = {
+        let mut alerted = detector
+            .alerted
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        match alerted.get(&agent_id) {
+            Some(at) if now - *at < window => true,
+            _ => {
+                alerted.insert(agent_id.clone(), now);
+                false
+            }
+        }
+    };
+    let mut flagged = FlaggedAgent {
+        throttled_until: detector.throttled_until(&agent_id).map(|t| t.to_rfc3339()),
+        agent_id,
+        window_writes,
+        anomalies,
+        event_id: None,
+    };
+    if already_alerted {
+        return Ok(flagged);
+    }
+
+    if policy.throttle {
+        let until = now + Duration::minutes(policy.throttle_minutes as i64);
+        detector
+            .throttled
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(flagged.agent_id.clone(), until);
+        flagged.throttled_until = Some(until.to_rfc3339());
+    }
+    tracing::warn!(
+        agent_id = %flagged.agent_id,
+        window_writes,
+        anomalies = ?flagged.anomalies.iter().map(|a| a.kind).collect::<Vec<_>>(),
+        throttled_until = flagged.throttled_until.as_deref().unwrap_or_default(),
+        "anomalous write pattern"
+    );
+
+    let payload = serde_json::json!({
+        "window_writes": window_writes,
+        "window_minutes": policy.window_minutes,
+        "anomalies": flagged.anomalies,
+        "throttled_until": flagged.throttled_until,
+    });
+    // The event belongs to the flagged agent's stream.
+    let agent_id = flagged.agent_id.clone();
+    let event_id = crate::storage::with_agent_context(agent_id.clone(), async {
+        let event = super::event_builder::build_event(
+            engine,
+            &agent_id,
+            EventType::WriteAnomaly,
+            payload.clone(),
+            &now.to_rfc3339(),
+            None,
+        )
+        .await;
+        let event_id = event.id;
+        let mut batch = WriteBatch {
+            events: vec![event],
+            ..Default::default()
+        };
+        if engine.outbox_enabled {
+            let mut payload = payload;
+            payload["agent_id"] = serde_json::Value::String(agent_id.clone());
+            batch
+                .outbox
+                .push(OutboxMessage::new(WRITE_ANOMALY_TOPIC, payload));
+        }
+        engine.storage.commit_write(&batch).await.map(|()| event_id)
+    })
+    .await?;
+    flagged.event_id = Some(event_id);
+    Ok(flagged)
+}
@@ -0,0 +1,83 @@
+//! Per-request correlation IDs.
+//!
+//! Every transport resolves one ID per inbound request — the caller's
+//! `x-request-id` when it is usable, a fresh one otherwise — and runs the
+//! request inside [`scope`]. Engine code reads it back with [`current`]:
+//! events recorded during the request carry it, and transports attach it
+//! to their tracing span and echo it in the response, so logs and the
+//! event log of one request can be joined on a single value.
+
+use std::future::Future;
+
+use uuid::Uuid;
+
+/// Header (HTTP) and metadata key (gRPC) carrying the ID.
+pub const HEADER: &str = "x-request-id";
+
+/// Longest incoming ID that is accepted as is.
+pub const MAX_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// A fresh request ID.
+pub fn generate() -> String {
+    Uuid::now_v7().to_string()
+}
+
+/// The caller's ID when it is 1 to [`MAX_LEN`] printable ASCII characters,
+/// so it is safe to log and echo; a fresh one otherwise.
+pub fn accept(incoming: Option<&str>) -> String {
+    match incoming {
+        Some(id)
+            if !id.is_empty()
+                && id.len() <= MAX_LEN
+                && id.bytes().all(|b| b.is_ascii_graphic()) =>
+        {
+            id.to_string()
+        }
+        _ => generate(),
+    }
+}
+
+/// Run `fut` as part of the request `request_id`. Tasks spawned inside
+/// `fut` do not inherit it.
+pub async fn scope<F: Future>(request_id: String, fut: F) -> F::Output {
+    REQUEST_ID.scope(request_id, fut).await
+}
+
+/// [`scope`] for synchronous work, such as building the future a service
+/// returns.
+pub fn sync_scope<R>(request_id: String, f: impl FnOnce() -> R) -> R {
+    REQUEST_ID.sync_scope(request_id, f)
+}
+
+/// The ID set by the innermost [`scope`], if any.
+pub fn current() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_printable_ids_and_replaces_the_rest() {
+        assert_eq!(accept(Some("req-42")), "req-42");
+        assert_ne!(accept(Some("")), "");
+        assert_ne!(accept(Some("two words")), "two words");
+        assert_ne!(accept(Some("line\nbreak")), "line\nbreak");
+        assert_eq!(accept(Some(&"x".repeat(MAX_LEN))).len(), MAX_LEN);
+        assert_ne!(accept(Some(&"x".repeat(MAX_LEN + 1))).len(), MAX_LEN + 1);
+        assert_eq!(accept(None).len(), 36);
+    }
+
+    #[tokio::test]
+    async fn scope_sets_the_current_id() {
+        assert_eq!(current(), None);
+        let seen = scope("req-1".to_string(), async { current() }).await;
+        assert_eq!(seen.as_deref(), Some("req-1"));
+        assert_eq!(current(), None);
+    }
+}
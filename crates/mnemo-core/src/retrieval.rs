@@ -406,10 +406,17 @@ mod tests {
             tags: vec![],
             metadata: serde_json::Value::Null,
             score,
+            relevance: None,
             access_count: 0,
             created_at: "2026-05-17T00:00:00Z".to_string(),
             updated_at: "2026-05-17T00:00:00Z".to_string(),
             score_breakdown: None,
+            snippets: None,
+            provenance_chain: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
+            recalled_for: None,
         }
     }
 
@@ -469,6 +476,12 @@ mod tests {
             quarantined: false,
             quarantine_reason: None,
             decay_function: None,
+            pinned: false,
+            valid_from: None,
+            valid_until: None,
+            curation: None,
+            attestation: None,
+            channel: None,
         }
     }
 
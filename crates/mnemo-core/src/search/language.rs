@@ -0,0 +1,55 @@
+//! Per-memory language detection for the full-text index.
+//!
+//! Detection uses `whatlang` (trigram + script based, no model files). The
+//! detected ISO 639-3 code is stored under [`LANGUAGE_METADATA_KEY`] on the
+//! memory at write time and used by [`super::tantivy_index`] to pick a
+//! language-appropriate analyzer for both indexing and querying.
+
+/// Metadata key holding a memory's ISO 639-3 language code (e.g. `"eng"`,
+/// `"fra"`, `"cmn"`). Set on `remember` when detection is reliable and the
+/// caller did not supply one.
+pub const LANGUAGE_METADATA_KEY: &str = "language";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DetectedLanguage {
+    /// ISO 639-3 code.
+    pub code: &'static str,
+    pub confidence: f64,
+    /// `whatlang`'s reliability verdict. Short inputs (a few words) are
+    /// frequently unreliable.
+    pub reliable: bool,
+}
+
+/// Detect the dominant language of `text`. Returns `None` for empty or
+/// script-less input (numbers, punctuation).
+pub fn detect(text: &str) -> Option<DetectedLanguage> {
+    let info = whatlang::detect(text)?;
+    Some(DetectedLanguage {
+        code: info.lang().code(),
+        confidence: info.confidence(),
+        reliable: info.is_reliable(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_common_languages() {
+        let en =
+            detect("The user prefers to receive the weekly report on Monday mornings").unwrap();
+        assert_eq!(en.code, "eng");
+        let fr = detect("L'utilisateur préfère recevoir le rapport hebdomadaire le lundi matin")
+            .unwrap();
+        assert_eq!(fr.code, "fra");
+        let ja = detect("ユーザーは月曜日の朝に週報を受け取ることを好みます").unwrap();
+        assert_eq!(ja.code, "jpn");
+    }
+
+    #[test]
+    fn no_language_for_scriptless_input() {
+        assert!(detect("").is_none());
+        assert!(detect("12345 !!!").is_none());
+    }
+}
@@ -1,12 +1,106 @@
+pub mod language;
 pub mod tantivy_index;
 
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
 use crate::error::Result;
 use uuid::Uuid;
 
+/// How a full-text query string is interpreted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextQueryMode {
+    /// BM25 over the query's terms, OR-ed together. Double-quoted
+    /// sub-strings are matched as phrases.
+    #[default]
+    Terms,
+    /// The whole query must appear as a contiguous phrase.
+    Phrase,
+    /// Each term matches within a Levenshtein edit distance (clamped to
+    /// 1..=2), so typos like `recieve` still hit `receive`.
+    Fuzzy { distance: u8 },
+}
+
+impl std::str::FromStr for TextQueryMode {
+    type Err = crate::error::Error;
+
+    /// Parses `terms`, `phrase`, `fuzzy` (distance 1) or `fuzzy:N`.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "terms" => Ok(TextQueryMode::Terms),
+            "phrase" => Ok(TextQueryMode::Phrase),
+            "fuzzy" => Ok(TextQueryMode::Fuzzy { distance: 1 }),
+            other => other
+                .strip_prefix("fuzzy:")
+                .and_then(|d| d.parse::<u8>().ok())
+                .filter(|d| (1..=2).contains(d))
+                .map(|distance| TextQueryMode::Fuzzy { distance })
+                .ok_or_else(|| {
+                    crate::error::Error::Validation(format!(
+                        "invalid text_query_mode '{other}': expected terms, phrase, fuzzy, fuzzy:1 or fuzzy:2"
+                    ))
+                }),
+        }
+    }
+}
+
+/// Options for [`FullTextIndex::search_with_options`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TextSearchOptions {
+    #[serde(default)]
+    pub mode: TextQueryMode,
+    /// Score multipliers keyed by index field name (`content`, or a
+    /// per-language field such as `content_eng` / `content_cjk`). Unknown
+    /// names are ignored; unlisted fields keep a boost of 1.0.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub field_boosts: BTreeMap<String, f32>,
+}
+
+/// A highlighted excerpt of a document, explaining why it matched.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextSnippet {
+    pub fragment: String,
+    /// Byte ranges `(start, end)` into `fragment` covering matched terms.
+    pub highlights: Vec<(usize, usize)>,
+}
+
 pub trait FullTextIndex: Send + Sync {
     fn add(&self, id: Uuid, content: &str) -> Result<()>;
+    /// Index `content` with a known ISO 639-3 language code (see
+    /// [`language::LANGUAGE_METADATA_KEY`]). Backends without per-language
+    /// analyzers ignore the hint.
+    fn add_with_language(&self, id: Uuid, content: &str, language: Option<&str>) -> Result<()> {
+        let _ = language;
+        self.add(id, content)
+    }
     fn remove(&self, id: Uuid) -> Result<()>;
     fn search(&self, query: &str, limit: usize) -> Result<Vec<(Uuid, f32)>>;
+    /// [`search`](Self::search) with phrase / fuzzy modes and field boosts.
+    /// Backends without support fall back to a plain term search.
+    fn search_with_options(
+        &self,
+        query: &str,
+        limit: usize,
+        options: &TextSearchOptions,
+    ) -> Result<Vec<(Uuid, f32)>> {
+        let _ = options;
+        self.search(query, limit)
+    }
+    /// Extract the best fragment of `text` (at most `max_chars` long) for
+    /// `query`, with matched terms marked. `None` when nothing in `text`
+    /// matches or the backend cannot highlight.
+    fn highlight(
+        &self,
+        query: &str,
+        text: &str,
+        max_chars: usize,
+        options: &TextSearchOptions,
+    ) -> Result<Option<TextSnippet>> {
+        let _ = (query, text, max_chars, options);
+        Ok(None)
+    }
     fn commit(&self) -> Result<()>;
     fn save(&self) -> Result<()>;
     fn len(&self) -> usize;
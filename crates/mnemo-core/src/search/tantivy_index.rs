@@ -2,30 +2,116 @@ use std::path::Path;
 use std::sync::Mutex;
 
 use tantivy::collector::TopDocs;
-use tantivy::query::QueryParser;
+use tantivy::query::{Query, QueryParser};
 use tantivy::schema::Value;
-use tantivy::schema::{STORED, STRING, Schema, TEXT};
+use tantivy::schema::{
+    IndexRecordOption, STORED, STRING, Schema, TEXT, TextFieldIndexing, TextOptions,
+};
+use tantivy::snippet::SnippetGenerator;
+use tantivy::tokenizer::{
+    AsciiFoldingFilter, Language, LowerCaser, NgramTokenizer, RemoveLongFilter, SimpleTokenizer,
+    Stemmer, TextAnalyzer,
+};
 use tantivy::{Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument};
 
 use crate::error::{Error, Result};
-use crate::search::FullTextIndex;
+use crate::search::language;
+use crate::search::{FullTextIndex, TextQueryMode, TextSearchOptions, TextSnippet};
 use uuid::Uuid;
 
+/// Per-language content fields. Each entry is the field name (also used as
+/// the registered tokenizer name) and the ISO 639-3 codes routed to it.
+/// Every document is always indexed into the language-neutral `content`
+/// field too; the language field is added on top so queries in that
+/// language get stemming, accent folding, or CJK bigrams.
+const LANGUAGE_FIELDS: &[(&str, &[&str])] = &[
+    ("content_cjk", &["cmn", "jpn", "kor"]),
+    ("content_eng", &["eng"]),
+    ("content_fra", &["fra"]),
+    ("content_deu", &["deu"]),
+    ("content_spa", &["spa"]),
+    ("content_ita", &["ita"]),
+    ("content_por", &["por"]),
+    ("content_nld", &["nld"]),
+    ("content_swe", &["swe"]),
+    ("content_rus", &["rus"]),
+    ("content_tur", &["tur"]),
+];
+
 pub struct TantivyFullTextIndex {
     index: Index,
     writer: Mutex<IndexWriter>,
     reader: IndexReader,
     id_field: tantivy::schema::Field,
     content_field: tantivy::schema::Field,
+    /// Language fields present in this index's schema. Indexes created
+    /// before multilingual support only have `content`, so this is empty
+    /// for them and every document falls back to the default analyzer.
+    language_fields: Vec<(&'static str, tantivy::schema::Field)>,
 }
 
 fn build_schema() -> (Schema, tantivy::schema::Field, tantivy::schema::Field) {
     let mut schema_builder = Schema::builder();
     let id_field = schema_builder.add_text_field("id", STRING | STORED);
     let content_field = schema_builder.add_text_field("content", TEXT);
+    for (name, _) in LANGUAGE_FIELDS {
+        let indexing = TextFieldIndexing::default()
+            .set_tokenizer(name)
+            .set_index_option(IndexRecordOption::WithFreqsAndPositions);
+        schema_builder.add_text_field(name, TextOptions::default().set_indexing_options(indexing));
+    }
     (schema_builder.build(), id_field, content_field)
 }
 
+fn stemmed(language: Language) -> TextAnalyzer {
+    TextAnalyzer::builder(SimpleTokenizer::default())
+        .filter(RemoveLongFilter::limit(40))
+        .filter(LowerCaser)
+        .filter(AsciiFoldingFilter)
+        .filter(Stemmer::new(language))
+        .build()
+}
+
+fn analyzer_for(field_name: &str) -> Result<TextAnalyzer> {
+    let analyzer = match field_name {
+        // CJK scripts have no whitespace word boundaries; unigrams plus
+        // bigrams give usable recall without a dictionary segmenter.
+        "content_cjk" => TextAnalyzer::builder(
+            NgramTokenizer::new(1, 2, false).map_err(|e| Error::Index(e.to_string()))?,
+        )
+        .filter(LowerCaser)
+        .build(),
+        "content_eng" => stemmed(Language::English),
+        "content_fra" => stemmed(Language::French),
+        "content_deu" => stemmed(Language::German),
+        "content_spa" => stemmed(Language::Spanish),
+        "content_ita" => stemmed(Language::Italian),
+        "content_por" => stemmed(Language::Portuguese),
+        "content_nld" => stemmed(Language::Dutch),
+        "content_swe" => stemmed(Language::Swedish),
+        "content_rus" => stemmed(Language::Russian),
+        "content_tur" => stemmed(Language::Turkish),
+        other => {
+            return Err(Error::Index(format!("no analyzer for field {other}")));
+        }
+    };
+    Ok(analyzer)
+}
+
+/// Register the per-language tokenizers on `index` and resolve which
+/// language fields its schema actually has.
+fn register_language_fields(index: &Index) -> Result<Vec<(&'static str, tantivy::schema::Field)>> {
+    let schema = index.schema();
+    let mut fields = Vec::new();
+    for (name, _) in LANGUAGE_FIELDS {
+        if let Ok(field) = schema.get_field(name) {
+            index.tokenizers().register(name, analyzer_for(name)?);
+            fields.push((*name, field));
+        }
+    }
+    Ok(fields)
+}
+
 impl TantivyFullTextIndex {
     pub fn new(path: &Path) -> Result<Self> {
         let (schema, id_field, content_field) = build_schema();
@@ -41,6 +127,12 @@ impl TantivyFullTextIndex {
             Index::create(dir, schema, tantivy::IndexSettings::default())
                 .map_err(|e| Error::Index(e.to_string()))?
         };
+        let language_fields = register_language_fields(&index)?;
+        if language_fields.is_empty() {
+            tracing::info!(
+                "full-text index predates multilingual analyzers; rebuild it to enable per-language tokenization"
+            );
+        }
 
         let writer = index
             .writer(50_000_000) // 50MB heap
@@ -58,6 +150,7 @@ impl TantivyFullTextIndex {
             reader,
             id_field,
             content_field,
+            language_fields,
         })
     }
 
@@ -65,6 +158,7 @@ impl TantivyFullTextIndex {
         let (schema, id_field, content_field) = build_schema();
 
         let index = Index::create_in_ram(schema);
+        let language_fields = register_language_fields(&index)?;
 
         let writer = index
             .writer(50_000_000)
@@ -82,12 +176,84 @@ impl TantivyFullTextIndex {
             reader,
             id_field,
             content_field,
+            language_fields,
         })
     }
+
+    fn language_field(&self, code: &str) -> Option<tantivy::schema::Field> {
+        let name = LANGUAGE_FIELDS
+            .iter()
+            .find(|(_, codes)| codes.contains(&code))
+            .map(|(name, _)| *name)?;
+        self.language_fields
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, field)| *field)
+    }
+
+    fn cjk_field(&self) -> Option<tantivy::schema::Field> {
+        self.language_field("cmn")
+    }
+
+    /// Fields a query is parsed against: `content` plus the field for the
+    /// query's detected language, or every language field when detection
+    /// is unreliable (short queries usually are).
+    fn query_fields(&self, query: &str) -> Vec<tantivy::schema::Field> {
+        let mut fields = vec![self.content_field];
+        match language::detect(query)
+            .filter(|d| d.reliable)
+            .and_then(|d| self.language_field(d.code))
+        {
+            Some(field) => fields.push(field),
+            None => fields.extend(self.language_fields.iter().map(|(_, f)| *f)),
+        }
+        fields
+    }
+
+    fn build_query(&self, query: &str, options: &TextSearchOptions) -> Result<Box<dyn Query>> {
+        let fields = self.query_fields(query);
+        let mut query_parser = QueryParser::for_index(&self.index, fields.clone());
+
+        let schema = self.index.schema();
+        for (name, boost) in &options.field_boosts {
+            if let Ok(field) = schema.get_field(name)
+                && fields.contains(&field)
+            {
+                query_parser.set_field_boost(field, *boost);
+            }
+        }
+
+        let query_text = match options.mode {
+            TextQueryMode::Terms => query.to_string(),
+            TextQueryMode::Phrase => format!("\"{}\"", query.replace('"', " ")),
+            TextQueryMode::Fuzzy { distance } => {
+                let distance = distance.clamp(1, 2);
+                // Edit distance over CJK bigrams is meaningless; keep that
+                // field exact.
+                for field in fields.iter().filter(|f| Some(**f) != self.cjk_field()) {
+                    query_parser.set_field_fuzzy(*field, false, distance, true);
+                }
+                query.to_string()
+            }
+        };
+
+        query_parser
+            .parse_query(&query_text)
+            .map_err(|e| Error::Index(e.to_string()))
+    }
 }
 
 impl FullTextIndex for TantivyFullTextIndex {
     fn add(&self, id: Uuid, content: &str) -> Result<()> {
+        self.add_with_language(id, content, None)
+    }
+
+    fn add_with_language(&self, id: Uuid, content: &str, language: Option<&str>) -> Result<()> {
+        let language_field = match language {
+            Some(code) => self.language_field(code),
+            None => language::detect(content).and_then(|d| self.language_field(d.code)),
+        };
+
         let writer = self
             .writer
             .lock()
@@ -100,6 +266,9 @@ impl FullTextIndex for TantivyFullTextIndex {
         let mut doc = TantivyDocument::default();
         doc.add_text(self.id_field, id.to_string());
         doc.add_text(self.content_field, content);
+        if let Some(field) = language_field {
+            doc.add_text(field, content);
+        }
         writer
             .add_document(doc)
             .map_err(|e| Error::Index(e.to_string()))?;
@@ -117,11 +286,17 @@ impl FullTextIndex for TantivyFullTextIndex {
     }
 
     fn search(&self, query: &str, limit: usize) -> Result<Vec<(Uuid, f32)>> {
+        self.search_with_options(query, limit, &TextSearchOptions::default())
+    }
+
+    fn search_with_options(
+        &self,
+        query: &str,
+        limit: usize,
+        options: &TextSearchOptions,
+    ) -> Result<Vec<(Uuid, f32)>> {
         let searcher = self.reader.searcher();
-        let query_parser = QueryParser::for_index(&self.index, vec![self.content_field]);
-        let parsed_query = query_parser
-            .parse_query(query)
-            .map_err(|e| Error::Index(e.to_string()))?;
+        let parsed_query = self.build_query(query, options)?;
 
         // tantivy 0.26 made TopDocs ordering explicit. 0.25's
         // `TopDocs::with_limit(limit)` implicitly ordered by BM25
@@ -145,6 +320,41 @@ impl FullTextIndex for TantivyFullTextIndex {
         Ok(results)
     }
 
+    fn highlight(
+        &self,
+        query: &str,
+        text: &str,
+        max_chars: usize,
+        options: &TextSearchOptions,
+    ) -> Result<Option<TextSnippet>> {
+        let searcher = self.reader.searcher();
+        let parsed_query = self.build_query(query, options)?;
+
+        // Try the language-neutral field first, then whichever language
+        // field the text itself would have been indexed under.
+        let mut fields = vec![self.content_field];
+        if let Some(field) = language::detect(text).and_then(|d| self.language_field(d.code)) {
+            fields.push(field);
+        }
+        for field in fields {
+            let mut generator = SnippetGenerator::create(&searcher, &*parsed_query, field)
+                .map_err(|e| Error::Index(e.to_string()))?;
+            generator.set_max_num_chars(max_chars);
+            let snippet = generator.snippet(text);
+            if !snippet.highlighted().is_empty() {
+                return Ok(Some(TextSnippet {
+                    fragment: snippet.fragment().to_string(),
+                    highlights: snippet
+                        .highlighted()
+                        .iter()
+                        .map(|r| (r.start, r.end))
+                        .collect(),
+                }));
+            }
+        }
+        Ok(None)
+    }
+
     fn commit(&self) -> Result<()> {
         let mut writer = self
             .writer
@@ -216,6 +426,111 @@ mod tests {
         assert!(results.is_empty());
     }
 
+    #[test]
+    fn test_tantivy_cjk_and_stemmed_search() {
+        let index = TantivyFullTextIndex::open_in_memory().unwrap();
+
+        let ja = Uuid::now_v7();
+        let fr = Uuid::now_v7();
+        index.add(ja, "ユーザーは東京の本社で働いています").unwrap();
+        index
+            .add(
+                fr,
+                "L'utilisateur préfère les réunions hebdomadaires du lundi",
+            )
+            .unwrap();
+        index.commit().unwrap();
+
+        // A CJK substring is not a whitespace token, so only the bigram
+        // field can match it.
+        let results = index.search("東京", 10).unwrap();
+        assert_eq!(results.first().map(|r| r.0), Some(ja));
+
+        // Accent-folded, stemmed match against the French field.
+        let results = index.search("reunion", 10).unwrap();
+        assert_eq!(results.first().map(|r| r.0), Some(fr));
+
+        // A language hint overrides detection (a single word is too short
+        // to detect reliably).
+        let hinted = Uuid::now_v7();
+        index
+            .add_with_language(hinted, "réunions", Some("fra"))
+            .unwrap();
+        index.commit().unwrap();
+        assert!(
+            index
+                .search("reunion", 10)
+                .unwrap()
+                .iter()
+                .any(|r| r.0 == hinted)
+        );
+    }
+
+    #[test]
+    fn test_tantivy_phrase_and_fuzzy_modes() {
+        let index = TantivyFullTextIndex::open_in_memory().unwrap();
+
+        let ordered = Uuid::now_v7();
+        let shuffled = Uuid::now_v7();
+        index
+            .add(ordered, "the user wants to receive the weekly report")
+            .unwrap();
+        index
+            .add(shuffled, "the weekly digest report wants a new user")
+            .unwrap();
+        index.commit().unwrap();
+
+        let phrase = TextSearchOptions {
+            mode: TextQueryMode::Phrase,
+            ..Default::default()
+        };
+        let results = index
+            .search_with_options("weekly report", 10, &phrase)
+            .unwrap();
+        assert_eq!(
+            results.iter().map(|r| r.0).collect::<Vec<_>>(),
+            vec![ordered]
+        );
+
+        // Quoted sub-strings are phrases in the default mode too.
+        let results = index.search("\"digest report\"", 10).unwrap();
+        assert_eq!(results.first().map(|r| r.0), Some(shuffled));
+
+        assert!(index.search("recieve", 10).unwrap().is_empty());
+        let fuzzy = TextSearchOptions {
+            mode: TextQueryMode::Fuzzy { distance: 2 },
+            ..Default::default()
+        };
+        let results = index.search_with_options("recieve", 10, &fuzzy).unwrap();
+        assert_eq!(results.first().map(|r| r.0), Some(ordered));
+    }
+
+    #[test]
+    fn test_tantivy_highlight() {
+        let index = TantivyFullTextIndex::open_in_memory().unwrap();
+        let text = "The user prefers dark mode for all applications";
+        index.add(Uuid::now_v7(), text).unwrap();
+        index.commit().unwrap();
+
+        let snippet = index
+            .highlight("dark mode", text, 150, &TextSearchOptions::default())
+            .unwrap()
+            .unwrap();
+        let marked: Vec<&str> = snippet
+            .highlights
+            .iter()
+            .map(|(s, e)| &snippet.fragment[*s..*e])
+            .collect();
+        assert_eq!(marked, vec!["dark", "mode"]);
+
+        assert!(
+            index
+                .highlight("kubernetes", text, 150, &TextSearchOptions::default())
+                .unwrap()
+                .is_none()
+        );
+    }
+
     #[test]
     fn test_tantivy_save_and_load() {
         let dir = std::env::temp_dir().join(format!("tantivy_test_{}", Uuid::now_v7()));
@@ -10,7 +10,9 @@
 //! An [`InMemoryColdStorage`] implementation is provided for testing without
 //! requiring real S3 credentials or network access.
 //!
-//! S3 keys follow the format: `{prefix}/{agent_id}/{memory_id}.json`
+//! S3 keys follow the format: `{prefix}/{agent_id}/{memory_id}.json`.
+//! Other archives, such as event segments, are stored as opaque objects
+//! under `{prefix}/{key}`.
 //!
 //! # Example
 //!
@@ -99,6 +101,15 @@ pub trait ColdStorage: Send + Sync {
 
     /// Check if a memory is archived.
     async fn is_archived(&self, memory_id: Uuid) -> Result<bool>;
+
+    /// Store an opaque object under `{prefix}/{key}`, replacing any object
+    /// already there.
+    async fn put_object(&self, key: &str, body: Vec<u8>) -> Result<()>;
+
+    /// Read an object written by [`ColdStorage::put_object`].
+    ///
+    /// Returns an error if no object is stored under `key`.
+    async fn get_object(&self, key: &str) -> Result<Vec<u8>>;
 }
 
 /// Entry stored in the in-memory cold storage backend.
@@ -126,6 +137,7 @@ struct ArchivedEntry {
 pub struct InMemoryColdStorage {
     config: ColdStorageConfig,
     store: Mutex<HashMap<Uuid, ArchivedEntry>>,
+    objects: Mutex<HashMap<String, Vec<u8>>>,
 }
 
 impl InMemoryColdStorage {
@@ -134,6 +146,7 @@ impl InMemoryColdStorage {
         Self {
             config,
             store: Mutex::new(HashMap::new()),
+            objects: Mutex::new(HashMap::new()),
         }
     }
 
@@ -220,6 +233,23 @@ impl ColdStorage for InMemoryColdStorage {
 
         Ok(guard.contains_key(&memory_id))
     }
+
+    async fn put_object(&self, key: &str, body: Vec<u8>) -> Result<()> {
+        self.objects
+            .lock()
+            .map_err(|e| Error::Internal(format!("lock poisoned: {e}")))?
+            .insert(key.to_string(), body);
+        Ok(())
+    }
+
+    async fn get_object(&self, key: &str) -> Result<Vec<u8>> {
+        self.objects
+            .lock()
+            .map_err(|e| Error::Internal(format!("lock poisoned: {e}")))?
+            .get(key)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("cold storage object {key} not found")))
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -482,6 +512,40 @@ impl ColdStorage for S3ColdStorage {
             .filter_map(|obj| obj.key())
             .any(|k| k.ends_with(&target_suffix)))
     }
+
+    async fn put_object(&self, key: &str, body: Vec<u8>) -> Result<()> {
+        self.client
+            .put_object()
+            .bucket(&self.config.bucket)
+            .key(format!("{}{key}", self.bare_prefix()))
+            .body(aws_sdk_s3::primitives::ByteStream::from(body))
+            .send()
+            .await
+            .map_err(|e| Error::Storage(format!("S3 put_object failed: {e}")))?;
+        Ok(())
+    }
+
+    async fn get_object(&self, key: &str) -> Result<Vec<u8>> {
+        let resp = self
+            .client
+            .get_object()
+            .bucket(&self.config.bucket)
+            .key(format!("{}{key}", self.bare_prefix()))
+            .send()
+            .await
+            .map_err(|e| match e.as_service_error() {
+                Some(err) if err.is_no_such_key() => {
+                    Error::NotFound(format!("cold storage object {key} not found in S3"))
+                }
+                _ => Error::Storage(format!("S3 get_object failed: {e}")),
+            })?;
+        let body = resp
+            .body
+            .collect()
+            .await
+            .map_err(|e| Error::Storage(format!("S3 body collect failed: {e}")))?;
+        Ok(body.into_bytes().to_vec())
+    }
 }
 
 #[cfg(test)]
@@ -529,6 +593,12 @@ mod tests {
             quarantined: false,
             quarantine_reason: None,
             decay_function: None,
+            pinned: false,
+            valid_from: None,
+            valid_until: None,
+            curation: None,
+            attestation: None,
+            channel: None,
         }
     }
 
@@ -630,4 +700,16 @@ mod tests {
         storage.archive(&record).await.unwrap();
         assert!(storage.is_archived(id).await.unwrap());
     }
+
+    #[tokio::test]
+    async fn test_put_and_get_object() {
+        let storage = InMemoryColdStorage::new(sample_config());
+        let key = "events/agent-1/segment.parquet";
+        assert!(storage.get_object(key).await.is_err());
+
+        storage.put_object(key, vec![1, 2, 3]).await.unwrap();
+        assert_eq!(storage.get_object(key).await.unwrap(), [1, 2, 3]);
+        // Objects live apart from archived memories.
+        assert!(storage.list_archived(None, 10).await.unwrap().is_empty());
+    }
 }
@@ -0,0 +1,1991 @@
+//! Behavioural conformance suite for [`StorageBackend`] implementations.
+//!
+//! Every backend must pass the same checks, so the engine behaves the same
+//! whichever one it runs on. Each check is a public function taking the
+//! backend under test; [`run_all`] runs them in order, and
+//! [`storage_conformance!`](crate::storage_conformance) generates one
+//! `#[tokio::test]` per check for backends that are cheap to create:
+//!
+//! ```ignore
+//! mod duckdb {
+//!     mnemo_core::storage_conformance!(
+//!         mnemo_core::storage::duckdb::DuckDbStorage::open_in_memory().unwrap()
+//!     );
+//! }
+//! ```
+//!
+//! Checks key their rows by fresh agent names, thread ids and channel
+//! names, so they never see each other's data and can share one database.
+//! The outbox, access-log and expiry checks act on every row; run them
+//! against a database nothing else writes to.
+//!
+//! Memories are stored without embeddings, so the suite runs against a
+//! backend of any vector dimension.
+
+use uuid::Uuid;
+
+use super::{MemoryColumns, MemoryFilter, StorageBackend, WriteBatch};
+use crate::error::Error;
+use crate::model::access_log::{AccessOperation, MemoryAccess};
+use crate::model::access_request::{AccessRequest, AccessRequestStatus};
+use crate::model::acl::{Acl, Permission, PrincipalType};
+use crate::model::agent_profile::{AgentProfile, RetrievalDefaults};
+use crate::model::chain_checkpoint::ChainCheckpoint;
+use crate::model::channel::{Channel, ChannelMember};
+use crate::model::checkpoint::Checkpoint;
+use crate::model::conflict::{ConflictRecord, ConflictStatus};
+use crate::model::delegation::{Delegation, DelegationScope};
+use crate::model::embedding_baseline::EmbeddingBaseline;
+use crate::model::event::{AgentEvent, EventType};
+use crate::model::event_archive::EventArchiveSegment;
+use crate::model::memory::{MemoryRecord, MemoryType, Scope};
+use crate::model::outbox::OutboxMessage;
+use crate::model::pseudonym::PseudonymEntry;
+use crate::model::recall_profile::{RecallProfile, RecallProfileSettings};
+use crate::model::relation::Relation;
+use crate::model::share_link::RevokedShareLink;
+
+/// Long past and far future, in the RFC 3339 form the engine writes.
+const PAST: &str = "2000-01-01T00:00:00+00:00";
+const FUTURE: &str = "9999-01-01T00:00:00+00:00";
+
+/// Generates one `#[tokio::test]` per conformance check, each against a
+/// fresh backend built by `$make`. Invoke it inside a module per backend.
+#[macro_export]
+macro_rules! storage_conformance {
+    ($make:expr) => {
+        $crate::storage_conformance!(@checks $make;
+            memory_round_trip,
+            memory_updates,
+            memory_deletes,
+            memory_filters,
+            permissions,
+            delegations,
+            relations,
+            memory_chains,
+            chain_checkpoints,
+            events,
+            event_archives,
+            sync_support,
+            share_links,
+            pseudonyms,
+            agent_profiles,
+            conflicts,
+            access_requests,
+            channels,
+            content_stats,
+            growth,
+            recall_profiles,
+            access_log,
+            commit_write,
+            outbox,
+            checkpoints,
+            maintenance,
+        );
+    };
+    (@checks $make:expr; $($check:ident),* $(,)?) => {
+        $(
+            #[tokio::test]
+            async fn $check() {
+                let storage = $make;
+                $crate::storage::conformance::$check(&storage).await;
+            }
+        )*
+    };
+}
+
+/// Run every check against `storage`, in the order
+/// [`storage_conformance!`](crate::storage_conformance) lists them.
+pub async fn run_all(storage: &dyn StorageBackend) {
+    memory_round_trip(storage).await;
+    memory_updates(storage).await;
+    memory_deletes(storage).await;
+    memory_filters(storage).await;
+    permissions(storage).await;
+    delegations(storage).await;
+    relations(storage).await;
+    memory_chains(storage).await;
+    chain_checkpoints(storage).await;
+    events(storage).await;
+    event_archives(storage).await;
+    sync_support(storage).await;
+    share_links(storage).await;
+    pseudonyms(storage).await;
+    agent_profiles(storage).await;
+    conflicts(storage).await;
+    access_requests(storage).await;
+    channels(storage).await;
+    content_stats(storage).await;
+    growth(storage).await;
+    recall_profiles(storage).await;
+    access_log(storage).await;
+    commit_write(storage).await;
+    outbox(storage).await;
+    checkpoints(storage).await;
+    maintenance(storage).await;
+}
+
+fn unique(prefix: &str) -> String {
+    format!("{prefix}-{}", Uuid::now_v7().simple())
+}
+
+/// `2026-01-01T00:00:{second}+00:00`.
+fn at(second: u32) -> String {
+    format!("2026-01-01T00:00:{second:02}+00:00")
+}
+
+fn memory(agent_id: &str, content: &str, created_at: &str) -> MemoryRecord {
+    let mut record = MemoryRecord::new(agent_id.to_string(), content.to_string());
+    record.created_at = created_at.to_string();
+    record.updated_at = created_at.to_string();
+    record
+}
+
+fn event(agent_id: &str, thread_id: Option<&str>, clock: i64) -> AgentEvent {
+    let mut event = AgentEvent::new(
+        agent_id.to_string(),
+        EventType::MemoryWrite,
+        serde_json::json!({ "clock": clock }),
+        at(clock as u32),
+        vec![clock as u8; 32],
+    );
+    event.thread_id = thread_id.map(str::to_string);
+    event.logical_clock = clock;
+    event
+}
+
+fn acl(memory_id: Uuid, principal_id: &str, permission: Permission) -> Acl {
+    Acl {
+        id: Uuid::now_v7(),
+        memory_id,
+        principal_type: PrincipalType::Agent,
+        principal_id: principal_id.to_string(),
+        permission,
+        granted_by: "conformance".to_string(),
+        created_at: at(0),
+        expires_at: None,
+    }
+}
+
+fn delegation(delegator: &str, delegate: &str, scope: DelegationScope) -> Delegation {
+    Delegation {
+        id: Uuid::now_v7(),
+        delegator_id: delegator.to_string(),
+        delegate_id: delegate.to_string(),
+        permission: Permission::Write,
+        scope,
+        max_depth: 1,
+        current_depth: 0,
+        parent_delegation_id: None,
+        created_at: at(0),
+        expires_at: None,
+        revoked_at: None,
+    }
+}
+
+fn ids(records: &[MemoryRecord]) -> Vec<Uuid> {
+    records.iter().map(|r| r.id).collect()
+}
+
+/// Insert, read back and batch-read memories.
+pub async fn memory_round_trip(storage: &dyn StorageBackend) {
+    let agent = unique("agent");
+    let mut record = memory(&agent, "the deploy window is friday", &at(1));
+    record.memory_type = MemoryType::Semantic;
+    record.scope = Scope::Shared;
+    record.importance = 0.75;
+    record.tags = vec!["deploy".to_string(), "ops".to_string()];
+    record.metadata = serde_json::json!({ "source": "standup", "n": 3 });
+    record.thread_id = Some(unique("thread"));
+    record.org_id = Some(unique("org"));
+    record.source_id = Some("msg-1".to_string());
+    record.prev_hash = Some(vec![7; 32]);
+    record.expires_at = Some(FUTURE.to_string());
+    record.decay_rate = Some(0.25);
+    record.created_by = Some("user-1".to_string());
+    record.pinned = true;
+    record.valid_from = Some(at(0));
+    storage.insert_memory(&record).await.unwrap();
+
+    assert_eq!(
+        storage.get_memory(record.id).await.unwrap(),
+        Some(record.clone())
+    );
+    assert_eq!(storage.get_memory(Uuid::now_v7()).await.unwrap(), None);
+    assert!(
+        storage.insert_memory(&record).await.is_err(),
+        "a second insert with the same id must fail"
+    );
+
+    let other = memory(&agent, "the freeze starts monday", &at(2));
+    storage.insert_memory(&other).await.unwrap();
+    let mut batch = storage
+        .get_memories(
+            &[record.id, other.id, record.id, Uuid::now_v7()],
+            MemoryColumns::ALL,
+        )
+        .await
+        .unwrap();
+    batch.sort_by_key(|r| r.id);
+    assert_eq!(ids(&batch), vec![record.id, other.id]);
+    assert_eq!(batch[0].content, record.content);
+    assert_eq!(batch[0].metadata, record.metadata);
+    assert_eq!(batch[0].tags, record.tags);
+
+    let narrow = storage
+        .get_memories(
+            &[record.id],
+            MemoryColumns {
+                content: false,
+                metadata: false,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(narrow.len(), 1);
+    assert_eq!(narrow[0].content, "");
+    assert_eq!(narrow[0].metadata, serde_json::json!({}));
+    assert_eq!(narrow[0].embedding, None);
+    assert_eq!(narrow[0].content_hash, record.content_hash);
+
+    assert!(
+        storage
+            .get_memories(&[], MemoryColumns::ALL)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+/// Overwrites, optimistic updates, upserts and access counting.
+pub async fn memory_updates(storage: &dyn StorageBackend) {
+    let agent = unique("agent");
+    let record = memory(&agent, "first draft", &at(1));
+    storage.insert_memory(&record).await.unwrap();
+
+    let mut updated = record.clone();
+    updated.content = "second draft".to_string();
+    updated.tags = vec!["draft".to_string()];
+    updated.updated_at = at(2);
+    updated.version = 2;
+    storage.update_memory(&updated).await.unwrap();
+    assert_eq!(
+        storage.get_memory(record.id).await.unwrap(),
+        Some(updated.clone())
+    );
+
+    // `created_at` is fixed at insert.
+    let mut backdated = updated.clone();
+    backdated.created_at = PAST.to_string();
+    storage.update_memory(&backdated).await.unwrap();
+    let stored = storage.get_memory(record.id).await.unwrap().unwrap();
+    assert_eq!(stored.created_at, record.created_at);
+
+    let missing = memory(&agent, "never inserted", &at(1));
+    assert!(matches!(
+        storage.update_memory(&missing).await,
+        Err(Error::NotFound(_))
+    ));
+
+    let mut third = updated.clone();
+    third.content = "third draft".to_string();
+    third.version = 3;
+    assert!(!storage.update_memory_if_version(&third, 1).await.unwrap());
+    assert_eq!(
+        storage
+            .get_memory(record.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .content,
+        "second draft"
+    );
+    assert!(storage.update_memory_if_version(&third, 2).await.unwrap());
+    assert_eq!(
+        storage
+            .get_memory(record.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .version,
+        3
+    );
+    assert!(!storage.update_memory_if_version(&missing, 1).await.unwrap());
+    assert_eq!(storage.get_memory(missing.id).await.unwrap(), None);
+
+    let upserted = memory(&agent, "upserted", &at(3));
+    storage.upsert_memory(&upserted).await.unwrap();
+    let mut again = upserted.clone();
+    again.content = "upserted twice".to_string();
+    storage.upsert_memory(&again).await.unwrap();
+    assert_eq!(storage.get_memory(upserted.id).await.unwrap(), Some(again));
+
+    storage.touch_memory(record.id).await.unwrap();
+    storage.touch_memory(record.id).await.unwrap();
+    let touched = storage.get_memory(record.id).await.unwrap().unwrap();
+    assert_eq!(touched.access_count, 2);
+    assert!(touched.last_accessed_at.is_some());
+    storage.touch_memory(Uuid::now_v7()).await.unwrap();
+}
+
+/// Soft deletes keep the row; hard deletes remove it.
+pub async fn memory_deletes(storage: &dyn StorageBackend) {
+    let agent = unique("agent");
+    let record = memory(&agent, "to be forgotten", &at(1));
+    storage.insert_memory(&record).await.unwrap();
+
+    storage.soft_delete_memory(record.id).await.unwrap();
+    let deleted = storage.get_memory(record.id).await.unwrap().unwrap();
+    assert!(deleted.deleted_at.is_some());
+    assert!(matches!(
+        storage.soft_delete_memory(record.id).await,
+        Err(Error::NotFound(_))
+    ));
+    assert!(matches!(
+        storage.soft_delete_memory(Uuid::now_v7()).await,
+        Err(Error::NotFound(_))
+    ));
+    assert_eq!(
+        storage
+            .get_memories(&[record.id], MemoryColumns::ALL)
+            .await
+            .unwrap()
+            .len(),
+        1,
+        "get_memories returns deleted memories"
+    );
+
+    storage
+        .insert_acl(&acl(record.id, &unique("reader"), Permission::Read))
+        .await
+        .unwrap();
+    storage.hard_delete_memory(record.id).await.unwrap();
+    assert_eq!(storage.get_memory(record.id).await.unwrap(), None);
+    assert!(matches!(
+        storage.hard_delete_memory(record.id).await,
+        Err(Error::NotFound(_))
+    ));
+}
+
+/// Every [`MemoryFilter`] field, ordering and paging of `list_memories`.
+pub async fn memory_filters(storage: &dyn StorageBackend) {
+    let agent = unique("agent");
+    let org = unique("org");
+    let thread = unique("thread");
+
+    let mut oldest = memory(&agent, "oldest", &at(1));
+    oldest.memory_type = MemoryType::Semantic;
+    oldest.tags = vec!["alpha".to_string()];
+    oldest.importance = 0.2;
+    let mut middle = memory(&agent, "middle", &at(2));
+    middle.scope = Scope::Public;
+    middle.tags = vec!["beta".to_string(), "gamma".to_string()];
+    middle.importance = 0.6;
+    middle.org_id = Some(org.clone());
+    let mut newest = memory(&agent, "newest", &at(3));
+    newest.importance = 0.9;
+    newest.thread_id = Some(thread.clone());
+    let mut deleted = memory(&agent, "deleted", &at(4));
+    deleted.tags = vec!["alpha".to_string()];
+    let stranger = memory(&unique("agent"), "someone else's", &at(5));
+    for record in [&oldest, &middle, &newest, &deleted, &stranger] {
+        storage.insert_memory(record).await.unwrap();
+    }
+    storage.soft_delete_memory(deleted.id).await.unwrap();
+
+    let list = |filter: MemoryFilter| async move {
+        ids(&storage.list_memories(&filter, 100, 0).await.unwrap())
+    };
+    let by_agent = MemoryFilter {
+        agent_id: Some(agent.clone()),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        list(by_agent.clone()).await,
+        vec![newest.id, middle.id, oldest.id],
+        "newest first, deleted and other agents' memories left out"
+    );
+    assert_eq!(
+        list(MemoryFilter {
+            include_deleted: true,
+            ..by_agent.clone()
+        })
+        .await,
+        vec![deleted.id, newest.id, middle.id, oldest.id]
+    );
+    assert_eq!(
+        list(MemoryFilter {
+            memory_type: Some(MemoryType::Semantic),
+            ..by_agent.clone()
+        })
+        .await,
+        vec![oldest.id]
+    );
+    assert_eq!(
+        list(MemoryFilter {
+            scope: Some(Scope::Public),
+            ..by_agent.clone()
+        })
+        .await,
+        vec![middle.id]
+    );
+    assert_eq!(
+        list(MemoryFilter {
+            min_importance: Some(0.6),
+            ..by_agent.clone()
+        })
+        .await,
+        vec![newest.id, middle.id],
+        "min_importance is inclusive"
+    );
+    assert_eq!(
+        list(MemoryFilter {
+            org_id: Some(org.clone()),
+            ..Default::default()
+        })
+        .await,
+        vec![middle.id]
+    );
+    assert_eq!(
+        list(MemoryFilter {
+            thread_id: Some(thread.clone()),
+            ..Default::default()
+        })
+        .await,
+        vec![newest.id]
+    );
+    assert_eq!(
+        list(MemoryFilter {
+            tags: Some(vec!["alpha".to_string(), "gamma".to_string()]),
+            ..by_agent.clone()
+        })
+        .await,
+        vec![middle.id, oldest.id],
+        "tags match memories carrying any of them"
+    );
+    assert!(
+        list(MemoryFilter {
+            tags: Some(vec!["delta".to_string()]),
+            ..by_agent.clone()
+        })
+        .await
+        .is_empty()
+    );
+    assert!(
+        list(MemoryFilter {
+            memory_type: Some(MemoryType::Semantic),
+            scope: Some(Scope::Public),
+            ..by_agent.clone()
+        })
+        .await
+        .is_empty(),
+        "filters combine with AND"
+    );
+
+    let page = storage.list_memories(&by_agent, 1, 1).await.unwrap();
+    assert_eq!(ids(&page), vec![middle.id]);
+    assert!(
+        storage
+            .list_memories(&by_agent, 10, 3)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+/// Owner, ACL, public, channel and delegated access.
+pub async fn permissions(storage: &dyn StorageBackend) {
+    let owner = unique("owner");
+    let reader = unique("reader");
+    let expired = unique("expired");
+    let member = unique("member");
+    let delegate = unique("delegate");
+    let channel = unique("channel");
+
+    let mut private = memory(&owner, "private", &at(1));
+    private.channel = Some(channel.clone());
+    private.tags = vec!["shared".to_string()];
+    let mut public = memory(&owner, "public", &at(2));
+    public.scope = Scope::Public;
+    let mut gone = memory(&owner, "deleted", &at(3));
+    gone.scope = Scope::Public;
+    for record in [&private, &public, &gone] {
+        storage.insert_memory(record).await.unwrap();
+    }
+    storage.soft_delete_memory(gone.id).await.unwrap();
+
+    let check = |principal: String, id: Uuid, required: Permission| async move {
+        storage
+            .check_permission(id, &principal, required)
+            .await
+            .unwrap()
+    };
+
+    assert!(check(owner.clone(), private.id, Permission::Admin).await);
+    assert!(!check(reader.clone(), private.id, Permission::Read).await);
+    assert!(matches!(
+        storage
+            .check_permission(Uuid::now_v7(), &owner, Permission::Read)
+            .await,
+        Err(Error::NotFound(_))
+    ));
+
+    storage
+        .insert_acl(&acl(private.id, &reader, Permission::Read))
+        .await
+        .unwrap();
+    assert!(check(reader.clone(), private.id, Permission::Read).await);
+    assert!(!check(reader.clone(), private.id, Permission::Write).await);
+
+    let mut stale = acl(private.id, &expired, Permission::Admin);
+    stale.expires_at = Some(PAST.to_string());
+    storage.insert_acl(&stale).await.unwrap();
+    assert!(!check(expired.clone(), private.id, Permission::Read).await);
+
+    let mut anyone = acl(public.id, "*", Permission::Read);
+    anyone.principal_type = PrincipalType::Public;
+    storage.insert_acl(&anyone).await.unwrap();
+    assert!(check(reader.clone(), public.id, Permission::Read).await);
+    assert!(!check(reader.clone(), public.id, Permission::Write).await);
+
+    let joined = ChannelMember {
+        channel: channel.clone(),
+        agent_id: member.clone(),
+        joined_at: at(0),
+    };
+    storage.add_channel_member(&joined).await.unwrap();
+    assert!(check(member.clone(), private.id, Permission::Read).await);
+    assert!(
+        !check(member.clone(), private.id, Permission::Write).await,
+        "channel members only read"
+    );
+
+    let grant = delegation(
+        &owner,
+        &delegate,
+        DelegationScope::ByTag(vec!["shared".to_string()]),
+    );
+    storage.insert_delegation(&grant).await.unwrap();
+    assert!(check(delegate.clone(), private.id, Permission::Write).await);
+    assert!(!check(delegate.clone(), private.id, Permission::Delete).await);
+    assert!(!check(delegate.clone(), public.id, Permission::Write).await);
+    storage.revoke_delegation(grant.id).await.unwrap();
+    assert!(!check(delegate.clone(), private.id, Permission::Write).await);
+
+    let accessible = |agent: String| async move {
+        let mut ids = storage
+            .list_accessible_memory_ids(&agent, 1000)
+            .await
+            .unwrap();
+        ids.retain(|id| [private.id, public.id, gone.id].contains(id));
+        ids.sort();
+        ids
+    };
+    assert_eq!(accessible(owner.clone()).await, vec![private.id, public.id]);
+    assert_eq!(
+        accessible(reader.clone()).await,
+        vec![private.id, public.id]
+    );
+    assert_eq!(
+        accessible(member.clone()).await,
+        vec![private.id, public.id]
+    );
+    assert_eq!(accessible(expired.clone()).await, vec![public.id]);
+
+    // Point in time: memories created after `as_of` are left out.
+    let mut as_of = storage
+        .list_accessible_memory_ids_as_of(&owner, &at(1), 1000)
+        .await
+        .unwrap();
+    as_of.retain(|id| [private.id, public.id, gone.id].contains(id));
+    assert_eq!(as_of, vec![private.id]);
+
+    // Global memories are candidates for everyone; recall decides which
+    // are curated enough to show.
+    let mut global = memory(&owner, "global", &at(1));
+    global.scope = Scope::Global;
+    storage.insert_memory(&global).await.unwrap();
+    let ids = storage
+        .list_accessible_memory_ids(&expired, 1000)
+        .await
+        .unwrap();
+    assert!(ids.contains(&global.id));
+}
+
+/// Delegation listing, scopes and revocation.
+pub async fn delegations(storage: &dyn StorageBackend) {
+    let owner = unique("owner");
+    let delegate = unique("delegate");
+    let record = memory(&owner, "delegated", &at(1));
+    storage.insert_memory(&record).await.unwrap();
+
+    let all = delegation(&owner, &delegate, DelegationScope::AllMemories);
+    let by_id = delegation(
+        &owner,
+        &delegate,
+        DelegationScope::ByMemoryId(vec![record.id]),
+    );
+    let mut expired = delegation(&owner, &delegate, DelegationScope::AllMemories);
+    expired.expires_at = Some(PAST.to_string());
+    for d in [&all, &by_id, &expired] {
+        storage.insert_delegation(d).await.unwrap();
+    }
+
+    let mut listed = storage.list_delegations_for(&delegate).await.unwrap();
+    listed.sort_by_key(|d| d.id);
+    assert_eq!(listed, vec![all.clone(), by_id.clone()]);
+
+    assert!(
+        storage
+            .check_delegation(&delegate, record.id, Permission::Write)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !storage
+            .check_delegation(&delegate, record.id, Permission::Admin)
+            .await
+            .unwrap()
+    );
+    assert!(
+        !storage
+            .check_delegation(&delegate, Uuid::now_v7(), Permission::Read)
+            .await
+            .unwrap()
+    );
+
+    storage.revoke_delegation(all.id).await.unwrap();
+    assert!(matches!(
+        storage.revoke_delegation(all.id).await,
+        Err(Error::NotFound(_))
+    ));
+    assert_eq!(
+        storage.list_delegations_for(&delegate).await.unwrap(),
+        vec![by_id.clone()]
+    );
+    storage.revoke_delegation(by_id.id).await.unwrap();
+    assert!(
+        !storage
+            .check_delegation(&delegate, record.id, Permission::Write)
+            .await
+            .unwrap()
+    );
+}
+
+/// Relations in both directions.
+pub async fn relations(storage: &dyn StorageBackend) {
+    let (a, b, c) = (Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7());
+    let relation = |source_id, target_id, relation_type: &str| Relation {
+        id: Uuid::now_v7(),
+        source_id,
+        target_id,
+        relation_type: relation_type.to_string(),
+        weight: 0.5,
+        metadata: serde_json::json!({ "why": relation_type }),
+        created_at: at(1),
+    };
+    let ab = relation(a, b, "supports");
+    let ac = relation(a, c, "contradicts");
+    let cb = relation(c, b, "supports");
+    for r in [&ab, &ac, &cb] {
+        storage.insert_relation(r).await.unwrap();
+    }
+
+    let mut from_a = storage.get_relations_from(a).await.unwrap();
+    from_a.sort_by_key(|r| r.id);
+    assert_eq!(from_a, vec![ab.clone(), ac.clone()]);
+    let mut to_b = storage.get_relations_to(b).await.unwrap();
+    to_b.sort_by_key(|r| r.id);
+    assert_eq!(to_b, vec![ab.clone(), cb.clone()]);
+    assert!(storage.get_relations_to(a).await.unwrap().is_empty());
+
+    storage.delete_relation(ab.id).await.unwrap();
+    assert_eq!(storage.get_relations_from(a).await.unwrap(), vec![ac]);
+    assert!(matches!(
+        storage.delete_relation(ab.id).await,
+        Err(Error::NotFound(_))
+    ));
+}
+
+/// Chain heads and the ordered listings chain verification pages through.
+pub async fn memory_chains(storage: &dyn StorageBackend) {
+    let agent = unique("agent");
+    let thread = unique("thread");
+
+    let mut threaded = Vec::new();
+    for second in [3, 1, 2] {
+        let mut record = memory(&agent, &format!("step {second}"), &at(second));
+        record.thread_id = Some(thread.clone());
+        record.content_hash = vec![second as u8; 32];
+        threaded.push(record);
+    }
+    let mut global = memory(&agent, "no thread", &at(4));
+    global.content_hash = vec![4; 32];
+    let mut deleted = memory(&agent, "deleted", &at(5));
+    deleted.thread_id = Some(thread.clone());
+    deleted.content_hash = vec![5; 32];
+    for record in threaded.iter().chain([&global, &deleted]) {
+        storage.insert_memory(record).await.unwrap();
+    }
+    storage.soft_delete_memory(deleted.id).await.unwrap();
+    threaded.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+    assert_eq!(
+        storage
+            .get_latest_memory_hash(&agent, Some(&thread))
+            .await
+            .unwrap(),
+        Some(vec![3; 32]),
+        "the newest live memory of the thread"
+    );
+    assert_eq!(
+        storage.get_latest_memory_hash(&agent, None).await.unwrap(),
+        Some(vec![4; 32]),
+        "`None` is the chain of memories without a thread"
+    );
+    assert_eq!(
+        storage
+            .get_latest_memory_hash(&unique("agent"), None)
+            .await
+            .unwrap(),
+        None
+    );
+
+    let ordered = storage
+        .list_memories_by_agent_ordered(&agent, Some(&thread), 100)
+        .await
+        .unwrap();
+    assert_eq!(ids(&ordered), ids(&threaded));
+    let ordered = storage
+        .list_memories_by_agent_ordered(&agent, None, 100)
+        .await
+        .unwrap();
+    assert_eq!(
+        ids(&ordered),
+        [ids(&threaded), vec![global.id]].concat(),
+        "`None` lists all of the agent's live memories"
+    );
+
+    let mut paged = Vec::new();
+    let mut cursor: Option<(String, Uuid)> = None;
+    loop {
+        let page = storage
+            .list_memories_chain_page(
+                &agent,
+                None,
+                cursor.as_ref().map(|(at, id)| (at.as_str(), *id)),
+                2,
+            )
+            .await
+            .unwrap();
+        let Some(last) = page.last() else { break };
+        cursor = Some((last.created_at.clone(), last.id));
+        paged.extend(page);
+    }
+    assert_eq!(ids(&paged), [ids(&threaded), vec![global.id]].concat());
+
+    // Records sharing a timestamp are ordered by id.
+    let mut twins: Vec<MemoryRecord> = (0..3)
+        .map(|_| {
+            let mut record = memory(&agent, "twin", &at(30));
+            record.thread_id = Some(format!("{thread}-twins"));
+            record
+        })
+        .collect();
+    for record in twins.iter().rev() {
+        storage.insert_memory(record).await.unwrap();
+    }
+    twins.sort_by_key(|r| r.id);
+    let twins_thread = format!("{thread}-twins");
+    let first = storage
+        .list_memories_chain_page(&agent, Some(&twins_thread), None, 1)
+        .await
+        .unwrap();
+    assert_eq!(ids(&first), vec![twins[0].id]);
+    let rest = storage
+        .list_memories_chain_page(
+            &agent,
+            Some(&twins_thread),
+            Some((&first[0].created_at, first[0].id)),
+            10,
+        )
+        .await
+        .unwrap();
+    assert_eq!(ids(&rest), vec![twins[1].id, twins[2].id]);
+}
+
+/// Verification checkpoints, one per (agent, thread) chain.
+pub async fn chain_checkpoints(storage: &dyn StorageBackend) {
+    let agent = unique("agent");
+    let thread = unique("thread");
+    let checkpoint = |thread_id: Option<String>, verified_count| ChainCheckpoint {
+        agent_id: agent.clone(),
+        thread_id,
+        verified_count,
+        last_record_id: Uuid::now_v7(),
+        last_created_at: at(1),
+        last_content_hash: "ab".repeat(32),
+        rolling_root: "cd".repeat(32),
+        verified_at: at(2),
+    };
+
+    let global = checkpoint(None, 3);
+    let threaded = checkpoint(Some(thread.clone()), 5);
+    storage.put_chain_checkpoint(&global).await.unwrap();
+    storage.put_chain_checkpoint(&threaded).await.unwrap();
+    assert_eq!(
+        storage.get_chain_checkpoint(&agent, None).await.unwrap(),
+        Some(global.clone())
+    );
+    assert_eq!(
+        storage
+            .get_chain_checkpoint(&agent, Some(&thread))
+            .await
+            .unwrap(),
+        Some(threaded.clone())
+    );
+
+    let replaced = checkpoint(Some(thread.clone()), 9);
+    storage.put_chain_checkpoint(&replaced).await.unwrap();
+    assert_eq!(
+        storage
+            .get_chain_checkpoint(&agent, Some(&thread))
+            .await
+            .unwrap(),
+        Some(replaced)
+    );
+
+    storage.delete_chain_checkpoints(&agent).await.unwrap();
+    assert_eq!(
+        storage.get_chain_checkpoint(&agent, None).await.unwrap(),
+        None
+    );
+    assert_eq!(
+        storage
+            .get_chain_checkpoint(&agent, Some(&thread))
+            .await
+            .unwrap(),
+        None
+    );
+}
+
+/// The event log: lookups, orderings, clocks and chain heads.
+pub async fn events(storage: &dyn StorageBackend) {
+    let agent = unique("agent");
+    let thread = unique("thread");
+    let other_thread = unique("thread");
+
+    let mut first = event(&agent, Some(&thread), 1);
+    first.run_id = Some("run-1".to_string());
+    first.trace_id = Some("trace-1".to_string());
+    first.model = Some("model-x".to_string());
+    first.tokens_input = Some(120);
+    first.tokens_output = Some(40);
+    first.latency_ms = Some(350);
+    first.cost_usd = Some(0.25);
+    first.request_id = Some("req-1".to_string());
+    let mut child = event(&agent, Some(&thread), 2);
+    child.parent_event_id = Some(first.id);
+    child.prev_hash = Some(first.content_hash.clone());
+    let third = event(&agent, Some(&other_thread), 3);
+    for e in [&first, &child, &third] {
+        storage.insert_event(e).await.unwrap();
+    }
+    assert!(
+        storage.insert_event(&first).await.is_err(),
+        "a second insert with the same id must fail"
+    );
+
+    assert_eq!(
+        storage.get_event(first.id).await.unwrap(),
+        Some(first.clone())
+    );
+    assert_eq!(storage.get_event(Uuid::now_v7()).await.unwrap(), None);
+
+    let newest_first = storage.list_events(&agent, 10, 0).await.unwrap();
+    assert_eq!(
+        newest_first.iter().map(|e| e.id).collect::<Vec<_>>(),
+        vec![third.id, child.id, first.id]
+    );
+    let page = storage.list_events(&agent, 1, 1).await.unwrap();
+    assert_eq!(
+        page.iter().map(|e| e.id).collect::<Vec<_>>(),
+        vec![child.id]
+    );
+
+    let in_thread = storage.get_events_by_thread(&thread, 10).await.unwrap();
+    assert_eq!(
+        in_thread.iter().map(|e| e.id).collect::<Vec<_>>(),
+        vec![first.id, child.id]
+    );
+    assert_eq!(
+        storage.list_child_events(first.id, 10).await.unwrap(),
+        vec![child.clone()]
+    );
+
+    assert_eq!(storage.max_logical_clock(&agent).await.unwrap(), 3);
+    assert_eq!(
+        storage.max_logical_clock(&unique("agent")).await.unwrap(),
+        0
+    );
+    assert_eq!(
+        storage
+            .get_latest_event_hash(&agent, Some(&thread))
+            .await
+            .unwrap(),
+        Some(child.content_hash.clone())
+    );
+    assert_eq!(
+        storage.get_latest_event_hash(&agent, None).await.unwrap(),
+        Some(third.content_hash.clone()),
+        "`None` is the newest event of any thread"
+    );
+}
+
+/// Moving old events to cold storage.
+pub async fn event_archives(storage: &dyn StorageBackend) {
+    let agent = unique("agent");
+    let events: Vec<AgentEvent> = (1..=4).map(|clock| event(&agent, None, clock)).collect();
+    for e in events.iter().rev() {
+        storage.insert_event(e).await.unwrap();
+    }
+
+    let mut old = storage.list_events_before(&at(3), 100_000).await.unwrap();
+    old.retain(|e| e.agent_id == agent);
+    assert_eq!(
+        old.iter().map(|e| e.id).collect::<Vec<_>>(),
+        vec![events[0].id, events[1].id],
+        "strictly before the cutoff, in logical-clock order"
+    );
+
+    let segment = EventArchiveSegment {
+        id: Uuid::now_v7(),
+        agent_id: agent.clone(),
+        first_event_id: old[0].id,
+        last_event_id: old[1].id,
+        from_timestamp: old[0].timestamp.clone(),
+        to_timestamp: old[1].timestamp.clone(),
+        first_logical_clock: 1,
+        last_logical_clock: 2,
+        event_count: 2,
+        merkle_root: "ef".repeat(32),
+        last_content_hash: "02".repeat(32),
+        object_key: format!("events/{agent}/1-2"),
+        archived_at: at(10),
+    };
+    let archived: Vec<Uuid> = old.iter().map(|e| e.id).collect();
+    storage
+        .archive_event_segment(&segment, &archived)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        storage
+            .list_events(&agent, 10, 0)
+            .await
+            .unwrap()
+            .iter()
+            .map(|e| e.id)
+            .collect::<Vec<_>>(),
+        vec![events[3].id, events[2].id]
+    );
+    assert_eq!(storage.get_event(events[0].id).await.unwrap(), None);
+    assert_eq!(
+        storage.get_event_archive(segment.id).await.unwrap(),
+        Some(segment.clone())
+    );
+    assert_eq!(
+        storage.get_event_archive(Uuid::now_v7()).await.unwrap(),
+        None
+    );
+    assert_eq!(
+        storage.list_event_archives(Some(&agent)).await.unwrap(),
+        vec![segment.clone()]
+    );
+    assert!(
+        storage
+            .list_event_archives(None)
+            .await
+            .unwrap()
+            .contains(&segment)
+    );
+    assert!(
+        storage
+            .list_event_archives(Some(&unique("agent")))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+/// Sync watermarks, change listing and expiry.
+pub async fn sync_support(storage: &dyn StorageBackend) {
+    let key = unique("peer");
+    assert_eq!(storage.get_sync_watermark(&key).await.unwrap(), None);
+    storage.set_sync_watermark(&key, &at(1)).await.unwrap();
+    storage.set_sync_watermark(&key, &at(2)).await.unwrap();
+    assert_eq!(storage.get_sync_watermark(&key).await.unwrap(), Some(at(2)));
+
+    let agent = unique("agent");
+    let before = memory(&agent, "before the watermark", &at(1));
+    let mut after = memory(&agent, "after the watermark", &at(1));
+    after.updated_at = at(5);
+    let mut tombstone = memory(&agent, "deleted after the watermark", &at(1));
+    tombstone.updated_at = at(3);
+    tombstone.deleted_at = Some(at(3));
+    for record in [&before, &after, &tombstone] {
+        storage.insert_memory(record).await.unwrap();
+    }
+    let mut since = storage.list_memories_since(&at(2), 100_000).await.unwrap();
+    since.retain(|r| r.agent_id == agent);
+    assert_eq!(
+        ids(&since),
+        vec![tombstone.id, after.id],
+        "oldest change first, deletions included"
+    );
+
+    let mut expired = memory(&agent, "expired", &at(1));
+    expired.expires_at = Some(PAST.to_string());
+    let mut lasting = memory(&agent, "lasting", &at(1));
+    lasting.expires_at = Some(FUTURE.to_string());
+    storage.insert_memory(&expired).await.unwrap();
+    storage.insert_memory(&lasting).await.unwrap();
+    assert!(storage.cleanup_expired().await.unwrap() >= 1);
+    assert!(
+        storage
+            .get_memory(expired.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .deleted_at
+            .is_some()
+    );
+    assert!(
+        storage
+            .get_memory(lasting.id)
+            .await
+            .unwrap()
+            .unwrap()
+            .deleted_at
+            .is_none()
+    );
+    assert_eq!(storage.cleanup_expired().await.unwrap(), 0);
+}
+
+/// The share-link denylist.
+pub async fn share_links(storage: &dyn StorageBackend) {
+    let revoked = |expires_at: &str| RevokedShareLink {
+        link_id: Uuid::now_v7(),
+        memory_id: Uuid::now_v7(),
+        revoked_by: unique("agent"),
+        revoked_at: at(1),
+        expires_at: expires_at.to_string(),
+    };
+    let stale = revoked(PAST);
+    let live = revoked(FUTURE);
+    storage.revoke_share_link(&stale).await.unwrap();
+    storage.revoke_share_link(&live).await.unwrap();
+    storage.revoke_share_link(&live).await.unwrap();
+
+    let is_revoked =
+        |link: &RevokedShareLink| storage.is_share_link_revoked(link.link_id, link.memory_id);
+    assert!(is_revoked(&live).await.unwrap());
+    assert!(
+        !storage
+            .is_share_link_revoked(Uuid::now_v7(), live.memory_id)
+            .await
+            .unwrap()
+    );
+
+    // An entry only covers the memory it was recorded against, and one
+    // against another memory does not shadow it.
+    let elsewhere = RevokedShareLink {
+        memory_id: Uuid::now_v7(),
+        ..revoked(FUTURE)
+    };
+    assert!(
+        !storage
+            .is_share_link_revoked(elsewhere.link_id, live.memory_id)
+            .await
+            .unwrap()
+    );
+    storage.revoke_share_link(&elsewhere).await.unwrap();
+    let same_link = RevokedShareLink {
+        memory_id: live.memory_id,
+        ..elsewhere.clone()
+    };
+    assert!(!is_revoked(&same_link).await.unwrap());
+    storage.revoke_share_link(&same_link).await.unwrap();
+    assert!(is_revoked(&same_link).await.unwrap());
+    assert!(is_revoked(&elsewhere).await.unwrap());
+
+    assert!(storage.purge_revoked_share_links(&at(0)).await.unwrap() >= 1);
+    assert!(!is_revoked(&stale).await.unwrap());
+    assert!(is_revoked(&live).await.unwrap());
+}
+
+pub async fn pseudonyms(storage: &dyn StorageBackend) {
+    let org = unique("org");
+    let entry = PseudonymEntry {
+        org_id: org.clone(),
+        alias: unique("anon"),
+        sealed_agent_id: "sealed-1".to_string(),
+        created_at: at(1),
+    };
+    storage.record_pseudonym(&entry).await.unwrap();
+    // The first entry for an alias is kept.
+    let again = PseudonymEntry {
+        sealed_agent_id: "sealed-2".to_string(),
+        created_at: at(2),
+        ..entry.clone()
+    };
+    storage.record_pseudonym(&again).await.unwrap();
+    assert_eq!(
+        storage.get_pseudonym(&org, &entry.alias).await.unwrap(),
+        Some(entry.clone())
+    );
+
+    // Tables are per org; `""` is the table of memories without one.
+    assert!(
+        storage
+            .get_pseudonym("", &entry.alias)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    let orgless = PseudonymEntry {
+        org_id: String::new(),
+        ..entry.clone()
+    };
+    storage.record_pseudonym(&orgless).await.unwrap();
+    assert_eq!(
+        storage.get_pseudonym("", &entry.alias).await.unwrap(),
+        Some(orgless)
+    );
+}
+
+/// Agent profiles, retrieval defaults and embedding baselines.
+pub async fn agent_profiles(storage: &dyn StorageBackend) {
+    let agent = unique("agent");
+    assert_eq!(storage.get_agent_profile(&agent).await.unwrap(), None);
+
+    let defaults = RetrievalDefaults {
+        strategy: Some("semantic".to_string()),
+        limit: Some(7),
+        ..Default::default()
+    };
+    let profile = AgentProfile {
+        agent_id: agent.clone(),
+        avg_importance: 0.5,
+        avg_content_length: 42.0,
+        total_memories: 3,
+        last_updated: at(1),
+        retrieval: defaults.clone(),
+    };
+    storage
+        .insert_or_update_agent_profile(&profile)
+        .await
+        .unwrap();
+    assert_eq!(
+        storage.get_agent_profile(&agent).await.unwrap(),
+        Some(profile.clone())
+    );
+
+    let stats = AgentProfile {
+        avg_importance: 0.75,
+        total_memories: 4,
+        last_updated: at(2),
+        retrieval: RetrievalDefaults::default(),
+        ..profile.clone()
+    };
+    storage
+        .insert_or_update_agent_profile(&stats)
+        .await
+        .unwrap();
+    assert_eq!(
+        storage.get_agent_profile(&agent).await.unwrap(),
+        Some(AgentProfile {
+            retrieval: defaults.clone(),
+            ..stats.clone()
+        }),
+        "updating the stats keeps the retrieval defaults"
+    );
+
+    let edited = RetrievalDefaults {
+        thread_isolation: Some(true),
+        ..Default::default()
+    };
+    storage
+        .set_agent_retrieval_defaults(&agent, &edited, &at(3))
+        .await
+        .unwrap();
+    let stored = storage.get_agent_profile(&agent).await.unwrap().unwrap();
+    assert_eq!(stored.retrieval, edited);
+    assert_eq!(stored.total_memories, 4);
+    assert_eq!(stored.last_updated, at(3));
+
+    let fresh = unique("agent");
+    storage
+        .set_agent_retrieval_defaults(&fresh, &edited, &at(4))
+        .await
+        .unwrap();
+    let created = storage.get_agent_profile(&fresh).await.unwrap().unwrap();
+    assert_eq!(created.retrieval, edited);
+    assert_eq!(created.total_memories, 0);
+
+    assert_eq!(storage.get_embedding_baseline(&agent).await.unwrap(), None);
+    let baseline = EmbeddingBaseline {
+        agent_id: agent.clone(),
+        mu: vec![0.5, -0.25, 0.125],
+        cov_diag: vec![1.0, 2.0, 0.5],
+        n: 12,
+        updated_at: at(1),
+    };
+    storage
+        .insert_or_update_embedding_baseline(&baseline)
+        .await
+        .unwrap();
+    let moved = EmbeddingBaseline {
+        mu: vec![0.75, -0.5, 0.0],
+        n: 13,
+        updated_at: at(2),
+        ..baseline.clone()
+    };
+    storage
+        .insert_or_update_embedding_baseline(&moved)
+        .await
+        .unwrap();
+    assert_eq!(
+        storage.get_embedding_baseline(&agent).await.unwrap(),
+        Some(moved)
+    );
+}
+
+/// The conflict review queue.
+pub async fn conflicts(storage: &dyn StorageBackend) {
+    let agent = unique("agent");
+    let conflict = |created_at: String| ConflictRecord {
+        id: Uuid::now_v7(),
+        agent_id: agent.clone(),
+        memory_a: Uuid::now_v7(),
+        memory_b: Uuid::now_v7(),
+        similarity: 0.875,
+        reason: "contradictory deploy days".to_string(),
+        status: ConflictStatus::Open,
+        resolution: None,
+        winner_id: None,
+        resolved_by: None,
+        created_at,
+        resolved_at: None,
+    };
+    let older = conflict(at(1));
+    let newer = conflict(at(2));
+    storage.insert_conflict(&older).await.unwrap();
+    storage.insert_conflict(&newer).await.unwrap();
+    assert_eq!(
+        storage.get_conflict(older.id).await.unwrap(),
+        Some(older.clone())
+    );
+    assert_eq!(storage.get_conflict(Uuid::now_v7()).await.unwrap(), None);
+
+    let resolved = ConflictRecord {
+        status: ConflictStatus::Resolved,
+        resolution: Some("manual_winner".to_string()),
+        winner_id: Some(older.memory_a),
+        resolved_by: Some("reviewer".to_string()),
+        resolved_at: Some(at(3)),
+        ..older.clone()
+    };
+    storage.update_conflict(&resolved).await.unwrap();
+    assert_eq!(
+        storage.get_conflict(older.id).await.unwrap(),
+        Some(resolved.clone())
+    );
+    assert!(matches!(
+        storage.update_conflict(&conflict(at(4))).await,
+        Err(Error::NotFound(_))
+    ));
+
+    let listed = |status: Option<ConflictStatus>, limit, offset| {
+        let agent = agent.clone();
+        async move {
+            storage
+                .list_conflicts(Some(&agent), status, limit, offset)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|c| c.id)
+                .collect::<Vec<_>>()
+        }
+    };
+    assert_eq!(listed(None, 10, 0).await, vec![newer.id, older.id]);
+    assert_eq!(
+        listed(Some(ConflictStatus::Open), 10, 0).await,
+        vec![newer.id]
+    );
+    assert_eq!(
+        listed(Some(ConflictStatus::Resolved), 10, 0).await,
+        vec![older.id]
+    );
+    assert_eq!(listed(None, 1, 1).await, vec![older.id]);
+    assert!(
+        storage
+            .list_conflicts(None, Some(ConflictStatus::Dismissed), 100_000, 0)
+            .await
+            .unwrap()
+            .iter()
+            .all(|c| c.agent_id != agent)
+    );
+}
+
+/// Access requests and their compare-and-set decisions.
+pub async fn access_requests(storage: &dyn StorageBackend) {
+    let owner = unique("owner");
+    let requester = unique("requester");
+    let request = |created_at: String, requester: &str| AccessRequest {
+        id: Uuid::now_v7(),
+        requester_id: requester.to_string(),
+        owner_id: owner.clone(),
+        memory_ids: vec![Uuid::now_v7()],
+        tags: vec!["project-x".to_string()],
+        justification: "needed for the incident review".to_string(),
+        permission: Permission::Read,
+        expires_in_hours: Some(24.0),
+        status: AccessRequestStatus::Pending,
+        decided_by: None,
+        decision_note: None,
+        acl_ids: vec![],
+        created_at,
+        decided_at: None,
+    };
+    let older = request(at(1), &requester);
+    let newer = request(at(2), &requester);
+    let elsewhere = request(at(3), &unique("requester"));
+    for r in [&older, &newer, &elsewhere] {
+        storage.insert_access_request(r).await.unwrap();
+    }
+    assert_eq!(
+        storage.get_access_request(older.id).await.unwrap(),
+        Some(older.clone())
+    );
+    assert_eq!(
+        storage.get_access_request(Uuid::now_v7()).await.unwrap(),
+        None
+    );
+
+    let approved = AccessRequest {
+        status: AccessRequestStatus::Approved,
+        decided_by: Some("owner".to_string()),
+        decision_note: Some("ok".to_string()),
+        acl_ids: vec![Uuid::now_v7()],
+        decided_at: Some(at(4)),
+        ..older.clone()
+    };
+    assert!(
+        storage
+            .update_access_request(&approved, AccessRequestStatus::Pending)
+            .await
+            .unwrap()
+    );
+    let denied = AccessRequest {
+        status: AccessRequestStatus::Denied,
+        ..approved.clone()
+    };
+    assert!(
+        !storage
+            .update_access_request(&denied, AccessRequestStatus::Pending)
+            .await
+            .unwrap(),
+        "only a request still in the expected status is decided"
+    );
+    assert_eq!(
+        storage.get_access_request(older.id).await.unwrap(),
+        Some(approved)
+    );
+
+    let listed = |requester: Option<&str>, status, limit, offset| {
+        let owner = owner.clone();
+        let requester = requester.map(str::to_string);
+        async move {
+            storage
+                .list_access_requests(Some(&owner), requester.as_deref(), status, limit, offset)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|r| r.id)
+                .collect::<Vec<_>>()
+        }
+    };
+    assert_eq!(
+        listed(None, None, 10, 0).await,
+        vec![elsewhere.id, newer.id, older.id]
+    );
+    assert_eq!(
+        listed(Some(&requester), None, 10, 0).await,
+        vec![newer.id, older.id]
+    );
+    assert_eq!(
+        listed(Some(&requester), Some(AccessRequestStatus::Pending), 10, 0).await,
+        vec![newer.id]
+    );
+    assert_eq!(listed(None, None, 1, 2).await, vec![older.id]);
+}
+
+/// Channels, their members and their memory quota.
+pub async fn channels(storage: &dyn StorageBackend) {
+    let creator = unique("agent");
+    let first = unique("channel");
+    let second = unique("channel");
+    let channel = |name: &str| Channel {
+        name: name.to_string(),
+        created_by: creator.clone(),
+        max_memories: Some(10),
+        created_at: at(1),
+    };
+    storage.insert_channel(&channel(&first)).await.unwrap();
+    storage.insert_channel(&channel(&second)).await.unwrap();
+    assert!(
+        storage.insert_channel(&channel(&first)).await.is_err(),
+        "channel names are unique"
+    );
+    assert_eq!(
+        storage.get_channel(&first).await.unwrap(),
+        Some(channel(&first))
+    );
+    assert_eq!(storage.get_channel(&unique("channel")).await.unwrap(), None);
+
+    let unlimited = Channel {
+        max_memories: None,
+        ..channel(&first)
+    };
+    storage.update_channel(&unlimited).await.unwrap();
+    assert_eq!(
+        storage.get_channel(&first).await.unwrap(),
+        Some(unlimited.clone())
+    );
+    assert!(matches!(
+        storage.update_channel(&channel(&unique("channel"))).await,
+        Err(Error::NotFound(_))
+    ));
+
+    let member = |agent_id: &str, joined_at: String| ChannelMember {
+        channel: first.clone(),
+        agent_id: agent_id.to_string(),
+        joined_at,
+    };
+    let (early, late) = (unique("agent"), unique("agent"));
+    assert!(
+        storage
+            .add_channel_member(&member(&late, at(2)))
+            .await
+            .unwrap()
+    );
+    assert!(
+        storage
+            .add_channel_member(&member(&early, at(1)))
+            .await
+            .unwrap()
+    );
+    assert!(
+        !storage
+            .add_channel_member(&member(&early, at(3)))
+            .await
+            .unwrap(),
+        "joining twice is a no-op"
+    );
+    assert_eq!(
+        storage.list_channel_members(&first).await.unwrap(),
+        vec![member(&early, at(1)), member(&late, at(2))],
+        "in join order"
+    );
+    assert!(storage.is_channel_member(&first, &early).await.unwrap());
+    assert!(!storage.is_channel_member(&second, &early).await.unwrap());
+
+    assert_eq!(
+        storage.list_channels(Some(&early)).await.unwrap(),
+        vec![unlimited.clone()]
+    );
+    let all: Vec<String> = storage
+        .list_channels(None)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|c| c.name)
+        .filter(|name| [&first, &second].contains(&name))
+        .collect();
+    let mut by_name = vec![first.clone(), second.clone()];
+    by_name.sort();
+    assert_eq!(all, by_name);
+
+    assert!(storage.remove_channel_member(&first, &early).await.unwrap());
+    assert!(!storage.remove_channel_member(&first, &early).await.unwrap());
+    assert!(!storage.is_channel_member(&first, &early).await.unwrap());
+
+    let mut kept = memory(&late, "in the channel", &at(1));
+    kept.channel = Some(first.clone());
+    let mut dropped = memory(&late, "deleted from the channel", &at(2));
+    dropped.channel = Some(first.clone());
+    let mut owned = memory(&creator, "also in the channel", &at(3));
+    owned.channel = Some(first.clone());
+    for record in [&kept, &dropped, &owned] {
+        storage.insert_memory(record).await.unwrap();
+    }
+    storage.soft_delete_memory(dropped.id).await.unwrap();
+    assert_eq!(storage.count_channel_memories(&first).await.unwrap(), 2);
+    assert_eq!(storage.count_channel_memories(&second).await.unwrap(), 0);
+}
+
+/// Per-agent content storage statistics.
+pub async fn content_stats(storage: &dyn StorageBackend) {
+    let (a, b) = (unique("agent-a"), unique("agent-b"));
+    for (agent, content) in [(&a, "four"), (&a, "sixsix"), (&b, "seven77")] {
+        storage
+            .insert_memory(&memory(agent, content, &at(1)))
+            .await
+            .unwrap();
+    }
+
+    let stats = storage.content_storage_stats(Some(&a)).await.unwrap();
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].agent_id, a);
+    assert_eq!(stats[0].memories, 2);
+    assert_eq!(stats[0].compressed, 0, "short content is stored as is");
+    assert_eq!(stats[0].content_bytes, 10);
+    assert_eq!(stats[0].stored_bytes, 10);
+    assert_eq!(stats[0].saved_bytes, 0);
+
+    let all = storage.content_storage_stats(None).await.unwrap();
+    let ours: Vec<(&str, u64)> = all
+        .iter()
+        .filter(|s| s.agent_id == a || s.agent_id == b)
+        .map(|s| (s.agent_id.as_str(), s.memories))
+        .collect();
+    assert_eq!(
+        ours,
+        vec![(a.as_str(), 2), (b.as_str(), 1)],
+        "one row per agent, ordered by agent id"
+    );
+}
+
+/// Daily storage growth.
+pub async fn growth(storage: &dyn StorageBackend) {
+    const DAY: &str = "1987-06-05";
+    let on_day = |growth: Vec<crate::model::content_stats::DailyGrowth>| {
+        assert!(
+            growth.windows(2).all(|w| w[0].day < w[1].day),
+            "one entry per day, oldest first"
+        );
+        growth
+            .into_iter()
+            .find(|g| g.day == DAY)
+            .unwrap_or_default()
+    };
+    let before = on_day(storage.daily_growth().await.unwrap());
+
+    let agent = unique("agent");
+    for (content, at) in [("four", "T08:00:00+00:00"), ("sixsix", "T23:59:59+00:00")] {
+        storage
+            .insert_memory(&memory(&agent, content, &format!("{DAY}{at}")))
+            .await
+            .unwrap();
+    }
+    let mut logged = event(&agent, None, 1);
+    logged.timestamp = format!("{DAY}T12:00:00+00:00");
+    storage.insert_event(&logged).await.unwrap();
+
+    let after = on_day(storage.daily_growth().await.unwrap());
+    assert_eq!(after.day, DAY);
+    assert_eq!(after.memories - before.memories, 2);
+    assert_eq!(after.memory_bytes - before.memory_bytes, 10);
+    assert_eq!(after.events - before.events, 1);
+    assert!(after.event_bytes > before.event_bytes);
+    storage.database_size().await.unwrap();
+}
+
+/// Saved recall profiles.
+pub async fn recall_profiles(storage: &dyn StorageBackend) {
+    let agent = unique("agent");
+    let profile = |name: &str, limit| RecallProfile {
+        agent_id: agent.clone(),
+        name: name.to_string(),
+        settings: RecallProfileSettings {
+            strategy: Some("hybrid".to_string()),
+            limit: Some(limit),
+            tags: Some(vec!["ops".to_string()]),
+            ..Default::default()
+        },
+        use_count: 0,
+        last_used_at: None,
+        created_at: at(1),
+        updated_at: at(1),
+    };
+    let weekly = profile("weekly", 5);
+    storage.upsert_recall_profile(&weekly).await.unwrap();
+    storage
+        .upsert_recall_profile(&profile("daily", 3))
+        .await
+        .unwrap();
+    assert_eq!(
+        storage.get_recall_profile(&agent, "weekly").await.unwrap(),
+        Some(weekly.clone())
+    );
+    assert_eq!(
+        storage.get_recall_profile(&agent, "monthly").await.unwrap(),
+        None
+    );
+
+    storage
+        .record_recall_profile_use(&agent, "weekly", &at(2))
+        .await
+        .unwrap();
+    storage
+        .record_recall_profile_use(&agent, "weekly", &at(3))
+        .await
+        .unwrap();
+    storage
+        .record_recall_profile_use(&agent, "monthly", &at(3))
+        .await
+        .unwrap();
+
+    let edited = RecallProfile {
+        settings: RecallProfileSettings {
+            limit: Some(20),
+            ..weekly.settings.clone()
+        },
+        use_count: 0,
+        created_at: at(9),
+        updated_at: at(4),
+        ..weekly.clone()
+    };
+    storage.upsert_recall_profile(&edited).await.unwrap();
+    assert_eq!(
+        storage.get_recall_profile(&agent, "weekly").await.unwrap(),
+        Some(RecallProfile {
+            use_count: 2,
+            last_used_at: Some(at(3)),
+            created_at: at(1),
+            ..edited
+        }),
+        "an upsert replaces the settings and keeps the usage"
+    );
+
+    let names: Vec<String> = storage
+        .list_recall_profiles(&agent)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|p| p.name)
+        .collect();
+    assert_eq!(names, vec!["daily", "weekly"]);
+
+    assert!(
+        storage
+            .delete_recall_profile(&agent, "daily")
+            .await
+            .unwrap()
+    );
+    assert!(
+        !storage
+            .delete_recall_profile(&agent, "daily")
+            .await
+            .unwrap()
+    );
+    assert_eq!(storage.list_recall_profiles(&agent).await.unwrap().len(), 1);
+}
+
+/// The memory access log and its pruning.
+pub async fn access_log(storage: &dyn StorageBackend) {
+    let (memory_id, other_id) = (Uuid::now_v7(), Uuid::now_v7());
+    let access = |memory_id, second: u32| MemoryAccess {
+        id: Uuid::now_v7(),
+        memory_id,
+        principal: "reader".to_string(),
+        operation: AccessOperation::Recall,
+        query: Some("deploy window".to_string()),
+        accessed_at: at(second),
+    };
+    let mut ancient = access(memory_id, 0);
+    ancient.accessed_at = PAST.to_string();
+    ancient.operation = AccessOperation::Get;
+    ancient.query = None;
+    let accesses = vec![
+        ancient.clone(),
+        access(memory_id, 1),
+        access(memory_id, 2),
+        access(memory_id, 3),
+        access(other_id, 1),
+    ];
+    storage.insert_memory_accesses(&accesses).await.unwrap();
+    storage.insert_memory_accesses(&[]).await.unwrap();
+
+    assert_eq!(storage.count_memory_accesses(memory_id).await.unwrap(), 4);
+    let newest_first = storage
+        .list_memory_accesses(memory_id, 10, 0)
+        .await
+        .unwrap();
+    assert_eq!(
+        newest_first,
+        vec![
+            accesses[3].clone(),
+            accesses[2].clone(),
+            accesses[1].clone(),
+            ancient
+        ]
+    );
+    assert_eq!(
+        storage.list_memory_accesses(memory_id, 1, 1).await.unwrap(),
+        vec![accesses[2].clone()]
+    );
+
+    assert!(
+        storage
+            .prune_memory_accesses(Some(&at(0)), None)
+            .await
+            .unwrap()
+            >= 1
+    );
+    assert_eq!(storage.count_memory_accesses(memory_id).await.unwrap(), 3);
+    assert!(storage.prune_memory_accesses(None, Some(2)).await.unwrap() >= 1);
+    assert_eq!(
+        storage
+            .list_memory_accesses(memory_id, 10, 0)
+            .await
+            .unwrap(),
+        vec![accesses[3].clone(), accesses[2].clone()],
+        "the newest entries are kept"
+    );
+    assert_eq!(storage.count_memory_accesses(other_id).await.unwrap(), 1);
+}
+
+/// A write batch lands completely or not at all.
+pub async fn commit_write(storage: &dyn StorageBackend) {
+    let agent = unique("agent");
+    let existing = memory(&agent, "already there", &at(1));
+    storage.insert_memory(&existing).await.unwrap();
+    let logged = event(&agent, None, 1);
+    storage.insert_event(&logged).await.unwrap();
+
+    let record = memory(&agent, "written in one transaction", &at(2));
+    let mut revised = existing.clone();
+    revised.content = "revised in the same transaction".to_string();
+    revised.version = 2;
+    let relation = Relation {
+        id: Uuid::now_v7(),
+        source_id: record.id,
+        target_id: existing.id,
+        relation_type: "supersedes".to_string(),
+        weight: 1.0,
+        metadata: serde_json::json!({}),
+        created_at: at(2),
+    };
+    let reader = unique("reader");
+    let failing = WriteBatch {
+        memory: Some(record.clone()),
+        updates: vec![revised.clone()],
+        acls: vec![acl(record.id, &reader, Permission::Read)],
+        relations: vec![relation.clone()],
+        // Reuses an existing event id, so the batch must fail as a whole.
+        events: vec![event(&agent, None, 2), logged.clone()],
+        outbox: vec![OutboxMessage::new("memory_write", serde_json::json!({}))],
+    };
+    assert!(storage.commit_write(&failing).await.is_err());
+    assert_eq!(storage.get_memory(record.id).await.unwrap(), None);
+    assert_eq!(
+        storage.get_memory(existing.id).await.unwrap(),
+        Some(existing.clone())
+    );
+    assert!(
+        storage
+            .get_relations_from(record.id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(storage.max_logical_clock(&agent).await.unwrap(), 1);
+
+    let missing_update = WriteBatch {
+        memory: Some(record.clone()),
+        updates: vec![memory(&agent, "never inserted", &at(1))],
+        ..Default::default()
+    };
+    assert!(matches!(
+        storage.commit_write(&missing_update).await,
+        Err(Error::NotFound(_))
+    ));
+    assert_eq!(storage.get_memory(record.id).await.unwrap(), None);
+
+    let batch = WriteBatch {
+        events: vec![event(&agent, None, 2)],
+        ..failing
+    };
+    storage.commit_write(&batch).await.unwrap();
+    assert_eq!(
+        storage.get_memory(record.id).await.unwrap(),
+        Some(record.clone())
+    );
+    assert_eq!(
+        storage.get_memory(existing.id).await.unwrap(),
+        Some(revised)
+    );
+    assert!(
+        storage
+            .check_permission(record.id, &reader, Permission::Read)
+            .await
+            .unwrap()
+    );
+    assert_eq!(
+        storage.get_relations_from(record.id).await.unwrap(),
+        vec![relation]
+    );
+    assert_eq!(storage.max_logical_clock(&agent).await.unwrap(), 2);
+}
+
+/// Leasing, retrying, completing and pruning outbox messages.
+pub async fn outbox(storage: &dyn StorageBackend) {
+    let first = OutboxMessage::new("memory_write", serde_json::json!({ "n": 1 }));
+    let second = OutboxMessage::new("memory_write", serde_json::json!({ "n": 2 }));
+    let batch = WriteBatch {
+        outbox: vec![first.clone(), second.clone()],
+        ..Default::default()
+    };
+    storage.commit_write(&batch).await.unwrap();
+    let ours = [first.id, second.id];
+    let claim = |lease_until: &'static str| async move {
+        let mut claimed = storage.claim_outbox(100_000, lease_until).await.unwrap();
+        claimed.retain(|m| ours.contains(&m.id));
+        claimed
+    };
+
+    let claimed = claim(FUTURE).await;
+    assert_eq!(
+        claimed.iter().map(|m| m.id).collect::<Vec<_>>(),
+        vec![first.id, second.id],
+        "oldest first"
+    );
+    assert!(claimed.iter().all(|m| m.attempts == 1));
+    assert_eq!(claimed[0].payload, first.payload);
+    assert!(
+        claim(FUTURE).await.is_empty(),
+        "leased messages are skipped"
+    );
+
+    storage
+        .fail_outbox(first.id, "sink unavailable", PAST)
+        .await
+        .unwrap();
+    storage.complete_outbox(second.id).await.unwrap();
+    let retried = claim(FUTURE).await;
+    assert_eq!(retried.len(), 1);
+    assert_eq!(retried[0].id, first.id);
+    assert_eq!(retried[0].attempts, 2);
+    assert_eq!(retried[0].last_error.as_deref(), Some("sink unavailable"));
+
+    storage.complete_outbox(first.id).await.unwrap();
+    storage
+        .fail_outbox(first.id, "too late", PAST)
+        .await
+        .unwrap();
+    assert!(
+        claim(FUTURE).await.is_empty(),
+        "delivered messages are never claimed again"
+    );
+
+    assert_eq!(storage.prune_outbox(PAST).await.unwrap(), 0);
+    assert!(storage.prune_outbox(FUTURE).await.unwrap() >= 2);
+}
+
+/// Thread checkpoints and branches.
+pub async fn checkpoints(storage: &dyn StorageBackend) {
+    let thread = unique("thread");
+    let checkpoint = |branch: &str, second: u32, parent_id| Checkpoint {
+        id: Uuid::now_v7(),
+        thread_id: thread.clone(),
+        agent_id: "agent".to_string(),
+        parent_id,
+        branch_name: branch.to_string(),
+        state_snapshot: serde_json::json!({ "step": second }),
+        state_diff: Some(serde_json::json!({ "added": [second] })),
+        memory_refs: vec![Uuid::now_v7(), Uuid::now_v7()],
+        event_cursor: Some(Uuid::now_v7()),
+        label: Some(format!("step {second}")),
+        created_at: at(second),
+        metadata: serde_json::json!({ "by": "conformance" }),
+    };
+    let root = checkpoint("main", 1, None);
+    let next = checkpoint("main", 2, Some(root.id));
+    let fork = checkpoint("experiment", 3, Some(root.id));
+    for cp in [&root, &next, &fork] {
+        storage.insert_checkpoint(cp).await.unwrap();
+    }
+
+    assert_eq!(
+        storage.get_checkpoint(root.id).await.unwrap(),
+        Some(root.clone())
+    );
+    assert_eq!(storage.get_checkpoint(Uuid::now_v7()).await.unwrap(), None);
+    assert_eq!(
+        storage.list_checkpoints(&thread, None, 10).await.unwrap(),
+        vec![fork.clone(), next.clone(), root.clone()],
+        "newest first"
+    );
+    assert_eq!(
+        storage
+            .list_checkpoints(&thread, Some("main"), 1)
+            .await
+            .unwrap(),
+        vec![next.clone()]
+    );
+    assert_eq!(
+        storage
+            .get_latest_checkpoint(&thread, "experiment")
+            .await
+            .unwrap(),
+        Some(fork)
+    );
+    assert_eq!(
+        storage
+            .get_latest_checkpoint(&thread, "missing")
+            .await
+            .unwrap(),
+        None
+    );
+
+    let owner = || storage.thread_owner(&thread);
+    assert_eq!(owner().await.unwrap().as_deref(), Some("agent"));
+    let mut first = memory("writer", "first words", &at(4));
+    first.thread_id = Some(thread.clone());
+    storage.insert_memory(&first).await.unwrap();
+    assert_eq!(
+        owner().await.unwrap().as_deref(),
+        Some("writer"),
+        "memories outrank checkpoints"
+    );
+    storage
+        .insert_event(&event("speaker", Some(&thread), 2))
+        .await
+        .unwrap();
+    assert_eq!(owner().await.unwrap().as_deref(), Some("speaker"));
+    assert_eq!(storage.thread_owner(&unique("thread")).await.unwrap(), None);
+}
+
+/// Health, identity and compaction.
+pub async fn maintenance(storage: &dyn StorageBackend) {
+    storage.ping().await.unwrap();
+    let name = storage.backend_name();
+    assert!(
+        !name.is_empty() && name != "unknown",
+        "backends name themselves"
+    );
+
+    let agent = unique("agent");
+    let record = memory(&agent, "survives compaction", &at(1));
+    storage.insert_memory(&record).await.unwrap();
+    let doomed = memory(&agent, "hard deleted", &at(2));
+    storage.insert_memory(&doomed).await.unwrap();
+    storage.hard_delete_memory(doomed.id).await.unwrap();
+
+    let report = storage.compact().await.unwrap();
+    assert!(report.reclaimed_bytes() <= report.bytes_before);
+    assert_eq!(storage.get_memory(record.id).await.unwrap(), Some(record));
+}
@@ -0,0 +1,100 @@
+//! Transparent zstd compression of memory content.
+//!
+//! Backends store content of at least the compression threshold zstd-
+//! compressed in a `content_zstd` column, with its original size in
+//! `content_len` and an empty `content`, and decompress it when a row is
+//! read. Content that does not shrink, such as encrypted content, is
+//! stored as is. Rows written before compression was enabled are left
+//! alone until they are next updated, when they are compressed like any
+//! other write.
+
+/// Content size, in bytes, from which it is compressed by default.
+pub const DEFAULT_COMPRESSION_THRESHOLD: usize = 4096;
+
+/// zstd level: fast to write, and most of the gain on prose.
+const ZSTD_LEVEL: i32 = 3;
+
+/// One memory's content as it is written to storage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredContent<'a> {
+    /// The `content` column: the content itself, or empty when compressed.
+    pub text: &'a str,
+    /// The `content_zstd` column.
+    pub zstd: Option<Vec<u8>>,
+    /// The `content_len` column: the original size when compressed.
+    pub len: Option<i64>,
+}
+
+/// Compress `content` when it is at least `threshold` bytes and
+/// compression shrinks it. `None` disables compression.
+pub fn encode(content: &str, threshold: Option<usize>) -> StoredContent<'_> {
+    let uncompressed = StoredContent {
+        text: content,
+        zstd: None,
+        len: None,
+    };
+    let Some(threshold) = threshold else {
+        return uncompressed;
+    };
+    if content.len() < threshold {
+        return uncompressed;
+    }
+    match zstd::bulk::compress(content.as_bytes(), ZSTD_LEVEL) {
+        Ok(compressed) if compressed.len() < content.len() => StoredContent {
+            text: "",
+            zstd: Some(compressed),
+            len: Some(content.len() as i64),
+        },
+        Ok(_) => uncompressed,
+        Err(e) => {
+            tracing::warn!(error = %e, "failed to compress memory content; storing it as is");
+            uncompressed
+        }
+    }
+}
+
+/// Rebuild the content from its `content` and `content_zstd` columns.
+pub fn decode(
+    text: String,
+    zstd: Option<Vec<u8>>,
+) -> std::result::Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    match zstd {
+        Some(compressed) => Ok(String::from_utf8(zstd::decode_all(compressed.as_slice())?)?),
+        None => Ok(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_content_is_stored_as_is() {
+        let stored = encode("a short note", Some(DEFAULT_COMPRESSION_THRESHOLD));
+        assert_eq!(stored.text, "a short note");
+        assert_eq!(stored.zstd, None);
+        assert_eq!(stored.len, None);
+    }
+
+    #[test]
+    fn large_content_round_trips() {
+        let content = "the ingest workers restart after a failover. ".repeat(200);
+        let stored = encode(&content, Some(DEFAULT_COMPRESSION_THRESHOLD));
+        assert_eq!(stored.text, "");
+        assert_eq!(stored.len, Some(content.len() as i64));
+        let compressed = stored.zstd.unwrap();
+        assert!(compressed.len() < content.len());
+        assert_eq!(decode(String::new(), Some(compressed)).unwrap(), content);
+    }
+
+    #[test]
+    fn disabled_compression_stores_everything_as_is() {
+        let content = "x".repeat(DEFAULT_COMPRESSION_THRESHOLD * 2);
+        assert_eq!(encode(&content, None).zstd, None);
+    }
+
+    #[test]
+    fn corrupt_content_fails_to_decode() {
+        assert!(decode(String::new(), Some(vec![1, 2, 3])).is_err());
+    }
+}
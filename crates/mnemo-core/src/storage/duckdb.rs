@@ -1,21 +1,37 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use crate::error::{Error, Result};
+use crate::model::access_log::MemoryAccess;
+use crate::model::access_request::{AccessRequest, AccessRequestStatus};
 use crate::model::acl::{Acl, Permission};
-use crate::model::agent_profile::AgentProfile;
+use crate::model::agent_profile::{AgentProfile, RetrievalDefaults};
+use crate::model::chain_checkpoint::ChainCheckpoint;
+use crate::model::channel::{Channel, ChannelMember};
 use crate::model::checkpoint::Checkpoint;
+use crate::model::conflict::{ConflictRecord, ConflictStatus};
+use crate::model::content_stats::{ContentStorageStats, DailyGrowth};
 use crate::model::delegation::{Delegation, DelegationScope};
 use crate::model::embedding_baseline::EmbeddingBaseline;
 use crate::model::event::AgentEvent;
+use crate::model::event_archive::EventArchiveSegment;
 use crate::model::memory::MemoryRecord;
+use crate::model::outbox::OutboxMessage;
+use crate::model::pseudonym::PseudonymEntry;
+use crate::model::recall_profile::RecallProfile;
 use crate::model::relation::Relation;
-use crate::storage::{MemoryFilter, StorageBackend};
+use crate::model::share_link::RevokedShareLink;
+use crate::storage::content_codec::{self, DEFAULT_COMPRESSION_THRESHOLD};
+use crate::storage::{CompactionReport, MemoryColumns, MemoryFilter, StorageBackend, WriteBatch};
 use uuid::Uuid;
 
 pub struct DuckDbStorage {
     conn: Arc<Mutex<duckdb::Connection>>,
+    /// See [`DuckDbStorage::with_compression_threshold`].
+    compression_threshold: Option<usize>,
+    /// The database file; `None` in memory.
+    path: Option<PathBuf>,
 }
 
 impl DuckDbStorage {
@@ -24,6 +40,8 @@ impl DuckDbStorage {
         super::migrations::run_migrations(&conn)?;
         Ok(Self {
             conn: Arc::new(Mutex::new(conn)),
+            compression_threshold: Some(DEFAULT_COMPRESSION_THRESHOLD),
+            path: Some(path.to_path_buf()),
         })
     }
 
@@ -32,8 +50,66 @@ impl DuckDbStorage {
         super::migrations::run_migrations(&conn)?;
         Ok(Self {
             conn: Arc::new(Mutex::new(conn)),
+            compression_threshold: Some(DEFAULT_COMPRESSION_THRESHOLD),
+            path: None,
         })
     }
+
+    /// Store content of at least `threshold` bytes zstd-compressed; `None`
+    /// stores all content as is. Defaults to
+    /// [`DEFAULT_COMPRESSION_THRESHOLD`]. Only affects writes: reads
+    /// handle both forms. See [`content_codec`](super::content_codec).
+    pub fn with_compression_threshold(mut self, threshold: Option<usize>) -> Self {
+        self.compression_threshold = threshold;
+        self
+    }
+
+    /// Bytes on disk: the database file and its write-ahead log.
+    fn file_size(&self) -> u64 {
+        let Some(ref path) = self.path else {
+            return 0;
+        };
+        let mut wal = path.clone().into_os_string();
+        wal.push(".wal");
+        [path.as_os_str(), wal.as_os_str()]
+            .iter()
+            .filter_map(|p| std::fs::metadata(p).ok())
+            .map(|m| m.len())
+            .sum()
+    }
+
+    /// Memories `agent_id` can read, optionally only those created by
+    /// `as_of`.
+    async fn accessible_memory_ids(
+        &self,
+        agent_id: &str,
+        as_of: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<Uuid>> {
+        let conn = self.conn.lock().await;
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut stmt = conn.prepare(
+            "SELECT id FROM memories WHERE (agent_id = ? OR scope IN ('public', 'global') OR id IN (SELECT memory_id FROM acls WHERE principal_id = ? AND (expires_at IS NULL OR expires_at > ?)) OR channel IN (SELECT channel FROM channel_members WHERE agent_id = ?)) AND deleted_at IS NULL AND (CAST(? AS VARCHAR) IS NULL OR created_at <= ?) LIMIT ?",
+        )?;
+        let rows = stmt.query_map(
+            duckdb::params![
+                agent_id,
+                agent_id,
+                now,
+                agent_id,
+                as_of,
+                as_of,
+                limit as i64
+            ],
+            |row| row.get::<_, String>(0),
+        )?;
+        let mut ids = Vec::new();
+        for row in rows {
+            let id_str = row.map_err(|e| Error::Storage(e.to_string()))?;
+            ids.push(Uuid::parse_str(&id_str).map_err(|e| Error::Storage(e.to_string()))?);
+        }
+        Ok(ids)
+    }
 }
 
 fn serialize_embedding(embedding: &Option<Vec<f32>>) -> Option<Vec<u8>> {
@@ -51,8 +127,141 @@ fn deserialize_embedding(blob: Option<Vec<u8>>) -> Option<Vec<f32>> {
     })
 }
 
+const CONFLICT_COLUMNS: &str = "id, agent_id, memory_a, memory_b, similarity, reason, status, resolution, winner_id, resolved_by, created_at, resolved_at";
+
+fn row_to_conflict(row: &duckdb::Row<'_>) -> duckdb::Result<ConflictRecord> {
+    let parse_uuid = |idx: usize, s: String| {
+        Uuid::parse_str(&s).map_err(|e| {
+            duckdb::Error::FromSqlConversionFailure(idx, duckdb::types::Type::Text, Box::new(e))
+        })
+    };
+    let status: String = row.get(6)?;
+    let winner_id: Option<String> = row.get(8)?;
+    Ok(ConflictRecord {
+        id: parse_uuid(0, row.get(0)?)?,
+        agent_id: row.get(1)?,
+        memory_a: parse_uuid(2, row.get(2)?)?,
+        memory_b: parse_uuid(3, row.get(3)?)?,
+        similarity: row.get(4)?,
+        reason: row.get(5)?,
+        status: status.parse().map_err(|e: Error| {
+            duckdb::Error::FromSqlConversionFailure(
+                6,
+                duckdb::types::Type::Text,
+                e.to_string().into(),
+            )
+        })?,
+        resolution: row.get(7)?,
+        winner_id: winner_id.map(|s| parse_uuid(8, s)).transpose()?,
+        resolved_by: row.get(9)?,
+        created_at: row.get(10)?,
+        resolved_at: row.get(11)?,
+    })
+}
+
+fn row_to_channel(row: &duckdb::Row<'_>) -> duckdb::Result<Channel> {
+    Ok(Channel {
+        name: row.get(0)?,
+        created_by: row.get(1)?,
+        max_memories: row.get::<_, Option<i64>>(2)?.map(|n| n as u64),
+        created_at: row.get(3)?,
+    })
+}
+
+const ACCESS_REQUEST_COLUMNS: &str = "id, requester_id, owner_id, memory_ids, tags, justification, permission, expires_in_hours, status, decided_by, decision_note, acl_ids, created_at, decided_at";
+
+fn row_to_access_request(row: &duckdb::Row<'_>) -> duckdb::Result<AccessRequest> {
+    fn parse<T: std::str::FromStr>(idx: usize, s: String) -> duckdb::Result<T>
+    where
+        T::Err: std::fmt::Display,
+    {
+        s.parse().map_err(|e: T::Err| {
+            duckdb::Error::FromSqlConversionFailure(
+                idx,
+                duckdb::types::Type::Text,
+                e.to_string().into(),
+            )
+        })
+    }
+    fn json<T: serde::de::DeserializeOwned>(idx: usize, s: String) -> duckdb::Result<T> {
+        serde_json::from_str(&s).map_err(|e| {
+            duckdb::Error::FromSqlConversionFailure(idx, duckdb::types::Type::Text, Box::new(e))
+        })
+    }
+    Ok(AccessRequest {
+        id: parse(0, row.get(0)?)?,
+        requester_id: row.get(1)?,
+        owner_id: row.get(2)?,
+        memory_ids: json(3, row.get(3)?)?,
+        tags: json(4, row.get(4)?)?,
+        justification: row.get(5)?,
+        permission: parse(6, row.get(6)?)?,
+        expires_in_hours: row.get(7)?,
+        status: parse(8, row.get(8)?)?,
+        decided_by: row.get(9)?,
+        decision_note: row.get(10)?,
+        acl_ids: json(11, row.get(11)?)?,
+        created_at: row.get(12)?,
+        decided_at: row.get(13)?,
+    })
+}
+
+const MEMORY_ACCESS_COLUMNS: &str = "id, memory_id, principal, operation, query, accessed_at";
+
+fn row_to_memory_access(row: &duckdb::Row<'_>) -> duckdb::Result<MemoryAccess> {
+    let parse_uuid = |idx: usize, s: String| {
+        Uuid::parse_str(&s).map_err(|e| {
+            duckdb::Error::FromSqlConversionFailure(idx, duckdb::types::Type::Text, Box::new(e))
+        })
+    };
+    let operation: String = row.get(3)?;
+    Ok(MemoryAccess {
+        id: parse_uuid(0, row.get(0)?)?,
+        memory_id: parse_uuid(1, row.get(1)?)?,
+        principal: row.get(2)?,
+        operation: operation.parse().map_err(|e: Error| {
+            duckdb::Error::FromSqlConversionFailure(
+                3,
+                duckdb::types::Type::Text,
+                e.to_string().into(),
+            )
+        })?,
+        query: row.get(4)?,
+        accessed_at: row.get(5)?,
+    })
+}
+
+type RecallProfileParts = (String, String, String, i64, Option<String>, String, String);
+
+fn row_to_recall_profile_parts(row: &duckdb::Row<'_>) -> duckdb::Result<RecallProfileParts> {
+    Ok((
+        row.get(0)?,
+        row.get(1)?,
+        row.get(2)?,
+        row.get(3)?,
+        row.get(4)?,
+        row.get(5)?,
+        row.get(6)?,
+    ))
+}
+
+fn recall_profile_from_parts(parts: RecallProfileParts) -> Result<RecallProfile> {
+    let (agent_id, name, settings_json, use_count, last_used_at, created_at, updated_at) = parts;
+    Ok(RecallProfile {
+        agent_id,
+        name,
+        settings: serde_json::from_str(&settings_json)?,
+        use_count: use_count as u64,
+        last_used_at,
+        created_at,
+        updated_at,
+    })
+}
+
 fn row_to_memory(row: &duckdb::Row<'_>) -> duckdb::Result<MemoryRecord> {
     let id_str: String = row.get(0)?;
+    let content = content_codec::decode(row.get(2)?, row.get(35)?)
+        .map_err(|e| duckdb::Error::FromSqlConversionFailure(35, duckdb::types::Type::Blob, e))?;
     let tags_json: Option<String> = row.get(6)?;
     let metadata_json: Option<String> = row.get(7)?;
     let embedding_blob: Option<Vec<u8>> = row.get(8)?;
@@ -68,7 +277,7 @@ fn row_to_memory(row: &duckdb::Row<'_>) -> duckdb::Result<MemoryRecord> {
         id: Uuid::parse_str(&id_str)
             .map_err(|e| duckdb::Error::FromSqlConversionFailure(0, duckdb::types::Type::Text, Box::new(e)))?,
         agent_id: row.get(1)?,
-        content: row.get(2)?,
+        content,
         memory_type: memory_type_str.parse()
             .map_err(|e: Error| duckdb::Error::FromSqlConversionFailure(3, duckdb::types::Type::Text, e.to_string().into()))?,
         scope: scope_str.parse()
@@ -117,6 +326,267 @@ fn row_to_memory(row: &duckdb::Row<'_>) -> duckdb::Result<MemoryRecord> {
         quarantined: row.get::<_, bool>(26)?,
         quarantine_reason: row.get(27)?,
         decay_function: row.get(28).unwrap_or(None),
+        pinned: row.get::<_, Option<bool>>(29).unwrap_or(None).unwrap_or(false),
+        valid_from: row.get(30).unwrap_or(None),
+        valid_until: row.get(31).unwrap_or(None),
+        curation: row.get::<_, Option<String>>(32).unwrap_or(None).and_then(|s| s.parse().ok()),
+        attestation: row.get::<_, Option<String>>(33).unwrap_or(None).and_then(|s| s.parse().ok()),
+        channel: row.get(34).unwrap_or(None),
+    })
+}
+
+fn update_memory_row(
+    conn: &duckdb::Connection,
+    record: &MemoryRecord,
+    compression_threshold: Option<usize>,
+) -> Result<()> {
+    let content = content_codec::encode(&record.content, compression_threshold);
+    let tags_json = serde_json::to_string(&record.tags)?;
+    let metadata_json = serde_json::to_string(&record.metadata)?;
+    let embedding_blob = serialize_embedding(&record.embedding);
+
+    let affected = conn.execute(
+        "UPDATE memories SET agent_id=?, content=?, memory_type=?, scope=?, importance=?, tags=?, metadata=?, embedding=?, content_hash=?, prev_hash=?, source_type=?, source_id=?, consolidation_state=?, access_count=?, org_id=?, thread_id=?, updated_at=?, last_accessed_at=?, expires_at=?, deleted_at=?, decay_rate=?, created_by=?, version=?, prev_version_id=?, quarantined=?, quarantine_reason=?, decay_function=?, pinned=?, valid_from=?, valid_until=?, curation=?, attestation=?, channel=?, content_zstd=?, content_len=? WHERE id=?",
+        duckdb::params![
+            record.agent_id,
+            content.text,
+            record.memory_type.to_string(),
+            record.scope.to_string(),
+            record.importance,
+            tags_json,
+            metadata_json,
+            embedding_blob,
+            record.content_hash,
+            record.prev_hash,
+            record.source_type.to_string(),
+            record.source_id,
+            record.consolidation_state.to_string(),
+            record.access_count as i64,
+            record.org_id,
+            record.thread_id,
+            record.updated_at,
+            record.last_accessed_at,
+            record.expires_at,
+            record.deleted_at,
+            record.decay_rate,
+            record.created_by,
+            record.version as i32,
+            record.prev_version_id.map(|id| id.to_string()),
+            record.quarantined,
+            record.quarantine_reason,
+            record.decay_function,
+            record.pinned,
+            record.valid_from,
+            record.valid_until,
+            record.curation.map(|c| c.to_string()),
+            record.attestation.map(|a| a.to_string()),
+            record.channel,
+            content.zstd,
+            content.len,
+            record.id.to_string(),
+        ],
+    )?;
+    if affected == 0 {
+        return Err(Error::NotFound(format!("memory {} not found", record.id)));
+    }
+    Ok(())
+}
+
+fn insert_acl_row(conn: &duckdb::Connection, acl: &Acl) -> Result<()> {
+    conn.execute(
+        "INSERT INTO acls (id, memory_id, principal_type, principal_id, permission, granted_by, created_at, expires_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        duckdb::params![
+            acl.id.to_string(),
+            acl.memory_id.to_string(),
+            acl.principal_type.to_string(),
+            acl.principal_id,
+            acl.permission.to_string(),
+            acl.granted_by,
+            acl.created_at,
+            acl.expires_at,
+        ],
+    )?;
+    Ok(())
+}
+
+fn insert_memory_row(
+    conn: &duckdb::Connection,
+    record: &MemoryRecord,
+    compression_threshold: Option<usize>,
+) -> Result<()> {
+    let content = content_codec::encode(&record.content, compression_threshold);
+    let tags_json = serde_json::to_string(&record.tags)?;
+    let metadata_json = serde_json::to_string(&record.metadata)?;
+    let embedding_blob = serialize_embedding(&record.embedding);
+
+    conn.execute(
+        "INSERT INTO memories (id, agent_id, content, memory_type, scope, importance, tags, metadata, embedding, content_hash, prev_hash, source_type, source_id, consolidation_state, access_count, org_id, thread_id, created_at, updated_at, last_accessed_at, expires_at, deleted_at, decay_rate, created_by, version, prev_version_id, quarantined, quarantine_reason, decay_function, pinned, valid_from, valid_until, curation, attestation, channel, content_zstd, content_len) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        duckdb::params![
+            record.id.to_string(),
+            record.agent_id,
+            content.text,
+            record.memory_type.to_string(),
+            record.scope.to_string(),
+            record.importance,
+            tags_json,
+            metadata_json,
+            embedding_blob,
+            record.content_hash,
+            record.prev_hash,
+            record.source_type.to_string(),
+            record.source_id,
+            record.consolidation_state.to_string(),
+            record.access_count as i64,
+            record.org_id,
+            record.thread_id,
+            record.created_at,
+            record.updated_at,
+            record.last_accessed_at,
+            record.expires_at,
+            record.deleted_at,
+            record.decay_rate,
+            record.created_by,
+            record.version as i32,
+            record.prev_version_id.map(|id| id.to_string()),
+            record.quarantined,
+            record.quarantine_reason,
+            record.decay_function,
+            record.pinned,
+            record.valid_from,
+            record.valid_until,
+            record.curation.map(|c| c.to_string()),
+            record.attestation.map(|a| a.to_string()),
+            record.channel,
+            content.zstd,
+            content.len,
+        ],
+    )?;
+    Ok(())
+}
+
+fn insert_relation_row(conn: &duckdb::Connection, relation: &Relation) -> Result<()> {
+    conn.execute(
+        "INSERT INTO relations (id, source_id, target_id, relation_type, weight, metadata, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+        duckdb::params![
+            relation.id.to_string(),
+            relation.source_id.to_string(),
+            relation.target_id.to_string(),
+            relation.relation_type,
+            relation.weight,
+            serde_json::to_string(&relation.metadata)?,
+            relation.created_at,
+        ],
+    )?;
+    Ok(())
+}
+
+fn insert_event_row(conn: &duckdb::Connection, event: &AgentEvent) -> Result<()> {
+    let payload_json = serde_json::to_string(&event.payload)?;
+    let embedding_blob = serialize_embedding(&event.embedding);
+    conn.execute(
+        "INSERT INTO agent_events (id, agent_id, thread_id, run_id, parent_event_id, event_type, payload, trace_id, span_id, model, tokens_input, tokens_output, latency_ms, cost_usd, timestamp, logical_clock, content_hash, prev_hash, embedding, request_id) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        duckdb::params![
+            event.id.to_string(),
+            event.agent_id,
+            event.thread_id,
+            event.run_id,
+            event.parent_event_id.map(|id| id.to_string()),
+            event.event_type.to_string(),
+            payload_json,
+            event.trace_id,
+            event.span_id,
+            event.model,
+            event.tokens_input,
+            event.tokens_output,
+            event.latency_ms,
+            event.cost_usd,
+            event.timestamp,
+            event.logical_clock,
+            event.content_hash,
+            event.prev_hash,
+            embedding_blob,
+            event.request_id,
+        ],
+    )?;
+    Ok(())
+}
+
+fn insert_outbox_row(conn: &duckdb::Connection, message: &OutboxMessage) -> Result<()> {
+    conn.execute(
+        "INSERT INTO outbox (id, topic, payload, created_at, attempts, last_error, delivered_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+        duckdb::params![
+            message.id.to_string(),
+            message.topic,
+            serde_json::to_string(&message.payload)?,
+            message.created_at,
+            message.attempts as i32,
+            message.last_error,
+            message.delivered_at,
+        ],
+    )?;
+    Ok(())
+}
+
+const OUTBOX_COLUMNS: &str = "id, topic, payload, created_at, attempts, last_error, delivered_at";
+
+fn row_to_outbox(row: &duckdb::Row<'_>) -> duckdb::Result<OutboxMessage> {
+    let id: String = row.get(0)?;
+    let payload: String = row.get(2)?;
+    Ok(OutboxMessage {
+        id: Uuid::parse_str(&id).map_err(|e| {
+            duckdb::Error::FromSqlConversionFailure(0, duckdb::types::Type::Text, Box::new(e))
+        })?,
+        topic: row.get(1)?,
+        payload: serde_json::from_str(&payload).map_err(|e| {
+            duckdb::Error::FromSqlConversionFailure(2, duckdb::types::Type::Text, Box::new(e))
+        })?,
+        created_at: row.get(3)?,
+        attempts: u32::try_from(row.get::<_, i32>(4)?).unwrap_or(0),
+        last_error: row.get(5)?,
+        delivered_at: row.get(6)?,
+    })
+}
+
+const EVENT_ARCHIVE_COLUMNS: &str = "id, agent_id, first_event_id, last_event_id, from_timestamp, to_timestamp, first_logical_clock, last_logical_clock, event_count, merkle_root, last_content_hash, object_key, archived_at";
+
+fn row_to_event_archive(row: &duckdb::Row<'_>) -> duckdb::Result<EventArchiveSegment> {
+    let uuid_at = |idx: usize| -> duckdb::Result<Uuid> {
+        let s: String = row.get(idx)?;
+        Uuid::parse_str(&s).map_err(|e| {
+            duckdb::Error::FromSqlConversionFailure(idx, duckdb::types::Type::Text, Box::new(e))
+        })
+    };
+    Ok(EventArchiveSegment {
+        id: uuid_at(0)?,
+        agent_id: row.get(1)?,
+        first_event_id: uuid_at(2)?,
+        last_event_id: uuid_at(3)?,
+        from_timestamp: row.get(4)?,
+        to_timestamp: row.get(5)?,
+        first_logical_clock: row.get(6)?,
+        last_logical_clock: row.get(7)?,
+        event_count: usize::try_from(row.get::<_, i64>(8)?).unwrap_or(0),
+        merkle_root: row.get(9)?,
+        last_content_hash: row.get(10)?,
+        object_key: row.get(11)?,
+        archived_at: row.get(12)?,
+    })
+}
+
+fn row_to_chain_checkpoint(row: &duckdb::Row<'_>) -> duckdb::Result<ChainCheckpoint> {
+    let thread_id: String = row.get(1)?;
+    let last_record_id: String = row.get(3)?;
+    Ok(ChainCheckpoint {
+        agent_id: row.get(0)?,
+        thread_id: (!thread_id.is_empty()).then_some(thread_id),
+        verified_count: u64::try_from(row.get::<_, i64>(2)?).unwrap_or(0),
+        last_record_id: Uuid::parse_str(&last_record_id).map_err(|e| {
+            duckdb::Error::FromSqlConversionFailure(3, duckdb::types::Type::Text, Box::new(e))
+        })?,
+        last_created_at: row.get(4)?,
+        last_content_hash: row.get(5)?,
+        rolling_root: row.get(6)?,
+        verified_at: row.get(7)?,
     })
 }
 
@@ -126,53 +596,21 @@ impl StorageBackend for DuckDbStorage {
         "duckdb"
     }
 
-    async fn insert_memory(&self, record: &MemoryRecord) -> Result<()> {
+    async fn ping(&self) -> Result<()> {
         let conn = self.conn.lock().await;
-        let tags_json = serde_json::to_string(&record.tags)?;
-        let metadata_json = serde_json::to_string(&record.metadata)?;
-        let embedding_blob = serialize_embedding(&record.embedding);
-
-        conn.execute(
-            "INSERT INTO memories (id, agent_id, content, memory_type, scope, importance, tags, metadata, embedding, content_hash, prev_hash, source_type, source_id, consolidation_state, access_count, org_id, thread_id, created_at, updated_at, last_accessed_at, expires_at, deleted_at, decay_rate, created_by, version, prev_version_id, quarantined, quarantine_reason, decay_function) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-            duckdb::params![
-                record.id.to_string(),
-                record.agent_id,
-                record.content,
-                record.memory_type.to_string(),
-                record.scope.to_string(),
-                record.importance,
-                tags_json,
-                metadata_json,
-                embedding_blob,
-                record.content_hash,
-                record.prev_hash,
-                record.source_type.to_string(),
-                record.source_id,
-                record.consolidation_state.to_string(),
-                record.access_count as i64,
-                record.org_id,
-                record.thread_id,
-                record.created_at,
-                record.updated_at,
-                record.last_accessed_at,
-                record.expires_at,
-                record.deleted_at,
-                record.decay_rate,
-                record.created_by,
-                record.version as i32,
-                record.prev_version_id.map(|id| id.to_string()),
-                record.quarantined,
-                record.quarantine_reason,
-                record.decay_function,
-            ],
-        )?;
+        conn.query_row("SELECT 1", [], |row| row.get::<_, i32>(0))?;
         Ok(())
     }
 
+    async fn insert_memory(&self, record: &MemoryRecord) -> Result<()> {
+        let conn = self.conn.lock().await;
+        insert_memory_row(&conn, record, self.compression_threshold)
+    }
+
     async fn get_memory(&self, id: Uuid) -> Result<Option<MemoryRecord>> {
         let conn = self.conn.lock().await;
         let mut stmt = conn.prepare(
-            "SELECT id, agent_id, content, memory_type, scope, importance, tags, metadata, embedding, content_hash, prev_hash, source_type, source_id, consolidation_state, access_count, org_id, thread_id, created_at, updated_at, last_accessed_at, expires_at, deleted_at, decay_rate, created_by, version, prev_version_id, quarantined, quarantine_reason, decay_function FROM memories WHERE id = ?",
+            "SELECT id, agent_id, content, memory_type, scope, importance, tags, metadata, embedding, content_hash, prev_hash, source_type, source_id, consolidation_state, access_count, org_id, thread_id, created_at, updated_at, last_accessed_at, expires_at, deleted_at, decay_rate, created_by, version, prev_version_id, quarantined, quarantine_reason, decay_function, pinned, valid_from, valid_until, curation, attestation, channel, content_zstd FROM memories WHERE id = ?",
         )?;
         let result = stmt.query_row([id.to_string()], row_to_memory);
         match result {
@@ -182,49 +620,72 @@ impl StorageBackend for DuckDbStorage {
         }
     }
 
+    async fn get_memories(
+        &self,
+        ids: &[Uuid],
+        columns: MemoryColumns,
+    ) -> Result<Vec<MemoryRecord>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let conn = self.conn.lock().await;
+        // Columns left out are replaced by placeholders so `row_to_memory`
+        // still sees every position.
+        let content = if columns.content {
+            "content"
+        } else {
+            "'' AS content"
+        };
+        let metadata = if columns.metadata {
+            "metadata"
+        } else {
+            "NULL AS metadata"
+        };
+        let content_zstd = if columns.content {
+            "content_zstd"
+        } else {
+            "NULL AS content_zstd"
+        };
+        let placeholders = vec!["?"; ids.len()].join(", ");
+        let sql = format!(
+            "SELECT id, agent_id, {content}, memory_type, scope, importance, tags, {metadata}, NULL AS embedding, content_hash, prev_hash, source_type, source_id, consolidation_state, access_count, org_id, thread_id, created_at, updated_at, last_accessed_at, expires_at, deleted_at, decay_rate, created_by, version, prev_version_id, quarantined, quarantine_reason, decay_function, pinned, valid_from, valid_until, curation, attestation, channel, {content_zstd} FROM memories WHERE id IN ({placeholders})"
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let params: Vec<String> = ids.iter().map(Uuid::to_string).collect();
+        let rows = stmt.query_map(duckdb::params_from_iter(params.iter()), row_to_memory)?;
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row.map_err(|e| Error::Storage(e.to_string()))?);
+        }
+        Ok(results)
+    }
+
     async fn update_memory(&self, record: &MemoryRecord) -> Result<()> {
         let conn = self.conn.lock().await;
-        let tags_json = serde_json::to_string(&record.tags)?;
-        let metadata_json = serde_json::to_string(&record.metadata)?;
-        let embedding_blob = serialize_embedding(&record.embedding);
+        update_memory_row(&conn, record, self.compression_threshold)
+    }
 
-        let affected = conn.execute(
-            "UPDATE memories SET agent_id=?, content=?, memory_type=?, scope=?, importance=?, tags=?, metadata=?, embedding=?, content_hash=?, prev_hash=?, source_type=?, source_id=?, consolidation_state=?, access_count=?, org_id=?, thread_id=?, updated_at=?, last_accessed_at=?, expires_at=?, deleted_at=?, decay_rate=?, created_by=?, version=?, prev_version_id=?, quarantined=?, quarantine_reason=?, decay_function=? WHERE id=?",
-            duckdb::params![
-                record.agent_id,
-                record.content,
-                record.memory_type.to_string(),
-                record.scope.to_string(),
-                record.importance,
-                tags_json,
-                metadata_json,
-                embedding_blob,
-                record.content_hash,
-                record.prev_hash,
-                record.source_type.to_string(),
-                record.source_id,
-                record.consolidation_state.to_string(),
-                record.access_count as i64,
-                record.org_id,
-                record.thread_id,
-                record.updated_at,
-                record.last_accessed_at,
-                record.expires_at,
-                record.deleted_at,
-                record.decay_rate,
-                record.created_by,
-                record.version as i32,
-                record.prev_version_id.map(|id| id.to_string()),
-                record.quarantined,
-                record.quarantine_reason,
-                record.decay_function,
-                record.id.to_string(),
-            ],
-        )?;
-        if affected == 0 {
-            return Err(Error::NotFound(format!("memory {} not found", record.id)));
+    async fn update_memory_if_version(
+        &self,
+        record: &MemoryRecord,
+        expected_version: u32,
+    ) -> Result<bool> {
+        // Every statement goes through the one connection lock, so the
+        // check and the write cannot interleave with another writer.
+        let conn = self.conn.lock().await;
+        let stored = conn.query_row(
+            "SELECT version FROM memories WHERE id = ?",
+            [record.id.to_string()],
+            |row| row.get::<_, i32>(0),
+        );
+        match stored {
+            Ok(version) if version as u32 == expected_version => {
+                update_memory_row(&conn, record, self.compression_threshold)?;
+                Ok(true)
+            }
+            Ok(_) | Err(duckdb::Error::QueryReturnedNoRows) => Ok(false),
+            Err(e) => Err(Error::Storage(e.to_string())),
         }
-        Ok(())
     }
 
     async fn soft_delete_memory(&self, id: Uuid) -> Result<()> {
@@ -288,6 +749,19 @@ impl StorageBackend for DuckDbStorage {
             params.push(Box::new(scope.to_string()));
         }
 
+        if let Some(ref tags) = filter.tags {
+            let mut any = Vec::with_capacity(tags.len());
+            for tag in tags {
+                any.push(format!("json_contains(tags, ${})", params.len() + 1));
+                params.push(Box::new(serde_json::to_string(tag)?));
+            }
+            if any.is_empty() {
+                conditions.push("FALSE".to_string());
+            } else {
+                conditions.push(format!("({})", any.join(" OR ")));
+            }
+        }
+
         if let Some(min_importance) = filter.min_importance {
             conditions.push(format!("importance >= ${}", params.len() + 1));
             params.push(Box::new(min_importance));
@@ -310,7 +784,7 @@ impl StorageBackend for DuckDbStorage {
         };
 
         let sql = format!(
-            "SELECT id, agent_id, content, memory_type, scope, importance, tags, metadata, embedding, content_hash, prev_hash, source_type, source_id, consolidation_state, access_count, org_id, thread_id, created_at, updated_at, last_accessed_at, expires_at, deleted_at, decay_rate, created_by, version, prev_version_id, quarantined, quarantine_reason, decay_function FROM memories {where_clause} ORDER BY created_at DESC LIMIT {limit} OFFSET {offset}"
+            "SELECT id, agent_id, content, memory_type, scope, importance, tags, metadata, embedding, content_hash, prev_hash, source_type, source_id, consolidation_state, access_count, org_id, thread_id, created_at, updated_at, last_accessed_at, expires_at, deleted_at, decay_rate, created_by, version, prev_version_id, quarantined, quarantine_reason, decay_function, pinned, valid_from, valid_until, curation, attestation, channel, content_zstd FROM memories {where_clause} ORDER BY created_at DESC LIMIT {limit} OFFSET {offset}"
         );
 
         let mut stmt = conn.prepare(&sql)?;
@@ -336,20 +810,7 @@ impl StorageBackend for DuckDbStorage {
 
     async fn insert_acl(&self, acl: &Acl) -> Result<()> {
         let conn = self.conn.lock().await;
-        conn.execute(
-            "INSERT INTO acls (id, memory_id, principal_type, principal_id, permission, granted_by, created_at, expires_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
-            duckdb::params![
-                acl.id.to_string(),
-                acl.memory_id.to_string(),
-                acl.principal_type.to_string(),
-                acl.principal_id,
-                acl.permission.to_string(),
-                acl.granted_by,
-                acl.created_at,
-                acl.expires_at,
-            ],
-        )?;
-        Ok(())
+        insert_acl_row(&conn, acl)
     }
 
     async fn check_permission(
@@ -363,15 +824,31 @@ impl StorageBackend for DuckDbStorage {
             let conn = self.conn.lock().await;
 
             // Check if the principal is the owner (agent_id matches)
-            let mut stmt = conn.prepare("SELECT agent_id FROM memories WHERE id = ?")?;
-            let owner_result =
-                stmt.query_row([memory_id.to_string()], |row| row.get::<_, String>(0));
-            match owner_result {
-                Ok(owner) if owner == principal_id => return Ok(true),
+            let mut stmt = conn.prepare("SELECT agent_id, channel FROM memories WHERE id = ?")?;
+            let owner_result = stmt.query_row([memory_id.to_string()], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+            });
+            let channel = match owner_result {
+                Ok((owner, _)) if owner == principal_id => return Ok(true),
+                Ok((_, channel)) => channel,
                 Err(duckdb::Error::QueryReturnedNoRows) => {
                     return Err(Error::NotFound(format!("memory {memory_id} not found")));
                 }
-                _ => {}
+                Err(_) => None,
+            };
+
+            // Channel members can read the channel's memories
+            if let Some(channel) = channel
+                && Permission::Read.satisfies(required)
+            {
+                let mut stmt = conn.prepare(
+                    "SELECT COUNT(*) FROM channel_members WHERE channel = ? AND agent_id = ?",
+                )?;
+                let members: i64 =
+                    stmt.query_row(duckdb::params![channel, principal_id], |row| row.get(0))?;
+                if members > 0 {
+                    return Ok(true);
+                }
             }
 
             // Check ACLs
@@ -425,19 +902,7 @@ impl StorageBackend for DuckDbStorage {
 
     async fn insert_relation(&self, relation: &Relation) -> Result<()> {
         let conn = self.conn.lock().await;
-        conn.execute(
-            "INSERT INTO relations (id, source_id, target_id, relation_type, weight, metadata, created_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
-            duckdb::params![
-                relation.id.to_string(),
-                relation.source_id.to_string(),
-                relation.target_id.to_string(),
-                relation.relation_type,
-                relation.weight,
-                serde_json::to_string(&relation.metadata)?,
-                relation.created_at,
-            ],
-        )?;
-        Ok(())
+        insert_relation_row(&conn, relation)
     }
 
     async fn get_relations_from(&self, source_id: Uuid) -> Result<Vec<Relation>> {
@@ -515,14 +980,14 @@ impl StorageBackend for DuckDbStorage {
         let conn = self.conn.lock().await;
         let result = if let Some(tid) = thread_id {
             let mut stmt = conn.prepare(
-                "SELECT content_hash FROM agent_events WHERE agent_id = ? AND thread_id = ? ORDER BY timestamp DESC LIMIT 1",
+                "SELECT content_hash FROM agent_events WHERE agent_id = ? AND thread_id = ? ORDER BY logical_clock DESC, timestamp DESC LIMIT 1",
             )?;
             stmt.query_row(duckdb::params![agent_id, tid], |row| {
                 row.get::<_, Vec<u8>>(0)
             })
         } else {
             let mut stmt = conn.prepare(
-                "SELECT content_hash FROM agent_events WHERE agent_id = ? ORDER BY timestamp DESC LIMIT 1",
+                "SELECT content_hash FROM agent_events WHERE agent_id = ? ORDER BY logical_clock DESC, timestamp DESC LIMIT 1",
             )?;
             stmt.query_row(duckdb::params![agent_id], |row| row.get::<_, Vec<u8>>(0))
         };
@@ -533,6 +998,16 @@ impl StorageBackend for DuckDbStorage {
         }
     }
 
+    async fn max_logical_clock(&self, agent_id: &str) -> Result<i64> {
+        let conn = self.conn.lock().await;
+        let clock: i64 = conn.query_row(
+            "SELECT COALESCE(MAX(logical_clock), 0) FROM agent_events WHERE agent_id = ?",
+            [agent_id],
+            |row| row.get(0),
+        )?;
+        Ok(clock)
+    }
+
     async fn get_sync_watermark(&self, key: &str) -> Result<Option<String>> {
         let conn = self.conn.lock().await;
         let mut stmt = conn.prepare("SELECT value FROM sync_metadata WHERE key = ?")?;
@@ -561,55 +1036,94 @@ impl StorageBackend for DuckDbStorage {
         Ok(())
     }
 
-    async fn list_accessible_memory_ids(&self, agent_id: &str, limit: usize) -> Result<Vec<Uuid>> {
+    async fn revoke_share_link(&self, revoked: &RevokedShareLink) -> Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "INSERT OR IGNORE INTO revoked_share_links (link_id, memory_id, revoked_by, revoked_at, expires_at) VALUES (?, ?, ?, ?, ?)",
+            duckdb::params![
+                revoked.link_id.to_string(),
+                revoked.memory_id.to_string(),
+                revoked.revoked_by,
+                revoked.revoked_at,
+                revoked.expires_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    async fn is_share_link_revoked(&self, link_id: Uuid, memory_id: Uuid) -> Result<bool> {
         let conn = self.conn.lock().await;
-        let now = chrono::Utc::now().to_rfc3339();
         let mut stmt = conn.prepare(
-            "SELECT id FROM memories WHERE (agent_id = ? OR scope = 'public' OR id IN (SELECT memory_id FROM acls WHERE principal_id = ? AND (expires_at IS NULL OR expires_at > ?))) AND deleted_at IS NULL LIMIT ?",
+            "SELECT COUNT(*) FROM revoked_share_links WHERE link_id = ? AND memory_id = ?",
         )?;
-        let rows = stmt.query_map(
-            duckdb::params![agent_id, agent_id, now, limit as i64],
-            |row| row.get::<_, String>(0),
+        let count: i64 = stmt.query_row([link_id.to_string(), memory_id.to_string()], |row| {
+            row.get(0)
+        })?;
+        Ok(count > 0)
+    }
+
+    async fn purge_revoked_share_links(&self, now: &str) -> Result<usize> {
+        let conn = self.conn.lock().await;
+        let purged = conn.execute(
+            "DELETE FROM revoked_share_links WHERE expires_at < ?",
+            [now],
         )?;
-        let mut ids = Vec::new();
-        for row in rows {
-            let id_str = row.map_err(|e| Error::Storage(e.to_string()))?;
-            ids.push(Uuid::parse_str(&id_str).map_err(|e| Error::Storage(e.to_string()))?);
-        }
-        Ok(ids)
+        Ok(purged)
     }
 
-    async fn insert_event(&self, event: &AgentEvent) -> Result<()> {
+    async fn record_pseudonym(&self, entry: &PseudonymEntry) -> Result<()> {
         let conn = self.conn.lock().await;
-        let payload_json = serde_json::to_string(&event.payload)?;
-        let embedding_blob = serialize_embedding(&event.embedding);
         conn.execute(
-            "INSERT INTO agent_events (id, agent_id, thread_id, run_id, parent_event_id, event_type, payload, trace_id, span_id, model, tokens_input, tokens_output, latency_ms, cost_usd, timestamp, logical_clock, content_hash, prev_hash, embedding) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            "INSERT OR IGNORE INTO pseudonyms (org_id, alias, sealed_agent_id, created_at) VALUES (?, ?, ?, ?)",
             duckdb::params![
-                event.id.to_string(),
-                event.agent_id,
-                event.thread_id,
-                event.run_id,
-                event.parent_event_id.map(|id| id.to_string()),
-                event.event_type.to_string(),
-                payload_json,
-                event.trace_id,
-                event.span_id,
-                event.model,
-                event.tokens_input,
-                event.tokens_output,
-                event.latency_ms,
-                event.cost_usd,
-                event.timestamp,
-                event.logical_clock,
-                event.content_hash,
-                event.prev_hash,
-                embedding_blob,
+                entry.org_id,
+                entry.alias,
+                entry.sealed_agent_id,
+                entry.created_at,
             ],
         )?;
         Ok(())
     }
 
+    async fn get_pseudonym(&self, org_id: &str, alias: &str) -> Result<Option<PseudonymEntry>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT org_id, alias, sealed_agent_id, created_at FROM pseudonyms WHERE org_id = ? AND alias = ?",
+        )?;
+        let result = stmt.query_row([org_id, alias], |row| {
+            Ok(PseudonymEntry {
+                org_id: row.get(0)?,
+                alias: row.get(1)?,
+                sealed_agent_id: row.get(2)?,
+                created_at: row.get(3)?,
+            })
+        });
+        match result {
+            Ok(entry) => Ok(Some(entry)),
+            Err(duckdb::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(Error::Storage(e.to_string())),
+        }
+    }
+
+    async fn list_accessible_memory_ids(&self, agent_id: &str, limit: usize) -> Result<Vec<Uuid>> {
+        self.accessible_memory_ids(agent_id, None, limit).await
+    }
+
+    async fn list_accessible_memory_ids_as_of(
+        &self,
+        agent_id: &str,
+        as_of: &str,
+        limit: usize,
+    ) -> Result<Vec<Uuid>> {
+        self.accessible_memory_ids(agent_id, Some(as_of), limit)
+            .await
+    }
+
+    async fn insert_event(&self, event: &AgentEvent) -> Result<()> {
+        let conn = self.conn.lock().await;
+        insert_event_row(&conn, event)
+    }
+
     async fn list_events(
         &self,
         agent_id: &str,
@@ -618,7 +1132,7 @@ impl StorageBackend for DuckDbStorage {
     ) -> Result<Vec<AgentEvent>> {
         let conn = self.conn.lock().await;
         let mut stmt = conn.prepare(
-            "SELECT id, agent_id, thread_id, run_id, parent_event_id, event_type, payload, trace_id, span_id, model, tokens_input, tokens_output, latency_ms, cost_usd, timestamp, logical_clock, content_hash, prev_hash, embedding FROM agent_events WHERE agent_id = ? ORDER BY timestamp DESC LIMIT ? OFFSET ?",
+            "SELECT id, agent_id, thread_id, run_id, parent_event_id, event_type, payload, trace_id, span_id, model, tokens_input, tokens_output, latency_ms, cost_usd, timestamp, logical_clock, content_hash, prev_hash, embedding, request_id FROM agent_events WHERE agent_id = ? ORDER BY timestamp DESC LIMIT ? OFFSET ?",
         )?;
         let rows = stmt.query_map(
             duckdb::params![agent_id, limit as i64, offset as i64],
@@ -634,7 +1148,7 @@ impl StorageBackend for DuckDbStorage {
     async fn get_events_by_thread(&self, thread_id: &str, limit: usize) -> Result<Vec<AgentEvent>> {
         let conn = self.conn.lock().await;
         let mut stmt = conn.prepare(
-            "SELECT id, agent_id, thread_id, run_id, parent_event_id, event_type, payload, trace_id, span_id, model, tokens_input, tokens_output, latency_ms, cost_usd, timestamp, logical_clock, content_hash, prev_hash, embedding FROM agent_events WHERE thread_id = ? ORDER BY timestamp ASC LIMIT ?",
+            "SELECT id, agent_id, thread_id, run_id, parent_event_id, event_type, payload, trace_id, span_id, model, tokens_input, tokens_output, latency_ms, cost_usd, timestamp, logical_clock, content_hash, prev_hash, embedding, request_id FROM agent_events WHERE thread_id = ? ORDER BY timestamp ASC LIMIT ?",
         )?;
         let rows = stmt.query_map(duckdb::params![thread_id, limit as i64], row_to_event)?;
         let mut results = Vec::new();
@@ -647,7 +1161,7 @@ impl StorageBackend for DuckDbStorage {
     async fn get_event(&self, id: Uuid) -> Result<Option<AgentEvent>> {
         let conn = self.conn.lock().await;
         let mut stmt = conn.prepare(
-            "SELECT id, agent_id, thread_id, run_id, parent_event_id, event_type, payload, trace_id, span_id, model, tokens_input, tokens_output, latency_ms, cost_usd, timestamp, logical_clock, content_hash, prev_hash, embedding FROM agent_events WHERE id = ?",
+            "SELECT id, agent_id, thread_id, run_id, parent_event_id, event_type, payload, trace_id, span_id, model, tokens_input, tokens_output, latency_ms, cost_usd, timestamp, logical_clock, content_hash, prev_hash, embedding, request_id FROM agent_events WHERE id = ?",
         )?;
         let result = stmt.query_row([id.to_string()], row_to_event);
         match result {
@@ -664,7 +1178,7 @@ impl StorageBackend for DuckDbStorage {
     ) -> Result<Vec<AgentEvent>> {
         let conn = self.conn.lock().await;
         let mut stmt = conn.prepare(
-            "SELECT id, agent_id, thread_id, run_id, parent_event_id, event_type, payload, trace_id, span_id, model, tokens_input, tokens_output, latency_ms, cost_usd, timestamp, logical_clock, content_hash, prev_hash, embedding FROM agent_events WHERE parent_event_id = ? ORDER BY timestamp ASC LIMIT ?",
+            "SELECT id, agent_id, thread_id, run_id, parent_event_id, event_type, payload, trace_id, span_id, model, tokens_input, tokens_output, latency_ms, cost_usd, timestamp, logical_clock, content_hash, prev_hash, embedding, request_id FROM agent_events WHERE parent_event_id = ? ORDER BY timestamp ASC LIMIT ?",
         )?;
         let rows = stmt.query_map(
             duckdb::params![parent_event_id.to_string(), limit as i64],
@@ -677,6 +1191,89 @@ impl StorageBackend for DuckDbStorage {
         Ok(results)
     }
 
+    async fn list_events_before(&self, cutoff: &str, limit: usize) -> Result<Vec<AgentEvent>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT id, agent_id, thread_id, run_id, parent_event_id, event_type, payload, trace_id, span_id, model, tokens_input, tokens_output, latency_ms, cost_usd, timestamp, logical_clock, content_hash, prev_hash, embedding, request_id FROM agent_events WHERE timestamp < ? ORDER BY agent_id, logical_clock, timestamp, id LIMIT ?",
+        )?;
+        let rows = stmt.query_map(duckdb::params![cutoff, limit as i64], row_to_event)?;
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row.map_err(|e| Error::Storage(e.to_string()))?);
+        }
+        Ok(results)
+    }
+
+    async fn archive_event_segment(
+        &self,
+        segment: &EventArchiveSegment,
+        event_ids: &[Uuid],
+    ) -> Result<()> {
+        let mut conn = self.conn.lock().await;
+        let tx = conn.transaction()?;
+        tx.execute(
+            &format!(
+                "INSERT INTO event_archives ({EVENT_ARCHIVE_COLUMNS}) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            ),
+            duckdb::params![
+                segment.id.to_string(),
+                segment.agent_id,
+                segment.first_event_id.to_string(),
+                segment.last_event_id.to_string(),
+                segment.from_timestamp,
+                segment.to_timestamp,
+                segment.first_logical_clock,
+                segment.last_logical_clock,
+                segment.event_count as i64,
+                segment.merkle_root,
+                segment.last_content_hash,
+                segment.object_key,
+                segment.archived_at,
+            ],
+        )?;
+        for id in event_ids {
+            tx.execute("DELETE FROM agent_events WHERE id = ?", [id.to_string()])?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    async fn get_event_archive(&self, id: Uuid) -> Result<Option<EventArchiveSegment>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {EVENT_ARCHIVE_COLUMNS} FROM event_archives WHERE id = ?"
+        ))?;
+        match stmt.query_row([id.to_string()], row_to_event_archive) {
+            Ok(segment) => Ok(Some(segment)),
+            Err(duckdb::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(Error::Storage(e.to_string())),
+        }
+    }
+
+    async fn list_event_archives(
+        &self,
+        agent_id: Option<&str>,
+    ) -> Result<Vec<EventArchiveSegment>> {
+        let conn = self.conn.lock().await;
+        let filter = if agent_id.is_some() {
+            "WHERE agent_id = ?"
+        } else {
+            ""
+        };
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {EVENT_ARCHIVE_COLUMNS} FROM event_archives {filter} ORDER BY from_timestamp, id"
+        ))?;
+        let rows = match agent_id {
+            Some(agent_id) => stmt.query_map([agent_id], row_to_event_archive)?,
+            None => stmt.query_map([], row_to_event_archive)?,
+        };
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row.map_err(|e| Error::Storage(e.to_string()))?);
+        }
+        Ok(results)
+    }
+
     async fn list_memories_by_agent_ordered(
         &self,
         agent_id: &str,
@@ -686,7 +1283,7 @@ impl StorageBackend for DuckDbStorage {
         let conn = self.conn.lock().await;
         let (result,) = if let Some(tid) = thread_id {
             let mut stmt = conn.prepare(
-                "SELECT id, agent_id, content, memory_type, scope, importance, tags, metadata, embedding, content_hash, prev_hash, source_type, source_id, consolidation_state, access_count, org_id, thread_id, created_at, updated_at, last_accessed_at, expires_at, deleted_at, decay_rate, created_by, version, prev_version_id, quarantined, quarantine_reason, decay_function FROM memories WHERE agent_id = ? AND thread_id = ? AND deleted_at IS NULL ORDER BY created_at ASC LIMIT ?",
+                "SELECT id, agent_id, content, memory_type, scope, importance, tags, metadata, embedding, content_hash, prev_hash, source_type, source_id, consolidation_state, access_count, org_id, thread_id, created_at, updated_at, last_accessed_at, expires_at, deleted_at, decay_rate, created_by, version, prev_version_id, quarantined, quarantine_reason, decay_function, pinned, valid_from, valid_until, curation, attestation, channel, content_zstd FROM memories WHERE agent_id = ? AND thread_id = ? AND deleted_at IS NULL ORDER BY created_at ASC LIMIT ?",
             )?;
             let rows =
                 stmt.query_map(duckdb::params![agent_id, tid, limit as i64], row_to_memory)?;
@@ -697,7 +1294,7 @@ impl StorageBackend for DuckDbStorage {
             (results,)
         } else {
             let mut stmt = conn.prepare(
-                "SELECT id, agent_id, content, memory_type, scope, importance, tags, metadata, embedding, content_hash, prev_hash, source_type, source_id, consolidation_state, access_count, org_id, thread_id, created_at, updated_at, last_accessed_at, expires_at, deleted_at, decay_rate, created_by, version, prev_version_id, quarantined, quarantine_reason, decay_function FROM memories WHERE agent_id = ? AND deleted_at IS NULL ORDER BY created_at ASC LIMIT ?",
+                "SELECT id, agent_id, content, memory_type, scope, importance, tags, metadata, embedding, content_hash, prev_hash, source_type, source_id, consolidation_state, access_count, org_id, thread_id, created_at, updated_at, last_accessed_at, expires_at, deleted_at, decay_rate, created_by, version, prev_version_id, quarantined, quarantine_reason, decay_function, pinned, valid_from, valid_until, curation, attestation, channel, content_zstd FROM memories WHERE agent_id = ? AND deleted_at IS NULL ORDER BY created_at ASC LIMIT ?",
             )?;
             let rows = stmt.query_map(duckdb::params![agent_id, limit as i64], row_to_memory)?;
             let mut results = Vec::new();
@@ -709,14 +1306,111 @@ impl StorageBackend for DuckDbStorage {
         Ok(result)
     }
 
-    async fn list_memories_since(
+    async fn list_memories_chain_page(
         &self,
-        updated_after: &str,
-        limit: usize,
+        agent_id: &str,
+        thread_id: Option<&str>,
+        after: Option<(&str, Uuid)>,
+        limit: usize,
     ) -> Result<Vec<MemoryRecord>> {
+        let conn = self.conn.lock().await;
+        let mut conditions = vec!["agent_id = ?", "deleted_at IS NULL"];
+        let mut params = vec![agent_id.to_string()];
+        if let Some(tid) = thread_id {
+            conditions.push("thread_id = ?");
+            params.push(tid.to_string());
+        }
+        if let Some((created_at, id)) = after {
+            conditions.push("(created_at > ? OR (created_at = ? AND id > ?))");
+            params.extend([
+                created_at.to_string(),
+                created_at.to_string(),
+                id.to_string(),
+            ]);
+        }
+        let sql = format!(
+            "SELECT id, agent_id, content, memory_type, scope, importance, tags, metadata, embedding, content_hash, prev_hash, source_type, source_id, consolidation_state, access_count, org_id, thread_id, created_at, updated_at, last_accessed_at, expires_at, deleted_at, decay_rate, created_by, version, prev_version_id, quarantined, quarantine_reason, decay_function, pinned, valid_from, valid_until, curation, attestation, channel, content_zstd FROM memories WHERE {} ORDER BY created_at ASC, id ASC LIMIT {limit}",
+            conditions.join(" AND ")
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(duckdb::params_from_iter(params.iter()), row_to_memory)?;
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row.map_err(|e| Error::Storage(e.to_string()))?);
+        }
+        Ok(results)
+    }
+
+    async fn get_chain_checkpoint(
+        &self,
+        agent_id: &str,
+        thread_id: Option<&str>,
+    ) -> Result<Option<ChainCheckpoint>> {
         let conn = self.conn.lock().await;
         let mut stmt = conn.prepare(
-            "SELECT id, agent_id, content, memory_type, scope, importance, tags, metadata, embedding, content_hash, prev_hash, source_type, source_id, consolidation_state, access_count, org_id, thread_id, created_at, updated_at, last_accessed_at, expires_at, deleted_at, decay_rate, created_by, version, prev_version_id, quarantined, quarantine_reason, decay_function FROM memories WHERE updated_at > ? ORDER BY updated_at ASC LIMIT ?",
+            "SELECT agent_id, thread_id, verified_count, last_record_id, last_created_at, last_content_hash, rolling_root, verified_at FROM chain_checkpoints WHERE agent_id = ? AND thread_id = ?",
+        )?;
+        match stmt.query_row(
+            duckdb::params![agent_id, thread_id.unwrap_or_default()],
+            row_to_chain_checkpoint,
+        ) {
+            Ok(checkpoint) => Ok(Some(checkpoint)),
+            Err(duckdb::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(Error::Storage(e.to_string())),
+        }
+    }
+
+    async fn put_chain_checkpoint(&self, checkpoint: &ChainCheckpoint) -> Result<()> {
+        let conn = self.conn.lock().await;
+        let thread_id = checkpoint.thread_id.as_deref().unwrap_or_default();
+        let affected = conn.execute(
+            "UPDATE chain_checkpoints SET verified_count = ?, last_record_id = ?, last_created_at = ?, last_content_hash = ?, rolling_root = ?, verified_at = ? WHERE agent_id = ? AND thread_id = ?",
+            duckdb::params![
+                checkpoint.verified_count as i64,
+                checkpoint.last_record_id.to_string(),
+                checkpoint.last_created_at,
+                checkpoint.last_content_hash,
+                checkpoint.rolling_root,
+                checkpoint.verified_at,
+                checkpoint.agent_id,
+                thread_id,
+            ],
+        )?;
+        if affected == 0 {
+            conn.execute(
+                "INSERT INTO chain_checkpoints (agent_id, thread_id, verified_count, last_record_id, last_created_at, last_content_hash, rolling_root, verified_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+                duckdb::params![
+                    checkpoint.agent_id,
+                    thread_id,
+                    checkpoint.verified_count as i64,
+                    checkpoint.last_record_id.to_string(),
+                    checkpoint.last_created_at,
+                    checkpoint.last_content_hash,
+                    checkpoint.rolling_root,
+                    checkpoint.verified_at,
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    async fn delete_chain_checkpoints(&self, agent_id: &str) -> Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "DELETE FROM chain_checkpoints WHERE agent_id = ?",
+            [agent_id],
+        )?;
+        Ok(())
+    }
+
+    async fn list_memories_since(
+        &self,
+        updated_after: &str,
+        limit: usize,
+    ) -> Result<Vec<MemoryRecord>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT id, agent_id, content, memory_type, scope, importance, tags, metadata, embedding, content_hash, prev_hash, source_type, source_id, consolidation_state, access_count, org_id, thread_id, created_at, updated_at, last_accessed_at, expires_at, deleted_at, decay_rate, created_by, version, prev_version_id, quarantined, quarantine_reason, decay_function, pinned, valid_from, valid_until, curation, attestation, channel, content_zstd FROM memories WHERE updated_at > ? ORDER BY updated_at ASC LIMIT ?",
         )?;
         let rows = stmt.query_map(duckdb::params![updated_after, limit as i64], row_to_memory)?;
         let mut results = Vec::new();
@@ -855,13 +1549,14 @@ impl StorageBackend for DuckDbStorage {
         )?;
         if affected == 0 {
             conn.execute(
-                "INSERT INTO agent_profiles (agent_id, avg_importance, avg_content_length, total_memories, last_updated) VALUES (?, ?, ?, ?, ?)",
+                "INSERT INTO agent_profiles (agent_id, avg_importance, avg_content_length, total_memories, last_updated, retrieval_settings) VALUES (?, ?, ?, ?, ?, ?)",
                 duckdb::params![
                     profile.agent_id,
                     profile.avg_importance,
                     profile.avg_content_length,
                     profile.total_memories as i64,
                     profile.last_updated,
+                    serde_json::to_string(&profile.retrieval)?,
                 ],
             )?;
         }
@@ -871,15 +1566,19 @@ impl StorageBackend for DuckDbStorage {
     async fn get_agent_profile(&self, agent_id: &str) -> Result<Option<AgentProfile>> {
         let conn = self.conn.lock().await;
         let mut stmt = conn.prepare(
-            "SELECT agent_id, avg_importance, avg_content_length, total_memories, last_updated FROM agent_profiles WHERE agent_id = ?",
+            "SELECT agent_id, avg_importance, avg_content_length, total_memories, last_updated, retrieval_settings FROM agent_profiles WHERE agent_id = ?",
         )?;
         let result = stmt.query_row([agent_id], |row| {
+            let retrieval: Option<String> = row.get(5)?;
             Ok(AgentProfile {
                 agent_id: row.get(0)?,
                 avg_importance: row.get(1)?,
                 avg_content_length: row.get(2)?,
                 total_memories: row.get::<_, i64>(3)? as u64,
                 last_updated: row.get(4)?,
+                retrieval: retrieval
+                    .and_then(|json| serde_json::from_str(&json).ok())
+                    .unwrap_or_default(),
             })
         });
         match result {
@@ -889,6 +1588,27 @@ impl StorageBackend for DuckDbStorage {
         }
     }
 
+    async fn set_agent_retrieval_defaults(
+        &self,
+        agent_id: &str,
+        defaults: &RetrievalDefaults,
+        updated_at: &str,
+    ) -> Result<()> {
+        let conn = self.conn.lock().await;
+        let settings_json = serde_json::to_string(defaults)?;
+        let affected = conn.execute(
+            "UPDATE agent_profiles SET retrieval_settings = ?, last_updated = ? WHERE agent_id = ?",
+            duckdb::params![settings_json, updated_at, agent_id],
+        )?;
+        if affected == 0 {
+            conn.execute(
+                "INSERT INTO agent_profiles (agent_id, last_updated, retrieval_settings) VALUES (?, ?, ?)",
+                duckdb::params![agent_id, updated_at, settings_json],
+            )?;
+        }
+        Ok(())
+    }
+
     async fn insert_or_update_embedding_baseline(
         &self,
         baseline: &EmbeddingBaseline,
@@ -955,6 +1675,589 @@ impl StorageBackend for DuckDbStorage {
         }
     }
 
+    async fn insert_conflict(&self, conflict: &ConflictRecord) -> Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "INSERT INTO conflicts (id, agent_id, memory_a, memory_b, similarity, reason, status, resolution, winner_id, resolved_by, created_at, resolved_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            duckdb::params![
+                conflict.id.to_string(),
+                conflict.agent_id,
+                conflict.memory_a.to_string(),
+                conflict.memory_b.to_string(),
+                conflict.similarity,
+                conflict.reason,
+                conflict.status.to_string(),
+                conflict.resolution,
+                conflict.winner_id.map(|id| id.to_string()),
+                conflict.resolved_by,
+                conflict.created_at,
+                conflict.resolved_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    async fn get_conflict(&self, id: Uuid) -> Result<Option<ConflictRecord>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {CONFLICT_COLUMNS} FROM conflicts WHERE id = ?"
+        ))?;
+        match stmt.query_row([id.to_string()], row_to_conflict) {
+            Ok(conflict) => Ok(Some(conflict)),
+            Err(duckdb::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(Error::Storage(e.to_string())),
+        }
+    }
+
+    async fn list_conflicts(
+        &self,
+        agent_id: Option<&str>,
+        status: Option<ConflictStatus>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<ConflictRecord>> {
+        let conn = self.conn.lock().await;
+        let status = status.map(|s| s.to_string());
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {CONFLICT_COLUMNS} FROM conflicts WHERE (CAST(? AS VARCHAR) IS NULL OR agent_id = ?) AND (CAST(? AS VARCHAR) IS NULL OR status = ?) ORDER BY created_at DESC LIMIT {limit} OFFSET {offset}"
+        ))?;
+        let rows = stmt.query_map(
+            duckdb::params![agent_id, agent_id, status, status],
+            row_to_conflict,
+        )?;
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row.map_err(|e| Error::Storage(e.to_string()))?);
+        }
+        Ok(results)
+    }
+
+    async fn update_conflict(&self, conflict: &ConflictRecord) -> Result<()> {
+        let conn = self.conn.lock().await;
+        let affected = conn.execute(
+            "UPDATE conflicts SET status = ?, resolution = ?, winner_id = ?, resolved_by = ?, resolved_at = ? WHERE id = ?",
+            duckdb::params![
+                conflict.status.to_string(),
+                conflict.resolution,
+                conflict.winner_id.map(|id| id.to_string()),
+                conflict.resolved_by,
+                conflict.resolved_at,
+                conflict.id.to_string(),
+            ],
+        )?;
+        if affected == 0 {
+            return Err(Error::NotFound(format!(
+                "conflict {} not found",
+                conflict.id
+            )));
+        }
+        Ok(())
+    }
+
+    async fn insert_memory_accesses(&self, accesses: &[MemoryAccess]) -> Result<()> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(
+            "INSERT INTO memory_accesses (id, memory_id, principal, operation, query, accessed_at) VALUES (?, ?, ?, ?, ?, ?)",
+        )?;
+        for access in accesses {
+            stmt.execute(duckdb::params![
+                access.id.to_string(),
+                access.memory_id.to_string(),
+                access.principal,
+                access.operation.to_string(),
+                access.query,
+                access.accessed_at,
+            ])?;
+        }
+        Ok(())
+    }
+
+    async fn list_memory_accesses(
+        &self,
+        memory_id: Uuid,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<MemoryAccess>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {MEMORY_ACCESS_COLUMNS} FROM memory_accesses WHERE memory_id = ? ORDER BY accessed_at DESC, id DESC LIMIT {limit} OFFSET {offset}"
+        ))?;
+        let rows = stmt.query_map([memory_id.to_string()], row_to_memory_access)?;
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row.map_err(|e| Error::Storage(e.to_string()))?);
+        }
+        Ok(results)
+    }
+
+    async fn count_memory_accesses(&self, memory_id: Uuid) -> Result<usize> {
+        let conn = self.conn.lock().await;
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM memory_accesses WHERE memory_id = ?",
+            [memory_id.to_string()],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    async fn prune_memory_accesses(
+        &self,
+        older_than: Option<&str>,
+        keep_per_memory: Option<usize>,
+    ) -> Result<usize> {
+        let conn = self.conn.lock().await;
+        let mut deleted = 0;
+        if let Some(cutoff) = older_than {
+            deleted += conn.execute(
+                "DELETE FROM memory_accesses WHERE accessed_at < ?",
+                [cutoff],
+            )?;
+        }
+        if let Some(keep) = keep_per_memory {
+            deleted += conn.execute(
+                &format!(
+                    "DELETE FROM memory_accesses WHERE id IN (SELECT id FROM (SELECT id, ROW_NUMBER() OVER (PARTITION BY memory_id ORDER BY accessed_at DESC, id DESC) AS rn FROM memory_accesses) ranked WHERE rn > {keep})"
+                ),
+                [],
+            )?;
+        }
+        Ok(deleted)
+    }
+
+    async fn insert_access_request(&self, request: &AccessRequest) -> Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "INSERT INTO access_requests (id, requester_id, owner_id, memory_ids, tags, justification, permission, expires_in_hours, status, decided_by, decision_note, acl_ids, created_at, decided_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            duckdb::params![
+                request.id.to_string(),
+                request.requester_id,
+                request.owner_id,
+                serde_json::to_string(&request.memory_ids)?,
+                serde_json::to_string(&request.tags)?,
+                request.justification,
+                request.permission.to_string(),
+                request.expires_in_hours,
+                request.status.to_string(),
+                request.decided_by,
+                request.decision_note,
+                serde_json::to_string(&request.acl_ids)?,
+                request.created_at,
+                request.decided_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    async fn get_access_request(&self, id: Uuid) -> Result<Option<AccessRequest>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {ACCESS_REQUEST_COLUMNS} FROM access_requests WHERE id = ?"
+        ))?;
+        match stmt.query_row([id.to_string()], row_to_access_request) {
+            Ok(request) => Ok(Some(request)),
+            Err(duckdb::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(Error::Storage(e.to_string())),
+        }
+    }
+
+    async fn list_access_requests(
+        &self,
+        owner_id: Option<&str>,
+        requester_id: Option<&str>,
+        status: Option<AccessRequestStatus>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<AccessRequest>> {
+        let conn = self.conn.lock().await;
+        let status = status.map(|s| s.to_string());
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {ACCESS_REQUEST_COLUMNS} FROM access_requests WHERE (CAST(? AS VARCHAR) IS NULL OR owner_id = ?) AND (CAST(? AS VARCHAR) IS NULL OR requester_id = ?) AND (CAST(? AS VARCHAR) IS NULL OR status = ?) ORDER BY created_at DESC LIMIT {limit} OFFSET {offset}"
+        ))?;
+        let rows = stmt.query_map(
+            duckdb::params![
+                owner_id,
+                owner_id,
+                requester_id,
+                requester_id,
+                status,
+                status
+            ],
+            row_to_access_request,
+        )?;
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row.map_err(|e| Error::Storage(e.to_string()))?);
+        }
+        Ok(results)
+    }
+
+    async fn update_access_request(
+        &self,
+        request: &AccessRequest,
+        expected: AccessRequestStatus,
+    ) -> Result<bool> {
+        let conn = self.conn.lock().await;
+        let affected = conn.execute(
+            "UPDATE access_requests SET status = ?, decided_by = ?, decision_note = ?, acl_ids = ?, decided_at = ? WHERE id = ? AND status = ?",
+            duckdb::params![
+                request.status.to_string(),
+                request.decided_by,
+                request.decision_note,
+                serde_json::to_string(&request.acl_ids)?,
+                request.decided_at,
+                request.id.to_string(),
+                expected.to_string(),
+            ],
+        )?;
+        Ok(affected > 0)
+    }
+
+    async fn insert_channel(&self, channel: &Channel) -> Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "INSERT INTO channels (name, created_by, max_memories, created_at) VALUES (?, ?, ?, ?)",
+            duckdb::params![
+                channel.name,
+                channel.created_by,
+                channel.max_memories.map(|n| n as i64),
+                channel.created_at,
+            ],
+        )?;
+        Ok(())
+    }
+
+    async fn get_channel(&self, name: &str) -> Result<Option<Channel>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT name, created_by, max_memories, created_at FROM channels WHERE name = ?",
+        )?;
+        match stmt.query_row([name], row_to_channel) {
+            Ok(channel) => Ok(Some(channel)),
+            Err(duckdb::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(Error::Storage(e.to_string())),
+        }
+    }
+
+    async fn list_channels(&self, member: Option<&str>) -> Result<Vec<Channel>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT name, created_by, max_memories, created_at FROM channels WHERE CAST(? AS VARCHAR) IS NULL OR name IN (SELECT channel FROM channel_members WHERE agent_id = ?) ORDER BY name",
+        )?;
+        let rows = stmt.query_map(duckdb::params![member, member], row_to_channel)?;
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row.map_err(|e| Error::Storage(e.to_string()))?);
+        }
+        Ok(results)
+    }
+
+    async fn update_channel(&self, channel: &Channel) -> Result<()> {
+        let conn = self.conn.lock().await;
+        let affected = conn.execute(
+            "UPDATE channels SET max_memories = ? WHERE name = ?",
+            duckdb::params![channel.max_memories.map(|n| n as i64), channel.name],
+        )?;
+        if affected == 0 {
+            return Err(Error::NotFound(format!(
+                "channel {} not found",
+                channel.name
+            )));
+        }
+        Ok(())
+    }
+
+    async fn add_channel_member(&self, member: &ChannelMember) -> Result<bool> {
+        let conn = self.conn.lock().await;
+        let affected = conn.execute(
+            "INSERT INTO channel_members (channel, agent_id, joined_at) VALUES (?, ?, ?) ON CONFLICT DO NOTHING",
+            duckdb::params![member.channel, member.agent_id, member.joined_at],
+        )?;
+        Ok(affected > 0)
+    }
+
+    async fn remove_channel_member(&self, channel: &str, agent_id: &str) -> Result<bool> {
+        let conn = self.conn.lock().await;
+        let affected = conn.execute(
+            "DELETE FROM channel_members WHERE channel = ? AND agent_id = ?",
+            duckdb::params![channel, agent_id],
+        )?;
+        Ok(affected > 0)
+    }
+
+    async fn list_channel_members(&self, channel: &str) -> Result<Vec<ChannelMember>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT channel, agent_id, joined_at FROM channel_members WHERE channel = ? ORDER BY joined_at, agent_id",
+        )?;
+        let rows = stmt.query_map([channel], |row| {
+            Ok(ChannelMember {
+                channel: row.get(0)?,
+                agent_id: row.get(1)?,
+                joined_at: row.get(2)?,
+            })
+        })?;
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row.map_err(|e| Error::Storage(e.to_string()))?);
+        }
+        Ok(results)
+    }
+
+    async fn is_channel_member(&self, channel: &str, agent_id: &str) -> Result<bool> {
+        let conn = self.conn.lock().await;
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM channel_members WHERE channel = ? AND agent_id = ?",
+            duckdb::params![channel, agent_id],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    async fn count_channel_memories(&self, channel: &str) -> Result<u64> {
+        let conn = self.conn.lock().await;
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM memories WHERE channel = ? AND deleted_at IS NULL",
+            [channel],
+            |row| row.get(0),
+        )?;
+        Ok(count as u64)
+    }
+
+    async fn content_storage_stats(
+        &self,
+        agent_id: Option<&str>,
+    ) -> Result<Vec<ContentStorageStats>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT agent_id, COUNT(*), COUNT(content_zstd), \
+             CAST(SUM(COALESCE(content_len, strlen(content))) AS BIGINT), \
+             CAST(SUM(COALESCE(octet_length(content_zstd), strlen(content))) AS BIGINT) \
+             FROM memories WHERE CAST(? AS VARCHAR) IS NULL OR agent_id = ? \
+             GROUP BY agent_id ORDER BY agent_id",
+        )?;
+        let rows = stmt.query_map(duckdb::params![agent_id, agent_id], |row| {
+            let content_bytes = row.get::<_, i64>(3)? as u64;
+            let stored_bytes = row.get::<_, i64>(4)? as u64;
+            Ok(ContentStorageStats {
+                agent_id: row.get(0)?,
+                memories: row.get::<_, i64>(1)? as u64,
+                compressed: row.get::<_, i64>(2)? as u64,
+                content_bytes,
+                stored_bytes,
+                saved_bytes: content_bytes.saturating_sub(stored_bytes),
+            })
+        })?;
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row.map_err(|e| Error::Storage(e.to_string()))?);
+        }
+        Ok(results)
+    }
+
+    async fn daily_growth(&self) -> Result<Vec<DailyGrowth>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT day, CAST(SUM(memories) AS BIGINT), CAST(SUM(memory_bytes) AS BIGINT), \
+             CAST(SUM(events) AS BIGINT), CAST(SUM(event_bytes) AS BIGINT) FROM ( \
+             SELECT substr(created_at, 1, 10) AS day, COUNT(*) AS memories, \
+             SUM(COALESCE(octet_length(content_zstd), strlen(content))) AS memory_bytes, \
+             0 AS events, 0 AS event_bytes FROM memories GROUP BY 1 \
+             UNION ALL \
+             SELECT substr(timestamp, 1, 10), 0, 0, COUNT(*), \
+             SUM(COALESCE(strlen(CAST(payload AS VARCHAR)), 0)) FROM agent_events GROUP BY 1 \
+             ) GROUP BY day ORDER BY day",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(DailyGrowth {
+                day: row.get(0)?,
+                memories: row.get::<_, i64>(1)? as u64,
+                memory_bytes: row.get::<_, i64>(2)? as u64,
+                events: row.get::<_, i64>(3)? as u64,
+                event_bytes: row.get::<_, i64>(4)? as u64,
+            })
+        })?;
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row.map_err(|e| Error::Storage(e.to_string()))?);
+        }
+        Ok(results)
+    }
+
+    async fn database_size(&self) -> Result<u64> {
+        Ok(self.file_size())
+    }
+
+    /// `CHECKPOINT` folds the write-ahead log into the database file, marks
+    /// the blocks of deleted and rewritten rows free for reuse and truncates
+    /// free blocks at the end of the file; `VACUUM ANALYZE` refreshes the
+    /// table statistics. Writes wait on the connection until both finish.
+    async fn compact(&self) -> Result<CompactionReport> {
+        let conn = self.conn.lock().await;
+        let bytes_before = self.file_size();
+        conn.execute_batch("CHECKPOINT; VACUUM ANALYZE;")?;
+        Ok(CompactionReport {
+            bytes_before,
+            bytes_after: self.file_size(),
+        })
+    }
+
+    async fn upsert_recall_profile(&self, profile: &RecallProfile) -> Result<()> {
+        let conn = self.conn.lock().await;
+        let settings_json = serde_json::to_string(&profile.settings)?;
+        let affected = conn.execute(
+            "UPDATE recall_profiles SET settings = ?, updated_at = ? WHERE agent_id = ? AND name = ?",
+            duckdb::params![
+                settings_json,
+                profile.updated_at,
+                profile.agent_id,
+                profile.name,
+            ],
+        )?;
+        if affected == 0 {
+            conn.execute(
+                "INSERT INTO recall_profiles (agent_id, name, settings, use_count, last_used_at, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?)",
+                duckdb::params![
+                    profile.agent_id,
+                    profile.name,
+                    settings_json,
+                    profile.use_count as i64,
+                    profile.last_used_at,
+                    profile.created_at,
+                    profile.updated_at,
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    async fn get_recall_profile(
+        &self,
+        agent_id: &str,
+        name: &str,
+    ) -> Result<Option<RecallProfile>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT agent_id, name, settings, use_count, last_used_at, created_at, updated_at FROM recall_profiles WHERE agent_id = ? AND name = ?",
+        )?;
+        match stmt.query_row(duckdb::params![agent_id, name], row_to_recall_profile_parts) {
+            Ok(parts) => Ok(Some(recall_profile_from_parts(parts)?)),
+            Err(duckdb::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(Error::Storage(e.to_string())),
+        }
+    }
+
+    async fn list_recall_profiles(&self, agent_id: &str) -> Result<Vec<RecallProfile>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT agent_id, name, settings, use_count, last_used_at, created_at, updated_at FROM recall_profiles WHERE agent_id = ? ORDER BY name",
+        )?;
+        let rows = stmt.query_map([agent_id], row_to_recall_profile_parts)?;
+        let mut results = Vec::new();
+        for row in rows {
+            let parts = row.map_err(|e| Error::Storage(e.to_string()))?;
+            results.push(recall_profile_from_parts(parts)?);
+        }
+        Ok(results)
+    }
+
+    async fn delete_recall_profile(&self, agent_id: &str, name: &str) -> Result<bool> {
+        let conn = self.conn.lock().await;
+        let affected = conn.execute(
+            "DELETE FROM recall_profiles WHERE agent_id = ? AND name = ?",
+            duckdb::params![agent_id, name],
+        )?;
+        Ok(affected > 0)
+    }
+
+    async fn record_recall_profile_use(
+        &self,
+        agent_id: &str,
+        name: &str,
+        used_at: &str,
+    ) -> Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "UPDATE recall_profiles SET use_count = use_count + 1, last_used_at = ? WHERE agent_id = ? AND name = ?",
+            duckdb::params![used_at, agent_id, name],
+        )?;
+        Ok(())
+    }
+
+    async fn commit_write(&self, batch: &WriteBatch) -> Result<()> {
+        let mut conn = self.conn.lock().await;
+        let tx = conn.transaction()?;
+        if let Some(ref record) = batch.memory {
+            insert_memory_row(&tx, record, self.compression_threshold)?;
+        }
+        for record in &batch.updates {
+            update_memory_row(&tx, record, self.compression_threshold)?;
+        }
+        for acl in &batch.acls {
+            insert_acl_row(&tx, acl)?;
+        }
+        for relation in &batch.relations {
+            insert_relation_row(&tx, relation)?;
+        }
+        for event in &batch.events {
+            insert_event_row(&tx, event)?;
+        }
+        for message in &batch.outbox {
+            insert_outbox_row(&tx, message)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    async fn claim_outbox(&self, limit: usize, lease_until: &str) -> Result<Vec<OutboxMessage>> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {OUTBOX_COLUMNS} FROM outbox WHERE delivered_at IS NULL AND (available_at IS NULL OR available_at <= ?) ORDER BY created_at, id LIMIT {limit}"
+        ))?;
+        let rows = stmt.query_map([&now], row_to_outbox)?;
+        let mut claimed = Vec::new();
+        for row in rows {
+            claimed.push(row.map_err(|e| Error::Storage(e.to_string()))?);
+        }
+        // The connection mutex serializes claims, so no other caller can
+        // lease these rows between the SELECT and the UPDATE.
+        for message in &mut claimed {
+            conn.execute(
+                "UPDATE outbox SET attempts = attempts + 1, available_at = ? WHERE id = ?",
+                duckdb::params![lease_until, message.id.to_string()],
+            )?;
+            message.attempts += 1;
+        }
+        Ok(claimed)
+    }
+
+    async fn complete_outbox(&self, id: Uuid) -> Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "UPDATE outbox SET delivered_at = ?, last_error = NULL WHERE id = ?",
+            duckdb::params![chrono::Utc::now().to_rfc3339(), id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    async fn fail_outbox(&self, id: Uuid, error: &str, retry_at: &str) -> Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "UPDATE outbox SET last_error = ?, available_at = ? WHERE id = ? AND delivered_at IS NULL",
+            duckdb::params![error, retry_at, id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    async fn prune_outbox(&self, cutoff: &str) -> Result<usize> {
+        let conn = self.conn.lock().await;
+        let deleted = conn.execute(
+            "DELETE FROM outbox WHERE delivered_at IS NOT NULL AND delivered_at < ?",
+            [cutoff],
+        )?;
+        Ok(deleted)
+    }
+
     async fn insert_checkpoint(&self, cp: &Checkpoint) -> Result<()> {
         let conn = self.conn.lock().await;
         let state_snapshot_json = serde_json::to_string(&cp.state_snapshot)?;
@@ -1056,6 +2359,27 @@ impl StorageBackend for DuckDbStorage {
             Err(e) => Err(Error::Storage(e.to_string())),
         }
     }
+
+    async fn thread_owner(&self, thread_id: &str) -> Result<Option<String>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT agent_id FROM (
+                SELECT agent_id, created_at AS at, 0 AS fallback FROM memories WHERE thread_id = ?
+                UNION ALL
+                SELECT agent_id, timestamp, 0 FROM agent_events WHERE thread_id = ? AND event_type <> 'checkpoint'
+                UNION ALL
+                SELECT agent_id, created_at, 1 FROM checkpoints WHERE thread_id = ?
+            ) ORDER BY fallback, at LIMIT 1",
+        )?;
+        let result = stmt.query_row(duckdb::params![thread_id, thread_id, thread_id], |row| {
+            row.get::<_, String>(0)
+        });
+        match result {
+            Ok(owner) => Ok(Some(owner)),
+            Err(duckdb::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(Error::Storage(e.to_string())),
+        }
+    }
 }
 
 fn row_to_event(row: &duckdb::Row<'_>) -> duckdb::Result<AgentEvent> {
@@ -1097,6 +2421,7 @@ fn row_to_event(row: &duckdb::Row<'_>) -> duckdb::Result<AgentEvent> {
         content_hash,
         prev_hash,
         embedding: deserialize_embedding(embedding_blob),
+        request_id: row.get(19)?,
     })
 }
 
@@ -1256,6 +2581,12 @@ mod tests {
             quarantined: false,
             quarantine_reason: None,
             decay_function: None,
+            pinned: false,
+            valid_from: None,
+            valid_until: None,
+            curation: None,
+            attestation: None,
+            channel: None,
         }
     }
 
@@ -1453,6 +2784,7 @@ mod tests {
             content_hash: vec![1, 2, 3],
             prev_hash: None,
             embedding: None,
+            request_id: None,
         };
 
         storage.insert_event(&event).await.unwrap();
@@ -1495,6 +2827,7 @@ mod tests {
                 content_hash: vec![i as u8],
                 prev_hash: None,
                 embedding: None,
+                request_id: None,
             };
             storage.insert_event(&event).await.unwrap();
         }
@@ -1520,6 +2853,7 @@ mod tests {
             content_hash: vec![99],
             prev_hash: None,
             embedding: None,
+            request_id: None,
         };
         storage.insert_event(&event).await.unwrap();
 
@@ -0,0 +1,1832 @@
+//! Pure-Rust, process-local [`StorageBackend`] backed by ordered maps.
+//!
+//! [`InMemoryStorage`] keeps every table in a `BTreeMap` or `HashMap`
+//! behind one mutex, so it needs no native libraries and starts instantly.
+//! It mirrors the DuckDB backend's semantics (primary keys, soft deletes,
+//! ordering, permission checks) and is meant for tests and ephemeral
+//! agents that need no persistence: everything is lost when it is dropped.
+//!
+//! Timestamps are RFC 3339 strings compared as strings, exactly as the SQL
+//! backends compare their `VARCHAR` columns.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Display;
+use std::sync::{Mutex, MutexGuard};
+
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::model::access_log::MemoryAccess;
+use crate::model::access_request::{AccessRequest, AccessRequestStatus};
+use crate::model::acl::{Acl, Permission, PrincipalType};
+use crate::model::agent_profile::{AgentProfile, RetrievalDefaults};
+use crate::model::chain_checkpoint::ChainCheckpoint;
+use crate::model::channel::{Channel, ChannelMember};
+use crate::model::checkpoint::Checkpoint;
+use crate::model::conflict::{ConflictRecord, ConflictStatus};
+use crate::model::content_stats::{ContentStorageStats, DailyGrowth};
+use crate::model::delegation::{Delegation, DelegationScope};
+use crate::model::embedding_baseline::EmbeddingBaseline;
+use crate::model::event::{AgentEvent, EventType};
+use crate::model::event_archive::EventArchiveSegment;
+use crate::model::memory::{MemoryRecord, Scope};
+use crate::model::outbox::OutboxMessage;
+use crate::model::pseudonym::PseudonymEntry;
+use crate::model::recall_profile::RecallProfile;
+use crate::model::relation::Relation;
+use crate::model::share_link::RevokedShareLink;
+use crate::storage::{CompactionReport, MemoryColumns, MemoryFilter, StorageBackend, WriteBatch};
+
+/// An outbox row: the message plus the time its current lease (or retry
+/// delay) runs out, which is not part of [`OutboxMessage`].
+struct OutboxEntry {
+    message: OutboxMessage,
+    available_at: Option<String>,
+}
+
+#[derive(Default)]
+struct Tables {
+    memories: BTreeMap<Uuid, MemoryRecord>,
+    acls: BTreeMap<Uuid, Acl>,
+    relations: BTreeMap<Uuid, Relation>,
+    events: BTreeMap<Uuid, AgentEvent>,
+    event_archives: BTreeMap<Uuid, EventArchiveSegment>,
+    /// Keyed by (agent, thread or `""`).
+    chain_checkpoints: BTreeMap<(String, String), ChainCheckpoint>,
+    sync_metadata: HashMap<String, String>,
+    revoked_share_links: HashMap<(Uuid, Uuid), RevokedShareLink>,
+    /// Keyed by (org or `""`, alias).
+    pseudonyms: HashMap<(String, String), PseudonymEntry>,
+    delegations: BTreeMap<Uuid, Delegation>,
+    agent_profiles: HashMap<String, AgentProfile>,
+    embedding_baselines: HashMap<String, EmbeddingBaseline>,
+    conflicts: BTreeMap<Uuid, ConflictRecord>,
+    access_requests: BTreeMap<Uuid, AccessRequest>,
+    channels: BTreeMap<String, Channel>,
+    /// Keyed by (channel, agent).
+    channel_members: BTreeMap<(String, String), ChannelMember>,
+    /// Keyed by (agent, name).
+    recall_profiles: BTreeMap<(String, String), RecallProfile>,
+    memory_accesses: BTreeMap<Uuid, MemoryAccess>,
+    outbox: BTreeMap<Uuid, OutboxEntry>,
+    checkpoints: BTreeMap<Uuid, Checkpoint>,
+}
+
+/// Fail like a primary-key violation if any of `keys` is already in
+/// `table` or appears twice.
+fn check_new<'a, K, V>(
+    table: &BTreeMap<K, V>,
+    keys: impl IntoIterator<Item = &'a K>,
+    what: &str,
+) -> Result<()>
+where
+    K: Ord + Display + std::hash::Hash + 'a,
+{
+    let mut seen = HashSet::new();
+    for key in keys {
+        if table.contains_key(key) || !seen.insert(key) {
+            return Err(Error::Storage(format!("duplicate {what} key: {key}")));
+        }
+    }
+    Ok(())
+}
+
+fn page<T>(items: impl Iterator<Item = T>, limit: usize, offset: usize) -> Vec<T> {
+    items.skip(offset).take(limit).collect()
+}
+
+impl Tables {
+    fn live_memories(&self) -> impl Iterator<Item = &MemoryRecord> {
+        self.memories.values().filter(|m| m.deleted_at.is_none())
+    }
+
+    fn is_channel_member(&self, channel: &str, agent_id: &str) -> bool {
+        self.channel_members
+            .contains_key(&(channel.to_string(), agent_id.to_string()))
+    }
+
+    fn update_memory(&mut self, record: &MemoryRecord) -> Result<()> {
+        let Some(stored) = self.memories.get_mut(&record.id) else {
+            return Err(Error::NotFound(format!("memory {} not found", record.id)));
+        };
+        // Like the SQL backends, an update never moves `created_at`.
+        let created_at = std::mem::take(&mut stored.created_at);
+        *stored = MemoryRecord {
+            created_at,
+            ..record.clone()
+        };
+        Ok(())
+    }
+
+    fn delegation_grants(
+        &self,
+        delegate_id: &str,
+        memory_id: Uuid,
+        required: Permission,
+        now: &str,
+    ) -> bool {
+        let Some(memory) = self.memories.get(&memory_id) else {
+            return false;
+        };
+        self.delegations.values().any(|d| {
+            d.delegate_id == delegate_id
+                && d.revoked_at.is_none()
+                && d.expires_at.as_deref().is_none_or(|at| at > now)
+                && d.permission.satisfies(required)
+                && match &d.scope {
+                    DelegationScope::AllMemories => true,
+                    DelegationScope::ByMemoryId(ids) => ids.contains(&memory_id),
+                    DelegationScope::ByTag(tags) => tags.iter().any(|t| memory.tags.contains(t)),
+                }
+        })
+    }
+}
+
+/// Process-local storage with no persistence. See the [module docs](self).
+#[derive(Default)]
+pub struct InMemoryStorage {
+    tables: Mutex<Tables>,
+}
+
+impl InMemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn tables(&self) -> Result<MutexGuard<'_, Tables>> {
+        self.tables
+            .lock()
+            .map_err(|e| Error::Internal(format!("lock poisoned: {e}")))
+    }
+
+    fn accessible_memory_ids(
+        &self,
+        agent_id: &str,
+        as_of: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<Uuid>> {
+        let tables = self.tables()?;
+        let now = chrono::Utc::now().to_rfc3339();
+        let granted: HashSet<Uuid> = tables
+            .acls
+            .values()
+            .filter(|acl| acl.principal_id == agent_id)
+            .filter(|acl| acl.expires_at.as_deref().is_none_or(|at| at > now.as_str()))
+            .map(|acl| acl.memory_id)
+            .collect();
+        Ok(tables
+            .live_memories()
+            .filter(|m| {
+                m.agent_id == agent_id
+                    || matches!(m.scope, Scope::Public | Scope::Global)
+                    || granted.contains(&m.id)
+                    || m.channel
+                        .as_deref()
+                        .is_some_and(|c| tables.is_channel_member(c, agent_id))
+            })
+            .filter(|m| as_of.is_none_or(|as_of| m.created_at.as_str() <= as_of))
+            .map(|m| m.id)
+            .take(limit)
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl StorageBackend for InMemoryStorage {
+    fn backend_name(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn ping(&self) -> Result<()> {
+        self.tables().map(|_| ())
+    }
+
+    async fn insert_memory(&self, record: &MemoryRecord) -> Result<()> {
+        let mut tables = self.tables()?;
+        check_new(&tables.memories, [&record.id], "memory")?;
+        tables.memories.insert(record.id, record.clone());
+        Ok(())
+    }
+
+    async fn get_memory(&self, id: Uuid) -> Result<Option<MemoryRecord>> {
+        Ok(self.tables()?.memories.get(&id).cloned())
+    }
+
+    async fn get_memories(
+        &self,
+        ids: &[Uuid],
+        columns: MemoryColumns,
+    ) -> Result<Vec<MemoryRecord>> {
+        let tables = self.tables()?;
+        let unique: HashSet<&Uuid> = ids.iter().collect();
+        Ok(unique
+            .into_iter()
+            .filter_map(|id| tables.memories.get(id))
+            .map(|record| {
+                let mut record = record.clone();
+                record.embedding = None;
+                if !columns.content {
+                    record.content = String::new();
+                }
+                if !columns.metadata {
+                    record.metadata = serde_json::json!({});
+                }
+                record
+            })
+            .collect())
+    }
+
+    async fn update_memory(&self, record: &MemoryRecord) -> Result<()> {
+        self.tables()?.update_memory(record)
+    }
+
+    async fn update_memory_if_version(
+        &self,
+        record: &MemoryRecord,
+        expected_version: u32,
+    ) -> Result<bool> {
+        let mut tables = self.tables()?;
+        match tables.memories.get(&record.id) {
+            Some(stored) if stored.version == expected_version => {
+                tables.update_memory(record)?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn soft_delete_memory(&self, id: Uuid) -> Result<()> {
+        let mut tables = self.tables()?;
+        match tables.memories.get_mut(&id) {
+            Some(record) if record.deleted_at.is_none() => {
+                let now = chrono::Utc::now().to_rfc3339();
+                record.deleted_at = Some(now.clone());
+                record.updated_at = now;
+                Ok(())
+            }
+            _ => Err(Error::NotFound(format!(
+                "memory {id} not found or already deleted"
+            ))),
+        }
+    }
+
+    async fn hard_delete_memory(&self, id: Uuid) -> Result<()> {
+        let mut tables = self.tables()?;
+        if tables.memories.remove(&id).is_none() {
+            return Err(Error::NotFound(format!("memory {id} not found")));
+        }
+        tables.acls.retain(|_, acl| acl.memory_id != id);
+        Ok(())
+    }
+
+    async fn list_memories(
+        &self,
+        filter: &MemoryFilter,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<MemoryRecord>> {
+        let tables = self.tables()?;
+        let mut matches: Vec<&MemoryRecord> = tables
+            .memories
+            .values()
+            .filter(|m| filter.include_deleted || m.deleted_at.is_none())
+            .filter(|m| filter.agent_id.as_ref().is_none_or(|a| &m.agent_id == a))
+            .filter(|m| filter.memory_type.is_none_or(|t| m.memory_type == t))
+            .filter(|m| filter.scope.is_none_or(|s| m.scope == s))
+            .filter(|m| {
+                filter
+                    .tags
+                    .as_ref()
+                    .is_none_or(|tags| tags.iter().any(|t| m.tags.contains(t)))
+            })
+            .filter(|m| filter.min_importance.is_none_or(|i| m.importance >= i))
+            .filter(|m| {
+                filter
+                    .org_id
+                    .as_ref()
+                    .is_none_or(|o| m.org_id.as_ref() == Some(o))
+            })
+            .filter(|m| {
+                filter
+                    .thread_id
+                    .as_ref()
+                    .is_none_or(|t| m.thread_id.as_ref() == Some(t))
+            })
+            .collect();
+        matches.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(page(matches.into_iter().cloned(), limit, offset))
+    }
+
+    async fn touch_memory(&self, id: Uuid) -> Result<()> {
+        if let Some(record) = self.tables()?.memories.get_mut(&id) {
+            record.access_count += 1;
+            record.last_accessed_at = Some(chrono::Utc::now().to_rfc3339());
+        }
+        Ok(())
+    }
+
+    async fn insert_acl(&self, acl: &Acl) -> Result<()> {
+        let mut tables = self.tables()?;
+        check_new(&tables.acls, [&acl.id], "acl")?;
+        tables.acls.insert(acl.id, acl.clone());
+        Ok(())
+    }
+
+    async fn check_permission(
+        &self,
+        memory_id: Uuid,
+        principal_id: &str,
+        required: Permission,
+    ) -> Result<bool> {
+        let tables = self.tables()?;
+        let Some(memory) = tables.memories.get(&memory_id) else {
+            return Err(Error::NotFound(format!("memory {memory_id} not found")));
+        };
+        if memory.agent_id == principal_id {
+            return Ok(true);
+        }
+        // Channel members can read the channel's memories
+        if let Some(ref channel) = memory.channel
+            && Permission::Read.satisfies(required)
+            && tables.is_channel_member(channel, principal_id)
+        {
+            return Ok(true);
+        }
+        let now = chrono::Utc::now().to_rfc3339();
+        let acl_grants = tables.acls.values().any(|acl| {
+            acl.memory_id == memory_id
+                && (acl.principal_id == principal_id || acl.principal_type == PrincipalType::Public)
+                && acl.expires_at.as_deref().is_none_or(|at| at > now.as_str())
+                && acl.permission.satisfies(required)
+        });
+        Ok(acl_grants || tables.delegation_grants(principal_id, memory_id, required, &now))
+    }
+
+    async fn insert_relation(&self, relation: &Relation) -> Result<()> {
+        let mut tables = self.tables()?;
+        check_new(&tables.relations, [&relation.id], "relation")?;
+        tables.relations.insert(relation.id, relation.clone());
+        Ok(())
+    }
+
+    async fn get_relations_from(&self, source_id: Uuid) -> Result<Vec<Relation>> {
+        let tables = self.tables()?;
+        Ok(tables
+            .relations
+            .values()
+            .filter(|r| r.source_id == source_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_relations_to(&self, target_id: Uuid) -> Result<Vec<Relation>> {
+        let tables = self.tables()?;
+        Ok(tables
+            .relations
+            .values()
+            .filter(|r| r.target_id == target_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_relation(&self, id: Uuid) -> Result<()> {
+        match self.tables()?.relations.remove(&id) {
+            Some(_) => Ok(()),
+            None => Err(Error::NotFound(format!("relation {id} not found"))),
+        }
+    }
+
+    async fn get_latest_memory_hash(
+        &self,
+        agent_id: &str,
+        thread_id: Option<&str>,
+    ) -> Result<Option<Vec<u8>>> {
+        let tables = self.tables()?;
+        Ok(tables
+            .live_memories()
+            .filter(|m| m.agent_id == agent_id && m.thread_id.as_deref() == thread_id)
+            .max_by(|a, b| a.created_at.cmp(&b.created_at))
+            .map(|m| m.content_hash.clone()))
+    }
+
+    async fn get_latest_event_hash(
+        &self,
+        agent_id: &str,
+        thread_id: Option<&str>,
+    ) -> Result<Option<Vec<u8>>> {
+        let tables = self.tables()?;
+        Ok(tables
+            .events
+            .values()
+            .filter(|e| e.agent_id == agent_id)
+            .filter(|e| thread_id.is_none() || e.thread_id.as_deref() == thread_id)
+            .max_by(|a, b| (a.logical_clock, &a.timestamp).cmp(&(b.logical_clock, &b.timestamp)))
+            .map(|e| e.content_hash.clone()))
+    }
+
+    async fn max_logical_clock(&self, agent_id: &str) -> Result<i64> {
+        let tables = self.tables()?;
+        Ok(tables
+            .events
+            .values()
+            .filter(|e| e.agent_id == agent_id)
+            .map(|e| e.logical_clock)
+            .max()
+            .unwrap_or(0))
+    }
+
+    async fn get_sync_watermark(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.tables()?.sync_metadata.get(key).cloned())
+    }
+
+    async fn set_sync_watermark(&self, key: &str, value: &str) -> Result<()> {
+        self.tables()?
+            .sync_metadata
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    async fn revoke_share_link(&self, revoked: &RevokedShareLink) -> Result<()> {
+        self.tables()?
+            .revoked_share_links
+            .entry((revoked.link_id, revoked.memory_id))
+            .or_insert_with(|| revoked.clone());
+        Ok(())
+    }
+
+    async fn is_share_link_revoked(&self, link_id: Uuid, memory_id: Uuid) -> Result<bool> {
+        Ok(self
+            .tables()?
+            .revoked_share_links
+            .contains_key(&(link_id, memory_id)))
+    }
+
+    async fn purge_revoked_share_links(&self, now: &str) -> Result<usize> {
+        let mut tables = self.tables()?;
+        let before = tables.revoked_share_links.len();
+        tables
+            .revoked_share_links
+            .retain(|_, link| link.expires_at.as_str() >= now);
+        Ok(before - tables.revoked_share_links.len())
+    }
+
+    async fn record_pseudonym(&self, entry: &PseudonymEntry) -> Result<()> {
+        self.tables()?
+            .pseudonyms
+            .entry((entry.org_id.clone(), entry.alias.clone()))
+            .or_insert_with(|| entry.clone());
+        Ok(())
+    }
+
+    async fn get_pseudonym(&self, org_id: &str, alias: &str) -> Result<Option<PseudonymEntry>> {
+        Ok(self
+            .tables()?
+            .pseudonyms
+            .get(&(org_id.to_string(), alias.to_string()))
+            .cloned())
+    }
+
+    async fn list_accessible_memory_ids(&self, agent_id: &str, limit: usize) -> Result<Vec<Uuid>> {
+        self.accessible_memory_ids(agent_id, None, limit)
+    }
+
+    async fn list_accessible_memory_ids_as_of(
+        &self,
+        agent_id: &str,
+        as_of: &str,
+        limit: usize,
+    ) -> Result<Vec<Uuid>> {
+        self.accessible_memory_ids(agent_id, Some(as_of), limit)
+    }
+
+    async fn insert_event(&self, event: &AgentEvent) -> Result<()> {
+        let mut tables = self.tables()?;
+        check_new(&tables.events, [&event.id], "event")?;
+        tables.events.insert(event.id, event.clone());
+        Ok(())
+    }
+
+    async fn list_events(
+        &self,
+        agent_id: &str,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<AgentEvent>> {
+        let tables = self.tables()?;
+        let mut events: Vec<&AgentEvent> = tables
+            .events
+            .values()
+            .filter(|e| e.agent_id == agent_id)
+            .collect();
+        events.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        Ok(page(events.into_iter().cloned(), limit, offset))
+    }
+
+    async fn get_events_by_thread(&self, thread_id: &str, limit: usize) -> Result<Vec<AgentEvent>> {
+        let tables = self.tables()?;
+        let mut events: Vec<&AgentEvent> = tables
+            .events
+            .values()
+            .filter(|e| e.thread_id.as_deref() == Some(thread_id))
+            .collect();
+        events.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        Ok(page(events.into_iter().cloned(), limit, 0))
+    }
+
+    async fn get_event(&self, id: Uuid) -> Result<Option<AgentEvent>> {
+        Ok(self.tables()?.events.get(&id).cloned())
+    }
+
+    async fn list_child_events(
+        &self,
+        parent_event_id: Uuid,
+        limit: usize,
+    ) -> Result<Vec<AgentEvent>> {
+        let tables = self.tables()?;
+        let mut events: Vec<&AgentEvent> = tables
+            .events
+            .values()
+            .filter(|e| e.parent_event_id == Some(parent_event_id))
+            .collect();
+        events.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        Ok(page(events.into_iter().cloned(), limit, 0))
+    }
+
+    async fn list_events_before(&self, cutoff: &str, limit: usize) -> Result<Vec<AgentEvent>> {
+        let tables = self.tables()?;
+        let mut events: Vec<&AgentEvent> = tables
+            .events
+            .values()
+            .filter(|e| e.timestamp.as_str() < cutoff)
+            .collect();
+        events.sort_by(|a, b| {
+            (&a.agent_id, a.logical_clock, &a.timestamp, a.id).cmp(&(
+                &b.agent_id,
+                b.logical_clock,
+                &b.timestamp,
+                b.id,
+            ))
+        });
+        Ok(page(events.into_iter().cloned(), limit, 0))
+    }
+
+    async fn archive_event_segment(
+        &self,
+        segment: &EventArchiveSegment,
+        event_ids: &[Uuid],
+    ) -> Result<()> {
+        let mut tables = self.tables()?;
+        check_new(&tables.event_archives, [&segment.id], "event archive")?;
+        tables.event_archives.insert(segment.id, segment.clone());
+        for id in event_ids {
+            tables.events.remove(id);
+        }
+        Ok(())
+    }
+
+    async fn get_event_archive(&self, id: Uuid) -> Result<Option<EventArchiveSegment>> {
+        Ok(self.tables()?.event_archives.get(&id).cloned())
+    }
+
+    async fn list_event_archives(
+        &self,
+        agent_id: Option<&str>,
+    ) -> Result<Vec<EventArchiveSegment>> {
+        let tables = self.tables()?;
+        let mut segments: Vec<EventArchiveSegment> = tables
+            .event_archives
+            .values()
+            .filter(|s| agent_id.is_none_or(|a| s.agent_id == a))
+            .cloned()
+            .collect();
+        segments.sort_by(|a, b| (&a.from_timestamp, a.id).cmp(&(&b.from_timestamp, b.id)));
+        Ok(segments)
+    }
+
+    async fn list_memories_by_agent_ordered(
+        &self,
+        agent_id: &str,
+        thread_id: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<MemoryRecord>> {
+        let tables = self.tables()?;
+        let mut records: Vec<&MemoryRecord> = tables
+            .live_memories()
+            .filter(|m| m.agent_id == agent_id)
+            .filter(|m| thread_id.is_none() || m.thread_id.as_deref() == thread_id)
+            .collect();
+        records.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(page(records.into_iter().cloned(), limit, 0))
+    }
+
+    async fn list_memories_chain_page(
+        &self,
+        agent_id: &str,
+        thread_id: Option<&str>,
+        after: Option<(&str, Uuid)>,
+        limit: usize,
+    ) -> Result<Vec<MemoryRecord>> {
+        let tables = self.tables()?;
+        let mut records: Vec<&MemoryRecord> = tables
+            .live_memories()
+            .filter(|m| m.agent_id == agent_id)
+            .filter(|m| thread_id.is_none() || m.thread_id.as_deref() == thread_id)
+            .filter(|m| after.is_none_or(|cursor| (m.created_at.as_str(), m.id) > cursor))
+            .collect();
+        records.sort_by(|a, b| (&a.created_at, a.id).cmp(&(&b.created_at, b.id)));
+        Ok(page(records.into_iter().cloned(), limit, 0))
+    }
+
+    async fn get_chain_checkpoint(
+        &self,
+        agent_id: &str,
+        thread_id: Option<&str>,
+    ) -> Result<Option<ChainCheckpoint>> {
+        let key = (
+            agent_id.to_string(),
+            thread_id.unwrap_or_default().to_string(),
+        );
+        Ok(self.tables()?.chain_checkpoints.get(&key).cloned())
+    }
+
+    async fn put_chain_checkpoint(&self, checkpoint: &ChainCheckpoint) -> Result<()> {
+        let key = (
+            checkpoint.agent_id.clone(),
+            checkpoint.thread_id.clone().unwrap_or_default(),
+        );
+        self.tables()?
+            .chain_checkpoints
+            .insert(key, checkpoint.clone());
+        Ok(())
+    }
+
+    async fn delete_chain_checkpoints(&self, agent_id: &str) -> Result<()> {
+        self.tables()?
+            .chain_checkpoints
+            .retain(|(agent, _), _| agent != agent_id);
+        Ok(())
+    }
+
+    async fn list_memories_since(
+        &self,
+        updated_after: &str,
+        limit: usize,
+    ) -> Result<Vec<MemoryRecord>> {
+        let tables = self.tables()?;
+        let mut records: Vec<&MemoryRecord> = tables
+            .memories
+            .values()
+            .filter(|m| m.updated_at.as_str() > updated_after)
+            .collect();
+        records.sort_by(|a, b| a.updated_at.cmp(&b.updated_at));
+        Ok(page(records.into_iter().cloned(), limit, 0))
+    }
+
+    async fn upsert_memory(&self, record: &MemoryRecord) -> Result<()> {
+        let mut tables = self.tables()?;
+        match tables.update_memory(record) {
+            Err(Error::NotFound(_)) => {
+                tables.memories.insert(record.id, record.clone());
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    async fn cleanup_expired(&self) -> Result<usize> {
+        let mut tables = self.tables()?;
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut expired = 0;
+        for record in tables.memories.values_mut() {
+            if record.deleted_at.is_none()
+                && record
+                    .expires_at
+                    .as_deref()
+                    .is_some_and(|at| at < now.as_str())
+            {
+                record.deleted_at = Some(now.clone());
+                expired += 1;
+            }
+        }
+        Ok(expired)
+    }
+
+    async fn insert_delegation(&self, d: &Delegation) -> Result<()> {
+        let mut tables = self.tables()?;
+        check_new(&tables.delegations, [&d.id], "delegation")?;
+        tables.delegations.insert(d.id, d.clone());
+        Ok(())
+    }
+
+    async fn list_delegations_for(&self, delegate_id: &str) -> Result<Vec<Delegation>> {
+        let tables = self.tables()?;
+        let now = chrono::Utc::now().to_rfc3339();
+        Ok(tables
+            .delegations
+            .values()
+            .filter(|d| d.delegate_id == delegate_id && d.revoked_at.is_none())
+            .filter(|d| d.expires_at.as_deref().is_none_or(|at| at > now.as_str()))
+            .cloned()
+            .collect())
+    }
+
+    async fn revoke_delegation(&self, id: Uuid) -> Result<()> {
+        match self.tables()?.delegations.get_mut(&id) {
+            Some(d) if d.revoked_at.is_none() => {
+                d.revoked_at = Some(chrono::Utc::now().to_rfc3339());
+                Ok(())
+            }
+            _ => Err(Error::NotFound(format!(
+                "delegation {id} not found or already revoked"
+            ))),
+        }
+    }
+
+    async fn check_delegation(
+        &self,
+        delegate_id: &str,
+        memory_id: Uuid,
+        required: Permission,
+    ) -> Result<bool> {
+        let now = chrono::Utc::now().to_rfc3339();
+        Ok(self
+            .tables()?
+            .delegation_grants(delegate_id, memory_id, required, &now))
+    }
+
+    async fn insert_or_update_agent_profile(&self, profile: &AgentProfile) -> Result<()> {
+        let mut tables = self.tables()?;
+        match tables.agent_profiles.get_mut(&profile.agent_id) {
+            // Retrieval defaults are only written when the row is created;
+            // see `set_agent_retrieval_defaults`.
+            Some(stored) => {
+                stored.avg_importance = profile.avg_importance;
+                stored.avg_content_length = profile.avg_content_length;
+                stored.total_memories = profile.total_memories;
+                stored.last_updated = profile.last_updated.clone();
+            }
+            None => {
+                tables
+                    .agent_profiles
+                    .insert(profile.agent_id.clone(), profile.clone());
+            }
+        }
+        Ok(())
+    }
+
+    async fn get_agent_profile(&self, agent_id: &str) -> Result<Option<AgentProfile>> {
+        Ok(self.tables()?.agent_profiles.get(agent_id).cloned())
+    }
+
+    async fn set_agent_retrieval_defaults(
+        &self,
+        agent_id: &str,
+        defaults: &RetrievalDefaults,
+        updated_at: &str,
+    ) -> Result<()> {
+        let mut tables = self.tables()?;
+        let profile = tables
+            .agent_profiles
+            .entry(agent_id.to_string())
+            // The column defaults of the SQL schema.
+            .or_insert_with(|| AgentProfile {
+                agent_id: agent_id.to_string(),
+                avg_importance: 0.5,
+                avg_content_length: 100.0,
+                total_memories: 0,
+                last_updated: updated_at.to_string(),
+                retrieval: RetrievalDefaults::default(),
+            });
+        profile.retrieval = defaults.clone();
+        profile.last_updated = updated_at.to_string();
+        Ok(())
+    }
+
+    async fn insert_or_update_embedding_baseline(
+        &self,
+        baseline: &EmbeddingBaseline,
+    ) -> Result<()> {
+        self.tables()?
+            .embedding_baselines
+            .insert(baseline.agent_id.clone(), baseline.clone());
+        Ok(())
+    }
+
+    async fn get_embedding_baseline(&self, agent_id: &str) -> Result<Option<EmbeddingBaseline>> {
+        Ok(self.tables()?.embedding_baselines.get(agent_id).cloned())
+    }
+
+    async fn insert_conflict(&self, conflict: &ConflictRecord) -> Result<()> {
+        let mut tables = self.tables()?;
+        check_new(&tables.conflicts, [&conflict.id], "conflict")?;
+        tables.conflicts.insert(conflict.id, conflict.clone());
+        Ok(())
+    }
+
+    async fn get_conflict(&self, id: Uuid) -> Result<Option<ConflictRecord>> {
+        Ok(self.tables()?.conflicts.get(&id).cloned())
+    }
+
+    async fn list_conflicts(
+        &self,
+        agent_id: Option<&str>,
+        status: Option<ConflictStatus>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<ConflictRecord>> {
+        let tables = self.tables()?;
+        let mut conflicts: Vec<&ConflictRecord> = tables
+            .conflicts
+            .values()
+            .filter(|c| agent_id.is_none_or(|a| c.agent_id == a))
+            .filter(|c| status.is_none_or(|s| c.status == s))
+            .collect();
+        conflicts.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(page(conflicts.into_iter().cloned(), limit, offset))
+    }
+
+    async fn update_conflict(&self, conflict: &ConflictRecord) -> Result<()> {
+        let mut tables = self.tables()?;
+        let Some(stored) = tables.conflicts.get_mut(&conflict.id) else {
+            return Err(Error::NotFound(format!(
+                "conflict {} not found",
+                conflict.id
+            )));
+        };
+        stored.status = conflict.status;
+        stored.resolution = conflict.resolution.clone();
+        stored.winner_id = conflict.winner_id;
+        stored.resolved_by = conflict.resolved_by.clone();
+        stored.resolved_at = conflict.resolved_at.clone();
+        Ok(())
+    }
+
+    async fn insert_memory_accesses(&self, accesses: &[MemoryAccess]) -> Result<()> {
+        let mut tables = self.tables()?;
+        check_new(
+            &tables.memory_accesses,
+            accesses.iter().map(|a| &a.id),
+            "memory access",
+        )?;
+        for access in accesses {
+            tables.memory_accesses.insert(access.id, access.clone());
+        }
+        Ok(())
+    }
+
+    async fn list_memory_accesses(
+        &self,
+        memory_id: Uuid,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<MemoryAccess>> {
+        let tables = self.tables()?;
+        let mut accesses: Vec<&MemoryAccess> = tables
+            .memory_accesses
+            .values()
+            .filter(|a| a.memory_id == memory_id)
+            .collect();
+        accesses.sort_by(|a, b| (&b.accessed_at, b.id).cmp(&(&a.accessed_at, a.id)));
+        Ok(page(accesses.into_iter().cloned(), limit, offset))
+    }
+
+    async fn count_memory_accesses(&self, memory_id: Uuid) -> Result<usize> {
+        let tables = self.tables()?;
+        Ok(tables
+            .memory_accesses
+            .values()
+            .filter(|a| a.memory_id == memory_id)
+            .count())
+    }
+
+    async fn prune_memory_accesses(
+        &self,
+        older_than: Option<&str>,
+        keep_per_memory: Option<usize>,
+    ) -> Result<usize> {
+        let mut tables = self.tables()?;
+        let before = tables.memory_accesses.len();
+        if let Some(cutoff) = older_than {
+            tables
+                .memory_accesses
+                .retain(|_, a| a.accessed_at.as_str() >= cutoff);
+        }
+        if let Some(keep) = keep_per_memory {
+            let mut per_memory: HashMap<Uuid, Vec<(&str, Uuid)>> = HashMap::new();
+            for access in tables.memory_accesses.values() {
+                per_memory
+                    .entry(access.memory_id)
+                    .or_default()
+                    .push((access.accessed_at.as_str(), access.id));
+            }
+            let mut dropped = HashSet::new();
+            for mut entries in per_memory.into_values() {
+                entries.sort_by(|a, b| b.cmp(a));
+                dropped.extend(entries.into_iter().skip(keep).map(|(_, id)| id));
+            }
+            tables.memory_accesses.retain(|id, _| !dropped.contains(id));
+        }
+        Ok(before - tables.memory_accesses.len())
+    }
+
+    async fn insert_access_request(&self, request: &AccessRequest) -> Result<()> {
+        let mut tables = self.tables()?;
+        check_new(&tables.access_requests, [&request.id], "access request")?;
+        tables.access_requests.insert(request.id, request.clone());
+        Ok(())
+    }
+
+    async fn get_access_request(&self, id: Uuid) -> Result<Option<AccessRequest>> {
+        Ok(self.tables()?.access_requests.get(&id).cloned())
+    }
+
+    async fn list_access_requests(
+        &self,
+        owner_id: Option<&str>,
+        requester_id: Option<&str>,
+        status: Option<AccessRequestStatus>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<AccessRequest>> {
+        let tables = self.tables()?;
+        let mut requests: Vec<&AccessRequest> = tables
+            .access_requests
+            .values()
+            .filter(|r| owner_id.is_none_or(|o| r.owner_id == o))
+            .filter(|r| requester_id.is_none_or(|q| r.requester_id == q))
+            .filter(|r| status.is_none_or(|s| r.status == s))
+            .collect();
+        requests.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(page(requests.into_iter().cloned(), limit, offset))
+    }
+
+    async fn update_access_request(
+        &self,
+        request: &AccessRequest,
+        expected: AccessRequestStatus,
+    ) -> Result<bool> {
+        let mut tables = self.tables()?;
+        match tables.access_requests.get_mut(&request.id) {
+            Some(stored) if stored.status == expected => {
+                stored.status = request.status;
+                stored.decided_by = request.decided_by.clone();
+                stored.decision_note = request.decision_note.clone();
+                stored.acl_ids = request.acl_ids.clone();
+                stored.decided_at = request.decided_at.clone();
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn insert_channel(&self, channel: &Channel) -> Result<()> {
+        let mut tables = self.tables()?;
+        check_new(&tables.channels, [&channel.name], "channel")?;
+        tables
+            .channels
+            .insert(channel.name.clone(), channel.clone());
+        Ok(())
+    }
+
+    async fn get_channel(&self, name: &str) -> Result<Option<Channel>> {
+        Ok(self.tables()?.channels.get(name).cloned())
+    }
+
+    async fn list_channels(&self, member: Option<&str>) -> Result<Vec<Channel>> {
+        let tables = self.tables()?;
+        Ok(tables
+            .channels
+            .values()
+            .filter(|c| member.is_none_or(|m| tables.is_channel_member(&c.name, m)))
+            .cloned()
+            .collect())
+    }
+
+    async fn update_channel(&self, channel: &Channel) -> Result<()> {
+        match self.tables()?.channels.get_mut(&channel.name) {
+            Some(stored) => {
+                stored.max_memories = channel.max_memories;
+                Ok(())
+            }
+            None => Err(Error::NotFound(format!(
+                "channel {} not found",
+                channel.name
+            ))),
+        }
+    }
+
+    async fn add_channel_member(&self, member: &ChannelMember) -> Result<bool> {
+        let mut tables = self.tables()?;
+        let key = (member.channel.clone(), member.agent_id.clone());
+        if tables.channel_members.contains_key(&key) {
+            return Ok(false);
+        }
+        tables.channel_members.insert(key, member.clone());
+        Ok(true)
+    }
+
+    async fn remove_channel_member(&self, channel: &str, agent_id: &str) -> Result<bool> {
+        let key = (channel.to_string(), agent_id.to_string());
+        Ok(self.tables()?.channel_members.remove(&key).is_some())
+    }
+
+    async fn list_channel_members(&self, channel: &str) -> Result<Vec<ChannelMember>> {
+        let tables = self.tables()?;
+        let mut members: Vec<ChannelMember> = tables
+            .channel_members
+            .values()
+            .filter(|m| m.channel == channel)
+            .cloned()
+            .collect();
+        members.sort_by(|a, b| (&a.joined_at, &a.agent_id).cmp(&(&b.joined_at, &b.agent_id)));
+        Ok(members)
+    }
+
+    async fn is_channel_member(&self, channel: &str, agent_id: &str) -> Result<bool> {
+        Ok(self.tables()?.is_channel_member(channel, agent_id))
+    }
+
+    async fn count_channel_memories(&self, channel: &str) -> Result<u64> {
+        let tables = self.tables()?;
+        Ok(tables
+            .live_memories()
+            .filter(|m| m.channel.as_deref() == Some(channel))
+            .count() as u64)
+    }
+
+    /// Content is kept as is, never compressed, so `compressed` and
+    /// `saved_bytes` are always 0.
+    async fn content_storage_stats(
+        &self,
+        agent_id: Option<&str>,
+    ) -> Result<Vec<ContentStorageStats>> {
+        let tables = self.tables()?;
+        let mut per_agent: BTreeMap<&str, (u64, u64)> = BTreeMap::new();
+        for record in tables.memories.values() {
+            if agent_id.is_some_and(|a| record.agent_id != a) {
+                continue;
+            }
+            let (memories, bytes) = per_agent.entry(record.agent_id.as_str()).or_default();
+            *memories += 1;
+            *bytes += record.content.len() as u64;
+        }
+        Ok(per_agent
+            .into_iter()
+            .map(|(agent_id, (memories, bytes))| ContentStorageStats {
+                agent_id: agent_id.to_string(),
+                memories,
+                compressed: 0,
+                content_bytes: bytes,
+                stored_bytes: bytes,
+                saved_bytes: 0,
+            })
+            .collect())
+    }
+
+    async fn daily_growth(&self) -> Result<Vec<DailyGrowth>> {
+        fn entry_for<'a>(
+            days: &'a mut BTreeMap<String, DailyGrowth>,
+            at: &str,
+        ) -> &'a mut DailyGrowth {
+            let day = at.get(..10).unwrap_or(at);
+            days.entry(day.to_string()).or_insert_with(|| DailyGrowth {
+                day: day.to_string(),
+                ..Default::default()
+            })
+        }
+
+        let tables = self.tables()?;
+        let mut days: BTreeMap<String, DailyGrowth> = BTreeMap::new();
+        for record in tables.memories.values() {
+            let entry = entry_for(&mut days, &record.created_at);
+            entry.memories += 1;
+            entry.memory_bytes += record.content.len() as u64;
+        }
+        for event in tables.events.values() {
+            let entry = entry_for(&mut days, &event.timestamp);
+            entry.events += 1;
+            entry.event_bytes += event.payload.to_string().len() as u64;
+        }
+        Ok(days.into_values().collect())
+    }
+
+    async fn database_size(&self) -> Result<u64> {
+        Ok(0)
+    }
+
+    /// Removed rows are freed as they go, so there is nothing to reclaim.
+    async fn compact(&self) -> Result<CompactionReport> {
+        Ok(CompactionReport::default())
+    }
+
+    async fn upsert_recall_profile(&self, profile: &RecallProfile) -> Result<()> {
+        let mut tables = self.tables()?;
+        let key = (profile.agent_id.clone(), profile.name.clone());
+        match tables.recall_profiles.get_mut(&key) {
+            Some(stored) => {
+                stored.settings = profile.settings.clone();
+                stored.updated_at = profile.updated_at.clone();
+            }
+            None => {
+                tables.recall_profiles.insert(key, profile.clone());
+            }
+        }
+        Ok(())
+    }
+
+    async fn get_recall_profile(
+        &self,
+        agent_id: &str,
+        name: &str,
+    ) -> Result<Option<RecallProfile>> {
+        let key = (agent_id.to_string(), name.to_string());
+        Ok(self.tables()?.recall_profiles.get(&key).cloned())
+    }
+
+    async fn list_recall_profiles(&self, agent_id: &str) -> Result<Vec<RecallProfile>> {
+        let tables = self.tables()?;
+        Ok(tables
+            .recall_profiles
+            .values()
+            .filter(|p| p.agent_id == agent_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_recall_profile(&self, agent_id: &str, name: &str) -> Result<bool> {
+        let key = (agent_id.to_string(), name.to_string());
+        Ok(self.tables()?.recall_profiles.remove(&key).is_some())
+    }
+
+    async fn record_recall_profile_use(
+        &self,
+        agent_id: &str,
+        name: &str,
+        used_at: &str,
+    ) -> Result<()> {
+        let key = (agent_id.to_string(), name.to_string());
+        if let Some(profile) = self.tables()?.recall_profiles.get_mut(&key) {
+            profile.use_count += 1;
+            profile.last_used_at = Some(used_at.to_string());
+        }
+        Ok(())
+    }
+
+    /// Every check runs before the first row is written, so a failing
+    /// batch leaves no trace.
+    async fn commit_write(&self, batch: &WriteBatch) -> Result<()> {
+        let mut tables = self.tables()?;
+        check_new(
+            &tables.memories,
+            batch.memory.iter().map(|m| &m.id),
+            "memory",
+        )?;
+        for record in &batch.updates {
+            let written = batch.memory.as_ref().is_some_and(|m| m.id == record.id);
+            if !written && !tables.memories.contains_key(&record.id) {
+                return Err(Error::NotFound(format!("memory {} not found", record.id)));
+            }
+        }
+        check_new(&tables.acls, batch.acls.iter().map(|a| &a.id), "acl")?;
+        check_new(
+            &tables.relations,
+            batch.relations.iter().map(|r| &r.id),
+            "relation",
+        )?;
+        check_new(&tables.events, batch.events.iter().map(|e| &e.id), "event")?;
+        check_new(&tables.outbox, batch.outbox.iter().map(|m| &m.id), "outbox")?;
+
+        if let Some(ref record) = batch.memory {
+            tables.memories.insert(record.id, record.clone());
+        }
+        for record in &batch.updates {
+            tables.update_memory(record)?;
+        }
+        for acl in &batch.acls {
+            tables.acls.insert(acl.id, acl.clone());
+        }
+        for relation in &batch.relations {
+            tables.relations.insert(relation.id, relation.clone());
+        }
+        for event in &batch.events {
+            tables.events.insert(event.id, event.clone());
+        }
+        for message in &batch.outbox {
+            tables.outbox.insert(
+                message.id,
+                OutboxEntry {
+                    message: message.clone(),
+                    available_at: None,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    async fn claim_outbox(&self, limit: usize, lease_until: &str) -> Result<Vec<OutboxMessage>> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut tables = self.tables()?;
+        let mut ready: Vec<(String, Uuid)> = tables
+            .outbox
+            .values()
+            .filter(|e| e.message.delivered_at.is_none())
+            .filter(|e| {
+                e.available_at
+                    .as_deref()
+                    .is_none_or(|at| at <= now.as_str())
+            })
+            .map(|e| (e.message.created_at.clone(), e.message.id))
+            .collect();
+        ready.sort();
+        let mut claimed = Vec::new();
+        for (_, id) in ready.into_iter().take(limit) {
+            if let Some(entry) = tables.outbox.get_mut(&id) {
+                entry.message.attempts += 1;
+                entry.available_at = Some(lease_until.to_string());
+                claimed.push(entry.message.clone());
+            }
+        }
+        Ok(claimed)
+    }
+
+    async fn complete_outbox(&self, id: Uuid) -> Result<()> {
+        if let Some(entry) = self.tables()?.outbox.get_mut(&id) {
+            entry.message.delivered_at = Some(chrono::Utc::now().to_rfc3339());
+            entry.message.last_error = None;
+        }
+        Ok(())
+    }
+
+    async fn fail_outbox(&self, id: Uuid, error: &str, retry_at: &str) -> Result<()> {
+        if let Some(entry) = self.tables()?.outbox.get_mut(&id)
+            && entry.message.delivered_at.is_none()
+        {
+            entry.message.last_error = Some(error.to_string());
+            entry.available_at = Some(retry_at.to_string());
+        }
+        Ok(())
+    }
+
+    async fn prune_outbox(&self, cutoff: &str) -> Result<usize> {
+        let mut tables = self.tables()?;
+        let before = tables.outbox.len();
+        tables.outbox.retain(|_, e| {
+            e.message
+                .delivered_at
+                .as_deref()
+                .is_none_or(|at| at >= cutoff)
+        });
+        Ok(before - tables.outbox.len())
+    }
+
+    async fn insert_checkpoint(&self, cp: &Checkpoint) -> Result<()> {
+        let mut tables = self.tables()?;
+        check_new(&tables.checkpoints, [&cp.id], "checkpoint")?;
+        tables.checkpoints.insert(cp.id, cp.clone());
+        Ok(())
+    }
+
+    async fn get_checkpoint(&self, id: Uuid) -> Result<Option<Checkpoint>> {
+        Ok(self.tables()?.checkpoints.get(&id).cloned())
+    }
+
+    async fn list_checkpoints(
+        &self,
+        thread_id: &str,
+        branch: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<Checkpoint>> {
+        let tables = self.tables()?;
+        let mut checkpoints: Vec<&Checkpoint> = tables
+            .checkpoints
+            .values()
+            .filter(|cp| cp.thread_id == thread_id)
+            .filter(|cp| branch.is_none_or(|b| cp.branch_name == b))
+            .collect();
+        checkpoints.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(page(checkpoints.into_iter().cloned(), limit, 0))
+    }
+
+    async fn get_latest_checkpoint(
+        &self,
+        thread_id: &str,
+        branch: &str,
+    ) -> Result<Option<Checkpoint>> {
+        Ok(self
+            .list_checkpoints(thread_id, Some(branch), 1)
+            .await?
+            .pop())
+    }
+
+    async fn thread_owner(&self, thread_id: &str) -> Result<Option<String>> {
+        let tables = self.tables()?;
+        let in_thread = |t: Option<&str>| t == Some(thread_id);
+        let written = tables
+            .memories
+            .values()
+            .filter(|m| in_thread(m.thread_id.as_deref()))
+            .map(|m| (m.created_at.as_str(), &m.agent_id))
+            .chain(
+                tables
+                    .events
+                    .values()
+                    .filter(|e| {
+                        in_thread(e.thread_id.as_deref()) && e.event_type != EventType::Checkpoint
+                    })
+                    .map(|e| (e.timestamp.as_str(), &e.agent_id)),
+            )
+            .min();
+        let owner = written.or_else(|| {
+            tables
+                .checkpoints
+                .values()
+                .filter(|cp| cp.thread_id == thread_id)
+                .map(|cp| (cp.created_at.as_str(), &cp.agent_id))
+                .min()
+        });
+        Ok(owner.map(|(_, agent_id)| agent_id.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::compute_content_hash;
+    use crate::model::event::EventType;
+    use crate::model::memory::{ConsolidationState, MemoryType, SourceType};
+
+    fn make_record(agent_id: &str) -> MemoryRecord {
+        let now = chrono::Utc::now().to_rfc3339();
+        let content = "test memory content";
+        MemoryRecord {
+            id: Uuid::now_v7(),
+            agent_id: agent_id.to_string(),
+            content: content.to_string(),
+            memory_type: MemoryType::Semantic,
+            scope: Scope::Private,
+            importance: 0.7,
+            tags: vec!["test".to_string()],
+            metadata: serde_json::json!({"key": "value"}),
+            embedding: Some(vec![0.1, 0.2, 0.3]),
+            content_hash: compute_content_hash(content, agent_id, &now),
+            prev_hash: None,
+            source_type: SourceType::Agent,
+            source_id: None,
+            consolidation_state: ConsolidationState::Raw,
+            access_count: 0,
+            org_id: None,
+            thread_id: None,
+            created_at: now.clone(),
+            updated_at: now,
+            last_accessed_at: None,
+            expires_at: None,
+            deleted_at: None,
+            decay_rate: None,
+            created_by: None,
+            version: 1,
+            prev_version_id: None,
+            quarantined: false,
+            quarantine_reason: None,
+            decay_function: None,
+            pinned: false,
+            valid_from: None,
+            valid_until: None,
+            curation: None,
+            attestation: None,
+            channel: None,
+        }
+    }
+
+    fn make_event(agent_id: &str, thread_id: Option<&str>, clock: i64) -> AgentEvent {
+        AgentEvent {
+            id: Uuid::now_v7(),
+            agent_id: agent_id.to_string(),
+            thread_id: thread_id.map(str::to_string),
+            run_id: None,
+            parent_event_id: None,
+            event_type: EventType::MemoryWrite,
+            payload: serde_json::json!({"clock": clock}),
+            trace_id: None,
+            span_id: None,
+            model: None,
+            tokens_input: None,
+            tokens_output: None,
+            latency_ms: None,
+            cost_usd: None,
+            timestamp: format!("2026-01-01T00:00:{clock:02}Z"),
+            logical_clock: clock,
+            content_hash: vec![clock as u8],
+            prev_hash: None,
+            embedding: None,
+            request_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_insert_get_and_duplicate_key() {
+        let storage = InMemoryStorage::new();
+        let record = make_record("agent-1");
+        storage.insert_memory(&record).await.unwrap();
+
+        let fetched = storage.get_memory(record.id).await.unwrap().unwrap();
+        assert_eq!(fetched, record);
+        assert!(storage.get_memory(Uuid::now_v7()).await.unwrap().is_none());
+
+        let err = storage.insert_memory(&record).await.unwrap_err();
+        assert!(matches!(err, Error::Storage(_)), "{err}");
+
+        let partial = storage
+            .get_memories(
+                &[record.id, record.id],
+                MemoryColumns {
+                    content: false,
+                    metadata: true,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(partial.len(), 1);
+        assert_eq!(partial[0].content, "");
+        assert_eq!(partial[0].metadata, record.metadata);
+        assert!(partial[0].embedding.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_update_keeps_created_at_and_checks_version() {
+        let storage = InMemoryStorage::new();
+        let record = make_record("agent-1");
+        storage.insert_memory(&record).await.unwrap();
+
+        let mut updated = record.clone();
+        updated.content = "rewritten".to_string();
+        updated.created_at = "1999-01-01T00:00:00Z".to_string();
+        updated.version = 2;
+        assert!(!storage.update_memory_if_version(&updated, 7).await.unwrap());
+        assert!(storage.update_memory_if_version(&updated, 1).await.unwrap());
+        let fetched = storage.get_memory(record.id).await.unwrap().unwrap();
+        assert_eq!(fetched.content, "rewritten");
+        assert_eq!(fetched.created_at, record.created_at);
+
+        let missing = make_record("agent-1");
+        let err = storage.update_memory(&missing).await.unwrap_err();
+        assert!(matches!(err, Error::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_soft_and_hard_delete() {
+        let storage = InMemoryStorage::new();
+        let record = make_record("agent-1");
+        storage.insert_memory(&record).await.unwrap();
+
+        storage.soft_delete_memory(record.id).await.unwrap();
+        let fetched = storage.get_memory(record.id).await.unwrap().unwrap();
+        assert!(fetched.deleted_at.is_some());
+        assert!(storage.soft_delete_memory(record.id).await.is_err());
+
+        let filter = MemoryFilter::default();
+        assert!(
+            storage
+                .list_memories(&filter, 100, 0)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let filter = MemoryFilter {
+            include_deleted: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            storage.list_memories(&filter, 100, 0).await.unwrap().len(),
+            1
+        );
+
+        storage.hard_delete_memory(record.id).await.unwrap();
+        assert!(storage.get_memory(record.id).await.unwrap().is_none());
+        assert!(matches!(
+            storage.hard_delete_memory(record.id).await,
+            Err(Error::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_with_filters() {
+        let storage = InMemoryStorage::new();
+
+        let mut r1 = make_record("agent-1");
+        r1.memory_type = MemoryType::Episodic;
+        r1.created_at = "2026-01-01T00:00:00Z".to_string();
+        storage.insert_memory(&r1).await.unwrap();
+
+        let mut r2 = make_record("agent-1");
+        r2.created_at = "2026-01-02T00:00:00Z".to_string();
+        storage.insert_memory(&r2).await.unwrap();
+
+        let r3 = make_record("agent-2");
+        storage.insert_memory(&r3).await.unwrap();
+
+        let filter = MemoryFilter {
+            agent_id: Some("agent-1".to_string()),
+            ..Default::default()
+        };
+        let list = storage.list_memories(&filter, 100, 0).await.unwrap();
+        assert_eq!(
+            list.iter().map(|m| m.id).collect::<Vec<_>>(),
+            vec![r2.id, r1.id]
+        );
+        let list = storage.list_memories(&filter, 1, 1).await.unwrap();
+        assert_eq!(list[0].id, r1.id);
+
+        let filter = MemoryFilter {
+            memory_type: Some(MemoryType::Semantic),
+            ..Default::default()
+        };
+        assert_eq!(
+            storage.list_memories(&filter, 100, 0).await.unwrap().len(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_touch_memory() {
+        let storage = InMemoryStorage::new();
+        let record = make_record("agent-1");
+        storage.insert_memory(&record).await.unwrap();
+
+        storage.touch_memory(record.id).await.unwrap();
+        storage.touch_memory(record.id).await.unwrap();
+
+        let fetched = storage.get_memory(record.id).await.unwrap().unwrap();
+        assert_eq!(fetched.access_count, 2);
+        assert!(fetched.last_accessed_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_permissions_acl_channel_and_delegation() {
+        let storage = InMemoryStorage::new();
+        let mut record = make_record("agent-1");
+        record.channel = Some("ops".to_string());
+        record.tags = vec!["shared".to_string()];
+        storage.insert_memory(&record).await.unwrap();
+
+        assert!(
+            storage
+                .check_permission(record.id, "agent-1", Permission::Admin)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !storage
+                .check_permission(record.id, "agent-2", Permission::Read)
+                .await
+                .unwrap()
+        );
+        assert!(matches!(
+            storage
+                .check_permission(Uuid::now_v7(), "agent-2", Permission::Read)
+                .await,
+            Err(Error::NotFound(_))
+        ));
+
+        let acl = Acl {
+            id: Uuid::now_v7(),
+            memory_id: record.id,
+            principal_type: PrincipalType::Agent,
+            principal_id: "agent-2".to_string(),
+            permission: Permission::Read,
+            granted_by: "agent-1".to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            expires_at: None,
+        };
+        storage.insert_acl(&acl).await.unwrap();
+        assert!(
+            storage
+                .check_permission(record.id, "agent-2", Permission::Read)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !storage
+                .check_permission(record.id, "agent-2", Permission::Write)
+                .await
+                .unwrap()
+        );
+
+        let member = ChannelMember {
+            channel: "ops".to_string(),
+            agent_id: "agent-3".to_string(),
+            joined_at: chrono::Utc::now().to_rfc3339(),
+        };
+        assert!(storage.add_channel_member(&member).await.unwrap());
+        assert!(!storage.add_channel_member(&member).await.unwrap());
+        assert!(
+            storage
+                .check_permission(record.id, "agent-3", Permission::Read)
+                .await
+                .unwrap()
+        );
+
+        let delegation = Delegation {
+            id: Uuid::now_v7(),
+            delegator_id: "agent-1".to_string(),
+            delegate_id: "agent-4".to_string(),
+            permission: Permission::Write,
+            scope: DelegationScope::ByTag(vec!["shared".to_string()]),
+            max_depth: 1,
+            current_depth: 0,
+            parent_delegation_id: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            expires_at: None,
+            revoked_at: None,
+        };
+        storage.insert_delegation(&delegation).await.unwrap();
+        assert!(
+            storage
+                .check_permission(record.id, "agent-4", Permission::Write)
+                .await
+                .unwrap()
+        );
+        storage.revoke_delegation(delegation.id).await.unwrap();
+        assert!(
+            !storage
+                .check_permission(record.id, "agent-4", Permission::Write)
+                .await
+                .unwrap()
+        );
+
+        let mut ids = storage
+            .list_accessible_memory_ids("agent-2", 10)
+            .await
+            .unwrap();
+        ids.extend(
+            storage
+                .list_accessible_memory_ids("agent-3", 10)
+                .await
+                .unwrap(),
+        );
+        assert_eq!(ids, vec![record.id, record.id]);
+        assert!(
+            storage
+                .list_accessible_memory_ids("agent-4", 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_events_chain_heads_and_archive() {
+        let storage = InMemoryStorage::new();
+        for clock in 1..=3 {
+            storage
+                .insert_event(&make_event("agent-1", Some("thread-A"), clock))
+                .await
+                .unwrap();
+        }
+        let other = make_event("agent-1", Some("thread-B"), 4);
+        storage.insert_event(&other).await.unwrap();
+
+        assert_eq!(storage.max_logical_clock("agent-1").await.unwrap(), 4);
+        assert_eq!(storage.max_logical_clock("agent-2").await.unwrap(), 0);
+        assert_eq!(
+            storage
+                .get_latest_event_hash("agent-1", Some("thread-A"))
+                .await
+                .unwrap(),
+            Some(vec![3])
+        );
+        assert_eq!(
+            storage
+                .get_latest_event_hash("agent-1", None)
+                .await
+                .unwrap(),
+            Some(vec![4])
+        );
+
+        let thread_a = storage.get_events_by_thread("thread-A", 10).await.unwrap();
+        assert_eq!(
+            thread_a.iter().map(|e| e.logical_clock).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        let newest = storage.list_events("agent-1", 2, 0).await.unwrap();
+        assert_eq!(newest[0].id, other.id);
+
+        let old = storage
+            .list_events_before("2026-01-01T00:00:03Z", 10)
+            .await
+            .unwrap();
+        assert_eq!(old.len(), 2);
+        let segment = EventArchiveSegment {
+            id: Uuid::now_v7(),
+            agent_id: "agent-1".to_string(),
+            first_event_id: old[0].id,
+            last_event_id: old[1].id,
+            from_timestamp: old[0].timestamp.clone(),
+            to_timestamp: old[1].timestamp.clone(),
+            first_logical_clock: 1,
+            last_logical_clock: 2,
+            event_count: 2,
+            merkle_root: "root".to_string(),
+            last_content_hash: "02".to_string(),
+            object_key: "events/agent-1/1-2".to_string(),
+            archived_at: chrono::Utc::now().to_rfc3339(),
+        };
+        let ids: Vec<Uuid> = old.iter().map(|e| e.id).collect();
+        storage.archive_event_segment(&segment, &ids).await.unwrap();
+        assert_eq!(
+            storage.list_events("agent-1", 10, 0).await.unwrap().len(),
+            2
+        );
+        assert_eq!(
+            storage.list_event_archives(Some("agent-1")).await.unwrap(),
+            vec![segment]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_commit_write_is_all_or_nothing() {
+        let storage = InMemoryStorage::new();
+        let existing = make_event("agent-1", None, 1);
+        storage.insert_event(&existing).await.unwrap();
+
+        let record = make_record("agent-1");
+        let batch = WriteBatch {
+            memory: Some(record.clone()),
+            events: vec![make_event("agent-1", None, 2), existing],
+            outbox: vec![OutboxMessage::new("memory_write", serde_json::json!({}))],
+            ..Default::default()
+        };
+        assert!(storage.commit_write(&batch).await.is_err());
+        assert!(storage.get_memory(record.id).await.unwrap().is_none());
+        assert_eq!(storage.max_logical_clock("agent-1").await.unwrap(), 1);
+
+        let batch = WriteBatch {
+            events: vec![make_event("agent-1", None, 2)],
+            ..batch
+        };
+        storage.commit_write(&batch).await.unwrap();
+        assert!(storage.get_memory(record.id).await.unwrap().is_some());
+
+        let lease = "9999-01-01T00:00:00Z";
+        let claimed = storage.claim_outbox(10, lease).await.unwrap();
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].attempts, 1);
+        assert!(storage.claim_outbox(10, lease).await.unwrap().is_empty());
+        storage.complete_outbox(claimed[0].id).await.unwrap();
+        assert_eq!(storage.prune_outbox(lease).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_list_and_latest() {
+        let storage = InMemoryStorage::new();
+        let checkpoint = |branch: &str, created_at: &str| Checkpoint {
+            id: Uuid::now_v7(),
+            thread_id: "thread-1".to_string(),
+            agent_id: "agent-1".to_string(),
+            parent_id: None,
+            branch_name: branch.to_string(),
+            state_snapshot: serde_json::json!({}),
+            state_diff: None,
+            memory_refs: vec![],
+            event_cursor: None,
+            label: None,
+            created_at: created_at.to_string(),
+            metadata: serde_json::json!({}),
+        };
+        let cp1 = checkpoint("main", "2025-01-01T00:00:00Z");
+        let cp2 = checkpoint("main", "2025-01-02T00:00:00Z");
+        let cp3 = checkpoint("experiment", "2025-01-03T00:00:00Z");
+        for cp in [&cp1, &cp2, &cp3] {
+            storage.insert_checkpoint(cp).await.unwrap();
+        }
+
+        assert_eq!(
+            storage
+                .list_checkpoints("thread-1", None, 10)
+                .await
+                .unwrap()
+                .len(),
+            3
+        );
+        assert_eq!(
+            storage
+                .list_checkpoints("thread-1", Some("main"), 10)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        let latest = storage
+            .get_latest_checkpoint("thread-1", "main")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(latest.id, cp2.id);
+        assert!(
+            storage
+                .get_latest_checkpoint("thread-1", "nonexistent")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -28,7 +28,15 @@ CREATE TABLE IF NOT EXISTS memories (
     prev_version_id VARCHAR,
     quarantined BOOLEAN NOT NULL DEFAULT false,
     quarantine_reason VARCHAR,
-    decay_function VARCHAR
+    decay_function VARCHAR,
+    pinned BOOLEAN NOT NULL DEFAULT false,
+    valid_from VARCHAR,
+    valid_until VARCHAR,
+    curation VARCHAR,
+    attestation VARCHAR,
+    channel VARCHAR,
+    content_zstd BLOB,
+    content_len BIGINT
 );
 CREATE INDEX IF NOT EXISTS idx_memories_agent_id ON memories(agent_id);
 CREATE INDEX IF NOT EXISTS idx_memories_scope ON memories(scope);
@@ -74,7 +82,8 @@ CREATE INDEX IF NOT EXISTS idx_relations_target ON relations(target_id);
 // so enforcement is application-level. The PostgreSQL backend uses a
 // BEFORE UPDATE OR DELETE trigger (prevent_event_modification) to enforce
 // this at the schema level. Application code must never UPDATE or DELETE
-// rows from this table.
+// rows from this table, except for event archival, which deletes rows only
+// after copying them to cold storage (see CREATE_EVENT_ARCHIVES_TABLE).
 pub const CREATE_AGENT_EVENTS_TABLE: &str = "
 CREATE TABLE IF NOT EXISTS agent_events (
     id VARCHAR PRIMARY KEY,
@@ -145,6 +154,37 @@ pub const SPRINT4_COLUMN_ALTERS: &[&str] = &[
     "ALTER TABLE memories ADD COLUMN decay_function VARCHAR",
 ];
 
+// Sprint 5 migrations: protected (pinned) memories.
+pub const SPRINT5_COLUMN_ALTERS: &[&str] =
+    &["ALTER TABLE memories ADD COLUMN pinned BOOLEAN DEFAULT false"];
+
+// Sprint 6 migrations: per-agent retrieval defaults (JSON-encoded
+// `RetrievalDefaults`).
+pub const SPRINT6_COLUMN_ALTERS: &[&str] =
+    &["ALTER TABLE agent_profiles ADD COLUMN retrieval_settings JSON"];
+
+// Sprint 7 migrations: fact validity windows on memories.
+pub const SPRINT7_COLUMN_ALTERS: &[&str] = &[
+    "ALTER TABLE memories ADD COLUMN valid_from VARCHAR",
+    "ALTER TABLE memories ADD COLUMN valid_until VARCHAR",
+];
+
+// Sprint 8 migrations: review state of memories (global curation and
+// tag-based attestation), the channel a memory was remembered into, and
+// compressed content. Existing rows are compressed when next updated.
+pub const SPRINT8_COLUMN_ALTERS: &[&str] = &[
+    "ALTER TABLE memories ADD COLUMN curation VARCHAR",
+    "ALTER TABLE memories ADD COLUMN attestation VARCHAR",
+    "ALTER TABLE memories ADD COLUMN channel VARCHAR",
+    "ALTER TABLE memories ADD COLUMN content_zstd BLOB",
+    "ALTER TABLE memories ADD COLUMN content_len BIGINT",
+];
+
+// Sprint 9 migrations: the correlation ID of the request that recorded an
+// event.
+pub const SPRINT9_COLUMN_ALTERS: &[&str] =
+    &["ALTER TABLE agent_events ADD COLUMN request_id VARCHAR"];
+
 pub const CREATE_DELEGATIONS_TABLE: &str = "
 CREATE TABLE IF NOT EXISTS delegations (
     id VARCHAR PRIMARY KEY,
@@ -207,9 +247,210 @@ CREATE TABLE IF NOT EXISTS embedding_baseline (
 );
 ";
 
+/// Named recall configurations ("saved searches"), one row per
+/// `(agent_id, name)`. `settings` is the JSON-encoded
+/// `RecallProfileSettings`.
+pub const CREATE_RECALL_PROFILES_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS recall_profiles (
+    agent_id VARCHAR NOT NULL,
+    name VARCHAR NOT NULL,
+    settings JSON NOT NULL,
+    use_count BIGINT NOT NULL DEFAULT 0,
+    last_used_at VARCHAR,
+    created_at VARCHAR NOT NULL,
+    updated_at VARCHAR NOT NULL,
+    PRIMARY KEY (agent_id, name)
+);
+";
+
+/// Conflict review queue. Pairs found by `detect_conflicts` that were not
+/// auto-resolved wait here for a human decision.
+pub const CREATE_CONFLICTS_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS conflicts (
+    id VARCHAR PRIMARY KEY,
+    agent_id VARCHAR NOT NULL,
+    memory_a VARCHAR NOT NULL,
+    memory_b VARCHAR NOT NULL,
+    similarity FLOAT NOT NULL,
+    reason VARCHAR NOT NULL,
+    status VARCHAR NOT NULL DEFAULT 'open',
+    resolution VARCHAR,
+    winner_id VARCHAR,
+    resolved_by VARCHAR,
+    created_at VARCHAR NOT NULL,
+    resolved_at VARCHAR
+);
+CREATE INDEX IF NOT EXISTS idx_conflicts_agent_status ON conflicts(agent_id, status);
+";
+
+/// Per-memory read log, written only when the engine has an access-log
+/// policy attached. One row per (read, memory); `query` is truncated.
+pub const CREATE_MEMORY_ACCESSES_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS memory_accesses (
+    id VARCHAR PRIMARY KEY,
+    memory_id VARCHAR NOT NULL,
+    principal VARCHAR NOT NULL,
+    operation VARCHAR NOT NULL,
+    query VARCHAR,
+    accessed_at VARCHAR NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_memory_accesses_memory ON memory_accesses(memory_id, accessed_at);
+";
+
+/// Transactional outbox. Rows are written in the same transaction as the
+/// memory write they announce and deleted by `prune_outbox` after delivery.
+/// `available_at` is the end of the current lease or retry delay.
+pub const CREATE_OUTBOX_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS outbox (
+    id VARCHAR PRIMARY KEY,
+    topic VARCHAR NOT NULL,
+    payload JSON NOT NULL,
+    created_at VARCHAR NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error VARCHAR,
+    delivered_at VARCHAR,
+    available_at VARCHAR
+);
+";
+
+/// Memory access requests. One agent asks another for memories it has not
+/// shared; the owner approves (creating the ACLs) or denies.
+pub const CREATE_ACCESS_REQUESTS_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS access_requests (
+    id VARCHAR PRIMARY KEY,
+    requester_id VARCHAR NOT NULL,
+    owner_id VARCHAR NOT NULL,
+    memory_ids JSON NOT NULL,
+    tags JSON NOT NULL,
+    justification VARCHAR NOT NULL,
+    permission VARCHAR NOT NULL,
+    expires_in_hours DOUBLE,
+    status VARCHAR NOT NULL DEFAULT 'pending',
+    decided_by VARCHAR,
+    decision_note VARCHAR,
+    acl_ids JSON NOT NULL,
+    created_at VARCHAR NOT NULL,
+    decided_at VARCHAR
+);
+CREATE INDEX IF NOT EXISTS idx_access_requests_owner_status ON access_requests(owner_id, status);
+CREATE INDEX IF NOT EXISTS idx_access_requests_requester ON access_requests(requester_id);
+";
+
+/// Channels: named shared spaces. Every member can read the memories
+/// remembered into a channel without per-memory ACLs.
+pub const CREATE_CHANNELS_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS channels (
+    name VARCHAR PRIMARY KEY,
+    created_by VARCHAR NOT NULL,
+    max_memories BIGINT,
+    created_at VARCHAR NOT NULL
+);
+CREATE TABLE IF NOT EXISTS channel_members (
+    channel VARCHAR NOT NULL,
+    agent_id VARCHAR NOT NULL,
+    joined_at VARCHAR NOT NULL,
+    PRIMARY KEY (channel, agent_id)
+);
+CREATE INDEX IF NOT EXISTS idx_channel_members_agent ON channel_members(agent_id);
+CREATE INDEX IF NOT EXISTS idx_memories_channel ON memories(channel);
+";
+
+/// Manifest of event segments moved to cold storage by event archival. Each
+/// row keeps the Merkle root of the events it replaced in `agent_events`.
+pub const CREATE_EVENT_ARCHIVES_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS event_archives (
+    id VARCHAR PRIMARY KEY,
+    agent_id VARCHAR NOT NULL,
+    first_event_id VARCHAR NOT NULL,
+    last_event_id VARCHAR NOT NULL,
+    from_timestamp VARCHAR NOT NULL,
+    to_timestamp VARCHAR NOT NULL,
+    first_logical_clock BIGINT NOT NULL,
+    last_logical_clock BIGINT NOT NULL,
+    event_count BIGINT NOT NULL,
+    merkle_root VARCHAR NOT NULL,
+    last_content_hash VARCHAR NOT NULL,
+    object_key VARCHAR NOT NULL,
+    archived_at VARCHAR NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_event_archives_agent ON event_archives(agent_id, from_timestamp);
+";
+
+/// Verified-up-to markers for incremental chain verification, one per
+/// (agent, thread) chain. `thread_id` is '' for an agent's whole chain so
+/// it can be part of the key.
+pub const CREATE_CHAIN_CHECKPOINTS_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS chain_checkpoints (
+    agent_id VARCHAR NOT NULL,
+    thread_id VARCHAR NOT NULL,
+    verified_count BIGINT NOT NULL,
+    last_record_id VARCHAR NOT NULL,
+    last_created_at VARCHAR NOT NULL,
+    last_content_hash VARCHAR NOT NULL,
+    rolling_root VARCHAR NOT NULL,
+    verified_at VARCHAR NOT NULL,
+    PRIMARY KEY (agent_id, thread_id)
+);
+";
+
+/// Share links revoked before they expired. See
+/// [`crate::query::share_link`].
+pub const CREATE_REVOKED_SHARE_LINKS_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS revoked_share_links (
+    link_id VARCHAR NOT NULL,
+    memory_id VARCHAR NOT NULL,
+    revoked_by VARCHAR NOT NULL,
+    revoked_at VARCHAR NOT NULL,
+    expires_at VARCHAR NOT NULL,
+    PRIMARY KEY (link_id, memory_id)
+);
+";
+
+/// Per-org pseudonym alias tables. `org_id` is '' for memories without an
+/// org so it can be part of the key. See [`crate::query::pseudonym`].
+pub const CREATE_PSEUDONYMS_TABLE: &str = "
+CREATE TABLE IF NOT EXISTS pseudonyms (
+    org_id VARCHAR NOT NULL,
+    alias VARCHAR NOT NULL,
+    sealed_agent_id VARCHAR NOT NULL,
+    created_at VARCHAR NOT NULL,
+    PRIMARY KEY (org_id, alias)
+);
+";
+
+/// Move alias entries recorded under `pseudonym:<org>:<alias>` keys of the
+/// sync watermark store into the pseudonym table. Aliases never contain
+/// ':', so the org is everything up to the last one.
+pub const MOVE_PSEUDONYMS_OUT_OF_SYNC_METADATA: &str = "
+INSERT OR IGNORE INTO pseudonyms (org_id, alias, sealed_agent_id, created_at)
+SELECT regexp_extract(key, '^pseudonym:(.*):([^:]*)$', 1),
+       regexp_extract(key, '^pseudonym:(.*):([^:]*)$', 2),
+       value,
+       CAST(updated_at AS VARCHAR)
+FROM sync_metadata
+WHERE key LIKE 'pseudonym:%';
+DELETE FROM sync_metadata WHERE key LIKE 'pseudonym:%';
+";
+
+/// Keep grants made before export and replay became permissions of their
+/// own working. Every live all-memories delegation, which used to allow
+/// replaying the delegator's threads, gets a replay companion, and every
+/// share or delegate ACL, whose holder could already pass the memory on,
+/// gets an export companion. Runs once, on files stamped before v5.
+pub const GRANT_EXPORT_AND_REPLAY: &str = "
+INSERT INTO delegations (id, delegator_id, delegate_id, permission, scope_type, scope_value, max_depth, current_depth, parent_delegation_id, created_at, expires_at, revoked_at)
+SELECT CAST(uuid() AS VARCHAR), delegator_id, delegate_id, 'replay', scope_type, scope_value, max_depth, current_depth, parent_delegation_id, created_at, expires_at, NULL
+FROM delegations
+WHERE scope_type = 'all_memories' AND revoked_at IS NULL AND permission NOT IN ('admin', 'replay');
+INSERT INTO acls (id, memory_id, principal_type, principal_id, permission, granted_by, created_at, expires_at)
+SELECT CAST(uuid() AS VARCHAR), memory_id, principal_type, principal_id, 'export', granted_by, created_at, expires_at
+FROM acls
+WHERE permission IN ('share', 'delegate');
+";
+
 /// Persistence format version this release writes. Bump when the on-disk
 /// schema changes in a way that requires a migrator pass.
-pub const CURRENT_PERSISTENCE_VERSION: u32 = 4;
+pub const CURRENT_PERSISTENCE_VERSION: u32 = 5;
 
 pub fn run_migrations(conn: &duckdb::Connection) -> duckdb::Result<()> {
     conn.execute_batch(CREATE_MEMORIES_TABLE)?;
@@ -228,6 +469,20 @@ pub fn run_migrations(conn: &duckdb::Connection) -> duckdb::Result<()> {
     conn.execute_batch(CREATE_AGENT_PROFILES_TABLE)?;
     // Sprint 4 column upgrades.
     apply_alters_idempotent(conn, SPRINT4_COLUMN_ALTERS)?;
+    // Sprint 5 column upgrades.
+    apply_alters_idempotent(conn, SPRINT5_COLUMN_ALTERS)?;
+    // Sprint 6 column upgrades.
+    apply_alters_idempotent(conn, SPRINT6_COLUMN_ALTERS)?;
+    // Sprint 7 column upgrades.
+    apply_alters_idempotent(conn, SPRINT7_COLUMN_ALTERS)?;
+    // Sprint 8 column upgrades.
+    apply_alters_idempotent(conn, SPRINT8_COLUMN_ALTERS)?;
+    // Sprint 9 column upgrades.
+    apply_alters_idempotent(conn, SPRINT9_COLUMN_ALTERS)?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_events_request_id ON agent_events(request_id)",
+        [],
+    )?;
     // Create parent_event_id index if missing — `IF NOT EXISTS` is
     // first-class, no introspection required.
     conn.execute(
@@ -240,6 +495,30 @@ pub fn run_migrations(conn: &duckdb::Connection) -> duckdb::Result<()> {
     conn.execute_batch(CREATE_MNEMO_META_TABLE)?;
     // v0.3.3: embedding baseline table (z-score outlier detector).
     conn.execute_batch(CREATE_EMBEDDING_BASELINE_TABLE)?;
+    // Saved recall profiles.
+    conn.execute_batch(CREATE_RECALL_PROFILES_TABLE)?;
+    // Conflict review queue.
+    conn.execute_batch(CREATE_CONFLICTS_TABLE)?;
+    // Per-memory access log.
+    conn.execute_batch(CREATE_MEMORY_ACCESSES_TABLE)?;
+    // Transactional outbox.
+    conn.execute_batch(CREATE_OUTBOX_TABLE)?;
+    // Memory access requests.
+    conn.execute_batch(CREATE_ACCESS_REQUESTS_TABLE)?;
+    // Channels and their members.
+    conn.execute_batch(CREATE_CHANNELS_TABLE)?;
+    // Archived event segments.
+    conn.execute_batch(CREATE_EVENT_ARCHIVES_TABLE)?;
+    conn.execute_batch(CREATE_CHAIN_CHECKPOINTS_TABLE)?;
+    // Share link denylist.
+    conn.execute_batch(CREATE_REVOKED_SHARE_LINKS_TABLE)?;
+    // Pseudonym alias tables.
+    conn.execute_batch(CREATE_PSEUDONYMS_TABLE)?;
+    conn.execute_batch(MOVE_PSEUDONYMS_OUT_OF_SYNC_METADATA)?;
+    // v5: export and replay became permissions of their own.
+    if read_persistence_version(conn)?.is_none_or(|v| v < 5) {
+        conn.execute_batch(GRANT_EXPORT_AND_REPLAY)?;
+    }
     stamp_persistence_version(conn)?;
     Ok(())
 }
@@ -387,6 +666,59 @@ mod tests {
         );
     }
 
+    /// Grants made before v5 keep replay and export working after the
+    /// upgrade, and the companions are only added once.
+    #[test]
+    fn test_v4_grants_gain_export_and_replay_companions() {
+        let conn = duckdb::Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO delegations (id, delegator_id, delegate_id, permission, scope_type, created_at) VALUES
+                ('d1', 'owner', 'reviewer', 'read', 'all_memories', '2025-01-01T00:00:00Z'),
+                ('d2', 'owner', 'tagger', 'write', 'by_tag', '2025-01-01T00:00:00Z');
+             INSERT INTO delegations (id, delegator_id, delegate_id, permission, scope_type, created_at, revoked_at) VALUES
+                ('d3', 'owner', 'former', 'read', 'all_memories', '2025-01-01T00:00:00Z', '2025-02-01T00:00:00Z');
+             INSERT INTO acls (id, memory_id, principal_type, principal_id, permission, granted_by, created_at) VALUES
+                ('a1', 'm1', 'agent', 'partner', 'share', 'owner', '2025-01-01T00:00:00Z'),
+                ('a2', 'm1', 'agent', 'viewer', 'read', 'owner', '2025-01-01T00:00:00Z');
+             UPDATE mnemo_meta SET value = '4' WHERE key = 'persistence_version';",
+        )
+        .unwrap();
+
+        let companions = |conn: &duckdb::Connection| -> (Vec<String>, Vec<String>) {
+            let mut stmt = conn
+                .prepare("SELECT delegate_id FROM delegations WHERE permission = 'replay' ORDER BY delegate_id")
+                .unwrap();
+            let replay = stmt
+                .query_map([], |row| row.get(0))
+                .unwrap()
+                .map(|r| r.unwrap())
+                .collect();
+            let mut stmt = conn
+                .prepare("SELECT principal_id FROM acls WHERE permission = 'export' ORDER BY principal_id")
+                .unwrap();
+            let export = stmt
+                .query_map([], |row| row.get(0))
+                .unwrap()
+                .map(|r| r.unwrap())
+                .collect();
+            (replay, export)
+        };
+        run_migrations(&conn).unwrap();
+        assert_eq!(
+            companions(&conn),
+            (vec!["reviewer".to_string()], vec!["partner".to_string()])
+        );
+        assert_eq!(
+            read_persistence_version(&conn).unwrap(),
+            Some(CURRENT_PERSISTENCE_VERSION)
+        );
+
+        run_migrations(&conn).unwrap();
+        assert_eq!(companions(&conn).0.len(), 1);
+        assert_eq!(companions(&conn).1.len(), 1);
+    }
+
     #[test]
     fn sprint_alters_match_expected_shape() {
         // v0.4.2 (#41 Step 1): `apply_alters_idempotent` parses
@@ -396,6 +728,11 @@ mod tests {
         for sql in SPRINT3_COLUMN_ALTERS
             .iter()
             .chain(SPRINT4_COLUMN_ALTERS.iter())
+            .chain(SPRINT5_COLUMN_ALTERS.iter())
+            .chain(SPRINT6_COLUMN_ALTERS.iter())
+            .chain(SPRINT7_COLUMN_ALTERS.iter())
+            .chain(SPRINT8_COLUMN_ALTERS.iter())
+            .chain(SPRINT9_COLUMN_ALTERS.iter())
         {
             let parsed = parse_alter_table_add_column(sql);
             assert!(
@@ -1,23 +1,57 @@
 pub mod cold;
+#[cfg(feature = "conformance")]
+pub mod conformance;
+pub mod content_codec;
 pub mod duckdb;
+#[cfg(feature = "memory-storage")]
+pub mod memory;
 pub mod migrations;
 
 use crate::error::Result;
+use crate::model::access_log::MemoryAccess;
+use crate::model::access_request::{AccessRequest, AccessRequestStatus};
 use crate::model::acl::{Acl, Permission};
-use crate::model::agent_profile::AgentProfile;
+use crate::model::agent_profile::{AgentProfile, RetrievalDefaults};
+use crate::model::chain_checkpoint::ChainCheckpoint;
+use crate::model::channel::{Channel, ChannelMember};
 use crate::model::checkpoint::Checkpoint;
+use crate::model::conflict::{ConflictRecord, ConflictStatus};
+use crate::model::content_stats::{ContentStorageStats, DailyGrowth};
 use crate::model::delegation::Delegation;
 use crate::model::embedding_baseline::EmbeddingBaseline;
 use crate::model::event::AgentEvent;
+use crate::model::event_archive::EventArchiveSegment;
 use crate::model::memory::MemoryRecord;
+use crate::model::outbox::OutboxMessage;
+use crate::model::pseudonym::PseudonymEntry;
+use crate::model::recall_profile::RecallProfile;
 use crate::model::relation::Relation;
+use crate::model::share_link::RevokedShareLink;
 use uuid::Uuid;
 
+tokio::task_local! {
+    static AGENT_CONTEXT: String;
+}
+
+/// Run `fut` on behalf of `agent_id`. Backends that enforce isolation in
+/// the database (PostgreSQL row-level security) scope every query `fut`
+/// makes to what that agent may see. Tasks spawned inside `fut` do not
+/// inherit the context.
+pub async fn with_agent_context<F: std::future::Future>(agent_id: String, fut: F) -> F::Output {
+    AGENT_CONTEXT.scope(agent_id, fut).await
+}
+
+/// The agent set by the innermost [`with_agent_context`], if any.
+pub fn current_agent_context() -> Option<String> {
+    AGENT_CONTEXT.try_with(Clone::clone).ok()
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct MemoryFilter {
     pub agent_id: Option<String>,
     pub memory_type: Option<crate::model::memory::MemoryType>,
     pub scope: Option<crate::model::memory::Scope>,
+    /// Memories carrying any of these tags.
     pub tags: Option<Vec<String>>,
     pub min_importance: Option<f32>,
     pub org_id: Option<String>,
@@ -25,12 +59,69 @@ pub struct MemoryFilter {
     pub include_deleted: bool,
 }
 
+/// The large columns of a memory that [`StorageBackend::get_memories`]
+/// reads. A column left out comes back empty: `""` for content, `{}` for
+/// metadata. The embedding is never read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryColumns {
+    pub content: bool,
+    pub metadata: bool,
+}
+
+impl MemoryColumns {
+    pub const ALL: Self = Self {
+        content: true,
+        metadata: true,
+    };
+}
+
+/// Size of the database before and after [`StorageBackend::compact`], in
+/// bytes. Both are 0 for an in-memory database.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CompactionReport {
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+impl CompactionReport {
+    pub fn reclaimed_bytes(&self) -> u64 {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+}
+
+/// Rows that must be written together or not at all: a memory, the
+/// relations and audit events that describe it, and the outbox messages
+/// announcing it. See [`StorageBackend::commit_write`].
+#[derive(Debug, Clone, Default)]
+pub struct WriteBatch {
+    pub memory: Option<MemoryRecord>,
+    /// Existing memories to overwrite, as with
+    /// [`StorageBackend::update_memory`].
+    pub updates: Vec<MemoryRecord>,
+    pub acls: Vec<Acl>,
+    pub relations: Vec<Relation>,
+    pub events: Vec<AgentEvent>,
+    pub outbox: Vec<OutboxMessage>,
+}
+
 #[async_trait::async_trait]
 pub trait StorageBackend: Send + Sync {
     // Memory CRUD
     async fn insert_memory(&self, record: &MemoryRecord) -> Result<()>;
     async fn get_memory(&self, id: Uuid) -> Result<Option<MemoryRecord>>;
+    /// The memories among `ids` that exist, deleted ones included, in no
+    /// particular order. Only the large columns in `columns` are read.
+    async fn get_memories(&self, ids: &[Uuid], columns: MemoryColumns)
+    -> Result<Vec<MemoryRecord>>;
     async fn update_memory(&self, record: &MemoryRecord) -> Result<()>;
+    /// Write `record` only if the stored row is still at `expected_version`.
+    /// Returns `false`, writing nothing, when the version differs or the
+    /// row is gone.
+    async fn update_memory_if_version(
+        &self,
+        record: &MemoryRecord,
+        expected_version: u32,
+    ) -> Result<bool>;
     async fn soft_delete_memory(&self, id: Uuid) -> Result<()>;
     async fn hard_delete_memory(&self, id: Uuid) -> Result<()>;
     async fn list_memories(
@@ -67,13 +158,41 @@ pub trait StorageBackend: Send + Sync {
         agent_id: &str,
         thread_id: Option<&str>,
     ) -> Result<Option<Vec<u8>>>;
+    /// Highest `logical_clock` among the agent's events, `0` if none.
+    async fn max_logical_clock(&self, agent_id: &str) -> Result<i64>;
 
     // Sync watermarks
     async fn get_sync_watermark(&self, key: &str) -> Result<Option<String>>;
     async fn set_sync_watermark(&self, key: &str, value: &str) -> Result<()>;
 
+    // Share link denylist
+    /// Put a link on the denylist; revoking it again is a no-op. Entries
+    /// are keyed by link and memory.
+    async fn revoke_share_link(&self, revoked: &RevokedShareLink) -> Result<()>;
+    /// Whether the link `link_id` to `memory_id` is on the denylist. An
+    /// entry recorded against another memory does not count.
+    async fn is_share_link_revoked(&self, link_id: Uuid, memory_id: Uuid) -> Result<bool>;
+    /// Drop entries whose `expires_at` is before `now` (RFC 3339).
+    async fn purge_revoked_share_links(&self, now: &str) -> Result<usize>;
+
+    // Pseudonym alias tables
+    /// Add an alias to its org's table. An alias already there keeps its
+    /// first entry.
+    async fn record_pseudonym(&self, entry: &PseudonymEntry) -> Result<()>;
+    /// The entry for `alias` in `org_id`'s table (`""` for no org).
+    async fn get_pseudonym(&self, org_id: &str, alias: &str) -> Result<Option<PseudonymEntry>>;
+
     // Permission-safe ANN
     async fn list_accessible_memory_ids(&self, agent_id: &str, limit: usize) -> Result<Vec<Uuid>>;
+    /// [`list_accessible_memory_ids`](Self::list_accessible_memory_ids)
+    /// limited to memories created at or before `as_of` (RFC 3339, UTC),
+    /// so a point-in-time vector search never ranks later memories.
+    async fn list_accessible_memory_ids_as_of(
+        &self,
+        agent_id: &str,
+        as_of: &str,
+        limit: usize,
+    ) -> Result<Vec<Uuid>>;
 
     // Events
     async fn insert_event(&self, event: &AgentEvent) -> Result<()>;
@@ -91,6 +210,23 @@ pub trait StorageBackend: Send + Sync {
         limit: usize,
     ) -> Result<Vec<AgentEvent>>;
 
+    // Event retention
+    /// Events stamped before `cutoff` (RFC 3339), grouped by agent and in
+    /// logical-clock order within an agent.
+    async fn list_events_before(&self, cutoff: &str, limit: usize) -> Result<Vec<AgentEvent>>;
+    /// Record `segment` and delete `event_ids` from `agent_events` in one
+    /// transaction. This is the only path that removes event rows; call it
+    /// only once the events are safely in cold storage.
+    async fn archive_event_segment(
+        &self,
+        segment: &EventArchiveSegment,
+        event_ids: &[Uuid],
+    ) -> Result<()>;
+    async fn get_event_archive(&self, id: Uuid) -> Result<Option<EventArchiveSegment>>;
+    /// Oldest first. `Some(agent_id)` lists only that agent's segments.
+    async fn list_event_archives(&self, agent_id: Option<&str>)
+    -> Result<Vec<EventArchiveSegment>>;
+
     // Ordered listing for chain verification
     async fn list_memories_by_agent_ordered(
         &self,
@@ -98,6 +234,26 @@ pub trait StorageBackend: Send + Sync {
         thread_id: Option<&str>,
         limit: usize,
     ) -> Result<Vec<MemoryRecord>>;
+    /// One page of a chain in verification order, `created_at` then `id`,
+    /// starting after the `(created_at, id)` cursor when given. Like
+    /// [`Self::list_memories_by_agent_ordered`], `thread_id = None` covers
+    /// all of the agent's live memories.
+    async fn list_memories_chain_page(
+        &self,
+        agent_id: &str,
+        thread_id: Option<&str>,
+        after: Option<(&str, Uuid)>,
+        limit: usize,
+    ) -> Result<Vec<MemoryRecord>>;
+    async fn get_chain_checkpoint(
+        &self,
+        agent_id: &str,
+        thread_id: Option<&str>,
+    ) -> Result<Option<ChainCheckpoint>>;
+    /// Insert or replace the checkpoint of its (agent, thread) chain.
+    async fn put_chain_checkpoint(&self, checkpoint: &ChainCheckpoint) -> Result<()>;
+    /// Drop every chain checkpoint of `agent_id`.
+    async fn delete_chain_checkpoints(&self, agent_id: &str) -> Result<()>;
 
     // Sync support
     async fn list_memories_since(
@@ -124,12 +280,141 @@ pub trait StorageBackend: Send + Sync {
     // Agent Profiles
     async fn insert_or_update_agent_profile(&self, profile: &AgentProfile) -> Result<()>;
     async fn get_agent_profile(&self, agent_id: &str) -> Result<Option<AgentProfile>>;
+    /// Replace an agent's retrieval defaults, creating the profile row if
+    /// needed. `insert_or_update_agent_profile` only writes them when it
+    /// creates the row, so the write-path stats updater cannot clobber an
+    /// edit.
+    async fn set_agent_retrieval_defaults(
+        &self,
+        agent_id: &str,
+        defaults: &RetrievalDefaults,
+        updated_at: &str,
+    ) -> Result<()>;
 
     // Embedding baselines (v0.3.3, z-score outlier detector)
     async fn insert_or_update_embedding_baseline(&self, baseline: &EmbeddingBaseline)
     -> Result<()>;
     async fn get_embedding_baseline(&self, agent_id: &str) -> Result<Option<EmbeddingBaseline>>;
 
+    // Conflict review queue
+    async fn insert_conflict(&self, conflict: &ConflictRecord) -> Result<()>;
+    async fn get_conflict(&self, id: Uuid) -> Result<Option<ConflictRecord>>;
+    /// Newest first. `None` filters match every agent / status.
+    async fn list_conflicts(
+        &self,
+        agent_id: Option<&str>,
+        status: Option<ConflictStatus>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<ConflictRecord>>;
+    async fn update_conflict(&self, conflict: &ConflictRecord) -> Result<()>;
+
+    // Memory access requests
+    async fn insert_access_request(&self, request: &AccessRequest) -> Result<()>;
+    async fn get_access_request(&self, id: Uuid) -> Result<Option<AccessRequest>>;
+    /// Newest first. `None` filters match every owner / requester / status.
+    async fn list_access_requests(
+        &self,
+        owner_id: Option<&str>,
+        requester_id: Option<&str>,
+        status: Option<AccessRequestStatus>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<AccessRequest>>;
+    /// Store the decision fields of `request` if the stored request is still
+    /// in `expected` status. Returns `false`, writing nothing, otherwise.
+    async fn update_access_request(
+        &self,
+        request: &AccessRequest,
+        expected: AccessRequestStatus,
+    ) -> Result<bool>;
+
+    // Channels (shared memory spaces)
+    async fn insert_channel(&self, channel: &Channel) -> Result<()>;
+    async fn get_channel(&self, name: &str) -> Result<Option<Channel>>;
+    /// By name. `Some(agent_id)` lists only the channels the agent belongs to.
+    async fn list_channels(&self, member: Option<&str>) -> Result<Vec<Channel>>;
+    /// Store the channel's `max_memories`.
+    async fn update_channel(&self, channel: &Channel) -> Result<()>;
+    /// Returns `false` when the agent was already a member.
+    async fn add_channel_member(&self, member: &ChannelMember) -> Result<bool>;
+    /// Returns `false` when the agent was not a member.
+    async fn remove_channel_member(&self, channel: &str, agent_id: &str) -> Result<bool>;
+    /// In join order.
+    async fn list_channel_members(&self, channel: &str) -> Result<Vec<ChannelMember>>;
+    async fn is_channel_member(&self, channel: &str, agent_id: &str) -> Result<bool>;
+    /// Live (not deleted) memories remembered into the channel.
+    async fn count_channel_memories(&self, channel: &str) -> Result<u64>;
+
+    // Content storage
+    /// Per agent, by agent id. `Some(agent_id)` reports only that agent.
+    async fn content_storage_stats(
+        &self,
+        agent_id: Option<&str>,
+    ) -> Result<Vec<ContentStorageStats>>;
+    /// One entry per day anything was stored, oldest first.
+    async fn daily_growth(&self) -> Result<Vec<DailyGrowth>>;
+    /// Size of the database, in bytes; 0 for an in-memory database.
+    async fn database_size(&self) -> Result<u64>;
+
+    // Maintenance
+    /// Reclaim the space hard deletes and updates leave behind, and refresh
+    /// planner statistics. Blocks or slows writes while it runs.
+    async fn compact(&self) -> Result<CompactionReport>;
+
+    // Recall profiles (saved searches)
+    async fn upsert_recall_profile(&self, profile: &RecallProfile) -> Result<()>;
+    async fn get_recall_profile(&self, agent_id: &str, name: &str)
+    -> Result<Option<RecallProfile>>;
+    async fn list_recall_profiles(&self, agent_id: &str) -> Result<Vec<RecallProfile>>;
+    /// Returns `false` when no such profile existed.
+    async fn delete_recall_profile(&self, agent_id: &str, name: &str) -> Result<bool>;
+    /// Bump `use_count` and stamp `last_used_at` for one expansion.
+    async fn record_recall_profile_use(
+        &self,
+        agent_id: &str,
+        name: &str,
+        used_at: &str,
+    ) -> Result<()>;
+
+    // Memory access log
+    async fn insert_memory_accesses(&self, accesses: &[MemoryAccess]) -> Result<()>;
+    /// Newest first.
+    async fn list_memory_accesses(
+        &self,
+        memory_id: Uuid,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<MemoryAccess>>;
+    async fn count_memory_accesses(&self, memory_id: Uuid) -> Result<usize>;
+    /// Delete entries older than `older_than` (RFC 3339) and, per memory,
+    /// all but the newest `keep_per_memory`. Returns the number deleted.
+    async fn prune_memory_accesses(
+        &self,
+        older_than: Option<&str>,
+        keep_per_memory: Option<usize>,
+    ) -> Result<usize>;
+
+    /// Write every row of `batch` in one transaction, so a crash never
+    /// leaves a memory without its audit event (or an event without its
+    /// memory).
+    async fn commit_write(&self, batch: &WriteBatch) -> Result<()>;
+
+    // Transactional outbox
+    /// Lease up to `limit` undelivered messages whose previous lease has
+    /// run out, oldest first, until `lease_until` (RFC 3339), and count the
+    /// attempt. A leased message is not handed to any other caller until
+    /// the lease expires.
+    async fn claim_outbox(&self, limit: usize, lease_until: &str) -> Result<Vec<OutboxMessage>>;
+    /// Mark a claimed message delivered. It is never claimed again.
+    async fn complete_outbox(&self, id: Uuid) -> Result<()>;
+    /// Record a failed delivery; the message becomes claimable again at
+    /// `retry_at` (RFC 3339).
+    async fn fail_outbox(&self, id: Uuid, error: &str, retry_at: &str) -> Result<()>;
+    /// Delete messages delivered before `cutoff` (RFC 3339). Returns the
+    /// number deleted.
+    async fn prune_outbox(&self, cutoff: &str) -> Result<usize>;
+
     // Checkpoints
     async fn insert_checkpoint(&self, cp: &Checkpoint) -> Result<()>;
     async fn get_checkpoint(&self, id: Uuid) -> Result<Option<Checkpoint>>;
@@ -144,6 +429,10 @@ pub trait StorageBackend: Send + Sync {
         thread_id: &str,
         branch: &str,
     ) -> Result<Option<Checkpoint>>;
+    /// The agent that owns `thread_id`: the author of its oldest memory or
+    /// non-checkpoint event, falling back to the author of its oldest
+    /// checkpoint. `None` when nothing has been written to the thread.
+    async fn thread_owner(&self, thread_id: &str) -> Result<Option<String>>;
 
     /// Short, stable label for the backend implementation (e.g. `"duckdb"`,
     /// `"postgres"`). Used in diagnostics such as
@@ -153,11 +442,20 @@ pub trait StorageBackend: Send + Sync {
         "unknown"
     }
 
+    /// Cheap round-trip used by readiness probes. Defaults to a single
+    /// watermark read; real backends override it with `SELECT 1`.
+    async fn ping(&self) -> Result<()> {
+        self.get_sync_watermark("__health__").await.map(|_| ())
+    }
+
     /// Whether this backend guarantees the `agent_events` log is **append-only**
     /// — no code path (and, where enforceable, no schema path) can delete or
     /// rewrite an event row. Both shipped backends guarantee this: DuckDB has no
-    /// `DELETE`/`UPDATE` on `agent_events`, and PostgreSQL additionally enforces
-    /// it with a `prevent_event_modification` trigger. A retention-conformance
+    /// `UPDATE` on `agent_events`, and PostgreSQL additionally enforces it with
+    /// a `prevent_event_modification` trigger. The one exception is
+    /// [`StorageBackend::archive_event_segment`], which moves events older
+    /// than the configured hot window to cold storage, keeping their Merkle
+    /// root in the hot database. A retention-conformance
     /// profile relies on this to promise a retention floor; a backend that
     /// cannot honour it should override this to `false` so the profile fails
     /// loud (see `mnemo-compliance`'s `RetentionProfile`). Defaults to `true`.
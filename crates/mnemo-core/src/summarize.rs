@@ -0,0 +1,106 @@
+//! Text summarization for maintenance passes.
+//!
+//! The compression pass ([`MnemoEngine::run_compression`](crate::query::MnemoEngine::run_compression))
+//! folds old episodic memories into a few summaries. What a summary says is
+//! up to the attached [`Summarizer`]: an LLM-backed implementation prompts
+//! a model with the memory contents, and the engine core never embeds a
+//! model client. Attach one via
+//! [`MnemoEngine::with_summarizer`](crate::query::MnemoEngine::with_summarizer).
+//!
+//! [`ExtractiveSummarizer`] is the model-free fallback used when none is
+//! attached: it keeps the first sentence of each memory, drops repeats and
+//! truncates to a character budget.
+
+use std::collections::HashSet;
+
+use crate::error::Result;
+
+/// Pluggable summarization used by the compression pass.
+#[async_trait::async_trait]
+pub trait Summarizer: Send + Sync {
+    /// Summarize `texts`, given oldest first, into one passage.
+    async fn summarize(&self, texts: &[String]) -> Result<String>;
+    /// Stable identifier recorded in the summary's metadata.
+    fn name(&self) -> &str;
+}
+
+/// Default character budget of an [`ExtractiveSummarizer`] summary.
+pub const DEFAULT_EXTRACTIVE_MAX_CHARS: usize = 2000;
+
+/// Model-free summarizer: the distinct first sentences of the inputs,
+/// joined in order and cut at `max_chars`.
+#[derive(Debug, Clone)]
+pub struct ExtractiveSummarizer {
+    pub max_chars: usize,
+}
+
+impl Default for ExtractiveSummarizer {
+    fn default() -> Self {
+        Self {
+            max_chars: DEFAULT_EXTRACTIVE_MAX_CHARS,
+        }
+    }
+}
+
+impl ExtractiveSummarizer {
+    fn first_sentence(text: &str) -> &str {
+        let text = text.trim();
+        match text.find(['.', '!', '?', '\n']) {
+            Some(end) => text[..=end].trim_end_matches('\n').trim(),
+            None => text,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Summarizer for ExtractiveSummarizer {
+    async fn summarize(&self, texts: &[String]) -> Result<String> {
+        let mut seen = HashSet::new();
+        let mut summary = String::new();
+        for text in texts {
+            let sentence = Self::first_sentence(text);
+            if sentence.is_empty() || !seen.insert(sentence.to_lowercase()) {
+                continue;
+            }
+            if !summary.is_empty() {
+                summary.push(' ');
+            }
+            summary.push_str(sentence);
+            if summary.chars().count() >= self.max_chars {
+                break;
+            }
+        }
+        if summary.chars().count() > self.max_chars {
+            summary = summary.chars().take(self.max_chars).collect();
+        }
+        Ok(summary)
+    }
+
+    fn name(&self) -> &str {
+        "extractive"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_extractive_keeps_distinct_first_sentences() {
+        let summarizer = ExtractiveSummarizer::default();
+        let texts = [
+            "Deploys happen on tuesday. Ask ops first.",
+            "deploys happen on tuesday.",
+            "The staging db resets weekly",
+        ]
+        .map(String::from);
+        let summary = summarizer.summarize(&texts).await.unwrap();
+        assert_eq!(
+            summary,
+            "Deploys happen on tuesday. The staging db resets weekly"
+        );
+
+        let short = ExtractiveSummarizer { max_chars: 7 };
+        assert_eq!(short.summarize(&texts).await.unwrap(), "Deploys");
+    }
+}
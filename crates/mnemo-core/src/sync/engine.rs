@@ -1,10 +1,12 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::region::ReplicationOrigin;
 use crate::error::Result;
+use crate::model::memory::MemoryRecord;
 use crate::storage::StorageBackend;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,66 +21,194 @@ pub struct SyncConflict {
     pub memory_id: Uuid,
     pub local_updated_at: String,
     pub remote_updated_at: String,
+    /// Region the local version was written in, when regions are set.
+    #[serde(default)]
+    pub local_region: Option<String>,
+    /// Region the remote version was written in, when regions are set.
+    #[serde(default)]
+    pub remote_region: Option<String>,
+}
+
+/// Replication counters since the engine was created.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncMetrics {
+    pub pushed: u64,
+    pub pulled: u64,
+    /// Records not copied back to the region they came from.
+    pub echoes_skipped: u64,
+    pub conflicts: u64,
+    /// How long the oldest change copied by the last push had waited since
+    /// it was written; 0 when that push found nothing to copy.
+    pub push_lag_ms: Option<u64>,
+    /// Same for the last pull.
+    pub pull_lag_ms: Option<u64>,
+    pub last_push_at: Option<String>,
+    pub last_pull_at: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Regions {
+    local: String,
+    remote: String,
+}
+
+#[derive(Clone, Copy)]
+enum Direction {
+    Push,
+    Pull,
 }
 
 pub struct SyncEngine {
     local: Arc<dyn StorageBackend>,
     remote: Arc<dyn StorageBackend>,
+    regions: Option<Regions>,
+    metrics: Mutex<SyncMetrics>,
 }
 
 impl SyncEngine {
     pub fn new(local: Arc<dyn StorageBackend>, remote: Arc<dyn StorageBackend>) -> Self {
-        Self { local, remote }
+        Self {
+            local,
+            remote,
+            regions: None,
+            metrics: Mutex::new(SyncMetrics::default()),
+        }
     }
 
-    /// Push local changes to remote. Returns number of memories pushed.
-    /// Uses watermark persistence to resume from last sync point.
-    pub async fn push(&self, since: &str) -> Result<usize> {
-        let watermark_key = "push_watermark";
+    /// Replicate between named regions. Copied records carry a
+    /// [`ReplicationOrigin`], records are not copied back to the region
+    /// they came from, and watermarks are kept per remote region so one
+    /// local store can sync with several.
+    pub fn with_regions(mut self, local: impl Into<String>, remote: impl Into<String>) -> Self {
+        self.regions = Some(Regions {
+            local: local.into(),
+            remote: remote.into(),
+        });
+        self
+    }
+
+    pub fn metrics(&self) -> SyncMetrics {
+        self.metrics
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn watermark_key(&self, direction: Direction) -> String {
+        let key = match direction {
+            Direction::Push => "push_watermark",
+            Direction::Pull => "pull_watermark",
+        };
+        match self.regions {
+            Some(ref regions) => format!("{key}:{}", regions.remote),
+            None => key.to_string(),
+        }
+    }
+
+    /// Whether `record` came from the side it would be copied to.
+    fn is_echo(&self, record: &MemoryRecord, direction: Direction) -> bool {
+        let Some(ref regions) = self.regions else {
+            return false;
+        };
+        let target = match direction {
+            Direction::Push => &regions.remote,
+            Direction::Pull => &regions.local,
+        };
+        ReplicationOrigin::of(record).is_some_and(|origin| origin.region == *target)
+    }
+
+    /// The copy of `record` to write on the other side.
+    fn outgoing(&self, record: &MemoryRecord, direction: Direction) -> MemoryRecord {
+        match self.regions {
+            Some(ref regions) => {
+                let source = match direction {
+                    Direction::Push => &regions.local,
+                    Direction::Pull => &regions.remote,
+                };
+                ReplicationOrigin::stamp(record, source)
+            }
+            None => record.clone(),
+        }
+    }
+
+    /// Copy the records `from` has updated since its watermark to `to`.
+    async fn copy(
+        &self,
+        from: &dyn StorageBackend,
+        to: &dyn StorageBackend,
+        since: &str,
+        direction: Direction,
+    ) -> Result<usize> {
+        let watermark_key = self.watermark_key(direction);
         let effective_since = self
             .local
-            .get_sync_watermark(watermark_key)
+            .get_sync_watermark(&watermark_key)
             .await?
             .unwrap_or_else(|| since.to_string());
-        let local_memories = self
-            .local
+        let records = from
             .list_memories_since(&effective_since, crate::query::MAX_BATCH_QUERY_LIMIT)
             .await?;
-        let mut pushed = 0;
-        for record in &local_memories {
-            self.remote.upsert_memory(record).await?;
-            pushed += 1;
+        let now = Utc::now();
+        let mut copied = 0;
+        let mut echoes = 0;
+        let mut lag_ms = 0;
+        for record in &records {
+            if self.is_echo(record, direction) {
+                echoes += 1;
+                continue;
+            }
+            to.upsert_memory(&self.outgoing(record, direction)).await?;
+            lag_ms = lag_ms.max(lag_since(&record.updated_at, now));
+            copied += 1;
         }
-        if pushed > 0 {
-            let now = Utc::now().to_rfc3339();
-            self.local.set_sync_watermark(watermark_key, &now).await?;
+        // Resume after the last record read rather than at the current
+        // time, so writes landing during the pass are picked up next time.
+        if let Some(last) = records.last() {
+            self.local
+                .set_sync_watermark(&watermark_key, &last.updated_at)
+                .await?;
+        }
+
+        let mut metrics = self.metrics.lock().unwrap_or_else(|e| e.into_inner());
+        metrics.echoes_skipped += echoes;
+        let at = Some(now.to_rfc3339());
+        match direction {
+            Direction::Push => {
+                metrics.pushed += copied as u64;
+                metrics.push_lag_ms = Some(lag_ms);
+                metrics.last_push_at = at;
+            }
+            Direction::Pull => {
+                metrics.pulled += copied as u64;
+                metrics.pull_lag_ms = Some(lag_ms);
+                metrics.last_pull_at = at;
+            }
         }
-        Ok(pushed)
+        Ok(copied)
+    }
+
+    /// Push local changes to remote. Returns number of memories pushed.
+    /// Uses watermark persistence to resume from last sync point.
+    pub async fn push(&self, since: &str) -> Result<usize> {
+        self.copy(
+            self.local.as_ref(),
+            self.remote.as_ref(),
+            since,
+            Direction::Push,
+        )
+        .await
     }
 
     /// Pull remote changes to local. Returns number of memories pulled.
     /// Uses watermark persistence to resume from last sync point.
     pub async fn pull(&self, since: &str) -> Result<usize> {
-        let watermark_key = "pull_watermark";
-        let effective_since = self
-            .local
-            .get_sync_watermark(watermark_key)
-            .await?
-            .unwrap_or_else(|| since.to_string());
-        let remote_memories = self
-            .remote
-            .list_memories_since(&effective_since, crate::query::MAX_BATCH_QUERY_LIMIT)
-            .await?;
-        let mut pulled = 0;
-        for record in &remote_memories {
-            self.local.upsert_memory(record).await?;
-            pulled += 1;
-        }
-        if pulled > 0 {
-            let now = Utc::now().to_rfc3339();
-            self.local.set_sync_watermark(watermark_key, &now).await?;
-        }
-        Ok(pulled)
+        self.copy(
+            self.remote.as_ref(),
+            self.local.as_ref(),
+            since,
+            Direction::Pull,
+        )
+        .await
     }
 
     /// Full bidirectional sync. Pushes local changes, then pulls remote changes.
@@ -93,28 +223,42 @@ impl SyncEngine {
             .list_memories_since(since, crate::query::MAX_BATCH_QUERY_LIMIT)
             .await?;
 
-        // Build a map of remote memory IDs → updated_at for conflict detection
-        let remote_map: std::collections::HashMap<Uuid, String> = remote_memories
-            .iter()
-            .map(|m| (m.id, m.updated_at.clone()))
-            .collect();
+        // Build a map of remote memory IDs → record for conflict detection
+        let remote_map: std::collections::HashMap<Uuid, &MemoryRecord> =
+            remote_memories.iter().map(|m| (m.id, m)).collect();
 
         let mut conflicts = Vec::new();
         let mut pushed = 0;
+        let mut echoes = 0;
 
         // Push local → remote, detecting conflicts
         for record in &local_memories {
-            if let Some(remote_updated) = remote_map.get(&record.id) {
+            if self.is_echo(record, Direction::Push) {
+                echoes += 1;
+                continue;
+            }
+            if let Some(remote) = remote_map.get(&record.id) {
                 // Both sides modified — conflict (last-writer-wins: push local anyway)
-                if *remote_updated != record.updated_at {
+                if remote.updated_at != record.updated_at {
+                    let (local_region, remote_region) = match self.regions {
+                        Some(ref regions) => (
+                            Some(ReplicationOrigin::region_of(record, &regions.local)),
+                            Some(ReplicationOrigin::region_of(remote, &regions.remote)),
+                        ),
+                        None => (None, None),
+                    };
                     conflicts.push(SyncConflict {
                         memory_id: record.id,
                         local_updated_at: record.updated_at.clone(),
-                        remote_updated_at: remote_updated.clone(),
+                        remote_updated_at: remote.updated_at.clone(),
+                        local_region,
+                        remote_region,
                     });
                 }
             }
-            self.remote.upsert_memory(record).await?;
+            self.remote
+                .upsert_memory(&self.outgoing(record, Direction::Push))
+                .await?;
             pushed += 1;
         }
 
@@ -123,12 +267,26 @@ impl SyncEngine {
             local_memories.iter().map(|m| m.id).collect();
         let mut pulled = 0;
         for record in &remote_memories {
-            if !local_ids.contains(&record.id) {
-                self.local.upsert_memory(record).await?;
-                pulled += 1;
+            if local_ids.contains(&record.id) {
+                continue;
             }
+            if self.is_echo(record, Direction::Pull) {
+                echoes += 1;
+                continue;
+            }
+            self.local
+                .upsert_memory(&self.outgoing(record, Direction::Pull))
+                .await?;
+            pulled += 1;
         }
 
+        let mut metrics = self.metrics.lock().unwrap_or_else(|e| e.into_inner());
+        metrics.pushed += pushed as u64;
+        metrics.pulled += pulled as u64;
+        metrics.echoes_skipped += echoes;
+        metrics.conflicts += conflicts.len() as u64;
+        drop(metrics);
+
         Ok(SyncResult {
             pushed,
             pulled,
@@ -137,6 +295,14 @@ impl SyncEngine {
     }
 }
 
+/// Milliseconds from `updated_at` to `now`; 0 if it does not parse or is
+/// in the future.
+fn lag_since(updated_at: &str, now: DateTime<Utc>) -> u64 {
+    DateTime::parse_from_rfc3339(updated_at)
+        .map(|t| (now - t.with_timezone(&Utc)).num_milliseconds().max(0) as u64)
+        .unwrap_or(0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,6 +316,8 @@ mod tests {
                 memory_id: Uuid::now_v7(),
                 local_updated_at: "2025-01-01T00:00:00Z".to_string(),
                 remote_updated_at: "2025-01-01T01:00:00Z".to_string(),
+                local_region: Some("eu".to_string()),
+                remote_region: Some("us".to_string()),
             }],
         };
         let json = serde_json::to_string(&result).unwrap();
@@ -1,3 +1,5 @@
 pub mod engine;
+pub mod region;
 
-pub use engine::{SyncConflict, SyncEngine, SyncResult};
+pub use engine::{SyncConflict, SyncEngine, SyncMetrics, SyncResult};
+pub use region::ReplicationOrigin;
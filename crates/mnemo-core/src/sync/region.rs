@@ -0,0 +1,59 @@
+//! Origin-region metadata for multi-region replication.
+//!
+//! When [`SyncEngine`](super::SyncEngine) runs between named regions, every
+//! record it copies carries a [`ReplicationOrigin`] under
+//! `metadata["replication"]`: the region the record's current version was
+//! written in, and that version's `updated_at`. A record whose origin is
+//! the region it would be copied to, at the same `updated_at`, came from
+//! there and is not copied back. A later local write changes `updated_at`,
+//! so the edited record replicates again.
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::memory::MemoryRecord;
+
+/// Metadata key holding a record's [`ReplicationOrigin`].
+pub const ORIGIN_METADATA_KEY: &str = "replication";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplicationOrigin {
+    /// Region the version was written in.
+    pub region: String,
+    /// `updated_at` of that version.
+    pub updated_at: String,
+}
+
+impl ReplicationOrigin {
+    /// The origin recorded on `record`, if it describes its current version.
+    pub fn of(record: &MemoryRecord) -> Option<Self> {
+        let origin: Self =
+            serde_json::from_value(record.metadata.get(ORIGIN_METADATA_KEY)?.clone()).ok()?;
+        (origin.updated_at == record.updated_at).then_some(origin)
+    }
+
+    /// Region the current version of `record` was written in: its recorded
+    /// origin, or `here` when the record was written locally since.
+    pub fn region_of(record: &MemoryRecord, here: &str) -> String {
+        Self::of(record).map_or_else(|| here.to_string(), |o| o.region)
+    }
+
+    /// A copy of `record` with its origin recorded, `here` if it has none.
+    /// Records whose metadata is not a JSON object are copied unchanged.
+    pub(crate) fn stamp(record: &MemoryRecord, here: &str) -> MemoryRecord {
+        let origin = Self {
+            region: Self::region_of(record, here),
+            updated_at: record.updated_at.clone(),
+        };
+        let mut copy = record.clone();
+        if copy.metadata.is_null() {
+            copy.metadata = serde_json::json!({});
+        }
+        if let Some(map) = copy.metadata.as_object_mut() {
+            map.insert(
+                ORIGIN_METADATA_KEY.to_string(),
+                serde_json::to_value(origin).unwrap_or_default(),
+            );
+        }
+        copy
+    }
+}
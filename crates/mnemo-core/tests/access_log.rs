@@ -0,0 +1,131 @@
+//! Integration tests for the per-memory access log.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::access_log::AccessOperation;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::access_log::{AccessLogPolicy, AccessLogRequest};
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine(policy: Option<AccessLogPolicy>) -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    let engine = MnemoEngine::new(storage, index, embedding, "owner".to_string(), None);
+    match policy {
+        Some(policy) => engine.with_access_log(policy),
+        None => engine,
+    }
+}
+
+async fn remember(engine: &MnemoEngine, content: &str) -> uuid::Uuid {
+    engine
+        .remember(RememberRequest::new(content.to_string()))
+        .await
+        .unwrap()
+        .id
+}
+
+#[tokio::test]
+async fn recall_hits_are_logged_with_principal_and_query() {
+    let engine = create_engine(Some(AccessLogPolicy::default()));
+    let id = remember(&engine, "patient allergy: penicillin").await;
+
+    engine
+        .recall(RecallRequest::new("penicillin allergy".to_string()))
+        .await
+        .unwrap();
+    engine
+        .record_memory_access("owner", AccessOperation::Get, &[id])
+        .await;
+
+    let log = engine
+        .list_memory_accesses(AccessLogRequest::new(id))
+        .await
+        .unwrap();
+    assert!(log.enabled);
+    assert_eq!(log.total, 2);
+    assert_eq!(log.accesses[0].operation, AccessOperation::Get);
+    assert_eq!(log.accesses[0].query, None);
+    let recall = &log.accesses[1];
+    assert_eq!(recall.principal, "owner");
+    assert_eq!(recall.operation, AccessOperation::Recall);
+    assert_eq!(recall.query.as_deref(), Some("penicillin allergy"));
+}
+
+#[tokio::test]
+async fn disabled_log_records_nothing_and_queries_can_be_omitted() {
+    let engine = create_engine(None);
+    let id = remember(&engine, "salary band for L5").await;
+    engine
+        .recall(RecallRequest::new("salary".to_string()))
+        .await
+        .unwrap();
+    let log = engine
+        .list_memory_accesses(AccessLogRequest::new(id))
+        .await
+        .unwrap();
+    assert!(!log.enabled);
+    assert_eq!(log.total, 0);
+
+    let engine = create_engine(Some(AccessLogPolicy {
+        record_query: false,
+        ..Default::default()
+    }));
+    let id = remember(&engine, "salary band for L5").await;
+    engine
+        .recall(RecallRequest::new("salary".to_string()))
+        .await
+        .unwrap();
+    let log = engine
+        .list_memory_accesses(AccessLogRequest::new(id))
+        .await
+        .unwrap();
+    assert_eq!(log.total, 1);
+    assert_eq!(log.accesses[0].query, None);
+}
+
+#[tokio::test]
+async fn cleanup_enforces_per_memory_cap() {
+    let engine = create_engine(Some(AccessLogPolicy {
+        retention_days: None,
+        max_entries_per_memory: Some(2),
+        record_query: true,
+    }));
+    let id = remember(&engine, "incident postmortem notes").await;
+    for _ in 0..5 {
+        engine
+            .record_memory_access("owner", AccessOperation::Get, &[id])
+            .await;
+    }
+
+    let result = engine.run_cleanup().await.unwrap();
+    assert_eq!(result.access_log_pruned, 3);
+    let log = engine
+        .list_memory_accesses(AccessLogRequest::new(id))
+        .await
+        .unwrap();
+    assert_eq!(log.total, 2);
+}
+
+#[tokio::test]
+async fn listing_requires_admin_on_the_memory() {
+    let engine = create_engine(Some(AccessLogPolicy::default()));
+    let id = remember(&engine, "owner-only note").await;
+
+    let mut request = AccessLogRequest::new(id);
+    request.agent_id = Some("intruder".to_string());
+    let err = engine.list_memory_accesses(request).await.unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)));
+
+    let err = engine
+        .list_memory_accesses(AccessLogRequest::new(uuid::Uuid::now_v7()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::NotFound(_)));
+}
@@ -0,0 +1,185 @@
+//! Integration tests for agent-to-agent memory access requests.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::access_request::AccessRequestStatus;
+use mnemo_core::model::acl::Permission;
+use mnemo_core::model::event::EventType;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::access_request::{
+    ACCESS_REQUEST_TOPIC, AccessDecision, AccessRequestListRequest, DecideAccessRequest,
+    FileAccessRequest,
+};
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "owner".to_string(), None).with_outbox()
+}
+
+async fn remember(engine: &MnemoEngine, agent: &str, content: &str, tags: &[&str]) -> Uuid {
+    let mut request = RememberRequest::new(content.to_string());
+    request.agent_id = Some(agent.to_string());
+    request.tags = Some(tags.iter().map(|t| t.to_string()).collect());
+    engine.remember(request).await.unwrap().id
+}
+
+async fn can_read(engine: &MnemoEngine, id: Uuid, agent: &str) -> bool {
+    engine
+        .storage
+        .check_permission(id, agent, Permission::Read)
+        .await
+        .unwrap()
+}
+
+async fn events(engine: &MnemoEngine, agent: &str, event_type: EventType) -> usize {
+    engine
+        .storage
+        .list_events(agent, 100, 0)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|e| e.event_type == event_type)
+        .count()
+}
+
+fn ask(owner: &str, tags: &[&str]) -> FileAccessRequest {
+    FileAccessRequest {
+        requester_id: Some("analyst".to_string()),
+        tags: Some(tags.iter().map(|t| t.to_string()).collect()),
+        ..FileAccessRequest::new(owner.to_string(), "writing the q3 report".to_string())
+    }
+}
+
+fn decide(request_id: Uuid, decision: AccessDecision) -> DecideAccessRequest {
+    DecideAccessRequest {
+        agent_id: Some("owner".to_string()),
+        ..DecideAccessRequest::new(request_id, decision)
+    }
+}
+
+#[tokio::test]
+async fn approval_grants_the_requested_memories() {
+    let engine = create_engine();
+    let revenue = remember(&engine, "owner", "q3 revenue was 4.2m", &["q3"]).await;
+    let other = remember(&engine, "owner", "the office moves in june", &[]).await;
+
+    let filed = engine.request_access(ask("owner", &["q3"])).await.unwrap();
+    assert_eq!(filed.status, AccessRequestStatus::Pending);
+    assert_eq!(
+        events(&engine, "owner", EventType::AccessRequested).await,
+        1
+    );
+    let outbox = engine
+        .storage
+        .claim_outbox(10, "2099-01-01T00:00:00Z")
+        .await
+        .unwrap();
+    assert!(outbox.iter().any(|m| m.topic == ACCESS_REQUEST_TOPIC));
+
+    let incoming = engine
+        .list_access_requests(AccessRequestListRequest {
+            owner_id: Some("owner".to_string()),
+            status: Some(AccessRequestStatus::Pending),
+            ..AccessRequestListRequest::new()
+        })
+        .await
+        .unwrap();
+    assert_eq!(incoming.len(), 1);
+    assert_eq!(incoming[0].justification, "writing the q3 report");
+
+    let decided = engine
+        .decide_access_request(decide(filed.id, AccessDecision::Approve))
+        .await
+        .unwrap();
+    assert_eq!(decided.status, AccessRequestStatus::Approved);
+    assert_eq!(decided.acl_ids.len(), 1);
+    assert!(can_read(&engine, revenue, "analyst").await);
+    assert!(!can_read(&engine, other, "analyst").await);
+    assert_eq!(
+        events(&engine, "analyst", EventType::AccessRequestDecided).await,
+        1
+    );
+
+    // A request is decided once.
+    let err = engine
+        .decide_access_request(decide(filed.id, AccessDecision::Deny))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Validation(_)));
+}
+
+#[tokio::test]
+async fn only_the_owner_decides_and_denial_grants_nothing() {
+    let engine = create_engine();
+    let id = remember(&engine, "owner", "the launch date is friday", &[]).await;
+    let mut request = ask("owner", &[]);
+    request.tags = None;
+    request.memory_ids = Some(vec![id]);
+    let filed = engine.request_access(request).await.unwrap();
+
+    let mut hijack = decide(filed.id, AccessDecision::Approve);
+    hijack.agent_id = Some("analyst".to_string());
+    assert!(matches!(
+        engine.decide_access_request(hijack).await,
+        Err(Error::PermissionDenied(_))
+    ));
+
+    let mut deny = decide(filed.id, AccessDecision::Deny);
+    deny.note = Some("ask after the launch".to_string());
+    let decided = engine.decide_access_request(deny).await.unwrap();
+    assert_eq!(decided.status, AccessRequestStatus::Denied);
+    assert!(decided.acl_ids.is_empty());
+    assert!(!can_read(&engine, id, "analyst").await);
+}
+
+#[tokio::test]
+async fn failed_approvals_leave_the_request_pending() {
+    let engine = create_engine();
+    let theirs = remember(&engine, "peer", "the peer's private notes", &[]).await;
+    let mut request = ask("owner", &[]);
+    request.tags = None;
+    request.memory_ids = Some(vec![theirs]);
+    let filed = engine.request_access(request).await.unwrap();
+
+    // The owner cannot share a memory it does not own.
+    assert!(matches!(
+        engine
+            .decide_access_request(decide(filed.id, AccessDecision::Approve))
+            .await,
+        Err(Error::PermissionDenied(_))
+    ));
+    let stored = engine
+        .storage
+        .get_access_request(filed.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.status, AccessRequestStatus::Pending);
+    assert!(!can_read(&engine, theirs, "analyst").await);
+}
+
+#[tokio::test]
+async fn malformed_requests_are_rejected() {
+    let engine = create_engine();
+    let err = engine
+        .request_access(ask("analyst", &["q3"]))
+        .await
+        .unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "owner_id");
+
+    let mut request = ask("owner", &["q3"]);
+    request.justification = "  ".to_string();
+    let err = engine.request_access(request).await.unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "justification");
+
+    let err = engine.request_access(ask("owner", &[])).await.unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "memory_ids");
+}
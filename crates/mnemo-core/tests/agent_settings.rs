@@ -0,0 +1,130 @@
+//! Integration tests for per-agent default retrieval settings stored on the
+//! agent profile and applied by recall when request fields are unset.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::NoopEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::agent_profile::RetrievalDefaults;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::agent_settings::apply;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::search::tantivy_index::TantivyFullTextIndex;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+const AGENT: &str = "settings-agent";
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(128).unwrap());
+    let embedding = Arc::new(NoopEmbedding::new(128));
+    let full_text = Arc::new(TantivyFullTextIndex::open_in_memory().unwrap());
+    MnemoEngine::new(storage, index, embedding, AGENT.to_string(), None).with_full_text(full_text)
+}
+
+#[tokio::test]
+async fn defaults_roundtrip_and_survive_profile_stats_updates() {
+    let engine = create_engine();
+    assert!(
+        engine
+            .agent_retrieval_defaults(AGENT)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let defaults = RetrievalDefaults {
+        recency_half_life_hours: Some(48.0),
+        strategy: Some("lexical".to_string()),
+        ..Default::default()
+    };
+    engine
+        .set_agent_retrieval_defaults(AGENT, defaults.clone())
+        .await
+        .unwrap();
+
+    // Writes update the profile's running stats; the defaults must survive.
+    engine
+        .remember(RememberRequest::new("Lunch is at noon".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(
+        engine.agent_retrieval_defaults(AGENT).await.unwrap(),
+        defaults
+    );
+}
+
+#[tokio::test]
+async fn recall_uses_agent_defaults_when_request_fields_unset() {
+    let engine = create_engine();
+    for i in 0..3 {
+        engine
+            .remember(RememberRequest::new(format!("Standup note number {i}")))
+            .await
+            .unwrap();
+    }
+    engine
+        .set_agent_retrieval_defaults(
+            AGENT,
+            RetrievalDefaults {
+                strategy: Some("lexical".to_string()),
+                limit: Some(2),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    // NoopEmbedding cannot serve the built-in `auto` strategy; the lexical
+    // default makes this recall succeed.
+    let response = engine
+        .recall(RecallRequest::new("standup".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(response.memories.len(), 2);
+
+    let mut req = RecallRequest::new("standup".to_string());
+    req.limit = Some(3);
+    assert_eq!(engine.recall(req).await.unwrap().memories.len(), 3);
+}
+
+#[tokio::test]
+async fn invalid_defaults_are_rejected() {
+    let engine = create_engine();
+    for bad in [
+        RetrievalDefaults {
+            limit: Some(0),
+            ..Default::default()
+        },
+        RetrievalDefaults {
+            strategy: Some("telepathy".to_string()),
+            ..Default::default()
+        },
+        RetrievalDefaults {
+            recency_half_life_hours: Some(-1.0),
+            ..Default::default()
+        },
+    ] {
+        let err = engine
+            .set_agent_retrieval_defaults(AGENT, bad)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+    }
+}
+
+#[test]
+fn typed_mode_blocks_default_strategy() {
+    let mut req = RecallRequest::new("q".to_string());
+    req.mode = Some(mnemo_core::retrieval::RetrievalMode::Graph);
+    apply(
+        &RetrievalDefaults {
+            strategy: Some("lexical".to_string()),
+            ..Default::default()
+        },
+        &mut req,
+    );
+    assert!(req.strategy.is_none());
+}
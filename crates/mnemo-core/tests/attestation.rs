@@ -0,0 +1,138 @@
+//! Integration tests for attestation of high-stakes memories.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::event::EventType;
+use mnemo_core::model::memory::ReviewStatus;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::attestation::{AttestRequest, AttestationPolicy, TagReview};
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+const FACT: &str = "the patient is allergic to penicillin";
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    let policy = AttestationPolicy::default().with_tag(
+        "medical",
+        TagReview::default()
+            .with_reviewer("dr-lee")
+            .with_reviewer("dr-okafor")
+            .with_approvals(2),
+    );
+    MnemoEngine::new(storage, index, embedding, "server".to_string(), None).with_attestation(policy)
+}
+
+async fn remember(engine: &MnemoEngine, tag: &str) -> Uuid {
+    let mut request = RememberRequest::new(FACT.to_string());
+    request.agent_id = Some("nurse".to_string());
+    request.tags = Some(vec![tag.to_string()]);
+    engine.remember(request).await.unwrap().id
+}
+
+async fn recalled(engine: &MnemoEngine) -> Vec<Uuid> {
+    let mut request = RecallRequest::new(FACT.to_string());
+    request.agent_id = Some("nurse".to_string());
+    request.strategy = Some("semantic".to_string());
+    engine
+        .recall(request)
+        .await
+        .unwrap()
+        .memories
+        .into_iter()
+        .map(|m| m.id)
+        .collect()
+}
+
+fn attest_as(agent_id: &str, memory_id: Uuid, approve: bool) -> AttestRequest {
+    let mut request = AttestRequest::new(memory_id, approve);
+    request.agent_id = Some(agent_id.to_string());
+    request
+}
+
+#[tokio::test]
+async fn tagged_memories_need_every_approval_before_recall() {
+    let engine = create_engine();
+    let id = remember(&engine, "medical").await;
+    let untagged = remember(&engine, "notes").await;
+
+    let stored = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert_eq!(stored.attestation, Some(ReviewStatus::Pending));
+    assert_eq!(recalled(&engine).await, [untagged]);
+
+    let pending = engine
+        .list_pending_attestations(Some("dr-lee".to_string()), 10)
+        .await
+        .unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].review_tags, ["medical"]);
+
+    let err = engine
+        .attest(attest_as("nurse", id, true))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)), "{err}");
+    let err = engine
+        .attest(attest_as("intern", id, true))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)), "{err}");
+
+    let first = engine.attest(attest_as("dr-lee", id, true)).await.unwrap();
+    assert_eq!(first.status, ReviewStatus::Pending);
+    assert_eq!(recalled(&engine).await, [untagged]);
+
+    let second = engine
+        .attest(attest_as("dr-okafor", id, true))
+        .await
+        .unwrap();
+    assert_eq!(second.status, ReviewStatus::Approved);
+    assert_eq!(second.approved_by, ["dr-lee", "dr-okafor"]);
+    let mut ids = recalled(&engine).await;
+    ids.sort();
+    let mut expected = vec![id, untagged];
+    expected.sort();
+    assert_eq!(ids, expected);
+
+    let events = engine
+        .storage
+        .list_events("dr-okafor", 10, 0)
+        .await
+        .unwrap();
+    assert!(
+        events
+            .iter()
+            .any(|e| e.event_type == EventType::MemoryReviewed)
+    );
+}
+
+#[tokio::test]
+async fn rejected_memories_stay_out_of_recall() {
+    let engine = create_engine();
+    let id = remember(&engine, "medical").await;
+
+    let mut rejection = AttestRequest::new(id, false);
+    rejection.note = Some("dose is wrong".to_string());
+    let response = engine.attest(rejection).await.unwrap();
+    assert_eq!(response.status, ReviewStatus::Rejected);
+    assert!(recalled(&engine).await.is_empty());
+
+    let err = engine
+        .attest(attest_as("dr-lee", id, true))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Validation(_)), "{err}");
+
+    let stored = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert_eq!(
+        stored.metadata["attestation"]["decisions"][0]["note"],
+        "dose is wrong"
+    );
+}
@@ -0,0 +1,170 @@
+//! Integration tests for automatic thread checkpoints.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::auto_checkpoint::{
+    AUTO_CHECKPOINT_LABEL, AutoCheckpointConfig, AutoCheckpointPolicy,
+};
+use mnemo_core::query::checkpoint::CheckpointRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use serde_json::json;
+
+const AGENT: &str = "auto-agent";
+const THREAD: &str = "auto-thread";
+
+fn create_engine(config: AutoCheckpointConfig) -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, AGENT.to_string(), None)
+        .with_auto_checkpoint(config)
+}
+
+fn every(n: u32) -> AutoCheckpointPolicy {
+    AutoCheckpointPolicy {
+        every_n_memories: Some(n),
+        inactivity_seconds: None,
+    }
+}
+
+async fn remember(engine: &MnemoEngine, agent_id: &str, content: &str) {
+    remember_in(engine, agent_id, THREAD, content).await;
+}
+
+async fn remember_in(engine: &MnemoEngine, agent_id: &str, thread_id: &str, content: &str) {
+    let mut request = RememberRequest::new(content.to_string());
+    request.agent_id = Some(agent_id.to_string());
+    request.thread_id = Some(thread_id.to_string());
+    engine.remember(request).await.unwrap();
+}
+
+async fn auto_checkpoints(engine: &MnemoEngine) -> Vec<mnemo_core::model::checkpoint::Checkpoint> {
+    auto_checkpoints_in(engine, THREAD).await
+}
+
+async fn auto_checkpoints_in(
+    engine: &MnemoEngine,
+    thread_id: &str,
+) -> Vec<mnemo_core::model::checkpoint::Checkpoint> {
+    engine
+        .storage
+        .list_checkpoints(thread_id, Some("main"), 100)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|cp| cp.label.as_deref() == Some(AUTO_CHECKPOINT_LABEL))
+        .collect()
+}
+
+#[tokio::test]
+async fn every_n_remembers_checkpoints_the_thread() {
+    let engine = create_engine(AutoCheckpointConfig {
+        default: Some(every(3)),
+        ..Default::default()
+    });
+
+    for i in 0..7 {
+        remember(&engine, AGENT, &format!("step {i} of the deploy")).await;
+    }
+
+    let checkpoints = auto_checkpoints(&engine).await;
+    assert_eq!(checkpoints.len(), 2);
+    assert!(
+        checkpoints
+            .iter()
+            .all(|cp| cp.metadata["trigger"] == "memory_count" && cp.agent_id == AGENT)
+    );
+}
+
+#[tokio::test]
+async fn a_manual_checkpoint_restarts_the_count_and_is_carried_forward() {
+    let engine = create_engine(AutoCheckpointConfig {
+        default: Some(every(2)),
+        ..Default::default()
+    });
+
+    remember(&engine, AGENT, "first note").await;
+    let mut request = CheckpointRequest::new(THREAD.to_string(), json!({"plan": "ship"}));
+    request.agent_id = Some(AGENT.to_string());
+    engine.checkpoint(request).await.unwrap();
+    remember(&engine, AGENT, "second note").await;
+    assert!(auto_checkpoints(&engine).await.is_empty());
+
+    remember(&engine, AGENT, "third note").await;
+    let checkpoints = auto_checkpoints(&engine).await;
+    assert_eq!(checkpoints.len(), 1);
+    let state = mnemo_core::query::checkpoint::materialize(&engine, checkpoints[0].clone())
+        .await
+        .unwrap()
+        .state_snapshot;
+    assert_eq!(state, json!({"plan": "ship"}));
+}
+
+#[tokio::test]
+async fn agent_policies_override_the_default() {
+    let engine = create_engine(AutoCheckpointConfig {
+        default: None,
+        agents: HashMap::from([("planner".to_string(), every(1))]),
+    });
+
+    remember(&engine, AGENT, "no policy for this agent").await;
+    assert!(auto_checkpoints(&engine).await.is_empty());
+
+    // The planner writes to a thread of its own: the first one belongs to
+    // the other agent.
+    remember_in(
+        &engine,
+        "planner",
+        "planner-thread",
+        "planner writes are checkpointed",
+    )
+    .await;
+    let checkpoints = auto_checkpoints_in(&engine, "planner-thread").await;
+    assert_eq!(checkpoints.len(), 1);
+    assert_eq!(checkpoints[0].agent_id, "planner");
+}
+
+#[tokio::test]
+async fn idle_threads_are_checkpointed_by_the_pass() {
+    let engine = create_engine(AutoCheckpointConfig {
+        default: Some(AutoCheckpointPolicy {
+            every_n_memories: None,
+            inactivity_seconds: Some(1),
+        }),
+        ..Default::default()
+    });
+
+    remember(&engine, AGENT, "the thread goes quiet after this").await;
+    assert_eq!(engine.run_auto_checkpoints().await.unwrap().created, 0);
+
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    // A read-only engine refuses the pass and keeps the thread pending.
+    engine.set_read_only(true);
+    let err = engine.run_auto_checkpoints().await.unwrap_err();
+    assert!(matches!(err, Error::ReadOnly(_)), "{err}");
+    engine.set_read_only(false);
+    assert_eq!(engine.run_auto_checkpoints().await.unwrap().created, 1);
+    assert_eq!(
+        auto_checkpoints(&engine).await[0].metadata["trigger"],
+        "inactivity"
+    );
+
+    // Nothing new was written, so the next pass has nothing to do.
+    assert_eq!(engine.run_auto_checkpoints().await.unwrap().created, 0);
+}
+
+#[test]
+fn zero_triggers_are_rejected() {
+    let config = AutoCheckpointConfig {
+        default: Some(every(0)),
+        ..Default::default()
+    };
+    assert!(config.validate().unwrap_err().is_validation());
+}
@@ -0,0 +1,157 @@
+//! Integration tests for sharing many memories in one request.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::acl::Permission;
+use mnemo_core::model::event::EventType;
+use mnemo_core::model::memory::{MemoryRecord, Scope};
+use mnemo_core::query::limits::LimitsConfig;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::query::share::{ShareFilter, ShareRequest};
+use mnemo_core::query::{MAX_BATCH_QUERY_LIMIT, MnemoEngine};
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "lead".to_string(), None)
+}
+
+async fn remember(engine: &MnemoEngine, agent: &str, content: &str, tags: &[&str]) -> Uuid {
+    let mut request = RememberRequest::new(content.to_string());
+    request.agent_id = Some(agent.to_string());
+    request.tags = Some(tags.iter().map(|t| t.to_string()).collect());
+    engine.remember(request).await.unwrap().id
+}
+
+async fn can_read(engine: &MnemoEngine, id: Uuid, agent: &str) -> bool {
+    engine
+        .storage
+        .check_permission(id, agent, Permission::Read)
+        .await
+        .unwrap()
+}
+
+fn sorted(mut ids: Vec<Uuid>) -> Vec<Uuid> {
+    ids.sort();
+    ids
+}
+
+fn project_x() -> ShareFilter {
+    ShareFilter {
+        tags: vec!["project-x".to_string()],
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn filter_share_dry_run_then_grant() {
+    let engine = create_engine();
+    let a = remember(&engine, "lead", "x uses postgres", &["project-x"]).await;
+    let b = remember(&engine, "lead", "x ships in may", &["project-x", "dates"]).await;
+    let other = remember(&engine, "lead", "y uses redis", &["project-y"]).await;
+    // Other agents' memories never match a filter share.
+    remember(&engine, "peer", "x has a peer note", &["project-x"]).await;
+
+    let mut request = ShareRequest::by_filter(project_x(), "agent-7".to_string());
+    request.expires_in_hours = Some(30.0 * 24.0);
+    request.dry_run = Some(true);
+    let preview = engine.share(request.clone()).await.unwrap();
+    assert!(preview.dry_run);
+    assert_eq!(sorted(preview.memory_ids), sorted(vec![a, b]));
+    assert!(preview.acl_ids.is_empty());
+    assert!(!can_read(&engine, a, "agent-7").await);
+
+    request.dry_run = None;
+    let response = engine.share(request).await.unwrap();
+    assert_eq!(sorted(response.memory_ids), sorted(vec![a, b]));
+    assert_eq!(response.acl_ids.len(), 2);
+    assert!(can_read(&engine, a, "agent-7").await);
+    assert!(can_read(&engine, b, "agent-7").await);
+    assert!(!can_read(&engine, other, "agent-7").await);
+    let record = engine.storage.get_memory(b).await.unwrap().unwrap();
+    assert_eq!(record.scope, Scope::Shared);
+
+    // One audit event covers the whole share.
+    let shares: Vec<_> = engine
+        .storage
+        .list_events("lead", 100, 0)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|e| e.event_type == EventType::MemoryShare)
+        .collect();
+    assert_eq!(shares.len(), 1);
+    assert_eq!(shares[0].payload["memory_ids"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn listed_shares_are_all_or_nothing() {
+    let engine = create_engine();
+    let mine = remember(&engine, "lead", "the release checklist", &[]).await;
+    let theirs = remember(&engine, "peer", "the peer's private notes", &[]).await;
+
+    let mut request = ShareRequest::new(Uuid::nil(), "agent-7".to_string());
+    request.memory_ids = Some(vec![mine, theirs]);
+    let err = engine.share(request.clone()).await.unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)));
+    assert!(!can_read(&engine, mine, "agent-7").await);
+
+    request.memory_ids = Some(vec![mine, mine]);
+    request.target_agent_ids = Some(vec!["agent-7".to_string(), "agent-8".to_string()]);
+    let response = engine.share(request).await.unwrap();
+    assert_eq!(response.memory_ids, [mine]);
+    assert_eq!(response.acl_ids.len(), 2);
+    assert!(can_read(&engine, mine, "agent-8").await);
+}
+
+#[tokio::test]
+async fn empty_filters_share_nothing() {
+    let engine = create_engine();
+    let mut request = ShareRequest::by_filter(project_x(), "agent-7".to_string());
+    assert!(matches!(
+        engine.share(request.clone()).await,
+        Err(Error::NotFound(_))
+    ));
+    request.dry_run = Some(true);
+    assert!(engine.share(request).await.unwrap().memory_ids.is_empty());
+}
+
+#[tokio::test]
+async fn empty_target_lists_are_rejected() {
+    let engine = create_engine();
+    let id = remember(&engine, "lead", "the release checklist", &[]).await;
+
+    for dry_run in [None, Some(true)] {
+        let mut request = ShareRequest::new(id, "agent-7".to_string());
+        request.target_agent_ids = Some(Vec::new());
+        request.dry_run = dry_run;
+        let err = engine.share(request).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidFields(_)), "{err}");
+    }
+    assert!(!can_read(&engine, id, "agent-7").await);
+}
+
+#[tokio::test]
+async fn oversized_filter_shares_are_rejected_not_truncated() {
+    let engine = create_engine().with_limits(LimitsConfig {
+        max_batch_size: MAX_BATCH_QUERY_LIMIT,
+        ..Default::default()
+    });
+    // One more match than a single storage page holds.
+    for i in 0..=MAX_BATCH_QUERY_LIMIT {
+        let mut record = MemoryRecord::new("lead".to_string(), format!("x note {i}"));
+        record.tags = vec!["project-x".to_string()];
+        engine.storage.insert_memory(&record).await.unwrap();
+    }
+
+    let mut request = ShareRequest::by_filter(project_x(), "agent-7".to_string());
+    request.dry_run = Some(true);
+    let err = engine.share(request).await.unwrap_err();
+    assert!(matches!(err, Error::InvalidFields(_)), "{err}");
+}
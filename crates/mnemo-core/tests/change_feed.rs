@@ -0,0 +1,96 @@
+//! Integration tests for applying memory changes made by other processes.
+
+use std::sync::Arc;
+
+use mnemo_core::cache::MemoryCache;
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::memory::MemoryRecord;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::change_feed::{MemoryChange, spawn_change_listener};
+use mnemo_core::search::tantivy_index::TantivyFullTextIndex;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+const AGENT: &str = "feed-agent";
+
+fn create_engine() -> (MnemoEngine, Arc<MemoryCache>) {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    let cache = Arc::new(MemoryCache::new(300, 100));
+    let engine = MnemoEngine::new(storage, index, embedding, AGENT.to_string(), None)
+        .with_full_text(Arc::new(TantivyFullTextIndex::open_in_memory().unwrap()))
+        .with_cache(cache.clone());
+    (engine, cache)
+}
+
+/// Write a memory straight to storage, as another process would.
+async fn remote_insert(engine: &MnemoEngine, content: &str) -> MemoryRecord {
+    let mut record = MemoryRecord::new(AGENT.to_string(), content.to_string());
+    record.embedding = Some(engine.embedding.embed(content).await.unwrap());
+    engine.storage.insert_memory(&record).await.unwrap();
+    record
+}
+
+#[tokio::test]
+async fn remote_writes_update_indexes_and_cache() {
+    let (engine, cache) = create_engine();
+    let mut record = remote_insert(&engine, "the staging cluster moved to eu-west").await;
+    assert_eq!(engine.index.len(), 0);
+
+    engine
+        .apply_memory_change(MemoryChange::Inserted(record.id))
+        .await
+        .unwrap();
+    assert_eq!(engine.index.len(), 1);
+    let hits = engine
+        .full_text
+        .as_ref()
+        .unwrap()
+        .search("staging", 5)
+        .unwrap();
+    assert_eq!(hits[0].0, record.id);
+
+    cache.put(record.clone());
+    record.deleted_at = Some(chrono::Utc::now().to_rfc3339());
+    engine.storage.update_memory(&record).await.unwrap();
+    engine
+        .apply_memory_change(MemoryChange::Updated(record.id))
+        .await
+        .unwrap();
+    assert_eq!(engine.index.len(), 0);
+    assert!(cache.get(record.id).is_none());
+    let hits = engine
+        .full_text
+        .as_ref()
+        .unwrap()
+        .search("staging", 5)
+        .unwrap();
+    assert!(hits.is_empty());
+}
+
+#[tokio::test]
+async fn listener_applies_changes_until_the_feed_closes() {
+    let (engine, cache) = create_engine();
+    let engine = Arc::new(engine);
+    let kept = remote_insert(&engine, "deploys freeze on fridays").await;
+    let dropped = remote_insert(&engine, "the vpn needs a hardware key").await;
+    cache.put(kept.clone());
+
+    let (tx, rx) = tokio::sync::mpsc::channel(8);
+    let listener = spawn_change_listener(engine.clone(), rx);
+    tx.send(MemoryChange::Inserted(kept.id)).await.unwrap();
+    tx.send(MemoryChange::Inserted(dropped.id)).await.unwrap();
+    tx.send(MemoryChange::Deleted(dropped.id)).await.unwrap();
+    tx.send(MemoryChange::Resync).await.unwrap();
+    drop(tx);
+    listener.await.unwrap();
+
+    assert_eq!(engine.index.len(), 1);
+    assert!(cache.get(kept.id).is_none());
+    let hits = engine
+        .index
+        .search(kept.embedding.as_ref().unwrap(), 1)
+        .unwrap();
+    assert_eq!(hits[0].0, kept.id);
+}
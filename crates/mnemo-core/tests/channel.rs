@@ -0,0 +1,228 @@
+//! Integration tests for channels (shared memory spaces).
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::{Error, Result};
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::acl::Permission;
+use mnemo_core::model::memory::Scope;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::channel::{
+    ChannelMembershipRequest, CreateChannelRequest, SetChannelQuotaRequest,
+};
+use mnemo_core::query::forget::ForgetRequest;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "triage".to_string(), None)
+}
+
+async fn create(engine: &MnemoEngine, name: &str, max_memories: Option<u64>) {
+    let request = CreateChannelRequest {
+        max_memories,
+        ..CreateChannelRequest::new(name.to_string())
+    };
+    engine.create_channel(request).await.unwrap();
+}
+
+async fn join(engine: &MnemoEngine, name: &str, agent: &str) {
+    let mut request = ChannelMembershipRequest::new(name.to_string());
+    request.agent_id = Some(agent.to_string());
+    engine.join_channel(request).await.unwrap();
+}
+
+async fn remember(engine: &MnemoEngine, agent: &str, content: &str, channel: &str) -> Result<Uuid> {
+    let mut request = RememberRequest::new(content.to_string());
+    request.agent_id = Some(agent.to_string());
+    request.channel = Some(channel.to_string());
+    Ok(engine.remember(request).await?.id)
+}
+
+async fn recall(engine: &MnemoEngine, agent: &str, channel: &str) -> Result<Vec<Uuid>> {
+    let mut request = RecallRequest::new("the database failover".to_string());
+    request.agent_id = Some(agent.to_string());
+    request.channel = Some(channel.to_string());
+    let response = engine.recall(request).await?;
+    Ok(response.memories.into_iter().map(|m| m.id).collect())
+}
+
+async fn can_read(engine: &MnemoEngine, id: Uuid, agent: &str) -> bool {
+    engine
+        .storage
+        .check_permission(id, agent, Permission::Read)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn members_read_channel_memories_without_acls() {
+    let engine = create_engine();
+    create(&engine, "incident-42", None).await;
+    join(&engine, "incident-42", "comms").await;
+
+    let id = remember(
+        &engine,
+        "triage",
+        "the database failover started at 09:12",
+        "incident-42",
+    )
+    .await
+    .unwrap();
+    let record = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert_eq!(record.scope, Scope::Shared);
+    assert_eq!(record.channel.as_deref(), Some("incident-42"));
+    // Something outside the channel never comes back from a channel recall.
+    let mut other = RememberRequest::new("the database failover drill is in may".to_string());
+    other.scope = Some(Scope::Public);
+    engine.remember(other).await.unwrap();
+
+    assert!(can_read(&engine, id, "comms").await);
+    assert!(!can_read(&engine, id, "outsider").await);
+    assert!(
+        !engine
+            .storage
+            .check_permission(id, "comms", Permission::Write)
+            .await
+            .unwrap()
+    );
+    assert_eq!(recall(&engine, "comms", "incident-42").await.unwrap(), [id]);
+    assert!(matches!(
+        recall(&engine, "outsider", "incident-42").await,
+        Err(Error::PermissionDenied(_))
+    ));
+    assert!(matches!(
+        remember(&engine, "outsider", "a stray note", "incident-42").await,
+        Err(Error::PermissionDenied(_))
+    ));
+
+    // Leaving revokes access to the channel's memories at once.
+    let mut leave = ChannelMembershipRequest::new("incident-42".to_string());
+    leave.agent_id = Some("comms".to_string());
+    engine.leave_channel(leave).await.unwrap();
+    assert!(!can_read(&engine, id, "comms").await);
+}
+
+#[tokio::test]
+async fn full_channels_reject_writes_until_memories_are_forgotten() {
+    let engine = create_engine();
+    create(&engine, "standup", Some(2)).await;
+    let first = remember(&engine, "triage", "monday: failover test", "standup")
+        .await
+        .unwrap();
+    remember(&engine, "triage", "tuesday: index rebuild", "standup")
+        .await
+        .unwrap();
+
+    let err = remember(&engine, "triage", "wednesday: nothing", "standup")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::QuotaExceeded(_)), "{err}");
+
+    engine
+        .forget(ForgetRequest::new(vec![first]))
+        .await
+        .unwrap();
+    remember(&engine, "triage", "wednesday: nothing", "standup")
+        .await
+        .unwrap();
+
+    // Only the creator sets the quota.
+    join(&engine, "standup", "comms").await;
+    let mut raise = SetChannelQuotaRequest::new("standup".to_string(), Some(10));
+    raise.agent_id = Some("comms".to_string());
+    assert!(matches!(
+        engine.set_channel_quota(raise.clone()).await,
+        Err(Error::PermissionDenied(_))
+    ));
+    raise.agent_id = None;
+    let channel = engine.set_channel_quota(raise).await.unwrap();
+    assert_eq!(channel.max_memories, Some(10));
+}
+
+#[tokio::test]
+async fn membership_is_listed_and_managed_by_the_creator() {
+    let engine = create_engine();
+    create(&engine, "incident-42", None).await;
+    create(&engine, "standup", None).await;
+    join(&engine, "incident-42", "comms").await;
+    join(&engine, "incident-42", "oncall").await;
+
+    let channels = engine
+        .list_channels(Some("comms".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(channels.len(), 1);
+    assert_eq!(channels[0].name, "incident-42");
+    assert_eq!(engine.list_channels(None).await.unwrap().len(), 2);
+
+    let members = engine
+        .channel_members("incident-42", Some("comms".to_string()))
+        .await
+        .unwrap();
+    let names: Vec<_> = members.iter().map(|m| m.agent_id.as_str()).collect();
+    assert_eq!(names, ["triage", "comms", "oncall"]);
+    assert!(matches!(
+        engine
+            .channel_members("incident-42", Some("outsider".to_string()))
+            .await,
+        Err(Error::PermissionDenied(_))
+    ));
+
+    // Members leave on their own; only the creator removes others.
+    let mut kick = ChannelMembershipRequest::new("incident-42".to_string());
+    kick.agent_id = Some("comms".to_string());
+    kick.member_id = Some("oncall".to_string());
+    assert!(matches!(
+        engine.leave_channel(kick.clone()).await,
+        Err(Error::PermissionDenied(_))
+    ));
+    kick.agent_id = None;
+    engine.leave_channel(kick).await.unwrap();
+    assert_eq!(
+        engine
+            .channel_members("incident-42", None)
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn malformed_channel_requests_are_rejected() {
+    let engine = create_engine();
+    create(&engine, "incident-42", None).await;
+
+    let err = engine
+        .create_channel(CreateChannelRequest::new("incident-42".to_string()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Conflict(_)), "{err}");
+    let err = engine
+        .create_channel(CreateChannelRequest::new("no spaces".to_string()))
+        .await
+        .unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "channel");
+    let mut request = CreateChannelRequest::new("empty".to_string());
+    request.max_memories = Some(0);
+    let err = engine.create_channel(request).await.unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "max_memories");
+
+    let mut private = RememberRequest::new("a private note".to_string());
+    private.channel = Some("incident-42".to_string());
+    private.scope = Some(Scope::Private);
+    let err = engine.remember(private).await.unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "scope");
+
+    assert!(matches!(
+        remember(&engine, "triage", "into the void", "missing").await,
+        Err(Error::NotFound(_))
+    ));
+}
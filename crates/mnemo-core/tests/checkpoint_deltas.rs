@@ -0,0 +1,137 @@
+//! Integration tests for delta-compressed checkpoint states.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::branch::BranchRequest;
+use mnemo_core::query::checkpoint::{CheckpointRequest, MERGE_PATCH_KEY};
+use mnemo_core::query::limits::LimitsConfig;
+use mnemo_core::query::replay::ReplayRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use serde_json::json;
+use uuid::Uuid;
+
+const THREAD: &str = "delta-thread";
+
+fn create_engine(interval: u32) -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "delta-agent".to_string(), None)
+        .with_checkpoint_snapshot_interval(interval)
+}
+
+/// A state with a large, unchanging part so deltas pay off.
+fn state(step: u32, extra: serde_json::Value) -> serde_json::Value {
+    json!({
+        "transcript": "x".repeat(4096),
+        "step": step,
+        "extra": extra,
+    })
+}
+
+async fn checkpoint(engine: &MnemoEngine, snapshot: serde_json::Value) -> Uuid {
+    engine
+        .checkpoint(CheckpointRequest::new(THREAD.to_string(), snapshot))
+        .await
+        .unwrap()
+        .id
+}
+
+async fn replay(engine: &MnemoEngine, id: Uuid) -> serde_json::Value {
+    let mut request = ReplayRequest::new(THREAD.to_string());
+    request.checkpoint_id = Some(id);
+    engine
+        .replay(request)
+        .await
+        .unwrap()
+        .checkpoint
+        .state_snapshot
+}
+
+async fn is_delta(engine: &MnemoEngine, id: Uuid) -> bool {
+    let stored = engine.storage.get_checkpoint(id).await.unwrap().unwrap();
+    stored
+        .state_diff
+        .is_some_and(|d| d.get(MERGE_PATCH_KEY).is_some())
+}
+
+#[tokio::test]
+async fn full_snapshots_every_interval_and_deltas_between() {
+    let engine = create_engine(3);
+    let states = [
+        state(1, json!({"a": 1, "b": [1, 2]})),
+        state(2, json!({"a": 2, "b": [1, 2]})),
+        state(3, json!({"b": [null, 3]})),
+        state(4, json!({"c": {"nested": true}})),
+        state(5, json!("scalar")),
+    ];
+    let mut ids = Vec::new();
+    for s in &states {
+        ids.push(checkpoint(&engine, s.clone()).await);
+    }
+
+    let mut kinds = Vec::new();
+    for id in &ids {
+        kinds.push(is_delta(&engine, *id).await);
+    }
+    assert_eq!(kinds, [false, true, true, false, true]);
+
+    for (id, expected) in ids.iter().zip(&states) {
+        assert_eq!(&replay(&engine, *id).await, expected);
+    }
+    // Latest-checkpoint replay reconstructs too.
+    let latest = engine
+        .replay(ReplayRequest::new(THREAD.to_string()))
+        .await
+        .unwrap();
+    assert_eq!(latest.checkpoint.state_snapshot, states[4]);
+}
+
+#[tokio::test]
+async fn unrepresentable_changes_are_stored_in_full() {
+    let engine = create_engine(10);
+    checkpoint(&engine, state(1, json!({"a": 1}))).await;
+    let id = checkpoint(&engine, state(2, json!({"a": null}))).await;
+    assert!(!is_delta(&engine, id).await);
+    assert_eq!(replay(&engine, id).await, state(2, json!({"a": null})));
+}
+
+#[tokio::test]
+async fn branches_start_from_the_reconstructed_state() {
+    let engine = create_engine(10);
+    checkpoint(&engine, state(1, json!({}))).await;
+    checkpoint(&engine, state(2, json!({"k": "v"}))).await;
+
+    engine
+        .branch(BranchRequest::new(THREAD.to_string(), "fork".to_string()))
+        .await
+        .unwrap();
+    let mut request = ReplayRequest::new(THREAD.to_string());
+    request.branch_name = Some("fork".to_string());
+    let forked = engine.replay(request).await.unwrap();
+    assert_eq!(
+        forked.checkpoint.state_snapshot,
+        state(2, json!({"k": "v"}))
+    );
+}
+
+#[tokio::test]
+async fn oversized_snapshots_are_rejected() {
+    let engine = create_engine(10).with_limits(LimitsConfig {
+        max_snapshot_bytes: 1024,
+        ..Default::default()
+    });
+    let err = engine
+        .checkpoint(CheckpointRequest::new(
+            THREAD.to_string(),
+            state(1, json!({})),
+        ))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidFields(_)));
+    assert_eq!(err.field_violations()[0].path, "state_snapshot");
+}
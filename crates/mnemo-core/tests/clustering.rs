@@ -0,0 +1,106 @@
+//! Integration tests for the topic map over an agent's memories.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+const COOKING: &[&str] = &[
+    "pasta sauce garlic tomato basil simmer",
+    "garlic tomato pasta sauce with fresh basil",
+    "simmer the tomato sauce then add garlic",
+];
+const DEPLOYS: &[&str] = &[
+    "kubernetes deploy rollout canary cluster",
+    "canary rollout for the kubernetes cluster deploy",
+    "rollback the kubernetes deploy after failed canary",
+    "cluster deploy pipeline with canary rollout",
+];
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "server".to_string(), None)
+}
+
+async fn remember_as(engine: &MnemoEngine, agent_id: &str, content: &str) {
+    let mut request = RememberRequest::new(content.to_string());
+    request.agent_id = Some(agent_id.to_string());
+    engine.remember(request).await.unwrap();
+}
+
+#[tokio::test]
+async fn memories_split_into_topics_with_distinctive_terms() {
+    let engine = create_engine();
+    for content in COOKING.iter().chain(DEPLOYS) {
+        remember_as(&engine, "chef", content).await;
+    }
+    remember_as(&engine, "other", "kubernetes canary rollout notes").await;
+
+    let map = engine
+        .cluster_memories(Some("chef".to_string()), 2)
+        .await
+        .unwrap();
+    assert_eq!(map.agent_id, "chef");
+    assert_eq!(map.memory_count, COOKING.len() + DEPLOYS.len());
+    assert_eq!(map.clusters.len(), 2);
+
+    let deploys = &map.clusters[0];
+    assert_eq!(deploys.id, 0);
+    assert_eq!(deploys.size, DEPLOYS.len());
+    assert!(deploys.top_terms.iter().any(|t| t == "kubernetes"));
+    assert!(deploys.top_terms.iter().all(|t| t != "the"));
+    assert_eq!(deploys.representatives.len(), 3);
+    assert!(
+        deploys
+            .representatives
+            .iter()
+            .all(|r| DEPLOYS.contains(&r.content.as_str()))
+    );
+    assert!(
+        deploys
+            .representatives
+            .windows(2)
+            .all(|w| w[0].similarity >= w[1].similarity)
+    );
+
+    let cooking = &map.clusters[1];
+    assert_eq!(cooking.size, COOKING.len());
+    assert!(cooking.top_terms.iter().any(|t| t == "tomato"));
+    let norm: f32 = cooking.centroid.iter().map(|x| x * x).sum::<f32>().sqrt();
+    assert!((norm - 1.0).abs() < 1e-4);
+}
+
+#[tokio::test]
+async fn k_is_bounded_by_the_memory_count() {
+    let engine = create_engine();
+    let empty = engine
+        .cluster_memories(Some("chef".to_string()), 4)
+        .await
+        .unwrap();
+    assert_eq!(empty.memory_count, 0);
+    assert!(empty.clusters.is_empty());
+
+    for content in COOKING {
+        remember_as(&engine, "chef", content).await;
+    }
+    let map = engine
+        .cluster_memories(Some("chef".to_string()), 10)
+        .await
+        .unwrap();
+    assert!(map.clusters.len() <= COOKING.len());
+    assert_eq!(
+        map.clusters.iter().map(|c| c.size).sum::<usize>(),
+        COOKING.len()
+    );
+
+    let err = engine
+        .cluster_memories(Some("chef".to_string()), 0)
+        .await
+        .unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "k");
+}
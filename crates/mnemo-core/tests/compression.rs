@@ -0,0 +1,178 @@
+//! Integration tests for the thread compression pass.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::{Error, Result};
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::memory::{ConsolidationState, MemoryRecord, MemoryType};
+use mnemo_core::model::relation::CONSOLIDATED_FROM;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::compression::CompressionRequest;
+use mnemo_core::storage::cold::{ColdStorage, ColdStorageConfig, InMemoryColdStorage};
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use mnemo_core::summarize::Summarizer;
+use uuid::Uuid;
+
+const AGENT: &str = "compress-agent";
+
+fn create_engine() -> (MnemoEngine, Arc<InMemoryColdStorage>) {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    let cold = Arc::new(InMemoryColdStorage::new(ColdStorageConfig {
+        bucket: "test".to_string(),
+        prefix: "memories".to_string(),
+        endpoint: None,
+        region: "us-east-1".to_string(),
+    }));
+    let engine = MnemoEngine::new(storage, index, embedding, AGENT.to_string(), None)
+        .with_cold_storage(cold.clone());
+    (engine, cold)
+}
+
+/// Insert an episodic memory `age_days` old, bypassing remember so the
+/// timestamp can be backdated.
+async fn insert(
+    engine: &MnemoEngine,
+    content: &str,
+    thread: &str,
+    age_days: i64,
+    pinned: bool,
+) -> Uuid {
+    let mut record = MemoryRecord::new(AGENT.to_string(), content.to_string());
+    record.thread_id = Some(thread.to_string());
+    record.created_at = (chrono::Utc::now() - chrono::Duration::days(age_days)).to_rfc3339();
+    record.tags = vec![format!("tag-{}", content.len() % 3)];
+    record.pinned = pinned;
+    let embedding = engine.embedding.embed(content).await.unwrap();
+    record.embedding = Some(embedding.clone());
+    engine.storage.insert_memory(&record).await.unwrap();
+    engine.index.add(record.id, &embedding).unwrap();
+    record.id
+}
+
+#[tokio::test]
+async fn old_thread_memories_are_replaced_by_summaries() {
+    let (engine, cold) = create_engine();
+    let mut old = Vec::new();
+    for i in 0..12 {
+        old.push(
+            insert(
+                &engine,
+                &format!("step {i} of the migration ran."),
+                "t1",
+                40,
+                false,
+            )
+            .await,
+        );
+    }
+    let recent = insert(&engine, "the migration finished today.", "t1", 1, false).await;
+    let pinned = insert(&engine, "never drop the users table.", "t1", 40, true).await;
+    for i in 0..3 {
+        insert(&engine, &format!("short thread note {i}."), "t2", 40, false).await;
+    }
+    assert_eq!(engine.index.len(), 17);
+
+    let request = CompressionRequest {
+        batch_size: 5,
+        ..Default::default()
+    };
+    let report = engine.run_compression(request).await.unwrap();
+    assert_eq!(report.threads_compressed, 1);
+    assert_eq!(report.summaries_created, 3);
+    assert_eq!(report.originals_archived, 12);
+    assert_eq!(report.archived_to_cold_storage, 12);
+    assert_eq!(engine.index.len(), 17 - 12 + 3);
+
+    for id in &old {
+        let record = engine.storage.get_memory(*id).await.unwrap().unwrap();
+        assert_eq!(record.consolidation_state, ConsolidationState::Archived);
+        assert!(cold.is_archived(*id).await.unwrap());
+    }
+    for id in [recent, pinned] {
+        let record = engine.storage.get_memory(id).await.unwrap().unwrap();
+        assert_eq!(record.consolidation_state, ConsolidationState::Raw);
+    }
+
+    let first = engine
+        .storage
+        .get_memory(report.summary_ids[0])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(first.memory_type, MemoryType::Semantic);
+    assert_eq!(first.thread_id.as_deref(), Some("t1"));
+    assert!(first.content.starts_with("step 0 of the migration ran."));
+    assert_eq!(first.metadata["summarizer"], "extractive");
+    let relations = engine.storage.get_relations_from(first.id).await.unwrap();
+    assert_eq!(relations.len(), 5);
+    assert!(
+        relations
+            .iter()
+            .all(|r| r.relation_type == CONSOLIDATED_FROM)
+    );
+
+    // Nothing left to compress.
+    let report = engine
+        .run_compression(CompressionRequest::default())
+        .await
+        .unwrap();
+    assert_eq!(report.summaries_created, 0);
+}
+
+struct CountingSummarizer;
+
+#[async_trait::async_trait]
+impl Summarizer for CountingSummarizer {
+    async fn summarize(&self, texts: &[String]) -> Result<String> {
+        Ok(format!("{} earlier messages about deploys", texts.len()))
+    }
+
+    fn name(&self) -> &str {
+        "counting"
+    }
+}
+
+#[tokio::test]
+async fn attached_summarizer_writes_the_summary() {
+    let (engine, _cold) = create_engine();
+    let engine = engine.with_summarizer(Arc::new(CountingSummarizer));
+    for i in 0..4 {
+        insert(&engine, &format!("deploy chat {i}"), "t1", 60, false).await;
+    }
+    insert(&engine, "unrelated thread", "t2", 60, false).await;
+
+    let request = CompressionRequest {
+        thread_id: Some("t1".to_string()),
+        min_memories: 2,
+        ..Default::default()
+    };
+    let report = engine.run_compression(request).await.unwrap();
+    assert_eq!(report.summaries_created, 1);
+    let summary = engine
+        .storage
+        .get_memory(report.summary_ids[0])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(summary.content, "4 earlier messages about deploys");
+    assert_eq!(summary.metadata["summarizer"], "counting");
+    assert_eq!(
+        summary.metadata["compressed_from"]
+            .as_array()
+            .unwrap()
+            .len(),
+        4
+    );
+
+    let err = engine
+        .run_compression(CompressionRequest {
+            batch_size: 0,
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Validation(_)));
+}
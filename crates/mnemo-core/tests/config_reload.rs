@@ -0,0 +1,93 @@
+//! Integration tests for applying a changed config to a running engine.
+
+use mnemo_core::config::{EmbeddingConfig, MnemoEngineConfig};
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::remember::RememberRequest;
+
+async fn create_engine() -> (MnemoEngine, MnemoEngineConfig) {
+    let config = MnemoEngineConfig {
+        agent_id: "agent".to_string(),
+        dimensions: 64,
+        embedding: EmbeddingConfig::Deterministic,
+        ..Default::default()
+    };
+    let engine = MnemoEngine::from_config(&config).await.unwrap();
+    (engine, config)
+}
+
+fn remember_tagged(tags: usize) -> RememberRequest {
+    let mut request = RememberRequest::new("tagged note".to_string());
+    request.tags = Some((0..tags).map(|i| format!("t{i}")).collect());
+    request
+}
+
+#[tokio::test]
+async fn reload_applies_limits_to_the_running_engine() {
+    let (engine, mut running) = create_engine().await;
+    engine.remember(remember_tagged(3)).await.unwrap();
+
+    let mut next = running.clone();
+    next.limits.max_tags = 2;
+    next.read_only = true;
+    let reload = engine.reload_config(&mut running, &next).unwrap();
+    let settings: Vec<_> = reload.applied.iter().map(|c| c.setting.as_str()).collect();
+    assert_eq!(settings, ["limits.max_tags", "read_only"]);
+    assert!(reload.restart_required.is_empty());
+    assert_eq!(engine.limits().max_tags, 2);
+    assert!(engine.is_read_only());
+    assert!(running == next);
+
+    // Reloading the same file again changes nothing.
+    assert!(
+        engine
+            .reload_config(&mut running, &next)
+            .unwrap()
+            .is_empty()
+    );
+
+    next.read_only = false;
+    engine.reload_config(&mut running, &next).unwrap();
+    let err = engine.remember(remember_tagged(3)).await.unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "tags");
+}
+
+#[tokio::test]
+async fn reload_reports_settings_that_need_a_restart() {
+    let (engine, mut running) = create_engine().await;
+    let mut next = running.clone();
+    next.dimensions = 128;
+    next.encryption_key = Some("ab".repeat(32));
+
+    let reload = engine.reload_config(&mut running, &next).unwrap();
+    assert!(reload.applied.is_empty());
+    let key = reload
+        .restart_required
+        .iter()
+        .find(|c| c.setting == "encryption_key")
+        .unwrap();
+    assert_eq!(key.before, serde_json::Value::Null);
+    assert_eq!(key.after, "<redacted>");
+    assert!(
+        reload
+            .restart_required
+            .iter()
+            .any(|c| c.setting == "dimensions" && c.before == 64 && c.after == 128)
+    );
+
+    // Still pending until a restart, so a second reload reports it again.
+    let again = engine.reload_config(&mut running, &next).unwrap();
+    assert_eq!(again.restart_required.len(), reload.restart_required.len());
+}
+
+#[tokio::test]
+async fn invalid_config_is_rejected_without_applying_anything() {
+    let (engine, mut running) = create_engine().await;
+    let before = running.clone();
+    let mut next = running.clone();
+    next.limits.max_tags = 2;
+    next.dimensions = 0;
+
+    assert!(engine.reload_config(&mut running, &next).is_err());
+    assert_eq!(engine.limits().max_tags, before.limits.max_tags);
+    assert!(running == before);
+}
@@ -0,0 +1,151 @@
+//! Integration tests for the conflict review queue: detected pairs are
+//! queued once, and a human decision closes them by strategy, winner or
+//! dismissal.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::NoopEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::conflict::{ConflictRecord, ConflictStatus};
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::conflict::ResolutionStrategy;
+use mnemo_core::query::conflict_queue::{
+    ConflictDecision, ConflictListRequest, ResolveQueuedConflictRequest,
+};
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+/// The no-op embedder maps everything to the same vector, so any two
+/// memories with different content are detected as a conflict.
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(128).unwrap());
+    let embedding = Arc::new(NoopEmbedding::new(128));
+    MnemoEngine::new(storage, index, embedding, "queue-agent".to_string(), None)
+}
+
+async fn remember(engine: &MnemoEngine, content: &str) -> Uuid {
+    let mut req = RememberRequest::new(content.to_string());
+    req.importance = Some(0.9);
+    engine.remember(req).await.unwrap().id
+}
+
+async fn queued_pair(engine: &MnemoEngine) -> (ConflictRecord, Uuid, Uuid) {
+    let a = remember(engine, "The user's office is in Berlin").await;
+    let b = remember(engine, "The user's office is in Munich").await;
+    let queued = engine.queue_conflicts(None, 0.9).await.unwrap();
+    assert_eq!(queued.len(), 1);
+    (queued.into_iter().next().unwrap(), a, b)
+}
+
+#[tokio::test]
+async fn detected_conflicts_are_queued_once() {
+    let engine = create_engine();
+    let (record, a, b) = queued_pair(&engine).await;
+    assert_eq!(record.status, ConflictStatus::Open);
+    let mut pair = [record.memory_a, record.memory_b];
+    pair.sort();
+    let mut expected = [a, b];
+    expected.sort();
+    assert_eq!(pair, expected);
+
+    // A second scan must not duplicate the still-open entry.
+    assert!(engine.queue_conflicts(None, 0.9).await.unwrap().is_empty());
+
+    let mut request = ConflictListRequest::new();
+    request.status = Some(ConflictStatus::Open);
+    let open = engine.list_conflicts(request).await.unwrap();
+    assert_eq!(open.len(), 1);
+    assert_eq!(open[0].id, record.id);
+}
+
+#[tokio::test]
+async fn manual_winner_keeps_one_memory() {
+    let engine = create_engine();
+    let (record, a, b) = queued_pair(&engine).await;
+
+    let mut request = ResolveQueuedConflictRequest::new(record.id, ConflictDecision::Winner(b));
+    request.resolved_by = Some("alice".to_string());
+    let resolved = engine.resolve_queued_conflict(request).await.unwrap();
+
+    assert_eq!(resolved.status, ConflictStatus::Resolved);
+    assert_eq!(resolved.winner_id, Some(b));
+    assert_eq!(resolved.resolved_by.as_deref(), Some("alice"));
+    assert!(resolved.resolved_at.is_some());
+    let loser = engine.storage.get_memory(a).await.unwrap().unwrap();
+    assert!(loser.is_deleted());
+    let winner = engine.storage.get_memory(b).await.unwrap().unwrap();
+    assert!(!winner.is_deleted());
+}
+
+#[tokio::test]
+async fn dismiss_leaves_both_memories() {
+    let engine = create_engine();
+    let (record, a, b) = queued_pair(&engine).await;
+
+    let resolved = engine
+        .resolve_queued_conflict(ResolveQueuedConflictRequest::new(
+            record.id,
+            ConflictDecision::Dismiss,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resolved.status, ConflictStatus::Dismissed);
+    for id in [a, b] {
+        let memory = engine.storage.get_memory(id).await.unwrap().unwrap();
+        assert!(!memory.is_deleted());
+    }
+
+    // Closed entries cannot be resolved again.
+    let err = engine
+        .resolve_queued_conflict(ResolveQueuedConflictRequest::new(
+            record.id,
+            ConflictDecision::Strategy(ResolutionStrategy::KeepNewest),
+        ))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Validation(_)));
+}
+
+#[tokio::test]
+async fn strategy_decision_runs_resolution() {
+    let engine = create_engine();
+    let (record, a, _) = queued_pair(&engine).await;
+
+    let resolved = engine
+        .resolve_queued_conflict(ResolveQueuedConflictRequest::new(
+            record.id,
+            ConflictDecision::Strategy(ResolutionStrategy::KeepNewest),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resolved.resolution.as_deref(), Some("keep_newest"));
+    let older = engine.storage.get_memory(a).await.unwrap().unwrap();
+    assert!(older.is_deleted());
+}
+
+#[tokio::test]
+async fn winner_outside_pair_is_rejected() {
+    let engine = create_engine();
+    let (record, _, _) = queued_pair(&engine).await;
+
+    let err = engine
+        .resolve_queued_conflict(ResolveQueuedConflictRequest::new(
+            record.id,
+            ConflictDecision::Winner(Uuid::now_v7()),
+        ))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Validation(_)));
+
+    let err = engine
+        .resolve_queued_conflict(ResolveQueuedConflictRequest::new(
+            Uuid::now_v7(),
+            ConflictDecision::Dismiss,
+        ))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::NotFound(_)));
+}
@@ -0,0 +1,116 @@
+//! Integration tests for compressed memory content.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+fn create_engine(storage: DuckDbStorage) -> MnemoEngine {
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(
+        Arc::new(storage),
+        index,
+        embedding,
+        "archivist".to_string(),
+        None,
+    )
+}
+
+fn runbook() -> String {
+    "step: drain the node, restart the ingest workers, verify the lag. ".repeat(100)
+}
+
+async fn remember(engine: &MnemoEngine, content: &str) -> Uuid {
+    engine
+        .remember(RememberRequest::new(content.to_string()))
+        .await
+        .unwrap()
+        .id
+}
+
+#[tokio::test]
+async fn large_content_is_compressed_transparently() {
+    let engine = create_engine(DuckDbStorage::open_in_memory().unwrap());
+    let large = remember(&engine, &runbook()).await;
+    remember(&engine, "a short note").await;
+
+    let record = engine.storage.get_memory(large).await.unwrap().unwrap();
+    assert_eq!(record.content, runbook());
+    let hit = &engine
+        .recall(RecallRequest::new("restart the ingest workers".to_string()))
+        .await
+        .unwrap()
+        .memories[0];
+    assert_eq!(hit.content, runbook());
+
+    let stats = engine.content_storage_stats(None).await.unwrap();
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].agent_id, "archivist");
+    assert_eq!(stats[0].memories, 2);
+    assert_eq!(stats[0].compressed, 1);
+    assert_eq!(
+        stats[0].content_bytes,
+        (runbook().len() + "a short note".len()) as u64
+    );
+    assert!(stats[0].stored_bytes < stats[0].content_bytes);
+    assert_eq!(
+        stats[0].saved_bytes,
+        stats[0].content_bytes - stats[0].stored_bytes
+    );
+}
+
+#[tokio::test]
+async fn existing_rows_are_compressed_when_next_updated() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("compression.duckdb");
+    let open = |path: &Path| DuckDbStorage::open(path).unwrap();
+
+    let id = {
+        let engine = create_engine(open(&path).with_compression_threshold(None));
+        remember(&engine, &runbook()).await
+    };
+
+    let engine = create_engine(open(&path));
+    let stats = engine.content_storage_stats(None).await.unwrap();
+    assert_eq!(stats[0].compressed, 0);
+    assert_eq!(stats[0].saved_bytes, 0);
+
+    let mut record = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert_eq!(record.content, runbook());
+    record.importance = 0.9;
+    engine.storage.update_memory(&record).await.unwrap();
+
+    let stats = engine.content_storage_stats(None).await.unwrap();
+    assert_eq!(stats[0].compressed, 1);
+    assert!(stats[0].saved_bytes > 0);
+    let record = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert_eq!(record.content, runbook());
+}
+
+#[tokio::test]
+async fn stats_are_reported_per_agent() {
+    let engine = create_engine(DuckDbStorage::open_in_memory().unwrap());
+    remember(&engine, &runbook()).await;
+    let mut other = RememberRequest::new("the pager rotates weekly".to_string());
+    other.agent_id = Some("oncall".to_string());
+    engine.remember(other).await.unwrap();
+
+    let stats = engine.content_storage_stats(None).await.unwrap();
+    let agents: Vec<_> = stats.iter().map(|s| s.agent_id.as_str()).collect();
+    assert_eq!(agents, ["archivist", "oncall"]);
+
+    let stats = engine
+        .content_storage_stats(Some("oncall".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].compressed, 0);
+    assert_eq!(stats[0].stored_bytes, stats[0].content_bytes);
+}
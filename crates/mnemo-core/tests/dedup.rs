@@ -0,0 +1,119 @@
+//! Integration tests for remember-time near-duplicate detection.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::relation::DUPLICATE_OF;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::dedup::{DedupAction, DedupPolicy};
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+const ORIGINAL: &str = "the deploy window is tuesday afternoon";
+// Same tokens, different order: identical bag-of-words embedding.
+const PARAPHRASE: &str = "tuesday afternoon is the deploy window";
+
+fn create_engine(action: DedupAction) -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "dedup-agent".to_string(), None)
+        .with_dedup_policy(DedupPolicy::new(0.95, action))
+}
+
+fn request(content: &str, importance: f32, tags: &[&str]) -> RememberRequest {
+    let mut request = RememberRequest::new(content.to_string());
+    request.importance = Some(importance);
+    request.tags = Some(tags.iter().map(|t| t.to_string()).collect());
+    request
+}
+
+#[tokio::test]
+async fn merge_folds_importance_and_tags_into_existing_memory() {
+    let engine = create_engine(DedupAction::Merge);
+    let first = engine
+        .remember(request(ORIGINAL, 0.4, &["ops"]))
+        .await
+        .unwrap();
+    assert!(first.duplicate.is_none());
+
+    let second = engine
+        .remember(request(PARAPHRASE, 0.9, &["deploy"]))
+        .await
+        .unwrap();
+    assert_eq!(second.id, first.id);
+    assert_eq!(second.content_hash, first.content_hash);
+    let found = second.duplicate.unwrap();
+    assert_eq!(found.memory_id, first.id);
+    assert_eq!(found.action, DedupAction::Merge);
+    assert!(found.similarity >= 0.95);
+
+    let merged = engine.storage.get_memory(first.id).await.unwrap().unwrap();
+    assert_eq!(merged.importance, 0.9);
+    assert_eq!(merged.tags, ["ops", "deploy"]);
+    assert_eq!(merged.access_count, 1);
+    assert_eq!(engine.index.len(), 1);
+}
+
+#[tokio::test]
+async fn skip_returns_existing_memory_untouched() {
+    let engine = create_engine(DedupAction::Skip);
+    let first = engine
+        .remember(request(ORIGINAL, 0.4, &["ops"]))
+        .await
+        .unwrap();
+    let second = engine
+        .remember(request(PARAPHRASE, 0.9, &["deploy"]))
+        .await
+        .unwrap();
+    assert_eq!(second.id, first.id);
+    assert_eq!(second.duplicate.unwrap().action, DedupAction::Skip);
+
+    let existing = engine.storage.get_memory(first.id).await.unwrap().unwrap();
+    assert_eq!(existing.importance, 0.4);
+    assert_eq!(existing.tags, ["ops"]);
+}
+
+#[tokio::test]
+async fn link_stores_new_memory_with_duplicate_of_relation() {
+    let engine = create_engine(DedupAction::Link);
+    let first = engine.remember(request(ORIGINAL, 0.5, &[])).await.unwrap();
+    let second = engine
+        .remember(request(PARAPHRASE, 0.5, &[]))
+        .await
+        .unwrap();
+    assert_ne!(second.id, first.id);
+    assert_eq!(second.duplicate.unwrap().memory_id, first.id);
+
+    let relations = engine.storage.get_relations_from(second.id).await.unwrap();
+    assert_eq!(relations.len(), 1);
+    assert_eq!(relations[0].relation_type, DUPLICATE_OF);
+    assert_eq!(relations[0].target_id, first.id);
+}
+
+#[tokio::test]
+async fn other_agents_threads_and_distinct_content_are_not_duplicates() {
+    let engine = create_engine(DedupAction::Merge);
+    let first = engine.remember(request(ORIGINAL, 0.5, &[])).await.unwrap();
+
+    let mut other_agent = request(PARAPHRASE, 0.5, &[]);
+    other_agent.agent_id = Some("someone-else".to_string());
+    let mut other_thread = request(PARAPHRASE, 0.5, &[]);
+    other_thread.thread_id = Some("t-2".to_string());
+    let unrelated = request("lunch is served at noon in the cafeteria", 0.5, &[]);
+
+    for request in [other_agent, other_thread, unrelated] {
+        let response = engine.remember(request).await.unwrap();
+        assert_ne!(response.id, first.id);
+        assert!(response.duplicate.is_none());
+    }
+    assert_eq!(engine.index.len(), 4);
+}
+
+#[test]
+fn policy_rejects_out_of_range_threshold() {
+    assert!(DedupPolicy::default().validate().is_ok());
+    assert!(DedupPolicy::new(0.0, DedupAction::Skip).validate().is_err());
+    assert!(DedupPolicy::new(1.5, DedupAction::Skip).validate().is_err());
+}
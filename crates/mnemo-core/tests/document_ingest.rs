@@ -0,0 +1,100 @@
+//! Integration tests for document ingestion.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::ingest::{DOCUMENT_TAG, DocumentInput, IngestRequest};
+use mnemo_core::model::memory::{MemoryType, SourceType};
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::storage::MemoryFilter;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine() -> MnemoEngine {
+    MnemoEngine::new(
+        Arc::new(DuckDbStorage::open_in_memory().unwrap()),
+        Arc::new(UsearchIndex::new(64).unwrap()),
+        Arc::new(DeterministicEmbedding::new(64)),
+        "librarian".to_string(),
+        None,
+    )
+}
+
+const RUNBOOK: &str = "# Runbook\n\nOn-call owns production.\n\n## Deploys\n\nRun make deploy from the main branch.\n\n## Rollback\n\nRevert the merge and redeploy.\n";
+
+const SOURCE: &str =
+    "use std::io;\n\n/// Rotates the signing key.\npub fn rotate_key() {}\n\npub struct Keyring;\n";
+
+#[tokio::test]
+async fn ingest_remembers_chunks_with_provenance() {
+    let engine = create_engine();
+    let mut request = IngestRequest::new(vec![
+        DocumentInput::from_bytes("docs/runbook.md", RUNBOOK.as_bytes().to_vec()),
+        DocumentInput::from_bytes("src/keys.rs", SOURCE.as_bytes().to_vec()),
+    ]);
+    request.tags = Some(vec!["handbook".to_string()]);
+    let response = engine.ingest_documents(request).await.unwrap();
+
+    assert_eq!(response.documents.len(), 2);
+    let runbook = &response.documents[0];
+    assert_eq!(runbook.format, "markdown");
+    assert_eq!(runbook.title.as_deref(), Some("Runbook"));
+    assert_eq!(runbook.memory_ids.len(), 3);
+    let code = &response.documents[1];
+    assert_eq!(code.format, "code");
+    assert_eq!(code.memory_ids.len(), 3);
+    assert_eq!(response.memories, 6);
+
+    let deploys = engine
+        .storage
+        .get_memory(runbook.memory_ids[1])
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(deploys.content.contains("make deploy"));
+    assert_eq!(deploys.memory_type, MemoryType::Semantic);
+    assert_eq!(deploys.source_type, SourceType::Import);
+    assert_eq!(deploys.source_id.as_deref(), Some("docs/runbook.md"));
+    assert_eq!(deploys.tags, vec![DOCUMENT_TAG, "handbook"]);
+    assert_eq!(deploys.metadata["document"]["section"], "Runbook > Deploys");
+    assert_eq!(deploys.metadata["document"]["chunk"], 1);
+    assert_eq!(deploys.metadata["document"]["chunks"], 3);
+
+    let rotate = engine
+        .storage
+        .get_memory(code.memory_ids[1])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(rotate.metadata["document"]["symbol"], "fn rotate_key");
+    assert_eq!(
+        rotate.metadata["document"]["lines"],
+        serde_json::json!([3, 5])
+    );
+
+    let mut recall = RecallRequest::new("Revert the merge and redeploy.".to_string());
+    recall.strategy = Some("semantic".to_string());
+    let recalled = engine.recall(recall).await.unwrap();
+    assert_eq!(recalled.memories[0].id, runbook.memory_ids[2]);
+}
+
+#[tokio::test]
+async fn ingest_writes_nothing_when_a_document_cannot_be_loaded() {
+    let engine = create_engine();
+    let mut unknown = DocumentInput::from_bytes("notes.docx", b"binary".to_vec());
+    unknown.format = Some("docx".to_string());
+    let request = IngestRequest::new(vec![
+        DocumentInput::from_bytes("docs/runbook.md", RUNBOOK.as_bytes().to_vec()),
+        unknown,
+    ]);
+
+    let err = engine.ingest_documents(request).await.unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "documents[1].format");
+    let stored = engine
+        .storage
+        .list_memories(&MemoryFilter::default(), 10, 0)
+        .await
+        .unwrap();
+    assert!(stored.is_empty());
+}
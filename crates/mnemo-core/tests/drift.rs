@@ -0,0 +1,87 @@
+//! Integration tests for drift detection against a ground-truth corpus.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::drift::{self, DriftKind, DriftRequest};
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+const CORPUS: &str = r#"
+{"id": "deploy-policy", "content": "deploys are allowed on friday", "source": "runbook.md"}
+{"id": "rate-limit", "content": "the api rate limit is 200 requests per minute"}
+{"id": "backups", "content": "backups run every night at two", "updated_at": "2000-01-01T00:00:00Z"}
+
+{"content": "oncall rotation changes every monday"}
+{"id": "staging", "content": "the staging database lives in frankfurt"}
+"#;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "server".to_string(), None)
+}
+
+async fn remember(engine: &MnemoEngine, content: &str) -> Uuid {
+    let mut request = RememberRequest::new(content.to_string());
+    request.agent_id = Some("ops-bot".to_string());
+    engine.remember(request).await.unwrap().id
+}
+
+#[tokio::test]
+async fn reports_stale_and_contradicting_memories() {
+    let engine = create_engine();
+    let contradicting = remember(&engine, "deploys are not allowed on friday").await;
+    let stale = remember(&engine, "the api rate limit is 100 requests per minute").await;
+    // The corpus entry is older than this memory, so it is not stale.
+    remember(&engine, "backups run every night at three").await;
+    remember(&engine, "the staging database lives in frankfurt").await;
+    remember(&engine, "the cafeteria serves tacos on tuesday").await;
+
+    let mut request = DriftRequest::new(drift::parse_corpus(CORPUS).unwrap());
+    request.agent_id = Some("ops-bot".to_string());
+    let report = engine.detect_drift(request).await.unwrap();
+
+    assert_eq!(report.facts, 5);
+    assert_eq!(report.memories_checked, 5);
+    assert_eq!(report.consistent, 2);
+    assert_eq!(report.unmatched, 1);
+    assert_eq!(report.uncovered_facts, ["line-6"]);
+    assert_eq!(report.stale(), 1);
+    assert_eq!(report.contradicting(), 1);
+
+    let found = |id| report.findings.iter().find(|f| f.memory_id == id).unwrap();
+    let finding = found(contradicting);
+    assert_eq!(finding.kind, DriftKind::Contradicting);
+    assert_eq!(finding.fact_id, "deploy-policy");
+    assert_eq!(finding.fact_source.as_deref(), Some("runbook.md"));
+    let finding = found(stale);
+    assert_eq!(finding.kind, DriftKind::Stale);
+    assert_eq!(finding.fact_id, "rate-limit");
+    assert!(finding.similarity < 0.95);
+}
+
+#[tokio::test]
+async fn rejects_malformed_corpora_and_thresholds() {
+    let err = drift::parse_corpus("{\"id\": \"a\"}\nnot json").unwrap_err();
+    assert!(err.to_string().contains("corpus line 1"), "{err}");
+
+    let engine = create_engine();
+    let mut facts = drift::parse_corpus(CORPUS).unwrap();
+    facts[0].updated_at = Some("last tuesday".to_string());
+    let err = engine
+        .detect_drift(DriftRequest::new(facts))
+        .await
+        .unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "facts[0].updated_at");
+
+    let mut request = DriftRequest::new(drift::parse_corpus(CORPUS).unwrap());
+    request.match_threshold = Some(0.9);
+    request.consistent_threshold = Some(0.8);
+    let err = engine.detect_drift(request).await.unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "consistent_threshold");
+}
@@ -0,0 +1,163 @@
+//! Integration tests for dry runs of destructive operations.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::event::EventType;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::forget::{
+    ForgetCriteria, ForgetRequest, ForgetStrategy, ForgetSubjectRequest,
+};
+use mnemo_core::query::lifecycle::CleanupRequest;
+use mnemo_core::query::pin::PinRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "agent".to_string(), None)
+        .with_trash_retention_days(0)
+}
+
+async fn remember(engine: &MnemoEngine, content: &str, tags: &[&str]) -> Uuid {
+    let mut request = RememberRequest::new(content.to_string());
+    request.tags = Some(tags.iter().map(|t| t.to_string()).collect());
+    engine.remember(request).await.unwrap().id
+}
+
+async fn is_live(engine: &MnemoEngine, id: Uuid) -> bool {
+    engine
+        .storage
+        .get_memory(id)
+        .await
+        .unwrap()
+        .is_some_and(|m| m.deleted_at.is_none())
+}
+
+async fn delete_events(engine: &MnemoEngine) -> usize {
+    engine
+        .storage
+        .list_events("agent", 100, 0)
+        .await
+        .unwrap()
+        .iter()
+        .filter(|e| e.event_type == EventType::MemoryDelete)
+        .count()
+}
+
+#[tokio::test]
+async fn forget_dry_run_reports_matches_and_changes_nothing() {
+    let engine = create_engine();
+    let a = remember(&engine, "old note one", &["old"]).await;
+    let b = remember(&engine, "old note two", &["old"]).await;
+    let pinned = remember(&engine, "old but pinned", &["old"]).await;
+    engine.pin(PinRequest::new(pinned, true)).await.unwrap();
+
+    let mut request = ForgetRequest::new(vec![]);
+    request.strategy = Some(ForgetStrategy::HardDelete);
+    request.criteria = Some(ForgetCriteria {
+        max_age_hours: None,
+        min_importance_below: None,
+        memory_type: None,
+        tags: Some(vec!["old".to_string()]),
+    });
+    request.dry_run = Some(true);
+    let preview = engine.forget(request.clone()).await.unwrap();
+    assert!(preview.dry_run);
+    let mut forgotten = preview.forgotten.clone();
+    forgotten.sort();
+    let mut expected = vec![a, b];
+    expected.sort();
+    assert_eq!(forgotten, expected);
+    for id in [a, b, pinned] {
+        assert!(is_live(&engine, id).await);
+    }
+    assert_eq!(delete_events(&engine).await, 0);
+
+    // Listed ids get the same checks as a real run.
+    let mut by_id = ForgetRequest::new(vec![a, pinned]);
+    by_id.dry_run = Some(true);
+    let preview = engine.forget(by_id).await.unwrap();
+    assert_eq!(preview.forgotten, [a]);
+    assert_eq!(preview.errors[0].id, pinned);
+
+    request.dry_run = None;
+    let response = engine.forget(request).await.unwrap();
+    assert!(!response.dry_run);
+    assert_eq!(response.forgotten.len(), 2);
+    assert!(!is_live(&engine, a).await);
+    assert_eq!(delete_events(&engine).await, 2);
+}
+
+#[tokio::test]
+async fn dry_runs_are_allowed_while_read_only() {
+    let engine = create_engine();
+    let id = remember(&engine, "frozen note", &[]).await;
+    engine.set_read_only(true);
+
+    let mut request = ForgetRequest::new(vec![id]);
+    request.dry_run = Some(true);
+    assert_eq!(
+        engine.forget(request.clone()).await.unwrap().forgotten,
+        [id]
+    );
+    request.dry_run = None;
+    assert!(engine.forget(request).await.is_err());
+}
+
+#[tokio::test]
+async fn forget_subject_dry_run_leaves_the_subject_intact() {
+    let engine = create_engine();
+    let id = remember(&engine, "user u1 likes tea", &["subject:u1"]).await;
+    remember(&engine, "unrelated", &[]).await;
+
+    let response = engine
+        .forget_subject(ForgetSubjectRequest {
+            subject_id: "u1".to_string(),
+            agent_id: None,
+            strategy: ForgetStrategy::Redact,
+            dry_run: Some(true),
+        })
+        .await
+        .unwrap();
+    assert!(response.dry_run);
+    assert_eq!(response.matched, 1);
+    assert_eq!(response.forgotten, [id]);
+    let record = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert_eq!(record.content, "user u1 likes tea");
+}
+
+#[tokio::test]
+async fn cleanup_dry_run_lists_what_the_pass_would_delete() {
+    let engine = create_engine();
+    let expired = remember(&engine, "expired note", &[]).await;
+    let mut record = engine.storage.get_memory(expired).await.unwrap().unwrap();
+    record.expires_at = Some("2020-01-01T00:00:00+00:00".to_string());
+    engine.storage.update_memory(&record).await.unwrap();
+    let trashed = remember(&engine, "trashed note", &[]).await;
+    engine
+        .forget(ForgetRequest::new(vec![trashed]))
+        .await
+        .unwrap();
+
+    let mut request = CleanupRequest::new();
+    request.dry_run = Some(true);
+    let preview = engine.run_cleanup_with(request).await.unwrap();
+    assert!(preview.dry_run);
+    assert_eq!(preview.ttl.swept_count, 1);
+    assert_eq!(preview.ttl.memory_ids, [expired]);
+    assert_eq!(preview.trash.memory_ids, [trashed]);
+    assert!(engine.storage.get_memory(expired).await.unwrap().is_some());
+    assert!(engine.storage.get_memory(trashed).await.unwrap().is_some());
+
+    let result = engine.run_cleanup().await.unwrap();
+    assert!(!result.dry_run);
+    assert_eq!(result.ttl.memory_ids, preview.ttl.memory_ids);
+    assert_eq!(result.trash.memory_ids, preview.trash.memory_ids);
+    assert!(engine.storage.get_memory(expired).await.unwrap().is_none());
+    assert!(engine.storage.get_memory(trashed).await.unwrap().is_none());
+}
@@ -0,0 +1,77 @@
+//! Integration tests for the embedded `Mnemo` facade.
+
+use std::sync::Arc;
+
+use mnemo_core::Mnemo;
+use mnemo_core::config::{EmbeddingConfig, MnemoEngineConfig};
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::query::MnemoEngine;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn in_memory_defaults_round_trip() {
+    let mnemo = Mnemo::in_memory().await.unwrap();
+    assert_eq!(mnemo.agent_id(), "default");
+
+    let id = mnemo
+        .remember("the deploy window is tuesday afternoon")
+        .await
+        .unwrap();
+    mnemo.remember("lunch is at noon").await.unwrap();
+
+    // Noop embeddings: the facade falls back to lexical recall.
+    let hits = mnemo.recall("deploy window", 5).await.unwrap();
+    assert_eq!(hits[0].id, id);
+
+    assert!(mnemo.forget(id).await.unwrap());
+    assert!(!mnemo.forget(Uuid::now_v7()).await.unwrap());
+    let hits = mnemo.recall("deploy window", 5).await.unwrap();
+    assert!(hits.iter().all(|m| m.id != id));
+}
+
+#[tokio::test]
+async fn file_backed_store_survives_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = dir.path().join("agent.db");
+    let open = || {
+        Mnemo::builder()
+            .path(&db_path)
+            .agent_id("planner")
+            .dimensions(64)
+            .embedding(EmbeddingConfig::Deterministic)
+            .build()
+    };
+
+    let mnemo = open().await.unwrap();
+    let id = mnemo
+        .remember("the staging database resets every sunday")
+        .await
+        .unwrap();
+    mnemo.flush().await.unwrap();
+    drop(mnemo);
+    assert!(db_path.with_extension("usearch").exists());
+
+    let mnemo = open().await.unwrap();
+    assert_eq!(mnemo.agent_id(), "planner");
+    let hits = mnemo.recall("staging database resets", 3).await.unwrap();
+    assert_eq!(hits[0].id, id);
+}
+
+#[tokio::test]
+async fn custom_provider_sets_dimensions() {
+    let mnemo = Mnemo::builder()
+        .embedding_provider(Arc::new(DeterministicEmbedding::new(32)))
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(mnemo.engine().embedding.dimensions(), 32);
+    let id = mnemo.remember("vectors are 32 wide").await.unwrap();
+    assert_eq!(mnemo.recall("32 wide", 1).await.unwrap()[0].id, id);
+
+    let config = MnemoEngineConfig::default();
+    let result =
+        MnemoEngine::from_config_with_embedding(&config, Arc::new(DeterministicEmbedding::new(32)))
+            .await;
+    assert!(matches!(result, Err(Error::Validation(_))));
+}
@@ -0,0 +1,105 @@
+//! Integration tests for batched remember and re-embedding through the
+//! embedding pipeline.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use async_trait::async_trait;
+use mnemo_core::embedding::pipeline::{EmbeddingPipelineConfig, PipelinedEmbedding};
+use mnemo_core::embedding::{DeterministicEmbedding, EmbeddingProvider};
+use mnemo_core::error::Result as MnemoResult;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::reembed::ReembedRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+/// Deterministic embeddings that count `embed_batch` calls, which the
+/// pipeline makes for single texts too.
+#[derive(Default)]
+struct Counting {
+    batches: AtomicUsize,
+}
+
+#[async_trait]
+impl EmbeddingProvider for Counting {
+    async fn embed(&self, text: &str) -> MnemoResult<Vec<f32>> {
+        DeterministicEmbedding::new(64).embed(text).await
+    }
+    async fn embed_batch(&self, texts: &[&str]) -> MnemoResult<Vec<Vec<f32>>> {
+        self.batches.fetch_add(1, Ordering::SeqCst);
+        DeterministicEmbedding::new(64).embed_batch(texts).await
+    }
+    fn dimensions(&self) -> usize {
+        64
+    }
+}
+
+fn create_engine() -> (MnemoEngine, Arc<Counting>) {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let counting = Arc::new(Counting::default());
+    let embedding = PipelinedEmbedding::new(
+        counting.clone(),
+        EmbeddingPipelineConfig {
+            concurrency: 2,
+            chunk_size: 3,
+            ..Default::default()
+        },
+    );
+    let engine = MnemoEngine::new(
+        storage,
+        index,
+        Arc::new(embedding),
+        "importer".to_string(),
+        None,
+    );
+    (engine, counting)
+}
+
+fn requests(n: usize) -> Vec<RememberRequest> {
+    (0..n)
+        .map(|i| RememberRequest::new(format!("imported note number {i} about topic{i}")))
+        .collect()
+}
+
+#[tokio::test]
+async fn remember_batch_embeds_in_chunks_and_stores_in_order() {
+    let (engine, counting) = create_engine();
+
+    let responses = engine.remember_batch(requests(10)).await.unwrap();
+    assert_eq!(responses.len(), 10);
+    assert_eq!(counting.batches.load(Ordering::SeqCst), 4);
+
+    let mut request = RecallRequest::new("imported note number 7 about topic7".to_string());
+    request.strategy = Some("semantic".to_string());
+    let recalled = engine.recall(request).await.unwrap();
+    assert_eq!(recalled.memories[0].id, responses[7].id);
+}
+
+#[tokio::test]
+async fn remember_batch_rejects_an_empty_entry_before_embedding() {
+    let (engine, counting) = create_engine();
+    let mut batch = requests(3);
+    batch[1].content = "  ".to_string();
+
+    let err = engine.remember_batch(batch).await.unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "memories[1].content");
+    assert_eq!(counting.batches.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn reembed_replaces_every_embedding() {
+    let (engine, counting) = create_engine();
+    for request in requests(5) {
+        engine.remember(request).await.unwrap();
+    }
+    assert_eq!(counting.batches.load(Ordering::SeqCst), 5);
+
+    let response = engine.reembed(ReembedRequest::default()).await.unwrap();
+    assert_eq!(response.agent_id, "importer");
+    assert_eq!(response.reembedded, 5);
+    assert_eq!(response.skipped, 0);
+    assert_eq!(counting.batches.load(Ordering::SeqCst), 7);
+}
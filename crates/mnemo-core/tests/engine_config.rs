@@ -0,0 +1,214 @@
+//! Integration tests for `MnemoEngineConfig` / `MnemoEngine::from_config`.
+
+use mnemo_core::config::{
+    EmbeddingConfig, IndexConfig, MnemoEngineConfig, ShardingConfig, StorageConfig,
+};
+use mnemo_core::error::Error;
+use mnemo_core::index::ivf_pq::IvfPqConfig;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+
+#[test]
+fn deserializes_partial_config_with_defaults() {
+    let config: MnemoEngineConfig = serde_json::from_value(serde_json::json!({
+        "agent_id": "cfg-agent",
+        "dimensions": 64,
+        "embedding": { "provider": "deterministic" },
+        "cache": { "ttl_seconds": 60, "max_entries": 100 },
+    }))
+    .unwrap();
+    assert_eq!(config.agent_id, "cfg-agent");
+    assert_eq!(config.dimensions, 64);
+    assert_eq!(config.storage, StorageConfig::Memory);
+    assert_eq!(config.embedding, EmbeddingConfig::Deterministic);
+    assert!(config.full_text.enabled);
+    assert!(config.index.wal);
+
+    let unknown = serde_json::from_value::<MnemoEngineConfig>(serde_json::json!({
+        "agent": "typo",
+    }));
+    assert!(unknown.is_err());
+}
+
+#[test]
+fn duckdb_layout_derives_sibling_paths() {
+    let config = MnemoEngineConfig::duckdb(std::path::Path::new("/data/mnemo.db"));
+    assert_eq!(
+        config.index.path.as_deref(),
+        Some(std::path::Path::new("/data/mnemo.usearch"))
+    );
+    assert_eq!(
+        config.index.wal_path().as_deref(),
+        Some(std::path::Path::new("/data/mnemo.usearch.wal"))
+    );
+    assert_eq!(
+        config.full_text.path.as_deref(),
+        Some(std::path::Path::new("/data/mnemo.tantivy"))
+    );
+}
+
+#[test]
+fn validate_rejects_bad_values() {
+    let config = MnemoEngineConfig {
+        dimensions: 0,
+        ..Default::default()
+    };
+    assert!(matches!(config.validate(), Err(Error::Validation(_))));
+
+    let mut config = MnemoEngineConfig::default();
+    config.features.procedural_importance_floor = 1.5;
+    assert!(matches!(config.validate(), Err(Error::Validation(_))));
+}
+
+#[tokio::test]
+async fn from_config_builds_a_working_engine() {
+    let config = MnemoEngineConfig {
+        agent_id: "cfg-agent".to_string(),
+        dimensions: 64,
+        embedding: EmbeddingConfig::Deterministic,
+        ..Default::default()
+    };
+    let engine = MnemoEngine::from_config(&config).await.unwrap();
+    assert!(engine.full_text.is_some());
+
+    engine
+        .remember(RememberRequest::new(
+            "The deploy pipeline runs on Fridays".to_string(),
+        ))
+        .await
+        .unwrap();
+    let recalled = engine
+        .recall(RecallRequest::new("deploy pipeline".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(recalled.memories.len(), 1);
+}
+
+#[tokio::test]
+async fn from_config_replays_the_index_wal() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = MnemoEngineConfig {
+        dimensions: 64,
+        embedding: EmbeddingConfig::Deterministic,
+        index: IndexConfig {
+            path: Some(dir.path().join("mnemo.usearch")),
+            wal: true,
+            shards: None,
+            ivf_pq: None,
+        },
+        ..Default::default()
+    };
+    {
+        let engine = MnemoEngine::from_config(&config).await.unwrap();
+        engine.index.add(uuid::Uuid::now_v7(), &[0.5; 64]).unwrap();
+        // Dropped without saving a snapshot.
+    }
+    let engine = MnemoEngine::from_config(&config).await.unwrap();
+    assert_eq!(engine.index.len(), 1);
+}
+
+#[tokio::test]
+async fn sharded_index_routes_writes_and_recall_by_agent() {
+    let dir = tempfile::tempdir().unwrap();
+    let shard_dir = dir.path().join("shards");
+    let config = MnemoEngineConfig {
+        dimensions: 64,
+        embedding: EmbeddingConfig::Deterministic,
+        index: IndexConfig {
+            path: Some(shard_dir.clone()),
+            shards: Some(ShardingConfig {
+                max_loaded_shards: Some(1),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let engine = MnemoEngine::from_config(&config).await.unwrap();
+    let mut ids = Vec::new();
+    for agent in ["alpha", "beta"] {
+        let mut request = RememberRequest::new(format!("{agent} owns the deploy pipeline"));
+        request.agent_id = Some(agent.to_string());
+        ids.push(engine.remember(request).await.unwrap().id);
+    }
+
+    let mut request = RecallRequest::new("deploy pipeline".to_string());
+    request.agent_id = Some("alpha".to_string());
+    request.strategy = Some("semantic".to_string());
+    let recalled = engine.recall(request).await.unwrap();
+    assert_eq!(recalled.memories.len(), 1);
+    assert_eq!(recalled.memories[0].id, ids[0]);
+
+    engine.index.save(&shard_dir).unwrap();
+    assert!(shard_dir.join("routes.json").exists());
+    drop(engine);
+    let engine = MnemoEngine::from_config(&config).await.unwrap();
+    assert_eq!(engine.index.len(), 2);
+
+    let mut bad = config.clone();
+    bad.index.shards = Some(ShardingConfig {
+        max_loaded_shards: Some(0),
+        ..Default::default()
+    });
+    assert!(matches!(bad.validate(), Err(Error::Validation(_))));
+}
+
+#[tokio::test]
+async fn ivf_pq_index_is_selected_by_config_and_reopened() {
+    let dir = tempfile::tempdir().unwrap();
+    let index_dir = dir.path().join("ivf");
+    let config = MnemoEngineConfig {
+        dimensions: 64,
+        embedding: EmbeddingConfig::Deterministic,
+        index: IndexConfig {
+            path: Some(index_dir.clone()),
+            ivf_pq: Some(IvfPqConfig {
+                nlist: 2,
+                nprobe: 2,
+                subvectors: 8,
+                train_size: 4,
+                kmeans_iterations: 2,
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let engine = MnemoEngine::from_config(&config).await.unwrap();
+    let mut ids = Vec::new();
+    for topic in [
+        "deploy pipeline",
+        "billing export",
+        "oncall rota",
+        "vpn access",
+        "office wifi",
+    ] {
+        ids.push(
+            engine
+                .remember(RememberRequest::new(topic.to_string()))
+                .await
+                .unwrap()
+                .id,
+        );
+    }
+
+    let mut request = RecallRequest::new("deploy pipeline".to_string());
+    request.strategy = Some("semantic".to_string());
+    let recalled = engine.recall(request).await.unwrap();
+    assert_eq!(recalled.memories[0].id, ids[0]);
+
+    engine.index.save(&index_dir).unwrap();
+    drop(engine);
+    let engine = MnemoEngine::from_config(&config).await.unwrap();
+    assert_eq!(engine.index.len(), 5);
+
+    let mut bad = config.clone();
+    bad.index.shards = Some(ShardingConfig::default());
+    assert!(matches!(bad.validate(), Err(Error::Validation(_))));
+    let mut bad = config.clone();
+    bad.index.ivf_pq = Some(IvfPqConfig {
+        subvectors: 7,
+        ..Default::default()
+    });
+    assert!(matches!(bad.validate(), Err(Error::Validation(_))));
+}
@@ -0,0 +1,93 @@
+//! Integration tests for entity summaries.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::entity_summary::{ENTITY_SUMMARY_TAG, EntitySummaryRequest};
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "entity-agent".to_string(), None)
+}
+
+async fn remember(engine: &MnemoEngine, content: &str) {
+    engine
+        .remember(RememberRequest::new(content.to_string()))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn summaries_are_created_then_updated_incrementally() {
+    let engine = create_engine();
+    remember(&engine, "Alice owns the billing service.").await;
+    remember(&engine, "The Alice rotation starts on Mondays.").await;
+    remember(&engine, "Bob reviewed the billing migration.").await;
+    let mut tagged = RememberRequest::new("she prefers async code reviews".to_string());
+    tagged.metadata = Some(serde_json::json!({"entities": ["alice"]}));
+    engine.remember(tagged).await.unwrap();
+
+    let report = engine
+        .run_entity_summaries(EntitySummaryRequest::default())
+        .await
+        .unwrap();
+    assert_eq!(report.summaries_created, 1);
+    let first = engine.entity_summary(None, "ALICE").await.unwrap();
+    assert_eq!(first.entity, "Alice");
+    assert_eq!(first.source_ids.len(), 3);
+    assert!(first.content.starts_with("Alice: "));
+    assert!(matches!(
+        engine.entity_summary(None, "Bob").await,
+        Err(Error::NotFound(_))
+    ));
+
+    let again = engine
+        .run_entity_summaries(EntitySummaryRequest::default())
+        .await
+        .unwrap();
+    assert_eq!((again.summaries_created, again.unchanged), (0, 1));
+
+    remember(&engine, "Alice moved to the platform team.").await;
+    let update = engine
+        .run_entity_summaries(EntitySummaryRequest::default())
+        .await
+        .unwrap();
+    assert_eq!(update.summaries_updated, 1);
+    let second = engine.entity_summary(None, "alice").await.unwrap();
+    assert_ne!(second.memory_id, first.memory_id);
+    assert_eq!(second.source_ids.len(), 4);
+    assert!(second.content.contains("platform team"));
+
+    let old = engine
+        .storage
+        .get_memory(first.memory_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(old.is_deleted());
+    let new = engine
+        .storage
+        .get_memory(second.memory_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(new.tags.iter().any(|t| t == ENTITY_SUMMARY_TAG));
+}
+
+#[tokio::test]
+async fn min_mentions_must_be_positive() {
+    let engine = create_engine();
+    let request = EntitySummaryRequest {
+        min_mentions: 0,
+        ..Default::default()
+    };
+    let err = engine.run_entity_summaries(request).await.unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "min_mentions");
+}
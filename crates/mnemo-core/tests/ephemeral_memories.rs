@@ -0,0 +1,89 @@
+//! Integration tests for ephemeral scratchpad memories.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::memory::{ConsolidationState, MemoryType};
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::consolidate::ConsolidateRequest;
+use mnemo_core::query::ephemeral::{
+    DEFAULT_EPHEMERAL_TTL_SECONDS, EPHEMERAL_TAG, EphemeralRequest,
+};
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "planner".to_string(), None)
+}
+
+#[tokio::test]
+async fn ephemeral_memories_are_short_lived_and_unembedded() {
+    let engine = create_engine();
+    let mut request = EphemeralRequest::new("tool output: 3 open incidents".to_string());
+    request.tags = Some(vec!["scratch".to_string()]);
+    let id = engine.remember_ephemeral(request).await.unwrap().id;
+
+    let record = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert_eq!(record.memory_type, MemoryType::Working);
+    assert!(record.embedding.is_none());
+    assert_eq!(record.tags, ["scratch", EPHEMERAL_TAG]);
+    let created = chrono::DateTime::parse_from_rfc3339(&record.created_at).unwrap();
+    let expires =
+        chrono::DateTime::parse_from_rfc3339(record.expires_at.as_deref().unwrap()).unwrap();
+    assert_eq!(
+        (expires - created).num_seconds(),
+        DEFAULT_EPHEMERAL_TTL_SECONDS as i64
+    );
+    assert!(engine.index.is_empty());
+
+    // Exact recall finds it without an embedding.
+    let mut recall = RecallRequest::new("open incidents".to_string());
+    recall.strategy = Some("exact".to_string());
+    recall.tags = Some(vec![EPHEMERAL_TAG.to_string()]);
+    let response = engine.recall(recall).await.unwrap();
+    assert_eq!(response.memories[0].id, id);
+}
+
+#[tokio::test]
+async fn consolidation_skips_ephemeral_memories() {
+    let engine = create_engine();
+    let mut kept = Vec::new();
+    for content in ["draft plan: check logs", "draft plan: page on-call"] {
+        let mut request = EphemeralRequest::new(content.to_string());
+        request.memory_type = Some(MemoryType::Episodic);
+        request.tags = Some(vec!["plan".to_string()]);
+        kept.push(engine.remember_ephemeral(request).await.unwrap().id);
+    }
+    let result = engine.run_consolidation(None, 2).await.unwrap();
+    assert_eq!(result.clusters_found, 0);
+    for id in &kept {
+        let record = engine.storage.get_memory(*id).await.unwrap().unwrap();
+        assert_eq!(record.consolidation_state, ConsolidationState::Raw);
+    }
+
+    let mut durable = RememberRequest::new("the on-call rota is weekly".to_string());
+    durable.tags = Some(vec!["plan".to_string()]);
+    let durable = engine.remember(durable).await.unwrap().id;
+    let err = engine
+        .consolidate(ConsolidateRequest::new(
+            vec![durable, kept[0]],
+            "plan".to_string(),
+        ))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("ephemeral"));
+}
+
+#[tokio::test]
+async fn zero_ttl_is_rejected() {
+    let engine = create_engine();
+    let mut request = EphemeralRequest::new("scratch".to_string());
+    request.ttl_seconds = Some(0);
+    let err = engine.remember_ephemeral(request).await.unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "ttl_seconds");
+}
@@ -0,0 +1,105 @@
+//! Integration tests for event retention and archival to cold storage.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::hash::{compute_chain_hash, compute_content_hash};
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::event::{AgentEvent, EventType};
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::event_retention::EventRetentionPolicy;
+use mnemo_core::storage::cold::{ColdStorageConfig, InMemoryColdStorage};
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    let cold = InMemoryColdStorage::new(ColdStorageConfig {
+        bucket: "memory".to_string(),
+        prefix: "memories".to_string(),
+        endpoint: None,
+        region: "local".to_string(),
+    });
+    MnemoEngine::new(storage, index, embedding, "agent".to_string(), None)
+        .with_cold_storage(Arc::new(cold))
+        .with_event_retention(EventRetentionPolicy {
+            hot_days: 30,
+            ..EventRetentionPolicy::default()
+        })
+}
+
+/// A chained event for `agent_id` stamped `days_ago` days in the past.
+async fn insert_event(
+    engine: &MnemoEngine,
+    agent_id: &str,
+    days_ago: i64,
+    clock: i64,
+    prev: Option<&AgentEvent>,
+) -> AgentEvent {
+    let timestamp = (chrono::Utc::now() - chrono::Duration::days(days_ago)).to_rfc3339();
+    let payload = serde_json::json!({"step": clock});
+    let content_hash = compute_content_hash(&payload.to_string(), agent_id, &timestamp);
+    let mut event = AgentEvent::new(
+        agent_id.to_string(),
+        EventType::ToolCall,
+        payload,
+        timestamp,
+        content_hash,
+    );
+    event.logical_clock = clock;
+    event.thread_id = Some("t1".to_string());
+    event.prev_hash = prev.map(|p| compute_chain_hash(&event.content_hash, Some(&p.content_hash)));
+    engine.storage.insert_event(&event).await.unwrap();
+    event
+}
+
+#[tokio::test]
+async fn old_events_move_to_cold_storage_and_restore_verified() {
+    let engine = create_engine();
+    let first = insert_event(&engine, "agent", 60, 1, None).await;
+    let second = insert_event(&engine, "agent", 45, 2, Some(&first)).await;
+    let recent = insert_event(&engine, "agent", 1, 3, Some(&second)).await;
+    insert_event(&engine, "other", 40, 1, None).await;
+
+    let result = engine.run_cleanup().await.unwrap();
+    assert_eq!(result.events_archived, 3);
+
+    let hot = engine.storage.list_events("agent", 10, 0).await.unwrap();
+    assert_eq!(hot.len(), 1);
+    assert_eq!(hot[0].id, recent.id);
+
+    let segments = engine
+        .list_event_archives(Some("agent".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(segments.len(), 1);
+    let segment = &segments[0];
+    assert_eq!(segment.event_count, 2);
+    assert_eq!(segment.first_event_id, first.id);
+    assert_eq!(segment.last_event_id, second.id);
+    assert_eq!(segment.last_content_hash, hex::encode(&second.content_hash));
+    assert_eq!(engine.list_event_archives(None).await.unwrap().len(), 2);
+
+    let restored = engine.restore_event_archive(segment.id).await.unwrap();
+    assert!(restored.merkle_root_matches);
+    assert!(restored.chain.valid);
+    assert_eq!(restored.events.len(), 2);
+    assert_eq!(restored.events[0].id, first.id);
+    assert_eq!(restored.events[0].payload, first.payload);
+    assert_eq!(restored.events[1].prev_hash, second.prev_hash);
+
+    // Nothing left past the window.
+    let report = engine.archive_events().await.unwrap();
+    assert_eq!(report.events_archived, 0);
+}
+
+#[tokio::test]
+async fn restoring_an_unknown_segment_is_not_found() {
+    let engine = create_engine();
+    let err = engine
+        .restore_event_archive(uuid::Uuid::now_v7())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, mnemo_core::error::Error::NotFound(_)));
+}
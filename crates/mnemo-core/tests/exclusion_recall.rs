@@ -0,0 +1,72 @@
+//! Integration tests for recall with exclusion queries and tags.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "deployer".to_string(), None)
+}
+
+async fn remember(engine: &MnemoEngine, content: &str, tag: &str) -> Uuid {
+    let mut request = RememberRequest::new(content.to_string());
+    request.tags = Some(vec![tag.to_string()]);
+    engine.remember(request).await.unwrap().id
+}
+
+fn deployment_query() -> RecallRequest {
+    let mut request = RecallRequest::new("deployment finished".to_string());
+    request.strategy = Some("semantic".to_string());
+    request
+}
+
+#[tokio::test]
+async fn exclude_query_ranks_similar_memories_lower() {
+    let engine = create_engine();
+    let staging = remember(&engine, "deployment to staging finished", "staging").await;
+    let production = remember(&engine, "deployment to production finished", "prod").await;
+
+    let plain = engine.recall(deployment_query()).await.unwrap();
+    let plain_staging = plain.memories.iter().find(|m| m.id == staging).unwrap();
+    let plain_production = plain.memories.iter().find(|m| m.id == production).unwrap();
+    assert!((plain_staging.score - plain_production.score).abs() < 1e-4);
+
+    let mut request = deployment_query();
+    request.exclude_query = Some("staging".to_string());
+    let excluded = engine.recall(request).await.unwrap();
+    assert_eq!(excluded.memories[0].id, production);
+    let penalized = excluded.memories.iter().find(|m| m.id == staging).unwrap();
+    assert!(penalized.score < plain_staging.score * 0.75);
+    assert!((excluded.memories[0].score - plain_production.score).abs() < 1e-4);
+}
+
+#[tokio::test]
+async fn exclude_tags_drop_matching_memories() {
+    let engine = create_engine();
+    remember(&engine, "deployment to staging finished", "staging").await;
+    let production = remember(&engine, "deployment to production finished", "prod").await;
+
+    for strategy in ["semantic", "exact"] {
+        let mut request = deployment_query();
+        request.strategy = Some(strategy.to_string());
+        request.exclude_tags = Some(vec!["staging".to_string(), "qa".to_string()]);
+        let ids: Vec<Uuid> = engine
+            .recall(request)
+            .await
+            .unwrap()
+            .memories
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        assert_eq!(ids, [production], "{strategy}");
+    }
+}
@@ -0,0 +1,116 @@
+//! Integration tests for fact validity windows on memories.
+
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::query::validity::SetValidityRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+const FACT: &str = "the user's home city";
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(
+        storage,
+        index,
+        embedding,
+        "validity-agent".to_string(),
+        None,
+    )
+}
+
+async fn remember(
+    engine: &MnemoEngine,
+    tag: &str,
+    from: Option<String>,
+    until: Option<String>,
+) -> Uuid {
+    let mut request = RememberRequest::new(FACT.to_string());
+    request.tags = Some(vec![tag.to_string()]);
+    request.valid_from = from;
+    request.valid_until = until;
+    engine.remember(request).await.unwrap().id
+}
+
+async fn recall_tags(engine: &MnemoEngine, as_of: Option<String>) -> Vec<String> {
+    let mut request = RecallRequest::new(FACT.to_string());
+    request.strategy = Some("semantic".to_string());
+    request.as_of = as_of;
+    engine
+        .recall(request)
+        .await
+        .unwrap()
+        .memories
+        .into_iter()
+        .map(|m| m.tags[0].clone())
+        .collect()
+}
+
+#[tokio::test]
+async fn recall_prefers_facts_that_hold_now() {
+    let engine = create_engine();
+    let moved = (Utc::now() - Duration::days(30)).to_rfc3339();
+    remember(&engine, "berlin", None, Some(moved.clone())).await;
+    let lisbon = remember(&engine, "lisbon", Some(moved), None).await;
+
+    assert_eq!(recall_tags(&engine, None).await, ["lisbon", "berlin"]);
+
+    let stored = engine.storage.get_memory(lisbon).await.unwrap().unwrap();
+    assert!(stored.valid_from.is_some());
+    assert!(stored.valid_until.is_none());
+    assert!(stored.is_valid_at(Utc::now()));
+}
+
+#[tokio::test]
+async fn as_of_recall_keeps_only_facts_valid_at_that_time() {
+    let engine = create_engine();
+    let next_week = Utc::now() + Duration::days(7);
+    remember(&engine, "current", None, Some(next_week.to_rfc3339())).await;
+    remember(&engine, "planned", Some(next_week.to_rfc3339()), None).await;
+
+    let now = Utc::now().to_rfc3339();
+    assert_eq!(recall_tags(&engine, Some(now)).await, ["current"]);
+    let later = (next_week + Duration::days(1)).to_rfc3339();
+    assert_eq!(recall_tags(&engine, Some(later)).await, ["planned"]);
+}
+
+#[tokio::test]
+async fn set_validity_closes_a_fact() {
+    let engine = create_engine();
+    let id = remember(&engine, "berlin", None, None).await;
+
+    let mut request = SetValidityRequest::new(id);
+    request.valid_until = Some("2026-01-01T01:00:00+01:00".to_string());
+    let response = engine.set_validity(request).await.unwrap();
+    assert_eq!(
+        response.valid_until.as_deref(),
+        Some("2026-01-01T00:00:00+00:00")
+    );
+    let stored = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert_eq!(stored.valid_until, response.valid_until);
+}
+
+#[tokio::test]
+async fn invalid_windows_are_rejected() {
+    let engine = create_engine();
+
+    let mut request = RememberRequest::new(FACT.to_string());
+    request.valid_from = Some("2026-02-01T00:00:00Z".to_string());
+    request.valid_until = Some("2026-01-01T00:00:00Z".to_string());
+    let err = engine.remember(request).await.unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "valid_until");
+
+    let id = remember(&engine, "berlin", None, None).await;
+    let mut request = SetValidityRequest::new(id);
+    request.valid_from = Some("last spring".to_string());
+    let err = engine.set_validity(request).await.unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "valid_from");
+}
@@ -0,0 +1,147 @@
+//! Integration tests for recall field projections and batch gets.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::memory::Scope;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::get_batch::GetBatchRequest;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::MemoryColumns;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "writer".to_string(), None)
+}
+
+async fn remember(engine: &MnemoEngine, content: &str) -> Uuid {
+    let mut request = RememberRequest::new(content.to_string());
+    request.metadata = Some(serde_json::json!({"source": "runbook"}));
+    request.tags = Some(vec!["ops".to_string()]);
+    engine.remember(request).await.unwrap().id
+}
+
+fn fields(names: &[&str]) -> Option<Vec<String>> {
+    Some(names.iter().map(|n| n.to_string()).collect())
+}
+
+#[tokio::test]
+async fn recall_leaves_out_unrequested_content_and_metadata() {
+    let engine = create_engine();
+    let id = remember(&engine, "restart the ingest workers after a failover").await;
+
+    let mut request = RecallRequest::new("ingest failover".to_string());
+    request.fields = fields(&["id", "score"]);
+    let response = engine.recall(request).await.unwrap();
+    let hit = &response.memories[0];
+    assert_eq!(hit.id, id);
+    assert!(hit.content.is_empty());
+    assert!(hit.metadata.is_null());
+    // Small fields are always returned.
+    assert_eq!(hit.tags, ["ops"]);
+    let json = serde_json::to_value(hit).unwrap();
+    assert!(json.get("content").is_none());
+    assert!(json.get("metadata").is_none());
+    assert!(json.get("score").is_some());
+
+    let mut request = RecallRequest::new("ingest failover".to_string());
+    request.fields = fields(&["content"]);
+    let hit = &engine.recall(request).await.unwrap().memories[0];
+    assert_eq!(hit.content, "restart the ingest workers after a failover");
+    assert!(hit.metadata.is_null());
+
+    // Without a projection everything comes back.
+    let hit = &engine
+        .recall(RecallRequest::new("ingest failover".to_string()))
+        .await
+        .unwrap()
+        .memories[0];
+    assert_eq!(hit.metadata["source"], "runbook");
+}
+
+#[tokio::test]
+async fn projected_recall_still_feeds_steps_that_read_metadata() {
+    use mnemo_core::query::current_fact_resolver::CurrentFactResolverConfig;
+
+    let engine = create_engine();
+    let mut stale = RememberRequest::new("the primary region is us-east-1".to_string());
+    stale.metadata = Some(serde_json::json!({"fact_id": "primary-region"}));
+    engine.remember(stale).await.unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    let mut current = RememberRequest::new("the primary region is eu-west-1".to_string());
+    current.metadata = Some(serde_json::json!({"fact_id": "primary-region"}));
+    let current = engine.remember(current).await.unwrap().id;
+
+    // The resolver groups by metadata the caller did not ask for.
+    let mut request = RecallRequest::new("primary region".to_string());
+    request.fields = fields(&["id", "score"]);
+    request.current_fact_resolver = Some(CurrentFactResolverConfig::new("fact_id"));
+    let response = engine.recall(request).await.unwrap();
+    assert_eq!(response.memories.len(), 1);
+    assert_eq!(response.memories[0].id, current);
+    assert!(response.memories[0].metadata.is_null());
+    assert!(response.memories[0].content.is_empty());
+}
+
+#[tokio::test]
+async fn get_batch_returns_requested_memories_in_order() {
+    let engine = create_engine();
+    let first = remember(&engine, "the primary is in us-east-1").await;
+    let second = remember(&engine, "the replica is in eu-west-1").await;
+    let unknown = Uuid::now_v7();
+
+    let mut request = GetBatchRequest::new(vec![second, unknown, first, second]);
+    request.fields = fields(&["content"]);
+    let response = engine.get_batch(request).await.unwrap();
+    let ids: Vec<_> = response.memories.iter().map(|m| m.id).collect();
+    assert_eq!(ids, [second, first]);
+    assert_eq!(response.memories[0].content, "the replica is in eu-west-1");
+    assert!(response.memories[0].metadata.is_null());
+    assert_eq!(response.missing, [unknown]);
+
+    // Memories the caller could not recall are reported as missing.
+    let mut private = RememberRequest::new("the root password rotates monthly".to_string());
+    private.scope = Some(Scope::Private);
+    let hidden = engine.remember(private).await.unwrap().id;
+    let mut request = GetBatchRequest::new(vec![first, hidden]);
+    request.agent_id = Some("outsider".to_string());
+    let response = engine.get_batch(request).await.unwrap();
+    assert_eq!(response.missing, [first, hidden]);
+}
+
+#[tokio::test]
+async fn storage_reads_only_the_requested_columns() {
+    let engine = create_engine();
+    let id = remember(&engine, "page the on-call for sev1 incidents").await;
+
+    let columns = MemoryColumns {
+        content: false,
+        metadata: true,
+    };
+    let records = engine.storage.get_memories(&[id], columns).await.unwrap();
+    assert_eq!(records.len(), 1);
+    assert!(records[0].content.is_empty());
+    assert!(records[0].embedding.is_none());
+    assert_eq!(records[0].metadata["source"], "runbook");
+}
+
+#[tokio::test]
+async fn unknown_fields_are_rejected() {
+    let engine = create_engine();
+    let mut request = RecallRequest::new("anything".to_string());
+    request.fields = fields(&["id", "body"]);
+    let err = engine.recall(request).await.unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "fields");
+
+    let err = engine
+        .get_batch(GetBatchRequest::new(Vec::new()))
+        .await
+        .unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "ids");
+}
@@ -0,0 +1,142 @@
+//! Integration tests for curation of global-scope memories.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::acl::Permission;
+use mnemo_core::model::delegation::{Delegation, DelegationScope};
+use mnemo_core::model::memory::{ReviewStatus, Scope};
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::curation::{CurationPolicy, CurationReviewRequest};
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+const CURATOR: &str = "curator";
+const FACT: &str = "the staging database restarts every night";
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "server".to_string(), None)
+        .with_curation(CurationPolicy::default().with_curator(CURATOR))
+}
+
+async fn remember_global(engine: &MnemoEngine, agent_id: &str, tag: &str) -> Uuid {
+    let mut request = RememberRequest::new(FACT.to_string());
+    request.agent_id = Some(agent_id.to_string());
+    request.scope = Some(Scope::Global);
+    request.tags = Some(vec![tag.to_string()]);
+    engine.remember(request).await.unwrap().id
+}
+
+async fn recalled_by(engine: &MnemoEngine, agent_id: &str) -> Vec<Uuid> {
+    let mut request = RecallRequest::new(FACT.to_string());
+    request.agent_id = Some(agent_id.to_string());
+    request.strategy = Some("semantic".to_string());
+    engine
+        .recall(request)
+        .await
+        .unwrap()
+        .memories
+        .into_iter()
+        .map(|m| m.id)
+        .collect()
+}
+
+fn review_as(agent_id: &str, memory_id: Uuid, approve: bool) -> CurationReviewRequest {
+    let mut request = CurationReviewRequest::new(memory_id, approve);
+    request.agent_id = Some(agent_id.to_string());
+    request
+}
+
+#[tokio::test]
+async fn pending_global_writes_reach_others_only_after_approval() {
+    let engine = create_engine();
+    let id = remember_global(&engine, "writer", "ops").await;
+
+    let stored = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert_eq!(stored.curation, Some(ReviewStatus::Pending));
+    assert_eq!(recalled_by(&engine, "writer").await, [id]);
+    assert!(recalled_by(&engine, "reader").await.is_empty());
+
+    let pending = engine
+        .list_pending_curation(Some(CURATOR.to_string()), 10)
+        .await
+        .unwrap();
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].agent_id, "writer");
+    let none = engine
+        .list_pending_curation(Some("reader".to_string()), 10)
+        .await
+        .unwrap();
+    assert!(none.is_empty());
+
+    let err = engine
+        .review_curation(review_as("reader", id, true))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)), "{err}");
+
+    let mut approval = review_as(CURATOR, id, true);
+    approval.note = Some("matches the runbook".to_string());
+    let response = engine.review_curation(approval).await.unwrap();
+    assert_eq!(response.status, ReviewStatus::Approved);
+    assert_eq!(recalled_by(&engine, "reader").await, [id]);
+
+    let stored = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert_eq!(stored.agent_id, "writer");
+    assert_eq!(stored.metadata["curation"]["reviewed_by"], CURATOR);
+    assert_eq!(stored.metadata["curation"]["note"], "matches the runbook");
+}
+
+#[tokio::test]
+async fn curator_writes_are_approved_and_rejections_stay_private() {
+    let engine = create_engine();
+    let own = remember_global(&engine, CURATOR, "ops").await;
+    let stored = engine.storage.get_memory(own).await.unwrap().unwrap();
+    assert_eq!(stored.curation, Some(ReviewStatus::Approved));
+
+    let id = remember_global(&engine, "writer", "ops").await;
+    // The server's own agent acts as the admin curator.
+    let mut rejection = CurationReviewRequest::new(id, false);
+    rejection.note = Some("unverified".to_string());
+    engine.review_curation(rejection).await.unwrap();
+    assert_eq!(recalled_by(&engine, "reader").await, [own]);
+    assert!(recalled_by(&engine, "writer").await.contains(&id));
+}
+
+#[tokio::test]
+async fn delegated_curators_review_within_their_scope() {
+    let engine = create_engine();
+    let delegation = Delegation {
+        id: Uuid::now_v7(),
+        delegator_id: CURATOR.to_string(),
+        delegate_id: "deputy".to_string(),
+        permission: Permission::Admin,
+        scope: DelegationScope::ByTag(vec!["ops".to_string()]),
+        max_depth: 0,
+        current_depth: 0,
+        parent_delegation_id: None,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        expires_at: None,
+        revoked_at: None,
+    };
+    engine.storage.insert_delegation(&delegation).await.unwrap();
+
+    let ops = remember_global(&engine, "writer", "ops").await;
+    let billing = remember_global(&engine, "writer", "billing").await;
+    engine
+        .review_curation(review_as("deputy", ops, true))
+        .await
+        .unwrap();
+    let err = engine
+        .review_curation(review_as("deputy", billing, true))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)), "{err}");
+}
@@ -0,0 +1,116 @@
+//! Integration tests for memory graph export.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::relation::Relation;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::graph_export::{GraphExportRequest, GraphFormat};
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+fn create_engine() -> MnemoEngine {
+    MnemoEngine::new(
+        Arc::new(DuckDbStorage::open_in_memory().unwrap()),
+        Arc::new(UsearchIndex::new(64).unwrap()),
+        Arc::new(DeterministicEmbedding::new(64)),
+        "test-agent".to_string(),
+        None,
+    )
+}
+
+async fn remember(engine: &MnemoEngine, content: &str, thread_id: &str) -> Uuid {
+    let mut request = RememberRequest::new(content.to_string());
+    request.thread_id = Some(thread_id.to_string());
+    engine.remember(request).await.unwrap().id
+}
+
+async fn relate(engine: &MnemoEngine, source_id: Uuid, target_id: Uuid, kind: &str, weight: f32) {
+    engine
+        .storage
+        .insert_relation(&Relation {
+            id: Uuid::now_v7(),
+            source_id,
+            target_id,
+            relation_type: kind.to_string(),
+            weight,
+            metadata: serde_json::json!({}),
+            created_at: chrono::Utc::now().to_rfc3339(),
+        })
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn exports_a_thread_with_filtered_edges() {
+    let engine = create_engine();
+    let a = remember(&engine, "the deploy runs at noon", "t-1").await;
+    let b = remember(&engine, "deploys need an approval", "t-1").await;
+    let c = remember(&engine, "lunch is served at one", "t-2").await;
+    relate(&engine, a, b, "related_to", 0.9).await;
+    relate(&engine, b, a, "related_to", 0.1).await;
+    relate(&engine, a, c, "related_to", 0.9).await;
+
+    let request = GraphExportRequest {
+        thread_id: Some("t-1".to_string()),
+        min_weight: Some(0.5),
+        ..Default::default()
+    };
+    let graph = engine.export_graph(request).await.unwrap();
+    let mut nodes: Vec<Uuid> = graph.nodes.iter().map(|n| n.id).collect();
+    nodes.sort();
+    let mut expected = vec![a, b];
+    expected.sort();
+    assert_eq!(nodes, expected);
+    // The light edge and the edge into the other thread are left out.
+    assert_eq!(graph.edges.len(), 1);
+    assert_eq!((graph.edges[0].source, graph.edges[0].target), (a, b));
+    assert!(!graph.truncated);
+
+    let dot = graph.render(GraphFormat::Dot).unwrap();
+    assert!(dot.contains(&format!("\"{a}\" -> \"{b}\"")));
+    let graphml = graph.render(GraphFormat::GraphMl).unwrap();
+    assert_eq!(graphml.matches("<node ").count(), 2);
+}
+
+#[tokio::test]
+async fn caps_the_export_size() {
+    let engine = create_engine();
+    for i in 0..3 {
+        remember(&engine, &format!("note {i}"), "t-1").await;
+    }
+    let request = GraphExportRequest {
+        max_nodes: Some(2),
+        ..Default::default()
+    };
+    let graph = engine.export_graph(request).await.unwrap();
+    assert_eq!(graph.nodes.len(), 2);
+    assert!(graph.truncated);
+
+    let request = GraphExportRequest {
+        max_nodes: Some(0),
+        ..Default::default()
+    };
+    assert!(engine.export_graph(request).await.is_err());
+}
+
+#[tokio::test]
+async fn leaves_out_memories_recall_would_hide() {
+    let engine = create_engine();
+    let kept = remember(&engine, "the deploy runs at noon", "t-1").await;
+    let hidden = remember(&engine, "a note under review", "t-1").await;
+    relate(&engine, kept, hidden, "related_to", 0.9).await;
+    let mut record = engine.storage.get_memory(hidden).await.unwrap().unwrap();
+    record.quarantined = true;
+    engine.storage.update_memory(&record).await.unwrap();
+
+    let graph = engine
+        .export_graph(GraphExportRequest::default())
+        .await
+        .unwrap();
+    let nodes: Vec<Uuid> = graph.nodes.iter().map(|n| n.id).collect();
+    assert_eq!(nodes, vec![kept]);
+    assert!(graph.edges.is_empty());
+}
@@ -0,0 +1,80 @@
+//! Integration tests for ranking assertions with `HashEmbedding`.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::HashEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+const CLOSEST: &str = "the incident was caused by an expired certificate";
+const RELATED: &str = "certificates are renewed by a weekly cron job";
+const UNRELATED: &str = "the office plants are watered on mondays";
+const QUERY: &str = "why did the outage happen";
+
+fn create_engine(embedding: HashEmbedding) -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    MnemoEngine::new(
+        storage,
+        index,
+        Arc::new(embedding),
+        "agent".to_string(),
+        None,
+    )
+}
+
+async fn recall_order(engine: &MnemoEngine) -> Vec<String> {
+    let mut request = RecallRequest::new(QUERY.to_string());
+    request.strategy = Some("semantic".to_string());
+    engine
+        .recall(request)
+        .await
+        .unwrap()
+        .memories
+        .into_iter()
+        .map(|m| m.content)
+        .collect()
+}
+
+#[tokio::test]
+async fn recall_follows_the_pinned_geometry() {
+    // A query mostly about the incident and partly about renewals.
+    let hashes = HashEmbedding::new(64);
+    let closest = hashes.vector(CLOSEST);
+    let related = hashes.vector(RELATED);
+    let query = closest
+        .iter()
+        .zip(&related)
+        .map(|(c, r)| 0.8 * c + 0.5 * r)
+        .collect();
+    let engine = create_engine(HashEmbedding::new(64).with_vector(QUERY, query));
+
+    for content in [UNRELATED, RELATED, CLOSEST] {
+        engine
+            .remember(RememberRequest::new(content.to_string()))
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(recall_order(&engine).await, [CLOSEST, RELATED, UNRELATED]);
+}
+
+#[tokio::test]
+async fn exact_text_is_the_nearest_neighbour() {
+    let engine = create_engine(HashEmbedding::new(64));
+    for content in [CLOSEST, RELATED, UNRELATED] {
+        engine
+            .remember(RememberRequest::new(content.to_string()))
+            .await
+            .unwrap();
+    }
+
+    let mut request = RecallRequest::new(RELATED.to_string());
+    request.strategy = Some("semantic".to_string());
+    let recalled = engine.recall(request).await.unwrap();
+    assert_eq!(recalled.memories[0].content, RELATED);
+    assert!(recalled.memories[0].score > recalled.memories[1].score + 0.5);
+}
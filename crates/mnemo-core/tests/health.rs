@@ -0,0 +1,59 @@
+//! Integration tests for liveness / readiness probes.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::{DeterministicEmbedding, NoopEmbedding};
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::health::{HealthStatus, liveness};
+use mnemo_core::storage::cold::{ColdStorageConfig, InMemoryColdStorage};
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine(embedding: Arc<dyn mnemo_core::embedding::EmbeddingProvider>) -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(128).unwrap());
+    MnemoEngine::new(storage, index, embedding, "health-agent".to_string(), None)
+}
+
+#[test]
+fn liveness_has_no_checks() {
+    let report = liveness();
+    assert_eq!(report.status, HealthStatus::Ok);
+    assert!(report.ready);
+    assert!(report.checks.is_empty());
+}
+
+#[tokio::test]
+async fn readiness_probes_every_attached_dependency() {
+    let cold = Arc::new(InMemoryColdStorage::new(ColdStorageConfig {
+        bucket: "b".to_string(),
+        prefix: "p".to_string(),
+        endpoint: None,
+        region: "us-east-1".to_string(),
+    }));
+    let engine = create_engine(Arc::new(DeterministicEmbedding::new(128))).with_cold_storage(cold);
+
+    let report = engine.readiness().await;
+    assert!(report.ready);
+    assert_eq!(report.status, HealthStatus::Ok);
+    let names: Vec<&str> = report.checks.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["storage", "vector_index", "embedding", "cold_storage"]
+    );
+}
+
+#[tokio::test]
+async fn noop_embedding_degrades_but_stays_ready() {
+    let engine = create_engine(Arc::new(NoopEmbedding::new(128)));
+
+    let report = engine.readiness().await;
+    assert!(report.ready);
+    assert_eq!(report.status, HealthStatus::Degraded);
+    let embedding = report
+        .checks
+        .iter()
+        .find(|c| c.name == "embedding")
+        .unwrap();
+    assert_eq!(embedding.status, HealthStatus::Degraded);
+}
@@ -0,0 +1,221 @@
+//! Integration tests for the remember / recall / forget hook pipeline.
+
+use std::sync::{Arc, Mutex};
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::{Error, Result};
+use mnemo_core::hooks::{Hook, HookErrorPolicy, HookOptions};
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::forget::ForgetRequest;
+use mnemo_core::query::recall::{RecallRequest, RecallResponse};
+use mnemo_core::query::remember::{RememberRequest, RememberResponse};
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "hook-agent".to_string(), None)
+}
+
+/// Appends its name to a shared trace at every stage it sees.
+struct Tracer {
+    name: &'static str,
+    trace: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl Hook for Tracer {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    async fn pre_remember(&self, request: &mut RememberRequest) -> Result<()> {
+        self.trace
+            .lock()
+            .unwrap()
+            .push(format!("pre:{}", self.name));
+        request
+            .tags
+            .get_or_insert_with(Vec::new)
+            .push(self.name.to_string());
+        Ok(())
+    }
+
+    async fn post_remember(
+        &self,
+        _request: &RememberRequest,
+        _response: &RememberResponse,
+    ) -> Result<()> {
+        self.trace
+            .lock()
+            .unwrap()
+            .push(format!("post:{}", self.name));
+        Ok(())
+    }
+}
+
+/// Rejects credential writes, drops `noise`-tagged hits and blocks bulk forgets.
+struct Policy;
+
+#[async_trait::async_trait]
+impl Hook for Policy {
+    fn name(&self) -> &str {
+        "policy"
+    }
+
+    async fn pre_remember(&self, request: &mut RememberRequest) -> Result<()> {
+        if request.content.contains("password") {
+            return Err(Error::Validation("credentials are not stored".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn post_recall(
+        &self,
+        _request: &RecallRequest,
+        response: &mut RecallResponse,
+    ) -> Result<()> {
+        response
+            .memories
+            .retain(|m| !m.tags.contains(&"noise".to_string()));
+        response.total = response.memories.len();
+        Ok(())
+    }
+
+    async fn pre_forget(&self, request: &mut ForgetRequest) -> Result<()> {
+        if request.memory_ids.len() > 1 {
+            return Err(Error::PermissionDenied(
+                "bulk forget is disabled".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+struct Broken;
+
+#[async_trait::async_trait]
+impl Hook for Broken {
+    fn name(&self) -> &str {
+        "broken"
+    }
+
+    async fn pre_remember(&self, _request: &mut RememberRequest) -> Result<()> {
+        Err(Error::Internal("hook backend unavailable".to_string()))
+    }
+}
+
+#[tokio::test]
+async fn hooks_run_in_priority_order_and_can_rewrite_requests() {
+    let trace = Arc::new(Mutex::new(Vec::new()));
+    let tracer = |name| {
+        Arc::new(Tracer {
+            name,
+            trace: trace.clone(),
+        })
+    };
+    let engine = create_engine()
+        .with_hook(tracer("late"), HookOptions::new(10, HookErrorPolicy::Abort))
+        .with_hook(
+            tracer("early"),
+            HookOptions::new(-5, HookErrorPolicy::Abort),
+        )
+        .with_hook(tracer("middle"), HookOptions::default());
+    assert_eq!(engine.hooks.names(), ["early", "middle", "late"]);
+
+    let id = engine
+        .remember(RememberRequest::new("the build runs nightly".to_string()))
+        .await
+        .unwrap()
+        .id;
+    assert_eq!(
+        *trace.lock().unwrap(),
+        [
+            "pre:early",
+            "pre:middle",
+            "pre:late",
+            "post:early",
+            "post:middle",
+            "post:late"
+        ]
+    );
+    let stored = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert_eq!(stored.tags, ["early", "middle", "late"]);
+}
+
+#[tokio::test]
+async fn abort_policy_rejects_and_post_recall_filters() {
+    let engine = create_engine().with_hook(Arc::new(Policy), HookOptions::default());
+
+    let err = engine
+        .remember(RememberRequest::new(
+            "the admin password is hunter2".to_string(),
+        ))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Validation(_)));
+
+    let mut noisy = RememberRequest::new("deploy checklist draft".to_string());
+    noisy.tags = Some(vec!["noise".to_string()]);
+    let a = engine.remember(noisy).await.unwrap().id;
+    let b = engine
+        .remember(RememberRequest::new("deploy checklist final".to_string()))
+        .await
+        .unwrap()
+        .id;
+
+    let recalled = engine
+        .recall(RecallRequest::new("deploy checklist".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(recalled.total, 1);
+    assert_eq!(recalled.memories[0].id, b);
+
+    let err = engine
+        .forget(ForgetRequest::new(vec![a, b]))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)));
+    assert!(engine.forget(ForgetRequest::new(vec![a])).await.is_ok());
+}
+
+#[tokio::test]
+async fn warn_policy_continues_past_failing_hooks() {
+    let engine =
+        create_engine().with_hook(Arc::new(Broken), HookOptions::new(0, HookErrorPolicy::Warn));
+    assert!(
+        engine
+            .remember(RememberRequest::new("still stored".to_string()))
+            .await
+            .is_ok()
+    );
+
+    let engine = create_engine().with_hook(Arc::new(Broken), HookOptions::default());
+    let err = engine
+        .remember(RememberRequest::new("not stored".to_string()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Internal(_)));
+}
+
+#[cfg(not(feature = "wasm-hooks"))]
+#[tokio::test]
+async fn wasm_hooks_need_the_feature() {
+    use mnemo_core::hooks::wasm::WasmHook;
+
+    assert!(matches!(
+        WasmHook::load("/nonexistent/hook.wasm"),
+        Err(Error::Validation(_))
+    ));
+
+    let file = tempfile::Builder::new().suffix(".wasm").tempfile().unwrap();
+    let hook = WasmHook::load(file.path()).unwrap();
+    let engine = create_engine().with_hook(Arc::new(hook), HookOptions::default());
+    let err = engine
+        .remember(RememberRequest::new("blocked".to_string()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Internal(_)));
+}
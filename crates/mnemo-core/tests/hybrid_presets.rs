@@ -0,0 +1,107 @@
+//! Integration tests for typed hybrid weights and their presets.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::hybrid::{HYBRID_PRESETS, HybridConfig};
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::search::tantivy_index::TantivyFullTextIndex;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use serde_json::json;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    let full_text = Arc::new(TantivyFullTextIndex::open_in_memory().unwrap());
+    MnemoEngine::new(storage, index, embedding, "hybrid-agent".to_string(), None)
+        .with_full_text(full_text)
+}
+
+#[test]
+fn presets_and_pairs_parse() {
+    for name in HYBRID_PRESETS {
+        assert_eq!(
+            name.parse::<HybridConfig>().unwrap(),
+            HybridConfig::preset(name).unwrap()
+        );
+    }
+    assert_eq!(
+        "balanced".parse::<HybridConfig>().unwrap(),
+        HybridConfig::default()
+    );
+
+    let config: HybridConfig = "vector:2, importance:0.5".parse().unwrap();
+    assert_eq!(config.vector, 2.0);
+    assert_eq!(config.importance, 0.5);
+    assert_eq!(config.bm25, 1.0);
+}
+
+#[test]
+fn json_accepts_a_preset_or_named_weights() {
+    let preset: HybridConfig = serde_json::from_value(json!("recency")).unwrap();
+    assert_eq!(preset, HybridConfig::preset("recency").unwrap());
+
+    let weights: HybridConfig = serde_json::from_value(json!({"bm25": 0.25})).unwrap();
+    assert_eq!(weights.bm25, 0.25);
+    assert_eq!(weights.vector, 1.0);
+
+    assert!(serde_json::from_value::<HybridConfig>(json!("fastest")).is_err());
+    assert!(serde_json::from_value::<HybridConfig>(json!({"bm52": 1.0})).is_err());
+    assert!(serde_json::from_value::<HybridConfig>(json!({"vector": -1.0})).is_err());
+}
+
+#[test]
+fn invalid_weights_name_the_offending_signal() {
+    let err = "vector:-1".parse::<HybridConfig>().unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "hybrid.vector");
+
+    for bad in ["fastest", "vector:x", "speed:1"] {
+        assert!(bad.parse::<HybridConfig>().unwrap_err().is_validation());
+    }
+    let all_zero = "vector:0,bm25:0,graph:0,recency:0".parse::<HybridConfig>();
+    assert!(all_zero.unwrap_err().is_validation());
+}
+
+#[tokio::test]
+async fn importance_weight_reorders_hybrid_recall() {
+    let engine = create_engine();
+    for (content, importance) in [
+        ("deploy checklist for the staging cluster", 0.1),
+        ("deploy checklist for the production cluster", 0.9),
+    ] {
+        let mut request = RememberRequest::new(content.to_string());
+        request.importance = Some(importance);
+        engine.remember(request).await.unwrap();
+    }
+
+    let recall = |hybrid: Option<HybridConfig>| {
+        let mut request = RecallRequest::new("deploy checklist".to_string());
+        request.strategy = Some("hybrid".to_string());
+        request.hybrid = hybrid;
+        engine.recall(request)
+    };
+
+    let unweighted = recall(None).await.unwrap();
+    let balanced = recall(HybridConfig::preset("balanced")).await.unwrap();
+    let ids = |memories: &[mnemo_core::query::recall::ScoredMemory]| {
+        memories.iter().map(|m| m.id).collect::<Vec<_>>()
+    };
+    assert_eq!(ids(&unweighted.memories), ids(&balanced.memories));
+
+    let by_importance = "vector:0.01,bm25:0.01,graph:0,recency:0,importance:5"
+        .parse::<HybridConfig>()
+        .unwrap();
+    let response = recall(Some(by_importance)).await.unwrap();
+    assert_eq!(response.memories[0].importance, 0.9);
+
+    let mut invalid = RecallRequest::new("deploy checklist".to_string());
+    invalid.hybrid = Some(HybridConfig {
+        recency: f32::NAN,
+        ..HybridConfig::default()
+    });
+    assert!(engine.recall(invalid).await.unwrap_err().is_validation());
+}
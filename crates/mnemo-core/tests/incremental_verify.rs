@@ -0,0 +1,127 @@
+//! Integration tests for paginated and incremental chain verification.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::query::verify::VerifyRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "agent".to_string(), None)
+}
+
+async fn remember(engine: &MnemoEngine, count: usize) -> Vec<uuid::Uuid> {
+    let mut ids = Vec::new();
+    for i in 0..count {
+        let mut request = RememberRequest::new(format!("step {i} of the rollout"));
+        request.thread_id = Some("t1".to_string());
+        ids.push(engine.remember(request).await.unwrap().id);
+    }
+    ids
+}
+
+fn request(incremental: bool, max_records: Option<usize>) -> VerifyRequest {
+    let mut request = VerifyRequest::new();
+    request.thread_id = Some("t1".to_string());
+    request.incremental = incremental;
+    request.max_records = max_records;
+    request
+}
+
+#[tokio::test]
+async fn incremental_runs_verify_only_new_records() {
+    let engine = create_engine();
+    remember(&engine, 5).await;
+
+    let full = engine
+        .verify_memory_chain(request(false, None))
+        .await
+        .unwrap();
+    assert!(full.result.valid && full.complete);
+    assert_eq!(full.result.total_records, 5);
+    assert_eq!(full.checkpoint.as_ref().unwrap().verified_count, 5);
+
+    let added = remember(&engine, 2).await;
+    let incremental = engine
+        .verify_memory_chain(request(true, None))
+        .await
+        .unwrap();
+    assert!(incremental.result.valid);
+    assert_eq!(incremental.result.total_records, 2);
+    let checkpoint = incremental.checkpoint.unwrap();
+    assert_eq!(checkpoint.verified_count, 7);
+    assert_eq!(checkpoint.last_record_id, added[1]);
+
+    // Nothing new: the checkpoint stands.
+    let idle = engine
+        .verify_memory_chain(request(true, None))
+        .await
+        .unwrap();
+    assert_eq!(idle.result.total_records, 0);
+    assert_eq!(idle.checkpoint.as_ref(), Some(&checkpoint));
+
+    // A full run rebuilds the same rolling root.
+    let rebuilt = engine
+        .verify_memory_chain(request(false, None))
+        .await
+        .unwrap();
+    assert_eq!(rebuilt.result.total_records, 7);
+    assert_eq!(
+        rebuilt.checkpoint.unwrap().rolling_root,
+        checkpoint.rolling_root
+    );
+}
+
+#[tokio::test]
+async fn max_records_pages_through_the_chain() {
+    let engine = create_engine();
+    remember(&engine, 5).await;
+
+    let mut seen = Vec::new();
+    loop {
+        let page = engine
+            .verify_memory_chain(request(true, Some(2)))
+            .await
+            .unwrap();
+        assert!(page.result.valid);
+        seen.push(page.result.total_records);
+        if page.complete {
+            assert_eq!(page.checkpoint.unwrap().verified_count, 5);
+            break;
+        }
+    }
+    assert_eq!(seen, [2, 2, 1]);
+}
+
+#[tokio::test]
+async fn incremental_run_catches_tampering_after_the_checkpoint() {
+    let engine = create_engine();
+    remember(&engine, 3).await;
+    engine
+        .verify_memory_chain(request(false, None))
+        .await
+        .unwrap();
+
+    let added = remember(&engine, 2).await;
+    let mut record = engine.storage.get_memory(added[0]).await.unwrap().unwrap();
+    record.content = "step 3 never happened".to_string();
+    engine.storage.update_memory(&record).await.unwrap();
+
+    let response = engine
+        .verify_memory_chain(request(true, None))
+        .await
+        .unwrap();
+    assert!(!response.result.valid);
+    assert_eq!(response.result.first_broken_at, Some(added[0]));
+    assert_eq!(response.checkpoint.unwrap().verified_count, 3);
+
+    // The old entry point now reads the whole chain in pages.
+    let result = engine.verify_integrity(None, Some("t1")).await.unwrap();
+    assert!(!result.valid);
+}
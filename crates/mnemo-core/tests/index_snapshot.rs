@@ -0,0 +1,95 @@
+//! Integration tests for bootstrapping a replica's vector index from a
+//! snapshot plus deltas.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::{DeterministicEmbedding, EmbeddingProvider};
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::forget::ForgetRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::StorageBackend;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine(storage: Arc<dyn StorageBackend>) -> MnemoEngine {
+    MnemoEngine::new(
+        storage,
+        Arc::new(UsearchIndex::new(64).unwrap()),
+        Arc::new(DeterministicEmbedding::new(64)),
+        "primary".to_string(),
+        None,
+    )
+}
+
+async fn remember(engine: &MnemoEngine, content: &str) -> uuid::Uuid {
+    engine
+        .remember(RememberRequest::new(content.to_string()))
+        .await
+        .unwrap()
+        .id
+}
+
+async fn nearest(engine: &MnemoEngine, content: &str) -> uuid::Uuid {
+    let query = DeterministicEmbedding::new(64)
+        .embed(content)
+        .await
+        .unwrap();
+    engine.index.search(&query, 1).unwrap()[0].0
+}
+
+#[tokio::test]
+async fn replica_restores_a_snapshot_and_catches_up_with_deltas() {
+    let storage: Arc<dyn StorageBackend> = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let primary = create_engine(storage.clone());
+    let kept = remember(&primary, "the staging cluster runs in eu-west-1").await;
+    let forgotten = remember(&primary, "the old vpn endpoint is vpn1.example.com").await;
+
+    let snapshot = primary.index_snapshot().unwrap();
+    assert_eq!(snapshot.manifest.vectors, 2);
+
+    let added = remember(&primary, "backups are copied to the cold bucket nightly").await;
+    primary
+        .forget(ForgetRequest::new(vec![forgotten]))
+        .await
+        .unwrap();
+
+    let replica = create_engine(storage);
+    replica.restore_index_snapshot(&snapshot).unwrap();
+    assert_eq!(replica.index.len(), 2);
+    assert_eq!(
+        nearest(&replica, "the staging cluster runs in eu-west-1").await,
+        kept
+    );
+
+    let mut since = snapshot.manifest.watermark.clone();
+    loop {
+        let delta = primary.index_delta(&since, 1).await.unwrap();
+        let Some(next) = delta.next.clone() else {
+            break;
+        };
+        delta.apply(replica.index.as_ref()).unwrap();
+        since = next;
+    }
+    assert_eq!(replica.index.len(), 2);
+    assert_eq!(
+        nearest(&replica, "backups are copied to the cold bucket nightly").await,
+        added
+    );
+}
+
+#[tokio::test]
+async fn restore_rejects_a_corrupted_snapshot() {
+    let storage: Arc<dyn StorageBackend> = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let primary = create_engine(storage.clone());
+    remember(&primary, "the staging cluster runs in eu-west-1").await;
+
+    let mut snapshot = primary.index_snapshot().unwrap();
+    let last = snapshot.payload.len() - 1;
+    snapshot.payload[last] ^= 0xff;
+
+    let replica = create_engine(storage);
+    let err = replica.restore_index_snapshot(&snapshot).unwrap_err();
+    assert!(matches!(err, Error::IndexCorruption(_)));
+    assert_eq!(replica.index.len(), 0);
+}
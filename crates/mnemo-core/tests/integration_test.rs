@@ -81,6 +81,10 @@ async fn test_full_lifecycle() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .expect("remember should succeed");
@@ -104,6 +108,7 @@ async fn test_full_lifecycle() {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: None,
             explain: None,
@@ -114,6 +119,22 @@ async fn test_full_lifecycle() {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         })
         .await
         .expect("recall should succeed");
@@ -134,6 +155,9 @@ async fn test_full_lifecycle() {
             target_agent_ids: None,
             permission: Some(Permission::Read),
             expires_in_hours: None,
+            memory_ids: None,
+            filter: None,
+            dry_run: None,
         })
         .await
         .expect("share should succeed");
@@ -149,6 +173,8 @@ async fn test_full_lifecycle() {
             agent_id: None,
             strategy: Some(ForgetStrategy::SoftDelete),
             criteria: None,
+            force: None,
+            dry_run: None,
         })
         .await
         .expect("forget should succeed");
@@ -172,6 +198,7 @@ async fn test_full_lifecycle() {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: None,
             explain: None,
@@ -182,6 +209,22 @@ async fn test_full_lifecycle() {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         })
         .await
         .expect("recall should succeed");
@@ -211,6 +254,10 @@ async fn test_multiple_memories_with_filtering() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -232,6 +279,10 @@ async fn test_multiple_memories_with_filtering() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -257,6 +308,10 @@ async fn test_multiple_memories_with_filtering() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -277,6 +332,7 @@ async fn test_multiple_memories_with_filtering() {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: None,
             explain: None,
@@ -287,6 +343,22 @@ async fn test_multiple_memories_with_filtering() {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         })
         .await
         .unwrap();
@@ -308,6 +380,7 @@ async fn test_multiple_memories_with_filtering() {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: None,
             explain: None,
@@ -318,6 +391,22 @@ async fn test_multiple_memories_with_filtering() {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         })
         .await
         .unwrap();
@@ -339,6 +428,7 @@ async fn test_multiple_memories_with_filtering() {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: None,
             explain: None,
@@ -349,6 +439,22 @@ async fn test_multiple_memories_with_filtering() {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         })
         .await
         .unwrap();
@@ -371,6 +477,7 @@ async fn test_multiple_memories_with_filtering() {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: None,
             explain: None,
@@ -381,6 +488,22 @@ async fn test_multiple_memories_with_filtering() {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         })
         .await
         .unwrap();
@@ -409,6 +532,10 @@ async fn test_hard_delete_is_permanent() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -420,6 +547,8 @@ async fn test_hard_delete_is_permanent() {
             agent_id: None,
             strategy: Some(ForgetStrategy::HardDelete),
             criteria: None,
+            force: None,
+            dry_run: None,
         })
         .await
         .unwrap();
@@ -450,6 +579,10 @@ async fn test_access_count_increments() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -471,6 +604,7 @@ async fn test_access_count_increments() {
                 temporal_range: None,
                 recency_half_life_hours: None,
                 hybrid_weights: None,
+                hybrid: None,
                 rrf_k: None,
                 as_of: None,
                 explain: None,
@@ -481,6 +615,22 @@ async fn test_access_count_increments() {
                 evidence_budget: None,
                 retained_token_budget: None,
                 domain_scope: None,
+                text_query_mode: None,
+                text_field_boosts: None,
+                with_snippets: None,
+                profile: None,
+                include: None,
+                thread_id: None,
+                thread_isolation: None,
+                exclude_query: None,
+                exclude_tags: None,
+                queries: None,
+                expand_query: None,
+                channel: None,
+                fields: None,
+                deadline_ms: None,
+                agent_ids: None,
+                min_relevance: None,
             })
             .await
             .unwrap();
@@ -513,6 +663,10 @@ async fn test_checkpoint_and_replay() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -552,6 +706,10 @@ async fn test_checkpoint_and_replay() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -610,6 +768,10 @@ async fn test_branch_and_merge() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -693,6 +855,10 @@ async fn test_event_recorded_on_remember() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -728,6 +894,10 @@ async fn test_related_to_creates_relations() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -750,6 +920,10 @@ async fn test_related_to_creates_relations() {
             related_to: Some(vec![m1.id.to_string()]),
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -782,6 +956,10 @@ async fn test_ttl_sets_expires_at() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -823,6 +1001,10 @@ async fn test_chain_linking_consecutive() {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             })
             .await
             .unwrap();
@@ -881,6 +1063,10 @@ async fn test_exact_recall_strategy() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -902,6 +1088,10 @@ async fn test_exact_recall_strategy() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -922,6 +1112,7 @@ async fn test_exact_recall_strategy() {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: None,
             explain: None,
@@ -932,6 +1123,22 @@ async fn test_exact_recall_strategy() {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         })
         .await
         .unwrap();
@@ -962,6 +1169,10 @@ async fn test_delegation_grants_access() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -1028,6 +1239,10 @@ async fn test_delegation_expired_no_access() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -1079,6 +1294,10 @@ async fn test_cleanup_expired_memories() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -1118,6 +1337,10 @@ async fn test_quarantined_excluded_from_recall() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -1139,6 +1362,10 @@ async fn test_quarantined_excluded_from_recall() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -1165,6 +1392,7 @@ async fn test_quarantined_excluded_from_recall() {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: None,
             explain: None,
@@ -1175,6 +1403,22 @@ async fn test_quarantined_excluded_from_recall() {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         })
         .await
         .unwrap();
@@ -1206,6 +1450,10 @@ async fn test_agent_profile_updated_on_remember() {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             })
             .await
             .unwrap();
@@ -1245,6 +1493,10 @@ async fn test_recall_scope_filter() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -1267,6 +1519,10 @@ async fn test_recall_scope_filter() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -1287,6 +1543,7 @@ async fn test_recall_scope_filter() {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: None,
             explain: None,
@@ -1297,6 +1554,22 @@ async fn test_recall_scope_filter() {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         })
         .await
         .unwrap();
@@ -1320,6 +1593,7 @@ async fn test_recall_scope_filter() {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: None,
             explain: None,
@@ -1330,6 +1604,22 @@ async fn test_recall_scope_filter() {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         })
         .await
         .unwrap();
@@ -1365,6 +1655,10 @@ async fn test_recall_multi_type_filter() {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             })
             .await
             .unwrap();
@@ -1386,6 +1680,7 @@ async fn test_recall_multi_type_filter() {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: None,
             explain: None,
@@ -1396,6 +1691,22 @@ async fn test_recall_multi_type_filter() {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         })
         .await
         .unwrap();
@@ -1428,6 +1739,10 @@ async fn test_share_multi_target() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -1445,6 +1760,9 @@ async fn test_share_multi_target() {
             ]),
             permission: Some(Permission::Read),
             expires_in_hours: None,
+            memory_ids: None,
+            filter: None,
+            dry_run: None,
         })
         .await
         .unwrap();
@@ -1477,6 +1795,10 @@ async fn test_share_expiration() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -1489,6 +1811,9 @@ async fn test_share_expiration() {
             target_agent_ids: None,
             permission: Some(Permission::Read),
             expires_in_hours: Some(24.0),
+            memory_ids: None,
+            filter: None,
+            dry_run: None,
         })
         .await
         .unwrap();
@@ -1536,6 +1861,7 @@ async fn test_trace_causality() {
         content_hash: vec![1, 2, 3],
         prev_hash: None,
         embedding: None,
+        request_id: None,
     };
     engine.storage.insert_event(&parent_event).await.unwrap();
 
@@ -1560,6 +1886,7 @@ async fn test_trace_causality() {
         content_hash: vec![4, 5, 6],
         prev_hash: None,
         embedding: None,
+        request_id: None,
     };
     engine.storage.insert_event(&child_event).await.unwrap();
 
@@ -1600,6 +1927,10 @@ async fn test_conflict_detection() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -1621,6 +1952,10 @@ async fn test_conflict_detection() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -1659,6 +1994,10 @@ async fn test_conflict_resolution_keep_newest() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -1680,6 +2019,10 @@ async fn test_conflict_resolution_keep_newest() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -1726,6 +2069,10 @@ async fn test_event_embedding_stored() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -1777,6 +2124,12 @@ async fn test_custom_decay_linear() {
         quarantined: false,
         quarantine_reason: None,
         decay_function: Some("linear".to_string()),
+        pinned: false,
+        valid_from: None,
+        valid_until: None,
+        curation: None,
+        attestation: None,
+        channel: None,
     };
 
     // Fresh memory with linear decay → should be close to base importance
@@ -1852,6 +2205,10 @@ async fn test_hybrid_with_graph_signal() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -1874,6 +2231,10 @@ async fn test_hybrid_with_graph_signal() {
             related_to: Some(vec![mem_a.id.to_string()]),
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -1894,6 +2255,7 @@ async fn test_hybrid_with_graph_signal() {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: None,
             explain: None,
@@ -1904,6 +2266,22 @@ async fn test_hybrid_with_graph_signal() {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         })
         .await
         .unwrap();
@@ -1941,6 +2319,10 @@ async fn test_sync_push_pull() {
             ttl_seconds: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -1962,6 +2344,10 @@ async fn test_sync_push_pull() {
             ttl_seconds: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -1988,6 +2374,7 @@ async fn test_sync_push_pull() {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: None,
             explain: None,
@@ -1998,6 +2385,22 @@ async fn test_sync_push_pull() {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         })
         .await
         .unwrap();
@@ -2021,6 +2424,10 @@ async fn test_sync_push_pull() {
             ttl_seconds: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -2056,6 +2463,10 @@ async fn test_sync_full_conflict_detection() {
             ttl_seconds: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -2098,6 +2509,8 @@ async fn test_sync_result_serialization() {
             memory_id: uuid::Uuid::now_v7(),
             local_updated_at: "2025-06-01T00:00:00Z".to_string(),
             remote_updated_at: "2025-06-01T01:00:00Z".to_string(),
+            local_region: None,
+            remote_region: None,
         }],
     };
 
@@ -2147,6 +2560,10 @@ async fn test_permission_safe_ann() {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             })
             .await
             .unwrap();
@@ -2178,6 +2595,10 @@ async fn test_permission_safe_ann() {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             })
             .await
             .unwrap();
@@ -2199,6 +2620,7 @@ async fn test_permission_safe_ann() {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: None,
             explain: None,
@@ -2209,6 +2631,22 @@ async fn test_permission_safe_ann() {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         })
         .await
         .unwrap();
@@ -2248,6 +2686,10 @@ async fn test_as_of_point_in_time() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -2278,6 +2720,10 @@ async fn test_as_of_point_in_time() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -2292,6 +2738,8 @@ async fn test_as_of_point_in_time() {
             agent_id: None,
             strategy: Some(ForgetStrategy::SoftDelete),
             criteria: None,
+            force: None,
+            dry_run: None,
         })
         .await
         .unwrap();
@@ -2315,6 +2763,7 @@ async fn test_as_of_point_in_time() {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: Some(t_between.clone()),
             explain: None,
@@ -2325,6 +2774,22 @@ async fn test_as_of_point_in_time() {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         })
         .await
         .unwrap();
@@ -2348,6 +2813,7 @@ async fn test_as_of_point_in_time() {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: Some(t_after_both.clone()),
             explain: None,
@@ -2358,6 +2824,22 @@ async fn test_as_of_point_in_time() {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         })
         .await
         .unwrap();
@@ -2383,6 +2865,7 @@ async fn test_as_of_point_in_time() {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: Some(t_after_delete.clone()),
             explain: None,
@@ -2393,6 +2876,22 @@ async fn test_as_of_point_in_time() {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         })
         .await
         .unwrap();
@@ -2423,6 +2922,10 @@ async fn test_event_integrity_verification() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -2442,6 +2945,7 @@ async fn test_event_integrity_verification() {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: None,
             explain: None,
@@ -2452,6 +2956,22 @@ async fn test_event_integrity_verification() {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         })
         .await
         .unwrap();
@@ -2491,6 +3011,10 @@ async fn test_evidence_weighted_resolution() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -2513,6 +3037,10 @@ async fn test_evidence_weighted_resolution() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -2621,6 +3149,10 @@ async fn test_ttl_sweep_deletes_expired_and_emits_events() {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             })
             .await
             .unwrap();
@@ -2685,6 +3217,10 @@ async fn test_forget_subject_redact_preserves_hash_chain() {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             })
             .await
             .unwrap();
@@ -2707,6 +3243,10 @@ async fn test_forget_subject_redact_preserves_hash_chain() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -2722,6 +3262,7 @@ async fn test_forget_subject_redact_preserves_hash_chain() {
             subject_id: "user-42".to_string(),
             agent_id: None,
             strategy: ForgetStrategy::Redact,
+            dry_run: None,
         })
         .await
         .unwrap();
@@ -2800,6 +3341,10 @@ async fn test_minja_style_poisoning_tpr_fpr_bars() {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             })
             .await
             .unwrap();
@@ -2927,6 +3472,10 @@ async fn test_replay_quarantine_ordering_and_cutoff() {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             })
             .await
             .unwrap();
@@ -2975,6 +3524,10 @@ async fn test_coordinated_skips_below_new_record_floor() {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             })
             .await
             .unwrap();
@@ -3016,6 +3569,10 @@ async fn test_always_mode_ignores_cadence() {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             })
             .await
             .unwrap();
@@ -3066,6 +3623,10 @@ async fn test_dream_report_ingestion_is_idempotent() {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             })
             .await
             .unwrap();
@@ -3158,6 +3719,12 @@ async fn test_reflection_absolutizes_relative_dates() {
         quarantined: false,
         quarantine_reason: None,
         decay_function: None,
+        pinned: false,
+        valid_from: None,
+        valid_until: None,
+        curation: None,
+        attestation: None,
+        channel: None,
     };
     engine.storage.insert_memory(&record).await.unwrap();
 
@@ -3218,6 +3785,12 @@ async fn test_reflection_consolidates_near_duplicates() {
         quarantined: false,
         quarantine_reason: None,
         decay_function: None,
+        pinned: false,
+        valid_from: None,
+        valid_until: None,
+        curation: None,
+        attestation: None,
+        channel: None,
     };
     let id_a = uuid::Uuid::now_v7();
     let id_b = uuid::Uuid::now_v7();
@@ -3280,6 +3853,12 @@ async fn test_reflection_accepts_auto_dream_rewrite() {
         quarantined: false,
         quarantine_reason: None,
         decay_function: None,
+        pinned: false,
+        valid_from: None,
+        valid_until: None,
+        curation: None,
+        attestation: None,
+        channel: None,
     };
     engine.storage.insert_memory(&record).await.unwrap();
 
@@ -3326,6 +3905,10 @@ async fn test_working_tier_gets_auto_ttl() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -3372,6 +3955,10 @@ async fn test_procedural_tier_applies_importance_floor() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -3412,6 +3999,10 @@ async fn test_semantic_tier_has_no_auto_ttl() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -3457,6 +4048,10 @@ async fn test_recall_explain_populates_score_breakdown() {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             })
             .await
             .unwrap();
@@ -3545,6 +4140,12 @@ async fn test_replay_as_of_returns_historical_state() {
             quarantined: false,
             quarantine_reason: None,
             decay_function: None,
+            pinned: false,
+            valid_from: None,
+            valid_until: None,
+            curation: None,
+            attestation: None,
+            channel: None,
         };
         engine.storage.insert_memory(&record).await.unwrap();
         ids_by_label.insert(label, id);
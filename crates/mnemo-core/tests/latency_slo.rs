@@ -0,0 +1,150 @@
+//! Integration tests for recall's latency SLO ladder and per-request
+//! deadlines.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use mnemo_core::embedding::{DeterministicEmbedding, EmbeddingProvider};
+use mnemo_core::error::Result as MnemoResult;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::latency_slo::{DegradationLevel, LatencySloConfig, RecallStage};
+use mnemo_core::query::recall::{RecallRequest, RecallResponse};
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::search::tantivy_index::TantivyFullTextIndex;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+const QUERY: &str = "certificate rotation";
+
+/// Deterministic embeddings that stall on [`QUERY`] while `slow` is set.
+struct SlowEmbedding {
+    inner: DeterministicEmbedding,
+    delay: Duration,
+    slow: AtomicBool,
+}
+
+#[async_trait]
+impl EmbeddingProvider for SlowEmbedding {
+    async fn embed(&self, text: &str) -> MnemoResult<Vec<f32>> {
+        if text == QUERY && self.slow.load(Ordering::Relaxed) {
+            tokio::time::sleep(self.delay).await;
+        }
+        self.inner.embed(text).await
+    }
+    async fn embed_batch(&self, texts: &[&str]) -> MnemoResult<Vec<Vec<f32>>> {
+        self.inner.embed_batch(texts).await
+    }
+    fn dimensions(&self) -> usize {
+        self.inner.dimensions()
+    }
+}
+
+async fn create_engine(delay: Duration) -> (MnemoEngine, Arc<SlowEmbedding>) {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(SlowEmbedding {
+        inner: DeterministicEmbedding::new(64),
+        delay,
+        slow: AtomicBool::new(true),
+    });
+    let full_text = Arc::new(TantivyFullTextIndex::open_in_memory().unwrap());
+    let engine = MnemoEngine::new(storage, index, embedding.clone(), "ops".to_string(), None)
+        .with_full_text(full_text);
+    for content in [
+        "certificate rotation runs every sunday night",
+        "the deploy pipeline pins the base image digest",
+    ] {
+        engine
+            .remember(RememberRequest::new(content.to_string()))
+            .await
+            .unwrap();
+    }
+    (engine, embedding)
+}
+
+async fn recall(engine: &MnemoEngine) -> RecallResponse {
+    let mut request = RecallRequest::new(QUERY.to_string());
+    request.strategy = Some("hybrid".to_string());
+    engine.recall(request).await.unwrap()
+}
+
+fn level(response: &RecallResponse) -> DegradationLevel {
+    response.degradation.as_ref().unwrap().level
+}
+
+#[tokio::test]
+async fn slow_stages_step_recall_down_the_ladder_and_back() {
+    let (engine, embedding) = create_engine(Duration::from_millis(40)).await;
+    let engine = engine.with_latency_slo(LatencySloConfig {
+        deadline_ms: 10_000,
+        embed_ms: 10,
+        retrieve_ms: 10_000,
+        graph_ms: 10_000,
+        rerank_ms: 10_000,
+        recover_after: 2,
+    });
+    let slo = engine.latency_slo.clone().unwrap();
+
+    // Each recall runs at the level its predecessors left behind.
+    for expected in [
+        DegradationLevel::None,
+        DegradationLevel::SkipRerank,
+        DegradationLevel::SkipGraph,
+        DegradationLevel::VectorOnly,
+    ] {
+        let response = recall(&engine).await;
+        assert_eq!(level(&response), expected);
+        assert!(!response.memories.is_empty());
+    }
+    assert_eq!(slo.level(), DegradationLevel::Bm25Only);
+
+    // BM25 alone skips the embedding and still finds the memory.
+    let response = recall(&engine).await;
+    let report = response.degradation.as_ref().unwrap();
+    assert_eq!(report.level, DegradationLevel::Bm25Only);
+    assert!(report.skipped.contains(&RecallStage::Embed));
+    assert!(
+        response.memories[0]
+            .content
+            .contains("certificate rotation")
+    );
+    assert_eq!(slo.level(), DegradationLevel::Bm25Only);
+
+    // A second recall within budget moves one rung back up.
+    embedding.slow.store(false, Ordering::Relaxed);
+    recall(&engine).await;
+    assert_eq!(slo.level(), DegradationLevel::VectorOnly);
+    recall(&engine).await;
+    recall(&engine).await;
+    assert_eq!(slo.level(), DegradationLevel::SkipGraph);
+    assert!(slo.degraded_recalls() >= 6);
+}
+
+#[tokio::test]
+async fn request_deadline_abandons_a_slow_embedding_for_bm25() {
+    let (engine, _embedding) = create_engine(Duration::from_secs(5)).await;
+
+    let mut request = RecallRequest::new(QUERY.to_string());
+    request.strategy = Some("hybrid".to_string());
+    request.deadline_ms = Some(50);
+    let response = engine.recall(request).await.unwrap();
+
+    let report = response.degradation.unwrap();
+    assert_eq!(report.level, DegradationLevel::Bm25Only);
+    assert_eq!(report.deadline_ms, Some(50));
+    assert!(report.skipped.contains(&RecallStage::Embed));
+    assert!(
+        response.memories[0]
+            .content
+            .contains("certificate rotation")
+    );
+}
+
+#[tokio::test]
+async fn recall_without_a_deadline_reports_nothing() {
+    let (engine, embedding) = create_engine(Duration::ZERO).await;
+    embedding.slow.store(false, Ordering::Relaxed);
+    assert!(recall(&engine).await.degradation.is_none());
+}
@@ -0,0 +1,101 @@
+//! Integration tests for engine-wide request size limits.
+
+use std::sync::Arc;
+
+use mnemo_core::config::MnemoEngineConfig;
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::forget::ForgetRequest;
+use mnemo_core::query::limits::LimitsConfig;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+fn create_engine(limits: LimitsConfig) -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "limits-agent".to_string(), None)
+        .with_limits(limits)
+}
+
+#[tokio::test]
+async fn oversized_writes_are_rejected() {
+    let engine = create_engine(LimitsConfig {
+        max_content_bytes: 32,
+        max_tags: 2,
+        max_metadata_bytes: 16,
+        max_batch_size: 2,
+        ..Default::default()
+    });
+
+    let err = engine
+        .remember(RememberRequest::new("x".repeat(33)))
+        .await
+        .unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "content");
+
+    let mut request = RememberRequest::new("short".to_string());
+    request.tags = Some(vec!["a".into(), "b".into(), "c".into()]);
+    let err = engine.remember(request).await.unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "tags");
+
+    let mut request = RememberRequest::new("short".to_string());
+    request.metadata = Some(serde_json::json!({"note": "more than sixteen bytes"}));
+    let err = engine.remember(request).await.unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "metadata");
+
+    let ids = (0..3).map(|_| Uuid::now_v7()).collect();
+    let err = engine.forget(ForgetRequest::new(ids)).await.unwrap_err();
+    assert!(matches!(err, Error::InvalidFields(_)));
+    assert_eq!(err.field_violations()[0].path, "memory_ids");
+
+    let mut request = RememberRequest::new("within every limit".to_string());
+    request.tags = Some(vec!["a".into(), "b".into()]);
+    engine.remember(request).await.unwrap();
+}
+
+#[tokio::test]
+async fn recall_limit_is_lowered_to_the_maximum() {
+    let engine = create_engine(LimitsConfig {
+        max_recall_limit: 2,
+        ..Default::default()
+    });
+    for i in 0..4 {
+        engine
+            .remember(RememberRequest::new(format!("deploy note number {i}")))
+            .await
+            .unwrap();
+    }
+
+    let mut request = RecallRequest::new("deploy note".to_string());
+    request.limit = Some(100_000);
+    let response = engine.recall(request).await.unwrap();
+    assert!(!response.memories.is_empty());
+    assert!(response.memories.len() <= 2);
+}
+
+#[test]
+fn config_overrides_single_limits() {
+    let config: MnemoEngineConfig = serde_json::from_value(serde_json::json!({
+        "limits": { "max_tags": 8 },
+    }))
+    .unwrap();
+    assert_eq!(config.limits.max_tags, 8);
+    assert_eq!(
+        config.limits.max_recall_limit,
+        LimitsConfig::default().max_recall_limit
+    );
+
+    let config = MnemoEngineConfig {
+        limits: LimitsConfig {
+            max_batch_size: 0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(matches!(config.validate(), Err(Error::Validation(_))));
+}
@@ -0,0 +1,97 @@
+//! Integration tests for per-agent logical clocks on audit events.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::event::{AgentEvent, EventType};
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::clock::SkewKind;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::StorageBackend;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+const AGENT: &str = "clock-agent";
+
+fn create_engine(storage: Arc<DuckDbStorage>) -> MnemoEngine {
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, AGENT.to_string(), None)
+}
+
+async fn clocks(storage: &DuckDbStorage) -> Vec<i64> {
+    let mut clocks: Vec<i64> = storage
+        .list_events(AGENT, 100, 0)
+        .await
+        .unwrap()
+        .iter()
+        .map(|e| e.logical_clock)
+        .collect();
+    clocks.sort_unstable();
+    clocks
+}
+
+#[tokio::test]
+async fn events_get_strictly_increasing_clocks_across_restarts() {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let engine = create_engine(storage.clone());
+    for content in ["first", "second", "third"] {
+        engine
+            .remember(RememberRequest::new(content.to_string()))
+            .await
+            .unwrap();
+    }
+    let before = clocks(&storage).await;
+    assert_eq!(before, (1..=before.len() as i64).collect::<Vec<_>>());
+
+    // A new engine on the same storage continues from the stored maximum.
+    let restarted = create_engine(storage.clone());
+    restarted
+        .remember(RememberRequest::new("after restart".to_string()))
+        .await
+        .unwrap();
+    let after = clocks(&storage).await;
+    assert!(after.len() > before.len());
+    assert_eq!(after, (1..=after.len() as i64).collect::<Vec<_>>());
+
+    let verification = restarted.verify_event_integrity(None, None).await.unwrap();
+    assert!(verification.valid, "{:?}", verification.error_message);
+    assert!(
+        restarted
+            .detect_clock_skew(None, None)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn skew_and_repeated_clocks_are_reported() {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let engine = create_engine(storage.clone());
+    let event = |clock: i64, timestamp: &str| {
+        let mut event = AgentEvent::new(
+            AGENT.to_string(),
+            EventType::MemoryWrite,
+            serde_json::json!({}),
+            timestamp.to_string(),
+            vec![1],
+        );
+        event.logical_clock = clock;
+        event
+    };
+    let first = event(1, "2026-01-01T10:00:00+00:00");
+    let behind = event(2, "2026-01-01T09:00:00+00:00");
+    let repeated = event(2, "2026-01-01T11:00:00+00:00");
+    for e in [&first, &behind, &repeated] {
+        storage.insert_event(e).await.unwrap();
+    }
+
+    let skews = engine.detect_clock_skew(None, None).await.unwrap();
+    assert_eq!(skews.len(), 2);
+    assert_eq!(skews[0].kind, SkewKind::TimestampBehind);
+    assert_eq!(skews[0].event_id, behind.id);
+    assert_eq!(skews[0].previous_event_id, first.id);
+    assert_eq!(skews[1].kind, SkewKind::DuplicateClock);
+    assert_eq!(skews[1].logical_clock, 2);
+}
@@ -0,0 +1,94 @@
+//! Integration tests for the pure-Rust in-memory storage backend.
+#![cfg(feature = "memory-storage")]
+
+use std::sync::Arc;
+
+use mnemo_core::config::{EmbeddingConfig, MnemoEngineConfig, StorageConfig};
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::forget::ForgetRequest;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::query::verify::VerifyRequest;
+use mnemo_core::storage::memory::InMemoryStorage;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(InMemoryStorage::new());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "agent".to_string(), None)
+}
+
+#[tokio::test]
+async fn remember_recall_forget_round_trip() {
+    let engine = create_engine();
+    assert_eq!(engine.storage.backend_name(), "memory");
+
+    let kept = engine
+        .remember(RememberRequest::new(
+            "the deploy window is friday".to_string(),
+        ))
+        .await
+        .unwrap()
+        .id;
+    let dropped = engine
+        .remember(RememberRequest::new(
+            "the deploy window was thursday".to_string(),
+        ))
+        .await
+        .unwrap()
+        .id;
+
+    let recalled = engine
+        .recall(RecallRequest::new("deploy window".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(recalled.memories.len(), 2);
+
+    let forgotten = engine
+        .forget(ForgetRequest::new(vec![dropped]))
+        .await
+        .unwrap();
+    assert_eq!(forgotten.forgotten, vec![dropped]);
+    let recalled = engine
+        .recall(RecallRequest::new("deploy window".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(
+        recalled.memories.iter().map(|m| m.id).collect::<Vec<_>>(),
+        vec![kept]
+    );
+}
+
+#[tokio::test]
+async fn hash_chains_verify() {
+    let engine = create_engine();
+    for i in 0..5 {
+        let mut request = RememberRequest::new(format!("step {i} of the rollout"));
+        request.thread_id = Some("t1".to_string());
+        engine.remember(request).await.unwrap();
+    }
+
+    let mut request = VerifyRequest::new();
+    request.thread_id = Some("t1".to_string());
+    let verified = engine.verify_memory_chain(request).await.unwrap();
+    assert!(verified.result.valid);
+    assert_eq!(verified.result.total_records, 5);
+}
+
+#[tokio::test]
+async fn selected_from_config() {
+    let config = MnemoEngineConfig {
+        dimensions: 64,
+        embedding: EmbeddingConfig::Deterministic,
+        storage: StorageConfig::InMemory,
+        ..Default::default()
+    };
+    let engine = MnemoEngine::from_config(&config).await.unwrap();
+    assert_eq!(engine.storage.backend_name(), "memory");
+
+    let parsed: StorageConfig =
+        serde_json::from_value(serde_json::json!({ "backend": "in_memory" })).unwrap();
+    assert_eq!(parsed, StorageConfig::InMemory);
+}
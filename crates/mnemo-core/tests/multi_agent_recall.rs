@@ -0,0 +1,178 @@
+//! Integration tests for recall across several agents.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::acl::Permission;
+use mnemo_core::model::delegation::{Delegation, DelegationScope};
+use mnemo_core::model::event::EventType;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine() -> MnemoEngine {
+    MnemoEngine::new(
+        Arc::new(DuckDbStorage::open_in_memory().unwrap()),
+        Arc::new(UsearchIndex::new(64).unwrap()),
+        Arc::new(DeterministicEmbedding::new(64)),
+        "orchestrator".to_string(),
+        None,
+    )
+}
+
+async fn remember(engine: &MnemoEngine, agent: &str, content: &str, tag: &str) -> uuid::Uuid {
+    let mut request = RememberRequest::new(content.to_string());
+    request.agent_id = Some(agent.to_string());
+    request.tags = Some(vec![tag.to_string()]);
+    engine.remember(request).await.unwrap().id
+}
+
+async fn delegate(engine: &MnemoEngine, from: &str, scope: DelegationScope) {
+    let delegation = Delegation {
+        id: uuid::Uuid::now_v7(),
+        delegator_id: from.to_string(),
+        delegate_id: "orchestrator".to_string(),
+        permission: Permission::Read,
+        scope,
+        max_depth: 0,
+        current_depth: 0,
+        parent_delegation_id: None,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        expires_at: None,
+        revoked_at: None,
+    };
+    engine.storage.insert_delegation(&delegation).await.unwrap();
+}
+
+async fn reads(engine: &MnemoEngine, agent: &str) -> usize {
+    engine
+        .storage
+        .list_events(agent, 100, 0)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|e| e.event_type == EventType::MemoryRead)
+        .count()
+}
+
+fn recall_across(agent_ids: &[&str]) -> RecallRequest {
+    let mut request = RecallRequest::new("deploy the billing service".to_string());
+    request.strategy = Some("semantic".to_string());
+    request.agent_ids = Some(agent_ids.iter().map(|a| a.to_string()).collect());
+    request
+}
+
+#[tokio::test]
+async fn recall_merges_delegated_workers_and_dedups() {
+    let engine = create_engine();
+    let a = remember(
+        &engine,
+        "worker-a",
+        "deploy the billing service at noon",
+        "ops",
+    )
+    .await;
+    remember(
+        &engine,
+        "worker-b",
+        "deploy the billing service at noon",
+        "ops",
+    )
+    .await;
+    let b = remember(
+        &engine,
+        "worker-b",
+        "billing deploy needs a migration",
+        "ops",
+    )
+    .await;
+    let hidden = remember(&engine, "worker-b", "deploy billing salary review", "hr").await;
+    delegate(&engine, "worker-a", DelegationScope::AllMemories).await;
+    delegate(
+        &engine,
+        "worker-b",
+        DelegationScope::ByTag(vec!["ops".into()]),
+    )
+    .await;
+
+    let response = engine
+        .recall(recall_across(&["worker-a", "worker-b"]))
+        .await
+        .unwrap();
+    let ids: Vec<_> = response.memories.iter().map(|m| m.id).collect();
+    assert_eq!(response.memories.len(), 2);
+    assert!(ids.contains(&a) && ids.contains(&b));
+    assert!(!ids.contains(&hidden));
+    for hit in &response.memories {
+        assert_eq!(hit.recalled_for.as_deref(), Some(hit.agent_id.as_str()));
+    }
+    let scores: Vec<f32> = response.memories.iter().map(|m| m.score).collect();
+    assert!(scores.windows(2).all(|w| w[0] >= w[1]));
+}
+
+#[tokio::test]
+async fn recall_fails_without_a_delegation() {
+    let engine = create_engine();
+    remember(
+        &engine,
+        "worker-a",
+        "deploy the billing service at noon",
+        "ops",
+    )
+    .await;
+    remember(
+        &engine,
+        "worker-c",
+        "deploy the billing service tonight",
+        "ops",
+    )
+    .await;
+    delegate(&engine, "worker-a", DelegationScope::AllMemories).await;
+
+    let err = engine
+        .recall(recall_across(&["worker-a", "worker-c"]))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)), "{err}");
+}
+
+#[tokio::test]
+async fn scoped_recall_fills_the_limit_and_records_the_orchestrator() {
+    let engine = create_engine();
+    for i in 0..6 {
+        remember(
+            &engine,
+            "worker-b",
+            &format!("deploy the billing service salary review {i}"),
+            "hr",
+        )
+        .await;
+    }
+    let ops = [
+        remember(&engine, "worker-b", "billing deploy runbook", "ops").await,
+        remember(&engine, "worker-b", "service owner rota", "ops").await,
+    ];
+    delegate(
+        &engine,
+        "worker-b",
+        DelegationScope::ByTag(vec!["ops".into()]),
+    )
+    .await;
+
+    let mut request = recall_across(&["worker-b"]);
+    request.limit = Some(2);
+    let response = engine.recall(request).await.unwrap();
+    let mut ids: Vec<_> = response.memories.iter().map(|m| m.id).collect();
+    ids.sort();
+    assert_eq!(ids, ops);
+
+    assert_eq!(reads(&engine, "orchestrator").await, 1);
+    assert_eq!(reads(&engine, "worker-b").await, 0);
+    for id in ops {
+        let record = engine.storage.get_memory(id).await.unwrap().unwrap();
+        assert_eq!(record.access_count, 1);
+    }
+}
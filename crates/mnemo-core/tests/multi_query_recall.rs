@@ -0,0 +1,98 @@
+//! Integration tests for multi-query recall and query expansion.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::{Error, Result};
+use mnemo_core::expansion::QueryExpander;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::recall::{MAX_QUERY_VARIANTS, RecallRequest};
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+struct BillingExpander;
+
+#[async_trait::async_trait]
+impl QueryExpander for BillingExpander {
+    async fn expand(&self, _query: &str) -> Result<Vec<String>> {
+        Ok(vec![
+            "invoice overdue".to_string(),
+            " ".to_string(),
+            "payment overdue".to_string(),
+            "Invoice Overdue".to_string(),
+        ])
+    }
+
+    fn name(&self) -> &str {
+        "billing"
+    }
+}
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "assistant".to_string(), None)
+}
+
+async fn remember(engine: &MnemoEngine, content: &str) -> Uuid {
+    engine
+        .remember(RememberRequest::new(content.to_string()))
+        .await
+        .unwrap()
+        .id
+}
+
+fn billing_query() -> RecallRequest {
+    let mut request = RecallRequest::new("billing trouble".to_string());
+    request.strategy = Some("semantic".to_string());
+    request.limit = Some(1);
+    request
+}
+
+#[tokio::test]
+async fn alternate_phrasings_find_differently_worded_memories() {
+    let engine = create_engine();
+    let invoice = remember(&engine, "the invoice payment is overdue").await;
+    remember(&engine, "the cat food bowl is empty").await;
+
+    let mut request = billing_query();
+    request.queries = Some(vec![
+        "invoice overdue".to_string(),
+        "payment overdue".to_string(),
+    ]);
+    let response = engine.recall(request).await.unwrap();
+    assert_eq!(response.memories.len(), 1);
+    assert_eq!(response.memories[0].id, invoice);
+}
+
+#[tokio::test]
+async fn expand_query_uses_the_attached_expander() {
+    let engine = create_engine();
+    let invoice = remember(&engine, "the invoice payment is overdue").await;
+    remember(&engine, "the cat food bowl is empty").await;
+
+    let mut request = billing_query();
+    request.expand_query = Some(true);
+    let err = engine.recall(request.clone()).await.unwrap_err();
+    assert!(matches!(err, Error::Validation(_)), "{err}");
+
+    let engine = engine.with_query_expander(Arc::new(BillingExpander));
+    let response = engine.recall(request).await.unwrap();
+    assert_eq!(response.memories[0].id, invoice);
+}
+
+#[tokio::test]
+async fn too_many_phrasings_are_rejected() {
+    let engine = create_engine();
+    let mut request = billing_query();
+    request.queries = Some(
+        (0..=MAX_QUERY_VARIANTS)
+            .map(|i| format!("phrasing {i}"))
+            .collect(),
+    );
+    let err = engine.recall(request).await.unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "queries");
+}
@@ -0,0 +1,60 @@
+//! Integration tests for per-memory language detection and the
+//! multilingual full-text analyzers.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::NoopEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::search::FullTextIndex;
+use mnemo_core::search::language::LANGUAGE_METADATA_KEY;
+use mnemo_core::search::tantivy_index::TantivyFullTextIndex;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine() -> (MnemoEngine, Arc<TantivyFullTextIndex>) {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(128).unwrap());
+    let embedding = Arc::new(NoopEmbedding::new(128));
+    let full_text = Arc::new(TantivyFullTextIndex::open_in_memory().unwrap());
+    let engine = MnemoEngine::new(storage, index, embedding, "ml-agent".to_string(), None)
+        .with_full_text(full_text.clone());
+    (engine, full_text)
+}
+
+#[tokio::test]
+async fn remember_records_detected_language() {
+    let (engine, _) = create_engine();
+    let id = engine
+        .remember(RememberRequest::new(
+            "Der Benutzer bevorzugt kurze Antworten und arbeitet meistens abends".to_string(),
+        ))
+        .await
+        .unwrap()
+        .id;
+    let record = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert_eq!(record.metadata[LANGUAGE_METADATA_KEY], "deu");
+
+    // A caller-supplied language is never overwritten.
+    let mut req =
+        RememberRequest::new("Der Benutzer wohnt in Berlin und fährt gern Fahrrad".to_string());
+    req.metadata = Some(serde_json::json!({ LANGUAGE_METADATA_KEY: "gsw" }));
+    let id = engine.remember(req).await.unwrap().id;
+    let record = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert_eq!(record.metadata[LANGUAGE_METADATA_KEY], "gsw");
+}
+
+#[tokio::test]
+async fn cjk_memory_is_found_by_substring() {
+    let (engine, full_text) = create_engine();
+    let id = engine
+        .remember(RememberRequest::new(
+            "用户每天早上在上海的办公室喝咖啡".to_string(),
+        ))
+        .await
+        .unwrap()
+        .id;
+
+    let hits = full_text.search("上海", 10).unwrap();
+    assert_eq!(hits.first().map(|h| h.0), Some(id));
+}
@@ -0,0 +1,86 @@
+//! Integration tests for NLI contradiction checking during conflict
+//! detection, and for contradicted pairs reaching the review queue.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::NoopEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::nli::NegationChecker;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+/// No-op vectors are identical (similarity 1.0), so a threshold above 1
+/// switches off the near-duplicate path and isolates the NLI path.
+const NO_NEAR_DUPLICATES: f32 = 1.01;
+
+fn create_engine(with_checker: bool) -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(128).unwrap());
+    let embedding = Arc::new(NoopEmbedding::new(128));
+    let engine = MnemoEngine::new(storage, index, embedding, "nli-agent".to_string(), None);
+    if with_checker {
+        engine.with_contradiction_checker(Arc::new(NegationChecker))
+    } else {
+        engine
+    }
+}
+
+async fn remember(engine: &MnemoEngine, content: &str) -> Uuid {
+    engine
+        .remember(RememberRequest::new(content.to_string()))
+        .await
+        .unwrap()
+        .id
+}
+
+#[tokio::test]
+async fn checker_flags_contradiction_below_similarity_threshold() {
+    let engine = create_engine(true);
+    let a = remember(&engine, "The user prefers dark mode").await;
+    let b = remember(&engine, "The user hates dark mode").await;
+    remember(&engine, "The team meets every Tuesday").await;
+
+    let result = engine
+        .detect_conflicts(None, NO_NEAR_DUPLICATES)
+        .await
+        .unwrap();
+    assert_eq!(result.conflicts.len(), 1);
+    let pair = &result.conflicts[0];
+    let mut ids = [pair.memory_a, pair.memory_b];
+    ids.sort();
+    let mut expected = [a, b];
+    expected.sort();
+    assert_eq!(ids, expected);
+    let scores = pair.nli.unwrap();
+    assert!(scores.contradiction >= 0.7);
+    assert!(pair.reason.contains("negation"));
+}
+
+#[tokio::test]
+async fn detection_without_checker_is_similarity_only() {
+    let engine = create_engine(false);
+    remember(&engine, "The user prefers dark mode").await;
+    remember(&engine, "The user hates dark mode").await;
+
+    let result = engine
+        .detect_conflicts(None, NO_NEAR_DUPLICATES)
+        .await
+        .unwrap();
+    assert!(result.conflicts.is_empty());
+}
+
+#[tokio::test]
+async fn contradictions_feed_the_review_queue() {
+    let engine = create_engine(true);
+    remember(&engine, "The user prefers dark mode").await;
+    remember(&engine, "The user hates dark mode").await;
+
+    let queued = engine
+        .queue_conflicts(None, NO_NEAR_DUPLICATES)
+        .await
+        .unwrap();
+    assert_eq!(queued.len(), 1);
+    assert!(queued[0].reason.starts_with("NLI contradiction"));
+}
@@ -0,0 +1,151 @@
+//! Integration tests for memory updates with optimistic concurrency.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::query::update::UpdateRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+fn create_engine() -> Arc<MnemoEngine> {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    Arc::new(MnemoEngine::new(
+        storage,
+        index,
+        embedding,
+        "editor".to_string(),
+        None,
+    ))
+}
+
+async fn remember(engine: &MnemoEngine) -> Uuid {
+    engine
+        .remember(RememberRequest::new(
+            "the deploy window is friday".to_string(),
+        ))
+        .await
+        .unwrap()
+        .id
+}
+
+fn tag(id: Uuid, tag: &str, expected_version: Option<u32>) -> UpdateRequest {
+    UpdateRequest {
+        tags: Some(vec![tag.to_string()]),
+        expected_version,
+        ..UpdateRequest::new(id)
+    }
+}
+
+#[tokio::test]
+async fn updates_bump_the_version() {
+    let engine = create_engine();
+    let id = remember(&engine).await;
+
+    let mut request = tag(id, "ops", Some(1));
+    request.importance = Some(0.9);
+    request.metadata = Some(serde_json::json!({"owner": "sre"}));
+    let response = engine.update(request).await.unwrap();
+    assert_eq!(response.version, 2);
+
+    let record = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert_eq!(record.version, 2);
+    assert_eq!(record.tags, ["ops"]);
+    assert_eq!(record.importance, 0.9);
+    assert_eq!(record.metadata["owner"], "sre");
+    assert_eq!(record.content, "the deploy window is friday");
+}
+
+#[tokio::test]
+async fn stale_versions_conflict_with_the_current_record() {
+    let engine = create_engine();
+    let id = remember(&engine).await;
+    engine.update(tag(id, "first", Some(1))).await.unwrap();
+
+    let err = engine.update(tag(id, "second", Some(1))).await.unwrap_err();
+    let Error::VersionConflict {
+        memory_id,
+        expected,
+        actual,
+        current,
+    } = err
+    else {
+        panic!("expected a version conflict, got {err}");
+    };
+    assert_eq!((memory_id, expected, actual), (id, 1, 2));
+    assert_eq!(current.tags, ["first"]);
+    assert_eq!(current.version, 2);
+
+    // Nothing was written; retrying at the current version applies.
+    assert_eq!(
+        engine.storage.get_memory(id).await.unwrap().unwrap().tags,
+        ["first"]
+    );
+    let response = engine.update(tag(id, "second", Some(2))).await.unwrap();
+    assert_eq!(response.version, 3);
+}
+
+#[tokio::test]
+async fn updates_without_a_version_always_apply() {
+    let engine = create_engine();
+    let id = remember(&engine).await;
+    engine.update(tag(id, "first", None)).await.unwrap();
+    let response = engine.update(tag(id, "second", None)).await.unwrap();
+    assert_eq!(response.version, 3);
+}
+
+#[tokio::test]
+async fn concurrent_updates_at_one_version_have_one_winner() {
+    let engine = create_engine();
+    let id = remember(&engine).await;
+
+    let updates: Vec<_> = (0..8)
+        .map(|i| {
+            let engine = engine.clone();
+            tokio::spawn(async move {
+                engine
+                    .update(tag(id, &format!("writer-{i}"), Some(1)))
+                    .await
+            })
+        })
+        .collect();
+    let mut applied = 0;
+    for update in updates {
+        match update.await.unwrap() {
+            Ok(_) => applied += 1,
+            Err(Error::VersionConflict { actual, .. }) => assert_eq!(actual, 2),
+            Err(e) => panic!("unexpected error: {e}"),
+        }
+    }
+    assert_eq!(applied, 1);
+    assert_eq!(
+        engine
+            .storage
+            .get_memory(id)
+            .await
+            .unwrap()
+            .unwrap()
+            .version,
+        2
+    );
+}
+
+#[tokio::test]
+async fn missing_memories_and_bad_importance_are_rejected() {
+    let engine = create_engine();
+    assert!(matches!(
+        engine.update(UpdateRequest::new(Uuid::now_v7())).await,
+        Err(Error::NotFound(_))
+    ));
+
+    let id = remember(&engine).await;
+    let mut request = UpdateRequest::new(id);
+    request.importance = Some(1.5);
+    let err = engine.update(request).await.unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "importance");
+}
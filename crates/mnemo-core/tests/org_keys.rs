@@ -0,0 +1,104 @@
+//! Integration tests for per-org encryption keys.
+
+use std::collections::BTreeMap;
+
+use mnemo_core::config::{EmbeddingConfig, KeyProviderConfig, MnemoEngineConfig, OrgKeysConfig};
+use mnemo_core::error::Error;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+
+fn config(dir: &std::path::Path) -> MnemoEngineConfig {
+    std::fs::write(dir.join("acme-1.key"), "11".repeat(32)).unwrap();
+    std::fs::write(dir.join("globex-1.key"), "22".repeat(32)).unwrap();
+    MnemoEngineConfig {
+        dimensions: 64,
+        embedding: EmbeddingConfig::Deterministic,
+        org_keys: Some(OrgKeysConfig {
+            provider: KeyProviderConfig::File {
+                dir: dir.to_path_buf(),
+            },
+            orgs: BTreeMap::from([
+                ("acme".to_string(), "acme-1".to_string()),
+                ("globex".to_string(), "globex-1".to_string()),
+            ]),
+            retired: Vec::new(),
+        }),
+        ..Default::default()
+    }
+}
+
+async fn remember_in(
+    engine: &MnemoEngine,
+    org: &str,
+    content: &str,
+) -> mnemo_core::error::Result<uuid::Uuid> {
+    let mut request = RememberRequest::new(content.to_string());
+    request.org_id = Some(org.to_string());
+    Ok(engine.remember(request).await?.id)
+}
+
+#[tokio::test]
+async fn each_org_is_sealed_under_its_own_key() {
+    let dir = tempfile::tempdir().unwrap();
+    let engine = MnemoEngine::from_config(&config(dir.path())).await.unwrap();
+    assert!(engine.encrypts_content());
+
+    let acme = remember_in(&engine, "acme", "acme ships in march")
+        .await
+        .unwrap();
+    let globex = remember_in(&engine, "globex", "globex ships in may")
+        .await
+        .unwrap();
+    let stored = engine.storage.get_memory(acme).await.unwrap().unwrap();
+    assert!(stored.content.starts_with("acme-1:"));
+    assert!(!stored.content.contains("march"));
+    let stored = engine.storage.get_memory(globex).await.unwrap().unwrap();
+    assert!(stored.content.starts_with("globex-1:"));
+
+    let mut request = RecallRequest::new("acme ships".to_string());
+    request.org_id = Some("acme".to_string());
+    let recalled = engine.recall(request).await.unwrap();
+    assert_eq!(recalled.memories[0].content, "acme ships in march");
+
+    // Without an engine-wide key, an org with no key of its own cannot write.
+    let err = remember_in(&engine, "initech", "initech ships never")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Encryption(_)), "{err}");
+}
+
+#[tokio::test]
+async fn unlisted_orgs_fall_back_to_the_engine_wide_key() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut config = config(dir.path());
+    config.encryption_key = Some("33".repeat(32));
+    let engine = MnemoEngine::from_config(&config).await.unwrap();
+
+    let id = remember_in(&engine, "initech", "initech ships in june")
+        .await
+        .unwrap();
+    let stored = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert!(!stored.content.contains(':'));
+
+    let mut request = RecallRequest::new("initech ships".to_string());
+    request.org_id = Some("initech".to_string());
+    let recalled = engine.recall(request).await.unwrap();
+    assert_eq!(recalled.memories[0].content, "initech ships in june");
+}
+
+#[tokio::test]
+async fn a_missing_key_fails_startup() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut config = config(dir.path());
+    config
+        .org_keys
+        .as_mut()
+        .unwrap()
+        .retired
+        .push("acme-0".to_string());
+    assert!(MnemoEngine::from_config(&config).await.is_err());
+
+    config.org_keys.as_mut().unwrap().retired = vec!["acme/0".to_string()];
+    assert!(config.validate().is_err());
+}
@@ -0,0 +1,160 @@
+//! Integration tests for transactional memory writes and outbox delivery.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::{Error, Result};
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::event::EventType;
+use mnemo_core::model::memory::MemoryRecord;
+use mnemo_core::model::outbox::OutboxMessage;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::outbox::{MEMORY_WRITE_TOPIC, OutboxDispatcher, OutboxSink};
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::WriteBatch;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+const AGENT: &str = "outbox-agent";
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, AGENT.to_string(), None).with_outbox()
+}
+
+#[derive(Default)]
+struct RecordingSink {
+    delivered: Mutex<Vec<OutboxMessage>>,
+    failing: AtomicBool,
+}
+
+#[async_trait::async_trait]
+impl OutboxSink for RecordingSink {
+    async fn deliver(&self, message: &OutboxMessage) -> Result<()> {
+        if self.failing.load(Ordering::SeqCst) {
+            return Err(Error::Internal("receiver down".to_string()));
+        }
+        self.delivered.lock().unwrap().push(message.clone());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn remember_writes_event_and_outbox_message_with_the_memory() {
+    let engine = create_engine();
+    let sink = Arc::new(RecordingSink::default());
+    let dispatcher = OutboxDispatcher::new(engine.storage.clone(), sink.clone());
+
+    let id = engine
+        .remember(RememberRequest::new(
+            "the release train leaves on tuesdays".to_string(),
+        ))
+        .await
+        .unwrap()
+        .id;
+    let events = engine.storage.list_events(AGENT, 10, 0).await.unwrap();
+    assert!(
+        events
+            .iter()
+            .any(|e| e.event_type == EventType::MemoryWrite)
+    );
+
+    let report = dispatcher.dispatch_once().await.unwrap();
+    assert_eq!(report.delivered, 1);
+    let delivered = sink.delivered.lock().unwrap().clone();
+    assert_eq!(delivered[0].topic, MEMORY_WRITE_TOPIC);
+    assert_eq!(delivered[0].payload["memory_id"], id.to_string());
+    assert_eq!(delivered[0].attempts, 1);
+
+    // Delivered messages are never handed out again.
+    assert_eq!(dispatcher.dispatch_once().await.unwrap().delivered, 0);
+    assert_eq!(sink.delivered.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn failed_deliveries_are_retried_with_the_same_id() {
+    let engine = create_engine();
+    let sink = Arc::new(RecordingSink::default());
+    let dispatcher = OutboxDispatcher::new(engine.storage.clone(), sink.clone())
+        .with_retry_delay(Duration::ZERO);
+    engine
+        .remember(RememberRequest::new(
+            "staging runs on spot instances".to_string(),
+        ))
+        .await
+        .unwrap();
+
+    sink.failing.store(true, Ordering::SeqCst);
+    assert_eq!(dispatcher.dispatch_once().await.unwrap().failed, 1);
+
+    sink.failing.store(false, Ordering::SeqCst);
+    let report = dispatcher.dispatch_once().await.unwrap();
+    assert_eq!(report.delivered, 1);
+    assert_eq!(sink.delivered.lock().unwrap()[0].attempts, 2);
+}
+
+#[tokio::test]
+async fn claimed_messages_are_leased() {
+    let engine = create_engine();
+    engine
+        .remember(RememberRequest::new("on-call rotates weekly".to_string()))
+        .await
+        .unwrap();
+
+    let later = (chrono::Utc::now() + chrono::Duration::minutes(5)).to_rfc3339();
+    let claimed = engine.storage.claim_outbox(10, &later).await.unwrap();
+    assert_eq!(claimed.len(), 1);
+    assert!(
+        engine
+            .storage
+            .claim_outbox(10, &later)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    engine.storage.complete_outbox(claimed[0].id).await.unwrap();
+    let cutoff = (chrono::Utc::now() + chrono::Duration::seconds(1)).to_rfc3339();
+    assert_eq!(engine.storage.prune_outbox(&cutoff).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn a_failed_batch_writes_nothing() {
+    let engine = create_engine();
+    engine
+        .remember(RememberRequest::new("backups are encrypted".to_string()))
+        .await
+        .unwrap();
+    let record = MemoryRecord::new(AGENT.to_string(), "restores are tested".to_string());
+    let mut event = engine.storage.list_events(AGENT, 1, 0).await.unwrap()[0].clone();
+    event.id = Uuid::now_v7();
+    let message = OutboxMessage::new("test", serde_json::json!({}));
+
+    // The repeated outbox id fails the last insert, so the memory and event
+    // written before it must roll back.
+    let result = engine
+        .storage
+        .commit_write(&WriteBatch {
+            memory: Some(record.clone()),
+            relations: Vec::new(),
+            events: vec![event.clone()],
+            outbox: vec![message.clone(), message],
+            ..Default::default()
+        })
+        .await;
+    assert!(result.is_err());
+    assert!(
+        engine
+            .storage
+            .get_memory(record.id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(engine.storage.get_event(event.id).await.unwrap().is_none());
+}
@@ -0,0 +1,155 @@
+//! Integration tests for memory ownership transfer.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::acl::Permission;
+use mnemo_core::model::event::EventType;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::query::transfer::{TransferMode, TransferRequest};
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine() -> MnemoEngine {
+    MnemoEngine::new(
+        Arc::new(DuckDbStorage::open_in_memory().unwrap()),
+        Arc::new(UsearchIndex::new(64).unwrap()),
+        Arc::new(DeterministicEmbedding::new(64)),
+        "operator".to_string(),
+        None,
+    )
+}
+
+async fn remember(engine: &MnemoEngine, agent: &str, content: &str, tag: &str) -> uuid::Uuid {
+    let mut request = RememberRequest::new(content.to_string());
+    request.agent_id = Some(agent.to_string());
+    request.tags = Some(vec![tag.to_string()]);
+    engine.remember(request).await.unwrap().id
+}
+
+#[tokio::test]
+async fn reassigning_moves_matching_memories_and_keeps_chains_valid() {
+    let engine = create_engine();
+    let billing = remember(&engine, "bot-v1", "refunds take five days", "billing").await;
+    let other = remember(&engine, "bot-v1", "the office closes at six", "misc").await;
+    remember(&engine, "bot-v2", "invoices go out monthly", "billing").await;
+
+    let mut request = TransferRequest::new("bot-v1", "bot-v2");
+    request.filter.tags = Some(vec!["billing".to_string()]);
+    request.transferred_by = Some("bot-v1".to_string());
+    request.dry_run = true;
+    let preview = engine.transfer_ownership(request.clone()).await.unwrap();
+    assert_eq!(preview.memory_ids, vec![billing]);
+    assert_eq!(preview.transferred, 0);
+    let unchanged = engine.storage.get_memory(billing).await.unwrap().unwrap();
+    assert_eq!(unchanged.agent_id, "bot-v1");
+
+    request.dry_run = false;
+    let response = engine.transfer_ownership(request).await.unwrap();
+    assert_eq!(response.transferred, 1);
+    assert_eq!(response.rechained.len(), 2);
+    assert_eq!(response.event_ids.len(), 2);
+
+    let moved = engine.storage.get_memory(billing).await.unwrap().unwrap();
+    assert_eq!(moved.agent_id, "bot-v2");
+    assert_eq!(moved.metadata["transfer"]["from_agent"], "bot-v1");
+    let kept = engine.storage.get_memory(other).await.unwrap().unwrap();
+    assert_eq!(kept.agent_id, "bot-v1");
+    for agent in ["bot-v1", "bot-v2"] {
+        let result = engine
+            .verify_integrity(Some(agent.to_string()), None)
+            .await
+            .unwrap();
+        assert!(result.valid, "{agent}: {:?}", result.error_message);
+        let events = engine.storage.list_events(agent, 20, 0).await.unwrap();
+        assert!(
+            events
+                .iter()
+                .any(|e| e.event_type == EventType::OwnershipTransferred)
+        );
+    }
+}
+
+#[tokio::test]
+async fn granting_ownership_adds_admin_acls_without_moving() {
+    let engine = create_engine();
+    let id = remember(&engine, "bot-v1", "refunds take five days", "billing").await;
+
+    let mut request = TransferRequest::new("bot-v1", "bot-v2");
+    request.mode = TransferMode::GrantOwner;
+    request.transferred_by = Some("bot-v1".to_string());
+    let response = engine.transfer_ownership(request).await.unwrap();
+    assert_eq!(response.transferred, 1);
+    assert!(response.rechained.is_empty());
+
+    let record = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert_eq!(record.agent_id, "bot-v1");
+    assert!(
+        engine
+            .storage
+            .check_permission(id, "bot-v2", Permission::Admin)
+            .await
+            .unwrap()
+    );
+
+    let err = engine
+        .transfer_ownership(TransferRequest::new("bot-v1", "bot-v1"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidFields(_)), "{err}");
+}
+
+#[tokio::test]
+async fn transfers_need_admin_on_every_memory() {
+    let engine = create_engine();
+    let id = remember(&engine, "bot-v1", "refunds take five days", "billing").await;
+
+    // The engine's own agent holds no grant on bot-v1's memories.
+    for dry_run in [true, false] {
+        let mut request = TransferRequest::new("bot-v1", "bot-v2");
+        request.dry_run = dry_run;
+        let err = engine.transfer_ownership(request).await.unwrap_err();
+        assert!(matches!(err, Error::PermissionDenied(_)), "{err}");
+    }
+    let record = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert_eq!(record.agent_id, "bot-v1");
+    let events = engine.storage.list_events("bot-v2", 20, 0).await.unwrap();
+    assert!(
+        events
+            .iter()
+            .all(|e| e.event_type != EventType::OwnershipTransferred)
+    );
+
+    // An admin grant is enough.
+    let mut request = TransferRequest::new("bot-v1", "operator");
+    request.mode = TransferMode::GrantOwner;
+    request.transferred_by = Some("bot-v1".to_string());
+    engine.transfer_ownership(request).await.unwrap();
+    let response = engine
+        .transfer_ownership(TransferRequest::new("bot-v1", "bot-v2"))
+        .await
+        .unwrap();
+    assert_eq!(response.transferred, 1);
+}
+
+#[tokio::test]
+async fn reassigning_records_the_move_in_non_object_metadata() {
+    let engine = create_engine();
+    let mut request = RememberRequest::new("refunds take five days".to_string());
+    request.agent_id = Some("bot-v1".to_string());
+    request.metadata = Some(serde_json::json!(["imported", 2024]));
+    let id = engine.remember(request).await.unwrap().id;
+
+    let mut request = TransferRequest::new("bot-v1", "bot-v2");
+    request.transferred_by = Some("bot-v1".to_string());
+    engine.transfer_ownership(request).await.unwrap();
+
+    let moved = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert_eq!(moved.metadata["transfer"]["from_agent"], "bot-v1");
+    assert_eq!(
+        moved.metadata["value"],
+        serde_json::json!(["imported", 2024])
+    );
+}
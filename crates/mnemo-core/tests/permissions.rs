@@ -0,0 +1,145 @@
+//! Integration tests for the export and replay permissions.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::acl::{Acl, Permission, PrincipalType};
+use mnemo_core::model::delegation::{Delegation, DelegationScope};
+use mnemo_core::model::event::{AgentEvent, EventType};
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::export::{ExportDestination, ExportSchedule};
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+fn create_engine(default_agent: &str) -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, default_agent.to_string(), None)
+}
+
+async fn remember_as(engine: &MnemoEngine, agent_id: &str, content: &str) -> Uuid {
+    let mut request = RememberRequest::new(content.to_string());
+    request.agent_id = Some(agent_id.to_string());
+    engine.remember(request).await.unwrap().id
+}
+
+async fn grant(engine: &MnemoEngine, memory_id: Uuid, principal: &str, permission: Permission) {
+    let acl = Acl {
+        id: Uuid::now_v7(),
+        memory_id,
+        principal_type: PrincipalType::Agent,
+        principal_id: principal.to_string(),
+        permission,
+        granted_by: "planner".to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        expires_at: None,
+    };
+    engine.storage.insert_acl(&acl).await.unwrap();
+}
+
+async fn delegate(
+    engine: &MnemoEngine,
+    delegator: &str,
+    delegate_id: &str,
+    permission: Permission,
+) {
+    let delegation = Delegation {
+        id: Uuid::now_v7(),
+        delegator_id: delegator.to_string(),
+        delegate_id: delegate_id.to_string(),
+        permission,
+        scope: DelegationScope::AllMemories,
+        max_depth: 0,
+        current_depth: 0,
+        parent_delegation_id: None,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        expires_at: None,
+        revoked_at: None,
+    };
+    engine.storage.insert_delegation(&delegation).await.unwrap();
+}
+
+async fn insert_event(engine: &MnemoEngine, agent_id: &str, parent: Option<Uuid>) -> Uuid {
+    let event = AgentEvent {
+        id: Uuid::now_v7(),
+        agent_id: agent_id.to_string(),
+        thread_id: None,
+        run_id: None,
+        parent_event_id: parent,
+        event_type: EventType::MemoryWrite,
+        payload: serde_json::json!({}),
+        trace_id: None,
+        span_id: None,
+        model: None,
+        tokens_input: None,
+        tokens_output: None,
+        latency_ms: None,
+        cost_usd: None,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        logical_clock: 0,
+        content_hash: vec![1, 2, 3],
+        prev_hash: None,
+        embedding: None,
+        request_id: None,
+    };
+    engine.storage.insert_event(&event).await.unwrap();
+    event.id
+}
+
+#[tokio::test]
+async fn principal_exports_only_what_it_may_export() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut schedule = ExportSchedule::new(
+        "auditor",
+        "0 2 * * *",
+        ExportDestination::Filesystem {
+            path: dir.path().to_path_buf(),
+        },
+    );
+    schedule.principal = Some("auditor".to_string());
+    let engine = create_engine("server").with_exports(vec![schedule]);
+
+    remember_as(&engine, "auditor", "audit started monday").await;
+    let readable = remember_as(&engine, "planner", "sprint ends friday").await;
+    let exportable = remember_as(&engine, "planner", "budget approved").await;
+    remember_as(&engine, "planner", "hiring paused").await;
+    grant(&engine, readable, "auditor", Permission::Read).await;
+    grant(&engine, exportable, "auditor", Permission::Export).await;
+
+    let run = engine.run_export("auditor").await.unwrap();
+    assert_eq!(run.memories, 2);
+    let text = std::fs::read_to_string(run.location.unwrap()).unwrap();
+    assert!(text.contains("audit started monday"));
+    assert!(text.contains("budget approved"));
+    assert!(!text.contains("sprint ends friday"));
+}
+
+#[tokio::test]
+async fn tracing_another_agents_events_needs_replay() {
+    let engine = create_engine("investigator");
+    let root = insert_event(&engine, "planner", None).await;
+    let child = insert_event(&engine, "planner", Some(root)).await;
+    let foreign = insert_event(&engine, "stranger", Some(root)).await;
+
+    let err = engine.trace_causality(root, 3).await.unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)), "{err}");
+
+    // Read access is not enough to walk the event graph.
+    delegate(&engine, "planner", "investigator", Permission::Read).await;
+    let err = engine.trace_causality(root, 3).await.unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)), "{err}");
+
+    delegate(&engine, "planner", "investigator", Permission::Replay).await;
+    let chain = engine.trace_causality(root, 3).await.unwrap();
+    let ids: Vec<Uuid> = chain.nodes.iter().map(|n| n.event.id).collect();
+    assert!(ids.contains(&root) && ids.contains(&child));
+    assert!(!ids.contains(&foreign));
+
+    // The trace runs as the engine's own agent, which sees its own events.
+    let own = insert_event(&engine, "investigator", None).await;
+    assert_eq!(engine.trace_causality(own, 3).await.unwrap().nodes.len(), 1);
+}
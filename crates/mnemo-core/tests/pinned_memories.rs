@@ -0,0 +1,180 @@
+//! Integration tests for pinned (protected) memories.
+//!
+//! Covers: the `pinned` flag round-tripping through storage, pinned writes
+//! never receiving a TTL, the decay pass / TTL sweep / tag-cluster
+//! consolidation leaving pinned records alone, conflict resolution never
+//! picking a pinned loser, and forget requiring `force` for pinned ids
+//! while criteria-based forgetting skips them.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::NoopEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::memory::{ConsolidationState, MemoryType};
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::conflict::{ConflictPair, ResolutionStrategy};
+use mnemo_core::query::forget::{ForgetCriteria, ForgetRequest, ForgetStrategy};
+use mnemo_core::query::pin::PinRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+fn create_engine() -> Arc<MnemoEngine> {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(128).unwrap());
+    let embedding = Arc::new(NoopEmbedding::new(128));
+    Arc::new(MnemoEngine::new(
+        storage,
+        index,
+        embedding,
+        "pin-agent".to_string(),
+        None,
+    ))
+}
+
+async fn remember(engine: &MnemoEngine, content: &str, importance: f32, pinned: bool) -> Uuid {
+    let mut req = RememberRequest::new(content.to_string());
+    req.importance = Some(importance);
+    req.tags = Some(vec!["profile".to_string()]);
+    req.pinned = Some(pinned);
+    engine.remember(req).await.unwrap().id
+}
+
+#[tokio::test]
+async fn pinned_flag_round_trips_and_skips_ttl() {
+    let engine = create_engine();
+    let mut req = RememberRequest::new("Always answer in British English".to_string());
+    req.memory_type = Some(MemoryType::Working);
+    req.ttl_seconds = Some(1);
+    req.pinned = Some(true);
+    let id = engine.remember(req).await.unwrap().id;
+
+    let record = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert!(record.pinned);
+    assert!(record.expires_at.is_none());
+
+    let unpinned = remember(&engine, "scratch note", 0.5, false).await;
+    let record = engine.storage.get_memory(unpinned).await.unwrap().unwrap();
+    assert!(!record.pinned);
+}
+
+#[tokio::test]
+async fn decay_pass_and_consolidation_skip_pinned() {
+    let engine = create_engine();
+    let pinned = remember(&engine, "User's name is Priya", 0.01, true).await;
+    let plain = remember(&engine, "User mentioned the weather", 0.01, false).await;
+
+    let result = engine.run_decay_pass(None, 0.5, 0.1).await.unwrap();
+    assert_eq!(result.forgotten, 1);
+
+    let p = engine.storage.get_memory(pinned).await.unwrap().unwrap();
+    assert_eq!(p.consolidation_state, ConsolidationState::Raw);
+    let u = engine.storage.get_memory(plain).await.unwrap().unwrap();
+    assert_eq!(u.consolidation_state, ConsolidationState::Forgotten);
+
+    // Two pinned episodic memories sharing a tag never form a cluster.
+    let engine = create_engine();
+    remember(&engine, "User's name is Priya", 0.9, true).await;
+    remember(&engine, "User lives in Pune", 0.9, true).await;
+    let result = engine.run_consolidation(None, 2).await.unwrap();
+    assert_eq!(result.clusters_found, 0);
+}
+
+#[tokio::test]
+async fn forget_requires_force_for_pinned() {
+    let engine = create_engine();
+    let pinned = remember(&engine, "System: never share API keys", 0.9, true).await;
+
+    let resp = engine
+        .forget(ForgetRequest::new(vec![pinned]))
+        .await
+        .unwrap();
+    assert!(resp.forgotten.is_empty());
+    assert_eq!(resp.errors.len(), 1);
+    assert!(resp.errors[0].error.contains("pinned"));
+
+    let mut forced = ForgetRequest::new(vec![pinned]);
+    forced.force = Some(true);
+    forced.strategy = Some(ForgetStrategy::HardDelete);
+    let resp = engine.forget(forced).await.unwrap();
+    assert_eq!(resp.forgotten, vec![pinned]);
+    assert!(engine.storage.get_memory(pinned).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn forget_by_criteria_skips_pinned() {
+    let engine = create_engine();
+    let pinned = remember(&engine, "User is allergic to peanuts", 0.1, true).await;
+    let plain = remember(&engine, "User had toast", 0.1, false).await;
+
+    let mut req = ForgetRequest::new(vec![]);
+    req.criteria = Some(ForgetCriteria {
+        max_age_hours: None,
+        min_importance_below: Some(0.5),
+        memory_type: None,
+        tags: None,
+    });
+    let resp = engine.forget(req).await.unwrap();
+    assert_eq!(resp.forgotten, vec![plain]);
+    assert!(
+        engine
+            .storage
+            .get_memory(pinned)
+            .await
+            .unwrap()
+            .unwrap()
+            .deleted_at
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn conflict_resolution_never_drops_pinned() {
+    let engine = create_engine();
+    let older_pinned = remember(&engine, "User prefers tea", 0.5, true).await;
+    let newer = remember(&engine, "User prefers coffee", 0.5, false).await;
+
+    let pair = ConflictPair {
+        memory_a: older_pinned,
+        memory_b: newer,
+        similarity: 0.95,
+        reason: "test".to_string(),
+        nli: None,
+    };
+    engine
+        .resolve_conflict(&pair, ResolutionStrategy::KeepNewest)
+        .await
+        .unwrap();
+
+    let kept = engine
+        .storage
+        .get_memory(older_pinned)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(kept.deleted_at.is_none());
+    let dropped = engine.storage.get_memory(newer).await.unwrap().unwrap();
+    assert!(dropped.deleted_at.is_some());
+}
+
+#[tokio::test]
+async fn pin_and_unpin_existing_memory() {
+    let engine = create_engine();
+    let mut req = RememberRequest::new("User's timezone is IST".to_string());
+    req.ttl_seconds = Some(3600);
+    let id = engine.remember(req).await.unwrap().id;
+
+    let resp = engine.pin(PinRequest::new(id, true)).await.unwrap();
+    assert!(resp.pinned);
+    assert!(resp.changed);
+    let record = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert!(record.pinned);
+    assert!(record.expires_at.is_none());
+
+    let again = engine.pin(PinRequest::new(id, true)).await.unwrap();
+    assert!(!again.changed);
+
+    let resp = engine.pin(PinRequest::new(id, false)).await.unwrap();
+    assert!(!resp.pinned);
+    assert!(!engine.storage.get_memory(id).await.unwrap().unwrap().pinned);
+}
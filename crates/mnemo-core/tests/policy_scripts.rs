@@ -0,0 +1,116 @@
+//! Integration tests for Rhai policy scripts loaded from the engine config.
+
+use mnemo_core::config::{EmbeddingConfig, MnemoEngineConfig, ScriptHookConfig};
+use mnemo_core::error::Error;
+use mnemo_core::query::MnemoEngine;
+#[cfg(feature = "scripting")]
+use mnemo_core::query::remember::RememberRequest;
+
+const POLICY: &str = r#"
+fn pre_remember(memory) {
+    if memory.content.to_lower().contains("acme corp") {
+        throw "competitor names are not stored";
+    }
+    if memory.tags != () && "incident" in memory.tags {
+        memory.importance = 1.0;
+        return memory;
+    }
+}
+
+fn pre_forget(request) {
+    if request.strategy == "hard_delete" {
+        throw "hard deletes need a change ticket";
+    }
+}
+"#;
+
+fn config_with_script(dir: &std::path::Path, name: &str, source: &str) -> MnemoEngineConfig {
+    let path = dir.join(format!("{name}.rhai"));
+    std::fs::write(&path, source).unwrap();
+    let mut config = MnemoEngineConfig {
+        dimensions: 64,
+        embedding: EmbeddingConfig::Deterministic,
+        ..Default::default()
+    };
+    config.hooks.scripts.push(ScriptHookConfig {
+        path,
+        name: None,
+        priority: 0,
+        on_error: Default::default(),
+    });
+    config
+}
+
+#[test]
+fn scripts_deserialize_from_config() {
+    let config: MnemoEngineConfig = serde_json::from_value(serde_json::json!({
+        "hooks": { "scripts": [{ "path": "/etc/mnemo/policy.rhai", "priority": -1 }] }
+    }))
+    .unwrap();
+    assert_eq!(config.hooks.scripts.len(), 1);
+    assert_eq!(config.hooks.scripts[0].priority, -1);
+}
+
+#[cfg(feature = "scripting")]
+#[tokio::test]
+async fn policy_script_rejects_and_rewrites_writes() {
+    use mnemo_core::query::forget::{ForgetRequest, ForgetStrategy};
+
+    let dir = tempfile::tempdir().unwrap();
+    let engine = MnemoEngine::from_config(&config_with_script(dir.path(), "policy", POLICY))
+        .await
+        .unwrap();
+    assert_eq!(engine.hooks.names(), ["policy"]);
+
+    let err = engine
+        .remember(RememberRequest::new(
+            "ACME Corp is pitching the same customer".to_string(),
+        ))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Validation(ref m) if m.contains("competitor")));
+
+    let mut incident = RememberRequest::new("db failover at 02:14".to_string());
+    incident.tags = Some(vec!["incident".to_string()]);
+    incident.importance = Some(0.2);
+    let id = engine.remember(incident).await.unwrap().id;
+    let stored = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert_eq!(stored.importance, 1.0);
+
+    let mut hard = ForgetRequest::new(vec![id]);
+    hard.strategy = Some(ForgetStrategy::HardDelete);
+    assert!(matches!(
+        engine.forget(hard).await.unwrap_err(),
+        Error::Validation(_)
+    ));
+    assert!(engine.forget(ForgetRequest::new(vec![id])).await.is_ok());
+}
+
+#[cfg(feature = "scripting")]
+#[tokio::test]
+async fn runaway_scripts_are_stopped() {
+    let dir = tempfile::tempdir().unwrap();
+    let source = "fn pre_remember(memory) { loop { } }";
+    let engine = MnemoEngine::from_config(&config_with_script(dir.path(), "spin", source))
+        .await
+        .unwrap();
+    let err = engine
+        .remember(RememberRequest::new("anything".to_string()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Internal(_)));
+
+    let broken = config_with_script(dir.path(), "broken", "fn pre_remember(memory) {");
+    assert!(matches!(
+        MnemoEngine::from_config(&broken).await,
+        Err(Error::Validation(_))
+    ));
+}
+
+#[cfg(not(feature = "scripting"))]
+#[tokio::test]
+async fn scripts_need_the_feature() {
+    let dir = tempfile::tempdir().unwrap();
+    let result = MnemoEngine::from_config(&config_with_script(dir.path(), "policy", POLICY)).await;
+    assert!(matches!(result, Err(Error::Validation(_))));
+}
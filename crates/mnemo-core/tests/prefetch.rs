@@ -0,0 +1,96 @@
+//! Integration tests for thread prefetching.
+
+use std::sync::Arc;
+
+use mnemo_core::cache::MemoryCache;
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::checkpoint::CheckpointRequest;
+use mnemo_core::query::prefetch::PrefetchRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::query::replay::ReplayRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+fn create_engine() -> (MnemoEngine, Arc<MemoryCache>) {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    let cache = Arc::new(MemoryCache::new(300, 100));
+    let engine = MnemoEngine::new(storage, index, embedding, "planner".to_string(), None)
+        .with_cache(cache.clone());
+    (engine, cache)
+}
+
+async fn remember(engine: &MnemoEngine, thread: &str, content: &str, importance: f32) -> Uuid {
+    let mut request = RememberRequest::new(content.to_string());
+    request.thread_id = Some(thread.to_string());
+    request.importance = Some(importance);
+    engine.remember(request).await.unwrap().id
+}
+
+#[tokio::test]
+async fn prefetch_caches_important_and_recently_used_memories() {
+    let (engine, cache) = create_engine();
+    let mut ids = Vec::new();
+    for i in 0..8 {
+        let content = format!("sprint planning note {i}");
+        ids.push(remember(&engine, "sprint", &content, i as f32 / 10.0).await);
+    }
+    let other = remember(&engine, "retro", "the retro moved to friday", 1.0).await;
+    engine.storage.touch_memory(ids[0]).await.unwrap();
+    cache.clear();
+
+    let mut request = PrefetchRequest::new("sprint".to_string());
+    request.limit = Some(4);
+    let response = engine.prefetch(request).await.unwrap();
+    assert_eq!(response.cached, 4);
+    assert_eq!(cache.len(), 4);
+    // The two most important, then the most recently used.
+    assert!(cache.get(ids[7]).is_some());
+    assert!(cache.get(ids[6]).is_some());
+    assert!(cache.get(ids[0]).is_some());
+    assert!(cache.get(other).is_none());
+}
+
+#[tokio::test]
+async fn replaying_a_checkpoint_prefetches_its_thread() {
+    let (engine, cache) = create_engine();
+    let id = remember(&engine, "sprint", "ship the billing fix first", 0.9).await;
+    engine
+        .checkpoint(CheckpointRequest::new(
+            "sprint".to_string(),
+            serde_json::json!({"step": 1}),
+        ))
+        .await
+        .unwrap();
+    cache.clear();
+
+    engine
+        .replay(ReplayRequest::new("sprint".to_string()))
+        .await
+        .unwrap();
+    assert!(cache.get(id).is_some());
+}
+
+#[tokio::test]
+async fn prefetch_needs_access_to_the_thread() {
+    let (engine, _cache) = create_engine();
+    remember(&engine, "sprint", "ship the billing fix first", 0.9).await;
+    engine
+        .checkpoint(CheckpointRequest::new(
+            "sprint".to_string(),
+            serde_json::json!({"step": 1}),
+        ))
+        .await
+        .unwrap();
+
+    let mut request = PrefetchRequest::new("sprint".to_string());
+    request.agent_id = Some("outsider".to_string());
+    assert!(matches!(
+        engine.prefetch(request).await,
+        Err(Error::PermissionDenied(_))
+    ));
+}
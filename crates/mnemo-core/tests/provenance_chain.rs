@@ -46,6 +46,10 @@ async fn seed_three(engine: &MnemoEngine) {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             })
             .await
             .unwrap();
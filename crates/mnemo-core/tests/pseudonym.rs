@@ -0,0 +1,177 @@
+//! Integration tests for pseudonymous agent aliases in shared-scope
+//! recall and exports.
+
+use std::sync::Arc;
+
+use mnemo_core::config::{MnemoEngineConfig, PseudonymizationConfig};
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::memory::{MemoryRecord, Scope};
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::export::{ExportDestination, ExportSchedule};
+use mnemo_core::query::get_batch::GetBatchRequest;
+use mnemo_core::query::pseudonym::{ALIAS_PREFIX, Pseudonymizer};
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+const KEY: &str = "0101010101010101010101010101010101010101010101010101010101010101";
+const OTHER_KEY: &str = "0202020202020202020202020202020202020202020202020202020202020202";
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    let pseudonymizer = Pseudonymizer::from_hex(KEY)
+        .unwrap()
+        .with_orgs(["acme", "globex"]);
+    MnemoEngine::new(storage, index, embedding, "reader".to_string(), None)
+        .with_pseudonymizer(pseudonymizer)
+}
+
+async fn share(engine: &MnemoEngine, agent: &str, org: &str, content: &str) -> Uuid {
+    let mut request = RememberRequest::new(content.to_string());
+    request.agent_id = Some(agent.to_string());
+    request.org_id = Some(org.to_string());
+    request.scope = Some(Scope::Public);
+    engine.remember(request).await.unwrap().id
+}
+
+async fn authors(engine: &MnemoEngine, agent: &str, query: &str) -> Vec<String> {
+    let mut request = RecallRequest::new(query.to_string());
+    request.agent_id = Some(agent.to_string());
+    request.strategy = Some("semantic".to_string());
+    request.limit = Some(1);
+    engine
+        .recall(request)
+        .await
+        .unwrap()
+        .memories
+        .into_iter()
+        .map(|m| m.agent_id)
+        .collect()
+}
+
+#[tokio::test]
+async fn shared_hits_show_per_org_aliases_that_admins_can_reveal() {
+    let engine = create_engine();
+    share(&engine, "partner-bot", "acme", "acme ships on tuesdays").await;
+    share(&engine, "partner-bot", "globex", "globex ships on fridays").await;
+    share(
+        &engine,
+        "partner-bot",
+        "initech",
+        "initech ships on mondays",
+    )
+    .await;
+
+    let acme = authors(&engine, "reader", "acme ships on tuesdays").await;
+    let globex = authors(&engine, "reader", "globex ships on fridays").await;
+    assert!(acme[0].starts_with(ALIAS_PREFIX));
+    assert!(globex[0].starts_with(ALIAS_PREFIX));
+    // One agent, unrelated aliases across orgs; stable within an org.
+    assert_ne!(acme[0], globex[0]);
+    assert_eq!(
+        authors(&engine, "reader", "acme ships on tuesdays").await,
+        acme
+    );
+    // Orgs outside the policy and the author's own reads are unchanged.
+    assert_eq!(
+        authors(&engine, "reader", "initech ships on mondays").await,
+        ["partner-bot"]
+    );
+    assert_eq!(
+        authors(&engine, "partner-bot", "acme ships on tuesdays").await,
+        ["partner-bot"]
+    );
+
+    assert_eq!(
+        engine
+            .reveal_pseudonym(Some("acme"), &acme[0], KEY)
+            .await
+            .unwrap(),
+        "partner-bot"
+    );
+    assert!(matches!(
+        engine
+            .reveal_pseudonym(Some("acme"), &acme[0], OTHER_KEY)
+            .await,
+        Err(Error::PermissionDenied(_))
+    ));
+    // Aliases only resolve in the org that issued them.
+    assert!(matches!(
+        engine.reveal_pseudonym(Some("globex"), &acme[0], KEY).await,
+        Err(Error::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn reads_by_id_carry_the_alias_recall_shows() {
+    let engine = create_engine();
+    let id = share(&engine, "partner-bot", "acme", "acme ships on tuesdays").await;
+    let alias = authors(&engine, "reader", "acme ships on tuesdays").await[0].clone();
+
+    let mut batch = GetBatchRequest::new(vec![id]);
+    batch.agent_id = Some("reader".to_string());
+    let fetched = engine.get_batch(batch).await.unwrap();
+    assert_eq!(fetched.memories[0].agent_id, alias);
+
+    let mut record = engine.storage.get_memory(id).await.unwrap().unwrap();
+    engine
+        .alias_for_reader("reader", &mut record)
+        .await
+        .unwrap();
+    assert_eq!(record.agent_id, alias);
+
+    // The author reads its own memory unaliased.
+    let mut own = engine.storage.get_memory(id).await.unwrap().unwrap();
+    engine
+        .alias_for_reader("partner-bot", &mut own)
+        .await
+        .unwrap();
+    assert_eq!(own.agent_id, "partner-bot");
+}
+
+#[tokio::test]
+async fn exports_carry_aliases() {
+    let dir = tempfile::tempdir().unwrap();
+    let schedule = ExportSchedule::new(
+        "partners",
+        "@daily",
+        ExportDestination::Filesystem {
+            path: dir.path().to_path_buf(),
+        },
+    );
+    let engine = create_engine().with_exports(vec![schedule]);
+    share(&engine, "partner-bot", "acme", "acme ships on tuesdays").await;
+
+    let run = engine.run_export("partners").await.unwrap();
+    let text = std::fs::read_to_string(run.location.unwrap()).unwrap();
+    let record: MemoryRecord = serde_json::from_str(text.lines().next().unwrap()).unwrap();
+    assert!(record.agent_id.starts_with(ALIAS_PREFIX));
+    assert_ne!(record.created_by.as_deref(), Some("partner-bot"));
+    assert_eq!(
+        engine
+            .reveal_pseudonym(Some("acme"), &record.agent_id, KEY)
+            .await
+            .unwrap(),
+        "partner-bot"
+    );
+}
+
+#[test]
+fn config_rejects_malformed_keys() {
+    let mut config = MnemoEngineConfig::default();
+    config.features.pseudonymization = Some(PseudonymizationConfig {
+        key: "abcd".to_string(),
+        orgs: Vec::new(),
+    });
+    assert!(config.validate().is_err());
+    config.features.pseudonymization = Some(PseudonymizationConfig {
+        key: KEY.to_string(),
+        orgs: Vec::new(),
+    });
+    assert!(config.validate().is_ok());
+}
@@ -0,0 +1,200 @@
+//! Integration tests for the engine's read-only mode.
+
+use std::sync::Arc;
+
+use mnemo_core::config::{EmbeddingConfig, MnemoEngineConfig};
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::agent_profile::RetrievalDefaults;
+use mnemo_core::model::recall_profile::RecallProfileSettings;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::change_feed::MemoryChange;
+use mnemo_core::query::checkpoint::CheckpointRequest;
+use mnemo_core::query::compression::CompressionRequest;
+use mnemo_core::query::conflict_queue::{ConflictDecision, ResolveQueuedConflictRequest};
+use mnemo_core::query::forget::ForgetRequest;
+use mnemo_core::query::lifecycle::CleanupRequest;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::recall_profile::SaveRecallProfileRequest;
+use mnemo_core::query::reflection::ReflectionMode;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::query::replay::ReplayRequest;
+use mnemo_core::query::share::ShareRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "ro-agent".to_string(), None)
+}
+
+#[tokio::test]
+async fn writes_fail_and_reads_work_while_read_only() {
+    let engine = create_engine();
+    let id = engine
+        .remember(RememberRequest::new(
+            "the build cache lives in s3".to_string(),
+        ))
+        .await
+        .unwrap()
+        .id;
+    engine
+        .checkpoint(CheckpointRequest::new(
+            "ro-thread".to_string(),
+            serde_json::json!({"step": 1}),
+        ))
+        .await
+        .unwrap();
+
+    engine.set_read_only(true);
+    let err = engine
+        .remember(RememberRequest::new("blocked".to_string()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::ReadOnly(_)), "{err}");
+    let err = engine
+        .forget(ForgetRequest::new(vec![id]))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::ReadOnly(_)));
+    let err = engine
+        .share(ShareRequest::new(id, "other-agent".to_string()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::ReadOnly(_)));
+    let err = engine
+        .checkpoint(CheckpointRequest::new(
+            "ro-thread".to_string(),
+            serde_json::json!({"step": 2}),
+        ))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::ReadOnly(_)));
+
+    let recalled = engine
+        .recall(RecallRequest::new("build cache".to_string()))
+        .await
+        .unwrap();
+    assert!(recalled.memories.iter().any(|m| m.id == id));
+    engine
+        .replay(ReplayRequest::new("ro-thread".to_string()))
+        .await
+        .unwrap();
+
+    engine.set_read_only(false);
+    engine
+        .remember(RememberRequest::new("writes are back".to_string()))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn maintenance_and_settings_writes_fail_while_read_only() {
+    let engine = create_engine();
+    let id = engine
+        .remember(RememberRequest::new("kept while frozen".to_string()))
+        .await
+        .unwrap()
+        .id;
+    let snapshot = engine.index_snapshot().unwrap();
+
+    engine.set_read_only(true);
+    let results = [
+        engine
+            .run_compression(CompressionRequest::default())
+            .await
+            .map(drop),
+        engine
+            .resolve_queued_conflict(ResolveQueuedConflictRequest::new(
+                uuid::Uuid::now_v7(),
+                ConflictDecision::Dismiss,
+            ))
+            .await
+            .map(drop),
+        engine.queue_conflicts(None, 0.9).await.map(drop),
+        engine.run_decay_pass(None, 0.2, 0.1).await.map(drop),
+        engine.run_consolidation(None, 2).await.map(drop),
+        engine.run_ttl_sweep().await.map(drop),
+        engine.run_cleanup().await.map(drop),
+        engine.run_trash_purge().await.map(drop),
+        engine
+            .save_recall_profile(SaveRecallProfileRequest::new(
+                "frozen".to_string(),
+                RecallProfileSettings::default(),
+            ))
+            .await
+            .map(drop),
+        engine.delete_recall_profile(None, "frozen").await,
+        engine
+            .set_agent_retrieval_defaults("ro-agent", RetrievalDefaults::default())
+            .await
+            .map(drop),
+        engine
+            .restore_event_archive(uuid::Uuid::now_v7())
+            .await
+            .map(drop),
+        engine.restore_index_snapshot(&snapshot),
+    ];
+    for (i, result) in results.into_iter().enumerate() {
+        let err = result.unwrap_err();
+        assert!(matches!(err, Error::ReadOnly(_)), "call {i}: {err}");
+    }
+
+    // Following writes made elsewhere only refreshes local state.
+    engine
+        .apply_memory_change(MemoryChange::Updated(id))
+        .await
+        .unwrap();
+
+    // Dry runs only report, so they still work.
+    let mut request = CleanupRequest::new();
+    request.dry_run = Some(true);
+    assert!(engine.run_cleanup_with(request).await.unwrap().dry_run);
+}
+
+#[tokio::test]
+async fn background_passes_fail_while_read_only() {
+    let engine = create_engine();
+    engine
+        .remember(RememberRequest::new("kept while frozen".to_string()))
+        .await
+        .unwrap();
+
+    engine.set_read_only(true);
+    let results = [
+        engine.run_url_refresh().await.map(drop),
+        engine.run_reflection_pass(None).await.map(drop),
+        engine
+            .run_reflection_pass_with_mode(None, ReflectionMode::Always, true)
+            .await
+            .map(drop),
+        engine.run_auto_checkpoints().await.map(drop),
+        engine.run_scheduled_exports().await.map(drop),
+        engine.run_export("nightly").await.map(drop),
+        engine.run_write_anomaly_pass().await.map(drop),
+        engine.detect_conflicts(None, 0.9).await.map(drop),
+    ];
+    for (i, result) in results.into_iter().enumerate() {
+        let err = result.unwrap_err();
+        assert!(matches!(err, Error::ReadOnly(_)), "call {i}: {err}");
+    }
+}
+
+#[tokio::test]
+async fn config_can_start_read_only() {
+    let config = MnemoEngineConfig {
+        dimensions: 64,
+        embedding: EmbeddingConfig::Deterministic,
+        read_only: true,
+        ..Default::default()
+    };
+    let engine = MnemoEngine::from_config(&config).await.unwrap();
+    assert!(engine.is_read_only());
+    let err = engine
+        .remember(RememberRequest::new("blocked".to_string()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::ReadOnly(_)));
+}
@@ -0,0 +1,137 @@
+//! Integration tests for saved recall profiles: CRUD, server-side expansion
+//! of `RecallRequest::profile`, precedence of explicit fields and usage
+//! stats.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::NoopEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::recall_profile::RecallProfileSettings;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::recall_profile::{SaveRecallProfileRequest, apply};
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::search::tantivy_index::TantivyFullTextIndex;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(128).unwrap());
+    let embedding = Arc::new(NoopEmbedding::new(128));
+    let full_text = Arc::new(TantivyFullTextIndex::open_in_memory().unwrap());
+    MnemoEngine::new(storage, index, embedding, "profile-agent".to_string(), None)
+        .with_full_text(full_text)
+}
+
+fn support_settings() -> RecallProfileSettings {
+    RecallProfileSettings {
+        strategy: Some("lexical".to_string()),
+        limit: Some(1),
+        tags: Some(vec!["support".to_string()]),
+        ..Default::default()
+    }
+}
+
+async fn remember_tagged(engine: &MnemoEngine, content: &str, tag: &str) {
+    let mut req = RememberRequest::new(content.to_string());
+    req.tags = Some(vec![tag.to_string()]);
+    engine.remember(req).await.unwrap();
+}
+
+#[tokio::test]
+async fn save_get_list_delete_roundtrip() {
+    let engine = create_engine();
+    let saved = engine
+        .save_recall_profile(SaveRecallProfileRequest::new(
+            "support-context".to_string(),
+            support_settings(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(saved.use_count, 0);
+
+    let fetched = engine
+        .get_recall_profile(None, "support-context")
+        .await
+        .unwrap();
+    assert_eq!(fetched.settings, support_settings());
+
+    let listed = engine.list_recall_profiles(None).await.unwrap();
+    assert_eq!(listed.len(), 1);
+
+    engine
+        .delete_recall_profile(None, "support-context")
+        .await
+        .unwrap();
+    let err = engine
+        .get_recall_profile(None, "support-context")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::NotFound(_)));
+}
+
+#[tokio::test]
+async fn profile_expands_server_side_and_counts_uses() {
+    let engine = create_engine();
+    remember_tagged(&engine, "Customer reported a billing error", "support").await;
+    remember_tagged(
+        &engine,
+        "Billing error fixed in the invoice job",
+        "engineering",
+    )
+    .await;
+    engine
+        .save_recall_profile(SaveRecallProfileRequest::new(
+            "support-context".to_string(),
+            support_settings(),
+        ))
+        .await
+        .unwrap();
+
+    let mut req = RecallRequest::new("billing error".to_string());
+    req.profile = Some("support-context".to_string());
+    let response = engine.recall(req).await.unwrap();
+    assert_eq!(response.memories.len(), 1);
+    assert_eq!(response.memories[0].tags, vec!["support".to_string()]);
+
+    let profile = engine
+        .get_recall_profile(None, "support-context")
+        .await
+        .unwrap();
+    assert_eq!(profile.use_count, 1);
+    assert!(profile.last_used_at.is_some());
+}
+
+#[tokio::test]
+async fn unknown_profile_is_not_found() {
+    let engine = create_engine();
+    let mut req = RecallRequest::new("anything".to_string());
+    req.profile = Some("missing".to_string());
+    let err = engine.recall(req).await.unwrap_err();
+    assert!(matches!(err, Error::NotFound(_)));
+}
+
+#[tokio::test]
+async fn invalid_profile_name_is_rejected() {
+    let engine = create_engine();
+    let err = engine
+        .save_recall_profile(SaveRecallProfileRequest::new(
+            "has spaces".to_string(),
+            RecallProfileSettings::default(),
+        ))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Validation(_)));
+}
+
+#[test]
+fn explicit_request_fields_win_over_profile() {
+    let mut req = RecallRequest::new("q".to_string());
+    req.limit = Some(7);
+    req.strategy = Some("exact".to_string());
+    apply(&support_settings(), &mut req);
+    assert_eq!(req.limit, Some(7));
+    assert_eq!(req.strategy.as_deref(), Some("exact"));
+    assert_eq!(req.tags, Some(vec!["support".to_string()]));
+}
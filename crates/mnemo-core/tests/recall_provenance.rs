@@ -0,0 +1,139 @@
+//! Integration tests for the `include=provenance` recall expansion:
+//! derived memories carry the chain of source memories and events they
+//! came from, with hashes.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::NoopEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::relation::{CONSOLIDATED_FROM, DERIVED_FROM};
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::consolidate::ConsolidateRequest;
+use mnemo_core::query::lineage::INCLUDE_PROVENANCE;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::query::turn::{TURN_FACT_TAG, TurnRequest};
+use mnemo_core::search::tantivy_index::TantivyFullTextIndex;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(128).unwrap());
+    let embedding = Arc::new(NoopEmbedding::new(128));
+    let full_text = Arc::new(TantivyFullTextIndex::open_in_memory().unwrap());
+    MnemoEngine::new(storage, index, embedding, "lineage-agent".to_string(), None)
+        .with_full_text(full_text)
+}
+
+fn provenance_recall(query: &str, tag: &str) -> RecallRequest {
+    let mut req = RecallRequest::new(query.to_string());
+    req.strategy = Some("lexical".to_string());
+    req.tags = Some(vec![tag.to_string()]);
+    req.include = Some(vec![INCLUDE_PROVENANCE.to_string()]);
+    req
+}
+
+#[tokio::test]
+async fn consolidated_memory_lists_its_members_with_hashes() {
+    let engine = create_engine();
+    let m1 = engine
+        .remember(RememberRequest::new(
+            "Acme signed the MSA in January".to_string(),
+        ))
+        .await
+        .unwrap()
+        .id;
+    let m2 = engine
+        .remember(RememberRequest::new(
+            "Acme renews the MSA yearly".to_string(),
+        ))
+        .await
+        .unwrap()
+        .id;
+    let doc = engine
+        .consolidate(ConsolidateRequest::new(vec![m1, m2], "acme".to_string()))
+        .await
+        .unwrap();
+
+    let response = engine
+        .recall(provenance_recall("Acme MSA", "acme"))
+        .await
+        .unwrap();
+    let hit = &response.memories[0];
+    assert_eq!(hit.id, doc.topic_document_id);
+
+    let chain = hit.provenance_chain.as_ref().unwrap();
+    let stored = engine.storage.get_memory(hit.id).await.unwrap().unwrap();
+    assert_eq!(chain.content_hash, hex::encode(&stored.content_hash));
+    assert_eq!(chain.derived_from.len(), 2);
+    for node in &chain.derived_from {
+        assert_eq!(node.relation_type, CONSOLIDATED_FROM);
+        let member = engine
+            .storage
+            .get_memory(node.memory_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(node.content_hash, hex::encode(&member.content_hash));
+        assert_eq!(node.content.as_deref(), Some(member.content.as_str()));
+    }
+}
+
+#[tokio::test]
+async fn extracted_fact_traces_back_to_turn_and_event() {
+    let engine = create_engine();
+    let mut turn = TurnRequest::new(
+        "I live in Berlin and work remotely.".to_string(),
+        "Noted.".to_string(),
+    );
+    turn.extract_facts = Some(true);
+    let turn = engine.capture_turn(turn).await.unwrap();
+
+    let response = engine
+        .recall(provenance_recall("Berlin", TURN_FACT_TAG))
+        .await
+        .unwrap();
+    let chain = response.memories[0].provenance_chain.as_ref().unwrap();
+    assert_eq!(
+        chain.source_event.as_ref().unwrap().event_id,
+        turn.user_event_id
+    );
+    let source = &chain.derived_from[0];
+    assert_eq!(source.relation_type, DERIVED_FROM);
+    assert_eq!(source.memory_id, turn.memory_id);
+    assert_eq!(
+        source.source_event.as_ref().unwrap().event_id,
+        turn.assistant_event_id
+    );
+}
+
+#[tokio::test]
+async fn provenance_is_omitted_unless_requested() {
+    let engine = create_engine();
+    let mut req = RememberRequest::new("Standalone note about espresso".to_string());
+    req.tags = Some(vec!["note".to_string()]);
+    engine.remember(req).await.unwrap();
+
+    let mut recall = provenance_recall("espresso", "note");
+    recall.include = None;
+    let response = engine.recall(recall).await.unwrap();
+    assert!(response.memories[0].provenance_chain.is_none());
+
+    let response = engine
+        .recall(provenance_recall("espresso", "note"))
+        .await
+        .unwrap();
+    let chain = response.memories[0].provenance_chain.as_ref().unwrap();
+    assert!(chain.derived_from.is_empty());
+}
+
+#[tokio::test]
+async fn unknown_include_is_rejected() {
+    let engine = create_engine();
+    let mut req = RecallRequest::new("anything".to_string());
+    req.strategy = Some("lexical".to_string());
+    req.include = Some(vec!["everything".to_string()]);
+    let err = engine.recall(req).await.unwrap_err();
+    assert!(matches!(err, Error::Validation(_)));
+}
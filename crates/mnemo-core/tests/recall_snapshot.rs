@@ -0,0 +1,124 @@
+//! Integration tests for recall read versions under concurrent writes.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use mnemo_core::embedding::{DeterministicEmbedding, EmbeddingProvider};
+use mnemo_core::error::Result as MnemoResult;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::forget::{ForgetRequest, ForgetStrategy};
+use mnemo_core::query::recall::{RecallRequest, RecallResponse};
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use tokio::sync::Notify;
+use uuid::Uuid;
+
+const GATED_QUERY: &str = "vpn certificate";
+
+/// Deterministic embeddings that park recall of [`GATED_QUERY`] inside the
+/// engine until released.
+struct GatedEmbedding {
+    inner: DeterministicEmbedding,
+    entered: Notify,
+    release: Notify,
+}
+
+#[async_trait]
+impl EmbeddingProvider for GatedEmbedding {
+    async fn embed(&self, text: &str) -> MnemoResult<Vec<f32>> {
+        if text == GATED_QUERY {
+            self.entered.notify_one();
+            self.release.notified().await;
+        }
+        self.inner.embed(text).await
+    }
+    async fn embed_batch(&self, texts: &[&str]) -> MnemoResult<Vec<Vec<f32>>> {
+        self.inner.embed_batch(texts).await
+    }
+    fn dimensions(&self) -> usize {
+        self.inner.dimensions()
+    }
+}
+
+fn create_engine() -> (Arc<MnemoEngine>, Arc<GatedEmbedding>) {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(GatedEmbedding {
+        inner: DeterministicEmbedding::new(64),
+        entered: Notify::new(),
+        release: Notify::new(),
+    });
+    let engine = MnemoEngine::new(storage, index, embedding.clone(), "ops".to_string(), None);
+    (Arc::new(engine), embedding)
+}
+
+async fn remember(engine: &MnemoEngine, content: &str) -> Uuid {
+    engine
+        .remember(RememberRequest::new(content.to_string()))
+        .await
+        .unwrap()
+        .id
+}
+
+/// Start a recall of [`GATED_QUERY`] and return once it holds its read
+/// version.
+async fn start_recall(
+    engine: &Arc<MnemoEngine>,
+    gate: &GatedEmbedding,
+) -> tokio::task::JoinHandle<RecallResponse> {
+    let engine = engine.clone();
+    let recall = tokio::spawn(async move {
+        let mut request = RecallRequest::new(GATED_QUERY.to_string());
+        request.strategy = Some("semantic".to_string());
+        engine.recall(request).await.unwrap()
+    });
+    gate.entered.notified().await;
+    recall
+}
+
+fn ids(response: &RecallResponse) -> Vec<Uuid> {
+    response.memories.iter().map(|m| m.id).collect()
+}
+
+#[tokio::test]
+async fn hard_deletes_do_not_wait_for_the_query_embedding() {
+    let (engine, gate) = create_engine();
+    let id = remember(&engine, "the vpn certificate expires in june").await;
+
+    // Recalls hold no lock, so the delete goes through and the recall
+    // finds the memory gone.
+    let recall = start_recall(&engine, &gate).await;
+    let mut request = ForgetRequest::new(vec![id]);
+    request.strategy = Some(ForgetStrategy::HardDelete);
+    let forgotten = tokio::time::timeout(Duration::from_secs(5), engine.forget(request))
+        .await
+        .expect("hard delete waited for the embedding")
+        .unwrap();
+    assert_eq!(forgotten.forgotten, [id]);
+
+    gate.release.notify_one();
+    assert!(ids(&recall.await.unwrap()).is_empty());
+    assert!(engine.storage.get_memory(id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn writes_after_the_read_version_are_invisible() {
+    let (engine, gate) = create_engine();
+    let old = remember(&engine, "the vpn certificate expires in june").await;
+
+    let recall = start_recall(&engine, &gate).await;
+    let new = remember(&engine, "the vpn certificate was renewed").await;
+    gate.release.notify_one();
+    assert_eq!(ids(&recall.await.unwrap()), [old]);
+
+    // The next recall reads at a later version and sees both.
+    let next = start_recall(&engine, &gate).await;
+    gate.release.notify_one();
+    let mut seen = ids(&next.await.unwrap());
+    seen.sort();
+    let mut both = vec![old, new];
+    both.sort();
+    assert_eq!(seen, both);
+}
@@ -0,0 +1,86 @@
+//! Integration tests for `RecallRequest::with_snippets`: BM25 hits carry a
+//! highlighted fragment, other hits fall back to the leading chunk.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::NoopEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::recall::{RecallRequest, SNIPPET_MAX_CHARS, SnippetSource};
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::search::tantivy_index::TantivyFullTextIndex;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(128).unwrap());
+    let embedding = Arc::new(NoopEmbedding::new(128));
+    let full_text = Arc::new(TantivyFullTextIndex::open_in_memory().unwrap());
+    MnemoEngine::new(storage, index, embedding, "snippet-agent".to_string(), None)
+        .with_full_text(full_text)
+}
+
+async fn remember(engine: &MnemoEngine, content: &str) {
+    engine
+        .remember(RememberRequest::new(content.to_string()))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn lexical_hits_carry_highlighted_fragment() {
+    let engine = create_engine();
+    remember(
+        &engine,
+        "The user drinks a double espresso every morning before standup",
+    )
+    .await;
+
+    let mut req = RecallRequest::new("espresso".to_string());
+    req.strategy = Some("lexical".to_string());
+    req.with_snippets = Some(true);
+    let response = engine.recall(req).await.unwrap();
+
+    let snippets = response.memories[0].snippets.as_ref().unwrap();
+    assert_eq!(snippets.len(), 1);
+    let snippet = &snippets[0];
+    assert_eq!(snippet.source, SnippetSource::Bm25);
+    let marked: Vec<&str> = snippet
+        .highlights
+        .iter()
+        .map(|(s, e)| &snippet.fragment[*s..*e])
+        .collect();
+    assert_eq!(marked, vec!["espresso"]);
+}
+
+#[tokio::test]
+async fn snippets_are_omitted_unless_requested() {
+    let engine = create_engine();
+    remember(&engine, "The user drinks a double espresso every morning").await;
+
+    let mut req = RecallRequest::new("espresso".to_string());
+    req.strategy = Some("lexical".to_string());
+    let response = engine.recall(req).await.unwrap();
+
+    assert!(response.memories[0].snippets.is_none());
+    let json = serde_json::to_value(&response.memories[0]).unwrap();
+    assert!(json.get("snippets").is_none());
+}
+
+#[tokio::test]
+async fn non_lexical_hits_fall_back_to_leading_chunk() {
+    let engine = create_engine();
+    let long = "quarterly planning notes ".repeat(20);
+    remember(&engine, &long).await;
+
+    let mut req = RecallRequest::new("roadmap".to_string());
+    req.strategy = Some("exact".to_string());
+    req.with_snippets = Some(true);
+    let response = engine.recall(req).await.unwrap();
+
+    let snippets = response.memories[0].snippets.as_ref().unwrap();
+    assert_eq!(snippets[0].source, SnippetSource::Vector);
+    assert!(snippets[0].highlights.is_empty());
+    assert_eq!(snippets[0].fragment.chars().count(), SNIPPET_MAX_CHARS);
+    assert!(long.starts_with(&snippets[0].fragment));
+}
@@ -0,0 +1,109 @@
+//! Integration tests for rebuilding memory hash chains after an import.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::event::EventType;
+use mnemo_core::model::memory::MemoryRecord;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::rechain::RechainRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "agent".to_string(), None)
+}
+
+/// Store memories the way an import would: no chain links and, for the
+/// second one, a content hash computed some other way.
+async fn import(engine: &MnemoEngine, thread_id: &str) {
+    for day in 1..=3 {
+        let mut record = MemoryRecord::new(
+            "agent".to_string(),
+            format!("{thread_id} imported note {day}"),
+        );
+        record.thread_id = Some(thread_id.to_string());
+        record.created_at = format!("2025-01-0{day}T00:00:00+00:00");
+        if day == 2 {
+            record.content_hash = vec![0; 32];
+        }
+        record.prev_hash = None;
+        engine.storage.insert_memory(&record).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn rechain_makes_imported_chains_verifiable() {
+    let engine = create_engine();
+    import(&engine, "t1").await;
+    import(&engine, "t2").await;
+    assert!(
+        !engine
+            .verify_integrity(None, Some("t1"))
+            .await
+            .unwrap()
+            .valid
+    );
+
+    let response = engine.rechain(RechainRequest::new()).await.unwrap();
+    assert_eq!(response.chains.len(), 2);
+    for chain in &response.chains {
+        assert_eq!(chain.records, 3);
+        assert_eq!(chain.relinked, 3);
+        assert_ne!(chain.before_root, chain.after_root);
+        assert!(chain.event_id.is_some());
+    }
+    for thread in ["t1", "t2"] {
+        let result = engine.verify_integrity(None, Some(thread)).await.unwrap();
+        assert!(result.valid, "{thread}: {:?}", result.error_message);
+        assert_eq!(result.verified_records, 3);
+    }
+
+    let events = engine.storage.list_events("agent", 10, 0).await.unwrap();
+    let rebuilt: Vec<_> = events
+        .iter()
+        .filter(|e| e.event_type == EventType::ChainRebuilt)
+        .collect();
+    assert_eq!(rebuilt.len(), 2);
+    assert_eq!(rebuilt[0].payload["after_root"].as_str().unwrap().len(), 64);
+
+    // A second pass finds nothing to fix.
+    let again = engine.rechain(RechainRequest::new()).await.unwrap();
+    assert!(
+        again
+            .chains
+            .iter()
+            .all(|c| c.relinked == 0 && c.event_id.is_none())
+    );
+    assert_eq!(again.chains[0].after_root, response.chains[0].after_root);
+}
+
+#[tokio::test]
+async fn rechain_can_target_one_thread() {
+    let engine = create_engine();
+    import(&engine, "t1").await;
+    import(&engine, "t2").await;
+
+    let mut request = RechainRequest::new();
+    request.thread_id = Some("t2".to_string());
+    let response = engine.rechain(request).await.unwrap();
+    assert_eq!(response.chains.len(), 1);
+    assert_eq!(response.chains[0].thread_id.as_deref(), Some("t2"));
+    assert!(
+        engine
+            .verify_integrity(None, Some("t2"))
+            .await
+            .unwrap()
+            .valid
+    );
+    assert!(
+        !engine
+            .verify_integrity(None, Some("t1"))
+            .await
+            .unwrap()
+            .valid
+    );
+}
@@ -0,0 +1,69 @@
+//! Integration tests for splitting a replay into playback frames.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::event::{AgentEvent, EventType};
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::checkpoint::CheckpointRequest;
+use mnemo_core::query::replay::{ReplayFrame, ReplayRequest};
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use serde_json::json;
+
+#[tokio::test]
+async fn frames_start_with_the_state_and_follow_the_logical_clock() {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    let engine = MnemoEngine::new(storage, index, embedding, "player".to_string(), None);
+    engine
+        .checkpoint(CheckpointRequest::new(
+            "playback".to_string(),
+            json!({"step": 1}),
+        ))
+        .await
+        .unwrap();
+
+    let mut replay = engine
+        .replay(ReplayRequest::new("playback".to_string()))
+        .await
+        .unwrap();
+    replay.events = [3, 1, 2]
+        .into_iter()
+        .map(|clock| {
+            let mut event = AgentEvent::new(
+                "player".to_string(),
+                EventType::MemoryWrite,
+                json!({ "clock": clock }),
+                chrono::Utc::now().to_rfc3339(),
+                Vec::new(),
+            );
+            event.logical_clock = clock;
+            event
+        })
+        .collect();
+
+    let frames = replay.into_frames();
+    let ReplayFrame::State {
+        ref checkpoint,
+        event_count,
+        ..
+    } = frames[0]
+    else {
+        panic!("the first frame must be the state");
+    };
+    assert_eq!(checkpoint.state_snapshot, json!({"step": 1}));
+    assert_eq!(event_count, 3);
+    let clocks: Vec<i64> = frames[1..]
+        .iter()
+        .map(|frame| match frame {
+            ReplayFrame::Event { event } => event.logical_clock,
+            ReplayFrame::State { .. } => panic!("only one state frame"),
+        })
+        .collect();
+    assert_eq!(clocks, [1, 2, 3]);
+
+    let encoded = serde_json::to_value(&frames[1]).unwrap();
+    assert_eq!(encoded["type"], "event");
+}
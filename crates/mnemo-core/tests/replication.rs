@@ -0,0 +1,99 @@
+//! Integration tests for multi-region replication through `SyncEngine`.
+
+use std::sync::Arc;
+
+use mnemo_core::model::memory::MemoryRecord;
+use mnemo_core::storage::StorageBackend;
+use mnemo_core::storage::memory::InMemoryStorage;
+use mnemo_core::sync::{ReplicationOrigin, SyncEngine};
+
+const EPOCH: &str = "1970-01-01T00:00:00Z";
+
+fn record(content: &str) -> MemoryRecord {
+    let mut record = MemoryRecord::new("planner".to_string(), content.to_string());
+    record.updated_at = chrono::Utc::now().to_rfc3339();
+    record
+}
+
+#[tokio::test]
+async fn replicated_records_carry_their_origin_and_are_not_echoed_back() {
+    let eu: Arc<dyn StorageBackend> = Arc::new(InMemoryStorage::new());
+    let us: Arc<dyn StorageBackend> = Arc::new(InMemoryStorage::new());
+    let at_eu = SyncEngine::new(eu.clone(), us.clone()).with_regions("eu", "us");
+    let at_us = SyncEngine::new(us.clone(), eu.clone()).with_regions("us", "eu");
+
+    let written = record("the eu cluster is the primary for invoices");
+    eu.insert_memory(&written).await.unwrap();
+    assert_eq!(at_eu.push(EPOCH).await.unwrap(), 1);
+
+    let copy = us.get_memory(written.id).await.unwrap().unwrap();
+    assert_eq!(ReplicationOrigin::of(&copy).unwrap().region, "eu");
+
+    // Neither side sends the version back to eu.
+    assert_eq!(at_us.push(EPOCH).await.unwrap(), 0);
+    assert_eq!(at_eu.pull(EPOCH).await.unwrap(), 0);
+    assert_eq!(at_us.metrics().echoes_skipped, 1);
+
+    // An edit in us is a new version and replicates to eu, once.
+    let mut edited = copy.clone();
+    edited.content = "the us cluster is the primary for invoices".to_string();
+    edited.updated_at = chrono::Utc::now().to_rfc3339();
+    us.upsert_memory(&edited).await.unwrap();
+    assert_eq!(at_eu.pull(EPOCH).await.unwrap(), 1);
+
+    let local = eu.get_memory(written.id).await.unwrap().unwrap();
+    assert_eq!(local.content, edited.content);
+    assert_eq!(ReplicationOrigin::of(&local).unwrap().region, "us");
+    assert_eq!(at_eu.push(EPOCH).await.unwrap(), 0);
+
+    let metrics = at_eu.metrics();
+    assert_eq!(metrics.pushed, 1);
+    assert_eq!(metrics.pulled, 1);
+    assert_eq!(metrics.push_lag_ms, Some(0));
+    assert!(metrics.last_pull_at.is_some());
+}
+
+#[tokio::test]
+async fn watermarks_are_kept_per_remote_region() {
+    let eu: Arc<dyn StorageBackend> = Arc::new(InMemoryStorage::new());
+    let us: Arc<dyn StorageBackend> = Arc::new(InMemoryStorage::new());
+    let ap: Arc<dyn StorageBackend> = Arc::new(InMemoryStorage::new());
+    eu.insert_memory(&record("rotate the signing key quarterly"))
+        .await
+        .unwrap();
+
+    let to_us = SyncEngine::new(eu.clone(), us.clone()).with_regions("eu", "us");
+    assert_eq!(to_us.push(EPOCH).await.unwrap(), 1);
+    assert!(
+        eu.get_sync_watermark("push_watermark:us")
+            .await
+            .unwrap()
+            .is_some()
+    );
+
+    // A push to another region starts from its own watermark.
+    let to_ap = SyncEngine::new(eu.clone(), ap.clone()).with_regions("eu", "ap");
+    assert_eq!(to_ap.push(EPOCH).await.unwrap(), 1);
+    assert_eq!(to_us.push(EPOCH).await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn conflicts_name_the_region_of_each_version() {
+    let eu: Arc<dyn StorageBackend> = Arc::new(InMemoryStorage::new());
+    let us: Arc<dyn StorageBackend> = Arc::new(InMemoryStorage::new());
+    let sync = SyncEngine::new(eu.clone(), us.clone()).with_regions("eu", "us");
+
+    let written = record("invoices are archived after 7 years");
+    eu.insert_memory(&written).await.unwrap();
+    let mut theirs = written.clone();
+    theirs.content = "invoices are archived after 10 years".to_string();
+    theirs.updated_at = chrono::Utc::now().to_rfc3339();
+    us.insert_memory(&theirs).await.unwrap();
+
+    let result = sync.full_sync(EPOCH).await.unwrap();
+    assert_eq!(result.conflicts.len(), 1);
+    let conflict = &result.conflicts[0];
+    assert_eq!(conflict.local_region.as_deref(), Some("eu"));
+    assert_eq!(conflict.remote_region.as_deref(), Some("us"));
+    assert_eq!(sync.metrics().conflicts, 1);
+}
@@ -0,0 +1,69 @@
+//! Integration tests for engine-level timestamp and id validation.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::recall::{RecallRequest, TemporalRange};
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::query::replay::ReplayRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine() -> MnemoEngine {
+    MnemoEngine::new(
+        Arc::new(DuckDbStorage::open_in_memory().unwrap()),
+        Arc::new(UsearchIndex::new(64).unwrap()),
+        Arc::new(DeterministicEmbedding::new(64)),
+        "test-agent".to_string(),
+        None,
+    )
+}
+
+fn paths(err: Error) -> Vec<String> {
+    match err {
+        Error::InvalidFields(violations) => violations.into_iter().map(|v| v.path).collect(),
+        other => panic!("expected invalid fields, got {other}"),
+    }
+}
+
+#[tokio::test]
+async fn recall_rejects_bad_timestamps_by_field() {
+    let engine = create_engine();
+    engine
+        .remember(RememberRequest::new("the deploy runs at noon".to_string()))
+        .await
+        .unwrap();
+
+    let mut request = RecallRequest::new("deploy".to_string());
+    request.as_of = Some("last tuesday".to_string());
+    assert_eq!(paths(engine.recall(request).await.unwrap_err()), ["as_of"]);
+
+    let mut request = RecallRequest::new("deploy".to_string());
+    request.temporal_range = Some(TemporalRange {
+        after: Some("2026-02-01T00:00:00Z".to_string()),
+        before: Some("2026-01-01T00:00:00Z".to_string()),
+    });
+    assert_eq!(
+        paths(engine.recall(request).await.unwrap_err()),
+        ["temporal_range.before"]
+    );
+
+    // An offset is accepted and means the same instant in UTC.
+    let mut request = RecallRequest::new("deploy".to_string());
+    request.temporal_range = Some(TemporalRange {
+        after: Some("2000-01-01T02:00:00+02:00".to_string()),
+        before: None,
+    });
+    let response = engine.recall(request).await.unwrap();
+    assert_eq!(response.memories.len(), 1);
+}
+
+#[tokio::test]
+async fn replay_rejects_a_bad_as_of() {
+    let engine = create_engine();
+    let mut request = ReplayRequest::new("thread-1".to_string());
+    request.as_of = Some("2026-01-31".to_string());
+    assert_eq!(paths(engine.replay(request).await.unwrap_err()), ["as_of"]);
+}
@@ -0,0 +1,224 @@
+//! Integration tests for retention locks.
+
+use std::sync::Arc;
+
+use mnemo_core::config::MnemoEngineConfig;
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::event::EventType;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::forget::{ForgetRequest, ForgetStrategy, ForgetSubjectRequest};
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::rechain::RechainRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::query::retention_lock::{RetentionLockPolicy, RetentionPeriod};
+use mnemo_core::query::transfer::TransferRequest;
+use mnemo_core::query::trash;
+use mnemo_core::query::update::UpdateRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    let policy = RetentionLockPolicy::default()
+        .with_org("acme", RetentionPeriod::years(7))
+        .with_tag("audit", RetentionPeriod::days(30));
+    MnemoEngine::new(storage, index, embedding, "records".to_string(), None)
+        .with_retention_lock(policy)
+}
+
+async fn remember(engine: &MnemoEngine, content: &str, org: Option<&str>, tags: &[&str]) -> Uuid {
+    let mut request = RememberRequest::new(content.to_string());
+    request.org_id = org.map(str::to_string);
+    request.tags = Some(tags.iter().map(|t| t.to_string()).collect());
+    engine.remember(request).await.unwrap().id
+}
+
+async fn forget(engine: &MnemoEngine, id: Uuid, strategy: ForgetStrategy) -> (Vec<Uuid>, usize) {
+    let mut request = ForgetRequest::new(vec![id]);
+    request.strategy = Some(strategy);
+    request.force = Some(true);
+    let response = engine.forget(request).await.unwrap();
+    (response.forgotten, response.errors.len())
+}
+
+async fn violations(engine: &MnemoEngine) -> Vec<serde_json::Value> {
+    engine
+        .storage
+        .list_events("records", 100, 0)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|e| e.event_type == EventType::RetentionLockViolation)
+        .map(|e| e.payload)
+        .collect()
+}
+
+#[tokio::test]
+async fn locked_memories_refuse_erasure_and_log_attempts() {
+    let engine = create_engine();
+    let ledger = remember(&engine, "invoice 42 was paid in full", Some("acme"), &[]).await;
+    let note = remember(&engine, "the coffee machine is broken", Some("other"), &[]).await;
+
+    assert_eq!(
+        forget(&engine, ledger, ForgetStrategy::HardDelete).await,
+        (vec![], 1)
+    );
+    assert_eq!(
+        forget(&engine, ledger, ForgetStrategy::Redact).await,
+        (vec![], 1)
+    );
+    let record = engine.storage.get_memory(ledger).await.unwrap().unwrap();
+    assert_eq!(record.content, "invoice 42 was paid in full");
+
+    let logged = violations(&engine).await;
+    assert_eq!(logged.len(), 2);
+    assert!(logged.iter().all(|p| p["memory_id"] == ledger.to_string()));
+    let mut operations: Vec<&str> = logged
+        .iter()
+        .map(|p| p["operation"].as_str().unwrap())
+        .collect();
+    operations.sort();
+    assert_eq!(operations, ["hard_delete", "redact"]);
+
+    // Memories outside the policy are unaffected.
+    assert_eq!(
+        forget(&engine, note, ForgetStrategy::HardDelete).await,
+        (vec![note], 0)
+    );
+    assert!(engine.storage.get_memory(note).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn subject_erasure_respects_tag_locks() {
+    let engine = create_engine();
+    let locked = remember(
+        &engine,
+        "alice disputed a charge",
+        None,
+        &["subject:alice", "audit"],
+    )
+    .await;
+    let free = remember(&engine, "alice likes dark mode", None, &["subject:alice"]).await;
+
+    let response = engine
+        .forget_subject(ForgetSubjectRequest {
+            subject_id: "alice".to_string(),
+            agent_id: None,
+            strategy: ForgetStrategy::HardDelete,
+            dry_run: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.forgotten, [free]);
+    assert_eq!(response.errors.len(), 1);
+    assert_eq!(response.errors[0].id, locked);
+    assert!(engine.storage.get_memory(locked).await.unwrap().is_some());
+    assert_eq!(violations(&engine).await.len(), 1);
+}
+
+#[tokio::test]
+async fn soft_delete_hides_locked_memories_but_purge_keeps_them() {
+    let engine = create_engine();
+    let ledger = remember(&engine, "invoice 42 was paid in full", Some("acme"), &[]).await;
+
+    assert_eq!(
+        forget(&engine, ledger, ForgetStrategy::SoftDelete).await,
+        (vec![ledger], 0)
+    );
+    let mut recall = RecallRequest::new("invoice paid".to_string());
+    recall.strategy = Some("semantic".to_string());
+    assert!(engine.recall(recall).await.unwrap().memories.is_empty());
+
+    let report = trash::purge(&engine, 0).await.unwrap();
+    assert_eq!(report.purged_count, 0);
+    let record = engine.storage.get_memory(ledger).await.unwrap().unwrap();
+    assert!(record.deleted_at.is_some());
+}
+
+#[tokio::test]
+async fn expired_locks_allow_deletion() {
+    let engine = create_engine();
+    let recent = remember(&engine, "q1 audit findings", None, &["audit"]).await;
+    // Back-date a copy past the 30-day tag lock.
+    let mut record = engine.storage.get_memory(recent).await.unwrap().unwrap();
+    let old = Uuid::now_v7();
+    record.id = old;
+    record.created_at = (chrono::Utc::now() - chrono::Duration::days(31)).to_rfc3339();
+    engine.storage.insert_memory(&record).await.unwrap();
+
+    assert_eq!(
+        forget(&engine, old, ForgetStrategy::HardDelete).await,
+        (vec![old], 0)
+    );
+    assert!(violations(&engine).await.is_empty());
+}
+
+#[tokio::test]
+async fn updates_cannot_lift_a_lock() {
+    let engine = create_engine();
+    let audited = remember(&engine, "vendor x failed the audit", None, &["audit"]).await;
+    let ledger = remember(&engine, "invoice 42 was paid in full", Some("acme"), &[]).await;
+
+    let mut request = UpdateRequest::new(audited);
+    request.tags = Some(vec!["misc".to_string()]);
+    let err = engine.update(request).await.unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)), "{err}");
+    let record = engine.storage.get_memory(audited).await.unwrap().unwrap();
+    assert_eq!(record.tags, ["audit"]);
+    assert_eq!(violations(&engine).await[0]["operation"], "update");
+
+    // Changes that keep the lock go through.
+    let mut request = UpdateRequest::new(audited);
+    request.tags = Some(vec!["audit".to_string(), "vendor".to_string()]);
+    engine.update(request).await.unwrap();
+    let mut request = UpdateRequest::new(ledger);
+    request.tags = Some(vec!["finance".to_string()]);
+    engine.update(request).await.unwrap();
+    assert_eq!(violations(&engine).await.len(), 1);
+}
+
+#[tokio::test]
+async fn transfer_and_rechain_leave_locked_memories_alone() {
+    let engine = create_engine();
+    let ledger = remember(&engine, "invoice 42 was paid in full", Some("acme"), &[]).await;
+
+    let mut request = TransferRequest::new("records", "successor");
+    request.transferred_by = Some("records".to_string());
+    let err = engine.transfer_ownership(request).await.unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)), "{err}");
+    let record = engine.storage.get_memory(ledger).await.unwrap().unwrap();
+    assert_eq!(record.agent_id, "records");
+
+    // A stale hash on a locked memory stays stale.
+    let mut stale = record.clone();
+    stale.prev_hash = Some(vec![0; 32]);
+    engine.storage.update_memory(&stale).await.unwrap();
+    let response = engine.rechain(RechainRequest::new()).await.unwrap();
+    assert_eq!(response.chains[0].locked, 1);
+    assert_eq!(response.chains[0].relinked, 0);
+    let record = engine.storage.get_memory(ledger).await.unwrap().unwrap();
+    assert_eq!(record.prev_hash, Some(vec![0; 32]));
+
+    let mut operations: Vec<String> = violations(&engine)
+        .await
+        .iter()
+        .map(|p| p["operation"].as_str().unwrap().to_string())
+        .collect();
+    operations.sort();
+    assert_eq!(operations, ["rechain", "transfer"]);
+}
+
+#[test]
+fn config_rejects_empty_periods() {
+    let mut config = MnemoEngineConfig::default();
+    config.features.retention_lock =
+        Some(RetentionLockPolicy::default().with_tag("audit", RetentionPeriod::default()));
+    assert!(config.validate().is_err());
+    config.features.retention_lock =
+        Some(RetentionLockPolicy::default().with_tag("audit", RetentionPeriod::years(1)));
+    assert!(config.validate().is_ok());
+}
@@ -0,0 +1,219 @@
+//! Integration tests for scheduled exports.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use mnemo_core::config::MnemoEngineConfig;
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::memory::MemoryRecord;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::export::{
+    self, EXPORT_FAILED_TOPIC, ExportDestination, ExportFormat, ExportSchedule, ExportStatus,
+};
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine(schedules: Vec<ExportSchedule>) -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "server".to_string(), None).with_exports(schedules)
+}
+
+fn schedule(name: &str, dir: &Path) -> ExportSchedule {
+    ExportSchedule::new(
+        name,
+        "0 2 * * *",
+        ExportDestination::Filesystem {
+            path: dir.to_path_buf(),
+        },
+    )
+}
+
+async fn remember_as(engine: &MnemoEngine, agent_id: &str, content: &str) {
+    let mut request = RememberRequest::new(content.to_string());
+    request.agent_id = Some(agent_id.to_string());
+    engine.remember(request).await.unwrap();
+}
+
+fn objects(dir: &Path, name: &str) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir.join(name))
+        .map(|entries| {
+            entries
+                .map(|e| e.unwrap().file_name().into_string().unwrap())
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+fn at(s: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+}
+
+#[tokio::test]
+async fn export_writes_filtered_jsonl_and_records_the_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut nightly = schedule("nightly", dir.path());
+    nightly.filter.agent_id = Some("support-bot".to_string());
+    let engine = create_engine(vec![nightly]);
+    remember_as(&engine, "support-bot", "the customer prefers email").await;
+    remember_as(&engine, "support-bot", "refunds take five days").await;
+    remember_as(&engine, "planner", "sprint ends friday").await;
+
+    let run = engine.run_export("nightly").await.unwrap();
+    assert_eq!(run.status, ExportStatus::Succeeded);
+    assert_eq!(run.memories, 2);
+
+    let files = objects(dir.path(), "nightly");
+    assert_eq!(files.len(), 1);
+    assert!(files[0].starts_with("nightly-") && files[0].ends_with(".jsonl"));
+    let location = run.location.clone().unwrap();
+    assert!(location.ends_with(&files[0]));
+    let text = std::fs::read_to_string(&location).unwrap();
+    assert_eq!(text.len(), run.bytes);
+    let records: Vec<MemoryRecord> = text
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(records.len(), 2);
+    assert!(records.iter().all(|r| r.agent_id == "support-bot"));
+    assert!(records.iter().all(|r| r.embedding.is_none()));
+
+    let runs = engine.list_export_runs(Some("nightly"), 10).await.unwrap();
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0].id, run.id);
+    assert!(
+        engine
+            .list_export_runs(Some("weekly"), 10)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(engine.run_export("weekly").await.is_err());
+}
+
+#[tokio::test]
+async fn schedules_run_when_due_and_apply_retention() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut nightly = schedule("nightly", dir.path());
+    nightly.retention.keep_last = Some(2);
+    let engine = create_engine(vec![nightly]);
+    remember_as(&engine, "server", "backups are encrypted").await;
+
+    // A schedule seen for the first time starts counting from then.
+    let start = at("2026-03-01T12:00:00Z");
+    assert!(
+        export::run_scheduled_exports(&engine, start)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let before_two = at("2026-03-02T01:59:00Z");
+    assert!(
+        export::run_scheduled_exports(&engine, before_two)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    let mut pruned = Vec::new();
+    for day in 0..3 {
+        let now = at("2026-03-02T02:00:00Z") + Duration::days(day);
+        let runs = export::run_scheduled_exports(&engine, now).await.unwrap();
+        assert_eq!(runs.len(), 1, "day {day}");
+        // Not due again until the next night.
+        let later = now + Duration::hours(1);
+        assert!(
+            export::run_scheduled_exports(&engine, later)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        pruned.extend(runs[0].pruned.clone());
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    }
+    assert_eq!(objects(dir.path(), "nightly").len(), 2);
+    assert_eq!(pruned.len(), 1);
+    assert!(!Path::new(&pruned[0]).exists());
+    assert_eq!(engine.list_export_runs(None, 10).await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn parquet_exports_read_back_with_duckdb() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut nightly = schedule("nightly", dir.path());
+    nightly.format = ExportFormat::Parquet;
+    let engine = create_engine(vec![nightly]);
+    remember_as(&engine, "server", "the api rate limit is 200 per minute").await;
+    remember_as(&engine, "server", "deploys are allowed on friday").await;
+
+    let run = engine.run_export("nightly").await.unwrap();
+    let location = run.location.unwrap();
+    assert!(location.ends_with(".parquet"));
+    let conn = duckdb::Connection::open_in_memory().unwrap();
+    let (count, content, threads, importance): (i64, String, i64, f32) = conn
+        .query_row(
+            &format!(
+                "SELECT count(*), min(content), count(thread_id), max(importance) \
+                 FROM read_parquet('{location}')"
+            ),
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(content, "deploys are allowed on friday");
+    // Memories without a thread export a null.
+    assert_eq!(threads, 0);
+    assert_eq!(importance, 0.5);
+}
+
+#[tokio::test]
+async fn failed_runs_are_recorded_and_alerted() {
+    let dir = tempfile::tempdir().unwrap();
+    let blocker = dir.path().join("not-a-directory");
+    std::fs::write(&blocker, b"").unwrap();
+    let engine = create_engine(vec![schedule("nightly", &blocker)]).with_outbox();
+
+    let run = engine.run_export("nightly").await.unwrap();
+    assert_eq!(run.status, ExportStatus::Failed);
+    assert!(run.error.is_some());
+    assert!(run.location.is_none());
+    assert_eq!(
+        engine.list_export_runs(None, 10).await.unwrap()[0].status,
+        ExportStatus::Failed
+    );
+
+    let later = (Utc::now() + Duration::minutes(5)).to_rfc3339();
+    let alerts = engine.storage.claim_outbox(10, &later).await.unwrap();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].topic, EXPORT_FAILED_TOPIC);
+    assert_eq!(alerts[0].payload["schedule"], "nightly");
+}
+
+#[tokio::test]
+async fn config_rejects_bad_schedules() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut config = MnemoEngineConfig {
+        exports: vec![
+            schedule("nightly", dir.path()),
+            schedule("nightly", dir.path()),
+        ],
+        ..Default::default()
+    };
+    assert!(config.validate().is_err());
+
+    config.exports = vec![schedule("nightly", dir.path())];
+    config.exports[0].cron = "0 25 * * *".to_string();
+    assert!(config.validate().is_err());
+
+    config.exports = vec![schedule("../escape", dir.path())];
+    assert!(config.validate().is_err());
+
+    config.exports = vec![schedule("nightly", dir.path())];
+    assert!(config.validate().is_ok());
+}
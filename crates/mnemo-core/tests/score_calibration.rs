@@ -0,0 +1,96 @@
+//! Integration tests for calibrated recall relevance.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine() -> MnemoEngine {
+    MnemoEngine::new(
+        Arc::new(DuckDbStorage::open_in_memory().unwrap()),
+        Arc::new(UsearchIndex::new(64).unwrap()),
+        Arc::new(DeterministicEmbedding::new(64)),
+        "test-agent".to_string(),
+        None,
+    )
+}
+
+async fn remember_all(engine: &MnemoEngine) {
+    for content in [
+        "the deploy runs at noon",
+        "the deploy pipeline uses blue green",
+        "lunch is served at one",
+    ] {
+        engine
+            .remember(RememberRequest::new(content.to_string()))
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn every_hit_carries_a_relevance() {
+    let engine = create_engine();
+    remember_all(&engine).await;
+    for strategy in [None, Some("semantic"), Some("graph")] {
+        let mut request = RecallRequest::new("deploy".to_string());
+        request.strategy = strategy.map(str::to_string);
+        let response = engine.recall(request).await.unwrap();
+        for memory in &response.memories {
+            let relevance = memory.relevance.unwrap();
+            assert!(
+                (0.0..=1.0).contains(&relevance),
+                "{strategy:?}: {relevance}"
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn min_relevance_drops_weaker_hits() {
+    let engine = create_engine();
+    remember_all(&engine).await;
+    let mut request = RecallRequest::new("deploy".to_string());
+    request.strategy = Some("semantic".to_string());
+    let all = engine.recall(request.clone()).await.unwrap().memories;
+    let threshold = all.iter().filter_map(|m| m.relevance).fold(0.0, f32::max);
+
+    request.min_relevance = Some(threshold);
+    let kept = engine.recall(request).await.unwrap().memories;
+    assert!(!kept.is_empty());
+    assert!(kept.len() <= all.len());
+    assert!(kept.iter().all(|m| m.relevance.unwrap() >= threshold));
+}
+
+#[tokio::test]
+async fn feedback_labels_recent_recalls_once() {
+    let engine = create_engine();
+    remember_all(&engine).await;
+    let response = engine
+        .recall(RecallRequest::new("deploy".to_string()))
+        .await
+        .unwrap();
+    let useful = [response.memories[0].id];
+    assert_eq!(engine.recall_feedback(None, &useful).unwrap(), 1);
+    assert_eq!(engine.recall_feedback(None, &useful).unwrap(), 0);
+
+    let report = engine.score_calibration_report();
+    let semantic = report.iter().find(|s| s.strategy == "semantic").unwrap();
+    assert_eq!(semantic.samples, response.memories.len());
+}
+
+#[tokio::test]
+async fn rejects_an_out_of_range_min_relevance() {
+    let engine = create_engine();
+    let mut request = RecallRequest::new("deploy".to_string());
+    request.min_relevance = Some(1.5);
+    match engine.recall(request).await.unwrap_err() {
+        Error::InvalidFields(violations) => assert_eq!(violations[0].path, "min_relevance"),
+        other => panic!("expected invalid fields, got {other}"),
+    }
+}
@@ -0,0 +1,113 @@
+//! Integration tests for loading a synthetic dataset into an engine.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::eval::seed::{SeedConfig, seed};
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::storage::MemoryFilter;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "agent".to_string(), None)
+}
+
+fn config() -> SeedConfig {
+    SeedConfig {
+        agents: 2,
+        memories: 300,
+        threads: 3,
+        with_relations: true,
+        checkpoint_every: 20,
+        days: 10,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn seeded_data_loads_and_verifies() {
+    let engine = create_engine();
+    let report = seed(&engine, config()).await.unwrap();
+    assert_eq!(report.memories, 300);
+    assert!(report.relations > 0);
+    assert!(report.checkpoints > 0);
+    assert_eq!(report.events, report.memories + report.checkpoints);
+
+    let filter = MemoryFilter {
+        agent_id: Some("seed-agent-000".to_string()),
+        ..Default::default()
+    };
+    let stored = engine
+        .storage
+        .list_memories(&filter, 1000, 0)
+        .await
+        .unwrap();
+    assert!(!stored.is_empty());
+    assert!(stored.iter().all(|m| m.embedding.is_some()));
+
+    for thread in ["seed-thread-000", "seed-thread-001", "seed-thread-002"] {
+        // Memory chains run per agent within a thread.
+        let mut verified = 0;
+        for agent in ["seed-agent-000", "seed-agent-001"] {
+            let result = engine
+                .verify_integrity(Some(agent.to_string()), Some(thread))
+                .await
+                .unwrap();
+            assert!(result.valid, "{agent}/{thread}: {:?}", result.error_message);
+            verified += result.verified_records;
+        }
+        assert!(verified > 0, "{thread}");
+        let events = engine
+            .verify_event_integrity(None, Some(thread))
+            .await
+            .unwrap();
+        assert!(events.valid, "{thread}: {:?}", events.error_message);
+        let checkpoints = engine
+            .storage
+            .list_checkpoints(thread, None, 100)
+            .await
+            .unwrap();
+        assert!(!checkpoints.is_empty(), "{thread}");
+    }
+
+    let mut request = RecallRequest::new("runbook for the primary database".to_string());
+    request.agent_id = Some("seed-agent-000".to_string());
+    let recalled = engine.recall(request).await.unwrap();
+    assert!(!recalled.memories.is_empty());
+}
+
+#[tokio::test]
+async fn the_same_seed_loads_the_same_ids() {
+    let (a, b) = (create_engine(), create_engine());
+    seed(&a, config()).await.unwrap();
+    seed(&b, config()).await.unwrap();
+    let filter = MemoryFilter {
+        agent_id: Some("seed-agent-001".to_string()),
+        ..Default::default()
+    };
+    let mut ids_a: Vec<_> = a
+        .storage
+        .list_memories(&filter, 1000, 0)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|m| m.id)
+        .collect();
+    let mut ids_b: Vec<_> = b
+        .storage
+        .list_memories(&filter, 1000, 0)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|m| m.id)
+        .collect();
+    ids_a.sort();
+    ids_b.sort();
+    assert!(!ids_a.is_empty());
+    assert_eq!(ids_a, ids_b);
+}
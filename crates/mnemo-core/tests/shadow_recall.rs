@@ -0,0 +1,117 @@
+//! Integration tests for shadow recall.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::event::EventType;
+use mnemo_core::model::recall_profile::RecallProfileSettings;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::query::shadow_recall::{ShadowArm, ShadowRecallConfig, spawn_worker};
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine(config: ShadowRecallConfig) -> Arc<MnemoEngine> {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    Arc::new(
+        MnemoEngine::new(storage, index, embedding, "agent".to_string(), None)
+            .with_shadow_recall(config),
+    )
+}
+
+fn arm(name: &str, settings: RecallProfileSettings) -> ShadowArm {
+    ShadowArm {
+        name: name.to_string(),
+        settings,
+    }
+}
+
+#[tokio::test]
+async fn sampled_recalls_are_compared_off_the_read_path() {
+    let engine = create_engine(ShadowRecallConfig {
+        sample_rate: 1.0,
+        arms: vec![
+            arm(
+                "top1",
+                RecallProfileSettings {
+                    limit: Some(1),
+                    ..Default::default()
+                },
+            ),
+            arm(
+                "semantic",
+                RecallProfileSettings {
+                    strategy: Some("semantic".to_string()),
+                    ..Default::default()
+                },
+            ),
+        ],
+        ..Default::default()
+    });
+    for content in [
+        "the deploy runs every friday",
+        "the deploy pipeline uses blue green",
+        "lunch is at noon",
+    ] {
+        engine
+            .remember(RememberRequest::new(content.to_string()))
+            .await
+            .unwrap();
+    }
+    let worker = spawn_worker(engine.clone()).unwrap();
+    assert!(spawn_worker(engine.clone()).is_none());
+
+    let response = engine
+        .recall(RecallRequest::new("deploy schedule".to_string()))
+        .await
+        .unwrap();
+    let mut comparisons = Vec::new();
+    for _ in 0..100 {
+        comparisons = engine.shadow_recall_comparisons(10).unwrap();
+        if !comparisons.is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(comparisons.len(), 1);
+    let comparison = &comparisons[0];
+    let primary: Vec<_> = response.memories.iter().map(|m| m.id).collect();
+    assert_eq!(comparison.primary, primary);
+    let top1 = comparison.arms.iter().find(|a| a.arm == "top1").unwrap();
+    assert_eq!(top1.memories, primary[..1]);
+
+    // Only the caller's recall counts as a read.
+    let reads = engine
+        .storage
+        .list_events("agent", 100, 0)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|e| e.event_type == EventType::MemoryRead)
+        .count();
+    assert_eq!(reads, 1);
+
+    let useful = primary[primary.len() - 1];
+    assert_eq!(engine.shadow_recall_feedback(None, &[useful]).unwrap(), 1);
+    let report = engine.shadow_recall_report().unwrap();
+    assert_eq!(report.comparisons, 1);
+    assert_eq!(report.primary.unwrap().feedback_recall, Some(1.0));
+    // The arm that kept only the top hit missed the useful memory.
+    assert_eq!(report.arms.last().unwrap().arm, "top1");
+    assert_eq!(report.arms.last().unwrap().feedback_recall, Some(0.0));
+    worker.abort();
+}
+
+#[tokio::test]
+async fn shadow_recall_needs_arms() {
+    assert!(ShadowRecallConfig::default().validate().is_err());
+    let config = ShadowRecallConfig {
+        arms: vec![arm("primary", RecallProfileSettings::default())],
+        ..Default::default()
+    };
+    assert!(config.validate().is_err());
+}
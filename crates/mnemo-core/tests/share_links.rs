@@ -0,0 +1,132 @@
+//! Integration tests for share links.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::query::share_link::{
+    CreateShareLinkRequest, RevokeShareLinkRequest, ShareLinkSigner,
+};
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "agent".to_string(), None)
+}
+
+#[tokio::test]
+async fn links_need_a_key_and_share_permission() {
+    let engine = create_engine();
+    let id = engine
+        .remember(RememberRequest::new("draft review notes".to_string()))
+        .await
+        .unwrap()
+        .id;
+    let err = engine
+        .create_share_link(CreateShareLinkRequest::new(id))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Validation(_)), "{err}");
+
+    let engine = engine.with_share_links(ShareLinkSigner::new([1; 32]));
+    let mut request = CreateShareLinkRequest::new(id);
+    request.agent_id = Some("stranger".to_string());
+    let err = engine.create_share_link(request).await.unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)), "{err}");
+
+    let mut request = CreateShareLinkRequest::new(id);
+    request.expires_in_hours = Some(10_000.0);
+    let err = engine.create_share_link(request).await.unwrap_err();
+    assert!(err.is_validation(), "{err}");
+}
+
+#[tokio::test]
+async fn revoked_links_stop_verifying() {
+    let engine = create_engine().with_share_links(ShareLinkSigner::new([1; 32]));
+    let id = engine
+        .remember(RememberRequest::new("draft review notes".to_string()))
+        .await
+        .unwrap()
+        .id;
+    let link = engine
+        .create_share_link(CreateShareLinkRequest::new(id))
+        .await
+        .unwrap();
+    let claims = engine.verify_share_link(&link.token).await.unwrap();
+    assert_eq!(claims.memory_id, id);
+    assert_eq!(claims.issued_by, "agent");
+
+    // Only an agent that may share the memory may revoke its links.
+    let mut request = RevokeShareLinkRequest::new(id, link.link_id);
+    request.agent_id = Some("stranger".to_string());
+    assert!(engine.revoke_share_link(request).await.is_err());
+    assert!(engine.verify_share_link(&link.token).await.is_ok());
+
+    engine
+        .revoke_share_link(RevokeShareLinkRequest::new(id, link.link_id))
+        .await
+        .unwrap();
+    let err = engine.verify_share_link(&link.token).await.unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)), "{err}");
+}
+
+#[tokio::test]
+async fn revoking_under_another_memory_leaves_the_link_alone() {
+    let engine = create_engine().with_share_links(ShareLinkSigner::new([1; 32]));
+    let id = engine
+        .remember(RememberRequest::new("draft review notes".to_string()))
+        .await
+        .unwrap()
+        .id;
+    let link = engine
+        .create_share_link(CreateShareLinkRequest::new(id))
+        .await
+        .unwrap();
+
+    // The stranger may share its own memory, but that grants nothing over
+    // links to this one.
+    let mut request = RememberRequest::new("stranger's own notes".to_string());
+    request.agent_id = Some("stranger".to_string());
+    let own = engine.remember(request).await.unwrap().id;
+    let mut request = RevokeShareLinkRequest::new(own, link.link_id);
+    request.agent_id = Some("stranger".to_string());
+    engine.revoke_share_link(request).await.unwrap();
+    assert!(engine.verify_share_link(&link.token).await.is_ok());
+
+    engine
+        .revoke_share_link(RevokeShareLinkRequest::new(id, link.link_id))
+        .await
+        .unwrap();
+    assert!(engine.verify_share_link(&link.token).await.is_err());
+}
+
+#[tokio::test]
+async fn links_can_be_revoked_while_read_only() {
+    let engine = create_engine().with_share_links(ShareLinkSigner::new([1; 32]));
+    let id = engine
+        .remember(RememberRequest::new("draft review notes".to_string()))
+        .await
+        .unwrap()
+        .id;
+    let link = engine
+        .create_share_link(CreateShareLinkRequest::new(id))
+        .await
+        .unwrap();
+
+    engine.set_read_only(true);
+    let err = engine
+        .create_share_link(CreateShareLinkRequest::new(id))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::ReadOnly(_)), "{err}");
+    engine
+        .revoke_share_link(RevokeShareLinkRequest::new(id, link.link_id))
+        .await
+        .unwrap();
+    assert!(engine.verify_share_link(&link.token).await.is_err());
+}
@@ -0,0 +1,171 @@
+//! Integration tests for k-anonymity and attribution redaction on
+//! shared-scope recall.
+
+use std::sync::Arc;
+
+use mnemo_core::config::MnemoEngineConfig;
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::memory::Scope;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::get_batch::GetBatchRequest;
+use mnemo_core::query::privacy::{PrivacyPolicy, REDACTED_AGENT_ID, SharedRecallPrivacy};
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+const COMMON_FACT: &str = "the staging database is reset every sunday night";
+const PRIVATE_DETAIL: &str = "alice's home address is 12 elm street";
+
+fn create_engine(privacy: Option<SharedRecallPrivacy>) -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    let engine = MnemoEngine::new(storage, index, embedding, "reader".to_string(), None);
+    match privacy {
+        Some(privacy) => engine.with_shared_recall_privacy(privacy),
+        None => engine,
+    }
+}
+
+async fn share(engine: &MnemoEngine, agent: &str, org: Option<&str>, content: &str) -> Uuid {
+    let mut request = RememberRequest::new(content.to_string());
+    request.agent_id = Some(agent.to_string());
+    request.org_id = org.map(str::to_string);
+    request.scope = Some(Scope::Public);
+    engine.remember(request).await.unwrap().id
+}
+
+async fn seed(engine: &MnemoEngine, org: Option<&str>) {
+    for agent in ["agent-a", "agent-b", "agent-c"] {
+        share(engine, agent, org, COMMON_FACT).await;
+    }
+    share(engine, "agent-a", org, PRIVATE_DETAIL).await;
+}
+
+async fn recall_as(engine: &MnemoEngine, agent: &str, query: &str) -> Vec<(String, String)> {
+    let mut request = RecallRequest::new(query.to_string());
+    request.agent_id = Some(agent.to_string());
+    request.strategy = Some("semantic".to_string());
+    engine
+        .recall(request)
+        .await
+        .unwrap()
+        .memories
+        .into_iter()
+        .map(|m| (m.content, m.agent_id))
+        .collect()
+}
+
+#[tokio::test]
+async fn uncorroborated_shared_hits_are_withheld_from_non_owners() {
+    let engine = create_engine(Some(SharedRecallPrivacy::uniform(PrivacyPolicy::default())));
+    seed(&engine, None).await;
+
+    let hits = recall_as(&engine, "reader", PRIVATE_DETAIL).await;
+    assert!(hits.iter().all(|(content, _)| content != PRIVATE_DETAIL));
+    let common: Vec<_> = hits.iter().filter(|(c, _)| c == COMMON_FACT).collect();
+    assert_eq!(common.len(), 3);
+    assert!(common.iter().all(|(_, agent)| agent == REDACTED_AGENT_ID));
+
+    // The author still sees their own memory, attributed.
+    let hits = recall_as(&engine, "agent-a", PRIVATE_DETAIL).await;
+    assert!(
+        hits.iter()
+            .any(|(content, agent)| content == PRIVATE_DETAIL && agent == "agent-a")
+    );
+}
+
+#[tokio::test]
+async fn policies_are_per_org() {
+    let privacy = SharedRecallPrivacy::default().with_org(
+        "acme",
+        PrivacyPolicy {
+            min_sources: 2,
+            redact_attribution: false,
+            ..Default::default()
+        },
+    );
+    let engine = create_engine(Some(privacy));
+    seed(&engine, Some("acme")).await;
+    seed(&engine, Some("globex")).await;
+
+    let hits = recall_as(&engine, "reader", PRIVATE_DETAIL).await;
+    let private: Vec<_> = hits
+        .iter()
+        .filter(|(content, _)| content == PRIVATE_DETAIL)
+        .collect();
+    // acme's copy is withheld; globex has no policy and no default.
+    assert_eq!(private.len(), 1);
+    assert!(
+        hits.iter()
+            .filter(|(c, _)| c == COMMON_FACT)
+            .all(|(_, agent)| agent != REDACTED_AGENT_ID)
+    );
+}
+
+#[tokio::test]
+async fn disabled_privacy_returns_everything() {
+    let engine = create_engine(None);
+    seed(&engine, None).await;
+    let hits = recall_as(&engine, "reader", PRIVATE_DETAIL).await;
+    assert!(
+        hits.iter()
+            .any(|(content, agent)| content == PRIVATE_DETAIL && agent == "agent-a")
+    );
+}
+
+#[test]
+fn config_rejects_invalid_policies() {
+    let mut config = MnemoEngineConfig::default();
+    config.features.shared_recall_privacy = Some(SharedRecallPrivacy::uniform(PrivacyPolicy {
+        min_sources: 0,
+        ..Default::default()
+    }));
+    assert!(matches!(config.validate(), Err(Error::Validation(_))));
+
+    let parsed: MnemoEngineConfig = serde_json::from_value(serde_json::json!({
+        "features": {
+            "shared_recall_privacy": {
+                "orgs": { "acme": { "min_sources": 5 } }
+            }
+        }
+    }))
+    .unwrap();
+    let privacy = parsed.features.shared_recall_privacy.unwrap();
+    assert_eq!(privacy.policy_for(Some("acme")).unwrap().min_sources, 5);
+    assert!(privacy.policy_for(Some("other")).is_none());
+}
+
+#[tokio::test]
+async fn get_batch_does_not_reveal_what_recall_hides() {
+    let engine = create_engine(Some(SharedRecallPrivacy::uniform(PrivacyPolicy::default())));
+    let mut common = Vec::new();
+    for agent in ["agent-a", "agent-b", "agent-c"] {
+        common.push(share(&engine, agent, None, COMMON_FACT).await);
+    }
+    let private = share(&engine, "agent-a", None, PRIVATE_DETAIL).await;
+
+    let mut ids = common.clone();
+    ids.push(private);
+    let mut request = GetBatchRequest::new(ids);
+    request.agent_id = Some("reader".to_string());
+    let response = engine.get_batch(request).await.unwrap();
+    let returned: Vec<_> = response.memories.iter().map(|m| m.id).collect();
+    assert_eq!(returned, common);
+    assert!(
+        response
+            .memories
+            .iter()
+            .all(|m| m.agent_id == REDACTED_AGENT_ID)
+    );
+    assert_eq!(response.missing, [private]);
+
+    // The author still sees their own attribution.
+    let mut request = GetBatchRequest::new(vec![private]);
+    request.agent_id = Some("agent-a".to_string());
+    let response = engine.get_batch(request).await.unwrap();
+    assert_eq!(response.memories[0].agent_id, "agent-a");
+}
@@ -0,0 +1,95 @@
+//! Integration tests for the more-like-this lookup.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::memory::Scope;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::query::similar::SimilarRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "alice".to_string(), None)
+}
+
+async fn remember(engine: &MnemoEngine, agent_id: &str, content: &str, tag: &str) -> Uuid {
+    let mut request = RememberRequest::new(content.to_string());
+    request.agent_id = Some(agent_id.to_string());
+    request.tags = Some(vec![tag.to_string()]);
+    engine.remember(request).await.unwrap().id
+}
+
+#[tokio::test]
+async fn returns_nearest_neighbours_without_the_memory_itself() {
+    let engine = create_engine();
+    let source = remember(&engine, "alice", "the invoice was paid late", "billing").await;
+    let twin = remember(&engine, "alice", "the invoice was paid late", "billing").await;
+    let other = remember(&engine, "alice", "lunch is at noon", "office").await;
+
+    let response = engine
+        .similar(SimilarRequest::new(source, 5))
+        .await
+        .unwrap();
+    assert_eq!(response.memory_id, source);
+    let ids: Vec<Uuid> = response.memories.iter().map(|m| m.id).collect();
+    assert_eq!(ids[0], twin);
+    assert!(ids.contains(&other));
+    assert!(!ids.contains(&source));
+    assert!(response.memories[0].score > 0.99);
+
+    let mut tagged = SimilarRequest::new(source, 5);
+    tagged.tags = Some(vec!["office".to_string()]);
+    let response = engine.similar(tagged).await.unwrap();
+    let ids: Vec<Uuid> = response.memories.iter().map(|m| m.id).collect();
+    assert_eq!(ids, [other]);
+}
+
+#[tokio::test]
+async fn respects_visibility() {
+    let engine = create_engine();
+    let source = remember(&engine, "alice", "the deploy key rotates weekly", "ops").await;
+    let private = remember(&engine, "bob", "the deploy key rotates weekly", "ops").await;
+    let mut public = RememberRequest::new("the deploy key rotates weekly".to_string());
+    public.agent_id = Some("bob".to_string());
+    public.scope = Some(Scope::Public);
+    let public = engine.remember(public).await.unwrap().id;
+
+    let response = engine
+        .similar(SimilarRequest::new(source, 5))
+        .await
+        .unwrap();
+    let ids: Vec<Uuid> = response.memories.iter().map(|m| m.id).collect();
+    assert!(ids.contains(&public));
+    assert!(!ids.contains(&private));
+
+    let mut as_bob = SimilarRequest::new(source, 5);
+    as_bob.agent_id = Some("bob".to_string());
+    let err = engine.similar(as_bob).await.unwrap_err();
+    assert!(matches!(err, Error::NotFound(_)), "{err}");
+}
+
+#[tokio::test]
+async fn attributes_shared_neighbours_as_recall_does() {
+    use mnemo_core::query::pseudonym::{ALIAS_PREFIX, Pseudonymizer};
+
+    let engine = create_engine().with_pseudonymizer(Pseudonymizer::new([7; 32]));
+    let source = remember(&engine, "alice", "the cdn cache is purged nightly", "ops").await;
+    let mut shared = RememberRequest::new("the cdn cache is purged nightly".to_string());
+    shared.agent_id = Some("bob".to_string());
+    shared.scope = Some(Scope::Public);
+    let shared = engine.remember(shared).await.unwrap().id;
+
+    let response = engine
+        .similar(SimilarRequest::new(source, 5))
+        .await
+        .unwrap();
+    let neighbour = response.memories.iter().find(|m| m.id == shared).unwrap();
+    assert!(neighbour.agent_id.starts_with(ALIAS_PREFIX));
+}
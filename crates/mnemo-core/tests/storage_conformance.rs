@@ -0,0 +1,12 @@
+//! The storage conformance suite against the embedded backends. The
+//! PostgreSQL run lives in `mnemo-postgres/tests/storage_conformance.rs`.
+
+mod duckdb {
+    mnemo_core::storage_conformance!(
+        mnemo_core::storage::duckdb::DuckDbStorage::open_in_memory().unwrap()
+    );
+}
+
+mod memory {
+    mnemo_core::storage_conformance!(mnemo_core::storage::memory::InMemoryStorage::new());
+}
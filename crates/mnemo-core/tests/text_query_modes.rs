@@ -0,0 +1,98 @@
+//! Integration tests for `RecallRequest::text_query_mode` on the lexical
+//! (BM25) strategy: exact phrases, fuzzy terms and field boosts.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::NoopEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::search::TextQueryMode;
+use mnemo_core::search::tantivy_index::TantivyFullTextIndex;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(128).unwrap());
+    let embedding = Arc::new(NoopEmbedding::new(128));
+    let full_text = Arc::new(TantivyFullTextIndex::open_in_memory().unwrap());
+    MnemoEngine::new(storage, index, embedding, "tq-agent".to_string(), None)
+        .with_full_text(full_text)
+}
+
+async fn remember(engine: &MnemoEngine, content: &str) -> Uuid {
+    engine
+        .remember(RememberRequest::new(content.to_string()))
+        .await
+        .unwrap()
+        .id
+}
+
+async fn lexical(engine: &MnemoEngine, query: &str, mode: Option<TextQueryMode>) -> Vec<Uuid> {
+    let mut req = RecallRequest::new(query.to_string());
+    req.strategy = Some("lexical".to_string());
+    req.text_query_mode = mode;
+    engine
+        .recall(req)
+        .await
+        .unwrap()
+        .memories
+        .iter()
+        .map(|m| m.id)
+        .collect()
+}
+
+#[tokio::test]
+async fn phrase_mode_requires_contiguous_terms() {
+    let engine = create_engine();
+    let exact = remember(
+        &engine,
+        "The user booked a window seat on the morning flight",
+    )
+    .await;
+    let scattered = remember(
+        &engine,
+        "A seat by the window was free; the flight left in the morning",
+    )
+    .await;
+
+    let terms = lexical(&engine, "window seat", None).await;
+    assert!(terms.contains(&exact) && terms.contains(&scattered));
+
+    let phrase = lexical(&engine, "window seat", Some(TextQueryMode::Phrase)).await;
+    assert_eq!(phrase, vec![exact]);
+}
+
+#[tokio::test]
+async fn fuzzy_mode_tolerates_typos() {
+    let engine = create_engine();
+    let id = remember(
+        &engine,
+        "The user's favourite restaurant serves Ethiopian food",
+    )
+    .await;
+
+    assert!(lexical(&engine, "restuarant", None).await.is_empty());
+    let fuzzy = lexical(
+        &engine,
+        "restuarant",
+        Some(TextQueryMode::Fuzzy { distance: 2 }),
+    )
+    .await;
+    assert_eq!(fuzzy, vec![id]);
+}
+
+#[test]
+fn text_query_mode_parses_from_str() {
+    assert_eq!(
+        "phrase".parse::<TextQueryMode>().unwrap(),
+        TextQueryMode::Phrase
+    );
+    assert_eq!(
+        "fuzzy:2".parse::<TextQueryMode>().unwrap(),
+        TextQueryMode::Fuzzy { distance: 2 }
+    );
+    assert!("fuzzy:3".parse::<TextQueryMode>().is_err());
+}
@@ -0,0 +1,166 @@
+//! Integration tests for permission checks on checkpoint, replay, branch
+//! and merge.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::acl::Permission;
+use mnemo_core::model::delegation::{Delegation, DelegationScope};
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::branch::BranchRequest;
+use mnemo_core::query::checkpoint::CheckpointRequest;
+use mnemo_core::query::merge::MergeRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::query::replay::ReplayRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+const OWNER: &str = "thread-owner";
+const THREAD: &str = "private-thread";
+
+async fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    let engine = MnemoEngine::new(storage, index, embedding, OWNER.to_string(), None);
+    engine
+        .checkpoint(CheckpointRequest::new(
+            THREAD.to_string(),
+            serde_json::json!({"step": 1}),
+        ))
+        .await
+        .unwrap();
+    engine
+}
+
+fn replay_as(agent_id: &str) -> ReplayRequest {
+    let mut request = ReplayRequest::new(THREAD.to_string());
+    request.agent_id = Some(agent_id.to_string());
+    request
+}
+
+async fn delegate(engine: &MnemoEngine, delegate_id: &str, permission: Permission) {
+    let delegation = Delegation {
+        id: uuid::Uuid::now_v7(),
+        delegator_id: OWNER.to_string(),
+        delegate_id: delegate_id.to_string(),
+        permission,
+        scope: DelegationScope::AllMemories,
+        max_depth: 0,
+        current_depth: 0,
+        parent_delegation_id: None,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        expires_at: None,
+        revoked_at: None,
+    };
+    engine.storage.insert_delegation(&delegation).await.unwrap();
+}
+
+#[tokio::test]
+async fn replaying_another_agents_thread_is_denied() {
+    let engine = create_engine().await;
+    engine.replay(replay_as(OWNER)).await.unwrap();
+
+    let err = engine.replay(replay_as("intruder")).await.unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)), "{err}");
+
+    let mut as_of = replay_as("intruder");
+    as_of.as_of = Some(chrono::Utc::now().to_rfc3339());
+    let err = engine.replay(as_of).await.unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)));
+}
+
+#[tokio::test]
+async fn a_delegation_grants_replay() {
+    let engine = create_engine().await;
+    // Read access alone no longer covers the whole thread.
+    delegate(&engine, "reviewer", Permission::Read).await;
+    let err = engine.replay(replay_as("reviewer")).await.unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)), "{err}");
+
+    delegate(&engine, "reviewer", Permission::Replay).await;
+    let response = engine.replay(replay_as("reviewer")).await.unwrap();
+    assert_eq!(response.checkpoint.agent_id, OWNER);
+
+    // Replay access does not extend to branching.
+    let mut branch = BranchRequest::new(THREAD.to_string(), "reviewer-fork".to_string());
+    branch.agent_id = Some("reviewer".to_string());
+    let err = engine.branch(branch).await.unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)));
+}
+
+#[tokio::test]
+async fn branch_and_merge_need_write_access() {
+    let engine = create_engine().await;
+    engine
+        .branch(BranchRequest::new(
+            THREAD.to_string(),
+            "experiment".to_string(),
+        ))
+        .await
+        .unwrap();
+
+    let mut merge = MergeRequest::new(THREAD.to_string(), "experiment".to_string());
+    merge.agent_id = Some("intruder".to_string());
+    let err = engine.merge(merge).await.unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)));
+
+    delegate(&engine, "collaborator", Permission::Write).await;
+    let mut merge = MergeRequest::new(THREAD.to_string(), "experiment".to_string());
+    merge.agent_id = Some("collaborator".to_string());
+    engine.merge(merge).await.unwrap();
+
+    // The thread still belongs to the agent that started it.
+    engine.replay(replay_as(OWNER)).await.unwrap();
+    let err = engine.replay(replay_as("intruder")).await.unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)));
+}
+
+#[tokio::test]
+async fn checkpointing_into_another_agents_thread_is_denied() {
+    let engine = create_engine().await;
+    let mut request = CheckpointRequest::new(THREAD.to_string(), serde_json::json!({"step": 2}));
+    request.agent_id = Some("intruder".to_string());
+    let err = engine.checkpoint(request).await.unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)), "{err}");
+
+    delegate(&engine, "collaborator", Permission::Write).await;
+    let mut request = CheckpointRequest::new(THREAD.to_string(), serde_json::json!({"step": 2}));
+    request.agent_id = Some("collaborator".to_string());
+    engine.checkpoint(request).await.unwrap();
+}
+
+#[tokio::test]
+async fn a_squatted_checkpoint_does_not_own_a_thread_with_memories() {
+    let engine = create_engine().await;
+    let thread = "busy-thread";
+    let mut squat = CheckpointRequest::new(thread.to_string(), serde_json::json!({}));
+    squat.agent_id = Some("squatter".to_string());
+    engine.checkpoint(squat).await.unwrap();
+
+    let mut remember = RememberRequest::new("the real conversation".to_string());
+    remember.thread_id = Some(thread.to_string());
+    engine.remember(remember).await.unwrap();
+
+    assert_eq!(
+        engine
+            .storage
+            .thread_owner(thread)
+            .await
+            .unwrap()
+            .as_deref(),
+        Some(OWNER)
+    );
+    engine
+        .checkpoint(CheckpointRequest::new(
+            thread.to_string(),
+            serde_json::json!({"step": 1}),
+        ))
+        .await
+        .unwrap();
+    let mut squat = CheckpointRequest::new(thread.to_string(), serde_json::json!({}));
+    squat.agent_id = Some("squatter".to_string());
+    let err = engine.checkpoint(squat).await.unwrap_err();
+    assert!(matches!(err, Error::PermissionDenied(_)), "{err}");
+}
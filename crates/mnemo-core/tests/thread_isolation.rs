@@ -0,0 +1,132 @@
+//! Integration tests for thread-scoped recall isolation.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::agent_profile::RetrievalDefaults;
+use mnemo_core::model::memory::Scope;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+const AGENT: &str = "thread-agent";
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, AGENT.to_string(), None)
+}
+
+struct Seeded {
+    thread_a: Uuid,
+    thread_b: Uuid,
+    unthreaded: Uuid,
+    global: Uuid,
+}
+
+async fn remember(engine: &MnemoEngine, content: &str, thread: Option<&str>, scope: Scope) -> Uuid {
+    let mut request = RememberRequest::new(content.to_string());
+    request.thread_id = thread.map(str::to_string);
+    request.scope = Some(scope);
+    engine.remember(request).await.unwrap().id
+}
+
+async fn seed(engine: &MnemoEngine) -> Seeded {
+    Seeded {
+        thread_a: remember(
+            engine,
+            "deploy notes from thread a",
+            Some("a"),
+            Scope::Private,
+        )
+        .await,
+        thread_b: remember(
+            engine,
+            "deploy notes from thread b",
+            Some("b"),
+            Scope::Private,
+        )
+        .await,
+        unthreaded: remember(
+            engine,
+            "deploy notes without a thread",
+            None,
+            Scope::Private,
+        )
+        .await,
+        global: remember(
+            engine,
+            "deploy notes for everyone",
+            Some("b"),
+            Scope::Global,
+        )
+        .await,
+    }
+}
+
+async fn recall(
+    engine: &MnemoEngine,
+    thread: Option<&str>,
+    isolation: Option<bool>,
+) -> HashSet<Uuid> {
+    let mut request = RecallRequest::new("deploy notes".to_string());
+    request.strategy = Some("semantic".to_string());
+    request.thread_id = thread.map(str::to_string);
+    request.thread_isolation = isolation;
+    engine
+        .recall(request)
+        .await
+        .unwrap()
+        .memories
+        .into_iter()
+        .map(|m| m.id)
+        .collect()
+}
+
+#[tokio::test]
+async fn isolated_recall_sees_only_its_thread_and_global_memories() {
+    let engine = create_engine();
+    let m = seed(&engine).await;
+
+    assert_eq!(
+        recall(&engine, Some("a"), Some(true)).await,
+        HashSet::from([m.thread_a, m.global])
+    );
+    // Without a thread, isolation keeps to memories that have none.
+    assert_eq!(
+        recall(&engine, None, Some(true)).await,
+        HashSet::from([m.unthreaded, m.global])
+    );
+    // Isolation is opt-in.
+    assert_eq!(
+        recall(&engine, Some("a"), None).await,
+        HashSet::from([m.thread_a, m.thread_b, m.unthreaded, m.global])
+    );
+}
+
+#[tokio::test]
+async fn agent_default_enables_isolation_unless_request_overrides() {
+    let engine = create_engine();
+    let m = seed(&engine).await;
+    engine
+        .set_agent_retrieval_defaults(
+            AGENT,
+            RetrievalDefaults {
+                thread_isolation: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        recall(&engine, Some("b"), None).await,
+        HashSet::from([m.thread_b, m.global])
+    );
+    assert_eq!(recall(&engine, Some("b"), Some(false)).await.len(), 4);
+}
@@ -0,0 +1,196 @@
+//! Integration tests for the thread message store.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::memory::MemoryType;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::thread_message::{
+    CreateMessageRequest, DeleteMessageRequest, ListMessagesRequest, MessageOrder, MessageRole,
+};
+use mnemo_core::query::turn::{TURN_FACT_TAG, TURN_TAG};
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+const THREAD: &str = "support-42";
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "support-bot".to_string(), None)
+}
+
+async fn post(engine: &MnemoEngine, role: MessageRole, content: &str) -> Uuid {
+    let request = CreateMessageRequest::new(THREAD.to_string(), role, content.to_string());
+    engine
+        .create_thread_message(request)
+        .await
+        .unwrap()
+        .message
+        .id
+}
+
+async fn list(engine: &MnemoEngine, request: ListMessagesRequest) -> Vec<Uuid> {
+    let response = engine.list_thread_messages(request).await.unwrap();
+    response.messages.into_iter().map(|m| m.id).collect()
+}
+
+#[tokio::test]
+async fn messages_are_listed_in_order_and_chained() {
+    let engine = create_engine();
+    let system = post(&engine, MessageRole::System, "be concise").await;
+    let mut question = CreateMessageRequest::new(
+        THREAD.to_string(),
+        MessageRole::User,
+        "My order number is 1234. Where is it?".to_string(),
+    );
+    question.metadata = Some(serde_json::json!({"channel": "web"}));
+    let question = engine.create_thread_message(question).await.unwrap();
+    assert_eq!(question.turn_memory_id, None);
+
+    let mut answer = CreateMessageRequest::new(
+        THREAD.to_string(),
+        MessageRole::Assistant,
+        "It ships tomorrow.".to_string(),
+    );
+    answer.extract_facts = Some(true);
+    let answer = engine.create_thread_message(answer).await.unwrap();
+
+    let response = engine
+        .list_thread_messages(ListMessagesRequest::new(THREAD.to_string()))
+        .await
+        .unwrap();
+    let ids: Vec<_> = response.messages.iter().map(|m| m.id).collect();
+    assert_eq!(ids, [system, question.message.id, answer.message.id]);
+    assert_eq!(response.messages[1].role, MessageRole::User);
+    assert_eq!(response.messages[1].metadata["channel"], "web");
+    assert!(!response.has_more);
+
+    let event = engine
+        .storage
+        .get_event(answer.message.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.parent_event_id, Some(question.message.id));
+    assert_eq!(event.payload["role"], "assistant");
+
+    // The exchange was captured as a turn, with the user's fact extracted.
+    let turn = engine
+        .storage
+        .get_memory(answer.turn_memory_id.unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(turn.memory_type, MemoryType::Episodic);
+    assert!(turn.tags.contains(&TURN_TAG.to_string()));
+    assert_eq!(turn.thread_id.as_deref(), Some(THREAD));
+    assert_eq!(answer.extracted_memory_ids.len(), 1);
+    let fact = engine
+        .storage
+        .get_memory(answer.extracted_memory_ids[0])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(fact.content, "My order number is 1234");
+    assert!(fact.tags.contains(&TURN_FACT_TAG.to_string()));
+}
+
+#[tokio::test]
+async fn pages_follow_the_cursor_in_either_order() {
+    let engine = create_engine();
+    let mut ids = Vec::new();
+    for i in 0..5 {
+        ids.push(post(&engine, MessageRole::User, &format!("message {i}")).await);
+    }
+
+    let mut request = ListMessagesRequest::new(THREAD.to_string());
+    request.limit = Some(2);
+    let page = engine.list_thread_messages(request.clone()).await.unwrap();
+    assert_eq!(page.first_id, Some(ids[0]));
+    assert_eq!(page.last_id, Some(ids[1]));
+    assert!(page.has_more);
+
+    request.after = page.last_id;
+    assert_eq!(list(&engine, request.clone()).await, [ids[2], ids[3]]);
+    request.after = Some(ids[3]);
+    let last = engine.list_thread_messages(request).await.unwrap();
+    assert_eq!(last.messages.len(), 1);
+    assert!(!last.has_more);
+
+    let mut newest = ListMessagesRequest::new(THREAD.to_string());
+    newest.order = Some(MessageOrder::Desc);
+    newest.limit = Some(2);
+    assert_eq!(list(&engine, newest.clone()).await, [ids[4], ids[3]]);
+    // Paging backwards returns the messages right before the cursor.
+    newest.before = Some(ids[1]);
+    let page = engine.list_thread_messages(newest).await.unwrap();
+    assert_eq!(page.first_id, Some(ids[3]));
+    assert_eq!(page.last_id, Some(ids[2]));
+    assert!(page.has_more);
+
+    let mut before = ListMessagesRequest::new(THREAD.to_string());
+    before.before = Some(ids[2]);
+    assert_eq!(list(&engine, before).await, [ids[0], ids[1]]);
+}
+
+#[tokio::test]
+async fn deleted_messages_leave_the_listing() {
+    let engine = create_engine();
+    let first = post(&engine, MessageRole::User, "hello").await;
+    let second = post(&engine, MessageRole::User, "wrong thread, sorry").await;
+
+    let deleted = engine
+        .delete_thread_message(DeleteMessageRequest::new(THREAD.to_string(), second))
+        .await
+        .unwrap();
+    assert!(deleted.deleted);
+    assert_eq!(
+        list(&engine, ListMessagesRequest::new(THREAD.to_string())).await,
+        [first]
+    );
+    assert!(matches!(
+        engine
+            .delete_thread_message(DeleteMessageRequest::new(THREAD.to_string(), second))
+            .await,
+        Err(Error::NotFound(_))
+    ));
+
+    // The next message follows the last live one.
+    let third = post(&engine, MessageRole::User, "where is my order?").await;
+    let event = engine.storage.get_event(third).await.unwrap().unwrap();
+    assert_eq!(event.parent_event_id, Some(first));
+
+    // Other agents see only their own messages in the thread.
+    let mut other = ListMessagesRequest::new(THREAD.to_string());
+    other.agent_id = Some("billing-bot".to_string());
+    assert!(list(&engine, other).await.is_empty());
+}
+
+#[tokio::test]
+async fn malformed_message_requests_are_rejected() {
+    let engine = create_engine();
+    let mut request =
+        CreateMessageRequest::new(THREAD.to_string(), MessageRole::User, "  ".to_string());
+    request.metadata = Some(serde_json::json!(["not", "an", "object"]));
+    let err = engine.create_thread_message(request).await.unwrap_err();
+    let paths: Vec<_> = err
+        .field_violations()
+        .iter()
+        .map(|v| v.path.as_str())
+        .collect();
+    assert_eq!(paths, ["content", "metadata"]);
+
+    let mut request = ListMessagesRequest::new(THREAD.to_string());
+    request.limit = Some(0);
+    let err = engine.list_thread_messages(request).await.unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "limit");
+
+    let mut request = ListMessagesRequest::new(THREAD.to_string());
+    request.after = Some(Uuid::now_v7());
+    let err = engine.list_thread_messages(request).await.unwrap_err();
+    assert_eq!(err.field_violations()[0].path, "after");
+}
@@ -0,0 +1,63 @@
+//! Integration tests for point-in-time vector search.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::memory::MemoryRecord;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+const QUERY: &str = "quarterly revenue figures";
+
+fn create_engine() -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "agent".to_string(), None)
+}
+
+async fn store(engine: &MnemoEngine, content: &str, created_at: &str) -> Uuid {
+    let mut record = MemoryRecord::new("agent".to_string(), content.to_string());
+    record.created_at = created_at.to_string();
+    record.updated_at = created_at.to_string();
+    let embedding = engine.embedding.embed(content).await.unwrap();
+    engine.index.add(record.id, &embedding).unwrap();
+    record.embedding = Some(embedding);
+    engine.storage.insert_memory(&record).await.unwrap();
+    record.id
+}
+
+#[tokio::test]
+async fn as_of_recall_is_not_crowded_out_by_later_memories() {
+    let engine = create_engine();
+    let mut older = Vec::new();
+    for i in 0..5 {
+        let content = format!("revenue report {i}");
+        older.push(
+            store(
+                &engine,
+                &content,
+                &format!("2020-01-0{}T00:00:00+00:00", i + 1),
+            )
+            .await,
+        );
+    }
+    // Later memories that match the query exactly and would otherwise
+    // take every vector candidate.
+    for i in 0..30 {
+        store(&engine, QUERY, &format!("2026-01-01T00:00:{i:02}+00:00")).await;
+    }
+
+    let mut request = RecallRequest::new(QUERY.to_string());
+    request.strategy = Some("semantic".to_string());
+    request.limit = Some(5);
+    request.as_of = Some("2021-01-01T00:00:00Z".to_string());
+    let response = engine.recall(request).await.unwrap();
+    let mut ids: Vec<Uuid> = response.memories.iter().map(|m| m.id).collect();
+    ids.sort();
+    older.sort();
+    assert_eq!(ids, older);
+}
@@ -0,0 +1,132 @@
+//! Integration tests for the trash: listing soft-deleted memories,
+//! restoring them with `undelete`, and the retention purge.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::NoopEmbedding;
+use mnemo_core::index::VectorIndex;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::memory::MemoryRecord;
+use mnemo_core::query::forget::ForgetRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::query::trash::{TrashRequest, UndeleteRequest};
+use mnemo_core::query::{MAX_BATCH_QUERY_LIMIT, MnemoEngine};
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+fn create_engine() -> (Arc<MnemoEngine>, Arc<UsearchIndex>) {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(128).unwrap());
+    let embedding = Arc::new(NoopEmbedding::new(128));
+    let engine = MnemoEngine::new(
+        storage,
+        index.clone(),
+        embedding,
+        "trash-agent".to_string(),
+        None,
+    )
+    .with_trash_retention_days(30);
+    (Arc::new(engine), index)
+}
+
+async fn remember_and_forget(engine: &MnemoEngine, content: &str) -> Uuid {
+    let id = engine
+        .remember(RememberRequest::new(content.to_string()))
+        .await
+        .unwrap()
+        .id;
+    let resp = engine.forget(ForgetRequest::new(vec![id])).await.unwrap();
+    assert_eq!(resp.forgotten, vec![id]);
+    id
+}
+
+#[tokio::test]
+async fn trash_lists_soft_deleted_memories() {
+    let (engine, _) = create_engine();
+    engine
+        .remember(RememberRequest::new("still live".to_string()))
+        .await
+        .unwrap();
+    let deleted = remember_and_forget(&engine, "User's old address").await;
+
+    let trash = engine.list_trash(TrashRequest::new()).await.unwrap();
+    assert_eq!(trash.total, 1);
+    assert_eq!(trash.memories[0].id, deleted);
+    assert_eq!(trash.memories[0].content, "User's old address");
+}
+
+#[tokio::test]
+async fn undelete_restores_and_reindexes() {
+    let (engine, index) = create_engine();
+    let id = remember_and_forget(&engine, "User prefers dark mode").await;
+    assert_eq!(index.len(), 0);
+
+    let resp = engine.undelete(UndeleteRequest::new(id)).await.unwrap();
+    assert_eq!(resp.memory_id, id);
+
+    let record = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert!(record.deleted_at.is_none());
+    assert_eq!(index.len(), 1);
+    assert!(
+        engine
+            .list_trash(TrashRequest::new())
+            .await
+            .unwrap()
+            .memories
+            .is_empty()
+    );
+
+    // A live memory cannot be undeleted.
+    let err = engine.undelete(UndeleteRequest::new(id)).await.unwrap_err();
+    assert!(err.to_string().contains("not deleted"));
+}
+
+#[tokio::test]
+async fn undelete_requires_write_permission() {
+    let (engine, _) = create_engine();
+    let id = remember_and_forget(&engine, "private note").await;
+
+    let mut req = UndeleteRequest::new(id);
+    req.agent_id = Some("intruder".to_string());
+    let err = engine.undelete(req).await.unwrap_err();
+    assert!(matches!(err, mnemo_core::error::Error::PermissionDenied(_)));
+}
+
+#[tokio::test]
+async fn purge_hard_deletes_only_expired_trash() {
+    let (engine, _) = create_engine();
+    let old = remember_and_forget(&engine, "deleted long ago").await;
+    let recent = remember_and_forget(&engine, "deleted just now").await;
+
+    let mut record = engine.storage.get_memory(old).await.unwrap().unwrap();
+    record.deleted_at = Some((chrono::Utc::now() - chrono::Duration::days(45)).to_rfc3339());
+    engine.storage.update_memory(&record).await.unwrap();
+
+    let report = engine.run_trash_purge().await.unwrap();
+    assert_eq!(report.purged_count, 1);
+    assert!(report.errors.is_empty());
+    assert!(engine.storage.get_memory(old).await.unwrap().is_none());
+    assert!(engine.storage.get_memory(recent).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn trash_reaches_past_one_batch_of_memories() {
+    let (engine, _) = create_engine();
+    // The oldest memory sorts last, behind a full batch of live ones.
+    let mut old = MemoryRecord::new("trash-agent".to_string(), "deleted long ago".to_string());
+    old.created_at = (chrono::Utc::now() - chrono::Duration::days(90)).to_rfc3339();
+    old.deleted_at = Some((chrono::Utc::now() - chrono::Duration::days(45)).to_rfc3339());
+    engine.storage.insert_memory(&old).await.unwrap();
+    for i in 0..MAX_BATCH_QUERY_LIMIT {
+        let record = MemoryRecord::new("trash-agent".to_string(), format!("live {i}"));
+        engine.storage.insert_memory(&record).await.unwrap();
+    }
+
+    let trash = engine.list_trash(TrashRequest::new()).await.unwrap();
+    assert_eq!(trash.total, 1);
+    assert_eq!(trash.memories[0].id, old.id);
+
+    let report = engine.run_trash_purge().await.unwrap();
+    assert_eq!(report.purged_count, 1);
+    assert!(engine.storage.get_memory(old.id).await.unwrap().is_none());
+}
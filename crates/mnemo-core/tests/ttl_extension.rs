@@ -0,0 +1,109 @@
+//! Integration tests for TTL extension and pre-expiry `MemoryExpiring`
+//! warnings emitted by the TTL sweep.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::NoopEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::event::EventType;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::lifecycle::ExtendTtlRequest;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use uuid::Uuid;
+
+const AGENT: &str = "ttl-ext-agent";
+
+fn create_engine() -> Arc<MnemoEngine> {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(128).unwrap());
+    let embedding = Arc::new(NoopEmbedding::new(128));
+    Arc::new(
+        MnemoEngine::new(storage, index, embedding, AGENT.to_string(), None)
+            .with_expiry_warning_hours(24),
+    )
+}
+
+async fn remember_with_ttl(engine: &MnemoEngine, ttl_seconds: u64) -> Uuid {
+    let mut req = RememberRequest::new("User is travelling this week".to_string());
+    req.ttl_seconds = Some(ttl_seconds);
+    engine.remember(req).await.unwrap().id
+}
+
+async fn expiring_events(engine: &MnemoEngine) -> usize {
+    engine
+        .storage
+        .list_events(AGENT, 1000, 0)
+        .await
+        .unwrap()
+        .iter()
+        .filter(|e| e.event_type == EventType::MemoryExpiring)
+        .count()
+}
+
+#[tokio::test]
+async fn extend_ttl_pushes_expiry_forward() {
+    let engine = create_engine();
+    let id = remember_with_ttl(&engine, 3600).await;
+    let before = engine
+        .storage
+        .get_memory(id)
+        .await
+        .unwrap()
+        .unwrap()
+        .expires_at
+        .unwrap();
+
+    let resp = engine
+        .extend_ttl(ExtendTtlRequest::new(id, 7200))
+        .await
+        .unwrap();
+    assert_eq!(resp.previous_expires_at, before);
+
+    let before = chrono::DateTime::parse_from_rfc3339(&before).unwrap();
+    let after = chrono::DateTime::parse_from_rfc3339(&resp.expires_at).unwrap();
+    assert_eq!((after - before).num_seconds(), 7200);
+
+    let stored = engine.storage.get_memory(id).await.unwrap().unwrap();
+    assert_eq!(stored.expires_at, Some(resp.expires_at));
+}
+
+#[tokio::test]
+async fn extend_ttl_rejects_memory_without_ttl() {
+    let engine = create_engine();
+    let id = engine
+        .remember(RememberRequest::new("permanent fact".to_string()))
+        .await
+        .unwrap()
+        .id;
+    let err = engine
+        .extend_ttl(ExtendTtlRequest::new(id, 60))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("no TTL"));
+}
+
+#[tokio::test]
+async fn sweep_warns_once_per_expiry_and_rearms_after_extension() {
+    let engine = create_engine();
+    let soon = remember_with_ttl(&engine, 3600).await;
+    let _later = remember_with_ttl(&engine, 7 * 24 * 3600).await;
+
+    let report = engine.run_ttl_sweep().await.unwrap();
+    assert_eq!(report.warned_count, 1);
+    assert_eq!(report.swept_count, 0);
+    assert_eq!(expiring_events(&engine).await, 1);
+
+    // A second pass does not repeat the warning.
+    let report = engine.run_ttl_sweep().await.unwrap();
+    assert_eq!(report.warned_count, 0);
+
+    // Extending within the window re-arms it for the new expiry.
+    engine
+        .extend_ttl(ExtendTtlRequest::new(soon, 3600))
+        .await
+        .unwrap();
+    let report = engine.run_ttl_sweep().await.unwrap();
+    assert_eq!(report.warned_count, 1);
+    assert_eq!(expiring_events(&engine).await, 2);
+}
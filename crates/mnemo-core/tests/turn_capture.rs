@@ -0,0 +1,249 @@
+//! Integration tests for prompt/response turn capture
+//! (`MnemoEngine::capture_turn`).
+//!
+//! Covers: one event per message/tool call with token/cost accounting on
+//! the assistant event, causal linking via `parent_event_id`, the episodic
+//! memory written for the turn, opt-in fact extraction, and that a
+//! malformed, over-limit or hook-refused turn persists none of its events.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::NoopEmbedding;
+use mnemo_core::error::{Error, Result};
+use mnemo_core::hooks::{Hook, HookErrorPolicy, HookOptions};
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::event::EventType;
+use mnemo_core::model::memory::MemoryType;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::limits::LimitsConfig;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::query::turn::{TURN_FACT_TAG, TURN_TAG, TurnRequest, TurnToolCall};
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine() -> Arc<MnemoEngine> {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(128).unwrap());
+    let embedding = Arc::new(NoopEmbedding::new(128));
+    Arc::new(MnemoEngine::new(
+        storage,
+        index,
+        embedding,
+        "turn-agent".to_string(),
+        None,
+    ))
+}
+
+fn sample_turn() -> TurnRequest {
+    let mut req = TurnRequest::new(
+        "What's the weather in Berlin?".to_string(),
+        "It is 18C and sunny in Berlin.".to_string(),
+    );
+    req.thread_id = Some("thread-1".to_string());
+    req.model = Some("gpt-4o".to_string());
+    req.tokens_input = Some(120);
+    req.tokens_output = Some(30);
+    req.cost_usd = Some(0.0021);
+    req.tool_calls = Some(vec![TurnToolCall {
+        name: "weather".to_string(),
+        arguments: Some(serde_json::json!({"city": "Berlin"})),
+        result: Some(serde_json::json!({"temp_c": 18})),
+        latency_ms: Some(42),
+    }]);
+    req
+}
+
+#[tokio::test]
+async fn turn_records_linked_events_with_accounting() {
+    let engine = create_engine();
+    let resp = engine.capture_turn(sample_turn()).await.unwrap();
+
+    // ToolCall + ToolResult.
+    assert_eq!(resp.tool_event_ids.len(), 2);
+
+    let user = engine
+        .storage
+        .get_event(resp.user_event_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(user.event_type, EventType::UserMessage);
+    assert!(user.parent_event_id.is_none());
+
+    let call = engine
+        .storage
+        .get_event(resp.tool_event_ids[0])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(call.event_type, EventType::ToolCall);
+    assert_eq!(call.parent_event_id, Some(user.id));
+    assert_eq!(call.latency_ms, Some(42));
+
+    let result = engine
+        .storage
+        .get_event(resp.tool_event_ids[1])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(result.event_type, EventType::ToolResult);
+    assert_eq!(result.parent_event_id, Some(call.id));
+
+    let assistant = engine
+        .storage
+        .get_event(resp.assistant_event_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(assistant.event_type, EventType::AssistantMessage);
+    assert_eq!(assistant.parent_event_id, Some(user.id));
+    assert_eq!(assistant.model.as_deref(), Some("gpt-4o"));
+    assert_eq!(assistant.tokens_input, Some(120));
+    assert_eq!(assistant.tokens_output, Some(30));
+    assert!((assistant.cost_usd.unwrap() - 0.0021).abs() < 1e-9);
+
+    // The event chain stays verifiable after a turn.
+    let verification = engine.verify_event_integrity(None, None).await.unwrap();
+    assert!(verification.valid);
+}
+
+#[tokio::test]
+async fn turn_writes_episodic_memory() {
+    let engine = create_engine();
+    let resp = engine.capture_turn(sample_turn()).await.unwrap();
+
+    let memory = engine
+        .storage
+        .get_memory(resp.memory_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(memory.memory_type, MemoryType::Episodic);
+    assert!(memory.tags.contains(&TURN_TAG.to_string()));
+    assert!(
+        memory
+            .content
+            .contains("User: What's the weather in Berlin?")
+    );
+    assert!(memory.content.contains("Tools: weather"));
+    assert!(memory.content.contains("Assistant: It is 18C"));
+    assert_eq!(
+        memory.metadata["turn"]["assistant_event_id"],
+        resp.assistant_event_id.to_string()
+    );
+    assert!(resp.extracted_memory_ids.is_empty());
+}
+
+#[tokio::test]
+async fn turn_chains_to_previous_turn_and_extracts_facts() {
+    let engine = create_engine();
+    let first = engine.capture_turn(sample_turn()).await.unwrap();
+
+    let mut next = TurnRequest::new(
+        "I live in Berlin. My manager is Dana. Can you remind me tomorrow?".to_string(),
+        "Noted.".to_string(),
+    );
+    next.parent_event_id = Some(first.assistant_event_id);
+    next.extract_facts = Some(true);
+    let resp = engine.capture_turn(next).await.unwrap();
+
+    let user = engine
+        .storage
+        .get_event(resp.user_event_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(user.parent_event_id, Some(first.assistant_event_id));
+
+    assert_eq!(resp.extracted_memory_ids.len(), 2);
+    for id in &resp.extracted_memory_ids {
+        let fact = engine.storage.get_memory(*id).await.unwrap().unwrap();
+        assert_eq!(fact.memory_type, MemoryType::Semantic);
+        assert!(fact.tags.contains(&TURN_FACT_TAG.to_string()));
+        assert_eq!(
+            fact.metadata["extracted_from_turn"],
+            resp.memory_id.to_string()
+        );
+    }
+}
+
+#[tokio::test]
+async fn malformed_turn_is_rejected_without_writes() {
+    let engine = create_engine();
+
+    let empty = TurnRequest::new("hello".to_string(), "   ".to_string());
+    assert!(engine.capture_turn(empty).await.is_err());
+
+    let mut negative = TurnRequest::new("hello".to_string(), "hi".to_string());
+    negative.tokens_input = Some(-1);
+    assert!(engine.capture_turn(negative).await.is_err());
+
+    let events = engine
+        .storage
+        .list_events("turn-agent", 10, 0)
+        .await
+        .unwrap();
+    assert!(events.is_empty());
+}
+
+#[tokio::test]
+async fn turn_over_limits_is_rejected_without_writes() {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(128).unwrap());
+    let embedding = Arc::new(NoopEmbedding::new(128));
+    let engine = MnemoEngine::new(storage, index, embedding, "turn-agent".to_string(), None)
+        .with_limits(LimitsConfig {
+            max_content_bytes: 64,
+            max_tags: 2,
+            ..LimitsConfig::default()
+        });
+
+    // Each message fits on its own, the rendered turn does not.
+    let long = TurnRequest::new("a".repeat(40), "b".repeat(40));
+    assert!(engine.capture_turn(long).await.is_err());
+
+    // The turn tag counts against the limit alongside the caller's tags.
+    let mut tagged = TurnRequest::new("hello".to_string(), "hi".to_string());
+    tagged.tags = Some(vec!["one".to_string(), "two".to_string()]);
+    assert!(engine.capture_turn(tagged).await.is_err());
+
+    let events = engine
+        .storage
+        .list_events("turn-agent", 10, 0)
+        .await
+        .unwrap();
+    assert!(events.is_empty());
+}
+
+/// Refuses every memory write.
+struct RejectAll;
+
+#[async_trait::async_trait]
+impl Hook for RejectAll {
+    fn name(&self) -> &str {
+        "reject-all"
+    }
+
+    async fn pre_remember(&self, _request: &mut RememberRequest) -> Result<()> {
+        Err(Error::Validation("writes are closed".to_string()))
+    }
+}
+
+#[tokio::test]
+async fn turn_refused_by_a_hook_writes_no_events() {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(128).unwrap());
+    let embedding = Arc::new(NoopEmbedding::new(128));
+    let engine = MnemoEngine::new(storage, index, embedding, "turn-agent".to_string(), None)
+        .with_hook(
+            Arc::new(RejectAll),
+            HookOptions::new(0, HookErrorPolicy::Abort),
+        );
+
+    assert!(engine.capture_turn(sample_turn()).await.is_err());
+    let events = engine
+        .storage
+        .list_events("turn-agent", 10, 0)
+        .await
+        .unwrap();
+    assert!(events.is_empty());
+}
@@ -0,0 +1,140 @@
+//! Integration tests for web page ingestion and refresh.
+
+use std::sync::{Arc, Mutex};
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::ingest::UrlFetchPolicy;
+use mnemo_core::ingest::url::{PageStatus, UrlIngestRequest, run_url_refresh};
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+fn create_engine() -> MnemoEngine {
+    MnemoEngine::new(
+        Arc::new(DuckDbStorage::open_in_memory().unwrap()),
+        Arc::new(UsearchIndex::new(64).unwrap()),
+        Arc::new(DeterministicEmbedding::new(64)),
+        "librarian".to_string(),
+        None,
+    )
+    .with_url_fetch_policy(UrlFetchPolicy::default().allow_host("127.0.0.1"))
+}
+
+/// Serve `page` as HTML on a local port; returns the page's URL.
+async fn serve(page: Arc<Mutex<String>>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut socket, _)) = listener.accept().await else {
+                return;
+            };
+            let mut request = [0u8; 4096];
+            let _ = socket.read(&mut request).await;
+            let body = page.lock().unwrap().clone();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
+    });
+    format!("http://{addr}/pricing")
+}
+
+fn pricing(pro: &str) -> String {
+    format!(
+        "<html><head><title>Pricing</title></head><body><nav>Home | Docs</nav><main>\
+         <h1>Free</h1><p>The free plan has one seat.</p>\
+         <h1>Pro</h1><p>{pro}</p></main><footer>Copyright</footer></body></html>"
+    )
+}
+
+#[tokio::test]
+async fn reingesting_a_changed_page_invalidates_only_changed_chunks() {
+    let engine = create_engine();
+    let page = Arc::new(Mutex::new(pricing("Pro costs $20 a month.")));
+    let url = serve(page.clone()).await;
+
+    let first = engine
+        .ingest_url(UrlIngestRequest::new(&url))
+        .await
+        .unwrap();
+    assert_eq!(first.status, PageStatus::Created);
+    assert_eq!(first.format, "html");
+    assert_eq!(first.title.as_deref(), Some("Pricing"));
+    assert_eq!(first.memory_ids.len(), 2);
+    let pro = engine
+        .storage
+        .get_memory(first.memory_ids[1])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(pro.source_id.as_deref(), Some(url.as_str()));
+    assert!(pro.content.contains("$20") && !pro.content.contains("Copyright"));
+
+    let again = engine
+        .ingest_url(UrlIngestRequest::new(&url))
+        .await
+        .unwrap();
+    assert_eq!(again.status, PageStatus::Unchanged);
+    assert_eq!(again.memory_ids, first.memory_ids);
+    assert!(again.added.is_empty() && again.invalidated.is_empty());
+
+    *page.lock().unwrap() = pricing("Pro costs $25 a month.");
+    let changed = engine
+        .ingest_url(UrlIngestRequest::new(&url))
+        .await
+        .unwrap();
+    assert_eq!(changed.status, PageStatus::Updated);
+    assert_eq!(changed.memory_ids[0], first.memory_ids[0]);
+    assert_eq!(changed.invalidated, vec![first.memory_ids[1]]);
+    assert_eq!(changed.added, vec![changed.memory_ids[1]]);
+
+    let old = engine
+        .storage
+        .get_memory(first.memory_ids[1])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        old.valid_until.as_deref(),
+        Some(changed.fetched_at.as_str())
+    );
+    let new = engine
+        .storage
+        .get_memory(changed.added[0])
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(new.content.contains("$25"));
+    assert_eq!(new.valid_from.as_deref(), Some(changed.fetched_at.as_str()));
+}
+
+#[tokio::test]
+async fn refresh_pass_refetches_due_pages() {
+    let engine = create_engine();
+    let page = Arc::new(Mutex::new(pricing("Pro costs $20 a month.")));
+    let url = serve(page.clone()).await;
+
+    let mut request = UrlIngestRequest::new(&url);
+    request.refresh_interval_seconds = Some(3600);
+    let first = engine.ingest_url(request).await.unwrap();
+    assert!(first.next_refresh_at.is_some());
+    assert_eq!(engine.watched_pages().await.unwrap().len(), 1);
+
+    *page.lock().unwrap() = pricing("Pro is discontinued.");
+    let now = chrono::Utc::now();
+    let report = run_url_refresh(&engine, now).await.unwrap();
+    assert!(report.refreshed.is_empty());
+
+    let report = run_url_refresh(&engine, now + chrono::Duration::hours(2))
+        .await
+        .unwrap();
+    assert!(report.errors.is_empty());
+    assert_eq!(report.refreshed.len(), 1);
+    assert_eq!(report.refreshed[0].status, PageStatus::Updated);
+    assert_eq!(report.refreshed[0].invalidated, vec![first.memory_ids[1]]);
+}
@@ -0,0 +1,72 @@
+//! Integration tests for vacuum passes.
+
+use std::sync::Arc;
+
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::forget::{ForgetRequest, ForgetStrategy};
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::query::vacuum::VacuumRequest;
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine(storage: DuckDbStorage) -> MnemoEngine {
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(
+        Arc::new(storage),
+        index,
+        embedding,
+        "janitor".to_string(),
+        None,
+    )
+}
+
+#[tokio::test]
+async fn vacuum_compacts_the_database_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let storage = DuckDbStorage::open(&dir.path().join("vacuum.duckdb")).unwrap();
+    let engine = create_engine(storage);
+
+    let mut ids = Vec::new();
+    for i in 0..50 {
+        let request = RememberRequest::new(format!("nightly build log line {i}"));
+        ids.push(engine.remember(request).await.unwrap().id);
+    }
+    let mut forget = ForgetRequest::new(ids);
+    forget.strategy = Some(ForgetStrategy::HardDelete);
+    engine.forget(forget).await.unwrap();
+
+    // The writes above are too recent for a default pass.
+    let response = engine.vacuum(VacuumRequest::new()).await.unwrap();
+    assert!(!response.ran);
+    assert!(response.skipped_reason.unwrap().contains("write"));
+
+    let mut request = VacuumRequest::new();
+    request.force = true;
+    let response = engine.vacuum(request).await.unwrap();
+    assert!(response.ran);
+    assert!(response.bytes_before > 0);
+    assert!(response.bytes_after > 0);
+    assert_eq!(
+        response.reclaimed_bytes,
+        response.bytes_before.saturating_sub(response.bytes_after)
+    );
+}
+
+#[tokio::test]
+async fn vacuum_runs_after_a_quiet_period() {
+    let engine = create_engine(DuckDbStorage::open_in_memory().unwrap());
+    // Nothing written yet: nothing to wait for.
+    assert!(engine.vacuum(VacuumRequest::new()).await.unwrap().ran);
+
+    engine
+        .remember(RememberRequest::new("a fresh write".to_string()))
+        .await
+        .unwrap();
+    let mut request = VacuumRequest::new();
+    request.quiet_seconds = Some(0);
+    let response = engine.vacuum(request).await.unwrap();
+    assert!(response.ran);
+    assert_eq!(response.bytes_before, 0);
+}
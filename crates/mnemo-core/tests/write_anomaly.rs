@@ -0,0 +1,99 @@
+//! Integration tests for background write-pattern anomaly detection.
+
+use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use mnemo_core::embedding::DeterministicEmbedding;
+use mnemo_core::error::Error;
+use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::model::event::EventType;
+use mnemo_core::model::memory::MemoryRecord;
+use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::remember::RememberRequest;
+use mnemo_core::query::write_anomaly::{WriteAnomalyKind, WriteAnomalyPolicy};
+use mnemo_core::storage::duckdb::DuckDbStorage;
+
+fn create_engine(policy: WriteAnomalyPolicy) -> MnemoEngine {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(64).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(64));
+    MnemoEngine::new(storage, index, embedding, "agent".to_string(), None)
+        .with_write_anomaly_detection(policy)
+}
+
+/// Sixty low-importance writes spread over the past month, every hour.
+async fn seed_baseline(engine: &MnemoEngine) {
+    for i in 0..60 {
+        let mut record = MemoryRecord::new("agent".to_string(), format!("routine note {i}"));
+        let at = (Utc::now() - Duration::hours(24 + i * 12)).to_rfc3339();
+        record.importance = 0.2;
+        record.created_at = at.clone();
+        record.updated_at = at;
+        engine.storage.insert_memory(&record).await.unwrap();
+    }
+}
+
+async fn remember_burst(engine: &MnemoEngine, count: usize) {
+    for i in 0..count {
+        let mut request = RememberRequest::new(format!("urgent override {i}"));
+        request.importance = Some(0.95);
+        engine.remember(request).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn a_burst_of_inflated_writes_is_flagged_and_throttled() {
+    let engine = create_engine(WriteAnomalyPolicy {
+        throttle: true,
+        ..Default::default()
+    });
+    seed_baseline(&engine).await;
+    remember_burst(&engine, 15).await;
+
+    let report = engine.run_write_anomaly_pass().await.unwrap();
+    assert_eq!(report.agents_checked, 1);
+    let flagged = &report.flagged[0];
+    let kinds: Vec<_> = flagged.anomalies.iter().map(|a| a.kind).collect();
+    assert!(kinds.contains(&WriteAnomalyKind::WriteBurst), "{kinds:?}");
+    assert!(
+        kinds.contains(&WriteAnomalyKind::ImportanceInflation),
+        "{kinds:?}"
+    );
+    assert!(!kinds.contains(&WriteAnomalyKind::ScopeChange), "{kinds:?}");
+    assert!(flagged.event_id.is_some());
+    assert!(flagged.throttled_until.is_some());
+
+    let events = engine.storage.list_events("agent", 100, 0).await.unwrap();
+    assert!(
+        events
+            .iter()
+            .any(|e| e.event_type == EventType::WriteAnomaly)
+    );
+
+    let err = engine
+        .remember(RememberRequest::new("one more".to_string()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::RateLimited { .. }), "{err}");
+
+    assert!(engine.release_write_throttle("agent"));
+    engine
+        .remember(RememberRequest::new("one more".to_string()))
+        .await
+        .unwrap();
+
+    // The same window is reported again but not re-alerted.
+    let report = engine.run_write_anomaly_pass().await.unwrap();
+    assert!(report.flagged[0].event_id.is_none());
+    let detector = engine.write_anomaly.as_ref().unwrap();
+    assert!(detector.throttled_until("agent").is_none());
+}
+
+#[tokio::test]
+async fn agents_without_a_baseline_are_not_judged() {
+    let engine = create_engine(WriteAnomalyPolicy::default());
+    remember_burst(&engine, 15).await;
+    let report = engine.run_write_anomaly_pass().await.unwrap();
+    assert_eq!(report.agents_checked, 0);
+    assert!(report.flagged.is_empty());
+}
@@ -15,26 +15,43 @@
 //! ```
 
 use std::sync::Arc;
+use std::time::Duration;
 
+use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
+use tonic_types::{ErrorDetails, StatusExt};
 use uuid::Uuid;
 
+use mnemo_core::hash::ChainVerificationResult;
+use mnemo_core::index::snapshot::{
+    DEFAULT_DELTA_LIMIT, IndexDelta, IndexSnapshot, SnapshotManifest,
+};
 use mnemo_core::model::acl::Permission;
+use mnemo_core::model::checkpoint::Checkpoint;
 use mnemo_core::model::delegation::{Delegation, DelegationScope};
-use mnemo_core::model::memory::{MemoryType, Scope, SourceType};
+use mnemo_core::model::memory::{MemoryRecord, MemoryType, Scope, SourceType};
 use mnemo_core::query::MnemoEngine;
 use mnemo_core::query::branch::BranchRequest as CoreBranchRequest;
 use mnemo_core::query::checkpoint::CheckpointRequest as CoreCheckpointRequest;
 use mnemo_core::query::consolidate::ConsolidateRequest as CoreConsolidateRequest;
+use mnemo_core::query::ephemeral::EphemeralRequest as CoreEphemeralRequest;
 use mnemo_core::query::forget::{
     ForgetRequest as CoreForgetRequest, ForgetStrategy,
     ForgetSubjectRequest as CoreForgetSubjectRequest,
 };
+use mnemo_core::query::get_batch::GetBatchRequest as CoreGetBatchRequest;
+use mnemo_core::query::hybrid::HybridConfig;
+use mnemo_core::query::lifecycle;
 use mnemo_core::query::merge::{MergeRequest as CoreMergeRequest, MergeStrategy};
-use mnemo_core::query::recall::RecallRequest as CoreRecallRequest;
+use mnemo_core::query::pin::PinRequest as CorePinRequest;
+use mnemo_core::query::recall::{RecallRequest as CoreRecallRequest, ScoredMemory, SnippetSource};
 use mnemo_core::query::remember::RememberRequest as CoreRememberRequest;
-use mnemo_core::query::replay::ReplayRequest as CoreReplayRequest;
-use mnemo_core::query::share::ShareRequest as CoreShareRequest;
+use mnemo_core::query::replay::{MAX_REPLAY_PACE, ReplayFrame, ReplayRequest as CoreReplayRequest};
+use mnemo_core::query::share::{ShareFilter, ShareRequest as CoreShareRequest};
+use mnemo_core::query::similar::{DEFAULT_SIMILAR_LIMIT, SimilarRequest as CoreSimilarRequest};
+use mnemo_core::query::update::UpdateRequest as CoreUpdateRequest;
+use mnemo_core::query::validation;
+use mnemo_core::query::verify::VerifyRequest as CoreVerifyRequest;
 
 // ---------------------------------------------------------------------------
 // Generated protobuf code
@@ -44,24 +61,41 @@ pub mod proto {
     tonic::include_proto!("mnemo.v1");
 }
 
+use proto::index_snapshot_chunk::Part as SnapshotPart;
+use proto::mnemo_service_client::MnemoServiceClient;
 use proto::mnemo_service_server::{MnemoService, MnemoServiceServer};
 use proto::{
     BranchRequest as ProtoBranchRequest, BranchResponse as ProtoBranchResponse,
-    CheckpointRequest as ProtoCheckpointRequest, CheckpointResponse as ProtoCheckpointResponse,
+    ChainCheckpoint as ProtoChainCheckpoint, CheckpointRequest as ProtoCheckpointRequest,
+    CheckpointResponse as ProtoCheckpointResponse, CleanupRequest as ProtoCleanupRequest,
+    CleanupResponse as ProtoCleanupResponse, ClockSkew as ProtoClockSkew,
     ConsolidateRequest as ProtoConsolidateRequest, ConsolidateResponse as ProtoConsolidateResponse,
-    DelegateRequest as ProtoDelegateRequest, DelegateResponse as ProtoDelegateResponse,
+    ConsolidationRequest as ProtoConsolidationRequest,
+    ConsolidationResponse as ProtoConsolidationResponse, DecayPassRequest as ProtoDecayPassRequest,
+    DecayPassResponse as ProtoDecayPassResponse, DelegateRequest as ProtoDelegateRequest,
+    DelegateResponse as ProtoDelegateResponse, DeltaVector as ProtoDeltaVector, DependencyStatus,
     ForgetError as ProtoForgetError, ForgetRequest as ProtoForgetRequest,
     ForgetResponse as ProtoForgetResponse, ForgetSubjectRequest as ProtoForgetSubjectRequest,
-    ForgetSubjectResponse as ProtoForgetSubjectResponse, HealthRequest, HealthResponse,
-    MergeRequest as ProtoMergeRequest, MergeResponse as ProtoMergeResponse,
-    RecallRequest as ProtoRecallRequest, RecallResponse as ProtoRecallResponse,
+    ForgetSubjectResponse as ProtoForgetSubjectResponse, GetBatchRequest as ProtoGetBatchRequest,
+    GetBatchResponse as ProtoGetBatchResponse, HealthRequest, HealthResponse,
+    IndexDeltaRequest as ProtoIndexDeltaRequest, IndexDeltaResponse as ProtoIndexDeltaResponse,
+    IndexSnapshotChunk as ProtoIndexSnapshotChunk,
+    IndexSnapshotManifest as ProtoIndexSnapshotManifest,
+    IndexSnapshotRequest as ProtoIndexSnapshotRequest, MergeRequest as ProtoMergeRequest,
+    MergeResponse as ProtoMergeResponse, PinRequest as ProtoPinRequest,
+    PinResponse as ProtoPinResponse, RecallRequest as ProtoRecallRequest,
+    RecallResponse as ProtoRecallResponse,
+    RememberEphemeralRequest as ProtoRememberEphemeralRequest,
     RememberRequest as ProtoRememberRequest, RememberResponse as ProtoRememberResponse,
+    ReplayEvent as ProtoReplayEvent, ReplayFrame as ProtoReplayFrame,
     ReplayMemory as ProtoReplayMemory, ReplayRequest as ProtoReplayRequest,
-    ReplayResponse as ProtoReplayResponse, ScoredMemory as ProtoScoredMemory,
-    ShareRequest as ProtoShareRequest, ShareResponse as ProtoShareResponse,
-    TrajectoryAuditRequest as ProtoTrajectoryAuditRequest,
+    ReplayResponse as ProtoReplayResponse, ReplayStreamRequest as ProtoReplayStreamRequest,
+    ScoredMemory as ProtoScoredMemory, ShareRequest as ProtoShareRequest,
+    ShareResponse as ProtoShareResponse, SimilarRequest as ProtoSimilarRequest,
+    SimilarResponse as ProtoSimilarResponse, TrajectoryAuditRequest as ProtoTrajectoryAuditRequest,
     TrajectoryAuditResponse as ProtoTrajectoryAuditResponse,
-    TrajectoryFinding as ProtoTrajectoryFinding, VerifyRequest as ProtoVerifyRequest,
+    TrajectoryFinding as ProtoTrajectoryFinding, UpdateMemoryRequest as ProtoUpdateMemoryRequest,
+    UpdateMemoryResponse as ProtoUpdateMemoryResponse, VerifyRequest as ProtoVerifyRequest,
     VerifyResponse as ProtoVerifyResponse,
 };
 
@@ -172,6 +206,10 @@ impl MnemoService for MnemoGrpcServer {
             related_to,
             decay_rate: req.decay_rate,
             created_by: req.created_by,
+            pinned: req.pinned,
+            valid_from: req.valid_from,
+            valid_until: req.valid_until,
+            channel: None,
         };
 
         let result = self
@@ -186,6 +224,59 @@ impl MnemoService for MnemoGrpcServer {
         }))
     }
 
+    async fn remember_ephemeral(
+        &self,
+        request: Request<ProtoRememberEphemeralRequest>,
+    ) -> Result<Response<ProtoRememberResponse>, Status> {
+        let req = request.into_inner();
+
+        let memory_type = match req.memory_type {
+            Some(ref s) => Some(s.parse::<MemoryType>().map_err(|_| {
+                Status::invalid_argument(format!(
+                    "invalid memory_type '{s}': expected one of: episodic, semantic, procedural, working"
+                ))
+            })?),
+            None => None,
+        };
+        let scope = match req.scope {
+            Some(ref s) => Some(s.parse::<Scope>().map_err(|_| {
+                Status::invalid_argument(format!(
+                    "invalid scope '{s}': expected one of: private, shared, public, global"
+                ))
+            })?),
+            None => None,
+        };
+        let metadata = match req.metadata {
+            Some(ref s) => Some(
+                serde_json::from_str(s)
+                    .map_err(|e| Status::invalid_argument(format!("invalid metadata JSON: {e}")))?,
+            ),
+            None => None,
+        };
+
+        let mut core_req = CoreEphemeralRequest::new(req.content);
+        core_req.agent_id = req.agent_id;
+        core_req.memory_type = memory_type;
+        core_req.scope = scope;
+        core_req.importance = req.importance;
+        core_req.tags = (!req.tags.is_empty()).then_some(req.tags);
+        core_req.metadata = metadata;
+        core_req.org_id = req.org_id;
+        core_req.thread_id = req.thread_id;
+        core_req.ttl_seconds = req.ttl_seconds;
+
+        let result = self
+            .engine
+            .remember_ephemeral(core_req)
+            .await
+            .map_err(core_error_to_status)?;
+
+        Ok(Response::new(ProtoRememberResponse {
+            id: result.id.to_string(),
+            content_hash: result.content_hash,
+        }))
+    }
+
     // -- Recall ------------------------------------------------------------
 
     async fn recall(
@@ -225,12 +316,28 @@ impl MnemoService for MnemoGrpcServer {
         } else {
             Some(req.tags)
         };
+        let exclude_tags = if req.exclude_tags.is_empty() {
+            None
+        } else {
+            Some(req.exclude_tags)
+        };
+        let queries = if req.queries.is_empty() {
+            None
+        } else {
+            Some(req.queries)
+        };
 
         let hybrid_weights = if req.hybrid_weights.is_empty() {
             None
         } else {
             Some(req.hybrid_weights)
         };
+        let hybrid = req
+            .hybrid
+            .as_deref()
+            .map(str::parse::<HybridConfig>)
+            .transpose()
+            .map_err(core_error_to_status)?;
 
         let orientation_cache_cfg = req.orientation_cache.map(|o| {
             mnemo_core::query::orientation_cache::OrientationCacheConfig {
@@ -254,6 +361,7 @@ impl MnemoService for MnemoGrpcServer {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights,
+            hybrid,
             rrf_k: req.rrf_k,
             as_of: req.as_of,
             explain: req.explain,
@@ -264,6 +372,26 @@ impl MnemoService for MnemoGrpcServer {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: req.with_snippets,
+            profile: req.profile,
+            include: None,
+            thread_id: req.thread_id,
+            thread_isolation: req.thread_isolation,
+            exclude_query: req.exclude_query,
+            exclude_tags,
+            queries,
+            expand_query: req.expand_query,
+            channel: None,
+            fields: req.fields.as_deref().map(split_fields),
+            deadline_ms: None,
+            agent_ids: if req.agent_ids.is_empty() {
+                None
+            } else {
+                Some(req.agent_ids)
+            },
+            min_relevance: req.min_relevance,
         };
 
         let result = self
@@ -275,27 +403,7 @@ impl MnemoService for MnemoGrpcServer {
         let memories: Vec<ProtoScoredMemory> = result
             .memories
             .into_iter()
-            .map(|m| ProtoScoredMemory {
-                id: m.id.to_string(),
-                content: m.content,
-                memory_type: format!("{:?}", m.memory_type),
-                importance: m.importance,
-                score: m.score,
-                created_at: m.created_at,
-                agent_id: m.agent_id,
-                scope: format!("{:?}", m.scope),
-                tags: m.tags,
-                metadata: m.metadata.to_string(),
-                access_count: m.access_count,
-                updated_at: m.updated_at,
-                score_breakdown: m.score_breakdown.map(|b| proto::ScoreBreakdown {
-                    vector: b.vector,
-                    bm25: b.bm25,
-                    graph: b.graph,
-                    recency: b.recency,
-                    rrf_rank: b.rrf_rank,
-                }),
-            })
+            .map(scored_memory_to_proto)
             .collect();
 
         let total = result.total as u32;
@@ -361,6 +469,104 @@ impl MnemoService for MnemoGrpcServer {
         }))
     }
 
+    // -- RecallStream ------------------------------------------------------
+
+    type RecallStreamStream =
+        tokio_stream::Iter<std::vec::IntoIter<Result<ProtoScoredMemory, Status>>>;
+
+    async fn recall_stream(
+        &self,
+        request: Request<ProtoRecallRequest>,
+    ) -> Result<Response<Self::RecallStreamStream>, Status> {
+        // Same validation, ranking and hooks as unary recall; hits are sent
+        // as individual messages so clients can act on the top results
+        // before the tail arrives.
+        let response = self.recall(request).await?.into_inner();
+        let hits: Vec<Result<ProtoScoredMemory, Status>> =
+            response.memories.into_iter().map(Ok).collect();
+        Ok(Response::new(tokio_stream::iter(hits)))
+    }
+
+    // -- Similar -----------------------------------------------------------
+
+    async fn similar(
+        &self,
+        request: Request<ProtoSimilarRequest>,
+    ) -> Result<Response<ProtoSimilarResponse>, Status> {
+        let req = request.into_inner();
+
+        let memory_id =
+            validation::parse_uuid("memory_id", &req.memory_id).map_err(core_error_to_status)?;
+        let memory_type = match req.memory_type {
+            Some(ref s) => Some(s.parse::<MemoryType>().map_err(|_| {
+                Status::invalid_argument(format!(
+                    "invalid memory_type '{s}': expected one of: episodic, semantic, procedural, working"
+                ))
+            })?),
+            None => None,
+        };
+        let scope = match req.scope {
+            Some(ref s) => Some(s.parse::<Scope>().map_err(|_| {
+                Status::invalid_argument(format!(
+                    "invalid scope '{s}': expected one of: private, shared, public, global"
+                ))
+            })?),
+            None => None,
+        };
+
+        let limit = req.limit.map_or(DEFAULT_SIMILAR_LIMIT, |l| l as usize);
+        let mut core_req = CoreSimilarRequest::new(memory_id, limit);
+        core_req.agent_id = req.agent_id;
+        core_req.memory_type = memory_type;
+        core_req.scope = scope;
+        core_req.tags = (!req.tags.is_empty()).then_some(req.tags);
+        core_req.min_importance = req.min_importance;
+
+        let result = self
+            .engine
+            .similar(core_req)
+            .await
+            .map_err(core_error_to_status)?;
+
+        Ok(Response::new(ProtoSimilarResponse {
+            memory_id: result.memory_id.to_string(),
+            memories: result
+                .memories
+                .into_iter()
+                .map(scored_memory_to_proto)
+                .collect(),
+        }))
+    }
+
+    // -- GetBatch ----------------------------------------------------------
+
+    async fn get_batch(
+        &self,
+        request: Request<ProtoGetBatchRequest>,
+    ) -> Result<Response<ProtoGetBatchResponse>, Status> {
+        let req = request.into_inner();
+
+        let ids = validation::parse_uuids("ids", &req.ids).map_err(core_error_to_status)?;
+        let mut core_req = CoreGetBatchRequest::new(ids);
+        core_req.agent_id = req.agent_id;
+        core_req.fields = req.fields.as_deref().map(split_fields);
+
+        let result = self
+            .engine
+            .get_batch(core_req)
+            .await
+            .map_err(core_error_to_status)?;
+
+        Ok(Response::new(ProtoGetBatchResponse {
+            memories: result
+                .memories
+                .into_iter()
+                .map(scored_memory_to_proto)
+                .collect(),
+            missing: result.missing.iter().map(Uuid::to_string).collect(),
+        }))
+    }
+
     // -- Forget ------------------------------------------------------------
 
     async fn forget(
@@ -369,14 +575,8 @@ impl MnemoService for MnemoGrpcServer {
     ) -> Result<Response<ProtoForgetResponse>, Status> {
         let req = request.into_inner();
 
-        let memory_ids: Vec<Uuid> = req
-            .memory_ids
-            .iter()
-            .map(|s| {
-                Uuid::parse_str(s)
-                    .map_err(|e| Status::invalid_argument(format!("invalid UUID '{s}': {e}")))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+        let memory_ids =
+            validation::parse_uuids("memory_ids", &req.memory_ids).map_err(core_error_to_status)?;
 
         let strategy = match req.strategy {
             Some(ref s) => {
@@ -404,6 +604,8 @@ impl MnemoService for MnemoGrpcServer {
             agent_id: req.agent_id,
             strategy,
             criteria: None,
+            force: req.force,
+            dry_run: req.dry_run,
         };
 
         let result = self
@@ -423,18 +625,100 @@ impl MnemoService for MnemoGrpcServer {
             })
             .collect();
 
-        Ok(Response::new(ProtoForgetResponse { forgotten, errors }))
+        Ok(Response::new(ProtoForgetResponse {
+            forgotten,
+            errors,
+            dry_run: result.dry_run,
+        }))
+    }
+
+    // -- UpdateMemory ------------------------------------------------------
+
+    async fn update_memory(
+        &self,
+        request: Request<ProtoUpdateMemoryRequest>,
+    ) -> Result<Response<ProtoUpdateMemoryResponse>, Status> {
+        let req = request.into_inner();
+        let memory_id =
+            validation::parse_uuid("memory_id", &req.memory_id).map_err(core_error_to_status)?;
+        let metadata = match req.metadata {
+            Some(ref s) => Some(
+                serde_json::from_str(s)
+                    .map_err(|e| Status::invalid_argument(format!("invalid metadata JSON: {e}")))?,
+            ),
+            None => None,
+        };
+
+        let mut core_req = CoreUpdateRequest::new(memory_id);
+        core_req.agent_id = req.agent_id;
+        core_req.importance = req.importance;
+        core_req.tags = (!req.tags.is_empty()).then_some(req.tags);
+        core_req.metadata = metadata;
+        core_req.expected_version = req.expected_version;
+        let result = self
+            .engine
+            .update(core_req)
+            .await
+            .map_err(core_error_to_status)?;
+
+        Ok(Response::new(ProtoUpdateMemoryResponse {
+            id: result.id.to_string(),
+            version: result.version,
+            updated_at: result.updated_at,
+        }))
+    }
+
+    // -- Pin ---------------------------------------------------------------
+
+    async fn pin(
+        &self,
+        request: Request<ProtoPinRequest>,
+    ) -> Result<Response<ProtoPinResponse>, Status> {
+        let req = request.into_inner();
+        let memory_id =
+            validation::parse_uuid("memory_id", &req.memory_id).map_err(core_error_to_status)?;
+
+        let mut core_req = CorePinRequest::new(memory_id, req.pinned);
+        core_req.agent_id = req.agent_id;
+        let result = self
+            .engine
+            .pin(core_req)
+            .await
+            .map_err(core_error_to_status)?;
+
+        Ok(Response::new(ProtoPinResponse {
+            memory_id: result.memory_id.to_string(),
+            pinned: result.pinned,
+            changed: result.changed,
+        }))
     }
 
     // -- Health ------------------------------------------------------------
 
     async fn health(
         &self,
-        _request: Request<HealthRequest>,
+        request: Request<HealthRequest>,
     ) -> Result<Response<HealthResponse>, Status> {
+        let report = if request.into_inner().deep {
+            self.engine.readiness().await
+        } else {
+            mnemo_core::query::health::liveness()
+        };
         Ok(Response::new(HealthResponse {
-            status: "ok".to_string(),
-            version: env!("CARGO_PKG_VERSION").to_string(),
+            status: report.status.to_string(),
+            version: report.version,
+            ready: report.ready,
+            checks: report
+                .checks
+                .into_iter()
+                .map(|c| DependencyStatus {
+                    name: c.name,
+                    status: c.status.to_string(),
+                    required: c.required,
+                    latency_ms: c.latency_ms,
+                    detail: c.detail,
+                })
+                .collect(),
         }))
     }
 
@@ -445,8 +729,44 @@ impl MnemoService for MnemoGrpcServer {
         request: Request<ProtoShareRequest>,
     ) -> Result<Response<ProtoShareResponse>, Status> {
         let req = request.into_inner();
-        let memory_id = Uuid::parse_str(&req.memory_id)
-            .map_err(|e| Status::invalid_argument(format!("invalid UUID: {e}")))?;
+        let memory_ids = if req.memory_ids.is_empty() {
+            None
+        } else {
+            Some(
+                validation::parse_uuids("memory_ids", &req.memory_ids)
+                    .map_err(core_error_to_status)?,
+            )
+        };
+        let memory_id = if memory_ids.is_some() || req.filter.is_some() {
+            Uuid::nil()
+        } else {
+            validation::parse_uuid("memory_id", &req.memory_id).map_err(core_error_to_status)?
+        };
+        let filter = match req.filter {
+            Some(f) => Some(ShareFilter {
+                tags: f.tags,
+                memory_type: match f.memory_type {
+                    Some(ref s) => Some(s.parse::<MemoryType>().map_err(|_| {
+                        Status::invalid_argument(format!(
+                            "invalid memory_type '{s}': expected one of: episodic, semantic, procedural, working"
+                        ))
+                    })?),
+                    None => None,
+                },
+                scope: match f.scope {
+                    Some(ref s) => Some(s.parse::<Scope>().map_err(|_| {
+                        Status::invalid_argument(format!(
+                            "invalid scope '{s}': expected one of: private, shared, public, global"
+                        ))
+                    })?),
+                    None => None,
+                },
+                org_id: f.org_id,
+                thread_id: f.thread_id,
+                min_importance: f.min_importance,
+            }),
+            None => None,
+        };
         let permission = match req.permission {
             Some(ref s) => match s.parse::<Permission>() {
                 Ok(p) => Some(p),
@@ -472,6 +792,9 @@ impl MnemoService for MnemoGrpcServer {
             target_agent_ids,
             permission,
             expires_in_hours: req.expires_in_hours,
+            memory_ids,
+            filter,
+            dry_run: req.dry_run,
         };
         let result = self
             .engine
@@ -480,12 +803,18 @@ impl MnemoService for MnemoGrpcServer {
             .map_err(core_error_to_status)?;
 
         Ok(Response::new(ProtoShareResponse {
-            acl_id: result.acl_id.to_string(),
+            acl_id: if result.dry_run {
+                String::new()
+            } else {
+                result.acl_id.to_string()
+            },
             acl_ids: result.acl_ids.iter().map(|id| id.to_string()).collect(),
             memory_id: result.memory_id.to_string(),
             shared_with: result.shared_with,
             shared_with_all: result.shared_with_all,
             permission: result.permission.to_string(),
+            memory_ids: result.memory_ids.iter().map(|id| id.to_string()).collect(),
+            dry_run: result.dry_run,
         }))
     }
 
@@ -538,20 +867,14 @@ impl MnemoService for MnemoGrpcServer {
     ) -> Result<Response<ProtoConsolidateResponse>, Status> {
         let req = request.into_inner();
 
-        let mut memory_ids = Vec::with_capacity(req.memory_ids.len());
-        for s in &req.memory_ids {
-            memory_ids.push(
-                Uuid::parse_str(s).map_err(|e| {
-                    Status::invalid_argument(format!("invalid memory id '{s}': {e}"))
-                })?,
-            );
-        }
-        let supersede = match req.supersede {
-            Some(ref s) => Some(Uuid::parse_str(s).map_err(|e| {
-                Status::invalid_argument(format!("invalid supersede id '{s}': {e}"))
-            })?),
-            None => None,
-        };
+        let memory_ids =
+            validation::parse_uuids("memory_ids", &req.memory_ids).map_err(core_error_to_status)?;
+        let supersede = req
+            .supersede
+            .as_deref()
+            .map(|s| validation::parse_uuid("supersede", s))
+            .transpose()
+            .map_err(core_error_to_status)?;
         let metadata: Option<serde_json::Value> = match req.metadata {
             Some(ref s) => Some(
                 serde_json::from_str(s)
@@ -593,18 +916,12 @@ impl MnemoService for MnemoGrpcServer {
         request: Request<ProtoBranchRequest>,
     ) -> Result<Response<ProtoBranchResponse>, Status> {
         let req = request.into_inner();
-        let source_checkpoint_id = match req.source_checkpoint_id {
-            Some(ref s) => match Uuid::parse_str(s) {
-                Ok(id) => Some(id),
-                Err(e) => {
-                    return Err(Status::invalid_argument(format!(
-                        "invalid source_checkpoint_id '{}': {}",
-                        s, e
-                    )));
-                }
-            },
-            None => None,
-        };
+        let source_checkpoint_id = req
+            .source_checkpoint_id
+            .as_deref()
+            .map(|s| validation::parse_uuid("source_checkpoint_id", s))
+            .transpose()
+            .map_err(core_error_to_status)?;
 
         let core_req = CoreBranchRequest {
             thread_id: req.thread_id,
@@ -653,12 +970,10 @@ impl MnemoService for MnemoGrpcServer {
         let cherry_pick_ids = if req.cherry_pick_ids.is_empty() {
             None
         } else {
-            let ids: Result<Vec<Uuid>, _> = req
-                .cherry_pick_ids
-                .iter()
-                .map(|s| Uuid::parse_str(s))
-                .collect();
-            Some(ids.map_err(|e| Status::invalid_argument(format!("invalid UUID: {e}")))?)
+            Some(
+                validation::parse_uuids("cherry_pick_ids", &req.cherry_pick_ids)
+                    .map_err(core_error_to_status)?,
+            )
         };
 
         let core_req = CoreMergeRequest {
@@ -688,65 +1003,56 @@ impl MnemoService for MnemoGrpcServer {
         &self,
         request: Request<ProtoReplayRequest>,
     ) -> Result<Response<ProtoReplayResponse>, Status> {
-        let req = request.into_inner();
-        let checkpoint_id = match req.checkpoint_id {
-            Some(ref s) => match Uuid::parse_str(s) {
-                Ok(id) => Some(id),
-                Err(e) => {
-                    return Err(Status::invalid_argument(format!(
-                        "invalid checkpoint_id '{}': {}",
-                        s, e
-                    )));
-                }
-            },
-            None => None,
-        };
-
-        let core_req = CoreReplayRequest {
-            thread_id: req.thread_id,
-            agent_id: req.agent_id,
-            checkpoint_id,
-            branch_name: req.branch_name,
-            as_of: req.as_of,
-        };
+        let core_req = replay_request_from_proto(request.into_inner())?;
         let result = self
             .engine
             .replay(core_req)
             .await
             .map_err(core_error_to_status)?;
+        Ok(Response::new(replay_state_to_proto(
+            &result.checkpoint,
+            &result.memories,
+            result.chain_verification.as_ref(),
+            result.events.len(),
+        )))
+    }
 
-        let checkpoint_json =
-            serde_json::to_string(&result.checkpoint).unwrap_or_else(|_| "{}".to_string());
-        let memories: Vec<ProtoReplayMemory> = result
-            .memories
-            .iter()
-            .map(|m| ProtoReplayMemory {
-                id: m.id.to_string(),
-                content: m.content.clone(),
-                memory_type: format!("{:?}", m.memory_type),
-                created_at: m.created_at.clone(),
-            })
-            .collect();
+    // -- ReplayStream ------------------------------------------------------
 
-        let (chain_valid, chain_total, chain_verified) =
-            if let Some(ref cv) = result.chain_verification {
-                (
-                    Some(cv.valid),
-                    Some(cv.total_records as u32),
-                    Some(cv.verified_records as u32),
-                )
-            } else {
-                (None, None, None)
-            };
+    type ReplayStreamStream = ReceiverStream<Result<ProtoReplayFrame, Status>>;
 
-        Ok(Response::new(ProtoReplayResponse {
-            checkpoint_json,
-            memories,
-            event_count: result.events.len() as u32,
-            chain_valid,
-            chain_total,
-            chain_verified,
-        }))
+    async fn replay_stream(
+        &self,
+        request: Request<ProtoReplayStreamRequest>,
+    ) -> Result<Response<Self::ReplayStreamStream>, Status> {
+        let req = request.into_inner();
+        let core_req = replay_request_from_proto(
+            req.replay
+                .ok_or_else(|| Status::invalid_argument("replay is required"))?,
+        )?;
+        let pace = Duration::from_millis(u64::from(req.pace_ms.unwrap_or(0))).min(MAX_REPLAY_PACE);
+        // Errors (not found, permission denied) fail the call before any
+        // frame is sent.
+        let frames = self
+            .engine
+            .replay(core_req)
+            .await
+            .map_err(core_error_to_status)?
+            .into_frames();
+
+        let (tx, rx) = tokio::sync::mpsc::channel(16);
+        tokio::spawn(async move {
+            for (i, frame) in frames.into_iter().enumerate() {
+                if i > 1 && !pace.is_zero() {
+                    tokio::time::sleep(pace).await;
+                }
+                if tx.send(Ok(replay_frame_to_proto(frame))).await.is_err() {
+                    // The client went away.
+                    return;
+                }
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
     }
 
     // -- Delegate ----------------------------------------------------------
@@ -762,14 +1068,8 @@ impl MnemoService for MnemoGrpcServer {
             .map_err(|e: mnemo_core::error::Error| Status::invalid_argument(e.to_string()))?;
 
         let scope = if !req.memory_ids.is_empty() {
-            let ids: Vec<Uuid> = req
-                .memory_ids
-                .iter()
-                .map(|s| {
-                    Uuid::parse_str(s)
-                        .map_err(|e| Status::invalid_argument(format!("invalid UUID: {e}")))
-                })
-                .collect::<Result<Vec<_>, _>>()?;
+            let ids = validation::parse_uuids("memory_ids", &req.memory_ids)
+                .map_err(core_error_to_status)?;
             DelegationScope::ByMemoryId(ids)
         } else if !req.tags.is_empty() {
             DelegationScope::ByTag(req.tags)
@@ -778,9 +1078,9 @@ impl MnemoService for MnemoGrpcServer {
         };
 
         let now = chrono::Utc::now();
-        let expires_at = req
-            .expires_in_hours
-            .map(|h| (now + chrono::Duration::seconds((h * 3600.0) as i64)).to_rfc3339());
+        let expires_at =
+            validation::expiry_after_hours("expires_in_hours", req.expires_in_hours, now)
+                .map_err(core_error_to_status)?;
 
         let delegation = Delegation {
             id: Uuid::now_v7(),
@@ -814,11 +1114,33 @@ impl MnemoService for MnemoGrpcServer {
         request: Request<ProtoVerifyRequest>,
     ) -> Result<Response<ProtoVerifyResponse>, Status> {
         let req = request.into_inner();
-        let result = self
+        let mut verify = CoreVerifyRequest::new();
+        verify.agent_id = req.agent_id.clone();
+        verify.thread_id = req.thread_id.clone();
+        verify.incremental = req.incremental;
+        verify.max_records = req.max_records.map(|n| n as usize);
+        let verified = self
             .engine
-            .verify_integrity(req.agent_id, req.thread_id.as_deref())
+            .verify_memory_chain(verify)
             .await
             .map_err(core_error_to_status)?;
+        let result = verified.result;
+        let clock_skew = self
+            .engine
+            .detect_clock_skew(req.agent_id, req.thread_id.as_deref())
+            .await
+            .map_err(core_error_to_status)?
+            .into_iter()
+            .map(|skew| ProtoClockSkew {
+                kind: skew.kind.as_str().to_string(),
+                agent_id: skew.agent_id,
+                event_id: skew.event_id.to_string(),
+                previous_event_id: skew.previous_event_id.to_string(),
+                logical_clock: skew.logical_clock,
+                timestamp: skew.timestamp,
+                previous_timestamp: skew.previous_timestamp,
+            })
+            .collect();
 
         Ok(Response::new(ProtoVerifyResponse {
             valid: result.valid,
@@ -826,6 +1148,18 @@ impl MnemoService for MnemoGrpcServer {
             verified_records: result.verified_records as u32,
             first_broken_at: result.first_broken_at.map(|id| id.to_string()),
             error_message: result.error_message,
+            clock_skew,
+            complete: verified.complete,
+            checkpoint: verified.checkpoint.map(|c| ProtoChainCheckpoint {
+                agent_id: c.agent_id,
+                thread_id: c.thread_id,
+                verified_count: c.verified_count,
+                last_record_id: c.last_record_id.to_string(),
+                last_created_at: c.last_created_at,
+                last_content_hash: c.last_content_hash,
+                rolling_root: c.rolling_root,
+                verified_at: c.verified_at,
+            }),
         }))
     }
 
@@ -929,6 +1263,7 @@ impl MnemoService for MnemoGrpcServer {
             subject_id: req.subject_id,
             agent_id: req.agent_id,
             strategy,
+            dry_run: req.dry_run,
         };
 
         let result = self
@@ -963,10 +1298,381 @@ impl MnemoService for MnemoGrpcServer {
             forgotten: result.forgotten.iter().map(|id| id.to_string()).collect(),
             cascaded_events: result.cascaded_events as u32,
             errors,
+            dry_run: result.dry_run,
+        }))
+    }
+
+    // -- Maintenance -------------------------------------------------------
+
+    async fn run_decay_pass(
+        &self,
+        request: Request<ProtoDecayPassRequest>,
+    ) -> Result<Response<ProtoDecayPassResponse>, Status> {
+        let req = request.into_inner();
+        let result = self
+            .engine
+            .run_decay_pass(
+                req.agent_id,
+                req.archive_threshold
+                    .unwrap_or(lifecycle::DEFAULT_ARCHIVE_THRESHOLD),
+                req.forget_threshold
+                    .unwrap_or(lifecycle::DEFAULT_FORGET_THRESHOLD),
+            )
+            .await
+            .map_err(core_error_to_status)?;
+
+        Ok(Response::new(ProtoDecayPassResponse {
+            archived: result.archived as u32,
+            forgotten: result.forgotten as u32,
+            total_processed: result.total_processed as u32,
         }))
     }
+
+    async fn run_consolidation(
+        &self,
+        request: Request<ProtoConsolidationRequest>,
+    ) -> Result<Response<ProtoConsolidationResponse>, Status> {
+        let req = request.into_inner();
+        let min_cluster_size = req
+            .min_cluster_size
+            .map_or(lifecycle::DEFAULT_MIN_CLUSTER_SIZE, |n| n as usize);
+        if min_cluster_size == 0 {
+            return Err(Status::invalid_argument("min_cluster_size must be > 0"));
+        }
+        let result = self
+            .engine
+            .run_consolidation(req.agent_id, min_cluster_size)
+            .await
+            .map_err(core_error_to_status)?;
+
+        Ok(Response::new(ProtoConsolidationResponse {
+            clusters_found: result.clusters_found as u32,
+            new_memories_created: result.new_memories_created as u32,
+            originals_consolidated: result.originals_consolidated as u32,
+            clusters_skipped_below_threshold: result.clusters_skipped_below_threshold as u32,
+        }))
+    }
+
+    async fn run_cleanup(
+        &self,
+        request: Request<ProtoCleanupRequest>,
+    ) -> Result<Response<ProtoCleanupResponse>, Status> {
+        let mut core_req = lifecycle::CleanupRequest::new();
+        core_req.dry_run = request.into_inner().dry_run;
+        let result = self
+            .engine
+            .run_cleanup_with(core_req)
+            .await
+            .map_err(core_error_to_status)?;
+
+        Ok(Response::new(ProtoCleanupResponse {
+            dry_run: result.dry_run,
+            expired_ids: result.ttl.memory_ids.iter().map(Uuid::to_string).collect(),
+            purged_ids: result
+                .trash
+                .memory_ids
+                .iter()
+                .map(Uuid::to_string)
+                .collect(),
+            expired_deleted: result.ttl.swept_count as u32,
+            expiry_warnings: result.ttl.warned_count as u32,
+            trash_purged: result.trash.purged_count as u32,
+            access_log_pruned: result.access_log_pruned as u32,
+            events_archived: result.events_archived as u32,
+            ttl_errors: result
+                .ttl
+                .errors
+                .into_iter()
+                .map(|e| ProtoForgetError {
+                    id: e.memory_id.to_string(),
+                    error: e.error,
+                })
+                .collect(),
+            purge_errors: result
+                .trash
+                .errors
+                .into_iter()
+                .map(|e| ProtoForgetError {
+                    id: e.memory_id.to_string(),
+                    error: e.error,
+                })
+                .collect(),
+        }))
+    }
+
+    // -- Replica bootstrap -------------------------------------------------
+
+    type GetIndexSnapshotStream = ReceiverStream<Result<ProtoIndexSnapshotChunk, Status>>;
+
+    async fn get_index_snapshot(
+        &self,
+        _request: Request<ProtoIndexSnapshotRequest>,
+    ) -> Result<Response<Self::GetIndexSnapshotStream>, Status> {
+        let engine = self.engine.clone();
+        let snapshot = tokio::task::spawn_blocking(move || engine.index_snapshot())
+            .await
+            .map_err(|e| Status::internal(format!("index snapshot task failed: {e}")))?
+            .map_err(core_error_to_status)?;
+
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        tokio::spawn(async move {
+            let manifest = snapshot_manifest_to_proto(&snapshot.manifest);
+            let parts = std::iter::once(SnapshotPart::Manifest(manifest)).chain(
+                snapshot
+                    .payload
+                    .chunks(SNAPSHOT_CHUNK_BYTES)
+                    .map(|chunk| SnapshotPart::Data(chunk.to_vec())),
+            );
+            for part in parts {
+                let chunk = ProtoIndexSnapshotChunk { part: Some(part) };
+                if tx.send(Ok(chunk)).await.is_err() {
+                    // The client went away.
+                    return;
+                }
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn get_index_delta(
+        &self,
+        request: Request<ProtoIndexDeltaRequest>,
+    ) -> Result<Response<ProtoIndexDeltaResponse>, Status> {
+        let req = request.into_inner();
+        let limit = match req.limit {
+            Some(0) => return Err(Status::invalid_argument("limit must be > 0")),
+            Some(limit) => (limit as usize).min(mnemo_core::query::MAX_BATCH_QUERY_LIMIT),
+            None => DEFAULT_DELTA_LIMIT,
+        };
+        let delta = self
+            .engine
+            .index_delta(&req.since, limit)
+            .await
+            .map_err(core_error_to_status)?;
+
+        Ok(Response::new(ProtoIndexDeltaResponse {
+            vectors: delta
+                .vectors
+                .into_iter()
+                .map(|(id, vector)| ProtoDeltaVector {
+                    id: id.to_string(),
+                    vector,
+                })
+                .collect(),
+            removed: delta.removed.iter().map(Uuid::to_string).collect(),
+            next: delta.next,
+        }))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Replica bootstrap
+// ---------------------------------------------------------------------------
+
+/// Payload bytes per `GetIndexSnapshot` message.
+const SNAPSHOT_CHUNK_BYTES: usize = 1 << 20;
+
+/// What [`bootstrap_index`] restored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexBootstrap {
+    pub manifest: SnapshotManifest,
+    /// Vectors added or replaced by deltas after the snapshot.
+    pub delta_vectors: usize,
+    /// Vectors removed by deltas after the snapshot.
+    pub delta_removed: usize,
+    /// Where the replica caught up to; request the next delta from here.
+    pub since: String,
+}
+
+/// Bootstrap the vector index of `engine` from the node behind `client`:
+/// download and restore its latest index snapshot, then apply deltas from
+/// the snapshot's watermark until one comes back empty.
+///
+/// Only the index is restored; memories themselves reach the replica
+/// through storage replication or [`SyncEngine`](mnemo_core::sync::SyncEngine).
+pub async fn bootstrap_index<T>(
+    client: &mut MnemoServiceClient<T>,
+    engine: &MnemoEngine,
+) -> Result<IndexBootstrap, Status>
+where
+    T: tonic::client::GrpcService<tonic::body::Body>,
+    T::Error: Into<tonic::codegen::StdError>,
+    T::ResponseBody: tonic::codegen::Body<Data = tonic::codegen::Bytes> + Send + 'static,
+    <T::ResponseBody as tonic::codegen::Body>::Error: Into<tonic::codegen::StdError> + Send,
+{
+    let mut stream = client
+        .get_index_snapshot(ProtoIndexSnapshotRequest {})
+        .await?
+        .into_inner();
+    let manifest = match stream.message().await? {
+        Some(ProtoIndexSnapshotChunk {
+            part: Some(SnapshotPart::Manifest(manifest)),
+        }) => snapshot_manifest_from_proto(manifest),
+        _ => {
+            return Err(Status::data_loss(
+                "index snapshot stream did not start with a manifest",
+            ));
+        }
+    };
+    let mut payload = Vec::with_capacity(manifest.bytes as usize);
+    while let Some(chunk) = stream.message().await? {
+        match chunk.part {
+            Some(SnapshotPart::Data(data)) => payload.extend_from_slice(&data),
+            _ => return Err(Status::data_loss("unexpected index snapshot message")),
+        }
+    }
+    let snapshot = IndexSnapshot { manifest, payload };
+    engine
+        .restore_index_snapshot(&snapshot)
+        .map_err(core_error_to_status)?;
+    tracing::info!(
+        vectors = snapshot.manifest.vectors,
+        bytes = snapshot.manifest.bytes,
+        watermark = %snapshot.manifest.watermark,
+        "restored vector index snapshot"
+    );
+
+    let mut report = IndexBootstrap {
+        since: snapshot.manifest.watermark.clone(),
+        manifest: snapshot.manifest,
+        delta_vectors: 0,
+        delta_removed: 0,
+    };
+    loop {
+        let delta = client
+            .get_index_delta(ProtoIndexDeltaRequest {
+                since: report.since.clone(),
+                limit: None,
+            })
+            .await?
+            .into_inner();
+        let Some(next) = delta.next else {
+            return Ok(report);
+        };
+        let parse_id = |s: &str| {
+            Uuid::parse_str(s).map_err(|e| Status::data_loss(format!("invalid UUID in delta: {e}")))
+        };
+        let delta = IndexDelta {
+            vectors: delta
+                .vectors
+                .into_iter()
+                .map(|v| Ok((parse_id(&v.id)?, v.vector)))
+                .collect::<Result<_, Status>>()?,
+            removed: delta
+                .removed
+                .iter()
+                .map(|s| parse_id(s))
+                .collect::<Result<_, Status>>()?,
+            next: Some(next.clone()),
+        };
+        delta
+            .apply(engine.index.as_ref())
+            .map_err(core_error_to_status)?;
+        report.delta_vectors += delta.vectors.len();
+        report.delta_removed += delta.removed.len();
+        report.since = next;
+    }
+}
+
+fn snapshot_manifest_to_proto(manifest: &SnapshotManifest) -> ProtoIndexSnapshotManifest {
+    ProtoIndexSnapshotManifest {
+        format_version: manifest.format_version,
+        dimensions: manifest.dimensions as u64,
+        vectors: manifest.vectors as u64,
+        watermark: manifest.watermark.clone(),
+        checksum: manifest.checksum.clone(),
+        bytes: manifest.bytes,
+    }
+}
+
+fn snapshot_manifest_from_proto(manifest: ProtoIndexSnapshotManifest) -> SnapshotManifest {
+    SnapshotManifest {
+        format_version: manifest.format_version,
+        dimensions: manifest.dimensions as usize,
+        vectors: manifest.vectors as usize,
+        watermark: manifest.watermark,
+        checksum: manifest.checksum,
+        bytes: manifest.bytes,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Request IDs
+// ---------------------------------------------------------------------------
+
+/// Tower layer giving every RPC a correlation ID (see
+/// [`mnemo_core::request_id`]): the caller's `x-request-id` metadata when
+/// usable, a generated ID otherwise. The RPC runs inside the ID's scope and
+/// a tracing span carrying it, and the ID is echoed in the response's
+/// initial metadata.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestIdLayer;
+
+impl<S> tower::Layer<S> for RequestIdLayer {
+    type Service = RequestIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestIdService { inner }
+    }
+}
+
+/// The service [`RequestIdLayer`] wraps around the gRPC routes.
+#[derive(Debug, Clone)]
+pub struct RequestIdService<S> {
+    inner: S,
+}
+
+impl<S, B, ResBody> tower::Service<http::Request<B>> for RequestIdService<S>
+where
+    S: tower::Service<http::Request<B>, Response = http::Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future =
+        std::pin::Pin<Box<dyn std::future::Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<B>) -> Self::Future {
+        use tracing::Instrument;
+
+        let request_id = mnemo_core::request_id::accept(
+            req.headers()
+                .get(mnemo_core::request_id::HEADER)
+                .and_then(|v| v.to_str().ok()),
+        );
+        let span = tracing::info_span!(
+            "rpc",
+            request_id = %request_id,
+            path = %req.uri().path(),
+        );
+        let fut = span.in_scope(|| {
+            mnemo_core::request_id::sync_scope(request_id.clone(), || self.inner.call(req))
+        });
+        Box::pin(async move {
+            let mut response =
+                mnemo_core::request_id::scope(request_id.clone(), fut.instrument(span)).await?;
+            if let Ok(value) = http::HeaderValue::from_str(&request_id) {
+                response
+                    .headers_mut()
+                    .insert(mnemo_core::request_id::HEADER, value);
+            }
+            Ok(response)
+        })
+    }
 }
 
+/// The router [`router`] and [`router_with_auth`] build.
+pub type GrpcRouter = tonic::transport::server::Router<
+    tower::layer::util::Stack<RequestIdLayer, tower::layer::util::Identity>,
+>;
+
 // ---------------------------------------------------------------------------
 // Router constructor
 // ---------------------------------------------------------------------------
@@ -994,7 +1700,7 @@ impl MnemoService for MnemoGrpcServer {
 /// every RPC must carry a matching `authorization` metadata value or it is
 /// rejected with `UNAUTHENTICATED`. When unset, the server runs **open** and
 /// logs a warning.
-pub fn router(engine: Arc<MnemoEngine>) -> tonic::transport::server::Router {
+pub fn router(engine: Arc<MnemoEngine>) -> GrpcRouter {
     let token = std::env::var("MNEMO_AUTH_TOKEN")
         .ok()
         .filter(|s| !s.is_empty());
@@ -1004,10 +1710,12 @@ pub fn router(engine: Arc<MnemoEngine>) -> tonic::transport::server::Router {
 /// Like [`router`] but with the bearer secret passed explicitly. `Some(token)`
 /// installs a tonic interceptor that requires `authorization: <token>` (a bare
 /// token or `Bearer <token>`) on every RPC; `None` runs open (with a warning).
-pub fn router_with_auth(
-    engine: Arc<MnemoEngine>,
-    auth_token: Option<String>,
-) -> tonic::transport::server::Router {
+///
+/// When the engine carries an
+/// [`ActivityTracker`](mnemo_core::activity::ActivityTracker), every accepted
+/// RPC touches it so the idle-timeout watchdog sees gRPC traffic.
+pub fn router_with_auth(engine: Arc<MnemoEngine>, auth_token: Option<String>) -> GrpcRouter {
+    let tracker = engine.activity_tracker.clone();
     let svc = MnemoGrpcServer::new(engine);
     match auth_token {
         Some(token) if !token.is_empty() => {
@@ -1021,6 +1729,9 @@ pub fn router_with_auth(
                     .get("authorization")
                     .and_then(|v| v.to_str().ok());
                 if mnemo_core::auth::bearer_token_matches(provided, &expected) {
+                    if let Some(ref tracker) = tracker {
+                        tracker.touch();
+                    }
                     Ok(req)
                 } else {
                     Err(Status::unauthenticated(
@@ -1029,6 +1740,7 @@ pub fn router_with_auth(
                 }
             };
             tonic::transport::Server::builder()
+                .layer(RequestIdLayer)
                 .add_service(MnemoServiceServer::with_interceptor(svc, interceptor))
         }
         _ => {
@@ -1036,7 +1748,20 @@ pub fn router_with_auth(
                 "gRPC API running WITHOUT authentication — set MNEMO_AUTH_TOKEN to require a \
                  bearer token. Do not expose an unauthenticated memory server."
             );
-            tonic::transport::Server::builder().add_service(MnemoServiceServer::new(svc))
+            match tracker {
+                Some(tracker) => {
+                    let interceptor = move |req: Request<()>| -> Result<Request<()>, Status> {
+                        tracker.touch();
+                        Ok(req)
+                    };
+                    tonic::transport::Server::builder()
+                        .layer(RequestIdLayer)
+                        .add_service(MnemoServiceServer::with_interceptor(svc, interceptor))
+                }
+                None => tonic::transport::Server::builder()
+                    .layer(RequestIdLayer)
+                    .add_service(MnemoServiceServer::new(svc)),
+            }
         }
     }
 }
@@ -1045,14 +1770,195 @@ pub fn router_with_auth(
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Parse a comma-separated `fields` projection.
+fn split_fields(fields: &str) -> Vec<String> {
+    fields
+        .split(',')
+        .map(|f| f.trim().to_string())
+        .filter(|f| !f.is_empty())
+        .collect()
+}
+
 /// Map a `mnemo_core::error::Error` to a tonic `Status`.
+fn scored_memory_to_proto(m: ScoredMemory) -> ProtoScoredMemory {
+    ProtoScoredMemory {
+        id: m.id.to_string(),
+        content: m.content,
+        memory_type: format!("{:?}", m.memory_type),
+        importance: m.importance,
+        score: m.score,
+        created_at: m.created_at,
+        agent_id: m.agent_id,
+        scope: format!("{:?}", m.scope),
+        tags: m.tags,
+        // Null when a fields projection left it out.
+        metadata: if m.metadata.is_null() {
+            String::new()
+        } else {
+            m.metadata.to_string()
+        },
+        access_count: m.access_count,
+        updated_at: m.updated_at,
+        score_breakdown: m.score_breakdown.map(|b| proto::ScoreBreakdown {
+            vector: b.vector,
+            bm25: b.bm25,
+            graph: b.graph,
+            recency: b.recency,
+            rrf_rank: b.rrf_rank,
+        }),
+        snippets: m
+            .snippets
+            .unwrap_or_default()
+            .into_iter()
+            .map(|s| proto::Snippet {
+                fragment: s.fragment,
+                highlights: s
+                    .highlights
+                    .into_iter()
+                    .map(|(start, end)| proto::HighlightRange {
+                        start: start as u32,
+                        end: end as u32,
+                    })
+                    .collect(),
+                source: match s.source {
+                    SnippetSource::Bm25 => "bm25".to_string(),
+                    SnippetSource::Vector => "vector".to_string(),
+                },
+            })
+            .collect(),
+        valid_from: m.valid_from,
+        valid_until: m.valid_until,
+        relevance: m.relevance,
+        recalled_for: m.recalled_for,
+    }
+}
+
+fn replay_request_from_proto(req: ProtoReplayRequest) -> Result<CoreReplayRequest, Status> {
+    let checkpoint_id = req
+        .checkpoint_id
+        .as_deref()
+        .map(|s| validation::parse_uuid("checkpoint_id", s))
+        .transpose()
+        .map_err(core_error_to_status)?;
+    Ok(CoreReplayRequest {
+        thread_id: req.thread_id,
+        agent_id: req.agent_id,
+        checkpoint_id,
+        branch_name: req.branch_name,
+        as_of: req.as_of,
+    })
+}
+
+fn replay_state_to_proto(
+    checkpoint: &Checkpoint,
+    memories: &[MemoryRecord],
+    chain_verification: Option<&ChainVerificationResult>,
+    event_count: usize,
+) -> ProtoReplayResponse {
+    let checkpoint_json = serde_json::to_string(checkpoint).unwrap_or_else(|_| "{}".to_string());
+    let memories: Vec<ProtoReplayMemory> = memories
+        .iter()
+        .map(|m| ProtoReplayMemory {
+            id: m.id.to_string(),
+            content: m.content.clone(),
+            memory_type: format!("{:?}", m.memory_type),
+            created_at: m.created_at.clone(),
+        })
+        .collect();
+
+    let (chain_valid, chain_total, chain_verified) = if let Some(cv) = chain_verification {
+        (
+            Some(cv.valid),
+            Some(cv.total_records as u32),
+            Some(cv.verified_records as u32),
+        )
+    } else {
+        (None, None, None)
+    };
+
+    ProtoReplayResponse {
+        checkpoint_json,
+        memories,
+        event_count: event_count as u32,
+        chain_valid,
+        chain_total,
+        chain_verified,
+    }
+}
+
+fn replay_frame_to_proto(frame: ReplayFrame) -> ProtoReplayFrame {
+    let frame = match frame {
+        ReplayFrame::State {
+            checkpoint,
+            memories,
+            chain_verification,
+            event_count,
+        } => proto::replay_frame::Frame::State(replay_state_to_proto(
+            &checkpoint,
+            &memories,
+            chain_verification.as_ref(),
+            event_count,
+        )),
+        ReplayFrame::Event { event } => proto::replay_frame::Frame::Event(ProtoReplayEvent {
+            id: event.id.to_string(),
+            agent_id: event.agent_id,
+            event_type: event.event_type.to_string(),
+            payload_json: event.payload.to_string(),
+            timestamp: event.timestamp,
+            logical_clock: event.logical_clock,
+            parent_event_id: event.parent_event_id.map(|id| id.to_string()),
+        }),
+    };
+    ProtoReplayFrame { frame: Some(frame) }
+}
+
 fn core_error_to_status(err: mnemo_core::error::Error) -> Status {
     use mnemo_core::error::Error;
 
     match err {
         Error::Validation(msg) => Status::invalid_argument(msg),
+        ref e @ Error::InvalidFields(ref violations) => {
+            let details = ErrorDetails::with_bad_request(
+                violations
+                    .iter()
+                    .map(|v| tonic_types::FieldViolation::new(&v.path, &v.message))
+                    .collect::<Vec<_>>(),
+            );
+            Status::with_error_details(tonic::Code::InvalidArgument, e.to_string(), details)
+        }
         Error::PermissionDenied(msg) => Status::permission_denied(msg),
         Error::NotFound(msg) => Status::not_found(msg),
+        Error::ReadOnly(msg) => Status::failed_precondition(msg),
+        ref e @ Error::VersionConflict {
+            memory_id,
+            expected,
+            actual,
+            ref current,
+        } => {
+            let metadata = std::collections::HashMap::from([
+                ("memory_id".to_string(), memory_id.to_string()),
+                ("expected".to_string(), expected.to_string()),
+                ("actual".to_string(), actual.to_string()),
+                (
+                    "current".to_string(),
+                    serde_json::to_string(current).unwrap_or_default(),
+                ),
+            ]);
+            let details = ErrorDetails::with_error_info("VERSION_CONFLICT", "mnemo", metadata);
+            Status::with_error_details(tonic::Code::Aborted, e.to_string(), details)
+        }
+        Error::Conflict(msg) => Status::already_exists(msg),
+        Error::QuotaExceeded(msg) => Status::resource_exhausted(msg),
+        ref e @ Error::RateLimited {
+            retry_after_secs, ..
+        } => {
+            let details =
+                ErrorDetails::with_retry_info(retry_after_secs.map(std::time::Duration::from_secs));
+            Status::with_error_details(tonic::Code::ResourceExhausted, e.to_string(), details)
+        }
+        Error::Unavailable(msg) => Status::unavailable(msg),
+        Error::Timeout(msg) => Status::deadline_exceeded(msg),
+        Error::IndexCorruption(msg) => Status::data_loss(msg),
         other => Status::internal(other.to_string()),
     }
 }
@@ -1075,6 +1981,45 @@ fn severity_to_str(s: mnemo_compliance::Severity) -> &'static str {
 mod tests {
     use super::*;
 
+    /// Answers with the request ID in scope while it runs.
+    #[derive(Clone)]
+    struct CurrentRequestId;
+
+    impl tower::Service<http::Request<()>> for CurrentRequestId {
+        type Response = http::Response<Option<String>>;
+        type Error = std::convert::Infallible;
+        type Future = std::future::Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(
+            &mut self,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<Result<(), Self::Error>> {
+            std::task::Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _req: http::Request<()>) -> Self::Future {
+            std::future::ready(Ok(http::Response::new(mnemo_core::request_id::current())))
+        }
+    }
+
+    #[tokio::test]
+    async fn request_id_layer_scopes_and_echoes_the_id() {
+        use tower::{Layer, Service};
+
+        let mut svc = RequestIdLayer.layer(CurrentRequestId);
+        let request = http::Request::builder()
+            .header("x-request-id", "rpc-9")
+            .body(())
+            .unwrap();
+        let response = svc.call(request).await.unwrap();
+        assert_eq!(response.body().as_deref(), Some("rpc-9"));
+        assert_eq!(response.headers()["x-request-id"], "rpc-9");
+
+        let response = svc.call(http::Request::new(())).await.unwrap();
+        let generated = response.body().clone().unwrap();
+        assert_eq!(response.headers()["x-request-id"], generated.as_str());
+    }
+
     #[test]
     fn core_error_maps_correctly() {
         let validation =
@@ -1088,5 +2033,107 @@ mod tests {
 
         let not_found = core_error_to_status(mnemo_core::error::Error::NotFound("missing".into()));
         assert_eq!(not_found.code(), tonic::Code::NotFound);
+
+        let read_only = core_error_to_status(mnemo_core::error::Error::ReadOnly("frozen".into()));
+        assert_eq!(read_only.code(), tonic::Code::FailedPrecondition);
+
+        let fields = core_error_to_status(mnemo_core::error::Error::invalid_field(
+            "importance",
+            "must be between 0.0 and 1.0",
+        ));
+        assert_eq!(fields.code(), tonic::Code::InvalidArgument);
+        let bad_request = fields.get_details_bad_request().unwrap();
+        assert_eq!(bad_request.field_violations[0].field, "importance");
+
+        let mut current = MemoryRecord::new("agent".into(), "the current copy".into());
+        current.version = 3;
+        let conflict = core_error_to_status(mnemo_core::error::Error::VersionConflict {
+            memory_id: current.id,
+            expected: 2,
+            actual: 3,
+            current: Box::new(current),
+        });
+        assert_eq!(conflict.code(), tonic::Code::Aborted);
+        let info = conflict.get_details_error_info().unwrap();
+        assert_eq!(info.reason, "VERSION_CONFLICT");
+        assert_eq!(info.metadata["actual"], "3");
+        let record: MemoryRecord = serde_json::from_str(&info.metadata["current"]).unwrap();
+        assert_eq!(record.content, "the current copy");
+
+        let taken = core_error_to_status(mnemo_core::error::Error::Conflict("taken".into()));
+        assert_eq!(taken.code(), tonic::Code::AlreadyExists);
+        let full = core_error_to_status(mnemo_core::error::Error::QuotaExceeded("full".into()));
+        assert_eq!(full.code(), tonic::Code::ResourceExhausted);
+        let unavailable =
+            core_error_to_status(mnemo_core::error::Error::Unavailable("refused".into()));
+        assert_eq!(unavailable.code(), tonic::Code::Unavailable);
+        let timeout = core_error_to_status(mnemo_core::error::Error::Timeout("slow".into()));
+        assert_eq!(timeout.code(), tonic::Code::DeadlineExceeded);
+        let corrupt = core_error_to_status(mnemo_core::error::Error::IndexCorruption("bad".into()));
+        assert_eq!(corrupt.code(), tonic::Code::DataLoss);
+
+        let limited = core_error_to_status(mnemo_core::error::Error::RateLimited {
+            message: "slow down".into(),
+            retry_after_secs: Some(3),
+        });
+        assert_eq!(limited.code(), tonic::Code::ResourceExhausted);
+        let retry = limited.get_details_retry_info().unwrap();
+        assert_eq!(retry.retry_delay, Some(std::time::Duration::from_secs(3)));
+    }
+
+    #[tokio::test]
+    async fn replay_stream_sends_state_then_events() {
+        use tokio_stream::StreamExt;
+
+        let engine = Arc::new(MnemoEngine::new(
+            Arc::new(mnemo_core::storage::duckdb::DuckDbStorage::open_in_memory().unwrap()),
+            Arc::new(mnemo_core::index::usearch::UsearchIndex::new(64).unwrap()),
+            Arc::new(mnemo_core::embedding::DeterministicEmbedding::new(64)),
+            "grpc-agent".to_string(),
+            None,
+        ));
+        engine
+            .checkpoint(CoreCheckpointRequest::new(
+                "grpc-thread".to_string(),
+                serde_json::json!({"step": 1}),
+            ))
+            .await
+            .unwrap();
+        let server = MnemoGrpcServer::new(engine);
+
+        let request = ProtoReplayStreamRequest {
+            replay: Some(ProtoReplayRequest {
+                thread_id: "grpc-thread".to_string(),
+                ..Default::default()
+            }),
+            pace_ms: Some(1),
+        };
+        let frames: Vec<ProtoReplayFrame> = server
+            .replay_stream(Request::new(request))
+            .await
+            .unwrap()
+            .into_inner()
+            .map(|frame| frame.unwrap())
+            .collect()
+            .await;
+        let Some(proto::replay_frame::Frame::State(ref state)) = frames[0].frame else {
+            panic!("first frame must be the state");
+        };
+        assert_eq!(state.event_count as usize, frames.len() - 1);
+        assert!(frames[1..].iter().all(|f| matches!(
+            f.frame,
+            Some(proto::replay_frame::Frame::Event(ref e)) if e.event_type == "checkpoint"
+        )));
+
+        let Err(missing) = server
+            .replay_stream(Request::new(ProtoReplayStreamRequest {
+                replay: None,
+                pace_ms: None,
+            }))
+            .await
+        else {
+            panic!("a stream without a replay request must fail");
+        };
+        assert_eq!(missing.code(), tonic::Code::InvalidArgument);
     }
 }
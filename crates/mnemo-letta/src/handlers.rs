@@ -93,6 +93,7 @@ pub async fn send_message(
         temporal_range: None,
         recency_half_life_hours: None,
         hybrid_weights: None,
+        hybrid: None,
         rrf_k: None,
         as_of: None,
         explain: None,
@@ -103,6 +104,22 @@ pub async fn send_message(
         evidence_budget: None,
         retained_token_budget: None,
         domain_scope: None,
+        text_query_mode: None,
+        text_field_boosts: None,
+        with_snippets: None,
+        profile: None,
+        include: None,
+        thread_id: None,
+        thread_isolation: None,
+        exclude_query: None,
+        exclude_tags: None,
+        queries: None,
+        expand_query: None,
+        channel: None,
+        fields: None,
+        deadline_ms: None,
+        agent_ids: None,
+        min_relevance: None,
     };
     let resp = engine
         .recall(recall)
@@ -1,11 +1,10 @@
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, Ordering};
 
 use rmcp::{
     ErrorData as McpError, ServerHandler,
     handler::server::{router::tool::ToolRouter, wrapper::Parameters},
     model::*,
-    tool, tool_handler, tool_router,
+    tool, tool_router,
 };
 // rmcp 2.x renamed the tool-result content type `Content` -> `ContentBlock`
 // (same `::text()` / `::image()` / `::json()` constructors). Alias keeps the
@@ -13,47 +12,66 @@ use rmcp::{
 use rmcp::model::ContentBlock as Content;
 
 use mnemo_attention_state::AttentionStateStore;
+use mnemo_core::activity::ActivityTracker;
+use mnemo_core::model::access_request::AccessRequestStatus;
+use mnemo_core::model::conflict::ConflictStatus;
 use mnemo_core::model::memory::{MemoryType, Scope, SourceType};
 use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::access_request::{
+    AccessDecision, AccessRequestListRequest, DecideAccessRequest, FileAccessRequest,
+};
 use mnemo_core::query::branch::BranchRequest;
 use mnemo_core::query::checkpoint::CheckpointRequest;
+use mnemo_core::query::conflict::ResolutionStrategy;
+use mnemo_core::query::conflict_queue::{
+    ConflictDecision, ConflictListRequest, ResolveQueuedConflictRequest,
+};
 use mnemo_core::query::consolidate::ConsolidateRequest;
 use mnemo_core::query::experience::{RecallPlanRequest, RememberPlanRequest};
 use mnemo_core::query::forget::{ForgetRequest, ForgetStrategy, ForgetSubjectRequest};
+use mnemo_core::query::hybrid::HybridConfig;
 use mnemo_core::query::merge::{MergeRequest, MergeStrategy};
+use mnemo_core::query::pin::PinRequest;
 use mnemo_core::query::recall::{RecallRequest, TemporalRange};
 use mnemo_core::query::remember::RememberRequest;
 use mnemo_core::query::replay::ReplayRequest;
-use mnemo_core::query::share::ShareRequest;
+use mnemo_core::query::share::{ShareFilter, ShareRequest};
+use mnemo_core::query::validity::SetValidityRequest;
+use mnemo_core::query::verify::VerifyRequest;
+use mnemo_core::search::TextQueryMode;
 
+use crate::tools::access_request::{
+    AccessRequestsInput, DecideAccessRequestInput, RequestAccessInput,
+};
 use crate::tools::agent_managed::{
     AGENT_MANAGED_TAG, MemForgetInput, MemReadInput, MemReviseInput, MemWriteInput,
 };
 use crate::tools::attention_state::{AttentionStateGetInput, AttentionStatePutInput};
 use crate::tools::branch::BranchInput;
 use crate::tools::checkpoint::CheckpointInput;
+use crate::tools::conflicts::{ConflictsInput, ResolveConflictInput};
 use crate::tools::consolidate::ConsolidateInput;
 use crate::tools::delegate::DelegateInput;
 use crate::tools::experience::{RecallPlanInput, RememberPlanInput};
 use crate::tools::forget::ForgetInput;
 use crate::tools::forget_subject::ForgetSubjectInput;
 use crate::tools::merge::MergeInput;
+use crate::tools::pin::PinInput;
 use crate::tools::recall::RecallInput;
 use crate::tools::remember::RememberInput;
 use crate::tools::replay::ReplayInput;
 use crate::tools::share::ShareInput;
 use crate::tools::trajectory_audit::TrajectoryAuditInput;
+use crate::tools::validity::SetValidityInput;
 use crate::tools::verify::VerifyInput;
 
 #[derive(Clone)]
 pub struct MnemoServer {
     engine: Arc<MnemoEngine>,
-    // Populated by the `#[tool_router]` macro and consumed by the macro's
-    // generated code. Rustc can't see it referenced outside the macro
-    // expansion, so we silence the false-positive dead_code lint.
-    #[allow(dead_code)]
+    // Populated by the `#[tool_router]` macro; `call_tool` and
+    // `list_tools` dispatch through it.
     tool_router: ToolRouter<Self>,
-    activity_tracker: Option<Arc<AtomicU64>>,
+    activity_tracker: Option<ActivityTracker>,
     /// v0.4.5 — optional attention-state-memory store. When set, the
     /// `mnemo.attention_state.put` and `mnemo.attention_state.get`
     /// MCP tools dispatch into it. When unset, both tools return a
@@ -64,12 +82,12 @@ pub struct MnemoServer {
 
 impl MnemoServer {
     fn touch_activity(&self) {
-        if let Some(ref t) = self.activity_tracker {
-            let now = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-            t.store(now, Ordering::Relaxed);
+        let tracker = self
+            .activity_tracker
+            .as_ref()
+            .or(self.engine.activity_tracker.as_ref());
+        if let Some(t) = tracker {
+            t.touch();
         }
     }
 }
@@ -85,7 +103,9 @@ impl MnemoServer {
         }
     }
 
-    pub fn with_activity_tracker(mut self, tracker: Arc<AtomicU64>) -> Self {
+    /// Touch `tracker` on every tool call. Defaults to the engine's own
+    /// tracker when one is attached.
+    pub fn with_activity_tracker(mut self, tracker: ActivityTracker) -> Self {
         self.activity_tracker = Some(tracker);
         self
     }
@@ -162,14 +182,21 @@ impl MnemoServer {
         request.related_to = input.related_to;
         request.decay_rate = input.decay_rate;
         request.created_by = input.created_by;
+        request.pinned = input.pinned;
+        request.valid_from = input.valid_from;
+        request.valid_until = input.valid_until;
+        request.channel = input.channel;
 
         match self.engine.remember(request).await {
             Ok(response) => {
-                let result = serde_json::json!({
+                let mut result = serde_json::json!({
                     "id": response.id.to_string(),
                     "content_hash": response.content_hash,
                     "status": "remembered"
                 });
+                if let Some(ref duplicate) = response.duplicate {
+                    result["duplicate"] = serde_json::json!(duplicate);
+                }
                 Ok(CallToolResult::success(vec![Content::text(
                     serde_json::to_string_pretty(&result)
                         .unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}")),
@@ -233,6 +260,25 @@ impl MnemoServer {
             None => None,
         };
 
+        let hybrid = match input.hybrid {
+            Some(ref h) => match h.parse::<HybridConfig>() {
+                Ok(config) => Some(config),
+                Err(e) => {
+                    return Ok(CallToolResult::error(vec![Content::text(e.to_string())]));
+                }
+            },
+            None => None,
+        };
+        let text_query_mode = match input.text_query_mode {
+            Some(ref m) => match m.parse::<TextQueryMode>() {
+                Ok(mode) => Some(mode),
+                Err(e) => {
+                    return Ok(CallToolResult::error(vec![Content::text(e.to_string())]));
+                }
+            },
+            None => None,
+        };
+
         let temporal_range = input.temporal_range.map(|tr| {
             let mut range = TemporalRange::new();
             range.after = tr.after;
@@ -252,9 +298,22 @@ impl MnemoServer {
         request.temporal_range = temporal_range;
         request.recency_half_life_hours = input.recency_half_life_hours;
         request.hybrid_weights = input.hybrid_weights;
+        request.hybrid = hybrid;
         request.rrf_k = input.rrf_k;
         request.as_of = input.as_of;
         request.explain = input.explain;
+        request.text_query_mode = text_query_mode;
+        request.text_field_boosts = input.text_field_boosts;
+        request.with_snippets = input.with_snippets;
+        request.profile = input.profile;
+        request.include = input.include;
+        request.thread_id = input.thread_id;
+        request.thread_isolation = input.thread_isolation;
+        request.exclude_query = input.exclude_query;
+        request.exclude_tags = input.exclude_tags;
+        request.queries = input.queries;
+        request.expand_query = input.expand_query;
+        request.channel = input.channel;
         request.current_fact_resolver = input.current_fact_resolver.map(|c| {
             mnemo_core::query::current_fact_resolver::CurrentFactResolverConfig {
                 fact_key: c.fact_key,
@@ -304,6 +363,9 @@ impl MnemoServer {
                     result["reconstruction"] =
                         serde_json::to_value(reconstruction).unwrap_or_default();
                 }
+                if let Some(degradation) = response.degradation.as_ref() {
+                    result["degradation"] = serde_json::to_value(degradation).unwrap_or_default();
+                }
                 Ok(CallToolResult::success(vec![Content::text(
                     serde_json::to_string_pretty(&result)
                         .unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}")),
@@ -382,13 +444,53 @@ impl MnemoServer {
         let mut request = ForgetRequest::new(memory_ids);
         request.strategy = strategy;
         request.criteria = criteria;
+        request.force = input.force;
+        request.dry_run = input.dry_run;
 
         match self.engine.forget(request).await {
             Ok(response) => {
                 let result = serde_json::json!({
                     "forgotten": response.forgotten.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
                     "errors": response.errors,
-                    "status": "forgotten"
+                    "status": if response.dry_run { "dry_run" } else { "forgotten" }
+                });
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&result)
+                        .unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}")),
+                )]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    #[tool(
+        name = "mnemo.pin",
+        description = "Pin or unpin a memory. Pinned memories (system instructions, user identity facts) never decay, expire, get consolidated, or get auto-forgotten; removing one via mnemo.forget requires force=true."
+    )]
+    async fn pin(
+        &self,
+        Parameters(input): Parameters<PinInput>,
+    ) -> Result<CallToolResult, McpError> {
+        self.touch_activity();
+        let memory_id = match uuid::Uuid::parse_str(&input.memory_id) {
+            Ok(id) => id,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(format!(
+                    "invalid memory id '{}': {e}",
+                    input.memory_id
+                ))]));
+            }
+        };
+
+        let mut request = PinRequest::new(memory_id, input.pinned.unwrap_or(true));
+        request.agent_id = input.agent_id;
+
+        match self.engine.pin(request).await {
+            Ok(response) => {
+                let result = serde_json::json!({
+                    "memory_id": response.memory_id.to_string(),
+                    "pinned": response.pinned,
+                    "changed": response.changed,
                 });
                 Ok(CallToolResult::success(vec![Content::text(
                     serde_json::to_string_pretty(&result)
@@ -399,6 +501,169 @@ impl MnemoServer {
         }
     }
 
+    #[tool(
+        name = "mnemo.set_validity",
+        description = "Set when the fact in a memory holds. Use it to close a fact that stopped being true (e.g. the user moved: set valid_until to the move date) or to backdate when it started. Replaces the whole window; omitted bounds are open. Recall ranks facts outside their window lower, and as_of recall only returns facts valid at that time."
+    )]
+    async fn set_validity(
+        &self,
+        Parameters(input): Parameters<SetValidityInput>,
+    ) -> Result<CallToolResult, McpError> {
+        self.touch_activity();
+        let memory_id = match uuid::Uuid::parse_str(&input.memory_id) {
+            Ok(id) => id,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(format!(
+                    "invalid memory id '{}': {e}",
+                    input.memory_id
+                ))]));
+            }
+        };
+
+        let mut request = SetValidityRequest::new(memory_id);
+        request.agent_id = input.agent_id;
+        request.valid_from = input.valid_from;
+        request.valid_until = input.valid_until;
+
+        match self.engine.set_validity(request).await {
+            Ok(response) => {
+                let result = serde_json::json!({
+                    "memory_id": response.memory_id.to_string(),
+                    "valid_from": response.valid_from,
+                    "valid_until": response.valid_until,
+                });
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&result)
+                        .unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}")),
+                )]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    #[tool(
+        name = "mnemo.conflicts",
+        description = "List open entries in the conflict review queue: pairs of memories that contradict each other and were not auto-resolved. Each entry includes both memories' content so you can ask your human which one is right, then call mnemo.resolve_conflict. Set detect=true to scan for new conflicts first."
+    )]
+    async fn conflicts(
+        &self,
+        Parameters(input): Parameters<ConflictsInput>,
+    ) -> Result<CallToolResult, McpError> {
+        self.touch_activity();
+        let agent_id = input
+            .agent_id
+            .unwrap_or_else(|| self.engine.default_agent_id.clone());
+
+        if input.detect.unwrap_or(false) {
+            let threshold = input.threshold.unwrap_or(0.95);
+            if let Err(e) = self
+                .engine
+                .queue_conflicts(Some(agent_id.clone()), threshold)
+                .await
+            {
+                return Ok(CallToolResult::error(vec![Content::text(e.to_string())]));
+            }
+        }
+
+        let mut request = ConflictListRequest::new();
+        request.agent_id = Some(agent_id);
+        request.status = Some(ConflictStatus::Open);
+        request.limit = Some(input.limit.unwrap_or(20));
+        let conflicts = match self.engine.list_conflicts(request).await {
+            Ok(conflicts) => conflicts,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        };
+
+        let mut entries = Vec::with_capacity(conflicts.len());
+        for conflict in conflicts {
+            let mut contents = Vec::with_capacity(2);
+            for id in [conflict.memory_a, conflict.memory_b] {
+                let content = match self.engine.storage.get_memory(id).await {
+                    Ok(Some(record)) => Some(record.content),
+                    _ => None,
+                };
+                contents.push(content);
+            }
+            entries.push(serde_json::json!({
+                "conflict_id": conflict.id.to_string(),
+                "memory_a": {"id": conflict.memory_a.to_string(), "content": contents[0]},
+                "memory_b": {"id": conflict.memory_b.to_string(), "content": contents[1]},
+                "similarity": conflict.similarity,
+                "reason": conflict.reason,
+                "created_at": conflict.created_at,
+            }));
+        }
+        let result = serde_json::json!({ "conflicts": entries });
+        Ok(CallToolResult::success(vec![Content::text(
+            serde_json::to_string_pretty(&result)
+                .unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}")),
+        )]))
+    }
+
+    #[tool(
+        name = "mnemo.resolve_conflict",
+        description = "Close an open conflict from mnemo.conflicts with your human's decision. Set exactly one of: winner_id (keep that memory, remove the other), strategy (keep_newest, keep_highest_importance, merge_into_semantic, evidence_weighted) or dismiss=true (both memories are fine)."
+    )]
+    async fn resolve_conflict(
+        &self,
+        Parameters(input): Parameters<ResolveConflictInput>,
+    ) -> Result<CallToolResult, McpError> {
+        self.touch_activity();
+        let conflict_id = match uuid::Uuid::parse_str(&input.conflict_id) {
+            Ok(id) => id,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(format!(
+                    "invalid conflict id '{}': {e}",
+                    input.conflict_id
+                ))]));
+            }
+        };
+
+        let decision = match (
+            input.strategy,
+            input.winner_id,
+            input.dismiss.unwrap_or(false),
+        ) {
+            (Some(strategy), None, false) => {
+                match serde_json::from_value::<ResolutionStrategy>(serde_json::Value::String(
+                    strategy.clone(),
+                )) {
+                    Ok(s) => ConflictDecision::Strategy(s),
+                    Err(_) => {
+                        return Ok(CallToolResult::error(vec![Content::text(format!(
+                            "unknown resolution strategy: {strategy}"
+                        ))]));
+                    }
+                }
+            }
+            (None, Some(winner), false) => match uuid::Uuid::parse_str(&winner) {
+                Ok(id) => ConflictDecision::Winner(id),
+                Err(e) => {
+                    return Ok(CallToolResult::error(vec![Content::text(format!(
+                        "invalid winner id '{winner}': {e}"
+                    ))]));
+                }
+            },
+            (None, None, true) => ConflictDecision::Dismiss,
+            _ => {
+                return Ok(CallToolResult::error(vec![Content::text(
+                    "set exactly one of strategy, winner_id or dismiss".to_string(),
+                )]));
+            }
+        };
+
+        let mut request = ResolveQueuedConflictRequest::new(conflict_id, decision);
+        request.resolved_by = input.resolved_by;
+
+        match self.engine.resolve_queued_conflict(request).await {
+            Ok(record) => Ok(CallToolResult::success(vec![Content::text(
+                serde_json::to_string_pretty(&record)
+                    .unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}")),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
     #[tool(
         name = "mnemo.forget_subject",
         description = "GDPR / DPDPA-aligned subject erasure. Finds every memory tagged with `subject:<subject_id>` and either redacts the content (default, preserves the audit hash chain) or hard-deletes the rows. Use 'redact' when a verifiable audit trail must survive the erasure."
@@ -424,6 +689,7 @@ impl MnemoServer {
             subject_id: input.subject_id,
             agent_id: input.agent_id,
             strategy,
+            dry_run: input.dry_run,
         };
 
         match self.engine.forget_subject(request).await {
@@ -435,6 +701,7 @@ impl MnemoServer {
                     "forgotten": response.forgotten.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
                     "cascaded_events": response.cascaded_events,
                     "errors": response.errors,
+                    "dry_run": response.dry_run,
                 });
                 Ok(CallToolResult::success(vec![Content::text(
                     serde_json::to_string_pretty(&result)
@@ -447,7 +714,7 @@ impl MnemoServer {
 
     #[tool(
         name = "mnemo.share",
-        description = "Share one or more memories with another agent by granting them access permissions. Supports batch sharing via memory_ids. The memory scope will be updated to 'shared' automatically."
+        description = "Share one or more memories with another agent by granting them access permissions. Share a batch via memory_ids, or every memory of yours carrying all of the given tags. All grants are made together or not at all; set dry_run to see which memories would be shared. The memory scope will be updated to 'shared' automatically."
     )]
     async fn share(
         &self,
@@ -455,11 +722,6 @@ impl MnemoServer {
     ) -> Result<CallToolResult, McpError> {
         self.touch_activity();
 
-        // Support batch: memory_ids takes precedence over memory_id
-        let id_strings = input
-            .memory_ids
-            .unwrap_or_else(|| vec![input.memory_id.clone()]);
-
         let permission = match input.permission {
             Some(ref s) => match s.parse() {
                 Ok(p) => Some(p),
@@ -473,50 +735,176 @@ impl MnemoServer {
             None => None,
         };
 
-        let mut all_acl_ids: Vec<String> = Vec::new();
-        let mut all_shared_with: Vec<String> = Vec::new();
-        let mut errors: Vec<String> = Vec::new();
+        let parse_id =
+            |s: &str| uuid::Uuid::parse_str(s).map_err(|e| format!("invalid UUID '{s}': {e}"));
+        // A tag filter, then memory_ids, then memory_id select what to share.
+        let mut request = ShareRequest::new(uuid::Uuid::nil(), input.target_agent_id.clone());
+        if let Some(tags) = input.tags.filter(|t| !t.is_empty()) {
+            request.filter = Some(ShareFilter {
+                tags,
+                ..Default::default()
+            });
+        } else if let Some(ref id_strings) = input.memory_ids {
+            match id_strings
+                .iter()
+                .map(|s| parse_id(s))
+                .collect::<Result<Vec<_>, _>>()
+            {
+                Ok(ids) => request.memory_ids = Some(ids),
+                Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+            }
+        } else {
+            match parse_id(&input.memory_id) {
+                Ok(id) => request.memory_id = id,
+                Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+            }
+        }
+        request.target_agent_ids = input.target_agent_ids.clone();
+        request.permission = permission;
+        request.expires_in_hours = input.expires_in_hours;
+        request.dry_run = input.dry_run;
 
-        for id_str in &id_strings {
-            let memory_id = match uuid::Uuid::parse_str(id_str) {
-                Ok(id) => id,
-                Err(e) => {
-                    errors.push(format!("invalid UUID '{id_str}': {e}"));
-                    continue;
+        match self.engine.share(request).await {
+            Ok(response) => {
+                let result = serde_json::json!({
+                    "acl_ids": response.acl_ids,
+                    "memory_ids": response.memory_ids,
+                    "shared_with": response.shared_with_all,
+                    "status": if response.dry_run { "dry_run" } else { "shared" },
+                });
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&result)
+                        .unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}")),
+                )]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    #[tool(
+        name = "mnemo.request_access",
+        description = "Ask another agent for access to memories it has not shared with you, naming them by memory_ids or tags and saying why. The owner is notified and approves or denies; check the outcome with mnemo.access_requests direction=outgoing."
+    )]
+    async fn request_access(
+        &self,
+        Parameters(input): Parameters<RequestAccessInput>,
+    ) -> Result<CallToolResult, McpError> {
+        self.touch_activity();
+        let mut request = FileAccessRequest::new(input.owner_id, input.justification);
+        if let Some(ref s) = input.permission {
+            match s.parse() {
+                Ok(p) => request.permission = Some(p),
+                Err(_) => {
+                    return Ok(CallToolResult::error(vec![Content::text(format!(
+                        "invalid permission '{s}': expected one of: read, write, delete, share, delegate, admin"
+                    ))]));
                 }
-            };
+            }
+        }
+        if let Some(ref id_strings) = input.memory_ids {
+            match id_strings
+                .iter()
+                .map(|s| uuid::Uuid::parse_str(s).map_err(|e| format!("invalid UUID '{s}': {e}")))
+                .collect::<Result<Vec<_>, _>>()
+            {
+                Ok(ids) => request.memory_ids = Some(ids),
+                Err(e) => return Ok(CallToolResult::error(vec![Content::text(e)])),
+            }
+        }
+        request.tags = input.tags;
+        request.expires_in_hours = input.expires_in_hours;
 
-            let mut request = ShareRequest::new(memory_id, input.target_agent_id.clone());
-            request.target_agent_ids = input.target_agent_ids.clone();
-            request.permission = permission;
-            request.expires_in_hours = input.expires_in_hours;
+        match self.engine.request_access(request).await {
+            Ok(record) => {
+                let result = serde_json::json!({
+                    "request_id": record.id.to_string(),
+                    "owner_id": record.owner_id,
+                    "status": record.status,
+                });
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&result)
+                        .unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}")),
+                )]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
 
-            match self.engine.share(request).await {
-                Ok(response) => {
-                    for acl_id in &response.acl_ids {
-                        all_acl_ids.push(acl_id.to_string());
-                    }
-                    if all_shared_with.is_empty() {
-                        all_shared_with = response.shared_with_all;
-                    }
-                }
-                Err(e) => {
-                    errors.push(format!("share {id_str}: {e}"));
-                }
+    #[tool(
+        name = "mnemo.access_requests",
+        description = "List memory access requests. direction=incoming (default) shows other agents asking for your memories, with their justification, so you can ask your human and call mnemo.decide_access_request. direction=outgoing shows the requests you filed and their outcome."
+    )]
+    async fn access_requests(
+        &self,
+        Parameters(input): Parameters<AccessRequestsInput>,
+    ) -> Result<CallToolResult, McpError> {
+        self.touch_activity();
+        let agent_id = self.engine.default_agent_id.clone();
+        let mut request = AccessRequestListRequest::new();
+        match input.direction.as_deref().unwrap_or("incoming") {
+            "incoming" => request.owner_id = Some(agent_id),
+            "outgoing" => request.requester_id = Some(agent_id),
+            other => {
+                return Ok(CallToolResult::error(vec![Content::text(format!(
+                    "invalid direction '{other}': expected incoming or outgoing"
+                ))]));
             }
         }
+        request.status = match input.status.as_deref().unwrap_or("pending") {
+            "all" => None,
+            s => match s.parse::<AccessRequestStatus>() {
+                Ok(status) => Some(status),
+                Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+            },
+        };
+        request.limit = Some(input.limit.unwrap_or(20));
 
-        let result = serde_json::json!({
-            "acl_ids": all_acl_ids,
-            "memory_ids": id_strings,
-            "shared_with": all_shared_with,
-            "errors": errors,
-            "status": "shared"
-        });
-        Ok(CallToolResult::success(vec![Content::text(
-            serde_json::to_string_pretty(&result)
-                .unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}")),
-        )]))
+        match self.engine.list_access_requests(request).await {
+            Ok(requests) => {
+                let result = serde_json::json!({ "access_requests": requests });
+                Ok(CallToolResult::success(vec![Content::text(
+                    serde_json::to_string_pretty(&result)
+                        .unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}")),
+                )]))
+            }
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
+    }
+
+    #[tool(
+        name = "mnemo.decide_access_request",
+        description = "Approve or deny a pending request from mnemo.access_requests for your memories, with your human's decision. Approving shares the requested memories with the requester at the requested permission."
+    )]
+    async fn decide_access_request(
+        &self,
+        Parameters(input): Parameters<DecideAccessRequestInput>,
+    ) -> Result<CallToolResult, McpError> {
+        self.touch_activity();
+        let request_id = match uuid::Uuid::parse_str(&input.request_id) {
+            Ok(id) => id,
+            Err(e) => {
+                return Ok(CallToolResult::error(vec![Content::text(format!(
+                    "invalid access request id '{}': {e}",
+                    input.request_id
+                ))]));
+            }
+        };
+        let decision = if input.approve {
+            AccessDecision::Approve
+        } else {
+            AccessDecision::Deny
+        };
+        let mut request = DecideAccessRequest::new(request_id, decision);
+        request.note = input.note;
+        request.decided_by = input.decided_by;
+
+        match self.engine.decide_access_request(request).await {
+            Ok(record) => Ok(CallToolResult::success(vec![Content::text(
+                serde_json::to_string_pretty(&record)
+                    .unwrap_or_else(|e| format!("{{\"error\": \"{e}\"}}")),
+            )])),
+            Err(e) => Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        }
     }
 
     #[tool(
@@ -827,19 +1215,32 @@ impl MnemoServer {
         Parameters(input): Parameters<VerifyInput>,
     ) -> Result<CallToolResult, McpError> {
         self.touch_activity();
-        match self
+        let clock_skew = match self
             .engine
-            .verify_integrity(input.agent_id, input.thread_id.as_deref())
+            .detect_clock_skew(input.agent_id.clone(), input.thread_id.as_deref())
             .await
         {
-            Ok(result) => {
+            Ok(skew) => skew,
+            Err(e) => return Ok(CallToolResult::error(vec![Content::text(e.to_string())])),
+        };
+        let mut request = VerifyRequest::new();
+        request.agent_id = input.agent_id;
+        request.thread_id = input.thread_id;
+        request.incremental = input.incremental;
+        request.max_records = input.max_records;
+        match self.engine.verify_memory_chain(request).await {
+            Ok(verified) => {
+                let result = verified.result;
                 let response = serde_json::json!({
                     "valid": result.valid,
                     "total_records": result.total_records,
                     "verified_records": result.verified_records,
                     "first_broken_at": result.first_broken_at.map(|id| id.to_string()),
                     "error_message": result.error_message,
-                    "status": if result.valid { "verified" } else { "integrity_violation" }
+                    "status": if result.valid { "verified" } else { "integrity_violation" },
+                    "complete": verified.complete,
+                    "checkpoint": verified.checkpoint,
+                    "clock_skew": clock_skew,
                 });
                 Ok(CallToolResult::success(vec![Content::text(
                     serde_json::to_string_pretty(&response)
@@ -1333,8 +1734,49 @@ fn summarize(content: &str) -> String {
     out
 }
 
-#[tool_handler]
+/// Key of the correlation ID in a tool call's `_meta`, both ways.
+const REQUEST_ID_META_KEY: &str = "request_id";
+
 impl ServerHandler for MnemoServer {
+    /// Dispatch to the `#[tool]` methods, each call under its own
+    /// correlation ID (see [`mnemo_core::request_id`]): the caller's
+    /// `_meta.request_id` when usable, a generated one otherwise. The ID
+    /// tags the call's tracing span and events and comes back in the
+    /// result's `_meta`.
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParams,
+        context: rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        use tracing::Instrument;
+
+        let request_id = mnemo_core::request_id::accept(
+            context
+                .meta
+                .get(REQUEST_ID_META_KEY)
+                .and_then(|v| v.as_str()),
+        );
+        let span = tracing::info_span!("tool_call", request_id = %request_id, tool = %request.name);
+        let tcc = rmcp::handler::server::tool::ToolCallContext::new(self, request, context);
+        let mut result = mnemo_core::request_id::scope(
+            request_id.clone(),
+            self.tool_router.call(tcc).instrument(span),
+        )
+        .await?;
+        let mut meta = result.meta.take().unwrap_or_default();
+        meta.insert(REQUEST_ID_META_KEY.to_string(), request_id.into());
+        result.meta = Some(meta);
+        Ok(result)
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: rmcp::service::RequestContext<rmcp::RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        Ok(ListToolsResult::with_all_items(self.tool_router.list_all()))
+    }
+
     async fn list_resources(
         &self,
         _request: Option<rmcp::model::PaginatedRequestParams>,
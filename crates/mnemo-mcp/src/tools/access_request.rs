@@ -0,0 +1,40 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RequestAccessInput {
+    /// The agent whose memories you need.
+    pub owner_id: String,
+    /// The IDs of the memories you need.
+    pub memory_ids: Option<Vec<String>>,
+    /// Ask for every memory of the owner carrying all of these tags instead of memory_ids.
+    pub tags: Option<Vec<String>>,
+    /// Why you need the memories. Shown to the owner.
+    pub justification: String,
+    /// Permission level to ask for: "read", "write" or "admin". Defaults to "read".
+    pub permission: Option<String>,
+    /// Number of hours the access should last. If not set, it does not expire.
+    pub expires_in_hours: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AccessRequestsInput {
+    /// "incoming" (requests for your memories, the default) or "outgoing" (requests you filed).
+    pub direction: Option<String>,
+    /// Only requests in this status: "pending" (the default), "approved", "denied" or "all".
+    pub status: Option<String>,
+    /// Maximum number of requests to return. Defaults to 20.
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DecideAccessRequestInput {
+    /// The access request ID (UUID string) from mnemo.access_requests.
+    pub request_id: String,
+    /// True grants the requested memories; false denies the request.
+    pub approve: bool,
+    /// A note for the requester, e.g. why the request was denied.
+    pub note: Option<String>,
+    /// Who made the decision, e.g. the human's name or user id.
+    pub decided_by: Option<String>,
+}
@@ -0,0 +1,28 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ConflictsInput {
+    /// Agent whose review queue to read. Defaults to the server's agent.
+    pub agent_id: Option<String>,
+    /// Run conflict detection first and queue any new pairs. Defaults to false.
+    pub detect: Option<bool>,
+    /// Similarity threshold used when `detect` is true. Defaults to 0.95.
+    pub threshold: Option<f32>,
+    /// Maximum number of open conflicts to return. Defaults to 20.
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ResolveConflictInput {
+    /// The queued conflict ID (UUID string) from mnemo.conflicts.
+    pub conflict_id: String,
+    /// Resolve with a strategy: keep_newest, keep_highest_importance, merge_into_semantic or evidence_weighted.
+    pub strategy: Option<String>,
+    /// Resolve by keeping this memory ID; the other memory of the pair is removed.
+    pub winner_id: Option<String>,
+    /// Close the conflict without changing either memory. Defaults to false.
+    pub dismiss: Option<bool>,
+    /// Who made the decision, e.g. the human's name or user id.
+    pub resolved_by: Option<String>,
+}
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 pub struct DelegateInput {
     /// Agent ID to delegate permissions to.
     pub delegate_id: String,
-    /// Permission to delegate: "read", "write", "delete", "share", "delegate", or "admin".
+    /// Permission to delegate: "read", "write", "delete", "share", "delegate", "admin", "export" (scheduled exports) or "replay" (thread replay and causal traces).
     pub permission: String,
     /// Specific memory IDs to scope the delegation to. If empty, delegates for all memories or by tags.
     pub memory_ids: Option<Vec<String>>,
@@ -21,4 +21,8 @@ pub struct ForgetInput {
     pub strategy: Option<String>,
     /// Criteria-based forget: find and apply strategy to memories matching these filters. Used when memory_ids is empty.
     pub criteria: Option<ForgetCriteriaInput>,
+    /// Set to true to remove pinned memories. Without it, pinned memories are skipped.
+    pub force: Option<bool>,
+    /// Set to true to list the memories that would be forgotten without changing anything.
+    pub dry_run: Option<bool>,
 }
@@ -12,4 +12,7 @@ pub struct ForgetSubjectInput {
     pub strategy: Option<String>,
     /// Optional agent scope; defaults to the server's default agent id.
     pub agent_id: Option<String>,
+    /// Set to true to list the memories that would be erased without
+    /// erasing them.
+    pub dry_run: Option<bool>,
 }
@@ -0,0 +1,12 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PinInput {
+    /// The memory ID (UUID string) to pin or unpin.
+    pub memory_id: String,
+    /// true to pin (protect from decay, expiry, consolidation and auto-forget), false to unpin. Defaults to true.
+    pub pinned: Option<bool>,
+    /// Agent performing the change. Defaults to the server's agent.
+    pub agent_id: Option<String>,
+}
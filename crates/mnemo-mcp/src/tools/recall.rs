@@ -35,6 +35,8 @@ pub struct RecallInput {
     pub recency_half_life_hours: Option<f64>,
     /// Custom weights for hybrid RRF fusion. One weight per ranked list (vector, BM25, recency, graph). Defaults to uniform weights.
     pub hybrid_weights: Option<Vec<f32>>,
+    /// Named fusion weights, overriding hybrid_weights: a preset ("balanced", "precision" for direct matches and important memories, "recency" for fresh memories) or signal:weight pairs over vector, bm25, graph, recency and importance, e.g. "vector:1,bm25:0.5". Unlisted signals keep their balanced weight (1, importance 0).
+    pub hybrid: Option<String>,
     /// Custom k parameter for RRF fusion. Higher k reduces the impact of rank differences. Defaults to 60.0.
     pub rrf_k: Option<f32>,
     /// Point-in-time query: show memory state as it existed at this timestamp (RFC 3339). Excludes memories created after this time and memories already deleted by this time.
@@ -72,6 +74,39 @@ pub struct RecallInput {
     /// (private/shared/public/global). Default `None` leaves recall
     /// unchanged.
     pub domain_scope: Option<RecallDomainScopeInput>,
+    /// How the keyword (BM25) leg of lexical / hybrid recall reads the
+    /// query: "terms" (default), "phrase" (exact phrase), "fuzzy"
+    /// (typo-tolerant, edit distance 1) or "fuzzy:2".
+    pub text_query_mode: Option<String>,
+    /// Per-field keyword boosts, e.g. {"content_eng": 2.0}.
+    pub text_field_boosts: Option<std::collections::BTreeMap<String, f32>>,
+    /// When true, each result carries `snippets`: highlighted keyword
+    /// matches, or the best-matching excerpt for semantic hits.
+    pub with_snippets: Option<bool>,
+    /// Name of a saved recall profile (e.g. "support-context") whose
+    /// strategy, weights and filters fill any parameters not given here.
+    pub profile: Option<String>,
+    /// Extra detail to attach to each result. "provenance" adds the
+    /// memories and events a result was derived from, with their hashes.
+    pub include: Option<Vec<String>>,
+    /// Conversation thread this recall comes from.
+    pub thread_id: Option<String>,
+    /// When true, only return memories from `thread_id` (or memories with
+    /// no thread when it is omitted) plus global memories.
+    pub thread_isolation: Option<bool>,
+    /// What the results should NOT be about, e.g. "staging" when asking
+    /// about deployments. Memories similar to it rank lower.
+    pub exclude_query: Option<String>,
+    /// Leave out memories carrying any of these tags.
+    pub exclude_tags: Option<Vec<String>>,
+    /// Other phrasings of the same question, e.g. synonyms or a rewording.
+    /// Each is searched and the rankings are combined.
+    pub queries: Option<Vec<String>>,
+    /// When true, the server adds its own paraphrases of `query`. Fails if
+    /// the server has no query expander configured.
+    pub expand_query: Option<bool>,
+    /// Only search memories in this channel. You must be a member.
+    pub channel: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
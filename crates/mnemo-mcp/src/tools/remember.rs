@@ -31,4 +31,12 @@ pub struct RememberInput {
     pub decay_rate: Option<f32>,
     /// ID of the agent or user who created this memory.
     pub created_by: Option<String>,
+    /// Pin the memory so it never decays, expires, is consolidated, or is auto-forgotten (e.g. system instructions, user identity facts).
+    pub pinned: Option<bool>,
+    /// When the fact starts to hold (RFC 3339), e.g. the day the user moved to Berlin.
+    pub valid_from: Option<String>,
+    /// When the fact stops holding (RFC 3339, exclusive). Omit while it is still true. Recall ranks facts outside their window lower, and as_of recall drops them.
+    pub valid_until: Option<String>,
+    /// Channel to remember into; every member of the channel can recall it. You must be a member.
+    pub channel: Option<String>,
 }
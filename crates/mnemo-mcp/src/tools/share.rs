@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ShareInput {
     /// The ID of the memory to share.
+    #[serde(default)]
     pub memory_id: String,
     /// Share multiple memories at once. Takes precedence over memory_id if set.
     pub memory_ids: Option<Vec<String>>,
@@ -15,4 +16,8 @@ pub struct ShareInput {
     pub permission: Option<String>,
     /// Number of hours until the share expires. If not set, the share does not expire.
     pub expires_in_hours: Option<f64>,
+    /// Share every memory of yours carrying all of these tags instead of memory_id / memory_ids.
+    pub tags: Option<Vec<String>>,
+    /// Report which memories would be shared without granting anything.
+    pub dry_run: Option<bool>,
 }
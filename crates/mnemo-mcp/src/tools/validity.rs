@@ -0,0 +1,14 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SetValidityInput {
+    /// The memory ID (UUID string) whose validity window to replace.
+    pub memory_id: String,
+    /// When the fact starts to hold (RFC 3339). Omit to leave the start open.
+    pub valid_from: Option<String>,
+    /// When the fact stops holding (RFC 3339, exclusive). Omit while it is still true.
+    pub valid_until: Option<String>,
+    /// Agent performing the change. Defaults to the server's agent.
+    pub agent_id: Option<String>,
+}
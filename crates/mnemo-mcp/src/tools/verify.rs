@@ -7,4 +7,9 @@ pub struct VerifyInput {
     pub agent_id: Option<String>,
     /// Optional thread ID to limit verification to a specific thread.
     pub thread_id: Option<String>,
+    /// Only verify records added since the last verification run.
+    #[serde(default)]
+    pub incremental: bool,
+    /// Stop after this many records; a later incremental run continues.
+    pub max_records: Option<usize>,
 }
@@ -79,6 +79,10 @@ async fn test_resource_surface_storage_contract() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -119,6 +123,10 @@ async fn test_engine_remember_via_server_engine() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -149,6 +157,10 @@ async fn test_engine_recall_via_server_engine() {
             related_to: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -169,6 +181,7 @@ async fn test_engine_recall_via_server_engine() {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: None,
             explain: None,
@@ -179,6 +192,22 @@ async fn test_engine_recall_via_server_engine() {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets: None,
+            profile: None,
+            include: None,
+            thread_id: None,
+            thread_isolation: None,
+            exclude_query: None,
+            exclude_tags: None,
+            queries: None,
+            expand_query: None,
+            channel: None,
+            fields: None,
+            deadline_ms: None,
+            agent_ids: None,
+            min_relevance: None,
         })
         .await
         .unwrap();
@@ -297,6 +326,10 @@ async fn test_engine_verify_via_server_engine() {
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             })
             .await
             .unwrap();
@@ -0,0 +1,15 @@
+//! Feeds arbitrary Simple Query message bodies through the same decoding
+//! and parsing the server does. Any panic is a bug.
+//!
+//! ```text
+//! cd crates/mnemo-pgwire && cargo +nightly fuzz run parse_sql
+//! ```
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mnemo_pgwire::parser::{parse_sql, query_text};
+
+fuzz_target!(|body: &[u8]| {
+    let _ = parse_sql(&query_text(body));
+});
@@ -70,25 +70,53 @@ impl Default for PgWireConfig {
 pub async fn start_server(
     engine: Arc<MnemoEngine>,
     config: PgWireConfig,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    start_server_with_shutdown(engine, config, std::future::pending()).await
+}
+
+/// Like [`start_server`], but stops when `shutdown` resolves: the listener
+/// closes, open connections are told to finish their current query and
+/// disconnect, and the call returns once every connection task has ended.
+/// Wrap it in a timeout to bound the drain.
+pub async fn start_server_with_shutdown(
+    engine: Arc<MnemoEngine>,
+    config: PgWireConfig,
+    shutdown: impl Future<Output = ()>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let listener = tokio::net::TcpListener::bind(&config.bind_addr).await?;
     tracing::info!("pgwire server listening on {}", config.bind_addr);
 
     let semaphore = Arc::new(tokio::sync::Semaphore::new(config.max_connections));
+    let (stop_tx, stop_rx) = tokio::sync::watch::channel(false);
+    let mut connections = tokio::task::JoinSet::new();
+    tokio::pin!(shutdown);
 
     loop {
-        let (stream, addr) = listener.accept().await?;
+        let (stream, addr) = tokio::select! {
+            accepted = listener.accept() => accepted?,
+            () = &mut shutdown => break,
+        };
         tracing::debug!("pgwire connection from {addr}");
 
         let engine = engine.clone();
         let config = config.clone();
+        let stop_rx = stop_rx.clone();
         let permit = semaphore.clone().acquire_owned().await?;
 
-        tokio::spawn(async move {
-            if let Err(e) = server::handle_connection(stream, engine, &config).await {
+        connections.spawn(async move {
+            if let Err(e) = server::handle_connection_until(stream, engine, &config, stop_rx).await
+            {
                 tracing::warn!("pgwire connection error from {addr}: {e}");
             }
             drop(permit);
         });
+        // Reap finished connections so the set does not grow unbounded.
+        while connections.try_join_next().is_some() {}
     }
+
+    drop(listener);
+    let _ = stop_tx.send(true);
+    tracing::info!("pgwire server draining {} connection(s)", connections.len());
+    while connections.join_next().await.is_some() {}
+    Ok(())
 }
@@ -13,7 +13,7 @@ pub enum ParsedStatement {
     Insert(InsertQuery),
     /// DELETE from the memories table.
     Delete(DeleteQuery),
-    /// Unrecognized or unsupported statement.
+    /// Unrecognized or unsupported statement, with the reason.
     Unsupported(String),
 }
 
@@ -61,112 +61,327 @@ pub struct DeleteQuery {
     pub agent_id: Option<String>,
 }
 
+/// The SQL text of a Simple Query message body: lossy UTF-8 with the
+/// trailing NUL terminator removed.
+pub fn query_text(body: &[u8]) -> String {
+    String::from_utf8_lossy(body)
+        .trim_end_matches('\0')
+        .to_string()
+}
+
 /// Parse a SQL string into a `ParsedStatement`.
 ///
 /// Supports:
 /// - `SELECT * FROM memories [WHERE ...] [LIMIT n] [OFFSET n]`
 /// - `INSERT INTO memories (col, ...) VALUES (val, ...)`
 /// - `DELETE FROM memories WHERE id = '...'`
+///
+/// Anything else — other statements or tables, several statements in one
+/// query, unterminated literals or comments, malformed clauses — comes
+/// back as [`ParsedStatement::Unsupported`] with the reason. Parsing never
+/// panics, whatever the input.
 pub fn parse_sql(sql: &str) -> ParsedStatement {
-    let trimmed = sql.trim().trim_end_matches(';');
-    let upper = trimmed.to_uppercase();
-
-    if upper.starts_with("SELECT") {
-        parse_select(trimmed)
-    } else if upper.starts_with("INSERT") {
-        parse_insert(trimmed)
-    } else if upper.starts_with("DELETE") {
-        parse_delete(trimmed)
-    } else {
-        ParsedStatement::Unsupported(trimmed.to_string())
-    }
+    let scanned = match Scanned::new(sql.trim()) {
+        Ok(scanned) => scanned,
+        Err(reason) => return ParsedStatement::Unsupported(reason),
+    };
+    let parsed = match scanned.word_at(0) {
+        Some(("SELECT", _)) => parse_select(&scanned),
+        Some(("INSERT", _)) => parse_insert(&scanned),
+        Some(("DELETE", _)) => parse_delete(&scanned),
+        Some((other, _)) => Err(format!("{other} statements are not supported")),
+        None if scanned.masked.trim().is_empty() => Err("empty query".to_string()),
+        None => Err("unrecognized statement".to_string()),
+    };
+    parsed.unwrap_or_else(ParsedStatement::Unsupported)
 }
 
-fn parse_select(sql: &str) -> ParsedStatement {
-    let upper = sql.to_uppercase();
-    let mut query = SelectQuery {
-        agent_id: None,
-        query_text: None,
-        limit: 50,
-        offset: 0,
-        orientation_cache: upper.contains("/*+ ORIENTATION_CACHE")
-            || upper.contains("/*+ORIENTATION_CACHE"),
-        reconstruct: upper.contains("/*+ RECONSTRUCT") || upper.contains("/*+RECONSTRUCT"),
-    };
+/// A query split into what the parser reads and what it must not.
+struct Scanned<'a> {
+    sql: &'a str,
+    /// `sql` with ASCII letters upper-cased, and comments, trailing `;`s
+    /// and the insides of string literals blanked to spaces. Byte offsets
+    /// match `sql`, so a keyword found here slices `sql` at the same spot,
+    /// and text inside a literal can never be mistaken for a keyword.
+    masked: String,
+    /// Bodies of the `/* ... */` comments, for hints.
+    comments: Vec<&'a str>,
+}
 
-    // Extract LIMIT
-    if let Some(pos) = upper.find("LIMIT") {
-        let after = &sql[pos + 5..].trim();
-        if let Some(num_str) = after.split_whitespace().next()
-            && let Ok(n) = num_str.parse::<usize>()
-        {
-            query.limit = n;
+impl<'a> Scanned<'a> {
+    fn new(sql: &'a str) -> Result<Self, String> {
+        if sql.contains('\0') {
+            return Err("query contains a NUL byte".to_string());
+        }
+        let bytes = sql.as_bytes();
+        let mut masked = Vec::with_capacity(bytes.len());
+        let mut comments = Vec::new();
+        let mut ended = false;
+        let mut i = 0;
+        while i < bytes.len() {
+            let comment = bytes[i..].starts_with(b"--") || bytes[i..].starts_with(b"/*");
+            if ended && !comment && bytes[i] != b';' && !bytes[i].is_ascii_whitespace() {
+                return Err("multiple statements in one query are not supported".to_string());
+            }
+            match bytes[i] {
+                b'\'' => {
+                    let close = literal_end(bytes, i).ok_or("unterminated string literal")?;
+                    masked.push(b'\'');
+                    masked.resize(masked.len() + close - i - 1, b' ');
+                    masked.push(b'\'');
+                    i = close + 1;
+                }
+                b'"' => {
+                    let close = bytes[i + 1..]
+                        .iter()
+                        .position(|&b| b == b'"')
+                        .map(|p| i + 1 + p)
+                        .ok_or("unterminated quoted identifier")?;
+                    masked.extend(bytes[i..=close].iter().map(u8::to_ascii_uppercase));
+                    i = close + 1;
+                }
+                b'-' if comment => {
+                    let end = bytes[i..]
+                        .iter()
+                        .position(|&b| b == b'\n')
+                        .map_or(bytes.len(), |p| i + p);
+                    masked.resize(masked.len() + end - i, b' ');
+                    i = end;
+                }
+                b'/' if comment => {
+                    let close = sql[i + 2..]
+                        .find("*/")
+                        .map(|p| i + 2 + p)
+                        .ok_or("unterminated block comment")?;
+                    comments.push(&sql[i + 2..close]);
+                    masked.resize(masked.len() + close + 2 - i, b' ');
+                    i = close + 2;
+                }
+                b';' => {
+                    ended = true;
+                    masked.push(b' ');
+                    i += 1;
+                }
+                b => {
+                    masked.push(b.to_ascii_uppercase());
+                    i += 1;
+                }
+            }
         }
+        let masked = String::from_utf8(masked).map_err(|_| "query is not valid UTF-8")?;
+        Ok(Self {
+            sql,
+            masked,
+            comments,
+        })
     }
 
-    // Extract OFFSET
-    if let Some(pos) = upper.find("OFFSET") {
-        let after = &sql[pos + 6..].trim();
-        if let Some(num_str) = after.split_whitespace().next()
-            && let Ok(n) = num_str.parse::<usize>()
-        {
-            query.offset = n;
+    /// Position of `keyword` as a whole word, at or after `from`.
+    fn find_keyword(&self, keyword: &str, from: usize) -> Option<usize> {
+        let bytes = self.masked.as_bytes();
+        let mut start = from;
+        while let Some(p) = self.masked.get(start..)?.find(keyword) {
+            let pos = start + p;
+            let end = pos + keyword.len();
+            if (pos == 0 || !is_word_byte(bytes[pos - 1]))
+                && bytes.get(end).is_none_or(|&b| !is_word_byte(b))
+            {
+                return Some(pos);
+            }
+            start = pos + 1;
         }
+        None
     }
 
-    // Extract WHERE agent_id = '...'
-    if let Some(agent_id) = extract_string_condition(&upper, sql, "AGENT_ID") {
-        query.agent_id = Some(agent_id);
+    /// The upper-cased word at `pos`, after any whitespace, and the
+    /// position after it.
+    fn word_at(&self, pos: usize) -> Option<(&str, usize)> {
+        let bytes = self.masked.as_bytes();
+        let start = skip_whitespace(bytes, pos);
+        let len = bytes[start..]
+            .iter()
+            .take_while(|&&b| is_word_byte(b))
+            .count();
+        (len > 0).then(|| (&self.masked[start..start + len], start + len))
     }
 
-    // Extract WHERE content LIKE '%...%'
-    if let Some(pos) = upper.find("CONTENT LIKE") {
-        let after = &sql[pos + 12..].trim();
-        if let Some(value) = extract_quoted_value(after) {
-            // Strip % wildcards
-            let clean = value.trim_matches('%').to_string();
-            if !clean.is_empty() {
-                query.query_text = Some(clean);
+    /// Check that the table named at `pos` is `memories` (also spelled
+    /// `"memories"` or `public.memories`) and return the position after it.
+    fn table_at(&self, pos: usize) -> Result<usize, String> {
+        let bytes = self.masked.as_bytes();
+        let start = skip_whitespace(bytes, pos);
+        let len = bytes[start..]
+            .iter()
+            .take_while(|&&b| is_word_byte(b) || b == b'"' || b == b'.')
+            .count();
+        let name = self.masked[start..start + len].replace('"', "");
+        match name.strip_prefix("PUBLIC.").unwrap_or(&name) {
+            "MEMORIES" => Ok(start + len),
+            "" => Err("expected a table name".to_string()),
+            _ => Err(format!(
+                "unknown table {}; only memories is supported",
+                &self.sql[start..start + len]
+            )),
+        }
+    }
+
+    /// The string literal at `pos`, after any whitespace, unescaped, and
+    /// the position after it.
+    fn literal_at(&self, pos: usize) -> Option<(String, usize)> {
+        let start = skip_whitespace(self.masked.as_bytes(), pos);
+        if self.masked.as_bytes().get(start) != Some(&b'\'') {
+            return None;
+        }
+        let close = literal_end(self.sql.as_bytes(), start)?;
+        Some((self.sql[start + 1..close].replace("''", "'"), close + 1))
+    }
+
+    /// The value of a `column = '...'` condition at or after `from`.
+    fn condition(&self, column: &str, from: usize) -> Result<Option<String>, String> {
+        let Some(pos) = self.find_keyword(column, from) else {
+            return Ok(None);
+        };
+        let name = column.to_ascii_lowercase();
+        let bytes = self.masked.as_bytes();
+        let eq = skip_whitespace(bytes, pos + column.len());
+        if bytes.get(eq) != Some(&b'=') {
+            return Err(format!("{name} only supports {name} = '...'"));
+        }
+        match self.literal_at(eq + 1) {
+            Some((value, _)) => Ok(Some(value)),
+            None => Err(format!("{name} must be compared to a quoted string")),
+        }
+    }
+
+    /// The count after `keyword` (`LIMIT n`, `OFFSET n`), if present.
+    fn count_after(&self, keyword: &str, from: usize) -> Result<Option<usize>, String> {
+        let Some(pos) = self.find_keyword(keyword, from) else {
+            return Ok(None);
+        };
+        match self.word_at(pos + keyword.len()) {
+            Some((word, _)) => word
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("{keyword} expects a non-negative integer, got {word}")),
+            None => Err(format!("{keyword} expects a non-negative integer")),
+        }
+    }
+
+    /// Whether a `/*+ name */` hint is present.
+    fn hint(&self, name: &str) -> bool {
+        self.comments.iter().any(|body| {
+            body.strip_prefix('+')
+                .is_some_and(|hint| hint.trim_start().to_ascii_uppercase().starts_with(name))
+        })
+    }
+}
+
+/// Index of the quote closing the string literal opened at `open`; `''`
+/// inside a literal is an escaped quote.
+fn literal_end(bytes: &[u8], open: usize) -> Option<usize> {
+    let mut i = open + 1;
+    while i < bytes.len() {
+        if bytes[i] == b'\'' {
+            if bytes.get(i + 1) != Some(&b'\'') {
+                return Some(i);
             }
+            i += 1;
         }
+        i += 1;
     }
+    None
+}
 
-    ParsedStatement::Select(query)
+/// Identifier bytes. Every byte of a non-ASCII character counts, so a
+/// word never ends inside one.
+fn is_word_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || !b.is_ascii()
 }
 
-fn parse_insert(sql: &str) -> ParsedStatement {
-    // Extract column names and values from INSERT INTO memories (cols) VALUES (vals)
-    let upper = sql.to_uppercase();
+fn skip_whitespace(bytes: &[u8], pos: usize) -> usize {
+    let mut i = pos.min(bytes.len());
+    while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+        i += 1;
+    }
+    i
+}
 
-    let cols_start = match upper.find('(') {
-        Some(p) => p,
-        None => return ParsedStatement::Unsupported(sql.to_string()),
-    };
-    let cols_end = match upper[cols_start..].find(')') {
-        Some(p) => cols_start + p,
-        None => return ParsedStatement::Unsupported(sql.to_string()),
+fn parse_select(scanned: &Scanned) -> Result<ParsedStatement, String> {
+    let from = scanned
+        .find_keyword("FROM", 0)
+        .ok_or("SELECT must read FROM memories")?;
+    let table_end = scanned.table_at(from + 4)?;
+    let mut query = SelectQuery {
+        agent_id: None,
+        query_text: None,
+        limit: scanned.count_after("LIMIT", table_end)?.unwrap_or(50),
+        offset: scanned.count_after("OFFSET", table_end)?.unwrap_or(0),
+        orientation_cache: scanned.hint("ORIENTATION_CACHE"),
+        reconstruct: scanned.hint("RECONSTRUCT"),
     };
 
-    let values_marker = match upper[cols_end..].find("VALUES") {
-        Some(p) => cols_end + p,
-        None => return ParsedStatement::Unsupported(sql.to_string()),
-    };
+    if let Some(where_pos) = scanned.find_keyword("WHERE", table_end) {
+        // WHERE agent_id = '...'
+        query.agent_id = scanned.condition("AGENT_ID", where_pos)?;
+
+        // WHERE content LIKE '%...%'
+        if let Some(pos) = scanned.find_keyword("CONTENT", where_pos) {
+            let Some(("LIKE", end)) = scanned.word_at(pos + 7) else {
+                return Err("content only supports content LIKE '%...%'".to_string());
+            };
+            let (pattern, _) = scanned
+                .literal_at(end)
+                .ok_or("LIKE expects a quoted pattern")?;
+            // Strip % wildcards
+            let clean = pattern.trim_matches('%');
+            if !clean.is_empty() {
+                query.query_text = Some(clean.to_string());
+            }
+        }
+    }
 
-    let vals_start = match upper[values_marker..].find('(') {
-        Some(p) => values_marker + p,
-        None => return ParsedStatement::Unsupported(sql.to_string()),
-    };
-    let vals_end = match sql[vals_start..].rfind(')') {
-        Some(p) => vals_start + p,
-        None => return ParsedStatement::Unsupported(sql.to_string()),
+    Ok(ParsedStatement::Select(query))
+}
+
+fn parse_insert(scanned: &Scanned) -> Result<ParsedStatement, String> {
+    // INSERT INTO memories (cols) VALUES (vals)
+    let into = scanned
+        .find_keyword("INTO", 0)
+        .ok_or("expected INSERT INTO memories")?;
+    let table_end = scanned.table_at(into + 4)?;
+    let bytes = scanned.masked.as_bytes();
+
+    let cols_start = skip_whitespace(bytes, table_end);
+    if bytes.get(cols_start) != Some(&b'(') {
+        return Err("INSERT must list its columns".to_string());
+    }
+    let cols_end = scanned.masked[cols_start..]
+        .find(')')
+        .map(|p| cols_start + p)
+        .ok_or("unclosed column list")?;
+    let Some(("VALUES", values_end)) = scanned.word_at(cols_end + 1) else {
+        return Err("expected VALUES (...) after the column list".to_string());
     };
+    let vals_start = skip_whitespace(bytes, values_end);
+    if bytes.get(vals_start) != Some(&b'(') {
+        return Err("expected VALUES (...) after the column list".to_string());
+    }
+    let (values, vals_end) = split_values(scanned, vals_start).ok_or("unclosed VALUES list")?;
+    if skip_whitespace(bytes, vals_end + 1) < bytes.len() {
+        return Err("only a single VALUES row is supported".to_string());
+    }
 
-    let columns: Vec<String> = sql[cols_start + 1..cols_end]
+    let columns: Vec<&str> = scanned.masked[cols_start + 1..cols_end]
         .split(',')
-        .map(|c| c.trim().to_uppercase())
+        .map(|c| c.trim().trim_matches('"'))
         .collect();
-
-    let values: Vec<String> = split_sql_values(&sql[vals_start + 1..vals_end]);
+    if columns.len() != values.len() {
+        return Err(format!(
+            "INSERT lists {} columns but {} values",
+            columns.len(),
+            values.len()
+        ));
+    }
 
     let mut insert = InsertQuery {
         content: String::new(),
@@ -176,102 +391,81 @@ fn parse_insert(sql: &str) -> ParsedStatement {
         tags: vec![],
     };
 
-    for (i, col) in columns.iter().enumerate() {
-        if i >= values.len() {
-            break;
-        }
-        let val = unquote(&values[i]);
-        match col.as_str() {
+    for (col, raw) in columns.iter().zip(&values) {
+        let val = unquote(raw);
+        match *col {
             "CONTENT" => insert.content = val,
             "AGENT_ID" => insert.agent_id = Some(val),
-            "IMPORTANCE" => insert.importance = val.parse().ok(),
+            "IMPORTANCE" => {
+                let importance = val
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|v| v.is_finite())
+                    .ok_or_else(|| format!("importance must be a number, got {val}"))?;
+                insert.importance = Some(importance);
+            }
             "MEMORY_TYPE" => insert.memory_type = Some(val),
-            _ => {}
+            other => return Err(format!("unknown column {}", other.to_ascii_lowercase())),
         }
     }
 
     if insert.content.is_empty() {
-        return ParsedStatement::Unsupported(sql.to_string());
+        return Err("INSERT needs a non-empty content value".to_string());
     }
 
-    ParsedStatement::Insert(insert)
+    Ok(ParsedStatement::Insert(insert))
 }
 
-fn parse_delete(sql: &str) -> ParsedStatement {
-    let upper = sql.to_uppercase();
+fn parse_delete(scanned: &Scanned) -> Result<ParsedStatement, String> {
+    let from = scanned
+        .find_keyword("FROM", 0)
+        .ok_or("expected DELETE FROM memories")?;
+    let table_end = scanned.table_at(from + 4)?;
     let mut delete = DeleteQuery {
         memory_id: None,
         agent_id: None,
     };
 
-    if let Some(id) = extract_string_condition(&upper, sql, "ID") {
-        delete.memory_id = Some(id);
-    }
-    if let Some(agent_id) = extract_string_condition(&upper, sql, "AGENT_ID") {
-        delete.agent_id = Some(agent_id);
+    if let Some(where_pos) = scanned.find_keyword("WHERE", table_end) {
+        delete.memory_id = scanned.condition("ID", where_pos)?;
+        delete.agent_id = scanned.condition("AGENT_ID", where_pos)?;
     }
 
-    ParsedStatement::Delete(delete)
-}
-
-/// Extract a string value from `WHERE column = 'value'` pattern.
-fn extract_string_condition(upper: &str, original: &str, column: &str) -> Option<String> {
-    let pattern = format!("{column} =");
-    if let Some(pos) = upper.find(&pattern) {
-        let after = &original[pos + pattern.len()..].trim_start();
-        return extract_quoted_value(after);
-    }
-    None
-}
-
-/// Extract a single-quoted string value.
-fn extract_quoted_value(s: &str) -> Option<String> {
-    let s = s.trim();
-    if let Some(stripped) = s.strip_prefix('\'')
-        && let Some(end) = stripped.find('\'')
-    {
-        return Some(stripped[..end].to_string());
-    }
-    None
+    Ok(ParsedStatement::Delete(delete))
 }
 
-/// Split SQL values, respecting quoted strings.
-fn split_sql_values(s: &str) -> Vec<String> {
+/// Split the parenthesised value list opened at `open` on its top-level
+/// commas. Returns the values as written and the position of the closing
+/// parenthesis.
+fn split_values<'a>(scanned: &Scanned<'a>, open: usize) -> Option<(Vec<&'a str>, usize)> {
+    let bytes = scanned.masked.as_bytes();
     let mut values = vec![];
-    let mut current = String::new();
-    let mut in_quote = false;
-
-    for ch in s.chars() {
-        match ch {
-            '\'' if !in_quote => {
-                in_quote = true;
-                current.push(ch);
+    let mut depth = 0usize;
+    let mut start = open + 1;
+    for (i, &b) in bytes.iter().enumerate().skip(open + 1) {
+        match b {
+            b'(' => depth += 1,
+            b')' if depth == 0 => {
+                values.push(scanned.sql[start..i].trim());
+                return Some((values, i));
             }
-            '\'' if in_quote => {
-                in_quote = false;
-                current.push(ch);
+            b')' => depth -= 1,
+            b',' if depth == 0 => {
+                values.push(scanned.sql[start..i].trim());
+                start = i + 1;
             }
-            ',' if !in_quote => {
-                values.push(current.trim().to_string());
-                current.clear();
-            }
-            _ => current.push(ch),
+            _ => {}
         }
     }
-
-    let trimmed = current.trim().to_string();
-    if !trimmed.is_empty() {
-        values.push(trimmed);
-    }
-    values
+    None
 }
 
 /// Remove surrounding quotes from a value string.
 fn unquote(s: &str) -> String {
     let trimmed = s.trim();
-    if (trimmed.starts_with('\'') && trimmed.ends_with('\''))
-        || (trimmed.starts_with('"') && trimmed.ends_with('"'))
-    {
+    if trimmed.len() >= 2 && trimmed.starts_with('\'') && trimmed.ends_with('\'') {
+        trimmed[1..trimmed.len() - 1].replace("''", "'")
+    } else if trimmed.len() >= 2 && trimmed.starts_with('"') && trimmed.ends_with('"') {
         trimmed[1..trimmed.len() - 1].to_string()
     } else {
         trimmed.to_string()
@@ -379,4 +573,149 @@ mod tests {
             other => panic!("Expected Select, got {:?}", other),
         }
     }
+
+    fn reason(sql: &str) -> String {
+        match parse_sql(sql) {
+            ParsedStatement::Unsupported(reason) => reason,
+            other => panic!("Expected Unsupported, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_stacked_statements_rejected() {
+        let reason = reason("SELECT * FROM memories WHERE agent_id = 'a'; DROP TABLE memories");
+        assert!(reason.contains("multiple statements"), "{reason}");
+        // Trailing semicolons and comments are still one statement.
+        assert!(matches!(
+            parse_sql("SELECT * FROM memories;; -- done"),
+            ParsedStatement::Select(_)
+        ));
+    }
+
+    #[test]
+    fn test_injection_inside_literal_stays_a_value() {
+        let stmt = parse_sql(
+            "SELECT * FROM memories WHERE agent_id = 'x''; DROP TABLE memories; --' LIMIT 3",
+        );
+        match stmt {
+            ParsedStatement::Select(q) => {
+                assert_eq!(q.agent_id.as_deref(), Some("x'; DROP TABLE memories; --"));
+                assert_eq!(q.limit, 3);
+            }
+            other => panic!("Expected Select, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_keywords_inside_literals_are_ignored() {
+        let stmt = parse_sql("SELECT * FROM memories WHERE content LIKE '%LIMIT 1 OFFSET 9%'");
+        match stmt {
+            ParsedStatement::Select(q) => {
+                assert_eq!(q.query_text.as_deref(), Some("LIMIT 1 OFFSET 9"));
+                assert_eq!(q.limit, 50);
+                assert_eq!(q.offset, 0);
+            }
+            other => panic!("Expected Select, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_case_changing_characters_do_not_shift_offsets() {
+        // 'ß' upper-cases to "SS" and 'ﬁ' to "FI", which used to move
+        // every keyword offset and panic on slicing.
+        let stmt = parse_sql("SELECT * FROM memories WHERE agent_id = 'ßﬁ' LIMIT 2");
+        match stmt {
+            ParsedStatement::Select(q) => {
+                assert_eq!(q.agent_id.as_deref(), Some("ßﬁ"));
+                assert_eq!(q.limit, 2);
+            }
+            other => panic!("Expected Select, got {:?}", other),
+        }
+        assert!(matches!(
+            parse_sql("ßßßß LIMIT"),
+            ParsedStatement::Unsupported(_)
+        ));
+    }
+
+    #[test]
+    fn test_delete_by_agent_does_not_match_id() {
+        let stmt = parse_sql("DELETE FROM memories WHERE agent_id = 'bot'");
+        match stmt {
+            ParsedStatement::Delete(q) => {
+                assert_eq!(q.agent_id.as_deref(), Some("bot"));
+                assert!(q.memory_id.is_none());
+            }
+            other => panic!("Expected Delete, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_hints_are_read_from_comments_only() {
+        match parse_sql("/*+ reconstruct */ SELECT * FROM memories") {
+            ParsedStatement::Select(q) => assert!(q.reconstruct && !q.orientation_cache),
+            other => panic!("Expected Select, got {:?}", other),
+        }
+        match parse_sql("SELECT * FROM memories WHERE content LIKE '%/*+ reconstruct */%'") {
+            ParsedStatement::Select(q) => assert!(!q.reconstruct),
+            other => panic!("Expected Select, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_malformed_input_is_rejected_with_a_reason() {
+        for (sql, expected) in [
+            ("", "empty query"),
+            (
+                "SELECT * FROM memories WHERE agent_id = 'open",
+                "unterminated string",
+            ),
+            (
+                "SELECT /* never closed FROM memories",
+                "unterminated block comment",
+            ),
+            ("SELECT * FROM users", "unknown table users"),
+            ("SELECT 1", "FROM memories"),
+            ("SELECT * FROM memories LIMIT ten", "LIMIT expects"),
+            (
+                "SELECT * FROM memories WHERE agent_id = bot",
+                "quoted string",
+            ),
+            ("SELECT * FROM memories\0", "NUL"),
+            (
+                "INSERT INTO memories (content) VALUES ('a', 'b')",
+                "1 columns but 2 values",
+            ),
+            (
+                "INSERT INTO memories (content, owner) VALUES ('a', 'b')",
+                "unknown column owner",
+            ),
+            (
+                "INSERT INTO memories (content, importance) VALUES ('a', 'high')",
+                "importance",
+            ),
+            (
+                "INSERT INTO memories (content) VALUES ('a'), ('b')",
+                "single VALUES row",
+            ),
+            ("INSERT INTO memories (content) VALUES ('a'", "unclosed"),
+            ("UPDATE memories SET content = 'x'", "UPDATE statements"),
+        ] {
+            let reason = reason(sql);
+            assert!(reason.contains(expected), "{sql:?}: {reason}");
+        }
+    }
+
+    #[test]
+    fn test_parse_insert_with_escaped_quotes() {
+        let stmt = parse_sql(
+            "INSERT INTO public.memories (content, agent_id) VALUES ('it''s (fine), really', 'a')",
+        );
+        match stmt {
+            ParsedStatement::Insert(q) => {
+                assert_eq!(q.content, "it's (fine), really");
+                assert_eq!(q.agent_id.as_deref(), Some("a"));
+            }
+            other => panic!("Expected Insert, got {:?}", other),
+        }
+    }
 }
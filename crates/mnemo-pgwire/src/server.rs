@@ -10,6 +10,7 @@ use std::sync::Arc;
 
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
+use tokio::sync::watch;
 
 use mnemo_core::query::MnemoEngine;
 
@@ -18,9 +19,22 @@ use crate::parser::{self, ParsedStatement};
 
 /// Handle a single PostgreSQL wire protocol connection.
 pub async fn handle_connection(
+    stream: TcpStream,
+    engine: Arc<MnemoEngine>,
+    config: &PgWireConfig,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let (_never, shutdown) = watch::channel(false);
+    handle_connection_until(stream, engine, config, shutdown).await
+}
+
+/// Like [`handle_connection`], but closes the connection between queries
+/// once `shutdown` flips to `true`. A query already executing runs to
+/// completion first, so shutdown never cuts off a response mid-stream.
+pub async fn handle_connection_until(
     mut stream: TcpStream,
     engine: Arc<MnemoEngine>,
     config: &PgWireConfig,
+    mut shutdown: watch::Receiver<bool>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Phase 1: Startup message
     let startup_len_raw = stream.read_i32().await?;
@@ -97,7 +111,18 @@ pub async fn handle_connection(
     send_ready_for_query(&mut stream).await?;
 
     // Phase 3: Query loop
-    while let Ok(msg_type) = stream.read_u8().await {
+    loop {
+        let msg_type = tokio::select! {
+            read = stream.read_u8() => match read {
+                Ok(msg_type) => msg_type,
+                Err(_) => break,
+            },
+            () = wait_for_shutdown(&mut shutdown) => {
+                tracing::debug!("pgwire connection closing for server shutdown");
+                send_error(&mut stream, "terminating connection due to server shutdown").await?;
+                break;
+            }
+        };
         let msg_len_raw = stream.read_i32().await?;
         let msg_len = usize::try_from(msg_len_raw)
             .map_err(|_| format!("negative message length: {msg_len_raw}"))?;
@@ -113,18 +138,22 @@ pub async fn handle_connection(
         match msg_type {
             b'Q' => {
                 // Simple Query
-                let sql = String::from_utf8_lossy(&msg_buf)
-                    .trim_end_matches('\0')
-                    .to_string();
+                let sql = parser::query_text(&msg_buf);
 
                 tracing::debug!("pgwire query: {sql}");
+                // Keep the idle-timeout watchdog from firing mid-query.
+                let _activity = engine.activity_tracker.as_ref().map(|t| t.begin());
 
                 match handle_query(&sql, &engine, config).await {
                     Ok(response) => {
                         send_query_response(&mut stream, &response).await?;
                     }
                     Err(e) => {
-                        send_error(&mut stream, &e.to_string()).await?;
+                        let code = match e.downcast_ref::<mnemo_core::error::Error>() {
+                            Some(core) => sqlstate(core),
+                            None => "42000",
+                        };
+                        send_error_with_code(&mut stream, code, &e.to_string()).await?;
                     }
                 }
 
@@ -150,6 +179,16 @@ pub async fn handle_connection(
     Ok(())
 }
 
+/// Resolve once `shutdown` reads `true`. Never resolves if the sender is
+/// dropped without signalling.
+async fn wait_for_shutdown(shutdown: &mut watch::Receiver<bool>) {
+    while !*shutdown.borrow_and_update() {
+        if shutdown.changed().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
 /// Query response rows.
 struct QueryResponse {
     columns: Vec<String>,
@@ -197,6 +236,7 @@ async fn handle_query(
                 temporal_range: None,
                 recency_half_life_hours: None,
                 hybrid_weights: None,
+                hybrid: None,
                 rrf_k: None,
                 as_of: None,
                 explain: None,
@@ -207,6 +247,22 @@ async fn handle_query(
                 evidence_budget: None,
                 retained_token_budget: None,
                 domain_scope: None,
+                text_query_mode: None,
+                text_field_boosts: None,
+                with_snippets: None,
+                profile: None,
+                include: None,
+                thread_id: None,
+                thread_isolation: None,
+                exclude_query: None,
+                exclude_tags: None,
+                queries: None,
+                expand_query: None,
+                channel: None,
+                fields: None,
+                deadline_ms: None,
+                agent_ids: None,
+                min_relevance: None,
             };
 
             let response = engine.recall(request).await?;
@@ -269,6 +325,10 @@ async fn handle_query(
                 related_to: None,
                 decay_rate: None,
                 created_by: None,
+                pinned: None,
+                valid_from: None,
+                valid_until: None,
+                channel: None,
             };
 
             let response = engine.remember(request).await?;
@@ -295,6 +355,8 @@ async fn handle_query(
                     agent_id: Some(agent_id),
                     strategy: Some(mnemo_core::query::forget::ForgetStrategy::SoftDelete),
                     criteria: None,
+                    force: None,
+                    dry_run: None,
                 };
 
                 let response = engine.forget(request).await?;
@@ -353,9 +415,34 @@ async fn send_ready_for_query(
     Ok(())
 }
 
+/// The SQLSTATE reported for an engine error, so Postgres drivers apply
+/// their usual retry handling (class 08, 53 and 57 are transient).
+fn sqlstate(err: &mnemo_core::error::Error) -> &'static str {
+    use mnemo_core::error::Error;
+
+    match err {
+        Error::ReadOnly(_) => "25006",        // read_only_sql_transaction
+        Error::Conflict(_) => "23505",        // unique_violation
+        Error::QuotaExceeded(_) => "53400",   // configuration_limit_exceeded
+        Error::RateLimited { .. } => "53000", // insufficient_resources
+        Error::Unavailable(_) => "08006",     // connection_failure
+        Error::Timeout(_) => "57014",         // query_canceled
+        Error::IndexCorruption(_) => "XX001", // data_corrupted
+        _ => "42000",
+    }
+}
+
 async fn send_error(
     stream: &mut TcpStream,
     message: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    send_error_with_code(stream, "42000", message).await
+}
+
+async fn send_error_with_code(
+    stream: &mut TcpStream,
+    code: &str,
+    message: &str,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let mut buf = Vec::new();
     buf.push(b'E'); // ErrorResponse type
@@ -364,12 +451,13 @@ async fn send_error(
     // Severity
     fields.push(b'S');
     fields.extend_from_slice(b"ERROR\0");
-    // SQLSTATE (42000 = syntax error)
+    // SQLSTATE
     fields.push(b'C');
-    fields.extend_from_slice(b"42000\0");
-    // Message
+    fields.extend_from_slice(code.as_bytes());
+    fields.push(0);
+    // Message; a NUL inside it would end the field early
     fields.push(b'M');
-    fields.extend_from_slice(message.as_bytes());
+    fields.extend_from_slice(message.replace('\0', "").as_bytes());
     fields.push(0);
     // Terminator
     fields.push(0);
@@ -0,0 +1,160 @@
+//! Property tests for the pgwire SQL parser: it never panics, values
+//! round-trip through SQL quoting, and anything outside the supported
+//! subset is rejected rather than half-parsed.
+
+use mnemo_pgwire::parser::{ParsedStatement, parse_sql, query_text};
+use proptest::prelude::*;
+
+/// Fragments that stress the scanner: keywords, quoting, comments,
+/// statement separators and characters whose case mapping changes length.
+const FRAGMENTS: &[&str] = &[
+    "SELECT",
+    "INSERT",
+    "DELETE",
+    "INTO",
+    "FROM",
+    "memories",
+    "\"memories\"",
+    "public.",
+    "WHERE",
+    "agent_id",
+    "id",
+    "content",
+    "LIKE",
+    "LIMIT",
+    "OFFSET",
+    "VALUES",
+    "(",
+    ")",
+    ",",
+    "=",
+    "'",
+    "''",
+    "\"",
+    "/*",
+    "*/",
+    "/*+",
+    "--",
+    "\n",
+    ";",
+    "%",
+    "0",
+    "42",
+    "-1",
+    " ",
+    "ß",
+    "ﬁ",
+    "İ",
+    "🙂",
+    "\0",
+    "\\",
+    "$1",
+    "::text",
+    "DROP TABLE memories",
+];
+
+fn sqlish() -> impl Strategy<Value = String> {
+    prop::collection::vec(prop::sample::select(FRAGMENTS), 0..40).prop_map(|parts| parts.concat())
+}
+
+/// `value` as a SQL string literal.
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Strings without NUL, which the parser always rejects.
+fn text() -> impl Strategy<Value = String> {
+    "[^\x00]*"
+}
+
+fn select(stmt: ParsedStatement) -> mnemo_pgwire::parser::SelectQuery {
+    match stmt {
+        ParsedStatement::Select(q) => q,
+        other => panic!("Expected Select, got {:?}", other),
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2048))]
+
+    #[test]
+    fn never_panics_on_arbitrary_text(sql in any::<String>()) {
+        let _ = parse_sql(&sql);
+    }
+
+    #[test]
+    fn never_panics_on_sql_like_input(sql in sqlish()) {
+        let _ = parse_sql(&sql);
+    }
+
+    #[test]
+    fn never_panics_on_raw_message_bodies(body in prop::collection::vec(any::<u8>(), 0..256)) {
+        let _ = parse_sql(&query_text(&body));
+    }
+
+    #[test]
+    fn agent_id_round_trips(agent in text(), limit in any::<u32>()) {
+        let sql = format!(
+            "SELECT * FROM memories WHERE agent_id = {} LIMIT {limit}",
+            quote(&agent)
+        );
+        let q = select(parse_sql(&sql));
+        prop_assert_eq!(q.agent_id, Some(agent));
+        prop_assert_eq!(q.limit, limit as usize);
+    }
+
+    #[test]
+    fn insert_values_round_trip(content in "[^\x00]+", agent in text()) {
+        let sql = format!(
+            "INSERT INTO memories (content, agent_id) VALUES ({}, {})",
+            quote(&content),
+            quote(&agent)
+        );
+        match parse_sql(&sql) {
+            ParsedStatement::Insert(q) => {
+                prop_assert_eq!(q.content, content);
+                prop_assert_eq!(q.agent_id, Some(agent));
+            }
+            other => prop_assert!(false, "Expected Insert, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn like_patterns_never_leak_keywords(pattern in "[^\x00%]+") {
+        let sql = format!("SELECT * FROM memories WHERE content LIKE {}", quote(&format!("%{pattern}%")));
+        let q = select(parse_sql(&sql));
+        prop_assert_eq!(q.limit, 50);
+        prop_assert_eq!(q.offset, 0);
+        prop_assert!(!q.reconstruct && !q.orientation_cache);
+    }
+
+    #[test]
+    fn stacked_statements_are_rejected(prefix in "[a-z0-9 ]*", second in "(DROP|UPDATE|SELECT|DELETE)[a-zA-Z ]*") {
+        let sql = format!(
+            "SELECT * FROM memories WHERE agent_id = {}; {second}",
+            quote(&prefix)
+        );
+        prop_assert!(matches!(parse_sql(&sql), ParsedStatement::Unsupported(_)));
+    }
+
+    #[test]
+    fn other_statements_are_rejected(
+        verb in prop::sample::select(&["DROP", "UPDATE", "CREATE", "ALTER", "TRUNCATE", "COPY", "GRANT", "BEGIN", "SET"][..]),
+        rest in "[^\x00]*",
+    ) {
+        let sql = format!("{verb} {rest}");
+        prop_assert!(matches!(parse_sql(&sql), ParsedStatement::Unsupported(_)));
+    }
+
+    #[test]
+    fn other_tables_are_rejected(table in "[a-z_][a-z0-9_]{0,20}") {
+        prop_assume!(table != "memories");
+        for sql in [
+            format!("SELECT * FROM {table}"),
+            format!("DELETE FROM {table} WHERE id = 'x'"),
+            format!("INSERT INTO {table} (content) VALUES ('x')"),
+        ] {
+            prop_assert!(matches!(parse_sql(&sql), ParsedStatement::Unsupported(_)), "{}", sql);
+        }
+    }
+}
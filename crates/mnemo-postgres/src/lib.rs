@@ -22,5 +22,5 @@ pub mod migrations;
 pub mod pgvector_index;
 pub mod storage;
 
-pub use pgvector_index::PgVectorIndex;
+pub use pgvector_index::{HnswConfig, PgVectorIndex};
 pub use storage::PgStorage;
@@ -1,4 +1,7 @@
 use mnemo_core::error::{Error, Result};
+use sqlx::Row;
+
+use crate::pgvector_index::{HNSW_INDEX_NAME, HnswConfig};
 
 /// Run all PostgreSQL schema migrations.
 ///
@@ -43,7 +46,15 @@ CREATE TABLE IF NOT EXISTS memories (
     prev_version_id UUID,
     quarantined BOOLEAN NOT NULL DEFAULT FALSE,
     quarantine_reason VARCHAR,
-    decay_function VARCHAR
+    decay_function VARCHAR,
+    pinned BOOLEAN NOT NULL DEFAULT FALSE,
+    valid_from VARCHAR,
+    valid_until VARCHAR,
+    curation VARCHAR,
+    attestation VARCHAR,
+    channel VARCHAR,
+    content_zstd BYTEA,
+    content_len BIGINT
 )
 "#
     );
@@ -211,6 +222,275 @@ CREATE TABLE IF NOT EXISTS embedding_baseline (
     .await
     .map_err(|e| Error::Storage(format!("create embedding_baseline: {e}")))?;
 
+    // 10. recall_profiles (saved recall configurations)
+    sqlx::query(
+        r#"
+CREATE TABLE IF NOT EXISTS recall_profiles (
+    agent_id VARCHAR NOT NULL,
+    name VARCHAR NOT NULL,
+    settings JSONB NOT NULL,
+    use_count BIGINT NOT NULL DEFAULT 0,
+    last_used_at VARCHAR,
+    created_at VARCHAR NOT NULL,
+    updated_at VARCHAR NOT NULL,
+    PRIMARY KEY (agent_id, name)
+)
+"#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| Error::Storage(format!("create recall_profiles: {e}")))?;
+
+    // 11. conflicts (conflict review queue)
+    sqlx::query(
+        r#"
+CREATE TABLE IF NOT EXISTS conflicts (
+    id UUID PRIMARY KEY,
+    agent_id VARCHAR NOT NULL,
+    memory_a UUID NOT NULL,
+    memory_b UUID NOT NULL,
+    similarity REAL NOT NULL,
+    reason TEXT NOT NULL,
+    status VARCHAR NOT NULL DEFAULT 'open',
+    resolution VARCHAR,
+    winner_id UUID,
+    resolved_by VARCHAR,
+    created_at VARCHAR NOT NULL,
+    resolved_at VARCHAR
+)
+"#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| Error::Storage(format!("create conflicts: {e}")))?;
+
+    // 12. memory_accesses (per-memory read log)
+    sqlx::query(
+        r#"
+CREATE TABLE IF NOT EXISTS memory_accesses (
+    id UUID PRIMARY KEY,
+    memory_id UUID NOT NULL,
+    principal VARCHAR NOT NULL,
+    operation VARCHAR NOT NULL,
+    query TEXT,
+    accessed_at VARCHAR NOT NULL
+)
+"#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| Error::Storage(format!("create memory_accesses: {e}")))?;
+
+    // 13. outbox (notifications committed with the writes they announce)
+    sqlx::query(
+        r#"
+CREATE TABLE IF NOT EXISTS outbox (
+    id UUID PRIMARY KEY,
+    topic VARCHAR NOT NULL,
+    payload JSONB NOT NULL,
+    created_at VARCHAR NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    delivered_at VARCHAR,
+    available_at VARCHAR
+)
+"#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| Error::Storage(format!("create outbox: {e}")))?;
+
+    // 14. access_requests (agent-to-agent memory access requests)
+    sqlx::query(
+        r#"
+CREATE TABLE IF NOT EXISTS access_requests (
+    id UUID PRIMARY KEY,
+    requester_id VARCHAR NOT NULL,
+    owner_id VARCHAR NOT NULL,
+    memory_ids JSONB NOT NULL,
+    tags JSONB NOT NULL,
+    justification TEXT NOT NULL,
+    permission VARCHAR NOT NULL,
+    expires_in_hours DOUBLE PRECISION,
+    status VARCHAR NOT NULL DEFAULT 'pending',
+    decided_by VARCHAR,
+    decision_note TEXT,
+    acl_ids JSONB NOT NULL,
+    created_at VARCHAR NOT NULL,
+    decided_at VARCHAR
+)
+"#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| Error::Storage(format!("create access_requests: {e}")))?;
+
+    // 15. channels + channel_members (shared memory spaces)
+    sqlx::query(
+        r#"
+CREATE TABLE IF NOT EXISTS channels (
+    name VARCHAR PRIMARY KEY,
+    created_by VARCHAR NOT NULL,
+    max_memories BIGINT,
+    created_at VARCHAR NOT NULL
+)
+"#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| Error::Storage(format!("create channels: {e}")))?;
+
+    sqlx::query(
+        r#"
+CREATE TABLE IF NOT EXISTS channel_members (
+    channel VARCHAR NOT NULL,
+    agent_id VARCHAR NOT NULL,
+    joined_at VARCHAR NOT NULL,
+    PRIMARY KEY (channel, agent_id)
+)
+"#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| Error::Storage(format!("create channel_members: {e}")))?;
+
+    // 16. event_archives (event segments moved to cold storage)
+    sqlx::query(
+        r#"
+CREATE TABLE IF NOT EXISTS event_archives (
+    id UUID PRIMARY KEY,
+    agent_id VARCHAR NOT NULL,
+    first_event_id UUID NOT NULL,
+    last_event_id UUID NOT NULL,
+    from_timestamp VARCHAR NOT NULL,
+    to_timestamp VARCHAR NOT NULL,
+    first_logical_clock BIGINT NOT NULL,
+    last_logical_clock BIGINT NOT NULL,
+    event_count BIGINT NOT NULL,
+    merkle_root VARCHAR NOT NULL,
+    last_content_hash VARCHAR NOT NULL,
+    object_key VARCHAR NOT NULL,
+    archived_at VARCHAR NOT NULL
+)
+"#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| Error::Storage(format!("create event_archives: {e}")))?;
+
+    // 17. chain_checkpoints (incremental chain verification markers;
+    // thread_id is '' for an agent's whole chain)
+    sqlx::query(
+        r#"
+CREATE TABLE IF NOT EXISTS chain_checkpoints (
+    agent_id VARCHAR NOT NULL,
+    thread_id VARCHAR NOT NULL,
+    verified_count BIGINT NOT NULL,
+    last_record_id UUID NOT NULL,
+    last_created_at VARCHAR NOT NULL,
+    last_content_hash VARCHAR NOT NULL,
+    rolling_root VARCHAR NOT NULL,
+    verified_at VARCHAR NOT NULL,
+    PRIMARY KEY (agent_id, thread_id)
+)
+"#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| Error::Storage(format!("create chain_checkpoints: {e}")))?;
+
+    // 18. revoked_share_links (share link denylist)
+    sqlx::query(
+        r#"
+CREATE TABLE IF NOT EXISTS revoked_share_links (
+    link_id UUID NOT NULL,
+    memory_id UUID NOT NULL,
+    revoked_by VARCHAR NOT NULL,
+    revoked_at VARCHAR NOT NULL,
+    expires_at VARCHAR NOT NULL,
+    PRIMARY KEY (link_id, memory_id)
+)
+"#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| Error::Storage(format!("create revoked_share_links: {e}")))?;
+
+    // 19. pseudonyms (per-org alias tables; org_id is '' without an org)
+    sqlx::query(
+        r#"
+CREATE TABLE IF NOT EXISTS pseudonyms (
+    org_id VARCHAR NOT NULL,
+    alias VARCHAR NOT NULL,
+    sealed_agent_id TEXT NOT NULL,
+    created_at VARCHAR NOT NULL,
+    PRIMARY KEY (org_id, alias)
+)
+"#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| Error::Storage(format!("create pseudonyms: {e}")))?;
+    // Aliases used to live under `pseudonym:<org>:<alias>` keys of
+    // sync_metadata. Aliases never contain ':'.
+    sqlx::query(
+        r#"
+INSERT INTO pseudonyms (org_id, alias, sealed_agent_id, created_at)
+SELECT substring(key from '^pseudonym:(.*):[^:]*$'),
+       substring(key from '^pseudonym:.*:([^:]*)$'),
+       value,
+       updated_at
+FROM sync_metadata
+WHERE key LIKE 'pseudonym:%'
+ON CONFLICT (org_id, alias) DO NOTHING
+"#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| Error::Storage(format!("move pseudonyms: {e}")))?;
+    sqlx::query("DELETE FROM sync_metadata WHERE key LIKE 'pseudonym:%'")
+        .execute(pool)
+        .await
+        .map_err(|e| Error::Storage(format!("move pseudonyms: {e}")))?;
+
+    // 20. mnemo_meta (markers of one-time data migrations)
+    sqlx::query(
+        r#"
+CREATE TABLE IF NOT EXISTS mnemo_meta (
+    key VARCHAR PRIMARY KEY,
+    value VARCHAR NOT NULL,
+    updated_at VARCHAR NOT NULL
+)
+"#,
+    )
+    .execute(pool)
+    .await
+    .map_err(|e| Error::Storage(format!("create mnemo_meta: {e}")))?;
+
+    // ---- Column upgrades ----
+    // Columns added after a table first shipped. Postgres supports
+    // `ADD COLUMN IF NOT EXISTS`, so these are safe to re-run on every boot.
+    let column_alters: &[&str] = &[
+        "ALTER TABLE memories ADD COLUMN IF NOT EXISTS pinned BOOLEAN NOT NULL DEFAULT FALSE",
+        "ALTER TABLE agent_profiles ADD COLUMN IF NOT EXISTS retrieval_settings JSONB",
+        "ALTER TABLE memories ADD COLUMN IF NOT EXISTS valid_from VARCHAR",
+        "ALTER TABLE memories ADD COLUMN IF NOT EXISTS valid_until VARCHAR",
+        "ALTER TABLE memories ADD COLUMN IF NOT EXISTS curation VARCHAR",
+        "ALTER TABLE memories ADD COLUMN IF NOT EXISTS attestation VARCHAR",
+        "ALTER TABLE memories ADD COLUMN IF NOT EXISTS channel VARCHAR",
+        // Compressed content; existing rows are compressed when next updated.
+        "ALTER TABLE memories ADD COLUMN IF NOT EXISTS content_zstd BYTEA",
+        "ALTER TABLE memories ADD COLUMN IF NOT EXISTS content_len BIGINT",
+        "ALTER TABLE agent_events ADD COLUMN IF NOT EXISTS request_id VARCHAR",
+    ];
+
+    for stmt in column_alters {
+        sqlx::query(sqlx::AssertSqlSafe(*stmt))
+            .execute(pool)
+            .await
+            .map_err(|e| Error::Storage(format!("alter column: {e}")))?;
+    }
+
     // ---- Indexes ----
     // sqlx 0.9 gates dynamic SQL behind `SqlSafeStr`; these statements are
     // compile-time literals (no user data), so `AssertSqlSafe` is audited-safe.
@@ -224,9 +504,18 @@ CREATE TABLE IF NOT EXISTS embedding_baseline (
         "CREATE INDEX IF NOT EXISTS idx_events_agent ON agent_events(agent_id)",
         "CREATE INDEX IF NOT EXISTS idx_events_thread ON agent_events(thread_id)",
         "CREATE INDEX IF NOT EXISTS idx_events_parent ON agent_events(parent_event_id)",
+        "CREATE INDEX IF NOT EXISTS idx_events_request_id ON agent_events(request_id)",
+        "CREATE INDEX IF NOT EXISTS idx_conflicts_agent_status ON conflicts(agent_id, status)",
+        "CREATE INDEX IF NOT EXISTS idx_memory_accesses_memory ON memory_accesses(memory_id, accessed_at)",
         "CREATE INDEX IF NOT EXISTS idx_checkpoints_thread ON checkpoints(thread_id, branch_name)",
         "CREATE INDEX IF NOT EXISTS idx_delegations_delegator ON delegations(delegator_id)",
         "CREATE INDEX IF NOT EXISTS idx_delegations_delegate ON delegations(delegate_id)",
+        "CREATE INDEX IF NOT EXISTS idx_outbox_pending ON outbox(created_at) WHERE delivered_at IS NULL",
+        "CREATE INDEX IF NOT EXISTS idx_access_requests_owner_status ON access_requests(owner_id, status)",
+        "CREATE INDEX IF NOT EXISTS idx_access_requests_requester ON access_requests(requester_id)",
+        "CREATE INDEX IF NOT EXISTS idx_channel_members_agent ON channel_members(agent_id)",
+        "CREATE INDEX IF NOT EXISTS idx_memories_channel ON memories(channel)",
+        "CREATE INDEX IF NOT EXISTS idx_event_archives_agent ON event_archives(agent_id, from_timestamp)",
     ];
 
     for stmt in index_stmts {
@@ -236,11 +525,18 @@ CREATE TABLE IF NOT EXISTS embedding_baseline (
             .map_err(|e| Error::Storage(format!("create index: {e}")))?;
     }
 
-    // Append-only enforcement on agent_events: prevent UPDATE/DELETE at schema level
+    grant_export_and_replay(pool).await?;
+
+    // Append-only enforcement on agent_events: prevent UPDATE/DELETE at schema
+    // level. Event archival deletes the rows it has copied to cold storage
+    // inside a transaction that sets `mnemo.event_archival`.
     sqlx::query(
         r#"
 CREATE OR REPLACE FUNCTION prevent_event_modification() RETURNS trigger AS $$
 BEGIN
+    IF TG_OP = 'DELETE' AND current_setting('mnemo.event_archival', true) = 'on' THEN
+        RETURN OLD;
+    END IF;
     RAISE EXCEPTION 'agent_events is append-only: % not allowed', TG_OP;
     RETURN NULL;
 END;
@@ -287,3 +583,222 @@ $$
 
     Ok(())
 }
+
+/// Keep grants made before export and replay became permissions of their
+/// own working, as the DuckDB migration does: live all-memories
+/// delegations get a replay companion and share or delegate ACLs an
+/// export companion. Runs once per database, in one transaction with its
+/// `mnemo_meta` marker.
+async fn grant_export_and_replay(pool: &sqlx::PgPool) -> Result<()> {
+    let err = |e: sqlx::Error| Error::Storage(format!("grant export and replay: {e}"));
+    let mut tx = pool.begin().await.map_err(err)?;
+    let claimed = sqlx::query(
+        "INSERT INTO mnemo_meta (key, value, updated_at) VALUES ('export_replay_grants', 'done', $1) \
+         ON CONFLICT (key) DO NOTHING",
+    )
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(&mut *tx)
+    .await
+    .map_err(err)?
+    .rows_affected();
+    if claimed == 0 {
+        return Ok(());
+    }
+    // The copies belong to no one agent; lift row-level security if on.
+    sqlx::query("SELECT set_config('mnemo.agent_id', $1, true)")
+        .bind(RLS_SYSTEM_CONTEXT)
+        .execute(&mut *tx)
+        .await
+        .map_err(err)?;
+    sqlx::query(
+        r#"
+INSERT INTO delegations (id, delegator_id, delegate_id, permission, scope_type, scope_value, max_depth, current_depth, parent_delegation_id, created_at, expires_at, revoked_at)
+SELECT gen_random_uuid(), delegator_id, delegate_id, 'replay', scope_type, scope_value, max_depth, current_depth, parent_delegation_id, created_at, expires_at, NULL
+FROM delegations
+WHERE scope_type = 'all_memories' AND revoked_at IS NULL AND permission NOT IN ('admin', 'replay')
+"#,
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(err)?;
+    sqlx::query(
+        r#"
+INSERT INTO acls (id, memory_id, principal_type, principal_id, permission, granted_by, created_at, expires_at)
+SELECT gen_random_uuid(), memory_id, principal_type, principal_id, 'export', granted_by, created_at, expires_at
+FROM acls
+WHERE permission IN ('share', 'delegate')
+"#,
+    )
+    .execute(&mut *tx)
+    .await
+    .map_err(err)?;
+    tx.commit().await.map_err(err)
+}
+
+/// Bring the HNSW index's build options in line with `config`.
+///
+/// Changed options are written with `ALTER INDEX ... SET`, which only takes
+/// effect when the index is rebuilt. An index over no embeddings is rebuilt
+/// here, since that is instant. Otherwise the rebuild is left to
+/// [`PgStorage::maintain_vector_index`](crate::PgStorage::maintain_vector_index)
+/// and `true` is returned.
+pub async fn tune_hnsw_index(pool: &sqlx::PgPool, config: &HnswConfig) -> Result<bool> {
+    config.validate()?;
+    let row = sqlx::query("SELECT reloptions FROM pg_class WHERE relname = $1")
+        .bind(HNSW_INDEX_NAME)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| Error::Storage(format!("read hnsw index options: {e}")))?;
+    let Some(row) = row else {
+        return Err(Error::Storage(format!(
+            "hnsw index {HNSW_INDEX_NAME} is missing; run migrations first"
+        )));
+    };
+    let options: Option<Vec<String>> = row
+        .try_get("reloptions")
+        .map_err(|e| Error::Storage(e.to_string()))?;
+    let current = |key: &str, default: u32| {
+        options
+            .iter()
+            .flatten()
+            .find_map(|opt| opt.strip_prefix(key)?.strip_prefix('=')?.parse().ok())
+            .unwrap_or(default)
+    };
+    let defaults = HnswConfig::default();
+    if current("m", defaults.m) == config.m
+        && current("ef_construction", defaults.ef_construction) == config.ef_construction
+    {
+        return Ok(false);
+    }
+
+    // Integers only, so the formatted statements are SQL-safe.
+    let alter = format!(
+        "ALTER INDEX {HNSW_INDEX_NAME} SET (m = {}, ef_construction = {})",
+        config.m, config.ef_construction
+    );
+    sqlx::query(sqlx::AssertSqlSafe(alter.as_str()))
+        .execute(pool)
+        .await
+        .map_err(|e| Error::Storage(format!("alter hnsw index: {e}")))?;
+
+    let has_embeddings: bool =
+        sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM memories WHERE embedding IS NOT NULL)")
+            .fetch_one(pool)
+            .await
+            .map_err(|e| Error::Storage(e.to_string()))?;
+    if has_embeddings {
+        tracing::warn!(
+            m = config.m,
+            ef_construction = config.ef_construction,
+            "hnsw index options changed; they apply after the next vector index maintenance rebuild"
+        );
+        return Ok(true);
+    }
+    let reindex = format!("REINDEX INDEX {HNSW_INDEX_NAME}");
+    sqlx::query(sqlx::AssertSqlSafe(reindex.as_str()))
+        .execute(pool)
+        .await
+        .map_err(|e| Error::Storage(format!("reindex hnsw index: {e}")))?;
+    Ok(false)
+}
+
+/// `mnemo.agent_id` value that lifts row-level security, used for
+/// maintenance and other work done on no agent's behalf.
+pub const RLS_SYSTEM_CONTEXT: &str = "*";
+
+/// Optional migration: enforce agent isolation in the database itself with
+/// row-level security on `memories`, `agent_events` and `acls`.
+///
+/// Rows are visible only to the agent named by the `mnemo.agent_id` setting
+/// ([`PgStorage`](crate::PgStorage) sets it per operation), or to everyone
+/// when it is [`RLS_SYSTEM_CONTEXT`]. A connection that never sets it sees
+/// nothing. A memory is visible to its owner, when public or global, to
+/// principals with an ACL entry, to public ACLs, to active delegates of
+/// its owner and to members of its channel; it is writable by its owner and by ACL holders or delegates
+/// with write, delete or admin permission. Grant expiry and delegation
+/// scopes are still checked by the application, so the policies are a
+/// coarse second line of defense rather than a replacement.
+///
+/// The tables are `FORCE`d so the policies also bind their owner, but a
+/// superuser bypasses row-level security: run Mnemo as an ordinary role.
+pub async fn enable_row_level_security(pool: &sqlx::PgPool) -> Result<()> {
+    let statements: &[&str] = &[
+        r#"
+CREATE OR REPLACE FUNCTION mnemo_rls_agent() RETURNS TEXT
+LANGUAGE sql STABLE AS $$
+    SELECT coalesce(current_setting('mnemo.agent_id', true), '')
+$$
+"#,
+        "ALTER TABLE memories ENABLE ROW LEVEL SECURITY",
+        "ALTER TABLE memories FORCE ROW LEVEL SECURITY",
+        "ALTER TABLE agent_events ENABLE ROW LEVEL SECURITY",
+        "ALTER TABLE agent_events FORCE ROW LEVEL SECURITY",
+        "ALTER TABLE acls ENABLE ROW LEVEL SECURITY",
+        "ALTER TABLE acls FORCE ROW LEVEL SECURITY",
+        "DROP POLICY IF EXISTS mnemo_agent_isolation ON memories",
+        r#"
+CREATE POLICY mnemo_agent_isolation ON memories
+USING (
+    mnemo_rls_agent() = '*'
+    OR agent_id = mnemo_rls_agent()
+    OR scope IN ('public', 'global')
+    OR EXISTS (
+        SELECT 1 FROM acls a
+        WHERE a.memory_id = memories.id
+          AND (a.principal_id = mnemo_rls_agent() OR a.principal_type = 'public')
+    )
+    OR EXISTS (
+        SELECT 1 FROM delegations d
+        WHERE d.delegator_id = memories.agent_id
+          AND d.delegate_id = mnemo_rls_agent()
+          AND d.revoked_at IS NULL
+    )
+    OR EXISTS (
+        SELECT 1 FROM channel_members cm
+        WHERE cm.channel = memories.channel
+          AND cm.agent_id = mnemo_rls_agent()
+    )
+)
+WITH CHECK (
+    mnemo_rls_agent() = '*'
+    OR agent_id = mnemo_rls_agent()
+    OR EXISTS (
+        SELECT 1 FROM acls a
+        WHERE a.memory_id = memories.id
+          AND a.principal_id = mnemo_rls_agent()
+          AND a.permission IN ('write', 'delete', 'admin')
+    )
+    OR EXISTS (
+        SELECT 1 FROM delegations d
+        WHERE d.delegator_id = memories.agent_id
+          AND d.delegate_id = mnemo_rls_agent()
+          AND d.revoked_at IS NULL
+          AND d.permission IN ('write', 'delete', 'admin')
+    )
+)
+"#,
+        "DROP POLICY IF EXISTS mnemo_agent_isolation ON agent_events",
+        r#"
+CREATE POLICY mnemo_agent_isolation ON agent_events
+USING (mnemo_rls_agent() = '*' OR agent_id = mnemo_rls_agent())
+"#,
+        "DROP POLICY IF EXISTS mnemo_agent_isolation ON acls",
+        r#"
+CREATE POLICY mnemo_agent_isolation ON acls
+USING (
+    mnemo_rls_agent() = '*'
+    OR principal_id = mnemo_rls_agent()
+    OR principal_type = 'public'
+    OR granted_by = mnemo_rls_agent()
+)
+WITH CHECK (mnemo_rls_agent() = '*' OR granted_by = mnemo_rls_agent())
+"#,
+    ];
+    for stmt in statements {
+        sqlx::query(sqlx::AssertSqlSafe(*stmt))
+            .execute(pool)
+            .await
+            .map_err(|e| Error::Storage(format!("enable row-level security: {e}")))?;
+    }
+    Ok(())
+}
@@ -8,6 +8,71 @@ use pgvector::Vector;
 use sqlx::Row;
 use uuid::Uuid;
 
+use crate::migrations::RLS_SYSTEM_CONTEXT;
+
+/// Name of the HNSW index over `memories.embedding`.
+pub const HNSW_INDEX_NAME: &str = "idx_memories_embedding_hnsw";
+
+/// pgvector's build-time defaults, assumed for an index created without
+/// explicit options.
+const PGVECTOR_DEFAULT_M: u32 = 16;
+const PGVECTOR_DEFAULT_EF_CONSTRUCTION: u32 = 64;
+/// pgvector's ceiling for `hnsw.ef_search`.
+const MAX_EF_SEARCH: u32 = 1000;
+
+/// Tuning for the pgvector HNSW index.
+///
+/// `m` and `ef_construction` are build parameters: changing them on an
+/// existing database updates the index options, and the index picks them up
+/// on its next rebuild ([`PgStorage::maintain_vector_index`](crate::PgStorage::maintain_vector_index)).
+/// `ef_search` is applied per query by [`PgVectorIndex::with_ef_search`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HnswConfig {
+    /// Graph neighbours per node. Higher improves recall and costs memory.
+    pub m: u32,
+    /// Candidate list size while building. Must be at least `2 * m`.
+    pub ef_construction: u32,
+    /// Candidate list size while searching. `None` keeps the server setting
+    /// (pgvector defaults to 40).
+    pub ef_search: Option<u32>,
+}
+
+impl Default for HnswConfig {
+    fn default() -> Self {
+        Self {
+            m: PGVECTOR_DEFAULT_M,
+            ef_construction: PGVECTOR_DEFAULT_EF_CONSTRUCTION,
+            ef_search: None,
+        }
+    }
+}
+
+impl HnswConfig {
+    /// Check the values against pgvector's accepted ranges.
+    pub fn validate(&self) -> Result<()> {
+        if !(2..=100).contains(&self.m) {
+            return Err(Error::Validation(format!(
+                "hnsw m must be in [2, 100], got {}",
+                self.m
+            )));
+        }
+        if !(4..=1000).contains(&self.ef_construction) || self.ef_construction < 2 * self.m {
+            return Err(Error::Validation(format!(
+                "hnsw ef_construction must be in [4, 1000] and at least 2 * m, got {}",
+                self.ef_construction
+            )));
+        }
+        if let Some(ef) = self.ef_search
+            && !(1..=MAX_EF_SEARCH).contains(&ef)
+        {
+            return Err(Error::Validation(format!(
+                "hnsw ef_search must be in [1, {MAX_EF_SEARCH}], got {ef}"
+            )));
+        }
+        Ok(())
+    }
+}
+
 /// A pgvector-backed [`VectorIndex`] for the PostgreSQL backend.
 ///
 /// PostgreSQL stores each memory's embedding in a pgvector `vector` column
@@ -47,6 +112,12 @@ pub struct PgVectorIndex {
     /// dimension-mismatched query with a clear message instead of a raw
     /// Postgres error.
     dimensions: usize,
+    /// `hnsw.ef_search` applied to every ANN query; `None` keeps the server
+    /// setting.
+    ef_search: Option<u32>,
+    /// Set `mnemo.agent_id` before each ANN query so the row-level security
+    /// policies from `enable_row_level_security` apply to it.
+    row_level_security: bool,
     count: AtomicUsize,
 }
 
@@ -58,6 +129,8 @@ impl PgVectorIndex {
         Self {
             pool: None,
             dimensions: 0,
+            ef_search: None,
+            row_level_security: false,
             count: AtomicUsize::new(0),
         }
     }
@@ -70,31 +143,92 @@ impl PgVectorIndex {
         Self {
             pool: Some(pool),
             dimensions,
+            ef_search: None,
+            row_level_security: false,
             count: AtomicUsize::new(0),
         }
     }
 
+    /// Run every ANN query with `SET LOCAL hnsw.ef_search`. The value is
+    /// raised to the query's `limit` (up to pgvector's maximum of 1000), since
+    /// HNSW never returns more rows than its candidate list.
+    pub fn with_ef_search(mut self, ef_search: u32) -> Self {
+        self.ef_search = Some(ef_search);
+        self
+    }
+
+    /// Run every ANN query as the engine's acting agent (or the system
+    /// context outside one), for a schema migrated with
+    /// `enable_row_level_security`.
+    pub fn with_row_level_security(mut self) -> Self {
+        self.row_level_security = true;
+        self
+    }
+
+    /// The `mnemo.agent_id` value for a query issued now, when row-level
+    /// security is on. Read before the query future is driven, while the
+    /// caller's task-local agent context is still in scope.
+    fn rls_agent(&self) -> Option<String> {
+        self.row_level_security.then(|| {
+            mnemo_core::storage::current_agent_context()
+                .unwrap_or_else(|| RLS_SYSTEM_CONTEXT.to_string())
+        })
+    }
+
     /// The cosine-distance ANN query against the HNSW index. Returns up to
     /// `limit` `(id, distance)` rows, nearest first. `$1` (the query vector) is
     /// referenced twice — once for the projected distance, once for the
-    /// index-ordered `ORDER BY` — from a single bind.
+    /// index-ordered `ORDER BY` — from a single bind. With `ef_search` or an
+    /// `rls_agent` the query runs in a transaction that scopes the settings
+    /// to it.
     async fn ann_query(
         pool: &sqlx::PgPool,
         query: &Vector,
         limit: usize,
+        ef_search: Option<u32>,
+        rls_agent: Option<&str>,
     ) -> Result<Vec<(Uuid, f32)>> {
-        let rows = sqlx::query(
-            "SELECT id, (embedding <=> $1) AS dist \
+        let sql = "SELECT id, (embedding <=> $1) AS dist \
              FROM memories \
              WHERE embedding IS NOT NULL AND deleted_at IS NULL \
              ORDER BY embedding <=> $1 \
-             LIMIT $2",
-        )
-        .bind(query)
-        .bind(limit as i64)
-        .fetch_all(pool)
-        .await
-        .map_err(map_ann_error)?;
+             LIMIT $2";
+        let rows = if ef_search.is_none() && rls_agent.is_none() {
+            sqlx::query(sql)
+                .bind(query)
+                .bind(limit as i64)
+                .fetch_all(pool)
+                .await
+                .map_err(map_ann_error)?
+        } else {
+            let mut tx = pool.begin().await.map_err(map_ann_error)?;
+            if let Some(ef) = ef_search {
+                let ef = ef
+                    .max(u32::try_from(limit).unwrap_or(u32::MAX))
+                    .min(MAX_EF_SEARCH);
+                // `ef` is an integer, so the formatted statement is SQL-safe.
+                let set = format!("SET LOCAL hnsw.ef_search = {ef}");
+                sqlx::query(sqlx::AssertSqlSafe(set.as_str()))
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(map_ann_error)?;
+            }
+            if let Some(agent) = rls_agent {
+                sqlx::query("SELECT set_config('mnemo.agent_id', $1, true)")
+                    .bind(agent)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(map_ann_error)?;
+            }
+            let rows = sqlx::query(sql)
+                .bind(query)
+                .bind(limit as i64)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(map_ann_error)?;
+            tx.commit().await.map_err(map_ann_error)?;
+            rows
+        };
 
         let mut out = Vec::with_capacity(rows.len());
         for row in &rows {
@@ -202,7 +336,14 @@ impl VectorIndex for PgVectorIndex {
     fn search(&self, query: &[f32], limit: usize) -> Result<Vec<(Uuid, f32)>> {
         let pool = self.pool_for(query)?;
         let vec = Vector::from(query.to_vec());
-        block_on_query(Self::ann_query(pool, &vec, limit))
+        let agent = self.rls_agent();
+        block_on_query(Self::ann_query(
+            pool,
+            &vec,
+            limit,
+            self.ef_search,
+            agent.as_deref(),
+        ))
     }
 
     fn filtered_search(
@@ -221,10 +362,13 @@ impl VectorIndex for PgVectorIndex {
         // have `limit` accessible hits or the underlying table is exhausted
         // (the ANN query returned fewer rows than we asked for). Mirrors the
         // USearch backend so filtered recall never under-returns.
+        let agent = self.rls_agent();
         block_on_query(async move {
             let mut oversample = limit.saturating_mul(3).max(1);
             loop {
-                let candidates = Self::ann_query(pool, &vec, oversample).await?;
+                let candidates =
+                    Self::ann_query(pool, &vec, oversample, self.ef_search, agent.as_deref())
+                        .await?;
                 let exhausted = candidates.len() < oversample;
                 let filtered: Vec<(Uuid, f32)> = candidates
                     .into_iter()
@@ -298,6 +442,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn hnsw_config_enforces_pgvector_ranges() {
+        assert!(HnswConfig::default().validate().is_ok());
+        let tuned = HnswConfig {
+            m: 32,
+            ef_construction: 128,
+            ef_search: Some(200),
+        };
+        assert!(tuned.validate().is_ok());
+        for bad in [
+            HnswConfig {
+                m: 1,
+                ..Default::default()
+            },
+            HnswConfig {
+                m: 48,
+                ef_construction: 64,
+                ..Default::default()
+            },
+            HnswConfig {
+                ef_search: Some(0),
+                ..Default::default()
+            },
+        ] {
+            assert!(matches!(bad.validate(), Err(Error::Validation(_))));
+        }
+    }
+
     #[test]
     fn dimension_mismatch_is_loud() {
         // A pool-less index can't reach the dim check, but we can assert the
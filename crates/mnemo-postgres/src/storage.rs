@@ -1,17 +1,41 @@
 use mnemo_core::error::{Error, Result};
+use mnemo_core::model::access_log::MemoryAccess;
+use mnemo_core::model::access_request::{AccessRequest, AccessRequestStatus};
 use mnemo_core::model::acl::{Acl, Permission};
-use mnemo_core::model::agent_profile::AgentProfile;
+use mnemo_core::model::agent_profile::{AgentProfile, RetrievalDefaults};
+use mnemo_core::model::chain_checkpoint::ChainCheckpoint;
+use mnemo_core::model::channel::{Channel, ChannelMember};
 use mnemo_core::model::checkpoint::Checkpoint;
+use mnemo_core::model::conflict::{ConflictRecord, ConflictStatus};
+use mnemo_core::model::content_stats::{ContentStorageStats, DailyGrowth};
 use mnemo_core::model::delegation::{Delegation, DelegationScope};
 use mnemo_core::model::embedding_baseline::EmbeddingBaseline;
 use mnemo_core::model::event::AgentEvent;
+use mnemo_core::model::event_archive::EventArchiveSegment;
 use mnemo_core::model::memory::MemoryRecord;
+use mnemo_core::model::outbox::OutboxMessage;
+use mnemo_core::model::pseudonym::PseudonymEntry;
+use mnemo_core::model::recall_profile::RecallProfile;
 use mnemo_core::model::relation::Relation;
-use mnemo_core::storage::{MemoryFilter, StorageBackend};
+use mnemo_core::model::share_link::RevokedShareLink;
+use mnemo_core::query::change_feed::MemoryChange;
+use mnemo_core::storage::content_codec::{self, DEFAULT_COMPRESSION_THRESHOLD};
+use mnemo_core::storage::{
+    CompactionReport, MemoryColumns, MemoryFilter, StorageBackend, WriteBatch,
+};
 use pgvector::Vector;
+use serde::{Deserialize, Serialize};
 use sqlx::Row;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::mpsc;
 use uuid::Uuid;
 
+use crate::migrations::RLS_SYSTEM_CONTEXT;
+use crate::pgvector_index::{HNSW_INDEX_NAME, HnswConfig};
+use mnemo_core::storage::current_agent_context;
+use sqlx::Postgres;
+use sqlx::pool::PoolConnection;
+
 /// PostgreSQL-backed storage for Mnemo.
 ///
 /// Wraps a `sqlx::PgPool` and runs schema migrations on construction.
@@ -21,6 +45,27 @@ use uuid::Uuid;
 pub struct PgStorage {
     pool: sqlx::PgPool,
     dimensions: usize,
+    /// Set when the HNSW options changed and the index still has to be
+    /// rebuilt by [`PgStorage::maintain_vector_index`].
+    hnsw_rebuild_pending: AtomicBool,
+    /// Tags this instance's change notifications so its own subscription
+    /// can skip them.
+    origin: Uuid,
+    /// Set `mnemo.agent_id` on every connection an operation uses. See
+    /// [`PgStorage::with_row_level_security`].
+    row_level_security: bool,
+    /// See [`PgStorage::with_compression_threshold`].
+    compression_threshold: Option<usize>,
+}
+
+/// `NOTIFY` channel carrying [`MemoryChange`]s for every memory write.
+pub const MEMORY_CHANGES_CHANNEL: &str = "mnemo_memory_changes";
+
+/// Payload of a [`MEMORY_CHANGES_CHANNEL`] notification.
+#[derive(Serialize, Deserialize)]
+struct ChangeNotice {
+    origin: Uuid,
+    change: MemoryChange,
 }
 
 impl PgStorage {
@@ -32,15 +77,44 @@ impl PgStorage {
         let pool = sqlx::PgPool::connect(url)
             .await
             .map_err(|e| Error::Storage(e.to_string()))?;
-        let storage = Self { pool, dimensions };
+        let storage = Self {
+            pool,
+            dimensions,
+            hnsw_rebuild_pending: AtomicBool::new(false),
+            origin: Uuid::now_v7(),
+            row_level_security: false,
+            compression_threshold: Some(DEFAULT_COMPRESSION_THRESHOLD),
+        };
         crate::migrations::run_migrations(&storage.pool, dimensions).await?;
         Ok(storage)
     }
 
+    /// [`connect`](Self::connect), then apply `hnsw`'s build options to the
+    /// HNSW index (see [`crate::migrations::tune_hnsw_index`]).
+    pub async fn connect_with_hnsw(
+        url: &str,
+        dimensions: usize,
+        hnsw: &HnswConfig,
+    ) -> Result<Self> {
+        let storage = Self::connect(url, dimensions).await?;
+        let pending = crate::migrations::tune_hnsw_index(&storage.pool, hnsw).await?;
+        storage
+            .hnsw_rebuild_pending
+            .store(pending, Ordering::Relaxed);
+        Ok(storage)
+    }
+
     /// Build a `PgStorage` from an existing pool (useful for tests).
     pub async fn from_pool(pool: sqlx::PgPool, dimensions: usize) -> Result<Self> {
         crate::migrations::run_migrations(&pool, dimensions).await?;
-        Ok(Self { pool, dimensions })
+        Ok(Self {
+            pool,
+            dimensions,
+            hnsw_rebuild_pending: AtomicBool::new(false),
+            origin: Uuid::now_v7(),
+            row_level_security: false,
+            compression_threshold: Some(DEFAULT_COMPRESSION_THRESHOLD),
+        })
     }
 
     /// A clone of the connection pool, so a [`crate::PgVectorIndex`] can share
@@ -50,10 +124,192 @@ impl PgStorage {
         self.pool.clone()
     }
 
+    /// Enforce agent isolation in the database as well: create the
+    /// row-level security policies (see
+    /// [`crate::migrations::enable_row_level_security`]) and run every
+    /// operation with `mnemo.agent_id` set to the acting agent from
+    /// [`with_agent_context`](mnemo_core::storage::with_agent_context), or to
+    /// the system context outside one. Pair it with
+    /// [`PgVectorIndex::with_row_level_security`](crate::PgVectorIndex::with_row_level_security).
+    pub async fn with_row_level_security(mut self) -> Result<Self> {
+        crate::migrations::enable_row_level_security(&self.pool).await?;
+        self.row_level_security = true;
+        Ok(self)
+    }
+
+    /// Store content of at least `threshold` bytes zstd-compressed; `None`
+    /// stores all content as is. Defaults to
+    /// [`DEFAULT_COMPRESSION_THRESHOLD`]. Only affects writes: reads
+    /// handle both forms. See [`content_codec`].
+    pub fn with_compression_threshold(mut self, threshold: Option<usize>) -> Self {
+        self.compression_threshold = threshold;
+        self
+    }
+
+    /// A pooled connection for one operation, scoped to the acting agent.
+    async fn conn(&self) -> Result<PoolConnection<Postgres>> {
+        self.conn_as(current_agent_context().as_deref()).await
+    }
+
+    /// A pooled connection scoped to `agent`, or to the system context for
+    /// `None`. The setting is session-level, so it is rewritten on every
+    /// acquire rather than trusted from the connection's previous user.
+    async fn conn_as(&self, agent: Option<&str>) -> Result<PoolConnection<Postgres>> {
+        let mut conn = self.pool.acquire().await.map_err(map_sqlx)?;
+        if self.row_level_security {
+            sqlx::query("SELECT set_config('mnemo.agent_id', $1, false)")
+                .bind(agent.unwrap_or(RLS_SYSTEM_CONTEXT))
+                .execute(&mut *conn)
+                .await
+                .map_err(map_sqlx)?;
+        }
+        Ok(conn)
+    }
+
+    /// Memories `agent_id` can read, optionally only those created by
+    /// `as_of`.
+    async fn accessible_memory_ids(
+        &self,
+        agent_id: &str,
+        as_of: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<Uuid>> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let rows = sqlx::query(
+            r#"
+SELECT id FROM memories
+WHERE (
+    agent_id = $1
+    OR scope IN ('public', 'global')
+    OR id IN (
+        SELECT memory_id FROM acls
+        WHERE principal_id = $2 AND (expires_at IS NULL OR expires_at > $3)
+    )
+    OR channel IN (
+        SELECT channel FROM channel_members WHERE agent_id = $1
+    )
+)
+AND deleted_at IS NULL
+AND ($4::VARCHAR IS NULL OR created_at <= $4)
+LIMIT $5
+"#,
+        )
+        .bind(agent_id)
+        .bind(agent_id)
+        .bind(&now)
+        .bind(as_of)
+        .bind(limit as i64)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+
+        let ids: Vec<Uuid> = rows.iter().map(|r| r.get("id")).collect();
+        Ok(ids)
+    }
+
     /// The pgvector `vector(dim)` column width this storage was migrated with.
     pub fn dimensions(&self) -> usize {
         self.dimensions
     }
+
+    /// Periodic upkeep for vector search: refresh the planner statistics on
+    /// `memories` with `ANALYZE`, and rebuild the HNSW index when its build
+    /// options changed since it was built. Returns whether it rebuilt.
+    ///
+    /// The rebuild uses `REINDEX CONCURRENTLY`, so recall keeps working
+    /// (against the old index) while it runs.
+    pub async fn maintain_vector_index(&self) -> Result<bool> {
+        sqlx::query("ANALYZE memories")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Storage(format!("analyze memories: {e}")))?;
+        if !self.hnsw_rebuild_pending.load(Ordering::Relaxed) {
+            return Ok(false);
+        }
+        self.rebuild_vector_index().await?;
+        self.hnsw_rebuild_pending.store(false, Ordering::Relaxed);
+        Ok(true)
+    }
+
+    /// Subscribe to memory writes made by other `PgStorage` instances on the
+    /// same database, for [`spawn_change_listener`](mnemo_core::query::change_feed::spawn_change_listener).
+    ///
+    /// Notifications sent while the listener connection is down are lost; a
+    /// [`MemoryChange::Resync`] follows every reconnect.
+    pub async fn subscribe_changes(&self) -> Result<mpsc::Receiver<MemoryChange>> {
+        let mut listener = sqlx::postgres::PgListener::connect_with(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+        listener
+            .listen(MEMORY_CHANGES_CHANNEL)
+            .await
+            .map_err(map_sqlx)?;
+        let origin = self.origin;
+        let (tx, rx) = mpsc::channel(1024);
+        tokio::spawn(async move {
+            loop {
+                let change = match listener.try_recv().await {
+                    Ok(Some(notification)) => {
+                        match serde_json::from_str::<ChangeNotice>(notification.payload()) {
+                            Ok(notice) if notice.origin == origin => continue,
+                            Ok(notice) => notice.change,
+                            Err(e) => {
+                                tracing::warn!(error = %e, "ignoring malformed memory change notification");
+                                continue;
+                            }
+                        }
+                    }
+                    // The connection dropped; the next `try_recv` reconnects.
+                    Ok(None) => MemoryChange::Resync,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "memory change listener failed");
+                        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                        continue;
+                    }
+                };
+                if tx.send(change).await.is_err() {
+                    return;
+                }
+            }
+        });
+        Ok(rx)
+    }
+
+    /// Tell other instances about a memory write. Best effort: the write has
+    /// already happened, so a failed notification is only logged.
+    async fn notify_change(&self, change: MemoryChange) {
+        let notice = ChangeNotice {
+            origin: self.origin,
+            change,
+        };
+        let payload = match serde_json::to_string(&notice) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to encode memory change notification");
+                return;
+            }
+        };
+        if let Err(e) = sqlx::query("SELECT pg_notify($1, $2)")
+            .bind(MEMORY_CHANGES_CHANNEL)
+            .bind(payload)
+            .execute(&self.pool)
+            .await
+        {
+            tracing::warn!(?change, error = %e, "failed to notify memory change");
+        }
+    }
+
+    /// Rebuild the HNSW index now, e.g. after heavy deletes have left it
+    /// fragmented.
+    pub async fn rebuild_vector_index(&self) -> Result<()> {
+        let sql = format!("REINDEX INDEX CONCURRENTLY {HNSW_INDEX_NAME}");
+        sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Error::Storage(format!("reindex {HNSW_INDEX_NAME}: {e}")))?;
+        tracing::info!("rebuilt {HNSW_INDEX_NAME}");
+        Ok(())
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -61,7 +317,140 @@ impl PgStorage {
 // ---------------------------------------------------------------------------
 
 fn map_sqlx(e: sqlx::Error) -> Error {
-    Error::Storage(e.to_string())
+    match e {
+        sqlx::Error::PoolTimedOut => Error::Timeout(e.to_string()),
+        sqlx::Error::Io(_) | sqlx::Error::PoolClosed => Error::Unavailable(e.to_string()),
+        sqlx::Error::Database(ref db) if db.is_unique_violation() => Error::Conflict(e.to_string()),
+        _ => Error::Storage(e.to_string()),
+    }
+}
+
+const CONFLICT_COLUMNS: &str = "id, agent_id, memory_a, memory_b, similarity, reason, status, resolution, winner_id, resolved_by, created_at, resolved_at";
+
+fn row_to_conflict(r: &sqlx::postgres::PgRow) -> Result<ConflictRecord> {
+    let status: String = r.get("status");
+    Ok(ConflictRecord {
+        id: r.get("id"),
+        agent_id: r.get("agent_id"),
+        memory_a: r.get("memory_a"),
+        memory_b: r.get("memory_b"),
+        similarity: r.get("similarity"),
+        reason: r.get("reason"),
+        status: status.parse()?,
+        resolution: r.get("resolution"),
+        winner_id: r.get("winner_id"),
+        resolved_by: r.get("resolved_by"),
+        created_at: r.get("created_at"),
+        resolved_at: r.get("resolved_at"),
+    })
+}
+
+const ACCESS_REQUEST_COLUMNS: &str = "id, requester_id, owner_id, memory_ids, tags, justification, permission, expires_in_hours, status, decided_by, decision_note, acl_ids, created_at, decided_at";
+
+fn row_to_channel(r: &sqlx::postgres::PgRow) -> Channel {
+    Channel {
+        name: r.get("name"),
+        created_by: r.get("created_by"),
+        max_memories: r.get::<Option<i64>, _>("max_memories").map(|n| n as u64),
+        created_at: r.get("created_at"),
+    }
+}
+
+fn row_to_access_request(r: &sqlx::postgres::PgRow) -> Result<AccessRequest> {
+    let json = |column: &str| -> serde_json::Value { r.get(column) };
+    let permission: String = r.get("permission");
+    let status: String = r.get("status");
+    Ok(AccessRequest {
+        id: r.get("id"),
+        requester_id: r.get("requester_id"),
+        owner_id: r.get("owner_id"),
+        memory_ids: serde_json::from_value(json("memory_ids"))?,
+        tags: serde_json::from_value(json("tags"))?,
+        justification: r.get("justification"),
+        permission: permission.parse()?,
+        expires_in_hours: r.get("expires_in_hours"),
+        status: status.parse()?,
+        decided_by: r.get("decided_by"),
+        decision_note: r.get("decision_note"),
+        acl_ids: serde_json::from_value(json("acl_ids"))?,
+        created_at: r.get("created_at"),
+        decided_at: r.get("decided_at"),
+    })
+}
+
+const MEMORY_ACCESS_COLUMNS: &str = "id, memory_id, principal, operation, query, accessed_at";
+
+fn row_to_memory_access(r: &sqlx::postgres::PgRow) -> Result<MemoryAccess> {
+    let operation: String = r.get("operation");
+    Ok(MemoryAccess {
+        id: r.get("id"),
+        memory_id: r.get("memory_id"),
+        principal: r.get("principal"),
+        operation: operation.parse()?,
+        query: r.get("query"),
+        accessed_at: r.get("accessed_at"),
+    })
+}
+
+const OUTBOX_COLUMNS: &str = "id, topic, payload, created_at, attempts, last_error, delivered_at";
+
+fn row_to_outbox(r: &sqlx::postgres::PgRow) -> OutboxMessage {
+    OutboxMessage {
+        id: r.get("id"),
+        topic: r.get("topic"),
+        payload: r.get("payload"),
+        created_at: r.get("created_at"),
+        attempts: u32::try_from(r.get::<i32, _>("attempts")).unwrap_or(0),
+        last_error: r.get("last_error"),
+        delivered_at: r.get("delivered_at"),
+    }
+}
+
+const EVENT_ARCHIVE_COLUMNS: &str = "id, agent_id, first_event_id, last_event_id, from_timestamp, to_timestamp, first_logical_clock, last_logical_clock, event_count, merkle_root, last_content_hash, object_key, archived_at";
+
+fn row_to_event_archive(r: &sqlx::postgres::PgRow) -> EventArchiveSegment {
+    EventArchiveSegment {
+        id: r.get("id"),
+        agent_id: r.get("agent_id"),
+        first_event_id: r.get("first_event_id"),
+        last_event_id: r.get("last_event_id"),
+        from_timestamp: r.get("from_timestamp"),
+        to_timestamp: r.get("to_timestamp"),
+        first_logical_clock: r.get("first_logical_clock"),
+        last_logical_clock: r.get("last_logical_clock"),
+        event_count: usize::try_from(r.get::<i64, _>("event_count")).unwrap_or(0),
+        merkle_root: r.get("merkle_root"),
+        last_content_hash: r.get("last_content_hash"),
+        object_key: r.get("object_key"),
+        archived_at: r.get("archived_at"),
+    }
+}
+
+fn row_to_chain_checkpoint(r: &sqlx::postgres::PgRow) -> ChainCheckpoint {
+    let thread_id: String = r.get("thread_id");
+    ChainCheckpoint {
+        agent_id: r.get("agent_id"),
+        thread_id: (!thread_id.is_empty()).then_some(thread_id),
+        verified_count: u64::try_from(r.get::<i64, _>("verified_count")).unwrap_or(0),
+        last_record_id: r.get("last_record_id"),
+        last_created_at: r.get("last_created_at"),
+        last_content_hash: r.get("last_content_hash"),
+        rolling_root: r.get("rolling_root"),
+        verified_at: r.get("verified_at"),
+    }
+}
+
+fn row_to_recall_profile(r: &sqlx::postgres::PgRow) -> Result<RecallProfile> {
+    let settings: serde_json::Value = r.get("settings");
+    Ok(RecallProfile {
+        agent_id: r.get("agent_id"),
+        name: r.get("name"),
+        settings: serde_json::from_value(settings).map_err(|e| Error::Storage(e.to_string()))?,
+        use_count: r.get::<i64, _>("use_count") as u64,
+        last_used_at: r.get("last_used_at"),
+        created_at: r.get("created_at"),
+        updated_at: r.get("updated_at"),
+    })
 }
 
 fn serialize_embedding(embedding: &Option<Vec<f32>>) -> Option<Vec<u8>> {
@@ -108,7 +497,11 @@ fn row_to_memory(row: &sqlx::postgres::PgRow) -> std::result::Result<MemoryRecor
     Ok(MemoryRecord {
         id: row.get("id"),
         agent_id: row.get("agent_id"),
-        content: row.get("content"),
+        content: content_codec::decode(
+            row.get("content"),
+            row.try_get("content_zstd").unwrap_or(None),
+        )
+        .map_err(sqlx::Error::Decode)?,
         memory_type: row
             .get::<String, _>("memory_type")
             .parse()
@@ -147,6 +540,18 @@ fn row_to_memory(row: &sqlx::postgres::PgRow) -> std::result::Result<MemoryRecor
         quarantined: row.get("quarantined"),
         quarantine_reason: row.get("quarantine_reason"),
         decay_function: row.get("decay_function"),
+        pinned: row.try_get("pinned").unwrap_or(false),
+        valid_from: row.try_get("valid_from").unwrap_or(None),
+        valid_until: row.try_get("valid_until").unwrap_or(None),
+        curation: row
+            .try_get::<Option<String>, _>("curation")
+            .unwrap_or(None)
+            .and_then(|s| s.parse().ok()),
+        attestation: row
+            .try_get::<Option<String>, _>("attestation")
+            .unwrap_or(None)
+            .and_then(|s| s.parse().ok()),
+        channel: row.try_get("channel").unwrap_or(None),
     })
 }
 
@@ -166,9 +571,45 @@ const MEMORY_COLUMNS: &str = r#"
     consolidation_state, access_count, org_id, thread_id,
     created_at, updated_at, last_accessed_at, expires_at,
     deleted_at, decay_rate, created_by, version, prev_version_id,
-    quarantined, quarantine_reason, decay_function
+    quarantined, quarantine_reason, decay_function, pinned,
+    valid_from, valid_until, curation, attestation, channel,
+    content_zstd
 "#;
 
+/// [`MEMORY_COLUMNS`] without the embedding, and with the large columns
+/// `columns` leaves out replaced by empty placeholders. Keep in step with
+/// `MEMORY_COLUMNS`.
+fn projected_memory_columns(columns: MemoryColumns) -> String {
+    let content = if columns.content {
+        "content"
+    } else {
+        "''::TEXT AS content"
+    };
+    let content_zstd = if columns.content {
+        "content_zstd"
+    } else {
+        "NULL::BYTEA AS content_zstd"
+    };
+    let metadata = if columns.metadata {
+        "metadata"
+    } else {
+        "NULL::JSONB AS metadata"
+    };
+    format!(
+        r#"
+    id, agent_id, {content}, memory_type, scope, importance,
+    tags, {metadata},
+    content_hash, prev_hash, source_type, source_id,
+    consolidation_state, access_count, org_id, thread_id,
+    created_at, updated_at, last_accessed_at, expires_at,
+    deleted_at, decay_rate, created_by, version, prev_version_id,
+    quarantined, quarantine_reason, decay_function, pinned,
+    valid_from, valid_until, curation, attestation, channel,
+    {content_zstd}
+"#
+    )
+}
+
 fn row_to_event(row: &sqlx::postgres::PgRow) -> std::result::Result<AgentEvent, sqlx::Error> {
     let payload: serde_json::Value = row.try_get("payload").unwrap_or(serde_json::Value::Null);
     let embedding_blob: Option<Vec<u8>> = row.try_get("embedding").unwrap_or(None);
@@ -196,6 +637,7 @@ fn row_to_event(row: &sqlx::postgres::PgRow) -> std::result::Result<AgentEvent,
         content_hash: row.get("content_hash"),
         prev_hash: row.get("prev_hash"),
         embedding: deserialize_embedding(embedding_blob),
+        request_id: row.try_get("request_id").unwrap_or(None),
     })
 }
 
@@ -290,80 +732,289 @@ fn row_to_delegation(row: &sqlx::postgres::PgRow) -> std::result::Result<Delegat
     })
 }
 
+/// Write `record` over its row, only while the row is at `expected_version`
+/// when one is given. Returns the number of rows written.
+async fn update_memory_row(
+    conn: &mut sqlx::PgConnection,
+    record: &MemoryRecord,
+    expected_version: Option<u32>,
+    compression_threshold: Option<usize>,
+) -> Result<u64> {
+    let content = content_codec::encode(&record.content, compression_threshold);
+    let embedding_param: Option<Vector> =
+        record.embedding.as_ref().map(|v| Vector::from(v.clone()));
+
+    let tags_slice: &[String] = &record.tags;
+
+    let result = sqlx::query(
+        r#"
+UPDATE memories SET
+    agent_id = $1, content = $2, memory_type = $3, scope = $4,
+    importance = $5, tags = $6, metadata = $7,
+    embedding = $8,
+    content_hash = $9, prev_hash = $10, source_type = $11,
+    source_id = $12, consolidation_state = $13, access_count = $14,
+    org_id = $15, thread_id = $16, updated_at = $17,
+    last_accessed_at = $18, expires_at = $19, deleted_at = $20,
+    decay_rate = $21, created_by = $22, version = $23,
+    prev_version_id = $24, quarantined = $25, quarantine_reason = $26,
+    decay_function = $27, pinned = $28,
+    valid_from = $29, valid_until = $30, curation = $31,
+    attestation = $32, channel = $33, content_zstd = $34,
+    content_len = $35
+WHERE id = $36 AND ($37::INT IS NULL OR version = $37)
+"#,
+    )
+    .bind(&record.agent_id)
+    .bind(content.text)
+    .bind(record.memory_type.to_string())
+    .bind(record.scope.to_string())
+    .bind(record.importance)
+    .bind(tags_slice)
+    .bind(&record.metadata)
+    .bind(&embedding_param)
+    .bind(&record.content_hash)
+    .bind(&record.prev_hash)
+    .bind(record.source_type.to_string())
+    .bind(&record.source_id)
+    .bind(record.consolidation_state.to_string())
+    .bind(record.access_count as i64)
+    .bind(&record.org_id)
+    .bind(&record.thread_id)
+    .bind(&record.updated_at)
+    .bind(&record.last_accessed_at)
+    .bind(&record.expires_at)
+    .bind(&record.deleted_at)
+    .bind(record.decay_rate)
+    .bind(&record.created_by)
+    .bind(record.version as i32)
+    .bind(record.prev_version_id)
+    .bind(record.quarantined)
+    .bind(&record.quarantine_reason)
+    .bind(&record.decay_function)
+    .bind(record.pinned)
+    .bind(&record.valid_from)
+    .bind(&record.valid_until)
+    .bind(record.curation.map(|c| c.to_string()))
+    .bind(record.attestation.map(|a| a.to_string()))
+    .bind(&record.channel)
+    .bind(&content.zstd)
+    .bind(content.len)
+    .bind(record.id)
+    .bind(expected_version.map(|v| v as i32))
+    .execute(conn)
+    .await
+    .map_err(map_sqlx)?;
+    Ok(result.rows_affected())
+}
+
 // ---------------------------------------------------------------------------
 // StorageBackend implementation
 // ---------------------------------------------------------------------------
 
+async fn insert_acl_row(conn: &mut sqlx::PgConnection, acl: &Acl) -> Result<()> {
+    sqlx::query(
+        r#"
+INSERT INTO acls (id, memory_id, principal_type, principal_id, permission, granted_by, created_at, expires_at)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+"#,
+    )
+    .bind(acl.id)
+    .bind(acl.memory_id)
+    .bind(acl.principal_type.to_string())
+    .bind(&acl.principal_id)
+    .bind(acl.permission.to_string())
+    .bind(&acl.granted_by)
+    .bind(&acl.created_at)
+    .bind(&acl.expires_at)
+    .execute(conn)
+    .await
+    .map_err(map_sqlx)?;
+    Ok(())
+}
+
+async fn insert_memory_row(
+    conn: &mut sqlx::PgConnection,
+    record: &MemoryRecord,
+    compression_threshold: Option<usize>,
+) -> Result<()> {
+    let content = content_codec::encode(&record.content, compression_threshold);
+    let embedding_param: Option<Vector> =
+        record.embedding.as_ref().map(|v| Vector::from(v.clone()));
+
+    let tags_slice: &[String] = &record.tags;
+
+    sqlx::query(
+        r#"
+INSERT INTO memories (
+id, agent_id, content, memory_type, scope, importance,
+tags, metadata, embedding,
+content_hash, prev_hash, source_type, source_id,
+consolidation_state, access_count, org_id, thread_id,
+created_at, updated_at, last_accessed_at, expires_at,
+deleted_at, decay_rate, created_by, version, prev_version_id,
+quarantined, quarantine_reason, decay_function, pinned,
+valid_from, valid_until, curation, attestation, channel,
+content_zstd, content_len
+) VALUES (
+$1, $2, $3, $4, $5, $6,
+$7, $8, $9,
+$10, $11, $12, $13,
+$14, $15, $16, $17,
+$18, $19, $20, $21,
+$22, $23, $24, $25, $26,
+$27, $28, $29, $30,
+$31, $32, $33, $34, $35,
+$36, $37
+)
+"#,
+    )
+    .bind(record.id)
+    .bind(&record.agent_id)
+    .bind(content.text)
+    .bind(record.memory_type.to_string())
+    .bind(record.scope.to_string())
+    .bind(record.importance)
+    .bind(tags_slice)
+    .bind(&record.metadata)
+    .bind(&embedding_param)
+    .bind(&record.content_hash)
+    .bind(&record.prev_hash)
+    .bind(record.source_type.to_string())
+    .bind(&record.source_id)
+    .bind(record.consolidation_state.to_string())
+    .bind(record.access_count as i64)
+    .bind(&record.org_id)
+    .bind(&record.thread_id)
+    .bind(&record.created_at)
+    .bind(&record.updated_at)
+    .bind(&record.last_accessed_at)
+    .bind(&record.expires_at)
+    .bind(&record.deleted_at)
+    .bind(record.decay_rate)
+    .bind(&record.created_by)
+    .bind(record.version as i32)
+    .bind(record.prev_version_id)
+    .bind(record.quarantined)
+    .bind(&record.quarantine_reason)
+    .bind(&record.decay_function)
+    .bind(record.pinned)
+    .bind(&record.valid_from)
+    .bind(&record.valid_until)
+    .bind(record.curation.map(|c| c.to_string()))
+    .bind(record.attestation.map(|a| a.to_string()))
+    .bind(&record.channel)
+    .bind(&content.zstd)
+    .bind(content.len)
+    .execute(conn)
+    .await
+    .map_err(map_sqlx)?;
+    Ok(())
+}
+
+async fn insert_relation_row(conn: &mut sqlx::PgConnection, relation: &Relation) -> Result<()> {
+    sqlx::query(
+        r#"
+INSERT INTO relations (id, source_id, target_id, relation_type, weight, metadata, created_at)
+VALUES ($1, $2, $3, $4, $5, $6, $7)
+"#,
+    )
+    .bind(relation.id)
+    .bind(relation.source_id)
+    .bind(relation.target_id)
+    .bind(&relation.relation_type)
+    .bind(relation.weight)
+    .bind(&relation.metadata)
+    .bind(&relation.created_at)
+    .execute(conn)
+    .await
+    .map_err(map_sqlx)?;
+    Ok(())
+}
+
+async fn insert_event_row(conn: &mut sqlx::PgConnection, event: &AgentEvent) -> Result<()> {
+    let payload_json = &event.payload;
+    let embedding_blob = serialize_embedding(&event.embedding);
+
+    sqlx::query(
+        r#"
+INSERT INTO agent_events (
+id, agent_id, thread_id, run_id, parent_event_id, event_type,
+payload, trace_id, span_id, model, tokens_input, tokens_output,
+latency_ms, cost_usd, "timestamp", logical_clock, content_hash,
+prev_hash, embedding, request_id
+) VALUES (
+$1, $2, $3, $4, $5, $6,
+$7, $8, $9, $10, $11, $12,
+$13, $14, $15, $16, $17,
+$18, $19, $20
+)
+"#,
+    )
+    .bind(event.id)
+    .bind(&event.agent_id)
+    .bind(&event.thread_id)
+    .bind(&event.run_id)
+    .bind(event.parent_event_id)
+    .bind(event.event_type.to_string())
+    .bind(payload_json)
+    .bind(&event.trace_id)
+    .bind(&event.span_id)
+    .bind(&event.model)
+    .bind(event.tokens_input)
+    .bind(event.tokens_output)
+    .bind(event.latency_ms)
+    .bind(event.cost_usd)
+    .bind(&event.timestamp)
+    .bind(event.logical_clock)
+    .bind(&event.content_hash)
+    .bind(&event.prev_hash)
+    .bind(&embedding_blob)
+    .bind(&event.request_id)
+    .execute(conn)
+    .await
+    .map_err(map_sqlx)?;
+    Ok(())
+}
+
+async fn insert_outbox_row(conn: &mut sqlx::PgConnection, message: &OutboxMessage) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO outbox (id, topic, payload, created_at, attempts, last_error, delivered_at) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+    )
+    .bind(message.id)
+    .bind(&message.topic)
+    .bind(&message.payload)
+    .bind(&message.created_at)
+    .bind(message.attempts as i32)
+    .bind(&message.last_error)
+    .bind(&message.delivered_at)
+    .execute(conn)
+    .await
+    .map_err(map_sqlx)?;
+    Ok(())
+}
+
 #[async_trait::async_trait]
 impl StorageBackend for PgStorage {
     fn backend_name(&self) -> &'static str {
         "postgres"
     }
 
+    async fn ping(&self) -> Result<()> {
+        sqlx::query("SELECT 1")
+            .execute(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
+        Ok(())
+    }
+
     // -----------------------------------------------------------------------
     // Memory CRUD
     // -----------------------------------------------------------------------
 
     async fn insert_memory(&self, record: &MemoryRecord) -> Result<()> {
-        let embedding_param: Option<Vector> =
-            record.embedding.as_ref().map(|v| Vector::from(v.clone()));
-
-        let tags_slice: &[String] = &record.tags;
-
-        sqlx::query(
-            r#"
-INSERT INTO memories (
-    id, agent_id, content, memory_type, scope, importance,
-    tags, metadata, embedding,
-    content_hash, prev_hash, source_type, source_id,
-    consolidation_state, access_count, org_id, thread_id,
-    created_at, updated_at, last_accessed_at, expires_at,
-    deleted_at, decay_rate, created_by, version, prev_version_id,
-    quarantined, quarantine_reason, decay_function
-) VALUES (
-    $1, $2, $3, $4, $5, $6,
-    $7, $8, $9,
-    $10, $11, $12, $13,
-    $14, $15, $16, $17,
-    $18, $19, $20, $21,
-    $22, $23, $24, $25, $26,
-    $27, $28, $29
-)
-"#,
-        )
-        .bind(record.id)
-        .bind(&record.agent_id)
-        .bind(&record.content)
-        .bind(record.memory_type.to_string())
-        .bind(record.scope.to_string())
-        .bind(record.importance)
-        .bind(tags_slice)
-        .bind(&record.metadata)
-        .bind(&embedding_param)
-        .bind(&record.content_hash)
-        .bind(&record.prev_hash)
-        .bind(record.source_type.to_string())
-        .bind(&record.source_id)
-        .bind(record.consolidation_state.to_string())
-        .bind(record.access_count as i64)
-        .bind(&record.org_id)
-        .bind(&record.thread_id)
-        .bind(&record.created_at)
-        .bind(&record.updated_at)
-        .bind(&record.last_accessed_at)
-        .bind(&record.expires_at)
-        .bind(&record.deleted_at)
-        .bind(record.decay_rate)
-        .bind(&record.created_by)
-        .bind(record.version as i32)
-        .bind(record.prev_version_id)
-        .bind(record.quarantined)
-        .bind(&record.quarantine_reason)
-        .bind(&record.decay_function)
-        .execute(&self.pool)
-        .await
-        .map_err(map_sqlx)?;
-
+        insert_memory_row(&mut *self.conn().await?, record, self.compression_threshold).await?;
+        self.notify_change(MemoryChange::Inserted(record.id)).await;
         Ok(())
     }
 
@@ -371,7 +1022,7 @@ INSERT INTO memories (
         let sql = format!("SELECT {MEMORY_COLUMNS} FROM memories WHERE id = $1");
         let row = sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
             .bind(id)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.conn().await?)
             .await
             .map_err(map_sqlx)?;
 
@@ -381,66 +1032,63 @@ INSERT INTO memories (
         }
     }
 
-    async fn update_memory(&self, record: &MemoryRecord) -> Result<()> {
-        let embedding_param: Option<Vector> =
-            record.embedding.as_ref().map(|v| Vector::from(v.clone()));
-
-        let tags_slice: &[String] = &record.tags;
+    async fn get_memories(
+        &self,
+        ids: &[Uuid],
+        columns: MemoryColumns,
+    ) -> Result<Vec<MemoryRecord>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let sql = format!(
+            "SELECT {} FROM memories WHERE id = ANY($1)",
+            projected_memory_columns(columns)
+        );
+        let rows = sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
+            .bind(ids)
+            .fetch_all(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
+        rows.iter()
+            .map(|r| row_to_memory(r).map_err(map_sqlx))
+            .collect()
+    }
 
-        let result = sqlx::query(
-            r#"
-UPDATE memories SET
-    agent_id = $1, content = $2, memory_type = $3, scope = $4,
-    importance = $5, tags = $6, metadata = $7,
-    embedding = $8,
-    content_hash = $9, prev_hash = $10, source_type = $11,
-    source_id = $12, consolidation_state = $13, access_count = $14,
-    org_id = $15, thread_id = $16, updated_at = $17,
-    last_accessed_at = $18, expires_at = $19, deleted_at = $20,
-    decay_rate = $21, created_by = $22, version = $23,
-    prev_version_id = $24, quarantined = $25, quarantine_reason = $26,
-    decay_function = $27
-WHERE id = $28
-"#,
+    async fn update_memory(&self, record: &MemoryRecord) -> Result<()> {
+        if update_memory_row(
+            &mut *self.conn().await?,
+            record,
+            None,
+            self.compression_threshold,
         )
-        .bind(&record.agent_id)
-        .bind(&record.content)
-        .bind(record.memory_type.to_string())
-        .bind(record.scope.to_string())
-        .bind(record.importance)
-        .bind(tags_slice)
-        .bind(&record.metadata)
-        .bind(&embedding_param)
-        .bind(&record.content_hash)
-        .bind(&record.prev_hash)
-        .bind(record.source_type.to_string())
-        .bind(&record.source_id)
-        .bind(record.consolidation_state.to_string())
-        .bind(record.access_count as i64)
-        .bind(&record.org_id)
-        .bind(&record.thread_id)
-        .bind(&record.updated_at)
-        .bind(&record.last_accessed_at)
-        .bind(&record.expires_at)
-        .bind(&record.deleted_at)
-        .bind(record.decay_rate)
-        .bind(&record.created_by)
-        .bind(record.version as i32)
-        .bind(record.prev_version_id)
-        .bind(record.quarantined)
-        .bind(&record.quarantine_reason)
-        .bind(&record.decay_function)
-        .bind(record.id)
-        .execute(&self.pool)
-        .await
-        .map_err(map_sqlx)?;
-
-        if result.rows_affected() == 0 {
+        .await?
+            == 0
+        {
             return Err(Error::NotFound(format!("memory {} not found", record.id)));
         }
+        self.notify_change(MemoryChange::Updated(record.id)).await;
         Ok(())
     }
 
+    async fn update_memory_if_version(
+        &self,
+        record: &MemoryRecord,
+        expected_version: u32,
+    ) -> Result<bool> {
+        let written = update_memory_row(
+            &mut *self.conn().await?,
+            record,
+            Some(expected_version),
+            self.compression_threshold,
+        )
+        .await?;
+        if written == 0 {
+            return Ok(false);
+        }
+        self.notify_change(MemoryChange::Updated(record.id)).await;
+        Ok(true)
+    }
+
     async fn soft_delete_memory(&self, id: Uuid) -> Result<()> {
         let now = chrono::Utc::now().to_rfc3339();
         let result = sqlx::query(
@@ -449,7 +1097,7 @@ WHERE id = $28
         .bind(&now)
         .bind(&now)
         .bind(id)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
 
@@ -458,13 +1106,14 @@ WHERE id = $28
                 "memory {id} not found or already deleted"
             )));
         }
+        self.notify_change(MemoryChange::Deleted(id)).await;
         Ok(())
     }
 
     async fn hard_delete_memory(&self, id: Uuid) -> Result<()> {
         let result = sqlx::query("DELETE FROM memories WHERE id = $1")
             .bind(id)
-            .execute(&self.pool)
+            .execute(&mut *self.conn().await?)
             .await
             .map_err(map_sqlx)?;
 
@@ -475,10 +1124,11 @@ WHERE id = $28
         // Clean up ACLs for this memory
         sqlx::query("DELETE FROM acls WHERE memory_id = $1")
             .bind(id)
-            .execute(&self.pool)
+            .execute(&mut *self.conn().await?)
             .await
             .map_err(map_sqlx)?;
 
+        self.notify_change(MemoryChange::Deleted(id)).await;
         Ok(())
     }
 
@@ -510,6 +1160,7 @@ WHERE id = $28
         #[derive(Debug)]
         enum Param {
             Str(String),
+            StrArray(Vec<String>),
             F32(f32),
         }
         let mut params: Vec<Param> = Vec::new();
@@ -529,6 +1180,11 @@ WHERE id = $28
             conditions.push(format!("scope = ${param_idx}"));
             params.push(Param::Str(scope.to_string()));
         }
+        if let Some(ref tags) = filter.tags {
+            param_idx += 1;
+            conditions.push(format!("tags && ${param_idx}::TEXT[]"));
+            params.push(Param::StrArray(tags.clone()));
+        }
         if let Some(min_importance) = filter.min_importance {
             param_idx += 1;
             conditions.push(format!("importance >= ${param_idx}"));
@@ -559,11 +1215,15 @@ WHERE id = $28
         for p in &params {
             match p {
                 Param::Str(s) => query = query.bind(s),
+                Param::StrArray(v) => query = query.bind(v),
                 Param::F32(f) => query = query.bind(*f),
             }
         }
 
-        let rows = query.fetch_all(&self.pool).await.map_err(map_sqlx)?;
+        let rows = query
+            .fetch_all(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
         let mut results = Vec::with_capacity(rows.len());
         for r in &rows {
             results.push(row_to_memory(r).map_err(map_sqlx)?);
@@ -578,7 +1238,7 @@ WHERE id = $28
         )
         .bind(&now)
         .bind(id)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
         Ok(())
@@ -589,24 +1249,7 @@ WHERE id = $28
     // -----------------------------------------------------------------------
 
     async fn insert_acl(&self, acl: &Acl) -> Result<()> {
-        sqlx::query(
-            r#"
-INSERT INTO acls (id, memory_id, principal_type, principal_id, permission, granted_by, created_at, expires_at)
-VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
-"#,
-        )
-        .bind(acl.id)
-        .bind(acl.memory_id)
-        .bind(acl.principal_type.to_string())
-        .bind(&acl.principal_id)
-        .bind(acl.permission.to_string())
-        .bind(&acl.granted_by)
-        .bind(&acl.created_at)
-        .bind(&acl.expires_at)
-        .execute(&self.pool)
-        .await
-        .map_err(map_sqlx)?;
-        Ok(())
+        insert_acl_row(&mut *self.conn().await?, acl).await
     }
 
     async fn check_permission(
@@ -615,20 +1258,39 @@ VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
         principal_id: &str,
         required: Permission,
     ) -> Result<bool> {
+        // Runs in the system context: under row-level security a memory the
+        // principal cannot see would otherwise read as missing, not denied.
         // Check if the principal is the owner
-        let owner_row = sqlx::query("SELECT agent_id FROM memories WHERE id = $1")
+        let owner_row = sqlx::query("SELECT agent_id, channel FROM memories WHERE id = $1")
             .bind(memory_id)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.conn_as(None).await?)
             .await
             .map_err(map_sqlx)?;
 
-        match owner_row {
+        let channel: Option<String> = match owner_row {
             None => return Err(Error::NotFound(format!("memory {memory_id} not found"))),
             Some(row) => {
                 let owner: String = row.get("agent_id");
                 if owner == principal_id {
                     return Ok(true);
                 }
+                row.get("channel")
+            }
+        };
+
+        // Channel members can read the channel's memories
+        if let Some(channel) = channel
+            && Permission::Read.satisfies(required)
+        {
+            let member =
+                sqlx::query("SELECT 1 FROM channel_members WHERE channel = $1 AND agent_id = $2")
+                    .bind(&channel)
+                    .bind(principal_id)
+                    .fetch_optional(&mut *self.conn_as(None).await?)
+                    .await
+                    .map_err(map_sqlx)?;
+            if member.is_some() {
+                return Ok(true);
             }
         }
 
@@ -640,7 +1302,7 @@ VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
         .bind(memory_id)
         .bind(principal_id)
         .bind(&now)
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *self.conn_as(None).await?)
         .await
         .map_err(map_sqlx)?;
 
@@ -659,7 +1321,7 @@ VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
         )
         .bind(memory_id)
         .bind(&now)
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *self.conn_as(None).await?)
         .await
         .map_err(map_sqlx)?;
 
@@ -688,23 +1350,7 @@ VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
     // -----------------------------------------------------------------------
 
     async fn insert_relation(&self, relation: &Relation) -> Result<()> {
-        sqlx::query(
-            r#"
-INSERT INTO relations (id, source_id, target_id, relation_type, weight, metadata, created_at)
-VALUES ($1, $2, $3, $4, $5, $6, $7)
-"#,
-        )
-        .bind(relation.id)
-        .bind(relation.source_id)
-        .bind(relation.target_id)
-        .bind(&relation.relation_type)
-        .bind(relation.weight)
-        .bind(&relation.metadata)
-        .bind(&relation.created_at)
-        .execute(&self.pool)
-        .await
-        .map_err(map_sqlx)?;
-        Ok(())
+        insert_relation_row(&mut *self.conn().await?, relation).await
     }
 
     async fn get_relations_from(&self, source_id: Uuid) -> Result<Vec<Relation>> {
@@ -712,7 +1358,7 @@ VALUES ($1, $2, $3, $4, $5, $6, $7)
             "SELECT id, source_id, target_id, relation_type, weight, metadata, created_at FROM relations WHERE source_id = $1",
         )
         .bind(source_id)
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
 
@@ -728,7 +1374,7 @@ VALUES ($1, $2, $3, $4, $5, $6, $7)
             "SELECT id, source_id, target_id, relation_type, weight, metadata, created_at FROM relations WHERE target_id = $1",
         )
         .bind(target_id)
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
 
@@ -742,7 +1388,7 @@ VALUES ($1, $2, $3, $4, $5, $6, $7)
     async fn delete_relation(&self, id: Uuid) -> Result<()> {
         let result = sqlx::query("DELETE FROM relations WHERE id = $1")
             .bind(id)
-            .execute(&self.pool)
+            .execute(&mut *self.conn().await?)
             .await
             .map_err(map_sqlx)?;
 
@@ -767,7 +1413,7 @@ VALUES ($1, $2, $3, $4, $5, $6, $7)
             )
             .bind(agent_id)
             .bind(tid)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.conn().await?)
             .await
             .map_err(map_sqlx)?
         } else {
@@ -775,7 +1421,7 @@ VALUES ($1, $2, $3, $4, $5, $6, $7)
                 "SELECT content_hash FROM memories WHERE agent_id = $1 AND thread_id IS NULL AND deleted_at IS NULL ORDER BY created_at DESC LIMIT 1",
             )
             .bind(agent_id)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.conn().await?)
             .await
             .map_err(map_sqlx)?
         };
@@ -790,31 +1436,42 @@ VALUES ($1, $2, $3, $4, $5, $6, $7)
     ) -> Result<Option<Vec<u8>>> {
         let row = if let Some(tid) = thread_id {
             sqlx::query(
-                "SELECT content_hash FROM agent_events WHERE agent_id = $1 AND thread_id = $2 ORDER BY timestamp DESC LIMIT 1",
+                "SELECT content_hash FROM agent_events WHERE agent_id = $1 AND thread_id = $2 ORDER BY logical_clock DESC, timestamp DESC LIMIT 1",
             )
             .bind(agent_id)
             .bind(tid)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.conn().await?)
             .await
             .map_err(map_sqlx)?
         } else {
             sqlx::query(
-                "SELECT content_hash FROM agent_events WHERE agent_id = $1 ORDER BY timestamp DESC LIMIT 1",
+                "SELECT content_hash FROM agent_events WHERE agent_id = $1 ORDER BY logical_clock DESC, timestamp DESC LIMIT 1",
             )
             .bind(agent_id)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.conn().await?)
             .await
             .map_err(map_sqlx)?
         };
         Ok(row.map(|r| r.get::<Vec<u8>, _>("content_hash")))
     }
 
-    async fn get_sync_watermark(&self, key: &str) -> Result<Option<String>> {
-        let row = sqlx::query("SELECT value FROM sync_metadata WHERE key = $1")
-            .bind(key)
-            .fetch_optional(&self.pool)
-            .await
-            .map_err(map_sqlx)?;
+    async fn max_logical_clock(&self, agent_id: &str) -> Result<i64> {
+        let row = sqlx::query(
+            "SELECT COALESCE(MAX(logical_clock), 0) AS clock FROM agent_events WHERE agent_id = $1",
+        )
+        .bind(agent_id)
+        .fetch_one(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(row.get::<i64, _>("clock"))
+    }
+
+    async fn get_sync_watermark(&self, key: &str) -> Result<Option<String>> {
+        let row = sqlx::query("SELECT value FROM sync_metadata WHERE key = $1")
+            .bind(key)
+            .fetch_optional(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
         Ok(row.map(|r| r.get::<String, _>("value")))
     }
 
@@ -826,43 +1483,95 @@ VALUES ($1, $2, $3, $4, $5, $6, $7)
         .bind(key)
         .bind(value)
         .bind(now)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(())
+    }
+
+    async fn revoke_share_link(&self, revoked: &RevokedShareLink) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO revoked_share_links (link_id, memory_id, revoked_by, revoked_at, expires_at) VALUES ($1, $2, $3, $4, $5) ON CONFLICT (link_id, memory_id) DO NOTHING",
+        )
+        .bind(revoked.link_id)
+        .bind(revoked.memory_id)
+        .bind(&revoked.revoked_by)
+        .bind(&revoked.revoked_at)
+        .bind(&revoked.expires_at)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
         Ok(())
     }
 
+    async fn is_share_link_revoked(&self, link_id: Uuid, memory_id: Uuid) -> Result<bool> {
+        let revoked: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM revoked_share_links WHERE link_id = $1 AND memory_id = $2)",
+        )
+        .bind(link_id)
+        .bind(memory_id)
+        .fetch_one(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(revoked)
+    }
+
+    async fn purge_revoked_share_links(&self, now: &str) -> Result<usize> {
+        let result = sqlx::query("DELETE FROM revoked_share_links WHERE expires_at < $1")
+            .bind(now)
+            .execute(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
+        Ok(result.rows_affected() as usize)
+    }
+
+    async fn record_pseudonym(&self, entry: &PseudonymEntry) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO pseudonyms (org_id, alias, sealed_agent_id, created_at) VALUES ($1, $2, $3, $4) ON CONFLICT (org_id, alias) DO NOTHING",
+        )
+        .bind(&entry.org_id)
+        .bind(&entry.alias)
+        .bind(&entry.sealed_agent_id)
+        .bind(&entry.created_at)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(())
+    }
+
+    async fn get_pseudonym(&self, org_id: &str, alias: &str) -> Result<Option<PseudonymEntry>> {
+        let row = sqlx::query(
+            "SELECT org_id, alias, sealed_agent_id, created_at FROM pseudonyms WHERE org_id = $1 AND alias = $2",
+        )
+        .bind(org_id)
+        .bind(alias)
+        .fetch_optional(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(row.map(|r| PseudonymEntry {
+            org_id: r.get("org_id"),
+            alias: r.get("alias"),
+            sealed_agent_id: r.get("sealed_agent_id"),
+            created_at: r.get("created_at"),
+        }))
+    }
+
     // -----------------------------------------------------------------------
     // Permission-safe ANN
     // -----------------------------------------------------------------------
 
     async fn list_accessible_memory_ids(&self, agent_id: &str, limit: usize) -> Result<Vec<Uuid>> {
-        let now = chrono::Utc::now().to_rfc3339();
-        let rows = sqlx::query(
-            r#"
-SELECT id FROM memories
-WHERE (
-    agent_id = $1
-    OR scope = 'public'
-    OR id IN (
-        SELECT memory_id FROM acls
-        WHERE principal_id = $2 AND (expires_at IS NULL OR expires_at > $3)
-    )
-)
-AND deleted_at IS NULL
-LIMIT $4
-"#,
-        )
-        .bind(agent_id)
-        .bind(agent_id)
-        .bind(&now)
-        .bind(limit as i64)
-        .fetch_all(&self.pool)
-        .await
-        .map_err(map_sqlx)?;
+        self.accessible_memory_ids(agent_id, None, limit).await
+    }
 
-        let ids: Vec<Uuid> = rows.iter().map(|r| r.get("id")).collect();
-        Ok(ids)
+    async fn list_accessible_memory_ids_as_of(
+        &self,
+        agent_id: &str,
+        as_of: &str,
+        limit: usize,
+    ) -> Result<Vec<Uuid>> {
+        self.accessible_memory_ids(agent_id, Some(as_of), limit)
+            .await
     }
 
     // -----------------------------------------------------------------------
@@ -870,47 +1579,7 @@ LIMIT $4
     // -----------------------------------------------------------------------
 
     async fn insert_event(&self, event: &AgentEvent) -> Result<()> {
-        let payload_json = &event.payload;
-        let embedding_blob = serialize_embedding(&event.embedding);
-
-        sqlx::query(
-            r#"
-INSERT INTO agent_events (
-    id, agent_id, thread_id, run_id, parent_event_id, event_type,
-    payload, trace_id, span_id, model, tokens_input, tokens_output,
-    latency_ms, cost_usd, "timestamp", logical_clock, content_hash,
-    prev_hash, embedding
-) VALUES (
-    $1, $2, $3, $4, $5, $6,
-    $7, $8, $9, $10, $11, $12,
-    $13, $14, $15, $16, $17,
-    $18, $19
-)
-"#,
-        )
-        .bind(event.id)
-        .bind(&event.agent_id)
-        .bind(&event.thread_id)
-        .bind(&event.run_id)
-        .bind(event.parent_event_id)
-        .bind(event.event_type.to_string())
-        .bind(payload_json)
-        .bind(&event.trace_id)
-        .bind(&event.span_id)
-        .bind(&event.model)
-        .bind(event.tokens_input)
-        .bind(event.tokens_output)
-        .bind(event.latency_ms)
-        .bind(event.cost_usd)
-        .bind(&event.timestamp)
-        .bind(event.logical_clock)
-        .bind(&event.content_hash)
-        .bind(&event.prev_hash)
-        .bind(&embedding_blob)
-        .execute(&self.pool)
-        .await
-        .map_err(map_sqlx)?;
-        Ok(())
+        insert_event_row(&mut *self.conn().await?, event).await
     }
 
     async fn list_events(
@@ -924,7 +1593,7 @@ INSERT INTO agent_events (
 SELECT id, agent_id, thread_id, run_id, parent_event_id, event_type,
        payload, trace_id, span_id, model, tokens_input, tokens_output,
        latency_ms, cost_usd, "timestamp", logical_clock, content_hash,
-       prev_hash, embedding
+       prev_hash, embedding, request_id
 FROM agent_events
 WHERE agent_id = $1
 ORDER BY "timestamp" DESC
@@ -934,7 +1603,7 @@ LIMIT $2 OFFSET $3
         .bind(agent_id)
         .bind(limit as i64)
         .bind(offset as i64)
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
 
@@ -951,7 +1620,7 @@ LIMIT $2 OFFSET $3
 SELECT id, agent_id, thread_id, run_id, parent_event_id, event_type,
        payload, trace_id, span_id, model, tokens_input, tokens_output,
        latency_ms, cost_usd, "timestamp", logical_clock, content_hash,
-       prev_hash, embedding
+       prev_hash, embedding, request_id
 FROM agent_events
 WHERE thread_id = $1
 ORDER BY "timestamp" ASC
@@ -960,7 +1629,7 @@ LIMIT $2
         )
         .bind(thread_id)
         .bind(limit as i64)
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
 
@@ -977,13 +1646,13 @@ LIMIT $2
 SELECT id, agent_id, thread_id, run_id, parent_event_id, event_type,
        payload, trace_id, span_id, model, tokens_input, tokens_output,
        latency_ms, cost_usd, "timestamp", logical_clock, content_hash,
-       prev_hash, embedding
+       prev_hash, embedding, request_id
 FROM agent_events
 WHERE id = $1
 "#,
         )
         .bind(id)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
 
@@ -1003,7 +1672,7 @@ WHERE id = $1
 SELECT id, agent_id, thread_id, run_id, parent_event_id, event_type,
        payload, trace_id, span_id, model, tokens_input, tokens_output,
        latency_ms, cost_usd, "timestamp", logical_clock, content_hash,
-       prev_hash, embedding
+       prev_hash, embedding, request_id
 FROM agent_events
 WHERE parent_event_id = $1
 ORDER BY "timestamp" ASC
@@ -1012,7 +1681,7 @@ LIMIT $2
         )
         .bind(parent_event_id)
         .bind(limit as i64)
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
 
@@ -1023,6 +1692,107 @@ LIMIT $2
         Ok(results)
     }
 
+    // -----------------------------------------------------------------------
+    // Event retention
+    // -----------------------------------------------------------------------
+
+    async fn list_events_before(&self, cutoff: &str, limit: usize) -> Result<Vec<AgentEvent>> {
+        let rows = sqlx::query(
+            r#"
+SELECT id, agent_id, thread_id, run_id, parent_event_id, event_type,
+       payload, trace_id, span_id, model, tokens_input, tokens_output,
+       latency_ms, cost_usd, "timestamp", logical_clock, content_hash,
+       prev_hash, embedding, request_id
+FROM agent_events
+WHERE "timestamp" < $1
+ORDER BY agent_id, logical_clock, "timestamp", id
+LIMIT $2
+"#,
+        )
+        .bind(cutoff)
+        .bind(limit as i64)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+
+        let mut results = Vec::with_capacity(rows.len());
+        for r in &rows {
+            results.push(row_to_event(r).map_err(map_sqlx)?);
+        }
+        Ok(results)
+    }
+
+    async fn archive_event_segment(
+        &self,
+        segment: &EventArchiveSegment,
+        event_ids: &[Uuid],
+    ) -> Result<()> {
+        let mut conn = self.conn().await?;
+        let mut tx = sqlx::Connection::begin(&mut *conn)
+            .await
+            .map_err(map_sqlx)?;
+        // Lets the append-only trigger pass these deletes, for this
+        // transaction only.
+        sqlx::query("SELECT set_config('mnemo.event_archival', 'on', true)")
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlx)?;
+        let sql = format!(
+            "INSERT INTO event_archives ({EVENT_ARCHIVE_COLUMNS}) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)"
+        );
+        sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
+            .bind(segment.id)
+            .bind(&segment.agent_id)
+            .bind(segment.first_event_id)
+            .bind(segment.last_event_id)
+            .bind(&segment.from_timestamp)
+            .bind(&segment.to_timestamp)
+            .bind(segment.first_logical_clock)
+            .bind(segment.last_logical_clock)
+            .bind(segment.event_count as i64)
+            .bind(&segment.merkle_root)
+            .bind(&segment.last_content_hash)
+            .bind(&segment.object_key)
+            .bind(&segment.archived_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlx)?;
+        sqlx::query("DELETE FROM agent_events WHERE id = ANY($1)")
+            .bind(event_ids)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlx)?;
+        tx.commit().await.map_err(map_sqlx)?;
+        Ok(())
+    }
+
+    async fn get_event_archive(&self, id: Uuid) -> Result<Option<EventArchiveSegment>> {
+        let sql = format!("SELECT {EVENT_ARCHIVE_COLUMNS} FROM event_archives WHERE id = $1");
+        let row = sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
+            .bind(id)
+            .fetch_optional(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
+        Ok(row.as_ref().map(row_to_event_archive))
+    }
+
+    async fn list_event_archives(
+        &self,
+        agent_id: Option<&str>,
+    ) -> Result<Vec<EventArchiveSegment>> {
+        let sql = format!(
+            "SELECT {EVENT_ARCHIVE_COLUMNS} FROM event_archives \
+             WHERE $1::VARCHAR IS NULL OR agent_id = $1 \
+             ORDER BY from_timestamp, id"
+        );
+        let rows = sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
+            .bind(agent_id)
+            .fetch_all(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
+        Ok(rows.iter().map(row_to_event_archive).collect())
+    }
+
     // -----------------------------------------------------------------------
     // Ordered listing
     // -----------------------------------------------------------------------
@@ -1041,7 +1811,7 @@ LIMIT $2
                 .bind(agent_id)
                 .bind(tid)
                 .bind(limit as i64)
-                .fetch_all(&self.pool)
+                .fetch_all(&mut *self.conn().await?)
                 .await
                 .map_err(map_sqlx)?
         } else {
@@ -1051,7 +1821,7 @@ LIMIT $2
             sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
                 .bind(agent_id)
                 .bind(limit as i64)
-                .fetch_all(&self.pool)
+                .fetch_all(&mut *self.conn().await?)
                 .await
                 .map_err(map_sqlx)?
         };
@@ -1063,6 +1833,85 @@ LIMIT $2
         Ok(results)
     }
 
+    async fn list_memories_chain_page(
+        &self,
+        agent_id: &str,
+        thread_id: Option<&str>,
+        after: Option<(&str, Uuid)>,
+        limit: usize,
+    ) -> Result<Vec<MemoryRecord>> {
+        let sql = format!(
+            "SELECT {MEMORY_COLUMNS} FROM memories WHERE agent_id = $1 AND deleted_at IS NULL AND ($2::VARCHAR IS NULL OR thread_id = $2) AND ($3::VARCHAR IS NULL OR (created_at, id) > ($3, $4)) ORDER BY created_at ASC, id ASC LIMIT $5"
+        );
+        let rows = sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
+            .bind(agent_id)
+            .bind(thread_id)
+            .bind(after.map(|(created_at, _)| created_at))
+            .bind(after.map(|(_, id)| id))
+            .bind(limit as i64)
+            .fetch_all(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
+        let mut results = Vec::with_capacity(rows.len());
+        for r in &rows {
+            results.push(row_to_memory(r).map_err(map_sqlx)?);
+        }
+        Ok(results)
+    }
+
+    async fn get_chain_checkpoint(
+        &self,
+        agent_id: &str,
+        thread_id: Option<&str>,
+    ) -> Result<Option<ChainCheckpoint>> {
+        let row = sqlx::query(
+            "SELECT agent_id, thread_id, verified_count, last_record_id, last_created_at, last_content_hash, rolling_root, verified_at FROM chain_checkpoints WHERE agent_id = $1 AND thread_id = $2",
+        )
+        .bind(agent_id)
+        .bind(thread_id.unwrap_or_default())
+        .fetch_optional(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(row.as_ref().map(row_to_chain_checkpoint))
+    }
+
+    async fn put_chain_checkpoint(&self, checkpoint: &ChainCheckpoint) -> Result<()> {
+        sqlx::query(
+            r#"
+INSERT INTO chain_checkpoints (agent_id, thread_id, verified_count, last_record_id, last_created_at, last_content_hash, rolling_root, verified_at)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+ON CONFLICT (agent_id, thread_id) DO UPDATE SET
+    verified_count = EXCLUDED.verified_count,
+    last_record_id = EXCLUDED.last_record_id,
+    last_created_at = EXCLUDED.last_created_at,
+    last_content_hash = EXCLUDED.last_content_hash,
+    rolling_root = EXCLUDED.rolling_root,
+    verified_at = EXCLUDED.verified_at
+"#,
+        )
+        .bind(&checkpoint.agent_id)
+        .bind(checkpoint.thread_id.as_deref().unwrap_or_default())
+        .bind(checkpoint.verified_count as i64)
+        .bind(checkpoint.last_record_id)
+        .bind(&checkpoint.last_created_at)
+        .bind(&checkpoint.last_content_hash)
+        .bind(&checkpoint.rolling_root)
+        .bind(&checkpoint.verified_at)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(())
+    }
+
+    async fn delete_chain_checkpoints(&self, agent_id: &str) -> Result<()> {
+        sqlx::query("DELETE FROM chain_checkpoints WHERE agent_id = $1")
+            .bind(agent_id)
+            .execute(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
+        Ok(())
+    }
+
     // -----------------------------------------------------------------------
     // Sync support
     // -----------------------------------------------------------------------
@@ -1078,7 +1927,7 @@ LIMIT $2
         let rows = sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
             .bind(updated_after)
             .bind(limit as i64)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.conn().await?)
             .await
             .map_err(map_sqlx)?;
 
@@ -1103,16 +1952,19 @@ LIMIT $2
 
     async fn cleanup_expired(&self) -> Result<usize> {
         let now = chrono::Utc::now().to_rfc3339();
-        let result = sqlx::query(
-            "UPDATE memories SET deleted_at = $1 WHERE expires_at IS NOT NULL AND expires_at < $2 AND deleted_at IS NULL",
+        let expired: Vec<Uuid> = sqlx::query_scalar(
+            "UPDATE memories SET deleted_at = $1 WHERE expires_at IS NOT NULL AND expires_at < $2 AND deleted_at IS NULL RETURNING id",
         )
         .bind(&now)
         .bind(&now)
-        .execute(&self.pool)
+        .fetch_all(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
 
-        Ok(result.rows_affected() as usize)
+        for id in &expired {
+            self.notify_change(MemoryChange::Deleted(*id)).await;
+        }
+        Ok(expired.len())
     }
 
     // -----------------------------------------------------------------------
@@ -1150,7 +2002,7 @@ INSERT INTO delegations (
         .bind(&d.created_at)
         .bind(&d.expires_at)
         .bind(&d.revoked_at)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
         Ok(())
@@ -1169,7 +2021,7 @@ WHERE delegate_id = $1 AND revoked_at IS NULL AND (expires_at IS NULL OR expires
         )
         .bind(delegate_id)
         .bind(&now)
-        .fetch_all(&self.pool)
+        .fetch_all(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
 
@@ -1187,7 +2039,7 @@ WHERE delegate_id = $1 AND revoked_at IS NULL AND (expires_at IS NULL OR expires
         )
         .bind(&now)
         .bind(id)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
 
@@ -1239,10 +2091,14 @@ WHERE delegate_id = $1 AND revoked_at IS NULL AND (expires_at IS NULL OR expires
     // -----------------------------------------------------------------------
 
     async fn insert_or_update_agent_profile(&self, profile: &AgentProfile) -> Result<()> {
+        let retrieval_json =
+            serde_json::to_value(&profile.retrieval).map_err(|e| Error::Storage(e.to_string()))?;
+        // Retrieval defaults are written on insert only; edits go through
+        // `set_agent_retrieval_defaults`.
         sqlx::query(
             r#"
-INSERT INTO agent_profiles (agent_id, avg_importance, avg_content_length, total_memories, last_updated)
-VALUES ($1, $2, $3, $4, $5)
+INSERT INTO agent_profiles (agent_id, avg_importance, avg_content_length, total_memories, last_updated, retrieval_settings)
+VALUES ($1, $2, $3, $4, $5, $6)
 ON CONFLICT (agent_id) DO UPDATE SET
     avg_importance = EXCLUDED.avg_importance,
     avg_content_length = EXCLUDED.avg_content_length,
@@ -1255,7 +2111,8 @@ ON CONFLICT (agent_id) DO UPDATE SET
         .bind(profile.avg_content_length)
         .bind(profile.total_memories as i64)
         .bind(&profile.last_updated)
-        .execute(&self.pool)
+        .bind(&retrieval_json)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
         Ok(())
@@ -1263,22 +2120,54 @@ ON CONFLICT (agent_id) DO UPDATE SET
 
     async fn get_agent_profile(&self, agent_id: &str) -> Result<Option<AgentProfile>> {
         let row = sqlx::query(
-            "SELECT agent_id, avg_importance, avg_content_length, total_memories, last_updated FROM agent_profiles WHERE agent_id = $1",
+            "SELECT agent_id, avg_importance, avg_content_length, total_memories, last_updated, retrieval_settings FROM agent_profiles WHERE agent_id = $1",
         )
         .bind(agent_id)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
 
-        Ok(row.map(|r| AgentProfile {
-            agent_id: r.get("agent_id"),
-            avg_importance: r.get("avg_importance"),
-            avg_content_length: r.get("avg_content_length"),
-            total_memories: r.get::<i64, _>("total_memories") as u64,
-            last_updated: r.get("last_updated"),
+        Ok(row.map(|r| {
+            let retrieval: Option<serde_json::Value> = r.get("retrieval_settings");
+            AgentProfile {
+                agent_id: r.get("agent_id"),
+                avg_importance: r.get("avg_importance"),
+                avg_content_length: r.get("avg_content_length"),
+                total_memories: r.get::<i64, _>("total_memories") as u64,
+                last_updated: r.get("last_updated"),
+                retrieval: retrieval
+                    .and_then(|v| serde_json::from_value(v).ok())
+                    .unwrap_or_default(),
+            }
         }))
     }
 
+    async fn set_agent_retrieval_defaults(
+        &self,
+        agent_id: &str,
+        defaults: &RetrievalDefaults,
+        updated_at: &str,
+    ) -> Result<()> {
+        let settings_json =
+            serde_json::to_value(defaults).map_err(|e| Error::Storage(e.to_string()))?;
+        sqlx::query(
+            r#"
+INSERT INTO agent_profiles (agent_id, last_updated, retrieval_settings)
+VALUES ($1, $2, $3)
+ON CONFLICT (agent_id) DO UPDATE SET
+    retrieval_settings = EXCLUDED.retrieval_settings,
+    last_updated = EXCLUDED.last_updated
+"#,
+        )
+        .bind(agent_id)
+        .bind(updated_at)
+        .bind(&settings_json)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(())
+    }
+
     // -----------------------------------------------------------------------
     // Embedding baselines (v0.3.3)
     // -----------------------------------------------------------------------
@@ -1307,7 +2196,7 @@ ON CONFLICT (agent_id) DO UPDATE SET
         .bind(&cov_json)
         .bind(baseline.n as i64)
         .bind(&baseline.updated_at)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
         Ok(())
@@ -1318,7 +2207,7 @@ ON CONFLICT (agent_id) DO UPDATE SET
             "SELECT agent_id, mu, cov_diag, n, updated_at FROM embedding_baseline WHERE agent_id = $1",
         )
         .bind(agent_id)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
 
@@ -1342,10 +2231,666 @@ ON CONFLICT (agent_id) DO UPDATE SET
         }
     }
 
+    // -----------------------------------------------------------------------
+    // Conflict review queue
+    // -----------------------------------------------------------------------
+
+    async fn insert_conflict(&self, conflict: &ConflictRecord) -> Result<()> {
+        sqlx::query(
+            r#"
+INSERT INTO conflicts (
+    id, agent_id, memory_a, memory_b, similarity, reason, status,
+    resolution, winner_id, resolved_by, created_at, resolved_at
+) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+"#,
+        )
+        .bind(conflict.id)
+        .bind(&conflict.agent_id)
+        .bind(conflict.memory_a)
+        .bind(conflict.memory_b)
+        .bind(conflict.similarity)
+        .bind(&conflict.reason)
+        .bind(conflict.status.to_string())
+        .bind(&conflict.resolution)
+        .bind(conflict.winner_id)
+        .bind(&conflict.resolved_by)
+        .bind(&conflict.created_at)
+        .bind(&conflict.resolved_at)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(())
+    }
+
+    async fn get_conflict(&self, id: Uuid) -> Result<Option<ConflictRecord>> {
+        let sql = format!("SELECT {CONFLICT_COLUMNS} FROM conflicts WHERE id = $1");
+        let row = sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
+            .bind(id)
+            .fetch_optional(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
+        row.map(|r| row_to_conflict(&r)).transpose()
+    }
+
+    async fn list_conflicts(
+        &self,
+        agent_id: Option<&str>,
+        status: Option<ConflictStatus>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<ConflictRecord>> {
+        let sql = format!(
+            "SELECT {CONFLICT_COLUMNS} FROM conflicts \
+             WHERE ($1::VARCHAR IS NULL OR agent_id = $1) \
+             AND ($2::VARCHAR IS NULL OR status = $2) \
+             ORDER BY created_at DESC LIMIT $3 OFFSET $4"
+        );
+        let rows = sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
+            .bind(agent_id)
+            .bind(status.map(|s| s.to_string()))
+            .bind(limit as i64)
+            .bind(offset as i64)
+            .fetch_all(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
+        rows.iter().map(row_to_conflict).collect()
+    }
+
+    async fn update_conflict(&self, conflict: &ConflictRecord) -> Result<()> {
+        let result = sqlx::query(
+            "UPDATE conflicts SET status = $1, resolution = $2, winner_id = $3, resolved_by = $4, resolved_at = $5 WHERE id = $6",
+        )
+        .bind(conflict.status.to_string())
+        .bind(&conflict.resolution)
+        .bind(conflict.winner_id)
+        .bind(&conflict.resolved_by)
+        .bind(&conflict.resolved_at)
+        .bind(conflict.id)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        if result.rows_affected() == 0 {
+            return Err(Error::NotFound(format!(
+                "conflict {} not found",
+                conflict.id
+            )));
+        }
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Memory access requests
+    // -----------------------------------------------------------------------
+
+    async fn insert_access_request(&self, request: &AccessRequest) -> Result<()> {
+        sqlx::query(
+            r#"
+INSERT INTO access_requests (
+    id, requester_id, owner_id, memory_ids, tags, justification, permission,
+    expires_in_hours, status, decided_by, decision_note, acl_ids, created_at, decided_at
+) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+"#,
+        )
+        .bind(request.id)
+        .bind(&request.requester_id)
+        .bind(&request.owner_id)
+        .bind(serde_json::json!(request.memory_ids))
+        .bind(serde_json::json!(request.tags))
+        .bind(&request.justification)
+        .bind(request.permission.to_string())
+        .bind(request.expires_in_hours)
+        .bind(request.status.to_string())
+        .bind(&request.decided_by)
+        .bind(&request.decision_note)
+        .bind(serde_json::json!(request.acl_ids))
+        .bind(&request.created_at)
+        .bind(&request.decided_at)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(())
+    }
+
+    async fn get_access_request(&self, id: Uuid) -> Result<Option<AccessRequest>> {
+        let sql = format!("SELECT {ACCESS_REQUEST_COLUMNS} FROM access_requests WHERE id = $1");
+        let row = sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
+            .bind(id)
+            .fetch_optional(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
+        row.map(|r| row_to_access_request(&r)).transpose()
+    }
+
+    async fn list_access_requests(
+        &self,
+        owner_id: Option<&str>,
+        requester_id: Option<&str>,
+        status: Option<AccessRequestStatus>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<AccessRequest>> {
+        let sql = format!(
+            "SELECT {ACCESS_REQUEST_COLUMNS} FROM access_requests \
+             WHERE ($1::VARCHAR IS NULL OR owner_id = $1) \
+             AND ($2::VARCHAR IS NULL OR requester_id = $2) \
+             AND ($3::VARCHAR IS NULL OR status = $3) \
+             ORDER BY created_at DESC LIMIT $4 OFFSET $5"
+        );
+        let rows = sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
+            .bind(owner_id)
+            .bind(requester_id)
+            .bind(status.map(|s| s.to_string()))
+            .bind(limit as i64)
+            .bind(offset as i64)
+            .fetch_all(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
+        rows.iter().map(row_to_access_request).collect()
+    }
+
+    async fn update_access_request(
+        &self,
+        request: &AccessRequest,
+        expected: AccessRequestStatus,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE access_requests SET status = $1, decided_by = $2, decision_note = $3, acl_ids = $4, decided_at = $5 WHERE id = $6 AND status = $7",
+        )
+        .bind(request.status.to_string())
+        .bind(&request.decided_by)
+        .bind(&request.decision_note)
+        .bind(serde_json::json!(request.acl_ids))
+        .bind(&request.decided_at)
+        .bind(request.id)
+        .bind(expected.to_string())
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    // -----------------------------------------------------------------------
+    // Channels
+    // -----------------------------------------------------------------------
+
+    async fn insert_channel(&self, channel: &Channel) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO channels (name, created_by, max_memories, created_at) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(&channel.name)
+        .bind(&channel.created_by)
+        .bind(channel.max_memories.map(|n| n as i64))
+        .bind(&channel.created_at)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(())
+    }
+
+    async fn get_channel(&self, name: &str) -> Result<Option<Channel>> {
+        let row = sqlx::query(
+            "SELECT name, created_by, max_memories, created_at FROM channels WHERE name = $1",
+        )
+        .bind(name)
+        .fetch_optional(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(row.as_ref().map(row_to_channel))
+    }
+
+    async fn list_channels(&self, member: Option<&str>) -> Result<Vec<Channel>> {
+        let rows = sqlx::query(
+            "SELECT name, created_by, max_memories, created_at FROM channels \
+             WHERE $1::VARCHAR IS NULL \
+             OR name IN (SELECT channel FROM channel_members WHERE agent_id = $1) \
+             ORDER BY name",
+        )
+        .bind(member)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(rows.iter().map(row_to_channel).collect())
+    }
+
+    async fn update_channel(&self, channel: &Channel) -> Result<()> {
+        let result = sqlx::query("UPDATE channels SET max_memories = $1 WHERE name = $2")
+            .bind(channel.max_memories.map(|n| n as i64))
+            .bind(&channel.name)
+            .execute(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
+        if result.rows_affected() == 0 {
+            return Err(Error::NotFound(format!(
+                "channel {} not found",
+                channel.name
+            )));
+        }
+        Ok(())
+    }
+
+    async fn add_channel_member(&self, member: &ChannelMember) -> Result<bool> {
+        let result = sqlx::query(
+            "INSERT INTO channel_members (channel, agent_id, joined_at) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
+        )
+        .bind(&member.channel)
+        .bind(&member.agent_id)
+        .bind(&member.joined_at)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn remove_channel_member(&self, channel: &str, agent_id: &str) -> Result<bool> {
+        let result =
+            sqlx::query("DELETE FROM channel_members WHERE channel = $1 AND agent_id = $2")
+                .bind(channel)
+                .bind(agent_id)
+                .execute(&mut *self.conn().await?)
+                .await
+                .map_err(map_sqlx)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list_channel_members(&self, channel: &str) -> Result<Vec<ChannelMember>> {
+        let rows = sqlx::query(
+            "SELECT channel, agent_id, joined_at FROM channel_members WHERE channel = $1 ORDER BY joined_at, agent_id",
+        )
+        .bind(channel)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(rows
+            .iter()
+            .map(|r| ChannelMember {
+                channel: r.get("channel"),
+                agent_id: r.get("agent_id"),
+                joined_at: r.get("joined_at"),
+            })
+            .collect())
+    }
+
+    async fn is_channel_member(&self, channel: &str, agent_id: &str) -> Result<bool> {
+        let row = sqlx::query("SELECT 1 FROM channel_members WHERE channel = $1 AND agent_id = $2")
+            .bind(channel)
+            .bind(agent_id)
+            .fetch_optional(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
+        Ok(row.is_some())
+    }
+
+    async fn count_channel_memories(&self, channel: &str) -> Result<u64> {
+        // System context: the quota counts every member's memories.
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM memories WHERE channel = $1 AND deleted_at IS NULL",
+        )
+        .bind(channel)
+        .fetch_one(&mut *self.conn_as(None).await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(count as u64)
+    }
+
+    async fn content_storage_stats(
+        &self,
+        agent_id: Option<&str>,
+    ) -> Result<Vec<ContentStorageStats>> {
+        let rows = sqlx::query(
+            "SELECT agent_id, COUNT(*) AS memories, COUNT(content_zstd) AS compressed, \
+             SUM(COALESCE(content_len, octet_length(content)))::BIGINT AS content_bytes, \
+             SUM(COALESCE(octet_length(content_zstd), octet_length(content)))::BIGINT AS stored_bytes \
+             FROM memories WHERE $1::VARCHAR IS NULL OR agent_id = $1 \
+             GROUP BY agent_id ORDER BY agent_id",
+        )
+        .bind(agent_id)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let content_bytes = row.get::<i64, _>("content_bytes") as u64;
+                let stored_bytes = row.get::<i64, _>("stored_bytes") as u64;
+                ContentStorageStats {
+                    agent_id: row.get("agent_id"),
+                    memories: row.get::<i64, _>("memories") as u64,
+                    compressed: row.get::<i64, _>("compressed") as u64,
+                    content_bytes,
+                    stored_bytes,
+                    saved_bytes: content_bytes.saturating_sub(stored_bytes),
+                }
+            })
+            .collect())
+    }
+
+    async fn daily_growth(&self) -> Result<Vec<DailyGrowth>> {
+        let rows = sqlx::query(
+            "SELECT day, SUM(memories)::BIGINT AS memories, SUM(memory_bytes)::BIGINT AS memory_bytes, \
+             SUM(events)::BIGINT AS events, SUM(event_bytes)::BIGINT AS event_bytes FROM ( \
+             SELECT substr(created_at, 1, 10) AS day, COUNT(*) AS memories, \
+             SUM(COALESCE(octet_length(content_zstd), octet_length(content))) AS memory_bytes, \
+             0 AS events, 0 AS event_bytes FROM memories GROUP BY 1 \
+             UNION ALL \
+             SELECT substr(\"timestamp\", 1, 10), 0, 0, COUNT(*), \
+             SUM(COALESCE(octet_length(payload::TEXT), 0)) FROM agent_events GROUP BY 1 \
+             ) AS growth GROUP BY day ORDER BY day",
+        )
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(rows
+            .iter()
+            .map(|row| DailyGrowth {
+                day: row.get("day"),
+                memories: row.get::<i64, _>("memories") as u64,
+                memory_bytes: row.get::<i64, _>("memory_bytes") as u64,
+                events: row.get::<i64, _>("events") as u64,
+                event_bytes: row.get::<i64, _>("event_bytes") as u64,
+            })
+            .collect())
+    }
+
+    async fn database_size(&self) -> Result<u64> {
+        let bytes: i64 = sqlx::query_scalar("SELECT pg_database_size(current_database())")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+        Ok(bytes as u64)
+    }
+
+    /// Plain `VACUUM (ANALYZE)` of the tables the role owns: dead rows
+    /// become reusable and statistics are refreshed. It runs alongside
+    /// writes and only returns space at the end of a table to the OS; the
+    /// database size is reported before and after.
+    async fn compact(&self) -> Result<CompactionReport> {
+        let size = "SELECT pg_database_size(current_database())";
+        let bytes_before: i64 = sqlx::query_scalar(size)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+        sqlx::query("VACUUM (ANALYZE)")
+            .execute(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+        let bytes_after: i64 = sqlx::query_scalar(size)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(map_sqlx)?;
+        Ok(CompactionReport {
+            bytes_before: bytes_before as u64,
+            bytes_after: bytes_after as u64,
+        })
+    }
+
+    // -----------------------------------------------------------------------
+    // Memory access log
+    // -----------------------------------------------------------------------
+
+    async fn insert_memory_accesses(&self, accesses: &[MemoryAccess]) -> Result<()> {
+        let mut tx = self.pool.begin().await.map_err(map_sqlx)?;
+        for access in accesses {
+            sqlx::query(
+                "INSERT INTO memory_accesses (id, memory_id, principal, operation, query, accessed_at) VALUES ($1, $2, $3, $4, $5, $6)",
+            )
+            .bind(access.id)
+            .bind(access.memory_id)
+            .bind(&access.principal)
+            .bind(access.operation.to_string())
+            .bind(&access.query)
+            .bind(&access.accessed_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(map_sqlx)?;
+        }
+        tx.commit().await.map_err(map_sqlx)?;
+        Ok(())
+    }
+
+    async fn list_memory_accesses(
+        &self,
+        memory_id: Uuid,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<MemoryAccess>> {
+        let sql = format!(
+            "SELECT {MEMORY_ACCESS_COLUMNS} FROM memory_accesses WHERE memory_id = $1 \
+             ORDER BY accessed_at DESC, id DESC LIMIT $2 OFFSET $3"
+        );
+        let rows = sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
+            .bind(memory_id)
+            .bind(limit as i64)
+            .bind(offset as i64)
+            .fetch_all(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
+        rows.iter().map(row_to_memory_access).collect()
+    }
+
+    async fn count_memory_accesses(&self, memory_id: Uuid) -> Result<usize> {
+        let row = sqlx::query("SELECT COUNT(*) AS n FROM memory_accesses WHERE memory_id = $1")
+            .bind(memory_id)
+            .fetch_one(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
+        Ok(row.get::<i64, _>("n") as usize)
+    }
+
+    async fn prune_memory_accesses(
+        &self,
+        older_than: Option<&str>,
+        keep_per_memory: Option<usize>,
+    ) -> Result<usize> {
+        let mut deleted = 0;
+        if let Some(cutoff) = older_than {
+            let result = sqlx::query("DELETE FROM memory_accesses WHERE accessed_at < $1")
+                .bind(cutoff)
+                .execute(&mut *self.conn().await?)
+                .await
+                .map_err(map_sqlx)?;
+            deleted += result.rows_affected() as usize;
+        }
+        if let Some(keep) = keep_per_memory {
+            let result = sqlx::query(
+                "DELETE FROM memory_accesses WHERE id IN (\
+                 SELECT id FROM (SELECT id, ROW_NUMBER() OVER (PARTITION BY memory_id \
+                 ORDER BY accessed_at DESC, id DESC) AS rn FROM memory_accesses) ranked \
+                 WHERE rn > $1)",
+            )
+            .bind(keep as i64)
+            .execute(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
+            deleted += result.rows_affected() as usize;
+        }
+        Ok(deleted)
+    }
+
+    // -----------------------------------------------------------------------
+    // Recall profiles
+    // -----------------------------------------------------------------------
+
+    async fn upsert_recall_profile(&self, profile: &RecallProfile) -> Result<()> {
+        let settings_json =
+            serde_json::to_value(&profile.settings).map_err(|e| Error::Storage(e.to_string()))?;
+        sqlx::query(
+            r#"
+INSERT INTO recall_profiles (agent_id, name, settings, use_count, last_used_at, created_at, updated_at)
+VALUES ($1, $2, $3, $4, $5, $6, $7)
+ON CONFLICT (agent_id, name) DO UPDATE SET
+    settings = EXCLUDED.settings,
+    updated_at = EXCLUDED.updated_at
+"#,
+        )
+        .bind(&profile.agent_id)
+        .bind(&profile.name)
+        .bind(&settings_json)
+        .bind(profile.use_count as i64)
+        .bind(&profile.last_used_at)
+        .bind(&profile.created_at)
+        .bind(&profile.updated_at)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(())
+    }
+
+    async fn get_recall_profile(
+        &self,
+        agent_id: &str,
+        name: &str,
+    ) -> Result<Option<RecallProfile>> {
+        let row = sqlx::query(
+            "SELECT agent_id, name, settings, use_count, last_used_at, created_at, updated_at FROM recall_profiles WHERE agent_id = $1 AND name = $2",
+        )
+        .bind(agent_id)
+        .bind(name)
+        .fetch_optional(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        row.map(|r| row_to_recall_profile(&r)).transpose()
+    }
+
+    async fn list_recall_profiles(&self, agent_id: &str) -> Result<Vec<RecallProfile>> {
+        let rows = sqlx::query(
+            "SELECT agent_id, name, settings, use_count, last_used_at, created_at, updated_at FROM recall_profiles WHERE agent_id = $1 ORDER BY name",
+        )
+        .bind(agent_id)
+        .fetch_all(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        rows.iter().map(row_to_recall_profile).collect()
+    }
+
+    async fn delete_recall_profile(&self, agent_id: &str, name: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM recall_profiles WHERE agent_id = $1 AND name = $2")
+            .bind(agent_id)
+            .bind(name)
+            .execute(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn record_recall_profile_use(
+        &self,
+        agent_id: &str,
+        name: &str,
+        used_at: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            "UPDATE recall_profiles SET use_count = use_count + 1, last_used_at = $1 WHERE agent_id = $2 AND name = $3",
+        )
+        .bind(used_at)
+        .bind(agent_id)
+        .bind(name)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(())
+    }
+
     // -----------------------------------------------------------------------
     // Checkpoints
     // -----------------------------------------------------------------------
 
+    async fn commit_write(&self, batch: &WriteBatch) -> Result<()> {
+        let mut conn = self.conn().await?;
+        let mut tx = sqlx::Connection::begin(&mut *conn)
+            .await
+            .map_err(map_sqlx)?;
+        if let Some(ref record) = batch.memory {
+            insert_memory_row(&mut tx, record, self.compression_threshold).await?;
+        }
+        for record in &batch.updates {
+            if update_memory_row(&mut tx, record, None, self.compression_threshold).await? == 0 {
+                return Err(Error::NotFound(format!("memory {} not found", record.id)));
+            }
+        }
+        for acl in &batch.acls {
+            insert_acl_row(&mut tx, acl).await?;
+        }
+        for relation in &batch.relations {
+            insert_relation_row(&mut tx, relation).await?;
+        }
+        for event in &batch.events {
+            insert_event_row(&mut tx, event).await?;
+        }
+        for message in &batch.outbox {
+            insert_outbox_row(&mut tx, message).await?;
+        }
+        tx.commit().await.map_err(map_sqlx)?;
+        if let Some(ref record) = batch.memory {
+            self.notify_change(MemoryChange::Inserted(record.id)).await;
+        }
+        for record in &batch.updates {
+            self.notify_change(MemoryChange::Updated(record.id)).await;
+        }
+        Ok(())
+    }
+
+    async fn claim_outbox(&self, limit: usize, lease_until: &str) -> Result<Vec<OutboxMessage>> {
+        // `SKIP LOCKED` lets several dispatchers claim concurrently without
+        // handing the same row to two of them.
+        let sql = format!(
+            r#"
+UPDATE outbox SET attempts = attempts + 1, available_at = $2
+WHERE id IN (
+    SELECT id FROM outbox
+    WHERE delivered_at IS NULL AND (available_at IS NULL OR available_at <= $1)
+    ORDER BY created_at, id
+    LIMIT $3
+    FOR UPDATE SKIP LOCKED
+)
+RETURNING {OUTBOX_COLUMNS}
+"#
+        );
+        let rows = sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
+            .bind(chrono::Utc::now().to_rfc3339())
+            .bind(lease_until)
+            .bind(limit as i64)
+            .fetch_all(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
+        let mut claimed: Vec<OutboxMessage> = rows.iter().map(row_to_outbox).collect();
+        // RETURNING does not preserve the subquery's order.
+        claimed.sort_by(|a, b| (&a.created_at, a.id).cmp(&(&b.created_at, b.id)));
+        Ok(claimed)
+    }
+
+    async fn complete_outbox(&self, id: Uuid) -> Result<()> {
+        sqlx::query("UPDATE outbox SET delivered_at = $1, last_error = NULL WHERE id = $2")
+            .bind(chrono::Utc::now().to_rfc3339())
+            .bind(id)
+            .execute(&mut *self.conn().await?)
+            .await
+            .map_err(map_sqlx)?;
+        Ok(())
+    }
+
+    async fn fail_outbox(&self, id: Uuid, error: &str, retry_at: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE outbox SET last_error = $1, available_at = $2 WHERE id = $3 AND delivered_at IS NULL",
+        )
+        .bind(error)
+        .bind(retry_at)
+        .bind(id)
+        .execute(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(())
+    }
+
+    async fn prune_outbox(&self, cutoff: &str) -> Result<usize> {
+        let result =
+            sqlx::query("DELETE FROM outbox WHERE delivered_at IS NOT NULL AND delivered_at < $1")
+                .bind(cutoff)
+                .execute(&mut *self.conn().await?)
+                .await
+                .map_err(map_sqlx)?;
+        Ok(result.rows_affected() as usize)
+    }
+
     async fn insert_checkpoint(&self, cp: &Checkpoint) -> Result<()> {
         let memory_refs_strs: Vec<String> =
             cp.memory_refs.iter().map(|id| id.to_string()).collect();
@@ -1372,7 +2917,7 @@ INSERT INTO checkpoints (
         .bind(&cp.label)
         .bind(&cp.created_at)
         .bind(&cp.metadata)
-        .execute(&self.pool)
+        .execute(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
         Ok(())
@@ -1388,7 +2933,7 @@ FROM checkpoints WHERE id = $1
 "#,
         )
         .bind(id)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
 
@@ -1419,7 +2964,7 @@ LIMIT $3
             .bind(thread_id)
             .bind(branch_name)
             .bind(limit as i64)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.conn().await?)
             .await
             .map_err(map_sqlx)?
         } else {
@@ -1436,7 +2981,7 @@ LIMIT $2
             )
             .bind(thread_id)
             .bind(limit as i64)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.conn().await?)
             .await
             .map_err(map_sqlx)?
         };
@@ -1466,7 +3011,7 @@ LIMIT 1
         )
         .bind(thread_id)
         .bind(branch)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *self.conn().await?)
         .await
         .map_err(map_sqlx)?;
 
@@ -1475,4 +3020,26 @@ LIMIT 1
             None => Ok(None),
         }
     }
+
+    async fn thread_owner(&self, thread_id: &str) -> Result<Option<String>> {
+        let row = sqlx::query(
+            r#"
+SELECT agent_id FROM (
+    SELECT agent_id, created_at AS at, 0 AS fallback FROM memories WHERE thread_id = $1
+    UNION ALL
+    SELECT agent_id, "timestamp", 0 FROM agent_events
+    WHERE thread_id = $1 AND event_type <> 'checkpoint'
+    UNION ALL
+    SELECT agent_id, created_at, 1 FROM checkpoints WHERE thread_id = $1
+) AS written
+ORDER BY fallback, at
+LIMIT 1
+"#,
+        )
+        .bind(thread_id)
+        .fetch_optional(&mut *self.conn().await?)
+        .await
+        .map_err(map_sqlx)?;
+        Ok(row.map(|r| r.get("agent_id")))
+    }
 }
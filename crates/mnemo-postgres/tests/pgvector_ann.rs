@@ -29,7 +29,7 @@ use mnemo_core::error::Result as MnResult;
 use mnemo_core::query::MnemoEngine;
 use mnemo_core::query::recall::RecallRequest;
 use mnemo_core::query::remember::RememberRequest;
-use mnemo_postgres::{PgStorage, PgVectorIndex};
+use mnemo_postgres::{HnswConfig, PgStorage, PgVectorIndex};
 
 const DIM: usize = 4;
 const AGENT_A: &str = "pgann-A";
@@ -152,3 +152,62 @@ async fn pgvector_ann_semantic_auto_and_permission_filter() {
         "AGENT_B must see its own private record"
     );
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn hnsw_tuning_and_ef_search() {
+    let Ok(url) = std::env::var("MNEMO_TEST_POSTGRES_URL") else {
+        eprintln!("skipping HNSW tuning test: set MNEMO_TEST_POSTGRES_URL to run it");
+        return;
+    };
+
+    let hnsw = HnswConfig {
+        m: 24,
+        ef_construction: 96,
+        ef_search: Some(100),
+    };
+    let storage = Arc::new(
+        PgStorage::connect_with_hnsw(&url, DIM, &hnsw)
+            .await
+            .expect("connect + tune hnsw"),
+    );
+    // Any pending rebuild runs here; a second pass has nothing to rebuild.
+    storage.maintain_vector_index().await.expect("maintenance");
+    assert!(!storage.maintain_vector_index().await.expect("maintenance"));
+    let options: Option<Vec<String>> =
+        sqlx::query_scalar("SELECT reloptions FROM pg_class WHERE relname = $1")
+            .bind(mnemo_postgres::pgvector_index::HNSW_INDEX_NAME)
+            .fetch_one(&storage.pool())
+            .await
+            .expect("read reloptions");
+    let options = options.unwrap_or_default();
+    assert!(options.contains(&"m=24".to_string()), "{options:?}");
+    assert!(
+        options.contains(&"ef_construction=96".to_string()),
+        "{options:?}"
+    );
+
+    let index = Arc::new(PgVectorIndex::with_pool(storage.pool(), DIM).with_ef_search(100));
+    let engine = MnemoEngine::new(
+        storage.clone(),
+        index,
+        Arc::new(MapEmbedding),
+        AGENT_A.to_string(),
+        None,
+    );
+    engine
+        .remember(RememberRequest::new("alpha".to_string()))
+        .await
+        .expect("remember");
+    let mut req = RecallRequest::new("query".to_string());
+    req.strategy = Some("semantic".to_string());
+    let resp = engine.recall(req).await.expect("recall with ef_search");
+    assert!(resp.memories.iter().any(|m| m.content == "alpha"));
+
+    // Restore the defaults for the other tests sharing the database.
+    PgStorage::connect_with_hnsw(&url, DIM, &HnswConfig::default())
+        .await
+        .expect("restore defaults")
+        .maintain_vector_index()
+        .await
+        .expect("maintenance");
+}
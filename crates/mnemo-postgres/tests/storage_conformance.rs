@@ -0,0 +1,77 @@
+//! The storage conformance suite (`mnemo_core::storage::conformance`)
+//! against PostgreSQL.
+//!
+//! Uses the database at `MNEMO_TEST_POSTGRES_URL` when set, otherwise starts
+//! a throwaway `pgvector/pgvector:pg16` container. Without either (no URL and
+//! no Docker) the test **skips (passes)**. Each run migrates into a fresh
+//! schema, so it never sees rows from earlier runs or other tests.
+//!
+//! ```bash
+//! cargo test -p mnemo-postgres --test storage_conformance -- --nocapture
+//! ```
+
+use std::str::FromStr;
+
+use mnemo_core::storage::conformance;
+use mnemo_postgres::PgStorage;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use testcontainers_modules::postgres::Postgres;
+use testcontainers_modules::testcontainers::runners::AsyncRunner;
+use testcontainers_modules::testcontainers::{ContainerAsync, ImageExt};
+
+/// Embeddings are never written by the suite; any dimension works.
+const DIM: usize = 8;
+
+async fn database() -> Option<(String, Option<ContainerAsync<Postgres>>)> {
+    if let Ok(url) = std::env::var("MNEMO_TEST_POSTGRES_URL") {
+        return Some((url, None));
+    }
+    let container = match Postgres::default()
+        .with_name("pgvector/pgvector")
+        .with_tag("pg16")
+        .start()
+        .await
+    {
+        Ok(container) => container,
+        Err(e) => {
+            eprintln!(
+                "skipping storage conformance: no MNEMO_TEST_POSTGRES_URL and \
+                 no container ({e})"
+            );
+            return None;
+        }
+    };
+    let host = container.get_host().await.expect("container host");
+    let port = container
+        .get_host_port_ipv4(5432)
+        .await
+        .expect("container port");
+    let url = format!("postgres://postgres:postgres@{host}:{port}/postgres");
+    Some((url, Some(container)))
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn postgres_conforms() {
+    let Some((url, _container)) = database().await else {
+        return;
+    };
+
+    let schema = format!("conformance_{}", uuid::Uuid::now_v7().simple());
+    sqlx::query(sqlx::AssertSqlSafe(format!("CREATE SCHEMA {schema}")))
+        .execute(&PgPoolOptions::new().connect(&url).await.expect("connect"))
+        .await
+        .expect("create schema");
+
+    let options = PgConnectOptions::from_str(&url)
+        .expect("MNEMO_TEST_POSTGRES_URL")
+        .options([("search_path", format!("{schema},public"))]);
+    let pool = PgPoolOptions::new()
+        .connect_with(options)
+        .await
+        .expect("connect");
+    let storage = PgStorage::from_pool(pool, DIM)
+        .await
+        .expect("run migrations");
+
+    conformance::run_all(&storage).await;
+}
@@ -1,30 +1,80 @@
+use std::convert::Infallible;
 use std::sync::Arc;
+use std::time::Duration;
 
-use axum::Json;
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
+use axum::response::sse::{Event as SseEvent, KeepAlive, Sse};
 use axum::response::{IntoResponse, Response};
-use serde::Deserialize;
+use axum::{Extension, Json};
+use serde::{Deserialize, Serialize};
+use tokio_stream::wrappers::ReceiverStream;
 use uuid::Uuid;
 
 use mnemo_core::error::Error as CoreError;
 use mnemo_core::hash::compute_content_hash;
+use mnemo_core::ingest::url::{UrlIngestRequest, UrlIngestResponse, WatchedPage};
+use mnemo_core::ingest::{IngestRequest, IngestResponse};
+use mnemo_core::model::access_log::AccessOperation;
 use mnemo_core::model::acl::Permission;
+use mnemo_core::model::agent_profile::RetrievalDefaults;
+use mnemo_core::model::channel::{Channel, ChannelMember};
 use mnemo_core::model::delegation::{Delegation, DelegationScope};
 use mnemo_core::model::event::{AgentEvent, EventType};
+use mnemo_core::model::event_archive::EventArchiveSegment;
 use mnemo_core::model::memory::{MemoryType, Scope};
+use mnemo_core::model::recall_profile::RecallProfile;
 use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::access_log::{AccessLogRequest, AccessLogResponse};
+use mnemo_core::query::attestation::{AttestRequest, AttestResponse, PendingAttestation};
 use mnemo_core::query::branch::{BranchRequest, BranchResponse};
+use mnemo_core::query::calibration::StrategyCalibration;
+use mnemo_core::query::channel::{
+    ChannelMembershipRequest, CreateChannelRequest, SetChannelQuotaRequest,
+};
 use mnemo_core::query::checkpoint::{CheckpointRequest, CheckpointResponse};
+use mnemo_core::query::compression::{CompressionReport, CompressionRequest};
 use mnemo_core::query::consolidate::{ConsolidateRequest, ConsolidateResponse};
+use mnemo_core::query::curation::{CurationReviewRequest, CurationReviewResponse, PendingMemory};
+use mnemo_core::query::entity_summary::{EntitySummary, EntitySummaryReport, EntitySummaryRequest};
+use mnemo_core::query::ephemeral::EphemeralRequest;
+use mnemo_core::query::event_retention::{EventArchiveReport, RestoredEventSegment};
 use mnemo_core::query::forget::{
     ForgetRequest, ForgetResponse, ForgetStrategy, ForgetSubjectRequest, ForgetSubjectResponse,
 };
+use mnemo_core::query::get_batch::{GetBatchRequest, GetBatchResponse};
+use mnemo_core::query::graph_export::{GraphExportRequest, GraphFormat};
+use mnemo_core::query::health::{self, HealthReport};
+use mnemo_core::query::hybrid::HybridConfig;
+use mnemo_core::query::lifecycle::{
+    self, CleanupRequest, CleanupResult, ConsolidationResult, DecayPassResult, ExtendTtlRequest,
+    ExtendTtlResponse,
+};
 use mnemo_core::query::merge::{MergeRequest, MergeResponse};
+use mnemo_core::query::pin::{PinRequest, PinResponse};
+use mnemo_core::query::prefetch::{PrefetchRequest, PrefetchResponse};
 use mnemo_core::query::recall::{RecallRequest, RecallResponse};
+use mnemo_core::query::recall_profile::SaveRecallProfileRequest;
+use mnemo_core::query::reembed::{ReembedRequest, ReembedResponse};
 use mnemo_core::query::remember::{RememberRequest, RememberResponse};
-use mnemo_core::query::replay::{ReplayRequest, ReplayResponse};
-use mnemo_core::query::share::{ShareRequest, ShareResponse};
+use mnemo_core::query::replay::{MAX_REPLAY_PACE, ReplayFrame, ReplayRequest, ReplayResponse};
+use mnemo_core::query::shadow_recall::{ShadowComparison, ShadowRecallReport};
+use mnemo_core::query::share::{ShareFilter, ShareRequest, ShareResponse};
+use mnemo_core::query::share_link::{
+    CreateShareLinkRequest, RevokeShareLinkRequest, RevokeShareLinkResponse, ShareLink,
+};
+use mnemo_core::query::similar::{DEFAULT_SIMILAR_LIMIT, SimilarRequest, SimilarResponse};
+use mnemo_core::query::thread_message::{
+    CreateMessageRequest, CreateMessageResponse, DeleteMessageRequest, DeleteMessageResponse,
+    ListMessagesRequest, ListMessagesResponse, MessageOrder, MessageRole,
+};
+use mnemo_core::query::trash::{TrashRequest, TrashResponse, UndeleteRequest, UndeleteResponse};
+use mnemo_core::query::turn::{TurnRequest, TurnResponse};
+use mnemo_core::query::update::{UpdateRequest, UpdateResponse};
+use mnemo_core::query::validation;
+use mnemo_core::query::validity::{SetValidityRequest, SetValidityResponse};
+use mnemo_core::query::verify::VerifyRequest;
+use mnemo_core::search::TextQueryMode;
 
 type AppState = Arc<MnemoEngine>;
 
@@ -34,21 +84,109 @@ type AppState = Arc<MnemoEngine>;
 
 pub struct AppError(CoreError);
 
+/// Error bodies look like
+/// `{"error": {"code": "invalid_argument", "message": "...", "fields": [...]}}`.
+/// `code` uses the gRPC status names so both transports agree; `fields`
+/// (present for `invalid_argument` only) lists `{"path", "message"}` per
+/// invalid request field and is empty when the failure names no field.
+/// A version conflict (`aborted`, 409) carries the memory as stored now in
+/// `current`, plus the `expected` and `actual` versions. `retryable` tells
+/// clients whether resending the same request later can succeed; a rate
+/// limit also sets `Retry-After` when the upstream gave a hint.
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        let (status, msg) = match &self.0 {
-            CoreError::Validation(m) => (StatusCode::BAD_REQUEST, m.clone()),
-            CoreError::PermissionDenied(m) => (StatusCode::FORBIDDEN, m.clone()),
-            CoreError::NotFound(m) => (StatusCode::NOT_FOUND, m.clone()),
+        let (status, code, msg) = match &self.0 {
+            CoreError::Validation(m) => (StatusCode::BAD_REQUEST, "invalid_argument", m.clone()),
+            e @ CoreError::InvalidFields(_) => {
+                (StatusCode::BAD_REQUEST, "invalid_argument", e.to_string())
+            }
+            CoreError::PermissionDenied(m) => {
+                (StatusCode::FORBIDDEN, "permission_denied", m.clone())
+            }
+            CoreError::NotFound(m) => (StatusCode::NOT_FOUND, "not_found", m.clone()),
+            CoreError::ReadOnly(m) => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "failed_precondition",
+                m.clone(),
+            ),
+            e @ CoreError::VersionConflict { .. } => {
+                (StatusCode::CONFLICT, "aborted", e.to_string())
+            }
+            CoreError::Conflict(m) => (StatusCode::CONFLICT, "already_exists", m.clone()),
+            CoreError::QuotaExceeded(m) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                "resource_exhausted",
+                m.clone(),
+            ),
+            CoreError::RateLimited { message, .. } => (
+                StatusCode::TOO_MANY_REQUESTS,
+                "resource_exhausted",
+                message.clone(),
+            ),
+            CoreError::Unavailable(m) => {
+                tracing::warn!("backend unavailable: {m}");
+                (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "unavailable",
+                    "backend unavailable".to_string(),
+                )
+            }
+            CoreError::Timeout(m) => {
+                tracing::warn!("backend timeout: {m}");
+                (
+                    StatusCode::GATEWAY_TIMEOUT,
+                    "deadline_exceeded",
+                    "backend timed out".to_string(),
+                )
+            }
+            CoreError::IndexCorruption(m) => {
+                tracing::error!("index corruption: {m}");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "data_loss",
+                    "vector index is corrupt; rebuild it".to_string(),
+                )
+            }
             other => {
                 tracing::error!("internal error: {other}");
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal",
                     "internal server error".to_string(),
                 )
             }
         };
-        (status, Json(serde_json::json!({"error": msg}))).into_response()
+        let mut error = serde_json::json!({
+            "code": code,
+            "message": msg,
+            "retryable": self.0.is_retryable(),
+        });
+        if code == "invalid_argument" {
+            error["fields"] = serde_json::json!(self.0.field_violations());
+        }
+        if let CoreError::VersionConflict {
+            expected,
+            actual,
+            current,
+            ..
+        } = &self.0
+        {
+            error["expected"] = serde_json::json!(expected);
+            error["actual"] = serde_json::json!(actual);
+            error["current"] = serde_json::json!(current);
+        }
+        let mut response = (status, Json(serde_json::json!({ "error": error }))).into_response();
+        if let CoreError::RateLimited {
+            retry_after_secs: Some(secs),
+            ..
+        } = &self.0
+        {
+            response.headers_mut().insert(
+                axum::http::header::RETRY_AFTER,
+                axum::http::HeaderValue::from(*secs),
+            );
+        }
+        response
     }
 }
 
@@ -76,6 +214,9 @@ pub struct RecallParams {
     pub as_of: Option<String>,
     pub memory_types: Option<String>,
     pub hybrid_weights: Option<String>,
+    /// A hybrid preset name or `signal:weight` pairs; overrides
+    /// `hybrid_weights`.
+    pub hybrid: Option<String>,
     pub rrf_k: Option<f32>,
     pub explain: Option<bool>,
     /// v0.4.7 — opt-in current-fact resolver. Set the metadata key
@@ -105,12 +246,189 @@ pub struct RecallParams {
     /// Defaults to `true` when omitted; set to `false` for warm-up
     /// or inspection calls that should not mutate the map.
     pub orientation_distill: Option<bool>,
+    /// BM25 query mode for lexical / hybrid recall: `terms` (default),
+    /// `phrase`, `fuzzy`, `fuzzy:1` or `fuzzy:2`.
+    pub text_query_mode: Option<String>,
+    /// Comma-separated `field:boost` pairs, e.g. `content_eng:2.0,content:1.0`.
+    pub text_field_boosts: Option<String>,
+    /// Attach matched-snippet excerpts to each hit.
+    pub with_snippets: Option<bool>,
+    /// Saved recall profile to expand; explicit params override it.
+    pub profile: Option<String>,
+    /// Comma-separated response expansions, e.g. `provenance`.
+    pub include: Option<String>,
+    /// Thread the recall is issued from.
+    pub thread_id: Option<String>,
+    /// Restrict results to `thread_id` plus global memories.
+    pub thread_isolation: Option<bool>,
+    /// Text the results should not be about; similar memories rank lower.
+    pub exclude_query: Option<String>,
+    /// Comma-separated tags; memories carrying any of them are dropped.
+    pub exclude_tags: Option<String>,
+    /// `|`-separated further phrasings of `q`; rankings are fused.
+    pub queries: Option<String>,
+    /// Add paraphrases of `q` from the configured query expander.
+    pub expand_query: Option<bool>,
+    /// Recall only from this channel; the agent must be a member.
+    pub channel: Option<String>,
+    /// Comma-separated response fields, e.g. `id,score`. Content and
+    /// metadata are omitted unless listed.
+    pub fields: Option<String>,
+    /// Deadline for this recall; optional stages past it are skipped.
+    pub deadline_ms: Option<u64>,
+    /// Comma-separated agents to recall across; `agent_id` needs a read
+    /// delegation from each of the others.
+    pub agent_ids: Option<String>,
+    /// Drop hits whose calibrated relevance is below this, in `[0, 1]`.
+    pub min_relevance: Option<f32>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct ForgetParams {
     pub strategy: Option<String>,
     pub agent_id: Option<String>,
+    /// Required to remove a pinned memory.
+    pub force: Option<bool>,
+    /// Report whether the memory would be forgotten, changing nothing.
+    pub dry_run: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PinParams {
+    pub agent_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UndeleteParams {
+    pub agent_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecallProfileParams {
+    pub agent_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChannelParams {
+    pub agent_id: Option<String>,
+    /// When listing channels: only those this agent belongs to.
+    pub member: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateThreadMessageBody {
+    pub role: MessageRole,
+    pub content: String,
+    pub agent_id: Option<String>,
+    pub org_id: Option<String>,
+    pub run_id: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+    pub capture_turn: Option<bool>,
+    pub extract_facts: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ThreadMessagesParams {
+    pub agent_id: Option<String>,
+    pub limit: Option<usize>,
+    pub order: Option<MessageOrder>,
+    pub after: Option<Uuid>,
+    pub before: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct JoinChannelBody {
+    pub agent_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChannelQuotaBody {
+    pub agent_id: Option<String>,
+    /// `null` removes the cap.
+    pub max_memories: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SimilarParams {
+    /// Neighbours to return.
+    pub k: Option<usize>,
+    pub agent_id: Option<String>,
+    pub memory_type: Option<String>,
+    pub scope: Option<String>,
+    /// Comma-separated; neighbours need at least one.
+    pub tags: Option<String>,
+    pub min_importance: Option<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GraphExportParams {
+    /// `graphml`, `dot` or `json` (the default).
+    pub format: Option<String>,
+    pub agent_id: Option<String>,
+    pub thread_id: Option<String>,
+    pub memory_type: Option<String>,
+    /// Comma-separated; nodes need at least one.
+    pub tags: Option<String>,
+    pub min_importance: Option<f32>,
+    /// Comma-separated relation types to keep as edges.
+    pub relation_types: Option<String>,
+    pub min_weight: Option<f32>,
+    pub max_nodes: Option<usize>,
+    pub max_edges: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ShadowRecallFeedbackBody {
+    pub agent_id: Option<String>,
+    /// Memories that proved useful after the agent's recent recalls.
+    pub memory_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecallFeedbackBody {
+    pub agent_id: Option<String>,
+    /// Hits of the agent's recent recalls that proved useful.
+    pub memory_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ShadowRecallComparisonParams {
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EntitySummaryParams {
+    pub agent_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EventArchiveParams {
+    pub agent_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TrashParams {
+    pub agent_id: Option<String>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccessLogParams {
+    pub agent_id: Option<String>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReplayStreamParams {
+    pub thread_id: String,
+    pub agent_id: Option<String>,
+    pub checkpoint_id: Option<String>,
+    pub branch_name: Option<String>,
+    pub as_of: Option<String>,
+    /// Pause between events in milliseconds, capped at 10 000.
+    pub pace_ms: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -120,12 +438,52 @@ pub struct ShareBody {
     pub permission: Option<String>,
     pub expires_in_hours: Option<f64>,
     pub agent_id: Option<String>,
+    pub dry_run: Option<bool>,
+}
+
+/// Body of `POST /v1/share`: exactly one of `memory_ids` and `filter`.
+#[derive(Debug, Deserialize)]
+pub struct BulkShareBody {
+    pub memory_ids: Option<Vec<Uuid>>,
+    pub filter: Option<ShareFilter>,
+    pub target_agent_id: String,
+    pub target_agent_ids: Option<Vec<String>>,
+    pub permission: Option<String>,
+    pub expires_in_hours: Option<f64>,
+    pub agent_id: Option<String>,
+    pub dry_run: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct VerifyBody {
     pub agent_id: Option<String>,
     pub thread_id: Option<String>,
+    #[serde(default)]
+    pub incremental: bool,
+    pub max_records: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RememberBatchBody {
+    pub memories: Vec<RememberRequest>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RememberBatchResult {
+    pub memories: Vec<RememberResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DecayPassBody {
+    pub agent_id: Option<String>,
+    pub archive_threshold: Option<f32>,
+    pub forget_threshold: Option<f32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ConsolidationPassBody {
+    pub agent_id: Option<String>,
+    pub min_cluster_size: Option<usize>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -163,6 +521,25 @@ pub async fn remember_handler(
     Ok(Json(response))
 }
 
+/// POST /v1/memories/bulk -- store many memories with one batched
+/// embedding call.
+pub async fn remember_batch_handler(
+    State(engine): State<AppState>,
+    Json(body): Json<RememberBatchBody>,
+) -> Result<Json<RememberBatchResult>, AppError> {
+    let memories = engine.remember_batch(body.memories).await?;
+    Ok(Json(RememberBatchResult { memories }))
+}
+
+/// POST /v1/memories/ephemeral -- store a scratchpad memory.
+pub async fn remember_ephemeral_handler(
+    State(engine): State<AppState>,
+    Json(request): Json<EphemeralRequest>,
+) -> Result<Json<RememberResponse>, AppError> {
+    let response = engine.remember_ephemeral(request).await?;
+    Ok(Json(response))
+}
+
 /// GET /v1/memories?query=...&limit=...&memory_type=...&scope=...&strategy=...
 pub async fn recall_handler(
     State(engine): State<AppState>,
@@ -170,20 +547,22 @@ pub async fn recall_handler(
 ) -> Result<Json<RecallResponse>, AppError> {
     let memory_type = match params.memory_type.as_deref() {
         Some(s) => Some(s.parse::<MemoryType>().map_err(|_| {
-            AppError(CoreError::Validation(format!(
-                "invalid memory_type '{}': expected one of: episodic, semantic, procedural, working",
-                s
-            )))
+            AppError(CoreError::invalid_field(
+                "memory_type",
+                format!(
+                    "invalid value '{s}': expected one of: episodic, semantic, procedural, working"
+                ),
+            ))
         })?),
         None => None,
     };
 
     let scope = match params.scope.as_deref() {
         Some(s) => Some(s.parse::<Scope>().map_err(|_| {
-            AppError(CoreError::Validation(format!(
-                "invalid scope '{}': expected one of: private, shared, public, global",
-                s
-            )))
+            AppError(CoreError::invalid_field(
+                "scope",
+                format!("invalid value '{s}': expected one of: private, shared, public, global"),
+            ))
         })?),
         None => None,
     };
@@ -193,6 +572,18 @@ pub async fn recall_handler(
             .map(|s| s.trim().to_string())
             .collect::<Vec<_>>()
     });
+    let exclude_tags = params.exclude_tags.as_deref().map(|t| {
+        t.split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+    });
+    let queries = params.queries.as_deref().map(|q| {
+        q.split('|')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+    });
 
     let memory_types = match params.memory_types.as_deref() {
         Some(s) => {
@@ -200,10 +591,10 @@ pub async fn recall_handler(
             for t in s.split(',') {
                 let trimmed = t.trim();
                 let mt = trimmed.parse::<MemoryType>().map_err(|_| {
-                    AppError(CoreError::Validation(format!(
-                        "invalid memory_type '{}' in memory_types: expected one of: episodic, semantic, procedural, working",
-                        trimmed
-                    )))
+                    AppError(CoreError::invalid_field(
+                        "memory_types",
+                        format!("invalid memory_type '{trimmed}': expected one of: episodic, semantic, procedural, working"),
+                    ))
                 })?;
                 parsed.push(mt);
             }
@@ -218,10 +609,10 @@ pub async fn recall_handler(
             for w in s.split(',') {
                 let trimmed = w.trim();
                 let val = trimmed.parse::<f32>().map_err(|_| {
-                    AppError(CoreError::Validation(format!(
-                        "invalid weight '{}' in hybrid_weights: expected a floating-point number",
-                        trimmed
-                    )))
+                    AppError(CoreError::invalid_field(
+                        "hybrid_weights",
+                        format!("invalid weight '{trimmed}': expected a floating-point number"),
+                    ))
                 })?;
                 weights.push(val);
             }
@@ -230,6 +621,39 @@ pub async fn recall_handler(
         None => None,
     };
 
+    let hybrid = params
+        .hybrid
+        .as_deref()
+        .map(str::parse::<HybridConfig>)
+        .transpose()?;
+
+    let text_query_mode = params
+        .text_query_mode
+        .as_deref()
+        .map(str::parse::<TextQueryMode>)
+        .transpose()?;
+
+    let text_field_boosts = match params.text_field_boosts.as_deref() {
+        Some(s) => {
+            let mut boosts = std::collections::BTreeMap::new();
+            for pair in s.split(',') {
+                let trimmed = pair.trim();
+                let parsed = trimmed.split_once(':').and_then(|(field, boost)| {
+                    Some((field.trim(), boost.trim().parse::<f32>().ok()?))
+                });
+                let Some((field, boost)) = parsed else {
+                    return Err(AppError(CoreError::invalid_field(
+                        "text_field_boosts",
+                        format!("invalid entry '{trimmed}': expected field:boost"),
+                    )));
+                };
+                boosts.insert(field.to_string(), boost);
+            }
+            Some(boosts)
+        }
+        None => None,
+    };
+
     let request = RecallRequest {
         query: params.query,
         agent_id: params.agent_id,
@@ -244,6 +668,7 @@ pub async fn recall_handler(
         temporal_range: None,
         recency_half_life_hours: None,
         hybrid_weights,
+        hybrid,
         rrf_k: params.rrf_k,
         as_of: params.as_of,
         explain: params.explain,
@@ -270,22 +695,151 @@ pub async fn recall_handler(
         evidence_budget: None,
         retained_token_budget: None,
         domain_scope: None,
+        text_query_mode,
+        text_field_boosts,
+        with_snippets: params.with_snippets,
+        profile: params.profile,
+        include: params.include.map(|s| {
+            s.split(',')
+                .map(|i| i.trim().to_string())
+                .filter(|i| !i.is_empty())
+                .collect()
+        }),
+        thread_id: params.thread_id,
+        thread_isolation: params.thread_isolation,
+        exclude_query: params.exclude_query,
+        exclude_tags,
+        queries,
+        expand_query: params.expand_query,
+        channel: params.channel,
+        fields: params.fields.map(|s| {
+            s.split(',')
+                .map(|f| f.trim().to_string())
+                .filter(|f| !f.is_empty())
+                .collect()
+        }),
+        deadline_ms: params.deadline_ms,
+        agent_ids: params.agent_ids.map(|s| {
+            s.split(',')
+                .map(|a| a.trim().to_string())
+                .filter(|a| !a.is_empty())
+                .collect()
+        }),
+        min_relevance: params.min_relevance,
     };
 
     let response = engine.recall(request).await?;
     Ok(Json(response))
 }
 
-/// GET /v1/memories/:id -- retrieve a single memory by UUID.
+/// GET /v1/memories/:id/similar -- the memories closest to this one.
+pub async fn similar_handler(
+    State(engine): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<SimilarParams>,
+) -> Result<Json<SimilarResponse>, AppError> {
+    let memory_type = match params.memory_type.as_deref() {
+        Some(s) => Some(s.parse::<MemoryType>().map_err(|_| {
+            AppError(CoreError::invalid_field(
+                "memory_type",
+                format!(
+                    "invalid value '{s}': expected one of: episodic, semantic, procedural, working"
+                ),
+            ))
+        })?),
+        None => None,
+    };
+    let scope = match params.scope.as_deref() {
+        Some(s) => Some(s.parse::<Scope>().map_err(|_| {
+            AppError(CoreError::invalid_field(
+                "scope",
+                format!("invalid value '{s}': expected one of: private, shared, public, global"),
+            ))
+        })?),
+        None => None,
+    };
+
+    let mut request = SimilarRequest::new(id, params.k.unwrap_or(DEFAULT_SIMILAR_LIMIT));
+    request.agent_id = params.agent_id;
+    request.memory_type = memory_type;
+    request.scope = scope;
+    request.tags = params.tags.as_deref().map(|t| {
+        t.split(',')
+            .map(|s| s.trim().to_string())
+            .collect::<Vec<_>>()
+    });
+    request.min_importance = params.min_importance;
+    let response = engine.similar(request).await?;
+    Ok(Json(response))
+}
+
+/// GET /v1/graph/export?format=graphml|dot|json -- an agent's memories
+/// and the relations between them, for graph tools.
+pub async fn graph_export_handler(
+    State(engine): State<AppState>,
+    Query(params): Query<GraphExportParams>,
+) -> Result<Response, AppError> {
+    let format = match params.format.as_deref() {
+        Some(s) => s.parse::<GraphFormat>()?,
+        None => GraphFormat::default(),
+    };
+    let memory_type = match params.memory_type.as_deref() {
+        Some(s) => Some(s.parse::<MemoryType>().map_err(|_| {
+            AppError(CoreError::invalid_field(
+                "memory_type",
+                format!(
+                    "invalid value '{s}': expected one of: episodic, semantic, procedural, working"
+                ),
+            ))
+        })?),
+        None => None,
+    };
+    let split = |s: &str| {
+        s.split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+    };
+
+    let request = GraphExportRequest {
+        agent_id: params.agent_id,
+        thread_id: params.thread_id,
+        memory_type,
+        tags: params.tags.as_deref().map(split),
+        min_importance: params.min_importance,
+        relation_types: params.relation_types.as_deref().map(split),
+        min_weight: params.min_weight,
+        max_nodes: params.max_nodes,
+        max_edges: params.max_edges,
+    };
+    let graph = engine.export_graph(request).await?;
+    Ok((
+        [(axum::http::header::CONTENT_TYPE, format.content_type())],
+        graph.render(format)?,
+    )
+        .into_response())
+}
+
+/// GET /v1/memories/:id -- retrieve a single memory by UUID. Share link
+/// holders (see [`crate::ShareLinkGrant`]) see live, unquarantined
+/// memories only.
 pub async fn get_memory_handler(
     State(engine): State<AppState>,
     Path(id): Path<Uuid>,
+    grant: Option<Extension<crate::ShareLinkGrant>>,
 ) -> Result<Json<serde_json::Value>, AppError> {
-    let record = engine
+    let mut record = engine
         .storage
         .get_memory(id)
         .await?
+        .filter(|r| grant.is_none() || (r.deleted_at.is_none() && !r.quarantined))
         .ok_or_else(|| CoreError::NotFound(format!("memory {id} not found")))?;
+    engine.decrypt_record(&mut record);
+    // A link's reads are logged against the agent that minted it.
+    let reader = grant
+        .as_ref()
+        .map_or(&engine.default_agent_id, |g| &g.0.0.issued_by);
+    engine.alias_for_reader(reader, &mut record).await?;
 
     let value = serde_json::json!({
         "id": record.id,
@@ -315,10 +869,41 @@ pub async fn get_memory_handler(
         "quarantine_reason": record.quarantine_reason,
     });
 
+    engine
+        .record_memory_access(reader, AccessOperation::Get, &[id])
+        .await;
+
     Ok(Json(value))
 }
 
-/// DELETE /v1/memories/:id?strategy=soft_delete|hard_delete|decay|consolidate|archive
+/// POST /v1/memories/batch -- fetch several memories by id, e.g. the content
+/// of hits recalled with `fields`. Ids the caller cannot read come back in
+/// `missing`.
+pub async fn get_batch_handler(
+    State(engine): State<AppState>,
+    Json(request): Json<GetBatchRequest>,
+) -> Result<Json<GetBatchResponse>, AppError> {
+    let response = engine.get_batch(request).await?;
+    Ok(Json(response))
+}
+
+/// GET /v1/memories/:id/accesses?agent_id=...&limit=...&offset=... -- who
+/// read a memory, when and with what query (needs the access log enabled
+/// and `Admin` permission on the memory).
+pub async fn memory_accesses_handler(
+    State(engine): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<AccessLogParams>,
+) -> Result<Json<AccessLogResponse>, AppError> {
+    let mut request = AccessLogRequest::new(id);
+    request.agent_id = params.agent_id;
+    request.limit = params.limit;
+    request.offset = params.offset;
+    let response = engine.list_memory_accesses(request).await?;
+    Ok(Json(response))
+}
+
+/// DELETE /v1/memories/:id?strategy=soft_delete|hard_delete|decay|consolidate|archive[&force=true][&dry_run=true]
 pub async fn forget_handler(
     State(engine): State<AppState>,
     Path(id): Path<Uuid>,
@@ -333,10 +918,12 @@ pub async fn forget_handler(
             "archive" => ForgetStrategy::Archive,
             "redact" => ForgetStrategy::Redact,
             other => {
-                return Err(AppError(CoreError::Validation(format!(
-                    "invalid forget strategy '{}': expected one of: soft_delete, hard_delete, decay, consolidate, archive, redact",
-                    other
-                ))));
+                return Err(AppError(CoreError::invalid_field(
+                    "strategy",
+                    format!(
+                        "invalid value '{other}': expected one of: soft_delete, hard_delete, decay, consolidate, archive, redact"
+                    ),
+                )));
             }
         }),
         None => None,
@@ -347,17 +934,408 @@ pub async fn forget_handler(
         agent_id: params.agent_id,
         strategy,
         criteria: None,
+        force: params.force,
+        dry_run: params.dry_run,
     };
 
     let response = engine.forget(request).await?;
     Ok(Json(response))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct UpdateMemoryBody {
+    pub agent_id: Option<String>,
+    pub importance: Option<f32>,
+    pub tags: Option<Vec<String>>,
+    pub metadata: Option<serde_json::Value>,
+    pub expected_version: Option<u32>,
+}
+
+/// PATCH /v1/memories/:id -- update importance, tags or metadata. With
+/// `expected_version`, a memory updated since answers 409 `aborted` and
+/// the current record.
+pub async fn update_memory_handler(
+    State(engine): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<UpdateMemoryBody>,
+) -> Result<Json<UpdateResponse>, AppError> {
+    let request = UpdateRequest {
+        agent_id: body.agent_id,
+        importance: body.importance,
+        tags: body.tags,
+        metadata: body.metadata,
+        expected_version: body.expected_version,
+        ..UpdateRequest::new(id)
+    };
+    let response = engine.update(request).await?;
+    Ok(Json(response))
+}
+
+/// POST /v1/memories/:id/pin -- protect a memory from decay, expiry,
+/// consolidation and auto-forgetting.
+pub async fn pin_handler(
+    State(engine): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<PinParams>,
+) -> Result<Json<PinResponse>, AppError> {
+    let mut request = PinRequest::new(id, true);
+    request.agent_id = params.agent_id;
+    let response = engine.pin(request).await?;
+    Ok(Json(response))
+}
+
+/// DELETE /v1/memories/:id/pin -- remove the pin.
+pub async fn unpin_handler(
+    State(engine): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<PinParams>,
+) -> Result<Json<PinResponse>, AppError> {
+    let mut request = PinRequest::new(id, false);
+    request.agent_id = params.agent_id;
+    let response = engine.pin(request).await?;
+    Ok(Json(response))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetValidityBody {
+    pub valid_from: Option<String>,
+    pub valid_until: Option<String>,
+    pub agent_id: Option<String>,
+}
+
+/// PUT /v1/memories/:id/validity -- replace the fact validity window.
+pub async fn set_validity_handler(
+    State(engine): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<SetValidityBody>,
+) -> Result<Json<SetValidityResponse>, AppError> {
+    let mut request = SetValidityRequest::new(id);
+    request.valid_from = body.valid_from;
+    request.valid_until = body.valid_until;
+    request.agent_id = body.agent_id;
+    let response = engine.set_validity(request).await?;
+    Ok(Json(response))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CurationReviewBody {
+    pub agent_id: Option<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CurationPendingParams {
+    pub agent_id: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// GET /v1/curation/pending -- global memories awaiting the caller's review.
+pub async fn curation_pending_handler(
+    State(engine): State<AppState>,
+    Query(params): Query<CurationPendingParams>,
+) -> Result<Json<Vec<PendingMemory>>, AppError> {
+    let limit = params.limit.unwrap_or(100).min(1000);
+    let pending = engine.list_pending_curation(params.agent_id, limit).await?;
+    Ok(Json(pending))
+}
+
+/// POST /v1/curation/:id/approve -- publish a pending global memory.
+pub async fn curation_approve_handler(
+    State(engine): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<CurationReviewBody>,
+) -> Result<Json<CurationReviewResponse>, AppError> {
+    review_curation(engine, id, true, body).await
+}
+
+/// POST /v1/curation/:id/reject -- keep a pending global memory private
+/// to its author.
+pub async fn curation_reject_handler(
+    State(engine): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<CurationReviewBody>,
+) -> Result<Json<CurationReviewResponse>, AppError> {
+    review_curation(engine, id, false, body).await
+}
+
+async fn review_curation(
+    engine: AppState,
+    id: Uuid,
+    approve: bool,
+    body: CurationReviewBody,
+) -> Result<Json<CurationReviewResponse>, AppError> {
+    let mut request = CurationReviewRequest::new(id, approve);
+    request.agent_id = body.agent_id;
+    request.note = body.note;
+    let response = engine.review_curation(request).await?;
+    Ok(Json(response))
+}
+
+/// GET /v1/attestations/pending -- memories awaiting the caller's
+/// attestation. Takes the same query parameters as curation.
+pub async fn attestation_pending_handler(
+    State(engine): State<AppState>,
+    Query(params): Query<CurationPendingParams>,
+) -> Result<Json<Vec<PendingAttestation>>, AppError> {
+    let limit = params.limit.unwrap_or(100).min(1000);
+    let pending = engine
+        .list_pending_attestations(params.agent_id, limit)
+        .await?;
+    Ok(Json(pending))
+}
+
+/// POST /v1/attestations/:id/approve -- add the caller's approval.
+pub async fn attestation_approve_handler(
+    State(engine): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<CurationReviewBody>,
+) -> Result<Json<AttestResponse>, AppError> {
+    attest(engine, id, true, body).await
+}
+
+/// POST /v1/attestations/:id/reject -- keep a memory out of recall.
+pub async fn attestation_reject_handler(
+    State(engine): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<CurationReviewBody>,
+) -> Result<Json<AttestResponse>, AppError> {
+    attest(engine, id, false, body).await
+}
+
+async fn attest(
+    engine: AppState,
+    id: Uuid,
+    approve: bool,
+    body: CurationReviewBody,
+) -> Result<Json<AttestResponse>, AppError> {
+    let mut request = AttestRequest::new(id, approve);
+    request.agent_id = body.agent_id;
+    request.note = body.note;
+    let response = engine.attest(request).await?;
+    Ok(Json(response))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExtendTtlBody {
+    pub extra_seconds: u64,
+    pub agent_id: Option<String>,
+}
+
+/// POST /v1/memories/:id/ttl -- push a memory's expiry further out.
+pub async fn extend_ttl_handler(
+    State(engine): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<ExtendTtlBody>,
+) -> Result<Json<ExtendTtlResponse>, AppError> {
+    let mut request = ExtendTtlRequest::new(id, body.extra_seconds);
+    request.agent_id = body.agent_id;
+    let response = engine.extend_ttl(request).await?;
+    Ok(Json(response))
+}
+
+/// POST /v1/memories/:id/undelete -- restore a soft-deleted memory.
+pub async fn undelete_handler(
+    State(engine): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<UndeleteParams>,
+) -> Result<Json<UndeleteResponse>, AppError> {
+    let mut request = UndeleteRequest::new(id);
+    request.agent_id = params.agent_id;
+    let response = engine.undelete(request).await?;
+    Ok(Json(response))
+}
+
+/// GET /v1/trash?agent_id=...&limit=...&offset=... -- list soft-deleted memories.
+pub async fn trash_handler(
+    State(engine): State<AppState>,
+    Query(params): Query<TrashParams>,
+) -> Result<Json<TrashResponse>, AppError> {
+    let mut request = TrashRequest::new();
+    request.agent_id = params.agent_id;
+    request.limit = params.limit;
+    request.offset = params.offset;
+    let response = engine.list_trash(request).await?;
+    Ok(Json(response))
+}
+
+/// GET /v1/agents/:id/settings -- default retrieval settings for an agent.
+pub async fn get_agent_settings_handler(
+    State(engine): State<AppState>,
+    Path(agent_id): Path<String>,
+) -> Result<Json<RetrievalDefaults>, AppError> {
+    let defaults = engine.agent_retrieval_defaults(&agent_id).await?;
+    Ok(Json(defaults))
+}
+
+/// PUT /v1/agents/:id/settings -- replace an agent's default retrieval settings.
+pub async fn put_agent_settings_handler(
+    State(engine): State<AppState>,
+    Path(agent_id): Path<String>,
+    Json(body): Json<RetrievalDefaults>,
+) -> Result<Json<RetrievalDefaults>, AppError> {
+    let defaults = engine.set_agent_retrieval_defaults(&agent_id, body).await?;
+    Ok(Json(defaults))
+}
+
+/// POST /v1/recall_profiles -- create or replace a saved recall profile.
+pub async fn save_recall_profile_handler(
+    State(engine): State<AppState>,
+    Json(body): Json<SaveRecallProfileRequest>,
+) -> Result<Json<RecallProfile>, AppError> {
+    let profile = engine.save_recall_profile(body).await?;
+    Ok(Json(profile))
+}
+
+/// GET /v1/recall_profiles?agent_id=... -- list saved recall profiles.
+pub async fn list_recall_profiles_handler(
+    State(engine): State<AppState>,
+    Query(params): Query<RecallProfileParams>,
+) -> Result<Json<Vec<RecallProfile>>, AppError> {
+    let profiles = engine.list_recall_profiles(params.agent_id).await?;
+    Ok(Json(profiles))
+}
+
+/// GET /v1/recall_profiles/:name -- fetch one profile with its usage stats.
+pub async fn get_recall_profile_handler(
+    State(engine): State<AppState>,
+    Path(name): Path<String>,
+    Query(params): Query<RecallProfileParams>,
+) -> Result<Json<RecallProfile>, AppError> {
+    let profile = engine.get_recall_profile(params.agent_id, &name).await?;
+    Ok(Json(profile))
+}
+
+/// DELETE /v1/recall_profiles/:name -- delete a saved recall profile.
+pub async fn delete_recall_profile_handler(
+    State(engine): State<AppState>,
+    Path(name): Path<String>,
+    Query(params): Query<RecallProfileParams>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    engine.delete_recall_profile(params.agent_id, &name).await?;
+    Ok(Json(serde_json::json!({ "name": name, "deleted": true })))
+}
+
+/// POST /v1/recall_feedback -- mark the hits of recent recalls that proved
+/// useful, to calibrate relevance.
+pub async fn recall_feedback_handler(
+    State(engine): State<AppState>,
+    Json(body): Json<RecallFeedbackBody>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let labelled = engine.recall_feedback(body.agent_id, &body.memory_ids)?;
+    Ok(Json(serde_json::json!({ "recalls": labelled })))
+}
+
+/// GET /v1/recall_calibration -- how each strategy's scores map to
+/// relevance.
+pub async fn recall_calibration_handler(
+    State(engine): State<AppState>,
+) -> Json<Vec<StrategyCalibration>> {
+    Json(engine.score_calibration_report())
+}
+
+/// POST /v1/shadow_recall/feedback -- mark memories useful to score the
+/// shadow recall arms.
+pub async fn shadow_recall_feedback_handler(
+    State(engine): State<AppState>,
+    Json(body): Json<ShadowRecallFeedbackBody>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let applied = engine.shadow_recall_feedback(body.agent_id, &body.memory_ids)?;
+    Ok(Json(serde_json::json!({ "comparisons": applied })))
+}
+
+/// GET /v1/shadow_recall/report -- shadow recall arms ranked against the
+/// primary configuration.
+pub async fn shadow_recall_report_handler(
+    State(engine): State<AppState>,
+) -> Result<Json<ShadowRecallReport>, AppError> {
+    Ok(Json(engine.shadow_recall_report()?))
+}
+
+/// GET /v1/shadow_recall/comparisons?limit=... -- the newest shadow
+/// recall comparisons, newest first.
+pub async fn shadow_recall_comparisons_handler(
+    State(engine): State<AppState>,
+    Query(params): Query<ShadowRecallComparisonParams>,
+) -> Result<Json<Vec<ShadowComparison>>, AppError> {
+    Ok(Json(
+        engine.shadow_recall_comparisons(params.limit.unwrap_or(50))?,
+    ))
+}
+
+/// POST /v1/channels -- create a channel; the creator joins it.
+pub async fn create_channel_handler(
+    State(engine): State<AppState>,
+    Json(body): Json<CreateChannelRequest>,
+) -> Result<Json<Channel>, AppError> {
+    let channel = engine.create_channel(body).await?;
+    Ok(Json(channel))
+}
+
+/// GET /v1/channels?member=... -- list channels, optionally one agent's.
+pub async fn list_channels_handler(
+    State(engine): State<AppState>,
+    Query(params): Query<ChannelParams>,
+) -> Result<Json<Vec<Channel>>, AppError> {
+    let channels = engine.list_channels(params.member).await?;
+    Ok(Json(channels))
+}
+
+/// GET /v1/channels/:name/members?agent_id=... -- list a channel's members.
+pub async fn channel_members_handler(
+    State(engine): State<AppState>,
+    Path(name): Path<String>,
+    Query(params): Query<ChannelParams>,
+) -> Result<Json<Vec<ChannelMember>>, AppError> {
+    let members = engine.channel_members(&name, params.agent_id).await?;
+    Ok(Json(members))
+}
+
+/// POST /v1/channels/:name/members -- join a channel.
+pub async fn join_channel_handler(
+    State(engine): State<AppState>,
+    Path(name): Path<String>,
+    Json(body): Json<JoinChannelBody>,
+) -> Result<Json<ChannelMember>, AppError> {
+    let mut request = ChannelMembershipRequest::new(name);
+    request.agent_id = body.agent_id;
+    let member = engine.join_channel(request).await?;
+    Ok(Json(member))
+}
+
+/// DELETE /v1/channels/:name/members/:member_id?agent_id=... -- leave a
+/// channel, or remove a member as its creator.
+pub async fn leave_channel_handler(
+    State(engine): State<AppState>,
+    Path((name, member_id)): Path<(String, String)>,
+    Query(params): Query<ChannelParams>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let mut request = ChannelMembershipRequest::new(name.clone());
+    request.agent_id = params.agent_id;
+    request.member_id = Some(member_id.clone());
+    engine.leave_channel(request).await?;
+    Ok(Json(
+        serde_json::json!({ "channel": name, "agent_id": member_id, "removed": true }),
+    ))
+}
+
+/// PUT /v1/channels/:name/quota -- change a channel's memory cap.
+pub async fn set_channel_quota_handler(
+    State(engine): State<AppState>,
+    Path(name): Path<String>,
+    Json(body): Json<ChannelQuotaBody>,
+) -> Result<Json<Channel>, AppError> {
+    let mut request = SetChannelQuotaRequest::new(name, body.max_memories);
+    request.agent_id = body.agent_id;
+    let channel = engine.set_channel_quota(request).await?;
+    Ok(Json(channel))
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ForgetSubjectBody {
     pub subject_id: String,
     pub strategy: Option<String>,
     pub agent_id: Option<String>,
+    pub dry_run: Option<bool>,
 }
 
 /// POST /v1/forget_subject — GDPR / DPDPA-aligned subject erasure.
@@ -370,10 +1348,12 @@ pub async fn forget_subject_handler(
         "hard_delete" => ForgetStrategy::HardDelete,
         "soft_delete" => ForgetStrategy::SoftDelete,
         other => {
-            return Err(AppError(CoreError::Validation(format!(
-                "invalid forget_subject strategy '{}': expected one of: redact, hard_delete, soft_delete",
-                other
-            ))));
+            return Err(AppError(CoreError::invalid_field(
+                "strategy",
+                format!(
+                    "invalid value '{other}': expected one of: redact, hard_delete, soft_delete"
+                ),
+            )));
         }
     };
 
@@ -381,35 +1361,103 @@ pub async fn forget_subject_handler(
         subject_id: body.subject_id,
         agent_id: body.agent_id,
         strategy,
+        dry_run: body.dry_run,
     };
 
     let response = engine.forget_subject(request).await?;
     Ok(Json(response))
 }
 
+fn parse_share_permission(permission: Option<&str>) -> Result<Option<Permission>, AppError> {
+    match permission {
+        Some(s) => Ok(Some(s.parse::<Permission>().map_err(|_| {
+            AppError(CoreError::invalid_field(
+                "permission",
+                format!("invalid value '{s}': expected one of: read, write, delete, share, delegate, admin"),
+            ))
+        })?)),
+        None => Ok(None),
+    }
+}
+
 /// POST /v1/memories/:id/share
 pub async fn share_handler(
     State(engine): State<AppState>,
     Path(id): Path<Uuid>,
     Json(body): Json<ShareBody>,
 ) -> Result<Json<ShareResponse>, AppError> {
-    let permission = match body.permission.as_deref() {
-        Some(s) => Some(s.parse::<Permission>().map_err(|_| {
-            AppError(CoreError::Validation(format!(
-                "invalid permission '{}': expected one of: read, write, delete, share, delegate, admin",
-                s
-            )))
-        })?),
-        None => None,
+    let request = ShareRequest {
+        agent_id: body.agent_id,
+        target_agent_ids: body.target_agent_ids,
+        permission: parse_share_permission(body.permission.as_deref())?,
+        expires_in_hours: body.expires_in_hours,
+        dry_run: body.dry_run,
+        ..ShareRequest::new(id, body.target_agent_id)
     };
 
+    let response = engine.share(request).await?;
+    Ok(Json(response))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ShareLinkBody {
+    pub agent_id: Option<String>,
+    pub expires_in_hours: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ShareLinkParams {
+    pub agent_id: Option<String>,
+}
+
+/// POST /v1/memories/:id/links -- mint a share link: a token granting
+/// read access to the memory until it expires.
+pub async fn create_share_link_handler(
+    State(engine): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<ShareLinkBody>,
+) -> Result<Json<ShareLink>, AppError> {
+    let mut request = CreateShareLinkRequest::new(id);
+    request.agent_id = body.agent_id;
+    request.expires_in_hours = body.expires_in_hours;
+    let link = engine.create_share_link(request).await?;
+    Ok(Json(link))
+}
+
+/// DELETE /v1/memories/:id/links/:link_id -- revoke a share link.
+pub async fn revoke_share_link_handler(
+    State(engine): State<AppState>,
+    Path((id, link_id)): Path<(Uuid, Uuid)>,
+    Query(params): Query<ShareLinkParams>,
+) -> Result<Json<RevokeShareLinkResponse>, AppError> {
+    let mut request = RevokeShareLinkRequest::new(id, link_id);
+    request.agent_id = params.agent_id;
+    let response = engine.revoke_share_link(request).await?;
+    Ok(Json(response))
+}
+
+/// POST /v1/share -- share a list of memories, or every memory of the
+/// caller matching a filter, in one transaction. `dry_run` reports the
+/// memories that would be shared.
+pub async fn bulk_share_handler(
+    State(engine): State<AppState>,
+    Json(body): Json<BulkShareBody>,
+) -> Result<Json<ShareResponse>, AppError> {
+    if body.memory_ids.is_none() && body.filter.is_none() {
+        return Err(AppError(CoreError::invalid_field(
+            "memory_ids",
+            "either memory_ids or filter is required",
+        )));
+    }
     let request = ShareRequest {
-        memory_id: id,
         agent_id: body.agent_id,
-        target_agent_id: body.target_agent_id,
         target_agent_ids: body.target_agent_ids,
-        permission,
+        permission: parse_share_permission(body.permission.as_deref())?,
         expires_in_hours: body.expires_in_hours,
+        memory_ids: body.memory_ids,
+        filter: body.filter,
+        dry_run: body.dry_run,
+        ..ShareRequest::new(Uuid::nil(), body.target_agent_id)
     };
 
     let response = engine.share(request).await?;
@@ -434,6 +1482,123 @@ pub async fn consolidate_handler(
     Ok(Json(response))
 }
 
+/// POST /v1/maintenance/reembed -- re-embed an agent's memories with the
+/// current provider.
+pub async fn reembed_handler(
+    State(engine): State<AppState>,
+    Json(request): Json<ReembedRequest>,
+) -> Result<Json<ReembedResponse>, AppError> {
+    let response = engine.reembed(request).await?;
+    Ok(Json(response))
+}
+
+/// POST /v1/maintenance/decay -- archive or forget memories whose
+/// effective importance has decayed below the thresholds.
+pub async fn decay_pass_handler(
+    State(engine): State<AppState>,
+    Json(body): Json<DecayPassBody>,
+) -> Result<Json<DecayPassResult>, AppError> {
+    let result = engine
+        .run_decay_pass(
+            body.agent_id,
+            body.archive_threshold
+                .unwrap_or(lifecycle::DEFAULT_ARCHIVE_THRESHOLD),
+            body.forget_threshold
+                .unwrap_or(lifecycle::DEFAULT_FORGET_THRESHOLD),
+        )
+        .await?;
+    Ok(Json(result))
+}
+
+/// POST /v1/maintenance/consolidate -- fold tag clusters of episodic
+/// memories into semantic summaries.
+pub async fn consolidation_pass_handler(
+    State(engine): State<AppState>,
+    Json(body): Json<ConsolidationPassBody>,
+) -> Result<Json<ConsolidationResult>, AppError> {
+    let min_cluster_size = body
+        .min_cluster_size
+        .unwrap_or(lifecycle::DEFAULT_MIN_CLUSTER_SIZE);
+    if min_cluster_size == 0 {
+        return Err(AppError(CoreError::invalid_field(
+            "min_cluster_size",
+            "must be > 0",
+        )));
+    }
+    let result = engine
+        .run_consolidation(body.agent_id, min_cluster_size)
+        .await?;
+    Ok(Json(result))
+}
+
+/// POST /v1/maintenance/compress -- replace old episodic memories of each
+/// thread with semantic summaries and archive the originals.
+pub async fn compression_pass_handler(
+    State(engine): State<AppState>,
+    Json(request): Json<CompressionRequest>,
+) -> Result<Json<CompressionReport>, AppError> {
+    let report = engine.run_compression(request).await?;
+    Ok(Json(report))
+}
+
+/// POST /v1/maintenance/entity_summaries -- create or refresh one summary
+/// memory per frequently mentioned entity.
+pub async fn entity_summary_pass_handler(
+    State(engine): State<AppState>,
+    Json(request): Json<EntitySummaryRequest>,
+) -> Result<Json<EntitySummaryReport>, AppError> {
+    let report = engine.run_entity_summaries(request).await?;
+    Ok(Json(report))
+}
+
+/// GET /v1/entities/:name/summary -- the current summary of one entity.
+pub async fn entity_summary_handler(
+    State(engine): State<AppState>,
+    Path(name): Path<String>,
+    Query(params): Query<EntitySummaryParams>,
+) -> Result<Json<EntitySummary>, AppError> {
+    let summary = engine.entity_summary(params.agent_id, &name).await?;
+    Ok(Json(summary))
+}
+
+/// POST /v1/maintenance/cleanup[?dry_run=true] -- TTL sweep followed by a
+/// trash purge, access-log pruning and event archival.
+pub async fn cleanup_pass_handler(
+    State(engine): State<AppState>,
+    Query(request): Query<CleanupRequest>,
+) -> Result<Json<CleanupResult>, AppError> {
+    let result = engine.run_cleanup_with(request).await?;
+    Ok(Json(result))
+}
+
+/// POST /v1/maintenance/archive_events -- move events past the retention
+/// policy's hot window to cold storage.
+pub async fn archive_events_handler(
+    State(engine): State<AppState>,
+) -> Result<Json<EventArchiveReport>, AppError> {
+    let report = engine.archive_events().await?;
+    Ok(Json(report))
+}
+
+/// GET /v1/events/archives -- archived event segments, oldest first.
+pub async fn list_event_archives_handler(
+    State(engine): State<AppState>,
+    Query(params): Query<EventArchiveParams>,
+) -> Result<Json<Vec<EventArchiveSegment>>, AppError> {
+    let segments = engine.list_event_archives(params.agent_id).await?;
+    Ok(Json(segments))
+}
+
+/// GET /v1/events/archives/:id -- read one segment back from cold storage
+/// and verify it against its manifest.
+pub async fn restore_event_archive_handler(
+    State(engine): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<RestoredEventSegment>, AppError> {
+    let restored = engine.restore_event_archive(id).await?;
+    Ok(Json(restored))
+}
+
 /// POST /v1/branches
 pub async fn branch_handler(
     State(engine): State<AppState>,
@@ -461,14 +1626,132 @@ pub async fn replay_handler(
     Ok(Json(response))
 }
 
+/// POST /v1/prefetch -- warm the cache and vector index for a thread the
+/// agent is resuming.
+pub async fn prefetch_handler(
+    State(engine): State<AppState>,
+    Json(request): Json<PrefetchRequest>,
+) -> Result<Json<PrefetchResponse>, AppError> {
+    Ok(Json(engine.prefetch(request).await?))
+}
+
+/// GET /v1/replay/stream -- the replay as Server-Sent Events: one `state`
+/// event with the checkpoint and its memories, then one `event` event per
+/// thread event in logical-clock order, `pace_ms` apart. Errors are
+/// returned before the stream starts.
+pub async fn replay_stream_handler(
+    State(engine): State<AppState>,
+    Query(params): Query<ReplayStreamParams>,
+) -> Result<Sse<impl tokio_stream::Stream<Item = Result<SseEvent, Infallible>>>, AppError> {
+    let checkpoint_id = params
+        .checkpoint_id
+        .as_deref()
+        .map(|s| validation::parse_uuid("checkpoint_id", s))
+        .transpose()?;
+    let mut request = ReplayRequest::new(params.thread_id);
+    request.agent_id = params.agent_id;
+    request.checkpoint_id = checkpoint_id;
+    request.branch_name = params.branch_name;
+    request.as_of = params.as_of;
+    let pace = Duration::from_millis(params.pace_ms.unwrap_or(0)).min(MAX_REPLAY_PACE);
+    let frames = engine.replay(request).await?.into_frames();
+
+    let (tx, rx) = tokio::sync::mpsc::channel(16);
+    tokio::spawn(async move {
+        for (i, frame) in frames.into_iter().enumerate() {
+            if i > 1 && !pace.is_zero() {
+                tokio::time::sleep(pace).await;
+            }
+            let name = match frame {
+                ReplayFrame::State { .. } => "state",
+                ReplayFrame::Event { .. } => "event",
+            };
+            let data = serde_json::to_string(&frame).unwrap_or_else(|_| "{}".to_string());
+            if tx
+                .send(Ok(SseEvent::default().event(name).data(data)))
+                .await
+                .is_err()
+            {
+                // The client disconnected.
+                return;
+            }
+        }
+    });
+    Ok(Sse::new(ReceiverStream::new(rx)).keep_alive(KeepAlive::default()))
+}
+
+/// POST /v1/turns -- capture a user/assistant turn (with tool calls) as
+/// linked events plus an episodic memory.
+pub async fn turn_handler(
+    State(engine): State<AppState>,
+    Json(request): Json<TurnRequest>,
+) -> Result<Json<TurnResponse>, AppError> {
+    let response = engine.capture_turn(request).await?;
+    Ok(Json(response))
+}
+
+/// POST /v1/threads/:thread_id/messages -- append a message to a thread.
+pub async fn create_thread_message_handler(
+    State(engine): State<AppState>,
+    Path(thread_id): Path<String>,
+    Json(body): Json<CreateThreadMessageBody>,
+) -> Result<Json<CreateMessageResponse>, AppError> {
+    let mut request = CreateMessageRequest::new(thread_id, body.role, body.content);
+    request.agent_id = body.agent_id;
+    request.org_id = body.org_id;
+    request.run_id = body.run_id;
+    request.metadata = body.metadata;
+    request.capture_turn = body.capture_turn;
+    request.extract_facts = body.extract_facts;
+    let response = engine.create_thread_message(request).await?;
+    Ok(Json(response))
+}
+
+/// GET /v1/threads/:thread_id/messages?limit=&order=&after=&before=&agent_id=
+/// -- a page of a thread's messages.
+pub async fn list_thread_messages_handler(
+    State(engine): State<AppState>,
+    Path(thread_id): Path<String>,
+    Query(params): Query<ThreadMessagesParams>,
+) -> Result<Json<ListMessagesResponse>, AppError> {
+    let mut request = ListMessagesRequest::new(thread_id);
+    request.agent_id = params.agent_id;
+    request.limit = params.limit;
+    request.order = params.order;
+    request.after = params.after;
+    request.before = params.before;
+    let response = engine.list_thread_messages(request).await?;
+    Ok(Json(response))
+}
+
+/// DELETE /v1/threads/:thread_id/messages/:message_id?agent_id=... -- remove
+/// a message from the thread's listings.
+pub async fn delete_thread_message_handler(
+    State(engine): State<AppState>,
+    Path((thread_id, message_id)): Path<(String, Uuid)>,
+    Query(params): Query<ThreadMessagesParams>,
+) -> Result<Json<DeleteMessageResponse>, AppError> {
+    let mut request = DeleteMessageRequest::new(thread_id, message_id);
+    request.agent_id = params.agent_id;
+    let response = engine.delete_thread_message(request).await?;
+    Ok(Json(response))
+}
+
 /// POST /v1/verify -- verify hash chain integrity.
 pub async fn verify_handler(
     State(engine): State<AppState>,
     Json(body): Json<VerifyBody>,
 ) -> Result<Json<serde_json::Value>, AppError> {
-    let result = engine
-        .verify_integrity(body.agent_id, body.thread_id.as_deref())
+    let clock_skew = engine
+        .detect_clock_skew(body.agent_id.clone(), body.thread_id.as_deref())
         .await?;
+    let mut request = VerifyRequest::new();
+    request.agent_id = body.agent_id;
+    request.thread_id = body.thread_id;
+    request.incremental = body.incremental;
+    request.max_records = body.max_records;
+    let verified = engine.verify_memory_chain(request).await?;
+    let result = verified.result;
 
     let response = serde_json::json!({
         "valid": result.valid,
@@ -477,6 +1760,9 @@ pub async fn verify_handler(
         "first_broken_at": result.first_broken_at.map(|id| id.to_string()),
         "error_message": result.error_message,
         "status": if result.valid { "verified" } else { "integrity_violation" },
+        "complete": verified.complete,
+        "checkpoint": verified.checkpoint,
+        "clock_skew": clock_skew,
     });
 
     Ok(Json(response))
@@ -548,31 +1834,22 @@ pub async fn delegate_handler(
         .unwrap_or_else(|| engine.default_agent_id.clone());
 
     let scope = if let Some(ref ids) = body.memory_ids {
-        let parsed: std::result::Result<Vec<Uuid>, _> =
-            ids.iter().map(|s| Uuid::parse_str(s)).collect();
-        match parsed {
-            Ok(uuids) => {
-                // Verify caller has Delegate permission on each memory
-                for mid in &uuids {
-                    let has_perm = engine
-                        .storage
-                        .check_permission(*mid, &caller_agent_id, Permission::Delegate)
-                        .await?;
-                    if !has_perm {
-                        return Err(AppError(CoreError::PermissionDenied(format!(
-                            "agent '{}' lacks delegate permission on memory {}",
-                            caller_agent_id, mid
-                        ))));
-                    }
-                }
-                DelegationScope::ByMemoryId(uuids)
-            }
-            Err(e) => {
-                return Err(AppError(CoreError::Validation(format!(
-                    "invalid UUID in memory_ids: {e}"
+        engine.limits().check_batch("memory_ids", ids.len())?;
+        let uuids = validation::parse_uuids("memory_ids", ids)?;
+        // Verify caller has Delegate permission on each memory
+        for mid in &uuids {
+            let has_perm = engine
+                .storage
+                .check_permission(*mid, &caller_agent_id, Permission::Delegate)
+                .await?;
+            if !has_perm {
+                return Err(AppError(CoreError::PermissionDenied(format!(
+                    "agent '{}' lacks delegate permission on memory {}",
+                    caller_agent_id, mid
                 ))));
             }
         }
+        DelegationScope::ByMemoryId(uuids)
     } else if let Some(ref tags) = body.tags {
         DelegationScope::ByTag(tags.clone())
     } else {
@@ -580,9 +1857,8 @@ pub async fn delegate_handler(
     };
 
     let now = chrono::Utc::now();
-    let expires_at = body
-        .expires_in_hours
-        .map(|h| (now + chrono::Duration::seconds((h * 3600.0) as i64)).to_rfc3339());
+    let expires_at =
+        validation::expiry_after_hours("expires_in_hours", body.expires_in_hours, now)?;
 
     let delegation = Delegation {
         id: Uuid::now_v7(),
@@ -611,9 +1887,104 @@ pub async fn delegate_handler(
     Ok(Json(response))
 }
 
-/// GET /v1/health
-pub async fn health_handler() -> Json<serde_json::Value> {
-    Json(serde_json::json!({"status": "ok"}))
+/// GET /v1/health, GET /v1/health/live -- liveness; never touches a dependency.
+pub async fn health_handler() -> Json<HealthReport> {
+    Json(health::liveness())
+}
+
+/// GET /v1/health/ready -- probe every dependency; `503` when a required one
+/// is down so orchestrators stop routing to this instance.
+pub async fn readiness_handler(State(engine): State<AppState>) -> (StatusCode, Json<HealthReport>) {
+    let report = engine.readiness().await;
+    let status = if report.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report))
+}
+
+/// GET /metrics -- Prometheus text exposition of the engine's counters.
+/// Cache and latency-SLO series are omitted when those features are off.
+pub async fn metrics_handler(State(engine): State<AppState>) -> Response {
+    let mut series: Vec<(&str, &str, &str, u64)> = Vec::new();
+    if let Some(ref cache) = engine.cache {
+        let stats = cache.stats();
+        series.extend([
+            (
+                "mnemo_cache_hits_total",
+                "counter",
+                "Cache lookups that found a live entry.",
+                stats.hits,
+            ),
+            (
+                "mnemo_cache_misses_total",
+                "counter",
+                "Cache lookups that fell through to storage.",
+                stats.misses,
+            ),
+            (
+                "mnemo_cache_evictions_total",
+                "counter",
+                "Entries dropped to make room.",
+                stats.evictions,
+            ),
+            (
+                "mnemo_cache_expirations_total",
+                "counter",
+                "Entries dropped because their TTL ran out.",
+                stats.expirations,
+            ),
+            (
+                "mnemo_cache_entries",
+                "gauge",
+                "Entries currently cached.",
+                stats.entries as u64,
+            ),
+            (
+                "mnemo_cache_capacity",
+                "gauge",
+                "Most entries the cache holds.",
+                stats.capacity as u64,
+            ),
+            (
+                "mnemo_cache_bytes",
+                "gauge",
+                "Approximate memory held by cached records.",
+                stats.bytes as u64,
+            ),
+        ]);
+    }
+    if let Some(ref slo) = engine.latency_slo {
+        series.extend([
+            (
+                "mnemo_recall_degradation_level",
+                "gauge",
+                "Current rung of the recall latency ladder (0 = full quality).",
+                slo.level() as u64,
+            ),
+            (
+                "mnemo_recall_degraded_total",
+                "counter",
+                "Recalls that ran below full quality.",
+                slo.degraded_recalls(),
+            ),
+        ]);
+    }
+    let mut body = String::new();
+    for (name, kind, help, value) in series {
+        body.push_str(&format!(
+            "# HELP {name} {help}\n# TYPE {name} {kind}\n{name} {value}\n"
+        ));
+    }
+    (
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        body,
+    )
+        .into_response()
 }
 
 // ---------------------------------------------------------------------------
@@ -708,6 +2079,34 @@ fn extract_genai_fields(span: &serde_json::Value) -> GenAiFields {
     }
 }
 
+/// POST /v1/ingest/documents -- chunk documents and remember each chunk
+/// with its source provenance.
+pub async fn ingest_documents_handler(
+    State(engine): State<AppState>,
+    Json(request): Json<IngestRequest>,
+) -> Result<Json<IngestResponse>, AppError> {
+    let response = engine.ingest_documents(request).await?;
+    Ok(Json(response))
+}
+
+/// POST /v1/ingest/url -- fetch a web page and remember its main content,
+/// optionally re-fetching it on a schedule.
+pub async fn ingest_url_handler(
+    State(engine): State<AppState>,
+    Json(request): Json<UrlIngestRequest>,
+) -> Result<Json<UrlIngestResponse>, AppError> {
+    let response = engine.ingest_url(request).await?;
+    Ok(Json(response))
+}
+
+/// GET /v1/ingest/url -- every ingested page with its schedule and chunks.
+pub async fn list_watched_pages_handler(
+    State(engine): State<AppState>,
+) -> Result<Json<Vec<WatchedPage>>, AppError> {
+    let pages = engine.watched_pages().await?;
+    Ok(Json(pages))
+}
+
 /// POST /v1/ingest/otlp -- ingest simplified OTLP JSON spans as agent events.
 pub async fn otlp_ingest_handler(
     State(engine): State<AppState>,
@@ -817,6 +2216,7 @@ pub async fn otlp_ingest_handler(
                 let content_hash =
                     compute_content_hash(&payload.to_string(), &agent_id, &timestamp);
 
+                let logical_clock = engine.next_logical_clock(&agent_id).await;
                 let event = AgentEvent {
                     id: Uuid::now_v7(),
                     agent_id,
@@ -833,10 +2233,11 @@ pub async fn otlp_ingest_handler(
                     latency_ms,
                     cost_usd: genai.cost_usd,
                     timestamp,
-                    logical_clock: 0,
+                    logical_clock,
                     content_hash,
                     prev_hash: None,
                     embedding: None,
+                    request_id: mnemo_core::request_id::current(),
                 };
 
                 engine.storage.insert_event(&event).await?;
@@ -889,4 +2290,38 @@ mod tests {
         assert!(fields.model.is_none());
         assert!(fields.tokens_input.is_none());
     }
+
+    #[test]
+    fn test_error_status_and_retry_hints() {
+        let cases = [
+            (CoreError::Conflict("taken".into()), StatusCode::CONFLICT),
+            (
+                CoreError::QuotaExceeded("full".into()),
+                StatusCode::TOO_MANY_REQUESTS,
+            ),
+            (
+                CoreError::Unavailable("refused".into()),
+                StatusCode::SERVICE_UNAVAILABLE,
+            ),
+            (
+                CoreError::Timeout("slow".into()),
+                StatusCode::GATEWAY_TIMEOUT,
+            ),
+            (
+                CoreError::IndexCorruption("bad mappings".into()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+        ];
+        for (error, status) in cases {
+            assert_eq!(AppError(error).into_response().status(), status);
+        }
+
+        let response = AppError(CoreError::RateLimited {
+            message: "slow down".into(),
+            retry_after_secs: Some(7),
+        })
+        .into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()["retry-after"], "7");
+    }
 }
@@ -7,22 +7,28 @@ use axum::extract::{DefaultBodyLimit, Request, State};
 use axum::http::{Method, StatusCode, header};
 use axum::middleware::{self, Next};
 use axum::response::Response;
-use axum::routing::{get, post};
+use axum::routing::{delete, get, post, put};
 use mnemo_core::query::MnemoEngine;
+use mnemo_core::query::share_link::{ShareLinkClaims, TOKEN_PREFIX};
 use tower_http::cors::{AllowOrigin, CorsLayer};
 
 /// Construct the full Axum router for the Mnemo REST API, reading the
 /// bearer-token secret from the `MNEMO_AUTH_TOKEN` environment variable.
 ///
 /// When `MNEMO_AUTH_TOKEN` is set (non-empty), every request except
-/// `/v1/health` and CORS preflight (`OPTIONS`) must carry a matching
+/// the `/v1/health*` probes and CORS preflight (`OPTIONS`) must carry a matching
 /// `Authorization: Bearer <token>` header or it is rejected with `401`. When
 /// the variable is unset, the server runs **open** and logs a warning — the
 /// floor for "don't run an unauthenticated memory server" is opt-in but loud.
 ///
-/// All routes are nested under `/v1/` and the router carries
+/// All routes except the Prometheus `/metrics` scrape are nested under
+/// `/v1/` and the router carries
 /// `Arc<MnemoEngine>` as shared state. CORS is restrictive by default
 /// (localhost only); set `MNEMO_CORS_ORIGINS` to override.
+///
+/// Every response carries an `x-request-id` header: the caller's own, when
+/// it sent a usable one, or a generated ID. The same ID tags the request's
+/// tracing span and the events it records.
 pub fn router(engine: Arc<MnemoEngine>) -> Router {
     let token = std::env::var("MNEMO_AUTH_TOKEN")
         .ok()
@@ -41,16 +47,172 @@ pub fn router_with_auth(engine: Arc<MnemoEngine>, auth_token: Option<String>) ->
             "/v1/memories",
             post(handlers::remember_handler).get(handlers::recall_handler),
         )
+        .route("/v1/memories/batch", post(handlers::get_batch_handler))
+        .route("/v1/memories/bulk", post(handlers::remember_batch_handler))
+        .route(
+            "/v1/memories/ephemeral",
+            post(handlers::remember_ephemeral_handler),
+        )
         .route(
             "/v1/memories/{id}",
-            get(handlers::get_memory_handler).delete(handlers::forget_handler),
+            get(handlers::get_memory_handler)
+                .patch(handlers::update_memory_handler)
+                .delete(handlers::forget_handler),
+        )
+        .route(
+            "/v1/memories/{id}/accesses",
+            get(handlers::memory_accesses_handler),
         )
         .route("/v1/memories/{id}/share", post(handlers::share_handler))
+        .route(
+            "/v1/memories/{id}/links",
+            post(handlers::create_share_link_handler),
+        )
+        .route(
+            "/v1/memories/{id}/links/{link_id}",
+            delete(handlers::revoke_share_link_handler),
+        )
+        .route("/v1/share", post(handlers::bulk_share_handler))
+        .route(
+            "/v1/memories/{id}/pin",
+            post(handlers::pin_handler).delete(handlers::unpin_handler),
+        )
+        .route("/v1/memories/{id}/ttl", post(handlers::extend_ttl_handler))
+        .route(
+            "/v1/memories/{id}/validity",
+            put(handlers::set_validity_handler),
+        )
+        .route("/v1/memories/{id}/similar", get(handlers::similar_handler))
+        .route(
+            "/v1/memories/{id}/undelete",
+            post(handlers::undelete_handler),
+        )
+        .route("/v1/trash", get(handlers::trash_handler))
+        .route("/v1/graph/export", get(handlers::graph_export_handler))
+        .route(
+            "/v1/curation/pending",
+            get(handlers::curation_pending_handler),
+        )
+        .route(
+            "/v1/curation/{id}/approve",
+            post(handlers::curation_approve_handler),
+        )
+        .route(
+            "/v1/curation/{id}/reject",
+            post(handlers::curation_reject_handler),
+        )
+        .route(
+            "/v1/attestations/pending",
+            get(handlers::attestation_pending_handler),
+        )
+        .route(
+            "/v1/attestations/{id}/approve",
+            post(handlers::attestation_approve_handler),
+        )
+        .route(
+            "/v1/attestations/{id}/reject",
+            post(handlers::attestation_reject_handler),
+        )
+        .route(
+            "/v1/agents/{id}/settings",
+            get(handlers::get_agent_settings_handler).put(handlers::put_agent_settings_handler),
+        )
+        .route(
+            "/v1/recall_profiles",
+            post(handlers::save_recall_profile_handler).get(handlers::list_recall_profiles_handler),
+        )
+        .route(
+            "/v1/recall_profiles/{name}",
+            get(handlers::get_recall_profile_handler)
+                .delete(handlers::delete_recall_profile_handler),
+        )
+        .route(
+            "/v1/recall_feedback",
+            post(handlers::recall_feedback_handler),
+        )
+        .route(
+            "/v1/recall_calibration",
+            get(handlers::recall_calibration_handler),
+        )
+        .route(
+            "/v1/shadow_recall/feedback",
+            post(handlers::shadow_recall_feedback_handler),
+        )
+        .route(
+            "/v1/shadow_recall/report",
+            get(handlers::shadow_recall_report_handler),
+        )
+        .route(
+            "/v1/shadow_recall/comparisons",
+            get(handlers::shadow_recall_comparisons_handler),
+        )
+        .route(
+            "/v1/channels",
+            post(handlers::create_channel_handler).get(handlers::list_channels_handler),
+        )
+        .route(
+            "/v1/channels/{name}/members",
+            get(handlers::channel_members_handler).post(handlers::join_channel_handler),
+        )
+        .route(
+            "/v1/channels/{name}/members/{member_id}",
+            delete(handlers::leave_channel_handler),
+        )
+        .route(
+            "/v1/channels/{name}/quota",
+            put(handlers::set_channel_quota_handler),
+        )
         .route("/v1/checkpoints", post(handlers::checkpoint_handler))
         .route("/v1/consolidate", post(handlers::consolidate_handler))
+        .route("/v1/maintenance/decay", post(handlers::decay_pass_handler))
+        .route(
+            "/v1/maintenance/consolidate",
+            post(handlers::consolidation_pass_handler),
+        )
+        .route(
+            "/v1/maintenance/compress",
+            post(handlers::compression_pass_handler),
+        )
+        .route(
+            "/v1/maintenance/entity_summaries",
+            post(handlers::entity_summary_pass_handler),
+        )
+        .route(
+            "/v1/entities/{name}/summary",
+            get(handlers::entity_summary_handler),
+        )
+        .route(
+            "/v1/maintenance/cleanup",
+            post(handlers::cleanup_pass_handler),
+        )
+        .route("/v1/maintenance/reembed", post(handlers::reembed_handler))
+        .route(
+            "/v1/maintenance/archive_events",
+            post(handlers::archive_events_handler),
+        )
+        .route(
+            "/v1/events/archives",
+            get(handlers::list_event_archives_handler),
+        )
+        .route(
+            "/v1/events/archives/{id}",
+            get(handlers::restore_event_archive_handler),
+        )
         .route("/v1/branches", post(handlers::branch_handler))
         .route("/v1/merge", post(handlers::merge_handler))
         .route("/v1/replay", post(handlers::replay_handler))
+        .route("/v1/replay/stream", get(handlers::replay_stream_handler))
+        .route("/v1/prefetch", post(handlers::prefetch_handler))
+        .route("/v1/turns", post(handlers::turn_handler))
+        .route(
+            "/v1/threads/{thread_id}/messages",
+            post(handlers::create_thread_message_handler)
+                .get(handlers::list_thread_messages_handler),
+        )
+        .route(
+            "/v1/threads/{thread_id}/messages/{message_id}",
+            delete(handlers::delete_thread_message_handler),
+        )
         .route("/v1/verify", post(handlers::verify_handler))
         .route(
             "/v1/compliance/trajectory_audit",
@@ -59,7 +221,22 @@ pub fn router_with_auth(engine: Arc<MnemoEngine>, auth_token: Option<String>) ->
         .route("/v1/delegate", post(handlers::delegate_handler))
         .route("/v1/forget_subject", post(handlers::forget_subject_handler))
         .route("/v1/ingest/otlp", post(handlers::otlp_ingest_handler))
+        .route(
+            "/v1/ingest/documents",
+            post(handlers::ingest_documents_handler),
+        )
+        .route(
+            "/v1/ingest/url",
+            get(handlers::list_watched_pages_handler).post(handlers::ingest_url_handler),
+        )
         .route("/v1/health", get(handlers::health_handler))
+        .route("/v1/health/live", get(handlers::health_handler))
+        .route("/v1/health/ready", get(handlers::readiness_handler))
+        .route("/metrics", get(handlers::metrics_handler))
+        .layer(middleware::from_fn_with_state(
+            engine.clone(),
+            track_activity,
+        ))
         .layer(DefaultBodyLimit::max(2 * 1024 * 1024)) // 2 MB max request body
         .layer(cors)
         .layer(tower_http::trace::TraceLayer::new_for_http());
@@ -86,18 +263,134 @@ pub fn router_with_auth(engine: Arc<MnemoEngine>, auth_token: Option<String>) ->
         }
     };
 
-    app.with_state(engine)
+    // Outside the bearer gate, so link holders never need the secret.
+    let app = app.layer(middleware::from_fn_with_state(
+        engine.clone(),
+        admit_share_links,
+    ));
+
+    // Outermost, so even a rejected request gets an ID.
+    app.layer(middleware::from_fn(assign_request_id))
+        .with_state(engine)
+}
+
+/// Set on requests admitted by a share link; the claims of the link.
+#[derive(Debug, Clone)]
+pub struct ShareLinkGrant(pub ShareLinkClaims);
+
+/// Axum middleware: admit share link holders (see
+/// [`mnemo_core::query::share_link`]). A request carrying a link token, as
+/// `Authorization: Bearer <token>` or a `link` query parameter, may only
+/// `GET /v1/memories/{id}` of the link's memory, and only while the link
+/// is genuine, unexpired and not revoked; it is answered `401` otherwise.
+/// Admitted requests skip the bearer gate and carry a [`ShareLinkGrant`].
+/// Requests without a link token pass through untouched.
+async fn admit_share_links(
+    State(engine): State<Arc<MnemoEngine>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let Some(token) = share_link_token(&req) else {
+        return Ok(next.run(req).await);
+    };
+    let target = (req.method() == Method::GET)
+        .then(|| req.uri().path().strip_prefix("/v1/memories/"))
+        .flatten()
+        .and_then(|id| id.parse::<uuid::Uuid>().ok());
+    let claims = match engine.verify_share_link(&token).await {
+        Ok(claims) if target == Some(claims.memory_id) => claims,
+        Ok(_) => return Err(StatusCode::UNAUTHORIZED),
+        Err(e) => {
+            tracing::debug!(error = %e, "share link refused");
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+    };
+    req.extensions_mut().insert(ShareLinkGrant(claims));
+    Ok(next.run(req).await)
+}
+
+/// The share link token of a request, from its bearer header or `link`
+/// query parameter.
+fn share_link_token(req: &Request) -> Option<String> {
+    let from_header = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(str::trim);
+    let from_query = req
+        .uri()
+        .query()
+        .and_then(|query| query.split('&').find_map(|pair| pair.strip_prefix("link=")));
+    from_header
+        .filter(|t| t.starts_with(TOKEN_PREFIX))
+        .or(from_query)
+        .map(str::to_string)
+}
+
+/// Axum middleware: resolve the request's correlation ID (see
+/// [`mnemo_core::request_id`]), run the request inside its scope and a
+/// tracing span that carries it, and echo it in the `x-request-id`
+/// response header.
+async fn assign_request_id(req: Request, next: Next) -> Response {
+    let request_id = mnemo_core::request_id::accept(
+        req.headers()
+            .get(mnemo_core::request_id::HEADER)
+            .and_then(|v| v.to_str().ok()),
+    );
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %req.method(),
+        path = %req.uri().path(),
+    );
+    let mut response = mnemo_core::request_id::scope(
+        request_id.clone(),
+        tracing::Instrument::instrument(next.run(req), span),
+    )
+    .await;
+    if let Ok(value) = header::HeaderValue::from_str(&request_id) {
+        response
+            .headers_mut()
+            .insert(mnemo_core::request_id::HEADER, value);
+    }
+    response
+}
+
+/// Axum middleware: hold an activity guard on the engine's shared
+/// [`ActivityTracker`](mnemo_core::activity::ActivityTracker) for the whole
+/// request so the idle-timeout watchdog sees REST traffic. Health probes and
+/// metrics scrapes are excluded — they must not keep an idle server awake.
+async fn track_activity(
+    State(engine): State<Arc<MnemoEngine>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let _guard = match engine.activity_tracker {
+        Some(ref tracker)
+            if !is_health_path(req.uri().path()) && req.uri().path() != "/metrics" =>
+        {
+            Some(tracker.begin())
+        }
+        _ => None,
+    };
+    next.run(req).await
 }
 
 /// Axum middleware: require `Authorization: Bearer <expected>` on every request
-/// except `/v1/health` and CORS preflight (`OPTIONS`). Returns `401` otherwise.
+/// except the `/v1/health*` probes, CORS preflight (`OPTIONS`) and requests
+/// admitted by a share link. Returns `401` otherwise.
 async fn require_bearer(
     State(expected): State<Arc<String>>,
     req: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    // Liveness probes and CORS preflight must not require the secret.
-    if req.method() == Method::OPTIONS || req.uri().path() == "/v1/health" {
+    // Health probes, CORS preflight and share link holders must not
+    // require the secret.
+    if req.method() == Method::OPTIONS
+        || is_health_path(req.uri().path())
+        || req.extensions().get::<ShareLinkGrant>().is_some()
+    {
         return Ok(next.run(req).await);
     }
     let provided = req
@@ -111,15 +404,29 @@ async fn require_bearer(
     }
 }
 
+/// `/v1/health`, `/v1/health/live` and `/v1/health/ready`.
+fn is_health_path(path: &str) -> bool {
+    path == "/v1/health" || path.starts_with("/v1/health/")
+}
+
 fn build_cors_layer() -> CorsLayer {
     use axum::http::{HeaderName, Method};
 
     let base = CorsLayer::new()
-        .allow_methods([Method::GET, Method::POST, Method::DELETE, Method::OPTIONS])
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+            Method::OPTIONS,
+        ])
         .allow_headers([
             HeaderName::from_static("content-type"),
             HeaderName::from_static("authorization"),
+            HeaderName::from_static(mnemo_core::request_id::HEADER),
         ])
+        .expose_headers([HeaderName::from_static(mnemo_core::request_id::HEADER)])
         .max_age(std::time::Duration::from_secs(3600));
 
     match std::env::var("MNEMO_CORS_ORIGINS") {
@@ -77,6 +77,36 @@ async fn test_rest_remember_endpoint() {
     assert!(json["content_hash"].is_string());
 }
 
+#[tokio::test]
+async fn test_rest_validation_error_names_the_field() {
+    let engine = create_test_engine();
+    let app = mnemo_rest::router(engine);
+
+    let body = serde_json::json!({
+        "content": "importance out of range",
+        "importance": 1.5
+    });
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/memories")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"]["code"], "invalid_argument");
+    assert_eq!(json["error"]["retryable"], false);
+    assert_eq!(json["error"]["fields"][0]["path"], "importance");
+    assert!(json["error"]["fields"][0]["message"].is_string());
+}
+
 #[tokio::test]
 async fn test_rest_recall_endpoint() {
     let engine = create_test_engine();
@@ -99,6 +129,10 @@ async fn test_rest_recall_endpoint() {
             ttl_seconds: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -123,6 +157,35 @@ async fn test_rest_recall_endpoint() {
     assert!(json["memories"].is_array());
 }
 
+#[tokio::test]
+async fn test_rest_recall_hybrid_weights_are_validated() {
+    let engine = create_test_engine();
+    engine
+        .remember(mnemo_core::query::remember::RememberRequest::new(
+            "hybrid preset content".to_string(),
+        ))
+        .await
+        .unwrap();
+    let app = mnemo_rest::router(engine);
+    let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+    let response = app
+        .clone()
+        .oneshot(get("/v1/memories?query=hybrid+preset&hybrid=precision"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .oneshot(get("/v1/memories?query=hybrid+preset&hybrid=vector:-1"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"]["fields"][0]["path"], "hybrid.vector");
+}
+
 #[tokio::test]
 async fn test_rest_forget_endpoint() {
     let engine = create_test_engine();
@@ -145,6 +208,10 @@ async fn test_rest_forget_endpoint() {
             ttl_seconds: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -191,6 +258,10 @@ async fn test_rest_get_memory_endpoint() {
             ttl_seconds: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -237,6 +308,10 @@ async fn test_rest_verify_endpoint() {
             ttl_seconds: None,
             decay_rate: None,
             created_by: None,
+            pinned: None,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
         })
         .await
         .unwrap();
@@ -287,6 +362,53 @@ async fn test_rest_not_found_memory() {
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
 }
 
+#[tokio::test]
+async fn test_rest_update_rejects_stale_versions() {
+    let engine = create_test_engine();
+    let mem = engine
+        .remember(mnemo_core::query::remember::RememberRequest::new(
+            "Updated via PATCH".to_string(),
+        ))
+        .await
+        .unwrap();
+    let app = mnemo_rest::router(engine);
+
+    let patch = |body: serde_json::Value| {
+        Request::builder()
+            .method("PATCH")
+            .uri(format!("/v1/memories/{}", mem.id))
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_string(&body).unwrap()))
+            .unwrap()
+    };
+
+    let response = app
+        .clone()
+        .oneshot(patch(
+            serde_json::json!({"importance": 0.9, "expected_version": 1}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["version"], 2);
+
+    let response = app
+        .oneshot(patch(
+            serde_json::json!({"tags": ["stale"], "expected_version": 1}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"]["code"], "aborted");
+    assert_eq!(json["error"]["actual"], 2);
+    assert_eq!(json["error"]["current"]["version"], 2);
+    assert_eq!(json["error"]["current"]["content"], "Updated via PATCH");
+}
+
 // --- Bearer-token auth (MNEMO_AUTH_TOKEN floor) --------------------------
 
 #[tokio::test]
@@ -367,3 +489,558 @@ async fn no_token_runs_open() {
         .unwrap();
     assert_eq!(response.status(), StatusCode::OK);
 }
+
+#[tokio::test]
+async fn test_rest_turn_endpoint() {
+    let engine = create_test_engine();
+    let app = mnemo_rest::router(engine);
+
+    let body = serde_json::json!({
+        "user_message": "Find me a flight to Lisbon",
+        "assistant_message": "The cheapest option departs at 09:10.",
+        "tool_calls": [{"name": "flight_search", "arguments": {"to": "LIS"}}],
+        "tokens_input": 200,
+        "tokens_output": 40,
+        "cost_usd": 0.003
+    });
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/turns")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(json["user_event_id"].is_string());
+    assert!(json["assistant_event_id"].is_string());
+    assert!(json["memory_id"].is_string());
+    assert_eq!(json["tool_event_ids"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_rest_trash_and_undelete() {
+    let engine = create_test_engine();
+    let id = engine
+        .remember(mnemo_core::query::remember::RememberRequest::new(
+            "User's old phone number".to_string(),
+        ))
+        .await
+        .unwrap()
+        .id;
+    engine
+        .forget(mnemo_core::query::forget::ForgetRequest::new(vec![id]))
+        .await
+        .unwrap();
+    let app = mnemo_rest::router(engine);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/v1/trash")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["total"], 1);
+    assert_eq!(json["memories"][0]["id"], id.to_string());
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/v1/memories/{id}/undelete"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["memory_id"], id.to_string());
+}
+
+#[tokio::test]
+async fn test_rest_agent_settings() {
+    let engine = create_test_engine();
+    let app = mnemo_rest::router(engine);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri("/v1/agents/agent-1/settings")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    r#"{"recency_half_life_hours": 24.0, "strategy": "lexical", "limit": 5}"#,
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/v1/agents/agent-1/settings")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["strategy"], "lexical");
+    assert_eq!(json["limit"], 5);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri("/v1/agents/agent-1/settings")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"limit": 0}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_rest_readiness_reports_dependencies() {
+    let engine = create_test_engine();
+    let app = mnemo_rest::router(engine);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/v1/health/ready")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["ready"], true);
+    let names: Vec<&str> = json["checks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["storage", "vector_index", "embedding"]);
+    assert!(
+        json["checks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|c| c["status"] == "ok")
+    );
+}
+
+#[tokio::test]
+async fn test_rest_maintenance_passes() {
+    let engine = create_test_engine();
+    for i in 0..3 {
+        let mut req = mnemo_core::query::remember::RememberRequest::new(format!("deploy note {i}"));
+        req.tags = Some(vec!["deploys".to_string()]);
+        engine.remember(req).await.unwrap();
+    }
+    let app = mnemo_rest::router(engine);
+
+    let post = |uri: &str, body: &'static str| {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    };
+
+    let response = app
+        .clone()
+        .oneshot(post("/v1/maintenance/decay", "{}"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["total_processed"], 3);
+    assert_eq!(json["forgotten"], 0);
+
+    let response = app
+        .clone()
+        .oneshot(post(
+            "/v1/maintenance/decay",
+            r#"{"archive_threshold": 0.1, "forget_threshold": 0.5}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app
+        .clone()
+        .oneshot(post(
+            "/v1/maintenance/consolidate",
+            r#"{"min_cluster_size": 2}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["clusters_found"], 1);
+    assert_eq!(json["originals_consolidated"], 3);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/maintenance/cleanup")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["ttl"]["swept_count"], 0);
+    assert_eq!(json["trash"]["purged_count"], 0);
+}
+
+#[tokio::test]
+async fn test_rest_memory_access_log() {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(128).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(128));
+    let engine = Arc::new(
+        MnemoEngine::new(storage, index, embedding, "test-agent".to_string(), None)
+            .with_access_log(mnemo_core::query::access_log::AccessLogPolicy::default()),
+    );
+    let id = engine
+        .remember(mnemo_core::query::remember::RememberRequest::new(
+            "quarterly revenue figures".to_string(),
+        ))
+        .await
+        .unwrap()
+        .id;
+    let app = mnemo_rest::router(engine);
+
+    let get = |uri: String| Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+    let response = app
+        .clone()
+        .oneshot(get(format!("/v1/memories/{id}")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .clone()
+        .oneshot(get(format!("/v1/memories/{id}/accesses")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["enabled"], true);
+    assert_eq!(json["total"], 1);
+    assert_eq!(json["accesses"][0]["operation"], "get");
+    assert_eq!(json["accesses"][0]["principal"], "test-agent");
+
+    let response = app
+        .oneshot(get(format!(
+            "/v1/memories/{id}/accesses?agent_id=someone-else"
+        )))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_rest_replay_stream() {
+    let engine = create_test_engine();
+    engine
+        .checkpoint(mnemo_core::query::checkpoint::CheckpointRequest::new(
+            "stream-thread".to_string(),
+            serde_json::json!({"step": 1}),
+        ))
+        .await
+        .unwrap();
+    let app = mnemo_rest::router(engine);
+
+    let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+    let response = app
+        .clone()
+        .oneshot(get("/v1/replay/stream?thread_id=stream-thread&pace_ms=1"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    let names: Vec<&str> = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("event: "))
+        .collect();
+    assert_eq!(names.first(), Some(&"state"));
+    assert!(names[1..].iter().all(|name| *name == "event"));
+    let state = body
+        .lines()
+        .find_map(|line| line.strip_prefix("data: "))
+        .unwrap();
+    let state: serde_json::Value = serde_json::from_str(state).unwrap();
+    assert_eq!(state["type"], "state");
+    assert_eq!(state["checkpoint"]["state_snapshot"]["step"], 1);
+    assert_eq!(state["event_count"], names.len() - 1);
+
+    // Errors come back as ordinary responses before any event is sent.
+    let response = app
+        .oneshot(get("/v1/replay/stream?thread_id=no-such-thread"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_rest_metrics_export_cache_counters() {
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(128).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(128));
+    let cache = Arc::new(mnemo_core::cache::MemoryCache::new(300, 16));
+    let engine = Arc::new(
+        MnemoEngine::new(storage, index, embedding, "test-agent".to_string(), None)
+            .with_cache(cache.clone()),
+    );
+    assert!(cache.get(uuid::Uuid::now_v7()).is_none());
+    let app = mnemo_rest::router(engine);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/metrics")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(
+        response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain; version=0.0.4")
+    );
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert!(body.contains("# TYPE mnemo_cache_misses_total counter\n"));
+    assert!(
+        body.lines()
+            .any(|line| line == "mnemo_cache_misses_total 1")
+    );
+    assert!(body.lines().any(|line| line == "mnemo_cache_capacity 16"));
+}
+
+#[tokio::test]
+async fn test_rest_request_id_is_echoed_and_recorded_on_events() {
+    let engine = create_test_engine();
+    let app = mnemo_rest::router(engine.clone());
+
+    let body = serde_json::json!({"content": "the deploy window is friday"});
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/memories")
+                .header("content-type", "application/json")
+                .header("x-request-id", "req-7f3a")
+                .body(Body::from(serde_json::to_string(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-request-id"], "req-7f3a");
+
+    let events = engine
+        .storage
+        .list_events("test-agent", 10, 0)
+        .await
+        .unwrap();
+    assert!(!events.is_empty());
+    assert!(
+        events
+            .iter()
+            .all(|e| e.request_id.as_deref() == Some("req-7f3a"))
+    );
+
+    // Without one, the server makes one up.
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/v1/health")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let generated = response.headers()["x-request-id"].to_str().unwrap();
+    assert_eq!(generated.len(), 36);
+}
+
+#[tokio::test]
+async fn test_rest_share_links() {
+    use mnemo_core::encryption::ContentEncryption;
+    use mnemo_core::query::remember::RememberRequest;
+    use mnemo_core::query::share_link::ShareLinkSigner;
+
+    // Content is sealed at rest; link holders still read plaintext.
+    let storage = Arc::new(DuckDbStorage::open_in_memory().unwrap());
+    let index = Arc::new(UsearchIndex::new(128).unwrap());
+    let embedding = Arc::new(DeterministicEmbedding::new(128));
+    let engine = Arc::new(
+        MnemoEngine::new(storage, index, embedding, "test-agent".to_string(), None)
+            .with_encryption(Arc::new(ContentEncryption::new([9; 32])))
+            .with_share_links(ShareLinkSigner::new([3; 32])),
+    );
+    let shared = engine
+        .remember(RememberRequest::new("the launch slips a week".to_string()))
+        .await
+        .unwrap()
+        .id;
+    let other = engine
+        .remember(RememberRequest::new("the budget is frozen".to_string()))
+        .await
+        .unwrap()
+        .id;
+    let app = mnemo_rest::router_with_auth(engine.clone(), Some("s3cret".to_string()));
+    let call = |method: &str, uri: String, auth: Option<&str>, body: Body| {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json");
+        if let Some(auth) = auth {
+            request = request.header("authorization", format!("Bearer {auth}"));
+        }
+        app.clone().oneshot(request.body(body).unwrap())
+    };
+
+    let response = call(
+        "POST",
+        format!("/v1/memories/{shared}/links"),
+        Some("s3cret"),
+        Body::from(r#"{"expires_in_hours": 1}"#),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let link: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let token = link["token"].as_str().unwrap().to_string();
+    let link_id = link["link_id"].as_str().unwrap().to_string();
+
+    // The token reads its memory without the server secret, either way.
+    let response = call(
+        "GET",
+        format!("/v1/memories/{shared}?link={token}"),
+        None,
+        Body::empty(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let memory: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(memory["content"], "the launch slips a week");
+    let response = call(
+        "GET",
+        format!("/v1/memories/{shared}"),
+        Some(&token),
+        Body::empty(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Nothing else.
+    for (method, uri) in [
+        ("GET", format!("/v1/memories/{other}")),
+        ("DELETE", format!("/v1/memories/{shared}")),
+        ("GET", "/v1/memories?query=launch".to_string()),
+    ] {
+        let response = call(method, uri, Some(&token), Body::empty())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    // A quarantined memory is hidden from link holders, not from the owner.
+    let mut record = engine.storage.get_memory(shared).await.unwrap().unwrap();
+    record.quarantined = true;
+    engine.storage.update_memory(&record).await.unwrap();
+    let response = call(
+        "GET",
+        format!("/v1/memories/{shared}"),
+        Some(&token),
+        Body::empty(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = call(
+        "GET",
+        format!("/v1/memories/{shared}"),
+        Some("s3cret"),
+        Body::empty(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    record.quarantined = false;
+    engine.storage.update_memory(&record).await.unwrap();
+
+    let response = call(
+        "DELETE",
+        format!("/v1/memories/{shared}/links/{link_id}"),
+        Some("s3cret"),
+        Body::empty(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = call(
+        "GET",
+        format!("/v1/memories/{shared}?link={token}"),
+        None,
+        Body::empty(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
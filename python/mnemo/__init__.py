@@ -20,9 +20,9 @@ __all__: list[str] = []
 # import submodules without having run `maturin develop`. Any adapter that
 # actually requires MnemoClient will raise when it tries to instantiate it.
 try:
-    from mnemo._mnemo import MnemoClient  # type: ignore[attr-defined]
+    from mnemo._mnemo import MnemoClient, VersionConflictError  # type: ignore[attr-defined]
 
-    __all__.append("MnemoClient")
+    __all__.extend(["MnemoClient", "VersionConflictError"])
 except ImportError:
     MnemoClient = None  # type: ignore[assignment]
 
@@ -75,9 +75,9 @@ except ImportError:
     pass
 
 try:
-    from mnemo.autogen_memory import MnemoAutoGenWorkbench
+    from mnemo.autogen_memory import MnemoAutoGenMemory, MnemoAutoGenWorkbench
 
-    __all__.append("MnemoAutoGenWorkbench")
+    __all__.extend(["MnemoAutoGenMemory", "MnemoAutoGenWorkbench"])
 except ImportError:
     pass
 
@@ -116,6 +116,13 @@ try:
 except ImportError:
     pass
 
+try:
+    from mnemo.llamaindex_memory import MnemoMemory, MnemoVectorStore
+
+    __all__.extend(["MnemoMemory", "MnemoVectorStore"])
+except ImportError:
+    pass
+
 try:
     from mnemo.dspy_tools import create_mnemo_tools
 
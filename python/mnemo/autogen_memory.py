@@ -1,10 +1,24 @@
 """Microsoft AutoGen integration for Mnemo.
 
-Provides a wrapper that connects AutoGen agents to Mnemo's MCP server,
-giving agents access to persistent memory tools via McpWorkbench.
+Provides two ways to give AutoGen agents persistent memory:
+
+* :class:`MnemoAutoGenMemory` implements ``autogen_core``'s ``Memory``
+  protocol, so it can be passed as ``AssistantAgent(memory=[...])``. Before
+  each model call it recalls the memories relevant to the latest message
+  and adds them to the model context.
+* :class:`MnemoAutoGenWorkbench` connects agents to Mnemo's MCP server,
+  giving them memory tools via McpWorkbench.
 
 Example::
 
+    from autogen_agentchat.agents import AssistantAgent
+    from mnemo.autogen_memory import MnemoAutoGenMemory
+
+    memory = MnemoAutoGenMemory(db_path="agent.db", agent_id="assistant")
+    agent = AssistantAgent("assistant", model_client=model_client, memory=[memory])
+
+Or, with the MCP workbench::
+
     import asyncio
     from mnemo.autogen_memory import MnemoAutoGenWorkbench
 
@@ -31,8 +45,175 @@ Requires:
 
 from __future__ import annotations
 
+import asyncio
+import json
+from typing import Any, Optional
+
 from mnemo.mcp_config import MnemoMCPConfig
 
+try:
+    from autogen_core import CancellationToken
+    from autogen_core.memory import (
+        Memory,
+        MemoryContent,
+        MemoryMimeType,
+        MemoryQueryResult,
+        UpdateContextResult,
+    )
+    from autogen_core.model_context import ChatCompletionContext
+    from autogen_core.models import SystemMessage
+
+    _AUTOGEN_CORE = True
+except ImportError:  # pragma: no cover - the workbench works without it
+    Memory = object  # type: ignore[assignment,misc]
+    _AUTOGEN_CORE = False
+
+MEMORY_TAG_PREFIX = "autogen:memory:"
+# Mnemo caps a single recall at 100 memories.
+_LISTING_LIMIT = 100
+
+
+class MnemoAutoGenMemory(Memory):  # type: ignore[misc]
+    """AutoGen ``Memory`` backed by Mnemo.
+
+    Each added ``MemoryContent`` is one memory tagged
+    ``autogen:memory:<name>``; its MIME type and metadata are kept in the
+    memory's metadata. Text is stored as is and JSON content serialized;
+    binary and image content are rejected.
+
+    Args:
+        name: Distinguishes several AutoGen memories in one database.
+        client: A configured ``MnemoClient``, or anything with the same
+            ``remember`` / ``recall`` / ``forget`` surface. When omitted,
+            one is opened from ``db_path``, ``agent_id`` and ``client_kwargs``.
+        k: Memories recalled into the model context per call.
+        memory_type: Mnemo memory type for added content.
+        strategy: Recall strategy; hybrid by default.
+    """
+
+    def __init__(
+        self,
+        name: str = "mnemo",
+        client: Any = None,
+        db_path: str = "mnemo.db",
+        agent_id: str = "default",
+        k: int = 5,
+        memory_type: str = "semantic",
+        strategy: str = "hybrid",
+        **client_kwargs: Any,
+    ) -> None:
+        if not _AUTOGEN_CORE:
+            raise ImportError(
+                "autogen-core is required for MnemoAutoGenMemory. "
+                "Install with: pip install autogen-agentchat"
+            )
+        if not name:
+            raise ValueError("name must be a non-empty string")
+        if client is None:
+            from mnemo._mnemo import MnemoClient  # type: ignore[attr-defined]
+
+            client = MnemoClient(db_path=db_path, agent_id=agent_id, **client_kwargs)
+        self._client = client
+        self._name = name
+        self._tag = f"{MEMORY_TAG_PREFIX}{name}"
+        self._k = k
+        self._memory_type = memory_type
+        self._strategy = strategy
+
+    @property
+    def name(self) -> str:
+        return self._name
+
+    async def add(
+        self,
+        content: MemoryContent,
+        cancellation_token: Optional[CancellationToken] = None,
+    ) -> None:
+        mime_type = _mime_type(content.mime_type)
+        if mime_type == MemoryMimeType.JSON.value:
+            text = json.dumps(content.content, ensure_ascii=False)
+        elif isinstance(content.content, str):
+            text = content.content
+        else:
+            raise ValueError(f"MnemoAutoGenMemory cannot store {mime_type} content")
+        metadata = dict(content.metadata or {})
+        metadata["_mime_type"] = mime_type
+        await asyncio.to_thread(
+            self._client.remember,
+            content=text,
+            memory_type=self._memory_type,
+            tags=[self._tag],
+            metadata=metadata,
+        )
+
+    async def query(
+        self,
+        query: "str | MemoryContent" = "",
+        cancellation_token: Optional[CancellationToken] = None,
+        **kwargs: Any,
+    ) -> MemoryQueryResult:
+        text = query if isinstance(query, str) else str(query.content)
+        if not text:
+            return MemoryQueryResult(results=[])
+        found = await asyncio.to_thread(
+            self._client.recall,
+            query=text,
+            limit=kwargs.get("k", self._k),
+            tags=[self._tag],
+            strategy=self._strategy,
+        )
+        return MemoryQueryResult(
+            results=[_to_content(memory) for memory in found.get("memories", [])]
+        )
+
+    async def update_context(self, model_context: ChatCompletionContext) -> UpdateContextResult:
+        """Recall memories relevant to the latest message into the context."""
+        messages = await model_context.get_messages()
+        if not messages:
+            return UpdateContextResult(memories=MemoryQueryResult(results=[]))
+        latest = messages[-1].content
+        result = await self.query(latest if isinstance(latest, str) else str(latest))
+        if result.results:
+            lines = [f"{i}. {item.content}" for i, item in enumerate(result.results, 1)]
+            await model_context.add_message(
+                SystemMessage(content="Relevant memory content:\n" + "\n".join(lines))
+            )
+        return UpdateContextResult(memories=result)
+
+    async def clear(self) -> None:
+        """Forget every memory added through this instance's name."""
+        found = await asyncio.to_thread(
+            self._client.recall,
+            query=self._tag,
+            limit=_LISTING_LIMIT,
+            tags=[self._tag],
+            strategy="exact",
+        )
+        ids = [memory["id"] for memory in found.get("memories", [])]
+        if ids:
+            await asyncio.to_thread(self._client.forget, ids)
+
+    async def close(self) -> None:
+        """Nothing to release; the client is owned by the caller."""
+
+
+def _mime_type(mime_type: Any) -> str:
+    return mime_type.value if hasattr(mime_type, "value") else str(mime_type)
+
+
+def _to_content(memory: dict[str, Any]) -> "MemoryContent":
+    metadata = dict(memory.get("metadata") or {})
+    mime_type = metadata.pop("_mime_type", MemoryMimeType.TEXT.value)
+    content: Any = memory.get("content", "")
+    if mime_type == MemoryMimeType.JSON.value:
+        try:
+            content = json.loads(content)
+        except ValueError:
+            mime_type = MemoryMimeType.TEXT.value
+    metadata["score"] = memory.get("score")
+    metadata["memory_id"] = memory.get("id")
+    return MemoryContent(content=content, mime_type=mime_type, metadata=metadata)
+
 
 class MnemoAutoGenWorkbench:
     """AutoGen integration for Mnemo MCP memory server.
@@ -1,10 +1,27 @@
 """CrewAI memory backend integration for Mnemo.
 
-Provides ASMDMemory that can be used as a shared memory backend
-for CrewAI agents.
+Provides storages for CrewAI's three memory buckets, each mapped to a
+Mnemo memory type:
+
+* short-term memory -> ``working`` memories (expire with the working TTL),
+* long-term memory -> ``episodic`` memories of task outcomes,
+* entity memory -> ``semantic`` memories.
+
+:func:`mnemo_crew_memories` builds all three for a ``Crew``. ASMDMemory is
+a plain add/search wrapper for use outside CrewAI's memory system.
 
 Usage::
 
+    from crewai import Crew
+    from mnemo.crewai_memory import mnemo_crew_memories
+
+    crew = Crew(
+        agents=[researcher, writer],
+        tasks=[research, write],
+        memory=True,
+        **mnemo_crew_memories(db_path="crew.mnemo.db"),
+    )
+
     from mnemo.crewai_memory import ASMDMemory
 
     memory = ASMDMemory(db_path="crew.mnemo.db", scope="shared")
@@ -18,6 +35,180 @@ from typing import Any, Optional
 
 from mnemo import MnemoClient
 
+BUCKET_TYPES = {
+    "short_term": "working",
+    "long_term": "episodic",
+    "entity": "semantic",
+}
+BUCKET_TAG_PREFIX = "crewai:"
+# Mnemo caps a single recall at 100 memories.
+_LISTING_LIMIT = 100
+
+
+def _open_client(client: Any, db_path: str, agent_id: str) -> Any:
+    if client is not None:
+        return client
+    if MnemoClient is None:
+        from mnemo.availability import MnemoClientUnavailable
+
+        raise MnemoClientUnavailable("CrewAI storages need the native mnemo._mnemo extension")
+    return MnemoClient(db_path=db_path, agent_id=agent_id)
+
+
+class _BucketStorage:
+    def __init__(
+        self,
+        bucket: str,
+        client: Any,
+        db_path: str,
+        agent_id: str,
+        scope: str,
+    ) -> None:
+        if bucket not in BUCKET_TYPES:
+            raise ValueError(f"bucket must be one of {sorted(BUCKET_TYPES)}, got {bucket!r}")
+        self.bucket = bucket
+        self.memory_type = BUCKET_TYPES[bucket]
+        self.scope = scope
+        self.client = _open_client(client, db_path, agent_id)
+        self._tag = f"{BUCKET_TAG_PREFIX}{bucket}"
+
+    def _listing(self) -> list[dict]:
+        found = self.client.recall(
+            query=self._tag, limit=_LISTING_LIMIT, tags=[self._tag], strategy="exact"
+        )
+        return found.get("memories", [])
+
+    def reset(self) -> None:
+        """Forget every memory in this bucket."""
+        ids = [memory["id"] for memory in self._listing()]
+        if ids:
+            self.client.forget(ids)
+
+
+class MnemoCrewStorage(_BucketStorage):
+    """CrewAI RAG storage for the short-term or entity bucket.
+
+    Implements the ``save`` / ``search`` / ``reset`` surface that
+    ``ShortTermMemory`` and ``EntityMemory`` call on their storage. Search
+    uses hybrid recall; ``score_threshold`` is ignored because hybrid
+    scores are fused ranks, not similarities.
+    """
+
+    def __init__(
+        self,
+        bucket: str = "short_term",
+        client: Any = None,
+        db_path: str = "mnemo.db",
+        agent_id: str = "crewai",
+        scope: str = "shared",
+    ) -> None:
+        super().__init__(bucket, client, db_path, agent_id, scope)
+
+    def save(self, value: Any, metadata: Optional[dict] = None) -> None:
+        self.client.remember(
+            content=str(value),
+            memory_type=self.memory_type,
+            scope=self.scope,
+            tags=[self._tag],
+            metadata=metadata or {},
+        )
+
+    def search(
+        self,
+        query: str,
+        limit: int = 3,
+        score_threshold: float = 0.35,
+        **kwargs: Any,
+    ) -> list[dict]:
+        found = self.client.recall(query=query, limit=limit, tags=[self._tag], strategy="hybrid")
+        return [
+            {
+                "id": memory["id"],
+                "context": memory.get("content", ""),
+                "metadata": memory.get("metadata") or {},
+                "score": memory.get("score", 0.0),
+            }
+            for memory in found.get("memories", [])
+        ]
+
+
+class MnemoCrewLongTermStorage(_BucketStorage):
+    """CrewAI long-term storage: task outcomes and their quality scores.
+
+    Implements the ``save`` / ``load`` / ``reset`` surface that
+    ``LongTermMemory`` calls. ``load`` returns the latest outcomes of the
+    exact task description, newest first.
+    """
+
+    def __init__(
+        self,
+        client: Any = None,
+        db_path: str = "mnemo.db",
+        agent_id: str = "crewai",
+        scope: str = "shared",
+    ) -> None:
+        super().__init__("long_term", client, db_path, agent_id, scope)
+
+    def save(
+        self,
+        task_description: str,
+        metadata: dict,
+        datetime: str,
+        score: float,
+    ) -> None:
+        self.client.remember(
+            content=task_description,
+            memory_type=self.memory_type,
+            scope=self.scope,
+            tags=[self._tag],
+            metadata={
+                "task_description": task_description,
+                "datetime": datetime,
+                "score": score,
+                "metadata": metadata,
+            },
+        )
+
+    def load(self, task_description: str, latest_n: int = 3) -> Optional[list[dict]]:
+        rows = [
+            memory.get("metadata") or {}
+            for memory in self._listing()
+            if (memory.get("metadata") or {}).get("task_description") == task_description
+        ]
+        rows.sort(key=lambda row: str(row.get("datetime", "")), reverse=True)
+        outcomes = [
+            {
+                "metadata": row.get("metadata", {}),
+                "datetime": row.get("datetime"),
+                "score": row.get("score"),
+            }
+            for row in rows[:latest_n]
+        ]
+        return outcomes or None
+
+
+def mnemo_crew_memories(
+    client: Any = None,
+    db_path: str = "mnemo.db",
+    agent_id: str = "crewai",
+    scope: str = "shared",
+) -> dict[str, Any]:
+    """``Crew`` keyword arguments for all three memory buckets on Mnemo."""
+    from crewai.memory import EntityMemory, LongTermMemory, ShortTermMemory
+
+    client = _open_client(client, db_path, agent_id)
+    return {
+        "short_term_memory": ShortTermMemory(
+            storage=MnemoCrewStorage("short_term", client=client, scope=scope)
+        ),
+        "long_term_memory": LongTermMemory(
+            storage=MnemoCrewLongTermStorage(client=client, scope=scope)
+        ),
+        "entity_memory": EntityMemory(
+            storage=MnemoCrewStorage("entity", client=client, scope=scope)
+        ),
+    }
+
 
 class ASMDMemory:
     """CrewAI-compatible shared memory backed by Mnemo.
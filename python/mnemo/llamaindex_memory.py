@@ -0,0 +1,320 @@
+"""LlamaIndex integration for Mnemo.
+
+Provides two LlamaIndex components backed by one Mnemo database:
+
+* :class:`MnemoVectorStore` — a ``BasePydanticVectorStore``. Nodes are
+  stored as Mnemo memories and queries run through Mnemo's hybrid recall
+  (vector + BM25 + graph + recency, fused with RRF) rather than plain
+  cosine similarity. Mnemo embeds content itself, so the store asks
+  LlamaIndex for the query text instead of a query embedding.
+* :class:`MnemoMemory` — a ``BaseMemory`` for chat engines and agents.
+  Every message is an episodic memory in the session's thread; ``get``
+  returns the recent history, prefixed with older messages and memories
+  that hybrid recall finds relevant to the new input.
+
+Example::
+
+    from llama_index.core import StorageContext, VectorStoreIndex
+    from llama_index.core.chat_engine import SimpleChatEngine
+    from mnemo import MnemoClient
+    from mnemo.llamaindex_memory import MnemoMemory, MnemoVectorStore
+
+    client = MnemoClient(db_path="agent.mnemo.db", agent_id="docs-bot")
+
+    store = MnemoVectorStore(client=client)
+    index = VectorStoreIndex.from_documents(
+        documents, storage_context=StorageContext.from_defaults(vector_store=store)
+    )
+    answer = index.as_query_engine().query("How do I rotate the keys?")
+
+    memory = MnemoMemory.from_defaults(client=client, session_id="user-42")
+    chat = SimpleChatEngine.from_defaults(memory=memory)
+
+Requires:
+    pip install 'mnemo-db[llamaindex]'
+"""
+
+from __future__ import annotations
+
+import json
+from typing import Any, Optional
+
+from llama_index.core.bridge.pydantic import Field, PrivateAttr
+from llama_index.core.llms import ChatMessage, MessageRole
+from llama_index.core.memory.types import BaseMemory
+from llama_index.core.schema import (
+    BaseNode,
+    MetadataMode,
+    NodeRelationship,
+    RelatedNodeInfo,
+    TextNode,
+)
+from llama_index.core.vector_stores.types import (
+    BasePydanticVectorStore,
+    VectorStoreQuery,
+    VectorStoreQueryResult,
+)
+
+NODE_TAG_PREFIX = "llamaindex:node:"
+DOC_TAG_PREFIX = "llamaindex:doc:"
+SESSION_TAG_PREFIX = "llamaindex:session:"
+DEFAULT_STRATEGY = "hybrid"
+# Mnemo caps a single recall at 100 memories.
+_LISTING_LIMIT = 100
+
+
+def _open_client(client: Any, kwargs: dict[str, Any]) -> Any:
+    """Use ``client`` when given, else open a ``MnemoClient`` from ``kwargs``."""
+    if client is not None:
+        return client
+    try:
+        from mnemo._mnemo import MnemoClient  # type: ignore[attr-defined]
+    except ImportError as exc:  # pragma: no cover
+        from mnemo.availability import MnemoClientUnavailable
+
+        raise MnemoClientUnavailable(
+            "the LlamaIndex integration needs the native mnemo._mnemo extension"
+        ) from exc
+    return MnemoClient(**kwargs)
+
+
+def _listing(client: Any, tag: str) -> list[dict[str, Any]]:
+    """Every memory carrying ``tag``, without embedding a query."""
+    found = client.recall(query=tag, limit=_LISTING_LIMIT, tags=[tag], strategy="exact")
+    return found.get("memories", []) if isinstance(found, dict) else []
+
+
+class MnemoVectorStore(BasePydanticVectorStore):
+    """LlamaIndex vector store backed by Mnemo.
+
+    Args:
+        client: A configured ``MnemoClient``, or anything with the same
+            ``remember`` / ``recall`` / ``forget`` surface. When omitted,
+            one is opened from ``client_kwargs``.
+        strategy: Recall strategy for queries; ``"hybrid"`` by default.
+        memory_type: Memory type for stored nodes.
+        client_kwargs: Forwarded to ``MnemoClient`` when ``client`` is
+            omitted (``db_path``, ``agent_id``, ...).
+
+    Node metadata is stored as memory metadata; the node and source
+    document ids are kept as tags so :meth:`delete` and
+    :meth:`delete_nodes` find them without a search.
+    """
+
+    stores_text: bool = True
+    is_embedding_query: bool = False
+    flat_metadata: bool = False
+
+    strategy: str = DEFAULT_STRATEGY
+    memory_type: str = "semantic"
+
+    _client: Any = PrivateAttr()
+
+    def __init__(
+        self,
+        client: Any = None,
+        strategy: str = DEFAULT_STRATEGY,
+        memory_type: str = "semantic",
+        **client_kwargs: Any,
+    ) -> None:
+        super().__init__(strategy=strategy, memory_type=memory_type)
+        self._client = _open_client(client, client_kwargs)
+
+    @classmethod
+    def class_name(cls) -> str:
+        return "MnemoVectorStore"
+
+    @property
+    def client(self) -> Any:
+        return self._client
+
+    def add(self, nodes: list[BaseNode], **add_kwargs: Any) -> list[str]:
+        """Remember each node; returns the node ids."""
+        ids = []
+        for node in nodes:
+            tags = [f"{NODE_TAG_PREFIX}{node.node_id}"]
+            if node.ref_doc_id:
+                tags.append(f"{DOC_TAG_PREFIX}{node.ref_doc_id}")
+            metadata = dict(node.metadata)
+            metadata["_node_id"] = node.node_id
+            if node.ref_doc_id:
+                metadata["_ref_doc_id"] = node.ref_doc_id
+            self._client.remember(
+                content=node.get_content(metadata_mode=MetadataMode.NONE),
+                memory_type=self.memory_type,
+                tags=tags,
+                metadata=metadata,
+            )
+            ids.append(node.node_id)
+        return ids
+
+    def delete(self, ref_doc_id: str, **delete_kwargs: Any) -> None:
+        """Forget every node of a source document."""
+        self._forget_tagged([f"{DOC_TAG_PREFIX}{ref_doc_id}"])
+
+    def delete_nodes(
+        self,
+        node_ids: Optional[list[str]] = None,
+        filters: Any = None,
+        **delete_kwargs: Any,
+    ) -> None:
+        """Forget nodes by id. Metadata ``filters`` are not supported."""
+        if filters is not None:
+            raise NotImplementedError("MnemoVectorStore does not delete by metadata filters")
+        self._forget_tagged([f"{NODE_TAG_PREFIX}{node_id}" for node_id in node_ids or []])
+
+    def query(self, query: VectorStoreQuery, **kwargs: Any) -> VectorStoreQueryResult:
+        """Recall the nodes most relevant to ``query.query_str``."""
+        if not query.query_str:
+            raise ValueError("MnemoVectorStore queries by text; query_str is required")
+        if query.filters is not None:
+            raise NotImplementedError("MnemoVectorStore does not filter by metadata")
+        found = self._client.recall(
+            query=query.query_str,
+            limit=query.similarity_top_k,
+            strategy=self.strategy,
+        )
+        nodes, similarities, ids = [], [], []
+        for memory in found.get("memories", []):
+            metadata = dict(memory.get("metadata") or {})
+            node_id = metadata.pop("_node_id", None)
+            if node_id is None:
+                # Not written through this store.
+                continue
+            ref_doc_id = metadata.pop("_ref_doc_id", None)
+            relationships = {}
+            if ref_doc_id:
+                relationships[NodeRelationship.SOURCE] = RelatedNodeInfo(node_id=ref_doc_id)
+            node = TextNode(
+                id_=node_id,
+                text=memory.get("content", ""),
+                metadata=metadata,
+                relationships=relationships,
+            )
+            nodes.append(node)
+            similarities.append(float(memory.get("score", 0.0)))
+            ids.append(node_id)
+        return VectorStoreQueryResult(nodes=nodes, similarities=similarities, ids=ids)
+
+    def _forget_tagged(self, tags: list[str]) -> None:
+        ids = [memory["id"] for tag in tags for memory in _listing(self._client, tag)]
+        if ids:
+            self._client.forget(ids)
+
+
+class MnemoMemory(BaseMemory):
+    """LlamaIndex chat memory backed by Mnemo.
+
+    Messages are stored as episodic memories in the ``session_id`` thread.
+    ``get`` returns the last ``history_limit`` messages; with an input, it
+    also recalls up to ``recall_limit`` older messages and memories of the
+    agent relevant to it, using hybrid recall, and prepends them as one
+    system message.
+    """
+
+    session_id: str = Field(description="Conversation the messages belong to.")
+    history_limit: int = Field(default=20, description="Recent messages returned by get().")
+    recall_limit: int = Field(default=5, description="Relevant memories recalled by get().")
+    strategy: str = Field(default=DEFAULT_STRATEGY, description="Recall strategy.")
+
+    _client: Any = PrivateAttr()
+
+    @classmethod
+    def class_name(cls) -> str:
+        return "MnemoMemory"
+
+    @classmethod
+    def from_defaults(
+        cls,
+        client: Any = None,
+        session_id: str = "default",
+        history_limit: int = 20,
+        recall_limit: int = 5,
+        strategy: str = DEFAULT_STRATEGY,
+        **client_kwargs: Any,
+    ) -> "MnemoMemory":
+        if not session_id:
+            raise ValueError("session_id is required")
+        memory = cls(
+            session_id=session_id,
+            history_limit=history_limit,
+            recall_limit=recall_limit,
+            strategy=strategy,
+        )
+        memory._client = _open_client(client, client_kwargs)
+        return memory
+
+    @property
+    def _tag(self) -> str:
+        return f"{SESSION_TAG_PREFIX}{self.session_id}"
+
+    def get(self, input: Optional[str] = None, **kwargs: Any) -> list[ChatMessage]:
+        history = self._history()
+        recent = history[-self.history_limit :] if self.history_limit > 0 else []
+        if not input or self.recall_limit <= 0:
+            return [message for _, message in recent]
+
+        shown = {memory_id for memory_id, _ in recent}
+        found = self._client.recall(
+            query=input,
+            limit=self.recall_limit + len(shown),
+            strategy=self.strategy,
+            thread_id=self.session_id,
+        )
+        recalled = [
+            memory.get("content", "")
+            for memory in found.get("memories", [])
+            if memory["id"] not in shown and memory.get("content")
+        ][: self.recall_limit]
+        messages = [message for _, message in recent]
+        if recalled:
+            context = "Relevant memories:\n" + "\n".join(f"- {text}" for text in recalled)
+            messages.insert(0, ChatMessage(role=MessageRole.SYSTEM, content=context))
+        return messages
+
+    def get_all(self) -> list[ChatMessage]:
+        return [message for _, message in self._history()]
+
+    def put(self, message: ChatMessage) -> None:
+        history = self._history()
+        self._remember(message, len(history))
+
+    def set(self, messages: list[ChatMessage]) -> None:
+        self.reset()
+        for position, message in enumerate(messages):
+            self._remember(message, position)
+
+    def reset(self) -> None:
+        ids = [memory["id"] for memory in _listing(self._client, self._tag)]
+        if ids:
+            self._client.forget(ids)
+
+    def _remember(self, message: ChatMessage, position: int) -> None:
+        self._client.remember(
+            content=f"{message.role.value}: {message.content or ''}",
+            memory_type="episodic",
+            tags=[self._tag],
+            metadata={
+                "session_id": self.session_id,
+                "position": position,
+                "message": json.loads(message.model_dump_json()),
+            },
+            thread_id=self.session_id,
+        )
+
+    def _history(self) -> list[tuple[str, ChatMessage]]:
+        """(memory id, message) pairs of the session, oldest first."""
+        rows = []
+        for memory in _listing(self._client, self._tag):
+            metadata = memory.get("metadata") or {}
+            if "message" not in metadata:
+                continue
+            rows.append(
+                (
+                    metadata.get("position", 0),
+                    memory["id"],
+                    ChatMessage.model_validate(metadata["message"]),
+                )
+            )
+        rows.sort(key=lambda row: row[0])
+        return [(memory_id, message) for _, memory_id, message in rows]
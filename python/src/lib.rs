@@ -1,36 +1,163 @@
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock, Mutex, Weak};
 
 use pyo3::exceptions::PyRuntimeError;
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
 
-use mnemo_core::embedding::openai::OpenAiEmbedding;
-use mnemo_core::embedding::{EmbeddingProvider, NoopEmbedding};
-use mnemo_core::index::VectorIndex;
-use mnemo_core::index::usearch::UsearchIndex;
+use mnemo_core::config::{EmbeddingConfig, MnemoEngineConfig, StorageConfig};
 use mnemo_core::model::memory::{MemoryType, Scope};
 use mnemo_core::query::MnemoEngine;
 use mnemo_core::query::branch::BranchRequest;
 use mnemo_core::query::checkpoint::CheckpointRequest;
+use mnemo_core::query::ephemeral::EphemeralRequest;
 use mnemo_core::query::forget::{ForgetRequest, ForgetStrategy};
+use mnemo_core::query::get_batch::GetBatchRequest;
+use mnemo_core::query::lifecycle;
 use mnemo_core::query::merge::MergeRequest;
-use mnemo_core::query::recall::RecallRequest;
+use mnemo_core::query::pin::PinRequest;
+use mnemo_core::query::recall::{RecallRequest, ScoredMemory, SnippetSource};
 use mnemo_core::query::remember::RememberRequest;
 use mnemo_core::query::replay::ReplayRequest;
-use mnemo_core::query::share::ShareRequest;
-use mnemo_core::search::tantivy_index::TantivyFullTextIndex;
-use mnemo_core::storage::duckdb::DuckDbStorage;
+use mnemo_core::query::share::{ShareFilter, ShareRequest};
+use mnemo_core::query::similar::SimilarRequest;
+use mnemo_core::query::update::UpdateRequest;
+
+pyo3::create_exception!(
+    _mnemo,
+    VersionConflictError,
+    PyRuntimeError,
+    "An update's `expected_version` is stale; `current` holds the memory as stored now."
+);
 
 fn to_py_err(e: impl std::fmt::Display) -> PyErr {
     PyRuntimeError::new_err(e.to_string())
 }
 
-#[pyclass]
-struct MnemoClient {
+/// Like [`to_py_err`], but a version conflict raises `VersionConflictError`
+/// with the current record attached as `current`.
+fn update_err(e: mnemo_core::error::Error) -> PyErr {
+    let mnemo_core::error::Error::VersionConflict { ref current, .. } = e else {
+        return to_py_err(e);
+    };
+    let current = serde_json::to_value(current).map_err(to_py_err);
+    let err = VersionConflictError::new_err(e.to_string());
+    Python::attach(|py| {
+        if let Ok(current) = current.and_then(json_to_py) {
+            let _ = err.value(py).setattr("current", current);
+        }
+    });
+    err
+}
+
+/// One engine (and its runtime) per database path, shared by every
+/// `MnemoClient` opened on that path. Opening the same DuckDB / usearch
+/// files twice from one process would fight over the file lock and let the
+/// last index save clobber the other's vectors.
+struct SharedEngine {
     engine: Arc<MnemoEngine>,
-    index: Arc<UsearchIndex>,
     runtime: tokio::runtime::Runtime,
-    index_path: std::path::PathBuf,
+    config: MnemoEngineConfig,
+}
+
+impl SharedEngine {
+    /// Persist the vector index snapshot (truncating its WAL) and commit
+    /// the full-text index.
+    fn flush(&self) -> PyResult<()> {
+        if let Some(ref path) = self.config.index.path {
+            self.engine.index.save(path).map_err(to_py_err)?;
+        }
+        if let Some(ref ft) = self.engine.full_text {
+            ft.commit().map_err(to_py_err)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for SharedEngine {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+static ENGINES: LazyLock<Mutex<HashMap<PathBuf, Weak<SharedEngine>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Return the live engine for `config`'s database path, or open one. The
+/// agent id is per client; every other setting must match the engine
+/// already open on that path.
+fn open_shared(config: &MnemoEngineConfig) -> PyResult<Arc<SharedEngine>> {
+    let key = match config.storage {
+        StorageConfig::DuckDb { ref path } => Some(std::path::absolute(path).map_err(to_py_err)?),
+        StorageConfig::Memory | StorageConfig::InMemory => None,
+    };
+    let mut engines = ENGINES.lock().unwrap_or_else(|e| e.into_inner());
+    engines.retain(|_, shared| shared.strong_count() > 0);
+
+    if let Some(shared) = key.as_ref().and_then(|k| engines.get(k)?.upgrade()) {
+        let requested = MnemoEngineConfig {
+            agent_id: shared.config.agent_id.clone(),
+            ..config.clone()
+        };
+        if requested != shared.config {
+            return Err(PyRuntimeError::new_err(format!(
+                "MnemoClient: {} is already open in this process with a different \
+                 configuration (dimensions, embedding or full-text settings); \
+                 reuse the existing settings or close the other client first",
+                key.as_ref()
+                    .map(|k| k.display().to_string())
+                    .unwrap_or_default()
+            )));
+        }
+        return Ok(shared);
+    }
+
+    let runtime = tokio::runtime::Runtime::new().map_err(to_py_err)?;
+    let engine = runtime
+        .block_on(MnemoEngine::from_config(config))
+        .map_err(to_py_err)?;
+    let shared = Arc::new(SharedEngine {
+        engine: Arc::new(engine),
+        runtime,
+        config: config.clone(),
+    });
+    if let Some(key) = key {
+        engines.insert(key, Arc::downgrade(&shared));
+    }
+    Ok(shared)
+}
+
+/// Rebuild a client from the config stored by `MnemoClient.__reduce__`.
+#[pyfunction]
+fn _restore_client(config_json: &str) -> PyResult<MnemoClient> {
+    let config: MnemoEngineConfig = serde_json::from_str(config_json).map_err(to_py_err)?;
+    MnemoClient::from_config(config)
+}
+
+#[pyclass(module = "mnemo._mnemo")]
+struct MnemoClient {
+    /// `None` once the client has been closed.
+    shared: Option<Arc<SharedEngine>>,
+    agent_id: String,
+    config: MnemoEngineConfig,
+}
+
+impl MnemoClient {
+    fn from_config(config: MnemoEngineConfig) -> PyResult<Self> {
+        let shared = open_shared(&config)?;
+        Ok(Self {
+            shared: Some(shared),
+            agent_id: config.agent_id.clone(),
+            config,
+        })
+    }
+
+    fn shared(&self) -> PyResult<&SharedEngine> {
+        self.shared
+            .as_deref()
+            .ok_or_else(|| PyRuntimeError::new_err("MnemoClient is closed"))
+    }
 }
 
 #[pymethods]
@@ -48,18 +175,17 @@ impl MnemoClient {
         with_full_text: bool,
         with_noop_embedding: bool,
     ) -> PyResult<Self> {
-        let runtime = tokio::runtime::Runtime::new().map_err(to_py_err)?;
-        let db_path = std::path::PathBuf::from(db_path);
-        let storage = Arc::new(DuckDbStorage::open(&db_path).map_err(to_py_err)?);
-
-        let embedding: Arc<dyn EmbeddingProvider> = if let Some(api_key) = openai_api_key {
-            Arc::new(OpenAiEmbedding::new(
-                api_key,
-                embedding_model.to_string(),
-                dimensions,
-            ))
+        let mut config = MnemoEngineConfig::duckdb(std::path::Path::new(db_path));
+        config.agent_id = agent_id.to_string();
+        config.org_id = org_id;
+        config.dimensions = dimensions;
+        config.embedding = if let Some(api_key) = openai_api_key {
+            EmbeddingConfig::OpenAi {
+                api_key: Some(api_key),
+                model: embedding_model.to_string(),
+            }
         } else if with_noop_embedding {
-            Arc::new(NoopEmbedding::new(dimensions))
+            EmbeddingConfig::Noop
         } else {
             return Err(PyRuntimeError::new_err(
                 "MnemoClient: no openai_api_key supplied and with_noop_embedding=False. \
@@ -67,42 +193,60 @@ impl MnemoClient {
                  or leave with_noop_embedding=True to fall back to zero vectors.",
             ));
         };
-
-        let index = Arc::new(UsearchIndex::new(dimensions).map_err(to_py_err)?);
-
-        let index_path = db_path.with_extension("usearch");
-        if index_path.exists() {
-            index.load(&index_path).map_err(to_py_err)?;
-        }
-
-        let mut engine = MnemoEngine::new(
-            storage,
-            index.clone(),
-            embedding,
-            agent_id.to_string(),
-            org_id,
-        );
-        // Attach a persistent Tantivy full-text index by default (v0.3.3).
+        // A persistent Tantivy full-text index is attached by default (v0.3.3).
         // Without it, the Python client's `recall(..., strategy="hybrid_rrf")`
         // falls through to vector-only retrieval and substring-style
         // benchmarks (LongMemEval, LoCoMo) collapse. Callers that want
         // the old zero-tantivy behaviour pass `with_full_text=False`.
-        if with_full_text {
-            let ft_path = db_path.with_extension("tantivy");
-            let ft = Arc::new(TantivyFullTextIndex::new(&ft_path).map_err(to_py_err)?);
-            engine = engine.with_full_text(ft);
+        config.full_text.enabled = with_full_text;
+
+        Self::from_config(config)
+    }
+
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    #[pyo3(signature = (*_args))]
+    fn __exit__(&mut self, _args: &Bound<'_, pyo3::types::PyTuple>) -> PyResult<bool> {
+        self.close()?;
+        Ok(false)
+    }
+
+    /// Flush the vector and full-text indexes and release this client's
+    /// handle on the shared engine. Other clients on the same path keep
+    /// working. Idempotent; any later call on this client raises.
+    fn close(&mut self) -> PyResult<()> {
+        match self.shared.take() {
+            Some(shared) => shared.flush(),
+            None => Ok(()),
         }
-        let engine = Arc::new(engine);
+    }
 
-        Ok(Self {
-            engine,
-            index,
-            runtime,
-            index_path,
-        })
+    #[getter]
+    fn closed(&self) -> bool {
+        self.shared.is_none()
     }
 
-    #[pyo3(signature = (content, memory_type=None, scope=None, importance=None, tags=None, metadata=None, thread_id=None, ttl_seconds=None, related_to=None))]
+    /// Pickle as the engine configuration. The OpenAI key is dropped: an
+    /// unpickled client reads `OPENAI_API_KEY` from its own environment.
+    fn __reduce__(&self, py: Python<'_>) -> PyResult<(Py<PyAny>, (String,))> {
+        let mut config = self.config.clone();
+        if let EmbeddingConfig::OpenAi {
+            ref mut api_key, ..
+        } = config.embedding
+        {
+            *api_key = None;
+        }
+        let config_json = serde_json::to_string(&config).map_err(to_py_err)?;
+        let restore = py
+            .import("mnemo._mnemo")?
+            .getattr("_restore_client")?
+            .unbind();
+        Ok((restore, (config_json,)))
+    }
+
+    #[pyo3(signature = (content, memory_type=None, scope=None, importance=None, tags=None, metadata=None, thread_id=None, ttl_seconds=None, related_to=None, pinned=None))]
     #[allow(clippy::too_many_arguments)]
     fn remember(
         &self,
@@ -115,6 +259,7 @@ impl MnemoClient {
         thread_id: Option<String>,
         ttl_seconds: Option<u64>,
         related_to: Option<Vec<String>>,
+        pinned: Option<bool>,
     ) -> PyResult<Py<PyAny>> {
         let metadata_value = match metadata {
             Some(dict) => pythonize_dict(dict)?,
@@ -123,7 +268,7 @@ impl MnemoClient {
 
         let request = RememberRequest {
             content,
-            agent_id: None,
+            agent_id: Some(self.agent_id.clone()),
             memory_type: memory_type.and_then(|s| s.parse::<MemoryType>().ok()),
             scope: scope.and_then(|s| s.parse::<Scope>().ok()),
             importance,
@@ -137,11 +282,59 @@ impl MnemoClient {
             related_to,
             decay_rate: None,
             created_by: None,
+            pinned,
+            valid_from: None,
+            valid_until: None,
+            channel: None,
+        };
+
+        let shared = self.shared()?;
+
+        let response = shared
+            .runtime
+            .block_on(shared.engine.remember(request))
+            .map_err(to_py_err)?;
+
+        Python::attach(|py| {
+            let dict = PyDict::new(py);
+            dict.set_item("id", response.id.to_string())?;
+            dict.set_item("content_hash", response.content_hash)?;
+            Ok(dict.into_any().unbind())
+        })
+    }
+
+    /// Remember a scratchpad result: Working tier, a short TTL (600 s by
+    /// default), no embedding and never consolidated. Recall it with the
+    /// `exact` or `bm25` strategy.
+    #[pyo3(signature = (content, memory_type=None, importance=None, tags=None, metadata=None, thread_id=None, ttl_seconds=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn remember_ephemeral(
+        &self,
+        content: String,
+        memory_type: Option<String>,
+        importance: Option<f32>,
+        tags: Option<Vec<String>>,
+        metadata: Option<&Bound<'_, PyDict>>,
+        thread_id: Option<String>,
+        ttl_seconds: Option<u64>,
+    ) -> PyResult<Py<PyAny>> {
+        let mut request = EphemeralRequest::new(content);
+        request.agent_id = Some(self.agent_id.clone());
+        request.memory_type = memory_type.and_then(|s| s.parse::<MemoryType>().ok());
+        request.importance = importance;
+        request.tags = tags;
+        request.metadata = match metadata {
+            Some(dict) => pythonize_dict(dict)?,
+            None => None,
         };
+        request.thread_id = thread_id;
+        request.ttl_seconds = ttl_seconds;
 
-        let response = self
+        let shared = self.shared()?;
+
+        let response = shared
             .runtime
-            .block_on(self.engine.remember(request))
+            .block_on(shared.engine.remember_ephemeral(request))
             .map_err(to_py_err)?;
 
         Python::attach(|py| {
@@ -173,10 +366,11 @@ impl MnemoClient {
             None,
             None,
             None,
+            None,
         )
     }
 
-    #[pyo3(signature = (query, limit=None, memory_type=None, min_importance=None, tags=None, strategy=None, explain=None, with_provenance=None))]
+    #[pyo3(signature = (query, limit=None, memory_type=None, min_importance=None, tags=None, strategy=None, explain=None, with_provenance=None, with_snippets=None, profile=None, thread_id=None, thread_isolation=None, exclude_query=None, exclude_tags=None, queries=None, expand_query=None, fields=None, deadline_ms=None, agent_ids=None, min_relevance=None))]
     #[allow(clippy::too_many_arguments)]
     fn recall(
         &self,
@@ -188,10 +382,22 @@ impl MnemoClient {
         strategy: Option<String>,
         explain: Option<bool>,
         with_provenance: Option<bool>,
+        with_snippets: Option<bool>,
+        profile: Option<String>,
+        thread_id: Option<String>,
+        thread_isolation: Option<bool>,
+        exclude_query: Option<String>,
+        exclude_tags: Option<Vec<String>>,
+        queries: Option<Vec<String>>,
+        expand_query: Option<bool>,
+        fields: Option<Vec<String>>,
+        deadline_ms: Option<u64>,
+        agent_ids: Option<Vec<String>>,
+        min_relevance: Option<f32>,
     ) -> PyResult<Py<PyAny>> {
         let request = RecallRequest {
             query,
-            agent_id: None,
+            agent_id: Some(self.agent_id.clone()),
             limit,
             memory_type: memory_type.and_then(|s| s.parse::<MemoryType>().ok()),
             memory_types: None,
@@ -203,6 +409,7 @@ impl MnemoClient {
             temporal_range: None,
             recency_half_life_hours: None,
             hybrid_weights: None,
+            hybrid: None,
             rrf_k: None,
             as_of: None,
             explain,
@@ -213,11 +420,29 @@ impl MnemoClient {
             evidence_budget: None,
             retained_token_budget: None,
             domain_scope: None,
+            text_query_mode: None,
+            text_field_boosts: None,
+            with_snippets,
+            profile,
+            include: None,
+            thread_id,
+            thread_isolation,
+            exclude_query,
+            exclude_tags,
+            queries,
+            expand_query,
+            channel: None,
+            fields,
+            deadline_ms,
+            agent_ids,
+            min_relevance,
         };
 
-        let response = self
+        let shared = self.shared()?;
+
+        let response = shared
             .runtime
-            .block_on(self.engine.recall(request))
+            .block_on(shared.engine.recall(request))
             .map_err(to_py_err)?;
 
         Python::attach(|py| {
@@ -225,34 +450,14 @@ impl MnemoClient {
             let memories: Vec<Py<PyAny>> = response
                 .memories
                 .iter()
-                .map(|m| {
-                    let dict = PyDict::new(py);
-                    dict.set_item("id", m.id.to_string()).unwrap();
-                    dict.set_item("content", &m.content).unwrap();
-                    dict.set_item("agent_id", &m.agent_id).unwrap();
-                    dict.set_item("memory_type", m.memory_type.to_string())
-                        .unwrap();
-                    dict.set_item("scope", m.scope.to_string()).unwrap();
-                    dict.set_item("importance", m.importance).unwrap();
-                    dict.set_item("tags", &m.tags).unwrap();
-                    dict.set_item("score", m.score).unwrap();
-                    dict.set_item("access_count", m.access_count).unwrap();
-                    dict.set_item("created_at", &m.created_at).unwrap();
-                    dict.set_item("updated_at", &m.updated_at).unwrap();
-                    if let Some(ref b) = m.score_breakdown {
-                        let bd = PyDict::new(py);
-                        bd.set_item("vector", b.vector).unwrap();
-                        bd.set_item("bm25", b.bm25).unwrap();
-                        bd.set_item("graph", b.graph).unwrap();
-                        bd.set_item("recency", b.recency).unwrap();
-                        bd.set_item("rrf_rank", b.rrf_rank).unwrap();
-                        dict.set_item("score_breakdown", bd).unwrap();
-                    }
-                    dict.into_any().unbind()
-                })
+                .map(|m| scored_memory_dict(py, m))
                 .collect();
             result.set_item("memories", memories)?;
             result.set_item("total", response.total)?;
+            if let Some(degradation) = &response.degradation {
+                let value = serde_json::to_value(degradation).map_err(to_py_err)?;
+                result.set_item("degradation", json_to_py(value)?)?;
+            }
             Ok(result.into_any().unbind())
         })
     }
@@ -276,11 +481,109 @@ impl MnemoClient {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
     }
 
-    #[pyo3(signature = (memory_ids, strategy=None))]
-    fn forget(&self, memory_ids: Vec<String>, strategy: Option<String>) -> PyResult<Py<PyAny>> {
+    /// Memories closest to `memory_id`, by its stored embedding.
+    #[pyo3(signature = (memory_id, k=10, memory_type=None, tags=None, min_importance=None))]
+    fn similar(
+        &self,
+        memory_id: String,
+        k: usize,
+        memory_type: Option<String>,
+        tags: Option<Vec<String>>,
+        min_importance: Option<f32>,
+    ) -> PyResult<Py<PyAny>> {
+        let memory_id = uuid::Uuid::parse_str(&memory_id).map_err(to_py_err)?;
+        let mut request = SimilarRequest::new(memory_id, k);
+        request.agent_id = Some(self.agent_id.clone());
+        request.memory_type = memory_type
+            .map(|s| s.parse::<MemoryType>())
+            .transpose()
+            .map_err(to_py_err)?;
+        request.tags = tags;
+        request.min_importance = min_importance;
+
+        let shared = self.shared()?;
+
+        let response = shared
+            .runtime
+            .block_on(shared.engine.similar(request))
+            .map_err(to_py_err)?;
+
+        Python::attach(|py| {
+            let result = PyDict::new(py);
+            let memories: Vec<Py<PyAny>> = response
+                .memories
+                .iter()
+                .map(|m| scored_memory_dict(py, m))
+                .collect();
+            result.set_item("memory_id", response.memory_id.to_string())?;
+            result.set_item("memories", memories)?;
+            Ok(result.into_any().unbind())
+        })
+    }
+
+    /// Fetch several memories by id, e.g. the content of hits recalled
+    /// with `fields`. Ids this agent cannot read come back in `missing`.
+    #[pyo3(signature = (memory_ids, fields=None))]
+    fn get_batch(
+        &self,
+        memory_ids: Vec<String>,
+        fields: Option<Vec<String>>,
+    ) -> PyResult<Py<PyAny>> {
+        let ids = memory_ids
+            .iter()
+            .map(|s| uuid::Uuid::parse_str(s))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(to_py_err)?;
+        let mut request = GetBatchRequest::new(ids);
+        request.agent_id = Some(self.agent_id.clone());
+        request.fields = fields;
+
+        let shared = self.shared()?;
+
+        let response = shared
+            .runtime
+            .block_on(shared.engine.get_batch(request))
+            .map_err(to_py_err)?;
+
+        Python::attach(|py| {
+            let result = PyDict::new(py);
+            let memories: Vec<Py<PyAny>> = response
+                .memories
+                .iter()
+                .map(|m| scored_memory_dict(py, m))
+                .collect();
+            let missing: Vec<String> = response.missing.iter().map(|id| id.to_string()).collect();
+            result.set_item("memories", memories)?;
+            result.set_item("missing", missing)?;
+            Ok(result.into_any().unbind())
+        })
+    }
+
+    /// Forget memories. With `dry_run=True`, returns the memories that
+    /// would be forgotten and changes nothing.
+    #[pyo3(signature = (memory_ids, strategy=None, force=None, dry_run=false))]
+    fn forget(
+        &self,
+        memory_ids: Vec<String>,
+        strategy: Option<String>,
+        force: Option<bool>,
+        dry_run: bool,
+    ) -> PyResult<Py<PyAny>> {
         let parsed_ids: Result<Vec<uuid::Uuid>, _> = memory_ids
             .iter()
             .map(|s| uuid::Uuid::parse_str(s))
@@ -289,7 +592,7 @@ impl MnemoClient {
 
         let request = ForgetRequest {
             memory_ids: parsed_ids,
-            agent_id: None,
+            agent_id: Some(self.agent_id.clone()),
             strategy: strategy.map(|s| match s.as_str() {
                 "hard_delete" => ForgetStrategy::HardDelete,
                 "decay" => ForgetStrategy::Decay,
@@ -299,11 +602,15 @@ impl MnemoClient {
                 _ => ForgetStrategy::SoftDelete,
             }),
             criteria: None,
+            force,
+            dry_run: Some(dry_run),
         };
 
-        let response = self
+        let shared = self.shared()?;
+
+        let response = shared
             .runtime
-            .block_on(self.engine.forget(request))
+            .block_on(shared.engine.forget(request))
             .map_err(to_py_err)?;
 
         Python::attach(|py| {
@@ -319,6 +626,7 @@ impl MnemoClient {
                     .map(|e| format!("{}: {}", e.id, e.error))
                     .collect::<Vec<_>>(),
             )?;
+            dict.set_item("dry_run", response.dry_run)?;
             Ok(dict.into_any().unbind())
         })
     }
@@ -326,7 +634,69 @@ impl MnemoClient {
     /// Mem0-compatible alias for forget
     #[pyo3(signature = (memory_ids, strategy=None))]
     fn delete(&self, memory_ids: Vec<String>, strategy: Option<String>) -> PyResult<Py<PyAny>> {
-        self.forget(memory_ids, strategy)
+        self.forget(memory_ids, strategy, None, false)
+    }
+
+    /// Update a memory's importance, tags or metadata. With
+    /// `expected_version`, raises `VersionConflictError` (carrying the
+    /// current record as `current`) if the memory was updated since.
+    #[pyo3(signature = (memory_id, importance=None, tags=None, metadata=None, expected_version=None))]
+    fn update(
+        &self,
+        memory_id: String,
+        importance: Option<f32>,
+        tags: Option<Vec<String>>,
+        metadata: Option<&Bound<'_, PyDict>>,
+        expected_version: Option<u32>,
+    ) -> PyResult<Py<PyAny>> {
+        let id = uuid::Uuid::parse_str(&memory_id).map_err(to_py_err)?;
+        let metadata = match metadata {
+            Some(dict) => pythonize_dict(dict)?,
+            None => None,
+        };
+        let shared = self.shared()?;
+        let response = shared
+            .runtime
+            .block_on(shared.engine.update(UpdateRequest {
+                agent_id: Some(self.agent_id.clone()),
+                importance,
+                tags,
+                metadata,
+                expected_version,
+                ..UpdateRequest::new(id)
+            }))
+            .map_err(update_err)?;
+
+        Python::attach(|py| {
+            let dict = PyDict::new(py);
+            dict.set_item("id", response.id.to_string())?;
+            dict.set_item("version", response.version)?;
+            dict.set_item("updated_at", response.updated_at)?;
+            Ok(dict.into_any().unbind())
+        })
+    }
+
+    /// Pin (or, with `pinned=False`, unpin) a memory so it is protected
+    /// from decay, consolidation and auto-forgetting.
+    #[pyo3(signature = (memory_id, pinned=true))]
+    fn pin(&self, memory_id: String, pinned: bool) -> PyResult<Py<PyAny>> {
+        let id = uuid::Uuid::parse_str(&memory_id).map_err(to_py_err)?;
+        let shared = self.shared()?;
+        let response = shared
+            .runtime
+            .block_on(shared.engine.pin(PinRequest {
+                agent_id: Some(self.agent_id.clone()),
+                ..PinRequest::new(id, pinned)
+            }))
+            .map_err(to_py_err)?;
+
+        Python::attach(|py| {
+            let dict = PyDict::new(py);
+            dict.set_item("memory_id", response.memory_id.to_string())?;
+            dict.set_item("pinned", response.pinned)?;
+            dict.set_item("changed", response.changed)?;
+            Ok(dict.into_any().unbind())
+        })
     }
 
     #[pyo3(signature = (memory_id, target_agent_id, permission=None))]
@@ -339,17 +709,16 @@ impl MnemoClient {
         let mid = uuid::Uuid::parse_str(&memory_id).map_err(to_py_err)?;
 
         let request = ShareRequest {
-            memory_id: mid,
-            agent_id: None,
-            target_agent_id,
-            target_agent_ids: None,
+            agent_id: Some(self.agent_id.clone()),
             permission: permission.and_then(|s| s.parse().ok()),
-            expires_in_hours: None,
+            ..ShareRequest::new(mid, target_agent_id)
         };
 
-        let response = self
+        let shared = self.shared()?;
+
+        let response = shared
             .runtime
-            .block_on(self.engine.share(request))
+            .block_on(shared.engine.share(request))
             .map_err(to_py_err)?;
 
         Python::attach(|py| {
@@ -362,6 +731,68 @@ impl MnemoClient {
         })
     }
 
+    /// Share several memories at once: the listed `memory_ids`, or every
+    /// memory of this agent carrying all of `tags`. Nothing is shared unless
+    /// everything can be. With `dry_run=True`, returns the memories that
+    /// would be shared without granting anything.
+    #[pyo3(signature = (target_agent_id, memory_ids=None, tags=None, permission=None, expires_in_hours=None, dry_run=false))]
+    fn share_many(
+        &self,
+        target_agent_id: String,
+        memory_ids: Option<Vec<String>>,
+        tags: Option<Vec<String>>,
+        permission: Option<String>,
+        expires_in_hours: Option<f64>,
+        dry_run: bool,
+    ) -> PyResult<Py<PyAny>> {
+        let memory_ids = match memory_ids {
+            Some(ids) => Some(
+                ids.iter()
+                    .map(|s| uuid::Uuid::parse_str(s).map_err(to_py_err))
+                    .collect::<PyResult<Vec<_>>>()?,
+            ),
+            None => None,
+        };
+        let filter = tags.map(|tags| ShareFilter {
+            tags,
+            ..Default::default()
+        });
+        if memory_ids.is_none() && filter.is_none() {
+            return Err(to_py_err("either memory_ids or tags is required"));
+        }
+
+        let request = ShareRequest {
+            agent_id: Some(self.agent_id.clone()),
+            permission: permission.and_then(|s| s.parse().ok()),
+            expires_in_hours,
+            memory_ids,
+            filter,
+            dry_run: Some(dry_run),
+            ..ShareRequest::new(uuid::Uuid::nil(), target_agent_id)
+        };
+        let shared = self.shared()?;
+        let response = shared
+            .runtime
+            .block_on(shared.engine.share(request))
+            .map_err(to_py_err)?;
+
+        Python::attach(|py| {
+            let dict = PyDict::new(py);
+            let ids: Vec<String> = response
+                .memory_ids
+                .iter()
+                .map(|id| id.to_string())
+                .collect();
+            let acl_ids: Vec<String> = response.acl_ids.iter().map(|id| id.to_string()).collect();
+            dict.set_item("memory_ids", ids)?;
+            dict.set_item("acl_ids", acl_ids)?;
+            dict.set_item("shared_with", &response.shared_with_all)?;
+            dict.set_item("permission", response.permission.to_string())?;
+            dict.set_item("dry_run", response.dry_run)?;
+            Ok(dict.into_any().unbind())
+        })
+    }
+
     #[pyo3(signature = (thread_id, state_snapshot, branch_name=None, label=None, metadata=None))]
     fn checkpoint(
         &self,
@@ -380,16 +811,18 @@ impl MnemoClient {
 
         let request = CheckpointRequest {
             thread_id,
-            agent_id: None,
+            agent_id: Some(self.agent_id.clone()),
             branch_name,
             state_snapshot: snapshot_value,
             label,
             metadata: metadata_value,
         };
 
-        let response = self
+        let shared = self.shared()?;
+
+        let response = shared
             .runtime
-            .block_on(self.engine.checkpoint(request))
+            .block_on(shared.engine.checkpoint(request))
             .map_err(to_py_err)?;
 
         Python::attach(|py| {
@@ -411,15 +844,17 @@ impl MnemoClient {
     ) -> PyResult<Py<PyAny>> {
         let request = BranchRequest {
             thread_id,
-            agent_id: None,
+            agent_id: Some(self.agent_id.clone()),
             new_branch_name,
             source_checkpoint_id: source_checkpoint_id.and_then(|s| uuid::Uuid::parse_str(&s).ok()),
             source_branch,
         };
 
-        let response = self
+        let shared = self.shared()?;
+
+        let response = shared
             .runtime
-            .block_on(self.engine.branch(request))
+            .block_on(shared.engine.branch(request))
             .map_err(to_py_err)?;
 
         Python::attach(|py| {
@@ -459,16 +894,18 @@ impl MnemoClient {
 
         let request = MergeRequest {
             thread_id,
-            agent_id: None,
+            agent_id: Some(self.agent_id.clone()),
             source_branch,
             target_branch,
             strategy: merge_strategy,
             cherry_pick_ids: cherry_ids,
         };
 
-        let response = self
+        let shared = self.shared()?;
+
+        let response = shared
             .runtime
-            .block_on(self.engine.merge(request))
+            .block_on(shared.engine.merge(request))
             .map_err(to_py_err)?;
 
         Python::attach(|py| {
@@ -490,15 +927,17 @@ impl MnemoClient {
     ) -> PyResult<Py<PyAny>> {
         let request = ReplayRequest {
             thread_id,
-            agent_id: None,
+            agent_id: Some(self.agent_id.clone()),
             checkpoint_id: checkpoint_id.and_then(|s| uuid::Uuid::parse_str(&s).ok()),
             branch_name,
             as_of,
         };
 
-        let response = self
+        let shared = self.shared()?;
+
+        let response = shared
             .runtime
-            .block_on(self.engine.replay(request))
+            .block_on(shared.engine.replay(request))
             .map_err(to_py_err)?;
 
         Python::attach(|py| {
@@ -530,19 +969,119 @@ impl MnemoClient {
         })
     }
 
+    /// Archive or forget memories whose effective importance has decayed
+    /// below the thresholds. Returns the `DecayPassResult` as a dict.
+    #[pyo3(signature = (archive_threshold=None, forget_threshold=None))]
+    fn run_decay_pass(
+        &self,
+        archive_threshold: Option<f32>,
+        forget_threshold: Option<f32>,
+    ) -> PyResult<Py<PyAny>> {
+        let shared = self.shared()?;
+        let result = shared
+            .runtime
+            .block_on(shared.engine.run_decay_pass(
+                Some(self.agent_id.clone()),
+                archive_threshold.unwrap_or(lifecycle::DEFAULT_ARCHIVE_THRESHOLD),
+                forget_threshold.unwrap_or(lifecycle::DEFAULT_FORGET_THRESHOLD),
+            ))
+            .map_err(to_py_err)?;
+        json_to_py(serde_json::to_value(result).map_err(to_py_err)?)
+    }
+
+    /// Fold tag clusters of episodic memories into semantic summaries.
+    /// Returns the `ConsolidationResult` as a dict.
+    #[pyo3(signature = (min_cluster_size=None))]
+    fn run_consolidation(&self, min_cluster_size: Option<usize>) -> PyResult<Py<PyAny>> {
+        let min_cluster_size = min_cluster_size.unwrap_or(lifecycle::DEFAULT_MIN_CLUSTER_SIZE);
+        if min_cluster_size == 0 {
+            return Err(PyRuntimeError::new_err("min_cluster_size must be > 0"));
+        }
+        let shared = self.shared()?;
+        let result = shared
+            .runtime
+            .block_on(
+                shared
+                    .engine
+                    .run_consolidation(Some(self.agent_id.clone()), min_cluster_size),
+            )
+            .map_err(to_py_err)?;
+        json_to_py(serde_json::to_value(result).map_err(to_py_err)?)
+    }
+
+    /// TTL sweep followed by a trash purge. Returns
+    /// `{"ttl": {...}, "trash": {...}}`. With `dry_run=True`, reports what
+    /// would be deleted and archived and changes nothing.
+    #[pyo3(signature = (dry_run=false))]
+    fn run_cleanup(&self, dry_run: bool) -> PyResult<Py<PyAny>> {
+        let shared = self.shared()?;
+        let mut request = lifecycle::CleanupRequest::new();
+        request.dry_run = Some(dry_run);
+        let result = shared
+            .runtime
+            .block_on(shared.engine.run_cleanup_with(request))
+            .map_err(to_py_err)?;
+        json_to_py(serde_json::to_value(result).map_err(to_py_err)?)
+    }
+
     fn save_index(&self) -> PyResult<()> {
-        self.index.save(&self.index_path).map_err(to_py_err)
+        self.shared()?.flush()
     }
 
-    fn index_size(&self) -> usize {
-        self.index.len()
+    fn index_size(&self) -> PyResult<usize> {
+        Ok(self.shared()?.engine.index.len())
     }
 }
 
-impl Drop for MnemoClient {
-    fn drop(&mut self) {
-        let _ = self.index.save(&self.index_path);
+fn scored_memory_dict(py: Python<'_>, m: &ScoredMemory) -> Py<PyAny> {
+    let dict = PyDict::new(py);
+    dict.set_item("id", m.id.to_string()).unwrap();
+    // Empty when a `fields` projection left it out.
+    if !m.content.is_empty() {
+        dict.set_item("content", &m.content).unwrap();
     }
+    dict.set_item("agent_id", &m.agent_id).unwrap();
+    dict.set_item("memory_type", m.memory_type.to_string())
+        .unwrap();
+    dict.set_item("scope", m.scope.to_string()).unwrap();
+    dict.set_item("importance", m.importance).unwrap();
+    dict.set_item("tags", &m.tags).unwrap();
+    // Null when a `fields` projection left it out.
+    if !m.metadata.is_null() {
+        dict.set_item("metadata", json_to_py(m.metadata.clone()).unwrap())
+            .unwrap();
+    }
+    dict.set_item("score", m.score).unwrap();
+    dict.set_item("access_count", m.access_count).unwrap();
+    dict.set_item("created_at", &m.created_at).unwrap();
+    dict.set_item("updated_at", &m.updated_at).unwrap();
+    if let Some(ref b) = m.score_breakdown {
+        let bd = PyDict::new(py);
+        bd.set_item("vector", b.vector).unwrap();
+        bd.set_item("bm25", b.bm25).unwrap();
+        bd.set_item("graph", b.graph).unwrap();
+        bd.set_item("recency", b.recency).unwrap();
+        bd.set_item("rrf_rank", b.rrf_rank).unwrap();
+        dict.set_item("score_breakdown", bd).unwrap();
+    }
+    if let Some(ref snippets) = m.snippets {
+        let items: Vec<Py<PyAny>> = snippets
+            .iter()
+            .map(|s| {
+                let sd = PyDict::new(py);
+                sd.set_item("fragment", &s.fragment).unwrap();
+                sd.set_item("highlights", s.highlights.clone()).unwrap();
+                let source = match s.source {
+                    SnippetSource::Bm25 => "bm25",
+                    SnippetSource::Vector => "vector",
+                };
+                sd.set_item("source", source).unwrap();
+                sd.into_any().unbind()
+            })
+            .collect();
+        dict.set_item("snippets", items).unwrap();
+    }
+    dict.into_any().unbind()
 }
 
 fn pythonize_dict(dict: &Bound<'_, PyDict>) -> PyResult<Option<serde_json::Value>> {
@@ -553,8 +1092,21 @@ fn pythonize_dict(dict: &Bound<'_, PyDict>) -> PyResult<Option<serde_json::Value
     Ok(Some(value))
 }
 
+fn json_to_py(value: serde_json::Value) -> PyResult<Py<PyAny>> {
+    Python::attach(|py| {
+        let json_mod = py.import("json")?;
+        let obj = json_mod.call_method1("loads", (value.to_string(),))?;
+        Ok(obj.unbind())
+    })
+}
+
 #[pymodule]
 fn _mnemo(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<MnemoClient>()?;
+    m.add(
+        "VersionConflictError",
+        m.py().get_type::<VersionConflictError>(),
+    )?;
+    m.add_function(wrap_pyfunction!(_restore_client, m)?)?;
     Ok(())
 }
@@ -0,0 +1,92 @@
+"""Tests for the AutoGen ``Memory`` adapter, against an in-process client.
+
+Skipped when ``autogen-core`` is not installed.
+"""
+
+from __future__ import annotations
+
+import asyncio
+from dataclasses import dataclass, field
+from typing import Any
+from uuid import uuid4
+
+import pytest
+
+pytest.importorskip("autogen_core")
+
+from autogen_core.memory import MemoryContent, MemoryMimeType  # noqa: E402
+from autogen_core.model_context import UnboundedChatCompletionContext  # noqa: E402
+from autogen_core.models import SystemMessage, UserMessage  # noqa: E402
+
+from mnemo.autogen_memory import MnemoAutoGenMemory  # noqa: E402
+
+
+@dataclass
+class FakeMnemoClient:
+    """Tag-filtered remember / recall / forget; non-exact recall matches words."""
+
+    records: list[dict[str, Any]] = field(default_factory=list)
+
+    def remember(self, content, memory_type=None, tags=None, metadata=None):
+        rid = str(uuid4())
+        self.records.append(
+            {"id": rid, "content": content, "tags": tags or [], "metadata": metadata or {}}
+        )
+        return {"id": rid, "content_hash": "deadbeef"}
+
+    def recall(self, query, limit=None, tags=None, strategy=None):
+        hits = [r for r in self.records if set(tags or []).issubset(r["tags"])]
+        if strategy != "exact":
+            words = set(query.lower().split())
+            hits = [r for r in hits if words & set(r["content"].lower().split())]
+        return {"memories": [{**r, "score": 0.5} for r in hits[:limit]], "total": len(hits)}
+
+    def forget(self, memory_ids, strategy=None):
+        self.records = [r for r in self.records if r["id"] not in memory_ids]
+        return {"forgotten": list(memory_ids), "errors": []}
+
+
+def test_added_content_is_recalled_into_the_model_context() -> None:
+    async def run() -> None:
+        memory = MnemoAutoGenMemory(client=FakeMnemoClient())
+        await memory.add(
+            MemoryContent(
+                content="the user prefers metric units",
+                mime_type=MemoryMimeType.TEXT,
+                metadata={"source": "onboarding"},
+            )
+        )
+        await memory.add(
+            MemoryContent(content={"units": "metric"}, mime_type=MemoryMimeType.JSON)
+        )
+
+        result = await memory.query("which units does the user like?")
+        assert [item.content for item in result.results] == ["the user prefers metric units"]
+        assert result.results[0].metadata["source"] == "onboarding"
+
+        context = UnboundedChatCompletionContext()
+        await context.add_message(UserMessage(content="convert 5 miles to units", source="user"))
+        update = await memory.update_context(context)
+        assert len(update.memories.results) == 1
+        messages = await context.get_messages()
+        assert isinstance(messages[-1], SystemMessage)
+        assert "metric units" in messages[-1].content
+
+        json_result = await memory.query('{"units": "metric"}')
+        assert {"units": "metric"} in [item.content for item in json_result.results]
+
+    asyncio.run(run())
+
+
+def test_clear_only_forgets_this_memorys_content() -> None:
+    async def run() -> None:
+        client = FakeMnemoClient()
+        notes = MnemoAutoGenMemory(name="notes", client=client)
+        prefs = MnemoAutoGenMemory(name="prefs", client=client)
+        await notes.add(MemoryContent(content="standup at nine", mime_type="text/plain"))
+        await prefs.add(MemoryContent(content="dark mode", mime_type="text/plain"))
+
+        await notes.clear()
+        assert [r["content"] for r in client.records] == ["dark mode"]
+
+    asyncio.run(run())
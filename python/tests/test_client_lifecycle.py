@@ -0,0 +1,64 @@
+"""Tests for MnemoClient lifecycle: context manager, close(), pickling and
+per-path engine sharing.
+
+Needs the native extension (`maturin develop`); skipped otherwise.
+"""
+
+from __future__ import annotations
+
+import pickle
+
+import pytest
+
+_mnemo = pytest.importorskip("mnemo._mnemo")
+MnemoClient = _mnemo.MnemoClient
+
+
+def test_context_manager_closes_and_flushes(tmp_path):
+    db = tmp_path / "agent.db"
+    with MnemoClient(db_path=str(db), dimensions=8) as m:
+        m.remember("the deploy runs on fridays")
+        assert m.index_size() == 1
+    assert m.closed
+    assert (tmp_path / "agent.usearch").exists()
+    with pytest.raises(RuntimeError, match="closed"):
+        m.recall("deploy")
+    # Closing twice is a no-op.
+    m.close()
+
+
+def test_clients_on_same_path_share_one_engine(tmp_path):
+    db = str(tmp_path / "shared.db")
+    a = MnemoClient(db_path=db, agent_id="agent-a", dimensions=8)
+    b = MnemoClient(db_path=db, agent_id="agent-b", dimensions=8)
+    a.remember("alpha note")
+    assert b.index_size() == 1
+    a.close()
+    # The other client keeps working after its sibling closes.
+    b.remember("beta note")
+    assert b.index_size() == 2
+    b.close()
+
+
+def test_conflicting_config_on_same_path_is_rejected(tmp_path):
+    db = str(tmp_path / "conflict.db")
+    with MnemoClient(db_path=db, dimensions=8):
+        with pytest.raises(RuntimeError, match="different configuration"):
+            MnemoClient(db_path=db, dimensions=16)
+
+
+def test_pickle_roundtrip_drops_api_key(tmp_path):
+    db = str(tmp_path / "pickled.db")
+    with MnemoClient(db_path=db, agent_id="agent-p", dimensions=8) as m:
+        payload = pickle.dumps(m)
+        restored = pickle.loads(payload)
+        assert not restored.closed
+        restored.close()
+
+    secret = MnemoClient(
+        db_path=str(tmp_path / "secret.db"),
+        dimensions=8,
+        openai_api_key="sk-do-not-pickle",
+    )
+    assert b"sk-do-not-pickle" not in pickle.dumps(secret)
+    secret.close()
@@ -0,0 +1,80 @@
+"""Tests for the CrewAI memory storages, against an in-process client."""
+
+from __future__ import annotations
+
+from dataclasses import dataclass, field
+from typing import Any
+from uuid import uuid4
+
+import pytest
+
+from mnemo.crewai_memory import MnemoCrewLongTermStorage, MnemoCrewStorage
+
+
+@dataclass
+class FakeMnemoClient:
+    """Tag-filtered remember / recall / forget; non-exact recall matches words."""
+
+    records: list[dict[str, Any]] = field(default_factory=list)
+
+    def remember(self, content, memory_type=None, scope=None, tags=None, metadata=None):
+        rid = str(uuid4())
+        self.records.append(
+            {
+                "id": rid,
+                "content": content,
+                "memory_type": memory_type,
+                "tags": tags or [],
+                "metadata": metadata or {},
+            }
+        )
+        return {"id": rid, "content_hash": "deadbeef"}
+
+    def recall(self, query, limit=None, tags=None, strategy=None):
+        hits = [r for r in self.records if set(tags or []).issubset(r["tags"])]
+        if strategy != "exact":
+            words = set(query.lower().split())
+            hits = [r for r in hits if words & set(r["content"].lower().split())]
+        return {"memories": [{**r, "score": 1.0} for r in hits[:limit]], "total": len(hits)}
+
+    def forget(self, memory_ids, strategy=None):
+        self.records = [r for r in self.records if r["id"] not in memory_ids]
+        return {"forgotten": list(memory_ids), "errors": []}
+
+
+def test_buckets_map_to_memory_types_and_stay_apart() -> None:
+    client = FakeMnemoClient()
+    short_term = MnemoCrewStorage("short_term", client=client)
+    entity = MnemoCrewStorage("entity", client=client)
+
+    short_term.save("the draft is due friday", {"agent": "writer"})
+    entity.save("Acme(client): prefers python over javascript", {"relationships": ""})
+    assert [r["memory_type"] for r in client.records] == ["working", "semantic"]
+
+    results = short_term.search("draft due", limit=3)
+    assert [r["context"] for r in results] == ["the draft is due friday"]
+    assert results[0]["metadata"] == {"agent": "writer"}
+    assert entity.search("draft due") == []
+
+    short_term.reset()
+    assert [r["memory_type"] for r in client.records] == ["semantic"]
+
+
+def test_long_term_loads_latest_outcomes_of_a_task() -> None:
+    client = FakeMnemoClient()
+    storage = MnemoCrewLongTermStorage(client=client)
+    storage.save("summarize the incident", {"quality": 6}, "2026-05-01T10:00:00", 6.0)
+    storage.save("summarize the incident", {"quality": 9}, "2026-05-02T10:00:00", 9.0)
+    storage.save("write the postmortem", {"quality": 7}, "2026-05-03T10:00:00", 7.0)
+    assert client.records[0]["memory_type"] == "episodic"
+
+    outcomes = storage.load("summarize the incident", latest_n=1)
+    assert outcomes == [
+        {"metadata": {"quality": 9}, "datetime": "2026-05-02T10:00:00", "score": 9.0}
+    ]
+    assert storage.load("an unknown task") is None
+
+
+def test_unknown_buckets_are_rejected() -> None:
+    with pytest.raises(ValueError):
+        MnemoCrewStorage("medium_term", client=FakeMnemoClient())
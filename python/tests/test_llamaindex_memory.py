@@ -0,0 +1,153 @@
+"""Tests for the LlamaIndex vector store and chat memory.
+
+Run against an in-process stand-in for ``MnemoClient``; skipped when
+``llama-index-core`` is not installed.
+"""
+
+from __future__ import annotations
+
+from dataclasses import dataclass, field
+from typing import Any
+from uuid import uuid4
+
+import pytest
+
+pytest.importorskip("llama_index.core")
+
+from llama_index.core.llms import ChatMessage, MessageRole  # noqa: E402
+from llama_index.core.schema import NodeRelationship, RelatedNodeInfo, TextNode  # noqa: E402
+from llama_index.core.vector_stores.types import VectorStoreQuery  # noqa: E402
+
+from mnemo.llamaindex_memory import MnemoMemory, MnemoVectorStore  # noqa: E402
+
+
+@dataclass
+class FakeMnemoClient:
+    """Records recall arguments; ranks by shared words for non-exact recalls."""
+
+    records: list[dict[str, Any]] = field(default_factory=list)
+    recalls: list[dict[str, Any]] = field(default_factory=list)
+
+    def remember(self, content, memory_type=None, tags=None, metadata=None, thread_id=None):
+        rid = str(uuid4())
+        self.records.append(
+            {
+                "id": rid,
+                "content": content,
+                "tags": tags or [],
+                "metadata": metadata or {},
+                "thread_id": thread_id,
+            }
+        )
+        return {"id": rid, "content_hash": "deadbeef"}
+
+    def recall(self, query, limit=None, tags=None, strategy=None, thread_id=None):
+        self.recalls.append({"query": query, "strategy": strategy, "thread_id": thread_id})
+        wanted = set(tags or [])
+        hits = []
+        for record in self.records:
+            if not wanted.issubset(record["tags"]):
+                continue
+            if strategy == "exact":
+                score = 1.0
+            else:
+                words = set(query.lower().split())
+                score = len(words & set(record["content"].lower().split())) / len(words)
+                if score == 0:
+                    continue
+            hits.append({**record, "score": score})
+        hits.sort(key=lambda hit: -hit["score"])
+        return {"memories": hits[:limit], "total": len(hits)}
+
+    def forget(self, memory_ids, strategy=None):
+        self.records = [r for r in self.records if r["id"] not in memory_ids]
+        return {"forgotten": list(memory_ids), "errors": []}
+
+
+def _node(node_id: str, text: str, doc_id: str) -> TextNode:
+    return TextNode(
+        id_=node_id,
+        text=text,
+        metadata={"source": "runbook.md"},
+        relationships={NodeRelationship.SOURCE: RelatedNodeInfo(node_id=doc_id)},
+    )
+
+
+def test_vector_store_queries_through_hybrid_recall() -> None:
+    client = FakeMnemoClient()
+    store = MnemoVectorStore(client=client)
+    assert not store.is_embedding_query
+
+    ids = store.add(
+        [
+            _node("n1", "rotate the signing keys every quarter", "doc-a"),
+            _node("n2", "restart the ingest workers after a failover", "doc-b"),
+        ]
+    )
+    assert ids == ["n1", "n2"]
+
+    result = store.query(VectorStoreQuery(query_str="rotate keys", similarity_top_k=1))
+    assert client.recalls[-1]["strategy"] == "hybrid"
+    assert result.ids == ["n1"]
+    node = result.nodes[0]
+    assert node.get_content() == "rotate the signing keys every quarter"
+    assert node.metadata == {"source": "runbook.md"}
+    assert node.ref_doc_id == "doc-a"
+
+
+def test_vector_store_deletes_by_document_and_node() -> None:
+    client = FakeMnemoClient()
+    store = MnemoVectorStore(client=client)
+    store.add(
+        [
+            _node("n1", "rotate the signing keys", "doc-a"),
+            _node("n2", "rotate the tls certificates", "doc-a"),
+            _node("n3", "rotate the pager schedule", "doc-b"),
+        ]
+    )
+
+    store.delete("doc-a")
+    assert [r["metadata"]["_node_id"] for r in client.records] == ["n3"]
+    store.delete_nodes(["n3"])
+    assert client.records == []
+
+
+def test_memory_keeps_history_in_order_and_recalls_older_context() -> None:
+    client = FakeMnemoClient()
+    memory = MnemoMemory.from_defaults(client=client, session_id="user-42", history_limit=2)
+    memory.put(ChatMessage(role=MessageRole.USER, content="my deploy target is eu-west-1"))
+    memory.put(ChatMessage(role=MessageRole.ASSISTANT, content="noted"))
+    memory.put(ChatMessage(role=MessageRole.USER, content="what about staging?"))
+
+    assert [m.content for m in memory.get_all()] == [
+        "my deploy target is eu-west-1",
+        "noted",
+        "what about staging?",
+    ]
+    assert [m.content for m in memory.get()] == ["noted", "what about staging?"]
+
+    messages = memory.get(input="which deploy target did I pick?")
+    assert client.recalls[-1]["thread_id"] == "user-42"
+    assert messages[0].role == MessageRole.SYSTEM
+    assert "eu-west-1" in messages[0].content
+    assert [m.content for m in messages[1:]] == ["noted", "what about staging?"]
+
+
+def test_memory_set_and_reset_replace_the_session() -> None:
+    client = FakeMnemoClient()
+    memory = MnemoMemory.from_defaults(client=client, session_id="user-42")
+    other = MnemoMemory.from_defaults(client=client, session_id="user-7")
+    other.put(ChatMessage(role=MessageRole.USER, content="hello"))
+
+    memory.set([ChatMessage(role=MessageRole.USER, content="first")])
+    memory.set([ChatMessage(role=MessageRole.USER, content="second")])
+    assert [m.content for m in memory.get_all()] == ["second"]
+
+    memory.reset()
+    assert memory.get_all() == []
+    assert [m.content for m in other.get_all()] == ["hello"]
+
+
+def test_memory_requires_a_session_id() -> None:
+    with pytest.raises(ValueError):
+        MnemoMemory.from_defaults(client=FakeMnemoClient(), session_id="")